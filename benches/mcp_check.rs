@@ -0,0 +1,111 @@
+// 🏎️ MCP Check Bench - How much the settings cache saves per version check! 🏎️
+// Compares the release-info lookup in /mcp/check before (three settings queries
+// per request) and after (one `SettingsCache` snapshot load).
+//
+// The cache comes from the feedbacker library crate. The "before" half needs a
+// Postgres database:
+//   BENCH_DATABASE_URL=postgres://... cargo bench --bench mcp_check
+// Without it only the cached path is measured.
+// Created with love by Aye & Hue! ✨
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use feedbacker::api::settings_cache::SettingsCache;
+use sqlx::PgPool;
+
+/// 📋 What /mcp/check needs from the settings table
+type ReleaseInfo = (Option<String>, Option<String>, Option<Vec<String>>);
+
+/// 🐢 The old hot path: one query per key, every request
+async fn release_info_from_queries(pool: &PgPool) -> ReleaseInfo {
+    let lookup = |key: &'static str| async move {
+        sqlx::query_scalar::<_, String>("SELECT value FROM settings WHERE key = $1")
+            .bind(key)
+            .fetch_optional(pool)
+            .await
+            .ok()
+            .flatten()
+    };
+    let version = lookup("smart_tree_latest_version").await;
+    let notes = lookup("smart_tree_release_notes").await;
+    let features = lookup("smart_tree_new_features")
+        .await
+        .and_then(|json| serde_json::from_str(&json).ok());
+    (version, notes, features)
+}
+
+/// ⚡ The new hot path: clone out of the current snapshot
+fn release_info_from_cache(cache: &SettingsCache) -> ReleaseInfo {
+    let settings = cache.get();
+    (
+        settings.smart_tree_latest_version.clone(),
+        settings.smart_tree_release_notes.clone(),
+        settings.smart_tree_new_features.clone(),
+    )
+}
+
+/// 🗄️ Create and fill the settings table the benchmark reads
+async fn seed_settings(pool: &PgPool) -> anyhow::Result<()> {
+    sqlx::query(
+        "CREATE TABLE IF NOT EXISTS settings (
+            key VARCHAR(255) PRIMARY KEY,
+            value TEXT NOT NULL,
+            updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+        )",
+    )
+    .execute(pool)
+    .await?;
+    // 🔢 The cache also reads the console admin's second factor
+    sqlx::query(
+        "CREATE TABLE IF NOT EXISTS admin_second_factors (
+            subject VARCHAR(64) PRIMARY KEY,
+            enabled_at TIMESTAMPTZ
+        )",
+    )
+    .execute(pool)
+    .await?;
+    sqlx::query(
+        "INSERT INTO settings (key, value) VALUES
+            ('smart_tree_latest_version', '9.9.9'),
+            ('smart_tree_release_notes', 'Benchmark release'),
+            ('smart_tree_new_features', '[\"speed\", \"more speed\"]')
+         ON CONFLICT (key) DO UPDATE SET value = EXCLUDED.value",
+    )
+    .execute(pool)
+    .await?;
+    Ok(())
+}
+
+fn mcp_check_release_info(c: &mut Criterion) {
+    let runtime = tokio::runtime::Runtime::new().expect("tokio runtime");
+    let cache = SettingsCache::default();
+    let mut group = c.benchmark_group("mcp_check_release_info");
+
+    match std::env::var("BENCH_DATABASE_URL") {
+        Ok(url) => {
+            let pool = runtime.block_on(async {
+                let pool = PgPool::connect(&url)
+                    .await
+                    .expect("connect to BENCH_DATABASE_URL");
+                seed_settings(&pool).await.expect("seed settings");
+                cache.refresh(&pool).await.expect("load settings cache");
+                pool
+            });
+            assert_eq!(
+                runtime.block_on(release_info_from_queries(&pool)),
+                release_info_from_cache(&cache)
+            );
+            group.bench_function("settings_queries", |b| {
+                b.iter(|| black_box(runtime.block_on(release_info_from_queries(&pool))))
+            });
+        }
+        Err(_) => eprintln!("ℹ️ BENCH_DATABASE_URL not set, skipping the per-query baseline"),
+    }
+
+    group.bench_function("settings_cache", |b| {
+        b.iter(|| black_box(release_info_from_cache(&cache)))
+    });
+    group.finish();
+}
+
+criterion_group!(benches, mcp_check_release_info);
+criterion_main!(benches);
@@ -0,0 +1,61 @@
+// 🏗️ Build Script - Every binary knows where it came from! 🏗️
+// Stamps the git commit and build time into the binary (see src/build_info.rs), so
+// "which build is prod running?" has an answer. GIT_SHA overrides the commit for builds
+// without a .git directory (the Dockerfile passes it as a build arg), and
+// SOURCE_DATE_EPOCH pins the build time for reproducible builds. The script only reruns
+// when HEAD moves or those variables change, so the build time is when the current
+// commit was first built here.
+// Created with love by Aye & Hue! ✨
+
+use std::path::Path;
+use std::process::Command;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+fn main() {
+    let sha = std::env::var("GIT_SHA")
+        .ok()
+        .map(|sha| sha.trim().to_string())
+        .filter(|sha| !sha.is_empty())
+        .or_else(|| {
+            Command::new("git")
+                .args(["rev-parse", "HEAD"])
+                .output()
+                .ok()
+                .filter(|output| output.status.success())
+                .and_then(|output| String::from_utf8(output.stdout).ok())
+                .map(|sha| sha.trim().to_string())
+        })
+        .unwrap_or_else(|| "unknown".to_string());
+
+    let built_at = std::env::var("SOURCE_DATE_EPOCH")
+        .ok()
+        .and_then(|epoch| epoch.trim().parse::<u64>().ok())
+        .unwrap_or_else(|| {
+            SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|elapsed| elapsed.as_secs())
+                .unwrap_or_default()
+        });
+
+    println!("cargo:rustc-env=FEEDBACKER_GIT_SHA={}", sha);
+    println!("cargo:rustc-env=FEEDBACKER_BUILD_EPOCH={}", built_at);
+    println!("cargo:rerun-if-env-changed=GIT_SHA");
+    println!("cargo:rerun-if-env-changed=SOURCE_DATE_EPOCH");
+    println!("cargo:rerun-if-changed=build.rs");
+
+    // 🔁 HEAD names the branch; the branch's ref file (or packed-refs) moves on commit
+    let git = Path::new(".git");
+    if git.join("HEAD").exists() {
+        println!("cargo:rerun-if-changed=.git/HEAD");
+        if let Ok(head) = std::fs::read_to_string(git.join("HEAD")) {
+            if let Some(reference) = head.trim().strip_prefix("ref: ") {
+                if git.join(reference).exists() {
+                    println!("cargo:rerun-if-changed=.git/{}", reference);
+                }
+            }
+        }
+        if git.join("packed-refs").exists() {
+            println!("cargo:rerun-if-changed=.git/packed-refs");
+        }
+    }
+}
@@ -6,7 +6,10 @@
 //
 // Endpoints:
 // - POST https://f.8t.is/api/feedback - Submit feedback and feature requests
+// - GET  https://f.8t.is/api/feedback/{id} - Status, queue position and estimated start
+// - GET  https://f.8t.is/api/me/feedback - Your own feedback, newest first (needs a token)
 // - GET  https://f.8t.is/mcp/check - Get latest version info with platform/arch (preferred)
+// - GET  https://f.8t.is/mcp/changelog - Combined release notes since a version
 // - GET  https://f.8t.is/api/smart-tree/latest - Get latest version info (legacy fallback)
 // -----------------------------------------------------------------------------
 
@@ -16,9 +19,30 @@ use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use std::time::Duration;
 
+// The response envelope and error codes, shared with the server so that a renamed or
+// removed code fails to compile here instead of confusing users
+#[allow(dead_code)]
+#[path = "../src/feedbacker_types.rs"]
+mod feedbacker_types;
+
+use feedbacker_types::{ApiResponse, ErrorCode};
+
 const FEEDBACK_API_BASE: &str = "https://f.8t.is";
 const USER_AGENT: &str = concat!("smart-tree/", env!("CARGO_PKG_VERSION"));
 
+/// Is telemetry turned off? `SMART_TREE_NO_TELEMETRY` or the common `DO_NOT_TRACK`,
+/// set to anything but "" or "0". Update checks still happen, they just ask (with
+/// `dnt=1` and `DNT: 1`) not to be counted.
+fn telemetry_disabled() -> bool {
+    ["SMART_TREE_NO_TELEMETRY", "DO_NOT_TRACK"]
+        .iter()
+        .any(|name| {
+            std::env::var(name)
+                .map(|value| !value.is_empty() && value != "0")
+                .unwrap_or(false)
+        })
+}
+
 /// Feedback submission request structure
 #[derive(Debug, Serialize)]
 pub struct FeedbackRequest {
@@ -69,6 +93,101 @@ pub struct FeedbackResponse {
     pub status: String,
 }
 
+/// Status of a submitted feedback item (the `data` of the API envelope)
+#[derive(Debug, Deserialize)]
+pub struct FeedbackStatus {
+    pub id: String,
+    pub status: String,
+    pub pull_request_url: Option<String>,
+    pub queue_position: Option<u64>,
+    pub estimated_start: Option<chrono::DateTime<chrono::Utc>>,
+    pub average_duration_seconds: Option<f64>,
+}
+
+/// One of your own feedback items, as listed by `/api/me/feedback`
+#[derive(Debug, Deserialize)]
+pub struct MyFeedback {
+    pub id: String,
+    pub repository: String,
+    pub content_preview: String,
+    pub status: String,
+    pub pull_request_url: Option<String>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// A page of your own feedback
+#[derive(Debug, Deserialize)]
+pub struct MyFeedbackPage {
+    pub items: Vec<MyFeedback>,
+    pub next_cursor: Option<String>,
+}
+
+/// Turn an estimated start into something a person would say, e.g. "about 5 minutes"
+pub fn friendly_wait(
+    estimated_start: chrono::DateTime<chrono::Utc>,
+    now: chrono::DateTime<chrono::Utc>,
+) -> String {
+    let minutes = ((estimated_start - now).num_seconds() as f64 / 60.0).round() as i64;
+    match minutes {
+        i64::MIN..=0 => "any moment now".to_string(),
+        1 => "about 1 minute".to_string(),
+        2..=90 => format!("about {} minutes", minutes),
+        _ => format!("about {} hours", (minutes as f64 / 60.0).round() as i64),
+    }
+}
+
+/// Turn an error response into something a person can act on. Known codes get a
+/// message of their own; anything else shows the server's message.
+pub fn describe_error(status: StatusCode, body: &str) -> String {
+    let Some(error) = serde_json::from_str::<ApiResponse<Value>>(body)
+        .ok()
+        .and_then(|response| response.error)
+    else {
+        return format!("API error ({}): {}", status, body);
+    };
+    let details = error.details.unwrap_or(Value::Null);
+    match error.code {
+        ErrorCode::RateLimitExceeded => match details["retry_after_seconds"].as_u64() {
+            Some(seconds) => format!(
+                "Rate limit exceeded. Please try again in {} seconds.",
+                seconds
+            ),
+            None => "Rate limit exceeded. Please try again later.".to_string(),
+        },
+        ErrorCode::ValidationError => {
+            let reasons: Vec<&str> = details["errors"]
+                .as_array()
+                .map(|errors| errors.iter().filter_map(Value::as_str).collect())
+                .unwrap_or_default();
+            if reasons.is_empty() {
+                format!("The request was rejected: {}", error.message)
+            } else {
+                format!("The request was rejected: {}", reasons.join("; "))
+            }
+        }
+        ErrorCode::ChallengeFailed => {
+            "Anonymous feedback needs a solved challenge. Sign in or try again.".to_string()
+        }
+        ErrorCode::Unauthorized => "Your token was not accepted. Sign in again.".to_string(),
+        ErrorCode::NotFound => "No such feedback.".to_string(),
+        ErrorCode::InternalError => format!(
+            "The feedback service had a problem ({}). Please try again later.",
+            status
+        ),
+        _ => format!("API error ({}, {}): {}", status, error.code, error.message),
+    }
+}
+
+/// The error for a response that wasn't a success
+async fn api_error(response: reqwest::Response) -> anyhow::Error {
+    let status = response.status();
+    let body = response
+        .text()
+        .await
+        .unwrap_or_else(|_| "Unknown error".to_string());
+    anyhow::anyhow!(describe_error(status, &body))
+}
+
 /// Latest version info from legacy endpoint
 #[derive(Debug, Deserialize)]
 pub struct VersionInfo {
@@ -91,9 +210,28 @@ pub struct McpCheckResponse {
     pub message: Option<String>,
 }
 
+/// One release in a changelog
+#[derive(Debug, Deserialize)]
+pub struct ChangelogRelease {
+    pub version: String,
+    pub notes: Option<String>,
+    pub features: Vec<String>,
+}
+
+/// Response from the MCP changelog endpoint (releases newest first)
+#[derive(Debug, Deserialize)]
+pub struct Changelog {
+    pub to: Option<String>,
+    pub releases: Vec<ChangelogRelease>,
+    /// Every release's notes as one markdown document
+    pub markdown: String,
+}
+
 /// API client for f.8t.is
 pub struct FeedbackClient {
     client: Client,
+    /// Send Do-Not-Track with update checks
+    do_not_track: bool,
 }
 
 impl FeedbackClient {
@@ -103,7 +241,10 @@ impl FeedbackClient {
             .timeout(Duration::from_secs(30))
             .build()?;
 
-        Ok(Self { client })
+        Ok(Self {
+            client,
+            do_not_track: telemetry_disabled(),
+        })
     }
 
     /// Submit feedback to f.8t.is
@@ -117,16 +258,41 @@ impl FeedbackClient {
                 let data = response.json::<FeedbackResponse>().await?;
                 Ok(data)
             }
-            StatusCode::TOO_MANY_REQUESTS => Err(anyhow::anyhow!(
-                "Rate limit exceeded. Please try again later."
-            )),
-            status => {
-                let error_text = response
-                    .text()
-                    .await
-                    .unwrap_or_else(|_| "Unknown error".to_string());
-                Err(anyhow::anyhow!("API error ({}): {}", status, error_text))
-            }
+            _ => Err(api_error(response).await),
+        }
+    }
+
+    /// Fetch the status (and queue estimate) of submitted feedback
+    pub async fn feedback_status(&self, feedback_id: &str) -> Result<FeedbackStatus> {
+        let url = format!("{}/api/feedback/{}", FEEDBACK_API_BASE, feedback_id);
+
+        let response = self.client.get(&url).send().await?;
+
+        match response.status() {
+            StatusCode::OK => response
+                .json::<ApiResponse<FeedbackStatus>>()
+                .await?
+                .data
+                .ok_or_else(|| anyhow::anyhow!("API response had no data")),
+            _ => Err(api_error(response).await),
+        }
+    }
+
+    /// List the feedback you submitted (newest first), with status and PR links.
+    /// `token` is the bearer token of your f.8t.is account.
+    pub async fn list_my_feedback(&self, token: &str) -> Result<Vec<MyFeedback>> {
+        let url = format!("{}/api/me/feedback", FEEDBACK_API_BASE);
+
+        let response = self.client.get(&url).bearer_auth(token).send().await?;
+
+        match response.status() {
+            StatusCode::OK => response
+                .json::<ApiResponse<MyFeedbackPage>>()
+                .await?
+                .data
+                .map(|page| page.items)
+                .ok_or_else(|| anyhow::anyhow!("API response had no data")),
+            _ => Err(api_error(response).await),
         }
     }
 
@@ -141,16 +307,23 @@ impl FeedbackClient {
                 let data = response.json::<FeedbackResponse>().await?;
                 Ok(data)
             }
-            StatusCode::TOO_MANY_REQUESTS => Err(anyhow::anyhow!(
-                "Rate limit exceeded. Please try again later."
-            )),
-            status => {
-                let error_text = response
-                    .text()
-                    .await
-                    .unwrap_or_else(|_| "Unknown error".to_string());
-                Err(anyhow::anyhow!("API error ({}): {}", status, error_text))
-            }
+            _ => Err(api_error(response).await),
+        }
+    }
+
+    /// Everything released after `version`, up to the latest stable release
+    pub async fn changelog_since(&self, version: &str) -> Result<Changelog> {
+        let url = format!("{}/mcp/changelog", FEEDBACK_API_BASE);
+        let response = self
+            .client
+            .get(&url)
+            .query(&[("product", "smart-tree"), ("from", version)])
+            .send()
+            .await?;
+
+        match response.status() {
+            StatusCode::OK => Ok(response.json::<Changelog>().await?),
+            _ => Err(api_error(response).await),
         }
     }
 
@@ -169,13 +342,21 @@ impl FeedbackClient {
         // Try the new MCP endpoint first (with platform and architecture detection)
         let platform = std::env::consts::OS;
         let arch = std::env::consts::ARCH;
-        let mcp_url = format!(
+        let mut mcp_url = format!(
             "{}/mcp/check?version={}&platform={}&arch={}",
             FEEDBACK_API_BASE, current_version, platform, arch
         );
+        if self.do_not_track {
+            // Telemetry off: still get update info, but ask not to be logged
+            mcp_url.push_str("&dnt=1");
+        }
+        let mut request = self.client.get(&mcp_url);
+        if self.do_not_track {
+            request = request.header("DNT", "1");
+        }
 
         // Attempt to use the new MCP endpoint
-        match self.client.get(&mcp_url).send().await {
+        match request.send().await {
             Ok(response) if response.status() == StatusCode::OK => {
                 if let Ok(mcp_data) = response.json::<McpCheckResponse>().await {
                     // Convert MCP response to VersionInfo format
@@ -216,13 +397,7 @@ impl FeedbackClient {
                 let data = response.json::<VersionInfo>().await?;
                 Ok(data)
             }
-            status => {
-                let error_text = response
-                    .text()
-                    .await
-                    .unwrap_or_else(|_| "Unknown error".to_string());
-                Err(anyhow::anyhow!("API error ({}): {}", status, error_text))
-            }
+            _ => Err(api_error(response).await),
         }
     }
 }
@@ -254,6 +429,57 @@ async fn main() -> Result<()> {
         Err(e) => println!("Failed to check for updates: {}", e),
     }
 
+    // Everything we missed since this version
+    match client.changelog_since(env!("CARGO_PKG_VERSION")).await {
+        Ok(changelog) if changelog.releases.is_empty() => {
+            println!("\nNothing new since this version")
+        }
+        Ok(changelog) => println!("\nWhat's new:\n{}", changelog.markdown),
+        Err(e) => println!("Failed to fetch the changelog: {}", e),
+    }
+
+    // Check on earlier feedback, if we were given an id
+    if let Ok(feedback_id) = std::env::var("FEEDBACK_ID") {
+        println!("\nChecking feedback {}...", feedback_id);
+        match client.feedback_status(&feedback_id).await {
+            Ok(status) => {
+                println!("Status: {}", status.status);
+                if let Some(position) = status.queue_position {
+                    println!("Items ahead of yours: {}", position);
+                }
+                if let Some(start) = status.estimated_start {
+                    println!(
+                        "Processing should start in {}",
+                        friendly_wait(start, chrono::Utc::now())
+                    );
+                }
+                if let Some(url) = status.pull_request_url {
+                    println!("Pull request: {}", url);
+                }
+            }
+            Err(e) => println!("Failed to check feedback status: {}", e),
+        }
+    }
+
+    // Everything we've sent so far, if we have an account token
+    if let Ok(token) = std::env::var("FEEDBACK_TOKEN") {
+        println!("\nYour feedback:");
+        match client.list_my_feedback(&token).await {
+            Ok(items) => {
+                for item in items {
+                    println!(
+                        "- [{}] {}: {}",
+                        item.status, item.repository, item.content_preview
+                    );
+                    if let Some(url) = item.pull_request_url {
+                        println!("  Pull request: {}", url);
+                    }
+                }
+            }
+            Err(e) => println!("Failed to list your feedback: {}", e),
+        }
+    }
+
     println!("\nExample complete!");
     Ok(())
 }
@@ -287,6 +513,133 @@ mod tests {
         assert!(!response.update_available);
     }
 
+    #[test]
+    fn test_changelog_deserialization() {
+        let json = r###"{
+            "product": "smart-tree",
+            "from": "4.6.0",
+            "to": "4.9.1",
+            "releases": [
+                {"version": "4.9.1", "notes": "Fixes", "features": ["quieter logs"], "published_at": "2026-01-01T12:00:00Z"},
+                {"version": "4.7.0", "notes": null, "features": [], "published_at": "2025-12-01T12:00:00Z"}
+            ],
+            "markdown": "## 4.9.1\n\nFixes\n\n- quieter logs\n\n## 4.7.0\n"
+        }"###;
+
+        let changelog: Changelog = serde_json::from_str(json).unwrap();
+        assert_eq!(changelog.to.as_deref(), Some("4.9.1"));
+        assert_eq!(changelog.releases.len(), 2);
+        assert_eq!(changelog.releases[0].features, vec!["quieter logs"]);
+        assert!(changelog.markdown.starts_with("## 4.9.1"));
+    }
+
+    #[test]
+    fn test_friendly_wait() {
+        let now = chrono::Utc::now();
+        let later = |seconds| now + chrono::Duration::seconds(seconds);
+        assert_eq!(friendly_wait(later(-30), now), "any moment now");
+        assert_eq!(friendly_wait(later(20), now), "any moment now");
+        assert_eq!(friendly_wait(later(70), now), "about 1 minute");
+        assert_eq!(friendly_wait(later(600), now), "about 10 minutes");
+        assert_eq!(friendly_wait(later(3 * 3600), now), "about 3 hours");
+    }
+
+    #[test]
+    fn test_feedback_status_deserialization() {
+        let json = r#"{
+            "success": true,
+            "message": "Feedback found",
+            "timestamp": "2026-01-01T12:00:00Z",
+            "data": {
+                "id": "6f1c2d9e-0000-0000-0000-000000000000",
+                "status": "pending",
+                "queue_position": 3,
+                "estimated_start": "2026-01-01T12:10:00Z",
+                "average_duration_seconds": 300.0
+            }
+        }"#;
+
+        let status = serde_json::from_str::<ApiResponse<FeedbackStatus>>(json)
+            .unwrap()
+            .data
+            .unwrap();
+        assert_eq!(status.queue_position, Some(3));
+        assert!(status.estimated_start.is_some());
+        assert!(status.pull_request_url.is_none());
+    }
+
+    #[test]
+    fn test_my_feedback_deserialization() {
+        let json = r#"{
+            "success": true,
+            "message": "Your feedback",
+            "timestamp": "2026-01-01T12:30:00Z",
+            "data": {
+                "items": [{
+                    "id": "6f1c2d9e-0000-0000-0000-000000000000",
+                    "repository": "8b-is/smart-tree",
+                    "content_preview": "Add a --since flag",
+                    "status": "completed",
+                    "pull_request_url": "https://github.com/8b-is/smart-tree/pull/42",
+                    "created_at": "2026-01-01T12:00:00Z",
+                    "updated_at": "2026-01-01T12:30:00Z"
+                }],
+                "next_cursor": null
+            }
+        }"#;
+
+        let page = serde_json::from_str::<ApiResponse<MyFeedbackPage>>(json)
+            .unwrap()
+            .data
+            .unwrap();
+        assert_eq!(page.items.len(), 1);
+        assert_eq!(page.items[0].status, "completed");
+        assert!(page.items[0].pull_request_url.is_some());
+        assert!(page.next_cursor.is_none());
+    }
+
+    #[test]
+    fn test_error_codes_are_described() {
+        let error = |code: &str, details: Value| {
+            serde_json::json!({
+                "success": false,
+                "message": "Operation failed",
+                "error": { "code": code, "message": "Server says no", "details": details },
+                "timestamp": "2026-01-01T12:00:00Z"
+            })
+            .to_string()
+        };
+        assert_eq!(
+            describe_error(
+                StatusCode::TOO_MANY_REQUESTS,
+                &error(
+                    "rate_limit_exceeded",
+                    serde_json::json!({ "retry_after_seconds": 30 })
+                )
+            ),
+            "Rate limit exceeded. Please try again in 30 seconds."
+        );
+        assert_eq!(
+            describe_error(
+                StatusCode::BAD_REQUEST,
+                &error(
+                    "validation_error",
+                    serde_json::json!({ "errors": ["content is too short"] })
+                )
+            ),
+            "The request was rejected: content is too short"
+        );
+        // A code this client doesn't know yet still shows the server's message
+        assert_eq!(
+            describe_error(StatusCode::CONFLICT, &error("brand_new_code", Value::Null)),
+            "API error (409 Conflict, unknown): Server says no"
+        );
+        assert_eq!(
+            describe_error(StatusCode::BAD_GATEWAY, "<html>Bad gateway</html>"),
+            "API error (502 Bad Gateway): <html>Bad gateway</html>"
+        );
+    }
+
     #[test]
     fn test_version_info_deserialization() {
         let json = r#"{
@@ -1,7 +1,31 @@
 // 🔧 Admin Interface - System Management Dashboard! 🔧
 // Created with love by Aye & Hue! ✨
 
-use crate::api::AppState;
+use crate::api::{
+    assets,
+    labels::{label_html, label_style},
+    AppState,
+};
+use crate::auth::session::{self, SessionSubject};
+use crate::config::{LabelStyle, LlmProvider};
+use crate::database::feedback_votes::{self, DuplicateRefused};
+use crate::database::models::{Feedback, FeedbackStatus, User};
+use crate::database::processing_holds::{self, HeldFeedback};
+use crate::database::project_config::{
+    self, stored_version, ProjectConfig, CURRENT_CONFIG_VERSION,
+};
+use crate::database::project_repositories;
+use crate::database::second_factors::{self, ChallengeOutcome, SecondFactor};
+use crate::github::{
+    availability::{self, Reactivation},
+    patch::{self, FilePatch},
+    repo_hooks, ChangeType,
+};
+use crate::jobs::approval::{self, Decision, DecisionOutcome, PendingApproval};
+use crate::llm::health::{HealthReport, HEALTH_WINDOW_HOURS};
+use crate::utils::syntax::{highlight_line, Language};
+use crate::utils::timezones::{self, TimeZone};
+use anyhow::Context;
 use axum::{
     extract::{Path, Query, State},
     http::{header, HeaderMap, StatusCode},
@@ -23,43 +47,139 @@ pub struct LoginForm {
     pub password: String,
 }
 
-/// 🔐 Check if admin is authenticated via cookie
-fn is_admin_authenticated(jar: &CookieJar, app_state: &AppState) -> bool {
-    if app_state.config.auth.admin_password.is_empty() {
-        // No password configured = no auth required (dev mode)
-        return true;
-    }
-
-    if let Some(cookie) = jar.get(ADMIN_SESSION_COOKIE) {
-        // Simple token check: hash of username + password + secret
-        let expected_token = generate_session_token(
-            &app_state.config.auth.admin_username,
-            &app_state.config.auth.admin_password,
-            &app_state.config.auth.jwt_secret,
-        );
-        return cookie.value() == expected_token;
+/// 🔢 Second login step: the challenge token from the password step plus a code
+#[derive(Debug, Deserialize)]
+pub struct TotpLoginForm {
+    pub token: String,
+    pub code: String,
+}
+
+/// 🔢 A TOTP (or backup) code on its own
+#[derive(Debug, Deserialize)]
+pub struct TotpCodeForm {
+    pub code: String,
+}
+
+/// 🪪 Who is using the admin console
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct AdminIdentity {
+    /// 👤 The admin or service account (None for the configured bootstrap admin)
+    pub user_id: Option<uuid::Uuid>,
+    /// 🏷️ Name recorded in the audit log: the account's email or the configured username
+    pub actor: String,
+}
+
+impl AdminIdentity {
+    /// 🪪 The session subject this admin signs in as
+    fn subject(&self) -> SessionSubject {
+        match self.user_id {
+            Some(id) => SessionSubject::User(id),
+            None => SessionSubject::Bootstrap,
+        }
+    }
+}
+
+/// 🔐 Who holds a valid admin session cookie (None = not logged in). Sessions of
+/// accounts that were deactivated or lost their admin role stop working right away,
+/// and so do sessions from before the subject's second factor was switched on or off.
+pub(crate) async fn admin_identity(jar: &CookieJar, app_state: &AppState) -> Option<AdminIdentity> {
+    let auth = &app_state.config.auth;
+    let bootstrap = || AdminIdentity {
+        user_id: None,
+        actor: auth.admin_username.clone(),
+    };
+    let now = chrono::Utc::now().timestamp();
+
+    if let Some(session) = jar
+        .get(ADMIN_SESSION_COOKIE)
+        .and_then(|cookie| session::parse(cookie.value()))
+    {
+        match session.subject {
+            SessionSubject::Bootstrap => {
+                // ⚡ The console factor comes from the settings cache; until it has
+                // loaded nobody knows whether 2FA is on, so these sessions wait
+                let runtime = app_state.settings.get();
+                if !runtime.loaded {
+                    warn!("⏳ Runtime settings not loaded yet, can't check the admin session");
+                } else if session.verify(
+                    &session::credential(&auth.admin_password, runtime.console_second_factor_since),
+                    &auth.jwt_secret,
+                    now,
+                ) {
+                    return Some(bootstrap());
+                }
+            }
+            SessionSubject::User(id) => {
+                match second_factors::find_active_account(&app_state.db_pool, id).await {
+                    Ok(Some(account))
+                        if account.user.role.can_administer()
+                            && session.verify(
+                                &session::credential(
+                                    &account.user.password_hash,
+                                    account.second_factor_since,
+                                ),
+                                &auth.jwt_secret,
+                                now,
+                            ) =>
+                    {
+                        return Some(AdminIdentity {
+                            user_id: Some(account.user.id),
+                            actor: account.user.email,
+                        });
+                    }
+                    Ok(_) => {}
+                    Err(e) => warn!("❌ Failed to check admin session: {:#}", e),
+                }
+            }
+        }
     }
-    false
+
+    // No password configured = no auth required (dev mode)
+    auth.admin_password.is_empty().then(bootstrap)
 }
 
-/// 🔑 Generate a simple session token
-fn generate_session_token(username: &str, password: &str, secret: &str) -> String {
-    use std::collections::hash_map::DefaultHasher;
-    use std::hash::{Hash, Hasher};
+/// 🔐 Check if admin is authenticated via cookie
+async fn is_admin_authenticated(jar: &CookieJar, app_state: &AppState) -> bool {
+    admin_identity(jar, app_state).await.is_some()
+}
 
-    let mut hasher = DefaultHasher::new();
-    format!("{}:{}:{}", username, password, secret).hash(&mut hasher);
-    format!("{:x}", hasher.finish())
+/// 🔑 The active admin or service account with this email (or GitHub username) and
+/// password, if any
+async fn authenticate_admin_user(
+    app_state: &AppState,
+    handle: &str,
+    password: &str,
+) -> anyhow::Result<Option<User>> {
+    let Some(user) = User::find_active_by_handle(&app_state.db_pool, handle).await? else {
+        return Ok(None);
+    };
+    if !user.role.can_administer() {
+        return Ok(None);
+    }
+    // 🐢 Argon2 is deliberately slow, keep it off the async workers
+    let (password, hash) = (password.to_string(), user.password_hash.clone());
+    let matches = tokio::task::spawn_blocking(move || {
+        crate::auth::password::verify_password(&password, &hash)
+    })
+    .await?;
+    if !matches {
+        return Ok(None);
+    }
+    sqlx::query("UPDATE users SET last_login_at = NOW() WHERE id = $1")
+        .bind(user.id)
+        .execute(&app_state.db_pool)
+        .await?;
+    Ok(Some(user))
 }
 
 /// 🔐 Admin Login Page
 pub async fn admin_login(State(app_state): State<AppState>, jar: CookieJar) -> impl IntoResponse {
     // If already authenticated, redirect to dashboard
-    if is_admin_authenticated(&jar, &app_state) {
+    if is_admin_authenticated(&jar, &app_state).await {
         return Redirect::to("/admin").into_response();
     }
 
-    Html(render_login_page(None)).into_response()
+    Html(render_login_page(None, label_style(&app_state, &jar))).into_response()
 }
 
 /// 🔐 Admin Login POST Handler
@@ -72,28 +192,621 @@ pub async fn admin_login_post(
     let expected_password = &app_state.config.auth.admin_password;
 
     if form.username == *expected_username && form.password == *expected_password {
-        info!("🔓 Admin login successful for user: {}", form.username);
-
-        let token = generate_session_token(
-            expected_username,
+        return finish_password_step(
+            &app_state,
+            &jar,
+            SessionSubject::Bootstrap,
             expected_password,
-            &app_state.config.auth.jwt_secret,
-        );
+            expected_username,
+        )
+        .await;
+    }
 
-        let cookie = Cookie::build((ADMIN_SESSION_COOKIE, token))
-            .path("/admin")
-            .http_only(true)
-            .secure(app_state.config.is_production())
-            .max_age(time::Duration::hours(24))
-            .build();
+    // 👥 Otherwise it has to be an admin or service account from `users`
+    match authenticate_admin_user(&app_state, &form.username, &form.password).await {
+        Ok(Some(user)) => {
+            finish_password_step(
+                &app_state,
+                &jar,
+                SessionSubject::User(user.id),
+                &user.password_hash,
+                &user.email,
+            )
+            .await
+        }
+        result => {
+            if let Err(e) = result {
+                warn!("❌ Failed to check admin account: {:#}", e);
+            }
+            warn!("🚫 Admin login failed for user: {}", form.username);
+            Html(render_login_page(
+                Some("Invalid username or password"),
+                label_style(&app_state, &jar),
+            ))
+            .into_response()
+        }
+    }
+}
 
-        (jar.add(cookie), Redirect::to("/admin")).into_response()
-    } else {
-        warn!("🚫 Admin login failed for user: {}", form.username);
-        Html(render_login_page(Some("Invalid username or password"))).into_response()
+/// 🔢 The password was right: a subject with its own second factor gets the code step
+/// (a short-lived pre-auth token), everyone else a session straight away
+async fn finish_password_step(
+    app_state: &AppState,
+    jar: &CookieJar,
+    subject: SessionSubject,
+    password: &str,
+    actor: &str,
+) -> Response {
+    match second_factors::enabled_since(&app_state.db_pool, subject).await {
+        Ok(Some(_)) => {
+            info!(
+                "🔢 Admin password accepted for {}, waiting for TOTP code",
+                actor
+            );
+            match second_factors::issue_challenge(&app_state.db_pool, subject).await {
+                Ok(token) => Html(render_totp_login_page(
+                    &token,
+                    None,
+                    label_style(app_state, jar),
+                ))
+                .into_response(),
+                Err(e) => {
+                    warn!("❌ Failed to start the code step for {}: {:#}", actor, e);
+                    Html(render_login_page(
+                        Some("Could not sign you in, please try again"),
+                        label_style(app_state, jar),
+                    ))
+                    .into_response()
+                }
+            }
+        }
+        Ok(None) => {
+            info!("🔓 Admin login successful for {}", actor);
+            (
+                jar.clone().add(admin_session_cookie(
+                    app_state,
+                    subject,
+                    &session::credential(password, None),
+                )),
+                Redirect::to("/admin"),
+            )
+                .into_response()
+        }
+        Err(e) => {
+            // 🔒 Unknown 2FA state: no session rather than a password-only one
+            warn!(
+                "❌ Failed to check the second factor for {}: {:#}",
+                actor, e
+            );
+            Html(render_login_page(
+                Some("Could not sign you in, please try again"),
+                label_style(app_state, jar),
+            ))
+            .into_response()
+        }
+    }
+}
+
+/// 🔑 Who `subject` is and the password (hash) their sessions are signed over - None
+/// for accounts that were deactivated or lost their admin role
+async fn session_owner(
+    app_state: &AppState,
+    subject: SessionSubject,
+) -> anyhow::Result<Option<(String, String)>> {
+    let auth = &app_state.config.auth;
+    Ok(match subject {
+        SessionSubject::Bootstrap => {
+            Some((auth.admin_username.clone(), auth.admin_password.clone()))
+        }
+        SessionSubject::User(id) => User::find_active_by_id(&app_state.db_pool, id)
+            .await?
+            .filter(|user| user.role.can_administer())
+            .map(|user| (user.email, user.password_hash)),
+    })
+}
+
+/// 🍪 Session cookie handed out once every login step has passed. `credential` comes
+/// from `session::credential`: the account's password hash (or the configured password)
+/// and its second factor, so changing either logs them out.
+fn admin_session_cookie(
+    app_state: &AppState,
+    subject: SessionSubject,
+    credential: &str,
+) -> Cookie<'static> {
+    let token = session::issue(
+        subject,
+        credential,
+        &app_state.config.auth.jwt_secret,
+        chrono::Utc::now().timestamp(),
+    );
+
+    Cookie::build((ADMIN_SESSION_COOKIE, token))
+        .path("/admin")
+        .http_only(true)
+        .secure(app_state.config.is_production())
+        .max_age(time::Duration::seconds(session::SESSION_TTL_SECS))
+        .build()
+}
+
+/// 🔢 Admin Login second step: TOTP or backup code
+pub async fn admin_login_totp_post(
+    State(app_state): State<AppState>,
+    jar: CookieJar,
+    Form(form): Form<TotpLoginForm>,
+) -> Response {
+    let expired = || {
+        Html(render_login_page(
+            Some("Your sign-in expired, please log in again"),
+            label_style(&app_state, &jar),
+        ))
+        .into_response()
+    };
+    let retry = |error: &str| {
+        Html(render_totp_login_page(
+            &form.token,
+            Some(error),
+            label_style(&app_state, &jar),
+        ))
+        .into_response()
+    };
+
+    let (subject, verified) =
+        match second_factors::answer_challenge(&app_state.db_pool, &form.token, &form.code).await {
+            Ok(ChallengeOutcome::Passed { subject, verified }) => (subject, verified),
+            Ok(ChallengeOutcome::WrongCode { attempts_left }) => {
+                warn!(
+                    "🚫 Admin TOTP code rejected ({} attempts left)",
+                    attempts_left
+                );
+                return retry(&format!("Invalid code ({} attempts left)", attempts_left));
+            }
+            Ok(ChallengeOutcome::Expired) => {
+                warn!("🚫 Admin TOTP step with an unknown, spent or expired challenge");
+                return expired();
+            }
+            Ok(ChallengeOutcome::Locked) => {
+                return Html(render_login_page(
+                    Some("Too many wrong codes - wait a few minutes before trying again"),
+                    label_style(&app_state, &jar),
+                ))
+                .into_response();
+            }
+            Err(e) => {
+                warn!("❌ Failed to check admin TOTP code: {:#}", e);
+                return retry("Could not check the code, please try again");
+            }
+        };
+    let (actor, password) = match session_owner(&app_state, subject).await {
+        Ok(Some(owner)) => owner,
+        Ok(None) => {
+            warn!("🚫 Admin TOTP step for an account that can no longer sign in");
+            return expired();
+        }
+        Err(e) => {
+            warn!("❌ Failed to load the admin signing in: {:#}", e);
+            return expired();
+        }
+    };
+
+    info!(
+        "🔓 Admin login successful for {} ({})",
+        actor, verified.method
+    );
+    if verified.method == SecondFactor::BackupCode {
+        audit_log_as(
+            &app_state,
+            &actor,
+            "admin_login_backup_code",
+            serde_json::json!({}),
+        )
+        .await;
+    }
+    (
+        jar.add(admin_session_cookie(
+            &app_state,
+            subject,
+            &session::credential(&password, Some(verified.enabled_at)),
+        )),
+        Redirect::to("/admin"),
+    )
+        .into_response()
+}
+
+/// 💾 Insert-or-update a settings row inside a transaction
+async fn upsert_setting(
+    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    key: &str,
+    value: &str,
+) -> anyhow::Result<()> {
+    sqlx::query(
+        "INSERT INTO settings (key, value, updated_at) VALUES ($1, $2, NOW()) ON CONFLICT (key) DO UPDATE SET value = $2, updated_at = NOW()"
+    )
+    .bind(key)
+    .bind(value)
+    .execute(&mut **tx)
+    .await?;
+    Ok(())
+}
+
+/// 📜 Record a security-relevant admin action by whoever is logged in to the admin UI
+/// (failures are logged, never fatal)
+pub(crate) async fn audit_log(
+    app_state: &AppState,
+    jar: &CookieJar,
+    action: &str,
+    details: serde_json::Value,
+) {
+    let actor = audit_actor(app_state, jar).await;
+    audit_log_as(app_state, &actor, action, details).await
+}
+
+/// 🪪 Who admin audit entries are recorded for
+async fn audit_actor(app_state: &AppState, jar: &CookieJar) -> String {
+    match admin_identity(jar, app_state).await {
+        Some(identity) => identity.actor,
+        None => app_state.config.auth.admin_username.clone(),
+    }
+}
+
+/// 📜 Same as `audit_log`, for actions taken outside an admin session
+pub(crate) async fn audit_log_as(
+    app_state: &AppState,
+    actor: &str,
+    action: &str,
+    details: serde_json::Value,
+) {
+    if let Err(e) = record_audit(&app_state.db_pool, actor, action, details).await {
+        warn!("⚠️ Failed to write admin audit log: {:#}", e);
+    }
+}
+
+/// 📜 Write an audit entry with the caller's executor - inside a handler's `Tx` it
+/// commits (or not) with the action it records
+pub(crate) async fn record_audit(
+    executor: impl sqlx::PgExecutor<'_>,
+    actor: &str,
+    action: &str,
+    details: serde_json::Value,
+) -> anyhow::Result<()> {
+    info!("📜 Audit ({}): {} {}", actor, action, details);
+    sqlx::query("INSERT INTO admin_audit_log (actor, action, details) VALUES ($1, $2, $3)")
+        .bind(actor)
+        .bind(action)
+        .bind(details)
+        .execute(executor)
+        .await?;
+    Ok(())
+}
+
+/// 🔢 Start enrollment of the signed-in admin's own factor: create a pending secret and
+/// show the QR code
+pub async fn admin_totp_enroll(State(app_state): State<AppState>, jar: CookieJar) -> Response {
+    let Some(admin) = admin_identity(&jar, &app_state).await else {
+        return Redirect::to("/admin/login").into_response();
+    };
+
+    match second_factors::start_enrollment(&app_state.db_pool, admin.subject()).await {
+        Ok(Some(secret)) => Html(render_totp_enroll_page(
+            &admin.actor,
+            &secret,
+            None,
+            label_style(&app_state, &jar),
+        ))
+        .into_response(),
+        Ok(None) => {
+            // 🔒 Replacing an active secret would skip proving the current one - disable first
+            warn!(
+                "🚫 Refusing TOTP enrollment for {}: two-factor is already enabled",
+                admin.actor
+            );
+            Redirect::to("/admin/settings").into_response()
+        }
+        Err(e) => {
+            warn!("❌ Failed to store pending TOTP secret: {:#}", e);
+            Redirect::to("/admin/settings").into_response()
+        }
+    }
+}
+
+/// 🔁 Re-sign the acting admin's cookie after their second factor changed - the old one
+/// was signed over the previous state and stops working with it. The bootstrap admin's
+/// factor is read from the settings cache, so that is reloaded first; other instances
+/// pick the change up on their next refresh.
+async fn resign_session(
+    app_state: &AppState,
+    jar: CookieJar,
+    admin: &AdminIdentity,
+    second_factor_since: Option<chrono::DateTime<chrono::Utc>>,
+) -> CookieJar {
+    let subject = admin.subject();
+    if subject == SessionSubject::Bootstrap {
+        if let Err(e) = app_state.settings.reload(&app_state.db_pool).await {
+            warn!("⚠️ Failed to reload runtime settings: {:#}", e);
+        }
+    }
+    match session_owner(app_state, subject).await {
+        Ok(Some((_, password))) => jar.add(admin_session_cookie(
+            app_state,
+            subject,
+            &session::credential(&password, second_factor_since),
+        )),
+        Ok(None) => jar,
+        Err(e) => {
+            warn!("❌ Failed to re-sign the admin session: {:#}", e);
+            jar
+        }
+    }
+}
+
+/// 🔢 Finish enrollment: a valid code activates 2FA for the signed-in admin, ends their
+/// sessions from before and reveals the backup codes once
+pub async fn admin_totp_activate(
+    State(app_state): State<AppState>,
+    jar: CookieJar,
+    Form(form): Form<TotpCodeForm>,
+) -> Response {
+    let Some(admin) = admin_identity(&jar, &app_state).await else {
+        return Redirect::to("/admin/login").into_response();
+    };
+    let secret = match second_factors::pending_secret(&app_state.db_pool, admin.subject()).await {
+        Ok(Some(secret)) => secret,
+        Ok(None) => return Redirect::to("/admin/settings").into_response(),
+        Err(e) => {
+            warn!("❌ Failed to load pending TOTP secret: {:#}", e);
+            return Redirect::to("/admin/settings").into_response();
+        }
+    };
+
+    let now = chrono::Utc::now().timestamp() as u64;
+    let Some(step) = crate::auth::totp::verify_code(&secret, &form.code, now) else {
+        return Html(render_totp_enroll_page(
+            &admin.actor,
+            &secret,
+            Some("That code didn't match - check your device clock and try again"),
+            label_style(&app_state, &jar),
+        ))
+        .into_response();
+    };
+
+    let activated =
+        match second_factors::activate(&app_state.db_pool, admin.subject(), &secret, step).await {
+            Ok(Some(activated)) => activated,
+            Ok(None) => return Redirect::to("/admin/settings").into_response(),
+            Err(e) => {
+                warn!("❌ Failed to activate TOTP: {:#}", e);
+                return Redirect::to("/admin/settings").into_response();
+            }
+        };
+    audit_log_as(
+        &app_state,
+        &admin.actor,
+        "admin_totp_enabled",
+        serde_json::json!({ "backup_codes": activated.backup_codes.len() }),
+    )
+    .await;
+    let style = label_style(&app_state, &jar);
+    let jar = resign_session(&app_state, jar, &admin, Some(activated.enabled_at)).await;
+
+    let codes: String = activated
+        .backup_codes
+        .iter()
+        .map(|code| format!("<li><code>{}</code></li>", code))
+        .collect();
+    (
+        jar,
+        Html(render_admin_page(
+            "Two-Factor Enabled - Feedbacker Admin",
+            "/admin/settings",
+            &format!(
+                r#"
+    <div class="header">
+        <h2>{heading}</h2>
+    </div>
+    <div class="card">
+        <div class="card-header">
+            <h3>{codes_heading}</h3>
+        </div>
+        <div class="card-body">
+            <p>Each code works once in place of an authenticator code. Store them somewhere safe - they won't be shown again.</p>
+            <p class="muted">Your other sessions were signed out.</p>
+            <ul class="backup-codes">{}</ul>
+            <a href="/admin/settings" class="btn btn-primary">Done</a>
+        </div>
+    </div>
+"#,
+                codes,
+                heading = label_html("🔢 Two-Factor Authentication Enabled", style),
+                codes_heading = label_html("🎟️ Backup Codes", style),
+            ),
+            style,
+        )),
+    )
+        .into_response()
+}
+
+/// 🔢 Turn the signed-in admin's 2FA off (needs a current TOTP or backup code)
+pub async fn admin_totp_disable(
+    State(app_state): State<AppState>,
+    jar: CookieJar,
+    Form(form): Form<TotpCodeForm>,
+) -> Response {
+    let Some(admin) = admin_identity(&jar, &app_state).await else {
+        return Redirect::to("/admin/login").into_response();
+    };
+
+    match second_factors::check(&app_state.db_pool, admin.subject(), &form.code).await {
+        Ok(Some(verified)) => {
+            match second_factors::disable(&app_state.db_pool, admin.subject()).await {
+                Ok(()) => {
+                    audit_log_as(
+                        &app_state,
+                        &admin.actor,
+                        "admin_totp_disabled",
+                        serde_json::json!({ "confirmed_with": verified.method.to_string() }),
+                    )
+                    .await;
+                    let jar = resign_session(&app_state, jar, &admin, None).await;
+                    return (jar, Redirect::to("/admin/settings")).into_response();
+                }
+                Err(e) => warn!("❌ Failed to disable TOTP: {:#}", e),
+            }
+        }
+        Ok(None) => warn!("🚫 Refusing to disable TOTP: invalid code"),
+        Err(e) => warn!("❌ Failed to check TOTP code: {:#}", e),
+    }
+    Redirect::to("/admin/settings").into_response()
+}
+
+/// 🧼 Escape text for use inside HTML attributes and content
+pub(crate) fn html_escape(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// 🕰️ A timestamp in the admin's time zone ("2024-03-31 03:00 CEST"), with the exact UTC
+/// instant in `datetime` and as a hover title
+pub(crate) fn fmt_ts(at: chrono::DateTime<chrono::Utc>, tz: &TimeZone) -> String {
+    let iso = at.to_rfc3339_opts(chrono::SecondsFormat::Secs, true);
+    let (local, abbreviation) = tz.to_local(at);
+    format!(
+        r#"<time datetime="{0}" title="{0}">{1} {2}</time>"#,
+        iso,
+        local.format("%Y-%m-%d %H:%M"),
+        html_escape(&abbreviation)
+    )
+}
+
+/// 📡 JSON keeps the UTC "YYYY-MM-DD HH:MM" it always had, whatever the pages show
+fn serialize_minutes<S: serde::Serializer>(
+    at: &chrono::DateTime<chrono::Utc>,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    serializer.collect_str(&at.format("%Y-%m-%d %H:%M"))
+}
+
+/// 🔑 Settings key holding one admin's time zone (by account id, the bootstrap admin by name)
+fn timezone_key(admin: &AdminIdentity) -> String {
+    match admin.user_id {
+        Some(id) => format!("admin_timezone:{}", id),
+        None => format!("admin_timezone:{}", admin.actor),
     }
 }
 
+/// 🕰️ The time zone the signed-in admin picked (UTC until they pick one, or if the
+/// stored zone is no longer in the tz database)
+async fn admin_timezone(app_state: &AppState, jar: &CookieJar) -> TimeZone {
+    let Some(admin) = admin_identity(jar, app_state).await else {
+        return TimeZone::utc();
+    };
+    let Some(name) = get_setting(app_state, &timezone_key(&admin)).await else {
+        return TimeZone::utc();
+    };
+    TimeZone::load(&name).unwrap_or_else(|e| {
+        warn!(
+            "⚠️ Stored time zone for {} is unusable: {:#}",
+            admin.actor, e
+        );
+        TimeZone::utc()
+    })
+}
+
+/// 🔢 Enrollment page: QR code, manual secret and the confirmation form
+fn render_totp_enroll_page(
+    account: &str,
+    secret: &str,
+    error: Option<&str>,
+    style: LabelStyle,
+) -> String {
+    let uri = crate::auth::totp::otpauth_uri(secret, account).unwrap_or_default();
+    let qr = crate::auth::totp::qr_svg(&uri).unwrap_or_default();
+    let error_html = error
+        .map(|e| format!(r#"<div class="error-message" role="alert">{}</div>"#, e))
+        .unwrap_or_default();
+
+    render_admin_page(
+        "Set Up Two-Factor - Feedbacker Admin",
+        "/admin/settings",
+        &format!(
+            r#"
+    <div class="header">
+        <h2>{heading}</h2>
+    </div>
+    <div class="card">
+        <div class="card-body">
+            <p>Scan this code with your authenticator app, then enter the 6-digit code it shows.</p>
+            <div class="totp-qr">{qr}</div>
+            <div class="setting-row">
+                <span class="setting-label">Manual entry key</span>
+                <span class="setting-value"><code>{secret}</code></span>
+            </div>
+            <div class="setting-row">
+                <span class="setting-label">URI</span>
+                <span class="setting-value"><code>{uri}</code></span>
+            </div>
+            {error_html}
+            <form method="POST" action="/admin/settings/totp/activate">
+                <div class="form-group">
+                    <label for="code">Code</label>
+                    <input type="text" id="code" name="code" required inputmode="numeric" autocomplete="one-time-code" pattern="[0-9]{{6}}">
+                </div>
+                <button type="submit" class="btn btn-primary">Activate</button>
+            </form>
+        </div>
+    </div>
+"#,
+            qr = qr,
+            secret = secret,
+            uri = html_escape(&uri),
+            error_html = error_html,
+            heading = label_html("🔢 Set Up Two-Factor Authentication", style),
+        ),
+        style,
+    )
+}
+
+/// 🔢 Second login step page
+fn render_totp_login_page(token: &str, error: Option<&str>, style: LabelStyle) -> String {
+    let error_html = error
+        .map(|e| format!(r#"<div class="error-message" role="alert">{}</div>"#, e))
+        .unwrap_or_default();
+
+    format!(
+        r#"
+<!DOCTYPE html>
+<html lang="en">
+<head>
+    <meta charset="UTF-8">
+    <meta name="viewport" content="width=device-width, initial-scale=1.0">
+    <title>Two-Factor Login - Feedbacker</title>
+    {css_link}
+</head>
+<body class="login">
+    <main class="login-container">
+        <h1>{heading}</h1>
+        {error_html}
+        <form method="POST" action="/admin/login/totp">
+            <input type="hidden" name="token" value="{token}">
+            <div class="form-group">
+                <label for="code">Authenticator or backup code</label>
+                <input type="text" id="code" name="code" required autofocus autocomplete="one-time-code">
+            </div>
+            <button type="submit" class="btn">Verify</button>
+        </form>
+        <a href="/admin/login" class="back-link">← Start over</a>
+    </main>
+</body>
+</html>
+"#,
+        css_link = assets::admin_css_link(),
+        heading = label_html("🔢 Two-Factor Code", style),
+        error_html = error_html,
+        token = html_escape(token),
+    )
+}
+
 /// 🚪 Admin Logout Handler
 pub async fn admin_logout(jar: CookieJar) -> impl IntoResponse {
     info!("🚪 Admin logged out");
@@ -107,9 +820,9 @@ pub async fn admin_logout(jar: CookieJar) -> impl IntoResponse {
 }
 
 /// 🔐 Render login page HTML
-fn render_login_page(error: Option<&str>) -> String {
+fn render_login_page(error: Option<&str>, style: LabelStyle) -> String {
     let error_html = error
-        .map(|e| format!(r#"<div class="error-message">{}</div>"#, e))
+        .map(|e| format!(r#"<div class="error-message" role="alert">{}</div>"#, e))
         .unwrap_or_default();
 
     format!(
@@ -120,89 +833,11 @@ fn render_login_page(error: Option<&str>) -> String {
     <meta charset="UTF-8">
     <meta name="viewport" content="width=device-width, initial-scale=1.0">
     <title>Admin Login - Feedbacker</title>
-    <style>
-        * {{ margin: 0; padding: 0; box-sizing: border-box; }}
-        body {{
-            font-family: -apple-system, BlinkMacSystemFont, 'Segoe UI', Roboto, sans-serif;
-            background: #0f0f23;
-            color: #cccccc;
-            min-height: 100vh;
-            display: flex;
-            align-items: center;
-            justify-content: center;
-        }}
-        .login-container {{
-            background: #1a1a2e;
-            padding: 40px;
-            border-radius: 12px;
-            border: 1px solid #333;
-            width: 100%;
-            max-width: 400px;
-        }}
-        .login-container h1 {{
-            color: #00d4ff;
-            text-align: center;
-            margin-bottom: 30px;
-        }}
-        .form-group {{
-            margin-bottom: 20px;
-        }}
-        .form-group label {{
-            display: block;
-            margin-bottom: 8px;
-            color: #888;
-        }}
-        .form-group input {{
-            width: 100%;
-            padding: 12px;
-            border: 1px solid #333;
-            border-radius: 8px;
-            background: #0f0f23;
-            color: #fff;
-            font-size: 16px;
-        }}
-        .form-group input:focus {{
-            outline: none;
-            border-color: #00d4ff;
-        }}
-        .btn {{
-            width: 100%;
-            padding: 14px;
-            background: #00d4ff;
-            color: #000;
-            border: none;
-            border-radius: 8px;
-            font-size: 16px;
-            font-weight: 600;
-            cursor: pointer;
-            transition: background 0.2s;
-        }}
-        .btn:hover {{
-            background: #00a8cc;
-        }}
-        .error-message {{
-            background: #3d0000;
-            color: #ff4444;
-            padding: 12px;
-            border-radius: 8px;
-            margin-bottom: 20px;
-            text-align: center;
-        }}
-        .back-link {{
-            display: block;
-            text-align: center;
-            margin-top: 20px;
-            color: #888;
-            text-decoration: none;
-        }}
-        .back-link:hover {{
-            color: #00d4ff;
-        }}
-    </style>
+    {css_link}
 </head>
-<body>
-    <div class="login-container">
-        <h1>🔐 Admin Login</h1>
+<body class="login">
+    <main class="login-container">
+        <h1>{heading}</h1>
         {error_html}
         <form method="POST" action="/admin/login">
             <div class="form-group">
@@ -216,1410 +851,6433 @@ fn render_login_page(error: Option<&str>) -> String {
             <button type="submit" class="btn">Login</button>
         </form>
         <a href="/" class="back-link">← Back to Site</a>
-    </div>
+    </main>
 </body>
 </html>
 "#,
+        css_link = assets::admin_css_link(),
+        heading = label_html("🔐 Admin Login", style),
         error_html = error_html
     )
 }
 
-/// 🔐 Middleware-like function to check auth and redirect if not logged in
-fn require_admin_auth(jar: &CookieJar, app_state: &AppState) -> Option<Response> {
-    if !is_admin_authenticated(jar, app_state) {
-        Some(Redirect::to("/admin/login").into_response())
-    } else {
-        None
-    }
-}
+/// 🧭 Sidebar navigation entries (href, label)
+const ADMIN_NAV: &[(&str, &str)] = &[
+    ("/admin", "📊 Dashboard"),
+    ("/admin/feedback", "📝 Feedback"),
+    ("/admin/projects", "🏠 Projects"),
+    ("/admin/users", "👥 Users"),
+    ("/admin/jobs", "⚙️ Background Jobs"),
+    ("/admin/migrations", "🗄️ Migrations"),
+    ("/admin/mcp", "🤖 MCP Analytics"),
+    ("/admin/settings", "🔧 Settings"),
+];
 
-/// 📊 Dashboard statistics
-#[derive(Debug, Serialize)]
-pub struct DashboardStats {
-    pub total_users: i64,
-    pub total_projects: i64,
-    pub total_feedback: i64,
-    pub pending_feedback: i64,
-    pub completed_feedback: i64,
-    pub failed_feedback: i64,
-}
+/// 📅 Nav entries that understand `?range=` and should keep it while navigating
+const RANGED_PAGES: &[&str] = &["/admin", "/admin/feedback"];
 
-/// 📋 Feedback item for listing
-#[derive(Debug, Serialize)]
-pub struct FeedbackItem {
-    pub id: String,
-    pub repository: String,
-    pub status: String,
-    pub created_at: String,
-    pub content_preview: String,
+/// 🖼️ Render a full admin page: shared stylesheet, sidebar and the page content
+fn render_admin_page(title: &str, active: &str, content: &str, style: LabelStyle) -> String {
+    render_admin_page_ranged(title, active, content, DashboardRange::default(), style)
 }
 
-/// 🏠 Admin Dashboard
-pub async fn admin_dashboard(State(app_state): State<AppState>, jar: CookieJar) -> Response {
-    if let Some(redirect) = require_admin_auth(&jar, &app_state) {
-        return redirect;
-    }
-    info!("🔧 Admin dashboard accessed");
-
-    let stats = get_dashboard_stats(&app_state)
-        .await
-        .unwrap_or(DashboardStats {
-            total_users: 0,
-            total_projects: 0,
-            total_feedback: 0,
-            pending_feedback: 0,
-            completed_feedback: 0,
-            failed_feedback: 0,
-        });
-
-    let recent_feedback = get_recent_feedback(&app_state, 10)
-        .await
-        .unwrap_or_default();
+/// 🖼️ Same as `render_admin_page`, carrying the selected time range in the ranged nav links
+fn render_admin_page_ranged(
+    title: &str,
+    active: &str,
+    content: &str,
+    range: DashboardRange,
+    style: LabelStyle,
+) -> String {
+    let nav: String = ADMIN_NAV
+        .iter()
+        .map(|(href, label)| {
+            let current = if *href == active {
+                r#" class="active" aria-current="page""#
+            } else {
+                ""
+            };
+            let href = if RANGED_PAGES.contains(href) {
+                range.link(href)
+            } else {
+                href.to_string()
+            };
+            format!(
+                r#"            <a href="{}"{}>{}</a>
+"#,
+                href,
+                current,
+                label_html(label, style)
+            )
+        })
+        .collect();
 
-    Html(format!(
-        r#"
+    format!(
+        r##"
 <!DOCTYPE html>
 <html lang="en">
 <head>
     <meta charset="UTF-8">
     <meta name="viewport" content="width=device-width, initial-scale=1.0">
-    <title>Admin Dashboard - Feedbacker</title>
-    <style>
-        * {{ margin: 0; padding: 0; box-sizing: border-box; }}
-        body {{
-            font-family: -apple-system, BlinkMacSystemFont, 'Segoe UI', Roboto, sans-serif;
-            background: #0f0f23;
-            color: #cccccc;
-            min-height: 100vh;
-        }}
-        .sidebar {{
-            position: fixed;
-            left: 0;
-            top: 0;
-            width: 250px;
-            height: 100vh;
-            background: #1a1a2e;
-            padding: 20px;
-            border-right: 1px solid #333;
-        }}
-        .sidebar h1 {{
-            color: #00d4ff;
-            font-size: 1.5em;
-            margin-bottom: 30px;
-            padding-bottom: 20px;
-            border-bottom: 1px solid #333;
-        }}
-        .sidebar nav a {{
-            display: block;
-            color: #888;
-            text-decoration: none;
-            padding: 12px 15px;
-            margin: 5px 0;
-            border-radius: 8px;
-            transition: all 0.2s;
-        }}
-        .sidebar nav a:hover, .sidebar nav a.active {{
-            background: #252542;
-            color: #00d4ff;
-        }}
-        .main {{
-            margin-left: 250px;
-            padding: 30px;
-        }}
-        .header {{
-            display: flex;
-            justify-content: space-between;
-            align-items: center;
-            margin-bottom: 30px;
-        }}
-        .header h2 {{
-            color: #fff;
-            font-size: 1.8em;
-        }}
-        .stats-grid {{
-            display: grid;
-            grid-template-columns: repeat(auto-fit, minmax(200px, 1fr));
-            gap: 20px;
-            margin-bottom: 30px;
-        }}
-        .stat-card {{
-            background: #1a1a2e;
-            padding: 25px;
-            border-radius: 12px;
-            border: 1px solid #333;
-        }}
-        .stat-card h3 {{
-            color: #888;
-            font-size: 0.9em;
-            margin-bottom: 10px;
-        }}
-        .stat-card .value {{
-            font-size: 2.5em;
-            font-weight: bold;
-            color: #00d4ff;
-        }}
-        .stat-card.success .value {{ color: #00ff88; }}
-        .stat-card.warning .value {{ color: #ffaa00; }}
-        .stat-card.danger .value {{ color: #ff4444; }}
-        .card {{
-            background: #1a1a2e;
-            border-radius: 12px;
-            border: 1px solid #333;
-            margin-bottom: 20px;
-        }}
-        .card-header {{
-            padding: 20px;
-            border-bottom: 1px solid #333;
-            display: flex;
-            justify-content: space-between;
-            align-items: center;
-        }}
-        .card-header h3 {{
-            color: #fff;
-        }}
-        .card-body {{
-            padding: 20px;
-        }}
-        table {{
-            width: 100%;
-            border-collapse: collapse;
-        }}
-        th, td {{
-            padding: 12px 15px;
-            text-align: left;
-            border-bottom: 1px solid #333;
-        }}
-        th {{
-            color: #888;
-            font-weight: 500;
-            font-size: 0.85em;
-            text-transform: uppercase;
-        }}
-        .status {{
-            display: inline-block;
-            padding: 4px 12px;
-            border-radius: 20px;
-            font-size: 0.85em;
-            font-weight: 500;
-        }}
-        .status-pending {{ background: #3d3d00; color: #ffaa00; }}
-        .status-completed {{ background: #003d00; color: #00ff88; }}
-        .status-failed {{ background: #3d0000; color: #ff4444; }}
-        .status-processing {{ background: #003d3d; color: #00d4ff; }}
-        .btn {{
-            display: inline-block;
-            padding: 10px 20px;
-            border-radius: 8px;
-            text-decoration: none;
-            font-weight: 500;
-            transition: all 0.2s;
-            border: none;
-            cursor: pointer;
-        }}
-        .btn-primary {{
-            background: #00d4ff;
-            color: #000;
-        }}
-        .btn-primary:hover {{
-            background: #00a8cc;
-        }}
-        .empty-state {{
-            text-align: center;
-            padding: 40px;
-            color: #666;
-        }}
-    </style>
+    <title>{title}</title>
+    {css_link}
 </head>
 <body>
-    <div class="sidebar">
-        <h1>🚢 Feedbacker</h1>
-        <nav>
-            <a href="/admin" class="active">📊 Dashboard</a>
-            <a href="/admin/feedback">📝 Feedback</a>
-            <a href="/admin/projects">🏠 Projects</a>
-            <a href="/admin/users">👥 Users</a>
-            <a href="/admin/jobs">⚙️ Background Jobs</a>
-            <a href="/admin/mcp">🤖 MCP Analytics</a>
-            <a href="/admin/settings">🔧 Settings</a>
-            <a href="/">← Back to Site</a>
-            <a href="/admin/logout" style="margin-top: 30px; color: #ff4444;">🚪 Logout</a>
+    <a href="#main-content" class="skip-link">Skip to content</a>
+    <header class="sidebar">
+        <h1>{brand}</h1>
+        <p class="build" title="{git_sha}">v{version} · {short_sha}</p>
+        <nav aria-label="Admin">
+{nav}            <a href="/">← Back to Site</a>
+            <a href="/admin/logout" class="logout">{logout}</a>
         </nav>
-    </div>
+    </header>
 
-    <div class="main">
-        <div class="header">
-            <h2>📊 Dashboard</h2>
-            <span style="color: #888;">Welcome, Admin</span>
-        </div>
+    <main class="main" id="main-content">
+{content}    </main>
+</body>
+</html>
+"##,
+        title = title,
+        css_link = assets::admin_css_link(),
+        brand = label_html("🚢 Feedbacker", style),
+        git_sha = crate::build_info::GIT_SHA,
+        version = crate::build_info::VERSION,
+        short_sha = crate::build_info::short_sha(),
+        nav = nav,
+        logout = label_html("🚪 Logout", style),
+        content = content,
+    )
+}
 
-        <div class="stats-grid">
-            <div class="stat-card">
-                <h3>Total Users</h3>
-                <div class="value">{}</div>
-            </div>
-            <div class="stat-card">
-                <h3>Total Projects</h3>
-                <div class="value">{}</div>
-            </div>
-            <div class="stat-card">
-                <h3>Total Feedback</h3>
-                <div class="value">{}</div>
-            </div>
-            <div class="stat-card warning">
-                <h3>Pending</h3>
-                <div class="value">{}</div>
-            </div>
-            <div class="stat-card success">
-                <h3>Completed</h3>
-                <div class="value">{}</div>
-            </div>
-            <div class="stat-card danger">
-                <h3>Failed</h3>
-                <div class="value">{}</div>
-            </div>
-        </div>
-
-        <div class="card">
-            <div class="card-header">
-                <h3>📝 Recent Feedback</h3>
-                <a href="/admin/feedback" class="btn btn-primary">View All</a>
-            </div>
-            <div class="card-body">
-                {}
-            </div>
-        </div>
-    </div>
-</body>
-</html>
-"#,
-        stats.total_users,
-        stats.total_projects,
-        stats.total_feedback,
-        stats.pending_feedback,
-        stats.completed_feedback,
-        stats.failed_feedback,
-        render_feedback_table(&recent_feedback),
-    ))
-    .into_response()
+/// 🔐 Middleware-like function to check auth and redirect if not logged in
+pub(crate) async fn require_admin_auth(jar: &CookieJar, app_state: &AppState) -> Option<Response> {
+    if !is_admin_authenticated(jar, app_state).await {
+        Some(Redirect::to("/admin/login").into_response())
+    } else {
+        None
+    }
 }
 
-/// 📝 Feedback Management Page
-pub async fn admin_feedback(State(app_state): State<AppState>, jar: CookieJar) -> Response {
-    if let Some(redirect) = require_admin_auth(&jar, &app_state) {
-        return redirect;
+/// 🔐 Same check for JSON admin APIs - answer 401 instead of redirecting to the login page
+pub(crate) async fn require_admin_api_auth(
+    jar: &CookieJar,
+    app_state: &AppState,
+) -> Option<Response> {
+    if !is_admin_authenticated(jar, app_state).await {
+        Some(crate::api::utils::unauthorized_error().into_response())
+    } else {
+        None
     }
-    info!("🔧 Admin feedback page accessed");
-
-    let feedback = get_recent_feedback(&app_state, 50)
-        .await
-        .unwrap_or_default();
-
-    Html(format!(r#"
-<!DOCTYPE html>
-<html lang="en">
-<head>
-    <meta charset="UTF-8">
-    <meta name="viewport" content="width=device-width, initial-scale=1.0">
-    <title>Feedback Management - Feedbacker Admin</title>
-    <style>
-        * {{ margin: 0; padding: 0; box-sizing: border-box; }}
-        body {{
-            font-family: -apple-system, BlinkMacSystemFont, 'Segoe UI', Roboto, sans-serif;
-            background: #0f0f23;
-            color: #cccccc;
-            min-height: 100vh;
-        }}
-        .sidebar {{
-            position: fixed;
-            left: 0;
-            top: 0;
-            width: 250px;
-            height: 100vh;
-            background: #1a1a2e;
-            padding: 20px;
-            border-right: 1px solid #333;
-        }}
-        .sidebar h1 {{ color: #00d4ff; font-size: 1.5em; margin-bottom: 30px; padding-bottom: 20px; border-bottom: 1px solid #333; }}
-        .sidebar nav a {{ display: block; color: #888; text-decoration: none; padding: 12px 15px; margin: 5px 0; border-radius: 8px; transition: all 0.2s; }}
-        .sidebar nav a:hover, .sidebar nav a.active {{ background: #252542; color: #00d4ff; }}
-        .main {{ margin-left: 250px; padding: 30px; }}
-        .header {{ display: flex; justify-content: space-between; align-items: center; margin-bottom: 30px; }}
-        .header h2 {{ color: #fff; font-size: 1.8em; }}
-        .card {{ background: #1a1a2e; border-radius: 12px; border: 1px solid #333; }}
-        .card-header {{ padding: 20px; border-bottom: 1px solid #333; }}
-        .card-body {{ padding: 20px; }}
-        table {{ width: 100%; border-collapse: collapse; }}
-        th, td {{ padding: 12px 15px; text-align: left; border-bottom: 1px solid #333; }}
-        th {{ color: #888; font-weight: 500; font-size: 0.85em; text-transform: uppercase; }}
-        .status {{ display: inline-block; padding: 4px 12px; border-radius: 20px; font-size: 0.85em; font-weight: 500; }}
-        .status-pending {{ background: #3d3d00; color: #ffaa00; }}
-        .status-completed {{ background: #003d00; color: #00ff88; }}
-        .status-failed {{ background: #3d0000; color: #ff4444; }}
-        .status-processing {{ background: #003d3d; color: #00d4ff; }}
-        .empty-state {{ text-align: center; padding: 40px; color: #666; }}
-    </style>
-</head>
-<body>
-    <div class="sidebar">
-        <h1>🚢 Feedbacker</h1>
-        <nav>
-            <a href="/admin">📊 Dashboard</a>
-            <a href="/admin/feedback" class="active">📝 Feedback</a>
-            <a href="/admin/projects">🏠 Projects</a>
-            <a href="/admin/users">👥 Users</a>
-            <a href="/admin/jobs">⚙️ Background Jobs</a>
-            <a href="/admin/mcp">🤖 MCP Analytics</a>
-            <a href="/admin/settings">🔧 Settings</a>
-            <a href="/">← Back to Site</a>
-            <a href="/admin/logout" style="margin-top: 30px; color: #ff4444;">🚪 Logout</a>
-        </nav>
-    </div>
-    <div class="main">
-        <div class="header">
-            <h2>📝 Feedback Management</h2>
-        </div>
-        <div class="card">
-            <div class="card-header">
-                <h3>All Feedback Submissions</h3>
-            </div>
-            <div class="card-body">
-                {}
-            </div>
-        </div>
-    </div>
-</body>
-</html>
-"#, render_feedback_table(&feedback))).into_response()
 }
 
-/// 🏠 Project item for listing
-#[derive(Debug, Serialize)]
-pub struct ProjectItem {
-    pub id: String,
-    pub repository: String,
-    pub description: Option<String>,
-    pub is_active: bool,
-    pub created_at: String,
-    pub feedback_count: i64,
+/// 📅 Time range the dashboard statistics cover
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum DashboardRange {
+    Day,
+    Week,
+    Month,
+    #[default]
+    All,
 }
 
-/// 🏠 Projects Management Page
-pub async fn admin_projects(State(app_state): State<AppState>, jar: CookieJar) -> Response {
-    if let Some(redirect) = require_admin_auth(&jar, &app_state) {
-        return redirect;
+impl DashboardRange {
+    /// 📚 Every range, in selector order
+    pub const ALL: [DashboardRange; 4] = [
+        DashboardRange::Day,
+        DashboardRange::Week,
+        DashboardRange::Month,
+        DashboardRange::All,
+    ];
+
+    /// 🔍 Parse the `?range=` query value; anything unrecognised means all time
+    pub fn from_param(value: Option<&str>) -> Self {
+        Self::ALL
+            .into_iter()
+            .find(|range| Some(range.as_param()) == value)
+            .unwrap_or_default()
     }
-    info!("🔧 Admin projects page accessed");
 
-    let projects = get_all_projects(&app_state).await.unwrap_or_default();
+    /// 🏷️ Value used in the query string
+    pub fn as_param(&self) -> &'static str {
+        match self {
+            DashboardRange::Day => "24h",
+            DashboardRange::Week => "7d",
+            DashboardRange::Month => "30d",
+            DashboardRange::All => "all",
+        }
+    }
 
-    Html(format!(r#"
-<!DOCTYPE html>
-<html lang="en">
-<head>
-    <meta charset="UTF-8">
-    <meta name="viewport" content="width=device-width, initial-scale=1.0">
-    <title>Projects - Feedbacker Admin</title>
-    <style>
-        * {{ margin: 0; padding: 0; box-sizing: border-box; }}
-        body {{ font-family: -apple-system, BlinkMacSystemFont, 'Segoe UI', Roboto, sans-serif; background: #0f0f23; color: #cccccc; min-height: 100vh; }}
-        .sidebar {{ position: fixed; left: 0; top: 0; width: 250px; height: 100vh; background: #1a1a2e; padding: 20px; border-right: 1px solid #333; }}
-        .sidebar h1 {{ color: #00d4ff; font-size: 1.5em; margin-bottom: 30px; padding-bottom: 20px; border-bottom: 1px solid #333; }}
-        .sidebar nav a {{ display: block; color: #888; text-decoration: none; padding: 12px 15px; margin: 5px 0; border-radius: 8px; transition: all 0.2s; }}
-        .sidebar nav a:hover, .sidebar nav a.active {{ background: #252542; color: #00d4ff; }}
-        .main {{ margin-left: 250px; padding: 30px; }}
-        .header {{ display: flex; justify-content: space-between; align-items: center; margin-bottom: 30px; }}
-        .header h2 {{ color: #fff; font-size: 1.8em; }}
-        .card {{ background: #1a1a2e; border-radius: 12px; border: 1px solid #333; margin-bottom: 20px; }}
-        .card-header {{ padding: 20px; border-bottom: 1px solid #333; display: flex; justify-content: space-between; align-items: center; }}
-        .card-header h3 {{ color: #fff; }}
-        .card-body {{ padding: 20px; }}
-        table {{ width: 100%; border-collapse: collapse; }}
-        th, td {{ padding: 12px 15px; text-align: left; border-bottom: 1px solid #333; }}
-        th {{ color: #888; font-weight: 500; font-size: 0.85em; text-transform: uppercase; }}
-        .form-group {{ margin-bottom: 15px; }}
-        .form-group label {{ display: block; margin-bottom: 8px; color: #888; }}
-        .form-group input, .form-group textarea {{ width: 100%; padding: 10px; background: #0f0f23; border: 1px solid #333; border-radius: 8px; color: #fff; font-family: inherit; }}
-        .form-group textarea {{ resize: vertical; min-height: 80px; }}
-        .btn {{ padding: 10px 20px; background: #00d4ff; color: #000; border: none; border-radius: 8px; cursor: pointer; font-weight: 600; }}
-        .btn:hover {{ background: #00a8cc; }}
-        .status {{ display: inline-block; padding: 4px 12px; border-radius: 20px; font-size: 0.85em; font-weight: 500; }}
-        .status-active {{ background: #003d00; color: #00ff88; }}
-        .status-inactive {{ background: #3d0000; color: #ff4444; }}
-        .empty-state {{ text-align: center; padding: 40px; color: #666; }}
-        .quick-add {{ display: flex; gap: 10px; margin-top: 15px; flex-wrap: wrap; }}
-        .quick-add button {{ padding: 8px 16px; background: #252542; color: #00d4ff; border: 1px solid #00d4ff; border-radius: 8px; cursor: pointer; font-size: 0.9em; }}
-        .quick-add button:hover {{ background: #00d4ff; color: #000; }}
-    </style>
-</head>
-<body>
-    <div class="sidebar">
-        <h1>🚢 Feedbacker</h1>
-        <nav>
-            <a href="/admin">📊 Dashboard</a>
-            <a href="/admin/feedback">📝 Feedback</a>
-            <a href="/admin/projects" class="active">🏠 Projects</a>
-            <a href="/admin/users">👥 Users</a>
-            <a href="/admin/jobs">⚙️ Background Jobs</a>
-            <a href="/admin/mcp">🤖 MCP Analytics</a>
-            <a href="/admin/settings">🔧 Settings</a>
-            <a href="/">← Back to Site</a>
-            <a href="/admin/logout" style="margin-top: 30px; color: #ff4444;">🚪 Logout</a>
-        </nav>
-    </div>
-    <div class="main">
-        <div class="header">
-            <h2>🏠 Projects Management</h2>
-        </div>
+    /// 📝 Human readable label
+    pub fn label(&self) -> &'static str {
+        match self {
+            DashboardRange::Day => "Last 24 hours",
+            DashboardRange::Week => "Last 7 days",
+            DashboardRange::Month => "Last 30 days",
+            DashboardRange::All => "All time",
+        }
+    }
 
-        <div class="card">
-            <div class="card-header">
-                <h3>➕ Add New Project</h3>
-            </div>
-            <div class="card-body">
-                <form method="POST" action="/admin/projects/add">
-                    <div class="form-group">
-                        <label for="repository">Repository (owner/repo format)</label>
-                        <input type="text" id="repository" name="repository" placeholder="8b-is/smart-tree" required>
-                    </div>
-                    <div class="form-group">
-                        <label for="description">Description</label>
-                        <textarea id="description" name="description" placeholder="Project description..."></textarea>
-                    </div>
-                    <button type="submit" class="btn">Add Project</button>
-                </form>
-                <div class="quick-add">
-                    <span style="color: #888; line-height: 36px;">Quick add:</span>
-                    <form method="POST" action="/admin/projects/add" style="display: inline;">
-                        <input type="hidden" name="repository" value="8b-is/smart-tree">
-                        <input type="hidden" name="description" value="Smart Tree - AI-optimized filesystem navigation MCP server">
-                        <button type="submit">🌲 Smart Tree</button>
-                    </form>
-                    <form method="POST" action="/admin/projects/add" style="display: inline;">
-                        <input type="hidden" name="repository" value="8b-is/feedbacker">
-                        <input type="hidden" name="description" value="Feedbacker - AI-Powered Repository Management Service">
-                        <button type="submit">🚢 Feedbacker</button>
-                    </form>
-                </div>
-            </div>
-        </div>
+    /// ⏰ Earliest `created_at` included in the range (inclusive), None for all time.
+    /// Truncated to microseconds so it compares exactly against Postgres timestamps.
+    pub fn cutoff(
+        &self,
+        now: chrono::DateTime<chrono::Utc>,
+    ) -> Option<chrono::DateTime<chrono::Utc>> {
+        use chrono::SubsecRound;
+        let span = match self {
+            DashboardRange::Day => chrono::Duration::hours(24),
+            DashboardRange::Week => chrono::Duration::days(7),
+            DashboardRange::Month => chrono::Duration::days(30),
+            DashboardRange::All => return None,
+        };
+        Some((now - span).trunc_subsecs(6))
+    }
 
-        <div class="card">
-            <div class="card-header">
-                <h3>📋 All Projects</h3>
-            </div>
-            <div class="card-body">
-                {}
-            </div>
-        </div>
-    </div>
-</body>
-</html>
-"#, render_projects_table(&projects))).into_response()
+    /// 🔗 Link to a ranged page, leaving the default range out of the URL
+    pub fn link(&self, path: &str) -> String {
+        match self {
+            DashboardRange::All => path.to_string(),
+            range => format!("{}?range={}", path, range.as_param()),
+        }
+    }
 }
 
-/// ➕ Add Project Form
-#[derive(Debug, Deserialize)]
-pub struct AddProjectForm {
-    pub repository: String,
-    pub description: Option<String>,
+/// 📅 `?range=` (and, on the feedback page, `?sort=`, `?dir=`, `?tag=`, `?source=`,
+/// `?status=`, `?repo=`, `?q=`, `?from=`, `?to=`, `?cursor=` and `?view=`) query parameters
+#[derive(Debug, Default, Deserialize)]
+pub struct DashboardQuery {
+    pub range: Option<String>,
+    pub sort: Option<String>,
+    pub dir: Option<String>,
+    pub tag: Option<String>,
+    pub source: Option<String>,
+    pub status: Option<String>,
+    pub repo: Option<String>,
+    pub q: Option<String>,
+    pub from: Option<String>,
+    pub to: Option<String>,
+    pub cursor: Option<String>,
+    /// 🔖 A saved view to open instead (see api::saved_views)
+    pub view: Option<uuid::Uuid>,
 }
 
-/// ➕ Add Project POST Handler
-pub async fn admin_projects_add(
-    State(app_state): State<AppState>,
-    jar: CookieJar,
-    Form(form): Form<AddProjectForm>,
-) -> Response {
-    if let Some(redirect) = require_admin_auth(&jar, &app_state) {
-        return redirect;
-    }
-    info!("➕ Adding project: {}", form.repository);
-
-    // Ensure system user exists
-    let system_user_id = get_or_create_system_user(&app_state).await;
+/// 🔎 Feedback page filters carried along by its links (already normalized)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct FeedbackFilter<'a> {
+    pub tag: Option<&'a str>,
+    pub source: Option<&'a str>,
+    pub status: Option<&'a str>,
+    /// 📦 Repository, compared case-insensitively
+    pub repository: Option<&'a str>,
+    /// 🔍 Text the feedback content contains (case-insensitive)
+    pub search: Option<&'a str>,
+    /// 📅 First and last day (UTC, both included) of the creation window
+    pub from: Option<chrono::NaiveDate>,
+    pub to: Option<chrono::NaiveDate>,
+}
 
-    if let Some(user_id) = system_user_id {
-        // Create the project
-        let result = sqlx::query(
-            r#"
-            INSERT INTO projects (owner_id, repository, description, is_active, created_at, updated_at)
-            VALUES ($1, $2, $3, true, NOW(), NOW())
-            ON CONFLICT (owner_id, repository) DO UPDATE SET
-                description = COALESCE($3, projects.description),
-                updated_at = NOW()
-            "#
+impl FeedbackFilter<'_> {
+    /// ⏰ The creation window as `BETWEEN` bounds: the start of `from` and the last
+    /// microsecond of `to`
+    fn created_between(
+        &self,
+    ) -> (
+        Option<chrono::DateTime<chrono::Utc>>,
+        Option<chrono::DateTime<chrono::Utc>>,
+    ) {
+        let start = |day: chrono::NaiveDate| day.and_hms_opt(0, 0, 0).map(|t| t.and_utc());
+        (
+            self.from.and_then(start),
+            self.to
+                .and_then(|day| Some(start(day.succ_opt()?)? - chrono::Duration::microseconds(1))),
         )
-        .bind(user_id)
-        .bind(&form.repository)
-        .bind(&form.description)
-        .execute(&app_state.db_pool)
-        .await;
+    }
+}
 
-        match result {
-            Ok(_) => info!("✅ Project {} added successfully", form.repository),
-            Err(e) => warn!("❌ Failed to add project: {}", e),
-        }
+/// 📅 `?from=` and `?to=` as days (`YYYY-MM-DD`); unreadable dates are ignored and a
+/// reversed pair is swapped
+fn date_window(
+    from: Option<&str>,
+    to: Option<&str>,
+) -> (Option<chrono::NaiveDate>, Option<chrono::NaiveDate>) {
+    let day =
+        |value: Option<&str>| chrono::NaiveDate::parse_from_str(value?.trim(), "%Y-%m-%d").ok();
+    match (day(from), day(to)) {
+        (Some(from), Some(to)) if from > to => (Some(to), Some(from)),
+        window => window,
     }
+}
 
-    Redirect::to("/admin/projects").into_response()
+/// ⚡ Date window shortcuts on the feedback page, each ending today (UTC)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DatePreset {
+    Today,
+    Week,
+    Month,
 }
 
-/// 🤖 Get or create system user for admin-created projects
-async fn get_or_create_system_user(app_state: &AppState) -> Option<uuid::Uuid> {
-    // Try to find existing system user
-    let existing: Option<uuid::Uuid> =
-        sqlx::query_scalar("SELECT id FROM users WHERE email = 'system@feedbacker.local'")
-            .fetch_optional(&app_state.db_pool)
-            .await
-            .ok()
-            .flatten();
+impl DatePreset {
+    /// 📚 Every preset, in button order
+    pub const ALL: [DatePreset; 3] = [DatePreset::Today, DatePreset::Week, DatePreset::Month];
 
-    if let Some(id) = existing {
-        return Some(id);
+    /// 🏷️ Button label
+    pub fn label(&self) -> &'static str {
+        match self {
+            DatePreset::Today => "today",
+            DatePreset::Week => "7d",
+            DatePreset::Month => "30d",
+        }
     }
 
-    // Create system user
-    let result = sqlx::query_scalar::<_, uuid::Uuid>(
-        r#"
-        INSERT INTO users (email, name, password_hash, email_verified, role, is_active)
-        VALUES ('system@feedbacker.local', 'System', 'not-a-real-hash', true, 'service', true)
-        RETURNING id
-        "#,
-    )
-    .fetch_one(&app_state.db_pool)
-    .await;
-
-    match result {
-        Ok(id) => {
-            info!("✅ Created system user with ID: {}", id);
-            Some(id)
-        }
-        Err(e) => {
-            warn!("❌ Failed to create system user: {}", e);
-            None
-        }
+    /// 📅 First and last day of the preset's window
+    pub fn window(&self, today: chrono::NaiveDate) -> (chrono::NaiveDate, chrono::NaiveDate) {
+        let days = match self {
+            DatePreset::Today => 0,
+            DatePreset::Week => 6,
+            DatePreset::Month => 29,
+        };
+        (today - chrono::Duration::days(days), today)
     }
 }
 
-/// 📋 Get all projects from database
-async fn get_all_projects(app_state: &AppState) -> anyhow::Result<Vec<ProjectItem>> {
-    let rows = sqlx::query(
-        r#"
-        SELECT
-            p.id, p.repository, p.description, p.is_active, p.created_at,
-            COALESCE((SELECT COUNT(*) FROM feedback f WHERE f.repository = p.repository), 0) as feedback_count
-        FROM projects p
-        ORDER BY p.created_at DESC
-        "#
-    )
-    .fetch_all(&app_state.db_pool)
-    .await?;
+/// 📋 `?status=` as a status this build knows (anything else is ignored)
+fn status_filter(value: Option<&str>) -> Option<FeedbackStatus> {
+    value
+        .map(FeedbackStatus::from_db)
+        .filter(|status| !matches!(status, FeedbackStatus::Unknown(_)))
+}
 
-    let items = rows
-        .iter()
-        .map(|row| ProjectItem {
-            id: row.get::<uuid::Uuid, _>("id").to_string(),
-            repository: row.get("repository"),
-            description: row.get("description"),
-            is_active: row.get("is_active"),
-            created_at: row
-                .get::<chrono::DateTime<chrono::Utc>, _>("created_at")
-                .format("%Y-%m-%d %H:%M")
-                .to_string(),
-            feedback_count: row.get("feedback_count"),
-        })
-        .collect();
+/// 🔍 Longest `?q=` searched for; the rest is ignored
+const SEARCH_MAX_CHARS: usize = 200;
 
-    Ok(items)
+/// 🔍 `?repo=` and `?q=` trimmed, blank ones ignored, the search cut to SEARCH_MAX_CHARS
+fn text_filter(value: Option<&str>) -> Option<&str> {
+    let value = value?.trim();
+    let end = value
+        .char_indices()
+        .nth(SEARCH_MAX_CHARS)
+        .map_or(value.len(), |(end, _)| end);
+    Some(value[..end].trim_end()).filter(|value| !value.is_empty())
 }
 
-/// 📋 Render projects table
-fn render_projects_table(projects: &[ProjectItem]) -> String {
-    if projects.is_empty() {
-        return r#"<div class="empty-state">📋 No projects yet. Add one above!</div>"#.to_string();
+/// ↕️ Column the admin feedback list is ordered by (the whitelist behind `?sort=`)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum FeedbackSort {
+    #[default]
+    Created,
+    /// 🔝 Queue order: highest priority first, oldest first within a priority
+    Priority,
+    Status,
+    Repository,
+    /// 👍 Most voted first
+    Votes,
+}
+
+/// ↕️ Direction of the feedback list ordering (`?dir=`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SortDir {
+    Asc,
+    Desc,
+}
+
+impl SortDir {
+    /// 🔍 Parse `?dir=`; anything unrecognised means the column's default direction
+    pub fn from_param(value: Option<&str>, default: SortDir) -> Self {
+        match value {
+            Some("asc") => SortDir::Asc,
+            Some("desc") => SortDir::Desc,
+            _ => default,
+        }
     }
 
-    let rows: String = projects
-        .iter()
-        .map(|p| {
-            let status_class = if p.is_active { "status-active" } else { "status-inactive" };
-            let status_text = if p.is_active { "Active" } else { "Inactive" };
-            format!(
-                r#"<tr>
-                    <td><a href="https://github.com/{}" target="_blank" style="color: #00d4ff;">{}</a></td>
-                    <td>{}</td>
-                    <td><span class="status {}">{}</span></td>
-                    <td>{}</td>
-                    <td>{}</td>
-                </tr>"#,
-                p.repository,
-                p.repository,
-                p.description.as_deref().unwrap_or("-"),
-                status_class,
-                status_text,
-                p.feedback_count,
-                p.created_at,
-            )
-        })
-        .collect();
+    /// 🏷️ Value used in the query string
+    pub fn as_param(&self) -> &'static str {
+        match self {
+            SortDir::Asc => "asc",
+            SortDir::Desc => "desc",
+        }
+    }
 
-    format!(
-        r#"<table>
-            <thead>
-                <tr>
-                    <th>Repository</th>
-                    <th>Description</th>
-                    <th>Status</th>
-                    <th>Feedback</th>
-                    <th>Created</th>
-                </tr>
-            </thead>
-            <tbody>{}</tbody>
-        </table>"#,
-        rows
-    )
+    /// 🔄 The other direction (what clicking the active header switches to)
+    pub fn reversed(&self) -> Self {
+        match self {
+            SortDir::Asc => SortDir::Desc,
+            SortDir::Desc => SortDir::Asc,
+        }
+    }
 }
 
-/// 👥 Users Management Page
-pub async fn admin_users(State(app_state): State<AppState>, jar: CookieJar) -> Response {
-    if let Some(redirect) = require_admin_auth(&jar, &app_state) {
-        return redirect;
+impl FeedbackSort {
+    /// 📚 Every sortable column
+    pub const ALL: [FeedbackSort; 5] = [
+        FeedbackSort::Created,
+        FeedbackSort::Priority,
+        FeedbackSort::Status,
+        FeedbackSort::Repository,
+        FeedbackSort::Votes,
+    ];
+
+    /// 🔍 Parse `?sort=`; anything not on the whitelist falls back to created_at
+    pub fn from_param(value: Option<&str>) -> Self {
+        Self::ALL
+            .into_iter()
+            .find(|sort| Some(sort.as_param()) == value)
+            .unwrap_or_default()
     }
-    info!("🔧 Admin users page accessed");
 
-    Html(r#"
-<!DOCTYPE html>
-<html lang="en">
-<head>
-    <meta charset="UTF-8">
-    <meta name="viewport" content="width=device-width, initial-scale=1.0">
-    <title>Users - Feedbacker Admin</title>
-    <style>
-        * { margin: 0; padding: 0; box-sizing: border-box; }
-        body { font-family: -apple-system, BlinkMacSystemFont, 'Segoe UI', Roboto, sans-serif; background: #0f0f23; color: #cccccc; min-height: 100vh; }
-        .sidebar { position: fixed; left: 0; top: 0; width: 250px; height: 100vh; background: #1a1a2e; padding: 20px; border-right: 1px solid #333; }
-        .sidebar h1 { color: #00d4ff; font-size: 1.5em; margin-bottom: 30px; padding-bottom: 20px; border-bottom: 1px solid #333; }
-        .sidebar nav a { display: block; color: #888; text-decoration: none; padding: 12px 15px; margin: 5px 0; border-radius: 8px; transition: all 0.2s; }
-        .sidebar nav a:hover, .sidebar nav a.active { background: #252542; color: #00d4ff; }
-        .main { margin-left: 250px; padding: 30px; }
-        .header { display: flex; justify-content: space-between; align-items: center; margin-bottom: 30px; }
-        .header h2 { color: #fff; font-size: 1.8em; }
-        .card { background: #1a1a2e; border-radius: 12px; border: 1px solid #333; padding: 40px; text-align: center; }
-        .card p { color: #666; margin-top: 10px; }
-    </style>
-</head>
-<body>
-    <div class="sidebar">
-        <h1>🚢 Feedbacker</h1>
-        <nav>
-            <a href="/admin">📊 Dashboard</a>
-            <a href="/admin/feedback">📝 Feedback</a>
-            <a href="/admin/projects">🏠 Projects</a>
-            <a href="/admin/users" class="active">👥 Users</a>
-            <a href="/admin/jobs">⚙️ Background Jobs</a>
-            <a href="/admin/mcp">🤖 MCP Analytics</a>
-            <a href="/admin/settings">🔧 Settings</a>
-            <a href="/">← Back to Site</a>
-            <a href="/admin/logout" style="margin-top: 30px; color: #ff4444;">🚪 Logout</a>
-        </nav>
-    </div>
-    <div class="main">
-        <div class="header">
-            <h2>👥 User Management</h2>
-        </div>
-        <div class="card">
-            <h3>👤 No users yet</h3>
-            <p>Users will appear here when they register.</p>
-        </div>
-    </div>
-</body>
-</html>
-"#).into_response()
-}
+    /// 🔍 Parse `?sort=` and `?dir=` together: an unknown column means created_at desc
+    /// whatever the direction says
+    pub fn from_query(sort: Option<&str>, dir: Option<&str>) -> (Self, SortDir) {
+        let column = Self::from_param(sort);
+        if Some(column.as_param()) != sort {
+            return (FeedbackSort::Created, SortDir::Desc);
+        }
+        (column, SortDir::from_param(dir, column.default_dir()))
+    }
 
-/// ⚙️ Background Jobs Page
-pub async fn admin_jobs(State(app_state): State<AppState>, jar: CookieJar) -> Response {
-    if let Some(redirect) = require_admin_auth(&jar, &app_state) {
-        return redirect;
+    /// 🏷️ Value used in the query string
+    pub fn as_param(&self) -> &'static str {
+        match self {
+            FeedbackSort::Created => "created_at",
+            FeedbackSort::Priority => "priority",
+            FeedbackSort::Status => "status",
+            FeedbackSort::Repository => "repository",
+            FeedbackSort::Votes => "votes",
+        }
     }
-    info!("🔧 Admin jobs page accessed");
 
-    Html(r#"
-<!DOCTYPE html>
-<html lang="en">
-<head>
-    <meta charset="UTF-8">
-    <meta name="viewport" content="width=device-width, initial-scale=1.0">
-    <title>Background Jobs - Feedbacker Admin</title>
-    <style>
-        * { margin: 0; padding: 0; box-sizing: border-box; }
-        body { font-family: -apple-system, BlinkMacSystemFont, 'Segoe UI', Roboto, sans-serif; background: #0f0f23; color: #cccccc; min-height: 100vh; }
-        .sidebar { position: fixed; left: 0; top: 0; width: 250px; height: 100vh; background: #1a1a2e; padding: 20px; border-right: 1px solid #333; }
-        .sidebar h1 { color: #00d4ff; font-size: 1.5em; margin-bottom: 30px; padding-bottom: 20px; border-bottom: 1px solid #333; }
-        .sidebar nav a { display: block; color: #888; text-decoration: none; padding: 12px 15px; margin: 5px 0; border-radius: 8px; transition: all 0.2s; }
-        .sidebar nav a:hover, .sidebar nav a.active { background: #252542; color: #00d4ff; }
-        .main { margin-left: 250px; padding: 30px; }
-        .header { display: flex; justify-content: space-between; align-items: center; margin-bottom: 30px; }
-        .header h2 { color: #fff; font-size: 1.8em; }
-        .card { background: #1a1a2e; border-radius: 12px; border: 1px solid #333; padding: 40px; text-align: center; }
-        .card p { color: #666; margin-top: 10px; }
-    </style>
-</head>
-<body>
-    <div class="sidebar">
-        <h1>🚢 Feedbacker</h1>
-        <nav>
-            <a href="/admin">📊 Dashboard</a>
-            <a href="/admin/feedback">📝 Feedback</a>
-            <a href="/admin/projects">🏠 Projects</a>
-            <a href="/admin/users">👥 Users</a>
-            <a href="/admin/jobs" class="active">⚙️ Background Jobs</a>
-            <a href="/admin/mcp">🤖 MCP Analytics</a>
-            <a href="/admin/settings">🔧 Settings</a>
-            <a href="/">← Back to Site</a>
-            <a href="/admin/logout" style="margin-top: 30px; color: #ff4444;">🚪 Logout</a>
-        </nav>
-    </div>
-    <div class="main">
-        <div class="header">
-            <h2>⚙️ Background Jobs</h2>
-        </div>
-        <div class="card">
-            <h3>🔄 No jobs running</h3>
-            <p>Background jobs will appear here when processing feedback.</p>
-        </div>
-    </div>
-</body>
-</html>
-"#).into_response()
-}
+    /// ⬇️ Direction a header click starts with: newest, most urgent and most voted
+    /// first, names A-Z
+    pub fn default_dir(&self) -> SortDir {
+        match self {
+            FeedbackSort::Created | FeedbackSort::Priority | FeedbackSort::Votes => SortDir::Desc,
+            FeedbackSort::Status | FeedbackSort::Repository => SortDir::Asc,
+        }
+    }
 
-/// 🔧 Settings Page
-pub async fn admin_settings(State(app_state): State<AppState>, jar: CookieJar) -> Response {
-    if let Some(redirect) = require_admin_auth(&jar, &app_state) {
-        return redirect;
+    /// 🗄️ ORDER BY clause (id breaks ties so the order is stable). Only these fixed
+    /// strings ever reach the SQL, never the query parameter itself.
+    fn order_by(&self, dir: SortDir) -> &'static str {
+        match (self, dir) {
+            (FeedbackSort::Created, SortDir::Desc) => "created_at DESC, id DESC",
+            (FeedbackSort::Created, SortDir::Asc) => "created_at ASC, id ASC",
+            (FeedbackSort::Priority, SortDir::Desc) => "priority DESC, created_at ASC, id ASC",
+            (FeedbackSort::Priority, SortDir::Asc) => "priority ASC, created_at DESC, id DESC",
+            (FeedbackSort::Status, SortDir::Asc) => "status ASC, created_at DESC, id DESC",
+            (FeedbackSort::Status, SortDir::Desc) => "status DESC, created_at DESC, id DESC",
+            (FeedbackSort::Repository, SortDir::Asc) => "repository ASC, created_at DESC, id DESC",
+            (FeedbackSort::Repository, SortDir::Desc) => {
+                "repository DESC, created_at DESC, id DESC"
+            }
+            (FeedbackSort::Votes, SortDir::Desc) => "votes DESC, created_at DESC, id DESC",
+            (FeedbackSort::Votes, SortDir::Asc) => "votes ASC, created_at DESC, id DESC",
+        }
     }
-    info!("🔧 Admin settings page accessed");
 
-    Html(format!(r#"
-<!DOCTYPE html>
-<html lang="en">
-<head>
-    <meta charset="UTF-8">
-    <meta name="viewport" content="width=device-width, initial-scale=1.0">
-    <title>Settings - Feedbacker Admin</title>
-    <style>
-        * {{ margin: 0; padding: 0; box-sizing: border-box; }}
-        body {{ font-family: -apple-system, BlinkMacSystemFont, 'Segoe UI', Roboto, sans-serif; background: #0f0f23; color: #cccccc; min-height: 100vh; }}
-        .sidebar {{ position: fixed; left: 0; top: 0; width: 250px; height: 100vh; background: #1a1a2e; padding: 20px; border-right: 1px solid #333; }}
-        .sidebar h1 {{ color: #00d4ff; font-size: 1.5em; margin-bottom: 30px; padding-bottom: 20px; border-bottom: 1px solid #333; }}
-        .sidebar nav a {{ display: block; color: #888; text-decoration: none; padding: 12px 15px; margin: 5px 0; border-radius: 8px; transition: all 0.2s; }}
-        .sidebar nav a:hover, .sidebar nav a.active {{ background: #252542; color: #00d4ff; }}
-        .main {{ margin-left: 250px; padding: 30px; }}
-        .header {{ display: flex; justify-content: space-between; align-items: center; margin-bottom: 30px; }}
-        .header h2 {{ color: #fff; font-size: 1.8em; }}
-        .card {{ background: #1a1a2e; border-radius: 12px; border: 1px solid #333; margin-bottom: 20px; }}
-        .card-header {{ padding: 20px; border-bottom: 1px solid #333; }}
-        .card-header h3 {{ color: #fff; }}
-        .card-body {{ padding: 20px; }}
-        .setting-row {{ display: flex; justify-content: space-between; align-items: center; padding: 15px 0; border-bottom: 1px solid #333; }}
-        .setting-row:last-child {{ border-bottom: none; }}
-        .setting-label {{ color: #fff; }}
-        .setting-value {{ color: #00d4ff; font-family: monospace; }}
-        .setting-status {{ padding: 4px 12px; border-radius: 20px; font-size: 0.85em; }}
-        .status-ok {{ background: #003d00; color: #00ff88; }}
-        .status-warn {{ background: #3d3d00; color: #ffaa00; }}
-    </style>
-</head>
-<body>
-    <div class="sidebar">
-        <h1>🚢 Feedbacker</h1>
-        <nav>
-            <a href="/admin">📊 Dashboard</a>
-            <a href="/admin/feedback">📝 Feedback</a>
-            <a href="/admin/projects">🏠 Projects</a>
-            <a href="/admin/users">👥 Users</a>
-            <a href="/admin/jobs">⚙️ Background Jobs</a>
-            <a href="/admin/mcp">🤖 MCP Analytics</a>
-            <a href="/admin/settings" class="active">🔧 Settings</a>
-            <a href="/">← Back to Site</a>
-            <a href="/admin/logout" style="margin-top: 30px; color: #ff4444;">🚪 Logout</a>
-        </nav>
-    </div>
-    <div class="main">
-        <div class="header">
-            <h2>🔧 Settings</h2>
-        </div>
+    /// 🔑 Column compared first when resuming after a cursor (cast back from text)
+    fn key_column(&self) -> &'static str {
+        match self {
+            FeedbackSort::Created => "created_at",
+            FeedbackSort::Priority => "priority",
+            FeedbackSort::Status => "status",
+            FeedbackSort::Repository => "repository",
+            FeedbackSort::Votes => "votes",
+        }
+    }
 
-        <div class="card">
-            <div class="card-header">
-                <h3>🐙 GitHub Integration</h3>
-            </div>
-            <div class="card-body">
-                <div class="setting-row">
-                    <span class="setting-label">GitHub Username</span>
-                    <span class="setting-value">{}</span>
-                </div>
-                <div class="setting-row">
-                    <span class="setting-label">GitHub Token</span>
-                    <span class="setting-status status-ok">✓ Configured</span>
-                </div>
-            </div>
-        </div>
+    /// 📍 Keyset condition for rows after the cursor ($5 sort key, $6 created_at, $7 id),
+    /// mirroring `order_by` exactly. Fixed strings again, one per ordering.
+    fn keyset(&self, dir: SortDir) -> &'static str {
+        match (self, dir) {
+            (FeedbackSort::Created, SortDir::Desc) => {
+                "(created_at < $5::timestamptz OR (created_at = $5::timestamptz AND (created_at, id) < ($6, $7::uuid)))"
+            }
+            (FeedbackSort::Created, SortDir::Asc) => {
+                "(created_at > $5::timestamptz OR (created_at = $5::timestamptz AND (created_at, id) > ($6, $7::uuid)))"
+            }
+            (FeedbackSort::Priority, SortDir::Desc) => {
+                "(priority < $5::int OR (priority = $5::int AND (created_at, id) > ($6, $7::uuid)))"
+            }
+            (FeedbackSort::Priority, SortDir::Asc) => {
+                "(priority > $5::int OR (priority = $5::int AND (created_at, id) < ($6, $7::uuid)))"
+            }
+            (FeedbackSort::Status, SortDir::Asc) => {
+                "(status > $5::feedback_status OR (status = $5::feedback_status AND (created_at, id) < ($6, $7::uuid)))"
+            }
+            (FeedbackSort::Status, SortDir::Desc) => {
+                "(status < $5::feedback_status OR (status = $5::feedback_status AND (created_at, id) < ($6, $7::uuid)))"
+            }
+            (FeedbackSort::Repository, SortDir::Asc) => {
+                "(repository > $5::text OR (repository = $5::text AND (created_at, id) < ($6, $7::uuid)))"
+            }
+            (FeedbackSort::Repository, SortDir::Desc) => {
+                "(repository < $5::text OR (repository = $5::text AND (created_at, id) < ($6, $7::uuid)))"
+            }
+            (FeedbackSort::Votes, SortDir::Desc) => {
+                "(votes < $5::bigint OR (votes = $5::bigint AND (created_at, id) < ($6, $7::uuid)))"
+            }
+            (FeedbackSort::Votes, SortDir::Asc) => {
+                "(votes > $5::bigint OR (votes = $5::bigint AND (created_at, id) < ($6, $7::uuid)))"
+            }
+        }
+    }
 
-        <div class="card">
-            <div class="card-header">
-                <h3>🤖 LLM Providers</h3>
-            </div>
-            <div class="card-body">
-                <div class="setting-row">
-                    <span class="setting-label">OpenAI</span>
-                    <span class="setting-status {}">{}</span>
-                </div>
-                <div class="setting-row">
-                    <span class="setting-label">Anthropic</span>
-                    <span class="setting-status {}">{}</span>
-                </div>
-                <div class="setting-row">
-                    <span class="setting-label">Default Provider</span>
-                    <span class="setting-value">{:?}</span>
-                </div>
-            </div>
-        </div>
+    /// 🔗 Feedback page link for this sort, keeping the selected range
+    pub fn link(&self, range: DashboardRange) -> String {
+        self.link_tagged(range, None)
+    }
 
-        <div class="card">
-            <div class="card-header">
-                <h3>🚦 Rate Limiting</h3>
-            </div>
-            <div class="card-body">
-                <div class="setting-row">
-                    <span class="setting-label">Requests per Minute</span>
-                    <span class="setting-value">{}</span>
-                </div>
-                <div class="setting-row">
-                    <span class="setting-label">Feedback per Hour</span>
-                    <span class="setting-value">{}</span>
-                </div>
-            </div>
-        </div>
-    </div>
-</body>
-</html>
-"#,
-        app_state.config.github.username,
-        if app_state.config.llm.openai.is_some() { "status-ok" } else { "status-warn" },
-        if app_state.config.llm.openai.is_some() { "✓ Configured" } else { "⚠ Not configured" },
-        if app_state.config.llm.anthropic.is_some() { "status-ok" } else { "status-warn" },
-        if app_state.config.llm.anthropic.is_some() { "✓ Configured" } else { "⚠ Not configured" },
-        app_state.config.llm.default_provider,
-        app_state.config.rate_limiting.requests_per_minute,
-        app_state.config.rate_limiting.feedback_per_hour,
-    )).into_response()
+    /// 🔗 Same, also keeping a `?tag=` filter
+    pub fn link_tagged(&self, range: DashboardRange, tag: Option<&str>) -> String {
+        self.link_filtered(
+            range,
+            FeedbackFilter {
+                tag,
+                ..FeedbackFilter::default()
+            },
+        )
+    }
+
+    /// 🔗 Same, keeping every filter
+    pub fn link_filtered(&self, range: DashboardRange, filter: FeedbackFilter) -> String {
+        self.link_directed(self.default_dir(), range, filter)
+    }
+
+    /// 🔗 Same, in an explicit direction (left out of the URL when it is the default)
+    pub fn link_directed(
+        &self,
+        dir: SortDir,
+        range: DashboardRange,
+        filter: FeedbackFilter,
+    ) -> String {
+        let mut link = range.link("/admin/feedback");
+        let mut push = |param: String| {
+            link.push(if link.contains('?') { '&' } else { '?' });
+            link.push_str(&param);
+        };
+        if *self != FeedbackSort::Created {
+            push(format!("sort={}", self.as_param()));
+        }
+        if dir != self.default_dir() {
+            push(format!("dir={}", dir.as_param()));
+        }
+        if let Some(tag) = filter.tag {
+            push(format!("tag={}", crate::api::tags::encode_query_value(tag)));
+        }
+        if let Some(source) = filter.source {
+            push(format!(
+                "source={}",
+                crate::api::tags::encode_query_value(source)
+            ));
+        }
+        if let Some(status) = filter.status {
+            push(format!(
+                "status={}",
+                crate::api::tags::encode_query_value(status)
+            ));
+        }
+        if let Some(repository) = filter.repository {
+            push(format!(
+                "repo={}",
+                crate::api::tags::encode_query_value(repository)
+            ));
+        }
+        if let Some(search) = filter.search {
+            push(format!(
+                "q={}",
+                crate::api::tags::encode_query_value(search)
+            ));
+        }
+        if let Some(from) = filter.from {
+            push(format!("from={}", from));
+        }
+        if let Some(to) = filter.to {
+            push(format!("to={}", to));
+        }
+        link
+    }
 }
 
-/// 🤖 MCP Analytics Page
-pub async fn admin_mcp(State(app_state): State<AppState>, jar: CookieJar) -> Response {
-    if let Some(redirect) = require_admin_auth(&jar, &app_state) {
-        return redirect;
+/// 🍪 Cookie holding the feedback table columns this admin has hidden
+pub const FEEDBACK_COLUMNS_COOKIE: &str = "feedbacker_feedback_columns";
+
+/// 📋 A column of the admin feedback table
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FeedbackColumn {
+    Id,
+    Repository,
+    Source,
+    Status,
+    Priority,
+    Votes,
+    Created,
+    Content,
+    Attachments,
+}
+
+impl FeedbackColumn {
+    /// 📚 Every column, in table order
+    pub const ALL: [FeedbackColumn; 9] = [
+        FeedbackColumn::Id,
+        FeedbackColumn::Repository,
+        FeedbackColumn::Source,
+        FeedbackColumn::Status,
+        FeedbackColumn::Priority,
+        FeedbackColumn::Votes,
+        FeedbackColumn::Created,
+        FeedbackColumn::Content,
+        FeedbackColumn::Attachments,
+    ];
+
+    /// 🏷️ Name used in the cookie and the column form
+    pub fn as_param(&self) -> &'static str {
+        match self {
+            FeedbackColumn::Id => "id",
+            FeedbackColumn::Repository => "repository",
+            FeedbackColumn::Source => "source",
+            FeedbackColumn::Status => "status",
+            FeedbackColumn::Priority => "priority",
+            FeedbackColumn::Votes => "votes",
+            FeedbackColumn::Created => "created",
+            FeedbackColumn::Content => "content",
+            FeedbackColumn::Attachments => "attachments",
+        }
     }
-    info!("🔧 Admin MCP page accessed");
 
-    let stats = get_mcp_stats(&app_state).await.unwrap_or_default();
-    let current_version = get_setting(&app_state, "smart_tree_latest_version")
-        .await
-        .unwrap_or_else(|| "Not set".to_string());
+    /// 📝 Header label
+    pub fn label(&self) -> &'static str {
+        match self {
+            FeedbackColumn::Id => "ID",
+            FeedbackColumn::Repository => "Repository",
+            FeedbackColumn::Source => "Source",
+            FeedbackColumn::Status => "Status",
+            FeedbackColumn::Priority => "Priority",
+            FeedbackColumn::Votes => "Votes",
+            FeedbackColumn::Created => "Created",
+            FeedbackColumn::Content => "Content",
+            FeedbackColumn::Attachments => "Attachments",
+        }
+    }
 
-    Html(format!(r#"
-<!DOCTYPE html>
-<html lang="en">
-<head>
-    <meta charset="UTF-8">
-    <meta name="viewport" content="width=device-width, initial-scale=1.0">
-    <title>MCP Analytics - Feedbacker Admin</title>
-    <style>
-        * {{ margin: 0; padding: 0; box-sizing: border-box; }}
-        body {{ font-family: -apple-system, BlinkMacSystemFont, 'Segoe UI', Roboto, sans-serif; background: #0f0f23; color: #cccccc; min-height: 100vh; }}
-        .sidebar {{ position: fixed; left: 0; top: 0; width: 250px; height: 100vh; background: #1a1a2e; padding: 20px; border-right: 1px solid #333; }}
-        .sidebar h1 {{ color: #00d4ff; font-size: 1.5em; margin-bottom: 30px; padding-bottom: 20px; border-bottom: 1px solid #333; }}
-        .sidebar nav a {{ display: block; color: #888; text-decoration: none; padding: 12px 15px; margin: 5px 0; border-radius: 8px; transition: all 0.2s; }}
-        .sidebar nav a:hover, .sidebar nav a.active {{ background: #252542; color: #00d4ff; }}
-        .main {{ margin-left: 250px; padding: 30px; }}
-        .header {{ display: flex; justify-content: space-between; align-items: center; margin-bottom: 30px; }}
-        .header h2 {{ color: #fff; font-size: 1.8em; }}
-        .stats-grid {{ display: grid; grid-template-columns: repeat(auto-fit, minmax(200px, 1fr)); gap: 20px; margin-bottom: 30px; }}
-        .stat-card {{ background: #1a1a2e; padding: 25px; border-radius: 12px; border: 1px solid #333; }}
-        .stat-card h3 {{ color: #888; font-size: 0.9em; margin-bottom: 10px; }}
-        .stat-card .value {{ font-size: 2.5em; font-weight: bold; color: #00d4ff; }}
-        .card {{ background: #1a1a2e; border-radius: 12px; border: 1px solid #333; margin-bottom: 20px; }}
-        .card-header {{ padding: 20px; border-bottom: 1px solid #333; display: flex; justify-content: space-between; align-items: center; }}
-        .card-header h3 {{ color: #fff; }}
-        .card-body {{ padding: 20px; }}
-        table {{ width: 100%; border-collapse: collapse; }}
-        th, td {{ padding: 12px 15px; text-align: left; border-bottom: 1px solid #333; }}
-        th {{ color: #888; font-weight: 500; font-size: 0.85em; text-transform: uppercase; }}
-        .form-group {{ margin-bottom: 15px; }}
-        .form-group label {{ display: block; margin-bottom: 8px; color: #888; }}
-        .form-group input {{ width: 100%; padding: 10px; background: #0f0f23; border: 1px solid #333; border-radius: 8px; color: #fff; }}
-        .btn {{ padding: 10px 20px; background: #00d4ff; color: #000; border: none; border-radius: 8px; cursor: pointer; font-weight: 600; }}
-        .btn:hover {{ background: #00a8cc; }}
-        .empty-state {{ text-align: center; padding: 40px; color: #666; }}
-    </style>
-</head>
-<body>
-    <div class="sidebar">
-        <h1>🚢 Feedbacker</h1>
-        <nav>
-            <a href="/admin">📊 Dashboard</a>
-            <a href="/admin/feedback">📝 Feedback</a>
-            <a href="/admin/projects">🏠 Projects</a>
-            <a href="/admin/users">👥 Users</a>
-            <a href="/admin/jobs">⚙️ Background Jobs</a>
-            <a href="/admin/mcp" class="active">🤖 MCP Analytics</a>
-            <a href="/admin/settings">🔧 Settings</a>
-            <a href="/">← Back to Site</a>
-            <a href="/admin/logout" style="margin-top: 30px; color: #ff4444;">🚪 Logout</a>
-        </nav>
-    </div>
-    <div class="main">
-        <div class="header">
-            <h2>🤖 MCP Analytics</h2>
-        </div>
+    /// ↕️ The sort a click on this header selects, if it is sortable
+    fn sort(&self) -> Option<FeedbackSort> {
+        match self {
+            FeedbackColumn::Id
+            | FeedbackColumn::Source
+            | FeedbackColumn::Content
+            | FeedbackColumn::Attachments => None,
+            FeedbackColumn::Repository => Some(FeedbackSort::Repository),
+            FeedbackColumn::Status => Some(FeedbackSort::Status),
+            FeedbackColumn::Priority => Some(FeedbackSort::Priority),
+            FeedbackColumn::Votes => Some(FeedbackSort::Votes),
+            FeedbackColumn::Created => Some(FeedbackSort::Created),
+        }
+    }
+}
 
-        <div class="stats-grid">
-            <div class="stat-card">
-                <h3>Total Checks</h3>
-                <div class="value">{}</div>
-            </div>
-            <div class="stat-card">
-                <h3>Current Version</h3>
-                <div class="value" style="font-size: 1.5em;">{}</div>
-            </div>
-        </div>
+/// 👁️ Feedback table columns an admin has hidden (new columns show up by default)
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct HiddenColumns(Vec<FeedbackColumn>);
 
-        <div class="card">
-            <div class="card-header">
-                <h3>🔧 Set Smart Tree Version</h3>
-            </div>
-            <div class="card-body">
-                <form method="POST" action="/admin/mcp/set-version">
-                    <div class="form-group">
-                        <label for="version">Version (e.g., 0.9.0)</label>
-                        <input type="text" id="version" name="version" placeholder="0.9.0" required>
-                    </div>
-                    <div class="form-group">
-                        <label for="release_notes">Release Notes</label>
-                        <input type="text" id="release_notes" name="release_notes" placeholder="New features and improvements...">
-                    </div>
-                    <button type="submit" class="btn">Update Version</button>
-                </form>
-            </div>
-        </div>
+impl HiddenColumns {
+    /// 🔍 Parse the cookie value ("content,id"); unknown names are ignored
+    pub fn from_cookie_value(value: &str) -> Self {
+        Self(
+            FeedbackColumn::ALL
+                .into_iter()
+                .filter(|column| {
+                    value
+                        .split(',')
+                        .any(|name| name.trim() == column.as_param())
+                })
+                .collect(),
+        )
+    }
 
-        <div class="card">
-            <div class="card-header">
-                <h3>📊 Platform Distribution</h3>
-            </div>
-            <div class="card-body">
-                {}
-            </div>
-        </div>
+    /// 🍪 Value stored in the cookie
+    pub fn to_cookie_value(&self) -> String {
+        self.0
+            .iter()
+            .map(FeedbackColumn::as_param)
+            .collect::<Vec<_>>()
+            .join(",")
+    }
 
-        <div class="card">
-            <div class="card-header">
-                <h3>📈 Version Distribution</h3>
-            </div>
-            <div class="card-body">
-                {}
-            </div>
-        </div>
+    /// 🍪 This admin's preference, nothing hidden without the cookie
+    pub fn from_jar(jar: &CookieJar) -> Self {
+        jar.get(FEEDBACK_COLUMNS_COOKIE)
+            .map(|cookie| Self::from_cookie_value(cookie.value()))
+            .unwrap_or_default()
+    }
 
-        <div class="card">
-            <div class="card-header">
-                <h3>🌍 Location Distribution</h3>
-            </div>
-            <div class="card-body">
-                {}
-            </div>
-        </div>
+    pub fn is_hidden(&self, column: FeedbackColumn) -> bool {
+        self.0.contains(&column)
+    }
+}
 
-        <div class="card">
-            <div class="card-header">
-                <h3>🕐 Recent Checks</h3>
-            </div>
-            <div class="card-body">
-                {}
-            </div>
-        </div>
-    </div>
-</body>
-</html>
-"#,
-        stats.total_checks,
-        current_version,
-        render_platform_table(&stats.platforms),
-        render_version_table(&stats.versions),
-        render_locations_table(&stats.locations),
-        render_recent_checks_table(&stats.recent_checks),
-    )).into_response()
+/// 🔗 Feedback page state the table's sort links carry along
+#[derive(Debug, Clone, Copy)]
+struct FeedbackListState<'a> {
+    range: DashboardRange,
+    sort: FeedbackSort,
+    dir: SortDir,
+    filter: FeedbackFilter<'a>,
+    /// ➡️ Where the next page starts, when there is one
+    next_cursor: Option<&'a str>,
 }
 
-/// 🔧 Set Smart Tree version (admin POST handler)
-#[derive(Debug, Deserialize)]
-pub struct SetVersionForm {
-    pub version: String,
-    pub release_notes: Option<String>,
+/// 📄 Rows the feedback page renders itself (the fallback when scripts are off)
+const FEEDBACK_PAGE_ROWS: i64 = 10;
+
+/// 📄 Rows per `/admin/api/feedback` page
+const FEEDBACK_API_PAGE_ROWS: i64 = 50;
+
+/// 📍 Position in a feedback list ordering: the last row's sort key and tie-breakers.
+/// Travels as opaque base64url JSON in `?cursor=`, tagged with the ordering it belongs to.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FeedbackCursor {
+    order: String,
+    key: String,
+    created_at: chrono::DateTime<chrono::Utc>,
+    id: uuid::Uuid,
 }
 
-pub async fn admin_mcp_set_version(
-    State(app_state): State<AppState>,
-    jar: CookieJar,
-    Form(form): Form<SetVersionForm>,
-) -> Response {
-    if let Some(redirect) = require_admin_auth(&jar, &app_state) {
-        return redirect;
+impl FeedbackCursor {
+    /// 🏷️ The ordering a cursor is valid for, like "priority:desc"
+    fn order(sort: FeedbackSort, dir: SortDir) -> String {
+        format!("{}:{}", sort.as_param(), dir.as_param())
     }
-    info!("🔧 Setting Smart Tree version to: {}", form.version);
 
-    // Save version to settings
-    let _ = set_setting(&app_state, "smart_tree_latest_version", &form.version).await;
-    if let Some(notes) = form.release_notes {
-        if !notes.is_empty() {
-            let _ = set_setting(&app_state, "smart_tree_release_notes", &notes).await;
-        }
+    /// 📦 Value for `?cursor=`
+    pub fn encode(&self) -> String {
+        use base64::Engine;
+        base64::engine::general_purpose::URL_SAFE_NO_PAD
+            .encode(serde_json::to_vec(self).unwrap_or_default())
     }
 
-    Redirect::to("/admin/mcp").into_response()
+    /// 🔍 Parse `?cursor=`; None when it is garbage or was made for another ordering
+    pub fn decode(value: &str, sort: FeedbackSort, dir: SortDir) -> Option<Self> {
+        use base64::Engine;
+        let bytes = base64::engine::general_purpose::URL_SAFE_NO_PAD
+            .decode(value)
+            .ok()?;
+        serde_json::from_slice::<Self>(&bytes)
+            .ok()
+            .filter(|cursor| cursor.order == Self::order(sort, dir))
+    }
 }
 
-// MCP Stats structures
+/// 🔗 `link` with `?cursor=` appended
+fn with_cursor(link: &str, cursor: &str) -> String {
+    format!(
+        "{}{}cursor={}",
+        link,
+        if link.contains('?') { '&' } else { '?' },
+        cursor
+    )
+}
+
+/// 📊 Dashboard statistics
+#[derive(Debug, Clone, Serialize)]
+pub struct DashboardStats {
+    pub total_users: i64,
+    pub total_projects: i64,
+    pub total_feedback: i64,
+    pub pending_feedback: i64,
+    pub completed_feedback: i64,
+    pub failed_feedback: i64,
+}
+
+/// 📦 Per-repository feedback counts for the selected range
+#[derive(Debug, Clone, Serialize)]
+pub struct RepositoryStats {
+    pub repository: String,
+    pub total_feedback: i64,
+    pub completed_feedback: i64,
+}
+
+impl RepositoryStats {
+    /// 📈 Share of feedback that ended in a completed PR, in percent
+    pub fn completion_rate(&self) -> f64 {
+        if self.total_feedback == 0 {
+            0.0
+        } else {
+            self.completed_feedback as f64 * 100.0 / self.total_feedback as f64
+        }
+    }
+}
+
+/// ⏱️ How long dashboard numbers are reused before hitting the database again
+const DASHBOARD_CACHE_TTL: std::time::Duration = std::time::Duration::from_secs(30);
+
+/// 🗃️ One cached dashboard computation: when it was stored, the counts and the repository table
+type CachedDashboard = (std::time::Instant, DashboardStats, Vec<RepositoryStats>);
+
+/// 📊 A fresh dashboard load: the counts and the repository table, each None on failure
+type DashboardLoad = (Option<DashboardStats>, Option<Vec<RepositoryStats>>);
+
+/// 🗃️ Short-lived cache of dashboard statistics, keyed by range
 #[derive(Debug, Default)]
-struct McpStats {
-    total_checks: i64,
-    platforms: Vec<(String, String, i64)>, // (platform, arch, count)
-    versions: Vec<(String, i64)>,          // (version, count)
-    locations: Vec<(String, String, i64)>, // (city, country, count)
-    recent_checks: Vec<RecentMcpCheck>,
+pub struct DashboardCache {
+    entries: std::sync::Mutex<std::collections::HashMap<DashboardRange, CachedDashboard>>,
+    /// 🧵 Loads in flight, so simultaneous misses for a range share one set of queries
+    loads: crate::utils::coalesce::Coalesce<DashboardRange, DashboardLoad>,
 }
 
-#[derive(Debug)]
-struct RecentMcpCheck {
-    version: String,
-    platform: String,
-    arch: String,
-    city: Option<String>,
-    country: Option<String>,
-    timestamp: String,
+impl DashboardCache {
+    fn get(&self, range: DashboardRange) -> Option<(DashboardStats, Vec<RepositoryStats>)> {
+        let entries = self.entries.lock().unwrap();
+        entries
+            .get(&range)
+            .filter(|(stored_at, _, _)| stored_at.elapsed() < DASHBOARD_CACHE_TTL)
+            .map(|(_, stats, repositories)| (stats.clone(), repositories.clone()))
+    }
+
+    fn put(
+        &self,
+        range: DashboardRange,
+        stats: DashboardStats,
+        repositories: Vec<RepositoryStats>,
+    ) {
+        self.entries
+            .lock()
+            .unwrap()
+            .insert(range, (std::time::Instant::now(), stats, repositories));
+    }
+
+    /// 🎯 Cached numbers for `range`, else `load` them (once, however many ask) and
+    /// cache them if both parts loaded
+    async fn get_or_load<Fut>(
+        &self,
+        range: DashboardRange,
+        load: impl FnOnce() -> Fut,
+    ) -> DashboardLoad
+    where
+        Fut: std::future::Future<Output = DashboardLoad>,
+    {
+        if let Some((stats, repositories)) = self.get(range) {
+            return (Some(stats), Some(repositories));
+        }
+        self.loads
+            .run(range, || async {
+                let (stats, repositories) = load().await;
+                if let (Some(stats), Some(repositories)) = (&stats, &repositories) {
+                    self.put(range, stats.clone(), repositories.clone());
+                }
+                (stats, repositories)
+            })
+            .await
+    }
 }
 
-async fn get_mcp_stats(app_state: &AppState) -> Option<McpStats> {
-    let total_checks: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM mcp_analytics")
-        .fetch_one(&app_state.db_pool)
-        .await
-        .unwrap_or(0);
+/// 📋 Feedback item for listing
+#[derive(Debug, Serialize)]
+pub struct FeedbackItem {
+    pub id: String,
+    pub repository: String,
+    pub source: String,
+    pub status: FeedbackStatus,
+    /// 🎨 Badge class for the status, so the table script needn't know the statuses
+    pub status_class: &'static str,
+    pub priority: i32,
+    /// 👍 Votes from signed-in users
+    pub votes: i64,
+    /// ⏰ Sent as "YYYY-MM-DD HH:MM" in UTC; the pages show it in the admin's time zone
+    #[serde(serialize_with = "serialize_minutes")]
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    pub content_preview: String,
+    /// 📎 (attachment id, filename), oldest first
+    pub attachments: Vec<(uuid::Uuid, String)>,
+}
 
-    let platform_rows = sqlx::query(
-        "SELECT platform, arch, COUNT(*) as count FROM mcp_analytics GROUP BY platform, arch ORDER BY count DESC LIMIT 20"
-    )
-    .fetch_all(&app_state.db_pool)
-    .await
-    .unwrap_or_default();
+/// 📄 One page of a feedback list
+#[derive(Debug, Default, Serialize)]
+pub struct FeedbackPage {
+    pub items: Vec<FeedbackItem>,
+    /// ➡️ `?cursor=` for the following page, None on the last one
+    pub next_cursor: Option<String>,
+}
 
-    let platforms: Vec<(String, String, i64)> = platform_rows
-        .iter()
-        .map(|row| (row.get("platform"), row.get("arch"), row.get("count")))
-        .collect();
+/// 🏠 Admin Dashboard
+pub async fn admin_dashboard(
+    State(app_state): State<AppState>,
+    Query(query): Query<DashboardQuery>,
+    jar: CookieJar,
+) -> Response {
+    if let Some(redirect) = require_admin_auth(&jar, &app_state).await {
+        return redirect;
+    }
+    info!("🔧 Admin dashboard accessed");
+    let tz = admin_timezone(&app_state, &jar).await;
 
-    let version_rows = sqlx::query(
-        "SELECT client_version, COUNT(*) as count FROM mcp_analytics GROUP BY client_version ORDER BY count DESC LIMIT 20"
-    )
-    .fetch_all(&app_state.db_pool)
-    .await
-    .unwrap_or_default();
+    let range = DashboardRange::from_param(query.range.as_deref());
+    let since = range.cutoff(chrono::Utc::now());
 
-    let versions: Vec<(String, i64)> = version_rows
-        .iter()
-        .map(|row| (row.get("client_version"), row.get("count")))
-        .collect();
+    let (stats, top_repositories) = app_state
+        .dashboard_cache
+        .get_or_load(range, || async {
+            (
+                get_dashboard_stats(&app_state, since).await.ok(),
+                get_top_repositories(&app_state, since, 10).await.ok(),
+            )
+        })
+        .await;
+    let stats = stats.unwrap_or(DashboardStats {
+        total_users: 0,
+        total_projects: 0,
+        total_feedback: 0,
+        pending_feedback: 0,
+        completed_feedback: 0,
+        failed_feedback: 0,
+    });
+    let top_repositories = top_repositories.unwrap_or_default();
 
-    // Location distribution
-    let location_rows = sqlx::query(
-        r#"
-        SELECT COALESCE(city, 'Unknown') as city, COALESCE(country, 'Unknown') as country, COUNT(*) as count
-        FROM mcp_analytics
-        WHERE city IS NOT NULL OR country IS NOT NULL
-        GROUP BY city, country
-        ORDER BY count DESC
-        LIMIT 20
-        "#
+    let recent_feedback = get_recent_feedback(
+        &app_state,
+        10,
+        since,
+        (FeedbackSort::Created, SortDir::Desc),
+        FeedbackFilter::default(),
+        None,
     )
-    .fetch_all(&app_state.db_pool)
     .await
-    .unwrap_or_default();
+    .unwrap_or_default()
+    .items;
+    let top_tags = crate::api::tags::top_tags(&app_state, since, 30)
+        .await
+        .unwrap_or_else(|e| {
+            warn!("⚠️ Failed to load tag statistics: {:#}", e);
+            Vec::new()
+        });
+    let sources = crate::api::sources::source_counts(&app_state, since)
+        .await
+        .unwrap_or_else(|e| {
+            warn!("⚠️ Failed to load source statistics: {:#}", e);
+            Vec::new()
+        });
+    let (history, history_counted_at) = crate::api::stats_history::stats_history_through_today(
+        &app_state.db_pool,
+        crate::api::stats_history::DEFAULT_HISTORY_DAYS,
+    )
+    .await
+    .unwrap_or_else(|e| {
+        warn!("⚠️ Failed to load statistics history: {:#}", e);
+        (Vec::new(), None)
+    });
+
+    let style = label_style(&app_state, &jar);
+    Html(render_admin_page_ranged(
+        "Admin Dashboard - Feedbacker",
+        "/admin",
+        &format!(
+            r#"
+    <div class="header">
+        <h2>{heading}</h2>
+        {}
+    </div>
+
+    <div class="stats-grid">
+        <div class="stat-card">
+            <h3>New Users</h3>
+            <div class="value">{}</div>
+        </div>
+        <div class="stat-card">
+            <h3>Total Projects</h3>
+            <div class="value">{}</div>
+        </div>
+        <div class="stat-card">
+            <h3>Feedback</h3>
+            <div class="value">{}</div>
+        </div>
+        <div class="stat-card warning">
+            <h3>Pending</h3>
+            <div class="value">{}</div>
+        </div>
+        <div class="stat-card success">
+            <h3>Completed</h3>
+            <div class="value">{}</div>
+        </div>
+        <div class="stat-card danger">
+            <h3>Failed</h3>
+            <div class="value">{}</div>
+        </div>
+    </div>
+
+    <div class="card">
+        <div class="card-header">
+            <h3>{trend_heading}</h3>
+            <a href="/admin/api/stats/history" class="muted">JSON</a>
+        </div>
+        <div class="card-body">
+            {}{}
+        </div>
+    </div>
+
+    <div class="card">
+        <div class="card-header">
+            <h3>{repositories_heading}</h3>
+        </div>
+        <div class="card-body">
+            {}
+        </div>
+    </div>
+
+    <div class="card">
+        <div class="card-header">
+            <h3>{tags_heading}</h3>
+        </div>
+        <div class="card-body">
+            {}
+        </div>
+    </div>
+
+    <div class="card">
+        <div class="card-header">
+            <h3>{sources_heading}</h3>
+        </div>
+        <div class="card-body">
+            {}
+        </div>
+    </div>
+
+    <div class="card">
+        <div class="card-header">
+            <h3>{recent_heading}</h3>
+            <a href="{}" class="btn btn-primary">View All</a>
+        </div>
+        <div class="card-body">
+            {}
+        </div>
+    </div>
+"#,
+            render_range_selector("/admin", range),
+            stats.total_users,
+            stats.total_projects,
+            stats.total_feedback,
+            stats.pending_feedback,
+            stats.completed_feedback,
+            stats.failed_feedback,
+            crate::api::stats_history::render_trend_chart(&history, style),
+            history_counted_at
+                .map(|at| format!(
+                    r#"<div class="muted trend-freshness">Today as of {}</div>"#,
+                    fmt_ts(at, &tz)
+                ))
+                .unwrap_or_default(),
+            render_repository_table(&top_repositories, style),
+            render_tag_cloud(&top_tags, range, style),
+            render_source_breakdown(&sources, range, style),
+            range.link("/admin/feedback"),
+            render_feedback_table(
+                &recent_feedback,
+                None,
+                &HiddenColumns::from_jar(&jar),
+                &tz,
+                style,
+            ),
+            heading = label_html("📊 Dashboard", style),
+            trend_heading = label_html("📈 Last 90 Days", style),
+            repositories_heading = label_html("📦 Top Repositories", style),
+            tags_heading = label_html("🏷️ Top Tags", style),
+            sources_heading = label_html("📡 Sources", style),
+            recent_heading = label_html("📝 Recent Feedback", style),
+        ),
+        range,
+        style,
+    ))
+    .into_response()
+}
+
+/// 📭 Placeholder for a section with nothing to show
+fn empty_state(label: &str, style: LabelStyle) -> String {
+    format!(
+        r#"<div class="empty-state">{}</div>"#,
+        label_html(label, style)
+    )
+}
+
+/// 📅 Range selector links; the query string carries the choice between pages
+fn render_range_selector(path: &str, selected: DashboardRange) -> String {
+    let links: String = DashboardRange::ALL
+        .iter()
+        .map(|range| {
+            let class = if *range == selected { " active" } else { "" };
+            format!(
+                r#"<a href="{}" class="range-option{}">{}</a>"#,
+                range.link(path),
+                class,
+                range.as_param()
+            )
+        })
+        .collect();
+    format!(
+        r#"<div class="range-selector"><span class="muted">{}</span>{}</div>"#,
+        selected.label(),
+        links
+    )
+}
+
+/// 📅 Creation date filter of the feedback page: preset buttons and a from/to form,
+/// both keeping the sort and every other filter
+fn render_date_filter(
+    (sort, dir): (FeedbackSort, SortDir),
+    range: DashboardRange,
+    filter: FeedbackFilter,
+    today: chrono::NaiveDate,
+) -> String {
+    let presets: String = DatePreset::ALL
+        .iter()
+        .map(|preset| {
+            let (from, to) = preset.window(today);
+            let class = if (filter.from, filter.to) == (Some(from), Some(to)) {
+                " active"
+            } else {
+                ""
+            };
+            format!(
+                r#"<a href="{}" class="range-option{}">{}</a>"#,
+                html_escape(&sort.link_directed(
+                    dir,
+                    range,
+                    FeedbackFilter {
+                        from: Some(from),
+                        to: Some(to),
+                        ..filter
+                    }
+                )),
+                class,
+                preset.label()
+            )
+        })
+        .collect();
+
+    // 🧷 The form replaces the query string, so everything else rides along hidden
+    let mut kept: Vec<(&str, String)> = Vec::new();
+    if range != DashboardRange::All {
+        kept.push(("range", range.as_param().to_string()));
+    }
+    if sort != FeedbackSort::Created {
+        kept.push(("sort", sort.as_param().to_string()));
+    }
+    if dir != sort.default_dir() {
+        kept.push(("dir", dir.as_param().to_string()));
+    }
+    for (name, value) in [
+        ("tag", filter.tag),
+        ("source", filter.source),
+        ("status", filter.status),
+        ("repo", filter.repository),
+    ] {
+        if let Some(value) = value {
+            kept.push((name, value.to_string()));
+        }
+    }
+    let hidden: String = kept
+        .iter()
+        .map(|(name, value)| {
+            format!(
+                r#"<input type="hidden" name="{}" value="{}">"#,
+                name,
+                html_escape(value)
+            )
+        })
+        .collect();
+    let day = |day: Option<chrono::NaiveDate>| day.map(|day| day.to_string()).unwrap_or_default();
+
+    format!(
+        r#"<div class="date-filter"><span class="muted">Created</span>{}<form method="get" action="/admin/feedback">{}<input type="date" name="from" value="{}" aria-label="From"><input type="date" name="to" value="{}" aria-label="To"><input type="search" name="q" value="{}" placeholder="Search feedback" maxlength="{}" aria-label="Search"><button type="submit" class="btn">Apply</button></form></div>"#,
+        presets,
+        hidden,
+        day(filter.from),
+        day(filter.to),
+        html_escape(filter.search.unwrap_or_default()),
+        SEARCH_MAX_CHARS
+    )
+}
+
+/// 📦 Top repositories table with completion rates
+fn render_repository_table(repositories: &[RepositoryStats], style: LabelStyle) -> String {
+    if repositories.is_empty() {
+        return empty_state("📭 No feedback in this range", style);
+    }
+
+    let rows: String = repositories
+        .iter()
+        .map(|r| {
+            format!(
+                r#"<tr>
+                    <td>{}</td>
+                    <td>{}</td>
+                    <td>{}</td>
+                    <td>{:.0}%</td>
+                </tr>"#,
+                r.repository,
+                r.total_feedback,
+                r.completed_feedback,
+                r.completion_rate(),
+            )
+        })
+        .collect();
+
+    format!(
+        r#"<table>
+            <thead>
+                <tr>
+                    <th>Repository</th>
+                    <th>Feedback</th>
+                    <th>Completed</th>
+                    <th>Completion Rate</th>
+                </tr>
+            </thead>
+            <tbody>{}</tbody>
+        </table>"#,
+        rows
+    )
+}
+
+/// 🏷️ Tag cloud: bigger chips for more used tags, each linking to the filtered feedback list
+fn render_tag_cloud(
+    tags: &[crate::api::tags::TagCount],
+    range: DashboardRange,
+    style: LabelStyle,
+) -> String {
+    let Some(max) = tags.iter().map(|t| t.count).max() else {
+        return empty_state("🏷️ No tagged feedback in this range", style);
+    };
+
+    let chips: String = tags
+        .iter()
+        .map(|t| {
+            let size = 0.85 + 0.75 * t.count as f64 / max as f64;
+            format!(
+                r#"<a href="{}" class="tag-chip" style="font-size: {:.2}em" title="{} feedback">{} <span class="muted">{}</span></a>"#,
+                html_escape(&FeedbackSort::Created.link_tagged(range, Some(&t.tag))),
+                size,
+                t.count,
+                html_escape(&t.tag),
+                t.count
+            )
+        })
+        .collect();
+    format!(r#"<div class="tag-cloud">{}</div>"#, chips)
+}
+
+/// 📡 Feedback per source, each linking to the filtered feedback list
+fn render_source_breakdown(
+    sources: &[crate::api::sources::SourceCount],
+    range: DashboardRange,
+    style: LabelStyle,
+) -> String {
+    let total: i64 = sources.iter().map(|s| s.count).sum();
+    if total == 0 {
+        return empty_state("📡 No feedback in this range", style);
+    }
+
+    let rows: String = sources
+        .iter()
+        .map(|s| {
+            let filter = FeedbackFilter {
+                source: Some(&s.source),
+                ..FeedbackFilter::default()
+            };
+            format!(
+                r#"<tr>
+                    <td><a href="{}" class="tag-chip">{}</a></td>
+                    <td>{}</td>
+                    <td>{:.0}%</td>
+                </tr>"#,
+                html_escape(&FeedbackSort::Created.link_filtered(range, filter)),
+                html_escape(&s.source),
+                s.count,
+                s.count as f64 * 100.0 / total as f64,
+            )
+        })
+        .collect();
+
+    format!(
+        r#"<table>
+            <thead>
+                <tr>
+                    <th>Source</th>
+                    <th>Feedback</th>
+                    <th>Share</th>
+                </tr>
+            </thead>
+            <tbody>{}</tbody>
+        </table>"#,
+        rows
+    )
+}
+
+/// 📝 Feedback Management Page
+pub async fn admin_feedback(
+    State(app_state): State<AppState>,
+    Query(query): Query<DashboardQuery>,
+    jar: CookieJar,
+) -> Response {
+    if let Some(redirect) = require_admin_auth(&jar, &app_state).await {
+        return redirect;
+    }
+    if let Some(view_id) = query.view {
+        return Redirect::to(&crate::api::saved_views::view_link(&jar, &app_state, view_id).await)
+            .into_response();
+    }
+    info!("🔧 Admin feedback page accessed");
+    let tz = admin_timezone(&app_state, &jar).await;
+
+    let range = DashboardRange::from_param(query.range.as_deref());
+    let (sort, dir) = FeedbackSort::from_query(query.sort.as_deref(), query.dir.as_deref());
+    let tag = query
+        .tag
+        .as_deref()
+        .and_then(crate::api::tags::normalize_tag);
+    let source = query
+        .source
+        .as_deref()
+        .and_then(crate::api::sources::normalize_source);
+    let status = status_filter(query.status.as_deref());
+    let (from, to) = date_window(query.from.as_deref(), query.to.as_deref());
+    let filter = FeedbackFilter {
+        tag: tag.as_deref(),
+        source: source.as_deref(),
+        status: status.as_ref().map(FeedbackStatus::as_str),
+        repository: text_filter(query.repo.as_deref()),
+        search: text_filter(query.q.as_deref()),
+        from,
+        to,
+    };
+    // 🤷 A stale or mangled cursor just starts from the top again
+    let after = query
+        .cursor
+        .as_deref()
+        .and_then(|cursor| FeedbackCursor::decode(cursor, sort, dir));
+    let page = get_recent_feedback(
+        &app_state,
+        FEEDBACK_PAGE_ROWS,
+        range.cutoff(chrono::Utc::now()),
+        (sort, dir),
+        filter,
+        after.as_ref(),
+    )
+    .await
+    .unwrap_or_default();
+
+    // 🔎 One chip per active filter, each clearing only itself
+    let style = label_style(&app_state, &jar);
+    let clear = label_html("✖ clear", style);
+    let mut chips = Vec::new();
+    if let Some(tag) = filter.tag {
+        chips.push(format!(
+            r#"tagged <span class="tag-chip">{}</span> <a href="{}" class="muted">{clear}</a>"#,
+            html_escape(tag),
+            html_escape(&sort.link_directed(
+                dir,
+                range,
+                FeedbackFilter {
+                    tag: None,
+                    ..filter
+                }
+            ))
+        ));
+    }
+    if let Some(source) = filter.source {
+        chips.push(format!(
+            r#"from <span class="tag-chip">{}</span> <a href="{}" class="muted">{clear}</a>"#,
+            html_escape(source),
+            html_escape(&sort.link_directed(
+                dir,
+                range,
+                FeedbackFilter {
+                    source: None,
+                    ..filter
+                }
+            ))
+        ));
+    }
+    if let Some(status) = filter.status {
+        chips.push(format!(
+            r#"that are <span class="tag-chip">{}</span> <a href="{}" class="muted">{clear}</a>"#,
+            html_escape(status),
+            html_escape(&sort.link_directed(
+                dir,
+                range,
+                FeedbackFilter {
+                    status: None,
+                    ..filter
+                }
+            ))
+        ));
+    }
+    if let Some(repository) = filter.repository {
+        chips.push(format!(
+            r#"in <span class="tag-chip">{}</span> <a href="{}" class="muted">{clear}</a>"#,
+            html_escape(repository),
+            html_escape(&sort.link_directed(
+                dir,
+                range,
+                FeedbackFilter {
+                    repository: None,
+                    ..filter
+                }
+            ))
+        ));
+    }
+    if let Some(search) = filter.search {
+        chips.push(format!(
+            r#"mentioning <span class="tag-chip">{}</span> <a href="{}" class="muted">{clear}</a>"#,
+            html_escape(search),
+            html_escape(&sort.link_directed(
+                dir,
+                range,
+                FeedbackFilter {
+                    search: None,
+                    ..filter
+                }
+            ))
+        ));
+    }
+    if filter.from.is_some() || filter.to.is_some() {
+        let day = |day: chrono::NaiveDate| format!(r#"<span class="tag-chip">{}</span>"#, day);
+        let window = match (filter.from, filter.to) {
+            (Some(from), Some(to)) if from == to => format!("on {}", day(from)),
+            (Some(from), Some(to)) => format!("between {} and {}", day(from), day(to)),
+            (Some(from), None) => format!("since {}", day(from)),
+            (None, Some(to)) => format!("until {}", day(to)),
+            (None, None) => String::new(),
+        };
+        chips.push(format!(
+            r#"created {} <a href="{}" class="muted">{clear}</a>"#,
+            window,
+            html_escape(&sort.link_directed(
+                dir,
+                range,
+                FeedbackFilter {
+                    from: None,
+                    to: None,
+                    ..filter
+                }
+            ))
+        ));
+    }
+    let heading = if chips.is_empty() {
+        "All Feedback Submissions".to_string()
+    } else {
+        format!("Feedback {}", chips.join(" "))
+    };
+    // ✋ Held changes are the ones somebody has to act on, so they get a shortcut
+    let awaiting_link = if filter.status.is_none() {
+        format!(
+            r#"<a href="{}" class="btn">{label}</a>"#,
+            html_escape(&sort.link_directed(
+                dir,
+                range,
+                FeedbackFilter {
+                    status: Some(FeedbackStatus::AwaitingApproval.as_str()),
+                    ..filter
+                }
+            )),
+            label = label_html("✋ Awaiting approval", style),
+        )
+    } else {
+        String::new()
+    };
+    let hidden = HiddenColumns::from_jar(&jar);
+    let return_to = sort.link_directed(dir, range, filter);
+    let views = crate::api::saved_views::views_for(&jar, &app_state).await;
+
+    Html(render_admin_page_ranged(
+        "Feedback Management - Feedbacker Admin",
+        "/admin/feedback",
+        &format!(
+            r#"
+    <div class="header">
+        <h2>{page_heading}</h2>
+        {}
+    </div>
+    <div class="card">
+        <div class="card-header">
+            <h3>{}</h3>
+            {}
+            {}
+            {}
+        </div>
+        <div class="card-body">
+            {}
+            {}
+        </div>
+    </div>
+"#,
+            render_range_selector("/admin/feedback", range),
+            heading,
+            awaiting_link,
+            crate::api::saved_views::render_view_picker(&views, &return_to),
+            render_column_picker(&hidden, &return_to),
+            render_date_filter((sort, dir), range, filter, chrono::Utc::now().date_naive()),
+            render_feedback_table(
+                &page.items,
+                Some(FeedbackListState {
+                    range,
+                    sort,
+                    dir,
+                    filter,
+                    next_cursor: page.next_cursor.as_deref(),
+                }),
+                &hidden,
+                &tz,
+                style,
+            ),
+            page_heading = label_html("📝 Feedback Management", style),
+        ),
+        range,
+        style,
+    ))
+    .into_response()
+}
+
+/// 🔍 GET /admin/feedback/:id - one feedback item, with its generated changes shown
+/// as diffs and, while held changes wait for a decision, the approve/reject buttons
+pub async fn admin_feedback_detail(
+    State(app_state): State<AppState>,
+    Path(feedback_id): Path<uuid::Uuid>,
+    jar: CookieJar,
+) -> Response {
+    if let Some(redirect) = require_admin_auth(&jar, &app_state).await {
+        return redirect;
+    }
+    let style = label_style(&app_state, &jar);
+    let tz = admin_timezone(&app_state, &jar).await;
+    let pool = &app_state.db_pool;
+    let feedback = match Feedback::find_by_id(pool, feedback_id).await {
+        Ok(Some(feedback)) => feedback,
+        Ok(None) => {
+            return (
+                StatusCode::NOT_FOUND,
+                Html(render_admin_page(
+                    "Feedback not found - Feedbacker Admin",
+                    "/admin/feedback",
+                    &empty_state("🤷 No such feedback", style),
+                    style,
+                )),
+            )
+                .into_response()
+        }
+        Err(e) => {
+            warn!("❌ Failed to load feedback {}: {:#}", feedback_id, e);
+            return (StatusCode::INTERNAL_SERVER_ERROR, "Failed to load feedback").into_response();
+        }
+    };
+    let approval = PendingApproval::find(pool, feedback_id)
+        .await
+        .inspect_err(|e| warn!("⚠️ Failed to load approval for {}: {:#}", feedback_id, e))
+        .ok()
+        .flatten();
+    let reception = feedback_votes::reception(pool, feedback_id)
+        .await
+        .inspect_err(|e| warn!("⚠️ Failed to count votes for {}: {:#}", feedback_id, e))
+        .unwrap_or_default();
+    let duplicate_of = feedback_votes::duplicate_of(pool, feedback_id)
+        .await
+        .inspect_err(|e| warn!("⚠️ Failed to load duplicate of {}: {:#}", feedback_id, e))
+        .ok()
+        .flatten();
+
+    let optional_row = |label: &str, value: Option<String>| {
+        value
+            .map(|value| format!("<tr><th>{}</th><td>{}</td></tr>", label, value))
+            .unwrap_or_default()
+    };
+    let details = format!(
+        r#"<table class="details">
+                <tr><th>ID</th><td><code>{}</code></td></tr>
+                <tr><th>Source</th><td>{}</td></tr>
+                <tr><th>Priority</th><td>{}</td></tr>
+                <tr><th>Created</th><td>{}</td></tr>
+                <tr><th>Votes</th><td>{} · also reported by {}</td></tr>
+                {}{}{}{}
+            </table>
+            <p class="feedback-content">{}</p>
+            <form method="POST" action="/admin/feedback/{}/duplicate" class="inline-form">
+                <input type="text" name="of" value="{}" placeholder="Duplicate of (feedback ID, blank for none)">
+                <button type="submit" class="btn">{save}</button>
+            </form>"#,
+        feedback.id,
+        html_escape(&feedback.source),
+        feedback.priority,
+        fmt_ts(feedback.created_at, &tz),
+        reception.votes,
+        reception.also_reported_by,
+        optional_row(
+            "Duplicate of",
+            duplicate_of.map(|original| format!(
+                r#"<a href="/admin/feedback/{0}"><code>{0}</code></a>"#,
+                original
+            ))
+        ),
+        optional_row(
+            "Pull request",
+            feedback.pull_request_url.as_deref().map(|url| format!(
+                r#"<a href="{0}" target="_blank" class="repo-link">{0}</a>"#,
+                html_escape(url)
+            ))
+        ),
+        optional_row(
+            "Branch",
+            feedback
+                .branch_name
+                .as_deref()
+                .map(|branch| format!("<code>{}</code>", html_escape(branch)))
+        ),
+        optional_row("Error", feedback.error_message.as_deref().map(html_escape)),
+        html_escape(&feedback.content),
+        feedback.id,
+        duplicate_of
+            .map(|original| original.to_string())
+            .unwrap_or_default(),
+        save = label_html("👯 Save", style),
+    );
+
+    Html(render_admin_page(
+        "Feedback - Feedbacker Admin",
+        "/admin/feedback",
+        &format!(
+            r#"
+    <div class="header">
+        <h2>{heading} <code>{}</code></h2>
+        <a href="/admin/feedback" class="muted">← All feedback</a>
+    </div>
+    <div class="card">
+        <div class="card-header">
+            <h3>{}</h3>
+            <span class="status {}">{}</span>
+        </div>
+        <div class="card-body">
+            {}
+        </div>
+    </div>
+    {}
+"#,
+            &feedback_id.to_string()[..8],
+            html_escape(&feedback.repository),
+            feedback.status.css_class(),
+            feedback.status,
+            details,
+            render_changes(&feedback, approval.as_ref(), &tz, style),
+            heading = label_html("📝 Feedback", style),
+        ),
+        style,
+    ))
+    .into_response()
+}
+
+/// 📐 A file's diff starts collapsed when it runs longer than this many lines
+const COLLAPSED_DIFF_LINES: usize = 200;
+
+/// 🧩 A feedback item's proposed changes as per-file diffs, with the approval state
+/// and, while they wait for a decision, the approve/reject buttons
+fn render_changes(
+    feedback: &Feedback,
+    approval: Option<&PendingApproval>,
+    tz: &TimeZone,
+    style: LabelStyle,
+) -> String {
+    // 🗄️ Changes held before patches were recorded only have their raw contents
+    let Some(patches) = patch::stored_patch(feedback.metadata.as_ref()).or_else(|| {
+        approval.map(|approval| {
+            approval
+                .changes
+                .iter()
+                .map(FilePatch::from_change)
+                .collect()
+        })
+    }) else {
+        return String::new();
+    };
+    let (state, actions) = approval
+        .map(|approval| approval_state(feedback, approval, tz, style))
+        .unwrap_or_default();
+    let files: String = patches.iter().map(render_file_patch).collect();
+    format!(
+        r#"
+    <div class="card">
+        <div class="card-header">
+            <h3>{}</h3>
+            {}
+        </div>
+        <div class="card-body">
+            {}
+            {}
+        </div>
+    </div>
+"#,
+        label_html("🧩 Proposed changes", style),
+        state,
+        actions,
+        files
+    )
+}
+
+/// ✋ Either the buttons or what was decided on held changes
+fn approval_state(
+    feedback: &Feedback,
+    approval: &PendingApproval,
+    tz: &TimeZone,
+    style: LabelStyle,
+) -> (String, String) {
+    match (&approval.decision, &approval.decided_at) {
+        (Some(decision), decided_at) => (
+            format!(
+                r#"<span class="status {}">{}{}</span>"#,
+                if decision == Decision::Approved.as_str() {
+                    "status-completed"
+                } else {
+                    "status-failed"
+                },
+                html_escape(decision),
+                decided_at
+                    .map(|at| format!(" {}", fmt_ts(at, tz)))
+                    .unwrap_or_default()
+            ),
+            String::new(),
+        ),
+        (None, _) => (
+            format!(
+                r#"<span class="status status-pending">waiting until {}</span>"#,
+                fmt_ts(approval.expires_at, tz)
+            ),
+            if feedback.status == FeedbackStatus::AwaitingApproval {
+                format!(
+                    r#"<div class="approval-actions">
+                <form method="POST" action="/admin/feedback/{0}/approve"><button type="submit" class="btn btn-primary">{1}</button></form>
+                <form method="POST" action="/admin/feedback/{0}/reject"><button type="submit" class="btn btn-danger">{2}</button></form>
+            </div>"#,
+                    feedback.id,
+                    label_html("✅ Approve and open the PR", style),
+                    label_html("🚫 Reject", style),
+                )
+            } else {
+                String::new()
+            },
+        ),
+    }
+}
+
+/// 📄 One file's unified diff, highlighted line by line and collapsed when long
+fn render_file_patch(patch: &FilePatch) -> String {
+    let kind = match patch.change_type {
+        ChangeType::Create => "new file",
+        ChangeType::Modify => "modified",
+        ChangeType::Append => "appended to",
+        ChangeType::Delete => "deleted",
+    };
+    let language = Language::from_path(&patch.file_path);
+    let mut in_header = true;
+    let body: String = patch
+        .diff
+        .lines()
+        .map(|line| {
+            let (class, content) = if line.starts_with("@@") {
+                in_header = false;
+                ("diff-hunk", html_escape(line))
+            } else if in_header {
+                ("diff-meta", html_escape(line))
+            } else {
+                // 🖍️ The +/-/space marker stays plain, the code after it is highlighted
+                let (sign, code) = line.split_at(line.chars().next().map_or(0, char::len_utf8));
+                let class = match sign {
+                    "+" => "diff-add",
+                    "-" => "diff-del",
+                    _ => "diff-context",
+                };
+                (
+                    class,
+                    format!("{}{}", html_escape(sign), highlight_line(code, language)),
+                )
+            };
+            format!(
+                r#"<span class="{}">{}</span>
+"#,
+                class, content
+            )
+        })
+        .collect();
+    let lines = patch.diff.lines().count();
+    let collapsed = lines > COLLAPSED_DIFF_LINES;
+    format!(
+        r#"<details class="diff-file"{}>
+                <summary class="diff-header"><code>{}</code> <span class="diff-add">+{}</span> <span class="diff-del">-{}</span> <span class="muted">{} - {}{}</span></summary>
+                <pre class="diff">{}</pre>
+            </details>"#,
+        if collapsed { "" } else { " open" },
+        html_escape(&patch.file_path),
+        patch.additions,
+        patch.deletions,
+        kind,
+        html_escape(&patch.description),
+        if collapsed {
+            format!(" ({} lines, click to expand)", lines)
+        } else {
+            String::new()
+        },
+        body
+    )
+}
+
+/// 👯 Mark Duplicate Form
+#[derive(Debug, Deserialize)]
+pub struct DuplicateForm {
+    /// 🆔 The earlier report (blank unmarks)
+    #[serde(default)]
+    pub of: String,
+}
+
+/// 👯 POST /admin/feedback/:id/duplicate - mark a feedback item as a repeat of an
+/// earlier report (it then counts in that report's PR body), or unmark it
+pub async fn admin_feedback_duplicate(
+    State(app_state): State<AppState>,
+    Path(feedback_id): Path<uuid::Uuid>,
+    jar: CookieJar,
+    Form(form): Form<DuplicateForm>,
+) -> Response {
+    if let Some(redirect) = require_admin_auth(&jar, &app_state).await {
+        return redirect;
+    }
+    let back = Redirect::to(&format!("/admin/feedback/{}", feedback_id)).into_response();
+    let original = match form.of.trim() {
+        "" => None,
+        of => match of.parse::<uuid::Uuid>() {
+            Ok(original) => Some(original),
+            Err(_) => {
+                warn!("❌ Not a feedback ID: {}", of);
+                return back;
+            }
+        },
+    };
+    match feedback_votes::mark_duplicate(&app_state.db_pool, feedback_id, original).await {
+        Ok(Ok(())) => {
+            audit_log(
+                &app_state,
+                &jar,
+                "feedback_duplicate_marked",
+                serde_json::json!({ "feedback_id": feedback_id, "duplicate_of": original }),
+            )
+            .await
+        }
+        Ok(Err(DuplicateRefused::UnknownOriginal)) => {
+            warn!(
+                "❌ No feedback {:?} to mark {} against",
+                original, feedback_id
+            )
+        }
+        Ok(Err(DuplicateRefused::Cycle)) => warn!(
+            "❌ Marking {} as a duplicate of {:?} would make a loop",
+            feedback_id, original
+        ),
+        Err(e) => warn!(
+            "❌ Failed to mark feedback {} as a duplicate: {:#}",
+            feedback_id, e
+        ),
+    }
+    back
+}
+
+/// ✅ POST /admin/feedback/:id/approve - approve held changes from the console
+pub async fn admin_feedback_approve(
+    State(app_state): State<AppState>,
+    Path(feedback_id): Path<uuid::Uuid>,
+    jar: CookieJar,
+) -> Response {
+    admin_decide(&app_state, &jar, feedback_id, Decision::Approved).await
+}
+
+/// 🚫 POST /admin/feedback/:id/reject - reject held changes from the console
+pub async fn admin_feedback_reject(
+    State(app_state): State<AppState>,
+    Path(feedback_id): Path<uuid::Uuid>,
+    jar: CookieJar,
+) -> Response {
+    admin_decide(&app_state, &jar, feedback_id, Decision::Rejected).await
+}
+
+/// ⚖️ Record an admin's decision (audited) and go back to the feedback page
+async fn admin_decide(
+    app_state: &AppState,
+    jar: &CookieJar,
+    feedback_id: uuid::Uuid,
+    decision: Decision,
+) -> Response {
+    if let Some(redirect) = require_admin_auth(jar, app_state).await {
+        return redirect;
+    }
+    let decided_by = admin_identity(jar, app_state)
+        .await
+        .and_then(|identity| identity.user_id);
+    let actor = audit_actor(app_state, jar).await;
+    match crate::api::feedback::record_decision(
+        app_state,
+        feedback_id,
+        decision,
+        decided_by,
+        &actor,
+    )
+    .await
+    {
+        Ok(DecisionOutcome::Decided(feedback)) => {
+            app_state
+                .events
+                .publish(crate::api::events::AppEvent::FeedbackStatusChanged {
+                    id: feedback.id,
+                    status: feedback.status.clone(),
+                });
+        }
+        Ok(outcome) => info!(
+            "ℹ️ Nothing to decide for feedback {}: {:?}",
+            feedback_id, outcome
+        ),
+        Err(e) => warn!(
+            "❌ Failed to record the decision on feedback {}: {:#}",
+            feedback_id, e
+        ),
+    }
+    Redirect::to(&format!("/admin/feedback/{}", feedback_id)).into_response()
+}
+
+/// 🏠 Project item for listing
+#[derive(Debug, Serialize)]
+pub struct ProjectItem {
+    pub id: String,
+    pub repository: String,
+    pub description: Option<String>,
+    pub is_active: bool,
+    #[serde(serialize_with = "serialize_minutes")]
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    pub feedback_count: i64,
+    /// ⚙️ Stored config, as written (None when the project has none)
+    pub config: Option<serde_json::Value>,
+    /// 🪝 The webhook we registered on the repository
+    pub webhook_id: Option<i64>,
+    /// ❌ Why registering it failed (None when it worked or wasn't attempted)
+    pub webhook_error: Option<String>,
+    /// ✋ Generated changes wait for the owner's approval before a PR is opened
+    pub require_approval: bool,
+    /// 🗂️ Further repositories whose feedback also belongs to this project
+    pub repositories: Vec<String>,
+}
+
+/// 🏠 Projects Management Page
+pub async fn admin_projects(State(app_state): State<AppState>, jar: CookieJar) -> Response {
+    if let Some(redirect) = require_admin_auth(&jar, &app_state).await {
+        return redirect;
+    }
+    info!("🔧 Admin projects page accessed");
+
+    let projects = get_all_projects(&app_state).await.unwrap_or_default();
+    let held = processing_holds::list(&app_state.db_pool, HELD_FEEDBACK_SHOWN)
+        .await
+        .unwrap_or_else(|e| {
+            warn!("❌ Failed to list held feedback: {:#}", e);
+            Vec::new()
+        });
+    let tz = admin_timezone(&app_state, &jar).await;
+    let style = label_style(&app_state, &jar);
+
+    Html(render_admin_page(
+        "Projects - Feedbacker Admin",
+        "/admin/projects",
+        &format!(
+            r#"
+    <div class="header">
+        <h2>{heading}</h2>
+    </div>
+
+    <div class="card">
+        <div class="card-header">
+            <h3>{add_heading}</h3>
+        </div>
+        <div class="card-body">
+            <form method="POST" action="/admin/projects/add">
+                <div class="form-group">
+                    <label for="repository">Repository (owner/repo format)</label>
+                    <input type="text" id="repository" name="repository" placeholder="8b-is/smart-tree" required>
+                </div>
+                <div class="form-group">
+                    <label for="description">Description</label>
+                    <textarea id="description" name="description" placeholder="Project description..."></textarea>
+                </div>
+                <button type="submit" class="btn">Add Project</button>
+            </form>
+            <div class="quick-add">
+                <span class="label">Quick add:</span>
+                <form method="POST" action="/admin/projects/add">
+                    <input type="hidden" name="repository" value="8b-is/smart-tree">
+                    <input type="hidden" name="description" value="Smart Tree - AI-optimized filesystem navigation MCP server">
+                    <button type="submit">{smart_tree}</button>
+                </form>
+                <form method="POST" action="/admin/projects/add">
+                    <input type="hidden" name="repository" value="8b-is/feedbacker">
+                    <input type="hidden" name="description" value="Feedbacker - AI-Powered Repository Management Service">
+                    <button type="submit">{feedbacker}</button>
+                </form>
+            </div>
+        </div>
+    </div>
+
+    <div class="card">
+        <div class="card-header">
+            <h3>{projects_heading}</h3>
+        </div>
+        <div class="card-body">
+            {}
+        </div>
+    </div>
+
+    <div class="card">
+        <div class="card-header">
+            <h3>{held_heading}</h3>
+        </div>
+        <div class="card-body">
+            {}
+        </div>
+    </div>
+"#,
+            render_projects_table(&projects, &tz, style),
+            render_held_feedback(&held, &tz, style),
+            heading = label_html("🏠 Projects Management", style),
+            add_heading = label_html("➕ Add New Project", style),
+            smart_tree = label_html("🌲 Smart Tree", style),
+            feedbacker = label_html("🚢 Feedbacker", style),
+            projects_heading = label_html("📋 All Projects", style),
+            held_heading = label_html("⏸️ Held Below Threshold", style),
+        ),
+        style,
+    ))
+    .into_response()
+}
+
+/// ⏸️ Held feedback listed on the projects page (oldest first)
+const HELD_FEEDBACK_SHOWN: i64 = 50;
+
+/// ⏸️ Feedback waiting below its project's threshold, each with a "Release hold" button
+fn render_held_feedback(held: &[HeldFeedback], tz: &TimeZone, style: LabelStyle) -> String {
+    if held.is_empty() {
+        return empty_state(
+            "⏸️ Nothing is held. Set a threshold above to hold low-impact feedback.",
+            style,
+        );
+    }
+    let rows: String = held
+        .iter()
+        .map(|h| {
+            format!(
+                r#"<tr>
+                    <td><a href="/admin/feedback/{}">{}</a></td>
+                    <td>{}</td>
+                    <td>{} &lt; {}</td>
+                    <td>{}</td>
+                    <td><form method="POST" action="/admin/feedback/{}/release" class="release-hold"><button type="submit" class="btn">Release hold</button></form></td>
+                </tr>"#,
+                h.feedback_id,
+                html_escape(&h.repository),
+                html_escape(&h.content.chars().take(80).collect::<String>()),
+                h.score,
+                h.min_score,
+                fmt_ts(h.held_at, tz),
+                h.feedback_id,
+            )
+        })
+        .collect();
+    format!(
+        r#"<table>
+            <thead>
+                <tr>
+                    <th>Repository</th>
+                    <th>Feedback</th>
+                    <th>Score</th>
+                    <th>Held</th>
+                    <th></th>
+                </tr>
+            </thead>
+            <tbody>{}</tbody>
+        </table>"#,
+        rows
+    )
+}
+
+/// ➕ Add Project Form
+#[derive(Debug, Deserialize)]
+pub struct AddProjectForm {
+    pub repository: String,
+    pub description: Option<String>,
+}
+
+/// ➕ Add Project POST Handler
+pub async fn admin_projects_add(
+    State(app_state): State<AppState>,
+    jar: CookieJar,
+    Form(form): Form<AddProjectForm>,
+) -> Response {
+    if let Some(redirect) = require_admin_auth(&jar, &app_state).await {
+        return redirect;
+    }
+    info!("➕ Adding project: {}", form.repository);
+
+    // Ensure system user exists
+    let system_user_id = get_or_create_system_user(&app_state).await;
+
+    if let Some(user_id) = system_user_id {
+        // Create the project
+        let result = sqlx::query_scalar::<_, uuid::Uuid>(
+            r#"
+            INSERT INTO projects (owner_id, repository, description, is_active, created_at, updated_at)
+            VALUES ($1, $2, $3, true, NOW(), NOW())
+            ON CONFLICT (owner_id, repository) DO UPDATE SET
+                description = COALESCE($3, projects.description),
+                updated_at = NOW()
+            RETURNING id
+            "#
+        )
+        .bind(user_id)
+        .bind(&form.repository)
+        .bind(&form.description)
+        .fetch_one(&app_state.db_pool)
+        .await;
+
+        match result {
+            Ok(project_id) => {
+                info!("✅ Project {} added successfully", form.repository);
+                install_webhook(&app_state, project_id).await;
+            }
+            Err(e) => warn!("❌ Failed to add project: {}", e),
+        }
+    }
+
+    Redirect::to("/admin/projects").into_response()
+}
+
+/// 🪝 Register a project's repository webhook (the outcome is recorded on the project)
+pub(crate) async fn install_webhook(app_state: &AppState, project_id: uuid::Uuid) {
+    if let Err(e) = repo_hooks::install_project_webhook(
+        &*app_state.github,
+        &app_state.db_pool,
+        &app_state.config,
+        project_id,
+    )
+    .await
+    {
+        warn!("❌ Failed to register project webhook: {:#}", e);
+    }
+}
+
+/// 🔁 POST /admin/projects/:id/webhook - try registering the webhook again
+pub async fn admin_project_webhook_retry(
+    State(app_state): State<AppState>,
+    jar: CookieJar,
+    Path(project_id): Path<uuid::Uuid>,
+) -> Response {
+    if let Some(redirect) = require_admin_auth(&jar, &app_state).await {
+        return redirect;
+    }
+    install_webhook(&app_state, project_id).await;
+    Redirect::to("/admin/projects").into_response()
+}
+
+/// 🗑️ POST /admin/projects/:id/delete - remove a project and its webhook
+pub async fn admin_project_delete(
+    State(app_state): State<AppState>,
+    jar: CookieJar,
+    Path(project_id): Path<uuid::Uuid>,
+) -> Response {
+    if let Some(redirect) = require_admin_auth(&jar, &app_state).await {
+        return redirect;
+    }
+    match repo_hooks::remove_project(&*app_state.github, &app_state.db_pool, project_id).await {
+        Ok(true) => {
+            audit_log(
+                &app_state,
+                &jar,
+                "project_removed",
+                serde_json::json!({ "project_id": project_id }),
+            )
+            .await;
+        }
+        Ok(false) => info!("ℹ️ Project {} was already gone", project_id),
+        Err(e) => warn!("❌ Failed to remove project: {:#}", e),
+    }
+    Redirect::to("/admin/projects").into_response()
+}
+
+/// 🤖 Get or create system user for admin-created projects
+pub(crate) async fn get_or_create_system_user(app_state: &AppState) -> Option<uuid::Uuid> {
+    // Try to find existing system user
+    let existing: Option<uuid::Uuid> =
+        sqlx::query_scalar("SELECT id FROM users WHERE email = 'system@feedbacker.local'")
+            .fetch_optional(&app_state.db_pool)
+            .await
+            .ok()
+            .flatten();
+
+    if let Some(id) = existing {
+        return Some(id);
+    }
+
+    // Create system user
+    let result = sqlx::query_scalar::<_, uuid::Uuid>(
+        r#"
+        INSERT INTO users (email, name, password_hash, email_verified, role, is_active)
+        VALUES ('system@feedbacker.local', 'System', 'not-a-real-hash', true, 'service', true)
+        RETURNING id
+        "#,
+    )
+    .fetch_one(&app_state.db_pool)
+    .await;
+
+    match result {
+        Ok(id) => {
+            info!("✅ Created system user with ID: {}", id);
+            Some(id)
+        }
+        Err(e) => {
+            warn!("❌ Failed to create system user: {}", e);
+            None
+        }
+    }
+}
+
+/// 📋 Get all projects from database
+async fn get_all_projects(app_state: &AppState) -> anyhow::Result<Vec<ProjectItem>> {
+    let rows = sqlx::query(
+        r#"
+        SELECT
+            p.id, p.repository, p.description, p.is_active, p.created_at, p.config,
+            p.webhook_id, p.webhook_error, p.require_approval,
+            ARRAY(SELECT pr.repository FROM project_repositories pr
+                  WHERE pr.project_id = p.id ORDER BY pr.created_at, pr.repository) as repositories,
+            COALESCE((SELECT COUNT(*) FROM feedback f WHERE f.repository IN
+                (SELECT repository FROM project_repository_routes WHERE project_id = p.id)), 0)
+              + COALESCE((SELECT SUM(r.feedback_count) FROM feedback_rollup r WHERE r.repository IN
+                (SELECT repository FROM project_repository_routes WHERE project_id = p.id)), 0)::bigint as feedback_count
+        FROM projects p
+        ORDER BY p.created_at DESC, p.id DESC
+        "#
+    )
+    .fetch_all(&app_state.db_pool)
+    .await?;
+
+    let items = rows
+        .iter()
+        .map(|row| {
+            Ok(ProjectItem {
+                id: row.try_get::<uuid::Uuid, _>("id")?.to_string(),
+                repository: row.try_get("repository")?,
+                description: row.try_get("description")?,
+                is_active: row.try_get("is_active")?,
+                created_at: row.try_get("created_at")?,
+                feedback_count: row.try_get("feedback_count")?,
+                config: row.try_get("config")?,
+                webhook_id: row.try_get("webhook_id")?,
+                webhook_error: row.try_get("webhook_error")?,
+                require_approval: row.try_get("require_approval")?,
+                repositories: row.try_get("repositories")?,
+            })
+        })
+        .collect::<Result<_, sqlx::Error>>()
+        .context("Failed to read project row")?;
+
+    Ok(items)
+}
+
+/// 📋 Render projects table
+fn render_projects_table(projects: &[ProjectItem], tz: &TimeZone, style: LabelStyle) -> String {
+    if projects.is_empty() {
+        return empty_state("📋 No projects yet. Add one above!", style);
+    }
+
+    let rows: String = projects
+        .iter()
+        .map(|p| {
+            let status_class = if p.is_active { "status-active" } else { "status-inactive" };
+            let status_text = if p.is_active { "Active" } else { "Inactive" };
+            format!(
+                r#"<tr>
+                    <td><a href="https://github.com/{}" target="_blank" class="repo-link">{}</a>{}</td>
+                    <td>{}</td>
+                    <td><span class="status {}">{}</span></td>
+                    <td>{}</td>
+                    <td>{}</td>
+                    <td>{}</td>
+                    <td>{}</td>
+                    <td>{}</td>
+                    <td>{}</td>
+                    <td><form method="POST" action="/admin/projects/{}/delete" class="project-delete"><button type="submit" class="btn">Remove</button></form></td>
+                </tr>"#,
+                p.repository,
+                p.repository,
+                render_project_repositories(p),
+                p.description.as_deref().unwrap_or("-"),
+                status_class,
+                status_text,
+                render_config_version(p),
+                render_webhook_state(p),
+                render_approval_setting(p),
+                render_processing_threshold(p),
+                p.feedback_count,
+                fmt_ts(p.created_at, tz),
+                p.id,
+            )
+        })
+        .collect();
+
+    format!(
+        r#"<table>
+            <thead>
+                <tr>
+                    <th>Repository</th>
+                    <th>Description</th>
+                    <th>Status</th>
+                    <th>Config</th>
+                    <th>Webhook</th>
+                    <th>Approval</th>
+                    <th>Threshold</th>
+                    <th>Feedback</th>
+                    <th>Created</th>
+                    <th></th>
+                </tr>
+            </thead>
+            <tbody>{}</tbody>
+        </table>"#,
+        rows
+    )
+}
+
+/// ⚙️ A project's config version: current, upgradable (with a "migrate now" button),
+/// or written by a newer build - in which case automation is paused for it
+fn render_config_version(project: &ProjectItem) -> String {
+    let Some(config) = &project.config else {
+        return r#"<span class="muted">defaults</span>"#.to_string();
+    };
+    match stored_version(config) {
+        Ok(version) if version > CURRENT_CONFIG_VERSION => format!(
+            r#"<span class="status status-failed" title="Issue automation is skipped until a build that understands this config is deployed">v{} ⚠️ newer than this build (v{}) - automation paused</span>"#,
+            version, CURRENT_CONFIG_VERSION
+        ),
+        Ok(version) if version < CURRENT_CONFIG_VERSION => format!(
+            r#"v{} <form method="POST" action="/admin/projects/{}/config/migrate" class="config-migrate"><button type="submit" class="btn">Migrate now</button></form>"#,
+            version, project.id
+        ),
+        Ok(version) => format!("v{}", version),
+        Err(_) => r#"<span class="status status-failed">invalid config_version</span>"#.to_string(),
+    }
+}
+
+/// 🪝 A project's webhook: installed, or "not installed" with the reason and a retry button
+fn render_webhook_state(project: &ProjectItem) -> String {
+    match (&project.webhook_id, &project.webhook_error) {
+        (_, Some(error)) => format!(
+            r#"<span class="status status-failed" title="{}">webhook not installed</span> <form method="POST" action="/admin/projects/{}/webhook" class="webhook-retry"><button type="submit" class="btn">Retry</button></form>"#,
+            html_escape(error),
+            project.id
+        ),
+        (Some(hook_id), None) => {
+            format!(r#"<span class="status status-active">#{}</span>"#, hook_id)
+        }
+        (None, None) => r#"<span class="muted">-</span>"#.to_string(),
+    }
+}
+
+/// 🗂️ A project's further repositories, each with a remove button, and a form to add one
+fn render_project_repositories(project: &ProjectItem) -> String {
+    let listed: String = project
+        .repositories
+        .iter()
+        .map(|repository| {
+            format!(
+                r#"<div>+ <a href="https://github.com/{}" target="_blank" class="repo-link">{}</a><form method="POST" action="/admin/projects/{}/repositories/remove"><input type="hidden" name="repository" value="{}"><button type="submit" class="btn" title="Stop routing this repository's feedback here">✕</button></form></div>"#,
+                html_escape(repository),
+                html_escape(repository),
+                project.id,
+                html_escape(repository)
+            )
+        })
+        .collect();
+    format!(
+        r#"<div class="project-repos">{}<form method="POST" action="/admin/projects/{}/repositories"><input type="text" name="repository" placeholder="owner/another-repo" required><button type="submit" class="btn">Add repo</button></form></div>"#,
+        listed, project.id
+    )
+}
+
+/// 🗂️ Form naming one of a project's further repositories
+#[derive(Debug, Deserialize)]
+pub struct ProjectRepositoryForm {
+    pub repository: String,
+}
+
+/// ➕ POST /admin/projects/:id/repositories - route another repository's feedback to the project
+pub async fn admin_project_repository_add(
+    State(app_state): State<AppState>,
+    jar: CookieJar,
+    Path(project_id): Path<uuid::Uuid>,
+    Form(form): Form<ProjectRepositoryForm>,
+) -> Response {
+    if let Some(redirect) = require_admin_auth(&jar, &app_state).await {
+        return redirect;
+    }
+    match project_repositories::add(&app_state.db_pool, project_id, &form.repository).await {
+        Ok(true) => {
+            audit_log(
+                &app_state,
+                &jar,
+                "project_repository_added",
+                serde_json::json!({ "project_id": project_id, "repository": form.repository.trim() }),
+            )
+            .await;
+        }
+        Ok(false) => info!(
+            "ℹ️ {} already belongs to project {}",
+            form.repository, project_id
+        ),
+        Err(e) => warn!("❌ Failed to add project repository: {:#}", e),
+    }
+    Redirect::to("/admin/projects").into_response()
+}
+
+/// ➖ POST /admin/projects/:id/repositories/remove - stop routing a repository to the project
+pub async fn admin_project_repository_remove(
+    State(app_state): State<AppState>,
+    jar: CookieJar,
+    Path(project_id): Path<uuid::Uuid>,
+    Form(form): Form<ProjectRepositoryForm>,
+) -> Response {
+    if let Some(redirect) = require_admin_auth(&jar, &app_state).await {
+        return redirect;
+    }
+    match project_repositories::remove(&app_state.db_pool, project_id, &form.repository).await {
+        Ok(true) => {
+            audit_log(
+                &app_state,
+                &jar,
+                "project_repository_removed",
+                serde_json::json!({ "project_id": project_id, "repository": form.repository }),
+            )
+            .await;
+        }
+        Ok(false) => info!(
+            "ℹ️ {} was not one of project {}'s repositories",
+            form.repository, project_id
+        ),
+        Err(e) => warn!("❌ Failed to remove project repository: {:#}", e),
+    }
+    Redirect::to("/admin/projects").into_response()
+}
+
+/// ✋ Whether the project holds changes for approval, with a button flipping it
+fn render_approval_setting(project: &ProjectItem) -> String {
+    let (state, action) = if project.require_approval {
+        (
+            r#"<span class="status status-pending">required</span>"#,
+            "Turn off",
+        )
+    } else {
+        (r#"<span class="muted">off</span>"#, "Require")
+    };
+    format!(
+        r#"{} <form method="POST" action="/admin/projects/{}/approval" class="approval-toggle"><button type="submit" class="btn">{}</button></form>"#,
+        state, project.id, action
+    )
+}
+
+/// ✋ POST /admin/projects/:id/approval - switch "require approval" on or off
+pub async fn admin_project_approval_toggle(
+    State(app_state): State<AppState>,
+    jar: CookieJar,
+    Path(project_id): Path<uuid::Uuid>,
+) -> Response {
+    if let Some(redirect) = require_admin_auth(&jar, &app_state).await {
+        return redirect;
+    }
+    match sqlx::query_scalar::<_, bool>(
+        "UPDATE projects SET require_approval = NOT require_approval, updated_at = NOW() WHERE id = $1 RETURNING require_approval",
+    )
+    .bind(project_id)
+    .fetch_optional(&app_state.db_pool)
+    .await
+    {
+        Ok(Some(require_approval)) => {
+            audit_log(
+                &app_state,
+                &jar,
+                "project_approval_changed",
+                serde_json::json!({ "project_id": project_id, "require_approval": require_approval }),
+            )
+            .await;
+        }
+        Ok(None) => info!("ℹ️ Project {} was already gone", project_id),
+        Err(e) => warn!("❌ Failed to change the approval setting: {:#}", e),
+    }
+    Redirect::to("/admin/projects").into_response()
+}
+
+/// 📏 The project's minimum impact × frequency for processing, editable in place
+/// (empty: everything is processed)
+fn render_processing_threshold(project: &ProjectItem) -> String {
+    let config = match project.config.clone().map(ProjectConfig::from_value) {
+        None => ProjectConfig::default(),
+        Some(Ok(config)) => config,
+        Some(Err(_)) => return r#"<span class="muted">-</span>"#.to_string(),
+    };
+    let value = config
+        .processing
+        .min_score
+        .map(|score| score.to_string())
+        .unwrap_or_default();
+    format!(
+        r#"<form method="POST" action="/admin/projects/{}/threshold" class="threshold" title="Feedback scoring impact × frequency below this waits for &quot;Release hold&quot;"><input type="number" name="min_score" min="1" max="100" value="{}" placeholder="off"><button type="submit" class="btn">Save</button></form>"#,
+        project.id, value
+    )
+}
+
+/// 📏 Threshold form (an empty `min_score` turns holding off)
+#[derive(Debug, Deserialize)]
+pub struct ThresholdForm {
+    #[serde(default)]
+    pub min_score: String,
+}
+
+/// 📏 POST /admin/projects/:id/threshold - set or clear the processing threshold
+pub async fn admin_project_threshold(
+    State(app_state): State<AppState>,
+    jar: CookieJar,
+    Path(project_id): Path<uuid::Uuid>,
+    Form(form): Form<ThresholdForm>,
+) -> Response {
+    if let Some(redirect) = require_admin_auth(&jar, &app_state).await {
+        return redirect;
+    }
+    let min_score = match form.min_score.trim() {
+        "" => None,
+        value => match value.parse::<i32>() {
+            Ok(score) if (1..=100).contains(&score) => Some(score),
+            _ => {
+                warn!("❌ Ignoring processing threshold {:?}: not 1-100", value);
+                return Redirect::to("/admin/projects").into_response();
+            }
+        },
+    };
+
+    match project_config::update_stored(&app_state.db_pool, project_id, |config| {
+        config.processing.min_score = min_score
+    })
+    .await
+    {
+        Ok(Some(_)) => {
+            audit_log(
+                &app_state,
+                &jar,
+                "project_threshold_changed",
+                serde_json::json!({ "project_id": project_id, "min_score": min_score }),
+            )
+            .await;
+        }
+        Ok(None) => info!("ℹ️ Project {} was already gone", project_id),
+        Err(e) => warn!("❌ Failed to change the processing threshold: {:#}", e),
+    }
+    Redirect::to("/admin/projects").into_response()
+}
+
+/// ▶️ POST /admin/feedback/:id/release - "Release hold": lift the threshold hold so
+/// held feedback rejoins the pending queue like any other item
+pub async fn admin_feedback_release(
+    State(app_state): State<AppState>,
+    Path(feedback_id): Path<uuid::Uuid>,
+    jar: CookieJar,
+) -> Response {
+    if let Some(redirect) = require_admin_auth(&jar, &app_state).await {
+        return redirect;
+    }
+    match processing_holds::release(&app_state.db_pool, feedback_id).await {
+        Ok(Some(hold)) => {
+            info!("▶️ Feedback {} released for processing", feedback_id);
+            audit_log(
+                &app_state,
+                &jar,
+                "feedback_hold_released",
+                serde_json::json!({
+                    "feedback_id": feedback_id,
+                    "score": hold.score,
+                    "min_score": hold.min_score,
+                }),
+            )
+            .await;
+        }
+        Ok(None) => info!("ℹ️ Feedback {} wasn't held", feedback_id),
+        Err(e) => warn!("❌ Failed to release feedback {}: {:#}", feedback_id, e),
+    }
+    Redirect::to("/admin/projects").into_response()
+}
+
+/// 🔓 POST /admin/api/projects/:id/reactivate - check GitHub access to every repository
+/// the project serves again, then make it active and let automation back in
+pub async fn admin_project_reactivate_api(
+    State(app_state): State<AppState>,
+    jar: CookieJar,
+    Path(project_id): Path<uuid::Uuid>,
+) -> Response {
+    if let Some(denied) = require_admin_api_auth(&jar, &app_state).await {
+        return denied;
+    }
+    match availability::reactivate(&app_state.db_pool, &*app_state.github, project_id).await {
+        Ok(Reactivation::Reactivated(repositories)) => {
+            info!("🔓 Project {} reactivated", project_id);
+            audit_log(
+                &app_state,
+                &jar,
+                "project_reactivated",
+                serde_json::json!({ "project_id": project_id, "repositories": repositories }),
+            )
+            .await;
+            (
+                StatusCode::OK,
+                Json(crate::api::ApiResponse::success(
+                    "Project reactivated".to_string(),
+                    serde_json::json!({ "project_id": project_id, "repositories": repositories }),
+                )),
+            )
+                .into_response()
+        }
+        Ok(Reactivation::StillUnavailable(unavailable)) => (
+            StatusCode::CONFLICT,
+            Json(crate::api::ApiResponse::<()>::error(
+                crate::api::ErrorCode::RepositoryUnavailable,
+                unavailable.to_string(),
+                Some(serde_json::json!(unavailable)),
+            )),
+        )
+            .into_response(),
+        Ok(Reactivation::NotFound) => crate::api::utils::not_found_error("Project").into_response(),
+        Err(e) => {
+            warn!(
+                "⚠️ Could not check access for project {}: {:#}",
+                project_id, e
+            );
+            (
+                StatusCode::BAD_GATEWAY,
+                Json(crate::api::ApiResponse::<()>::error(
+                    crate::api::ErrorCode::GithubUnavailable,
+                    "Could not verify repository access with GitHub".to_string(),
+                    None,
+                )),
+            )
+                .into_response()
+        }
+    }
+}
+
+/// 🪜 POST /admin/projects/:id/config/migrate - rewrite a project's config at the current version
+pub async fn admin_project_config_migrate(
+    State(app_state): State<AppState>,
+    jar: CookieJar,
+    Path(project_id): Path<uuid::Uuid>,
+) -> Response {
+    if let Some(redirect) = require_admin_auth(&jar, &app_state).await {
+        return redirect;
+    }
+
+    match project_config::migrate_stored(&app_state.db_pool, project_id).await {
+        Ok(Some((from, to))) => {
+            info!(
+                "🪜 Migrated config of project {} from v{} to v{}",
+                project_id, from, to
+            );
+            audit_log(
+                &app_state,
+                &jar,
+                "project_config_migrated",
+                serde_json::json!({
+                    "project_id": project_id,
+                    "from_version": from,
+                    "to_version": to,
+                }),
+            )
+            .await;
+        }
+        Ok(None) => info!("ℹ️ Project {} has no config to migrate", project_id),
+        Err(e) => warn!("❌ Failed to migrate project config: {:#}", e),
+    }
+
+    Redirect::to("/admin/projects").into_response()
+}
+
+/// 👥 Users Management Page
+pub async fn admin_users(State(app_state): State<AppState>, jar: CookieJar) -> Response {
+    if let Some(redirect) = require_admin_auth(&jar, &app_state).await {
+        return redirect;
+    }
+    info!("🔧 Admin users page accessed");
+
+    let style = label_style(&app_state, &jar);
+    Html(render_admin_page(
+        "Users - Feedbacker Admin",
+        "/admin/users",
+        &format!(
+            r#"
+    <div class="header">
+        <h2>{heading}</h2>
+    </div>
+    <div class="card">
+        <h3>{empty_heading}</h3>
+        <p>Users will appear here when they register.</p>
+    </div>
+"#,
+            heading = label_html("👥 User Management", style),
+            empty_heading = label_html("👤 No users yet", style),
+        ),
+        style,
+    ))
+    .into_response()
+}
+
+/// ⚙️ Background Jobs Page - why jobs failed, by kind
+pub async fn admin_jobs(
+    State(app_state): State<AppState>,
+    Query(query): Query<DashboardQuery>,
+    jar: CookieJar,
+) -> Response {
+    if let Some(redirect) = require_admin_auth(&jar, &app_state).await {
+        return redirect;
+    }
+    info!("🔧 Admin jobs page accessed");
+
+    let range = DashboardRange::from_param(query.range.as_deref());
+    let since = range.cutoff(chrono::Utc::now());
+    let failures = crate::jobs::failure_breakdown(&app_state.db_pool, since)
+        .await
+        .unwrap_or_else(|e| {
+            warn!("⚠️ Failed to load job failures: {:#}", e);
+            Vec::new()
+        });
+
+    let style = label_style(&app_state, &jar);
+    Html(render_admin_page_ranged(
+        "Background Jobs - Feedbacker Admin",
+        "/admin/jobs",
+        &format!(
+            r#"
+    <div class="header">
+        <h2>{heading}</h2>
+        {}
+    </div>
+    <div class="card">
+        <div class="card-header">
+            <h3>{failures_heading}</h3>
+        </div>
+        <div class="card-body">
+            {}
+        </div>
+    </div>
+"#,
+            render_range_selector("/admin/jobs", range),
+            render_failure_breakdown(&failures, style),
+            heading = label_html("⚙️ Background Jobs", style),
+            failures_heading = label_html("💀 Failures by Kind", style),
+        ),
+        range,
+        style,
+    ))
+    .into_response()
+}
+
+/// 💀 Failed jobs per error kind: how many, which job types, and the latest message
+fn render_failure_breakdown(
+    failures: &[crate::jobs::FailureBreakdown],
+    style: LabelStyle,
+) -> String {
+    let total: i64 = failures.iter().map(|f| f.count).sum();
+    if total == 0 {
+        return empty_state("✅ No failed jobs in this range", style);
+    }
+
+    let rows: String = failures
+        .iter()
+        .map(|f| {
+            format!(
+                r#"<tr>
+                    <td><span class="tag-chip">{}</span></td>
+                    <td>{}</td>
+                    <td>{:.0}%</td>
+                    <td>{}</td>
+                    <td class="muted">{}</td>
+                </tr>"#,
+                f.kind,
+                f.count,
+                f.count as f64 * 100.0 / total as f64,
+                html_escape(&f.job_types.join(", ")),
+                html_escape(f.latest_message.as_deref().unwrap_or("-")),
+            )
+        })
+        .collect();
+
+    format!(
+        r#"<table>
+            <thead>
+                <tr>
+                    <th>Kind</th>
+                    <th>Failed Jobs</th>
+                    <th>Share</th>
+                    <th>Job Types</th>
+                    <th>Latest Error</th>
+                </tr>
+            </thead>
+            <tbody>{}</tbody>
+        </table>"#,
+        rows
+    )
+}
+
+/// 🗄️ Database Migrations Page - which migrations ran, which are waiting, which drifted
+pub async fn admin_migrations(State(app_state): State<AppState>, jar: CookieJar) -> Response {
+    if let Some(redirect) = require_admin_auth(&jar, &app_state).await {
+        return redirect;
+    }
+    info!("🔧 Admin migrations page accessed");
+    let tz = admin_timezone(&app_state, &jar).await;
+    let style = label_style(&app_state, &jar);
+
+    let content = match crate::database::migrations::migration_status(&app_state.db_pool).await {
+        Ok(states) => {
+            let pending = states.iter().filter(|state| state.pending).count();
+            let drifted = states.iter().filter(|state| state.has_drifted()).count();
+            let summary = match (pending, drifted) {
+                (0, 0) => "✅ Schema is up to date".to_string(),
+                (pending, 0) => format!("⏳ {} pending", pending),
+                (0, drifted) => format!("⚠️ {} drifted", drifted),
+                (pending, drifted) => format!("⏳ {} pending, ⚠️ {} drifted", pending, drifted),
+            };
+            format!(
+                r#"
+    <div class="card">
+        <h3>{}</h3>
+        {}
+    </div>
+"#,
+                label_html(&summary, style),
+                render_migration_table(&states, &tz)
+            )
+        }
+        Err(e) => {
+            warn!("❌ Failed to load migration status: {:#}", e);
+            format!(
+                r#"
+    <div class="card">
+        <h3>{}</h3>
+        <p>Check the server logs for details.</p>
+    </div>
+"#,
+                label_html("❌ Could not read migration status", style)
+            )
+        }
+    };
+
+    Html(render_admin_page(
+        "Migrations - Feedbacker Admin",
+        "/admin/migrations",
+        &format!(
+            r#"
+    <div class="header">
+        <h2>{}</h2>
+    </div>
+{}"#,
+            label_html("🗄️ Database Migrations", style),
+            content
+        ),
+        style,
+    ))
+    .into_response()
+}
+
+/// 🎨 Migrations table: applied time, pending state and checksum drift per migration
+fn render_migration_table(
+    states: &[crate::database::migrations::MigrationState],
+    tz: &TimeZone,
+) -> String {
+    let rows: String = states
+        .iter()
+        .map(|state| {
+            let (status_class, status_text) = if state.pending {
+                ("status-pending", "Pending")
+            } else {
+                ("status-completed", "Applied")
+            };
+            let checksum = match state.checksum_matches {
+                Some(true) => r#"<span class="status status-ok">Matches</span>"#,
+                Some(false) => r#"<span class="status status-failed">Drifted</span>"#,
+                None => "-",
+            };
+            format!(
+                r#"<tr>
+                    <td><code>{}</code></td>
+                    <td>{}</td>
+                    <td><span class="status {}">{}</span></td>
+                    <td>{}</td>
+                    <td>{}</td>
+                </tr>"#,
+                html_escape(&state.id),
+                html_escape(&state.description),
+                status_class,
+                status_text,
+                state
+                    .applied_at
+                    .map(|applied_at| fmt_ts(applied_at, tz))
+                    .unwrap_or_else(|| "-".to_string()),
+                checksum,
+            )
+        })
+        .collect();
+
+    format!(
+        r#"<table>
+            <thead>
+                <tr>
+                    <th>Migration</th>
+                    <th>Description</th>
+                    <th>Status</th>
+                    <th>Applied</th>
+                    <th>Checksum</th>
+                </tr>
+            </thead>
+            <tbody>{}</tbody>
+        </table>"#,
+        rows
+    )
+}
+
+/// 🔤 Label style choice from the settings page
+#[derive(Debug, Deserialize)]
+pub struct LabelStyleForm {
+    pub style: String,
+}
+
+/// 🔤 Remember this browser's label style (emoji or plain text)
+pub async fn admin_settings_labels(
+    State(app_state): State<AppState>,
+    jar: CookieJar,
+    Form(form): Form<LabelStyleForm>,
+) -> Response {
+    if let Some(redirect) = require_admin_auth(&jar, &app_state).await {
+        return redirect;
+    }
+    let Ok(style) = form.style.parse::<LabelStyle>() else {
+        return (StatusCode::BAD_REQUEST, "Unknown label style").into_response();
+    };
+    info!("🔤 Admin label style set to {}", style.as_str());
+
+    let cookie = Cookie::build((crate::api::labels::LABEL_STYLE_COOKIE, style.as_str()))
+        .path("/")
+        .http_only(true)
+        .secure(app_state.config.is_production())
+        .max_age(time::Duration::days(365))
+        .build();
+    (jar.add(cookie), Redirect::to("/admin/settings")).into_response()
+}
+
+/// 🕰️ Time zone choice from the settings page
+#[derive(Debug, Deserialize)]
+pub struct TimezoneForm {
+    pub timezone: String,
+}
+
+/// 🕰️ Remember the signed-in admin's time zone for timestamps in the console
+pub async fn admin_settings_timezone(
+    State(app_state): State<AppState>,
+    jar: CookieJar,
+    Form(form): Form<TimezoneForm>,
+) -> Response {
+    let Some(admin) = admin_identity(&jar, &app_state).await else {
+        return Redirect::to("/admin/login").into_response();
+    };
+    let timezone = form.timezone.trim();
+    if let Err(e) = TimeZone::load(timezone) {
+        warn!("🕰️ Rejected time zone {:?}: {:#}", timezone, e);
+        return (StatusCode::BAD_REQUEST, "Unknown time zone").into_response();
+    }
+    if let Err(e) = set_setting(&app_state, &timezone_key(&admin), timezone).await {
+        warn!("❌ Failed to save time zone: {:#}", e);
+        return (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "Failed to save time zone",
+        )
+            .into_response();
+    }
+    info!("🕰️ {} set their time zone to {}", admin.actor, timezone);
+    Redirect::to("/admin/settings").into_response()
+}
+
+/// 🔧 Settings Page
+pub async fn admin_settings(State(app_state): State<AppState>, jar: CookieJar) -> Response {
+    if let Some(redirect) = require_admin_auth(&jar, &app_state).await {
+        return redirect;
+    }
+    info!("🔧 Admin settings page accessed");
+
+    let admin = admin_identity(&jar, &app_state).await;
+    let status = match &admin {
+        Some(admin) => second_factors::status(&app_state.db_pool, admin.subject())
+            .await
+            .unwrap_or_else(|e| {
+                warn!("❌ Failed to load the second factor: {:#}", e);
+                second_factors::Status::default()
+            }),
+        None => second_factors::Status::default(),
+    };
+    let style = label_style(&app_state, &jar);
+    let two_factor = if status.enabled_since.is_some() {
+        format!(
+            r#"<div class="setting-row">
+                <span class="setting-label">Status</span>
+                <span class="setting-status status-ok">{}</span>
+            </div>
+            <form method="POST" action="/admin/settings/totp/disable" class="inline-form">
+                <input type="text" name="code" required placeholder="Code to confirm" autocomplete="one-time-code">
+                <button type="submit" class="btn btn-danger">Disable</button>
+            </form>
+            <p class="muted">To move to a new authenticator, disable two-factor and set it up again.</p>"#,
+            label_html(
+                &format!("✓ Enabled ({} backup codes left)", status.backup_codes_left),
+                style
+            )
+        )
+    } else {
+        format!(
+            r#"<div class="setting-row">
+                <span class="setting-label">Status</span>
+                <span class="setting-status status-warn">{}</span>
+            </div>
+            <form method="POST" action="/admin/settings/totp/enroll">
+                <button type="submit" class="btn btn-primary">Set up two-factor</button>
+            </form>
+            <p class="muted">Protects your own sign-in. Every admin sets up their own authenticator.</p>"#,
+            label_html("⚠ Not enabled", style)
+        )
+    };
+
+    let (current_style, switch_to, switch_label) = match style {
+        LabelStyle::Emoji => ("Emoji", LabelStyle::Plain, "Use plain-text labels"),
+        LabelStyle::Plain => ("Plain text", LabelStyle::Emoji, "Use emoji labels"),
+    };
+    let label_settings = format!(
+        r#"<div class="setting-row">
+                <span class="setting-label">Label style (this browser)</span>
+                <span class="setting-value">{}</span>
+            </div>
+            <form method="POST" action="/admin/settings/labels">
+                <input type="hidden" name="style" value="{}">
+                <button type="submit" class="btn btn-primary">{}</button>
+            </form>"#,
+        current_style,
+        switch_to.as_str(),
+        switch_label
+    );
+
+    let tz = admin_timezone(&app_state, &jar).await;
+    let options: String = timezones::available_zones()
+        .iter()
+        .map(|zone| {
+            format!(
+                r#"<option value="{0}"{1}>{0}</option>"#,
+                html_escape(zone),
+                if zone == tz.name() { " selected" } else { "" }
+            )
+        })
+        .collect();
+    let timezone_settings = format!(
+        r#"<div class="setting-row">
+                <span class="setting-label">Time zone (your account)</span>
+                <span class="setting-value">{} · now {}</span>
+            </div>
+            <form method="POST" action="/admin/settings/timezone" class="inline-form">
+                <select name="timezone" aria-label="Time zone">{}</select>
+                <button type="submit" class="btn btn-primary">Save</button>
+            </form>"#,
+        html_escape(tz.name()),
+        fmt_ts(chrono::Utc::now(), &tz),
+        options
+    );
+
+    Html(render_admin_page(
+        "Settings - Feedbacker Admin",
+        "/admin/settings",
+        &format!(
+            r#"
+    <div class="header">
+        <h2>{heading}</h2>
+    </div>
+
+    <div class="card">
+        <div class="card-header">
+            <h3>{two_factor_heading}</h3>
+        </div>
+        <div class="card-body">
+            {}
+        </div>
+    </div>
+
+    <div class="card">
+        <div class="card-header">
+            <h3>{accessibility_heading}</h3>
+        </div>
+        <div class="card-body">
+            {}
+        </div>
+    </div>
+
+    <div class="card">
+        <div class="card-header">
+            <h3>{timezone_heading}</h3>
+        </div>
+        <div class="card-body">
+            {}
+        </div>
+    </div>
+
+    <div class="card">
+        <div class="card-header">
+            <h3>{github_heading}</h3>
+        </div>
+        <div class="card-body">
+            <div class="setting-row">
+                <span class="setting-label">GitHub Username</span>
+                <span class="setting-value">{}</span>
+            </div>
+            <div class="setting-row">
+                <span class="setting-label">GitHub Token</span>
+                <span class="setting-status status-ok">{token_configured}</span>
+            </div>
+        </div>
+    </div>
+
+    <div class="card">
+        <div class="card-header">
+            <h3>{llm_heading}</h3>
+        </div>
+        <div class="card-body">
+            {}
+        </div>
+    </div>
+
+    <div class="card">
+        <div class="card-header">
+            <h3>{rate_limit_heading}</h3>
+        </div>
+        <div class="card-body">
+            <div class="setting-row">
+                <span class="setting-label">Requests per Minute</span>
+                <span class="setting-value">{}</span>
+            </div>
+            <div class="setting-row">
+                <span class="setting-label">Feedback per Hour</span>
+                <span class="setting-value">{}</span>
+            </div>
+        </div>
+    </div>
+"#,
+            two_factor,
+            label_settings,
+            timezone_settings,
+            app_state.config.github.username,
+            render_llm_settings(&app_state, style),
+            app_state.config.rate_limiting.requests_per_minute,
+            app_state.config.rate_limiting.feedback_per_hour,
+            heading = label_html("🔧 Settings", style),
+            two_factor_heading = label_html("🔢 Two-Factor Authentication", style),
+            accessibility_heading = label_html("🔤 Accessibility", style),
+            timezone_heading = label_html("🕰️ Time Zone", style),
+            github_heading = label_html("🐙 GitHub Integration", style),
+            token_configured = label_html("✓ Configured", style),
+            llm_heading = label_html("🤖 LLM Providers", style),
+            rate_limit_heading = label_html("🚦 Rate Limiting", style),
+        ),
+        style,
+    ))
+    .into_response()
+}
+
+/// 🤖 The LLM providers card: credentials, health over the last day, and the default
+fn render_llm_settings(app_state: &AppState, style: LabelStyle) -> String {
+    let llm = &app_state.config.llm;
+    let settings = app_state.settings.get();
+    let report = HealthReport::from_settings(settings.llm_health.as_ref());
+    let mut rows = String::new();
+    for provider in LlmProvider::ALL {
+        let configured = app_state.llm.is_configured(&provider);
+        let health = match report.as_ref().and_then(|report| report.get(&provider)) {
+            Some(health) => format!(
+                "{:.0}% ok · p95 {:.1}s · {:.0}% timeouts · score {:.2} · {} calls",
+                health.success_rate * 100.0,
+                health.p95_latency_ms as f64 / 1000.0,
+                health.timeout_rate * 100.0,
+                health.score,
+                health.calls
+            ),
+            None => format!("No calls in the last {}h", HEALTH_WINDOW_HOURS),
+        };
+        rows.push_str(&format!(
+            r#"<div class="setting-row">
+                <span class="setting-label">{}</span>
+                <span class="provider-health">{}</span>
+                <span class="setting-status {}">{}</span>
+            </div>
+            "#,
+            provider.label(),
+            health,
+            if configured {
+                "status-ok"
+            } else {
+                "status-warn"
+            },
+            label_html(
+                if configured {
+                    "✓ Configured"
+                } else {
+                    "⚠ Not configured"
+                },
+                style
+            )
+        ));
+    }
+
+    let current = app_state.llm.default_provider();
+    let switched = if current != llm.default_provider {
+        format!(
+            " (switched at runtime, LLM_DEFAULT_PROVIDER is {})",
+            llm.default_provider.label()
+        )
+    } else {
+        String::new()
+    };
+    rows.push_str(&format!(
+        r#"<div class="setting-row">
+                <span class="setting-label">Default Provider</span>
+                <span class="setting-value">{}{}</span>
+            </div>
+            <div class="setting-row">
+                <span class="setting-label">Health Floor</span>
+                <span class="setting-value">{:.0}% success, {}</span>
+            </div>"#,
+        current.label(),
+        switched,
+        llm.health_success_floor * 100.0,
+        if llm.auto_switch {
+            "switches automatically"
+        } else {
+            "suggests a switch"
+        }
+    ));
+    rows
+}
+
+/// 🩺 GET /admin/api/llm/health - per-provider health over the last day as JSON
+pub async fn admin_llm_health_api(State(app_state): State<AppState>, jar: CookieJar) -> Response {
+    if let Some(response) = require_admin_api_auth(&jar, &app_state).await {
+        return response;
+    }
+    let settings = app_state.settings.get();
+    let report = HealthReport::from_settings(settings.llm_health.as_ref());
+    let providers: Vec<serde_json::Value> = LlmProvider::ALL
+        .iter()
+        .map(|provider| {
+            serde_json::json!({
+                "provider": provider.as_str(),
+                "configured": app_state.llm.is_configured(provider),
+                "health": report.as_ref().and_then(|report| report.get(provider)),
+            })
+        })
+        .collect();
+    (
+        StatusCode::OK,
+        Json(crate::api::ApiResponse::success(
+            "LLM provider health".to_string(),
+            serde_json::json!({
+                "computed_at": report.as_ref().map(|report| report.computed_at),
+                "window_hours": HEALTH_WINDOW_HOURS,
+                "default_provider": app_state.llm.default_provider().as_str(),
+                "configured_default_provider": app_state.config.llm.default_provider.as_str(),
+                "success_floor": app_state.config.llm.health_success_floor,
+                "auto_switch": app_state.config.llm.auto_switch,
+                "providers": providers,
+            }),
+        )),
+    )
+        .into_response()
+}
+
+/// 🤖 MCP Analytics Page
+pub async fn admin_mcp(
+    State(app_state): State<AppState>,
+    jar: CookieJar,
+    Query(query): Query<crate::api::mcp::McpStatsQuery>,
+) -> Response {
+    if let Some(redirect) = require_admin_auth(&jar, &app_state).await {
+        return redirect;
+    }
+    info!("🔧 Admin MCP page accessed");
+    let tz = admin_timezone(&app_state, &jar).await;
+
+    let user_agent = query.user_agent();
+    let stats = get_mcp_stats(&app_state, user_agent)
+        .await
+        .unwrap_or_else(|e| {
+            warn!("❌ Failed to load MCP stats: {:#}", e);
+            McpStats::default()
+        });
+    let current_version = get_setting(&app_state, "smart_tree_latest_version")
+        .await
+        .unwrap_or_else(|| "Not set".to_string());
+    let style = label_style(&app_state, &jar);
+
+    Html(render_admin_page(
+        "MCP Analytics - Feedbacker Admin",
+        "/admin/mcp",
+        &format!(
+            r#"
+    <div class="header">
+        <h2>{heading}</h2>
+    </div>
+
+    <div class="stats-grid">
+        <div class="stat-card">
+            <h3>Total Checks</h3>
+            <div class="value">{}</div>
+        </div>
+        <div class="stat-card">
+            <h3>Current Version</h3>
+            <div class="value small">{}</div>
+        </div>
+    </div>
+
+    <div class="card">
+        <div class="card-header">
+            <h3>{version_heading}</h3>
+        </div>
+        <div class="card-body">
+            <form method="POST" action="/admin/mcp/set-version">
+                <div class="form-group">
+                    <label for="version">Version (e.g., 0.9.0)</label>
+                    <input type="text" id="version" name="version" placeholder="0.9.0" required>
+                </div>
+                <div class="form-group">
+                    <label for="release_notes">Release Notes</label>
+                    <input type="text" id="release_notes" name="release_notes" placeholder="New features and improvements...">
+                </div>
+                <div class="form-group">
+                    <label for="new_features">New Features (comma-separated)</label>
+                    <input type="text" id="new_features" name="new_features" placeholder="Quantum mode, Faster scans">
+                </div>
+                <button type="submit" class="btn">Update Version</button>
+            </form>
+        </div>
+    </div>
+
+    <div class="card">
+        <div class="card-header">
+            <h3>{export_heading}</h3>
+        </div>
+        <div class="card-body">
+            <form method="GET" action="/admin/api/mcp/analytics/export.csv" class="inline-form">
+                <input type="date" name="from" aria-label="From">
+                <input type="date" name="to" aria-label="To">
+                <input type="text" name="country" placeholder="Country (e.g. DE)" maxlength="2">
+                <button type="submit" class="btn">Download</button>
+            </form>
+        </div>
+    </div>
+
+    <div class="card">
+        <div class="card-header">
+            <h3>{platforms_heading}</h3>
+        </div>
+        <div class="card-body">
+            {}
+        </div>
+    </div>
+
+    <div class="card">
+        <div class="card-header">
+            <h3>{versions_heading}</h3>
+        </div>
+        <div class="card-body">
+            {}
+        </div>
+    </div>
+
+    <div class="card">
+        <div class="card-header">
+            <h3>{locations_heading}</h3>
+        </div>
+        <div class="card-body">
+            {}
+        </div>
+    </div>
+
+    <div class="card">
+        <div class="card-header">
+            <h3>{user_agents_heading}</h3>
+        </div>
+        <div class="card-body">
+            {}
+        </div>
+    </div>
+
+    <div class="card" id="recent-checks">
+        <div class="card-header">
+            <h3>{recent_heading}</h3>
+        </div>
+        <div class="card-body">
+            <form method="GET" action="/admin/mcp#recent-checks" class="inline-form">
+                <input type="text" name="user_agent" value="{}" placeholder="Exact User-Agent" aria-label="User-Agent">
+                <button type="submit" class="btn">Filter</button>
+                {}
+            </form>
+            {}
+        </div>
+    </div>
+"#,
+        stats.total_checks,
+        current_version,
+        render_platform_table(&stats.platforms),
+        render_version_table(&stats.versions),
+        render_locations_table(&stats.locations),
+        render_user_agents_table(&stats.user_agents),
+        html_escape(user_agent.unwrap_or_default()),
+        if user_agent.is_some() {
+            r#"<a href="/admin/mcp#recent-checks">Clear</a>"#
+        } else {
+            ""
+        },
+        render_recent_checks_table(&stats.recent_checks, &tz),
+        heading = label_html("🤖 MCP Analytics", style),
+        version_heading = label_html("🔧 Set Smart Tree Version", style),
+        export_heading = label_html("📤 Export Checks (CSV)", style),
+        platforms_heading = label_html("📊 Platform Distribution", style),
+        versions_heading = label_html("📈 Version Distribution", style),
+        locations_heading = label_html("🌍 Location Distribution", style),
+        user_agents_heading = label_html("🕵️ Top User Agents", style),
+        recent_heading = label_html("🕐 Recent Checks", style),
+    ), style)).into_response()
+}
+
+/// 🔧 Set Smart Tree version (admin POST handler)
+#[derive(Debug, Deserialize)]
+pub struct SetVersionForm {
+    pub version: String,
+    pub release_notes: Option<String>,
+    /// ✨ Comma-separated headline features
+    pub new_features: Option<String>,
+}
+
+pub async fn admin_mcp_set_version(
+    State(app_state): State<AppState>,
+    jar: CookieJar,
+    Form(form): Form<SetVersionForm>,
+) -> Response {
+    if let Some(redirect) = require_admin_auth(&jar, &app_state).await {
+        return redirect;
+    }
+    info!("🔧 Setting Smart Tree version to: {}", form.version);
+
+    // Save version to settings
+    let _ = set_setting(&app_state, "smart_tree_latest_version", &form.version).await;
+    let notes = form.release_notes.filter(|notes| !notes.is_empty());
+    if let Some(notes) = &notes {
+        let _ = set_setting(&app_state, "smart_tree_release_notes", notes).await;
+    }
+    let features: Option<Vec<String>> = form
+        .new_features
+        .map(|features| {
+            features
+                .split(',')
+                .map(str::trim)
+                .filter(|feature| !feature.is_empty())
+                .map(str::to_string)
+                .collect::<Vec<_>>()
+        })
+        .filter(|features| !features.is_empty());
+    if let Some(features) = &features {
+        if let Ok(value) = serde_json::to_string(features) {
+            let _ = set_setting(
+                &app_state,
+                crate::api::settings_cache::NEW_FEATURES_KEY,
+                &value,
+            )
+            .await;
+        }
+    }
+    // 📦 Assets of the previous release would point at the wrong files
+    let _ = sqlx::query("DELETE FROM settings WHERE key = $1")
+        .bind(crate::api::settings_cache::RELEASE_DOWNLOADS_KEY)
+        .execute(&app_state.db_pool)
+        .await;
+    // 📜 Keep the history for /mcp/changelog
+    if let Err(e) = crate::api::releases::record_release(
+        &app_state.db_pool,
+        crate::api::downloads::DEFAULT_PRODUCT,
+        &form.version,
+        notes.as_deref(),
+        features.as_deref(),
+    )
+    .await
+    {
+        warn!("⚠️ Failed to record release {}: {:#}", form.version, e);
+    }
+    // ⚡ /mcp/check reads the settings cache, so publish the new version right away
+    if let Err(e) = app_state.settings.reload(&app_state.db_pool).await {
+        warn!("⚠️ Failed to refresh runtime settings: {:#}", e);
+    }
+
+    Redirect::to("/admin/mcp").into_response()
+}
+
+// MCP Stats structures
+#[derive(Debug, Default)]
+struct McpStats {
+    total_checks: i64,
+    platforms: Vec<(String, String, i64)>, // (platform, arch, count)
+    versions: Vec<(String, i64)>,          // (version, count)
+    locations: Vec<(String, String, i64)>, // (city, country, count)
+    user_agents: Vec<(Option<String>, i64)>, // (user agent, count)
+    recent_checks: Vec<RecentMcpCheck>,
+}
+
+#[derive(Debug)]
+struct RecentMcpCheck {
+    version: String,
+    platform: String,
+    arch: String,
+    city: Option<String>,
+    country: Option<String>,
+    user_agent: Option<String>,
+    integration: Option<String>,
+    checked_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// 📊 MCP analytics for the admin page (recent checks optionally from one User-Agent)
+async fn get_mcp_stats(app_state: &AppState, user_agent: Option<&str>) -> anyhow::Result<McpStats> {
+    let total_checks: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM mcp_analytics")
+        .fetch_one(&app_state.db_pool)
+        .await?;
+
+    let platform_rows = sqlx::query(
+        "SELECT platform, arch, COUNT(*) as count FROM mcp_analytics GROUP BY platform, arch ORDER BY count DESC LIMIT 20"
+    )
+    .fetch_all(&app_state.db_pool)
+    .await?;
+
+    let platforms: Vec<(String, String, i64)> = platform_rows
+        .iter()
+        .map(|row| {
+            Ok((
+                row.try_get("platform")?,
+                row.try_get("arch")?,
+                row.try_get("count")?,
+            ))
+        })
+        .collect::<Result<_, sqlx::Error>>()
+        .context("Failed to read platform row")?;
+
+    let version_rows = sqlx::query(
+        "SELECT client_version, COUNT(*) as count FROM mcp_analytics GROUP BY client_version ORDER BY count DESC LIMIT 20"
+    )
+    .fetch_all(&app_state.db_pool)
+    .await?;
+
+    let versions: Vec<(String, i64)> = version_rows
+        .iter()
+        .map(|row| Ok((row.try_get("client_version")?, row.try_get("count")?)))
+        .collect::<Result<_, sqlx::Error>>()
+        .context("Failed to read version row")?;
+
+    // Location distribution
+    let location_rows = sqlx::query(
+        r#"
+        SELECT COALESCE(city, 'Unknown') as city, COALESCE(country, 'Unknown') as country, COUNT(*) as count
+        FROM mcp_analytics
+        WHERE city IS NOT NULL OR country IS NOT NULL
+        GROUP BY city, country
+        ORDER BY count DESC
+        LIMIT 20
+        "#
+    )
+    .fetch_all(&app_state.db_pool)
+    .await?;
+
+    let locations: Vec<(String, String, i64)> = location_rows
+        .iter()
+        .map(|row| {
+            Ok((
+                row.try_get("city")?,
+                row.try_get("country")?,
+                row.try_get("count")?,
+            ))
+        })
+        .collect::<Result<_, sqlx::Error>>()
+        .context("Failed to read location row")?;
+
+    let user_agent_rows = sqlx::query(
+        "SELECT user_agent, COUNT(*) as count FROM mcp_analytics GROUP BY user_agent ORDER BY count DESC, user_agent LIMIT 20"
+    )
+    .fetch_all(&app_state.db_pool)
+    .await?;
+
+    let user_agents: Vec<(Option<String>, i64)> = user_agent_rows
+        .iter()
+        .map(|row| Ok((row.try_get("user_agent")?, row.try_get("count")?)))
+        .collect::<Result<_, sqlx::Error>>()
+        .context("Failed to read user agent row")?;
+
+    let recent_rows = sqlx::query(
+        r#"
+        SELECT client_version, platform, arch, city, country, user_agent, integration, checked_at
+        FROM mcp_analytics
+        WHERE ($1::text IS NULL OR user_agent = $1)
+        ORDER BY checked_at DESC, id DESC
+        LIMIT 20
+        "#,
+    )
+    .bind(user_agent)
+    .fetch_all(&app_state.db_pool)
+    .await?;
+
+    let recent_checks: Vec<RecentMcpCheck> = recent_rows
+        .iter()
+        .map(|row| {
+            Ok(RecentMcpCheck {
+                version: row.try_get("client_version")?,
+                platform: row.try_get("platform")?,
+                arch: row.try_get("arch")?,
+                city: row.try_get("city")?,
+                country: row.try_get("country")?,
+                user_agent: row.try_get("user_agent")?,
+                integration: row.try_get("integration")?,
+                checked_at: row.try_get("checked_at")?,
+            })
+        })
+        .collect::<Result<_, sqlx::Error>>()
+        .context("Failed to read recent check row")?;
+
+    Ok(McpStats {
+        total_checks,
+        platforms,
+        versions,
+        locations,
+        user_agents,
+        recent_checks,
+    })
+}
+
+async fn get_setting(app_state: &AppState, key: &str) -> Option<String> {
+    sqlx::query_scalar::<_, String>("SELECT value FROM settings WHERE key = $1")
+        .bind(key)
+        .fetch_optional(&app_state.db_pool)
+        .await
+        .ok()
+        .flatten()
+}
+
+async fn set_setting(app_state: &AppState, key: &str, value: &str) -> anyhow::Result<()> {
+    sqlx::query(
+        "INSERT INTO settings (key, value, updated_at) VALUES ($1, $2, NOW()) ON CONFLICT (key) DO UPDATE SET value = $2, updated_at = NOW()"
+    )
+    .bind(key)
+    .bind(value)
+    .execute(&app_state.db_pool)
+    .await?;
+    Ok(())
+}
+
+fn render_platform_table(platforms: &[(String, String, i64)]) -> String {
+    if platforms.is_empty() {
+        return r#"<div class="empty-state">No data yet</div>"#.to_string();
+    }
+
+    let rows: String = platforms
+        .iter()
+        .map(|(platform, arch, count)| {
+            format!(
+                r#"<tr><td>{}</td><td>{}</td><td>{}</td></tr>"#,
+                platform, arch, count
+            )
+        })
+        .collect();
+
+    format!(
+        r#"<table><thead><tr><th>Platform</th><th>Arch</th><th>Count</th></tr></thead><tbody>{}</tbody></table>"#,
+        rows
+    )
+}
+
+fn render_version_table(versions: &[(String, i64)]) -> String {
+    if versions.is_empty() {
+        return r#"<div class="empty-state">No data yet</div>"#.to_string();
+    }
+
+    let rows: String = versions
+        .iter()
+        .map(|(version, count)| format!(r#"<tr><td>{}</td><td>{}</td></tr>"#, version, count))
+        .collect();
+
+    format!(
+        r#"<table><thead><tr><th>Version</th><th>Count</th></tr></thead><tbody>{}</tbody></table>"#,
+        rows
+    )
+}
+
+fn render_recent_checks_table(checks: &[RecentMcpCheck], tz: &TimeZone) -> String {
+    if checks.is_empty() {
+        return r#"<div class="empty-state">No checks yet</div>"#.to_string();
+    }
+
+    let rows: String = checks
+        .iter()
+        .map(|c| {
+            let location = match (&c.city, &c.country) {
+                (Some(city), Some(country)) => format!("{}, {}", city, country),
+                (None, Some(country)) => country.clone(),
+                (Some(city), None) => city.clone(),
+                (None, None) => "-".to_string(),
+            };
+            format!(
+                r#"<tr><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td></tr>"#,
+                c.version,
+                c.platform,
+                c.arch,
+                location,
+                html_escape(c.user_agent.as_deref().unwrap_or("-")),
+                html_escape(c.integration.as_deref().unwrap_or("-")),
+                fmt_ts(c.checked_at, tz)
+            )
+        })
+        .collect();
+
+    format!(
+        r#"<table><thead><tr><th>Version</th><th>Platform</th><th>Arch</th><th>Location</th><th>User-Agent</th><th>Integration</th><th>Time</th></tr></thead><tbody>{}</tbody></table>"#,
+        rows
+    )
+}
+
+/// 🕵️ Checks per User-Agent, each linking to its recent checks
+fn render_user_agents_table(user_agents: &[(Option<String>, i64)]) -> String {
+    if user_agents.is_empty() {
+        return r#"<div class="empty-state">No data yet</div>"#.to_string();
+    }
+
+    let rows: String = user_agents
+        .iter()
+        .map(|(user_agent, count)| {
+            let label = match user_agent {
+                Some(user_agent) => format!(
+                    r#"<a href="/admin/mcp?user_agent={}#recent-checks">{}</a>"#,
+                    crate::api::tags::encode_query_value(user_agent),
+                    html_escape(user_agent)
+                ),
+                None => "(none sent)".to_string(),
+            };
+            format!(r#"<tr><td>{}</td><td>{}</td></tr>"#, label, count)
+        })
+        .collect();
+
+    format!(
+        r#"<table><thead><tr><th>User-Agent</th><th>Count</th></tr></thead><tbody>{}</tbody></table>"#,
+        rows
+    )
+}
+
+fn render_locations_table(locations: &[(String, String, i64)]) -> String {
+    if locations.is_empty() {
+        return r#"<div class="empty-state">No location data yet. Install GeoLite2-City.mmdb to enable geo tracking.</div>"#.to_string();
+    }
+
+    let rows: String = locations
+        .iter()
+        .map(|(city, country, count)| {
+            format!(
+                r#"<tr><td>{}</td><td>{}</td><td>{}</td></tr>"#,
+                city, country, count
+            )
+        })
+        .collect();
+
+    format!(
+        r#"<table><thead><tr><th>City</th><th>Country</th><th>Count</th></tr></thead><tbody>{}</tbody></table>"#,
+        rows
+    )
+}
+
+// Helper functions
+
+/// 📊 Dashboard counts for rows created at or after `since` (None = all time), including
+/// completed feedback that retention has since removed
+pub(crate) async fn get_dashboard_stats(
+    app_state: &AppState,
+    since: Option<chrono::DateTime<chrono::Utc>>,
+) -> anyhow::Result<DashboardStats> {
+    let total_users: i64 = sqlx::query_scalar(
+        "SELECT COUNT(*) FROM users WHERE ($1::timestamptz IS NULL OR created_at >= $1)",
+    )
+    .bind(since)
+    .fetch_one(&app_state.db_pool)
+    .await?;
+
+    let total_projects: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM projects")
+        .fetch_one(&app_state.db_pool)
+        .await?;
+
+    let row = sqlx::query(
+        r#"
+        SELECT COUNT(*) AS total,
+               COUNT(*) FILTER (WHERE status = $2) AS pending,
+               COUNT(*) FILTER (WHERE status = $3) AS completed,
+               COUNT(*) FILTER (WHERE status = $4) AS failed
+        FROM feedback
+        WHERE ($1::timestamptz IS NULL OR created_at >= $1)
+        "#,
+    )
+    .bind(since)
+    .bind(FeedbackStatus::Pending)
+    .bind(FeedbackStatus::Completed)
+    .bind(FeedbackStatus::Failed)
+    .fetch_one(&app_state.db_pool)
+    .await?;
+    let rolled_up = crate::jobs::retention::rolled_up_count(&app_state.db_pool, since).await?;
+
+    Ok(DashboardStats {
+        total_users,
+        total_projects,
+        total_feedback: row.try_get::<i64, _>("total")? + rolled_up,
+        pending_feedback: row.try_get("pending")?,
+        completed_feedback: row.try_get::<i64, _>("completed")? + rolled_up,
+        failed_feedback: row.try_get("failed")?,
+    })
+}
+
+/// 📦 Repositories with the most feedback created at or after `since` (rollup included)
+async fn get_top_repositories(
+    app_state: &AppState,
+    since: Option<chrono::DateTime<chrono::Utc>>,
+    limit: i64,
+) -> anyhow::Result<Vec<RepositoryStats>> {
+    let rows = sqlx::query(
+        r#"
+        SELECT repository, SUM(total)::bigint AS total, SUM(completed)::bigint AS completed
+        FROM (
+            SELECT repository,
+                   COUNT(*) AS total,
+                   COUNT(*) FILTER (WHERE status = $2) AS completed
+            FROM feedback
+            WHERE ($1::timestamptz IS NULL OR created_at >= $1)
+            GROUP BY repository
+            UNION ALL
+            SELECT repository, SUM(feedback_count), SUM(feedback_count)
+            FROM feedback_rollup
+            WHERE ($1::timestamptz IS NULL OR created_date >= ($1 AT TIME ZONE 'UTC')::date)
+            GROUP BY repository
+        ) counts
+        GROUP BY repository
+        ORDER BY total DESC, repository
+        LIMIT $3
+        "#,
+    )
+    .bind(since)
+    .bind(FeedbackStatus::Completed)
+    .bind(limit)
+    .fetch_all(&app_state.db_pool)
+    .await?;
+
+    rows.iter()
+        .map(|row| {
+            Ok(RepositoryStats {
+                repository: row.try_get("repository")?,
+                total_feedback: row.try_get("total")?,
+                completed_feedback: row.try_get("completed")?,
+            })
+        })
+        .collect::<Result<_, sqlx::Error>>()
+        .context("Failed to read repository stats row")
+}
+
+/// 🗄️ SQL behind the feedback lists. Only the first 51 characters of the content are
+/// read (enough for the 50-character preview and its "..."), never the whole submission.
+/// Vote counts come from a lateral subquery so `votes` can be sorted and resumed on.
+fn feedback_list_sql(sort: FeedbackSort, dir: SortDir) -> String {
+    format!(
+        r#"
+        SELECT id, repository, source, status, priority, votes, created_at,
+            {}::text AS sort_key,
+            LEFT(content, 51) AS content_head,
+            ARRAY(SELECT a.id FROM attachments a WHERE a.feedback_id = feedback.id ORDER BY a.created_at, a.id) AS attachment_ids,
+            ARRAY(SELECT a.filename FROM attachments a WHERE a.feedback_id = feedback.id ORDER BY a.created_at, a.id) AS attachment_names
+        FROM feedback
+        CROSS JOIN LATERAL (
+            SELECT COUNT(*) AS votes FROM feedback_votes v WHERE v.feedback_id = feedback.id
+        ) vote_counts
+        WHERE ($2::timestamptz IS NULL OR created_at >= $2)
+          AND ($3::text IS NULL OR EXISTS (
+              SELECT 1 FROM feedback_tags t WHERE t.feedback_id = feedback.id AND t.tag = $3
+          ))
+          AND ($4::text IS NULL OR source = $4)
+          AND ($8::text IS NULL OR status::text = $8)
+          AND ($11::text IS NULL OR LOWER(repository) = LOWER($11))
+          AND ($12::text IS NULL OR STRPOS(LOWER(content), LOWER($12)) > 0)
+          AND created_at BETWEEN COALESCE($9::timestamptz, '-infinity') AND COALESCE($10::timestamptz, 'infinity')
+          AND ($6::timestamptz IS NULL OR {})
+        ORDER BY {} LIMIT $1
+        "#,
+        sort.key_column(),
+        sort.keyset(dir),
+        sort.order_by(dir)
+    )
+}
+
+/// 📋 Up to `limit` feedback rows in the given order, starting after `after`
+async fn get_recent_feedback(
+    app_state: &AppState,
+    limit: i64,
+    since: Option<chrono::DateTime<chrono::Utc>>,
+    (sort, dir): (FeedbackSort, SortDir),
+    filter: FeedbackFilter<'_>,
+    after: Option<&FeedbackCursor>,
+) -> anyhow::Result<FeedbackPage> {
+    let (created_from, created_to) = filter.created_between();
+    // ➕ One extra row tells us whether there is a next page
+    let mut rows = sqlx::query(&feedback_list_sql(sort, dir))
+        .bind(limit + 1)
+        .bind(since)
+        .bind(filter.tag)
+        .bind(filter.source)
+        .bind(after.map(|cursor| cursor.key.as_str()))
+        .bind(after.map(|cursor| cursor.created_at))
+        .bind(after.map(|cursor| cursor.id))
+        .bind(filter.status)
+        .bind(created_from)
+        .bind(created_to)
+        .bind(filter.repository)
+        .bind(filter.search)
+        .fetch_all(&app_state.db_pool)
+        .await?;
+
+    let next_cursor = if rows.len() as i64 > limit {
+        rows.truncate(limit as usize);
+        let last = rows.last().context("Feedback page without rows")?;
+        Some(
+            FeedbackCursor {
+                order: FeedbackCursor::order(sort, dir),
+                key: last.try_get("sort_key")?,
+                created_at: last.try_get("created_at")?,
+                id: last.try_get("id")?,
+            }
+            .encode(),
+        )
+    } else {
+        None
+    };
+
+    let items = rows
+        .iter()
+        .map(|row| {
+            let content_head: String = row.try_get("content_head")?;
+            let attachment_ids: Vec<uuid::Uuid> = row.try_get("attachment_ids")?;
+            let attachment_names: Vec<String> = row.try_get("attachment_names")?;
+            let status: FeedbackStatus = row.try_get("status")?;
+            Ok(FeedbackItem {
+                id: row.try_get::<uuid::Uuid, _>("id")?.to_string(),
+                repository: row.try_get("repository")?,
+                source: row.try_get("source")?,
+                status_class: status.css_class(),
+                status,
+                priority: row.try_get("priority")?,
+                votes: row.try_get("votes")?,
+                created_at: row.try_get("created_at")?,
+                content_preview: content_head.chars().take(50).collect::<String>()
+                    + if content_head.chars().count() > 50 {
+                        "..."
+                    } else {
+                        ""
+                    },
+                attachments: attachment_ids.into_iter().zip(attachment_names).collect(),
+            })
+        })
+        .collect::<Result<_, sqlx::Error>>()
+        .context("Failed to read feedback row")?;
+
+    Ok(FeedbackPage { items, next_cursor })
+}
+
+/// 📡 GET /admin/api/feedback - the feedback list as JSON, a page at a time. Takes the
+/// feedback page's query (`range`, `sort`, `dir`, `tag`, `source`, `status`, `repo`, `q`,
+/// `from`, `to`) plus `cursor`.
+pub async fn admin_feedback_api(
+    State(app_state): State<AppState>,
+    Query(query): Query<DashboardQuery>,
+    jar: CookieJar,
+) -> Response {
+    if let Some(denied) = require_admin_api_auth(&jar, &app_state).await {
+        return denied;
+    }
+    let range = DashboardRange::from_param(query.range.as_deref());
+    let (sort, dir) = FeedbackSort::from_query(query.sort.as_deref(), query.dir.as_deref());
+    let after = match query.cursor.as_deref() {
+        None => None,
+        Some(cursor) => match FeedbackCursor::decode(cursor, sort, dir) {
+            Some(after) => Some(after),
+            None => {
+                return crate::api::utils::validation_error(vec![
+                    "cursor is not valid for this ordering".to_string(),
+                ])
+                .into_response()
+            }
+        },
+    };
+    let tag = query
+        .tag
+        .as_deref()
+        .and_then(crate::api::tags::normalize_tag);
+    let source = query
+        .source
+        .as_deref()
+        .and_then(crate::api::sources::normalize_source);
+    let status = status_filter(query.status.as_deref());
+    let (from, to) = date_window(query.from.as_deref(), query.to.as_deref());
+    let filter = FeedbackFilter {
+        tag: tag.as_deref(),
+        source: source.as_deref(),
+        status: status.as_ref().map(FeedbackStatus::as_str),
+        repository: text_filter(query.repo.as_deref()),
+        search: text_filter(query.q.as_deref()),
+        from,
+        to,
+    };
+
+    match get_recent_feedback(
+        &app_state,
+        FEEDBACK_API_PAGE_ROWS,
+        range.cutoff(chrono::Utc::now()),
+        (sort, dir),
+        filter,
+        after.as_ref(),
+    )
+    .await
+    {
+        Ok(page) => (
+            StatusCode::OK,
+            Json(crate::api::ApiResponse::success(
+                format!("{} feedback items", page.items.len()),
+                page,
+            )),
+        )
+            .into_response(),
+        Err(e) => crate::api::utils::handle_error(e).into_response(),
+    }
+}
+
+/// 📋 Feedback table; with a list state the sortable headers become sort links. Columns the
+/// admin has hidden are left out of both the header and the rows.
+fn render_feedback_table(
+    feedback: &[FeedbackItem],
+    sorting: Option<FeedbackListState>,
+    hidden: &HiddenColumns,
+    tz: &TimeZone,
+    style: LabelStyle,
+) -> String {
+    if feedback.is_empty() {
+        return empty_state("📭 No feedback yet", style);
+    }
+    let columns: Vec<FeedbackColumn> = FeedbackColumn::ALL
+        .into_iter()
+        .filter(|column| !hidden.is_hidden(*column))
+        .collect();
+
+    let rows: String = feedback
+        .iter()
+        .map(|f| {
+            let cells: String = columns
+                .iter()
+                .map(|column| match column {
+                    FeedbackColumn::Id => format!(
+                        r#"<td><a href="/admin/feedback/{}" class="repo-link"><code>{}</code></a></td>"#,
+                        f.id,
+                        &f.id[..8]
+                    ),
+                    FeedbackColumn::Repository => {
+                        format!("<td>{}</td>", html_escape(&f.repository))
+                    }
+                    FeedbackColumn::Source => {
+                        // 🔗 Narrow the list to this source, keeping whatever else is selected
+                        let (range, filter) = sorting
+                            .map(|state| (state.range, state.filter))
+                            .unwrap_or((DashboardRange::All, FeedbackFilter::default()));
+                        format!(
+                            r#"<td><a href="{}" class="repo-link">{}</a></td>"#,
+                            html_escape(&FeedbackSort::Created.link_filtered(
+                                range,
+                                FeedbackFilter {
+                                    source: Some(&f.source),
+                                    ..filter
+                                }
+                            )),
+                            html_escape(&f.source)
+                        )
+                    }
+                    FeedbackColumn::Status => format!(
+                        r#"<td><span class="status {}">{}</span></td>"#,
+                        f.status.css_class(),
+                        f.status
+                    ),
+                    FeedbackColumn::Priority => format!("<td>{}</td>", f.priority),
+                    FeedbackColumn::Votes => format!("<td>{}</td>", f.votes),
+                    FeedbackColumn::Created => format!("<td>{}</td>", fmt_ts(f.created_at, tz)),
+                    FeedbackColumn::Content => {
+                        format!("<td>{}</td>", html_escape(&f.content_preview))
+                    }
+                    FeedbackColumn::Attachments => format!(
+                        "<td>{}</td>",
+                        f.attachments
+                            .iter()
+                            .map(|(id, filename)| format!(
+                                r#"<a href="/admin/feedback/{}/attachments/{}" class="attachment-link">{}</a>"#,
+                                f.id,
+                                id,
+                                html_escape(filename)
+                            ))
+                            .collect::<Vec<_>>()
+                            .join("<br>")
+                    ),
+                })
+                .collect();
+            format!("<tr>{}</tr>", cells)
+        })
+        .collect();
+
+    let headers: String = columns
+        .iter()
+        .map(|column| match (sorting, column.sort()) {
+            (Some(state), Some(sort)) if state.sort == sort => {
+                let (arrow, aria) = match state.dir {
+                    SortDir::Asc => ("↑", "ascending"),
+                    SortDir::Desc => ("↓", "descending"),
+                };
+                format!(
+                    r#"<th aria-sort="{}"><a href="{}" class="sort-link active">{} {}</a></th>"#,
+                    aria,
+                    html_escape(&sort.link_directed(
+                        state.dir.reversed(),
+                        state.range,
+                        state.filter
+                    )),
+                    column.label(),
+                    arrow
+                )
+            }
+            (Some(state), Some(sort)) => format!(
+                r#"<th><a href="{}" class="sort-link">{}</a></th>"#,
+                html_escape(&sort.link_filtered(state.range, state.filter)),
+                column.label()
+            ),
+            _ => format!("<th>{}</th>", column.label()),
+        })
+        .collect();
+
+    // ➡️ A plain link to the next server-rendered page, which the table script (when it
+    // runs) turns into in-place loading from the JSON API
+    let (range, filter) = sorting
+        .map(|state| (state.range, state.filter))
+        .unwrap_or((DashboardRange::All, FeedbackFilter::default()));
+    let source_base = FeedbackSort::Created.link_filtered(
+        range,
+        FeedbackFilter {
+            source: None,
+            ..filter
+        },
+    );
+    let (next_attr, more) = match sorting.and_then(|state| {
+        state
+            .next_cursor
+            .map(|cursor| (state.sort.link_directed(state.dir, range, filter), cursor))
+    }) {
+        Some((page_link, cursor)) => {
+            let api_link = page_link.replacen("/admin/feedback", "/admin/api/feedback", 1);
+            (
+                format!(
+                    r#" data-next="{}""#,
+                    html_escape(&with_cursor(&api_link, cursor))
+                ),
+                format!(
+                    r#"
+        <p class="load-more"><a id="feedback-more" href="{}" class="btn btn-primary">More →</a></p>
+        <script src="{}" defer></script>"#,
+                    html_escape(&with_cursor(&page_link, cursor)),
+                    assets::feedback_table_js_url()
+                ),
+            )
+        }
+        None => (String::new(), String::new()),
+    };
+
+    format!(
+        r#"<table data-columns="{}" data-source-base="{}" data-timezone="{}" data-more-link="feedback-more"{}>
+            <thead>
+                <tr>{}</tr>
+            </thead>
+            <tbody>{}</tbody>
+        </table>{}"#,
+        columns
+            .iter()
+            .map(FeedbackColumn::as_param)
+            .collect::<Vec<_>>()
+            .join(","),
+        html_escape(&source_base),
+        html_escape(tz.name()),
+        next_attr,
+        headers,
+        rows,
+        more
+    )
+}
+
+/// 👁️ Column picker for the feedback table (saved per browser by POST /admin/feedback/columns)
+fn render_column_picker(hidden: &HiddenColumns, return_to: &str) -> String {
+    let checkboxes: String = FeedbackColumn::ALL
+        .iter()
+        .map(|column| {
+            format!(
+                r#"<label><input type="checkbox" name="show" value="{}"{}> {}</label>
+"#,
+                column.as_param(),
+                if hidden.is_hidden(*column) {
+                    ""
+                } else {
+                    " checked"
+                },
+                column.label()
+            )
+        })
+        .collect();
+    format!(
+        r#"<details class="column-picker">
+                <summary>Columns</summary>
+                <form method="POST" action="/admin/feedback/columns">
+                    <input type="hidden" name="return_to" value="{}">
+                    {}<button type="submit" class="btn btn-primary">Save</button>
+                </form>
+            </details>"#,
+        html_escape(return_to),
+        checkboxes
+    )
+}
+
+/// 👁️ Remember which feedback table columns this browser shows
+pub async fn admin_feedback_columns(
+    State(app_state): State<AppState>,
+    jar: CookieJar,
+    Form(fields): Form<Vec<(String, String)>>,
+) -> Response {
+    if let Some(redirect) = require_admin_auth(&jar, &app_state).await {
+        return redirect;
+    }
+    // 📝 `show` repeats once per ticked box, so read the raw pairs
+    let field = |name: &'static str| {
+        fields
+            .iter()
+            .filter(move |(key, _)| key == name)
+            .map(|(_, value)| value.as_str())
+    };
+    let hidden = HiddenColumns(
+        FeedbackColumn::ALL
+            .into_iter()
+            .filter(|column| !field("show").any(|name| name == column.as_param()))
+            .collect(),
+    );
+    // 🔒 Only ever send the admin back to the feedback page
+    let return_to = field("return_to")
+        .next()
+        .filter(|path| path.starts_with("/admin/feedback") && !path.contains("//"))
+        .unwrap_or("/admin/feedback");
+
+    let cookie = Cookie::build((FEEDBACK_COLUMNS_COOKIE, hidden.to_cookie_value()))
+        .path("/admin")
+        .http_only(true)
+        .secure(app_state.config.is_production())
+        .max_age(time::Duration::days(365))
+        .build();
+    (jar.add(cookie), Redirect::to(return_to)).into_response()
+}
+
+// 🧪 Tests - Knocking on the admin door with and without a key!
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::github::CodeImprovement;
+    use crate::test_support::{spawn_test_app, TestApp, TEST_ADMIN_USERNAME};
+    use axum::http::StatusCode;
+
+    #[test]
+    fn test_dashboard_range_parsing_and_links() {
+        assert_eq!(DashboardRange::from_param(Some("7d")), DashboardRange::Week);
+        assert_eq!(DashboardRange::from_param(Some("24h")), DashboardRange::Day);
+        assert_eq!(DashboardRange::from_param(Some("1y")), DashboardRange::All);
+        assert_eq!(DashboardRange::from_param(None), DashboardRange::All);
+        assert_eq!(DashboardRange::Month.link("/admin"), "/admin?range=30d");
+        assert_eq!(DashboardRange::All.link("/admin"), "/admin");
+
+        let now = chrono::Utc::now();
+        assert_eq!(DashboardRange::All.cutoff(now), None);
+        let cutoff = DashboardRange::Day.cutoff(now).unwrap();
+        assert!(now - cutoff >= chrono::Duration::hours(24));
+        assert_eq!(cutoff.timestamp_subsec_nanos() % 1_000, 0);
+        println!("✅ Dashboard range test passed!");
+    }
+
+    #[tokio::test]
+    async fn test_dashboard_range_includes_rows_exactly_at_cutoff() {
+        let Some(app) = spawn_test_app().await else {
+            return;
+        };
+        let cutoff = DashboardRange::Week.cutoff(chrono::Utc::now()).unwrap();
+        let just_before = cutoff - chrono::Duration::microseconds(1);
+
+        for (email, created_at) in [
+            ("at@example.com", cutoff),
+            ("before@example.com", just_before),
+        ] {
+            sqlx::query(
+                "INSERT INTO users (email, name, password_hash, created_at) VALUES ($1, 'U', 'x', $2)",
+            )
+            .bind(email)
+            .bind(created_at)
+            .execute(&app.db_pool)
+            .await
+            .unwrap();
+        }
+        for (repository, status, created_at) in [
+            ("8b-is/smart-tree", FeedbackStatus::Completed, cutoff),
+            ("8b-is/smart-tree", FeedbackStatus::Pending, cutoff),
+            ("8b-is/mem8", FeedbackStatus::Completed, just_before),
+        ] {
+            sqlx::query(
+                "INSERT INTO feedback (repository, content, status, created_at) VALUES ($1, 'Hi', $2, $3)",
+            )
+            .bind(repository)
+            .bind(status)
+            .bind(created_at)
+            .execute(&app.db_pool)
+            .await
+            .unwrap();
+        }
+
+        let week = get_dashboard_stats(&app.app_state, Some(cutoff))
+            .await
+            .unwrap();
+        assert_eq!(week.total_users, 1);
+        assert_eq!(week.total_feedback, 2);
+        assert_eq!(week.completed_feedback, 1);
+        assert_eq!(week.pending_feedback, 1);
+
+        let all = get_dashboard_stats(&app.app_state, None).await.unwrap();
+        assert_eq!(all.total_users, 2);
+        assert_eq!(all.total_feedback, 3);
+
+        let repositories = get_top_repositories(&app.app_state, Some(cutoff), 10)
+            .await
+            .unwrap();
+        assert_eq!(repositories.len(), 1);
+        assert_eq!(repositories[0].repository, "8b-is/smart-tree");
+        assert_eq!(repositories[0].completion_rate(), 50.0);
+
+        let recent = get_recent_feedback(
+            &app.app_state,
+            10,
+            Some(cutoff),
+            (FeedbackSort::Created, SortDir::Desc),
+            FeedbackFilter::default(),
+            None,
+        )
+        .await
+        .unwrap()
+        .items;
+        assert_eq!(recent.len(), 2);
+        println!("✅ Dashboard cutoff boundary test passed!");
+    }
+
+    #[tokio::test]
+    async fn test_dashboard_range_persists_and_is_cached_per_range() {
+        let Some(app) = spawn_test_app().await else {
+            return;
+        };
+        app.login_admin().await.unwrap();
+
+        let html = app
+            .client
+            .get(app.url("/admin?range=7d"))
+            .send()
+            .await
+            .unwrap()
+            .text()
+            .await
+            .unwrap();
+        assert!(html.contains("Last 7 days"));
+        assert!(html.contains(r#"href="/admin/feedback?range=7d""#));
+        assert!(html.contains(r#"href="/admin?range=30d" class="range-option""#));
+        assert!(html.contains(&format!(
+            r#"<p class="build" title="{}">"#,
+            crate::build_info::GIT_SHA
+        )));
+
+        // 🗃️ Each range gets its own cache entry
+        assert!(app
+            .app_state
+            .dashboard_cache
+            .get(DashboardRange::Week)
+            .is_some());
+        assert!(app
+            .app_state
+            .dashboard_cache
+            .get(DashboardRange::All)
+            .is_none());
+        app.client.get(app.url("/admin")).send().await.unwrap();
+        assert!(app
+            .app_state
+            .dashboard_cache
+            .get(DashboardRange::All)
+            .is_some());
+        println!("✅ Dashboard range persistence test passed!");
+    }
+
+    /// 🎫 Pull the pre-auth token out of the second-step login page
+    fn pre_auth_token(html: &str) -> String {
+        let start = html.find(r#"name="token" value=""#).unwrap() + r#"name="token" value=""#.len();
+        html[start..start + html[start..].find('"').unwrap()].to_string()
+    }
+
+    /// 🔢 Switch a subject's second factor on directly, returning its secret and backup codes
+    async fn enable_second_factor(app: &TestApp, subject: SessionSubject) -> (String, Vec<String>) {
+        let secret = second_factors::start_enrollment(&app.db_pool, subject)
+            .await
+            .unwrap()
+            .unwrap();
+        let activated = second_factors::activate(&app.db_pool, subject, &secret, 0)
+            .await
+            .unwrap()
+            .unwrap();
+        // ⚡ What the activation handler does for the console admin
+        app.app_state.settings.reload(&app.db_pool).await.unwrap();
+        (secret, activated.backup_codes)
+    }
+
+    #[tokio::test]
+    async fn test_totp_login_flow_and_backup_codes() {
+        let Some(app) = spawn_test_app().await else {
+            return;
+        };
+        let response = app.login_admin().await.unwrap();
+        assert_eq!(response.status(), StatusCode::SEE_OTHER);
+        let (secret, backup) = enable_second_factor(&app, SessionSubject::Bootstrap).await;
+
+        // 🍪 The session from before 2FA was switched on is over...
+        let response = app.client.get(app.url("/admin")).send().await.unwrap();
+        assert_eq!(response.status(), StatusCode::SEE_OTHER);
+
+        // 🔑 ...and the password alone no longer opens the dashboard
+        let page = app.login_admin().await.unwrap();
+        assert_eq!(page.status(), StatusCode::OK);
+        let token = pre_auth_token(&page.text().await.unwrap());
+        let response = app.client.get(app.url("/admin")).send().await.unwrap();
+        assert_eq!(response.status(), StatusCode::SEE_OTHER);
+
+        let submit = |code: String, token: String| {
+            let client = app.client.clone();
+            let url = app.url("/admin/login/totp");
+            async move {
+                client
+                    .post(url)
+                    .form(&[("token", token), ("code", code)])
+                    .send()
+                    .await
+                    .unwrap()
+            }
+        };
+
+        // 🚫 Wrong code and forged token are both turned away
+        let response = submit("000000".to_string(), token.clone()).await;
+        assert!(response.text().await.unwrap().contains("Invalid code"));
+        let response = submit("000000".to_string(), "1.forged".to_string()).await;
+        assert!(response.text().await.unwrap().contains("sign-in expired"));
+
+        // ✅ A current code logs in...
+        let now = chrono::Utc::now().timestamp() as u64;
+        let code = crate::auth::totp::code_at(&secret, now).unwrap();
+        let response = submit(code.clone(), token.clone()).await;
+        assert_eq!(response.status(), StatusCode::SEE_OTHER);
+        let response = app.client.get(app.url("/admin")).send().await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        // 🎫 ...and spends the challenge, whatever code comes with it next
+        let response = submit(backup[1].clone(), token.clone()).await;
+        assert!(response.text().await.unwrap().contains("sign-in expired"));
+
+        // 🔁 ...but the same code can't be replayed
+        app.client
+            .get(app.url("/admin/logout"))
+            .send()
+            .await
+            .unwrap();
+        let token = pre_auth_token(&app.login_admin().await.unwrap().text().await.unwrap());
+        let response = submit(code, token.clone()).await;
+        assert!(response.text().await.unwrap().contains("Invalid code"));
+
+        // 🎟️ Backup codes work exactly once
+        let response = submit(backup[0].clone(), token.clone()).await;
+        assert_eq!(response.status(), StatusCode::SEE_OTHER);
+        app.client
+            .get(app.url("/admin/logout"))
+            .send()
+            .await
+            .unwrap();
+        let token = pre_auth_token(&app.login_admin().await.unwrap().text().await.unwrap());
+        let response = submit(backup[0].clone(), token).await;
+        assert!(response.text().await.unwrap().contains("Invalid code"));
+
+        let audited: i64 = sqlx::query_scalar(
+            "SELECT COUNT(*) FROM admin_audit_log WHERE action = 'admin_login_backup_code'",
+        )
+        .fetch_one(&app.db_pool)
+        .await
+        .unwrap();
+        assert_eq!(audited, 1);
+        println!("✅ TOTP login flow test passed!");
+    }
+
+    #[tokio::test]
+    async fn test_wrong_codes_spend_the_challenge_and_lock_the_account() {
+        let Some(app) = spawn_test_app().await else {
+            return;
+        };
+        let (secret, _) = enable_second_factor(&app, SessionSubject::Bootstrap).await;
+        let submit = |token: &str, code: &str| {
+            let request = app
+                .client
+                .post(app.url("/admin/login/totp"))
+                .form(&[("token", token), ("code", code)]);
+            async move { request.send().await.unwrap().text().await.unwrap() }
+        };
+        let challenge =
+            || async { pre_auth_token(&app.login_admin().await.unwrap().text().await.unwrap()) };
+
+        // 🎯 Each challenge takes a few guesses, then it is spent
+        let token = challenge().await;
+        for left in (1..second_factors::MAX_CHALLENGE_FAILURES).rev() {
+            let page = submit(&token, "000000").await;
+            assert!(page.contains(&format!("Invalid code ({} attempts left)", left)));
+        }
+        assert!(submit(&token, "000000").await.contains("sign-in expired"));
+        let code =
+            crate::auth::totp::code_at(&secret, chrono::Utc::now().timestamp() as u64).unwrap();
+        assert!(submit(&token, &code).await.contains("sign-in expired"));
+
+        // 🔒 Fresh challenges don't reset the account's count: past the limit even the
+        // right code is refused
+        let token = challenge().await;
+        for _ in
+            0..second_factors::MAX_SUBJECT_FAILURES - second_factors::MAX_CHALLENGE_FAILURES as i64
+        {
+            submit(&token, "000000").await;
+        }
+        let token = challenge().await;
+        assert!(submit(&token, &code).await.contains("Too many wrong codes"));
+        let response = app.client.get(app.url("/admin")).send().await.unwrap();
+        assert_eq!(response.status(), StatusCode::SEE_OTHER);
+        println!("✅ Login challenge limits test passed!");
+    }
+
+    #[tokio::test]
+    async fn test_projects_page_shows_config_versions_and_migrates_old_ones() {
+        let Some(app) = spawn_test_app().await else {
+            return;
+        };
+        let owner_id: uuid::Uuid = sqlx::query_scalar(
+            "INSERT INTO users (email, name, password_hash) VALUES ('configs@example.com', 'Configs', 'x') RETURNING id",
+        )
+        .fetch_one(&app.db_pool)
+        .await
+        .unwrap();
+        let mut ids = Vec::new();
+        for (repository, config) in [
+            (
+                "acme/legacy",
+                serde_json::json!({ "allowed_paths": ["src/**"], "comment_footer": null }),
+            ),
+            (
+                "acme/future",
+                serde_json::json!({ "config_version": CURRENT_CONFIG_VERSION + 1 }),
+            ),
+        ] {
+            let id: uuid::Uuid = sqlx::query_scalar(
+                "INSERT INTO projects (owner_id, repository, config) VALUES ($1, $2, $3) RETURNING id",
+            )
+            .bind(owner_id)
+            .bind(repository)
+            .bind(config)
+            .fetch_one(&app.db_pool)
+            .await
+            .unwrap();
+            ids.push(id);
+        }
+        let (legacy, future) = (ids[0], ids[1]);
+        app.login_admin().await.unwrap();
+
+        let page = app
+            .client
+            .get(app.url("/admin/projects"))
+            .send()
+            .await
+            .unwrap()
+            .text()
+            .await
+            .unwrap();
+        assert!(page.contains(&format!("/admin/projects/{}/config/migrate", legacy)));
+        assert!(!page.contains(&format!("/admin/projects/{}/config/migrate", future)));
+        assert!(page.contains("automation paused"));
+
+        // 🪜 Migrating rewrites the legacy config at the current version, and is audited
+        for id in [legacy, future] {
+            let response = app
+                .client
+                .post(app.url(&format!("/admin/projects/{}/config/migrate", id)))
+                .send()
+                .await
+                .unwrap();
+            assert_eq!(response.status(), StatusCode::SEE_OTHER);
+        }
+        let stored = |id: uuid::Uuid| {
+            sqlx::query_scalar::<_, serde_json::Value>("SELECT config FROM projects WHERE id = $1")
+                .bind(id)
+                .fetch_one(&app.db_pool)
+        };
+        assert_eq!(
+            stored(legacy).await.unwrap(),
+            serde_json::json!({
+                "config_version": CURRENT_CONFIG_VERSION,
+                "paths": { "allowed": ["src/**"] },
+                "comments": { "footer": null },
+            })
+        );
+        // ⏭️ ...but a config from a newer build is never downgraded
+        assert_eq!(
+            stored(future).await.unwrap(),
+            serde_json::json!({ "config_version": CURRENT_CONFIG_VERSION + 1 })
+        );
+        let audited: i64 = sqlx::query_scalar(
+            "SELECT COUNT(*) FROM admin_audit_log WHERE action = 'project_config_migrated'",
+        )
+        .fetch_one(&app.db_pool)
+        .await
+        .unwrap();
+        assert_eq!(audited, 1);
+
+        let page = app
+            .client
+            .get(app.url("/admin/projects"))
+            .send()
+            .await
+            .unwrap()
+            .text()
+            .await
+            .unwrap();
+        assert!(!page.contains(&format!("/admin/projects/{}/config/migrate", legacy)));
+        println!("✅ Project config migration page test passed!");
+    }
+
+    #[tokio::test]
+    async fn test_threshold_holds_feedback_until_released() {
+        let Some(app) = spawn_test_app().await else {
+            return;
+        };
+        let owner_id: uuid::Uuid = sqlx::query_scalar(
+            "INSERT INTO users (email, name, password_hash) VALUES ('holds@example.com', 'Holds', 'x') RETURNING id",
+        )
+        .fetch_one(&app.db_pool)
+        .await
+        .unwrap();
+        let project_id: uuid::Uuid = sqlx::query_scalar(
+            "INSERT INTO projects (owner_id, repository) VALUES ($1, '8b-is/smart-tree') RETURNING id",
+        )
+        .bind(owner_id)
+        .fetch_one(&app.db_pool)
+        .await
+        .unwrap();
+        app.login_admin().await.unwrap();
+        let set_threshold = |min_score: &'static str| {
+            app.client
+                .post(app.url(&format!("/admin/projects/{}/threshold", project_id)))
+                .form(&[("min_score", min_score)])
+                .send()
+        };
+        let stored = || {
+            sqlx::query_scalar::<_, serde_json::Value>("SELECT config FROM projects WHERE id = $1")
+                .bind(project_id)
+                .fetch_one(&app.db_pool)
+        };
+
+        // 📏 Setting the threshold writes it into the project's config
+        assert_eq!(
+            set_threshold("15").await.unwrap().status(),
+            StatusCode::SEE_OTHER
+        );
+        assert_eq!(
+            stored().await.unwrap()["processing"],
+            serde_json::json!({ "min_score": 15 })
+        );
+        // 🚫 Out of range is ignored
+        set_threshold("500").await.unwrap();
+        assert_eq!(
+            stored().await.unwrap()["processing"],
+            serde_json::json!({ "min_score": 15 })
+        );
+
+        let feedback = Feedback::create(
+            &app.db_pool,
+            None,
+            "8b-is/smart-tree".to_string(),
+            "Capitalise the footer".to_string(),
+            None,
+            2,
+            None,
+            "cli",
+        )
+        .await
+        .unwrap();
+        let mut conn = app.db_pool.acquire().await.unwrap();
+        processing_holds::hold_if_below_threshold(&mut conn, feedback.id, "8b-is/smart-tree", 2)
+            .await
+            .unwrap()
+            .unwrap();
+        drop(conn);
+        let page = || async {
+            app.client
+                .get(app.url("/admin/projects"))
+                .send()
+                .await
+                .unwrap()
+                .text()
+                .await
+                .unwrap()
+        };
+        let release = format!("/admin/feedback/{}/release", feedback.id);
+        let listed = page().await;
+        assert!(listed.contains(r#"name="min_score" min="1" max="100" value="15""#));
+        assert!(listed.contains(&release));
+        assert!(listed.contains("2 &lt; 15"));
+
+        // ▶️ "Release hold" lifts the hold, leaving an ordinary pending item
+        let response = app.client.post(app.url(&release)).send().await.unwrap();
+        assert_eq!(response.status(), StatusCode::SEE_OTHER);
+        assert_eq!(
+            processing_holds::find(&app.db_pool, feedback.id)
+                .await
+                .unwrap(),
+            None
+        );
+        let released = Feedback::find_by_id(&app.db_pool, feedback.id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(released.status, FeedbackStatus::Pending);
+        assert!(!page().await.contains(&release));
+
+        // 🧹 An empty threshold turns holding off again
+        set_threshold("").await.unwrap();
+        assert!(stored().await.unwrap().get("processing").is_none());
+        let audited: Vec<String> = sqlx::query_scalar(
+            "SELECT action FROM admin_audit_log WHERE action IN ('project_threshold_changed', 'feedback_hold_released') ORDER BY created_at, id",
+        )
+        .fetch_all(&app.db_pool)
+        .await
+        .unwrap();
+        assert_eq!(
+            audited,
+            vec![
+                "project_threshold_changed",
+                "feedback_hold_released",
+                "project_threshold_changed"
+            ]
+        );
+        println!("✅ Processing threshold admin test passed!");
+    }
+
+    #[tokio::test]
+    async fn test_projects_page_flags_missing_webhooks_and_retries() {
+        let Some(app) = spawn_test_app().await else {
+            return;
+        };
+        app.login_admin().await.unwrap();
+        let projects_page = || async {
+            app.client
+                .get(app.url("/admin/projects"))
+                .send()
+                .await
+                .unwrap()
+                .text()
+                .await
+                .unwrap()
+        };
+
+        // 🛑 Adding works even when GitHub refuses the hook
+        *app.github.fail_with.lock().unwrap() = Some("Resource not accessible".to_string());
+        app.client
+            .post(app.url("/admin/projects/add"))
+            .form(&[("repository", "8b-is/smart-tree")])
+            .send()
+            .await
+            .unwrap();
+        let project_id: uuid::Uuid =
+            sqlx::query_scalar("SELECT id FROM projects WHERE repository = '8b-is/smart-tree'")
+                .fetch_one(&app.db_pool)
+                .await
+                .unwrap();
+        let page = projects_page().await;
+        assert!(page.contains("webhook not installed"));
+        assert!(page.contains("Resource not accessible"));
+        assert!(page.contains(&format!("/admin/projects/{}/webhook", project_id)));
+
+        // 🔁 Retry installs it
+        *app.github.fail_with.lock().unwrap() = None;
+        app.client
+            .post(app.url(&format!("/admin/projects/{}/webhook", project_id)))
+            .send()
+            .await
+            .unwrap();
+        assert!(!projects_page().await.contains("webhook not installed"));
+        assert!(matches!(
+            app.github.calls().as_slice(),
+            [crate::test_support::GitHubCall::CreateHook { .. }]
+        ));
+
+        // 🗑️ Removing the project takes the hook with it, and is audited
+        app.client
+            .post(app.url(&format!("/admin/projects/{}/delete", project_id)))
+            .send()
+            .await
+            .unwrap();
+        assert!(projects_page().await.contains("No projects yet"));
+        assert!(matches!(
+            app.github.calls().last(),
+            Some(crate::test_support::GitHubCall::DeleteHook { .. })
+        ));
+        let audited: i64 = sqlx::query_scalar(
+            "SELECT COUNT(*) FROM admin_audit_log WHERE action = 'project_removed'",
+        )
+        .fetch_one(&app.db_pool)
+        .await
+        .unwrap();
+        assert_eq!(audited, 1);
+        println!("✅ Project webhook admin page test passed!");
+    }
+
+    #[tokio::test]
+    async fn test_projects_page_routes_further_repositories_to_the_project() {
+        let Some(app) = spawn_test_app().await else {
+            return;
+        };
+        let owner_id: uuid::Uuid = sqlx::query_scalar(
+            "INSERT INTO users (email, name, password_hash) VALUES ('mono@example.com', 'Mono', 'x') RETURNING id",
+        )
+        .fetch_one(&app.db_pool)
+        .await
+        .unwrap();
+        let project_id: uuid::Uuid = sqlx::query_scalar(
+            "INSERT INTO projects (owner_id, repository, config) VALUES ($1, '8b-is/smart-tree', $2) RETURNING id",
+        )
+        .bind(owner_id)
+        .bind(serde_json::json!({ "config_version": 3, "comments": { "footer": "— Tree Bot" } }))
+        .fetch_one(&app.db_pool)
+        .await
+        .unwrap();
+        app.login_admin().await.unwrap();
+        let change = |action: &'static str, repository: &'static str| {
+            app.client
+                .post(app.url(&format!("/admin/projects/{}/{}", project_id, action)))
+                .form(&[("repository", repository)])
+                .send()
+        };
+
+        assert_eq!(
+            change("repositories", " 8b-is/smart-tree-vscode ")
+                .await
+                .unwrap()
+                .status(),
+            StatusCode::SEE_OTHER
+        );
+        change("repositories", "not a repo").await.unwrap();
+        let page = app
+            .client
+            .get(app.url("/admin/projects"))
+            .send()
+            .await
+            .unwrap()
+            .text()
+            .await
+            .unwrap();
+        assert!(page.contains(r#"value="8b-is/smart-tree-vscode""#));
+        assert!(!page.contains("not a repo"));
+
+        // ⚙️ Feedback from the extra repository gets the project's config
+        let config =
+            project_config::ProjectConfig::for_repository(&app.db_pool, "8b-is/smart-tree-vscode")
+                .await
+                .unwrap()
+                .unwrap();
+        assert_eq!(config.comments.footer, Some(Some("— Tree Bot".to_string())));
+
+        change("repositories/remove", "8b-is/smart-tree-vscode")
+            .await
+            .unwrap();
+        assert!(project_config::ProjectConfig::for_repository(
+            &app.db_pool,
+            "8b-is/smart-tree-vscode"
+        )
+        .await
+        .unwrap()
+        .is_none());
+        let audited: Vec<String> = sqlx::query_scalar(
+            "SELECT action FROM admin_audit_log WHERE action LIKE 'project_repository_%' ORDER BY action",
+        )
+        .fetch_all(&app.db_pool)
+        .await
+        .unwrap();
+        assert_eq!(
+            audited,
+            ["project_repository_added", "project_repository_removed"]
+        );
+        println!("✅ Project repositories admin page test passed!");
+    }
+
+    #[tokio::test]
+    async fn test_totp_enrollment_requires_code_and_is_audited() {
+        let Some(app) = spawn_test_app().await else {
+            return;
+        };
+        app.login_admin().await.unwrap();
+
+        let page = app
+            .client
+            .post(app.url("/admin/settings/totp/enroll"))
+            .send()
+            .await
+            .unwrap()
+            .text()
+            .await
+            .unwrap();
+        assert!(page.contains("<svg"));
+        assert!(page.contains("otpauth://totp/Feedbacker:admin"));
+        let factor = || async {
+            sqlx::query_as::<_, (Option<String>, Option<String>)>(
+                "SELECT secret, pending_secret FROM admin_second_factors WHERE subject = 'config'",
+            )
+            .fetch_optional(&app.db_pool)
+            .await
+            .unwrap()
+        };
+        let pending = factor().await.unwrap().1.unwrap();
+
+        // 🚫 A bad code leaves 2FA off
+        let page = app
+            .client
+            .post(app.url("/admin/settings/totp/activate"))
+            .form(&[("code", "000000")])
+            .send()
+            .await
+            .unwrap()
+            .text()
+            .await
+            .unwrap();
+        assert!(page.contains("didn't match"));
+        assert_eq!(factor().await.unwrap().0, None);
+
+        // ✅ A good one turns it on and shows ten backup codes
+        let code =
+            crate::auth::totp::code_at(&pending, chrono::Utc::now().timestamp() as u64).unwrap();
+        let page = app
+            .client
+            .post(app.url("/admin/settings/totp/activate"))
+            .form(&[("code", code.as_str())])
+            .send()
+            .await
+            .unwrap()
+            .text()
+            .await
+            .unwrap();
+        assert_eq!(page.matches("<li><code>").count(), 10);
+        assert_eq!(factor().await.unwrap().0, Some(pending.clone()));
+
+        // 🍪 The admin who switched it on got a session signed over the new factor
+        let response = app.client.get(app.url("/admin")).send().await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        // 🔓 Disabling needs a code too (the enrollment code was already spent)
+        let first_backup = {
+            let start = page.find("<li><code>").unwrap() + "<li><code>".len();
+            page[start..start + 9].to_string()
+        };
+        app.client
+            .post(app.url("/admin/settings/totp/disable"))
+            .form(&[("code", first_backup.as_str())])
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(factor().await, None);
+        let response = app.client.get(app.url("/admin")).send().await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let actions: Vec<String> =
+            sqlx::query_scalar("SELECT action FROM admin_audit_log ORDER BY created_at, id")
+                .fetch_all(&app.db_pool)
+                .await
+                .unwrap();
+        assert_eq!(actions, vec!["admin_totp_enabled", "admin_totp_disabled"]);
+        println!("✅ TOTP enrollment test passed!");
+    }
+
+    #[tokio::test]
+    async fn test_totp_cannot_be_re_enrolled_while_active() {
+        let Some(app) = spawn_test_app().await else {
+            return;
+        };
+        let (active, _) = enable_second_factor(&app, SessionSubject::Bootstrap).await;
+        let token = pre_auth_token(&app.login_admin().await.unwrap().text().await.unwrap());
+        let now = chrono::Utc::now().timestamp() as u64;
+        let code = crate::auth::totp::code_at(&active, now).unwrap();
+        app.client
+            .post(app.url("/admin/login/totp"))
+            .form(&[("token", token), ("code", code)])
+            .send()
+            .await
+            .unwrap();
+        let factor = || async {
+            sqlx::query_as::<_, (Option<String>, Option<String>)>(
+                "SELECT secret, pending_secret FROM admin_second_factors WHERE subject = 'config'",
+            )
+            .fetch_one(&app.db_pool)
+            .await
+            .unwrap()
+        };
+
+        // 🚫 No new secret is handed out...
+        let response = app
+            .client
+            .post(app.url("/admin/settings/totp/enroll"))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::SEE_OTHER);
+        assert_eq!(factor().await, (Some(active.clone()), None));
+
+        // 🚫 ...and one left pending from earlier can't replace the active one
+        let stale = crate::auth::totp::generate_secret();
+        sqlx::query("UPDATE admin_second_factors SET pending_secret = $1 WHERE subject = 'config'")
+            .bind(&stale)
+            .execute(&app.db_pool)
+            .await
+            .unwrap();
+        let stale_code = crate::auth::totp::code_at(&stale, now).unwrap();
+        let response = app
+            .client
+            .post(app.url("/admin/settings/totp/activate"))
+            .form(&[("code", stale_code.as_str())])
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::SEE_OTHER);
+        assert_eq!(factor().await.0, Some(active.clone()));
+        let response = app.client.get(app.url("/admin")).send().await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        println!("✅ TOTP re-enrollment test passed!");
+    }
+
+    #[tokio::test]
+    async fn test_feedback_list_sorts_by_priority() {
+        let Some(app) = spawn_test_app().await else {
+            return;
+        };
+        assert_eq!(
+            FeedbackSort::Priority.link(DashboardRange::Week),
+            "/admin/feedback?range=7d&sort=priority"
+        );
+        assert_eq!(
+            FeedbackSort::Priority.link(DashboardRange::All),
+            "/admin/feedback?sort=priority"
+        );
+        assert_eq!(
+            FeedbackSort::from_param(Some("bogus")),
+            FeedbackSort::Created
+        );
+
+        for (repository, priority) in [("8b-is/low", 4), ("8b-is/high", 81), ("8b-is/mid", 30)] {
+            sqlx::query(
+                "INSERT INTO feedback (repository, content, priority) VALUES ($1, 'Hi', $2)",
+            )
+            .bind(repository)
+            .bind(priority)
+            .execute(&app.db_pool)
+            .await
+            .unwrap();
+        }
+        app.login_admin().await.unwrap();
+
+        let page = |query: &'static str| {
+            let client = app.client.clone();
+            let url = app.url(&format!("/admin/feedback{}", query));
+            async move { client.get(url).send().await.unwrap().text().await.unwrap() }
+        };
+        let position = |html: &str, needle: &str| html.find(needle).unwrap();
+
+        let by_priority = page("?sort=priority").await;
+        assert!(position(&by_priority, "8b-is/high") < position(&by_priority, "8b-is/mid"));
+        assert!(position(&by_priority, "8b-is/mid") < position(&by_priority, "8b-is/low"));
+        assert!(by_priority.contains(r#"class="sort-link active">Priority ↓</a>"#));
+        assert!(by_priority.contains("<td>81</td>"));
+
+        let newest = page("").await;
+        assert!(position(&newest, "8b-is/mid") < position(&newest, "8b-is/low"));
+        assert!(newest.contains(r#"class="sort-link active">Created ↓</a>"#));
+        println!("✅ Priority sorting test passed!");
+    }
+
+    #[tokio::test]
+    async fn test_feedback_list_filters_by_tag_and_dashboard_shows_cloud() {
+        let Some(app) = spawn_test_app().await else {
+            return;
+        };
+        assert_eq!(
+            FeedbackSort::Priority.link_tagged(DashboardRange::Week, Some("c++")),
+            "/admin/feedback?range=7d&sort=priority&tag=c%2B%2B"
+        );
+
+        for (repository, tags) in [
+            ("8b-is/tagged-ui", vec!["ui", "dark-mode"]),
+            ("8b-is/tagged-api", vec!["api"]),
+            ("8b-is/untagged", vec![]),
+        ] {
+            let id: uuid::Uuid = sqlx::query_scalar(
+                "INSERT INTO feedback (repository, content) VALUES ($1, 'Hi') RETURNING id",
+            )
+            .bind(repository)
+            .fetch_one(&app.db_pool)
+            .await
+            .unwrap();
+            let tags: Vec<String> = tags.into_iter().map(str::to_string).collect();
+            crate::api::tags::store_tags(&app.db_pool, id, &tags)
+                .await
+                .unwrap();
+        }
+        app.login_admin().await.unwrap();
+
+        let page = |path: &'static str| {
+            let client = app.client.clone();
+            let url = app.url(path);
+            async move { client.get(url).send().await.unwrap().text().await.unwrap() }
+        };
+
+        // 🏷️ The filter normalizes what was typed, just like submission does
+        let filtered = page("/admin/feedback?tag=Dark%20Mode&sort=priority").await;
+        assert!(filtered.contains("8b-is/tagged-ui"));
+        assert!(!filtered.contains("8b-is/tagged-api"));
+        assert!(!filtered.contains("8b-is/untagged"));
+        assert!(filtered.contains(r#"<span class="tag-chip">dark-mode</span>"#));
+        assert!(filtered.contains("/admin/feedback?tag=dark-mode"));
+
+        let dashboard = page("/admin").await;
+        assert!(dashboard.contains(r#"<span aria-hidden="true">🏷️</span> Top Tags"#));
+        assert!(dashboard.contains(r#"href="/admin/feedback?tag=api""#));
+        println!("✅ Tag filter and cloud test passed!");
+    }
+
+    #[tokio::test]
+    async fn test_feedback_list_filters_by_source_and_dashboard_breaks_it_down() {
+        let Some(app) = spawn_test_app().await else {
+            return;
+        };
+        let request =
+            |repository: &str, source: Option<&str>| crate::api::feedback::SubmitFeedbackRequest {
+                repository: repository.to_string(),
+                content: "Where did this one come from?".to_string(),
+                llm_provider: None,
+                metadata: None,
+                user_info: None,
+                callback_url: None,
+                impact_score: None,
+                frequency_score: None,
+                priority: None,
+                tags: Some(vec!["ui".to_string()]),
+                source: source.map(str::to_string),
+                challenge: None,
+            };
+        // 📡 Explicit source wins, then the User-Agent guess, then "unknown"
+        for (repository, source, user_agent) in [
+            ("8b-is/from-bot", Some(" Acme-Bot "), Some("curl/8.4.0")),
+            ("8b-is/from-cli", None, Some("smart-tree/5.2.0")),
+            ("8b-is/from-nowhere", None, Some("python-requests/2.31")),
+        ] {
+            crate::api::feedback::create_feedback_record(
+                &app.app_state,
+                request(repository, source),
+                None,
+                user_agent,
+            )
+            .await
+            .unwrap();
+        }
+        let stored: Vec<(String, String)> =
+            sqlx::query_as("SELECT repository, source FROM feedback ORDER BY repository")
+                .fetch_all(&app.db_pool)
+                .await
+                .unwrap();
+        assert_eq!(
+            stored,
+            vec![
+                ("8b-is/from-bot".to_string(), "acme-bot".to_string()),
+                ("8b-is/from-cli".to_string(), "cli".to_string()),
+                ("8b-is/from-nowhere".to_string(), "unknown".to_string()),
+            ]
+        );
+        assert!(request("8b-is/x", Some("web form"))
+            .validate_with_limit(1000)
+            .is_err());
+        app.login_admin().await.unwrap();
+
+        let page = |path: &'static str| {
+            let client = app.client.clone();
+            let url = app.url(path);
+            async move { client.get(url).send().await.unwrap().text().await.unwrap() }
+        };
+
+        let filtered = page("/admin/feedback?source=CLI&tag=ui").await;
+        assert!(filtered.contains("8b-is/from-cli"));
+        assert!(!filtered.contains("8b-is/from-bot"));
+        assert!(!filtered.contains("8b-is/from-nowhere"));
+        assert!(filtered.contains(r#"from <span class="tag-chip">cli</span>"#));
+        // ✖ Clearing the source keeps the tag, and vice versa
+        assert!(filtered.contains(
+            r#"href="/admin/feedback?tag=ui" class="muted"><span aria-hidden="true">✖</span> clear"#
+        ));
+        assert!(filtered.contains(r#"href="/admin/feedback?source=cli" class="muted"><span aria-hidden="true">✖</span> clear"#));
+
+        let dashboard = page("/admin").await;
+        assert!(dashboard.contains(r#"<span aria-hidden="true">📡</span> Sources"#));
+        assert!(dashboard.contains(r#"href="/admin/feedback?source=acme-bot""#));
+        assert!(dashboard.contains(r#"href="/admin/feedback?source=unknown""#));
+        println!("✅ Source filter and breakdown test passed!");
+    }
+
+    #[tokio::test]
+    async fn test_feedback_list_filters_by_date_window_and_presets() {
+        let Some(app) = spawn_test_app().await else {
+            return;
+        };
+        let at = |value: &str| value.parse::<chrono::DateTime<chrono::Utc>>().unwrap();
+        for (repository, status, created_at) in [
+            (
+                "8b-is/too-early",
+                FeedbackStatus::Pending,
+                at("2026-03-08T23:59:59.999999Z"),
+            ),
+            (
+                "8b-is/first-moment",
+                FeedbackStatus::Pending,
+                at("2026-03-09T00:00:00Z"),
+            ),
+            (
+                "8b-is/wrong-status",
+                FeedbackStatus::Failed,
+                at("2026-03-10T12:00:00Z"),
+            ),
+            (
+                "8b-is/last-moment",
+                FeedbackStatus::Pending,
+                at("2026-03-15T23:59:59.999999Z"),
+            ),
+            (
+                "8b-is/too-late",
+                FeedbackStatus::Pending,
+                at("2026-03-16T00:00:00Z"),
+            ),
+            (
+                "8b-is/just-now",
+                FeedbackStatus::Pending,
+                chrono::Utc::now(),
+            ),
+        ] {
+            sqlx::query(
+                "INSERT INTO feedback (repository, content, status, created_at) VALUES ($1, 'Hi', $2, $3)",
+            )
+            .bind(repository)
+            .bind(status)
+            .bind(created_at)
+            .execute(&app.db_pool)
+            .await
+            .unwrap();
+        }
+        app.login_admin().await.unwrap();
+        let page = |path: String| {
+            let client = app.client.clone();
+            let url = app.url(&path);
+            async move { client.get(url).send().await.unwrap().text().await.unwrap() }
+        };
+        let shown = |html: &str| {
+            [
+                "too-early",
+                "first-moment",
+                "wrong-status",
+                "last-moment",
+                "too-late",
+                "just-now",
+            ]
+            .into_iter()
+            .filter(|name| html.contains(&format!("8b-is/{}", name)))
+            .collect::<Vec<_>>()
+        };
+
+        // 📅 Both days are whole and included, and the status filter still applies
+        let html =
+            page("/admin/feedback?to=2026-03-15&from=2026-03-09&status=pending".into()).await;
+        assert_eq!(shown(&html), vec!["first-moment", "last-moment"]);
+        assert!(html.contains(
+            r#"created between <span class="tag-chip">2026-03-09</span> and <span class="tag-chip">2026-03-15</span>"#
+        ));
+        assert!(html.contains(r#"href="/admin/feedback?status=pending" class="muted"><span aria-hidden="true">✖</span> clear"#));
+        assert!(html.contains(r#"<input type="hidden" name="status" value="pending">"#));
+        assert!(html.contains(r#"name="from" value="2026-03-09""#));
+
+        // 🔄 A reversed pair is swapped, garbage is ignored, open ends work
+        assert_eq!(
+            shown(&page("/admin/feedback?from=2026-03-15&to=2026-03-09".into()).await),
+            vec!["first-moment", "wrong-status", "last-moment"]
+        );
+        assert_eq!(
+            shown(&page("/admin/feedback?from=2026-03-16&to=someday".into()).await),
+            vec!["too-late", "just-now"]
+        );
+
+        // ⚡ Presets set the window and light up when it matches
+        let today = chrono::Utc::now().date_naive();
+        let html = page(format!("/admin/feedback?from={}&to={}", today, today)).await;
+        assert_eq!(shown(&html), vec!["just-now"]);
+        assert!(html.contains(&format!(
+            r#"<a href="/admin/feedback?from={}&amp;to={}" class="range-option active">today</a>"#,
+            today, today
+        )));
+        assert!(html.contains(&format!(
+            r#"<a href="/admin/feedback?from={}&amp;to={}" class="range-option">7d</a>"#,
+            today - chrono::Duration::days(6),
+            today
+        )));
+
+        // 📡 The JSON pages take the same window
+        let body: serde_json::Value = app
+            .client
+            .get(app.url("/admin/api/feedback?from=2026-03-09&to=2026-03-09"))
+            .send()
+            .await
+            .unwrap()
+            .json()
+            .await
+            .unwrap();
+        assert_eq!(body["data"]["items"].as_array().unwrap().len(), 1);
+        assert_eq!(body["data"]["items"][0]["repository"], "8b-is/first-moment");
+        println!("✅ Feedback date window filter test passed!");
+    }
+
+    #[test]
+    fn test_feedback_sort_whitelist() {
+        assert_eq!(
+            FeedbackSort::from_query(Some("status"), Some("desc")),
+            (FeedbackSort::Status, SortDir::Desc)
+        );
+        assert_eq!(
+            FeedbackSort::from_query(Some("repository"), Some("sideways")),
+            (FeedbackSort::Repository, SortDir::Asc)
+        );
+        assert_eq!(
+            FeedbackSort::from_query(Some("votes"), None),
+            (FeedbackSort::Votes, SortDir::Desc)
+        );
+        // 🚫 Anything off the whitelist is created_at desc, whatever the direction says
+        for sort in [
+            "created_at; DROP TABLE feedback--",
+            "repository, (SELECT 1)",
+            "CREATED_AT",
+            "VOTES",
+            "",
+        ] {
+            assert_eq!(
+                FeedbackSort::from_query(Some(sort), Some("asc")),
+                (FeedbackSort::Created, SortDir::Desc)
+            );
+        }
+        // 🗄️ Every ORDER BY is built only from column names and ASC/DESC
+        for sort in FeedbackSort::ALL {
+            for dir in [SortDir::Asc, SortDir::Desc] {
+                assert!(sort
+                    .order_by(dir)
+                    .chars()
+                    .all(|c| c.is_ascii_alphabetic() || c == '_' || c == ',' || c == ' '));
+            }
+        }
+
+        assert_eq!(
+            FeedbackSort::Status.link_directed(
+                SortDir::Desc,
+                DashboardRange::Week,
+                FeedbackFilter::default()
+            ),
+            "/admin/feedback?range=7d&sort=status&dir=desc"
+        );
+        assert_eq!(
+            FeedbackSort::Created.link_directed(
+                SortDir::Asc,
+                DashboardRange::All,
+                FeedbackFilter {
+                    tag: Some("ui"),
+                    source: Some("cli"),
+                    status: Some("awaiting_approval"),
+                    repository: Some("8b-is/smart-tree"),
+                    search: Some("dark mode"),
+                    from: chrono::NaiveDate::from_ymd_opt(2026, 10, 9),
+                    to: chrono::NaiveDate::from_ymd_opt(2026, 10, 15),
+                }
+            ),
+            "/admin/feedback?dir=asc&tag=ui&source=cli&status=awaiting_approval&repo=8b-is%2Fsmart-tree&q=dark%20mode&from=2026-10-09&to=2026-10-15"
+        );
+        assert_eq!(
+            FeedbackSort::Repository.link(DashboardRange::All),
+            "/admin/feedback?sort=repository"
+        );
+        assert_eq!(
+            FeedbackSort::Votes.link(DashboardRange::All),
+            "/admin/feedback?sort=votes"
+        );
+        println!("✅ Feedback sort whitelist test passed!");
+    }
+
+    #[test]
+    fn test_hidden_columns_cookie_value_round_trip() {
+        let hidden = HiddenColumns(vec![FeedbackColumn::Id, FeedbackColumn::Content]);
+        assert_eq!(hidden.to_cookie_value(), "id,content");
+        assert_eq!(HiddenColumns::from_cookie_value("id,content"), hidden);
+        // 🧹 Unknown names and stray spaces are dropped
+        assert_eq!(
+            HiddenColumns::from_cookie_value(" content ,bogus,<script>"),
+            HiddenColumns(vec![FeedbackColumn::Content])
+        );
+        assert_eq!(
+            HiddenColumns::from_cookie_value(""),
+            HiddenColumns::default()
+        );
+        println!("✅ Hidden columns cookie test passed!");
+    }
+
+    #[tokio::test]
+    async fn test_feedback_table_sorting_and_column_preferences() {
+        let Some(app) = spawn_test_app().await else {
+            return;
+        };
+        for (repository, status, votes) in [
+            ("8b-is/bravo", "pending", 0),
+            ("8b-is/alpha", "failed", 1),
+            ("8b-is/charlie", "completed", 2),
+        ] {
+            let id: uuid::Uuid = sqlx::query_scalar("INSERT INTO feedback (repository, content, status) VALUES ($1, 'Hi', $2::feedback_status) RETURNING id")
+                .bind(repository)
+                .bind(status)
+                .fetch_one(&app.db_pool)
+                .await
+                .unwrap();
+            for voter in 0..votes {
+                sqlx::query(
+                    "WITH voter AS (INSERT INTO users (email, name, password_hash) VALUES ($2, 'voter', 'x') RETURNING id) \
+                     INSERT INTO feedback_votes (feedback_id, user_id) SELECT $1, id FROM voter",
+                )
+                .bind(id)
+                .bind(format!("{}-{}@8b.is", voter, repository.replace('/', "-")))
+                .execute(&app.db_pool)
+                .await
+                .unwrap();
+            }
+        }
+        app.login_admin().await.unwrap();
+
+        let page = |query: &'static str| {
+            let client = app.client.clone();
+            let url = app.url(&format!("/admin/feedback{}", query));
+            async move { client.get(url).send().await.unwrap().text().await.unwrap() }
+        };
+        let position = |html: &str, needle: &str| html.find(needle).unwrap();
+
+        let by_repository = page("?sort=repository").await;
+        assert!(position(&by_repository, "8b-is/alpha") < position(&by_repository, "8b-is/bravo"));
+        assert!(
+            position(&by_repository, "8b-is/bravo") < position(&by_repository, "8b-is/charlie")
+        );
+        assert!(by_repository.contains(
+            r#"<th aria-sort="ascending"><a href="/admin/feedback?sort=repository&amp;dir=desc" class="sort-link active">Repository ↑</a></th>"#
+        ));
+
+        let reversed = page("?sort=repository&dir=desc").await;
+        assert!(position(&reversed, "8b-is/charlie") < position(&reversed, "8b-is/alpha"));
+
+        // 👍 Most voted first, with its own header arrow
+        let by_votes = page("?sort=votes").await;
+        assert!(position(&by_votes, "8b-is/charlie") < position(&by_votes, "8b-is/alpha"));
+        assert!(position(&by_votes, "8b-is/alpha") < position(&by_votes, "8b-is/bravo"));
+        assert!(by_votes.contains(
+            r#"<th aria-sort="descending"><a href="/admin/feedback?sort=votes&amp;dir=asc" class="sort-link active">Votes ↓</a></th>"#
+        ));
+
+        // 💉 A crafted sort value is ignored rather than reaching the SQL
+        let injected = page("?sort=created_at%3B%20DROP%20TABLE%20feedback--&dir=asc").await;
+        assert!(injected.contains(r#"class="sort-link active">Created ↓</a>"#));
+        let remaining: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM feedback")
+            .fetch_one(&app.db_pool)
+            .await
+            .unwrap();
+        assert_eq!(remaining, 3);
+
+        // 👁️ Hide the content and id columns, then come back to the same sort
+        let response = app
+            .client
+            .post(app.url("/admin/feedback/columns"))
+            .form(&[
+                ("show", "repository"),
+                ("show", "source"),
+                ("show", "status"),
+                ("show", "priority"),
+                ("show", "votes"),
+                ("show", "created"),
+                ("show", "attachments"),
+                ("return_to", "/admin/feedback?sort=status"),
+            ])
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::SEE_OTHER);
+        assert_eq!(
+            response.headers()["location"],
+            "/admin/feedback?sort=status"
+        );
+        let cookie = response.headers()["set-cookie"].to_str().unwrap();
+        assert!(cookie.starts_with("feedbacker_feedback_columns=id%2Ccontent;"));
+
+        let narrow = page("").await;
+        assert!(!narrow.contains("<th>Content</th>"));
+        assert!(!narrow.contains("<th>ID</th>"));
+        assert!(narrow.contains(r#"<input type="checkbox" name="show" value="content">"#));
+        assert!(narrow.contains(r#"<input type="checkbox" name="show" value="status" checked>"#));
+
+        // 🔒 The redirect never leaves the feedback page
+        let response = app
+            .client
+            .post(app.url("/admin/feedback/columns"))
+            .form(&[("show", "content"), ("return_to", "https://evil.example/")])
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.headers()["location"], "/admin/feedback");
+        println!("✅ Feedback table sorting and column test passed!");
+    }
+
+    #[tokio::test]
+    async fn test_migrations_page_shows_pending_and_drift() {
+        let Some(app) = spawn_test_app().await else {
+            return;
+        };
+        let latest = crate::database::migrations::get_all_migrations()
+            .pop()
+            .unwrap()
+            .id;
+        // 🔑 Sign in first - the latest migration may be one the login depends on
+        app.login_admin().await.unwrap();
+        crate::database::migrations::rollback_migration(&app.db_pool, &latest)
+            .await
+            .unwrap();
+        // ✏️ Someone edited the first migration after it ran
+        sqlx::query("UPDATE migrations SET checksum = 'edited' WHERE id = 'v1_initial_schema'")
+            .execute(&app.db_pool)
+            .await
+            .unwrap();
+
+        let states = crate::database::migrations::migration_status(&app.db_pool)
+            .await
+            .unwrap();
+        let first = states.iter().find(|s| s.id == "v1_initial_schema").unwrap();
+        assert!(!first.pending && first.has_drifted());
+        let last = states.last().unwrap();
+        assert!(last.pending && last.checksum_matches.is_none());
+        assert!(states
+            .iter()
+            .filter(|s| s.id != "v1_initial_schema" && !s.pending)
+            .all(|s| s.checksum_matches == Some(true)));
+
+        let html = app
+            .client
+            .get(app.url("/admin/migrations"))
+            .send()
+            .await
+            .unwrap()
+            .text()
+            .await
+            .unwrap();
+        assert!(html.contains(r#"<span aria-hidden="true">⏳</span> 1 pending, ⚠️ 1 drifted"#));
+        assert!(html.contains(r#"<span class="status status-failed">Drifted</span>"#));
+        assert!(html.contains(&format!("<td><code>{}</code></td>", latest)));
+        println!("✅ Migrations page test passed!");
+    }
+
+    /// 🔎 Text that opens with a decorative emoji, outside the cells and code users' text lives in
+    fn decorated_labels(html: &str) -> Vec<String> {
+        html.split('<')
+            .filter_map(|chunk| chunk.split_once('>'))
+            .filter(|(tag, _)| {
+                let name = tag.split_whitespace().next().unwrap_or_default();
+                !matches!(name, "td" | "code" | "textarea") && !tag.contains("feedback-content")
+            })
+            .map(|(_, text)| text.trim_start())
+            .filter(|text| !crate::api::labels::split_decoration(text).0.is_empty())
+            .map(str::to_string)
+            .collect()
+    }
 
-    let locations: Vec<(String, String, i64)> = location_rows
-        .iter()
-        .map(|row| (row.get("city"), row.get("country"), row.get("count")))
-        .collect();
+    async fn page_text(app: &crate::test_support::TestApp, path: &str) -> String {
+        app.client
+            .get(app.url(path))
+            .send()
+            .await
+            .unwrap()
+            .text()
+            .await
+            .unwrap()
+    }
 
-    let recent_rows = sqlx::query(
-        "SELECT client_version, platform, arch, city, country, checked_at FROM mcp_analytics ORDER BY checked_at DESC LIMIT 20"
-    )
-    .fetch_all(&app_state.db_pool)
-    .await
-    .unwrap_or_default();
+    #[tokio::test]
+    async fn test_label_style_toggle_and_landmarks() {
+        let Some(app) = spawn_test_app().await else {
+            return;
+        };
+        app.login_admin().await.unwrap();
+        let page = |path: &'static str| {
+            let client = app.client.clone();
+            let url = app.url(path);
+            async move { client.get(url).send().await.unwrap().text().await.unwrap() }
+        };
 
-    let recent_checks: Vec<RecentMcpCheck> = recent_rows
-        .iter()
-        .map(|row| {
-            let ts: chrono::DateTime<chrono::Utc> = row.get("checked_at");
-            RecentMcpCheck {
-                version: row.get("client_version"),
-                platform: row.get("platform"),
-                arch: row.get("arch"),
-                city: row.get("city"),
-                country: row.get("country"),
-                timestamp: ts.format("%Y-%m-%d %H:%M:%S").to_string(),
-            }
-        })
-        .collect();
+        // 🎨 Default: emoji stay, but the layout hides its own from screen readers
+        let settings = page("/admin/settings").await;
+        assert!(settings.contains(r#"<nav aria-label="Admin">"#));
+        assert!(settings.contains(r##"<a href="#main-content" class="skip-link">"##));
+        assert!(settings.contains(r#"<main class="main" id="main-content">"#));
+        assert!(settings.contains(
+            r#"<a href="/admin/settings" class="active" aria-current="page"><span aria-hidden="true">🔧</span> Settings</a>"#
+        ));
+        assert!(settings.contains(r#"<h2><span aria-hidden="true">🔧</span> Settings</h2>"#));
 
-    Some(McpStats {
-        total_checks,
-        platforms,
-        versions,
-        locations,
-        recent_checks,
-    })
-}
+        // 📄 Switch this browser to plain text
+        let response = app
+            .client
+            .post(app.url("/admin/settings/labels"))
+            .form(&[("style", "plain")])
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::SEE_OTHER);
 
-async fn get_setting(app_state: &AppState, key: &str) -> Option<String> {
-    sqlx::query_scalar::<_, String>("SELECT value FROM settings WHERE key = $1")
-        .bind(key)
-        .fetch_optional(&app_state.db_pool)
+        let settings = page("/admin/settings").await;
+        assert!(settings.contains("<h2>Settings</h2>"));
+        assert!(settings.contains("<h3>Two-Factor Authentication</h3>"));
+        assert!(settings.contains(r#"aria-current="page">Settings</a>"#));
+        assert!(!settings.contains("aria-hidden"));
+        assert!(settings.contains("Use emoji labels"));
+        // 🔎 No page has a label left that opens with an emoji
+        let feedback = Feedback::create(
+            &app.db_pool,
+            None,
+            "8b-is/smart-tree".to_string(),
+            "🎉 Party mode please".to_string(),
+            None,
+            25,
+            None,
+            "cli",
+        )
         .await
-        .ok()
-        .flatten()
-}
+        .unwrap();
+        let detail = format!("/admin/feedback/{}", feedback.id);
+        for path in [
+            "/admin",
+            "/admin/feedback",
+            detail.as_str(),
+            "/admin/projects",
+            "/admin/users",
+            "/admin/jobs",
+            "/admin/migrations",
+            "/admin/mcp",
+            "/admin/settings",
+        ] {
+            let html = page_text(&app, path).await;
+            assert_eq!(decorated_labels(&html), Vec::<String>::new(), "{}", path);
+        }
+        // 🙅 ...while what users wrote is shown as written
+        assert!(page_text(&app, &detail)
+            .await
+            .contains("🎉 Party mode please"));
 
-async fn set_setting(app_state: &AppState, key: &str, value: &str) -> anyhow::Result<()> {
-    sqlx::query(
-        "INSERT INTO settings (key, value, updated_at) VALUES ($1, $2, NOW()) ON CONFLICT (key) DO UPDATE SET value = $2, updated_at = NOW()"
-    )
-    .bind(key)
-    .bind(value)
-    .execute(&app_state.db_pool)
-    .await?;
-    Ok(())
-}
+        // 🚫 Unknown styles are refused
+        let response = app
+            .client
+            .post(app.url("/admin/settings/labels"))
+            .form(&[("style", "sparkly")])
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        println!("✅ Label style toggle test passed!");
+    }
 
-fn render_platform_table(platforms: &[(String, String, i64)]) -> String {
-    if platforms.is_empty() {
-        return r#"<div class="empty-state">No data yet</div>"#.to_string();
+    #[test]
+    fn test_timestamps_render_in_zone_with_utc_title() {
+        use chrono::TimeZone as _;
+        let at = chrono::Utc.with_ymd_and_hms(2024, 3, 31, 1, 0, 0).unwrap();
+        assert_eq!(
+            fmt_ts(at, &TimeZone::utc()),
+            r#"<time datetime="2024-03-31T01:00:00Z" title="2024-03-31T01:00:00Z">2024-03-31 01:00 UTC</time>"#
+        );
+        let berlin = TimeZone::load("Europe/Berlin").unwrap();
+        assert!(fmt_ts(at, &berlin).contains(">2024-03-31 03:00 CEST</time>"));
+
+        // 📡 The JSON API still sends plain UTC minutes
+        let item = FeedbackItem {
+            id: uuid::Uuid::nil().to_string(),
+            repository: "8b-is/clock".to_string(),
+            source: "api".to_string(),
+            status: FeedbackStatus::Pending,
+            status_class: FeedbackStatus::Pending.css_class(),
+            priority: 0,
+            votes: 0,
+            created_at: at,
+            content_preview: "Tick".to_string(),
+            attachments: Vec::new(),
+        };
+        assert_eq!(
+            serde_json::to_value(&item).unwrap()["created_at"],
+            "2024-03-31 01:00"
+        );
+        println!("✅ Timestamp formatting test passed!");
     }
 
-    let rows: String = platforms
-        .iter()
-        .map(|(platform, arch, count)| {
-            format!(
-                r#"<tr><td>{}</td><td>{}</td><td>{}</td></tr>"#,
-                platform, arch, count
-            )
-        })
-        .collect();
+    #[tokio::test]
+    async fn test_timezone_preference_is_per_admin_and_validated() {
+        let Some(app) = spawn_test_app().await else {
+            return;
+        };
+        app.login_admin().await.unwrap();
+        sqlx::query(
+            "INSERT INTO feedback (repository, content, created_at) VALUES ('8b-is/clock', 'Hi', '2024-07-04 16:00:00+00')",
+        )
+        .execute(&app.db_pool)
+        .await
+        .unwrap();
+        let page = |path: &'static str| {
+            let client = app.client.clone();
+            let url = app.url(path);
+            async move { client.get(url).send().await.unwrap().text().await.unwrap() }
+        };
+        let set = |timezone: &'static str| {
+            let request = app
+                .client
+                .post(app.url("/admin/settings/timezone"))
+                .form(&[("timezone", timezone)]);
+            async move { request.send().await.unwrap().status() }
+        };
 
-    format!(
-        r#"<table><thead><tr><th>Platform</th><th>Arch</th><th>Count</th></tr></thead><tbody>{}</tbody></table>"#,
-        rows
-    )
-}
+        let feedback = page("/admin/feedback").await;
+        assert!(feedback.contains(r#"title="2024-07-04T16:00:00Z">2024-07-04 16:00 UTC</time>"#));
 
-fn render_version_table(versions: &[(String, i64)]) -> String {
-    if versions.is_empty() {
-        return r#"<div class="empty-state">No data yet</div>"#.to_string();
+        assert_eq!(set("America/New_York").await, StatusCode::SEE_OTHER);
+        let stored: String = sqlx::query_scalar("SELECT value FROM settings WHERE key = $1")
+            .bind(format!("admin_timezone:{}", TEST_ADMIN_USERNAME))
+            .fetch_one(&app.db_pool)
+            .await
+            .unwrap();
+        assert_eq!(stored, "America/New_York");
+        let feedback = page("/admin/feedback").await;
+        assert!(feedback.contains(r#"title="2024-07-04T16:00:00Z">2024-07-04 12:00 EDT</time>"#));
+        assert!(feedback.contains(r#"data-timezone="America/New_York""#));
+        let settings = page("/admin/settings").await;
+        assert!(settings.contains(r#"<option value="America/New_York" selected>"#));
+
+        // 🚫 Only zones from the database, and nothing path-shaped
+        for bogus in ["Mars/Olympus_Mons", "../../etc/passwd", ""] {
+            assert_eq!(set(bogus).await, StatusCode::BAD_REQUEST);
+        }
+        let feedback = page("/admin/feedback").await;
+        assert!(feedback.contains("2024-07-04 12:00 EDT"));
+        println!("✅ Time zone preference test passed!");
     }
 
-    let rows: String = versions
-        .iter()
-        .map(|(version, count)| format!(r#"<tr><td>{}</td><td>{}</td></tr>"#, version, count))
-        .collect();
+    #[tokio::test]
+    async fn test_unknown_status_from_newer_schema_renders() {
+        let Some(app) = spawn_test_app().await else {
+            return;
+        };
 
-    format!(
-        r#"<table><thead><tr><th>Version</th><th>Count</th></tr></thead><tbody>{}</tbody></table>"#,
-        rows
-    )
-}
+        // 🆕 A newer deploy added a status this binary has never heard of
+        sqlx::query("ALTER TYPE feedback_status ADD VALUE 'awaiting_review'")
+            .execute(&app.db_pool)
+            .await
+            .unwrap();
+        sqlx::query(
+            "INSERT INTO feedback (repository, content, status) VALUES ('8b-is/smart-tree', 'Hi', 'awaiting_review')",
+        )
+        .execute(&app.db_pool)
+        .await
+        .unwrap();
 
-fn render_recent_checks_table(checks: &[RecentMcpCheck]) -> String {
-    if checks.is_empty() {
-        return r#"<div class="empty-state">No checks yet</div>"#.to_string();
+        let items = get_recent_feedback(
+            &app.app_state,
+            10,
+            None,
+            (FeedbackSort::Created, SortDir::Desc),
+            FeedbackFilter::default(),
+            None,
+        )
+        .await
+        .unwrap()
+        .items;
+        assert_eq!(
+            items[0].status,
+            FeedbackStatus::Unknown("awaiting_review".to_string())
+        );
+        let html = render_feedback_table(
+            &items,
+            None,
+            &HiddenColumns::default(),
+            &TimeZone::utc(),
+            LabelStyle::Emoji,
+        );
+        assert!(html.contains(r#"<span class="status status-unknown">awaiting_review</span>"#));
+        println!("✅ Unknown status rendering test passed!");
     }
 
-    let rows: String = checks
-        .iter()
-        .map(|c| {
-            let location = match (&c.city, &c.country) {
-                (Some(city), Some(country)) => format!("{}, {}", city, country),
-                (None, Some(country)) => country.clone(),
-                (Some(city), None) => city.clone(),
-                (None, None) => "-".to_string(),
-            };
-            format!(
-                r#"<tr><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td></tr>"#,
-                c.version, c.platform, c.arch, location, c.timestamp
+    #[test]
+    fn test_every_layout_links_the_hashed_stylesheet() {
+        for page in [
+            render_login_page(None, LabelStyle::Emoji),
+            render_totp_login_page("token", None, LabelStyle::Emoji),
+            render_admin_page("Dashboard", "dashboard", "<p>Hi</p>", LabelStyle::Emoji),
+            crate::api::web::render_public_page("Changelog", "<p>Hi</p>"),
+        ] {
+            assert_eq!(page.matches(r#"rel="stylesheet""#).count(), 1);
+            assert!(page.contains(&assets::admin_css_link()));
+            assert!(!page.contains(assets::ADMIN_CSS_STABLE_FILE));
+        }
+        println!("✅ Stylesheet link test passed!");
+    }
+
+    #[test]
+    fn test_feedback_list_reads_only_the_preview() {
+        for sort in FeedbackSort::ALL {
+            for dir in [SortDir::Asc, SortDir::Desc] {
+                let sql = feedback_list_sql(sort, dir);
+                assert!(sql.contains("LEFT(content, 51) AS content_head"));
+                // 📦 The only content selected is the truncated preview (the search
+                // filter reads it, but never returns it)
+                assert!(!sql
+                    .replace("LEFT(content, 51) AS content_head", "")
+                    .replace("STRPOS(LOWER(content), LOWER($12))", "")
+                    .contains("content"));
+            }
+        }
+
+        let cursor = FeedbackCursor {
+            order: FeedbackCursor::order(FeedbackSort::Priority, SortDir::Desc),
+            key: "42".to_string(),
+            created_at: chrono::Utc::now(),
+            id: uuid::Uuid::new_v4(),
+        };
+        let encoded = cursor.encode();
+        assert_eq!(
+            FeedbackCursor::decode(&encoded, FeedbackSort::Priority, SortDir::Desc),
+            Some(cursor)
+        );
+        // 🚫 Cursors only resume the ordering they came from
+        assert_eq!(
+            FeedbackCursor::decode(&encoded, FeedbackSort::Priority, SortDir::Asc),
+            None
+        );
+        assert_eq!(
+            FeedbackCursor::decode("not-a-cursor", FeedbackSort::Created, SortDir::Desc),
+            None
+        );
+        println!("✅ Feedback list preview and cursor test passed!");
+    }
+
+    #[tokio::test]
+    async fn test_feedback_pages_resume_every_ordering_by_cursor() {
+        let Some(app) = spawn_test_app().await else {
+            return;
+        };
+        let base = chrono::Utc::now() - chrono::Duration::hours(1);
+        for i in 0..25i64 {
+            let status = ["pending", "completed", "failed"][(i % 3) as usize];
+            sqlx::query(
+                "INSERT INTO feedback (repository, content, priority, status, created_at) \
+                 VALUES ($1, 'Hi', $2, $3::feedback_status, $4)",
             )
-        })
-        .collect();
+            .bind(format!("8b-is/repo-{}", i % 4))
+            .bind((i % 5) as i32 * 10)
+            .bind(status)
+            // ⏱️ Pairs share a timestamp, so the id tie-breaker matters too
+            .bind(base + chrono::Duration::minutes(i / 2))
+            .execute(&app.db_pool)
+            .await
+            .unwrap();
+        }
 
-    format!(
-        r#"<table><thead><tr><th>Version</th><th>Platform</th><th>Arch</th><th>Location</th><th>Time</th></tr></thead><tbody>{}</tbody></table>"#,
-        rows
-    )
-}
+        for sort in FeedbackSort::ALL {
+            for dir in [SortDir::Asc, SortDir::Desc] {
+                let list = |limit, after: Option<FeedbackCursor>| {
+                    let app_state = app.app_state.clone();
+                    async move {
+                        get_recent_feedback(
+                            &app_state,
+                            limit,
+                            None,
+                            (sort, dir),
+                            FeedbackFilter::default(),
+                            after.as_ref(),
+                        )
+                        .await
+                        .unwrap()
+                    }
+                };
+                let whole: Vec<String> = list(100, None)
+                    .await
+                    .items
+                    .into_iter()
+                    .map(|item| item.id)
+                    .collect();
+                assert_eq!(whole.len(), 25);
 
-fn render_locations_table(locations: &[(String, String, i64)]) -> String {
-    if locations.is_empty() {
-        return r#"<div class="empty-state">No location data yet. Install GeoLite2-City.mmdb to enable geo tracking.</div>"#.to_string();
+                let mut paged = Vec::new();
+                let mut after = None;
+                loop {
+                    let page = list(4, after).await;
+                    paged.extend(page.items.into_iter().map(|item| item.id));
+                    match page.next_cursor {
+                        Some(cursor) => {
+                            after = Some(FeedbackCursor::decode(&cursor, sort, dir).unwrap())
+                        }
+                        None => break,
+                    }
+                }
+                assert_eq!(paged, whole, "{:?} {:?}", sort, dir);
+            }
+        }
+        println!("✅ Feedback cursor pagination test passed!");
     }
 
-    let rows: String = locations
-        .iter()
-        .map(|(city, country, count)| {
-            format!(
-                r#"<tr><td>{}</td><td>{}</td><td>{}</td></tr>"#,
-                city, country, count
+    #[tokio::test]
+    async fn test_feedback_page_renders_ten_rows_without_scripts() {
+        let Some(app) = spawn_test_app().await else {
+            return;
+        };
+        let long = format!("<script>alert(1)</script>{}", "x".repeat(5000));
+        for _ in 0..12 {
+            sqlx::query(
+                "INSERT INTO feedback (repository, content) VALUES ('8b-is/smart-tree', $1)",
             )
-        })
-        .collect();
+            .bind(&long)
+            .execute(&app.db_pool)
+            .await
+            .unwrap();
+        }
+        app.login_admin().await.unwrap();
+        let get = |path: String| {
+            let client = app.client.clone();
+            let url = app.url(&path);
+            async move { client.get(url).send().await.unwrap() }
+        };
 
-    format!(
-        r#"<table><thead><tr><th>City</th><th>Country</th><th>Count</th></tr></thead><tbody>{}</tbody></table>"#,
-        rows
-    )
-}
+        let html = get("/admin/feedback".to_string())
+            .await
+            .text()
+            .await
+            .unwrap();
+        let rows = |html: &str| html.matches("<tr>").count() - 1;
+        assert_eq!(rows(&html), 10);
+        assert!(html.contains("&lt;script&gt;alert(1)&lt;/script&gt;xxxxxxxxxxxxxxxxxxxxxxxxx..."));
+        assert!(!html.contains("<script>alert(1)"));
+        assert!(!html.contains(&"x".repeat(100)));
+        assert!(html.contains(&assets::feedback_table_js_url()));
+        assert!(html.contains(r#"data-next="/admin/api/feedback?cursor="#));
 
-// Helper functions
+        // ➡️ Without JS, the More link is the next server-rendered page
+        let more = html
+            .split(r#"id="feedback-more" href=""#)
+            .nth(1)
+            .and_then(|rest| rest.split('"').next())
+            .unwrap()
+            .to_string();
+        assert!(more.starts_with("/admin/feedback?cursor="));
+        let second = get(more).await.text().await.unwrap();
+        assert_eq!(rows(&second), 2);
+        assert!(!second.contains(r#"id="feedback-more""#));
 
-async fn get_dashboard_stats(app_state: &AppState) -> anyhow::Result<DashboardStats> {
-    let total_users: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM users")
-        .fetch_one(&app_state.db_pool)
-        .await
-        .unwrap_or(0);
+        // 📡 The same list as JSON, with the badge class the script needs
+        let response = get("/admin/api/feedback".to_string()).await;
+        assert_eq!(response.status(), StatusCode::OK);
+        let body: serde_json::Value = response.json().await.unwrap();
+        let items = body["data"]["items"].as_array().unwrap();
+        assert_eq!(items.len(), 12);
+        assert_eq!(items[0]["status_class"], "status-pending");
+        assert_eq!(
+            items[0]["content_preview"]
+                .as_str()
+                .unwrap()
+                .chars()
+                .count(),
+            53
+        );
+        assert!(body["data"]["next_cursor"].is_null());
 
-    let total_projects: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM projects")
-        .fetch_one(&app_state.db_pool)
-        .await
-        .unwrap_or(0);
+        let bogus = get("/admin/api/feedback?sort=priority&cursor=bogus".to_string()).await;
+        assert_eq!(bogus.status(), StatusCode::BAD_REQUEST);
+        println!("✅ Feedback page fallback rendering test passed!");
+    }
 
-    let total_feedback: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM feedback")
-        .fetch_one(&app_state.db_pool)
-        .await
-        .unwrap_or(0);
+    #[tokio::test]
+    async fn test_admin_requires_login() {
+        let Some(app) = spawn_test_app().await else {
+            return;
+        };
+
+        // 🚫 No session cookie: pages redirect, JSON APIs answer 401
+        let response = app.client.get(app.url("/admin")).send().await.unwrap();
+        assert_eq!(response.status(), StatusCode::SEE_OTHER);
+        assert_eq!(response.headers()["location"], "/admin/login");
+        let response = app
+            .client
+            .get(app.url("/admin/api/events"))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
 
-    let pending_feedback: i64 =
-        sqlx::query_scalar("SELECT COUNT(*) FROM feedback WHERE status = 'pending'")
-            .fetch_one(&app_state.db_pool)
+        // 🔑 Wrong password stays on the login page
+        let response = app
+            .client
+            .post(app.url("/admin/login"))
+            .form(&[("username", TEST_ADMIN_USERNAME), ("password", "nope")])
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert!(response
+            .text()
             .await
-            .unwrap_or(0);
+            .unwrap()
+            .contains("Invalid username or password"));
 
-    let completed_feedback: i64 =
-        sqlx::query_scalar("SELECT COUNT(*) FROM feedback WHERE status = 'completed'")
-            .fetch_one(&app_state.db_pool)
+        // 🔓 Correct password sets the cookie and unlocks the dashboard
+        let response = app.login_admin().await.unwrap();
+        assert_eq!(response.status(), StatusCode::SEE_OTHER);
+        let response = app.client.get(app.url("/admin")).send().await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        // 🚪 Logout clears it again
+        app.client
+            .get(app.url("/admin/logout"))
+            .send()
+            .await
+            .unwrap();
+        let response = app.client.get(app.url("/admin")).send().await.unwrap();
+        assert_eq!(response.status(), StatusCode::SEE_OTHER);
+        println!("✅ Admin login flow test passed!");
+    }
+    #[tokio::test]
+    async fn test_admin_accounts_sign_in_and_are_audited_by_name() {
+        let Some(app) = spawn_test_app().await else {
+            return;
+        };
+        let account = |email: &'static str, password: &'static str, role: &'static str| {
+            let hash = crate::auth::password::hash_password(password).unwrap();
+            sqlx::query_scalar::<_, uuid::Uuid>(
+                "INSERT INTO users (email, name, password_hash, role) VALUES ($1, 'Someone', $2, $3::user_role) RETURNING id",
+            )
+            .bind(email)
+            .bind(hash)
+            .bind(role)
+            .fetch_one(&app.db_pool)
+        };
+        let ops_id = account("ops@example.com", "ops-password", "admin")
             .await
-            .unwrap_or(0);
+            .unwrap();
+        account("viewer@example.com", "viewer-password", "user")
+            .await
+            .unwrap();
+        let project_id: uuid::Uuid = sqlx::query_scalar(
+            "INSERT INTO projects (owner_id, repository, config) VALUES ($1, 'acme/legacy', '{\"comment_footer\": null}') RETURNING id",
+        )
+        .bind(ops_id)
+        .fetch_one(&app.db_pool)
+        .await
+        .unwrap();
+
+        // 🍪 Everyone gets their own browser
+        let app = &app;
+        let sign_in = |username: &'static str, password: &'static str| async move {
+            let client = reqwest::Client::builder()
+                .cookie_store(true)
+                .redirect(reqwest::redirect::Policy::none())
+                .build()
+                .unwrap();
+            let response = client
+                .post(app.url("/admin/login"))
+                .form(&[("username", username), ("password", password)])
+                .send()
+                .await
+                .unwrap();
+            (client, response.status())
+        };
+        let dashboard = |client: reqwest::Client| async move {
+            client.get(app.url("/admin")).send().await.unwrap().status()
+        };
 
-    let failed_feedback: i64 =
-        sqlx::query_scalar("SELECT COUNT(*) FROM feedback WHERE status = 'failed'")
-            .fetch_one(&app_state.db_pool)
+        // 🚫 Plain users and wrong passwords stay on the login page
+        for (username, password) in [
+            ("viewer@example.com", "viewer-password"),
+            ("ops@example.com", "viewer-password"),
+        ] {
+            let (client, status) = sign_in(username, password).await;
+            assert_eq!(status, StatusCode::OK);
+            assert_eq!(dashboard(client).await, StatusCode::SEE_OTHER);
+        }
+
+        // 🔓 An admin account gets in, and what it does is recorded under its name
+        let (ops, status) = sign_in("ops@example.com", "ops-password").await;
+        assert_eq!(status, StatusCode::SEE_OTHER);
+        assert_eq!(dashboard(ops.clone()).await, StatusCode::OK);
+        ops.post(app.url(&format!("/admin/projects/{}/config/migrate", project_id)))
+            .send()
             .await
-            .unwrap_or(0);
+            .unwrap();
+        let actor: String = sqlx::query_scalar(
+            "SELECT actor FROM admin_audit_log WHERE action = 'project_config_migrated'",
+        )
+        .fetch_one(&app.db_pool)
+        .await
+        .unwrap();
+        assert_eq!(actor, "ops@example.com");
+        let last_login: Option<chrono::DateTime<chrono::Utc>> =
+            sqlx::query_scalar("SELECT last_login_at FROM users WHERE id = $1")
+                .bind(ops_id)
+                .fetch_one(&app.db_pool)
+                .await
+                .unwrap();
+        assert!(last_login.is_some());
 
-    Ok(DashboardStats {
-        total_users,
-        total_projects,
-        total_feedback,
-        pending_feedback,
-        completed_feedback,
-        failed_feedback,
-    })
-}
+        // 🥾 The configured admin still works as the bootstrap fallback
+        assert_eq!(
+            app.login_admin().await.unwrap().status(),
+            StatusCode::SEE_OTHER
+        );
+        assert_eq!(dashboard(app.client.clone()).await, StatusCode::OK);
 
-async fn get_recent_feedback(
-    app_state: &AppState,
-    limit: i64,
-) -> anyhow::Result<Vec<FeedbackItem>> {
-    let rows = sqlx::query(
-        "SELECT id, repository, status::text, created_at, content FROM feedback ORDER BY created_at DESC LIMIT $1"
-    )
-    .bind(limit)
-    .fetch_all(&app_state.db_pool)
-    .await?;
+        // 👋 Losing the admin role ends the session on the next request
+        sqlx::query("UPDATE users SET role = 'user' WHERE id = $1")
+            .bind(ops_id)
+            .execute(&app.db_pool)
+            .await
+            .unwrap();
+        assert_eq!(dashboard(ops).await, StatusCode::SEE_OTHER);
+        println!("✅ Admin account login test passed!");
+    }
 
-    let items = rows
-        .iter()
-        .map(|row| {
-            let content: String = row.get("content");
-            FeedbackItem {
-                id: row.get::<uuid::Uuid, _>("id").to_string(),
-                repository: row.get("repository"),
-                status: row.get("status"),
-                created_at: row
-                    .get::<chrono::DateTime<chrono::Utc>, _>("created_at")
-                    .format("%Y-%m-%d %H:%M")
-                    .to_string(),
-                content_preview: content.chars().take(50).collect::<String>()
-                    + if content.len() > 50 { "..." } else { "" },
+    #[tokio::test]
+    async fn test_second_factors_belong_to_each_admin_account() {
+        let Some(app) = spawn_test_app().await else {
+            return;
+        };
+        let app = &app;
+        let hash = crate::auth::password::hash_password("ops-password").unwrap();
+        let ops_id: uuid::Uuid = sqlx::query_scalar(
+            "INSERT INTO users (email, name, password_hash, role) VALUES ('ops@example.com', 'Ops', $1, 'admin') RETURNING id",
+        )
+        .bind(hash)
+        .fetch_one(&app.db_pool)
+        .await
+        .unwrap();
+        let sign_in = || async {
+            let client = reqwest::Client::builder()
+                .cookie_store(true)
+                .redirect(reqwest::redirect::Policy::none())
+                .build()
+                .unwrap();
+            let response = client
+                .post(app.url("/admin/login"))
+                .form(&[
+                    ("username", "ops@example.com"),
+                    ("password", "ops-password"),
+                ])
+                .send()
+                .await
+                .unwrap();
+            (client, response)
+        };
+        let dashboard = |client: reqwest::Client| async move {
+            client.get(app.url("/admin")).send().await.unwrap().status()
+        };
+
+        // 🔓 The console admin's 2FA doesn't lock accounts out - they have their own
+        let (console_secret, _) = enable_second_factor(app, SessionSubject::Bootstrap).await;
+        let (earlier, response) = sign_in().await;
+        assert_eq!(response.status(), StatusCode::SEE_OTHER);
+        assert_eq!(dashboard(earlier.clone()).await, StatusCode::OK);
+
+        // 🍪 Once the account switches its own on, its older session stops working...
+        let (secret, _) = enable_second_factor(app, SessionSubject::User(ops_id)).await;
+        assert_eq!(dashboard(earlier).await, StatusCode::SEE_OTHER);
+
+        // 🔢 ...and the password alone only leads to the code step
+        let (client, response) = sign_in().await;
+        assert_eq!(response.status(), StatusCode::OK);
+        let token = pre_auth_token(&response.text().await.unwrap());
+        assert_eq!(dashboard(client.clone()).await, StatusCode::SEE_OTHER);
+
+        // 🚫 The console admin's code is not this account's code
+        let now = chrono::Utc::now().timestamp() as u64;
+        let submit = |code: String| {
+            let client = client.clone();
+            let token = token.clone();
+            async move {
+                client
+                    .post(app.url("/admin/login/totp"))
+                    .form(&[("token", token), ("code", code)])
+                    .send()
+                    .await
+                    .unwrap()
             }
-        })
-        .collect();
+        };
+        let response = submit(crate::auth::totp::code_at(&console_secret, now).unwrap()).await;
+        assert!(response.text().await.unwrap().contains("Invalid code"));
+        let response = submit(crate::auth::totp::code_at(&secret, now).unwrap()).await;
+        assert_eq!(response.status(), StatusCode::SEE_OTHER);
+        assert_eq!(dashboard(client).await, StatusCode::OK);
+        println!("✅ Per-account second factor test passed!");
+    }
 
-    Ok(items)
-}
+    #[tokio::test]
+    async fn test_held_changes_are_filtered_diffed_and_approved_in_the_console() {
+        let Some(app) = spawn_test_app().await else {
+            return;
+        };
+        let pool = &app.db_pool;
+        let owner_id: uuid::Uuid = sqlx::query_scalar(
+            "INSERT INTO users (email, name, password_hash) VALUES ('held@example.com', 'Held', 'x') RETURNING id",
+        )
+        .fetch_one(pool)
+        .await
+        .unwrap();
+        let project_id: uuid::Uuid = sqlx::query_scalar(
+            "INSERT INTO projects (owner_id, repository) VALUES ($1, '8b-is/held') RETURNING id",
+        )
+        .bind(owner_id)
+        .fetch_one(pool)
+        .await
+        .unwrap();
+        app.login_admin().await.unwrap();
+        let page = |path: String| {
+            let client = app.client.clone();
+            let url = app.url(&path);
+            async move { client.get(url).send().await.unwrap().text().await.unwrap() }
+        };
 
-fn render_feedback_table(feedback: &[FeedbackItem]) -> String {
-    if feedback.is_empty() {
-        return r#"<div class="empty-state">📭 No feedback yet</div>"#.to_string();
+        // ✋ Switching approval on from the projects page is audited
+        app.client
+            .post(app.url(&format!("/admin/projects/{}/approval", project_id)))
+            .send()
+            .await
+            .unwrap();
+        assert!(page("/admin/projects".to_string())
+            .await
+            .contains(r#"<span class="status status-pending">required</span>"#));
+        let audited: i64 = sqlx::query_scalar(
+            "SELECT COUNT(*) FROM admin_audit_log WHERE action = 'project_approval_changed'",
+        )
+        .fetch_one(pool)
+        .await
+        .unwrap();
+        assert_eq!(audited, 1);
+
+        let create = |repository: &'static str| async move {
+            Feedback::create(
+                pool,
+                None,
+                repository.to_string(),
+                "The intro should say what the tool is for".to_string(),
+                None,
+                25,
+                None,
+                "cli",
+            )
+            .await
+            .unwrap()
+        };
+        let mut held = create("8b-is/held").await;
+        let other = create("8b-is/not-held").await;
+        let change = CodeImprovement {
+            file_path: "README.md".to_string(),
+            description: "Say what it is for".to_string(),
+            change_type: ChangeType::Modify,
+            original_content: Some("# Tool\nOld intro\n".to_string()),
+            new_content: "# Tool\nA <fast> directory viewer\n".to_string(),
+            line_number: None,
+        };
+        let generated = CodeImprovement {
+            file_path: "src/generated.rs".to_string(),
+            description: "A long new file".to_string(),
+            change_type: ChangeType::Create,
+            original_content: None,
+            new_content: (0..300).map(|n| format!("// line {}\n", n)).collect(),
+            line_number: None,
+        };
+        let gated = approval::gate(pool, &mut held, vec![change, generated], 14)
+            .await
+            .unwrap();
+        assert!(matches!(gated, approval::ApprovalGate::Held));
+        let patches = patch::stored_patch(held.metadata.as_ref()).unwrap();
+        assert_eq!(patches.len(), 2);
+        assert_eq!((patches[1].additions, patches[1].deletions), (300, 0));
+
+        // 🔎 The shortcut narrows the list to held feedback
+        let list = page("/admin/feedback".to_string()).await;
+        assert!(list.contains(r#"href="/admin/feedback?status=awaiting_approval""#));
+        let filtered = page("/admin/feedback?status=awaiting_approval".to_string()).await;
+        assert!(filtered.contains(&format!(r#"href="/admin/feedback/{}""#, held.id)));
+        assert!(!filtered.contains(&other.id.to_string()));
+        assert!(filtered.contains(r#"<span class="tag-chip">awaiting_approval</span>"#));
+
+        // 📄 The detail page shows the diff (escaped) and the buttons
+        let detail = page(format!("/admin/feedback/{}", held.id)).await;
+        assert!(detail.contains(r#"<span class="diff-del">-Old intro</span>"#));
+        assert!(
+            detail.contains(r#"<span class="diff-add">+A &lt;fast&gt; directory viewer</span>"#)
+        );
+        assert!(detail.contains(r#"<span class="diff-context"> # Tool</span>"#));
+        assert!(detail.contains(r#"<span class="diff-hunk">@@ -1,2 +1,2 @@</span>"#));
+        assert!(detail.contains(r#"<span class="diff-meta">+++ b/README.md</span>"#));
+        // 🖍️ Code in a language we know is highlighted after its marker
+        assert!(detail
+            .contains(r#"<span class="diff-add">+<span class="tok-com">// line 7</span></span>"#));
+        // 📐 Short diffs start open, long ones collapsed
+        assert!(detail.contains(
+            r#"<details class="diff-file" open>
+                <summary class="diff-header"><code>README.md</code> <span class="diff-add">+1</span> <span class="diff-del">-1</span>"#
+        ));
+        assert!(detail.contains(
+            r#"<details class="diff-file">
+                <summary class="diff-header"><code>src/generated.rs</code>"#
+        ));
+        assert!(detail.contains("(303 lines, click to expand)"));
+        assert!(detail.contains(&format!("/admin/feedback/{}/approve", held.id)));
+
+        let response = app
+            .client
+            .post(app.url(&format!("/admin/feedback/{}/approve", held.id)))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::SEE_OTHER);
+        let approved = Feedback::find_by_id(pool, held.id).await.unwrap().unwrap();
+        assert_eq!(approved.status, FeedbackStatus::CreatingPullRequest);
+        let detail = page(format!("/admin/feedback/{}", held.id)).await;
+        assert!(!detail.contains(&format!("/admin/feedback/{}/approve", held.id)));
+        assert!(detail.contains(r#"<span class="status status-completed">approved"#));
+        assert_eq!(
+            app.client
+                .get(app.url(&format!("/admin/feedback/{}", uuid::Uuid::new_v4())))
+                .send()
+                .await
+                .unwrap()
+                .status(),
+            StatusCode::NOT_FOUND
+        );
+        println!("✅ Held changes console test passed!");
     }
 
-    let rows: String = feedback
-        .iter()
-        .map(|f| {
-            let status_class = match f.status.as_str() {
-                "pending" => "status-pending",
-                "completed" => "status-completed",
-                "failed" => "status-failed",
-                _ => "status-processing",
-            };
-            format!(
-                r#"<tr>
-                    <td><code>{}</code></td>
-                    <td>{}</td>
-                    <td><span class="status {}">{}</span></td>
-                    <td>{}</td>
-                    <td>{}</td>
-                </tr>"#,
-                &f.id[..8],
-                f.repository,
-                status_class,
-                f.status,
-                f.created_at,
-                f.content_preview,
+    #[tokio::test]
+    async fn test_duplicates_are_marked_from_the_detail_page() {
+        let Some(app) = spawn_test_app().await else {
+            return;
+        };
+        let pool = &app.db_pool;
+        let create = || async {
+            Feedback::create(
+                pool,
+                None,
+                "8b-is/smart-tree".to_string(),
+                "Please add dark mode".to_string(),
+                None,
+                25,
+                None,
+                "cli",
             )
-        })
-        .collect();
+            .await
+            .unwrap()
+        };
+        let original = create().await;
+        let repeat = create().await;
+        app.login_admin().await.unwrap();
+        let mark = |of: String| {
+            let request = app
+                .client
+                .post(app.url(&format!("/admin/feedback/{}/duplicate", repeat.id)))
+                .form(&[("of", of)]);
+            async move { request.send().await.unwrap().status() }
+        };
+        let page = |id: uuid::Uuid| {
+            let request = app.client.get(app.url(&format!("/admin/feedback/{}", id)));
+            async move { request.send().await.unwrap().text().await.unwrap() }
+        };
 
-    format!(
-        r#"<table>
-            <thead>
-                <tr>
-                    <th>ID</th>
-                    <th>Repository</th>
-                    <th>Status</th>
-                    <th>Created</th>
-                    <th>Content</th>
-                </tr>
-            </thead>
-            <tbody>{}</tbody>
-        </table>"#,
-        rows
-    )
+        assert_eq!(
+            mark(format!(" {} ", original.id)).await,
+            StatusCode::SEE_OTHER
+        );
+        assert!(page(original.id).await.contains("0 · also reported by 1"));
+        let detail = page(repeat.id).await;
+        assert!(detail.contains(&format!(
+            r#"<tr><th>Duplicate of</th><td><a href="/admin/feedback/{0}"><code>{0}</code></a></td></tr>"#,
+            original.id
+        )));
+        assert!(detail.contains(&format!(r#"name="of" value="{}""#, original.id)));
+
+        // 🙅 Nonsense and loops change nothing, a blank unmarks
+        mark("not-an-id".to_string()).await;
+        mark(repeat.id.to_string()).await;
+        assert_eq!(
+            feedback_votes::duplicate_of(pool, repeat.id).await.unwrap(),
+            Some(original.id)
+        );
+        mark(String::new()).await;
+        assert!(page(original.id).await.contains("0 · also reported by 0"));
+        let audited: i64 = sqlx::query_scalar(
+            "SELECT COUNT(*) FROM admin_audit_log WHERE action = 'feedback_duplicate_marked'",
+        )
+        .fetch_one(pool)
+        .await
+        .unwrap();
+        assert_eq!(audited, 2);
+        println!("✅ Duplicate marking console test passed!");
+    }
 }
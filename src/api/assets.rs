@@ -0,0 +1,257 @@
+// 🎨 Embedded Static Assets - CSS and JS baked right into the binary! 🎨
+// Assets are served under content-hashed names so browsers can cache them forever,
+// and every page we render links those (through `admin_css_link`). The stable `app.css`
+// alias is for references from outside the binary; it revalidates via ETag/Last-Modified.
+// Created with love by Aye & Hue! ✨
+
+use axum::{
+    extract::Path,
+    http::{header, HeaderMap, HeaderValue, StatusCode},
+    response::{IntoResponse, Response},
+};
+use chrono::{DateTime, Utc};
+use sha2::{Digest, Sha256};
+
+use crate::build_info;
+
+/// 🎨 The shared admin stylesheet (compiled into the binary)
+pub const ADMIN_CSS: &str = include_str!("assets/admin.css");
+
+/// 📜 Progressive enhancement for the admin feedback table (compiled into the binary)
+pub const FEEDBACK_TABLE_JS: &str = include_str!("assets/feedback-table.js");
+
+/// ⏰ Cache header for hashed assets - the name changes when the content does
+pub const IMMUTABLE_CACHE_CONTROL: &str = "public, max-age=31536000, immutable";
+
+/// ⏰ Cache header for the stable `app.css` alias - long, but revalidated via ETag
+pub const STABLE_ASSET_CACHE_CONTROL: &str = "public, max-age=86400";
+
+/// 🏷️ Stable (unhashed) name of the admin stylesheet
+pub const ADMIN_CSS_STABLE_FILE: &str = "app.css";
+
+/// ⏰ Cache header for public pages that change occasionally (feeds, release notes)
+pub const SHORT_PUBLIC_CACHE_CONTROL: &str = "public, max-age=300";
+
+lazy_static::lazy_static! {
+    /// 🔖 Hashed file name of the admin stylesheet, computed once at startup
+    static ref ADMIN_CSS_FILE: String = hashed_file_name("admin", "css", ADMIN_CSS);
+    /// 🏷️ Strong ETag for the admin stylesheet
+    static ref ADMIN_CSS_ETAG: String = content_etag(ADMIN_CSS);
+    /// 🔖 Hashed file name of the feedback table script
+    static ref FEEDBACK_TABLE_JS_FILE: String =
+        hashed_file_name("feedback-table", "js", FEEDBACK_TABLE_JS);
+    /// 🏷️ Strong ETag for the feedback table script
+    static ref FEEDBACK_TABLE_JS_ETAG: String = content_etag(FEEDBACK_TABLE_JS);
+    /// ⏰ Embedded assets can only change with a new binary, so its build time is our
+    /// Last-Modified - the same across restarts and replicas of one build. A build
+    /// without a timestamp sends none (the ETag still revalidates).
+    static ref ASSETS_LAST_MODIFIED: Option<DateTime<Utc>> = build_info::built_at();
+}
+
+/// 🔖 Build a content-hashed file name like `admin.3f2a9c1b0d4e.css`
+pub fn hashed_file_name(stem: &str, extension: &str, content: &str) -> String {
+    let digest = Sha256::digest(content.as_bytes());
+    format!("{}.{}.{}", stem, &hex::encode(digest)[..12], extension)
+}
+
+/// 🏷️ Strong ETag derived from the content hash
+pub fn content_etag(content: &str) -> String {
+    let digest = Sha256::digest(content.as_bytes());
+    format!("\"{}\"", &hex::encode(digest)[..16])
+}
+
+/// 📅 Format a timestamp as an HTTP-date (RFC 7231 IMF-fixdate)
+pub fn http_date(timestamp: &DateTime<Utc>) -> String {
+    timestamp.format("%a, %d %b %Y %H:%M:%S GMT").to_string()
+}
+
+/// 🔍 Has the client's cached copy (If-None-Match / If-Modified-Since) still got the right bytes?
+fn is_not_modified(headers: &HeaderMap, etag: &str, last_modified: Option<&DateTime<Utc>>) -> bool {
+    if let Some(if_none_match) = headers
+        .get(header::IF_NONE_MATCH)
+        .and_then(|value| value.to_str().ok())
+    {
+        // 🏷️ If-None-Match wins over If-Modified-Since when both are present
+        return if_none_match
+            .split(',')
+            .map(|tag| tag.trim().trim_start_matches("W/"))
+            .any(|tag| tag == etag || tag == "*");
+    }
+
+    let Some(last_modified) = last_modified else {
+        return false;
+    };
+    headers
+        .get(header::IF_MODIFIED_SINCE)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| DateTime::parse_from_rfc2822(value).ok())
+        .map(|since| since.timestamp() >= last_modified.timestamp())
+        .unwrap_or(false)
+}
+
+/// 🔗 URL of the stylesheet under its hashed name
+pub fn admin_css_url() -> String {
+    format!("/admin/assets/{}", ADMIN_CSS_FILE.as_str())
+}
+
+/// 🔗 The `<link>` every admin and public page puts in its `<head>`
+pub fn admin_css_link() -> String {
+    format!(r#"<link rel="stylesheet" href="{}">"#, admin_css_url())
+}
+
+/// 🔗 URL the feedback table references its script by
+pub fn feedback_table_js_url() -> String {
+    format!("/admin/assets/{}", FEEDBACK_TABLE_JS_FILE.as_str())
+}
+
+/// 🎨 Serve an embedded admin asset, by hashed name (immutable) or stable name (revalidated)
+pub async fn admin_asset(Path(file): Path<String>, headers: HeaderMap) -> Response {
+    let (content, content_type, etag, cache_control) = if file == ADMIN_CSS_FILE.as_str() {
+        (
+            ADMIN_CSS,
+            "text/css; charset=utf-8",
+            ADMIN_CSS_ETAG.as_str(),
+            IMMUTABLE_CACHE_CONTROL,
+        )
+    } else if file == ADMIN_CSS_STABLE_FILE {
+        (
+            ADMIN_CSS,
+            "text/css; charset=utf-8",
+            ADMIN_CSS_ETAG.as_str(),
+            STABLE_ASSET_CACHE_CONTROL,
+        )
+    } else if file == FEEDBACK_TABLE_JS_FILE.as_str() {
+        (
+            FEEDBACK_TABLE_JS,
+            "text/javascript; charset=utf-8",
+            FEEDBACK_TABLE_JS_ETAG.as_str(),
+            IMMUTABLE_CACHE_CONTROL,
+        )
+    } else {
+        return (StatusCode::NOT_FOUND, "Asset not found").into_response();
+    };
+
+    let mut response_headers = HeaderMap::new();
+    response_headers.insert(header::ETAG, HeaderValue::from_static(etag));
+    response_headers.insert(
+        header::CACHE_CONTROL,
+        HeaderValue::from_static(cache_control),
+    );
+    if let Some(last_modified) = ASSETS_LAST_MODIFIED.as_ref() {
+        if let Ok(value) = HeaderValue::from_str(&http_date(last_modified)) {
+            response_headers.insert(header::LAST_MODIFIED, value);
+        }
+    }
+
+    if is_not_modified(&headers, etag, ASSETS_LAST_MODIFIED.as_ref()) {
+        return (StatusCode::NOT_MODIFIED, response_headers).into_response();
+    }
+
+    response_headers.insert(header::CONTENT_TYPE, HeaderValue::from_static(content_type));
+    (StatusCode::OK, response_headers, content).into_response()
+}
+
+// 🧪 Tests - Making sure our cache busting actually busts!
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::body::to_bytes;
+
+    #[test]
+    fn test_hashed_file_name_changes_with_content() {
+        let original = hashed_file_name("admin", "css", "body { color: red; }");
+        let changed = hashed_file_name("admin", "css", "body { color: blue; }");
+        assert_ne!(original, changed);
+        assert_eq!(
+            original,
+            hashed_file_name("admin", "css", "body { color: red; }")
+        );
+        assert!(original.starts_with("admin.") && original.ends_with(".css"));
+        assert!(admin_css_url().starts_with("/admin/assets/admin."));
+        println!("✅ Hashed asset name test passed!");
+    }
+
+    #[tokio::test]
+    async fn test_admin_asset_served_with_immutable_cache() {
+        let response = admin_asset(Path(ADMIN_CSS_FILE.clone()), HeaderMap::new()).await;
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(
+            response.headers().get(header::CACHE_CONTROL).unwrap(),
+            IMMUTABLE_CACHE_CONTROL
+        );
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        assert_eq!(body, ADMIN_CSS.as_bytes());
+
+        let script = admin_asset(
+            Path(
+                feedback_table_js_url()
+                    .trim_start_matches("/admin/assets/")
+                    .to_string(),
+            ),
+            HeaderMap::new(),
+        )
+        .await;
+        assert_eq!(script.status(), StatusCode::OK);
+        assert_eq!(
+            script.headers().get(header::CONTENT_TYPE).unwrap(),
+            "text/javascript; charset=utf-8"
+        );
+        let body = to_bytes(script.into_body(), usize::MAX).await.unwrap();
+        assert_eq!(body, FEEDBACK_TABLE_JS.as_bytes());
+
+        let missing = admin_asset(Path("admin.deadbeef.css".to_string()), HeaderMap::new()).await;
+        assert_eq!(missing.status(), StatusCode::NOT_FOUND);
+        println!("✅ Admin asset serving test passed!");
+    }
+
+    #[tokio::test]
+    async fn test_app_css_etag_revalidation() {
+        let stable = || Path(ADMIN_CSS_STABLE_FILE.to_string());
+
+        let first = admin_asset(stable(), HeaderMap::new()).await;
+        assert_eq!(first.status(), StatusCode::OK);
+        assert_eq!(
+            first.headers().get(header::CACHE_CONTROL).unwrap(),
+            STABLE_ASSET_CACHE_CONTROL
+        );
+        let etag = first.headers().get(header::ETAG).unwrap().clone();
+        let last_modified = first.headers().get(header::LAST_MODIFIED).unwrap().clone();
+        // 🏗️ Stamped at build time, not whenever this process happened to start
+        assert_eq!(
+            last_modified.to_str().unwrap(),
+            http_date(&build_info::built_at().unwrap())
+        );
+
+        let mut headers = HeaderMap::new();
+        headers.insert(header::IF_NONE_MATCH, etag);
+        let revalidated = admin_asset(stable(), headers).await;
+        assert_eq!(revalidated.status(), StatusCode::NOT_MODIFIED);
+
+        let mut headers = HeaderMap::new();
+        headers.insert(header::IF_MODIFIED_SINCE, last_modified);
+        let revalidated = admin_asset(stable(), headers).await;
+        assert_eq!(revalidated.status(), StatusCode::NOT_MODIFIED);
+
+        let mut headers = HeaderMap::new();
+        headers.insert(header::IF_NONE_MATCH, "\"stale\"".parse().unwrap());
+        let changed = admin_asset(stable(), headers).await;
+        assert_eq!(changed.status(), StatusCode::OK);
+        println!("✅ app.css ETag revalidation test passed!");
+    }
+
+    #[test]
+    fn test_if_modified_since_needs_a_build_time() {
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            header::IF_MODIFIED_SINCE,
+            "Thu, 01 Jan 2026 00:00:00 GMT".parse().unwrap(),
+        );
+        // 🕳️ Without a build time there's nothing to compare against
+        assert!(!is_not_modified(&headers, "\"x\"", None));
+        let built = DateTime::parse_from_rfc2822("Wed, 31 Dec 2025 00:00:00 GMT")
+            .unwrap()
+            .with_timezone(&Utc);
+        assert!(is_not_modified(&headers, "\"x\"", Some(&built)));
+        println!("✅ Missing build time test passed!");
+    }
+}
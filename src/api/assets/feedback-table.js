@@ -0,0 +1,170 @@
+// 📜 Feedback Table - Loads further pages from /admin/api/feedback in place! 📜
+// The server renders the first rows and a plain "More" link (which works without JS).
+// This script takes that link over: each click fetches the next page as JSON, appends
+// the rows with the same visible columns, and follows `next_cursor` until it runs out.
+// Rows are built with DOM calls only - feedback text never goes through innerHTML.
+// Times arrive in UTC and are shown in the admin's zone from `data-timezone`.
+// Created with love by Aye & Hue! ✨
+(function () {
+  "use strict";
+
+  function cell(row, child) {
+    var td = document.createElement("td");
+    if (typeof child === "string") {
+      td.textContent = child;
+    } else if (child) {
+      td.appendChild(child);
+    }
+    row.appendChild(td);
+    return td;
+  }
+
+  function link(href, className, text) {
+    var a = document.createElement("a");
+    a.href = href;
+    a.className = className;
+    a.textContent = text;
+    return a;
+  }
+
+  // 🕰️ "YYYY-MM-DD HH:MM" (UTC) as a <time> in the given zone, like the server renders it
+  function timestamp(value, timeZone) {
+    var at = new Date(value.replace(" ", "T") + ":00Z");
+    var time = document.createElement("time");
+    if (isNaN(at.getTime())) {
+      time.textContent = value;
+      return time;
+    }
+    var iso = at.toISOString().replace(".000Z", "Z");
+    time.dateTime = iso;
+    time.title = iso;
+    try {
+      var parts = {};
+      new Intl.DateTimeFormat("en-US", {
+        timeZone: timeZone || "UTC",
+        year: "numeric",
+        month: "2-digit",
+        day: "2-digit",
+        hour: "2-digit",
+        minute: "2-digit",
+        hourCycle: "h23",
+        timeZoneName: "short"
+      }).formatToParts(at).forEach(function (part) {
+        parts[part.type] = part.value;
+      });
+      time.textContent = parts.year + "-" + parts.month + "-" + parts.day + " " +
+        parts.hour + ":" + parts.minute + " " + parts.timeZoneName;
+    } catch (e) {
+      time.textContent = value + " UTC";
+    }
+    return time;
+  }
+
+  function renderRow(table, item) {
+    var columns = table.dataset.columns.split(",");
+    var sourceBase = table.dataset.sourceBase;
+    var row = document.createElement("tr");
+    columns.forEach(function (column) {
+      switch (column) {
+        case "id":
+          var code = document.createElement("code");
+          code.textContent = item.id.slice(0, 8);
+          var detail = link("/admin/feedback/" + item.id, "repo-link", "");
+          detail.appendChild(code);
+          cell(row, detail);
+          break;
+        case "repository":
+          cell(row, item.repository);
+          break;
+        case "source":
+          var separator = sourceBase.indexOf("?") === -1 ? "?" : "&";
+          cell(row, link(
+            sourceBase + separator + "source=" + encodeURIComponent(item.source),
+            "repo-link",
+            item.source
+          ));
+          break;
+        case "status":
+          var badge = document.createElement("span");
+          badge.className = "status " + item.status_class;
+          badge.textContent = item.status;
+          cell(row, badge);
+          break;
+        case "priority":
+          cell(row, String(item.priority));
+          break;
+        case "votes":
+          cell(row, String(item.votes));
+          break;
+        case "created":
+          cell(row, timestamp(item.created_at, table.dataset.timezone));
+          break;
+        case "content":
+          cell(row, item.content_preview);
+          break;
+        case "attachments":
+          var td = cell(row, null);
+          item.attachments.forEach(function (attachment, index) {
+            if (index > 0) {
+              td.appendChild(document.createElement("br"));
+            }
+            td.appendChild(link(
+              "/admin/feedback/" + item.id + "/attachments/" + attachment[0],
+              "attachment-link",
+              attachment[1]
+            ));
+          });
+          break;
+      }
+    });
+    table.tBodies[0].appendChild(row);
+  }
+
+  function enhance(table) {
+    var more = document.getElementById(table.dataset.moreLink);
+    if (!more || !table.dataset.next) {
+      return;
+    }
+    more.addEventListener("click", function (event) {
+      event.preventDefault();
+      if (more.dataset.loading) {
+        return;
+      }
+      more.dataset.loading = "1";
+      more.textContent = "Loading…";
+      fetch(table.dataset.next, {
+        credentials: "same-origin",
+        headers: { Accept: "application/json" }
+      })
+        .then(function (response) {
+          if (!response.ok) {
+            throw new Error("HTTP " + response.status);
+          }
+          return response.json();
+        })
+        .then(function (body) {
+          body.data.items.forEach(function (item) {
+            renderRow(table, item);
+          });
+          delete more.dataset.loading;
+          if (body.data.next_cursor) {
+            var next = new URL(table.dataset.next, window.location.href);
+            next.searchParams.set("cursor", body.data.next_cursor);
+            table.dataset.next = next.pathname + next.search;
+            var fallback = new URL(more.href, window.location.href);
+            fallback.searchParams.set("cursor", body.data.next_cursor);
+            more.href = fallback.pathname + fallback.search;
+            more.textContent = "More →";
+          } else {
+            more.remove();
+          }
+        })
+        .catch(function () {
+          // 🔁 Fall back to the server-rendered page the link points at
+          window.location.href = more.href;
+        });
+    });
+  }
+
+  document.querySelectorAll("table[data-next]").forEach(enhance);
+})();
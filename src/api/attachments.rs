@@ -0,0 +1,532 @@
+// 📎 Attachments API - Logs and screenshots for richer bug reports! 📎
+// POST /api/feedback/:id/attachments?filename=crash.log with the raw bytes as the
+// body and their Content-Type. Types are checked against ATTACHMENTS_ALLOWED_TYPES
+// and sizes against ATTACHMENTS_MAX_BYTES before anything is stored. The bytes go
+// to the blob store (local directory or S3), the metadata to `attachments`.
+// Downloads are always served as attachments with nosniff, whatever the type.
+// Created with love by Aye & Hue! ✨
+
+use anyhow::{Context, Result};
+use axum::{
+    body::Body,
+    extract::{Path, Query, State},
+    http::{header, HeaderMap, StatusCode},
+    response::{IntoResponse, Json, Response},
+};
+use axum_extra::extract::CookieJar;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use sqlx::PgPool;
+use tracing::{error, info, warn};
+use uuid::Uuid;
+
+use crate::api::{
+    utils::{handle_error, not_found_error, validation_error},
+    ApiResponse, AppState, ErrorCode,
+};
+use crate::config::AttachmentsConfig;
+use crate::database::models::Feedback;
+
+/// 📏 Longest filename we keep
+const MAX_FILENAME_LENGTH: usize = 100;
+
+/// 📎 One stored attachment
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct Attachment {
+    pub id: Uuid,
+    pub feedback_id: Uuid,
+    pub filename: String,
+    pub content_type: String,
+    pub size_bytes: i64,
+    /// #️⃣ Hex SHA-256 of the bytes
+    pub sha256: String,
+    pub created_at: DateTime<Utc>,
+    /// 🔗 Where to download it from
+    #[sqlx(skip)]
+    pub url: String,
+    #[serde(skip)]
+    pub storage_key: String,
+}
+
+impl Attachment {
+    /// 🔗 API download path
+    fn with_url(mut self) -> Self {
+        self.url = format!("/api/feedback/{}/attachments/{}", self.feedback_id, self.id);
+        self
+    }
+}
+
+/// 🔍 POST /api/feedback/:id/attachments query
+#[derive(Debug, Deserialize)]
+pub struct UploadQuery {
+    /// 📄 Original filename (sanitized before it is stored)
+    pub filename: Option<String>,
+}
+
+/// 🧾 The bare media type, lowercased ("Text/Plain; charset=utf-8" → "text/plain")
+fn media_type(content_type: &str) -> String {
+    content_type
+        .split(';')
+        .next()
+        .unwrap_or_default()
+        .trim()
+        .to_lowercase()
+}
+
+/// ✅ Is this content type on the allow list? (`image/*` allows every image type)
+pub fn is_allowed_content_type(config: &AttachmentsConfig, content_type: &str) -> bool {
+    let media_type = media_type(content_type);
+    let Some((kind, _)) = media_type.split_once('/') else {
+        return false;
+    };
+    config.allowed_content_types.iter().any(|allowed| {
+        allowed == &media_type
+            || allowed
+                .strip_suffix("/*")
+                .is_some_and(|allowed_kind| allowed_kind == kind)
+    })
+}
+
+/// 🧼 A filename safe to store and to put in a Content-Disposition header
+pub fn sanitize_filename(filename: Option<&str>) -> String {
+    let name = filename
+        .unwrap_or_default()
+        .rsplit(['/', '\\'])
+        .next()
+        .unwrap_or_default();
+    let cleaned: String = name
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | '_') {
+                c
+            } else {
+                '_'
+            }
+        })
+        .take(MAX_FILENAME_LENGTH)
+        .collect();
+    let cleaned = cleaned.trim_start_matches('.');
+    if cleaned.is_empty() {
+        "attachment".to_string()
+    } else {
+        cleaned.to_string()
+    }
+}
+
+/// 📥 Store the bytes, then record them (the blob is removed again if the insert fails)
+pub async fn store_attachment(
+    app_state: &AppState,
+    feedback_id: Uuid,
+    filename: &str,
+    content_type: &str,
+    bytes: Vec<u8>,
+) -> Result<Attachment> {
+    let id = Uuid::new_v4();
+    let storage_key = format!("{}/{}", feedback_id, id);
+    let sha256 = hex::encode(Sha256::digest(&bytes));
+    let size_bytes = bytes.len() as i64;
+    let content_type = media_type(content_type);
+
+    app_state
+        .blobs
+        .put(&storage_key, &content_type, bytes)
+        .await
+        .context("Failed to store attachment")?;
+
+    let inserted = sqlx::query_as::<_, Attachment>(
+        r#"
+        INSERT INTO attachments
+            (id, feedback_id, filename, content_type, size_bytes, sha256, storage_backend, storage_key)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+        RETURNING id, feedback_id, filename, content_type, size_bytes, sha256, created_at, storage_key
+        "#,
+    )
+    .bind(id)
+    .bind(feedback_id)
+    .bind(filename)
+    .bind(&content_type)
+    .bind(size_bytes)
+    .bind(&sha256)
+    .bind(app_state.blobs.backend())
+    .bind(&storage_key)
+    .fetch_one(&app_state.db_pool)
+    .await;
+
+    match inserted {
+        Ok(attachment) => Ok(attachment.with_url()),
+        Err(e) => {
+            if let Err(cleanup) = app_state.blobs.delete(&storage_key).await {
+                warn!(
+                    "⚠️ Failed to remove orphaned attachment blob {}: {:#}",
+                    storage_key, cleanup
+                );
+            }
+            Err(e).context("Failed to record attachment")
+        }
+    }
+}
+
+/// 📋 A feedback item's attachments, oldest first
+pub async fn for_feedback(pool: &PgPool, feedback_id: Uuid) -> Result<Vec<Attachment>> {
+    let attachments = sqlx::query_as::<_, Attachment>(
+        r#"
+        SELECT id, feedback_id, filename, content_type, size_bytes, sha256, created_at, storage_key
+        FROM attachments WHERE feedback_id = $1
+        ORDER BY created_at, id
+        "#,
+    )
+    .bind(feedback_id)
+    .fetch_all(pool)
+    .await
+    .context("Failed to load attachments")?;
+    Ok(attachments.into_iter().map(Attachment::with_url).collect())
+}
+
+/// 🔍 One attachment of a feedback item
+async fn find(pool: &PgPool, feedback_id: Uuid, attachment_id: Uuid) -> Result<Option<Attachment>> {
+    let attachment = sqlx::query_as::<_, Attachment>(
+        r#"
+        SELECT id, feedback_id, filename, content_type, size_bytes, sha256, created_at, storage_key
+        FROM attachments WHERE feedback_id = $1 AND id = $2
+        "#,
+    )
+    .bind(feedback_id)
+    .bind(attachment_id)
+    .fetch_optional(pool)
+    .await
+    .context("Failed to load attachment")?;
+    Ok(attachment.map(Attachment::with_url))
+}
+
+/// ❌ Error response with a status of its own
+fn attachment_error(status: StatusCode, code: ErrorCode, message: String) -> Response {
+    (status, Json(ApiResponse::<()>::error(code, message, None))).into_response()
+}
+
+/// 📏 413 for anything over ATTACHMENTS_MAX_BYTES
+fn too_large(max_bytes: usize) -> Response {
+    attachment_error(
+        StatusCode::PAYLOAD_TOO_LARGE,
+        ErrorCode::AttachmentTooLarge,
+        format!("Attachments can be at most {} bytes", max_bytes),
+    )
+}
+
+/// 📎 POST /api/feedback/:id/attachments - store a log, screenshot, ... for a feedback item
+pub async fn upload_attachment(
+    State(app_state): State<AppState>,
+    Path(feedback_id): Path<Uuid>,
+    Query(query): Query<UploadQuery>,
+    headers: HeaderMap,
+    body: Body,
+) -> Response {
+    let config = &app_state.config.attachments;
+
+    let content_type = headers
+        .get(header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .unwrap_or_default();
+    if !is_allowed_content_type(config, content_type) {
+        return attachment_error(
+            StatusCode::UNSUPPORTED_MEDIA_TYPE,
+            ErrorCode::UnsupportedAttachmentType,
+            format!(
+                "Attachments of type {:?} are not accepted (allowed: {})",
+                media_type(content_type),
+                config.allowed_content_types.join(", ")
+            ),
+        );
+    }
+
+    // 📏 Refuse oversized uploads from the header before reading a byte
+    let declared_length = headers
+        .get(header::CONTENT_LENGTH)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.parse::<usize>().ok());
+    if declared_length.is_some_and(|length| length > config.max_bytes) {
+        return too_large(config.max_bytes);
+    }
+
+    match Feedback::find_by_id(&app_state.db_pool, feedback_id).await {
+        Ok(Some(_)) => {}
+        Ok(None) => return not_found_error("Feedback").into_response(),
+        Err(e) => return handle_error(e).into_response(),
+    }
+
+    // 🧮 Read at most max_bytes (the server-wide body cap doesn't apply to raw bodies)
+    let bytes = match axum::body::to_bytes(body, config.max_bytes).await {
+        Ok(bytes) => bytes,
+        Err(_) => return too_large(config.max_bytes),
+    };
+    if bytes.is_empty() {
+        return validation_error(vec!["Attachment body is empty".to_string()]).into_response();
+    }
+
+    let filename = sanitize_filename(query.filename.as_deref());
+    match store_attachment(
+        &app_state,
+        feedback_id,
+        &filename,
+        content_type,
+        bytes.to_vec(),
+    )
+    .await
+    {
+        Ok(attachment) => {
+            info!(
+                "📎 Stored attachment {} ({} bytes, {}) for feedback {}",
+                attachment.id, attachment.size_bytes, attachment.content_type, feedback_id
+            );
+            (
+                StatusCode::CREATED,
+                Json(ApiResponse::success(
+                    "Attachment stored".to_string(),
+                    attachment,
+                )),
+            )
+                .into_response()
+        }
+        Err(e) => {
+            error!("❌ Failed to store attachment for {}: {:#}", feedback_id, e);
+            handle_error(e).into_response()
+        }
+    }
+}
+
+/// 📤 The attachment's bytes, always as a download
+async fn attachment_download(
+    app_state: &AppState,
+    feedback_id: Uuid,
+    attachment_id: Uuid,
+) -> Response {
+    let attachment = match find(&app_state.db_pool, feedback_id, attachment_id).await {
+        Ok(Some(attachment)) => attachment,
+        Ok(None) => return not_found_error("Attachment").into_response(),
+        Err(e) => return handle_error(e).into_response(),
+    };
+    match app_state.blobs.get(&attachment.storage_key).await {
+        Ok(Some(bytes)) => (
+            [
+                (header::CONTENT_TYPE, attachment.content_type),
+                (
+                    header::CONTENT_DISPOSITION,
+                    format!("attachment; filename=\"{}\"", attachment.filename),
+                ),
+                (header::X_CONTENT_TYPE_OPTIONS, "nosniff".to_string()),
+                (header::CACHE_CONTROL, "private, no-store".to_string()),
+            ],
+            bytes,
+        )
+            .into_response(),
+        Ok(None) => {
+            warn!(
+                "⚠️ Attachment {} is recorded but its blob {} is missing",
+                attachment.id, attachment.storage_key
+            );
+            not_found_error("Attachment").into_response()
+        }
+        Err(e) => handle_error(e).into_response(),
+    }
+}
+
+/// 📤 GET /api/feedback/:id/attachments/:attachment_id
+pub async fn download_attachment(
+    State(app_state): State<AppState>,
+    Path((feedback_id, attachment_id)): Path<(Uuid, Uuid)>,
+) -> Response {
+    attachment_download(&app_state, feedback_id, attachment_id).await
+}
+
+/// 📤 GET /admin/feedback/:id/attachments/:attachment_id (admin session)
+pub async fn admin_download_attachment(
+    State(app_state): State<AppState>,
+    Path((feedback_id, attachment_id)): Path<(Uuid, Uuid)>,
+    jar: CookieJar,
+) -> Response {
+    if let Some(redirect) = crate::api::admin::require_admin_auth(&jar, &app_state).await {
+        return redirect;
+    }
+    attachment_download(&app_state, feedback_id, attachment_id).await
+}
+
+// 🧪 Tests - Bug reports with the evidence attached!
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::spawn_test_app_with_config;
+    use tower::ServiceExt;
+
+    #[test]
+    fn test_content_types_and_filenames() {
+        let config = AttachmentsConfig {
+            backend: crate::config::AttachmentBackend::Local,
+            local_dir: String::new(),
+            max_bytes: 10,
+            allowed_content_types: vec!["text/plain".to_string(), "image/*".to_string()],
+            s3: None,
+        };
+        assert!(is_allowed_content_type(&config, "text/plain"));
+        assert!(is_allowed_content_type(
+            &config,
+            "Text/Plain; charset=utf-8"
+        ));
+        assert!(is_allowed_content_type(&config, "image/png"));
+        assert!(!is_allowed_content_type(&config, "text/html"));
+        assert!(!is_allowed_content_type(&config, "image"));
+        assert!(!is_allowed_content_type(&config, ""));
+
+        assert_eq!(sanitize_filename(Some("crash.log")), "crash.log");
+        assert_eq!(sanitize_filename(Some("../../etc/passwd")), "passwd");
+        assert_eq!(
+            sanitize_filename(Some("C:\\logs\\my \"run\".txt")),
+            "my__run_.txt"
+        );
+        assert_eq!(sanitize_filename(Some(".hidden")), "hidden");
+        assert_eq!(sanitize_filename(None), "attachment");
+        assert_eq!(
+            sanitize_filename(Some(&"a".repeat(300))).len(),
+            MAX_FILENAME_LENGTH
+        );
+        println!("✅ Attachment validation test passed!");
+    }
+
+    #[tokio::test]
+    async fn test_attachments_are_stored_listed_and_downloaded() {
+        let Some(app) = spawn_test_app_with_config(|config| {
+            config.attachments.max_bytes = 64;
+        })
+        .await
+        else {
+            return;
+        };
+        let feedback = Feedback::create(
+            &app.db_pool,
+            None,
+            "8b-is/smart-tree".to_string(),
+            "The tree crashes on symlink loops".to_string(),
+            None,
+            0,
+            None,
+            "unknown",
+        )
+        .await
+        .unwrap();
+
+        // 🔐 The API routes sit behind JWT auth, so the handlers are mounted directly
+        let router = axum::Router::new()
+            .route(
+                "/api/feedback/:id/attachments",
+                axum::routing::post(upload_attachment),
+            )
+            .route(
+                "/api/feedback/:id/attachments/:attachment_id",
+                axum::routing::get(download_attachment),
+            )
+            .with_state(app.app_state.clone());
+        let upload = |feedback_id: Uuid, content_type: &str, body: Vec<u8>| {
+            let request = axum::http::Request::post(format!(
+                "/api/feedback/{}/attachments?filename=logs/crash%20report.log",
+                feedback_id
+            ))
+            .header("content-type", content_type)
+            .body(Body::from(body))
+            .unwrap();
+            router.clone().oneshot(request)
+        };
+        let json = |response: Response| async move {
+            let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+                .await
+                .unwrap();
+            serde_json::from_slice::<serde_json::Value>(&bytes).unwrap()
+        };
+
+        let response = upload(
+            feedback.id,
+            "text/plain; charset=utf-8",
+            b"panic: loop".to_vec(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(response.status(), StatusCode::CREATED);
+        let body = json(response).await;
+        assert_eq!(body["data"]["filename"], "crash_report.log");
+        assert_eq!(body["data"]["content_type"], "text/plain");
+        assert_eq!(body["data"]["size_bytes"], 11);
+        let url = body["data"]["url"].as_str().unwrap().to_string();
+
+        // 🚫 Wrong type, too big, empty, unknown feedback
+        let refused = [
+            (
+                upload(feedback.id, "text/html", b"<script>".to_vec()).await,
+                StatusCode::UNSUPPORTED_MEDIA_TYPE,
+            ),
+            (
+                upload(feedback.id, "text/plain", vec![b'x'; 65]).await,
+                StatusCode::PAYLOAD_TOO_LARGE,
+            ),
+            (
+                upload(feedback.id, "text/plain", Vec::new()).await,
+                StatusCode::BAD_REQUEST,
+            ),
+            (
+                upload(Uuid::new_v4(), "text/plain", b"hi".to_vec()).await,
+                StatusCode::NOT_FOUND,
+            ),
+        ];
+        for (response, status) in refused {
+            assert_eq!(response.unwrap().status(), status);
+        }
+
+        // 📤 Downloaded as an attachment with the original bytes
+        let response = router
+            .clone()
+            .oneshot(axum::http::Request::get(&url).body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(
+            response.headers()[header::CONTENT_DISPOSITION],
+            "attachment; filename=\"crash_report.log\""
+        );
+        assert_eq!(
+            response.headers()[header::X_CONTENT_TYPE_OPTIONS],
+            "nosniff"
+        );
+        let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        assert_eq!(&bytes[..], b"panic: loop");
+
+        // 📋 Only the accepted upload was recorded, and the detail lookup lists it
+        let attachments = for_feedback(&app.db_pool, feedback.id).await.unwrap();
+        assert_eq!(attachments.len(), 1);
+        assert_eq!(attachments[0].url, url);
+
+        // 🔐 Admin downloads need a session
+        let admin_url = format!(
+            "/admin/feedback/{}/attachments/{}",
+            feedback.id, attachments[0].id
+        );
+        let response = app.client.get(app.url(&admin_url)).send().await.unwrap();
+        assert_eq!(response.status(), StatusCode::SEE_OTHER);
+        app.login_admin().await.unwrap();
+        let response = app.client.get(app.url(&admin_url)).send().await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.bytes().await.unwrap().as_ref(), b"panic: loop");
+        let page = app
+            .client
+            .get(app.url("/admin/feedback"))
+            .send()
+            .await
+            .unwrap()
+            .text()
+            .await
+            .unwrap();
+        assert!(page.contains(&format!(
+            r#"<a href="{}" class="attachment-link">crash_report.log</a>"#,
+            admin_url
+        )));
+        println!("✅ Attachment upload test passed!");
+    }
+}
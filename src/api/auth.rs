@@ -14,8 +14,9 @@ use tracing::{error, info, warn};
 
 use crate::{
     api::{
+        json::ApiJson,
         utils::{handle_error, validation_error},
-        ApiResponse, AppState, ValidateRequest,
+        ApiResponse, AppState, ErrorCode, ValidateRequest,
     },
     database::models::{User, UserRole},
 };
@@ -102,13 +103,13 @@ impl ValidateRequest for RegisterRequest {
 /// 🔐 User login endpoint
 pub async fn login(
     State(app_state): State<AppState>,
-    Json(request): Json<LoginRequest>,
+    ApiJson(request): ApiJson<LoginRequest>,
 ) -> Response {
     info!("🔐 Login attempt for email: {}", request.email);
 
     if let Err(errors) = request.validate() {
         let api_response = ApiResponse::<()>::error(
-            "validation_error".to_string(),
+            ErrorCode::ValidationError,
             "Request validation failed".to_string(),
             Some(serde_json::json!({ "errors": errors })),
         );
@@ -131,7 +132,7 @@ pub async fn login(
             warn!("❌ Login failed: {:#}", e);
             let error_msg = format!("{:#}", e);
             let api_response = ApiResponse::<()>::error(
-                "internal_error".to_string(),
+                ErrorCode::InternalError,
                 "An internal error occurred".to_string(),
                 Some(serde_json::json!({ "details": error_msg })),
             );
@@ -143,13 +144,13 @@ pub async fn login(
 /// 📝 User registration endpoint
 pub async fn register(
     State(app_state): State<AppState>,
-    Json(request): Json<RegisterRequest>,
+    ApiJson(request): ApiJson<RegisterRequest>,
 ) -> Response {
     info!("📝 Registration attempt for email: {}", request.email);
 
     if let Err(errors) = request.validate() {
         let api_response = ApiResponse::<()>::error(
-            "validation_error".to_string(),
+            ErrorCode::ValidationError,
             "Request validation failed".to_string(),
             Some(serde_json::json!({ "errors": errors })),
         );
@@ -175,7 +176,7 @@ pub async fn register(
             warn!("❌ Registration failed: {:#}", e);
             let error_msg = format!("{:#}", e);
             let api_response = ApiResponse::<()>::error(
-                "internal_error".to_string(),
+                ErrorCode::InternalError,
                 "An internal error occurred".to_string(),
                 Some(serde_json::json!({ "details": error_msg })),
             );
@@ -0,0 +1,215 @@
+// 🔄 Callback Secret Rotation - New key, no missed deliveries! 🔄
+// Admin-only rotation of a feedback item's callback signing secret. The new
+// secret is shown exactly once (in the response) and signs every delivery from
+// now on; the old one keeps riding along in X-Feedbacker-Signature-Previous
+// until WEBHOOK_SECRET_OVERLAP_HOURS have passed.
+// Created with love by Aye & Hue! ✨
+
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::{IntoResponse, Json, Response},
+};
+use axum_extra::extract::cookie::CookieJar;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use uuid::Uuid;
+
+use crate::{
+    api::{
+        admin::{audit_log, require_admin_api_auth},
+        ApiResponse, AppState,
+    },
+    jobs::callbacks,
+};
+
+/// 🔑 The freshly rotated secret - this response is the only place it's ever shown
+#[derive(Debug, Serialize)]
+pub struct RotatedSecret {
+    pub feedback_id: Uuid,
+    pub callback_secret: String,
+    /// ⏰ Until then deliveries also carry a signature made with the old secret
+    pub previous_secret_expires_at: DateTime<Utc>,
+}
+
+/// 🔄 POST /admin/api/feedback/:id/callback-secret/rotate
+pub async fn rotate_callback_secret_handler(
+    State(app_state): State<AppState>,
+    jar: CookieJar,
+    Path(feedback_id): Path<Uuid>,
+) -> Response {
+    if let Some(denied) = require_admin_api_auth(&jar, &app_state).await {
+        return denied;
+    }
+
+    match callbacks::rotate_callback_secret(
+        &app_state.db_pool,
+        feedback_id,
+        app_state.config.secret_overlap(),
+    )
+    .await
+    {
+        Ok(Some((callback_secret, previous_secret_expires_at))) => {
+            audit_log(
+                &app_state,
+                &jar,
+                "callback_secret_rotated",
+                serde_json::json!({
+                    "feedback_id": feedback_id,
+                    "previous_secret_expires_at": previous_secret_expires_at,
+                }),
+            )
+            .await;
+            (
+                StatusCode::OK,
+                Json(ApiResponse::success(
+                    "Callback secret rotated - store it now, it won't be shown again".to_string(),
+                    RotatedSecret {
+                        feedback_id,
+                        callback_secret,
+                        previous_secret_expires_at,
+                    },
+                )),
+            )
+                .into_response()
+        }
+        Ok(None) => crate::api::utils::not_found_error("Feedback callback").into_response(),
+        Err(e) => crate::api::utils::handle_error(e).into_response(),
+    }
+}
+
+// 🧪 Tests - Changing the locks while the mail keeps coming!
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::models::{Feedback, FeedbackStatus};
+    use crate::test_support::spawn_test_app_with_config;
+    use crate::utils::signatures::sign;
+    use wiremock::matchers::method;
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    #[tokio::test]
+    async fn test_rotation_is_audited_and_overlaps_old_secret() {
+        let Some(app) = spawn_test_app_with_config(|config| {
+            config.feedback.allow_private_callbacks = true;
+            config.auth.secret_overlap_hours = 2;
+        })
+        .await
+        else {
+            return;
+        };
+        let receiver = MockServer::start().await;
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(204))
+            .mount(&receiver)
+            .await;
+        let mut feedback = Feedback::create(
+            &app.db_pool,
+            None,
+            "8b-is/smart-tree".to_string(),
+            "Please make the tree output colourful".to_string(),
+            Some(format!("{}/hooks", receiver.uri())),
+            0,
+            None,
+            "unknown",
+        )
+        .await
+        .unwrap();
+        let old_secret = feedback.callback_secret.clone().unwrap();
+        let rotate_path = format!("/admin/api/feedback/{}/callback-secret/rotate", feedback.id);
+
+        // 🔐 Admins only
+        let denied = app.client.post(app.url(&rotate_path)).send().await.unwrap();
+        assert_eq!(denied.status(), StatusCode::UNAUTHORIZED);
+
+        app.login_admin().await.unwrap();
+        let body: serde_json::Value = app
+            .client
+            .post(app.url(&rotate_path))
+            .send()
+            .await
+            .unwrap()
+            .json()
+            .await
+            .unwrap();
+        let new_secret = body["data"]["callback_secret"]
+            .as_str()
+            .unwrap()
+            .to_string();
+        assert_ne!(new_secret, old_secret);
+        let expires_at: DateTime<Utc> =
+            serde_json::from_value(body["data"]["previous_secret_expires_at"].clone()).unwrap();
+        let overlap = expires_at - Utc::now();
+        assert!(overlap > chrono::Duration::minutes(119) && overlap <= chrono::Duration::hours(2));
+
+        // 📜 Audited, without the secret itself
+        let details: serde_json::Value = sqlx::query_scalar(
+            "SELECT details FROM admin_audit_log WHERE action = 'callback_secret_rotated'",
+        )
+        .fetch_one(&app.db_pool)
+        .await
+        .unwrap();
+        assert_eq!(details["feedback_id"], feedback.id.to_string());
+        assert!(!details.to_string().contains(&new_secret));
+
+        // 📞 Deliveries are signed with the new secret, the old one rides along
+        feedback
+            .update_status(&app.db_pool, FeedbackStatus::Completed, None)
+            .await
+            .unwrap();
+        crate::jobs::outbox::dispatch_due(&app.app_state)
+            .await
+            .unwrap();
+        assert_eq!(crate::jobs::run_due_jobs(&app.app_state).await.unwrap(), 1);
+        let requests = receiver.received_requests().await.unwrap();
+        let delivered = requests.last().unwrap();
+        let header = |name: &str| delivered.headers[name].to_str().unwrap().to_string();
+        assert_eq!(
+            header(callbacks::SIGNATURE_HEADER),
+            sign(&new_secret, &delivered.body)
+        );
+        assert_eq!(
+            header(callbacks::PREVIOUS_SIGNATURE_HEADER),
+            sign(&old_secret, &delivered.body)
+        );
+
+        // ⏰ Once the overlap is over only the primary signature is sent
+        sqlx::query(
+            "UPDATE feedback SET callback_secret_previous_expires_at = NOW() - INTERVAL '1 minute'",
+        )
+        .execute(&app.db_pool)
+        .await
+        .unwrap();
+        let secrets = callbacks::callback_secrets(&app.db_pool, feedback.id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(secrets.primary, new_secret);
+        assert_eq!(secrets.active_previous(Utc::now()), None);
+
+        // 🔍 No callback, nothing to rotate
+        let without_callback = Feedback::create(
+            &app.db_pool,
+            None,
+            "8b-is/smart-tree".to_string(),
+            "No callback for this one please".to_string(),
+            None,
+            0,
+            None,
+            "unknown",
+        )
+        .await
+        .unwrap();
+        let missing = app
+            .client
+            .post(app.url(&format!(
+                "/admin/api/feedback/{}/callback-secret/rotate",
+                without_callback.id
+            )))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(missing.status(), StatusCode::NOT_FOUND);
+        println!("✅ Callback secret rotation test passed!");
+    }
+}
@@ -0,0 +1,545 @@
+// 🧩 Anonymous Challenge - A little friction for bots, none for accounts! 🧩
+// Submissions without an account (the public form, and POST /api/feedback without a
+// user) are the easiest to abuse, so ANON_CHALLENGE can make them solve something first:
+//   hcaptcha / turnstile - the widget's response token, checked with the provider's
+//     siteverify API (CAPTCHA_SECRET_KEY) before anything is stored
+//   pow - GET /api/feedback/challenge hands out a short-lived challenge signed with
+//     JWT_SECRET; the client finds a nonce so that SHA-256("<challenge>:<nonce>") starts
+//     with POW_DIFFICULTY zero bits. Each solved challenge is redeemed once.
+// Off by default. A missing or wrong answer is a 400.
+// Created with love by Aye & Hue! ✨
+
+use axum::{
+    extract::State,
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    Json,
+};
+use hmac::{Hmac, Mac};
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::net::IpAddr;
+use std::sync::Mutex;
+use std::time::Duration;
+use tracing::warn;
+
+use crate::api::{ApiResponse, AppState};
+use crate::auth::totp::constant_time_eq;
+use crate::config::{ChallengeConfig, ChallengeKind};
+
+/// 🔗 hCaptcha's siteverify endpoint
+pub const HCAPTCHA_VERIFY_URL: &str = "https://api.hcaptcha.com/siteverify";
+/// 🔗 Turnstile's siteverify endpoint
+pub const TURNSTILE_VERIFY_URL: &str = "https://challenges.cloudflare.com/turnstile/v0/siteverify";
+/// 📏 Longest nonce accepted in a proof-of-work answer
+const MAX_NONCE_LENGTH: usize = 32;
+
+/// ❌ Why an anonymous submission's challenge didn't pass
+#[derive(Debug)]
+pub enum ChallengeError {
+    /// 🕳️ Nothing was sent
+    Missing,
+    /// 🚫 Sent, but wrong, expired or already used
+    Rejected(String),
+    /// 📡 The captcha provider couldn't be asked
+    Unavailable(anyhow::Error),
+}
+
+impl std::fmt::Display for ChallengeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ChallengeError::Missing => write!(f, "This submission needs a completed challenge"),
+            ChallengeError::Rejected(reason) => {
+                write!(f, "The challenge answer was not accepted: {}", reason)
+            }
+            ChallengeError::Unavailable(_) => {
+                write!(f, "The challenge could not be checked right now")
+            }
+        }
+    }
+}
+
+impl std::error::Error for ChallengeError {}
+
+impl ChallengeError {
+    /// 🚦 400 for a bad answer, 503 when the provider is unreachable
+    pub fn status(&self) -> StatusCode {
+        match self {
+            ChallengeError::Unavailable(_) => StatusCode::SERVICE_UNAVAILABLE,
+            _ => StatusCode::BAD_REQUEST,
+        }
+    }
+}
+
+/// 🧾 Proof-of-work challenges already redeemed, until they would have expired anyway
+#[derive(Debug, Default)]
+pub struct SpentChallenges {
+    spent: Mutex<HashMap<String, i64>>,
+}
+
+impl SpentChallenges {
+    /// 🔒 Mark `challenge` used; false when it already was
+    fn redeem(&self, challenge: &str, expires: i64, now: i64) -> bool {
+        let mut spent = self.spent.lock().unwrap();
+        spent.retain(|_, until| *until >= now);
+        spent.insert(challenge.to_string(), expires).is_none()
+    }
+}
+
+/// 🎫 What a client needs to answer the challenge
+#[derive(Debug, Serialize)]
+pub struct ChallengeInfo {
+    /// 🧩 off, hcaptcha, turnstile or pow
+    pub kind: ChallengeKind,
+    /// 🪪 Widget site key (captchas)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub site_key: Option<String>,
+    /// 🎲 The challenge to solve (pow)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub challenge: Option<String>,
+    /// 💪 Leading zero bits required (pow)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub difficulty: Option<u32>,
+    /// ⏰ Unix time after which the challenge is no longer accepted (pow)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expires_at: Option<i64>,
+}
+
+impl ChallengeInfo {
+    /// 🎫 A fresh challenge under the current configuration
+    pub fn issue(config: &ChallengeConfig, key: &str, now: i64) -> Self {
+        let mut info = Self {
+            kind: config.kind,
+            site_key: None,
+            challenge: None,
+            difficulty: None,
+            expires_at: None,
+        };
+        match config.kind {
+            ChallengeKind::Off => {}
+            ChallengeKind::HCaptcha | ChallengeKind::Turnstile => {
+                info.site_key = config.site_key.clone();
+            }
+            ChallengeKind::ProofOfWork => {
+                let expires = now + config.pow_ttl_seconds as i64;
+                info.challenge = Some(issue_pow(config.pow_difficulty, key, expires));
+                info.difficulty = Some(config.pow_difficulty);
+                info.expires_at = Some(expires);
+            }
+        }
+        info
+    }
+}
+
+/// 🎫 GET /api/feedback/challenge - what anonymous submissions must solve right now
+pub async fn get_challenge(State(app_state): State<AppState>) -> Response {
+    let info = ChallengeInfo::issue(
+        &app_state.config.challenge,
+        &app_state.config.auth.jwt_secret,
+        chrono::Utc::now().timestamp(),
+    );
+    Json(ApiResponse::success(
+        "Solve this before submitting without an account".to_string(),
+        info,
+    ))
+    .into_response()
+}
+
+/// ✅ Check an anonymous submission's answer (the captcha token, or
+/// `<challenge>:<nonce>` for proof-of-work). Always passes when ANON_CHALLENGE is off.
+pub async fn verify(
+    app_state: &AppState,
+    answer: Option<&str>,
+    remote_ip: Option<IpAddr>,
+) -> Result<(), ChallengeError> {
+    let config = &app_state.config.challenge;
+    if config.kind == ChallengeKind::Off {
+        return Ok(());
+    }
+    let answer = answer
+        .map(str::trim)
+        .filter(|answer| !answer.is_empty())
+        .ok_or(ChallengeError::Missing)?;
+    match config.kind {
+        ChallengeKind::Off => Ok(()),
+        ChallengeKind::HCaptcha | ChallengeKind::Turnstile => {
+            verify_captcha(config, answer, remote_ip).await
+        }
+        ChallengeKind::ProofOfWork => verify_pow(
+            answer,
+            config.pow_difficulty,
+            &app_state.config.auth.jwt_secret,
+            &app_state.spent_challenges,
+            chrono::Utc::now().timestamp(),
+        ),
+    }
+}
+
+/// 📨 What siteverify answers (both providers use the same shape)
+#[derive(Debug, Deserialize)]
+struct SiteVerifyResponse {
+    success: bool,
+    #[serde(default, rename = "error-codes")]
+    error_codes: Vec<String>,
+}
+
+/// 🧑 Ask the captcha provider whether the widget's token is genuine
+async fn verify_captcha(
+    config: &ChallengeConfig,
+    token: &str,
+    remote_ip: Option<IpAddr>,
+) -> Result<(), ChallengeError> {
+    let url = config.verify_url.as_deref().unwrap_or(match config.kind {
+        ChallengeKind::Turnstile => TURNSTILE_VERIFY_URL,
+        _ => HCAPTCHA_VERIFY_URL,
+    });
+    let mut form = vec![
+        ("secret", config.secret_key.clone().unwrap_or_default()),
+        ("response", token.to_string()),
+    ];
+    if let Some(site_key) = &config.site_key {
+        form.push(("sitekey", site_key.clone()));
+    }
+    if let Some(ip) = remote_ip {
+        form.push(("remoteip", ip.to_string()));
+    }
+
+    let unavailable = |e: reqwest::Error| ChallengeError::Unavailable(e.into());
+    let client = reqwest::Client::builder()
+        .timeout(Duration::from_secs(10))
+        .build()
+        .map_err(unavailable)?;
+    let verdict: SiteVerifyResponse = client
+        .post(url)
+        .form(&form)
+        .send()
+        .await
+        .and_then(|response| response.error_for_status())
+        .map_err(unavailable)?
+        .json()
+        .await
+        .map_err(unavailable)?;
+    if verdict.success {
+        Ok(())
+    } else {
+        warn!("🧩 Captcha rejected: {:?}", verdict.error_codes);
+        Err(ChallengeError::Rejected(
+            if verdict.error_codes.is_empty() {
+                "captcha failed".to_string()
+            } else {
+                verdict.error_codes.join(", ")
+            },
+        ))
+    }
+}
+
+/// 🎲 A signed proof-of-work challenge: `<expires>.<salt>.<hmac>`
+fn issue_pow(difficulty: u32, key: &str, expires: i64) -> String {
+    let mut salt = [0u8; 16];
+    rand::thread_rng().fill_bytes(&mut salt);
+    let salt = hex::encode(salt);
+    let signature = pow_signature(difficulty, key, expires, &salt);
+    format!("{}.{}.{}", expires, salt, signature)
+}
+
+/// 🔏 HMAC over the challenge's parts (and the difficulty it was issued for)
+fn pow_signature(difficulty: u32, key: &str, expires: i64, salt: &str) -> String {
+    let mut mac = Hmac::<Sha256>::new_from_slice(key.as_bytes()).expect("HMAC takes any key");
+    mac.update(format!("feedback-pow:{}:{}:{}", difficulty, expires, salt).as_bytes());
+    hex::encode(mac.finalize().into_bytes())
+}
+
+/// 💪 Check a `<challenge>:<nonce>` answer and redeem the challenge
+fn verify_pow(
+    answer: &str,
+    difficulty: u32,
+    key: &str,
+    spent: &SpentChallenges,
+    now: i64,
+) -> Result<(), ChallengeError> {
+    let rejected = |reason: &str| Err(ChallengeError::Rejected(reason.to_string()));
+    let Some((challenge, nonce)) = answer.rsplit_once(':') else {
+        return rejected("expected <challenge>:<nonce>");
+    };
+    let mut parts = challenge.splitn(3, '.');
+    let (Some(expires), Some(salt), Some(signature)) = (
+        parts.next().and_then(|expires| expires.parse::<i64>().ok()),
+        parts.next(),
+        parts.next(),
+    ) else {
+        return rejected("malformed challenge");
+    };
+    if !constant_time_eq(
+        signature.as_bytes(),
+        pow_signature(difficulty, key, expires, salt).as_bytes(),
+    ) {
+        return rejected("unknown challenge");
+    }
+    if expires < now {
+        return rejected("challenge expired");
+    }
+    if nonce.is_empty() || nonce.len() > MAX_NONCE_LENGTH {
+        return rejected("bad nonce");
+    }
+    if leading_zero_bits(&Sha256::digest(answer.as_bytes())) < difficulty {
+        return rejected("not enough work");
+    }
+    if !spent.redeem(challenge, expires, now) {
+        return rejected("challenge already used");
+    }
+    Ok(())
+}
+
+/// 🔢 Zero bits at the start of a hash
+fn leading_zero_bits(bytes: &[u8]) -> u32 {
+    let mut bits = 0;
+    for byte in bytes {
+        bits += byte.leading_zeros();
+        if *byte != 0 {
+            break;
+        }
+    }
+    bits
+}
+
+/// 📜 Browser solver for the feedback form: finds the nonce on submit, then posts
+pub const POW_SOLVER_SCRIPT: &str = r#"(function () {
+  var form = document.getElementById('feedback-form');
+  if (!form || !window.crypto || !crypto.subtle) return;
+  var leadingZeroBits = function (bytes) {
+    var bits = 0;
+    for (var i = 0; i < bytes.length; i++) {
+      if (bytes[i] !== 0) return bits + Math.clz32(bytes[i]) - 24;
+      bits += 8;
+    }
+    return bits;
+  };
+  form.addEventListener('submit', async function (event) {
+    var answer = form.elements['pow_answer'];
+    if (answer.value) return;
+    event.preventDefault();
+    var challenge = form.dataset.powChallenge;
+    var difficulty = Number(form.dataset.powDifficulty);
+    var encoder = new TextEncoder();
+    for (var nonce = 0; ; nonce++) {
+      var candidate = challenge + ':' + nonce.toString(36);
+      var digest = await crypto.subtle.digest('SHA-256', encoder.encode(candidate));
+      if (leadingZeroBits(new Uint8Array(digest)) >= difficulty) {
+        answer.value = candidate;
+        form.submit();
+        return;
+      }
+    }
+  });
+})();"#;
+
+// 🧪 Tests - Proving work, and proving it only once!
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wiremock::matchers::{body_string_contains, method};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    const KEY: &str = "this_is_a_very_long_secret_key_for_testing_purposes";
+
+    /// 🔨 Find a nonce the way the browser script does
+    fn solve(challenge: &str, difficulty: u32) -> String {
+        (0u64..)
+            .map(|nonce| format!("{}:{}", challenge, nonce))
+            .find(|answer| leading_zero_bits(&Sha256::digest(answer.as_bytes())) >= difficulty)
+            .unwrap()
+    }
+
+    fn captcha_config(kind: ChallengeKind, verify_url: String) -> ChallengeConfig {
+        ChallengeConfig {
+            kind,
+            site_key: Some("site-key".to_string()),
+            secret_key: Some("secret-key".to_string()),
+            verify_url: Some(verify_url),
+            pow_difficulty: 18,
+            pow_ttl_seconds: 600,
+        }
+    }
+
+    #[test]
+    fn test_proof_of_work_is_checked_and_redeemed_once() {
+        assert_eq!(leading_zero_bits(&[0, 0x1f, 0xff]), 11);
+        assert_eq!(leading_zero_bits(&[0, 0]), 16);
+
+        let spent = SpentChallenges::default();
+        let now = 1_700_000_000;
+        let challenge = issue_pow(8, KEY, now + 60);
+        let answer = solve(&challenge, 8);
+        let check = |answer: &str, difficulty, now| {
+            verify_pow(answer, difficulty, KEY, &spent, now).map_err(|e| e.to_string())
+        };
+
+        // 🚫 Forged, re-targeted, lazy and late answers all fail
+        let forged = format!(
+            "{}{}:1",
+            now + 3600,
+            &challenge[challenge.find('.').unwrap()..]
+        );
+        assert!(check(&forged, 8, now).unwrap_err().contains("unknown"));
+        assert!(check(&answer, 4, now).unwrap_err().contains("unknown"));
+        let lazy = (0u64..)
+            .map(|nonce| format!("{}:{}", challenge, nonce))
+            .find(|answer| leading_zero_bits(&Sha256::digest(answer.as_bytes())) == 0)
+            .unwrap();
+        assert!(check(&lazy, 8, now)
+            .unwrap_err()
+            .contains("not enough work"));
+        assert!(check(&answer, 8, now + 61).unwrap_err().contains("expired"));
+
+        // ✅ The real answer works exactly once
+        assert!(check(&answer, 8, now).is_ok());
+        assert!(check(&answer, 8, now).unwrap_err().contains("already used"));
+        println!("✅ Proof-of-work verification test passed!");
+    }
+
+    #[tokio::test]
+    async fn test_captcha_tokens_are_verified_with_the_provider() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(body_string_contains("response=good"))
+            .and(body_string_contains("secret=secret-key"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "success": true
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(body_string_contains("response=bad"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "success": false,
+                "error-codes": ["invalid-input-response"]
+            })))
+            .mount(&server)
+            .await;
+
+        let config = captcha_config(ChallengeKind::Turnstile, server.uri());
+        let ip = Some("203.0.113.9".parse().unwrap());
+        assert!(verify_captcha(&config, "good", ip).await.is_ok());
+        let Err(ChallengeError::Rejected(reason)) = verify_captcha(&config, "bad", ip).await else {
+            panic!("expected the token to be rejected");
+        };
+        assert_eq!(reason, "invalid-input-response");
+
+        // 📡 An unreachable provider is not the submitter's fault
+        let down = captcha_config(ChallengeKind::HCaptcha, "http://127.0.0.1:9".to_string());
+        let error = verify_captcha(&down, "good", None).await.unwrap_err();
+        assert_eq!(error.status(), StatusCode::SERVICE_UNAVAILABLE);
+        println!("✅ Captcha verification test passed!");
+    }
+
+    #[tokio::test]
+    async fn test_anonymous_submissions_need_a_solved_challenge() {
+        let Some(app) = crate::test_support::spawn_test_app_with_config(|config| {
+            config.challenge.kind = ChallengeKind::ProofOfWork;
+            config.challenge.pow_difficulty = 8;
+        })
+        .await
+        else {
+            return;
+        };
+        let owner: uuid::Uuid = sqlx::query_scalar(
+            "INSERT INTO users (email, name, password_hash) VALUES ('owner@example.com', 'Owner', 'x') RETURNING id",
+        )
+        .fetch_one(&app.db_pool)
+        .await
+        .unwrap();
+        sqlx::query("INSERT INTO projects (owner_id, repository) VALUES ($1, '8b-is/smart-tree')")
+            .bind(owner)
+            .execute(&app.db_pool)
+            .await
+            .unwrap();
+        let feedback_count = || async {
+            sqlx::query_scalar::<_, i64>("SELECT COUNT(*) FROM feedback")
+                .fetch_one(&app.db_pool)
+                .await
+                .unwrap()
+        };
+
+        // 🖼️ The form carries its own challenge and the solver
+        let html = app
+            .client
+            .get(app.url("/feedback"))
+            .send()
+            .await
+            .unwrap()
+            .text()
+            .await
+            .unwrap();
+        assert!(html.contains(r#"data-pow-difficulty="8""#));
+        assert!(html.contains(r#"name="pow_answer""#));
+
+        // 🚫 Unsolved, the submission is refused and nothing is stored
+        let post = |answer: String| {
+            app.client.post(app.url("/feedback")).form(&[
+                ("repository", "8b-is/smart-tree".to_string()),
+                ("category", "bug".to_string()),
+                ("title", "Crash on symlinks".to_string()),
+                ("description", "It loops forever on a cycle.".to_string()),
+                ("pow_answer", answer),
+            ])
+        };
+        let response = post(String::new()).send().await.unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        assert!(response
+            .text()
+            .await
+            .unwrap()
+            .contains("needs a completed challenge"));
+        assert_eq!(feedback_count().await, 0);
+
+        // 💪 Solved, it goes through - once
+        let issued: serde_json::Value = app
+            .client
+            .get(app.url("/api/feedback/challenge"))
+            .send()
+            .await
+            .unwrap()
+            .json()
+            .await
+            .unwrap();
+        assert_eq!(issued["data"]["kind"], "pow");
+        let answer = solve(issued["data"]["challenge"].as_str().unwrap(), 8);
+        let response = post(answer.clone()).send().await.unwrap();
+        assert_eq!(response.status(), StatusCode::CREATED);
+        let response = post(answer).send().await.unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        assert_eq!(feedback_count().await, 1);
+
+        // 📝 The JSON API asks the same of submissions without a user
+        let submit = |challenge: Option<String>| {
+            let request = serde_json::from_value(serde_json::json!({
+                "repository": "8b-is/smart-tree",
+                "content": "Please add a --json flag to the stats output",
+                "challenge": challenge,
+            }))
+            .unwrap();
+            crate::api::feedback::submit_feedback(
+                State(app.app_state.clone()),
+                axum::http::HeaderMap::new(),
+                None,
+                None,
+                crate::api::intake::VersionedFeedback {
+                    api_version: crate::api::intake::CURRENT_API_VERSION,
+                    request,
+                },
+            )
+        };
+        assert_eq!(submit(None).await.status(), StatusCode::BAD_REQUEST);
+        let info = ChallengeInfo::issue(
+            &app.app_state.config.challenge,
+            &app.app_state.config.auth.jwt_secret,
+            chrono::Utc::now().timestamp(),
+        );
+        let answer = solve(info.challenge.as_deref().unwrap(), 8);
+        assert_eq!(submit(Some(answer)).await.status(), StatusCode::CREATED);
+        assert_eq!(feedback_count().await, 2);
+        println!("✅ Anonymous challenge enforcement test passed!");
+    }
+}
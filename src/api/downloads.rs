@@ -0,0 +1,428 @@
+// 📦 Downloads - Where /mcp/check sends clients for the new release! 📦
+// GitHub releases by default. Operators can serve chosen product/platform pairs
+// from their own storage (ARTIFACT_SIGNED_TARGETS) through short-lived signed URLs:
+// {ARTIFACT_BASE_URL}/{product}/v{version}/{product}-{platform}-{arch}
+//   ?expires=<unix seconds>&signature=<hex HMAC-SHA256 of "<path>:<expires>">
+// The CDN or bucket checks the signature with the shared ARTIFACT_SIGNING_SECRET.
+// Versions published with a verified GitHub release also carry its assets, so
+// GitHub downloads point at the file for the client's platform/arch when there is one.
+// Created with love by Aye & Hue! ✨
+
+use chrono::{DateTime, Utc};
+
+use super::settings_cache::ReleaseDownload;
+use crate::config::DownloadsConfig;
+use crate::github::releases::GitHubRelease;
+use crate::utils::signatures::{sign, SecretPair};
+
+/// 🌳 Product a check is about when the client doesn't say
+pub const DEFAULT_PRODUCT: &str = "smart-tree";
+
+/// 🔗 Where a client should fetch a release from
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DownloadLink {
+    pub url: String,
+    /// ⏳ When a signed URL stops working (None for GitHub links)
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+/// 🧼 Product, platform, arch and version end up in a URL path, so only plain names qualify
+fn is_path_safe(segment: &str) -> bool {
+    !segment.is_empty()
+        && segment.len() <= 64
+        && !segment.starts_with('.')
+        && segment
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'))
+}
+
+/// 🐙 The GitHub release page (the default for everything)
+pub fn github_release_url(product: &str, version: &str) -> String {
+    format!(
+        "https://github.com/8b-is/{}/releases/tag/v{}",
+        product, version
+    )
+}
+
+/// 🎯 Is this product/platform pair served from the artifact storage?
+pub fn is_signed_target(config: &DownloadsConfig, product: &str, platform: &str) -> bool {
+    config.signed_targets.iter().any(|target| {
+        let (target_product, target_platform) = target.split_once('/').unwrap_or((target, "*"));
+        (target_product == "*" || target_product == product)
+            && (target_platform == "*" || target_platform == platform)
+    })
+}
+
+/// 🌳 Does /mcp/check know this product? (MCP_PRODUCTS, case-insensitive)
+pub fn is_known_product(config: &DownloadsConfig, product: &str) -> bool {
+    config
+        .products
+        .iter()
+        .any(|known| known.eq_ignore_ascii_case(product))
+}
+
+/// 💬 What a check about a product we don't know is told
+pub fn unknown_product_message(config: &DownloadsConfig, product: &str) -> String {
+    match &config.unknown_product_message {
+        Some(message) => message.replace("{product}", product),
+        None => format!(
+            "Unknown product \"{}\". This server answers update checks for: {}. \
+             Check the `product` your client sends.",
+            product,
+            config.products.join(", ")
+        ),
+    }
+}
+
+/// 🖥️ Platform and arch an asset is built for, read from its file name
+/// ("st-v5.2.0-aarch64-apple-darwin.tar.gz" -> macos/aarch64). Checksums and
+/// signatures, and names that don't say both, have no target.
+pub fn asset_target(name: &str) -> Option<(&'static str, &'static str)> {
+    let name = name.to_lowercase();
+    if [".sha256", ".sha512", ".sig", ".asc", ".txt", ".json"]
+        .iter()
+        .any(|suffix| name.ends_with(suffix))
+    {
+        return None;
+    }
+    let has = |words: &[&str]| words.iter().any(|word| name.contains(word));
+    let platform = if has(&["linux"]) {
+        "linux"
+    } else if has(&["darwin", "macos", "apple", "osx"]) {
+        "macos"
+    } else if has(&["windows", "win64", ".exe"]) {
+        "windows"
+    } else {
+        return None;
+    };
+    let arch = if has(&["x86_64", "amd64", "x64"]) {
+        "x86_64"
+    } else if has(&["aarch64", "arm64"]) {
+        "aarch64"
+    } else {
+        return None;
+    };
+    Some((platform, arch))
+}
+
+/// 📦 One download per platform/arch the release ships (the first matching asset wins)
+pub fn release_downloads(release: &GitHubRelease) -> Vec<ReleaseDownload> {
+    let mut downloads: Vec<ReleaseDownload> = Vec::new();
+    for asset in &release.assets {
+        let Some((platform, arch)) = asset_target(&asset.name) else {
+            continue;
+        };
+        if downloads
+            .iter()
+            .any(|known| known.platform == platform && known.arch == arch)
+        {
+            continue;
+        }
+        downloads.push(ReleaseDownload {
+            platform: platform.to_string(),
+            arch: arch.to_string(),
+            url: asset.browser_download_url.clone(),
+            size: asset.size,
+        });
+    }
+    downloads
+}
+
+/// 🔍 The release asset for a client's platform/arch, if the release has one
+pub fn release_download<'a>(
+    downloads: &'a [ReleaseDownload],
+    platform: &str,
+    arch: &str,
+) -> Option<&'a ReleaseDownload> {
+    let arch = match arch.to_lowercase().as_str() {
+        "amd64" | "x64" => "x86_64".to_string(),
+        "arm64" => "aarch64".to_string(),
+        other => other.to_string(),
+    };
+    let platform = match platform.to_lowercase().as_str() {
+        "darwin" => "macos".to_string(),
+        other => other.to_string(),
+    };
+    downloads
+        .iter()
+        .find(|download| download.platform == platform && download.arch == arch)
+}
+
+/// 🔏 Hex signature the storage expects for `path` until `expires` (unix seconds)
+pub fn download_signature(secret: &str, path: &str, expires: i64) -> String {
+    sign(secret, format!("{}:{}", path, expires).as_bytes())
+        .trim_start_matches("sha256=")
+        .to_string()
+}
+
+/// ✅ What the storage does: the URL hasn't expired and the signature matches (constant time)
+pub fn verify_download(
+    secret: &str,
+    path: &str,
+    expires: i64,
+    signature: &str,
+    now: DateTime<Utc>,
+) -> bool {
+    if now.timestamp() >= expires {
+        return false;
+    }
+    let secrets = SecretPair {
+        primary: secret.to_string(),
+        previous: None,
+        previous_expires_at: None,
+    };
+    secrets
+        .verify(
+            format!("{}:{}", path, expires).as_bytes(),
+            &format!("sha256={}", signature),
+            now,
+        )
+        .is_some()
+}
+
+/// 🔗 Download link for a release: signed artifact URL when configured, GitHub otherwise.
+/// Anything unusual in the names falls back to GitHub rather than building an odd path.
+pub fn download_link(
+    config: &DownloadsConfig,
+    product: &str,
+    version: &str,
+    platform: &str,
+    arch: &str,
+    now: DateTime<Utc>,
+) -> DownloadLink {
+    let product = if is_path_safe(product) {
+        product
+    } else {
+        DEFAULT_PRODUCT
+    };
+    let github = DownloadLink {
+        url: github_release_url(product, version),
+        expires_at: None,
+    };
+
+    let (Some(base_url), Some(secret)) = (&config.artifact_base_url, &config.signing_secret) else {
+        return github;
+    };
+    if !is_signed_target(config, product, platform)
+        || ![version, platform, arch].into_iter().all(is_path_safe)
+    {
+        return github;
+    }
+
+    let path = format!("/{product}/v{version}/{product}-{platform}-{arch}");
+    let expires_at = now + chrono::Duration::seconds(config.url_ttl_seconds as i64);
+    let expires = expires_at.timestamp();
+    DownloadLink {
+        url: format!(
+            "{}{}?expires={}&signature={}",
+            base_url,
+            path,
+            expires,
+            download_signature(secret, &path, expires)
+        ),
+        expires_at: Some(expires_at),
+    }
+}
+
+// 🧪 Tests - Handing out keys that expire!
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SECRET: &str = "an-artifact-secret-of-32-chars!!";
+
+    fn config(targets: &[&str]) -> DownloadsConfig {
+        DownloadsConfig {
+            artifact_base_url: Some("https://cdn.example.com/releases".to_string()),
+            signing_secret: Some(SECRET.to_string()),
+            url_ttl_seconds: 600,
+            signed_targets: targets.iter().map(|t| t.to_string()).collect(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_unknown_products_get_a_helpful_message() {
+        let mut config = config(&[]);
+        config.products = vec!["smart-tree".to_string(), "mem8".to_string()];
+        assert!(is_known_product(&config, "Smart-Tree"));
+        assert!(!is_known_product(&config, "smart-forest"));
+        assert_eq!(
+            unknown_product_message(&config, "smart-forest"),
+            "Unknown product \"smart-forest\". This server answers update checks for: \
+             smart-tree, mem8. Check the `product` your client sends."
+        );
+        config.unknown_product_message = Some("No updates for {product} here".to_string());
+        assert_eq!(
+            unknown_product_message(&config, "smart-forest"),
+            "No updates for smart-forest here"
+        );
+        println!("✅ Unknown product message test passed!");
+    }
+
+    #[test]
+    fn test_release_assets_are_matched_to_platforms() {
+        let asset = |name: &str, size: u64| crate::github::releases::ReleaseAsset {
+            name: name.to_string(),
+            browser_download_url: format!(
+                "https://github.com/8b-is/smart-tree/releases/download/v5.2.0/{}",
+                name
+            ),
+            size,
+        };
+        let release = GitHubRelease {
+            tag_name: "v5.2.0".to_string(),
+            draft: false,
+            prerelease: false,
+            html_url: "https://github.com/8b-is/smart-tree/releases/tag/v5.2.0".to_string(),
+            assets: vec![
+                asset("st-v5.2.0-x86_64-unknown-linux-gnu.tar.gz", 100),
+                asset("st-v5.2.0-x86_64-unknown-linux-gnu.tar.gz.sha256", 64),
+                asset("st-v5.2.0-x86_64-unknown-linux-musl.tar.gz", 90),
+                asset("st-v5.2.0-aarch64-apple-darwin.tar.gz", 80),
+                asset("st-v5.2.0-x86_64-pc-windows-msvc.zip", 120),
+                asset("install.sh", 2),
+            ],
+        };
+        let downloads = release_downloads(&release);
+        assert_eq!(downloads.len(), 3);
+        let linux = release_download(&downloads, "linux", "amd64").unwrap();
+        assert_eq!(linux.size, 100);
+        assert!(linux.url.ends_with("linux-gnu.tar.gz"));
+        assert_eq!(
+            release_download(&downloads, "Darwin", "arm64")
+                .unwrap()
+                .size,
+            80
+        );
+        assert!(release_download(&downloads, "windows", "aarch64").is_none());
+        println!("✅ Release asset matching test passed!");
+    }
+
+    #[test]
+    fn test_signed_targets_match_product_and_platform() {
+        let config = config(&["smart-tree/linux", "other/*"]);
+        assert!(is_signed_target(&config, "smart-tree", "linux"));
+        assert!(!is_signed_target(&config, "smart-tree", "macos"));
+        assert!(is_signed_target(&config, "other", "windows"));
+        assert!(is_signed_target(&self::config(&["*/macos"]), "x", "macos"));
+        assert!(!is_signed_target(&self::config(&[]), "smart-tree", "linux"));
+        println!("✅ Signed target matching test passed!");
+    }
+
+    #[test]
+    fn test_signed_download_link_verifies_until_it_expires() {
+        let now = Utc::now();
+        let link = download_link(
+            &config(&["smart-tree/linux"]),
+            "smart-tree",
+            "5.3.0",
+            "linux",
+            "x86_64",
+            now,
+        );
+        let expires_at = link.expires_at.unwrap();
+        assert_eq!(expires_at, now + chrono::Duration::seconds(600));
+
+        let url = reqwest::Url::parse(&link.url).unwrap();
+        assert_eq!(url.host_str(), Some("cdn.example.com"));
+        let path = url.path().trim_start_matches("/releases").to_string();
+        assert_eq!(path, "/smart-tree/v5.3.0/smart-tree-linux-x86_64");
+        let query: std::collections::HashMap<_, _> = url.query_pairs().into_owned().collect();
+        let expires: i64 = query["expires"].parse().unwrap();
+        assert_eq!(expires, expires_at.timestamp());
+
+        assert!(verify_download(
+            SECRET,
+            &path,
+            expires,
+            &query["signature"],
+            now
+        ));
+        // ⏰ Expired, tampered with, or signed with another key: rejected
+        assert!(!verify_download(
+            SECRET,
+            &path,
+            expires,
+            &query["signature"],
+            expires_at
+        ));
+        assert!(!verify_download(
+            SECRET,
+            "/smart-tree/v5.3.0/smart-tree-linux-aarch64",
+            expires,
+            &query["signature"],
+            now
+        ));
+        assert!(!verify_download(
+            SECRET,
+            &path,
+            expires + 3600,
+            &query["signature"],
+            now
+        ));
+        assert!(!verify_download(
+            "another-secret-that-is-32-chars!",
+            &path,
+            expires,
+            &query["signature"],
+            now
+        ));
+        println!("✅ Signed download link test passed!");
+    }
+
+    #[test]
+    fn test_github_stays_the_default() {
+        let now = Utc::now();
+        let github = |link: DownloadLink| {
+            assert_eq!(link.expires_at, None);
+            link.url
+        };
+        // 🐙 Not a signed target, nothing configured, or a name that doesn't belong in a path
+        assert_eq!(
+            github(download_link(
+                &config(&["smart-tree/linux"]),
+                "smart-tree",
+                "5.3.0",
+                "macos",
+                "aarch64",
+                now
+            )),
+            "https://github.com/8b-is/smart-tree/releases/tag/v5.3.0"
+        );
+        assert_eq!(
+            github(download_link(
+                &DownloadsConfig::default(),
+                "smart-tree",
+                "5.3.0",
+                "linux",
+                "x86_64",
+                now
+            )),
+            "https://github.com/8b-is/smart-tree/releases/tag/v5.3.0"
+        );
+        assert_eq!(
+            github(download_link(
+                &config(&["*"]),
+                "smart-tree",
+                "5.3.0",
+                "linux",
+                "../../etc",
+                now
+            )),
+            "https://github.com/8b-is/smart-tree/releases/tag/v5.3.0"
+        );
+        assert_eq!(
+            github(download_link(
+                &config(&[]),
+                "../x",
+                "5.3.0",
+                "linux",
+                "x86_64",
+                now
+            )),
+            "https://github.com/8b-is/smart-tree/releases/tag/v5.3.0"
+        );
+        println!("✅ GitHub default download test passed!");
+    }
+}
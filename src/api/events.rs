@@ -0,0 +1,364 @@
+// 📣 Event Bus - Live updates for the admin, without unbounded queues! 📣
+// Producers publish a typed `AppEvent` (ids and status only - subscribers fetch the
+// details they need - except that completion carries the feedback's GitHub links) on
+// one bounded tokio `broadcast` channel on AppState. A subscriber
+// that falls more than EVENT_BUS_CAPACITY events behind is not disconnected and does
+// not buffer: its missed events are dropped, the lag is counted on /metrics, and it gets
+// a fresh snapshot from the database instead, so it is in sync again either way.
+// GET /admin/api/events streams all of it as server-sent events.
+// Created with love by Aye & Hue! ✨
+
+use axum::{
+    extract::State,
+    response::{
+        sse::{Event, KeepAlive, Sse},
+        IntoResponse, Response,
+    },
+};
+use axum_extra::extract::CookieJar;
+use serde::Serialize;
+use sqlx::PgPool;
+use std::collections::BTreeMap;
+use std::convert::Infallible;
+use std::sync::Arc;
+use tokio::sync::broadcast::{self, error::RecvError};
+use tracing::warn;
+use uuid::Uuid;
+
+use crate::api::AppState;
+use crate::database::models::FeedbackStatus;
+use crate::github::artifacts::GithubArtifacts;
+use crate::metrics::Metrics;
+
+/// 📏 Events a subscriber may fall behind before it is resynchronized. Each slot holds
+/// one small `AppEvent`, so the whole buffer stays a few KB however slow a client is.
+pub const EVENT_BUS_CAPACITY: usize = 256;
+/// 📋 Most recently changed items included in a snapshot
+const SNAPSHOT_RECENT: i64 = 50;
+
+/// 📣 Everything that can go on the bus (producers can't publish anything else)
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+#[allow(clippy::enum_variant_names)]
+pub enum AppEvent {
+    /// 📥 New feedback was accepted
+    FeedbackCreated { id: Uuid, status: FeedbackStatus },
+    /// 🔄 A feedback item moved to another status
+    FeedbackStatusChanged { id: Uuid, status: FeedbackStatus },
+    /// 🎉 A feedback item completed with a pull request (boxed to keep slots small)
+    FeedbackCompleted {
+        id: Uuid,
+        github: Box<GithubArtifacts>,
+    },
+}
+
+impl AppEvent {
+    /// 🏷️ SSE event name
+    pub fn name(&self) -> &'static str {
+        match self {
+            AppEvent::FeedbackCreated { .. } => "feedback_created",
+            AppEvent::FeedbackStatusChanged { .. } => "feedback_status_changed",
+            AppEvent::FeedbackCompleted { .. } => "feedback_completed",
+        }
+    }
+}
+
+/// 📸 Where things stand, for subscribers (re)joining
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct EventSnapshot {
+    /// 🔢 Feedback per status
+    pub counts: BTreeMap<String, i64>,
+    /// 📋 The most recently created or finished items
+    pub recent: Vec<FeedbackState>,
+}
+
+/// 🪪 One item's id and status
+#[derive(Debug, Clone, PartialEq, Serialize, sqlx::FromRow)]
+pub struct FeedbackState {
+    pub id: Uuid,
+    pub status: FeedbackStatus,
+}
+
+impl EventSnapshot {
+    /// 🗄️ Read the current counts and recent items
+    pub async fn load(pool: &PgPool) -> anyhow::Result<Self> {
+        let counts: Vec<(FeedbackStatus, i64)> =
+            sqlx::query_as("SELECT status, COUNT(*) FROM feedback GROUP BY status")
+                .fetch_all(pool)
+                .await?;
+        let recent = sqlx::query_as(
+            r#"
+            SELECT id, status FROM feedback
+            ORDER BY COALESCE(completed_at, created_at) DESC, id DESC
+            LIMIT $1
+            "#,
+        )
+        .bind(SNAPSHOT_RECENT)
+        .fetch_all(pool)
+        .await?;
+        Ok(Self {
+            counts: counts
+                .into_iter()
+                .map(|(status, count)| (status.to_string(), count))
+                .collect(),
+            recent,
+        })
+    }
+}
+
+/// 📬 What a subscriber receives next
+#[derive(Debug, Clone, PartialEq)]
+pub enum Delivery {
+    Event(AppEvent),
+    /// 🔁 Events were missed; here is the current state instead (None when the
+    /// database couldn't be read - the subscriber should refetch on its own)
+    Resync(Option<EventSnapshot>),
+}
+
+/// 📣 The bounded broadcast channel behind live updates
+#[derive(Debug)]
+pub struct EventBus {
+    sender: broadcast::Sender<AppEvent>,
+}
+
+impl Default for EventBus {
+    fn default() -> Self {
+        Self::new(EVENT_BUS_CAPACITY)
+    }
+}
+
+impl EventBus {
+    /// ➕ A bus keeping at most `capacity` events for its slowest subscriber
+    pub fn new(capacity: usize) -> Self {
+        let (sender, _) = broadcast::channel(capacity);
+        Self { sender }
+    }
+
+    /// 📤 Publish to whoever is listening (nobody listening is fine)
+    pub fn publish(&self, event: AppEvent) {
+        let _ = self.sender.send(event);
+    }
+
+    /// 👂 Listen from now on; lag is resolved from `pool` and counted on `metrics`
+    pub fn subscribe(&self, pool: PgPool, metrics: Arc<Metrics>) -> EventSubscriber {
+        EventSubscriber {
+            receiver: self.sender.subscribe(),
+            pool,
+            metrics,
+        }
+    }
+}
+
+/// 👂 One subscriber, with the lag policy built in
+pub struct EventSubscriber {
+    receiver: broadcast::Receiver<AppEvent>,
+    pool: PgPool,
+    metrics: Arc<Metrics>,
+}
+
+impl EventSubscriber {
+    /// 📬 The next event, or a snapshot after falling behind; None once the bus is gone
+    pub async fn next(&mut self) -> Option<Delivery> {
+        match self.receiver.recv().await {
+            Ok(event) => Some(Delivery::Event(event)),
+            Err(RecvError::Lagged(skipped)) => {
+                // 🐢 The receiver already jumped to the oldest event still buffered
+                self.metrics.record_event_lag(skipped);
+                warn!(
+                    "🐢 Event subscriber fell {} events behind, resynchronizing",
+                    skipped
+                );
+                let snapshot = EventSnapshot::load(&self.pool)
+                    .await
+                    .inspect_err(|e| warn!("⚠️ Failed to load resync snapshot: {:#}", e))
+                    .ok();
+                Some(Delivery::Resync(snapshot))
+            }
+            Err(RecvError::Closed) => None,
+        }
+    }
+}
+
+/// 📡 GET /admin/api/events - live feedback events as server-sent events. Starts with a
+/// `snapshot`, then `feedback_created` / `feedback_status_changed` as they happen, and a
+/// `resync` snapshot whenever this connection falls behind.
+pub async fn admin_events(State(app_state): State<AppState>, jar: CookieJar) -> Response {
+    if let Some(denied) = crate::api::admin::require_admin_api_auth(&jar, &app_state).await {
+        return denied;
+    }
+    // 👂 Subscribe before reading the snapshot, so nothing slips in between
+    let subscriber = app_state
+        .events
+        .subscribe(app_state.db_pool.clone(), app_state.metrics.clone());
+    let snapshot = EventSnapshot::load(&app_state.db_pool)
+        .await
+        .inspect_err(|e| warn!("⚠️ Failed to load event snapshot: {:#}", e))
+        .ok();
+    let first = sse_event("snapshot", &snapshot);
+
+    let stream = futures_util::stream::unfold(
+        (Some(first), subscriber),
+        |(first, mut subscriber)| async move {
+            if let Some(first) = first {
+                return Some((Ok::<_, Infallible>(first), (None, subscriber)));
+            }
+            let event = match subscriber.next().await? {
+                Delivery::Event(event) => sse_event(event.name(), &event),
+                Delivery::Resync(snapshot) => sse_event("resync", &snapshot),
+            };
+            Some((Ok(event), (None, subscriber)))
+        },
+    );
+    Sse::new(stream)
+        .keep_alive(KeepAlive::default())
+        .into_response()
+}
+
+/// ✉️ A named SSE event with a JSON body
+fn sse_event(name: &str, data: &impl Serialize) -> Event {
+    Event::default()
+        .event(name)
+        .json_data(data)
+        .unwrap_or_else(|_| Event::default().event(name).data("null"))
+}
+
+// 🧪 Tests - Slow listeners catch up instead of falling over!
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 📖 Read the stream into `received` until `needle` shows up
+    async fn read_until(stream: &mut reqwest::Response, received: &mut String, needle: &str) {
+        while !received.contains(needle) {
+            let chunk = tokio::time::timeout(std::time::Duration::from_secs(5), stream.chunk())
+                .await
+                .expect("event stream stalled")
+                .unwrap()
+                .expect("event stream ended");
+            received.push_str(&String::from_utf8_lossy(&chunk));
+        }
+    }
+
+    #[test]
+    fn test_events_serialize_small_and_typed() {
+        let id = Uuid::nil();
+        let event = AppEvent::FeedbackStatusChanged {
+            id,
+            status: FeedbackStatus::Completed,
+        };
+        assert_eq!(event.name(), "feedback_status_changed");
+        assert_eq!(
+            serde_json::to_value(&event).unwrap(),
+            serde_json::json!({
+                "type": "feedback_status_changed",
+                "id": id,
+                "status": "completed"
+            })
+        );
+        let completed = AppEvent::FeedbackCompleted {
+            id,
+            github: Box::new(crate::github::artifacts::GithubArtifacts::assemble(
+                &crate::github::artifacts::StoredArtifacts {
+                    repository: "8b-is/smart-tree",
+                    pull_request_url: Some("https://github.com/8b-is/smart-tree/pull/42"),
+                    ..Default::default()
+                },
+            )),
+        };
+        assert_eq!(completed.name(), "feedback_completed");
+        let body = serde_json::to_value(&completed).unwrap();
+        assert_eq!(body["type"], "feedback_completed");
+        assert_eq!(body["github"]["pull_request"]["number"], 42);
+        assert_eq!(body["github"]["pull_request"]["state"], "open");
+        println!("✅ Event serialization test passed!");
+    }
+
+    #[tokio::test]
+    async fn test_lagging_subscriber_recovers_with_a_snapshot() {
+        let Some(app) = crate::test_support::spawn_test_app().await else {
+            return;
+        };
+        sqlx::query(
+            "INSERT INTO feedback (repository, content, status) VALUES ('8b-is/smart-tree', 'a', 'completed'), ('8b-is/smart-tree', 'b', 'pending')",
+        )
+        .execute(&app.db_pool)
+        .await
+        .unwrap();
+
+        // 🤏 Room for two events, and five are published before anyone reads
+        let bus = EventBus::new(2);
+        let metrics = Arc::new(Metrics::default());
+        let mut subscriber = bus.subscribe(app.db_pool.clone(), metrics.clone());
+        let ids: Vec<Uuid> = (0..5).map(|_| Uuid::new_v4()).collect();
+        for id in &ids {
+            bus.publish(AppEvent::FeedbackCreated {
+                id: *id,
+                status: FeedbackStatus::Pending,
+            });
+        }
+
+        let Some(Delivery::Resync(Some(snapshot))) = subscriber.next().await else {
+            panic!("expected a resync snapshot");
+        };
+        assert_eq!(snapshot.counts["completed"], 1);
+        assert_eq!(snapshot.counts["pending"], 1);
+        assert_eq!(snapshot.recent.len(), 2);
+        assert_eq!(metrics.event_lags(), (1, 3));
+
+        // 📬 Then the events that were still buffered, and live ones after that
+        for id in &ids[3..] {
+            assert_eq!(
+                subscriber.next().await,
+                Some(Delivery::Event(AppEvent::FeedbackCreated {
+                    id: *id,
+                    status: FeedbackStatus::Pending
+                }))
+            );
+        }
+        drop(bus);
+        assert_eq!(subscriber.next().await, None);
+        println!("✅ Event lag recovery test passed!");
+    }
+
+    #[tokio::test]
+    async fn test_admin_event_stream_carries_new_feedback() {
+        let Some(app) = crate::test_support::spawn_test_app().await else {
+            return;
+        };
+        assert_eq!(
+            app.client
+                .get(app.url("/admin/api/events"))
+                .send()
+                .await
+                .unwrap()
+                .status(),
+            axum::http::StatusCode::UNAUTHORIZED
+        );
+        app.login_admin().await.unwrap();
+        let mut stream = app
+            .client
+            .get(app.url("/admin/api/events"))
+            .send()
+            .await
+            .unwrap();
+        assert!(stream.headers()["content-type"]
+            .to_str()
+            .unwrap()
+            .starts_with("text/event-stream"));
+
+        let mut received = String::new();
+        read_until(&mut stream, &mut received, "event: snapshot").await;
+
+        let request = serde_json::from_value(serde_json::json!({
+            "repository": "8b-is/smart-tree",
+            "content": "Live updates in the admin would be lovely"
+        }))
+        .unwrap();
+        crate::api::feedback::create_feedback_record(&app.app_state, request, None, None)
+            .await
+            .unwrap();
+        read_until(&mut stream, &mut received, "event: feedback_created").await;
+        assert!(received.contains("\"status\":\"pending\""));
+        println!("✅ Admin event stream test passed!");
+    }
+}
@@ -6,21 +6,42 @@
 
 use anyhow::{Context, Result};
 use axum::{
-    extract::{Path, Query, State},
-    http::StatusCode,
+    extract::{ConnectInfo, Path, Query, State},
+    http::{HeaderMap, StatusCode},
     response::{IntoResponse, Json, Response},
+    Extension,
 };
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
 use sqlx::Row; // 🔧 Added Row trait import for database row access
+use std::net::SocketAddr;
 use tracing::{error, info, warn};
 use uuid::Uuid;
 
 use crate::{
     api::{
-        utils::{handle_error, not_found_error, validation_error},
-        ApiResponse, AppState, PaginatedResponse, PaginationParams, ValidateRequest,
+        admin::record_audit,
+        intake::VersionedFeedback,
+        json::ApiJson,
+        queue_stats::QueueEstimate,
+        sources,
+        utils::{
+            forbidden_error, handle_error, not_found_error, rate_limit_error, validation_error,
+        },
+        ApiResponse, AppState, ErrorCode, PaginatedResponse, PaginationParams, ValidateRequest,
+    },
+    database::{
+        feedback_votes,
+        models::{Feedback, FeedbackStats, FeedbackStatus},
+        processing_holds::{self, ProcessingHold},
+    },
+    github::{
+        artifacts::{self, ArtifactLookup, GithubArtifacts},
+        availability::{self, RepositoryUnavailable},
     },
-    database::models::{Feedback, FeedbackStats, FeedbackStatus},
+    jobs::approval::{self, Decision, DecisionOutcome},
+    middleware::{auth::AuthenticatedUser, rate_limiting::RateLimitScope},
+    utils::net::resolve_outbound_url,
 };
 
 /// 📝 Feedback submission request structure
@@ -37,6 +58,21 @@ pub struct SubmitFeedbackRequest {
     pub metadata: Option<serde_json::Value>,
     /// 👤 User information (for anonymous submissions)
     pub user_info: Option<AnonymousUserInfo>,
+    /// 📞 https URL to POST the final status to (optional)
+    pub callback_url: Option<String>,
+    /// 💥 How much this hurts, 1-10 (optional)
+    pub impact_score: Option<u8>,
+    /// 🔁 How often it comes up, 1-10 (optional)
+    pub frequency_score: Option<u8>,
+    /// 🔝 Explicit queue priority, 0-100 (optional, wins over the scores)
+    pub priority: Option<i32>,
+    /// 🏷️ Free-form tags (optional, merged with any `metadata.tags`)
+    pub tags: Option<Vec<String>>,
+    /// 📡 Submission channel, e.g. "cli" (optional, else `metadata.source`, else the User-Agent)
+    pub source: Option<String>,
+    /// 🧩 Answer to the anonymous challenge: the captcha token, or `<challenge>:<nonce>`
+    /// for proof-of-work (only checked without an account, see GET /api/feedback/challenge)
+    pub challenge: Option<String>,
 }
 
 /// 👤 Anonymous user information for feedback without accounts
@@ -55,10 +91,24 @@ pub struct SubmitFeedbackResponse {
     pub feedback_id: Uuid,
     /// 📋 Current status of the feedback
     pub status: FeedbackStatus,
-    /// 🔗 URL to track the feedback progress
+    /// 🔗 URL to track the feedback progress (relative, kept for older clients)
     pub tracking_url: String,
+    /// 🔗 Absolute URL of the JSON status (GET /api/feedback/:id)
+    pub status_url: String,
+    /// 🌐 Absolute URL of the public status page to send people to
+    pub html_url: String,
     /// ⏰ Estimated processing time in minutes
     pub estimated_processing_time: u32,
+    /// 🔏 Secret for verifying callback signatures (only when a callback_url was given,
+    /// and never again for a duplicate)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub callback_secret: Option<String>,
+    /// 🔁 True when this is an earlier identical submission, returned instead of a new one
+    pub duplicate: bool,
+    /// ⏸️ Set when impact × frequency is below the project's `processing.min_score`:
+    /// the feedback stays pending until someone starts it by hand
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub held_below_threshold: Option<ProcessingHold>,
 }
 
 /// 📊 Detailed feedback information for responses
@@ -86,6 +136,22 @@ pub struct FeedbackDetails {
     pub updated_at: chrono::DateTime<chrono::Utc>,
     /// ✅ When completed (if applicable)
     pub completed_at: Option<chrono::DateTime<chrono::Utc>>,
+    /// 🔝 Effective queue priority
+    pub priority: i32,
+    /// 🏷️ Normalized tags
+    pub tags: Vec<String>,
+    /// 📡 Submission channel
+    pub source: String,
+    /// ⏳ Queue position and timing estimates (single-item lookups only)
+    #[serde(flatten)]
+    pub queue: QueueEstimate,
+    /// 📎 Logs, screenshots, ... (single-item lookups only)
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub attachments: Vec<crate::api::attachments::Attachment>,
+    /// 🔗 Branch, pull request and issue links with their current state (single-item
+    /// lookups only)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub github: Option<GithubArtifacts>,
 }
 
 /// 🔍 Feedback query parameters for listing
@@ -103,11 +169,29 @@ pub struct FeedbackQuery {
     pub from_date: Option<chrono::DateTime<chrono::Utc>>,
     /// ⏰ Filter by date range (to)
     pub to_date: Option<chrono::DateTime<chrono::Utc>>,
+    /// 🏷️ Filter by tag
+    pub tag: Option<String>,
+    /// 📡 Filter by submission channel
+    pub source: Option<String>,
 }
 
+/// 📏 Default maximum feedback content length (characters), see FEEDBACK_MAX_CONTENT_LENGTH
+pub const DEFAULT_MAX_CONTENT_LENGTH: usize = 10_000;
+/// 💥 Highest impact/frequency score
+pub const MAX_SCORE: u8 = 10;
+/// 🔝 Highest queue priority (a 10 x 10 score)
+pub const MAX_PRIORITY: i32 = 100;
+
 impl ValidateRequest for SubmitFeedbackRequest {
-    /// ✅ Validate feedback submission request
+    /// ✅ Validate feedback submission request against the default content limit
     fn validate(&self) -> Result<(), Vec<String>> {
+        self.validate_with_limit(DEFAULT_MAX_CONTENT_LENGTH)
+    }
+}
+
+impl SubmitFeedbackRequest {
+    /// ✅ Validate feedback submission request with a configured content limit
+    pub fn validate_with_limit(&self, max_content_length: usize) -> Result<(), Vec<String>> {
         let mut errors = Vec::new();
 
         // 🎯 Validate repository format
@@ -117,12 +201,16 @@ impl ValidateRequest for SubmitFeedbackRequest {
             errors.push("Repository must be in 'owner/repo' format".to_string());
         }
 
-        // 📝 Validate content
+        // 📝 Validate content (lengths in characters, not bytes, both ways)
+        let content_length = self.content.chars().count();
         if self.content.trim().is_empty() {
             errors.push("Feedback content cannot be empty".to_string());
-        } else if self.content.len() > 10000 {
-            errors.push("Feedback content cannot exceed 10,000 characters".to_string());
-        } else if self.content.len() < 10 {
+        } else if content_length > max_content_length {
+            errors.push(format!(
+                "Feedback content cannot exceed {} characters",
+                max_content_length
+            ));
+        } else if content_length < 10 {
             errors.push("Feedback content must be at least 10 characters".to_string());
         }
 
@@ -133,6 +221,37 @@ impl ValidateRequest for SubmitFeedbackRequest {
             }
         }
 
+        // 🔝 Scores and priority share one scale: impact x frequency lands in 1-100
+        for (name, score) in [
+            ("impact_score", self.impact_score),
+            ("frequency_score", self.frequency_score),
+        ] {
+            if score.is_some_and(|score| !(1..=MAX_SCORE).contains(&score)) {
+                errors.push(format!("{} must be between 1 and {}", name, MAX_SCORE));
+            }
+        }
+        if self
+            .priority
+            .is_some_and(|priority| !(0..=MAX_PRIORITY).contains(&priority))
+        {
+            errors.push(format!("priority must be between 0 and {}", MAX_PRIORITY));
+        }
+
+        // 🏷️ Tags are checked after normalization, which is how they are stored
+        errors.extend(crate::api::tags::validate_tags(&self.normalized_tags()));
+
+        // 📡 An explicit source must already be a valid channel name (metadata.source is best effort)
+        if self
+            .source
+            .as_deref()
+            .is_some_and(|source| sources::normalize_source(source).is_none())
+        {
+            errors.push(format!(
+                "source must be 1-{} characters of a-z, 0-9, '_', '-' or '.'",
+                sources::MAX_SOURCE_LENGTH
+            ));
+        }
+
         // 📧 Validate anonymous user info if provided
         if let Some(user_info) = &self.user_info {
             if let Some(email) = &user_info.email {
@@ -148,51 +267,199 @@ impl ValidateRequest for SubmitFeedbackRequest {
             Err(errors)
         }
     }
+
+    /// 🔝 Explicit priority, else impact x frequency, else the configured default
+    pub fn effective_priority(&self, default_priority: i32) -> i32 {
+        match (self.priority, self.impact_score, self.frequency_score) {
+            (Some(priority), _, _) => priority,
+            (None, Some(impact), Some(frequency)) => impact as i32 * frequency as i32,
+            // 🤷 Only one score: pair it with a middling guess for the other
+            (None, Some(score), None) | (None, None, Some(score)) => {
+                score as i32 * (MAX_SCORE as i32 / 2)
+            }
+            (None, None, None) => default_priority,
+        }
+    }
+
+    /// 📏 Impact × frequency, only when both were given (what the processing threshold checks)
+    pub fn threshold_score(&self) -> Option<i32> {
+        Some(self.impact_score? as i32 * self.frequency_score? as i32)
+    }
+
+    /// 🏷️ Normalized tags from `tags` plus any strings in `metadata.tags`
+    pub fn normalized_tags(&self) -> Vec<String> {
+        let metadata_tags = self
+            .metadata
+            .as_ref()
+            .and_then(|metadata| metadata.get("tags"))
+            .and_then(|tags| tags.as_array())
+            .into_iter()
+            .flatten()
+            .filter_map(|tag| tag.as_str());
+        crate::api::tags::normalize_tags(
+            self.tags
+                .iter()
+                .flatten()
+                .map(String::as_str)
+                .chain(metadata_tags),
+        )
+    }
+
+    /// 📡 `source`, else `metadata.source`, else a guess from the User-Agent, else "unknown"
+    pub fn effective_source(&self, user_agent: Option<&str>) -> String {
+        let metadata_source = self
+            .metadata
+            .as_ref()
+            .and_then(|metadata| metadata.get("source"))
+            .and_then(|source| source.as_str());
+        self.source
+            .as_deref()
+            .into_iter()
+            .chain(metadata_source)
+            .find_map(sources::normalize_source)
+            .or_else(|| {
+                user_agent
+                    .and_then(sources::source_from_user_agent)
+                    .map(str::to_string)
+            })
+            .unwrap_or_else(|| sources::UNKNOWN_SOURCE.to_string())
+    }
+
+    /// 🔁 SHA256 of who sent it, where to and what it says, so an accidental double
+    /// submit hashes the same: the repository is lowercased and runs of whitespace in
+    /// the content collapse to one space
+    pub fn dedup_hash(&self, user_id: Uuid) -> String {
+        let content = self
+            .content
+            .split_whitespace()
+            .collect::<Vec<_>>()
+            .join(" ");
+        let mut hasher = Sha256::new();
+        hasher.update(user_id.as_bytes());
+        hasher.update(self.repository.trim().to_lowercase().as_bytes());
+        hasher.update([0]);
+        hasher.update(content.as_bytes());
+        hex::encode(hasher.finalize())
+    }
 }
 
 /// 📝 Submit new feedback for processing
 /// This is the main endpoint where users submit their improvement ideas!
 pub async fn submit_feedback(
     State(app_state): State<AppState>,
-    Json(request): Json<SubmitFeedbackRequest>,
+    headers: HeaderMap,
+    connect_info: Option<ConnectInfo<SocketAddr>>,
+    user: Option<Extension<AuthenticatedUser>>,
+    VersionedFeedback {
+        api_version,
+        request,
+    }: VersionedFeedback,
 ) -> Response {
     info!(
-        "📝 Received feedback submission for repository: {}",
-        request.repository
+        "📝 Received v{} feedback submission for repository: {}",
+        api_version, request.repository
     );
 
-    // ✅ Validate the request
-    if let Err(errors) = request.validate() {
+    // 🚦 Per-IP allowance (RATE_LIMIT_FEEDBACK_PER_HOUR), shared with the HTML form
+    let client_ip = crate::api::mcp::extract_client_ip(&app_state, &headers, connect_info.as_ref());
+    if let Some(ip) = client_ip {
+        if let Err(retry_after) = app_state.rate_limiter.check(RateLimitScope::Feedback, ip) {
+            warn!("🚫 Feedback rate limit exceeded for {}", ip);
+            return rate_limit_error(retry_after).into_response();
+        }
+    }
+
+    // ✅ Validate the request (the callback URL check may resolve DNS)
+    let max_content_length = app_state.config.feedback.max_content_length;
+    let mut validation = request.validate_with_limit(max_content_length);
+    if let Some(callback_url) = &request.callback_url {
+        if let Err(e) = resolve_outbound_url(
+            callback_url,
+            app_state.config.feedback.allow_private_callbacks,
+        )
+        .await
+        {
+            let message = format!("Invalid callback_url: {}", e);
+            match &mut validation {
+                Ok(()) => validation = Err(vec![message]),
+                Err(errors) => errors.push(message),
+            }
+        }
+    }
+    if let Err(errors) = validation {
         warn!("❌ Validation failed for feedback submission: {:?}", errors);
         let api_response = ApiResponse::<()>::error(
-            "validation_error".to_string(),
+            ErrorCode::ValidationError,
             "Request validation failed".to_string(),
-            Some(serde_json::json!({ "errors": errors })),
+            Some(serde_json::json!({
+                "errors": errors,
+                "max_content_length": max_content_length,
+            })),
         );
         return (StatusCode::BAD_REQUEST, Json(api_response)).into_response();
     }
 
+    // 🧩 Without an account, the anonymous challenge must be solved first (ANON_CHALLENGE)
+    if user.is_none() {
+        if let Err(e) =
+            crate::api::challenge::verify(&app_state, request.challenge.as_deref(), client_ip).await
+        {
+            warn!("🧩 Anonymous feedback challenge failed: {}", e);
+            let api_response = ApiResponse::<()>::error(
+                ErrorCode::ChallengeFailed,
+                e.to_string(),
+                Some(serde_json::json!({
+                    "challenge_url": app_state.config.public_url("/api/feedback/challenge"),
+                })),
+            );
+            return (e.status(), Json(api_response)).into_response();
+        }
+    }
+
     // 🔍 Check if the repository is accessible and aye-is is a collaborator
     // TODO: Add repository validation when GitHub module is ready
     // if !github_client.is_collaborator(&request.repository, "aye-is").await? {
     //     return forbidden_error();
     // }
 
-    match create_feedback_record(&app_state, request).await {
+    let user_agent = sources::user_agent(&headers);
+    let user_id = user.map(|Extension(user)| user.id);
+    match create_feedback_record(&app_state, request, user_id, user_agent.as_deref()).await {
+        Ok(response) if response.duplicate => {
+            info!(
+                "🔁 Duplicate submission, returning earlier feedback: {}",
+                response.feedback_id
+            );
+            (
+                StatusCode::OK,
+                Json(ApiResponse::<SubmitFeedbackResponse>::success(
+                    "Identical feedback was submitted moments ago, returning it instead."
+                        .to_string(),
+                    response,
+                )),
+            )
+                .into_response()
+        }
         Ok(response) => {
             info!(
                 "✅ Feedback submitted successfully: {}",
                 response.feedback_id
             );
 
-            // 🚀 Queue the feedback for processing
+            // 🚀 Queue the feedback for processing (held feedback waits for "Release hold")
             // TODO: Add job queuing when background jobs module is ready
             // app_state.job_queue.queue_feedback_processing(response.feedback_id).await?;
 
+            let message = if response.held_below_threshold.is_some() {
+                "Feedback submitted! It scores below this project's threshold, so it will \
+                 wait for a maintainer to start it."
+            } else {
+                "Feedback submitted successfully! Processing will begin shortly."
+            };
             (
                 StatusCode::CREATED,
                 Json(ApiResponse::<SubmitFeedbackResponse>::success(
-                    "Feedback submitted successfully! Processing will begin shortly.".to_string(),
+                    message.to_string(),
                     response,
                 )),
             )
@@ -202,7 +469,7 @@ pub async fn submit_feedback(
             error!("❌ Failed to submit feedback: {:#}", e);
             let error_msg = format!("{:#}", e);
             let api_response = ApiResponse::<()>::error(
-                "internal_error".to_string(),
+                ErrorCode::InternalError,
                 "An internal error occurred".to_string(),
                 Some(serde_json::json!({ "details": error_msg })),
             );
@@ -219,7 +486,8 @@ pub async fn get_feedback(
 ) -> Response {
     info!("🔍 Fetching feedback details for ID: {}", feedback_id);
 
-    match fetch_feedback_details(&app_state, feedback_id).await {
+    // 🗃️ Anyone with the id may ask, so GitHub states come from the cache alone
+    match fetch_feedback_details(&app_state, feedback_id, ArtifactLookup::Cached).await {
         Ok(Some(feedback)) => {
             info!("✅ Found feedback: {}", feedback_id);
             (
@@ -231,7 +499,7 @@ pub async fn get_feedback(
         Ok(None) => {
             warn!("🔍 Feedback not found: {}", feedback_id);
             let api_response = ApiResponse::<()>::error(
-                "not_found".to_string(),
+                ErrorCode::NotFound,
                 "Feedback not found".to_string(),
                 None,
             );
@@ -241,7 +509,7 @@ pub async fn get_feedback(
             error!("❌ Failed to fetch feedback {}: {:#}", feedback_id, e);
             let error_msg = format!("{:#}", e);
             let api_response = ApiResponse::<()>::error(
-                "internal_error".to_string(),
+                ErrorCode::InternalError,
                 "An internal error occurred".to_string(),
                 Some(serde_json::json!({ "details": error_msg })),
             );
@@ -277,7 +545,7 @@ pub async fn list_feedback(
             error!("❌ Failed to list feedback: {:#}", e);
             let error_msg = format!("{:#}", e);
             let api_response = ApiResponse::<()>::error(
-                "internal_error".to_string(),
+                ErrorCode::InternalError,
                 "An internal error occurred".to_string(),
                 Some(serde_json::json!({ "details": error_msg })),
             );
@@ -310,7 +578,7 @@ pub async fn get_feedback_stats(
             error!("❌ Failed to get feedback statistics: {:#}", e);
             let error_msg = format!("{:#}", e);
             let api_response = ApiResponse::<()>::error(
-                "internal_error".to_string(),
+                ErrorCode::InternalError,
                 "An internal error occurred".to_string(),
                 Some(serde_json::json!({ "details": error_msg })),
             );
@@ -338,11 +606,20 @@ pub async fn retry_feedback(
             )
                 .into_response()
         }
+        Err(e) if RepositoryUnavailable::find(&e).is_some() => {
+            info!("🚪 Not retrying feedback {}: {:#}", feedback_id, e);
+            let api_response = ApiResponse::<()>::error(
+                ErrorCode::RepositoryUnavailable,
+                format!("{:#}", e),
+                None,
+            );
+            (StatusCode::CONFLICT, Json(api_response)).into_response()
+        }
         Err(e) => {
             error!("❌ Failed to retry feedback {}: {:#}", feedback_id, e);
             let error_msg = format!("{:#}", e);
             let api_response = ApiResponse::<()>::error(
-                "internal_error".to_string(),
+                ErrorCode::InternalError,
                 "An internal error occurred".to_string(),
                 Some(serde_json::json!({ "details": error_msg })),
             );
@@ -351,45 +628,346 @@ pub async fn retry_feedback(
     }
 }
 
+/// ✋ Approve a feedback item's held changes (project owner or admin) and resume the
+/// pipeline at the commit stage. Approving twice is harmless.
+pub async fn approve_feedback(
+    State(app_state): State<AppState>,
+    Path(feedback_id): Path<Uuid>,
+    Extension(user): Extension<AuthenticatedUser>,
+) -> Response {
+    decide_held_feedback(&app_state, feedback_id, &user, Decision::Approved).await
+}
+
+/// 🚫 Reject a feedback item's held changes (project owner or admin); the feedback
+/// fails with `rejected_by_owner`
+pub async fn reject_feedback(
+    State(app_state): State<AppState>,
+    Path(feedback_id): Path<Uuid>,
+    Extension(user): Extension<AuthenticatedUser>,
+) -> Response {
+    decide_held_feedback(&app_state, feedback_id, &user, Decision::Rejected).await
+}
+
+/// 👍 Vote for a feedback item (one vote per signed-in user; voting again is harmless).
+/// Votes show up in the body of the feedback's pull request.
+pub async fn vote_feedback(
+    State(app_state): State<AppState>,
+    Path(feedback_id): Path<Uuid>,
+    Extension(user): Extension<AuthenticatedUser>,
+) -> Response {
+    cast_vote(&app_state, feedback_id, &user, true).await
+}
+
+/// 👎 Take a vote back
+pub async fn unvote_feedback(
+    State(app_state): State<AppState>,
+    Path(feedback_id): Path<Uuid>,
+    Extension(user): Extension<AuthenticatedUser>,
+) -> Response {
+    cast_vote(&app_state, feedback_id, &user, false).await
+}
+
+/// 🗳️ Shared by vote and unvote: answers with the votes the feedback now has
+async fn cast_vote(
+    app_state: &AppState,
+    feedback_id: Uuid,
+    user: &AuthenticatedUser,
+    up: bool,
+) -> Response {
+    let pool = &app_state.db_pool;
+    match Feedback::find_by_id(pool, feedback_id).await {
+        Ok(Some(_)) => {}
+        Ok(None) => return not_found_error("Feedback").into_response(),
+        Err(e) => return handle_error(e).into_response(),
+    }
+    let votes = if up {
+        feedback_votes::vote(pool, feedback_id, user.id).await
+    } else {
+        feedback_votes::unvote(pool, feedback_id, user.id).await
+    };
+    match votes {
+        Ok(votes) => (
+            StatusCode::OK,
+            Json(ApiResponse::success(
+                if up { "Vote recorded" } else { "Vote removed" }.to_string(),
+                serde_json::json!({ "id": feedback_id, "votes": votes }),
+            )),
+        )
+            .into_response(),
+        Err(e) => handle_error(e).into_response(),
+    }
+}
+
+/// ⚖️ Shared by approve and reject: check who's asking, then record the decision
+async fn decide_held_feedback(
+    app_state: &AppState,
+    feedback_id: Uuid,
+    user: &AuthenticatedUser,
+    decision: Decision,
+) -> Response {
+    let pool = &app_state.db_pool;
+    let feedback = match Feedback::find_by_id(pool, feedback_id).await {
+        Ok(Some(feedback)) => feedback,
+        Ok(None) => return not_found_error("Feedback").into_response(),
+        Err(e) => return handle_error(e).into_response(),
+    };
+    let owns_project: bool = match sqlx::query_scalar(
+        r#"
+        SELECT EXISTS (
+            SELECT 1 FROM projects p
+            JOIN project_repository_routes r ON r.project_id = p.id
+            WHERE r.repository = $1 AND p.owner_id = $2
+        )
+        "#,
+    )
+    .bind(&feedback.repository)
+    .bind(user.id)
+    .fetch_one(pool)
+    .await
+    {
+        Ok(owns_project) => owns_project,
+        Err(e) => return handle_error(e.into()).into_response(),
+    };
+    if !owns_project && !user.is_admin() {
+        return forbidden_error().into_response();
+    }
+
+    match record_decision(app_state, feedback_id, decision, Some(user.id), &user.email).await {
+        Ok(DecisionOutcome::Decided(feedback)) => {
+            info!(
+                "⚖️ {} {} the changes for feedback {}",
+                user.email,
+                decision.as_str(),
+                feedback_id
+            );
+            app_state
+                .events
+                .publish(crate::api::events::AppEvent::FeedbackStatusChanged {
+                    id: feedback.id,
+                    status: feedback.status.clone(),
+                });
+            (
+                StatusCode::OK,
+                Json(ApiResponse::success(
+                    format!("Changes {}", decision.as_str()),
+                    serde_json::json!({ "id": feedback.id, "status": feedback.status }),
+                )),
+            )
+                .into_response()
+        }
+        Ok(DecisionOutcome::AlreadyDecided(earlier)) if earlier == decision => (
+            StatusCode::OK,
+            Json(ApiResponse::<()>::success_no_data(format!(
+                "Changes were already {}",
+                earlier.as_str()
+            ))),
+        )
+            .into_response(),
+        Ok(DecisionOutcome::AlreadyDecided(earlier)) => (
+            StatusCode::CONFLICT,
+            Json(ApiResponse::<()>::error(
+                ErrorCode::AlreadyDecided,
+                format!("Changes were already {}", earlier.as_str()),
+                None,
+            )),
+        )
+            .into_response(),
+        Ok(DecisionOutcome::NotHeld) => (
+            StatusCode::CONFLICT,
+            Json(ApiResponse::<()>::error(
+                ErrorCode::NotAwaitingApproval,
+                "Feedback has no changes waiting for approval".to_string(),
+                None,
+            )),
+        )
+            .into_response(),
+        Err(e) => handle_error(e).into_response(),
+    }
+}
+
 // 🔧 Helper functions for the API endpoints
 
-/// ➕ Create a new feedback record in the database
-async fn create_feedback_record(
+/// ⚖️ Decide on held changes and audit it in one transaction (the console shares this)
+pub(crate) async fn record_decision(
+    app_state: &AppState,
+    feedback_id: Uuid,
+    decision: Decision,
+    decided_by: Option<Uuid>,
+    actor: &str,
+) -> Result<DecisionOutcome> {
+    let mut tx = app_state.tx().await?;
+    let outcome = async {
+        let outcome = approval::decide(&mut *tx, feedback_id, decision, decided_by).await?;
+        if let DecisionOutcome::Decided(_) = outcome {
+            record_audit(
+                &mut *tx,
+                actor,
+                &format!("feedback_changes_{}", decision.as_str()),
+                serde_json::json!({ "feedback_id": feedback_id }),
+            )
+            .await?;
+        }
+        Ok(outcome)
+    }
+    .await;
+    tx.finish(outcome).await
+}
+
+/// ➕ Create a new feedback record in the database (`user_agent` only feeds the source guess).
+/// A signed-in user's identical submission within FEEDBACK_DEDUP_WINDOW_SECONDS returns
+/// the earlier record, marked `duplicate`; anonymous feedback is never deduplicated.
+pub(crate) async fn create_feedback_record(
     app_state: &AppState,
     request: SubmitFeedbackRequest,
+    user_id: Option<Uuid>,
+    user_agent: Option<&str>,
 ) -> Result<SubmitFeedbackResponse> {
-    // TODO: Get user_id from authentication when auth module is ready
-    let user_id = None; // For now, support anonymous feedback
+    let priority = request.effective_priority(app_state.config.feedback.default_priority);
+    let score = request.threshold_score();
+    let tags = request.normalized_tags();
+    let source = request.effective_source(user_agent);
+    let window = app_state.config.feedback.dedup_window_seconds;
+    let dedup_hash = user_id
+        .filter(|_| window > 0)
+        .map(|user_id| request.dedup_hash(user_id));
+    // 🧾 The record and its hold commit together, so no worker ever sees held feedback unheld
+    let mut tx = app_state.tx().await?;
+    let (feedback, duplicate) = match dedup_hash {
+        Some(dedup_hash) => Feedback::create_deduplicated(
+            &mut tx,
+            user_id,
+            request.repository.clone(),
+            request.content,
+            request.callback_url,
+            priority,
+            request.metadata,
+            &source,
+            &dedup_hash,
+            chrono::Duration::seconds(window as i64),
+        )
+        .await
+        .context("Failed to create feedback record")?,
+        None => (
+            Feedback::create_in(
+                &mut tx,
+                user_id,
+                request.repository.clone(),
+                request.content,
+                request.callback_url,
+                priority,
+                request.metadata,
+                &source,
+            )
+            .await
+            .context("Failed to create feedback record")?,
+            false,
+        ),
+    };
+    if duplicate {
+        tx.commit().await?;
+        return Ok(submit_response(app_state, &feedback, None, true));
+    }
 
-    let feedback = Feedback::create(
-        &app_state.db_pool,
-        user_id,
-        request.repository.clone(),
-        request.content,
-    )
-    .await
-    .context("Failed to create feedback record")?;
+    // ⏸️ Below the project's threshold: stored, but nobody spends tokens on it unasked
+    let held_below_threshold = match score {
+        Some(score) => processing_holds::hold_if_below_threshold(
+            &mut tx,
+            feedback.id,
+            &feedback.repository,
+            score,
+        )
+        .await
+        .context("Failed to check the processing threshold")?,
+        None => None,
+    };
+    tx.commit().await?;
+
+    // 🏷️ The feedback is already accepted, so a tagging hiccup only costs us statistics
+    if let Err(e) = crate::api::tags::store_tags(&app_state.db_pool, feedback.id, &tags).await {
+        warn!(
+            "⚠️ Failed to store tags for feedback {}: {:#}",
+            feedback.id, e
+        );
+    }
+
+    app_state
+        .events
+        .publish(crate::api::events::AppEvent::FeedbackCreated {
+            id: feedback.id,
+            status: feedback.status.clone(),
+        });
+
+    let callback_secret = feedback.callback_secret.clone();
+    let mut response = submit_response(app_state, &feedback, callback_secret, false);
+    response.held_below_threshold = held_below_threshold;
+    Ok(response)
+}
 
-    let response = SubmitFeedbackResponse {
+/// 📦 The submission response for a stored feedback record
+fn submit_response(
+    app_state: &AppState,
+    feedback: &Feedback,
+    callback_secret: Option<String>,
+    duplicate: bool,
+) -> SubmitFeedbackResponse {
+    SubmitFeedbackResponse {
         feedback_id: feedback.id,
-        status: feedback.status,
+        status: feedback.status.clone(),
         tracking_url: format!("/api/feedback/{}", feedback.id),
+        status_url: app_state
+            .config
+            .public_url(&format!("/api/feedback/{}", feedback.id)),
+        html_url: app_state
+            .config
+            .public_url(&format!("/feedback/{}", feedback.id)),
         estimated_processing_time: 5, // 5 minutes estimate
-    };
-
-    Ok(response)
+        callback_secret,
+        duplicate,
+        held_below_threshold: None,
+    }
 }
 
 /// 🔍 Fetch detailed feedback information
-async fn fetch_feedback_details(
+pub(crate) async fn fetch_feedback_details(
     app_state: &AppState,
     feedback_id: Uuid,
+    lookup: ArtifactLookup,
 ) -> Result<Option<FeedbackDetails>> {
-    let feedback = Feedback::find_by_id(&app_state.db_pool, feedback_id)
+    let Some(f) = Feedback::find_by_id(&app_state.db_pool, feedback_id)
         .await
-        .context("Failed to fetch feedback from database")?;
+        .context("Failed to fetch feedback from database")?
+    else {
+        return Ok(None);
+    };
+    feedback_details(app_state, f, lookup).await.map(Some)
+}
 
-    Ok(feedback.map(|f| FeedbackDetails {
+/// 📊 Tags, attachments and queue estimate for an already loaded feedback item
+pub(crate) async fn feedback_details(
+    app_state: &AppState,
+    f: Feedback,
+    lookup: ArtifactLookup,
+) -> Result<FeedbackDetails> {
+    let tags = crate::api::tags::tags_for(&app_state.db_pool, f.id).await?;
+    let attachments = crate::api::attachments::for_feedback(&app_state.db_pool, f.id).await?;
+    let queue = app_state
+        .queue_stats
+        .snapshot(
+            &app_state.db_pool,
+            app_state.config.feedback.worker_concurrency,
+        )
+        .await?
+        .estimate(&f, chrono::Utc::now());
+    let github = artifacts::current_artifacts(
+        &app_state.db_pool,
+        &app_state.github,
+        &app_state.github_states,
+        &f,
+        lookup,
+    )
+    .await?;
+
+    Ok(FeedbackDetails {
         id: f.id,
         repository: f.repository,
         content_preview: truncate_content(&f.content, 200),
@@ -401,7 +979,13 @@ async fn fetch_feedback_details(
         created_at: f.created_at,
         updated_at: f.updated_at,
         completed_at: f.completed_at,
-    }))
+        priority: f.priority,
+        tags,
+        source: f.source,
+        queue,
+        attachments,
+        github: Some(github),
+    })
 }
 
 /// 📋 Fetch a paginated list of feedback
@@ -415,21 +999,41 @@ async fn fetch_feedback_list(
     let mut params = Vec::new();
     let mut param_index = 1;
 
+    // 🔗 Every filter is bound as text and cast in SQL
     if let Some(status) = &query.status {
-        sql_where.push(format!("status = ${}", param_index));
-        params.push(serde_json::to_value(status)?);
+        sql_where.push(format!("status = ${}::feedback_status", param_index));
+        params.push(status.as_str().to_string());
         param_index += 1;
     }
 
     if let Some(repository) = &query.repository {
         sql_where.push(format!("repository = ${}", param_index));
-        params.push(serde_json::to_value(repository)?);
+        params.push(repository.clone());
         param_index += 1;
     }
 
     if let Some(user_id) = &query.user_id {
-        sql_where.push(format!("user_id = ${}", param_index));
-        params.push(serde_json::to_value(user_id)?);
+        sql_where.push(format!("user_id = ${}::uuid", param_index));
+        params.push(user_id.to_string());
+        param_index += 1;
+    }
+
+    if let Some(tag) = query
+        .tag
+        .as_deref()
+        .and_then(crate::api::tags::normalize_tag)
+    {
+        sql_where.push(format!(
+            "EXISTS (SELECT 1 FROM feedback_tags t WHERE t.feedback_id = feedback.id AND t.tag = ${})",
+            param_index
+        ));
+        params.push(tag);
+        param_index += 1;
+    }
+
+    if let Some(source) = query.source.as_deref().and_then(sources::normalize_source) {
+        sql_where.push(format!("source = ${}", param_index));
+        params.push(source);
         // param_index would be incremented here if more filters were added
     }
 
@@ -441,18 +1045,22 @@ async fn fetch_feedback_list(
 
     // 📊 Get total count
     let count_query = format!("SELECT COUNT(*) FROM feedback {}", where_clause);
-    let total = sqlx::query_scalar::<_, i64>(&count_query)
+    let mut count_sql = sqlx::query_scalar::<_, i64>(&count_query);
+    for param in &params {
+        count_sql = count_sql.bind(param);
+    }
+    let total = count_sql
         .fetch_one(&app_state.db_pool)
         .await
         .context("Failed to get feedback count")?;
 
-    // 📋 Get the actual feedback records
+    // 📋 Get the actual feedback records (id breaks timestamp ties so pages never overlap)
+    let direction = match pagination.sort_order {
+        crate::api::SortOrder::Asc => "ASC",
+        crate::api::SortOrder::Desc => "DESC",
+    };
     let order_clause = format!(
-        "ORDER BY created_at {} LIMIT {} OFFSET {}",
-        match pagination.sort_order {
-            crate::api::SortOrder::Asc => "ASC",
-            crate::api::SortOrder::Desc => "DESC",
-        },
+        "ORDER BY created_at {direction}, id {direction} LIMIT {} OFFSET {}",
         pagination.limit,
         pagination.offset()
     );
@@ -460,7 +1068,8 @@ async fn fetch_feedback_list(
     let query_sql = format!(
         r#"
         SELECT id, repository, content, status, branch_name, pull_request_url,
-               llm_provider, error_message, created_at, updated_at, completed_at
+               llm_provider, error_message, created_at, updated_at, completed_at, priority, source,
+               ARRAY(SELECT t.tag FROM feedback_tags t WHERE t.feedback_id = feedback.id ORDER BY t.tag)::text[] AS tags
         FROM feedback
         {}
         {}
@@ -468,7 +1077,11 @@ async fn fetch_feedback_list(
         where_clause, order_clause
     );
 
-    let feedback_records = sqlx::query(&query_sql)
+    let mut records_sql = sqlx::query(&query_sql);
+    for param in &params {
+        records_sql = records_sql.bind(param);
+    }
+    let feedback_records = records_sql
         .fetch_all(&app_state.db_pool)
         .await
         .context("Failed to fetch feedback list")?;
@@ -479,7 +1092,7 @@ async fn fetch_feedback_list(
             id: row.get("id"),
             repository: row.get("repository"),
             content_preview: truncate_content(&row.get::<String, _>("content"), 200),
-            status: serde_json::from_value(row.get("status")).unwrap_or(FeedbackStatus::Pending),
+            status: row.get("status"),
             branch_name: row.get("branch_name"),
             pull_request_url: row.get("pull_request_url"),
             llm_provider: row.get("llm_provider"),
@@ -487,6 +1100,12 @@ async fn fetch_feedback_list(
             created_at: row.get("created_at"),
             updated_at: row.get("updated_at"),
             completed_at: row.get("completed_at"),
+            priority: row.get("priority"),
+            tags: row.get("tags"),
+            source: row.get("source"),
+            queue: QueueEstimate::default(),
+            attachments: Vec::new(),
+            github: None,
         })
         .collect();
 
@@ -513,16 +1132,30 @@ async fn retry_feedback_processing(app_state: &AppState, feedback_id: Uuid) -> R
         FeedbackStatus::Failed | FeedbackStatus::Paused
     ) {
         anyhow::bail!(
-            "Feedback is not in a retryable state (current status: {:?})",
+            "Feedback is not in a retryable state (current status: {})",
             feedback.status
         );
     }
+    // 🚪 Retrying against a repository that's gone can't work until it's reactivated
+    if feedback.error_message.as_deref() == Some(availability::REPOSITORY_UNAVAILABLE) {
+        if let Some(unavailable) =
+            availability::unavailable(&app_state.db_pool, &feedback.repository).await?
+        {
+            return Err(unavailable.into());
+        }
+    }
 
     // 🔄 Reset the feedback status to pending
     feedback
         .update_status(&app_state.db_pool, FeedbackStatus::Pending, None)
         .await
         .context("Failed to reset feedback status")?;
+    app_state
+        .events
+        .publish(crate::api::events::AppEvent::FeedbackStatusChanged {
+            id: feedback_id,
+            status: feedback.status.clone(),
+        });
 
     // 🚀 Queue the feedback for processing again
     // TODO: Add job queuing when background jobs module is ready
@@ -533,12 +1166,11 @@ async fn retry_feedback_processing(app_state: &AppState, feedback_id: Uuid) -> R
     Ok(())
 }
 
-/// ✂️ Truncate content for preview (privacy-friendly)
-fn truncate_content(content: &str, max_length: usize) -> String {
-    if content.len() <= max_length {
-        content.to_string()
-    } else {
-        format!("{}...", &content[..max_length])
+/// ✂️ Truncate content for preview (privacy-friendly), to `max_length` characters
+pub(crate) fn truncate_content(content: &str, max_length: usize) -> String {
+    match content.char_indices().nth(max_length) {
+        Some((cut, _)) => format!("{}...", &content[..cut]),
+        None => content.to_string(),
     }
 }
 
@@ -555,6 +1187,13 @@ mod tests {
             llm_provider: Some("openai".to_string()),
             metadata: None,
             user_info: None,
+            callback_url: None,
+            impact_score: None,
+            frequency_score: None,
+            priority: None,
+            tags: None,
+            source: None,
+            challenge: None,
         };
 
         assert!(valid_request.validate().is_ok());
@@ -566,13 +1205,550 @@ mod tests {
             llm_provider: Some("invalid_provider".to_string()),
             metadata: None,
             user_info: None,
+            callback_url: None,
+            impact_score: None,
+            frequency_score: None,
+            priority: None,
+            tags: None,
+            source: None,
+            challenge: None,
         };
 
         let errors = invalid_request.validate().unwrap_err();
-        assert!(errors.len() > 0);
+        assert!(!errors.is_empty());
         println!("✅ Invalid feedback request validation test passed!");
     }
 
+    #[test]
+    fn test_content_length_limit() {
+        let request = SubmitFeedbackRequest {
+            repository: "owner/repo".to_string(),
+            content: "é".repeat(50),
+            llm_provider: None,
+            metadata: None,
+            user_info: None,
+            callback_url: None,
+            impact_score: None,
+            frequency_score: None,
+            priority: None,
+            tags: None,
+            source: None,
+            challenge: None,
+        };
+
+        // 📏 Limit counts characters, not bytes
+        assert!(request.validate_with_limit(50).is_ok());
+        let errors = request.validate_with_limit(49).unwrap_err();
+        assert_eq!(
+            errors,
+            vec!["Feedback content cannot exceed 49 characters".to_string()]
+        );
+
+        // 📏 So does the minimum: five emoji are 20 bytes but still too short
+        let short = SubmitFeedbackRequest {
+            content: "🌳".repeat(5),
+            ..request
+        };
+        assert_eq!(
+            short.validate_with_limit(50).unwrap_err(),
+            vec!["Feedback content must be at least 10 characters".to_string()]
+        );
+        println!("✅ Feedback content length limit test passed!");
+    }
+
+    #[test]
+    fn test_effective_priority() {
+        let request = |impact, frequency, priority| SubmitFeedbackRequest {
+            repository: "owner/repo".to_string(),
+            content: "Scores should decide what gets looked at first".to_string(),
+            llm_provider: None,
+            metadata: None,
+            user_info: None,
+            callback_url: None,
+            impact_score: impact,
+            frequency_score: frequency,
+            priority,
+            tags: None,
+            source: None,
+            challenge: None,
+        };
+
+        assert_eq!(request(Some(9), Some(8), None).effective_priority(25), 72);
+        assert_eq!(request(Some(9), Some(8), Some(3)).effective_priority(25), 3);
+        assert_eq!(request(Some(6), None, None).effective_priority(25), 30);
+        assert_eq!(request(None, None, None).effective_priority(25), 25);
+
+        assert!(request(Some(10), Some(1), Some(100)).validate().is_ok());
+        let errors = request(Some(0), Some(11), Some(101))
+            .validate()
+            .unwrap_err();
+        assert_eq!(errors.len(), 3);
+        println!("✅ Effective priority test passed!");
+    }
+
+    #[tokio::test]
+    async fn test_priority_is_persisted_on_submission() {
+        let Some(app) = crate::test_support::spawn_test_app().await else {
+            return;
+        };
+        let request = SubmitFeedbackRequest {
+            repository: "8b-is/smart-tree".to_string(),
+            content: "The tree view crashes on symlink loops".to_string(),
+            llm_provider: None,
+            metadata: None,
+            user_info: None,
+            callback_url: None,
+            impact_score: Some(9),
+            frequency_score: Some(7),
+            priority: None,
+            tags: None,
+            source: None,
+            challenge: None,
+        };
+        let created = create_feedback_record(&app.app_state, request, None, None)
+            .await
+            .unwrap();
+
+        let details =
+            fetch_feedback_details(&app.app_state, created.feedback_id, ArtifactLookup::Live)
+                .await
+                .unwrap()
+                .unwrap();
+        assert_eq!(details.priority, 63);
+        println!("✅ Persisted priority test passed!");
+    }
+
+    #[tokio::test]
+    async fn test_feedback_from_before_priorities_is_backfilled() {
+        let Some(app) = crate::test_support::spawn_test_app().await else {
+            return;
+        };
+        let pool = &app.db_pool;
+        let migration = "v38_backfill_feedback_priority";
+        crate::database::migrations::rollback_migration(pool, migration)
+            .await
+            .unwrap();
+        let insert = |age_days: i32, priority: i32| async move {
+            sqlx::query_scalar::<_, Uuid>(
+                "INSERT INTO feedback (repository, content, priority, created_at) \
+                 VALUES ('8b-is/smart-tree', 'Hi', $1, NOW() - make_interval(days => $2)) RETURNING id",
+            )
+            .bind(priority)
+            .bind(age_days)
+            .fetch_one(pool)
+            .await
+            .unwrap()
+        };
+        let old = insert(30, 0).await;
+        let explicit_zero = insert(0, 0).await;
+        sqlx::query(
+            "INSERT INTO background_jobs (job_type, payload, created_at) \
+             VALUES ('feedback_callback', $1, NOW() - INTERVAL '30 days')",
+        )
+        .bind(serde_json::json!({ "url": "https://example.com/hook", "body": { "feedback_id": old } }))
+        .execute(pool)
+        .await
+        .unwrap();
+        let priorities = || async move {
+            let feedback: Vec<i32> = sqlx::query_scalar(
+                "SELECT priority FROM feedback WHERE id = ANY($1) ORDER BY created_at",
+            )
+            .bind(vec![old, explicit_zero])
+            .fetch_all(pool)
+            .await
+            .unwrap();
+            let job: i32 = sqlx::query_scalar(
+                "SELECT priority FROM background_jobs WHERE job_type = 'feedback_callback'",
+            )
+            .fetch_one(pool)
+            .await
+            .unwrap();
+            (feedback, job)
+        };
+
+        // ⬆️ Old feedback and its callback get the default; a deliberate 0 stays 0
+        crate::database::run_migrations(pool).await.unwrap();
+        assert_eq!(priorities().await, (vec![25, 0], 25));
+
+        // ⬇️ And back
+        crate::database::migrations::rollback_migration(pool, migration)
+            .await
+            .unwrap();
+        assert_eq!(priorities().await, (vec![0, 0], 0));
+        println!("✅ Priority backfill migration test passed!");
+    }
+
+    #[tokio::test]
+    async fn test_tags_are_normalized_and_stored_on_submission() {
+        let Some(app) = crate::test_support::spawn_test_app().await else {
+            return;
+        };
+        let request = |tags: Vec<String>| SubmitFeedbackRequest {
+            repository: "8b-is/smart-tree".to_string(),
+            content: "Dark mode toggles back on every reload".to_string(),
+            llm_provider: None,
+            metadata: Some(serde_json::json!({ "tags": ["UI", "Dark Mode", 42] })),
+            user_info: None,
+            callback_url: None,
+            impact_score: None,
+            frequency_score: None,
+            priority: None,
+            tags: Some(tags),
+            source: None,
+            challenge: None,
+        };
+
+        let too_many = request(
+            (0..=crate::api::tags::MAX_TAGS)
+                .map(|n| n.to_string())
+                .collect(),
+        );
+        assert!(too_many.validate().is_err());
+
+        let created = create_feedback_record(
+            &app.app_state,
+            request(vec!["dark-mode".to_string(), " Regression ".to_string()]),
+            None,
+            None,
+        )
+        .await
+        .unwrap();
+        let details =
+            fetch_feedback_details(&app.app_state, created.feedback_id, ArtifactLookup::Live)
+                .await
+                .unwrap()
+                .unwrap();
+        assert_eq!(details.tags, vec!["dark-mode", "regression", "ui"]);
+        println!("✅ Tag submission test passed!");
+    }
+
+    #[tokio::test]
+    async fn test_malformed_json_gets_a_structured_400() {
+        use tower::ServiceExt;
+
+        let Some(app) = crate::test_support::spawn_test_app().await else {
+            return;
+        };
+
+        // 🧾 Missing required field: 400 (not axum's 422) pointing at the spot.
+        // The handler is mounted directly - /api/feedback sits behind JWT auth.
+        let router = axum::Router::new()
+            .route("/api/feedback", axum::routing::post(submit_feedback))
+            .with_state(app.app_state.clone());
+        let response = router
+            .oneshot(
+                axum::http::Request::post("/api/feedback")
+                    .header("content-type", "application/json")
+                    .body(axum::body::Body::from(
+                        r#"{"repository": "8b-is/smart-tree"}"#,
+                    ))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&bytes).unwrap();
+        assert_eq!(body["error"]["code"], "invalid_json");
+        assert_eq!(body["error"]["details"]["kind"], "data");
+        assert_eq!(body["error"]["details"]["line"], 1);
+        assert!(body["error"]["message"]
+            .as_str()
+            .unwrap()
+            .contains("missing field `content`"));
+
+        // 🪝 The issue webhook answers broken deliveries the same way
+        let response = app
+            .client
+            .post(app.url("/api/webhook/issues"))
+            .header("content-type", "application/json")
+            .body("{\"action\": \"opened\",")
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        let body: serde_json::Value = response.json().await.unwrap();
+        assert_eq!(body["error"]["code"], "invalid_json");
+        assert_eq!(body["error"]["details"]["kind"], "eof");
+        println!("✅ Malformed JSON submission test passed!");
+    }
+
+    #[tokio::test]
+    async fn test_paging_is_stable_with_identical_timestamps() {
+        let Some(app) = crate::test_support::spawn_test_app().await else {
+            return;
+        };
+        // ⏰ One INSERT, one NOW() - every row shares the same created_at
+        sqlx::query(
+            "INSERT INTO feedback (repository, content) SELECT '8b-is/smart-tree', 'Bulk feedback #' || n FROM generate_series(1, 25) AS n",
+        )
+        .execute(&app.db_pool)
+        .await
+        .unwrap();
+
+        let query = FeedbackQuery {
+            status: None,
+            repository: None,
+            user_id: None,
+            llm_provider: None,
+            from_date: None,
+            to_date: None,
+            tag: None,
+            source: None,
+        };
+        let mut paged = Vec::new();
+        for page in 1..=3 {
+            let pagination = PaginationParams {
+                page,
+                limit: 10,
+                sort_by: None,
+                sort_order: crate::api::SortOrder::Desc,
+            };
+            let response = fetch_feedback_list(&app.app_state, &pagination, &query)
+                .await
+                .unwrap();
+            paged.extend(response.items.into_iter().map(|item| item.id));
+        }
+
+        let expected: Vec<Uuid> =
+            sqlx::query_scalar("SELECT id FROM feedback ORDER BY created_at DESC, id DESC")
+                .fetch_all(&app.db_pool)
+                .await
+                .unwrap();
+        assert_eq!(paged, expected);
+        println!("✅ Stable paging test passed!");
+    }
+
+    #[test]
+    fn test_dedup_hash_ignores_whitespace_and_repository_case() {
+        let request = |repository: &str, content: &str| SubmitFeedbackRequest {
+            repository: repository.to_string(),
+            content: content.to_string(),
+            llm_provider: None,
+            metadata: None,
+            user_info: None,
+            callback_url: None,
+            impact_score: None,
+            frequency_score: None,
+            priority: None,
+            tags: None,
+            source: None,
+            challenge: None,
+        };
+        let user = Uuid::new_v4();
+        let hash = request("8b-is/smart-tree", "Symlink loops crash the tree").dedup_hash(user);
+        assert_eq!(hash.len(), 64);
+        assert_eq!(
+            request("8B-is/Smart-Tree", "  Symlink loops\n crash   the tree ").dedup_hash(user),
+            hash
+        );
+        // 🚫 Anything else that matters changes it
+        assert_ne!(
+            request("8b-is/smart-tree", "Symlink loops crash the tree").dedup_hash(Uuid::new_v4()),
+            hash
+        );
+        assert_ne!(
+            request("8b-is/other", "Symlink loops crash the tree").dedup_hash(user),
+            hash
+        );
+        assert_ne!(
+            request("8b-is/smart-tree", "symlink loops crash the tree").dedup_hash(user),
+            hash
+        );
+        println!("✅ Dedup hash test passed!");
+    }
+
+    #[tokio::test]
+    async fn test_feedback_below_the_project_threshold_is_held() {
+        let Some(app) = crate::test_support::spawn_test_app().await else {
+            return;
+        };
+        let owner_id: Uuid = sqlx::query_scalar(
+            "INSERT INTO users (email, name, password_hash) VALUES ('owner@example.com', 'Owner', 'x') \
+             RETURNING id",
+        )
+        .fetch_one(&app.db_pool)
+        .await
+        .unwrap();
+        sqlx::query(
+            "INSERT INTO projects (owner_id, repository, config) VALUES ($1, '8b-is/smart-tree', $2)",
+        )
+        .bind(owner_id)
+        .bind(serde_json::json!({ "processing": { "min_score": 20 } }))
+        .execute(&app.db_pool)
+        .await
+        .unwrap();
+        let submit = |impact_score: Option<u8>, frequency_score: Option<u8>| {
+            let app_state = app.app_state.clone();
+            let request = SubmitFeedbackRequest {
+                repository: "8b-is/smart-tree".to_string(),
+                content: "The footer has a typo".to_string(),
+                llm_provider: None,
+                metadata: None,
+                user_info: None,
+                callback_url: None,
+                impact_score,
+                frequency_score,
+                priority: None,
+                tags: None,
+                source: None,
+                challenge: None,
+            };
+            async move {
+                create_feedback_record(&app_state, request, None, None)
+                    .await
+                    .unwrap()
+            }
+        };
+
+        let trivial = submit(Some(2), Some(3)).await;
+        assert_eq!(trivial.status, FeedbackStatus::Pending);
+        assert_eq!(
+            trivial.held_below_threshold,
+            Some(ProcessingHold {
+                score: 6,
+                min_score: 20
+            })
+        );
+        // 📏 At the threshold, or without both scores, it goes ahead
+        assert!(submit(Some(4), Some(5))
+            .await
+            .held_below_threshold
+            .is_none());
+        assert!(submit(Some(1), None).await.held_below_threshold.is_none());
+
+        // ⏳ Held feedback isn't counted as waiting in the queue
+        let snapshot = crate::api::queue_stats::QueueSnapshot::load(&app.db_pool, 1)
+            .await
+            .unwrap();
+        let later = chrono::Utc::now() + chrono::Duration::hours(1);
+        assert_eq!(snapshot.queue_position(later, Uuid::nil()), 2);
+
+        // 🧾 The record and its hold commit together: no threshold check, no feedback
+        sqlx::query("UPDATE projects SET config = $1")
+            .bind(serde_json::json!({ "config_version": 999 }))
+            .execute(&app.db_pool)
+            .await
+            .unwrap();
+        let request = SubmitFeedbackRequest {
+            repository: "8b-is/smart-tree".to_string(),
+            content: "Nobody can tell whether this is held".to_string(),
+            llm_provider: None,
+            metadata: None,
+            user_info: None,
+            callback_url: None,
+            impact_score: Some(1),
+            frequency_score: Some(1),
+            priority: None,
+            tags: None,
+            source: None,
+            challenge: None,
+        };
+        assert!(create_feedback_record(&app.app_state, request, None, None)
+            .await
+            .is_err());
+        let stored: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM feedback")
+            .fetch_one(&app.db_pool)
+            .await
+            .unwrap();
+        assert_eq!(stored, 3);
+        println!("✅ Processing threshold submission test passed!");
+    }
+
+    #[tokio::test]
+    async fn test_double_submits_within_the_window_return_the_first() {
+        let Some(app) = crate::test_support::spawn_test_app().await else {
+            return;
+        };
+        let user_id: Uuid = sqlx::query_scalar(
+            "INSERT INTO users (email, name, password_hash) VALUES ('cli@example.com', 'CLI', 'x') \
+             RETURNING id",
+        )
+        .fetch_one(&app.db_pool)
+        .await
+        .unwrap();
+        let request = |content: &str| SubmitFeedbackRequest {
+            repository: "8b-is/smart-tree".to_string(),
+            content: content.to_string(),
+            llm_provider: None,
+            metadata: None,
+            user_info: None,
+            callback_url: Some("https://hooks.example.com/feedback".to_string()),
+            impact_score: None,
+            frequency_score: None,
+            priority: None,
+            tags: None,
+            source: None,
+            challenge: None,
+        };
+        let submit = |content: &'static str, user: Option<Uuid>| {
+            let app_state = app.app_state.clone();
+            async move {
+                create_feedback_record(&app_state, request(content), user, None)
+                    .await
+                    .unwrap()
+            }
+        };
+        let count = || async {
+            sqlx::query_scalar::<_, i64>("SELECT COUNT(*) FROM feedback")
+                .fetch_one(&app.db_pool)
+                .await
+                .unwrap()
+        };
+
+        // 🏃 Two racing submits still make one record
+        let (a, b) = tokio::join!(
+            submit("The tree crashes on symlink loops", Some(user_id)),
+            submit("The tree  crashes on symlink loops ", Some(user_id)),
+        );
+        assert_eq!(a.feedback_id, b.feedback_id);
+        assert_ne!(a.duplicate, b.duplicate);
+        let (first, repeat) = if a.duplicate { (b, a) } else { (a, b) };
+        assert!(first.callback_secret.is_some());
+        // 🔏 The callback secret is only ever handed out once
+        assert!(repeat.callback_secret.is_none());
+        assert_eq!(count().await, 1);
+
+        // 📝 Different content, or no known user, is always new
+        let other = submit("Dark mode resets on reload", Some(user_id)).await;
+        assert!(!other.duplicate);
+        for _ in 0..2 {
+            assert!(
+                !submit("The tree crashes on symlink loops", None)
+                    .await
+                    .duplicate
+            );
+        }
+        assert_eq!(count().await, 4);
+
+        // ⏰ Once the window has passed the same text is a new submission again
+        sqlx::query(
+            "UPDATE feedback SET created_at = created_at - INTERVAL '1 hour' WHERE id = $1",
+        )
+        .bind(first.feedback_id)
+        .execute(&app.db_pool)
+        .await
+        .unwrap();
+        let later = submit("The tree crashes on symlink loops", Some(user_id)).await;
+        assert!(!later.duplicate);
+        assert_ne!(later.feedback_id, first.feedback_id);
+        let released: Option<String> =
+            sqlx::query_scalar("SELECT dedup_hash FROM feedback WHERE id = $1")
+                .bind(first.feedback_id)
+                .fetch_one(&app.db_pool)
+                .await
+                .unwrap();
+        assert_eq!(released, None);
+        assert!(
+            submit("The tree crashes on symlink loops", Some(user_id))
+                .await
+                .duplicate
+        );
+        println!("✅ Feedback dedup window test passed!");
+    }
+
     #[test]
     fn test_content_truncation() {
         let short_content = "Short content";
@@ -582,6 +1758,11 @@ mod tests {
         let long_content = "This is a very long content that should be truncated when it exceeds the maximum length limit";
         let truncated = truncate_content(long_content, 20);
         assert_eq!(truncated, "This is a very long ...".to_string());
+
+        // 🌍 Counted in characters, and never cut inside one
+        assert_eq!(truncate_content("🌳🌲🌴", 3), "🌳🌲🌴");
+        assert_eq!(truncate_content("🌳🌲🌴", 2), "🌳🌲...");
+        assert_eq!(truncate_content("Größenänderung", 3), "Grö...");
         println!("✅ Content truncation test passed!");
     }
 
@@ -591,11 +1772,332 @@ mod tests {
             feedback_id: Uuid::new_v4(),
             status: FeedbackStatus::Pending,
             tracking_url: "/api/feedback/123".to_string(),
+            status_url: "https://f.8b.is/api/feedback/123".to_string(),
+            html_url: "https://f.8b.is/feedback/123".to_string(),
             estimated_processing_time: 5,
+            callback_secret: None,
+            duplicate: false,
+            held_below_threshold: None,
         };
 
-        let serialized = serde_json::to_string(&response);
-        assert!(serialized.is_ok());
+        let serialized = serde_json::to_value(&response).unwrap();
+        assert_eq!(serialized["status_url"], "https://f.8b.is/api/feedback/123");
+        assert_eq!(serialized["html_url"], "https://f.8b.is/feedback/123");
+        assert!(serialized.get("held_below_threshold").is_none());
         println!("✅ Feedback response serialization test passed!");
     }
+
+    #[tokio::test]
+    async fn test_only_owners_and_admins_decide_on_held_changes() {
+        use crate::database::models::{User, UserRole};
+        use crate::github::{ChangeType, CodeImprovement};
+        use crate::middleware::auth::jwt_utils;
+
+        let Some(app) = crate::test_support::spawn_test_app().await else {
+            return;
+        };
+        let pool = &app.db_pool;
+        let user = |email: &'static str, role: UserRole| async move {
+            sqlx::query_as::<_, User>(
+                "INSERT INTO users (email, name, password_hash, role) VALUES ($1, $1, 'x', $2) RETURNING *",
+            )
+            .bind(email)
+            .bind(role)
+            .fetch_one(pool)
+            .await
+            .unwrap()
+        };
+        let owner = user("owner@example.com", UserRole::User).await;
+        let stranger = user("stranger@example.com", UserRole::User).await;
+        let admin = user("admin@example.com", UserRole::Admin).await;
+        sqlx::query(
+            "INSERT INTO projects (owner_id, repository, require_approval) VALUES ($1, '8b-is/smart-tree', TRUE)",
+        )
+        .bind(owner.id)
+        .execute(pool)
+        .await
+        .unwrap();
+
+        let held = || async {
+            let mut feedback = Feedback::create(
+                pool,
+                None,
+                "8b-is/smart-tree".to_string(),
+                "Explain the --stream flag in the docs".to_string(),
+                None,
+                25,
+                None,
+                "cli",
+            )
+            .await
+            .unwrap();
+            let change = CodeImprovement {
+                file_path: "docs/stream.md".to_string(),
+                description: "Document --stream".to_string(),
+                change_type: ChangeType::Create,
+                original_content: None,
+                new_content: "# Streaming\n".to_string(),
+                line_number: None,
+            };
+            approval::hold_for_approval(pool, &mut feedback, &[change], 14)
+                .await
+                .unwrap();
+            feedback.id
+        };
+        let decide = |as_user: Option<&User>, id: Uuid, action: &str| {
+            let mut request = app
+                .client
+                .post(app.url(&format!("/api/feedback/{}/{}", id, action)));
+            if let Some(as_user) = as_user {
+                let token =
+                    jwt_utils::create_jwt_token(as_user, &app.app_state.config.auth.jwt_secret, 1)
+                        .unwrap();
+                request = request.bearer_auth(token);
+            }
+            async move {
+                let response = request.send().await.unwrap();
+                let status = response.status();
+                (status, response.json::<serde_json::Value>().await.unwrap())
+            }
+        };
+
+        // 🔐 Signed in, and the project's owner (or an admin)
+        let first = held().await;
+        assert_eq!(
+            decide(None, first, "approve").await.0,
+            StatusCode::UNAUTHORIZED
+        );
+        assert_eq!(
+            decide(Some(&stranger), first, "approve").await.0,
+            StatusCode::FORBIDDEN
+        );
+        let (status, body) = decide(Some(&owner), first, "approve").await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["data"]["status"], "creating_pull_request");
+
+        // 🔁 Approving again is fine, rejecting after approval is not
+        let (status, body) = decide(Some(&owner), first, "approve").await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["message"], "Changes were already approved");
+        let (status, body) = decide(Some(&owner), first, "reject").await;
+        assert_eq!(status, StatusCode::CONFLICT);
+        assert_eq!(body["error"]["code"], "already_decided");
+
+        // 👑 Admins can reject other people's held changes
+        let second = held().await;
+        assert_eq!(
+            decide(Some(&admin), second, "reject").await.0,
+            StatusCode::OK
+        );
+        let rejected = Feedback::find_by_id(pool, second).await.unwrap().unwrap();
+        assert_eq!(rejected.status, FeedbackStatus::Failed);
+        assert_eq!(
+            rejected.error_message.as_deref(),
+            Some(approval::REJECTED_BY_OWNER)
+        );
+        let audited: Vec<String> =
+            sqlx::query_scalar("SELECT action FROM admin_audit_log ORDER BY created_at")
+                .fetch_all(pool)
+                .await
+                .unwrap();
+        assert_eq!(
+            audited,
+            ["feedback_changes_approved", "feedback_changes_rejected"]
+        );
+
+        // 💥 When the audit entry can't be written, the decision isn't either
+        let third = held().await;
+        sqlx::raw_sql(
+            r#"
+            CREATE FUNCTION refuse_audit() RETURNS trigger AS $$
+            BEGIN RAISE EXCEPTION 'audit log is read-only'; END;
+            $$ LANGUAGE plpgsql;
+            CREATE TRIGGER refuse_audit BEFORE INSERT ON admin_audit_log
+                FOR EACH ROW EXECUTE FUNCTION refuse_audit();
+            "#,
+        )
+        .execute(pool)
+        .await
+        .unwrap();
+        assert_eq!(
+            decide(Some(&owner), third, "approve").await.0,
+            StatusCode::INTERNAL_SERVER_ERROR
+        );
+        let still_held = Feedback::find_by_id(pool, third).await.unwrap().unwrap();
+        assert_eq!(still_held.status, FeedbackStatus::AwaitingApproval);
+        let (decision, commits): (Option<String>, i64) = sqlx::query_as(
+            "SELECT (SELECT decision FROM feedback_approvals WHERE feedback_id = $1), \
+                    (SELECT COUNT(*) FROM background_jobs WHERE payload->>'feedback_id' = $1::text)",
+        )
+        .bind(third)
+        .fetch_one(pool)
+        .await
+        .unwrap();
+        assert_eq!((decision, commits), (None, 0));
+        println!("✅ Held changes decision API test passed!");
+    }
+
+    #[tokio::test]
+    async fn test_status_links_the_github_artifacts_with_their_current_state() {
+        let Some(app) = crate::test_support::spawn_test_app().await else {
+            return;
+        };
+        let feedback = Feedback::create(
+            &app.db_pool,
+            None,
+            "8b-is/smart-tree".to_string(),
+            "Please add dark mode".to_string(),
+            None,
+            25,
+            None,
+            "cli",
+        )
+        .await
+        .unwrap();
+        sqlx::query(
+            "UPDATE feedback SET status = 'completed', branch_name = 'feedbacker/dark-mode', \
+             pull_request_url = 'https://github.com/8b-is/smart-tree/pull/42' WHERE id = $1",
+        )
+        .bind(feedback.id)
+        .execute(&app.db_pool)
+        .await
+        .unwrap();
+        let user: crate::database::models::User = sqlx::query_as(
+            "INSERT INTO users (email, name, password_hash) VALUES ('ayes@8b.is', 'Aye', 'x') RETURNING *",
+        )
+        .fetch_one(&app.db_pool)
+        .await
+        .unwrap();
+        let token = crate::middleware::auth::jwt_utils::create_jwt_token(
+            &user,
+            &app.app_state.config.auth.jwt_secret,
+            1,
+        )
+        .unwrap();
+        let status = || async {
+            let body: serde_json::Value = app
+                .client
+                .get(app.url(&format!("/api/feedback/{}", feedback.id)))
+                .bearer_auth(&token)
+                .send()
+                .await
+                .unwrap()
+                .json()
+                .await
+                .unwrap();
+            body["data"]["github"].clone()
+        };
+
+        // 🔀 We only recorded the PR's creation, GitHub knows it was merged since.
+        // The first visitor gets what we recorded, the lookup runs in the background...
+        app.github
+            .merged_pull_requests
+            .lock()
+            .unwrap()
+            .push(("8b-is/smart-tree".to_string(), 42));
+        let github = status().await;
+        assert_eq!(github["stale"], false);
+        assert_eq!(github["pull_request"]["state"], "open");
+        assert_eq!(
+            github["branch"]["url"],
+            "https://github.com/8b-is/smart-tree/tree/feedbacker/dark-mode"
+        );
+        // 🗃️ ...and later visitors get GitHub's answer from the cache
+        let mut github = status().await;
+        for _ in 0..50 {
+            if github["pull_request"]["state"] == "merged" {
+                break;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+            github = status().await;
+        }
+        assert_eq!(github["stale"], false);
+        assert_eq!(github["pull_request"]["number"], 42);
+        assert_eq!(github["pull_request"]["state"], "merged");
+        assert_eq!(github["issue"], serde_json::Value::Null);
+
+        // 🕰️ GitHub going down doesn't matter while the answer is cached
+        *app.github.fail_with.lock().unwrap() = Some("GitHub is down".to_string());
+        let github = status().await;
+        assert_eq!(github["stale"], false);
+        assert_eq!(github["pull_request"]["state"], "merged");
+        println!("✅ Feedback GitHub artifacts test passed!");
+    }
+
+    #[tokio::test]
+    async fn test_signed_in_users_vote_once() {
+        use crate::database::models::User;
+        use crate::middleware::auth::jwt_utils;
+
+        let Some(app) = crate::test_support::spawn_test_app().await else {
+            return;
+        };
+        let pool = &app.db_pool;
+        let mut tokens = Vec::new();
+        for email in ["ayes@8b.is", "hues@8b.is"] {
+            let user: User = sqlx::query_as(
+                "INSERT INTO users (email, name, password_hash) VALUES ($1, $1, 'x') RETURNING *",
+            )
+            .bind(email)
+            .fetch_one(pool)
+            .await
+            .unwrap();
+            tokens.push(
+                jwt_utils::create_jwt_token(&user, &app.app_state.config.auth.jwt_secret, 1)
+                    .unwrap(),
+            );
+        }
+        let feedback = Feedback::create(
+            pool,
+            None,
+            "8b-is/smart-tree".to_string(),
+            "Please add dark mode".to_string(),
+            None,
+            25,
+            None,
+            "cli",
+        )
+        .await
+        .unwrap();
+        let vote = |id: Uuid, token: Option<&String>, up: bool| {
+            let url = app.url(&format!("/api/feedback/{}/vote", id));
+            let mut request = if up {
+                app.client.post(url)
+            } else {
+                app.client.delete(url)
+            };
+            if let Some(token) = token {
+                request = request.bearer_auth(token);
+            }
+            async move {
+                let response = request.send().await.unwrap();
+                let status = response.status();
+                (status, response.json::<serde_json::Value>().await.unwrap())
+            }
+        };
+
+        assert_eq!(
+            vote(feedback.id, None, true).await.0,
+            StatusCode::UNAUTHORIZED
+        );
+        let (status, body) = vote(feedback.id, Some(&tokens[0]), true).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["data"]["votes"], 1);
+        assert_eq!(
+            vote(feedback.id, Some(&tokens[0]), true).await.1["data"]["votes"],
+            1
+        );
+        assert_eq!(
+            vote(feedback.id, Some(&tokens[1]), true).await.1["data"]["votes"],
+            2
+        );
+        assert_eq!(
+            vote(feedback.id, Some(&tokens[0]), false).await.1["data"]["votes"],
+            1
+        );
+        let (status, body) = vote(Uuid::new_v4(), Some(&tokens[0]), true).await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+        assert_eq!(body["error"]["code"], "not_found");
+        println!("✅ Feedback voting API test passed!");
+    }
 }
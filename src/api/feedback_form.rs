@@ -0,0 +1,848 @@
+// 📮 Public Feedback Form - For everyone without the Smart Tree CLI! 📮
+// A plain HTML form at /feedback that feeds the same submission pipeline as
+// POST /api/feedback. Bots get a honeypot field and a per-IP allowance, humans
+// get inline errors with everything they typed still in place. /feedback/:id is
+// where submitters follow their feedback afterwards (the response's `html_url`).
+// With ANON_CHALLENGE set, the form also carries the captcha widget or the
+// proof-of-work solver, and the answer is checked before anything is stored.
+// Created with love by Aye & Hue! ✨
+
+use axum::{
+    extract::{ConnectInfo, Path, State},
+    http::{header, HeaderMap, StatusCode},
+    response::{Html, IntoResponse, Response},
+    Form,
+};
+use serde::Deserialize;
+use std::net::SocketAddr;
+use tracing::{error, info, warn};
+
+use crate::api::{
+    admin::html_escape,
+    challenge::{self, ChallengeInfo, POW_SOLVER_SCRIPT},
+    feedback::{
+        create_feedback_record, fetch_feedback_details, AnonymousUserInfo, SubmitFeedbackRequest,
+    },
+    sources::WEB_FORM_SOURCE,
+    web::render_public_page,
+    AppState,
+};
+use crate::config::ChallengeKind;
+use crate::github::artifacts::ArtifactLookup;
+use crate::middleware::rate_limiting::RateLimitScope;
+
+/// 🏷️ Categories offered on the form (value, label); the value also becomes a tag
+pub const CATEGORIES: &[(&str, &str)] = &[
+    ("bug", "🐛 Bug report"),
+    ("feature", "✨ Feature request"),
+    ("docs", "📚 Documentation"),
+    ("other", "💬 Something else"),
+];
+
+/// 📏 Longest accepted title (characters)
+pub const MAX_TITLE_LENGTH: usize = 200;
+
+/// 📝 What the form posts. Every field defaults to empty so a half-filled form still parses.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct FeedbackFormInput {
+    pub repository: String,
+    pub category: String,
+    pub title: String,
+    pub description: String,
+    pub email: String,
+    /// 🍯 Hidden from humans; anything in here came from a bot
+    pub website: String,
+    /// 🧑 Token filled in by the hCaptcha widget
+    #[serde(rename = "h-captcha-response")]
+    pub h_captcha_response: String,
+    /// ☁️ Token filled in by the Turnstile widget
+    #[serde(rename = "cf-turnstile-response")]
+    pub cf_turnstile_response: String,
+    /// 💪 `<challenge>:<nonce>` found by the proof-of-work script
+    pub pow_answer: String,
+}
+
+/// ❌ One validation problem, attached to the field it belongs to
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FieldError {
+    pub field: &'static str,
+    pub message: String,
+}
+
+impl FieldError {
+    fn new(field: &'static str, message: impl Into<String>) -> Self {
+        Self {
+            field,
+            message: message.into(),
+        }
+    }
+}
+
+impl FeedbackFormInput {
+    /// 🧩 Whichever challenge answer the form carried
+    pub fn challenge_answer(&self) -> Option<&str> {
+        [
+            &self.h_captcha_response,
+            &self.cf_turnstile_response,
+            &self.pow_answer,
+        ]
+        .into_iter()
+        .map(|answer| answer.trim())
+        .find(|answer| !answer.is_empty())
+    }
+
+    /// 🔄 Turn the form into a regular submission, or explain what's wrong per field.
+    /// Form-level checks come first; the pipeline's own validation then runs on the result.
+    pub fn to_request(
+        &self,
+        repositories: &[String],
+        max_content_length: usize,
+    ) -> Result<SubmitFeedbackRequest, Vec<FieldError>> {
+        let mut errors = Vec::new();
+        let title = self.title.trim();
+        let description = self.description.trim();
+        let email = self.email.trim();
+
+        if !repositories.contains(&self.repository) {
+            errors.push(FieldError::new("repository", "Please pick a project"));
+        }
+        if !CATEGORIES.iter().any(|(value, _)| *value == self.category) {
+            errors.push(FieldError::new("category", "Please pick a category"));
+        }
+        if title.is_empty() {
+            errors.push(FieldError::new("title", "Please add a short title"));
+        } else if title.chars().count() > MAX_TITLE_LENGTH {
+            errors.push(FieldError::new(
+                "title",
+                format!("Titles are limited to {} characters", MAX_TITLE_LENGTH),
+            ));
+        }
+        if description.is_empty() {
+            errors.push(FieldError::new(
+                "description",
+                "Please describe your feedback",
+            ));
+        }
+        if !email.is_empty() && (!email.contains('@') || email.len() > 255) {
+            errors.push(FieldError::new(
+                "email",
+                "That doesn't look like an email address",
+            ));
+        }
+        if !errors.is_empty() {
+            return Err(errors);
+        }
+
+        let request = SubmitFeedbackRequest {
+            repository: self.repository.clone(),
+            content: format!("{}\n\n{}", title, description),
+            llm_provider: None,
+            metadata: Some(serde_json::json!({
+                "source": WEB_FORM_SOURCE,
+                "category": self.category,
+                "title": title,
+            })),
+            user_info: (!email.is_empty()).then(|| AnonymousUserInfo {
+                email: Some(email.to_string()),
+                name: None,
+            }),
+            callback_url: None,
+            impact_score: None,
+            frequency_score: None,
+            priority: None,
+            tags: Some(vec![self.category.clone()]),
+            source: Some(WEB_FORM_SOURCE.to_string()),
+            challenge: self.challenge_answer().map(str::to_string),
+        };
+
+        // ✅ Anything the pipeline still objects to is about the combined content
+        request
+            .validate_with_limit(max_content_length)
+            .map_err(|messages| {
+                messages
+                    .into_iter()
+                    .map(|message| FieldError::new("description", message))
+                    .collect::<Vec<_>>()
+            })?;
+        Ok(request)
+    }
+}
+
+/// 🌍 Repositories the form offers: those of active projects, each listed once
+async fn public_repositories(app_state: &AppState) -> anyhow::Result<Vec<String>> {
+    use anyhow::Context;
+    sqlx::query_scalar(
+        r#"
+        SELECT DISTINCT r.repository FROM project_repository_routes r
+        JOIN projects p ON p.id = r.project_id
+        WHERE p.is_active
+        ORDER BY r.repository
+        "#,
+    )
+    .fetch_all(&app_state.db_pool)
+    .await
+    .context("Failed to list public projects")
+}
+
+/// 🎫 A challenge for the next form render (every render gets its own)
+fn fresh_challenge(app_state: &AppState) -> ChallengeInfo {
+    ChallengeInfo::issue(
+        &app_state.config.challenge,
+        &app_state.config.auth.jwt_secret,
+        chrono::Utc::now().timestamp(),
+    )
+}
+
+/// 📮 GET /feedback - the empty form
+pub async fn feedback_form_page(State(app_state): State<AppState>) -> Response {
+    match public_repositories(&app_state).await {
+        Ok(repositories) => Html(render_form_page(
+            &repositories,
+            &FeedbackFormInput::default(),
+            &[],
+            &fresh_challenge(&app_state),
+        ))
+        .into_response(),
+        Err(e) => {
+            error!("❌ Failed to load the feedback form: {:#}", e);
+            unavailable_page()
+        }
+    }
+}
+
+/// 📮 POST /feedback - honeypot, per-IP allowance, validation, the anonymous challenge,
+/// then the regular pipeline
+pub async fn feedback_form_submit(
+    State(app_state): State<AppState>,
+    headers: HeaderMap,
+    connect_info: Option<ConnectInfo<SocketAddr>>,
+    Form(input): Form<FeedbackFormInput>,
+) -> Response {
+    let client_ip = crate::api::mcp::extract_client_ip(&app_state, &headers, connect_info.as_ref());
+
+    // 🍯 Humans never see this field
+    if !input.website.trim().is_empty() {
+        warn!("🍯 Feedback form honeypot triggered from {:?}", client_ip);
+        return (
+            StatusCode::BAD_REQUEST,
+            Html(render_public_page(
+                "Submission rejected - Feedbacker",
+                r#"        <div class="card"><div class="card-body"><p>🚫 This submission could not be accepted.</p></div></div>
+"#,
+            )),
+        )
+            .into_response();
+    }
+
+    // 🚦 Per-IP allowance (RATE_LIMIT_FEEDBACK_PER_HOUR)
+    if let Some(ip) = client_ip {
+        if let Err(retry_after) = app_state.rate_limiter.check(RateLimitScope::Feedback, ip) {
+            warn!("🚫 Feedback form rate limit exceeded for {}", ip);
+            let seconds = retry_after.as_secs().max(1);
+            return (
+                StatusCode::TOO_MANY_REQUESTS,
+                [(header::RETRY_AFTER, seconds.to_string())],
+                Html(render_public_page(
+                    "Slow down - Feedbacker",
+                    &format!(
+                        r#"        <div class="card"><div class="card-body"><p>⏳ That's a lot of feedback from one place. Please try again in {} minutes.</p></div></div>
+"#,
+                        seconds.div_ceil(60)
+                    ),
+                )),
+            )
+                .into_response();
+        }
+    }
+
+    let repositories = match public_repositories(&app_state).await {
+        Ok(repositories) => repositories,
+        Err(e) => {
+            error!("❌ Failed to load projects for the feedback form: {:#}", e);
+            return unavailable_page();
+        }
+    };
+
+    let request =
+        match input.to_request(&repositories, app_state.config.feedback.max_content_length) {
+            Ok(request) => request,
+            Err(errors) => {
+                warn!("❌ Feedback form validation failed: {:?}", errors);
+                return (
+                    StatusCode::UNPROCESSABLE_ENTITY,
+                    Html(render_form_page(
+                        &repositories,
+                        &input,
+                        &errors,
+                        &fresh_challenge(&app_state),
+                    )),
+                )
+                    .into_response();
+            }
+        };
+
+    // 🧩 Captcha token or proof-of-work (ANON_CHALLENGE), checked before anything is stored
+    if let Err(e) = challenge::verify(&app_state, request.challenge.as_deref(), client_ip).await {
+        warn!(
+            "🧩 Feedback form challenge failed from {:?}: {}",
+            client_ip, e
+        );
+        return (
+            e.status(),
+            Html(render_form_page(
+                &repositories,
+                &input,
+                &[FieldError::new("challenge", e.to_string())],
+                &fresh_challenge(&app_state),
+            )),
+        )
+            .into_response();
+    }
+
+    match create_feedback_record(&app_state, request, None, None).await {
+        Ok(created) => {
+            info!(
+                "📮 Feedback form submission accepted: {}",
+                created.feedback_id
+            );
+            (
+                StatusCode::CREATED,
+                Html(render_public_page(
+                    "Thank you! - Feedbacker",
+                    &format!(
+                        r#"        <div class="card">
+            <div class="card-header"><h3>🎉 Thanks, we've got it!</h3></div>
+            <div class="card-body">
+                <p>Your feedback id is <code>{id}</code>.</p>
+                <p>You can follow its progress at <a class="repo-link" href="{url}">{url}</a>.</p>
+                <p><a href="/feedback" class="btn btn-primary">Send more feedback</a></p>
+            </div>
+        </div>
+"#,
+                        id = created.feedback_id,
+                        url = html_escape(&created.html_url),
+                    ),
+                )),
+            )
+                .into_response()
+        }
+        Err(e) => {
+            error!("❌ Failed to store feedback form submission: {:#}", e);
+            unavailable_page()
+        }
+    }
+}
+
+/// 🔎 GET /feedback/:id - status of one submission. The id is the unguessable
+/// UUID handed out at submission; the page never shows the feedback content.
+pub async fn feedback_status_page(
+    State(app_state): State<AppState>,
+    Path(id): Path<String>,
+) -> Response {
+    let not_found = || {
+        (
+            StatusCode::NOT_FOUND,
+            Html(render_public_page(
+                "Feedback not found - Feedbacker",
+                r#"        <div class="card"><div class="card-body"><p>🔍 We couldn't find that feedback. Check the link you were given.</p></div></div>
+"#,
+            )),
+        )
+            .into_response()
+    };
+    let Ok(id) = id.parse::<uuid::Uuid>() else {
+        return not_found();
+    };
+    // 🗃️ A public page: GitHub states come from the cache, never a live lookup
+    let details = match fetch_feedback_details(&app_state, id, ArtifactLookup::Cached).await {
+        Ok(Some(details)) => details,
+        Ok(None) => return not_found(),
+        Err(e) => {
+            error!(
+                "❌ Failed to load feedback {} for its status page: {:#}",
+                id, e
+            );
+            return unavailable_page();
+        }
+    };
+
+    let mut rows = vec![
+        ("Repository", html_escape(&details.repository)),
+        (
+            "Status",
+            format!(
+                r#"<span class="status {}">{}</span>"#,
+                details.status.css_class(),
+                details.status
+            ),
+        ),
+        (
+            "Submitted",
+            details.created_at.format("%Y-%m-%d %H:%M UTC").to_string(),
+        ),
+        (
+            "Last update",
+            details.updated_at.format("%Y-%m-%d %H:%M UTC").to_string(),
+        ),
+    ];
+    if let Some(position) = details.queue.queue_position {
+        rows.push(("Queue position", (position + 1).to_string()));
+    }
+    if let Some(start) = details.queue.estimated_start {
+        rows.push((
+            "Expected to start",
+            start.format("%Y-%m-%d %H:%M UTC").to_string(),
+        ));
+    }
+    if let Some(url) = details.pull_request_url.as_deref() {
+        rows.push((
+            "Pull request",
+            format!(
+                r#"<a class="repo-link" href="{url}">{url}</a>"#,
+                url = html_escape(url)
+            ),
+        ));
+    }
+    let rows: String = rows
+        .iter()
+        .map(|(label, value)| {
+            format!(
+                "                    <tr><th>{}</th><td>{}</td></tr>\n",
+                label, value
+            )
+        })
+        .collect();
+
+    Html(render_public_page(
+        "Feedback status - Feedbacker",
+        &format!(
+            r#"        <div class="card">
+            <div class="card-header"><h3>📬 Feedback <code>{id}</code></h3></div>
+            <div class="card-body">
+                <table class="table">
+{rows}                </table>
+                <p><a href="/feedback" class="btn btn-primary">Send more feedback</a></p>
+            </div>
+        </div>
+"#,
+            id = id,
+            rows = rows,
+        ),
+    ))
+    .into_response()
+}
+
+/// 😵 Something on our side went wrong
+fn unavailable_page() -> Response {
+    (
+        StatusCode::INTERNAL_SERVER_ERROR,
+        Html(render_public_page(
+            "Something went wrong - Feedbacker",
+            r#"        <div class="card"><div class="card-body"><p>😵 We couldn't take your feedback right now. Please try again in a little while.</p></div></div>
+"#,
+        )),
+    )
+        .into_response()
+}
+
+/// 🖼️ The form, pre-filled with `input`, annotated with `errors`, and carrying `challenge`
+fn render_form_page(
+    repositories: &[String],
+    input: &FeedbackFormInput,
+    errors: &[FieldError],
+    challenge: &ChallengeInfo,
+) -> String {
+    if repositories.is_empty() {
+        return render_public_page(
+            "Send Feedback - Feedbacker",
+            r#"        <div class="empty-state">📭 No projects are accepting feedback yet</div>
+"#,
+        );
+    }
+
+    let errors_for = |field: &str| -> String {
+        errors
+            .iter()
+            .filter(|e| e.field == field)
+            .map(|e| {
+                format!(
+                    r#"<div class="field-error">{}</div>"#,
+                    html_escape(&e.message)
+                )
+            })
+            .collect()
+    };
+    let options = |choices: Vec<(String, String)>, selected: &str| -> String {
+        choices
+            .into_iter()
+            .map(|(value, label)| {
+                format!(
+                    r#"<option value="{}"{}>{}</option>"#,
+                    html_escape(&value),
+                    if value == selected { " selected" } else { "" },
+                    html_escape(&label)
+                )
+            })
+            .collect()
+    };
+    let site_key = html_escape(challenge.site_key.as_deref().unwrap_or_default());
+    let (form_attributes, challenge_widget) = match challenge.kind {
+        ChallengeKind::Off => (String::new(), String::new()),
+        ChallengeKind::HCaptcha => (
+            String::new(),
+            format!(
+                r#"<div class="h-captcha" data-sitekey="{}"></div>
+                        <script src="https://js.hcaptcha.com/1/api.js" async defer></script>"#,
+                site_key
+            ),
+        ),
+        ChallengeKind::Turnstile => (
+            String::new(),
+            format!(
+                r#"<div class="cf-turnstile" data-sitekey="{}"></div>
+                        <script src="https://challenges.cloudflare.com/turnstile/v0/api.js" async defer></script>"#,
+                site_key
+            ),
+        ),
+        ChallengeKind::ProofOfWork => (
+            format!(
+                r#" data-pow-challenge="{}" data-pow-difficulty="{}""#,
+                html_escape(challenge.challenge.as_deref().unwrap_or_default()),
+                challenge.difficulty.unwrap_or_default()
+            ),
+            format!(
+                r#"<input type="hidden" name="pow_answer" value="">
+                        <noscript>This form needs JavaScript to prove it isn't a bot.</noscript>
+                        <script>{}</script>"#,
+                POW_SOLVER_SCRIPT
+            ),
+        ),
+    };
+    let challenge_group = if challenge_widget.is_empty() {
+        String::new()
+    } else {
+        format!(
+            r#"
+                    <div class="form-group">
+                        {}{}
+                    </div>"#,
+            challenge_widget,
+            errors_for("challenge")
+        )
+    };
+
+    render_public_page(
+        "Send Feedback - Feedbacker",
+        &format!(
+            r#"        <div class="card">
+            <div class="card-header"><h3>📮 Send Feedback</h3></div>
+            <div class="card-body">
+                <form id="feedback-form" method="POST" action="/feedback"{form_attributes}>
+                    <div class="form-group">
+                        <label for="repository">Project</label>
+                        <select id="repository" name="repository" required>
+                            <option value="">Choose a project…</option>{repository_options}
+                        </select>{repository_errors}
+                    </div>
+                    <div class="form-group">
+                        <label for="category">Category</label>
+                        <select id="category" name="category" required>
+                            <option value="">Choose a category…</option>{category_options}
+                        </select>{category_errors}
+                    </div>
+                    <div class="form-group">
+                        <label for="title">Title</label>
+                        <input type="text" id="title" name="title" maxlength="{max_title}" value="{title}" required>{title_errors}
+                    </div>
+                    <div class="form-group">
+                        <label for="description">Description</label>
+                        <textarea id="description" name="description" rows="8" required>{description}</textarea>{description_errors}
+                    </div>
+                    <div class="form-group">
+                        <label for="email">Email (optional, for updates)</label>
+                        <input type="email" id="email" name="email" value="{email}">{email_errors}
+                    </div>
+                    <div class="honeypot" aria-hidden="true">
+                        <label for="website">Leave this empty</label>
+                        <input type="text" id="website" name="website" tabindex="-1" autocomplete="off">
+                    </div>{challenge_group}
+                    <button type="submit" class="btn btn-primary">Send feedback</button>
+                </form>
+            </div>
+        </div>
+"#,
+            repository_options = options(
+                repositories
+                    .iter()
+                    .map(|r| (r.clone(), r.clone()))
+                    .collect(),
+                &input.repository
+            ),
+            repository_errors = errors_for("repository"),
+            category_options = options(
+                CATEGORIES
+                    .iter()
+                    .map(|(value, label)| (value.to_string(), label.to_string()))
+                    .collect(),
+                &input.category
+            ),
+            category_errors = errors_for("category"),
+            max_title = MAX_TITLE_LENGTH,
+            title = html_escape(&input.title),
+            title_errors = errors_for("title"),
+            description = html_escape(&input.description),
+            description_errors = errors_for("description"),
+            email = html_escape(&input.email),
+            email_errors = errors_for("email"),
+        ),
+    )
+}
+
+// 🧪 Tests - Filling in forms so you don't have to!
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::middleware::rate_limiting::WindowQuota;
+    use crate::test_support::spawn_test_app;
+
+    /// 🏠 One active project the form can offer
+    async fn seed_project(app: &crate::test_support::TestApp, repository: &str) {
+        let owner: uuid::Uuid = sqlx::query_scalar(
+            "INSERT INTO users (email, name, password_hash) VALUES ('owner@example.com', 'Owner', 'x') RETURNING id",
+        )
+        .fetch_one(&app.db_pool)
+        .await
+        .unwrap();
+        sqlx::query("INSERT INTO projects (owner_id, repository) VALUES ($1, $2)")
+            .bind(owner)
+            .bind(repository)
+            .execute(&app.db_pool)
+            .await
+            .unwrap();
+    }
+
+    async fn feedback_count(app: &crate::test_support::TestApp) -> i64 {
+        sqlx::query_scalar("SELECT COUNT(*) FROM feedback")
+            .fetch_one(&app.db_pool)
+            .await
+            .unwrap()
+    }
+
+    #[test]
+    fn test_form_input_conversion() {
+        let repositories = vec!["8b-is/smart-tree".to_string()];
+        let input = FeedbackFormInput {
+            repository: "8b-is/smart-tree".to_string(),
+            category: "bug".to_string(),
+            title: " Crash on symlinks ".to_string(),
+            description: "It loops forever on a symlink cycle.".to_string(),
+            email: "me@example.com".to_string(),
+            pow_answer: " 123.abc.def:42 ".to_string(),
+            ..FeedbackFormInput::default()
+        };
+        let request = input.to_request(&repositories, 10_000).unwrap();
+        assert_eq!(
+            request.content,
+            "Crash on symlinks\n\nIt loops forever on a symlink cycle."
+        );
+        assert_eq!(request.tags, Some(vec!["bug".to_string()]));
+        assert_eq!(request.source.as_deref(), Some("web_form"));
+        assert_eq!(request.metadata.unwrap()["source"], "web_form");
+        assert_eq!(request.challenge.as_deref(), Some("123.abc.def:42"));
+
+        let bad = FeedbackFormInput {
+            repository: "8b-is/private".to_string(),
+            category: "rant".to_string(),
+            email: "nope".to_string(),
+            ..FeedbackFormInput::default()
+        };
+        let fields: Vec<&str> = bad
+            .to_request(&repositories, 10_000)
+            .unwrap_err()
+            .iter()
+            .map(|e| e.field)
+            .collect();
+        assert_eq!(
+            fields,
+            vec!["repository", "category", "title", "description", "email"]
+        );
+
+        // 📏 The pipeline's own content limit still applies
+        let long = FeedbackFormInput {
+            description: "x".repeat(100),
+            ..input
+        };
+        let errors = long.to_request(&repositories, 50).unwrap_err();
+        assert_eq!(errors[0].field, "description");
+        println!("✅ Feedback form conversion test passed!");
+    }
+
+    #[tokio::test]
+    async fn test_honeypot_submissions_are_rejected() {
+        let Some(app) = spawn_test_app().await else {
+            return;
+        };
+        seed_project(&app, "8b-is/smart-tree").await;
+
+        let response = app
+            .client
+            .post(app.url("/feedback"))
+            .form(&[
+                ("repository", "8b-is/smart-tree"),
+                ("category", "bug"),
+                ("title", "Cheap pills"),
+                ("description", "Visit my totally legit site today"),
+                ("website", "https://spam.example"),
+            ])
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), 400);
+        assert_eq!(feedback_count(&app).await, 0);
+        println!("✅ Honeypot rejection test passed!");
+    }
+
+    #[tokio::test]
+    async fn test_validation_errors_rerender_the_form_with_input() {
+        let Some(app) = spawn_test_app().await else {
+            return;
+        };
+        seed_project(&app, "8b-is/smart-tree").await;
+
+        let form = app.client.get(app.url("/feedback")).send().await.unwrap();
+        assert_eq!(form.status(), 200);
+        let html = form.text().await.unwrap();
+        assert!(html.contains(r#"<option value="8b-is/smart-tree">8b-is/smart-tree</option>"#));
+        assert!(html.contains(r#"name="website""#));
+
+        let response = app
+            .client
+            .post(app.url("/feedback"))
+            .form(&[
+                ("repository", "8b-is/smart-tree"),
+                ("category", "feature"),
+                ("title", r#"<script>alert("hi")</script>"#),
+                ("description", ""),
+                ("email", "not-an-email"),
+            ])
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), 422);
+        let html = response.text().await.unwrap();
+        // 🧼 Input is kept, escaped, and the errors sit next to their fields
+        assert!(html.contains(r#"value="&lt;script&gt;alert(&quot;hi&quot;)&lt;/script&gt;""#));
+        assert!(!html.contains("<script>alert"));
+        assert!(html.contains(r#"<option value="8b-is/smart-tree" selected>"#));
+        assert!(html.contains(r#"<option value="feature" selected>"#));
+        assert!(html.contains(r#"value="not-an-email""#));
+        assert!(html.contains("Please describe your feedback"));
+        assert!(html.contains("That doesn't look like an email address"));
+        assert_eq!(feedback_count(&app).await, 0);
+
+        // ✅ Fixing it goes through the regular pipeline
+        let response = app
+            .client
+            .post(app.url("/feedback"))
+            .form(&[
+                ("repository", "8b-is/smart-tree"),
+                ("category", "feature"),
+                ("title", "Export to SVG"),
+                ("description", "A vector export would be great for docs."),
+            ])
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), 201);
+        let html = response.text().await.unwrap();
+        let id: uuid::Uuid = sqlx::query_scalar("SELECT id FROM feedback")
+            .fetch_one(&app.db_pool)
+            .await
+            .unwrap();
+        assert!(html.contains(&format!("<code>{}</code>", id)));
+        assert!(html.contains(
+            &app.app_state
+                .config
+                .public_url(&format!("/feedback/{}", id))
+        ));
+
+        // 🔎 The link leads to a status page without the feedback content
+        let status = app
+            .client
+            .get(app.url(&format!("/feedback/{}", id)))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(status.status(), 200);
+        let html = status.text().await.unwrap();
+        assert!(html.contains("8b-is/smart-tree"));
+        assert!(html.contains(">pending<"));
+        assert!(html.contains("Queue position</th><td>1"));
+        assert!(!html.contains("vector export"));
+        for missing in [uuid::Uuid::new_v4().to_string(), "not-a-uuid".to_string()] {
+            let response = app
+                .client
+                .get(app.url(&format!("/feedback/{}", missing)))
+                .send()
+                .await
+                .unwrap();
+            assert_eq!(response.status(), 404);
+        }
+        let tags = crate::api::tags::tags_for(&app.db_pool, id).await.unwrap();
+        assert_eq!(tags, vec!["feature"]);
+        println!("✅ Feedback form re-render test passed!");
+    }
+
+    #[tokio::test]
+    async fn test_form_submissions_are_limited_per_ip() {
+        let Some(app) = spawn_test_app().await else {
+            return;
+        };
+        seed_project(&app, "8b-is/smart-tree").await;
+        let state = AppState {
+            rate_limiter: std::sync::Arc::new(
+                crate::middleware::rate_limiting::IpRateLimiter::new(
+                    WindowQuota::per_hour(1),
+                    WindowQuota::per_hour(1),
+                ),
+            ),
+            ..app.app_state.clone()
+        };
+        // 🛡️ Our proxy passes the client on in X-Forwarded-For
+        let submit_from = |peer: &'static str, ip: &'static str| {
+            let state = state.clone();
+            let mut headers = HeaderMap::new();
+            headers.insert("x-forwarded-for", ip.parse().unwrap());
+            let connect_info = ConnectInfo(SocketAddr::new(peer.parse().unwrap(), 4000));
+            let input = FeedbackFormInput {
+                repository: "8b-is/smart-tree".to_string(),
+                category: "other".to_string(),
+                title: "Hello".to_string(),
+                description: "Just saying hi to the maintainers.".to_string(),
+                ..FeedbackFormInput::default()
+            };
+            async move {
+                feedback_form_submit(State(state), headers, Some(connect_info), Form(input))
+                    .await
+                    .status()
+            }
+        };
+        let submit = |ip: &'static str| submit_from("127.0.0.1", ip);
+
+        assert_eq!(submit("203.0.113.9").await, StatusCode::CREATED);
+        assert_eq!(submit("203.0.113.9").await, StatusCode::TOO_MANY_REQUESTS);
+        assert_eq!(submit("198.51.100.4").await, StatusCode::CREATED);
+        // 🙅 A client connecting directly can't make up a fresh address to dodge the limit
+        assert_eq!(
+            submit_from("192.0.2.50", "192.0.2.1").await,
+            StatusCode::CREATED
+        );
+        assert_eq!(
+            submit_from("192.0.2.50", "192.0.2.2").await,
+            StatusCode::TOO_MANY_REQUESTS
+        );
+        assert_eq!(feedback_count(&app).await, 3);
+        println!("✅ Feedback form per-IP limit test passed!");
+    }
+}
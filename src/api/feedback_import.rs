@@ -0,0 +1,1175 @@
+// 📦 Feedback Import - Historical feedback in, for the reports only! 📦
+// POST /admin/api/feedback/import takes a multipart upload whose `file` field is a
+// CSV file (with a header line) or a JSON array of objects. Columns and keys are
+// matched case-insensitively, spaces read as underscores:
+//   repository  (required) owner/repo the feedback was about
+//   content     (required) the feedback text
+//   created_at  (required) RFC 3339, `YYYY-MM-DD HH:MM:SS`, `YYYY-MM-DD` or
+//               `MM/DD/YYYY HH:MM:SS` (UTC unless an offset is given)
+//   title       optional, kept in the metadata
+//   category    optional, stored as a tag
+//   status      optional, the original status, kept in the metadata
+// Anything else is ignored and listed in the report. Rows land with the `imported`
+// status, which nothing ever processes, and `source: "import"`. The upload is read
+// chunk by chunk and parsed as it arrives, inside one transaction: a bad row is
+// skipped with its reason, while a broken file, too many rows or a database error
+// rolls the whole import back. Each row's hash of repository, date and content
+// makes a repeated row (in the file or from an earlier import) a skipped duplicate.
+// Created with love by Aye & Hue! ✨
+
+use anyhow::Context;
+use axum::{
+    body::Bytes,
+    extract::State,
+    http::StatusCode,
+    response::{IntoResponse, Json, Response},
+};
+use axum_extra::extract::{cookie::CookieJar, Multipart};
+use chrono::{DateTime, NaiveDate, NaiveDateTime, Utc};
+use futures_util::{Stream, TryStreamExt};
+use serde::Serialize;
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+use sqlx::{PgConnection, PgPool};
+use tracing::info;
+use uuid::Uuid;
+
+use crate::{
+    api::{
+        admin::{audit_log, require_admin_api_auth},
+        tags::{normalize_tag, store_tags, validate_tags},
+        ApiResponse, AppState, ErrorCode,
+    },
+    database::models::FeedbackStatus,
+};
+
+/// 🏷️ `feedback.source` (and `metadata.source`) of imported rows
+pub const IMPORT_SOURCE: &str = "import";
+/// 📏 Largest upload the import route accepts
+pub const IMPORT_MAX_BYTES: usize = 10 * 1024 * 1024;
+/// 🔢 Most rows one import may hold
+pub const IMPORT_MAX_ROWS: usize = 5_000;
+/// 📋 Columns an import understands
+pub const IMPORT_COLUMNS: [&str; 6] = [
+    "repository",
+    "title",
+    "content",
+    "category",
+    "created_at",
+    "status",
+];
+/// ❗ Columns every row needs
+const REQUIRED_COLUMNS: [&str; 3] = ["repository", "content", "created_at"];
+/// 📏 Longest title and original status we keep
+const MAX_TITLE_LENGTH: usize = 200;
+const MAX_STATUS_LENGTH: usize = 50;
+
+/// 📄 The file formats an import accepts
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ImportFormat {
+    Csv,
+    Json,
+}
+
+impl ImportFormat {
+    /// 🔍 From the uploaded file's name, else its content type
+    pub fn detect(file_name: Option<&str>, content_type: Option<&str>) -> Option<Self> {
+        let extension = file_name
+            .and_then(|name| name.rsplit_once('.'))
+            .map(|(_, extension)| extension.to_ascii_lowercase());
+        match extension.as_deref() {
+            Some("csv") => return Some(ImportFormat::Csv),
+            Some("json") => return Some(ImportFormat::Json),
+            _ => {}
+        }
+        let essence = content_type?.split(';').next()?.trim().to_ascii_lowercase();
+        match essence.as_str() {
+            "text/csv" | "application/csv" => Some(ImportFormat::Csv),
+            "application/json" | "text/json" => Some(ImportFormat::Json),
+            _ => None,
+        }
+    }
+}
+
+/// ❌ Why an import was rolled back
+#[derive(Debug)]
+pub enum ImportError {
+    /// 📄 The file can't be read as the format it claims to be
+    Malformed(String),
+    /// 🔢 More than the allowed number of rows
+    TooManyRows(usize),
+    /// 📡 The upload itself failed (status from the multipart reader)
+    Upload(StatusCode, String),
+    /// 🗄️ The database failed
+    Database(anyhow::Error),
+}
+
+impl IntoResponse for ImportError {
+    fn into_response(self) -> Response {
+        let (status, code, message) = match self {
+            ImportError::Malformed(message) => {
+                (StatusCode::BAD_REQUEST, ErrorCode::MalformedImport, message)
+            }
+            ImportError::TooManyRows(limit) => (
+                StatusCode::PAYLOAD_TOO_LARGE,
+                ErrorCode::ImportTooLarge,
+                format!(
+                    "Imports are limited to {} rows, nothing was imported",
+                    limit
+                ),
+            ),
+            ImportError::Upload(status, message) => (status, ErrorCode::UploadFailed, message),
+            ImportError::Database(e) => return crate::api::utils::handle_error(e).into_response(),
+        };
+        (status, Json(ApiResponse::<()>::error(code, message, None))).into_response()
+    }
+}
+
+/// 📝 One row as read from the file, before validation
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ImportRow {
+    pub repository: Option<String>,
+    pub title: Option<String>,
+    pub content: Option<String>,
+    pub category: Option<String>,
+    pub created_at: Option<String>,
+    pub status: Option<String>,
+}
+
+impl ImportRow {
+    /// ✍️ Set a known column (blank values count as missing); false for unknown ones
+    fn set(&mut self, column: &str, value: String) -> bool {
+        let slot = match column {
+            "repository" => &mut self.repository,
+            "title" => &mut self.title,
+            "content" => &mut self.content,
+            "category" => &mut self.category,
+            "created_at" => &mut self.created_at,
+            "status" => &mut self.status,
+            _ => return false,
+        };
+        *slot = Some(value).filter(|value| !value.trim().is_empty());
+        true
+    }
+}
+
+/// ✅ A row that passed validation
+#[derive(Debug, Clone, PartialEq)]
+pub struct ValidRow {
+    pub repository: String,
+    pub title: Option<String>,
+    pub content: String,
+    pub category: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub original_status: Option<String>,
+}
+
+impl ValidRow {
+    /// #️⃣ Identity of an imported row: repository, creation time and content
+    pub fn dedup_hash(&self) -> String {
+        let mut hasher = Sha256::new();
+        hasher.update(IMPORT_SOURCE.as_bytes());
+        hasher.update([0]);
+        hasher.update(self.repository.to_lowercase().as_bytes());
+        hasher.update([0]);
+        hasher.update(self.created_at.to_rfc3339().as_bytes());
+        hasher.update([0]);
+        hasher.update(self.content.as_bytes());
+        hex::encode(hasher.finalize())
+    }
+}
+
+/// 🧼 Normalize a column name: "Created At" -> "created_at"
+pub(crate) fn column_name(name: &str) -> String {
+    name.trim()
+        .trim_start_matches('\u{feff}')
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join("_")
+        .to_lowercase()
+}
+
+/// 📅 Parse an import timestamp (see the module header for the formats)
+pub fn parse_created_at(value: &str) -> Option<DateTime<Utc>> {
+    let value = value.trim();
+    if let Ok(timestamp) = DateTime::parse_from_rfc3339(value) {
+        return Some(timestamp.with_timezone(&Utc));
+    }
+    for format in [
+        "%Y-%m-%d %H:%M:%S",
+        "%Y-%m-%dT%H:%M:%S",
+        "%m/%d/%Y %H:%M:%S",
+    ] {
+        if let Ok(timestamp) = NaiveDateTime::parse_from_str(value, format) {
+            return Some(timestamp.and_utc());
+        }
+    }
+    let date = NaiveDate::parse_from_str(value, "%Y-%m-%d").ok()?;
+    Some(date.and_hms_opt(0, 0, 0)?.and_utc())
+}
+
+/// ✅ Validate one row, collecting every problem into one reason
+pub fn validate_row(
+    row: &ImportRow,
+    max_content_length: usize,
+    now: DateTime<Utc>,
+) -> Result<ValidRow, String> {
+    let mut errors = Vec::new();
+    let text = |value: &Option<String>| value.as_deref().map(str::trim).map(str::to_string);
+
+    let repository = text(&row.repository);
+    match repository.as_deref() {
+        None => errors.push("repository is missing".to_string()),
+        Some(repository) => {
+            let valid = repository.len() <= 255
+                && repository.split_once('/').is_some_and(|(owner, name)| {
+                    !owner.is_empty() && !name.is_empty() && !name.contains('/')
+                })
+                && !repository.chars().any(char::is_whitespace);
+            if !valid {
+                errors.push(format!("repository '{}' is not owner/repo", repository));
+            }
+        }
+    }
+
+    let content = text(&row.content);
+    match content.as_deref() {
+        None => errors.push("content is missing".to_string()),
+        Some(content) if content.chars().count() > max_content_length => errors.push(format!(
+            "content is longer than {} characters",
+            max_content_length
+        )),
+        Some(_) => {}
+    }
+
+    let created_at = match row.created_at.as_deref() {
+        None => {
+            errors.push("created_at is missing".to_string());
+            None
+        }
+        Some(value) => match parse_created_at(value) {
+            None => {
+                errors.push(format!(
+                    "created_at '{}' is not a date we can read",
+                    value.trim()
+                ));
+                None
+            }
+            Some(created_at) if created_at > now => {
+                errors.push(format!("created_at '{}' is in the future", value.trim()));
+                None
+            }
+            Some(created_at) => Some(created_at),
+        },
+    };
+
+    let category = row.category.as_deref().and_then(normalize_tag);
+    if let Some(category) = &category {
+        errors.extend(
+            validate_tags(std::slice::from_ref(category))
+                .into_iter()
+                .map(|error| format!("category: {}", error)),
+        );
+    }
+    let title = text(&row.title);
+    if title
+        .as_ref()
+        .is_some_and(|title| title.chars().count() > MAX_TITLE_LENGTH)
+    {
+        errors.push(format!(
+            "title is longer than {} characters",
+            MAX_TITLE_LENGTH
+        ));
+    }
+    let original_status = text(&row.status);
+    if original_status
+        .as_ref()
+        .is_some_and(|status| status.chars().count() > MAX_STATUS_LENGTH)
+    {
+        errors.push(format!(
+            "status is longer than {} characters",
+            MAX_STATUS_LENGTH
+        ));
+    }
+    // 🚫 Postgres text can't hold NUL, and one such row would abort the whole import
+    if [
+        &row.repository,
+        &row.title,
+        &row.content,
+        &row.category,
+        &row.status,
+    ]
+    .iter()
+    .any(|value| value.as_deref().is_some_and(|value| value.contains('\0')))
+    {
+        errors.push("values can't contain NUL characters".to_string());
+    }
+
+    match (repository, content, created_at) {
+        (Some(repository), Some(content), Some(created_at)) if errors.is_empty() => Ok(ValidRow {
+            repository,
+            title,
+            content,
+            category,
+            created_at,
+            original_status,
+        }),
+        _ => Err(errors.join("; ")),
+    }
+}
+
+/// 📑 Incremental CSV reader: RFC 4180 quoting (escaped quotes, line breaks inside
+/// quotes), `\n` or `\r\n` line ends, blank lines skipped. Chunks may split anywhere.
+#[derive(Debug, Default)]
+pub struct CsvReader {
+    field: Vec<u8>,
+    record: Vec<String>,
+    in_quotes: bool,
+    /// 🔚 A quote seen inside quotes: either an escaped quote or the closing one
+    quote_pending: bool,
+    /// 🧾 Anything read for the current record (to tell blank lines apart)
+    started: bool,
+}
+
+impl CsvReader {
+    /// 📥 Feed a chunk, returning the records it completed
+    pub fn feed(&mut self, bytes: &[u8]) -> Vec<Vec<String>> {
+        let mut records = Vec::new();
+        for &byte in bytes {
+            if self.in_quotes {
+                if self.quote_pending {
+                    self.quote_pending = false;
+                    if byte == b'"' {
+                        self.field.push(b'"');
+                        continue;
+                    }
+                    self.in_quotes = false;
+                } else {
+                    if byte == b'"' {
+                        self.quote_pending = true;
+                    } else {
+                        self.field.push(byte);
+                    }
+                    continue;
+                }
+            }
+            match byte {
+                b'"' if self.field.is_empty() => {
+                    self.in_quotes = true;
+                    self.started = true;
+                }
+                b',' => {
+                    self.end_field();
+                    self.started = true;
+                }
+                b'\n' => records.extend(self.end_record()),
+                b'\r' => {}
+                _ => {
+                    self.field.push(byte);
+                    self.started = true;
+                }
+            }
+        }
+        records
+    }
+
+    /// 🏁 The last record, when the file doesn't end with a line break
+    pub fn finish(&mut self) -> Option<Vec<String>> {
+        self.in_quotes = false;
+        self.quote_pending = false;
+        self.end_record()
+    }
+
+    fn end_field(&mut self) {
+        let field = std::mem::take(&mut self.field);
+        self.record
+            .push(String::from_utf8_lossy(&field).into_owned());
+    }
+
+    fn end_record(&mut self) -> Option<Vec<String>> {
+        if !self.started {
+            return None;
+        }
+        self.end_field();
+        self.started = false;
+        Some(std::mem::take(&mut self.record))
+    }
+}
+
+/// 🧩 Incremental splitter of a top-level JSON array into its element texts, so a
+/// malformed element fails on its own. Chunks may split anywhere.
+#[derive(Debug, Default)]
+pub struct JsonArraySplitter {
+    opened: bool,
+    closed: bool,
+    depth: usize,
+    in_string: bool,
+    escaped: bool,
+    element: Vec<u8>,
+}
+
+impl JsonArraySplitter {
+    /// 📥 Feed a chunk, returning the elements it completed
+    pub fn feed(&mut self, bytes: &[u8]) -> Result<Vec<Vec<u8>>, ImportError> {
+        let mut elements = Vec::new();
+        for &byte in bytes {
+            if self.closed {
+                if !byte.is_ascii_whitespace() {
+                    return Err(ImportError::Malformed(
+                        "Unexpected content after the JSON array".to_string(),
+                    ));
+                }
+                continue;
+            }
+            if !self.opened {
+                match byte {
+                    b'[' => self.opened = true,
+                    // 🧾 Whitespace and a UTF-8 byte order mark may come first
+                    0xEF | 0xBB | 0xBF => {}
+                    _ if byte.is_ascii_whitespace() => {}
+                    _ => {
+                        return Err(ImportError::Malformed(
+                            "JSON imports must be an array of objects".to_string(),
+                        ))
+                    }
+                }
+                continue;
+            }
+            if self.in_string {
+                self.element.push(byte);
+                if self.escaped {
+                    self.escaped = false;
+                } else if byte == b'\\' {
+                    self.escaped = true;
+                } else if byte == b'"' {
+                    self.in_string = false;
+                }
+                continue;
+            }
+            match byte {
+                b',' if self.depth == 0 => elements.push(std::mem::take(&mut self.element)),
+                b']' if self.depth == 0 => {
+                    self.closed = true;
+                    let last = std::mem::take(&mut self.element);
+                    if !last.trim_ascii().is_empty() {
+                        elements.push(last);
+                    }
+                }
+                _ => {
+                    match byte {
+                        b'"' => self.in_string = true,
+                        b'{' | b'[' => self.depth += 1,
+                        b'}' | b']' => self.depth = self.depth.saturating_sub(1),
+                        _ => {}
+                    }
+                    self.element.push(byte);
+                }
+            }
+        }
+        Ok(elements)
+    }
+
+    /// 🏁 Check the array was closed
+    pub fn finish(&self) -> Result<(), ImportError> {
+        if self.closed {
+            Ok(())
+        } else {
+            Err(ImportError::Malformed(
+                "The JSON array is not closed (is the file truncated?)".to_string(),
+            ))
+        }
+    }
+}
+
+/// 📖 Rows from a JSON array element: an object whose values are strings, numbers
+/// or null
+fn json_row(element: &[u8], ignored: &mut Vec<String>) -> Result<ImportRow, String> {
+    let value: Value =
+        serde_json::from_slice(element).map_err(|e| format!("not valid JSON: {}", e))?;
+    let Value::Object(object) = value else {
+        return Err("not a JSON object".to_string());
+    };
+    let mut row = ImportRow::default();
+    for (key, value) in object {
+        let column = column_name(&key);
+        let value = match value {
+            Value::Null => String::new(),
+            Value::String(text) => text,
+            Value::Number(number) => number.to_string(),
+            Value::Bool(flag) => flag.to_string(),
+            _ => return Err(format!("{} must be a string", key)),
+        };
+        if !row.set(&column, value) && !ignored.contains(&key) {
+            ignored.push(key);
+        }
+    }
+    Ok(row)
+}
+
+/// 📖 The rows of an upload, as they become complete
+enum RowReader {
+    Csv {
+        reader: CsvReader,
+        /// 📋 Column name per position, known once the header is read
+        header: Option<Vec<String>>,
+    },
+    Json(JsonArraySplitter),
+}
+
+impl RowReader {
+    fn new(format: ImportFormat) -> Self {
+        match format {
+            ImportFormat::Csv => RowReader::Csv {
+                reader: CsvReader::default(),
+                header: None,
+            },
+            ImportFormat::Json => RowReader::Json(JsonArraySplitter::default()),
+        }
+    }
+
+    /// 📥 Rows completed by a chunk (None for the final call)
+    fn rows(
+        &mut self,
+        chunk: Option<&[u8]>,
+        ignored: &mut Vec<String>,
+    ) -> Result<Vec<Result<ImportRow, String>>, ImportError> {
+        match self {
+            RowReader::Csv { reader, header } => {
+                let records = match chunk {
+                    Some(bytes) => reader.feed(bytes),
+                    None => reader.finish().into_iter().collect(),
+                };
+                let mut rows = Vec::with_capacity(records.len());
+                for record in records {
+                    match header {
+                        None => *header = Some(csv_header(record, ignored)?),
+                        Some(columns) => rows.push(csv_row(columns, record)),
+                    }
+                }
+                if chunk.is_none() && header.is_none() {
+                    return Err(ImportError::Malformed("The CSV file is empty".to_string()));
+                }
+                Ok(rows)
+            }
+            RowReader::Json(splitter) => {
+                let elements = match chunk {
+                    Some(bytes) => splitter.feed(bytes)?,
+                    None => {
+                        splitter.finish()?;
+                        Vec::new()
+                    }
+                };
+                Ok(elements
+                    .iter()
+                    .map(|element| json_row(element, ignored))
+                    .collect())
+            }
+        }
+    }
+}
+
+/// 📋 Read the CSV header: known columns by position, every required one present
+fn csv_header(record: Vec<String>, ignored: &mut Vec<String>) -> Result<Vec<String>, ImportError> {
+    let columns: Vec<String> = record.iter().map(|name| column_name(name)).collect();
+    for (name, column) in record.iter().zip(&columns) {
+        if !IMPORT_COLUMNS.contains(&column.as_str()) {
+            ignored.push(name.trim().trim_start_matches('\u{feff}').to_string());
+        }
+    }
+    let missing: Vec<&str> = REQUIRED_COLUMNS
+        .into_iter()
+        .filter(|required| !columns.iter().any(|column| column == required))
+        .collect();
+    if !missing.is_empty() {
+        return Err(ImportError::Malformed(format!(
+            "The CSV header is missing required columns: {}",
+            missing.join(", ")
+        )));
+    }
+    Ok(columns)
+}
+
+/// 📖 One CSV record under the header
+fn csv_row(columns: &[String], record: Vec<String>) -> Result<ImportRow, String> {
+    if record.len() > columns.len() {
+        return Err(format!(
+            "has {} fields but the header has {}",
+            record.len(),
+            columns.len()
+        ));
+    }
+    let mut row = ImportRow::default();
+    for (column, value) in columns.iter().zip(record) {
+        row.set(column, value);
+    }
+    Ok(row)
+}
+
+/// 📊 What became of one row
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RowOutcome {
+    Inserted,
+    Skipped,
+}
+
+/// 📊 One row of the report (rows count from 1, the CSV header excluded)
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct RowResult {
+    pub row: usize,
+    pub outcome: RowOutcome,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub feedback_id: Option<Uuid>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+}
+
+/// 📊 The result of a committed import
+#[derive(Debug, Clone, Serialize)]
+pub struct ImportReport {
+    /// 🏷️ Id recorded on every row of this import (`metadata.import.batch`)
+    pub batch: Uuid,
+    pub format: ImportFormat,
+    pub total_rows: usize,
+    pub inserted: usize,
+    pub skipped: usize,
+    /// 🙈 Columns (or JSON keys) that were not imported
+    pub ignored_columns: Vec<String>,
+    pub rows: Vec<RowResult>,
+}
+
+/// ➕ Insert one valid row; None when an identical row already exists
+async fn insert_row(
+    conn: &mut PgConnection,
+    batch: Uuid,
+    row_number: usize,
+    row: &ValidRow,
+) -> anyhow::Result<Option<Uuid>> {
+    let metadata = serde_json::json!({
+        "source": IMPORT_SOURCE,
+        "import": {
+            "batch": batch,
+            "row": row_number,
+            "title": row.title,
+            "category": row.category,
+            "original_status": row.original_status,
+        }
+    });
+    let id: Option<Uuid> = sqlx::query_scalar(
+        r#"
+        INSERT INTO feedback (repository, content, status, metadata, source, dedup_hash, created_at, updated_at)
+        VALUES ($1, $2, $3::feedback_status, $4, $5, $6, $7, $7)
+        ON CONFLICT (dedup_hash) WHERE dedup_hash IS NOT NULL DO NOTHING
+        RETURNING id
+        "#,
+    )
+    .bind(&row.repository)
+    .bind(&row.content)
+    .bind(FeedbackStatus::Imported)
+    .bind(metadata)
+    .bind(IMPORT_SOURCE)
+    .bind(row.dedup_hash())
+    .bind(row.created_at)
+    .fetch_optional(&mut *conn)
+    .await
+    .context("Failed to insert imported feedback")?;
+    if let (Some(id), Some(category)) = (id, &row.category) {
+        store_tags(&mut *conn, id, std::slice::from_ref(category)).await?;
+    }
+    Ok(id)
+}
+
+/// 📦 Import every row of `chunks` in one transaction, committed only when the whole
+/// file was read without a systemic failure
+pub async fn import_feedback<S>(
+    pool: &PgPool,
+    format: ImportFormat,
+    chunks: S,
+    max_rows: usize,
+    max_content_length: usize,
+) -> Result<ImportReport, ImportError>
+where
+    S: Stream<Item = Result<Bytes, ImportError>>,
+{
+    let mut chunks = std::pin::pin!(chunks);
+    let mut tx = pool
+        .begin()
+        .await
+        .context("Failed to start import transaction")
+        .map_err(ImportError::Database)?;
+    let mut reader = RowReader::new(format);
+    let mut report = ImportReport {
+        batch: Uuid::new_v4(),
+        format,
+        total_rows: 0,
+        inserted: 0,
+        skipped: 0,
+        ignored_columns: Vec::new(),
+        rows: Vec::new(),
+    };
+    let now = Utc::now();
+
+    let mut done = false;
+    while !done {
+        let chunk = chunks.try_next().await?;
+        done = chunk.is_none();
+        for row in reader.rows(chunk.as_deref(), &mut report.ignored_columns)? {
+            report.total_rows += 1;
+            if report.total_rows > max_rows {
+                return Err(ImportError::TooManyRows(max_rows));
+            }
+            let row_number = report.total_rows;
+            let outcome = match row.and_then(|row| validate_row(&row, max_content_length, now)) {
+                Err(reason) => Err(reason),
+                Ok(valid) => insert_row(&mut tx, report.batch, row_number, &valid)
+                    .await
+                    .map_err(ImportError::Database)?
+                    .ok_or_else(|| "duplicate of feedback that already exists".to_string()),
+            };
+            report.rows.push(match outcome {
+                Ok(id) => {
+                    report.inserted += 1;
+                    RowResult {
+                        row: row_number,
+                        outcome: RowOutcome::Inserted,
+                        feedback_id: Some(id),
+                        reason: None,
+                    }
+                }
+                Err(reason) => {
+                    report.skipped += 1;
+                    RowResult {
+                        row: row_number,
+                        outcome: RowOutcome::Skipped,
+                        feedback_id: None,
+                        reason: Some(reason),
+                    }
+                }
+            });
+        }
+    }
+
+    tx.commit()
+        .await
+        .context("Failed to commit import")
+        .map_err(ImportError::Database)?;
+    Ok(report)
+}
+
+/// 📦 POST /admin/api/feedback/import (multipart, the file in the `file` field)
+pub async fn import_feedback_handler(
+    State(app_state): State<AppState>,
+    jar: CookieJar,
+    mut multipart: Multipart,
+) -> Response {
+    if let Some(denied) = require_admin_api_auth(&jar, &app_state).await {
+        return denied;
+    }
+    let upload_error = |e: axum_extra::extract::multipart::MultipartError| {
+        ImportError::Upload(e.status(), e.body_text())
+    };
+
+    let field = loop {
+        match multipart.next_field().await {
+            Ok(Some(field)) if field.name() == Some("file") => break field,
+            Ok(Some(_)) => continue,
+            Ok(None) => {
+                return ImportError::Malformed("The upload has no `file` field".to_string())
+                    .into_response()
+            }
+            Err(e) => return upload_error(e).into_response(),
+        }
+    };
+    let file_name = field.file_name().map(str::to_string);
+    let Some(format) = ImportFormat::detect(file_name.as_deref(), field.content_type()) else {
+        return ImportError::Malformed(
+            "Upload a .csv or .json file (or send a text/csv or application/json part)".to_string(),
+        )
+        .into_response();
+    };
+
+    let report = match import_feedback(
+        &app_state.db_pool,
+        format,
+        field.map_err(upload_error),
+        IMPORT_MAX_ROWS,
+        app_state.config.feedback.max_content_length,
+    )
+    .await
+    {
+        Ok(report) => report,
+        Err(e) => return e.into_response(),
+    };
+    info!(
+        "📦 Imported {} of {} feedback rows from {}",
+        report.inserted,
+        report.total_rows,
+        file_name.as_deref().unwrap_or("an upload")
+    );
+    audit_log(
+        &app_state,
+        &jar,
+        "feedback_imported",
+        serde_json::json!({
+            "batch": report.batch,
+            "file": file_name,
+            "format": report.format,
+            "inserted": report.inserted,
+            "skipped": report.skipped,
+        }),
+    )
+    .await;
+    (
+        StatusCode::OK,
+        Json(ApiResponse::success(
+            format!(
+                "Imported {} feedback rows, skipped {}",
+                report.inserted, report.skipped
+            ),
+            report,
+        )),
+    )
+        .into_response()
+}
+
+// 🧪 Tests - Old feedback in, bad rows out, nothing twice!
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{spawn_test_app, TestApp};
+
+    /// 📑 Google Form export: two valid rows (one with a quoted, multi-line answer),
+    /// a duplicate of the first, and three malformed rows
+    const FIXTURE_CSV: &str = "\u{feff}Timestamp,Repository,Title,Content,Category,Created At,Status\r\n\
+        1,8b-is/smart-tree,Symlinks,Symlink loops crash the tree,Bug Report,2024-03-01 10:00:00,open\r\n\
+        2,8b-is/feedbacker,Dark mode,\"Please add \"\"dark\"\" mode,\nit hurts\",Feature Request,03/02/2024 09:30:00,\r\n\
+        3,8b-is/smart-tree,Symlinks again,Symlink loops crash the tree,bug report,2024-03-01T10:00:00Z,closed\r\n\
+        4,not-a-repo,Broken,Some content,,2024-03-03,\r\n\
+        5,8b-is/smart-tree,No date,Content without a date,,,\r\n\
+        6,8b-is/smart-tree,Future,From the future,,2999-01-01,,extra\r\n";
+
+    /// 🧩 The same story as JSON, with a malformed element in the middle
+    const FIXTURE_JSON: &str = r#"[
+        {"repository": "8b-is/smart-tree", "content": "Colors are hard to read", "created_at": "2024-04-01", "votes": 3},
+        {"repository": "8b-is/smart-tree", "content": "Colors are hard to read", "created_at": "2024-04-01T00:00:00+00:00"},
+        {"repository": "8b-is/smart-tree", "content": broken, "created_at": "2024-04-02"},
+        "not an object",
+        {"repository": "8b-is/smart-tree", "content": "", "created_at": "2024-04-03"},
+        {"Repository": "8b-is/feedbacker", "Content": "Needs an [export] button}", "Created At": "2024-04-04 12:00:00"}
+    ]"#;
+
+    /// 📮 Upload a file as the `file` part of a multipart body
+    async fn upload(app: &TestApp, file_name: &str, content: &str) -> reqwest::Response {
+        let boundary = "feedbacker-import-boundary";
+        let body = format!(
+            "--{b}\r\nContent-Disposition: form-data; name=\"note\"\r\n\r\nignored\r\n\
+             --{b}\r\nContent-Disposition: form-data; name=\"file\"; filename=\"{f}\"\r\n\
+             Content-Type: application/octet-stream\r\n\r\n{c}\r\n--{b}--\r\n",
+            b = boundary,
+            f = file_name,
+            c = content
+        );
+        app.client
+            .post(app.url("/admin/api/feedback/import"))
+            .header(
+                "content-type",
+                format!("multipart/form-data; boundary={}", boundary),
+            )
+            .body(body)
+            .send()
+            .await
+            .unwrap()
+    }
+
+    fn outcomes(report: &Value) -> Vec<(String, Option<String>)> {
+        report["rows"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|row| {
+                (
+                    row["outcome"].as_str().unwrap().to_string(),
+                    row["reason"].as_str().map(str::to_string),
+                )
+            })
+            .collect()
+    }
+
+    fn chunks(content: &str, size: usize) -> impl Stream<Item = Result<Bytes, ImportError>> {
+        let pieces: Vec<Result<Bytes, ImportError>> = content
+            .as_bytes()
+            .chunks(size)
+            .map(|piece| Ok(Bytes::copy_from_slice(piece)))
+            .collect();
+        futures_util::stream::iter(pieces)
+    }
+
+    #[test]
+    fn test_csv_reader_survives_any_chunking() {
+        let input = "a,\"b \"\"quoted\"\"\",c\r\n\r\n\"multi\nline\",,\"\"\nlast,row";
+        let expected = vec![
+            vec!["a", "b \"quoted\"", "c"],
+            vec!["multi\nline", "", ""],
+            vec!["last", "row"],
+        ];
+        for size in [1, 2, 5, input.len()] {
+            let mut reader = CsvReader::default();
+            let mut records: Vec<Vec<String>> = input
+                .as_bytes()
+                .chunks(size)
+                .flat_map(|piece| reader.feed(piece))
+                .collect();
+            records.extend(reader.finish());
+            assert_eq!(records, expected, "chunk size {}", size);
+        }
+        println!("✅ CSV reader chunking test passed!");
+    }
+
+    #[test]
+    fn test_json_splitter_isolates_elements() {
+        let mut splitter = JsonArraySplitter::default();
+        let elements: Vec<String> = br#" [{"a": "x,]}"}, [1, 2] , {"b": "\"{"} ] "#
+            .chunks(3)
+            .flat_map(|piece| splitter.feed(piece).unwrap())
+            .map(|element| String::from_utf8(element).unwrap().trim().to_string())
+            .collect();
+        assert_eq!(
+            elements,
+            vec![r#"{"a": "x,]}"}"#, "[1, 2]", r#"{"b": "\"{"}"#]
+        );
+        assert!(splitter.finish().is_ok());
+
+        assert!(JsonArraySplitter::default().feed(b"{\"a\": 1}").is_err());
+        let mut truncated = JsonArraySplitter::default();
+        truncated.feed(b"[{\"a\": 1},").unwrap();
+        assert!(truncated.finish().is_err());
+        assert!(JsonArraySplitter::default().feed(b"[] []").is_err());
+        println!("✅ JSON array splitter test passed!");
+    }
+
+    #[test]
+    fn test_rows_are_validated_with_every_reason() {
+        let now = "2026-01-01T00:00:00Z".parse::<DateTime<Utc>>().unwrap();
+        let row = ImportRow {
+            repository: Some(" 8b-is/smart-tree ".to_string()),
+            title: Some("Symlinks".to_string()),
+            content: Some("Symlink loops".to_string()),
+            category: Some("Bug  Report".to_string()),
+            created_at: Some("2024-03-01 10:00:00".to_string()),
+            status: Some("open".to_string()),
+        };
+        let valid = validate_row(&row, 100, now).unwrap();
+        assert_eq!(valid.repository, "8b-is/smart-tree");
+        assert_eq!(valid.category.as_deref(), Some("bug-report"));
+        assert_eq!(
+            valid.created_at,
+            "2024-03-01T10:00:00Z".parse::<DateTime<Utc>>().unwrap()
+        );
+        assert_eq!(
+            parse_created_at("03/01/2024 10:00:00"),
+            Some(valid.created_at)
+        );
+        assert_eq!(
+            parse_created_at("2024-03-01T12:00:00+02:00"),
+            Some(valid.created_at)
+        );
+
+        let reason = validate_row(
+            &ImportRow {
+                repository: Some("smart-tree".to_string()),
+                content: Some("x".repeat(101)),
+                created_at: Some("2027-01-01".to_string()),
+                ..Default::default()
+            },
+            100,
+            now,
+        )
+        .unwrap_err();
+        assert_eq!(
+            reason,
+            "repository 'smart-tree' is not owner/repo; content is longer than 100 characters; \
+             created_at '2027-01-01' is in the future"
+        );
+        assert_eq!(
+            validate_row(&ImportRow::default(), 100, now).unwrap_err(),
+            "repository is missing; content is missing; created_at is missing"
+        );
+        assert_eq!(
+            ImportFormat::detect(Some("Form Responses.CSV"), None),
+            Some(ImportFormat::Csv)
+        );
+        assert_eq!(
+            ImportFormat::detect(Some("export"), Some("application/json; charset=utf-8")),
+            Some(ImportFormat::Json)
+        );
+        assert_eq!(ImportFormat::detect(Some("export.xlsx"), None), None);
+        println!("✅ Import row validation test passed!");
+    }
+
+    #[tokio::test]
+    async fn test_csv_import_reports_every_row_and_skips_reimports() {
+        let Some(app) = spawn_test_app().await else {
+            return;
+        };
+        assert_eq!(
+            upload(&app, "responses.csv", FIXTURE_CSV).await.status(),
+            401
+        );
+        app.login_admin().await.unwrap();
+
+        let response = upload(&app, "responses.csv", FIXTURE_CSV).await;
+        assert_eq!(response.status(), 200);
+        let body: Value = response.json().await.unwrap();
+        let report = &body["data"];
+        assert_eq!(report["format"], "csv");
+        assert_eq!(
+            (
+                &report["total_rows"],
+                &report["inserted"],
+                &report["skipped"]
+            ),
+            (&Value::from(6), &Value::from(2), &Value::from(4))
+        );
+        assert_eq!(report["ignored_columns"], serde_json::json!(["Timestamp"]));
+        let reason = |text: &str| Some(text.to_string());
+        assert_eq!(
+            outcomes(report),
+            vec![
+                ("inserted".to_string(), None),
+                ("inserted".to_string(), None),
+                (
+                    "skipped".to_string(),
+                    reason("duplicate of feedback that already exists")
+                ),
+                (
+                    "skipped".to_string(),
+                    reason("repository 'not-a-repo' is not owner/repo")
+                ),
+                ("skipped".to_string(), reason("created_at is missing")),
+                (
+                    "skipped".to_string(),
+                    reason("has 8 fields but the header has 7")
+                ),
+            ]
+        );
+
+        // 📦 Imported rows keep their history but never enter the pipeline
+        let rows: Vec<(String, String, String, DateTime<Utc>, Value)> = sqlx::query_as(
+            "SELECT status::text, source, content, created_at, metadata FROM feedback ORDER BY created_at",
+        )
+        .fetch_all(&app.db_pool)
+        .await
+        .unwrap();
+        assert_eq!(rows.len(), 2);
+        assert!(rows
+            .iter()
+            .all(|(status, source, ..)| status == "imported" && source == IMPORT_SOURCE));
+        assert_eq!(rows[1].2, "Please add \"dark\" mode,\nit hurts");
+        assert_eq!(
+            rows[1].3,
+            "2024-03-02T09:30:00Z".parse::<DateTime<Utc>>().unwrap()
+        );
+        assert_eq!(rows[0].4["source"], IMPORT_SOURCE);
+        assert_eq!(rows[0].4["import"]["title"], "Symlinks");
+        assert_eq!(rows[0].4["import"]["original_status"], "open");
+        assert_eq!(rows[0].4["import"]["batch"], report["batch"]);
+        let first_id: Uuid = report["rows"][0]["feedback_id"]
+            .as_str()
+            .unwrap()
+            .parse()
+            .unwrap();
+        assert_eq!(
+            crate::api::tags::tags_for(&app.db_pool, first_id)
+                .await
+                .unwrap(),
+            vec!["bug-report".to_string()]
+        );
+
+        // 🔁 Importing the same file again adds nothing
+        let again: Value = upload(&app, "responses.csv", FIXTURE_CSV)
+            .await
+            .json()
+            .await
+            .unwrap();
+        assert_eq!(again["data"]["inserted"], 0);
+        assert_eq!(again["data"]["skipped"], 6);
+        let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM feedback")
+            .fetch_one(&app.db_pool)
+            .await
+            .unwrap();
+        assert_eq!(count, 2);
+
+        // 📄 A header without the required columns imports nothing at all
+        let response = upload(&app, "responses.csv", "repository,title\n8b-is/x,Hi\n").await;
+        assert_eq!(response.status(), 400);
+        let body: Value = response.json().await.unwrap();
+        assert!(body["error"]["message"]
+            .as_str()
+            .unwrap()
+            .contains("content, created_at"));
+        assert_eq!(upload(&app, "responses.xlsx", "PK").await.status(), 400);
+        println!("✅ CSV import test passed!");
+    }
+
+    #[tokio::test]
+    async fn test_json_import_and_row_cap_rollback() {
+        let Some(app) = spawn_test_app().await else {
+            return;
+        };
+        app.login_admin().await.unwrap();
+
+        let response = upload(&app, "responses.json", FIXTURE_JSON).await;
+        assert_eq!(response.status(), 200);
+        let body: Value = response.json().await.unwrap();
+        let report = &body["data"];
+        assert_eq!(report["format"], "json");
+        assert_eq!(report["ignored_columns"], serde_json::json!(["votes"]));
+        let outcomes = outcomes(report);
+        let kinds: Vec<&str> = outcomes.iter().map(|(kind, _)| kind.as_str()).collect();
+        assert_eq!(
+            kinds,
+            vec!["inserted", "skipped", "skipped", "skipped", "skipped", "inserted"]
+        );
+        assert_eq!(
+            outcomes[1].1.as_deref(),
+            Some("duplicate of feedback that already exists")
+        );
+        assert!(outcomes[2]
+            .1
+            .as_deref()
+            .unwrap()
+            .starts_with("not valid JSON"));
+        assert_eq!(outcomes[3].1.as_deref(), Some("not a JSON object"));
+        assert_eq!(outcomes[4].1.as_deref(), Some("content is missing"));
+
+        // 🔢 Going over the row cap rolls back the rows already inserted
+        let more = FIXTURE_JSON.replace("2024-04", "2023-04");
+        let result =
+            import_feedback(&app.db_pool, ImportFormat::Json, chunks(&more, 7), 5, 1000).await;
+        assert!(matches!(result, Err(ImportError::TooManyRows(5))));
+        // 📄 So does a truncated file
+        let truncated = &more[..more.len() - 10];
+        let result = import_feedback(
+            &app.db_pool,
+            ImportFormat::Json,
+            chunks(truncated, 7),
+            100,
+            1000,
+        )
+        .await;
+        assert!(matches!(result, Err(ImportError::Malformed(_))));
+        let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM feedback")
+            .fetch_one(&app.db_pool)
+            .await
+            .unwrap();
+        assert_eq!(count, 2);
+
+        // 📥 The same rows under the cap go through, chunked any which way
+        let report = import_feedback(&app.db_pool, ImportFormat::Json, chunks(&more, 7), 6, 1000)
+            .await
+            .unwrap();
+        assert_eq!(report.inserted, 2);
+        println!("✅ JSON import test passed!");
+    }
+}
@@ -15,6 +15,7 @@ use tracing::{error, info, warn};
 
 use crate::{
     api::{ApiResponse, AppState},
+    build_info,
     database::get_pool_stats,
 };
 
@@ -52,6 +53,25 @@ pub struct DetailedHealthResponse {
     pub metrics: PerformanceMetrics,
 }
 
+/// 🏷️ Which build is running, and against which schema
+#[derive(Debug, Serialize)]
+pub struct VersionResponse {
+    /// 📦 Crate version
+    pub version: String,
+    /// 🔖 Git commit the binary was built from
+    pub git_sha: String,
+    /// ✂️ Same, abbreviated
+    pub git_sha_short: String,
+    /// 🕰️ Build time
+    pub built_at: Option<chrono::DateTime<chrono::Utc>>,
+    /// 🗄️ Last applied migration in apply order (None if the database can't say)
+    pub migration_level: Option<String>,
+    /// ⏳ Migrations this binary ships that the database hasn't applied
+    pub pending_migrations: Option<usize>,
+    /// 🧩 Optional features compiled in
+    pub features: build_info::Features,
+}
+
 /// ✅ Health status enumeration
 #[derive(Debug, Serialize, PartialEq)]
 #[serde(rename_all = "lowercase")]
@@ -155,8 +175,6 @@ lazy_static::lazy_static! {
 /// 💚 Basic health check endpoint
 /// Perfect for load balancers and simple monitoring!
 pub async fn health_check(State(app_state): State<AppState>) -> impl IntoResponse {
-    info!("💚 Basic health check requested");
-
     let uptime = SERVICE_START_TIME.elapsed();
     let database_healthy = check_database_health(&app_state).await;
 
@@ -180,7 +198,23 @@ pub async fn health_check(State(app_state): State<AppState>) -> impl IntoRespons
         HealthStatus::Unhealthy => StatusCode::SERVICE_UNAVAILABLE,
     };
 
-    info!("💚 Health check completed - Status: {:?}", response.status);
+    // 🎲 Probes run constantly: sample the routine line, but a status change always shows
+    let sampler = &app_state.log_samplers.health;
+    let state = match response.status {
+        HealthStatus::Healthy => 0,
+        HealthStatus::Degraded => 1,
+        HealthStatus::Unhealthy => 2,
+    };
+    if response.status == HealthStatus::Unhealthy {
+        sampler.sample_state(state);
+        warn!("💔 Health check completed - Status: {:?}", response.status);
+    } else if let Some(skipped) = sampler.sample_state(state) {
+        info!(
+            "💚 Health check completed - Status: {:?}{}",
+            response.status,
+            sampler.note(skipped)
+        );
+    }
 
     (
         status_code,
@@ -233,15 +267,56 @@ pub async fn detailed_health_check(State(app_state): State<AppState>) -> impl In
     )
 }
 
+/// 🏷️ Build metadata and schema level (public: nothing here is secret)
+pub async fn version(State(app_state): State<AppState>) -> impl IntoResponse {
+    let (migration_level, pending_migrations) =
+        match crate::database::migrations::migration_status(&app_state.db_pool).await {
+            Ok(states) => (
+                states
+                    .iter()
+                    .rev()
+                    .find(|state| !state.pending)
+                    .map(|state| state.id.clone()),
+                Some(states.iter().filter(|state| state.pending).count()),
+            ),
+            Err(e) => {
+                warn!(
+                    "🗄️ Couldn't read the migration level for /api/version: {:#}",
+                    e
+                );
+                (None, None)
+            }
+        };
+
+    let response = VersionResponse {
+        version: build_info::VERSION.to_string(),
+        git_sha: build_info::GIT_SHA.to_string(),
+        git_sha_short: build_info::short_sha().to_string(),
+        built_at: build_info::built_at(),
+        migration_level,
+        pending_migrations,
+        features: build_info::features(),
+    };
+
+    (
+        StatusCode::OK,
+        Json(ApiResponse::success(
+            "Version information retrieved".to_string(),
+            response,
+        )),
+    )
+}
+
 /// 🔄 Readiness probe endpoint
 /// Kubernetes-style readiness probe for deployment orchestration
 pub async fn readiness_probe(State(app_state): State<AppState>) -> impl IntoResponse {
-    info!("🔄 Readiness probe requested");
-
     let database_ready = check_database_health(&app_state).await;
 
     if database_ready {
-        info!("🔄 Service is ready");
+        let sampler = &app_state.log_samplers.health;
+        if let Some(skipped) = sampler.sample() {
+            info!("🔄 Service is ready{}", sampler.note(skipped));
+        }
         (
             StatusCode::OK,
             Json(serde_json::json!({
@@ -264,8 +339,11 @@ pub async fn readiness_probe(State(app_state): State<AppState>) -> impl IntoResp
 
 /// 🔥 Liveness probe endpoint
 /// Kubernetes-style liveness probe for container health
-pub async fn liveness_probe() -> impl IntoResponse {
-    info!("🔥 Liveness probe requested");
+pub async fn liveness_probe(State(app_state): State<AppState>) -> impl IntoResponse {
+    let sampler = &app_state.log_samplers.health;
+    if let Some(skipped) = sampler.sample() {
+        info!("🔥 Liveness probe requested{}", sampler.note(skipped));
+    }
 
     // 🎯 Simple liveness check - if we can respond, we're alive!
     (
@@ -278,6 +356,21 @@ pub async fn liveness_probe() -> impl IntoResponse {
     )
 }
 
+/// 📈 Prometheus scrape endpoint (routed when ENABLE_METRICS is on)
+pub async fn metrics(State(app_state): State<AppState>) -> impl IntoResponse {
+    (
+        [(
+            axum::http::header::CONTENT_TYPE,
+            "text/plain; version=0.0.4; charset=utf-8",
+        )],
+        app_state.metrics.render_prometheus(
+            &app_state.rate_limiter,
+            &app_state.github_throttle,
+            &app_state.github_requests,
+        ),
+    )
+}
+
 // 🔧 Helper functions for health checks
 
 /// 🗄️ Check database health
@@ -530,6 +623,69 @@ mod tests {
         println!("✅ Health status serialization test passed!");
     }
 
+    #[tokio::test]
+    async fn test_version_endpoint_reports_build_and_migration_level() {
+        let Some(app) = crate::test_support::spawn_test_app().await else {
+            return;
+        };
+        let response = app
+            .client
+            .get(app.url("/api/version"))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), 200);
+        let body: serde_json::Value = response.json().await.unwrap();
+        let data = &body["data"];
+
+        // 🔒 Exactly these fields - nothing from the config finds its way in
+        let mut keys: Vec<&str> = data
+            .as_object()
+            .unwrap()
+            .keys()
+            .map(String::as_str)
+            .collect();
+        keys.sort_unstable();
+        assert_eq!(
+            keys,
+            [
+                "built_at",
+                "features",
+                "git_sha",
+                "git_sha_short",
+                "migration_level",
+                "pending_migrations",
+                "version"
+            ]
+        );
+        assert_eq!(data["version"], env!("CARGO_PKG_VERSION"));
+        assert_eq!(data["git_sha"], build_info::GIT_SHA);
+        assert_eq!(data["git_sha_short"], build_info::short_sha());
+        assert!(data["built_at"].is_string());
+        assert_eq!(
+            data["features"]["redis_cache"],
+            cfg!(feature = "redis-cache")
+        );
+        assert_eq!(data["pending_migrations"], 0);
+
+        // 🗄️ The level is the newest migration recorded in the migrations table
+        let latest: String = sqlx::query_scalar(
+            "SELECT id FROM migrations ORDER BY applied_at DESC, id DESC LIMIT 1",
+        )
+        .fetch_one(&app.db_pool)
+        .await
+        .unwrap();
+        assert_eq!(data["migration_level"], latest);
+        assert_eq!(
+            latest,
+            crate::database::migrations::get_all_migrations()
+                .last()
+                .unwrap()
+                .id
+        );
+        println!("✅ Version endpoint test passed!");
+    }
+
     #[test]
     fn test_overall_status_determination() {
         let healthy_components = ComponentHealth {
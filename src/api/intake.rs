@@ -0,0 +1,243 @@
+// 🗂️ Feedback Intake Versions - Old clients keep working while the API grows! 🗂️
+// POST /api/feedback bodies are versioned. Clients say which shape they send with an
+// `api_version` field in the body, or with the media type
+// `application/vnd.feedbacker.v1+json` (as Content-Type or Accept). Without either the
+// body is read as the current version. Each version deserializes into its own struct
+// and is normalized to `SubmitFeedbackRequest`; unknown versions get a 400
+// `unsupported_api_version` listing the ones we speak.
+// Created with love by Aye & Hue! ✨
+
+use axum::{
+    async_trait,
+    body::Bytes,
+    extract::{FromRequest, Request},
+    http::{header, HeaderMap, StatusCode},
+    response::{IntoResponse, Json, Response},
+};
+use serde::Deserialize;
+
+use super::{
+    feedback::SubmitFeedbackRequest,
+    json::{has_json_content_type, parse_json_body, unsupported_media_type},
+    ApiResponse, ErrorCode,
+};
+
+/// 🆕 The version bodies without one are read as
+pub const CURRENT_API_VERSION: u32 = 1;
+
+/// 📚 Every version the server still accepts
+pub const SUPPORTED_API_VERSIONS: &[u32] = &[1];
+
+/// 📦 A feedback submission in whichever version the client sent, normalized
+#[derive(Debug)]
+pub struct VersionedFeedback {
+    /// 🔢 The version it arrived as
+    pub api_version: u32,
+    /// 📝 The submission in the current internal shape
+    pub request: SubmitFeedbackRequest,
+}
+
+/// 🔢 Just the version field, read before the rest of the body
+#[derive(Deserialize)]
+struct VersionField {
+    api_version: Option<serde_json::Value>,
+}
+
+#[async_trait]
+impl<S> FromRequest<S> for VersionedFeedback
+where
+    S: Send + Sync,
+{
+    type Rejection = Response;
+
+    async fn from_request(req: Request, state: &S) -> Result<Self, Self::Rejection> {
+        if !has_json_content_type(req.headers()) {
+            return Err(unsupported_media_type());
+        }
+        let from_headers = media_type_version(req.headers());
+        let body = Bytes::from_request(req, state)
+            .await
+            .map_err(IntoResponse::into_response)?;
+        let field: VersionField = parse_json_body(&body).map_err(|response| *response)?;
+        let from_body = match field.api_version {
+            None | Some(serde_json::Value::Null) => None,
+            Some(value) => Some(body_version(&value).ok_or_else(|| {
+                unsupported_version(
+                    format!("api_version must be a version number, not {}", value),
+                    None,
+                )
+            })?),
+        };
+
+        let api_version = match (from_body, from_headers) {
+            (Some(body), Some(headers)) if body != headers => {
+                return Err(unsupported_version(
+                    format!(
+                        "api_version {} disagrees with the v{} media type",
+                        body, headers
+                    ),
+                    Some(body),
+                ))
+            }
+            (Some(version), _) | (None, Some(version)) => version,
+            (None, None) => CURRENT_API_VERSION,
+        };
+        let request = match api_version {
+            1 => parse_json_body::<SubmitFeedbackRequest>(&body).map_err(|response| *response)?,
+            other => {
+                return Err(unsupported_version(
+                    format!("API version {} is not supported", other),
+                    Some(other),
+                ))
+            }
+        };
+        Ok(Self {
+            api_version,
+            request,
+        })
+    }
+}
+
+/// 🔢 `1`, `"1"` or `"v1"`
+fn body_version(value: &serde_json::Value) -> Option<u32> {
+    match value {
+        serde_json::Value::Number(number) => number.as_u64()?.try_into().ok(),
+        serde_json::Value::String(text) => {
+            let text = text.trim();
+            text.strip_prefix(['v', 'V']).unwrap_or(text).parse().ok()
+        }
+        _ => None,
+    }
+}
+
+/// 🏷️ The N of `application/vnd.feedbacker.vN+json` in Content-Type, else Accept
+fn media_type_version(headers: &HeaderMap) -> Option<u32> {
+    [header::CONTENT_TYPE, header::ACCEPT]
+        .iter()
+        .filter_map(|name| headers.get(name)?.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .find_map(|media_type| {
+            let essence = media_type
+                .split(';')
+                .next()
+                .unwrap_or_default()
+                .trim()
+                .to_ascii_lowercase();
+            essence
+                .strip_prefix("application/vnd.feedbacker.v")?
+                .strip_suffix("+json")?
+                .parse()
+                .ok()
+        })
+}
+
+/// 🚫 400 naming the versions we do accept
+fn unsupported_version(message: String, requested: Option<u32>) -> Response {
+    let api_response = ApiResponse::<()>::error(
+        ErrorCode::UnsupportedApiVersion,
+        message,
+        Some(serde_json::json!({
+            "requested": requested,
+            "supported": SUPPORTED_API_VERSIONS,
+            "current": CURRENT_API_VERSION,
+        })),
+    );
+    (StatusCode::BAD_REQUEST, Json(api_response)).into_response()
+}
+
+// 🧪 Tests - Every client gets understood!
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{body::Body, routing::post, Router};
+    use tower::ServiceExt;
+
+    async fn submit(headers: &[(&str, &str)], body: serde_json::Value) -> (StatusCode, String) {
+        let app = Router::new().route(
+            "/feedback",
+            post(|feedback: VersionedFeedback| async move {
+                format!("v{} {}", feedback.api_version, feedback.request.repository)
+            }),
+        );
+        let mut request = axum::http::Request::builder()
+            .method("POST")
+            .uri("/feedback");
+        for (name, value) in headers {
+            request = request.header(*name, *value);
+        }
+        let response = app
+            .oneshot(request.body(Body::from(body.to_string())).unwrap())
+            .await
+            .unwrap();
+        let status = response.status();
+        let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        (status, String::from_utf8(bytes.to_vec()).unwrap())
+    }
+
+    #[tokio::test]
+    async fn test_versions_come_from_the_body_or_the_media_type() {
+        let json = [("content-type", "application/json")];
+        let feedback = |version: serde_json::Value| {
+            let mut body = serde_json::json!({
+                "repository": "8b-is/smart-tree",
+                "content": "Please add dark mode",
+            });
+            if !version.is_null() {
+                body["api_version"] = version;
+            }
+            body
+        };
+
+        // 🆕 Deployed clients send no version at all
+        assert_eq!(
+            submit(&json, feedback(serde_json::Value::Null)).await,
+            (StatusCode::OK, "v1 8b-is/smart-tree".to_string())
+        );
+        for version in [serde_json::json!(1), serde_json::json!("v1")] {
+            assert_eq!(submit(&json, feedback(version)).await.0, StatusCode::OK);
+        }
+        for headers in [
+            &[("content-type", "application/vnd.feedbacker.v1+json")][..],
+            &[
+                ("content-type", "application/json"),
+                ("accept", "text/html, application/vnd.feedbacker.v1+json"),
+            ],
+        ] {
+            let (status, body) = submit(headers, feedback(serde_json::Value::Null)).await;
+            assert_eq!(
+                (status, body.as_str()),
+                (StatusCode::OK, "v1 8b-is/smart-tree")
+            );
+        }
+
+        // 🚫 Versions we don't speak, or contradictory ones
+        let (status, body) = submit(&json, feedback(serde_json::json!(2))).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        let body: serde_json::Value = serde_json::from_str(&body).unwrap();
+        assert_eq!(body["error"]["code"], "unsupported_api_version");
+        assert_eq!(body["error"]["details"]["requested"], 2);
+        assert_eq!(
+            body["error"]["details"]["supported"],
+            serde_json::json!([1])
+        );
+        let (status, body) = submit(
+            &[("content-type", "application/vnd.feedbacker.v3+json")],
+            feedback(serde_json::Value::Null),
+        )
+        .await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert!(body.contains("API version 3 is not supported"));
+        let (status, body) = submit(
+            &[("content-type", "application/vnd.feedbacker.v1+json")],
+            feedback(serde_json::json!(2)),
+        )
+        .await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert!(body.contains("disagrees"));
+        let (status, _) = submit(&json, feedback(serde_json::json!("latest"))).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        println!("✅ Feedback intake version test passed!");
+    }
+}
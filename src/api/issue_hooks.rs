@@ -3,15 +3,32 @@
 // Created with love by Aye & Hue - Making issue management magical! ✨
 
 use crate::{
-    api::{ApiResponse, AppState},
-    github::client::GitHubClient,
+    api::{json::ApiJson, ApiResponse, AppState, ErrorCode},
+    config::CommentCooldownMode,
+    database::{
+        api_keys::{ApiKey, SCOPE_ISSUES_WRITE},
+        automation_log::{self, Cleanup},
+        project_config::{BusinessHours, ConfigTooNew, LabelOverride, ProjectConfig},
+    },
+    github::{
+        availability,
+        issue_forms::{parse_issue_form, IssueForm},
+        labels::LabelSpec,
+        ops::{FailedAssignee, GitHubOps, MinimizeReason, PostedComment},
+        throttle::WriteThrottled,
+    },
+    jobs::issue_automation::{defer_issue_automation, IssueAutomationJob},
 };
+use anyhow::Context;
 use axum::{
+    body::Bytes,
     extract::{Path, State},
-    http::StatusCode,
+    http::{HeaderMap, StatusCode},
     response::{IntoResponse, Json, Response},
 };
 use serde::{Deserialize, Serialize};
+use sqlx::PgConnection;
+use std::collections::BTreeMap;
 use tracing::{error, info, warn};
 
 /// 🎫 GitHub Issue webhook payload structure
@@ -21,6 +38,12 @@ pub struct IssueWebhookPayload {
     pub issue: IssueData,
     pub repository: RepositoryData,
     pub sender: UserData,
+    /// 🏷️ The label just added or removed (labeled/unlabeled events)
+    #[serde(default)]
+    pub label: Option<LabelData>,
+    /// 💬 The comment, when this is an `issue_comment` event
+    #[serde(default)]
+    pub comment: Option<CommentData>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -34,6 +57,19 @@ pub struct IssueData {
     pub user: UserData,
     pub labels: Vec<LabelData>,
     pub assignees: Vec<UserData>,
+    /// 🤝 Author's relationship to the repo (OWNER, MEMBER, FIRST_TIME_CONTRIBUTOR, ...)
+    #[serde(default)]
+    pub author_association: Option<String>,
+}
+
+impl IssueData {
+    /// 🧑‍🔧 Opened by someone who maintains the repository?
+    pub fn is_from_maintainer(&self) -> bool {
+        matches!(
+            self.author_association.as_deref(),
+            Some("OWNER" | "MEMBER" | "COLLABORATOR")
+        )
+    }
 }
 
 #[derive(Debug, Deserialize)]
@@ -50,10 +86,58 @@ pub struct UserData {
     pub login: String,
 }
 
+#[derive(Debug, Deserialize)]
+pub struct CommentData {
+    pub id: u64,
+    pub user: UserData,
+}
+
 #[derive(Debug, Deserialize)]
 pub struct LabelData {
     pub name: String,
     pub color: String,
+    /// 📝 Label description, when the repo set one
+    #[serde(default)]
+    pub description: Option<String>,
+    /// 🏭 One of GitHub's default labels?
+    #[serde(default)]
+    pub default: bool,
+}
+
+/// 🪜 GitHub writes the automation makes, recorded as they succeed so a deferred
+/// run picks up where the throttle stopped it instead of commenting twice
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AutomationStep {
+    Labels,
+    WelcomeComment,
+    Assign,
+    ThankYouComment,
+    NeedsInfoReminder,
+    ReminderCleanup,
+}
+
+impl AutomationStep {
+    /// 🏷️ Name used in the automation log (same as the serialized form)
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Labels => "labels",
+            Self::WelcomeComment => "welcome_comment",
+            Self::Assign => "assign",
+            Self::ThankYouComment => "thank_you_comment",
+            Self::NeedsInfoReminder => "needs_info_reminder",
+            Self::ReminderCleanup => "reminder_cleanup",
+        }
+    }
+}
+
+/// ⏳ Returned (202) when the write throttle pushed the automation onto the job queue
+#[derive(Debug, Serialize)]
+pub struct DeferredAutomationResponse {
+    pub issue_number: u32,
+    pub job_id: uuid::Uuid,
+    pub retry_after_seconds: u64,
+    pub completed_steps: Vec<AutomationStep>,
 }
 
 /// 🎯 Issue automation response structure
@@ -69,14 +153,50 @@ pub struct IssueAutomationResponse {
 /// 🪝 Main GitHub issue webhook handler
 pub async fn github_issue_webhook(
     State(app_state): State<AppState>,
-    Json(payload): Json<IssueWebhookPayload>,
+    headers: HeaderMap,
+    body: Bytes,
 ) -> Response {
+    let claim = match crate::api::webhooks::claim_delivery(&app_state, &headers, &body).await {
+        Ok(claim) => claim,
+        Err(rejected) => return rejected,
+    };
+    let response = process_issue_webhook(&app_state, &headers, &body).await;
+    claim.settle(&app_state, response).await
+}
+
+/// 🔧 Everything after verification, for `github_issue_webhook`
+async fn process_issue_webhook(app_state: &AppState, headers: &HeaderMap, body: &[u8]) -> Response {
+    if let Some(handled) = crate::api::webhooks::handle_setup_event(app_state, headers, body).await
+    {
+        return handled;
+    }
+    // 🔀 Our registered hook sends pull request events here too
+    if headers
+        .get(crate::api::webhooks::GITHUB_EVENT_HEADER)
+        .is_some_and(|event| event.as_bytes() == b"pull_request")
+    {
+        return crate::api::webhooks::process_github_webhook(app_state, headers, body).await;
+    }
+    if let Some(ignored) = crate::api::webhooks::ignore_unwanted_event(app_state, headers, body) {
+        return ignored;
+    }
+    let payload: IssueWebhookPayload = match crate::api::webhooks::parse_delivery(body) {
+        Ok(payload) => payload,
+        Err(response) => return *response,
+    };
     info!(
         "🎫 Received GitHub issue webhook: {} for issue #{} in {}",
         payload.action, payload.issue.number, payload.repository.full_name
     );
 
-    match process_issue_event(&app_state, &payload).await {
+    // 🗄️ Keep a copy of the event (with any form fields) for projects we know about
+    let form = payload.issue.body.as_deref().and_then(parse_issue_form);
+    if let Err(e) = record_issue_event(app_state, &payload, form.as_ref()).await {
+        warn!("⚠️ Failed to record issue webhook: {:#}", e);
+    }
+
+    let mut done = Vec::new();
+    match process_issue_event(app_state, &payload, &mut done).await {
         Ok(response) => {
             info!(
                 "✅ Issue automation completed for #{}",
@@ -91,12 +211,15 @@ pub async fn github_issue_webhook(
             )
                 .into_response()
         }
+        Err(e) if WriteThrottled::find(&e).is_some() => {
+            defer_from_webhook(app_state, &payload, body, done, &e).await
+        }
         Err(e) => {
             error!("❌ Failed to process issue automation: {:#}", e);
             (
                 StatusCode::INTERNAL_SERVER_ERROR,
                 Json(ApiResponse::<()>::error(
-                    "automation_failed".to_string(),
+                    ErrorCode::AutomationFailed,
                     "Failed to process issue automation".to_string(),
                     Some(serde_json::json!({ "error": e.to_string() })),
                 )),
@@ -106,18 +229,128 @@ pub async fn github_issue_webhook(
     }
 }
 
-/// 🤖 Process different types of issue events
-async fn process_issue_event(
+/// ⏳ The write throttle would have held the webhook up: queue the rest for later
+async fn defer_from_webhook(
     app_state: &AppState,
     payload: &IssueWebhookPayload,
+    body: &[u8],
+    done: Vec<AutomationStep>,
+    error: &anyhow::Error,
+) -> Response {
+    let retry_after = WriteThrottled::find(error)
+        .map(|throttled| throttled.retry_after)
+        .unwrap_or_default();
+    let deferred = async {
+        let event = serde_json::from_slice(body)?;
+        let job = IssueAutomationJob {
+            event,
+            done: done.clone(),
+            deferrals: 0,
+        };
+        defer_issue_automation(&app_state.db_pool, &job, retry_after).await
+    };
+    match deferred.await {
+        Ok(job_id) => {
+            info!(
+                "⏳ Deferred automation for issue #{} as job {} ({:?})",
+                payload.issue.number, job_id, retry_after
+            );
+            (
+                StatusCode::ACCEPTED,
+                Json(ApiResponse::success(
+                    "Issue automation deferred by the GitHub write throttle".to_string(),
+                    DeferredAutomationResponse {
+                        issue_number: payload.issue.number,
+                        job_id,
+                        retry_after_seconds: retry_after.as_secs_f64().ceil() as u64,
+                        completed_steps: done,
+                    },
+                )),
+            )
+                .into_response()
+        }
+        Err(e) => {
+            error!("❌ Failed to defer issue automation: {:#}", e);
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ApiResponse::<()>::error(
+                    ErrorCode::AutomationFailed,
+                    "Failed to process issue automation".to_string(),
+                    Some(serde_json::json!({ "error": error.to_string() })),
+                )),
+            )
+                .into_response()
+        }
+    }
+}
+
+/// 🤖 Process different types of issue events, skipping writes already in `done`
+/// and appending each one that succeeds
+pub(crate) async fn process_issue_event(
+    app_state: &AppState,
+    payload: &IssueWebhookPayload,
+    done: &mut Vec<AutomationStep>,
 ) -> anyhow::Result<IssueAutomationResponse> {
-    let github_client = GitHubClient::new(&app_state.config.github.token)?;
+    let skipped = |action_taken: &str| IssueAutomationResponse {
+        issue_number: payload.issue.number,
+        action_taken: action_taken.to_string(),
+        comment_added: None,
+        labels_applied: vec![],
+        assigned_to: None,
+    };
+    match availability::unavailable(&app_state.db_pool, &payload.repository.full_name).await {
+        Ok(None) => {}
+        Ok(Some(unavailable)) => {
+            // 🚪 Until an admin reactivates the project, GitHub would only refuse us
+            info!(
+                "⏭️ Skipping automation for {}#{}: {}",
+                payload.repository.full_name, payload.issue.number, unavailable
+            );
+            return Ok(skipped("skipped_repository_unavailable"));
+        }
+        Err(e) => warn!("⚠️ {:#}", e),
+    }
+    let form = payload.issue.body.as_deref().and_then(parse_issue_form);
+    let project_config = match automation_config(app_state, &payload.repository.full_name).await {
+        Ok(config) => config,
+        Err(too_new) => {
+            // ⏭️ A newer build wrote this config - guessing at it could undo what it set up
+            warn!(
+                "⏭️ Skipping automation for {}#{}: {}",
+                payload.repository.full_name, payload.issue.number, too_new
+            );
+            return Ok(skipped("skipped_config_too_new"));
+        }
+    };
 
     match payload.action.as_str() {
-        "opened" => handle_issue_opened(&github_client, payload).await,
-        "closed" => handle_issue_closed(&github_client, payload).await,
-        "labeled" => handle_issue_labeled(&github_client, payload).await,
-        "assigned" => handle_issue_assigned(&github_client, payload).await,
+        "opened" => {
+            let footer = comment_footer(app_state, project_config.as_ref());
+            let window = response_window(project_config.as_ref(), chrono::Utc::now());
+            handle_issue_opened(
+                app_state,
+                payload,
+                form.as_ref(),
+                project_config.as_ref(),
+                footer.as_deref(),
+                window,
+                done,
+            )
+            .await
+        }
+        "closed" => {
+            let footer = comment_footer(app_state, project_config.as_ref());
+            handle_issue_closed(app_state, payload, footer.as_deref(), done).await
+        }
+        "labeled" => {
+            let footer = comment_footer(app_state, project_config.as_ref());
+            handle_issue_labeled(app_state, payload, footer.as_deref(), done).await
+        }
+        "assigned" => handle_issue_assigned(payload).await,
+        "created" if payload.comment.is_some() => {
+            let footer = comment_footer(app_state, project_config.as_ref());
+            handle_comment_created(app_state, payload, footer.as_deref(), done).await
+        }
         _ => {
             info!("ℹ️ No automation configured for action: {}", payload.action);
             Ok(IssueAutomationResponse {
@@ -131,12 +364,267 @@ async fn process_issue_event(
     }
 }
 
+/// 🗄️ Store the issue event in the webhooks table when the repository belongs to a project
+async fn record_issue_event(
+    app_state: &AppState,
+    payload: &IssueWebhookPayload,
+    form: Option<&IssueForm>,
+) -> anyhow::Result<()> {
+    let project_id = crate::database::project_repositories::project_for_repository(
+        &app_state.db_pool,
+        &payload.repository.full_name,
+    )
+    .await?;
+
+    let Some(project_id) = project_id else {
+        return Ok(());
+    };
+
+    sqlx::query("INSERT INTO webhooks (project_id, event_type, payload) VALUES ($1, $2, $3)")
+        .bind(project_id)
+        .bind(match payload.comment {
+            Some(_) => format!("issue_comment.{}", payload.action),
+            None => format!("issues.{}", payload.action),
+        })
+        .bind(serde_json::json!({
+            "action": payload.action,
+            "repository": payload.repository.full_name,
+            "issue": {
+                "number": payload.issue.number,
+                "title": payload.issue.title,
+                "html_url": payload.issue.html_url,
+                "user": payload.issue.user.login,
+            },
+            "issue_form": form,
+        }))
+        .execute(&app_state.db_pool)
+        .await?;
+    Ok(())
+}
+
+/// ✍️ Footer for bot comments on a project's issues.
+/// The project's `comments.footer` wins (null or "" disables it);
+/// otherwise the global GITHUB_COMMENT_FOOTER applies.
+fn comment_footer(app_state: &AppState, project_config: Option<&ProjectConfig>) -> Option<String> {
+    match project_config.and_then(|config| config.comments.footer.as_ref()) {
+        Some(Some(footer)) => crate::config::parse_comment_footer(footer),
+        Some(None) => None,
+        None => app_state.config.github.comment_footer.clone(),
+    }
+}
+
+/// 🕘 What the welcome comment should promise about response times
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum ResponseWindow<'a> {
+    /// 📭 No schedule configured - the usual 24-48 hour promise
+    Unscheduled,
+    /// ⚡ Inside the project's business hours
+    BusinessHours,
+    /// 🌙 Outside them, until the next opening
+    AfterHours(&'a BusinessHours, chrono::DateTime<chrono::Utc>),
+}
+
+/// 🕘 The response window at `now` under the project's `schedule`, if it has one
+fn response_window(
+    project_config: Option<&ProjectConfig>,
+    now: chrono::DateTime<chrono::Utc>,
+) -> ResponseWindow<'_> {
+    match project_config.and_then(|config| config.schedule.as_ref()) {
+        None => ResponseWindow::Unscheduled,
+        Some(schedule) if schedule.is_open(now) => ResponseWindow::BusinessHours,
+        Some(schedule) => ResponseWindow::AfterHours(schedule, schedule.next_opening(now)),
+    }
+}
+
+/// ⚙️ The project's config for automation - Err(too new) means leave the issue alone.
+/// Any other problem is logged and automation carries on with the defaults.
+async fn automation_config(
+    app_state: &AppState,
+    repository: &str,
+) -> Result<Option<ProjectConfig>, ConfigTooNew> {
+    match ProjectConfig::for_repository(&app_state.db_pool, repository).await {
+        Ok(config) => Ok(config),
+        Err(e) => match ConfigTooNew::find(&e) {
+            Some(too_new) => Err(too_new),
+            None => {
+                warn!("⚠️ Ignoring project config for automation: {:#}", e);
+                Ok(None)
+            }
+        },
+    }
+}
+
+/// 🎨 Create any of `labels` the repository is missing, in our colors (or the project's
+/// overrides) rather than GitHub's grey. A failure is logged - applying the labels
+/// still goes ahead, and GitHub creates what's left in grey.
+async fn ensure_labels_exist(
+    github: &dyn GitHubOps,
+    owner: &str,
+    repo: &str,
+    labels: &[String],
+    overrides: &BTreeMap<String, LabelOverride>,
+) {
+    if let Err(e) = github
+        .ensure_labels(owner, repo, &LabelSpec::for_names(labels, overrides))
+        .await
+    {
+        warn!(
+            "⚠️ Couldn't create missing labels in {}/{}: {:#}",
+            owner, repo, e
+        );
+    }
+}
+
+/// 📒 Log the section automation just posted, so later steps find it (and add to it)
+async fn log_bot_comment(
+    conn: &mut PgConnection,
+    payload: &IssueWebhookPayload,
+    step: AutomationStep,
+    posted: &PostedComment,
+    section: &str,
+) -> anyhow::Result<()> {
+    automation_log::record_comment(
+        conn,
+        &payload.repository.full_name,
+        payload.issue.number,
+        step.as_str(),
+        posted,
+        section,
+    )
+    .await
+    .with_context(|| {
+        format!(
+            "Failed to log bot comment {} on #{}",
+            posted.id, payload.issue.number
+        )
+    })
+}
+
+/// ✍️ Append the footer (if any) to a comment body
+fn with_footer(body: String, footer: Option<&str>) -> String {
+    match footer {
+        Some(footer) => format!("{}\n\n{}", body, footer),
+        None => body,
+    }
+}
+
+/// 🧩 A consolidated bot comment: its sections in order, then the footer
+fn join_sections<'a>(sections: impl IntoIterator<Item = &'a str>, footer: Option<&str>) -> String {
+    with_footer(
+        sections.into_iter().collect::<Vec<_>>().join("\n\n---\n\n"),
+        footer,
+    )
+}
+
+/// 💬 Post `section` as `step`'s comment, minding GITHUB_COMMENT_COOLDOWN_SECONDS: when
+/// the last bot comment on the issue is more recent than that, the section is added to
+/// it (consolidate) or dropped (suppress). Returns the comment as it now reads, or None
+/// when it was suppressed. Steps on the same issue take turns here (an advisory lock on
+/// the issue), so two deliveries can't both miss each other's comment and post twice.
+async fn post_bot_comment(
+    app_state: &AppState,
+    payload: &IssueWebhookPayload,
+    step: AutomationStep,
+    section: String,
+    footer: Option<&str>,
+) -> anyhow::Result<Option<String>> {
+    let mut tx = app_state.tx().await?;
+    let result = post_bot_comment_locked(&mut tx, app_state, payload, step, section, footer).await;
+    tx.finish(result).await
+}
+
+/// 💬 `post_bot_comment` inside its transaction
+async fn post_bot_comment_locked(
+    conn: &mut PgConnection,
+    app_state: &AppState,
+    payload: &IssueWebhookPayload,
+    step: AutomationStep,
+    section: String,
+    footer: Option<&str>,
+) -> anyhow::Result<Option<String>> {
+    sqlx::query("SELECT pg_advisory_xact_lock(hashtext($1 || '#' || $2::text))")
+        .bind(&payload.repository.full_name)
+        .bind(payload.issue.number as i32)
+        .execute(&mut *conn)
+        .await
+        .context("Failed to lock the issue for a bot comment")?;
+    let github = &app_state.config.github;
+    let (owner, repo) = (&payload.repository.owner.login, &payload.repository.name);
+    if github.comment_cooldown_seconds > 0 {
+        let since =
+            chrono::Utc::now() - chrono::Duration::seconds(github.comment_cooldown_seconds as i64);
+        let last = automation_log::last_open_comment(
+            &mut *conn,
+            &payload.repository.full_name,
+            payload.issue.number,
+            since,
+        )
+        .await?;
+        if let Some(last) = last {
+            if github.comment_cooldown_mode == CommentCooldownMode::Suppress {
+                info!(
+                    "🔇 Skipping {} on #{}: a bot comment was posted in the last {}s",
+                    step.as_str(),
+                    payload.issue.number,
+                    github.comment_cooldown_seconds
+                );
+                return Ok(None);
+            }
+            let sections = automation_log::comment_sections(
+                &mut *conn,
+                &payload.repository.full_name,
+                last.comment_id,
+            )
+            .await?;
+            // 📜 Comments logged before sections were kept can't be rebuilt - post anew
+            let earlier: Option<Vec<String>> =
+                sections.into_iter().map(|section| section.body).collect();
+            if let Some(earlier) = earlier {
+                let comment = join_sections(
+                    earlier.iter().map(String::as_str).chain([section.as_str()]),
+                    footer,
+                );
+                info!(
+                    "🧺 Adding {} to bot comment {} on #{}",
+                    step.as_str(),
+                    last.comment_id,
+                    payload.issue.number
+                );
+                app_state
+                    .github
+                    .update_comment(owner, repo, last.comment_id as u64, &comment)
+                    .await?;
+                let posted = PostedComment {
+                    id: last.comment_id as u64,
+                    node_id: last.comment_node_id,
+                };
+                log_bot_comment(conn, payload, step, &posted, &section).await?;
+                return Ok(Some(comment));
+            }
+        }
+    }
+
+    let comment = with_footer(section.clone(), footer);
+    let posted = app_state
+        .github
+        .add_comment_to_issue(owner, repo, payload.issue.number, &comment)
+        .await?;
+    log_bot_comment(conn, payload, step, &posted, &section).await?;
+    Ok(Some(comment))
+}
+
 /// 🆕 Handle new issue creation
 async fn handle_issue_opened(
-    github_client: &GitHubClient,
+    app_state: &AppState,
     payload: &IssueWebhookPayload,
+    form: Option<&IssueForm>,
+    project_config: Option<&ProjectConfig>,
+    footer: Option<&str>,
+    window: ResponseWindow<'_>,
+    done: &mut Vec<AutomationStep>,
 ) -> anyhow::Result<IssueAutomationResponse> {
     info!("🆕 Processing newly opened issue #{}", payload.issue.number);
+    let github_client = app_state.github.as_ref();
 
     let mut response = IssueAutomationResponse {
         issue_number: payload.issue.number,
@@ -147,42 +635,69 @@ async fn handle_issue_opened(
     };
 
     // 🏷️ Auto-label based on issue content
-    let labels_to_add = analyze_issue_for_labels(&payload.issue).await;
-    if !labels_to_add.is_empty() {
-        github_client
-            .add_labels_to_issue(
+    if !done.contains(&AutomationStep::Labels) {
+        let labels_to_add = analyze_issue_for_labels(&payload.issue, form).await;
+        if !labels_to_add.is_empty() {
+            let overrides = project_config
+                .map(|config| config.labels.clone())
+                .unwrap_or_default();
+            ensure_labels_exist(
+                github_client,
                 &payload.repository.owner.login,
                 &payload.repository.name,
-                payload.issue.number,
                 &labels_to_add,
+                &overrides,
             )
-            .await?;
-        response.labels_applied = labels_to_add;
+            .await;
+            // 🔁 Labels the issue already carries (a redelivery, say) aren't sent again
+            let current: Vec<String> = payload
+                .issue
+                .labels
+                .iter()
+                .map(|label| label.name.clone())
+                .collect();
+            github_client
+                .add_missing_labels(
+                    &payload.repository.owner.login,
+                    &payload.repository.name,
+                    payload.issue.number,
+                    &labels_to_add,
+                    Some(&current),
+                )
+                .await?;
+            response.labels_applied = labels_to_add;
+        }
+        done.push(AutomationStep::Labels);
     }
 
     // 💬 Add welcome comment with helpful information
-    let welcome_comment = create_welcome_comment(&payload.issue).await;
-    github_client
-        .add_comment_to_issue(
-            &payload.repository.owner.login,
-            &payload.repository.name,
-            payload.issue.number,
-            &welcome_comment,
+    if !done.contains(&AutomationStep::WelcomeComment) {
+        let welcome_comment = create_welcome_comment(&payload.issue, form, None, window).await;
+        response.comment_added = post_bot_comment(
+            app_state,
+            payload,
+            AutomationStep::WelcomeComment,
+            welcome_comment,
+            footer,
         )
         .await?;
-    response.comment_added = Some(welcome_comment);
+        done.push(AutomationStep::WelcomeComment);
+    }
 
     // 🎯 Auto-assign if it's a specific type of issue
-    if let Some(assignee) = determine_auto_assignee(&payload.issue).await {
-        github_client
-            .assign_issue(
-                &payload.repository.owner.login,
-                &payload.repository.name,
-                payload.issue.number,
-                &assignee,
-            )
-            .await?;
-        response.assigned_to = Some(assignee);
+    if !done.contains(&AutomationStep::Assign) {
+        if let Some(assignee) = determine_auto_assignee(&payload.issue).await {
+            github_client
+                .assign_issue(
+                    &payload.repository.owner.login,
+                    &payload.repository.name,
+                    payload.issue.number,
+                    &assignee,
+                )
+                .await?;
+            response.assigned_to = Some(assignee);
+        }
+        done.push(AutomationStep::Assign);
     }
 
     Ok(response)
@@ -190,8 +705,10 @@ async fn handle_issue_opened(
 
 /// ✅ Handle issue closure
 async fn handle_issue_closed(
-    github_client: &GitHubClient,
+    app_state: &AppState,
     payload: &IssueWebhookPayload,
+    footer: Option<&str>,
+    done: &mut Vec<AutomationStep>,
 ) -> anyhow::Result<IssueAutomationResponse> {
     info!("✅ Processing closed issue #{}", payload.issue.number);
 
@@ -203,49 +720,175 @@ async fn handle_issue_closed(
         assigned_to: None,
     };
 
-    // 💬 Add thank you comment
-    let thank_you_comment = "🎉 Thank you for reporting this issue! If you have any other feedback or feature requests, feel free to submit them through our Feedbacker service at f.8b.is. \n\nHappy coding! 🚢\n\n*- Aye & Hue*";
+    if done.contains(&AutomationStep::ThankYouComment) {
+        return Ok(response);
+    }
 
-    github_client
-        .add_comment_to_issue(
-            &payload.repository.owner.login,
-            &payload.repository.name,
-            payload.issue.number,
-            thank_you_comment,
-        )
-        .await?;
-    response.comment_added = Some(thank_you_comment.to_string());
+    // 💬 Add thank you comment
+    let thank_you_comment = "🎉 Thank you for reporting this issue! If you have any other feedback or feature requests, feel free to submit them through our Feedbacker service at f.8b.is. \n\nHappy coding! 🚢".to_string();
+    response.comment_added = post_bot_comment(
+        app_state,
+        payload,
+        AutomationStep::ThankYouComment,
+        thank_you_comment,
+        footer,
+    )
+    .await?;
+    done.push(AutomationStep::ThankYouComment);
 
     Ok(response)
 }
 
-/// 🏷️ Handle issue labeling events
+/// 🏷️ Handle issue labeling events: adding `needs-info` posts a reminder asking the
+/// author for details (once - not again while an earlier reminder is still showing),
+/// quoting the label's description when it has one. Maintainers' issues are exempt.
 async fn handle_issue_labeled(
-    _github_client: &GitHubClient,
+    app_state: &AppState,
     payload: &IssueWebhookPayload,
+    footer: Option<&str>,
+    done: &mut Vec<AutomationStep>,
 ) -> anyhow::Result<IssueAutomationResponse> {
     info!("🏷️ Processing labeled issue #{}", payload.issue.number);
 
-    // Check if it's a "needs-info" label and respond accordingly
-    for label in &payload.issue.labels {
-        if label.name == "needs-info" || label.name == "question" {
-            // Could add a comment asking for more details
-            info!("🤔 Issue needs more information, user should provide details");
-        }
+    let mut response = IssueAutomationResponse {
+        issue_number: payload.issue.number,
+        action_taken: "issue_labeled".to_string(),
+        comment_added: None,
+        labels_applied: vec![],
+        assigned_to: None,
+    };
+
+    let is_needs_info = payload
+        .label
+        .as_ref()
+        .is_some_and(|label| label.name == "needs-info");
+    if !is_needs_info
+        || payload.issue.is_from_maintainer()
+        || done.contains(&AutomationStep::NeedsInfoReminder)
+    {
+        return Ok(response);
     }
 
-    Ok(IssueAutomationResponse {
+    let step = AutomationStep::NeedsInfoReminder;
+    let existing = automation_log::open_comments(
+        &app_state.db_pool,
+        &payload.repository.full_name,
+        payload.issue.number,
+        step.as_str(),
+    )
+    .await?;
+    if existing.is_empty() {
+        let reminder = format!(
+            concat!(
+                "🤔 **A few more details, please!**\n\n",
+                "@{} we need a little more information before we can move this forward. ",
+                "Could you reply with the steps you took, what you expected and what happened instead?\n\n",
+                "This reminder tidies itself away once you respond.",
+            ),
+            payload.issue.user.login
+        );
+        let reminder = match payload
+            .label
+            .as_ref()
+            .and_then(|label| label.description.as_deref())
+        {
+            Some(description) => format!("{}\n\n> 🏷️ **needs-info**: {}", reminder, description),
+            None => reminder,
+        };
+        response.comment_added =
+            post_bot_comment(app_state, payload, step, reminder, footer).await?;
+    }
+    done.push(step);
+
+    Ok(response)
+}
+
+/// 💬 Handle new comments: once the issue's author replies, their needs-info
+/// reminders are obsolete - minimized, or cut out of a comment they share with others
+async fn handle_comment_created(
+    app_state: &AppState,
+    payload: &IssueWebhookPayload,
+    footer: Option<&str>,
+    done: &mut Vec<AutomationStep>,
+) -> anyhow::Result<IssueAutomationResponse> {
+    let response = IssueAutomationResponse {
         issue_number: payload.issue.number,
-        action_taken: "issue_labeled".to_string(),
+        action_taken: "comment_created".to_string(),
         comment_added: None,
         labels_applied: vec![],
         assigned_to: None,
-    })
+    };
+
+    let from_author = payload.comment.as_ref().is_some_and(|comment| {
+        comment
+            .user
+            .login
+            .eq_ignore_ascii_case(&payload.issue.user.login)
+    });
+    if !from_author || done.contains(&AutomationStep::ReminderCleanup) {
+        return Ok(response);
+    }
+
+    let reminders = automation_log::open_comments(
+        &app_state.db_pool,
+        &payload.repository.full_name,
+        payload.issue.number,
+        AutomationStep::NeedsInfoReminder.as_str(),
+    )
+    .await?;
+    for reminder in reminders {
+        let sections = automation_log::comment_sections(
+            &app_state.db_pool,
+            &payload.repository.full_name,
+            reminder.comment_id,
+        )
+        .await?;
+        let others: Option<Vec<String>> = sections
+            .into_iter()
+            .filter(|section| section.id != reminder.id)
+            .map(|section| section.body)
+            .collect();
+        if let Some(others) = others.filter(|others| !others.is_empty()) {
+            info!(
+                "✂️ Author replied on #{}, removing the needs-info reminder from comment {}",
+                payload.issue.number, reminder.comment_id
+            );
+            app_state
+                .github
+                .update_comment(
+                    &payload.repository.owner.login,
+                    &payload.repository.name,
+                    reminder.comment_id as u64,
+                    &join_sections(others.iter().map(String::as_str), footer),
+                )
+                .await?;
+            automation_log::mark_cleaned_up(&app_state.db_pool, reminder.id, Cleanup::Deleted)
+                .await?;
+            continue;
+        }
+        info!(
+            "🙈 Author replied on #{}, minimizing needs-info reminder {}",
+            payload.issue.number, reminder.comment_id
+        );
+        app_state
+            .github
+            .minimize_comment(
+                &payload.repository.owner.login,
+                &payload.repository.name,
+                &reminder.comment_node_id,
+                MinimizeReason::Outdated,
+            )
+            .await?;
+        automation_log::mark_cleaned_up(&app_state.db_pool, reminder.id, Cleanup::Minimized)
+            .await?;
+    }
+    done.push(AutomationStep::ReminderCleanup);
+
+    Ok(response)
 }
 
 /// 👤 Handle issue assignment
 async fn handle_issue_assigned(
-    _github_client: &GitHubClient,
     payload: &IssueWebhookPayload,
 ) -> anyhow::Result<IssueAutomationResponse> {
     info!("👤 Processing assigned issue #{}", payload.issue.number);
@@ -260,7 +903,7 @@ async fn handle_issue_assigned(
 }
 
 /// 🔍 Analyze issue content to suggest appropriate labels
-async fn analyze_issue_for_labels(issue: &IssueData) -> Vec<String> {
+async fn analyze_issue_for_labels(issue: &IssueData, form: Option<&IssueForm>) -> Vec<String> {
     let mut labels = Vec::new();
     let content = format!("{} {}", issue.title, issue.body.as_deref().unwrap_or(""));
     let content_lower = content.to_lowercase();
@@ -308,12 +951,29 @@ async fn analyze_issue_for_labels(issue: &IssueData) -> Vec<String> {
         labels.push("performance".to_string());
     }
 
+    // 📋 Issue forms tell us the type outright, and whether anything is missing
+    if let Some(form) = form {
+        if form.is_bug_report() && !labels.iter().any(|label| label == "bug") {
+            labels.push("bug".to_string());
+        }
+        // 🧑‍🔧 Maintainers know what they left out - no needs-info nagging for them
+        if !form.missing_required().is_empty() && !issue.is_from_maintainer() {
+            labels.push("needs-info".to_string());
+        }
+    }
+
     labels
 }
 
 /// 💬 Create a welcoming comment for new issues
-async fn create_welcome_comment(issue: &IssueData) -> String {
-    let issue_type = if issue.title.to_lowercase().contains("bug") {
+async fn create_welcome_comment(
+    issue: &IssueData,
+    form: Option<&IssueForm>,
+    footer: Option<&str>,
+    window: ResponseWindow<'_>,
+) -> String {
+    let is_bug_form = form.map(|form| form.is_bug_report()).unwrap_or(false);
+    let issue_type = if is_bug_form || issue.title.to_lowercase().contains("bug") {
         "🐛 **Bug Report**"
     } else if issue.title.to_lowercase().contains("feature") {
         "✨ **Feature Request**"
@@ -321,13 +981,54 @@ async fn create_welcome_comment(issue: &IssueData) -> String {
         "🎫 **Issue**"
     };
 
-    format!(
+    // 🧑‍🔧 Maintainers get a short acknowledgement instead of the full welcome
+    if issue.is_from_maintainer() {
+        return with_footer(
+            format!(
+                "## {}\n\n🚢 Thanks! Automation has labelled this issue; nothing else to do here.",
+                issue_type
+            ),
+            footer,
+        );
+    }
+
+    // 🫥 Nudge the author about empty bug report sections
+    let missing_sections = match form.map(|form| form.missing_required()) {
+        Some(missing) if !missing.is_empty() => format!(
+            "\n**📋 A few sections are still empty:**\n{}\n\nFilling these in helps us reproduce the problem much faster!\n",
+            missing
+                .iter()
+                .map(|heading| format!("- {}", heading))
+                .collect::<Vec<_>>()
+                .join("\n")
+        ),
+        _ => String::new(),
+    };
+
+    let review = match window {
+        ResponseWindow::Unscheduled => {
+            "🔍 Our team will review this issue within 24-48 hours".to_string()
+        }
+        ResponseWindow::BusinessHours => {
+            "⚡ The team is around right now, so expect a quick response".to_string()
+        }
+        ResponseWindow::AfterHours(schedule, reopens) => format!(
+            "🌙 You've caught us outside business hours ({}) - we'll respond once we're back on {}",
+            schedule.describe(),
+            schedule
+                .timezone
+                .local(reopens)
+                .format("%A %-d %B at %H:%M")
+        ),
+    };
+
+    let body = format!(
         r#"## {issue_type}
 
 🚢 Ahoy! Thank you for submitting this issue to the Feedbacker project!
-
+{missing_sections}
 **What happens next:**
-- 🔍 Our team will review this issue within 24-48 hours
+- {review}
 - 🏷️ We've automatically applied relevant labels based on the content
 - 🤖 If this is a bug, we'll try to reproduce it and provide a fix
 - ✨ If this is a feature request, we'll evaluate it for inclusion in our roadmap
@@ -342,13 +1043,12 @@ async fn create_welcome_comment(issue: &IssueData) -> String {
 - 🎯 Explain the use case and benefits (for features)
 - 📊 Include environment details when relevant
 
-Thanks for helping make Feedbacker better!
-
-*Aye, aye! 🚢*
-
-*- The Feedbacker Team (Aye & Hue)*"#,
-        issue_type = issue_type
-    )
+Thanks for helping make Feedbacker better! 🚢"#,
+        issue_type = issue_type,
+        missing_sections = missing_sections,
+        review = review
+    );
+    with_footer(body, footer)
 }
 
 /// 🎯 Determine if an issue should be auto-assigned
@@ -391,33 +1091,138 @@ pub struct CreateIssueResponse {
     pub html_url: String,
     pub title: String,
     pub state: String,
+    /// 🏷️ Labels that made it onto the issue
+    pub applied_labels: Vec<String>,
+    /// 🏷️ Why the labels didn't, when they didn't
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub label_error: Option<String>,
+    /// 👤 Assignees GitHub wouldn't take
+    pub failed_assignees: Vec<FailedAssignee>,
+}
+
+/// 🔑 The API key sent as `X-API-Key` or `Authorization: Bearer`
+fn presented_api_key(headers: &HeaderMap) -> Option<&str> {
+    headers
+        .get("X-API-Key")
+        .and_then(|value| value.to_str().ok())
+        .or_else(|| {
+            headers
+                .get(axum::http::header::AUTHORIZATION)
+                .and_then(|value| value.to_str().ok())
+                .and_then(|value| value.strip_prefix("Bearer "))
+        })
+        .map(str::trim)
+}
+
+/// 🚫 A relay refusal in the usual API error shape
+fn relay_refusal(status: StatusCode, error: ErrorCode, message: String) -> Response {
+    (status, Json(ApiResponse::<()>::error(error, message, None))).into_response()
 }
 
-/// 🎫 Create a new issue in a repository (for AI to submit issues)
+/// 🎯 Why `key` may not create issues in `repository` (None = it may)
+async fn repo_restriction(
+    app_state: &AppState,
+    key: &ApiKey,
+    repository: &str,
+) -> anyhow::Result<Option<String>> {
+    match (key.allows_repo(repository), &key.allowed_repos) {
+        (Some(true), _) => Ok(None),
+        (Some(false), Some(repos)) => Ok(Some(format!(
+            "This API key may only create issues in: {}",
+            repos.join(", ")
+        ))),
+        _ => {
+            let registered: bool = sqlx::query_scalar(
+                r#"
+                SELECT EXISTS (
+                    SELECT 1 FROM projects p
+                    JOIN project_repository_routes r ON r.project_id = p.id
+                    WHERE LOWER(r.repository) = LOWER($1) AND p.is_active
+                )
+                "#,
+            )
+            .bind(repository)
+            .fetch_one(&app_state.db_pool)
+            .await?;
+            Ok((!registered).then(|| {
+                format!(
+                    "This API key may only create issues in registered projects, and {} is not one",
+                    repository
+                )
+            }))
+        }
+    }
+}
+
+/// 🎫 Create a new issue in a repository (for AI to submit issues). Needs an API key
+/// with the `issues:write` scope; the key decides which repositories it may target and
+/// how many issues a day it may open, and every issue is logged with the key's id.
 pub async fn create_issue(
     State(app_state): State<AppState>,
-    Json(request): Json<CreateIssueRequest>,
+    headers: HeaderMap,
+    ApiJson(request): ApiJson<CreateIssueRequest>,
 ) -> Response {
+    let pool = &app_state.db_pool;
+    let key = match presented_api_key(&headers) {
+        Some(presented) => match ApiKey::authenticate(pool, presented).await {
+            Ok(key) => key,
+            Err(e) => return crate::api::utils::handle_error(e).into_response(),
+        },
+        None => None,
+    };
+    let Some(key) = key else {
+        return relay_refusal(
+            StatusCode::UNAUTHORIZED,
+            ErrorCode::ApiKeyRequired,
+            "A valid API key (X-API-Key) is required to create issues".to_string(),
+        );
+    };
+    if !key.has_scope(SCOPE_ISSUES_WRITE) {
+        warn!("🚫 API key {} lacks {}", key.key_prefix, SCOPE_ISSUES_WRITE);
+        return relay_refusal(
+            StatusCode::FORBIDDEN,
+            ErrorCode::InsufficientScope,
+            format!("This API key lacks the {} scope", SCOPE_ISSUES_WRITE),
+        );
+    }
+    let repository = format!("{}/{}", request.owner, request.repo);
+    match repo_restriction(&app_state, &key, &repository).await {
+        Ok(None) => {}
+        Ok(Some(restriction)) => {
+            warn!(
+                "🚫 API key {} tried to create an issue in {}",
+                key.key_prefix, repository
+            );
+            return relay_refusal(
+                StatusCode::FORBIDDEN,
+                ErrorCode::RepositoryNotAllowed,
+                restriction,
+            );
+        }
+        Err(e) => return crate::api::utils::handle_error(e).into_response(),
+    }
+    let default_quota = app_state.config.github.issue_relay_daily_quota;
+    match key.reserve_issue(pool, default_quota).await {
+        Ok(true) => {}
+        Ok(false) => {
+            warn!("🚫 API key {} is out of issues for today", key.key_prefix);
+            return relay_refusal(
+                StatusCode::TOO_MANY_REQUESTS,
+                ErrorCode::IssueQuotaExhausted,
+                format!(
+                    "This API key has used its {} issues for today (UTC)",
+                    key.daily_issue_quota.unwrap_or(default_quota as i32)
+                ),
+            );
+        }
+        Err(e) => return crate::api::utils::handle_error(e).into_response(),
+    }
     info!(
-        "🎫 Creating issue '{}' in {}/{}",
-        request.title, request.owner, request.repo
+        "🎫 Creating issue '{}' in {} for API key {}",
+        request.title, repository, key.key_prefix
     );
 
-    let github_client = match GitHubClient::new(&app_state.config.github.token) {
-        Ok(client) => client,
-        Err(e) => {
-            error!("❌ Failed to create GitHub client: {:#}", e);
-            return (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(ApiResponse::<()>::error(
-                    "github_client_error".to_string(),
-                    "Failed to create GitHub client".to_string(),
-                    None,
-                )),
-            )
-                .into_response();
-        }
-    };
+    let github_client = app_state.github.as_ref();
 
     let labels = if request.labels.is_empty() {
         None
@@ -441,32 +1246,56 @@ pub async fn create_issue(
         )
         .await
     {
-        Ok(issue) => {
+        Ok(created) => {
+            let complete = created.is_complete();
+            let issue = created.issue;
             info!(
                 "✅ Issue #{} created in {}/{}",
                 issue.number, request.owner, request.repo
             );
+            if let Some(e) = &created.label_error {
+                warn!("⚠️ Labels not applied to issue #{}: {}", issue.number, e);
+            }
+            for failed in &created.failed_assignees {
+                warn!(
+                    "⚠️ {} not assigned to issue #{}: {}",
+                    failed.login, issue.number, failed.error
+                );
+            }
+            if let Err(e) =
+                automation_log::record_relayed_issue(pool, &repository, issue.number, key.id).await
+            {
+                warn!("⚠️ {:#}", e);
+            }
             let response = CreateIssueResponse {
                 issue_number: issue.number,
-                html_url: issue.html_url.to_string(),
+                html_url: issue.html_url,
                 title: issue.title,
-                state: format!("{:?}", issue.state),
+                state: issue.state,
+                applied_labels: created.applied_labels,
+                label_error: created.label_error,
+                failed_assignees: created.failed_assignees,
+            };
+            let message = if complete {
+                "Issue created successfully"
+            } else {
+                "Issue created, but some labels or assignees could not be applied"
             };
             (
                 StatusCode::CREATED,
-                Json(ApiResponse::success(
-                    "Issue created successfully".to_string(),
-                    response,
-                )),
+                Json(ApiResponse::success(message.to_string(), response)),
             )
                 .into_response()
         }
         Err(e) => {
             error!("❌ Failed to create issue: {:#}", e);
+            if let Err(e) = key.release_issue(pool).await {
+                warn!("⚠️ {:#}", e);
+            }
             (
                 StatusCode::INTERNAL_SERVER_ERROR,
                 Json(ApiResponse::<()>::error(
-                    "issue_creation_failed".to_string(),
+                    ErrorCode::IssueCreationFailed,
                     "Failed to create issue".to_string(),
                     Some(serde_json::json!({ "error": e.to_string() })),
                 )),
@@ -480,23 +1309,9 @@ pub async fn create_issue(
 pub async fn add_issue_comment(
     State(app_state): State<AppState>,
     Path((owner, repo, issue_number)): Path<(String, String, u32)>,
-    Json(comment): Json<serde_json::Value>,
+    ApiJson(comment): ApiJson<serde_json::Value>,
 ) -> Response {
-    let github_client = match GitHubClient::new(&app_state.config.github.token) {
-        Ok(client) => client,
-        Err(e) => {
-            error!("❌ Failed to create GitHub client: {:#}", e);
-            return (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(ApiResponse::<()>::error(
-                    "github_client_error".to_string(),
-                    "Failed to create GitHub client".to_string(),
-                    None,
-                )),
-            )
-                .into_response();
-        }
-    };
+    let github_client = app_state.github.as_ref();
 
     let comment_text = comment
         .get("body")
@@ -522,7 +1337,7 @@ pub async fn add_issue_comment(
             (
                 StatusCode::INTERNAL_SERVER_ERROR,
                 Json(ApiResponse::<()>::error(
-                    "comment_failed".to_string(),
+                    ErrorCode::CommentFailed,
                     "Failed to add comment".to_string(),
                     Some(serde_json::json!({ "error": e.to_string() })),
                 )),
@@ -536,29 +1351,23 @@ pub async fn add_issue_comment(
 pub async fn add_issue_labels(
     State(app_state): State<AppState>,
     Path((owner, repo, issue_number)): Path<(String, String, u32)>,
-    Json(labels): Json<Vec<String>>,
+    ApiJson(labels): ApiJson<Vec<String>>,
 ) -> Response {
-    let github_client = match GitHubClient::new(&app_state.config.github.token) {
-        Ok(client) => client,
-        Err(e) => {
-            return (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(ApiResponse::<()>::error(
-                    "github_client_error".to_string(),
-                    "Failed to create GitHub client".to_string(),
-                    None,
-                )),
-            )
-                .into_response();
-        }
-    };
+    let github_client = app_state.github.as_ref();
 
+    let overrides = automation_config(&app_state, &format!("{}/{}", owner, repo))
+        .await
+        .ok()
+        .flatten()
+        .map(|config| config.labels)
+        .unwrap_or_default();
+    ensure_labels_exist(github_client, &owner, &repo, &labels, &overrides).await;
     match github_client
-        .add_labels_to_issue(&owner, &repo, issue_number, &labels)
+        .add_missing_labels(&owner, &repo, issue_number, &labels, None)
         .await
     {
-        Ok(_) => {
-            info!("✅ Added labels to issue #{}: {:?}", issue_number, labels);
+        Ok(added) => {
+            info!("✅ Added labels to issue #{}: {:?}", issue_number, added);
             (
                 StatusCode::OK,
                 Json(ApiResponse::<()>::success_no_data(
@@ -572,7 +1381,7 @@ pub async fn add_issue_labels(
             (
                 StatusCode::INTERNAL_SERVER_ERROR,
                 Json(ApiResponse::<()>::error(
-                    "labels_failed".to_string(),
+                    ErrorCode::LabelsFailed,
                     "Failed to add labels".to_string(),
                     Some(serde_json::json!({ "error": e.to_string() })),
                 )),
@@ -586,22 +1395,9 @@ pub async fn add_issue_labels(
 pub async fn close_issue_with_comment(
     State(app_state): State<AppState>,
     Path((owner, repo, issue_number)): Path<(String, String, u32)>,
-    Json(payload): Json<serde_json::Value>,
+    ApiJson(payload): ApiJson<serde_json::Value>,
 ) -> Response {
-    let github_client = match GitHubClient::new(&app_state.config.github.token) {
-        Ok(client) => client,
-        Err(e) => {
-            return (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(ApiResponse::<()>::error(
-                    "github_client_error".to_string(),
-                    "Failed to create GitHub client".to_string(),
-                    None,
-                )),
-            )
-                .into_response();
-        }
-    };
+    let github_client = app_state.github.as_ref();
 
     // Add final comment
     if let Some(comment) = payload.get("comment").and_then(|c| c.as_str()) {
@@ -630,7 +1426,7 @@ pub async fn close_issue_with_comment(
             (
                 StatusCode::INTERNAL_SERVER_ERROR,
                 Json(ApiResponse::<()>::error(
-                    "close_failed".to_string(),
+                    ErrorCode::CloseFailed,
                     "Failed to close issue".to_string(),
                     Some(serde_json::json!({ "error": e.to_string() })),
                 )),
@@ -639,3 +1435,1084 @@ pub async fn close_issue_with_comment(
         }
     }
 }
+
+// 🧪 Tests - Making sure the robots greet people properly!
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn issue(title: &str, body: &str) -> IssueData {
+        IssueData {
+            id: 1,
+            number: 42,
+            title: title.to_string(),
+            body: Some(body.to_string()),
+            state: "open".to_string(),
+            html_url: "https://github.com/8b-is/smart-tree/issues/42".to_string(),
+            user: UserData {
+                id: 7,
+                login: "someone".to_string(),
+            },
+            labels: vec![],
+            assignees: vec![],
+            author_association: Some("FIRST_TIME_CONTRIBUTOR".to_string()),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_issue_form_drives_labels_and_welcome_comment() {
+        let issue = issue(
+            "Tree output is empty",
+            "### Steps to reproduce\n\nRun st\n\n### Expected behavior\n\n_No response_\n\n### Actual behavior\n\nNothing",
+        );
+        let form = parse_issue_form(issue.body.as_deref().unwrap());
+
+        let labels = analyze_issue_for_labels(&issue, form.as_ref()).await;
+        assert!(labels.contains(&"bug".to_string()));
+        assert!(labels.contains(&"needs-info".to_string()));
+
+        let comment =
+            create_welcome_comment(&issue, form.as_ref(), None, ResponseWindow::Unscheduled).await;
+        assert!(comment.starts_with("## 🐛 **Bug Report**"));
+        assert!(comment.contains("- Expected behavior"));
+        println!("✅ Issue form labeling test passed!");
+    }
+
+    #[tokio::test]
+    async fn test_free_form_issue_has_no_nudge() {
+        let issue = issue("Add a dark mode", "It would be lovely at night.");
+        let labels = analyze_issue_for_labels(&issue, None).await;
+        assert!(!labels.contains(&"needs-info".to_string()));
+        let comment = create_welcome_comment(&issue, None, None, ResponseWindow::Unscheduled).await;
+        assert!(!comment.contains("still empty"));
+        println!("✅ Free-form issue welcome test passed!");
+    }
+
+    #[tokio::test]
+    async fn test_author_association_picks_welcome_length() {
+        let first_timer = issue("Add a dark mode", "It would be lovely at night.");
+        let long =
+            create_welcome_comment(&first_timer, None, None, ResponseWindow::Unscheduled).await;
+        assert!(long.contains("What happens next"));
+
+        let mut member = issue("Add a dark mode", "It would be lovely at night.");
+        member.author_association = Some("MEMBER".to_string());
+        let short = create_welcome_comment(&member, None, None, ResponseWindow::Unscheduled).await;
+        assert!(!short.contains("What happens next"));
+        assert!(short.len() < long.len());
+
+        // 🧑‍🔧 ...and maintainers aren't labelled needs-info for empty sections
+        member.body = Some("### Steps to reproduce\n\n_No response_".to_string());
+        let form = parse_issue_form(member.body.as_deref().unwrap());
+        let labels = analyze_issue_for_labels(&member, form.as_ref()).await;
+        assert!(!labels.contains(&"needs-info".to_string()));
+        println!("✅ Author association welcome test passed!");
+    }
+
+    #[tokio::test]
+    async fn test_every_maintainer_association_is_exempt() {
+        let form_body = "### Steps to reproduce\n\n_No response_";
+        for association in ["OWNER", "MEMBER", "COLLABORATOR"] {
+            let mut maintainer = issue("Tree output is empty", form_body);
+            maintainer.author_association = Some(association.to_string());
+            assert!(maintainer.is_from_maintainer(), "{}", association);
+
+            let form = parse_issue_form(form_body);
+            let labels = analyze_issue_for_labels(&maintainer, form.as_ref()).await;
+            assert!(
+                !labels.contains(&"needs-info".to_string()),
+                "{}",
+                association
+            );
+            let welcome =
+                create_welcome_comment(&maintainer, None, None, ResponseWindow::Unscheduled).await;
+            assert!(!welcome.contains("What happens next"), "{}", association);
+        }
+        for association in ["CONTRIBUTOR", "FIRST_TIME_CONTRIBUTOR", "NONE"] {
+            let mut outsider = issue("Tree output is empty", form_body);
+            outsider.author_association = Some(association.to_string());
+            assert!(!outsider.is_from_maintainer(), "{}", association);
+        }
+        println!("✅ Maintainer association exemption test passed!");
+    }
+
+    #[tokio::test]
+    async fn test_comment_footer_is_appended_or_left_off() {
+        let first_timer = issue("Add a dark mode", "It would be lovely at night.");
+        let branded = create_welcome_comment(
+            &first_timer,
+            None,
+            Some("— Acme Bot"),
+            ResponseWindow::Unscheduled,
+        )
+        .await;
+        assert!(branded.ends_with("\n\n— Acme Bot"));
+        let plain =
+            create_welcome_comment(&first_timer, None, None, ResponseWindow::Unscheduled).await;
+        assert!(!plain.contains("Aye & Hue"));
+
+        let mut member = issue("Add a dark mode", "It would be lovely at night.");
+        member.author_association = Some("OWNER".to_string());
+        let short = create_welcome_comment(
+            &member,
+            None,
+            Some("— Acme Bot"),
+            ResponseWindow::Unscheduled,
+        )
+        .await;
+        assert!(short.ends_with("\n\n— Acme Bot"));
+        println!("✅ Comment footer test passed!");
+    }
+
+    #[tokio::test]
+    async fn test_schedule_picks_the_welcome_variant() {
+        use chrono::TimeZone;
+
+        let config = ProjectConfig::from_value(serde_json::json!({
+            "schedule": { "timezone": "-05:00", "start": "09:00", "end": "17:00" },
+        }))
+        .unwrap();
+        // 🕘 Wednesday 2026-10-14: 15:00 UTC is 10:00 local, 23:00 UTC is 18:00 local
+        let noon = chrono::Utc
+            .with_ymd_and_hms(2026, 10, 14, 15, 0, 0)
+            .unwrap();
+        let evening = chrono::Utc
+            .with_ymd_and_hms(2026, 10, 14, 23, 0, 0)
+            .unwrap();
+        assert_eq!(response_window(None, noon), ResponseWindow::Unscheduled);
+        assert_eq!(
+            response_window(Some(&ProjectConfig::default()), noon),
+            ResponseWindow::Unscheduled
+        );
+        assert_eq!(
+            response_window(Some(&config), noon),
+            ResponseWindow::BusinessHours
+        );
+        let after_hours = response_window(Some(&config), evening);
+        // 🌅 Back Thursday 09:00 local, 14:00 UTC
+        assert_eq!(
+            after_hours,
+            ResponseWindow::AfterHours(
+                config.schedule.as_ref().unwrap(),
+                chrono::Utc
+                    .with_ymd_and_hms(2026, 10, 15, 14, 0, 0)
+                    .unwrap()
+            )
+        );
+
+        let first_timer = issue("Add a dark mode", "It would be lovely at night.");
+        let always_on =
+            create_welcome_comment(&first_timer, None, None, ResponseWindow::Unscheduled).await;
+        assert!(always_on.contains("within 24-48 hours"));
+        let quick =
+            create_welcome_comment(&first_timer, None, None, ResponseWindow::BusinessHours).await;
+        assert!(quick.contains("expect a quick response"));
+        assert!(!quick.contains("24-48 hours"));
+        let later = create_welcome_comment(&first_timer, None, None, after_hours).await;
+        assert!(later.contains("once we're back on Thursday 15 October at 09:00"));
+        assert!(later.contains("09:00–17:00 UTC-05:00, Mon, Tue, Wed, Thu, Fri"));
+        println!("✅ Business hours welcome test passed!");
+    }
+
+    #[tokio::test]
+    async fn test_project_comment_footer_overrides_global() {
+        use crate::test_support::spawn_test_app;
+
+        let Some(app) = spawn_test_app().await else {
+            return;
+        };
+        let owner_id: uuid::Uuid = sqlx::query_scalar(
+            "INSERT INTO users (email, name, password_hash) VALUES ('footer@example.com', 'Footer', 'x') RETURNING id",
+        )
+        .fetch_one(&app.db_pool)
+        .await
+        .unwrap();
+        for (repository, config) in [
+            (
+                "acme/branded",
+                serde_json::json!({ "comment_footer": "— Acme Bot" }),
+            ),
+            (
+                "acme/unbranded",
+                serde_json::json!({ "comment_footer": null }),
+            ),
+            ("acme/default", serde_json::json!({})),
+        ] {
+            sqlx::query("INSERT INTO projects (owner_id, repository, config) VALUES ($1, $2, $3)")
+                .bind(owner_id)
+                .bind(repository)
+                .bind(config)
+                .execute(&app.db_pool)
+                .await
+                .unwrap();
+        }
+
+        let global = app.app_state.config.github.comment_footer.clone();
+        let footer = |repository: &'static str| async {
+            let config = automation_config(&app.app_state, repository).await.unwrap();
+            comment_footer(&app.app_state, config.as_ref())
+        };
+        assert_eq!(footer("acme/branded").await.as_deref(), Some("— Acme Bot"));
+        assert_eq!(footer("acme/unbranded").await, None);
+        assert_eq!(footer("acme/default").await, global);
+        assert_eq!(footer("someone/else").await, global);
+        println!("✅ Project comment footer test passed!");
+    }
+
+    #[tokio::test]
+    async fn test_automation_skips_projects_with_a_newer_config() {
+        use crate::database::project_config::CURRENT_CONFIG_VERSION;
+        use crate::test_support::spawn_test_app;
+
+        let Some(app) = spawn_test_app().await else {
+            return;
+        };
+        let owner_id: uuid::Uuid = sqlx::query_scalar(
+            "INSERT INTO users (email, name, password_hash) VALUES ('future@example.com', 'Future', 'x') RETURNING id",
+        )
+        .fetch_one(&app.db_pool)
+        .await
+        .unwrap();
+        sqlx::query("INSERT INTO projects (owner_id, repository, config) VALUES ($1, '8b-is/smart-tree', $2)")
+            .bind(owner_id)
+            .bind(serde_json::json!({ "config_version": CURRENT_CONFIG_VERSION + 1 }))
+            .execute(&app.db_pool)
+            .await
+            .unwrap();
+
+        let payload: IssueWebhookPayload = serde_json::from_value(serde_json::json!({
+            "action": "opened",
+            "issue": {
+                "id": 1, "number": 42, "title": "Tree output is empty", "body": "Nothing shows up",
+                "state": "open", "html_url": "https://github.com/8b-is/smart-tree/issues/42",
+                "user": { "id": 7, "login": "someone" }, "labels": [], "assignees": []
+            },
+            "repository": {
+                "id": 2, "name": "smart-tree", "full_name": "8b-is/smart-tree",
+                "owner": { "id": 3, "login": "8b-is" }
+            },
+            "sender": { "id": 7, "login": "someone" }
+        }))
+        .unwrap();
+        let response = process_issue_event(&app.app_state, &payload, &mut Vec::new())
+            .await
+            .unwrap();
+        assert_eq!(response.action_taken, "skipped_config_too_new");
+        assert!(app.github.calls().is_empty());
+        println!("✅ Too-new config automation guard test passed!");
+    }
+
+    #[test]
+    fn test_old_payloads_without_new_fields_still_parse() {
+        let label: LabelData =
+            serde_json::from_value(serde_json::json!({ "name": "bug", "color": "d73a4a" }))
+                .unwrap();
+        assert_eq!(label.description, None);
+        assert!(!label.default);
+
+        let rich: LabelData = serde_json::from_value(serde_json::json!({
+            "name": "bug", "color": "d73a4a",
+            "description": "Something isn't working", "default": true
+        }))
+        .unwrap();
+        assert_eq!(rich.description.as_deref(), Some("Something isn't working"));
+        assert!(rich.default);
+        println!("✅ Backwards-compatible payload test passed!");
+    }
+
+    #[tokio::test]
+    async fn test_issue_opened_webhook_uses_github_ops() {
+        use crate::test_support::{spawn_test_app, GitHubCall};
+
+        let Some(app) = spawn_test_app().await else {
+            return;
+        };
+        let owner_id: uuid::Uuid = sqlx::query_scalar(
+            "INSERT INTO users (email, name, password_hash) VALUES ('hooks@example.com', 'Hooks', 'x') RETURNING id",
+        )
+        .fetch_one(&app.db_pool)
+        .await
+        .unwrap();
+        sqlx::query("INSERT INTO projects (owner_id, repository) VALUES ($1, '8b-is/smart-tree')")
+            .bind(owner_id)
+            .execute(&app.db_pool)
+            .await
+            .unwrap();
+
+        let response = app
+            .client
+            .post(app.url("/api/webhook/issues"))
+            .json(&serde_json::json!({
+                "action": "opened",
+                "issue": {
+                    "id": 1,
+                    "number": 42,
+                    "title": "Tree output is empty",
+                    "body": "### Steps to reproduce\n\nRun st\n\n### Expected behavior\n\n_No response_",
+                    "state": "open",
+                    "html_url": "https://github.com/8b-is/smart-tree/issues/42",
+                    "user": { "id": 7, "login": "someone" },
+                    "labels": [],
+                    "assignees": [],
+                    "author_association": "FIRST_TIME_CONTRIBUTOR"
+                },
+                "repository": {
+                    "id": 2,
+                    "name": "smart-tree",
+                    "full_name": "8b-is/smart-tree",
+                    "owner": { "id": 3, "login": "8b-is" }
+                },
+                "sender": { "id": 7, "login": "someone" }
+            }))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        // 📼 Exactly one label call and one welcome comment, nothing else
+        let calls = app.github.calls();
+        assert_eq!(calls.len(), 2, "{:?}", calls);
+        assert_eq!(
+            calls[0],
+            GitHubCall::Labels {
+                repo: "8b-is/smart-tree".to_string(),
+                issue_number: 42,
+                labels: vec!["bug".to_string(), "needs-info".to_string()],
+            }
+        );
+        // 🎨 ... after making sure both labels exist, in our colors
+        let ensured = app.github.ensured_labels.lock().unwrap().clone();
+        assert_eq!(ensured.len(), 1);
+        assert_eq!(ensured[0].0, "8b-is/smart-tree");
+        assert_eq!(
+            ensured[0]
+                .1
+                .iter()
+                .map(|spec| (spec.name.as_str(), spec.color.as_str()))
+                .collect::<Vec<_>>(),
+            vec![("bug", "d73a4a"), ("needs-info", "e4e669")]
+        );
+        match &calls[1] {
+            GitHubCall::Comment {
+                issue_number, body, ..
+            } => {
+                assert_eq!(*issue_number, 42);
+                assert!(body.contains("- Expected behavior"));
+            }
+            other => panic!("expected a comment, got {:?}", other),
+        }
+
+        // 🗄️ The parsed form landed in the stored webhook payload
+        let stored: serde_json::Value =
+            sqlx::query_scalar("SELECT payload FROM webhooks WHERE event_type = 'issues.opened'")
+                .fetch_one(&app.db_pool)
+                .await
+                .unwrap();
+        assert_eq!(
+            stored["issue_form"]["sections"][0]["key"],
+            "steps_to_reproduce"
+        );
+        println!("✅ Issue webhook integration test passed!");
+    }
+
+    #[tokio::test]
+    async fn test_labels_already_on_the_issue_are_not_sent_again() {
+        use crate::database::models::User;
+        use crate::middleware::auth::jwt_utils;
+        use crate::test_support::{spawn_test_app, GitHubCall};
+
+        let Some(app) = spawn_test_app().await else {
+            return;
+        };
+
+        // 🔁 A redelivered "opened" already carries the labels we'd add
+        let response = app
+            .client
+            .post(app.url("/api/webhook/issues"))
+            .json(&serde_json::json!({
+                "action": "opened",
+                "issue": {
+                    "id": 1,
+                    "number": 42,
+                    "title": "Tree output is empty",
+                    "body": "### Steps to reproduce\n\nRun st\n\n### Expected behavior\n\n_No response_",
+                    "state": "open",
+                    "html_url": "https://github.com/8b-is/smart-tree/issues/42",
+                    "user": { "id": 7, "login": "someone" },
+                    "labels": [
+                        { "name": "Bug", "color": "d73a4a" },
+                        { "name": "needs-info", "color": "e4e669" }
+                    ],
+                    "assignees": [],
+                    "author_association": "FIRST_TIME_CONTRIBUTOR"
+                },
+                "repository": {
+                    "id": 2,
+                    "name": "smart-tree",
+                    "full_name": "8b-is/smart-tree",
+                    "owner": { "id": 3, "login": "8b-is" }
+                },
+                "sender": { "id": 7, "login": "someone" }
+            }))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let calls = app.github.calls();
+        assert!(
+            matches!(calls[..], [GitHubCall::Comment { .. }]),
+            "{:?}",
+            calls
+        );
+
+        // 🔧 The manual endpoint reads the issue's labels and sends only the delta
+        let user: User = sqlx::query_as(
+            "INSERT INTO users (email, name, password_hash) VALUES ('labels@example.com', 'Labels', 'x') RETURNING *",
+        )
+        .fetch_one(&app.db_pool)
+        .await
+        .unwrap();
+        let token =
+            jwt_utils::create_jwt_token(&user, &app.app_state.config.auth.jwt_secret, 1).unwrap();
+        let label = |labels: &[&str]| {
+            let request = app
+                .client
+                .post(app.url("/api/issues/8b-is/smart-tree/7/labels"))
+                .bearer_auth(&token)
+                .json(&labels);
+            async move { request.send().await.unwrap().status() }
+        };
+        let sent = || {
+            app.github
+                .calls()
+                .into_iter()
+                .filter_map(|call| match call {
+                    GitHubCall::Labels { labels, .. } => Some(labels),
+                    _ => None,
+                })
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(label(&["bug"]).await, StatusCode::OK);
+        assert_eq!(label(&["bug"]).await, StatusCode::OK);
+        assert_eq!(sent(), vec![vec!["bug".to_string()]]);
+        assert_eq!(label(&["BUG", "question"]).await, StatusCode::OK);
+        assert_eq!(
+            sent(),
+            vec![vec!["bug".to_string()], vec!["question".to_string()]]
+        );
+        // 🎨 Each request made sure its labels exist first, in our colors
+        let ensured = app.github.ensured_labels.lock().unwrap().clone();
+        assert_eq!(
+            ensured.last().unwrap().1,
+            LabelSpec::for_names(
+                &["BUG".to_string(), "question".to_string()],
+                &BTreeMap::new()
+            )
+        );
+        println!("✅ Idempotent labels test passed!");
+    }
+
+    #[tokio::test]
+    async fn test_needs_info_reminder_is_minimized_once_the_author_replies() {
+        use crate::github::ops::MinimizeReason;
+        use crate::test_support::{spawn_test_app, GitHubCall};
+
+        let Some(app) = spawn_test_app().await else {
+            return;
+        };
+        let event = |action: &str, extra: serde_json::Value| {
+            let mut event = serde_json::json!({
+                "action": action,
+                "issue": {
+                    "id": 1, "number": 42, "title": "Tree output is empty", "body": "It broke",
+                    "state": "open", "html_url": "https://github.com/8b-is/smart-tree/issues/42",
+                    "user": { "id": 7, "login": "someone" }, "labels": [], "assignees": []
+                },
+                "repository": {
+                    "id": 2, "name": "smart-tree", "full_name": "8b-is/smart-tree",
+                    "owner": { "id": 3, "login": "8b-is" }
+                },
+                "sender": { "id": 3, "login": "8b-is" }
+            });
+            event
+                .as_object_mut()
+                .unwrap()
+                .extend(extra.as_object().unwrap().clone());
+            serde_json::from_value::<IssueWebhookPayload>(event).unwrap()
+        };
+        let process = |payload: IssueWebhookPayload| {
+            let app_state = app.app_state.clone();
+            async move {
+                process_issue_event(&app_state, &payload, &mut Vec::new())
+                    .await
+                    .unwrap()
+            }
+        };
+        let labeled = || {
+            event(
+                "labeled",
+                serde_json::json!({ "label": {
+                    "name": "needs-info", "color": "d876e3",
+                    "description": "Waiting on the reporter"
+                } }),
+            )
+        };
+        let comment_by = |login: &str| {
+            event(
+                "created",
+                serde_json::json!({ "comment": { "id": 900, "user": { "id": 8, "login": login } } }),
+            )
+        };
+
+        // 💬 The welcome comment is logged with its ids
+        process(event("opened", serde_json::json!({}))).await;
+        let logged: Vec<(String, i64, String)> = sqlx::query_as(
+            "SELECT step, comment_id, comment_node_id FROM automation_log ORDER BY created_at",
+        )
+        .fetch_all(&app.db_pool)
+        .await
+        .unwrap();
+        assert_eq!(logged.len(), 1);
+        assert_eq!(logged[0].0, "welcome_comment");
+
+        // 🤔 needs-info posts one reminder, however often it's re-applied
+        let response = process(labeled()).await;
+        let added = response.comment_added.unwrap();
+        assert!(added.contains(
+            "\n\n@someone we need a little more information before we can move this forward. \
+             Could you reply"
+        ));
+        assert!(!added.lines().any(|line| line.starts_with(' ')));
+        assert!(added.contains("\n\nThis reminder tidies itself away once you respond."));
+        assert!(added.contains("Waiting on the reporter"));
+        assert!(process(labeled()).await.comment_added.is_none());
+        let reminder = automation_log::open_comments(
+            &app.db_pool,
+            "8b-is/smart-tree",
+            42,
+            AutomationStep::NeedsInfoReminder.as_str(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(reminder.len(), 1);
+
+        // 🙉 Someone else chiming in changes nothing
+        let calls_before = app.github.calls().len();
+        process(comment_by("bystander")).await;
+        assert_eq!(app.github.calls().len(), calls_before);
+
+        // 🙈 The author replying minimizes the reminder, once
+        process(comment_by("SomeOne")).await;
+        process(comment_by("someone")).await;
+        let minimized: Vec<GitHubCall> = app
+            .github
+            .calls()
+            .into_iter()
+            .filter(|call| matches!(call, GitHubCall::Minimize { .. }))
+            .collect();
+        assert_eq!(
+            minimized,
+            vec![GitHubCall::Minimize {
+                node_id: reminder[0].comment_node_id.clone(),
+                reason: MinimizeReason::Outdated,
+            }]
+        );
+        let cleanup: Option<String> =
+            sqlx::query_scalar("SELECT cleanup FROM automation_log WHERE id = $1")
+                .bind(reminder[0].id)
+                .fetch_one(&app.db_pool)
+                .await
+                .unwrap();
+        assert_eq!(cleanup.as_deref(), Some("minimized"));
+        println!("✅ Needs-info reminder cleanup test passed!");
+    }
+
+    #[tokio::test]
+    async fn test_needs_info_on_a_maintainers_issue_posts_no_reminder() {
+        use crate::test_support::spawn_test_app;
+
+        let Some(app) = spawn_test_app().await else {
+            return;
+        };
+        for (number, association) in [(51, "OWNER"), (52, "MEMBER"), (53, "COLLABORATOR")] {
+            let payload: IssueWebhookPayload = serde_json::from_value(serde_json::json!({
+                "action": "labeled",
+                "issue": {
+                    "id": number, "number": number, "title": "Tree output is empty",
+                    "body": "It broke", "state": "open",
+                    "html_url": format!("https://github.com/8b-is/smart-tree/issues/{}", number),
+                    "user": { "id": 3, "login": "8b-is" }, "labels": [], "assignees": [],
+                    "author_association": association
+                },
+                "repository": {
+                    "id": 2, "name": "smart-tree", "full_name": "8b-is/smart-tree",
+                    "owner": { "id": 3, "login": "8b-is" }
+                },
+                "sender": { "id": 3, "login": "8b-is" },
+                "label": { "name": "needs-info", "color": "d876e3" }
+            }))
+            .unwrap();
+            let response = process_issue_event(&app.app_state, &payload, &mut Vec::new())
+                .await
+                .unwrap();
+            assert!(response.comment_added.is_none(), "{}", association);
+        }
+        assert!(app.github.calls().is_empty());
+        println!("✅ Maintainer needs-info exemption test passed!");
+    }
+
+    #[tokio::test]
+    async fn test_comments_inside_the_cooldown_are_consolidated_or_suppressed() {
+        use crate::config::CommentCooldownMode;
+        use crate::test_support::{spawn_test_app_with_config, GitHubCall, TestApp};
+
+        let event = |action: &str, extra: serde_json::Value| {
+            let mut event = serde_json::json!({
+                "action": action,
+                "issue": {
+                    "id": 1, "number": 42, "title": "Tree output is empty", "body": "It broke",
+                    "state": "open", "html_url": "https://github.com/8b-is/smart-tree/issues/42",
+                    "user": { "id": 7, "login": "someone" }, "labels": [], "assignees": []
+                },
+                "repository": {
+                    "id": 2, "name": "smart-tree", "full_name": "8b-is/smart-tree",
+                    "owner": { "id": 3, "login": "8b-is" }
+                },
+                "sender": { "id": 3, "login": "8b-is" }
+            });
+            event
+                .as_object_mut()
+                .unwrap()
+                .extend(extra.as_object().unwrap().clone());
+            serde_json::from_value::<IssueWebhookPayload>(event).unwrap()
+        };
+        let labeled = || {
+            event(
+                "labeled",
+                serde_json::json!({ "label": { "name": "needs-info", "color": "d876e3" } }),
+            )
+        };
+        let reply = || {
+            event(
+                "created",
+                serde_json::json!({ "comment": { "id": 900, "user": { "id": 7, "login": "someone" } } }),
+            )
+        };
+        let process = |app: &TestApp, payload: IssueWebhookPayload| {
+            let app_state = app.app_state.clone();
+            async move {
+                process_issue_event(&app_state, &payload, &mut Vec::new())
+                    .await
+                    .unwrap()
+            }
+        };
+        let comment_calls = |app: &TestApp| -> Vec<GitHubCall> {
+            app.github
+                .calls()
+                .into_iter()
+                .filter(|call| {
+                    matches!(
+                        call,
+                        GitHubCall::Comment { .. }
+                            | GitHubCall::EditComment { .. }
+                            | GitHubCall::Minimize { .. }
+                    )
+                })
+                .collect()
+        };
+
+        // 🧺 Consolidate: the reminder becomes a second section of the welcome comment
+        let Some(app) = spawn_test_app_with_config(|config| {
+            config.github.comment_cooldown_seconds = 600;
+            config.github.comment_cooldown_mode = CommentCooldownMode::Consolidate;
+        })
+        .await
+        else {
+            return;
+        };
+        let footer = app.app_state.config.github.comment_footer.clone();
+        let welcome = process(&app, event("opened", serde_json::json!({})))
+            .await
+            .comment_added
+            .unwrap();
+        let consolidated = process(&app, labeled()).await.comment_added.unwrap();
+        let calls = comment_calls(&app);
+        assert_eq!(calls.len(), 2);
+        let GitHubCall::EditComment {
+            comment_id, body, ..
+        } = &calls[1]
+        else {
+            panic!(
+                "expected the welcome comment to be edited, got {:?}",
+                calls[1]
+            );
+        };
+        assert_eq!(body, &consolidated);
+        let welcome_section = match footer.as_deref() {
+            Some(footer) => welcome.strip_suffix(&format!("\n\n{}", footer)).unwrap(),
+            None => welcome.as_str(),
+        };
+        assert!(consolidated.starts_with(&format!("{}\n\n---\n\n", welcome_section)));
+        assert!(consolidated.contains("@someone"));
+        let logged: Vec<(String, i64)> =
+            sqlx::query_as("SELECT step, comment_id FROM automation_log ORDER BY created_at")
+                .fetch_all(&app.db_pool)
+                .await
+                .unwrap();
+        assert_eq!(
+            logged,
+            vec![
+                ("welcome_comment".to_string(), *comment_id as i64),
+                ("needs_info_reminder".to_string(), *comment_id as i64),
+            ]
+        );
+
+        // ✂️ The author's reply cuts the reminder back out instead of hiding everything
+        process(&app, reply()).await;
+        let calls = comment_calls(&app);
+        assert_eq!(
+            calls[2],
+            GitHubCall::EditComment {
+                repo: "8b-is/smart-tree".to_string(),
+                comment_id: *comment_id,
+                body: welcome.clone(),
+            }
+        );
+        assert_eq!(calls.len(), 3);
+        let cleanup: Option<String> = sqlx::query_scalar(
+            "SELECT cleanup FROM automation_log WHERE step = 'needs_info_reminder'",
+        )
+        .fetch_one(&app.db_pool)
+        .await
+        .unwrap();
+        assert_eq!(cleanup.as_deref(), Some("deleted"));
+
+        // 🔇 Suppress: the reminder is not posted at all
+        let Some(app) = spawn_test_app_with_config(|config| {
+            config.github.comment_cooldown_seconds = 600;
+            config.github.comment_cooldown_mode = CommentCooldownMode::Suppress;
+        })
+        .await
+        else {
+            return;
+        };
+        process(&app, event("opened", serde_json::json!({}))).await;
+        assert!(process(&app, labeled()).await.comment_added.is_none());
+        assert_eq!(comment_calls(&app).len(), 1);
+
+        // 🔒 Two steps racing on a fresh issue take turns: the second sees the first
+        let mut racing = labeled();
+        racing.issue.number = 43;
+        let post = |step: AutomationStep, section: &str| {
+            post_bot_comment(&app.app_state, &racing, step, section.to_string(), None)
+        };
+        let (welcome, reminder) = tokio::join!(
+            post(AutomationStep::WelcomeComment, "Welcome!"),
+            post(AutomationStep::NeedsInfoReminder, "Could you add more?"),
+        );
+        let posted = [welcome.unwrap(), reminder.unwrap()];
+        assert_eq!(posted.iter().flatten().count(), 1);
+        assert_eq!(comment_calls(&app).len(), 2);
+        let logged: i64 =
+            sqlx::query_scalar("SELECT COUNT(*) FROM automation_log WHERE issue_number = 43")
+                .fetch_one(&app.db_pool)
+                .await
+                .unwrap();
+        assert_eq!(logged, 1);
+        println!("✅ Comment cooldown test passed!");
+    }
+
+    #[tokio::test]
+    async fn test_throttled_automation_is_deferred_to_the_job_queue() {
+        use crate::github::throttle::WriteThrottle;
+        use crate::test_support::{spawn_test_app, FakeClock, GitHubCall};
+        use std::{sync::Arc, time::Duration};
+
+        let Some(app) = spawn_test_app().await else {
+            return;
+        };
+        // 🚰 One write a minute, and never hold a webhook up for more than 10s
+        let clock = Arc::new(FakeClock::default());
+        let throttle = Arc::new(WriteThrottle::with_clock(
+            1,
+            Duration::from_secs(10),
+            clock.clone(),
+        ));
+        *app.github.write_throttle.lock().unwrap() = Some(throttle.clone());
+
+        let response = app
+            .client
+            .post(app.url("/api/webhook/issues"))
+            .json(&serde_json::json!({
+                "action": "opened",
+                "issue": {
+                    "id": 1,
+                    "number": 7,
+                    "title": "Crash on empty directories",
+                    "body": "Run st in an empty folder",
+                    "state": "open",
+                    "html_url": "https://github.com/8b-is/smart-tree/issues/7",
+                    "user": { "id": 7, "login": "someone" },
+                    "labels": [],
+                    "assignees": []
+                },
+                "repository": {
+                    "id": 2,
+                    "name": "smart-tree",
+                    "full_name": "8b-is/smart-tree",
+                    "owner": { "id": 3, "login": "8b-is" }
+                },
+                "sender": { "id": 7, "login": "someone" }
+            }))
+            .send()
+            .await
+            .unwrap();
+
+        // ⏳ Labels went out; the welcome comment would have waited a minute, so it's queued
+        assert_eq!(response.status(), StatusCode::ACCEPTED);
+        let body: serde_json::Value = response.json().await.unwrap();
+        assert_eq!(
+            body["data"]["completed_steps"],
+            serde_json::json!(["labels"])
+        );
+        assert_eq!(body["data"]["retry_after_seconds"], 60);
+        assert!(matches!(
+            app.github.calls()[..],
+            [GitHubCall::Labels { .. }]
+        ));
+        assert_eq!(throttle.deferred_total(), 1);
+
+        // 🕰️ Not due yet
+        let registry = crate::jobs::JobRegistry::builtin();
+        assert_eq!(registry.run_due_jobs(&app.app_state).await.unwrap(), 0);
+        let make_due = || async {
+            sqlx::query("UPDATE background_jobs SET scheduled_at = NOW() WHERE status = 'pending'")
+                .execute(&app.db_pool)
+                .await
+                .unwrap();
+        };
+
+        // 🔁 Run while the bucket is still empty: it defers itself again, progress kept
+        make_due().await;
+        assert_eq!(registry.run_due_jobs(&app.app_state).await.unwrap(), 1);
+        let requeued: serde_json::Value = sqlx::query_scalar(
+            "SELECT payload FROM background_jobs WHERE status = 'pending' AND job_type = 'issue_automation'",
+        )
+        .fetch_one(&app.db_pool)
+        .await
+        .unwrap();
+        assert_eq!(requeued["done"], serde_json::json!(["labels"]));
+        assert_eq!(requeued["deferrals"], 1);
+        assert_eq!(app.github.calls().len(), 1);
+
+        // 💧 A minute later the comment goes out - and the labels aren't repeated
+        clock.advance(Duration::from_secs(60));
+        make_due().await;
+        assert_eq!(registry.run_due_jobs(&app.app_state).await.unwrap(), 1);
+        let calls = app.github.calls();
+        assert_eq!(calls.len(), 2, "{:?}", calls);
+        assert!(matches!(
+            calls[1],
+            GitHubCall::Comment {
+                issue_number: 7,
+                ..
+            }
+        ));
+        let pending: i64 =
+            sqlx::query_scalar("SELECT COUNT(*) FROM background_jobs WHERE status <> 'completed'")
+                .fetch_one(&app.db_pool)
+                .await
+                .unwrap();
+        assert_eq!(pending, 0);
+        println!("✅ Throttled automation deferral test passed!");
+    }
+
+    #[tokio::test]
+    async fn test_issue_relay_needs_a_scoped_key_within_its_repos_and_quota() {
+        use crate::test_support::{spawn_test_app_with_config, GitHubCall};
+
+        let Some(app) =
+            spawn_test_app_with_config(|config| config.github.issue_relay_daily_quota = 2).await
+        else {
+            return;
+        };
+        let pool = &app.db_pool;
+        let owner_id: uuid::Uuid = sqlx::query_scalar(
+            "INSERT INTO users (email, name, password_hash) VALUES ('owner@example.com', 'Owner', 'x') RETURNING id",
+        )
+        .fetch_one(pool)
+        .await
+        .unwrap();
+        sqlx::query("INSERT INTO projects (owner_id, repository) VALUES ($1, '8b-is/smart-tree')")
+            .bind(owner_id)
+            .execute(pool)
+            .await
+            .unwrap();
+        let issues_write = vec![SCOPE_ISSUES_WRITE.to_string()];
+        let (project_key, project_secret) =
+            ApiKey::create(pool, "projects", &issues_write, None, None)
+                .await
+                .unwrap();
+        let listed = vec!["8b-is/mem8".to_string()];
+        let (_, listed_secret) = ApiKey::create(pool, "listed", &issues_write, Some(&listed), None)
+            .await
+            .unwrap();
+        let (_, scopeless_secret) = ApiKey::create(pool, "scopeless", &[], None, None)
+            .await
+            .unwrap();
+        let create = |key: Option<&str>, owner: &str, repo: &str| {
+            let mut request = app
+                .client
+                .post(app.url("/api/issues"))
+                .json(&serde_json::json!({
+                    "owner": owner,
+                    "repo": repo,
+                    "title": "Tree output is empty",
+                    "body": "Reported by an assistant",
+                }));
+            if let Some(key) = key {
+                request = request.header("X-API-Key", key);
+            }
+            async move {
+                let response = request.send().await.unwrap();
+                let status = response.status();
+                let body: serde_json::Value = response.json().await.unwrap();
+                (status, body)
+            }
+        };
+
+        // 🔑 No key, or a key without the scope
+        let (status, _) = create(None, "8b-is", "smart-tree").await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+        let (status, _) = create(Some("fbk_made_up"), "8b-is", "smart-tree").await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+        let (status, body) = create(Some(&scopeless_secret), "8b-is", "smart-tree").await;
+        assert_eq!(status, StatusCode::FORBIDDEN);
+        assert_eq!(body["error"]["code"], "insufficient_scope");
+
+        // 🎯 Registered projects only, or exactly the key's own list
+        let (status, body) = create(Some(&project_secret), "8b-is", "secret-repo").await;
+        assert_eq!(status, StatusCode::FORBIDDEN);
+        assert!(body["error"]["message"]
+            .as_str()
+            .unwrap()
+            .contains("registered projects"));
+        let (status, body) = create(Some(&listed_secret), "8b-is", "smart-tree").await;
+        assert_eq!(status, StatusCode::FORBIDDEN);
+        assert!(body["error"]["message"]
+            .as_str()
+            .unwrap()
+            .contains("8b-is/mem8"));
+        let (status, _) = create(Some(&listed_secret), "8b-is", "mem8").await;
+        assert_eq!(status, StatusCode::CREATED);
+
+        // 📏 Two a day per key
+        for _ in 0..2 {
+            let (status, _) = create(Some(&project_secret), "8b-is", "smart-tree").await;
+            assert_eq!(status, StatusCode::CREATED);
+        }
+        let (status, body) = create(Some(&project_secret), "8b-is", "smart-tree").await;
+        assert_eq!(status, StatusCode::TOO_MANY_REQUESTS);
+        assert_eq!(body["error"]["code"], "issue_quota_exhausted");
+        assert_eq!(
+            app.github
+                .calls()
+                .iter()
+                .filter(|call| matches!(call, GitHubCall::CreateIssue { .. }))
+                .count(),
+            3
+        );
+
+        // 🧾 Every issue is attributed to its key
+        let logged: Vec<(String, i32, Option<uuid::Uuid>)> = sqlx::query_as(
+            "SELECT repository, issue_number, api_key_id FROM automation_log WHERE step = $1 ORDER BY created_at",
+        )
+        .bind(automation_log::RELAYED_ISSUE_STEP)
+        .fetch_all(pool)
+        .await
+        .unwrap();
+        assert_eq!(logged.len(), 3);
+        assert_eq!(
+            logged[1],
+            ("8b-is/smart-tree".to_string(), 2, Some(project_key.id))
+        );
+        println!("✅ Issue relay permissions test passed!");
+    }
+
+    #[tokio::test]
+    async fn test_relayed_issues_report_assignees_that_could_not_be_applied() {
+        use crate::test_support::{spawn_test_app, GitHubCall};
+
+        let Some(app) = spawn_test_app().await else {
+            return;
+        };
+        let pool = &app.db_pool;
+        let issues_write = vec![SCOPE_ISSUES_WRITE.to_string()];
+        let repos = vec!["8b-is/mem8".to_string()];
+        let (_, secret) = ApiKey::create(pool, "relay", &issues_write, Some(&repos), None)
+            .await
+            .unwrap();
+        app.github
+            .unassignable
+            .lock()
+            .unwrap()
+            .push("stranger".to_string());
+        let create = |assignees: Vec<&str>| {
+            let request = app
+                .client
+                .post(app.url("/api/issues"))
+                .header("X-API-Key", &secret)
+                .json(&serde_json::json!({
+                    "owner": "8b-is",
+                    "repo": "mem8",
+                    "title": "Wave memory leaks",
+                    "body": "Reported by an assistant",
+                    "labels": ["bug"],
+                    "assignees": assignees,
+                }));
+            async move {
+                let response = request.send().await.unwrap();
+                let status = response.status();
+                let body: serde_json::Value = response.json().await.unwrap();
+                (status, body)
+            }
+        };
+
+        let (status, body) = create(vec!["hue"]).await;
+        assert_eq!(status, StatusCode::CREATED);
+        assert_eq!(body["message"], "Issue created successfully");
+        assert_eq!(body["data"]["failed_assignees"], serde_json::json!([]));
+
+        // 👤 An assignee GitHub won't take costs only itself
+        let (status, body) = create(vec!["stranger", "aye"]).await;
+        assert_eq!(status, StatusCode::CREATED);
+        assert!(body["message"]
+            .as_str()
+            .unwrap()
+            .contains("could not be applied"));
+        let data = &body["data"];
+        assert_eq!(data["applied_labels"], serde_json::json!(["bug"]));
+        assert!(data.get("label_error").is_none());
+        assert_eq!(data["failed_assignees"][0]["login"], "stranger");
+        assert!(data["failed_assignees"][0]["error"]
+            .as_str()
+            .unwrap()
+            .contains("can't be assigned"));
+        let number = data["issue_number"].as_u64().unwrap() as u32;
+        let assigned: Vec<String> = app
+            .github
+            .calls()
+            .into_iter()
+            .filter_map(|call| match call {
+                GitHubCall::Assign {
+                    issue_number,
+                    assignee,
+                    ..
+                } if issue_number == number => Some(assignee),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(assigned, vec!["aye".to_string()]);
+        println!("✅ Partial issue creation test passed!");
+    }
+}
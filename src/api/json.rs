@@ -0,0 +1,190 @@
+// 🧾 JSON Bodies - Telling API consumers exactly where their JSON went wrong! 🧾
+// `ApiJson<T>` is a drop-in for axum's `Json<T>` extractor. Instead of a terse
+// plain-text 422, a body that can't be parsed gets a 400 `ApiResponse::error`
+// with serde's message and the line/column it stopped at.
+// Created with love by Aye & Hue! ✨
+
+use axum::{
+    async_trait,
+    body::Bytes,
+    extract::{FromRequest, Request},
+    http::{header, HeaderMap, StatusCode},
+    response::{IntoResponse, Json, Response},
+};
+use serde::de::DeserializeOwned;
+use serde_json::error::Category;
+
+use super::{ApiResponse, ErrorCode};
+
+/// 📦 JSON request body with structured error responses
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ApiJson<T>(pub T);
+
+#[async_trait]
+impl<T, S> FromRequest<S> for ApiJson<T>
+where
+    T: DeserializeOwned,
+    S: Send + Sync,
+{
+    type Rejection = Response;
+
+    async fn from_request(req: Request, state: &S) -> Result<Self, Self::Rejection> {
+        if !has_json_content_type(req.headers()) {
+            return Err(unsupported_media_type());
+        }
+        // 📏 Size limits and broken streams keep axum's own rejection
+        let body = Bytes::from_request(req, state)
+            .await
+            .map_err(IntoResponse::into_response)?;
+        parse_json_body(&body)
+            .map(ApiJson)
+            .map_err(|response| *response)
+    }
+}
+
+/// 🔍 Parse a JSON body, or build the 400 `invalid_json` response describing why not
+pub fn parse_json_body<T: DeserializeOwned>(body: &[u8]) -> Result<T, Box<Response>> {
+    serde_json::from_slice(body).map_err(|e| Box::new(invalid_json_error(&e)))
+}
+
+/// 🚫 400 with serde's message, where it happened and what kind of problem it is
+fn invalid_json_error(error: &serde_json::Error) -> Response {
+    let kind = match error.classify() {
+        Category::Syntax => "syntax",
+        Category::Data => "data",
+        Category::Eof => "eof",
+        Category::Io => "io",
+    };
+    let api_response = ApiResponse::<()>::error(
+        ErrorCode::InvalidJson,
+        format!("Invalid JSON body: {}", error_message(error)),
+        Some(serde_json::json!({
+            "kind": kind,
+            "line": error.line(),
+            "column": error.column(),
+        })),
+    );
+    (StatusCode::BAD_REQUEST, Json(api_response)).into_response()
+}
+
+/// ✂️ serde's message without the trailing " at line X column Y" (that goes in details)
+fn error_message(error: &serde_json::Error) -> String {
+    let message = error.to_string();
+    let suffix = format!(" at line {} column {}", error.line(), error.column());
+    message
+        .strip_suffix(&suffix)
+        .map(str::to_string)
+        .unwrap_or(message)
+}
+
+/// 🏷️ application/json, or any application/*+json type
+pub(crate) fn has_json_content_type(headers: &HeaderMap) -> bool {
+    let Some(content_type) = headers
+        .get(header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+    else {
+        return false;
+    };
+    let essence = content_type
+        .split(';')
+        .next()
+        .unwrap_or_default()
+        .trim()
+        .to_ascii_lowercase();
+    essence == "application/json"
+        || (essence.starts_with("application/") && essence.ends_with("+json"))
+}
+
+/// 🚫 415 when the body isn't declared as JSON
+pub(crate) fn unsupported_media_type() -> Response {
+    let api_response = ApiResponse::<()>::error(
+        ErrorCode::UnsupportedMediaType,
+        "Expected a request with `Content-Type: application/json`".to_string(),
+        None,
+    );
+    (StatusCode::UNSUPPORTED_MEDIA_TYPE, Json(api_response)).into_response()
+}
+
+// 🧪 Tests - Broken JSON gets a helpful answer!
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{body::Body, routing::post, Router};
+    use serde::Deserialize;
+    use tower::ServiceExt;
+
+    #[derive(Deserialize)]
+    struct Greeting {
+        #[allow(dead_code)]
+        name: String,
+    }
+
+    async fn post_body(
+        content_type: Option<&str>,
+        body: &'static str,
+    ) -> (StatusCode, serde_json::Value) {
+        let app = Router::new().route(
+            "/greet",
+            post(|ApiJson(_greeting): ApiJson<Greeting>| async { "hi" }),
+        );
+        let mut request = axum::http::Request::builder().method("POST").uri("/greet");
+        if let Some(content_type) = content_type {
+            request = request.header("content-type", content_type);
+        }
+        let response = app
+            .oneshot(request.body(Body::from(body)).unwrap())
+            .await
+            .unwrap();
+        let status = response.status();
+        let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        (status, serde_json::from_slice(&bytes).unwrap_or_default())
+    }
+
+    #[tokio::test]
+    async fn test_invalid_json_is_a_structured_400() {
+        let (status, body) = post_body(Some("application/json"), "{\"name\": \"Hue\",\n}").await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(body["success"], false);
+        assert_eq!(body["error"]["code"], "invalid_json");
+        assert_eq!(body["error"]["details"]["kind"], "syntax");
+        assert_eq!(body["error"]["details"]["line"], 2);
+        assert_eq!(body["error"]["details"]["column"], 1);
+        let message = body["error"]["message"].as_str().unwrap();
+        assert!(message.starts_with("Invalid JSON body: trailing comma"));
+        assert!(!message.contains(" at line "));
+
+        let (status, body) = post_body(Some("application/json"), "{}").await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(body["error"]["details"]["kind"], "data");
+        assert!(body["error"]["message"]
+            .as_str()
+            .unwrap()
+            .contains("missing field `name`"));
+
+        let (status, body) = post_body(Some("application/json"), "{\"name\": ").await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(body["error"]["details"]["kind"], "eof");
+        println!("✅ Invalid JSON error test passed!");
+    }
+
+    #[tokio::test]
+    async fn test_content_type_is_required() {
+        let (status, body) = post_body(None, "{\"name\": \"Aye\"}").await;
+        assert_eq!(status, StatusCode::UNSUPPORTED_MEDIA_TYPE);
+        assert_eq!(body["error"]["code"], "unsupported_media_type");
+
+        let (status, _) = post_body(Some("text/plain"), "{\"name\": \"Aye\"}").await;
+        assert_eq!(status, StatusCode::UNSUPPORTED_MEDIA_TYPE);
+
+        for content_type in [
+            "application/json; charset=utf-8",
+            "application/vnd.api+json",
+        ] {
+            let (status, _) = post_body(Some(content_type), "{\"name\": \"Aye\"}").await;
+            assert_eq!(status, StatusCode::OK, "{}", content_type);
+        }
+        println!("✅ JSON content type test passed!");
+    }
+}
@@ -0,0 +1,136 @@
+// 🔤 Labels - Decorative emoji that screen readers don't have to read out! 🔤
+// Our pages lead most headings, links and buttons with an emoji. Each of those
+// labels is rendered through `label_html`: in `emoji` style its emoji is hidden
+// from assistive tech, in `plain` style it is left out. User-supplied text never
+// goes through it, so it stays exactly as written.
+// The style comes from ADMIN_LABEL_STYLE, overridable per browser by cookie.
+// Created with love by Aye & Hue! ✨
+
+use axum_extra::extract::cookie::CookieJar;
+
+use crate::{api::AppState, config::LabelStyle};
+
+/// 🍪 Cookie holding a browser's label style override
+pub const LABEL_STYLE_COOKIE: &str = "feedbacker_label_style";
+
+/// 🔤 The label style for this request: the browser's cookie, else the configured default
+pub fn label_style(app_state: &AppState, jar: &CookieJar) -> LabelStyle {
+    jar.get(LABEL_STYLE_COOKIE)
+        .and_then(|cookie| cookie.value().parse().ok())
+        .unwrap_or(app_state.config.features.label_style)
+}
+
+/// 🎨 Pictographic code points we treat as decorative
+fn is_pictograph(c: char) -> bool {
+    matches!(c as u32,
+        0x1F000..=0x1FAFF // emoji, symbols and pictographs, flags
+        | 0x2600..=0x27BF // misc symbols and dingbats (☀ ✅ ✓ ⚠ ✨)
+        | 0x2300..=0x23FF // misc technical (⏰ ⏳ ⏱)
+        | 0x2B00..=0x2BFF // arrows and shapes (⭐ ⬆)
+        | 0x3030 | 0x303D | 0x3297 | 0x3299
+        | 0x00A9 | 0x00AE | 0x203C | 0x2049 | 0x2122 | 0x2139)
+}
+
+/// 🧩 Characters that extend an emoji into a cluster (variation selector, joiner, skin tones, keycap)
+fn is_emoji_modifier(c: char) -> bool {
+    matches!(c as u32, 0xFE0F | 0x200D | 0x20E3 | 0x1F3FB..=0x1F3FF)
+}
+
+/// 📏 Byte length of the emoji cluster `text` starts with (0 when it doesn't start with one)
+fn leading_emoji_len(text: &str) -> usize {
+    let mut chars = text.char_indices().peekable();
+    // #️⃣ Keycaps start with an ASCII character
+    let starts_keycap = text
+        .chars()
+        .nth(1)
+        .is_some_and(|c| c == '\u{FE0F}' || c == '\u{20E3}')
+        && text.starts_with(|c: char| c == '#' || c == '*' || c.is_ascii_digit());
+    match chars.next() {
+        Some((_, c)) if is_pictograph(c) || starts_keycap => {}
+        _ => return 0,
+    }
+    let mut end = text.chars().next().map_or(0, char::len_utf8);
+    // 🇺🇳 Flags are a pair of regional indicators
+    let is_regional = |c: char| matches!(c as u32, 0x1F1E6..=0x1F1FF);
+    if text.starts_with(is_regional) {
+        if let Some(&(index, c)) = chars.peek().filter(|(_, c)| is_regional(*c)) {
+            end = index + c.len_utf8();
+            chars.next();
+        }
+    }
+    let mut after_joiner = false;
+    while let Some(&(index, c)) = chars.peek() {
+        if is_emoji_modifier(c) || (after_joiner && is_pictograph(c)) {
+            after_joiner = c == '\u{200D}';
+            end = index + c.len_utf8();
+            chars.next();
+        } else {
+            break;
+        }
+    }
+    end
+}
+
+/// ✂️ Split a label into its decorative emoji and the rest, e.g. "🏠 Projects" -> ("🏠", "Projects").
+/// Only an emoji followed by whitespace and more text counts, so "🐛" on its own stays put.
+pub fn split_decoration(label: &str) -> (&str, &str) {
+    let emoji_len = leading_emoji_len(label);
+    if emoji_len == 0 {
+        return ("", label);
+    }
+    let rest = &label[emoji_len..];
+    let text = rest.trim_start();
+    if text.len() == rest.len() || text.is_empty() {
+        return ("", label);
+    }
+    (&label[..emoji_len], text)
+}
+
+/// 🏷️ A label we render ourselves (nav, headings, buttons): emoji hidden from screen readers, or dropped
+pub fn label_html(label: &str, style: LabelStyle) -> String {
+    match (split_decoration(label), style) {
+        (("", _), _) => label.to_string(),
+        ((_, text), LabelStyle::Plain) => text.to_string(),
+        ((emoji, text), LabelStyle::Emoji) => {
+            format!(r#"<span aria-hidden="true">{}</span> {}"#, emoji, text)
+        }
+    }
+}
+
+// 🧪 Tests - Reading the page the way a screen reader would!
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_decoration() {
+        assert_eq!(split_decoration("🏠 Projects"), ("🏠", "Projects"));
+        assert_eq!(
+            split_decoration("⚙️ Background Jobs"),
+            ("⚙️", "Background Jobs")
+        );
+        assert_eq!(split_decoration("👨‍💻 Developers"), ("👨‍💻", "Developers"));
+        assert_eq!(split_decoration("👍🏽 Thanks"), ("👍🏽", "Thanks"));
+        assert_eq!(split_decoration("#️⃣ Hashes"), ("#️⃣", "Hashes"));
+        assert_eq!(split_decoration("✓ Enabled"), ("✓", "Enabled"));
+        assert_eq!(split_decoration("🇳🇱 Dutch"), ("🇳🇱", "Dutch"));
+        // 🚫 Not decorative: no emoji, emoji alone, emoji glued to a word, arrows
+        assert_eq!(split_decoration("Projects"), ("", "Projects"));
+        assert_eq!(split_decoration("🐛"), ("", "🐛"));
+        assert_eq!(split_decoration("🐛bug"), ("", "🐛bug"));
+        assert_eq!(split_decoration("← Back to Site"), ("", "← Back to Site"));
+        assert_eq!(split_decoration("3 items"), ("", "3 items"));
+        println!("✅ Emoji decoration split test passed!");
+    }
+
+    #[test]
+    fn test_label_html_hides_or_drops_emoji() {
+        assert_eq!(
+            label_html("🏠 Projects", LabelStyle::Emoji),
+            r#"<span aria-hidden="true">🏠</span> Projects"#
+        );
+        assert_eq!(label_html("🏠 Projects", LabelStyle::Plain), "Projects");
+        assert_eq!(label_html("Projects", LabelStyle::Emoji), "Projects");
+        println!("✅ Label HTML test passed!");
+    }
+}
@@ -2,12 +2,20 @@
 // Logs and responds to MCP tool requests from Smart Tree clients
 // Created with love by Aye & Hue! ✨
 
-use crate::api::AppState;
+use crate::api::settings_cache::{ReleaseDownload, NEW_FEATURES_KEY, RELEASE_DOWNLOADS_KEY};
+use crate::api::{json::ApiJson, AppState};
+use crate::github::releases::{release_tag, GitHubRelease};
+use crate::middleware::auth::AuthenticatedUser;
+use crate::middleware::rate_limiting::RateLimitScope;
+use crate::utils::privacy::{anonymize_ip, StoredIp};
+use crate::utils::versions::Version;
+use anyhow::Context;
 use axum::{
     extract::{ConnectInfo, Query, State},
     http::HeaderMap,
     http::StatusCode,
-    response::{IntoResponse, Json},
+    response::{IntoResponse, Json, Response},
+    Extension,
 };
 use chrono::Utc;
 use serde::{Deserialize, Serialize};
@@ -53,6 +61,15 @@ const MAXMIND_DOWNLOAD_URL: &str =
 /// 🌍 Database refresh interval (default: 24 hours, MaxMind updates weekly)
 const DEFAULT_REFRESH_HOURS: u64 = 24;
 
+/// 🕵️ Longest User-Agent kept per check (characters)
+pub const USER_AGENT_MAX_CHARS: usize = 256;
+/// 🔌 Longest `integration` value kept per check (characters)
+pub const INTEGRATION_MAX_CHARS: usize = 64;
+/// 🔢 Longest raw version quoted in the unparseable-version warning (characters)
+const VERSION_LOG_MAX_CHARS: usize = 64;
+/// ❓ `latest_version` when we can't say (unknown product, nothing configured)
+pub const UNKNOWN_LATEST_VERSION: &str = "unknown";
+
 /// 🌍 Initialize GeoIP database (with optional auto-download)
 fn get_geoip_reader() -> Option<&'static maxminddb::Reader<Vec<u8>>> {
     GEOIP_DB
@@ -113,8 +130,8 @@ pub async fn init_geoip_database() {
                 }
             }
 
-            if existing_path.is_some() && !needs_refresh {
-                info!("🌍 GeoIP database found at: {}", existing_path.unwrap());
+            if let (Some(path), false) = (&existing_path, needs_refresh) {
+                info!("🌍 GeoIP database found at: {}", path);
             }
 
             // Download if missing or stale (and credentials are available)
@@ -267,34 +284,17 @@ fn lookup_geo(ip: IpAddr) -> GeoLocation {
     }
 }
 
-/// 🔍 Extract client IP from request headers or connection
-fn extract_client_ip(
+/// 🔍 The client's IP: the connection's address, or what a trusted proxy says it is
+pub(crate) fn extract_client_ip(
+    app_state: &AppState,
     headers: &HeaderMap,
     connect_info: Option<&ConnectInfo<SocketAddr>>,
 ) -> Option<IpAddr> {
-    // Check X-Forwarded-For first (for reverse proxies)
-    if let Some(xff) = headers.get("x-forwarded-for") {
-        if let Ok(xff_str) = xff.to_str() {
-            // Take the first IP (original client)
-            if let Some(first_ip) = xff_str.split(',').next() {
-                if let Ok(ip) = first_ip.trim().parse::<IpAddr>() {
-                    return Some(ip);
-                }
-            }
-        }
-    }
-
-    // Check X-Real-IP
-    if let Some(real_ip) = headers.get("x-real-ip") {
-        if let Ok(ip_str) = real_ip.to_str() {
-            if let Ok(ip) = ip_str.parse::<IpAddr>() {
-                return Some(ip);
-            }
-        }
-    }
-
-    // Fall back to connection info
-    connect_info.map(|ci| ci.0.ip())
+    crate::utils::client_ip::client_ip(
+        headers,
+        connect_info.map(|ci| ci.0.ip()),
+        &app_state.config.server.trusted_proxies,
+    )
 }
 
 /// 📊 MCP Check Request - Version and platform info from Smart Tree clients
@@ -303,14 +303,68 @@ pub struct McpCheckQuery {
     pub version: Option<String>,
     pub platform: Option<String>,
     pub arch: Option<String>,
+    /// 🔌 Which integration is calling (set by clients that wrap Smart Tree)
+    pub integration: Option<String>,
+    /// 📦 Product being checked (defaults to smart-tree), picks where downloads come from
+    pub product: Option<String>,
+    /// 🙈 `dnt=1`: the client has telemetry turned off (same as a `DNT: 1` header)
+    pub dnt: Option<String>,
+}
+
+impl McpCheckQuery {
+    /// 🙈 Did the client opt out of analytics, by `dnt=1` or the `DNT: 1` header?
+    pub fn do_not_track(&self, headers: &HeaderMap) -> bool {
+        self.dnt.as_deref().map(str::trim) == Some("1")
+            || headers
+                .get("dnt")
+                .and_then(|value| value.to_str().ok())
+                .map(str::trim)
+                == Some("1")
+    }
+}
+
+/// 🕵️ Who sent a check, kept for debugging misbehaving clients
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct McpClientInfo {
+    pub user_agent: Option<String>,
+    pub integration: Option<String>,
+}
+
+impl McpClientInfo {
+    /// 🔍 User-Agent header (non-UTF8 bytes replaced) and `integration`, trimmed and truncated
+    pub fn from_request(headers: &HeaderMap, integration: Option<&str>) -> Self {
+        let user_agent = headers
+            .get(axum::http::header::USER_AGENT)
+            .map(|value| String::from_utf8_lossy(value.as_bytes()).into_owned());
+        Self {
+            user_agent: clean_client_value(user_agent.as_deref(), USER_AGENT_MAX_CHARS),
+            integration: clean_client_value(integration, INTEGRATION_MAX_CHARS),
+        }
+    }
+}
+
+/// ✂️ Trimmed, at most `max_chars` characters, None when empty
+fn clean_client_value(value: Option<&str>, max_chars: usize) -> Option<String> {
+    let value = value?.trim();
+    (!value.is_empty()).then(|| value.chars().take(max_chars).collect())
 }
 
 /// 📊 MCP Check Response
 #[derive(Debug, Serialize)]
 pub struct McpCheckResponse {
+    /// 🏷️ Newest release, or "unknown" when we can't say
     pub latest_version: String,
+    /// 🆕 False when the client's version couldn't be compared
     pub update_available: bool,
+    /// 🌳 Is the product one this server answers for (MCP_PRODUCTS)?
+    pub supported: bool,
     pub download_url: Option<String>,
+    /// ⏳ When a signed `download_url` stops working (absent for GitHub links)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub download_expires_at: Option<chrono::DateTime<chrono::Utc>>,
+    /// 📏 Bytes behind `download_url`, when it is a release asset of known size
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub download_size: Option<u64>,
     pub release_notes: Option<String>,
     pub new_features: Option<Vec<String>>,
     pub message: Option<String>,
@@ -329,65 +383,178 @@ pub struct McpAnalytics {
 ///
 /// This endpoint is called by Smart Tree MCP clients to check for updates.
 /// It logs platform/version info for analytics and returns update info.
+/// Do-Not-Track checks (`dnt=1` or `DNT: 1`) get the same answer but leave no row,
+/// no geo lookup and no log line - only the `dnt_checks` counter moves. So do checks
+/// from a User-Agent outside MCP_TRUSTED_USER_AGENTS (counted as `untrusted_checks`).
 pub async fn mcp_check(
     State(app_state): State<AppState>,
     headers: HeaderMap,
     connect_info: Option<ConnectInfo<SocketAddr>>,
     Query(query): Query<McpCheckQuery>,
-) -> impl IntoResponse {
+) -> Response {
+    let do_not_track = query.do_not_track(&headers);
+    let product = query
+        .product
+        .as_deref()
+        .map(str::trim)
+        .filter(|product| !product.is_empty())
+        .unwrap_or(crate::api::downloads::DEFAULT_PRODUCT)
+        .to_lowercase();
+    let raw_version = query.version.clone();
     let version = query.version.unwrap_or_else(|| "unknown".to_string());
     let platform = query.platform.unwrap_or_else(|| "unknown".to_string());
     let arch = query.arch.unwrap_or_else(|| "unknown".to_string());
+    let client = McpClientInfo::from_request(&headers, query.integration.as_deref());
 
     // Extract client IP and do geo lookup
-    let client_ip = extract_client_ip(&headers, connect_info.as_ref());
-    let geo = client_ip.map(lookup_geo).unwrap_or_default();
+    let client_ip = extract_client_ip(&app_state, &headers, connect_info.as_ref());
 
-    info!(
-        "📊 MCP check received - version: {}, platform: {}, arch: {}, ip: {:?}, location: {:?}/{:?}",
-        version, platform, arch, client_ip, geo.city, geo.country
-    );
+    // 🚦 Per-IP allowance (RATE_LIMIT_MCP_CHECKS_PER_MINUTE), checked before any analytics are written
+    if let Some(ip) = client_ip {
+        if let Err(retry_after) = app_state.rate_limiter.check(RateLimitScope::McpCheck, ip) {
+            warn!("🚫 MCP check rate limit exceeded for {}", ip);
+            return crate::api::utils::rate_limit_error(retry_after).into_response();
+        }
+    }
 
-    // Log to database for analytics (with geo data)
-    if let Err(e) = log_mcp_analytics(&app_state, &version, &platform, &arch, client_ip, &geo).await
+    if do_not_track {
+        // 🙈 Opted out: counted in aggregate, nothing about this client is kept
+        app_state.metrics.record_dnt_check();
+    } else if !app_state
+        .config
+        .analytics
+        .is_trusted_client(client.user_agent.as_deref())
     {
-        debug!("Failed to log MCP analytics: {}", e);
-    }
+        // 🤷 Not one of our clients (a browser, a scraper): answered, but not analytics
+        app_state.metrics.record_untrusted_check();
+    } else {
+        let geo = client_ip.map(lookup_geo).unwrap_or_default();
+
+        // 🕶️ Geo lookup had the full address; everything after only sees what we may keep
+        let stored_ip = anonymize_ip(client_ip, &app_state.config.analytics);
+
+        // 🎲 Every client polls this - LOG_SAMPLE_EVERY keeps the routine line affordable
+        let sampler = &app_state.log_samplers.mcp_check;
+        if let Some(skipped) = sampler.sample() {
+            debug!(
+                "📊 MCP check received - version: {}, platform: {}, arch: {}, ip: {:?}, location: {:?}/{:?}, user agent: {:?}, integration: {:?}{}",
+                version, platform, arch, stored_ip.address, geo.city, geo.country, client.user_agent, client.integration,
+                sampler.note(skipped)
+            );
+        }
 
-    // TODO: Get actual latest version from releases table or config
-    // For now, just echo back that they're up to date
-    let latest_version = get_latest_smart_tree_version(&app_state)
+        // Log to database for analytics (with geo data)
+        if let Err(e) = log_mcp_analytics(
+            &app_state, &version, &platform, &arch, &stored_ip, &geo, &client,
+        )
         .await
-        .unwrap_or_else(|| version.clone());
+        {
+            warn!("⚠️ Failed to log MCP analytics: {:#}", e);
+        }
+    }
+
+    // 🌳 A product we don't answer for gets told so, instead of a made-up "latest"
+    let downloads = &app_state.config.downloads;
+    if !crate::api::downloads::is_known_product(downloads, &product) {
+        return Json(McpCheckResponse {
+            latest_version: UNKNOWN_LATEST_VERSION.to_string(),
+            update_available: false,
+            supported: false,
+            download_url: None,
+            download_expires_at: None,
+            download_size: None,
+            release_notes: None,
+            new_features: None,
+            message: Some(crate::api::downloads::unknown_product_message(
+                downloads, &product,
+            )),
+        })
+        .into_response();
+    }
+
+    // 🔢 A version we can't read is never "outdated" - but it is worth a look
+    if Version::parse(&version).is_none() && !do_not_track {
+        warn!(
+            "⚠️ MCP check with an unparseable version {:?} (product: {}, platform: {}, arch: {})",
+            raw_version.map(|raw| raw.chars().take(VERSION_LOG_MAX_CHARS).collect::<String>()),
+            product,
+            platform,
+            arch
+        );
+    }
+
+    // ⚡ Release info comes from the settings cache, never a query per check: Smart Tree's
+    // from its settings keys, every other product's from its release history
+    let settings = app_state.settings.get();
+    let (latest_version, latest_notes, latest_features, release_downloads) =
+        if product == crate::api::downloads::DEFAULT_PRODUCT {
+            (
+                settings.smart_tree_latest_version.clone(),
+                settings.smart_tree_release_notes.clone(),
+                settings.smart_tree_new_features.clone(),
+                settings.smart_tree_release_downloads.as_deref(),
+            )
+        } else {
+            match settings
+                .product_releases
+                .get(&product)
+                .and_then(|releases| crate::api::releases::latest_stable(releases))
+            {
+                Some(release) => (
+                    Some(release.version.clone()),
+                    release.notes.clone(),
+                    Some(release.features.clone()),
+                    None,
+                ),
+                None => (None, None, None, None),
+            }
+        };
+    let latest_version = latest_version.unwrap_or_else(|| UNKNOWN_LATEST_VERSION.to_string());
 
     let update_available = is_newer_version(&latest_version, &version);
 
     // Get release notes and features if available
     let (release_notes, new_features) = if update_available {
-        let notes = get_release_notes(&app_state).await;
-        let features = get_new_features(&app_state).await;
-        (notes, features)
+        (latest_notes, latest_features)
     } else {
         (None, None)
     };
 
+    // 📦 GitHub releases, unless this product/platform is served from the operator's storage
+    let mut download = update_available.then(|| {
+        crate::api::downloads::download_link(
+            downloads,
+            &product,
+            &latest_version,
+            &platform,
+            &arch,
+            chrono::Utc::now(),
+        )
+    });
+    // 🎯 A verified release names the exact file for this platform/arch
+    let mut download_size = None;
+    if let Some(link) = download.as_mut().filter(|link| link.expires_at.is_none()) {
+        if let Some(asset) = release_downloads
+            .and_then(|assets| crate::api::downloads::release_download(assets, &platform, &arch))
+        {
+            link.url = asset.url.clone();
+            download_size = Some(asset.size);
+        }
+    }
+
     let response = McpCheckResponse {
         latest_version: latest_version.clone(),
         update_available,
-        download_url: if update_available {
-            Some(format!(
-                "https://github.com/8b-is/smart-tree/releases/tag/v{}",
-                latest_version
-            ))
-        } else {
-            None
-        },
+        supported: true,
+        download_expires_at: download.as_ref().and_then(|link| link.expires_at),
+        download_size,
+        download_url: download.map(|link| link.url),
         release_notes,
         new_features,
         message: Some("Thanks for using Smart Tree! 🌲".to_string()),
     };
 
-    Json(response)
+    Json(response).into_response()
 }
 
 /// 📊 MCP Stats Response
@@ -396,6 +563,7 @@ pub struct McpStatsResponse {
     pub total_checks: i64,
     pub unique_platforms: Vec<PlatformStats>,
     pub version_distribution: Vec<VersionStats>,
+    pub top_user_agents: Vec<UserAgentStats>,
     pub recent_checks: Vec<RecentCheck>,
 }
 
@@ -412,24 +580,52 @@ pub struct VersionStats {
     pub count: i64,
 }
 
+/// 🕵️ Checks per User-Agent (null = no header sent)
+#[derive(Debug, Serialize)]
+pub struct UserAgentStats {
+    pub user_agent: Option<String>,
+    pub count: i64,
+}
+
 #[derive(Debug, Serialize)]
 pub struct RecentCheck {
     pub version: String,
     pub platform: String,
     pub arch: String,
+    pub user_agent: Option<String>,
+    pub integration: Option<String>,
     pub checked_at: String,
 }
 
+/// 🔍 GET /mcp/stats filter: only list recent checks from this exact User-Agent
+#[derive(Debug, Default, Deserialize)]
+pub struct McpStatsQuery {
+    pub user_agent: Option<String>,
+}
+
+impl McpStatsQuery {
+    /// 🕵️ The User-Agent to filter on (blank means no filter)
+    pub fn user_agent(&self) -> Option<&str> {
+        self.user_agent
+            .as_deref()
+            .filter(|ua| !ua.trim().is_empty())
+    }
+}
+
 /// 📊 GET /mcp/stats - Get MCP usage statistics (admin only)
-pub async fn mcp_stats(State(app_state): State<AppState>) -> impl IntoResponse {
-    info!("📊 MCP stats requested");
+pub async fn mcp_stats(
+    State(app_state): State<AppState>,
+    Query(query): Query<McpStatsQuery>,
+) -> impl IntoResponse {
+    debug!("📊 MCP stats requested");
 
-    let stats = get_mcp_stats(&app_state)
+    let stats = get_mcp_stats(&app_state, query.user_agent())
         .await
         .unwrap_or_else(|_| McpStatsResponse {
             total_checks: 0,
             unique_platforms: vec![],
             version_distribution: vec![],
+            top_user_agents: vec![],
             recent_checks: vec![],
         });
 
@@ -437,10 +633,20 @@ pub async fn mcp_stats(State(app_state): State<AppState>) -> impl IntoResponse {
 }
 
 /// 🔧 POST /mcp/version - Set the latest Smart Tree version (admin only)
+/// With `verify_release` (default MCP_VERIFY_RELEASES) the version's GitHub release in
+/// MCP_RELEASE_REPOSITORY must exist and not be a draft, or nothing is published (422);
+/// its assets become the per-platform downloads. `force` skips the check, audit-logged.
 #[derive(Debug, Deserialize)]
 pub struct SetVersionRequest {
     pub version: String,
     pub release_notes: Option<String>,
+    /// ✨ Headline features, shown by /mcp/check and kept with the release
+    pub new_features: Option<Vec<String>>,
+    /// 🔎 Check the GitHub release first (None = MCP_VERIFY_RELEASES)
+    pub verify_release: Option<bool>,
+    /// 🚨 Publish without checking the release
+    #[serde(default)]
+    pub force: bool,
 }
 
 #[derive(Debug, Serialize)]
@@ -448,110 +654,156 @@ pub struct SetVersionResponse {
     pub success: bool,
     pub version: String,
     pub message: String,
+    /// 📦 Per-platform downloads taken from the verified release
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub downloads: Vec<ReleaseDownload>,
 }
 
 pub async fn mcp_set_version(
     State(app_state): State<AppState>,
-    Json(request): Json<SetVersionRequest>,
-) -> impl IntoResponse {
+    Extension(user): Extension<AuthenticatedUser>,
+    ApiJson(request): ApiJson<SetVersionRequest>,
+) -> Response {
     info!("🔧 Setting Smart Tree version to: {}", request.version);
 
+    let verify = request
+        .verify_release
+        .unwrap_or(app_state.config.downloads.verify_releases);
+    let downloads = if request.force {
+        warn!(
+            "🚨 {} published Smart Tree {} with force - its GitHub release was not checked",
+            user.email, request.version
+        );
+        crate::api::admin::audit_log_as(
+            &app_state,
+            &user.email,
+            "mcp_version_forced",
+            serde_json::json!({
+                "version": request.version,
+                "verification_skipped": verify,
+                "repository": app_state.config.downloads.release_repository,
+            }),
+        )
+        .await;
+        None
+    } else if verify {
+        match verify_release(&app_state, &request.version).await {
+            Ok(release) => Some(crate::api::downloads::release_downloads(&release)),
+            Err(e) => {
+                warn!("🚫 Not publishing Smart Tree {}: {:#}", request.version, e);
+                return (
+                    StatusCode::UNPROCESSABLE_ENTITY,
+                    Json(SetVersionResponse {
+                        success: false,
+                        version: request.version,
+                        message: format!("Release check failed: {:#}", e),
+                        downloads: Vec::new(),
+                    }),
+                )
+                    .into_response();
+            }
+        }
+    } else {
+        None
+    };
+
     match set_latest_version(
         &app_state,
         &request.version,
         request.release_notes.as_deref(),
+        request.new_features.as_deref(),
+        downloads.as_deref(),
     )
     .await
     {
-        Ok(_) => Json(SetVersionResponse {
-            success: true,
-            version: request.version,
-            message: "Version updated successfully".to_string(),
-        }),
+        Ok(_) => {
+            // ⚡ Publish right away rather than waiting for the next background refresh
+            if let Err(e) = app_state.settings.reload(&app_state.db_pool).await {
+                warn!("⚠️ Failed to refresh runtime settings: {:#}", e);
+            }
+            Json(SetVersionResponse {
+                success: true,
+                version: request.version,
+                message: "Version updated successfully".to_string(),
+                downloads: downloads.unwrap_or_default(),
+            })
+            .into_response()
+        }
         Err(e) => Json(SetVersionResponse {
             success: false,
             version: request.version,
             message: format!("Failed to update version: {}", e),
-        }),
+            downloads: Vec::new(),
+        })
+        .into_response(),
     }
 }
 
+/// 🔎 The published, non-draft GitHub release behind a version
+async fn verify_release(app_state: &AppState, version: &str) -> anyhow::Result<GitHubRelease> {
+    let repository = &app_state.config.downloads.release_repository;
+    let (owner, repo) = repository
+        .split_once('/')
+        .with_context(|| format!("MCP_RELEASE_REPOSITORY {} is not owner/repo", repository))?;
+    let tag = release_tag(version);
+    let release = app_state
+        .github
+        .release_by_tag(owner, repo, &tag)
+        .await?
+        .with_context(|| format!("No GitHub release tagged {} in {}", tag, repository))?;
+    if release.draft {
+        anyhow::bail!("GitHub release {} in {} is still a draft", tag, repository);
+    }
+    Ok(release)
+}
+
 // Helper functions
 
-/// Log MCP analytics to database (with geo data)
+/// Log MCP analytics to database (with geo data and the already-anonymized IP)
 async fn log_mcp_analytics(
     app_state: &AppState,
     version: &str,
     platform: &str,
     arch: &str,
-    ip: Option<IpAddr>,
+    ip: &StoredIp,
     geo: &GeoLocation,
+    client: &McpClientInfo,
 ) -> anyhow::Result<()> {
     sqlx::query(
         r#"
         INSERT INTO mcp_analytics (
             client_version, platform, arch, checked_at,
-            ip_address, country, region, city, latitude, longitude
+            ip_address, ip_hash, country, region, city, latitude, longitude,
+            user_agent, integration
         )
-        VALUES ($1, $2, $3, NOW(), $4, $5, $6, $7, $8, $9)
+        VALUES ($1, $2, $3, NOW(), $4::inet, $10, $5, $6, $7, $8, $9, $11, $12)
         "#,
     )
     .bind(version)
     .bind(platform)
     .bind(arch)
-    .bind(ip.map(|ip| ip.to_string()))
+    .bind(ip.address.map(|ip| ip.to_string()))
     .bind(&geo.country)
     .bind(&geo.region)
     .bind(&geo.city)
     .bind(geo.latitude)
     .bind(geo.longitude)
+    .bind(&ip.hash)
+    .bind(&client.user_agent)
+    .bind(&client.integration)
     .execute(&app_state.db_pool)
     .await?;
 
     Ok(())
 }
 
-/// Get the latest Smart Tree version from settings
-async fn get_latest_smart_tree_version(app_state: &AppState) -> Option<String> {
-    let result = sqlx::query_scalar::<_, String>(
-        "SELECT value FROM settings WHERE key = 'smart_tree_latest_version'",
-    )
-    .fetch_optional(&app_state.db_pool)
-    .await
-    .ok()?;
-
-    result
-}
-
-/// Get release notes from settings
-async fn get_release_notes(app_state: &AppState) -> Option<String> {
-    sqlx::query_scalar::<_, String>(
-        "SELECT value FROM settings WHERE key = 'smart_tree_release_notes'",
-    )
-    .fetch_optional(&app_state.db_pool)
-    .await
-    .ok()
-    .flatten()
-}
-
-/// Get new features list from settings (stored as JSON array)
-async fn get_new_features(app_state: &AppState) -> Option<Vec<String>> {
-    let json_str = sqlx::query_scalar::<_, String>(
-        "SELECT value FROM settings WHERE key = 'smart_tree_new_features'",
-    )
-    .fetch_optional(&app_state.db_pool)
-    .await
-    .ok()
-    .flatten()?;
-
-    serde_json::from_str(&json_str).ok()
-}
-
-/// Set the latest Smart Tree version
+/// Set the latest Smart Tree version (and its downloads - without any, older ones are dropped)
 async fn set_latest_version(
     app_state: &AppState,
     version: &str,
     release_notes: Option<&str>,
+    new_features: Option<&[String]>,
+    downloads: Option<&[ReleaseDownload]>,
 ) -> anyhow::Result<()> {
     sqlx::query(
         r#"
@@ -577,11 +829,58 @@ async fn set_latest_version(
         .await?;
     }
 
-    Ok(())
+    if let Some(features) = new_features {
+        sqlx::query(
+            r#"
+            INSERT INTO settings (key, value, updated_at)
+            VALUES ($1, $2, NOW())
+            ON CONFLICT (key) DO UPDATE SET value = $2, updated_at = NOW()
+            "#,
+        )
+        .bind(NEW_FEATURES_KEY)
+        .bind(serde_json::to_string(features)?)
+        .execute(&app_state.db_pool)
+        .await?;
+    }
+
+    match downloads {
+        Some(downloads) => {
+            sqlx::query(
+                r#"
+                INSERT INTO settings (key, value, updated_at)
+                VALUES ($1, $2, NOW())
+                ON CONFLICT (key) DO UPDATE SET value = $2, updated_at = NOW()
+                "#,
+            )
+            .bind(RELEASE_DOWNLOADS_KEY)
+            .bind(serde_json::to_string(downloads)?)
+            .execute(&app_state.db_pool)
+            .await?;
+        }
+        None => {
+            sqlx::query("DELETE FROM settings WHERE key = $1")
+                .bind(RELEASE_DOWNLOADS_KEY)
+                .execute(&app_state.db_pool)
+                .await?;
+        }
+    }
+
+    // 📜 Keep the history for /mcp/changelog
+    crate::api::releases::record_release(
+        &app_state.db_pool,
+        crate::api::downloads::DEFAULT_PRODUCT,
+        version,
+        release_notes,
+        new_features,
+    )
+    .await
 }
 
-/// Get MCP statistics
-async fn get_mcp_stats(app_state: &AppState) -> anyhow::Result<McpStatsResponse> {
+/// Get MCP statistics (recent checks optionally narrowed to one User-Agent)
+async fn get_mcp_stats(
+    app_state: &AppState,
+    user_agent: Option<&str>,
+) -> anyhow::Result<McpStatsResponse> {
     // Total checks
     let total_checks: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM mcp_analytics")
         .fetch_one(&app_state.db_pool)
@@ -633,15 +932,39 @@ async fn get_mcp_stats(app_state: &AppState) -> anyhow::Result<McpStatsResponse>
         })
         .collect();
 
+    // User-Agent breakdown
+    let user_agent_rows = sqlx::query(
+        r#"
+        SELECT user_agent, COUNT(*) as count
+        FROM mcp_analytics
+        GROUP BY user_agent
+        ORDER BY count DESC, user_agent
+        LIMIT 20
+        "#,
+    )
+    .fetch_all(&app_state.db_pool)
+    .await
+    .unwrap_or_default();
+
+    let top_user_agents: Vec<UserAgentStats> = user_agent_rows
+        .iter()
+        .map(|row| UserAgentStats {
+            user_agent: row.get("user_agent"),
+            count: row.get("count"),
+        })
+        .collect();
+
     // Recent checks
     let recent_rows = sqlx::query(
         r#"
-        SELECT client_version, platform, arch, checked_at
+        SELECT client_version, platform, arch, user_agent, integration, checked_at
         FROM mcp_analytics
-        ORDER BY checked_at DESC
+        WHERE ($1::text IS NULL OR user_agent = $1)
+        ORDER BY checked_at DESC, id DESC
         LIMIT 50
         "#,
     )
+    .bind(user_agent)
     .fetch_all(&app_state.db_pool)
     .await
     .unwrap_or_default();
@@ -652,6 +975,8 @@ async fn get_mcp_stats(app_state: &AppState) -> anyhow::Result<McpStatsResponse>
             version: row.get("client_version"),
             platform: row.get("platform"),
             arch: row.get("arch"),
+            user_agent: row.get("user_agent"),
+            integration: row.get("integration"),
             checked_at: row
                 .get::<chrono::DateTime<chrono::Utc>, _>("checked_at")
                 .format("%Y-%m-%d %H:%M:%S")
@@ -663,38 +988,24 @@ async fn get_mcp_stats(app_state: &AppState) -> anyhow::Result<McpStatsResponse>
         total_checks,
         unique_platforms,
         version_distribution,
+        top_user_agents,
         recent_checks,
     })
 }
 
-/// Compare semantic versions to check if there's an update
+/// Compare semantic versions to check if there's an update.
+/// A current version we can't parse ("unknown", ...) counts as older than any release.
 fn is_newer_version(latest: &str, current: &str) -> bool {
-    let parse_version = |v: &str| -> Vec<u32> {
-        v.trim_start_matches('v')
-            .split('.')
-            .filter_map(|s| s.parse().ok())
-            .collect()
-    };
-
-    let latest_parts = parse_version(latest);
-    let current_parts = parse_version(current);
-
-    for i in 0..latest_parts.len().max(current_parts.len()) {
-        let l = latest_parts.get(i).unwrap_or(&0);
-        let c = current_parts.get(i).unwrap_or(&0);
-        if l > c {
-            return true;
-        } else if l < c {
-            return false;
-        }
+    match (Version::parse(latest), Version::parse(current)) {
+        (Some(latest), Some(current)) => latest > current,
+        _ => false,
     }
-
-    false
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::config::{AnalyticsConfig, IpStorageMode};
 
     #[test]
     fn test_version_comparison() {
@@ -704,6 +1015,846 @@ mod tests {
         assert!(!is_newer_version("1.0.0", "1.0.0"));
         assert!(!is_newer_version("1.0.0", "1.1.0"));
         assert!(!is_newer_version("0.9.0", "1.0.0"));
+        // 🧪 A release is newer than its own pre-releases, not the other way round
+        assert!(is_newer_version("2.0.0", "2.0.0-rc.1"));
+        assert!(!is_newer_version("2.0.0-rc.1", "2.0.0"));
+        // ❓ Nothing to compare against: never report an update
+        assert!(!is_newer_version("1.0.0", "unknown"));
+        assert!(!is_newer_version("unknown", "1.0.0"));
         println!("✅ Version comparison tests passed!");
     }
+
+    #[tokio::test]
+    async fn test_mcp_check_reports_updates_and_logs_analytics() {
+        let Some(app) = crate::test_support::spawn_test_app().await else {
+            return;
+        };
+        sqlx::query(
+            "INSERT INTO settings (key, value) VALUES ('smart_tree_latest_version', '2.0.0')
+             ON CONFLICT (key) DO UPDATE SET value = EXCLUDED.value",
+        )
+        .execute(&app.db_pool)
+        .await
+        .unwrap();
+        app.app_state.settings.refresh(&app.db_pool).await.unwrap();
+
+        let outdated: serde_json::Value = app
+            .client
+            .get(app.url("/mcp/check?version=1.0.0&platform=linux&arch=x86_64"))
+            .send()
+            .await
+            .unwrap()
+            .json()
+            .await
+            .unwrap();
+        assert_eq!(outdated["latest_version"], "2.0.0");
+        assert_eq!(outdated["update_available"], true);
+        assert!(outdated["download_url"]
+            .as_str()
+            .unwrap()
+            .ends_with("/tag/v2.0.0"));
+
+        let current: serde_json::Value = app
+            .client
+            .get(app.url("/mcp/check?version=2.0.0&platform=macos&arch=aarch64"))
+            .send()
+            .await
+            .unwrap()
+            .json()
+            .await
+            .unwrap();
+        assert_eq!(current["update_available"], false);
+
+        let logged: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM mcp_analytics")
+            .fetch_one(&app.db_pool)
+            .await
+            .unwrap();
+        assert_eq!(logged, 2);
+        println!("✅ MCP check integration test passed!");
+    }
+
+    #[tokio::test]
+    async fn test_mcp_endpoints_work_on_fresh_and_hand_made_schemas() {
+        use crate::database::models::{User, UserRole};
+        use crate::middleware::auth::jwt_utils;
+
+        // 🏚️ Tables someone created by hand before the migration shipped
+        let hand_made = r#"
+            CREATE TABLE settings (key TEXT, value TEXT);
+            INSERT INTO settings VALUES ('smart_tree_latest_version', '1.5.0');
+            CREATE TABLE mcp_analytics (client_version TEXT, platform TEXT, arch TEXT);
+        "#;
+        for existing in ["", hand_made] {
+            let Some(app) = crate::test_support::spawn_test_app_on_schema(existing).await else {
+                return;
+            };
+            let admin: User = sqlx::query_as(
+                "INSERT INTO users (email, name, password_hash, role) \
+                 VALUES ('release@example.com', 'Release', 'x', $1) RETURNING *",
+            )
+            .bind(UserRole::Admin)
+            .fetch_one(&app.db_pool)
+            .await
+            .unwrap();
+            let token =
+                jwt_utils::create_jwt_token(&admin, &app.app_state.config.auth.jwt_secret, 1)
+                    .unwrap();
+
+            let published = app
+                .client
+                .post(app.url("/mcp/version"))
+                .bearer_auth(&token)
+                .json(&serde_json::json!({
+                    "version": "3.0.0",
+                    "release_notes": "Faster",
+                    "new_features": ["quantum mode"],
+                }))
+                .send()
+                .await
+                .unwrap();
+            assert_eq!(published.status(), StatusCode::OK);
+            let published: serde_json::Value = published.json().await.unwrap();
+            assert_eq!(published["success"], true, "{}", published);
+
+            let check = app
+                .client
+                .get(app.url("/mcp/check?version=1.0.0&platform=linux&arch=x86_64"))
+                .send()
+                .await
+                .unwrap();
+            assert_eq!(check.status(), StatusCode::OK);
+            let check: serde_json::Value = check.json().await.unwrap();
+            assert_eq!(check["latest_version"], "3.0.0");
+            assert_eq!(check["update_available"], true);
+            assert_eq!(check["new_features"], serde_json::json!(["quantum mode"]));
+            let features: serde_json::Value =
+                sqlx::query_scalar("SELECT features FROM releases WHERE version = '3.0.0'")
+                    .fetch_one(&app.db_pool)
+                    .await
+                    .unwrap();
+            assert_eq!(features, serde_json::json!(["quantum mode"]));
+
+            let logged: i64 = sqlx::query_scalar(
+                "SELECT COUNT(*) FROM mcp_analytics WHERE checked_at IS NOT NULL AND country IS NULL",
+            )
+            .fetch_one(&app.db_pool)
+            .await
+            .unwrap();
+            assert_eq!(logged, 1);
+        }
+        println!("✅ MCP tables migration test passed!");
+    }
+
+    #[tokio::test]
+    async fn test_mcp_check_flags_unknown_products_and_versions() {
+        let Some(app) = crate::test_support::spawn_test_app().await else {
+            return;
+        };
+        sqlx::query(
+            "INSERT INTO settings (key, value) VALUES ('smart_tree_latest_version', '2.0.0')
+             ON CONFLICT (key) DO UPDATE SET value = EXCLUDED.value",
+        )
+        .execute(&app.db_pool)
+        .await
+        .unwrap();
+        app.app_state.settings.refresh(&app.db_pool).await.unwrap();
+        let check = |path: &str| {
+            let request = app.client.get(app.url(path));
+            async move {
+                let response = request.send().await.unwrap();
+                assert_eq!(response.status(), StatusCode::OK);
+                response.json::<serde_json::Value>().await.unwrap()
+            }
+        };
+
+        // 🌳 Not ours: no echoed "latest", no download, and a hint why
+        let unknown = check("/mcp/check?product=smart-forest&version=1.0.0").await;
+        assert_eq!(unknown["supported"], false);
+        assert_eq!(unknown["update_available"], false);
+        assert_eq!(unknown["latest_version"], UNKNOWN_LATEST_VERSION);
+        assert!(unknown["download_url"].is_null());
+        assert!(unknown["message"]
+            .as_str()
+            .unwrap()
+            .contains("\"smart-forest\""));
+
+        // 🔢 A version we can't read is not "outdated"
+        let garbled = check("/mcp/check?version=latest-nightly&platform=linux").await;
+        assert_eq!(garbled["supported"], true);
+        assert_eq!(garbled["latest_version"], "2.0.0");
+        assert_eq!(garbled["update_available"], false);
+        let missing = check("/mcp/check?product=Smart-Tree").await;
+        assert_eq!(missing["supported"], true);
+        assert_eq!(missing["update_available"], false);
+        println!("✅ MCP unknown product/version test passed!");
+    }
+
+    #[tokio::test]
+    async fn test_mcp_check_answers_each_product_with_its_own_release() {
+        let Some(app) = crate::test_support::spawn_test_app_with_config(|config| {
+            config.downloads.products = vec!["smart-tree".to_string(), "mem8".to_string()];
+        })
+        .await
+        else {
+            return;
+        };
+        sqlx::query(
+            r#"
+            INSERT INTO settings (key, value) VALUES ('smart_tree_latest_version', '5.0.0')
+            ON CONFLICT (key) DO UPDATE SET value = EXCLUDED.value;
+            "#,
+        )
+        .execute(&app.db_pool)
+        .await
+        .unwrap();
+        for (version, yanked) in [("1.2.0", false), ("1.10.0", false), ("1.11.0", true)] {
+            sqlx::query(
+                "INSERT INTO releases (product, version, notes, features, yanked) VALUES ('mem8', $1, 'Wave memory', '[\"echoes\"]', $2)",
+            )
+            .bind(version)
+            .bind(yanked)
+            .execute(&app.db_pool)
+            .await
+            .unwrap();
+        }
+        app.app_state.settings.refresh(&app.db_pool).await.unwrap();
+        let check = |path: &str| {
+            let request = app.client.get(app.url(path));
+            async move {
+                request
+                    .send()
+                    .await
+                    .unwrap()
+                    .json::<serde_json::Value>()
+                    .await
+                    .unwrap()
+            }
+        };
+
+        // 🌲 mem8 gets its own newest (not yanked) release, not Smart Tree's
+        let mem8 = check("/mcp/check?product=mem8&version=1.2.0&platform=linux&arch=x86_64").await;
+        assert_eq!(mem8["latest_version"], "1.10.0");
+        assert_eq!(mem8["update_available"], true);
+        assert_eq!(
+            mem8["download_url"],
+            "https://github.com/8b-is/mem8/releases/tag/v1.10.0"
+        );
+        assert_eq!(mem8["release_notes"], "Wave memory");
+        assert_eq!(mem8["new_features"], serde_json::json!(["echoes"]));
+
+        let tree = check("/mcp/check?version=4.0.0").await;
+        assert_eq!(tree["latest_version"], "5.0.0");
+        assert_eq!(
+            tree["download_url"],
+            "https://github.com/8b-is/smart-tree/releases/tag/v5.0.0"
+        );
+        println!("✅ Per-product MCP check test passed!");
+    }
+
+    #[tokio::test]
+    async fn test_do_not_track_checks_are_answered_but_never_logged() {
+        let Some(app) = crate::test_support::spawn_test_app_with_config(|config| {
+            config.features.enable_metrics = true;
+        })
+        .await
+        else {
+            return;
+        };
+        let check = |path: &str, dnt_header: bool| {
+            let request = app
+                .client
+                .get(app.url(path))
+                .header("x-forwarded-for", "198.51.100.7");
+            let request = if dnt_header {
+                request.header("DNT", "1")
+            } else {
+                request
+            };
+            async move {
+                let response = request.send().await.unwrap();
+                assert_eq!(response.status(), StatusCode::OK);
+                response.json::<serde_json::Value>().await.unwrap()
+            }
+        };
+        let path = "/mcp/check?version=1.0.0&platform=linux&arch=x86_64";
+        let by_param = check(&format!("{}&dnt=1", path), false).await;
+        let by_header = check(path, true).await;
+        let logged: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM mcp_analytics")
+            .fetch_one(&app.db_pool)
+            .await
+            .unwrap();
+        assert_eq!(logged, 0);
+        assert_eq!(app.app_state.metrics.dnt_checks(), 2);
+
+        // 🟰 Same answer as a tracked check, which is the only one that leaves a row
+        let tracked = check(path, false).await;
+        assert_eq!(by_param, tracked);
+        assert_eq!(by_header, tracked);
+        let logged: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM mcp_analytics")
+            .fetch_one(&app.db_pool)
+            .await
+            .unwrap();
+        assert_eq!(logged, 1);
+
+        let metrics = app
+            .client
+            .get(app.url("/metrics"))
+            .send()
+            .await
+            .unwrap()
+            .text()
+            .await
+            .unwrap();
+        assert!(metrics.contains("\nfeedbacker_mcp_dnt_checks_total 2\n"));
+        println!("✅ MCP Do-Not-Track test passed!");
+    }
+
+    #[tokio::test]
+    async fn test_mcp_check_signs_downloads_for_configured_targets() {
+        let Some(app) = crate::test_support::spawn_test_app().await else {
+            return;
+        };
+        sqlx::query(
+            "INSERT INTO settings (key, value) VALUES ('smart_tree_latest_version', '2.0.0')
+             ON CONFLICT (key) DO UPDATE SET value = EXCLUDED.value",
+        )
+        .execute(&app.db_pool)
+        .await
+        .unwrap();
+        app.app_state.settings.refresh(&app.db_pool).await.unwrap();
+
+        let mut config = (*app.app_state.config).clone();
+        config.downloads = crate::config::DownloadsConfig {
+            artifact_base_url: Some("https://cdn.example.com".to_string()),
+            signing_secret: Some("an-artifact-secret-of-32-chars!!".to_string()),
+            url_ttl_seconds: 300,
+            signed_targets: vec!["smart-tree/linux".to_string()],
+            ..Default::default()
+        };
+        let app_state = AppState {
+            config: std::sync::Arc::new(config),
+            ..app.app_state.clone()
+        };
+        let check = |platform: &str, arch: &str| {
+            let app_state = app_state.clone();
+            let query = McpCheckQuery {
+                version: Some("1.0.0".to_string()),
+                platform: Some(platform.to_string()),
+                arch: Some(arch.to_string()),
+                integration: None,
+                product: None,
+                dnt: None,
+            };
+            async move {
+                let response =
+                    mcp_check(State(app_state), HeaderMap::new(), None, Query(query)).await;
+                let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+                    .await
+                    .unwrap();
+                serde_json::from_slice::<serde_json::Value>(&bytes).unwrap()
+            }
+        };
+
+        let signed = check("linux", "x86_64").await;
+        let url = signed["download_url"].as_str().unwrap();
+        assert!(url.starts_with(
+            "https://cdn.example.com/smart-tree/v2.0.0/smart-tree-linux-x86_64?expires="
+        ));
+        assert!(url.contains("&signature="));
+        assert!(signed["download_expires_at"].is_string());
+
+        // 🐙 Everything else keeps the GitHub release page
+        let github = check("macos", "aarch64").await;
+        assert_eq!(
+            github["download_url"],
+            "https://github.com/8b-is/smart-tree/releases/tag/v2.0.0"
+        );
+        assert!(github.get("download_expires_at").is_none());
+        println!("✅ Signed MCP download test passed!");
+    }
+
+    #[test]
+    fn test_client_info_is_truncated_and_survives_non_utf8() {
+        use axum::http::HeaderValue;
+
+        let mut headers = HeaderMap::new();
+        assert_eq!(
+            McpClientInfo::from_request(&headers, None),
+            McpClientInfo::default()
+        );
+
+        headers.insert(
+            "user-agent",
+            HeaderValue::from_bytes(b"Bot\xff/1.0").unwrap(),
+        );
+        let client = McpClientInfo::from_request(&headers, Some("  vscode  "));
+        assert_eq!(client.user_agent.as_deref(), Some("Bot\u{FFFD}/1.0"));
+        assert_eq!(client.integration.as_deref(), Some("vscode"));
+
+        // ✂️ Counted in characters, so multi-byte text is never cut mid-character
+        let long = "é".repeat(300);
+        headers.insert(
+            "user-agent",
+            HeaderValue::from_bytes(long.as_bytes()).unwrap(),
+        );
+        let client = McpClientInfo::from_request(&headers, Some(&"x".repeat(100)));
+        assert_eq!(
+            client.user_agent.unwrap().chars().count(),
+            USER_AGENT_MAX_CHARS
+        );
+        assert_eq!(
+            client.integration.unwrap().chars().count(),
+            INTEGRATION_MAX_CHARS
+        );
+
+        headers.insert("user-agent", HeaderValue::from_static("   "));
+        assert_eq!(
+            McpClientInfo::from_request(&headers, Some("")),
+            McpClientInfo::default()
+        );
+        println!("✅ MCP client info test passed!");
+    }
+
+    #[tokio::test]
+    async fn test_user_agents_are_stored_and_reported() {
+        let Some(app) = crate::test_support::spawn_test_app().await else {
+            return;
+        };
+        let check = |user_agent: Option<axum::http::HeaderValue>, query: &str| {
+            let mut request = app.client.get(app.url(&format!("/mcp/check?{}", query)));
+            if let Some(user_agent) = user_agent {
+                request = request.header("user-agent", user_agent);
+            }
+            request.send()
+        };
+        let agent = |value: &[u8]| Some(axum::http::HeaderValue::from_bytes(value).unwrap());
+
+        for _ in 0..2 {
+            check(agent(b"acme-bot/2.1"), "version=1.0.0&integration=acme")
+                .await
+                .unwrap();
+        }
+        check(agent(b"weird\xfe-client"), "version=1.0.0")
+            .await
+            .unwrap();
+        check(agent("a".repeat(400).as_bytes()), "version=1.0.0")
+            .await
+            .unwrap();
+
+        let stored: Vec<(Option<String>, Option<String>)> = sqlx::query_as(
+            "SELECT user_agent, integration FROM mcp_analytics ORDER BY checked_at, id",
+        )
+        .fetch_all(&app.db_pool)
+        .await
+        .unwrap();
+        assert_eq!(stored.len(), 4);
+        assert!(stored.contains(&(Some("weird\u{FFFD}-client".to_string()), None)));
+        assert!(stored.contains(&(Some("a".repeat(USER_AGENT_MAX_CHARS)), None)));
+
+        // 🔐 /mcp/stats sits behind API auth, so ask the stats query directly
+        let stats = get_mcp_stats(&app.app_state, None).await.unwrap();
+        assert_eq!(
+            stats.top_user_agents[0].user_agent.as_deref(),
+            Some("acme-bot/2.1")
+        );
+        assert_eq!(stats.top_user_agents[0].count, 2);
+        assert_eq!(stats.recent_checks.len(), 4);
+
+        let filtered = get_mcp_stats(&app.app_state, Some("acme-bot/2.1"))
+            .await
+            .unwrap();
+        assert_eq!(filtered.recent_checks.len(), 2);
+        assert!(filtered
+            .recent_checks
+            .iter()
+            .all(|check| check.integration.as_deref() == Some("acme")));
+
+        app.login_admin().await.unwrap();
+        let page = app
+            .client
+            .get(app.url("/admin/mcp?user_agent=acme-bot%2F2.1"))
+            .send()
+            .await
+            .unwrap()
+            .text()
+            .await
+            .unwrap();
+        assert!(page.contains("Top User Agents"));
+        assert!(page.contains(r#"href="/admin/mcp?user_agent=acme-bot%2F2.1#recent-checks""#));
+        assert!(!page.contains("<td>weird"));
+        println!("✅ MCP user agent reporting test passed!");
+    }
+
+    #[tokio::test]
+    async fn test_set_version_checks_the_github_release() {
+        use crate::database::models::{User, UserRole};
+        use crate::github::releases::ReleaseAsset;
+        use crate::middleware::auth::jwt_utils;
+
+        let Some(app) = crate::test_support::spawn_test_app_with_config(|config| {
+            config.downloads.verify_releases = true;
+        })
+        .await
+        else {
+            return;
+        };
+        let admin: User = sqlx::query_as(
+            "INSERT INTO users (email, name, password_hash, role) \
+             VALUES ('release@example.com', 'Release', 'x', $1) RETURNING *",
+        )
+        .bind(UserRole::Admin)
+        .fetch_one(&app.db_pool)
+        .await
+        .unwrap();
+        let token =
+            jwt_utils::create_jwt_token(&admin, &app.app_state.config.auth.jwt_secret, 1).unwrap();
+        let publish = |body: serde_json::Value| {
+            let request = app
+                .client
+                .post(app.url("/mcp/version"))
+                .bearer_auth(&token)
+                .json(&body);
+            async move {
+                let response = request.send().await.unwrap();
+                let status = response.status();
+                (status, response.json::<serde_json::Value>().await.unwrap())
+            }
+        };
+        let release = |tag: &str, draft: bool| GitHubRelease {
+            tag_name: tag.to_string(),
+            draft,
+            prerelease: false,
+            html_url: format!("https://github.com/8b-is/smart-tree/releases/tag/{}", tag),
+            assets: vec![ReleaseAsset {
+                name: format!("st-{}-x86_64-unknown-linux-gnu.tar.gz", tag),
+                browser_download_url: format!(
+                    "https://github.com/8b-is/smart-tree/releases/download/{}/st-linux.tar.gz",
+                    tag
+                ),
+                size: 4_200_000,
+            }],
+        };
+        let latest = || async {
+            app.app_state.settings.refresh(&app.db_pool).await.unwrap();
+            app.app_state
+                .settings
+                .get()
+                .smart_tree_latest_version
+                .clone()
+        };
+
+        // 🕳️ No such release: nothing is published
+        let (status, body) = publish(serde_json::json!({ "version": "6.0.0" })).await;
+        assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+        assert_eq!(body["success"], false);
+        assert!(body["message"]
+            .as_str()
+            .unwrap()
+            .contains("No GitHub release tagged v6.0.0"));
+        assert_eq!(latest().await, None);
+
+        // 📝 A draft isn't downloadable yet
+        app.github.releases.lock().unwrap().insert(
+            "8b-is/smart-tree:v6.0.0".to_string(),
+            release("v6.0.0", true),
+        );
+        let (status, body) = publish(serde_json::json!({ "version": "6.0.0" })).await;
+        assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+        assert!(body["message"].as_str().unwrap().contains("still a draft"));
+
+        // ✅ A published release goes out with its per-platform downloads
+        app.github.releases.lock().unwrap().insert(
+            "8b-is/smart-tree:v6.0.0".to_string(),
+            release("v6.0.0", false),
+        );
+        let (status, body) = publish(serde_json::json!({ "version": "6.0.0" })).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["downloads"][0]["platform"], "linux");
+        assert_eq!(latest().await.as_deref(), Some("6.0.0"));
+        let check: serde_json::Value = app
+            .client
+            .get(app.url("/mcp/check?version=5.0.0&platform=linux&arch=x86_64"))
+            .send()
+            .await
+            .unwrap()
+            .json()
+            .await
+            .unwrap();
+        assert_eq!(
+            check["download_url"],
+            "https://github.com/8b-is/smart-tree/releases/download/v6.0.0/st-linux.tar.gz"
+        );
+        assert_eq!(check["download_size"], 4_200_000);
+
+        // 🚨 Force publishes anyway, drops the old assets and leaves an audit trail
+        let (status, _) = publish(serde_json::json!({ "version": "6.1.0", "force": true })).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(latest().await.as_deref(), Some("6.1.0"));
+        assert_eq!(
+            app.app_state.settings.get().smart_tree_release_downloads,
+            None
+        );
+        let forced: i64 = sqlx::query_scalar(
+            "SELECT COUNT(*) FROM admin_audit_log WHERE action = 'mcp_version_forced' \
+             AND actor = 'release@example.com' AND details->>'version' = '6.1.0'",
+        )
+        .fetch_one(&app.db_pool)
+        .await
+        .unwrap();
+        assert_eq!(forced, 1);
+        println!("✅ Set version release check test passed!");
+    }
+
+    #[tokio::test]
+    async fn test_mcp_check_reads_release_info_from_the_settings_cache() {
+        let Some(app) = crate::test_support::spawn_test_app().await else {
+            return;
+        };
+        let check = || async {
+            app.client
+                .get(app.url("/mcp/check?version=1.0.0"))
+                .send()
+                .await
+                .unwrap()
+                .json::<serde_json::Value>()
+                .await
+                .unwrap()
+        };
+        sqlx::query(
+            "INSERT INTO settings (key, value) VALUES
+                ('smart_tree_latest_version', '3.0.0'),
+                ('smart_tree_release_notes', 'Faster trees'),
+                ('smart_tree_new_features', '[\"quantum mode\"]')
+             ON CONFLICT (key) DO UPDATE SET value = EXCLUDED.value",
+        )
+        .execute(&app.db_pool)
+        .await
+        .unwrap();
+
+        // 🙈 Not refreshed yet, so the check doesn't know about 3.0.0
+        assert_eq!(check().await["update_available"], false);
+
+        app.app_state.settings.refresh(&app.db_pool).await.unwrap();
+        let body = check().await;
+        assert_eq!(body["latest_version"], "3.0.0");
+        assert_eq!(body["release_notes"], "Faster trees");
+        assert_eq!(body["new_features"], serde_json::json!(["quantum mode"]));
+        println!("✅ MCP check settings cache test passed!");
+    }
+
+    #[tokio::test]
+    async fn test_mcp_check_only_logs_trusted_user_agents() {
+        let Some(app) = crate::test_support::spawn_test_app_with_config(|config| {
+            config.analytics.mcp_trusted_user_agents = vec!["smart-tree/".to_string()];
+        })
+        .await
+        else {
+            return;
+        };
+        let check = |user_agent: &'static str| {
+            app.client
+                .get(app.url("/mcp/check?version=1.0.0&platform=linux&arch=x86_64"))
+                .header("user-agent", user_agent)
+                .send()
+        };
+
+        // 🤷 A browser gets the same answer, but leaves nothing behind
+        let browser = check("Mozilla/5.0 (X11; Linux x86_64)").await.unwrap();
+        assert_eq!(browser.status(), StatusCode::OK);
+        let answer: serde_json::Value = browser.json().await.unwrap();
+        assert_eq!(answer["supported"], true);
+        let client = check("smart-tree/5.2.0").await.unwrap();
+        assert_eq!(client.status(), StatusCode::OK);
+
+        let logged: Vec<Option<String>> =
+            sqlx::query_scalar("SELECT user_agent FROM mcp_analytics")
+                .fetch_all(&app.db_pool)
+                .await
+                .unwrap();
+        assert_eq!(logged, vec![Some("smart-tree/5.2.0".to_string())]);
+        assert_eq!(app.app_state.metrics.untrusted_checks(), 1);
+        println!("✅ Trusted MCP client analytics test passed!");
+    }
+
+    #[tokio::test]
+    async fn test_mcp_check_is_limited_per_ip_and_tracked_on_metrics() {
+        let Some(app) = crate::test_support::spawn_test_app_with_config(|config| {
+            config.rate_limiting.mcp_checks_per_minute = 2;
+            config.features.enable_metrics = true;
+        })
+        .await
+        else {
+            return;
+        };
+        let check = |ip: &'static str| {
+            app.client
+                .get(app.url("/mcp/check?version=1.0.0"))
+                .header("x-forwarded-for", ip)
+                .send()
+        };
+
+        assert_eq!(
+            check("198.51.100.1").await.unwrap().status(),
+            StatusCode::OK
+        );
+        assert_eq!(
+            check("198.51.100.1").await.unwrap().status(),
+            StatusCode::OK
+        );
+        let limited = check("198.51.100.1").await.unwrap();
+        assert_eq!(limited.status(), StatusCode::TOO_MANY_REQUESTS);
+        assert!(limited.headers().contains_key("retry-after"));
+        assert_eq!(
+            check("198.51.100.2").await.unwrap().status(),
+            StatusCode::OK
+        );
+
+        // 🙅 The refused check never reached the analytics table
+        let logged: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM mcp_analytics")
+            .fetch_one(&app.db_pool)
+            .await
+            .unwrap();
+        assert_eq!(logged, 3);
+
+        let metrics = app
+            .client
+            .get(app.url("/metrics"))
+            .send()
+            .await
+            .unwrap()
+            .text()
+            .await
+            .unwrap();
+        assert!(metrics.contains("\nfeedbacker_rate_limit_tracked_keys 2\n"));
+        assert!(metrics.contains("# TYPE feedbacker_rate_limit_evicted_total counter"));
+        assert!(metrics.contains("\nfeedbacker_github_writes_deferred_total 0\n"));
+        assert!(metrics.contains("\nfeedbacker_github_requests_in_flight 0\n"));
+        assert!(metrics.contains("# TYPE feedbacker_github_requests_limit gauge"));
+        println!("✅ MCP check rate limit test passed!");
+    }
+
+    #[tokio::test]
+    async fn test_analytics_store_only_the_anonymized_ip() {
+        let Some(app) = crate::test_support::spawn_test_app().await else {
+            return;
+        };
+        let ip: Option<IpAddr> = Some("203.0.113.77".parse().unwrap());
+        let geo = GeoLocation {
+            country: Some("NZ".to_string()),
+            ..Default::default()
+        };
+
+        for mode in [IpStorageMode::Truncate, IpStorageMode::Hash] {
+            let analytics = AnalyticsConfig {
+                ip_storage: mode,
+                ip_hash_salt: Some("a-very-salty-salt".to_string()),
+                mcp_trusted_user_agents: Vec::new(),
+            };
+            let stored = anonymize_ip(ip, &analytics);
+            log_mcp_analytics(
+                &app.app_state,
+                "1.0.0",
+                "linux",
+                "x86_64",
+                &stored,
+                &geo,
+                &McpClientInfo::default(),
+            )
+            .await
+            .unwrap();
+        }
+
+        let rows: Vec<(Option<String>, Option<String>, Option<String>)> = sqlx::query_as(
+            "SELECT host(ip_address), ip_hash, country FROM mcp_analytics ORDER BY ip_hash NULLS FIRST",
+        )
+        .fetch_all(&app.db_pool)
+        .await
+        .unwrap();
+        assert_eq!(rows.len(), 2);
+
+        // ✂️ Truncated: /24 network address, no hash, geo kept
+        assert_eq!(rows[0].0.as_deref(), Some("203.0.113.0"));
+        assert_eq!(rows[0].1, None);
+        assert_eq!(rows[0].2.as_deref(), Some("NZ"));
+
+        // #️⃣ Hashed: no address at all
+        assert_eq!(rows[1].0, None);
+        assert_eq!(rows[1].1.as_ref().map(String::len), Some(64));
+        assert_eq!(rows[1].2.as_deref(), Some("NZ"));
+        println!("✅ Anonymized analytics IP test passed!");
+    }
+
+    /// 🐘 Thundering herd: run with
+    /// `cargo test settings_loads_stay_flat -- --ignored --nocapture`
+    #[tokio::test]
+    #[ignore = "load test - fires a thousand concurrent version checks"]
+    async fn test_settings_loads_stay_flat_as_concurrency_rises() {
+        let Some(app) = crate::test_support::spawn_test_app_with_config(|config| {
+            config.rate_limiting.mcp_checks_per_minute = 100_000;
+        })
+        .await
+        else {
+            return;
+        };
+        sqlx::query(
+            "INSERT INTO settings (key, value) VALUES ('smart_tree_latest_version', '2.0.0')
+             ON CONFLICT (key) DO UPDATE SET value = EXCLUDED.value",
+        )
+        .execute(&app.db_pool)
+        .await
+        .unwrap();
+        let settings = &app.app_state.settings;
+        settings.refresh(&app.db_pool).await.unwrap();
+        let url = app.url("/mcp/check?version=1.0.0&platform=linux&arch=x86_64");
+
+        for concurrency in [1, 10, 100, 1000] {
+            let before = settings.loads();
+            // 🐢 A busy database: nothing reads the settings table until the herd is through
+            let mut slow = app.db_pool.begin().await.unwrap();
+            sqlx::query("LOCK TABLE settings IN ACCESS EXCLUSIVE MODE")
+                .execute(&mut *slow)
+                .await
+                .unwrap();
+            let started = std::time::Instant::now();
+            // ⏰ The top of the hour: every check arrives alongside a refresh tick
+            let refreshes: Vec<_> = (0..concurrency)
+                .map(|_| {
+                    let settings = std::sync::Arc::clone(settings);
+                    let pool = app.db_pool.clone();
+                    tokio::spawn(async move { settings.refresh(&pool).await })
+                })
+                .collect();
+            let checks: Vec<_> = (0..concurrency)
+                .map(|_| {
+                    let request = app.client.get(&url);
+                    tokio::spawn(async move {
+                        let response = request.send().await.unwrap();
+                        assert_eq!(response.status(), StatusCode::OK);
+                        response.json::<serde_json::Value>().await.unwrap()
+                    })
+                })
+                .collect();
+            // 📸 Every check is answered from the snapshot while the table is still locked
+            for check in checks {
+                let body = tokio::time::timeout(std::time::Duration::from_secs(60), check)
+                    .await
+                    .expect("a version check waited on the settings table")
+                    .unwrap();
+                assert_eq!(body["latest_version"], "2.0.0");
+                assert_eq!(body["update_available"], true);
+            }
+            let answered = started.elapsed();
+            slow.commit().await.unwrap();
+            for refresh in refreshes {
+                refresh.await.unwrap().unwrap();
+            }
+            let loads = settings.loads() - before;
+            println!(
+                "🐘 {:>4} concurrent checks answered in {:?} -> {} settings query",
+                concurrency, answered, loads
+            );
+            assert_eq!(loads, 1);
+        }
+        println!("✅ Settings load test passed!");
+    }
 }
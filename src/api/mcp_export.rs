@@ -0,0 +1,672 @@
+// 📤 MCP Analytics Export - Raw check data out, unwanted rows gone! 📤
+// Admin-only CSV export and purge of `mcp_analytics`, sharing one filter
+// (checked_at range + country). Both walk the table in batches so a
+// multi-million-row table never has to fit in memory, and the export applies
+// the configured IP storage mode so raw addresses never leave when it's on.
+// Created with love by Aye & Hue! ✨
+
+use anyhow::Result;
+use axum::{
+    body::Body,
+    extract::{Query, State},
+    http::{header, StatusCode},
+    response::{IntoResponse, Json, Response},
+};
+use axum_extra::extract::cookie::CookieJar;
+use chrono::{DateTime, NaiveDate, Utc};
+use futures_util::{Stream, TryStreamExt};
+use serde::{Deserialize, Serialize};
+use sqlx::PgPool;
+use tracing::{info, warn};
+use uuid::Uuid;
+
+use crate::{
+    api::{
+        admin::{audit_log, require_admin_api_auth},
+        json::ApiJson,
+        ApiResponse, AppState, ErrorCode,
+    },
+    config::{AnalyticsConfig, IpStorageMode},
+    utils::privacy::anonymize_ip,
+};
+
+/// 📦 Rows fetched per export query
+pub const EXPORT_BATCH_SIZE: i64 = 5_000;
+/// 🗑️ Rows deleted per purge statement
+pub const PURGE_BATCH_SIZE: i64 = 10_000;
+
+/// 🔍 The shared WHERE clause ($1 = from, $2 = to, $3 = country)
+const FILTER_SQL: &str = "($1::timestamptz IS NULL OR checked_at >= $1)
+    AND ($2::timestamptz IS NULL OR checked_at < $2)
+    AND ($3::text IS NULL OR upper(country) = $3)";
+
+/// 🔍 Filter as it arrives in the query string or JSON body
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct AnalyticsFilterParams {
+    /// 📅 Inclusive start: RFC 3339 timestamp or `YYYY-MM-DD`
+    pub from: Option<String>,
+    /// 📅 End: RFC 3339 timestamp (exclusive) or `YYYY-MM-DD` (that whole day included)
+    pub to: Option<String>,
+    /// 🌍 Two-letter country code, case-insensitive
+    pub country: Option<String>,
+}
+
+/// 🔍 A validated filter over `mcp_analytics`
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct AnalyticsFilter {
+    pub from: Option<DateTime<Utc>>,
+    pub to: Option<DateTime<Utc>>,
+    pub country: Option<String>,
+}
+
+impl AnalyticsFilterParams {
+    /// ✅ Parse and validate, collecting every problem
+    pub fn parse(&self) -> std::result::Result<AnalyticsFilter, Vec<String>> {
+        let mut errors = Vec::new();
+
+        let mut bound = |name: &str, value: &Option<String>, end_of_day: bool| {
+            let value = value.as_deref().map(str::trim).filter(|v| !v.is_empty())?;
+            let parsed = parse_bound(value, end_of_day);
+            if parsed.is_none() {
+                errors.push(format!(
+                    "{} must be an RFC 3339 timestamp or a YYYY-MM-DD date",
+                    name
+                ));
+            }
+            parsed
+        };
+        let from = bound("from", &self.from, false);
+        let to = bound("to", &self.to, true);
+
+        let country = self
+            .country
+            .as_deref()
+            .map(str::trim)
+            .filter(|c| !c.is_empty())
+            .map(str::to_ascii_uppercase);
+        if let Some(code) = &country {
+            if code.len() != 2 || !code.chars().all(|c| c.is_ascii_alphabetic()) {
+                errors.push("country must be a two-letter country code".to_string());
+            }
+        }
+
+        if let (Some(from), Some(to)) = (from, to) {
+            if from >= to {
+                errors.push("from must be before to".to_string());
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(AnalyticsFilter { from, to, country })
+        } else {
+            Err(errors)
+        }
+    }
+}
+
+/// 📅 RFC 3339 as-is; a bare date means its midnight (or the next midnight for an end bound)
+fn parse_bound(value: &str, end_of_day: bool) -> Option<DateTime<Utc>> {
+    if let Ok(timestamp) = DateTime::parse_from_rfc3339(value) {
+        return Some(timestamp.with_timezone(&Utc));
+    }
+    let date = NaiveDate::parse_from_str(value, "%Y-%m-%d").ok()?;
+    let date = if end_of_day { date.succ_opt()? } else { date };
+    Some(date.and_hms_opt(0, 0, 0)?.and_utc())
+}
+
+/// 📄 One exported check
+#[derive(Debug, sqlx::FromRow)]
+struct ExportRow {
+    id: Uuid,
+    checked_at: DateTime<Utc>,
+    client_version: String,
+    platform: String,
+    arch: String,
+    ip_address: Option<String>,
+    ip_hash: Option<String>,
+    country: Option<String>,
+    region: Option<String>,
+    city: Option<String>,
+    latitude: Option<f64>,
+    longitude: Option<f64>,
+}
+
+/// 🕶️ Name of the IP column the export carries, if any
+fn ip_column(mode: IpStorageMode) -> Option<&'static str> {
+    match mode {
+        IpStorageMode::Full | IpStorageMode::Truncate => Some("ip_address"),
+        IpStorageMode::Hash => Some("ip_hash"),
+        IpStorageMode::None => None,
+    }
+}
+
+/// 🕶️ The row's IP as the current mode allows it out (older rows may still hold a raw address)
+fn export_ip(row: &ExportRow, analytics: &AnalyticsConfig) -> String {
+    let stored = anonymize_ip(
+        row.ip_address.as_deref().and_then(|ip| ip.parse().ok()),
+        analytics,
+    );
+    let ip = match analytics.ip_storage {
+        IpStorageMode::Hash => row.ip_hash.clone().or(stored.hash),
+        _ => stored.address.map(|address| address.to_string()),
+    };
+    ip.unwrap_or_default()
+}
+
+/// 🧼 Quote a CSV field when it needs it
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// 📋 Header line for the configured IP mode
+fn csv_header(analytics: &AnalyticsConfig) -> String {
+    let mut columns = vec!["checked_at", "client_version", "platform", "arch"];
+    columns.extend(ip_column(analytics.ip_storage));
+    columns.extend(["country", "region", "city", "latitude", "longitude"]);
+    format!("{}\n", columns.join(","))
+}
+
+/// 📝 One CSV line
+fn csv_row(row: &ExportRow, analytics: &AnalyticsConfig) -> String {
+    let optional = |value: &Option<String>| csv_field(value.as_deref().unwrap_or_default());
+    let number = |value: Option<f64>| value.map(|v| v.to_string()).unwrap_or_default();
+
+    let mut fields = vec![
+        row.checked_at.to_rfc3339(),
+        csv_field(&row.client_version),
+        csv_field(&row.platform),
+        csv_field(&row.arch),
+    ];
+    if ip_column(analytics.ip_storage).is_some() {
+        fields.push(csv_field(&export_ip(row, analytics)));
+    }
+    fields.extend([
+        optional(&row.country),
+        optional(&row.region),
+        optional(&row.city),
+        number(row.latitude),
+        number(row.longitude),
+    ]);
+    format!("{}\n", fields.join(","))
+}
+
+/// 🧭 Where the export stream has got to
+struct ExportCursor {
+    pool: PgPool,
+    filter: AnalyticsFilter,
+    analytics: AnalyticsConfig,
+    batch_size: i64,
+    header_sent: bool,
+    after: Option<(DateTime<Utc>, Uuid)>,
+    done: bool,
+}
+
+/// 🌊 CSV for every matching row, oldest first, fetched `batch_size` rows at a time
+/// using keyset pagination on (checked_at, id)
+pub fn csv_stream(
+    pool: PgPool,
+    filter: AnalyticsFilter,
+    analytics: AnalyticsConfig,
+    batch_size: i64,
+) -> impl Stream<Item = std::result::Result<String, sqlx::Error>> + Send + 'static {
+    let cursor = ExportCursor {
+        pool,
+        filter,
+        analytics,
+        batch_size,
+        header_sent: false,
+        after: None,
+        done: false,
+    };
+
+    futures_util::stream::try_unfold(cursor, |mut cursor| async move {
+        if !cursor.header_sent {
+            cursor.header_sent = true;
+            return Ok(Some((csv_header(&cursor.analytics), cursor)));
+        }
+        if cursor.done {
+            return Ok(None);
+        }
+
+        let rows: Vec<ExportRow> = sqlx::query_as(&format!(
+            r#"
+            SELECT id, checked_at, client_version, platform, arch,
+                   host(ip_address) AS ip_address, ip_hash,
+                   country, region, city, latitude, longitude
+            FROM mcp_analytics
+            WHERE {}
+              AND ($4::timestamptz IS NULL OR (checked_at, id) > ($4, $5::uuid))
+            ORDER BY checked_at, id
+            LIMIT $6
+            "#,
+            FILTER_SQL
+        ))
+        .bind(cursor.filter.from)
+        .bind(cursor.filter.to)
+        .bind(&cursor.filter.country)
+        .bind(cursor.after.map(|(at, _)| at))
+        .bind(cursor.after.map(|(_, id)| id))
+        .bind(cursor.batch_size)
+        .fetch_all(&cursor.pool)
+        .await?;
+
+        cursor.done = (rows.len() as i64) < cursor.batch_size;
+        let Some(last) = rows.last() else {
+            return Ok(None);
+        };
+        cursor.after = Some((last.checked_at, last.id));
+
+        let chunk: String = rows
+            .iter()
+            .map(|row| csv_row(row, &cursor.analytics))
+            .collect();
+        Ok(Some((chunk, cursor)))
+    })
+}
+
+/// 🗑️ Delete matching rows `batch_size` at a time; returns how many went
+pub async fn purge_analytics(
+    pool: &PgPool,
+    filter: &AnalyticsFilter,
+    batch_size: i64,
+) -> Result<u64> {
+    let sql = format!(
+        "DELETE FROM mcp_analytics WHERE id IN (SELECT id FROM mcp_analytics WHERE {} LIMIT $4)",
+        FILTER_SQL
+    );
+    let mut deleted = 0;
+    loop {
+        let batch = sqlx::query(&sql)
+            .bind(filter.from)
+            .bind(filter.to)
+            .bind(&filter.country)
+            .bind(batch_size)
+            .execute(pool)
+            .await?
+            .rows_affected();
+        deleted += batch;
+        if (batch as i64) < batch_size {
+            return Ok(deleted);
+        }
+    }
+}
+
+/// 📤 GET /admin/api/mcp/analytics/export.csv?from=&to=&country=
+pub async fn export_analytics_csv(
+    State(app_state): State<AppState>,
+    jar: CookieJar,
+    Query(params): Query<AnalyticsFilterParams>,
+) -> Response {
+    if let Some(denied) = require_admin_api_auth(&jar, &app_state).await {
+        return denied;
+    }
+    let filter = match params.parse() {
+        Ok(filter) => filter,
+        Err(errors) => return crate::api::utils::validation_error(errors).into_response(),
+    };
+    info!("📤 Exporting MCP analytics: {:?}", filter);
+
+    let stream = csv_stream(
+        app_state.db_pool.clone(),
+        filter,
+        app_state.config.analytics.clone(),
+        EXPORT_BATCH_SIZE,
+    )
+    .inspect_err(|e| warn!("❌ MCP analytics export aborted: {}", e));
+
+    (
+        [
+            (header::CONTENT_TYPE, "text/csv; charset=utf-8"),
+            (
+                header::CONTENT_DISPOSITION,
+                "attachment; filename=\"mcp-analytics.csv\"",
+            ),
+        ],
+        Body::from_stream(stream),
+    )
+        .into_response()
+}
+
+/// 🗑️ Purge request: the export filters plus an explicit confirmation
+#[derive(Debug, Deserialize)]
+pub struct PurgeRequest {
+    #[serde(flatten)]
+    pub filter: AnalyticsFilterParams,
+    #[serde(default)]
+    pub confirm: bool,
+}
+
+/// 📊 What a purge removed
+#[derive(Debug, Serialize)]
+pub struct PurgeSummary {
+    pub deleted: u64,
+    pub filter: AnalyticsFilter,
+}
+
+/// 🗑️ POST /admin/api/mcp/analytics/purge
+pub async fn purge_analytics_handler(
+    State(app_state): State<AppState>,
+    jar: CookieJar,
+    ApiJson(request): ApiJson<PurgeRequest>,
+) -> Response {
+    if let Some(denied) = require_admin_api_auth(&jar, &app_state).await {
+        return denied;
+    }
+    let filter = match request.filter.parse() {
+        Ok(filter) => filter,
+        Err(errors) => return crate::api::utils::validation_error(errors).into_response(),
+    };
+    if !request.confirm {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(ApiResponse::<()>::error(
+                ErrorCode::ConfirmationRequired,
+                "Set \"confirm\": true to delete the matching analytics rows".to_string(),
+                Some(serde_json::json!({ "filter": filter })),
+            )),
+        )
+            .into_response();
+    }
+
+    match purge_analytics(&app_state.db_pool, &filter, PURGE_BATCH_SIZE).await {
+        Ok(deleted) => {
+            audit_log(
+                &app_state,
+                &jar,
+                "mcp_analytics_purged",
+                serde_json::json!({ "filter": filter, "deleted": deleted }),
+            )
+            .await;
+            (
+                StatusCode::OK,
+                Json(ApiResponse::success(
+                    format!("Purged {} MCP analytics rows", deleted),
+                    PurgeSummary { deleted, filter },
+                )),
+            )
+                .into_response()
+        }
+        Err(e) => crate::api::utils::handle_error(e).into_response(),
+    }
+}
+
+// 🧪 Tests - Exporting the right rows, forgetting the right rows!
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::spawn_test_app;
+
+    fn params(
+        from: Option<&str>,
+        to: Option<&str>,
+        country: Option<&str>,
+    ) -> AnalyticsFilterParams {
+        AnalyticsFilterParams {
+            from: from.map(str::to_string),
+            to: to.map(str::to_string),
+            country: country.map(str::to_string),
+        }
+    }
+
+    fn analytics(mode: IpStorageMode) -> AnalyticsConfig {
+        AnalyticsConfig {
+            ip_storage: mode,
+            ip_hash_salt: Some("a-very-salty-salt".to_string()),
+            mcp_trusted_user_agents: Vec::new(),
+        }
+    }
+
+    /// 🌱 Five checks over three days, each with a raw IP
+    async fn seed(pool: &PgPool) {
+        let rows = [
+            ("2026-03-01T10:00:00Z", "US", "203.0.113.10"),
+            ("2026-03-01T23:59:59Z", "DE", "203.0.113.11"),
+            ("2026-03-02T00:00:00Z", "US", "203.0.113.12"),
+            ("2026-03-02T12:00:00Z", "us", "203.0.113.13"),
+            ("2026-03-03T08:00:00Z", "JP", "2001:db8:abcd:12::1"),
+        ];
+        for (at, country, ip) in rows {
+            sqlx::query(
+                "INSERT INTO mcp_analytics (client_version, platform, arch, checked_at, ip_address, country, city)
+                 VALUES ('5.0.0', 'linux', 'x86_64', $1::timestamptz, $2::inet, $3, 'Springfield, \"North\"')",
+            )
+            .bind(at)
+            .bind(ip)
+            .bind(country)
+            .execute(pool)
+            .await
+            .unwrap();
+        }
+    }
+
+    async fn export(pool: &PgPool, filter: AnalyticsFilter, mode: IpStorageMode) -> Vec<String> {
+        let chunks: Vec<String> = csv_stream(pool.clone(), filter, analytics(mode), 2)
+            .try_collect()
+            .await
+            .unwrap();
+        chunks.concat().lines().map(str::to_string).collect()
+    }
+
+    fn filter(from: Option<&str>, to: Option<&str>, country: Option<&str>) -> AnalyticsFilter {
+        params(from, to, country).parse().unwrap()
+    }
+
+    #[test]
+    fn test_filter_parsing() {
+        let parsed = filter(Some("2026-03-01"), Some("2026-03-02"), Some(" us "));
+        assert_eq!(
+            parsed.from,
+            Some("2026-03-01T00:00:00Z".parse::<DateTime<Utc>>().unwrap())
+        );
+        // 📅 A bare end date covers that whole day
+        assert_eq!(
+            parsed.to,
+            Some("2026-03-03T00:00:00Z".parse::<DateTime<Utc>>().unwrap())
+        );
+        assert_eq!(parsed.country.as_deref(), Some("US"));
+
+        let exact = filter(Some("2026-03-01T12:00:00+02:00"), None, Some(""));
+        assert_eq!(
+            exact.from,
+            Some("2026-03-01T10:00:00Z".parse::<DateTime<Utc>>().unwrap())
+        );
+        assert_eq!(exact.country, None);
+        assert_eq!(
+            params(None, None, None).parse(),
+            Ok(AnalyticsFilter::default())
+        );
+
+        let errors = params(Some("yesterday"), None, Some("USA"))
+            .parse()
+            .unwrap_err();
+        assert_eq!(errors.len(), 2);
+        assert!(params(Some("2026-03-02"), Some("2026-03-01"), None)
+            .parse()
+            .is_err());
+        println!("✅ Analytics filter parsing test passed!");
+    }
+
+    #[test]
+    fn test_csv_field_quoting() {
+        assert_eq!(csv_field("linux"), "linux");
+        assert_eq!(csv_field("a,b"), "\"a,b\"");
+        assert_eq!(csv_field("say \"hi\""), "\"say \"\"hi\"\"\"");
+        assert_eq!(csv_field("two\nlines"), "\"two\nlines\"");
+        println!("✅ CSV quoting test passed!");
+    }
+
+    #[tokio::test]
+    async fn test_export_filter_combinations() {
+        let Some(app) = spawn_test_app().await else {
+            return;
+        };
+        seed(&app.db_pool).await;
+        let count = |lines: &[String]| lines.len() - 1;
+
+        // 🌊 Batches of 2 still yield every row, in order
+        let all = export(
+            &app.db_pool,
+            AnalyticsFilter::default(),
+            IpStorageMode::Full,
+        )
+        .await;
+        assert_eq!(count(&all), 5);
+        assert_eq!(
+            all[0],
+            "checked_at,client_version,platform,arch,ip_address,country,region,city,latitude,longitude"
+        );
+        assert!(
+            all[1].starts_with("2026-03-01T10:00:00+00:00,5.0.0,linux,x86_64,203.0.113.10,US,,")
+        );
+        assert!(all[1].contains(",\"Springfield, \"\"North\"\"\","));
+        assert!(all[5].contains("2001:db8:abcd:12::1"));
+
+        let cases = [
+            (filter(None, None, Some("us")), 3),
+            (filter(Some("2026-03-02"), None, None), 3),
+            (filter(None, Some("2026-03-01"), None), 2),
+            (
+                filter(Some("2026-03-02"), Some("2026-03-02"), Some("US")),
+                2,
+            ),
+            (
+                filter(
+                    Some("2026-03-02T00:00:00Z"),
+                    Some("2026-03-02T12:00:00Z"),
+                    None,
+                ),
+                1,
+            ),
+            (filter(None, None, Some("FR")), 0),
+        ];
+        for (filter, expected) in cases {
+            let lines = export(&app.db_pool, filter.clone(), IpStorageMode::Full).await;
+            assert_eq!(count(&lines), expected, "filter {:?}", filter);
+        }
+
+        // 🔐 The HTTP endpoint is admin-only and streams CSV
+        let denied = app
+            .client
+            .get(app.url("/admin/api/mcp/analytics/export.csv"))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(denied.status(), 401);
+        app.login_admin().await.unwrap();
+        let response = app
+            .client
+            .get(app.url("/admin/api/mcp/analytics/export.csv?country=de"))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), 200);
+        assert!(response.headers()[header::CONTENT_TYPE]
+            .to_str()
+            .unwrap()
+            .starts_with("text/csv"));
+        assert_eq!(response.text().await.unwrap().lines().count(), 2);
+        let invalid = app
+            .client
+            .get(app.url("/admin/api/mcp/analytics/export.csv?from=soon"))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(invalid.status(), 400);
+        println!("✅ MCP analytics export filter test passed!");
+    }
+
+    #[tokio::test]
+    async fn test_export_never_leaks_raw_ips_when_anonymized() {
+        let Some(app) = spawn_test_app().await else {
+            return;
+        };
+        seed(&app.db_pool).await;
+        let everything = AnalyticsFilter::default();
+
+        let truncated = export(&app.db_pool, everything.clone(), IpStorageMode::Truncate).await;
+        assert!(truncated[1].contains(",203.0.113.0,"));
+        assert!(truncated[5].contains(",2001:db8:abcd::,"));
+        assert!(!truncated.concat().contains("203.0.113.1"));
+
+        let hashed = export(&app.db_pool, everything.clone(), IpStorageMode::Hash).await;
+        assert!(hashed[0].contains(",ip_hash,"));
+        assert!(!hashed[0].contains("ip_address"));
+        assert!(!hashed.concat().contains("203.0.113"));
+        assert!(hashed[1].contains(&crate::utils::privacy::hash_ip(
+            "203.0.113.10".parse().unwrap(),
+            "a-very-salty-salt"
+        )));
+
+        let dropped = export(&app.db_pool, everything, IpStorageMode::None).await;
+        assert!(!dropped[0].contains("ip_"));
+        assert!(!dropped.concat().contains("203.0.113"));
+        assert!(!dropped.concat().contains("2001:db8"));
+        println!("✅ Anonymized export test passed!");
+    }
+
+    #[tokio::test]
+    async fn test_purge_requires_confirmation_and_is_audited() {
+        let Some(app) = spawn_test_app().await else {
+            return;
+        };
+        seed(&app.db_pool).await;
+        app.login_admin().await.unwrap();
+        let remaining = || async {
+            sqlx::query_scalar::<_, i64>("SELECT COUNT(*) FROM mcp_analytics")
+                .fetch_one(&app.db_pool)
+                .await
+                .unwrap()
+        };
+        let purge = |body: serde_json::Value| {
+            app.client
+                .post(app.url("/admin/api/mcp/analytics/purge"))
+                .json(&body)
+                .send()
+        };
+
+        // 🛑 No confirm, no delete
+        let refused = purge(serde_json::json!({ "country": "US" })).await.unwrap();
+        assert_eq!(refused.status(), 400);
+        let body: serde_json::Value = refused.json().await.unwrap();
+        assert_eq!(body["error"]["code"], "confirmation_required");
+        assert_eq!(remaining().await, 5);
+
+        let purged: serde_json::Value = purge(serde_json::json!({
+            "country": "us",
+            "from": "2026-03-02",
+            "confirm": true
+        }))
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+        assert_eq!(purged["data"]["deleted"], 2);
+        assert_eq!(remaining().await, 3);
+
+        let (action, details): (String, serde_json::Value) = sqlx::query_as(
+            "SELECT action, details FROM admin_audit_log WHERE action = 'mcp_analytics_purged'",
+        )
+        .fetch_one(&app.db_pool)
+        .await
+        .unwrap();
+        assert_eq!(action, "mcp_analytics_purged");
+        assert_eq!(details["deleted"], 2);
+        assert_eq!(details["filter"]["country"], "US");
+        assert_eq!(details["filter"]["from"], "2026-03-02T00:00:00Z");
+
+        // 🗑️ Batching keeps going until nothing matches
+        assert_eq!(
+            purge_analytics(&app.db_pool, &AnalyticsFilter::default(), 2)
+                .await
+                .unwrap(),
+            3
+        );
+        assert_eq!(remaining().await, 0);
+        println!("✅ MCP analytics purge test passed!");
+    }
+}
@@ -0,0 +1,248 @@
+// 📡 MCP Metrics - Smart Tree adoption as Prometheus gauges! 📡
+// GET /mcp/metrics renders the mcp_analytics aggregates (checks per platform, arch
+// and version, and per country) in the Prometheus text format, so adoption shows up
+// in monitoring without a custom exporter. Routed next to /metrics when
+// ENABLE_METRICS is on. The rendered text is reused for MCP_METRICS_TTL, so a scrape
+// every few seconds runs the GROUP BYs at most twice a minute.
+// Clients choose their own version strings, so only the biggest MAX_SERIES label sets
+// are exported one by one and the rest are summed under "other".
+// Created with love by Aye & Hue! ✨
+
+use anyhow::{Context, Result};
+use axum::{
+    extract::State,
+    http::{header, StatusCode},
+    response::{IntoResponse, Response},
+};
+use sqlx::PgPool;
+use std::fmt::Write;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tracing::error;
+
+use crate::api::AppState;
+
+/// ⏰ How long a rendered scrape is reused
+pub const MCP_METRICS_TTL: Duration = Duration::from_secs(30);
+/// 📏 Label sets exported individually per metric (the rest become "other")
+pub const MAX_SERIES: usize = 200;
+/// 🏷️ Label value for everything past MAX_SERIES, and for checks without a country
+const OTHER: &str = "other";
+const UNKNOWN_COUNTRY: &str = "unknown";
+
+/// 🗃️ The last rendered scrape
+#[derive(Debug, Default)]
+pub struct McpMetricsCache {
+    entry: Mutex<Option<(Instant, Arc<String>)>>,
+}
+
+impl McpMetricsCache {
+    /// 📸 The cached text, re-rendered when older than MCP_METRICS_TTL
+    pub async fn render(&self, pool: &PgPool) -> Result<Arc<String>> {
+        if let Some((stored_at, text)) = self.entry.lock().unwrap().as_ref() {
+            if stored_at.elapsed() < MCP_METRICS_TTL {
+                return Ok(text.clone());
+            }
+        }
+        // 🏃 Concurrent refreshes may both query; the last one wins, which is harmless
+        let text = Arc::new(render_mcp_metrics(pool).await?);
+        *self.entry.lock().unwrap() = Some((Instant::now(), text.clone()));
+        Ok(text)
+    }
+}
+
+/// 📡 GET /mcp/metrics - MCP analytics in the Prometheus text format
+pub async fn mcp_metrics(State(app_state): State<AppState>) -> Response {
+    match app_state.mcp_metrics.render(&app_state.db_pool).await {
+        Ok(text) => (
+            [(
+                header::CONTENT_TYPE,
+                "text/plain; version=0.0.4; charset=utf-8",
+            )],
+            text.as_str().to_owned(),
+        )
+            .into_response(),
+        Err(e) => {
+            error!("❌ Failed to render MCP metrics: {:#}", e);
+            // 📉 A failed scrape shows up as `up == 0`, which is what we want
+            (StatusCode::SERVICE_UNAVAILABLE, "MCP metrics unavailable\n").into_response()
+        }
+    }
+}
+
+/// 📜 Run the aggregations and render them
+async fn render_mcp_metrics(pool: &PgPool) -> Result<String> {
+    let checks: Vec<(String, String, String, i64)> = sqlx::query_as(
+        r#"
+        SELECT platform, arch, client_version, COUNT(*)
+        FROM mcp_analytics
+        GROUP BY platform, arch, client_version
+        ORDER BY COUNT(*) DESC, platform, arch, client_version
+        "#,
+    )
+    .fetch_all(pool)
+    .await
+    .context("Failed to aggregate MCP checks")?;
+    let countries: Vec<(Option<String>, i64)> = sqlx::query_as(
+        r#"
+        SELECT country, COUNT(*)
+        FROM mcp_analytics
+        GROUP BY country
+        ORDER BY COUNT(*) DESC, country
+        "#,
+    )
+    .fetch_all(pool)
+    .await
+    .context("Failed to aggregate MCP countries")?;
+
+    let mut out = String::new();
+    let _ = writeln!(
+        out,
+        "# HELP mcp_checks_total Smart Tree version checks recorded, by platform, arch and client version"
+    );
+    let _ = writeln!(out, "# TYPE mcp_checks_total gauge");
+    for (labels, count) in capped(
+        checks
+            .into_iter()
+            .map(|(platform, arch, version, count)| ([platform, arch, version], count)),
+        [OTHER.to_string(), OTHER.to_string(), OTHER.to_string()],
+    ) {
+        let [platform, arch, version] = labels;
+        let _ = writeln!(
+            out,
+            "mcp_checks_total{{platform=\"{}\",arch=\"{}\",version=\"{}\"}} {}",
+            escape_label(&platform),
+            escape_label(&arch),
+            escape_label(&version),
+            count
+        );
+    }
+
+    let _ = writeln!(
+        out,
+        "# HELP mcp_checks_by_country Smart Tree version checks recorded, by country (ISO code)"
+    );
+    let _ = writeln!(out, "# TYPE mcp_checks_by_country gauge");
+    for (country, count) in capped(
+        countries.into_iter().map(|(country, count)| {
+            (
+                country.unwrap_or_else(|| UNKNOWN_COUNTRY.to_string()),
+                count,
+            )
+        }),
+        OTHER.to_string(),
+    ) {
+        let _ = writeln!(
+            out,
+            "mcp_checks_by_country{{country=\"{}\"}} {}",
+            escape_label(&country),
+            count
+        );
+    }
+    Ok(out)
+}
+
+/// ✂️ The first MAX_SERIES rows as they are, the rest summed into one `other` row
+fn capped<L>(rows: impl Iterator<Item = (L, i64)>, other: L) -> Vec<(L, i64)> {
+    let mut kept = Vec::new();
+    let mut rest = 0;
+    for (index, (labels, count)) in rows.enumerate() {
+        if index < MAX_SERIES {
+            kept.push((labels, count));
+        } else {
+            rest += count;
+        }
+    }
+    if rest > 0 {
+        kept.push((other, rest));
+    }
+    kept
+}
+
+/// 🔤 Escape a label value for the text format (backslash, quote and newline)
+pub fn escape_label(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+// 🧪 Tests - Scraping our own adoption!
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn log_check(pool: &PgPool, version: &str, platform: &str, country: Option<&str>) {
+        sqlx::query(
+            "INSERT INTO mcp_analytics (client_version, platform, arch, country) VALUES ($1, $2, 'x86_64', $3)",
+        )
+        .bind(version)
+        .bind(platform)
+        .bind(country)
+        .execute(pool)
+        .await
+        .unwrap();
+    }
+
+    #[test]
+    fn test_labels_are_escaped_and_series_capped() {
+        assert_eq!(escape_label("a\"b\\c\nd"), "a\\\"b\\\\c\\nd");
+        let rows = (0..MAX_SERIES as i64 + 3).map(|n| (n.to_string(), 1));
+        let kept = capped(rows, "other".to_string());
+        assert_eq!(kept.len(), MAX_SERIES + 1);
+        assert_eq!(kept.last().unwrap(), &("other".to_string(), 3));
+        println!("✅ MCP metrics label test passed!");
+    }
+
+    #[tokio::test]
+    async fn test_mcp_metrics_are_scrapeable_and_cached() {
+        let Some(app) = crate::test_support::spawn_test_app_with_config(|config| {
+            config.features.enable_metrics = true;
+        })
+        .await
+        else {
+            return;
+        };
+        for _ in 0..2 {
+            log_check(&app.db_pool, "5.2.0", "linux", Some("NZ")).await;
+        }
+        log_check(&app.db_pool, "5.1.0", "macos", Some("DE")).await;
+        log_check(&app.db_pool, "5.2.0\"evil", "linux", None).await;
+
+        // 🔓 No token needed, like /metrics
+        let scrape = || async {
+            let response = app
+                .client
+                .get(app.url("/mcp/metrics"))
+                .send()
+                .await
+                .unwrap();
+            assert_eq!(response.status(), StatusCode::OK);
+            assert!(response.headers()[header::CONTENT_TYPE]
+                .to_str()
+                .unwrap()
+                .starts_with("text/plain; version=0.0.4"));
+            response.text().await.unwrap()
+        };
+        let text = scrape().await;
+        assert!(text.contains("# TYPE mcp_checks_total gauge\n"));
+        assert!(text.contains(
+            "\nmcp_checks_total{platform=\"linux\",arch=\"x86_64\",version=\"5.2.0\"} 2\n"
+        ));
+        assert!(text.contains(
+            "\nmcp_checks_total{platform=\"macos\",arch=\"x86_64\",version=\"5.1.0\"} 1\n"
+        ));
+        assert!(text.contains("version=\"5.2.0\\\"evil\"} 1\n"));
+        assert!(text.contains("\nmcp_checks_by_country{country=\"NZ\"} 2\n"));
+        assert!(text.contains("\nmcp_checks_by_country{country=\"unknown\"} 1\n"));
+
+        // 🗃️ New checks show up once the cached scrape expires, not before
+        log_check(&app.db_pool, "5.3.0", "windows", Some("NZ")).await;
+        assert_eq!(scrape().await, text);
+        *app.app_state.mcp_metrics.entry.lock().unwrap() = None;
+        assert!(scrape()
+            .await
+            .contains("\nmcp_checks_by_country{country=\"NZ\"} 3\n"));
+        println!("✅ MCP metrics scrape test passed!");
+    }
+}
@@ -10,79 +10,190 @@ use sqlx::PgPool;
 use std::sync::Arc;
 use uuid::Uuid;
 
-use crate::config::Config;
+use crate::{
+    config::Config,
+    github::{
+        client::GitHubClient, concurrency::RequestLimiter, cooldown::CooldownGate, ops::GitHubOps,
+        throttle::WriteThrottle,
+    },
+    llm::{LlmClient, LlmOps},
+};
 
 // 📦 Re-export all our API modules
 pub mod admin; // 🔧 Admin interface
+pub mod assets; // 🎨 Embedded static assets (CSS)
+pub mod attachments; // 📎 Feedback attachments (logs, screenshots) and their downloads
 pub mod auth; // 🔐 Authentication endpoints
+pub mod callback_secrets; // 🔄 Callback secret rotation (admin)
+pub mod challenge; // 🧩 CAPTCHA / proof-of-work for anonymous submissions
+#[cfg(feature = "dev-tools")]
+pub mod dev; // 🌱 Development seed data (dev-tools builds only, never in production)
+pub mod downloads; // 📦 Release download links (GitHub or signed artifact URLs)
+pub mod events; // 📣 Bounded event bus and the admin live-update stream
 pub mod feedback; // 📝 Feedback submission and management
+pub mod feedback_form; // 📮 Public HTML feedback form
+pub mod feedback_import; // 📦 Bulk import of historical feedback from CSV or JSON (admin)
 pub mod health; // 💚 Health check endpoints
+pub mod intake; // 🗂️ Versioned feedback submission bodies (api_version / vnd media type)
 pub mod issue_hooks; // 🎯 GitHub issue automation
+pub mod json; // 🧾 JSON body extractor with structured parse errors
+pub mod labels; // 🔤 Decorative emoji handling for rendered pages (accessibility)
 pub mod mcp; // 🤖 MCP (Model Context Protocol) for Smart Tree
+pub mod mcp_export; // 📤 MCP analytics CSV export and purge (admin)
+pub mod mcp_metrics; // 📡 MCP analytics as Prometheus gauges (/mcp/metrics)
+pub mod my_feedback; // 🙋 A signed-in user's own feedback: list, details, withdraw
+pub mod project_import; // 🗂️ Bulk project configuration from a CSV upload (admin)
 pub mod projects; // 🏠 Project management endpoints
+pub mod queue_stats; // ⏳ Cached queue position and duration estimates
+pub mod releases; // 📜 Release history and /mcp/changelog
+pub mod saved_views; // 🔖 Named feedback list filters per admin
+pub use feedbacker::api::settings_cache; // ⚡ Runtime settings overrides (lives in the library crate)
 pub mod smart_tree; // 🌳 Smart Tree integration
+pub mod sources; // 📡 Feedback submission channels (admin filter and breakdown)
+pub mod stats_history; // 📈 Nightly statistics snapshots and trend history (admin)
 pub mod status; // 📊 Status checking endpoints
+pub mod status_stats; // 🌐 Public service status from the refreshed stats view
+pub mod tags; // 🏷️ Feedback tags and tag statistics (admin)
 pub mod web; // 🎨 Web UI endpoints
 pub mod webhooks; // 🪝 GitHub webhook handlers
 
 /// 🎯 Application state shared across all handlers
 /// This contains everything our API endpoints need to function!
-#[derive(Debug, Clone)]
+#[derive(Clone)]
 pub struct AppState {
     /// ⚙️ Application configuration
     pub config: Arc<Config>,
     /// 🗄️ Database connection pool
     pub db_pool: PgPool,
-    // 🤖 LLM client manager (will be added when we create LLM module)
-    // pub llm_manager: Arc<crate::llm::LlmManager>,
-    // 🐙 GitHub client (will be added when we create GitHub module)
-    // pub github_client: Arc<crate::github::GitHubClient>,
+    /// 🐙 GitHub operations (the real client, or a fake in tests)
+    pub github: Arc<dyn GitHubOps>,
+    /// 🤖 LLM operations (the real client, or a fake in tests)
+    pub llm: Arc<dyn LlmOps>,
+    /// 🗃️ Short-lived admin dashboard statistics, keyed by time range
+    pub dashboard_cache: Arc<admin::DashboardCache>,
+    /// 📈 In-process counters (caught panics, ...)
+    pub metrics: Arc<crate::metrics::Metrics>,
+    /// 📡 Last /mcp/metrics scrape, reused briefly
+    pub mcp_metrics: Arc<mcp_metrics::McpMetricsCache>,
+    /// 🚦 Per-IP allowances shared by feedback ingestion and /mcp/check
+    pub rate_limiter: Arc<crate::middleware::rate_limiting::IpRateLimiter>,
+    /// ⏳ Shared pending-queue snapshot behind the status estimates
+    pub queue_stats: Arc<queue_stats::QueueStatsCache>,
+    /// 🔗 What GitHub last said about feedback pull requests and issues (briefly reused)
+    pub github_states: Arc<crate::github::artifacts::ArtifactStateCache>,
+    /// 🚰 GitHub write budget (the real client draws from it; /metrics reports it)
+    pub github_throttle: Arc<WriteThrottle>,
+    /// 🎟️ GitHub requests in flight (the real client takes its slots here; /metrics reports it)
+    pub github_requests: Arc<RequestLimiter>,
+    /// ⚡ Runtime settings overrides, reloaded in the background instead of per request
+    pub settings: Arc<settings_cache::SettingsCache>,
+    /// 🎲 1-in-N logging for the handlers every client polls
+    pub log_samplers: Arc<crate::utils::log_sampling::LogSamplers>,
+    /// 🗄️ Where attachment bytes are stored (local directory or S3 bucket)
+    pub blobs: Arc<dyn crate::storage::BlobStore>,
+    /// 📣 Live feedback events for admin subscribers (bounded, see EVENT_BUS_CAPACITY)
+    pub events: Arc<events::EventBus>,
+    /// 🧾 Proof-of-work challenges already redeemed (ANON_CHALLENGE=pow)
+    pub spent_challenges: Arc<challenge::SpentChallenges>,
+    /// 🛡️ Recently accepted webhook signatures (bounded, see GITHUB_WEBHOOK_REPLAY_CACHE_SIZE)
+    pub webhook_replays: Arc<crate::utils::deliveries::ReplayCache>,
 }
 
 impl AppState {
-    /// ➕ Create a new application state instance
-    pub fn new(config: Config, db_pool: PgPool) -> Self {
+    /// ➕ Create a new application state instance with the real GitHub and LLM clients
+    pub fn new(config: Config, db_pool: PgPool) -> anyhow::Result<Self> {
+        let github_throttle = Arc::new(WriteThrottle::from_config(&config.github));
+        let github_requests = Arc::new(RequestLimiter::from_config(&config.github));
+        let github = Arc::new(
+            GitHubClient::new(
+                &config.github.token,
+                &config.github.api_base_url,
+                github_throttle.clone(),
+                CooldownGate::from_config(&config.github),
+                github_requests.clone(),
+            )?
+            .with_installations(db_pool.clone()),
+        );
+        let llm = Arc::new(LlmClient::new(config.llm.clone())?);
+        let blobs = crate::storage::from_config(&config.attachments)?;
+        Ok(Self {
+            github_throttle,
+            github_requests,
+            blobs,
+            ..Self::with_clients(config, db_pool, github, llm)
+        })
+    }
+
+    /// 🧩 Create an application state with explicit GitHub and LLM implementations
+    /// (attachments always go to the local directory here)
+    pub fn with_clients(
+        config: Config,
+        db_pool: PgPool,
+        github: Arc<dyn GitHubOps>,
+        llm: Arc<dyn LlmOps>,
+    ) -> Self {
+        let rate_limiter = Arc::new(
+            crate::middleware::rate_limiting::IpRateLimiter::from_config(&config.rate_limiting),
+        );
+        let github_throttle = Arc::new(WriteThrottle::from_config(&config.github));
+        let github_requests = Arc::new(RequestLimiter::from_config(&config.github));
+        let log_samplers = Arc::new(crate::utils::log_sampling::LogSamplers::from_config(
+            &config.logging,
+        ));
+        let blobs = Arc::new(crate::storage::local::LocalStore::new(
+            &config.attachments.local_dir,
+        ));
+        let webhook_replays = Arc::new(crate::utils::deliveries::ReplayCache::new(
+            config.github.webhook_replay_cache_size,
+        ));
+        // 🧾 Every LLM call is recorded for the provider health report
+        let settings: Arc<settings_cache::SettingsCache> = Arc::default();
+        let llm = Arc::new(crate::llm::usage::RecordedLlm::new(
+            llm,
+            db_pool.clone(),
+            settings.clone(),
+        ));
         Self {
             config: Arc::new(config),
             db_pool,
-            // These will be uncommented when we create the respective modules
-            // llm_manager: Arc::new(crate::llm::LlmManager::new(&config.llm)),
-            // github_client: Arc::new(crate::github::GitHubClient::new(&config.github)),
+            github,
+            llm,
+            dashboard_cache: Arc::default(),
+            github_states: Arc::default(),
+            events: Arc::default(),
+            spent_challenges: Arc::default(),
+            webhook_replays,
+            metrics: Arc::default(),
+            mcp_metrics: Arc::default(),
+            rate_limiter,
+            queue_stats: Arc::default(),
+            github_throttle,
+            github_requests,
+            settings,
+            log_samplers,
+            blobs,
         }
     }
-}
 
-/// 📝 Standard API response structure
-/// Provides consistent response format across all endpoints
-#[derive(Debug, Serialize, Deserialize)]
-pub struct ApiResponse<T> {
-    /// ✅ Whether the operation was successful
-    pub success: bool,
-    /// 📝 Human-readable message
-    pub message: String,
-    /// 📊 Response data (optional)
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub data: Option<T>,
-    /// ❌ Error details (only present if success = false)
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub error: Option<ApiError>,
-    /// ⏰ Response timestamp
-    pub timestamp: chrono::DateTime<chrono::Utc>,
+    /// 🧾 Start a transaction for a handler's writes (commit it with `finish`/`commit`;
+    /// dropping it rolls back)
+    pub async fn tx(&self) -> anyhow::Result<crate::database::tx::Tx> {
+        crate::database::tx::Tx::begin(&self.db_pool).await
+    }
 }
 
-/// ❌ API error structure
-/// Provides structured error information for debugging and user feedback
-#[derive(Debug, Serialize, Deserialize)]
-pub struct ApiError {
-    /// 🎯 Error code for programmatic handling
-    pub code: String,
-    /// 📝 Human-readable error message
-    pub message: String,
-    /// 🔍 Additional error details (optional)
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub details: Option<serde_json::Value>,
+impl std::fmt::Debug for AppState {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AppState")
+            .field("config", &self.config)
+            .field("db_pool", &self.db_pool)
+            .finish_non_exhaustive()
+    }
 }
 
+// 🤝 The envelope and its error codes are shared with API clients
+pub use crate::feedbacker_types::{ApiError, ApiResponse, ErrorCode};
+
 impl<T> ApiResponse<T> {
     /// ✅ Create a successful response
     pub fn success(message: String, data: T) -> Self {
@@ -108,7 +219,7 @@ impl<T> ApiResponse<T> {
 
     /// ❌ Create an error response
     pub fn error(
-        code: String,
+        code: ErrorCode,
         message: String,
         details: Option<serde_json::Value>,
     ) -> ApiResponse<()> {
@@ -260,7 +371,7 @@ pub mod utils {
         tracing::error!("API error: {}", error_msg);
 
         let api_response = ApiResponse::<()>::error(
-            "internal_error".to_string(),
+            ErrorCode::InternalError,
             "An internal error occurred".to_string(),
             Some(serde_json::json!({ "details": error_msg })),
         );
@@ -271,7 +382,7 @@ pub mod utils {
     /// ✅ Create a validation error response
     pub fn validation_error(errors: Vec<String>) -> impl IntoResponse {
         let api_response = ApiResponse::<()>::error(
-            "validation_error".to_string(),
+            ErrorCode::ValidationError,
             "Request validation failed".to_string(),
             Some(serde_json::json!({ "errors": errors })),
         );
@@ -281,11 +392,8 @@ pub mod utils {
 
     /// 🔍 Create a not found error response
     pub fn not_found_error(resource: &str) -> impl IntoResponse {
-        let api_response = ApiResponse::<()>::error(
-            "not_found".to_string(),
-            format!("{} not found", resource),
-            None,
-        );
+        let api_response =
+            ApiResponse::<()>::error(ErrorCode::NotFound, format!("{} not found", resource), None);
 
         (StatusCode::NOT_FOUND, Json(api_response))
     }
@@ -293,7 +401,7 @@ pub mod utils {
     /// 🚫 Create an unauthorized error response
     pub fn unauthorized_error() -> impl IntoResponse {
         let api_response = ApiResponse::<()>::error(
-            "unauthorized".to_string(),
+            ErrorCode::Unauthorized,
             "Authentication required".to_string(),
             None,
         );
@@ -304,20 +412,25 @@ pub mod utils {
     /// 🛡️ Create a forbidden error response
     pub fn forbidden_error() -> impl IntoResponse {
         let api_response =
-            ApiResponse::<()>::error("forbidden".to_string(), "Access denied".to_string(), None);
+            ApiResponse::<()>::error(ErrorCode::Forbidden, "Access denied".to_string(), None);
 
         (StatusCode::FORBIDDEN, Json(api_response))
     }
 
-    /// 🚦 Create a rate limit error response
-    pub fn rate_limit_error() -> impl IntoResponse {
+    /// 🚦 Create a rate limit error response telling the client when to come back
+    pub fn rate_limit_error(retry_after: std::time::Duration) -> impl IntoResponse {
+        let seconds = retry_after.as_secs().max(1);
         let api_response = ApiResponse::<()>::error(
-            "rate_limit_exceeded".to_string(),
+            ErrorCode::RateLimitExceeded,
             "Rate limit exceeded. Please try again later.".to_string(),
-            None,
+            Some(serde_json::json!({ "retry_after_seconds": seconds })),
         );
 
-        (StatusCode::TOO_MANY_REQUESTS, Json(api_response))
+        (
+            StatusCode::TOO_MANY_REQUESTS,
+            [(axum::http::header::RETRY_AFTER, seconds.to_string())],
+            Json(api_response),
+        )
     }
 }
 
@@ -325,7 +438,6 @@ pub mod utils {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use serde_json;
 
     #[test]
     fn test_api_response_success() {
@@ -344,7 +456,7 @@ mod tests {
     #[test]
     fn test_api_response_error() {
         let response = ApiResponse::<()>::error(
-            "test_error".to_string(),
+            ErrorCode::ValidationError,
             "Test error message".to_string(),
             Some(serde_json::json!({"detail": "more info"})),
         );
@@ -354,7 +466,7 @@ mod tests {
         assert!(response.error.is_some());
 
         let error = response.error.unwrap();
-        assert_eq!(error.code, "test_error");
+        assert_eq!(error.code, ErrorCode::ValidationError);
         assert_eq!(error.message, "Test error message");
         println!("✅ API response error test passed!");
     }
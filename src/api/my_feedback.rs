@@ -0,0 +1,503 @@
+// 🙋 My Feedback - Your own submissions, and only yours! 🙋
+// GET /api/me/feedback lists the signed-in user's feedback newest first, a page at a
+// time (`?limit=`, `?cursor=` from the previous page's `next_cursor`).
+// GET /api/me/feedback/:id adds the full content and the events recorded for it, and
+// DELETE /api/me/feedback/:id withdraws an item that is still pending, taking it out
+// of the queue. Every query carries `user_id = $n`, so someone else's feedback is
+// never loaded at all - it is simply not found.
+// Created with love by Aye & Hue! ✨
+
+use anyhow::{Context, Result};
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    response::{IntoResponse, Json, Response},
+    Extension,
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::PgPool;
+use tracing::{info, warn};
+use uuid::Uuid;
+
+use crate::api::{
+    feedback::{feedback_details, truncate_content, FeedbackDetails},
+    utils::{handle_error, not_found_error, validation_error},
+    ApiResponse, AppState, ErrorCode,
+};
+use crate::database::models::{Feedback, FeedbackStatus};
+use crate::github::artifacts::ArtifactLookup;
+use crate::middleware::auth::AuthenticatedUser;
+
+/// 📄 Items per page when `?limit=` is missing
+const DEFAULT_LIMIT: i64 = 20;
+/// 📏 Most items a page may hold
+const MAX_LIMIT: i64 = 100;
+
+/// 🔍 `?limit=` and `?cursor=` for the list
+#[derive(Debug, Deserialize)]
+pub struct MyFeedbackQuery {
+    pub limit: Option<i64>,
+    pub cursor: Option<String>,
+}
+
+/// 📍 The last item of a page; the next page starts after it.
+/// Travels as opaque base64url JSON in `?cursor=`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MyFeedbackCursor {
+    created_at: DateTime<Utc>,
+    id: Uuid,
+}
+
+impl MyFeedbackCursor {
+    /// 📦 Value for `?cursor=`
+    pub fn encode(&self) -> String {
+        use base64::Engine;
+        base64::engine::general_purpose::URL_SAFE_NO_PAD
+            .encode(serde_json::to_vec(self).unwrap_or_default())
+    }
+
+    /// 🔍 Parse `?cursor=`; None when it is garbage
+    pub fn decode(value: &str) -> Option<Self> {
+        use base64::Engine;
+        let bytes = base64::engine::general_purpose::URL_SAFE_NO_PAD
+            .decode(value)
+            .ok()?;
+        serde_json::from_slice(&bytes).ok()
+    }
+}
+
+/// 📋 One of my feedback items in the list
+#[derive(Debug, Serialize, sqlx::FromRow)]
+pub struct MyFeedbackItem {
+    pub id: Uuid,
+    pub repository: String,
+    pub content_preview: String,
+    pub status: FeedbackStatus,
+    pub pull_request_url: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// 📋 A page of my feedback
+#[derive(Debug, Serialize)]
+pub struct MyFeedbackPage {
+    pub items: Vec<MyFeedbackItem>,
+    /// ➡️ `?cursor=` for the next page (None on the last one)
+    pub next_cursor: Option<String>,
+}
+
+/// 📣 Something that happened to a feedback item
+#[derive(Debug, Serialize, sqlx::FromRow)]
+pub struct FeedbackEvent {
+    pub event: String,
+    pub at: DateTime<Utc>,
+}
+
+/// 🔍 One of my feedback items in full
+#[derive(Debug, Serialize)]
+pub struct MyFeedbackDetails {
+    #[serde(flatten)]
+    pub details: FeedbackDetails,
+    pub content: String,
+    pub events: Vec<FeedbackEvent>,
+}
+
+/// 🗑️ How withdrawing went
+#[derive(Debug, PartialEq)]
+pub enum Withdrawal {
+    Withdrawn,
+    NotFound,
+    /// 🚧 Already picked up (or finished): it stays
+    NotPending(FeedbackStatus),
+}
+
+/// 📋 A page of a user's feedback, newest first, after `cursor`
+pub async fn list_for_user(
+    pool: &PgPool,
+    user_id: Uuid,
+    limit: i64,
+    cursor: Option<&MyFeedbackCursor>,
+) -> Result<MyFeedbackPage> {
+    let mut items: Vec<MyFeedbackItem> = sqlx::query_as(
+        r#"
+        SELECT id, repository, content AS content_preview, status, pull_request_url,
+               created_at, updated_at
+        FROM feedback
+        WHERE user_id = $1
+          AND ($2::timestamptz IS NULL OR (created_at, id) < ($2, $3))
+        ORDER BY created_at DESC, id DESC
+        LIMIT $4
+        "#,
+    )
+    .bind(user_id)
+    .bind(cursor.map(|cursor| cursor.created_at))
+    .bind(cursor.map(|cursor| cursor.id))
+    .bind(limit + 1)
+    .fetch_all(pool)
+    .await
+    .context("Failed to list the user's feedback")?;
+
+    let next_cursor = if items.len() as i64 > limit {
+        items.truncate(limit as usize);
+        items.last().map(|last| {
+            MyFeedbackCursor {
+                created_at: last.created_at,
+                id: last.id,
+            }
+            .encode()
+        })
+    } else {
+        None
+    };
+    for item in &mut items {
+        item.content_preview = truncate_content(&item.content_preview, 200);
+    }
+    Ok(MyFeedbackPage { items, next_cursor })
+}
+
+/// 🔍 A user's feedback item, or None when it doesn't exist or belongs to someone else
+pub async fn find_for_user(pool: &PgPool, user_id: Uuid, id: Uuid) -> Result<Option<Feedback>> {
+    sqlx::query_as("SELECT * FROM feedback WHERE id = $1 AND user_id = $2")
+        .bind(id)
+        .bind(user_id)
+        .fetch_optional(pool)
+        .await
+        .context("Failed to fetch the user's feedback")
+}
+
+/// 📣 Outbox events recorded for a feedback item, oldest first
+pub async fn events_for(pool: &PgPool, feedback_id: Uuid) -> Result<Vec<FeedbackEvent>> {
+    sqlx::query_as(
+        "SELECT event_type AS event, created_at AS at FROM outbox_events \
+         WHERE feedback_id = $1 ORDER BY created_at, id",
+    )
+    .bind(feedback_id)
+    .fetch_all(pool)
+    .await
+    .context("Failed to fetch feedback events")
+}
+
+/// 🗑️ Withdraw a user's feedback while it is still pending. The row is locked so a
+/// worker can't pick it up in between; attachment blobs go once the delete commits.
+pub async fn withdraw(app_state: &AppState, user_id: Uuid, id: Uuid) -> Result<Withdrawal> {
+    let mut tx = app_state.db_pool.begin().await?;
+    let status: Option<FeedbackStatus> =
+        sqlx::query_scalar("SELECT status FROM feedback WHERE id = $1 AND user_id = $2 FOR UPDATE")
+            .bind(id)
+            .bind(user_id)
+            .fetch_optional(&mut *tx)
+            .await
+            .context("Failed to lock the user's feedback")?;
+    match status {
+        None => return Ok(Withdrawal::NotFound),
+        Some(FeedbackStatus::Pending) => {}
+        Some(status) => return Ok(Withdrawal::NotPending(status)),
+    }
+
+    let blobs: Vec<String> =
+        sqlx::query_scalar("SELECT storage_key FROM attachments WHERE feedback_id = $1")
+            .bind(id)
+            .fetch_all(&mut *tx)
+            .await
+            .context("Failed to list attachments of withdrawn feedback")?;
+    sqlx::query("DELETE FROM feedback WHERE id = $1 AND user_id = $2 AND status = 'pending'")
+        .bind(id)
+        .bind(user_id)
+        .execute(&mut *tx)
+        .await
+        .context("Failed to withdraw feedback")?;
+    tx.commit()
+        .await
+        .context("Failed to commit feedback withdrawal")?;
+
+    for key in blobs {
+        if let Err(e) = app_state.blobs.delete(&key).await {
+            warn!("⚠️ Failed to delete attachment blob {}: {:#}", key, e);
+        }
+    }
+    Ok(Withdrawal::Withdrawn)
+}
+
+/// 📋 GET /api/me/feedback - the caller's feedback, newest first
+pub async fn list_my_feedback(
+    State(app_state): State<AppState>,
+    Extension(user): Extension<AuthenticatedUser>,
+    Query(query): Query<MyFeedbackQuery>,
+) -> Response {
+    let cursor = match query.cursor.as_deref().filter(|cursor| !cursor.is_empty()) {
+        Some(value) => match MyFeedbackCursor::decode(value) {
+            Some(cursor) => Some(cursor),
+            None => {
+                return validation_error(vec!["cursor is not valid".to_string()]).into_response()
+            }
+        },
+        None => None,
+    };
+    let limit = query.limit.unwrap_or(DEFAULT_LIMIT).clamp(1, MAX_LIMIT);
+
+    match list_for_user(&app_state.db_pool, user.id, limit, cursor.as_ref()).await {
+        Ok(page) => (
+            StatusCode::OK,
+            Json(ApiResponse::success("Your feedback".to_string(), page)),
+        )
+            .into_response(),
+        Err(e) => handle_error(e).into_response(),
+    }
+}
+
+/// 🔍 GET /api/me/feedback/:id - one of the caller's feedback items with its events
+pub async fn get_my_feedback(
+    State(app_state): State<AppState>,
+    Extension(user): Extension<AuthenticatedUser>,
+    Path(feedback_id): Path<Uuid>,
+) -> Response {
+    let pool = &app_state.db_pool;
+    let feedback = match find_for_user(pool, user.id, feedback_id).await {
+        Ok(Some(feedback)) => feedback,
+        Ok(None) => return not_found_error("Feedback").into_response(),
+        Err(e) => return handle_error(e).into_response(),
+    };
+    let content = feedback.content.clone();
+    let details = async {
+        let events = events_for(pool, feedback.id).await?;
+        let details = feedback_details(&app_state, feedback, ArtifactLookup::Live).await?;
+        Ok::<_, anyhow::Error>(MyFeedbackDetails {
+            details,
+            content,
+            events,
+        })
+    };
+    match details.await {
+        Ok(details) => (
+            StatusCode::OK,
+            Json(ApiResponse::success("Feedback found".to_string(), details)),
+        )
+            .into_response(),
+        Err(e) => handle_error(e).into_response(),
+    }
+}
+
+/// 🗑️ DELETE /api/me/feedback/:id - withdraw one of the caller's pending items
+pub async fn delete_my_feedback(
+    State(app_state): State<AppState>,
+    Extension(user): Extension<AuthenticatedUser>,
+    Path(feedback_id): Path<Uuid>,
+) -> Response {
+    match withdraw(&app_state, user.id, feedback_id).await {
+        Ok(Withdrawal::Withdrawn) => {
+            info!("🗑️ {} withdrew feedback {}", user.email, feedback_id);
+            (
+                StatusCode::OK,
+                Json(ApiResponse::<()>::success_no_data(
+                    "Feedback withdrawn".to_string(),
+                )),
+            )
+                .into_response()
+        }
+        Ok(Withdrawal::NotFound) => not_found_error("Feedback").into_response(),
+        Ok(Withdrawal::NotPending(status)) => (
+            StatusCode::CONFLICT,
+            Json(ApiResponse::<()>::error(
+                ErrorCode::NotPending,
+                "Only feedback that is still pending can be withdrawn".to_string(),
+                Some(serde_json::json!({ "status": status })),
+            )),
+        )
+            .into_response(),
+        Err(e) => handle_error(e).into_response(),
+    }
+}
+
+// 🧪 Tests - Your feedback, nobody else's!
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::models::{User, UserRole};
+    use crate::middleware::auth::jwt_utils;
+    use crate::test_support::{spawn_test_app, TestApp};
+    use reqwest::Method;
+
+    async fn user(app: &TestApp, email: &str) -> User {
+        sqlx::query_as(
+            "INSERT INTO users (email, name, password_hash, role) \
+             VALUES ($1, $1, 'x', $2) RETURNING *",
+        )
+        .bind(email)
+        .bind(UserRole::User)
+        .fetch_one(&app.db_pool)
+        .await
+        .unwrap()
+    }
+
+    async fn feedback(app: &TestApp, owner: &User, content: &str, status: &str) -> Uuid {
+        sqlx::query_scalar(
+            "INSERT INTO feedback (user_id, repository, content, status) \
+             VALUES ($1, '8b-is/smart-tree', $2, $3::feedback_status) RETURNING id",
+        )
+        .bind(owner.id)
+        .bind(content)
+        .bind(status)
+        .fetch_one(&app.db_pool)
+        .await
+        .unwrap()
+    }
+
+    async fn call(
+        app: &TestApp,
+        method: Method,
+        as_user: Option<&User>,
+        path: &str,
+    ) -> (StatusCode, serde_json::Value) {
+        let mut request = app.client.request(method, app.url(path));
+        if let Some(as_user) = as_user {
+            let token =
+                jwt_utils::create_jwt_token(as_user, &app.app_state.config.auth.jwt_secret, 1)
+                    .unwrap();
+            request = request.bearer_auth(token);
+        }
+        let response = request.send().await.unwrap();
+        let status = StatusCode::from_u16(response.status().as_u16()).unwrap();
+        (status, response.json().await.unwrap_or_default())
+    }
+
+    async fn exists(app: &TestApp, id: Uuid) -> bool {
+        sqlx::query_scalar("SELECT EXISTS (SELECT 1 FROM feedback WHERE id = $1)")
+            .bind(id)
+            .fetch_one(&app.db_pool)
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_users_only_see_their_own_feedback() {
+        let Some(app) = spawn_test_app().await else {
+            return;
+        };
+        let alice = user(&app, "alice@example.com").await;
+        let bob = user(&app, "bob@example.com").await;
+        let mut mine = Vec::new();
+        for n in 0..3 {
+            mine.push(feedback(&app, &alice, &format!("Alice #{}", n), "pending").await);
+        }
+        let theirs = feedback(&app, &bob, "Bob's idea", "pending").await;
+        sqlx::query(
+            "INSERT INTO outbox_events (event_type, feedback_id, payload) \
+             VALUES ('feedback.completed', $1, '{}')",
+        )
+        .bind(mine[0])
+        .execute(&app.db_pool)
+        .await
+        .unwrap();
+
+        // 🔐 Signed-in users only
+        let (status, _) = call(&app, Method::GET, None, "/api/me/feedback").await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+
+        // 📋 Two pages of Alice's feedback, newest first, without Bob's
+        let (status, body) =
+            call(&app, Method::GET, Some(&alice), "/api/me/feedback?limit=2").await;
+        assert_eq!(status, StatusCode::OK);
+        let ids = |body: &serde_json::Value| -> Vec<String> {
+            body["data"]["items"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .map(|item| item["id"].as_str().unwrap().to_string())
+                .collect()
+        };
+        let mut seen = ids(&body);
+        let cursor = body["data"]["next_cursor"].as_str().unwrap().to_string();
+        let (_, body) = call(
+            &app,
+            Method::GET,
+            Some(&alice),
+            &format!("/api/me/feedback?limit=2&cursor={}", cursor),
+        )
+        .await;
+        seen.extend(ids(&body));
+        assert!(body["data"]["next_cursor"].is_null());
+        let expected: Vec<String> = mine.iter().rev().map(Uuid::to_string).collect();
+        assert_eq!(seen, expected);
+        let (status, _) = call(
+            &app,
+            Method::GET,
+            Some(&alice),
+            "/api/me/feedback?cursor=nope",
+        )
+        .await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+
+        // 🔍 Details with content and events, but only for the owner
+        let (status, body) = call(
+            &app,
+            Method::GET,
+            Some(&alice),
+            &format!("/api/me/feedback/{}", mine[0]),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["data"]["content"], "Alice #0");
+        assert_eq!(body["data"]["events"][0]["event"], "feedback.completed");
+        let (status, _) = call(
+            &app,
+            Method::GET,
+            Some(&alice),
+            &format!("/api/me/feedback/{}", theirs),
+        )
+        .await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+
+        // 🗑️ Nor can Alice withdraw Bob's feedback
+        let (status, _) = call(
+            &app,
+            Method::DELETE,
+            Some(&alice),
+            &format!("/api/me/feedback/{}", theirs),
+        )
+        .await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+        assert!(exists(&app, theirs).await);
+        println!("✅ Own feedback only test passed!");
+    }
+
+    #[tokio::test]
+    async fn test_only_pending_feedback_can_be_withdrawn() {
+        let Some(app) = spawn_test_app().await else {
+            return;
+        };
+        let alice = user(&app, "alice@example.com").await;
+        let waiting = feedback(&app, &alice, "Still waiting", "pending").await;
+        let started = feedback(&app, &alice, "Being worked on", "processing").await;
+
+        let (status, body) = call(
+            &app,
+            Method::DELETE,
+            Some(&alice),
+            &format!("/api/me/feedback/{}", started),
+        )
+        .await;
+        assert_eq!(status, StatusCode::CONFLICT);
+        assert_eq!(body["error"]["code"], "not_pending");
+        assert!(exists(&app, started).await);
+
+        let (status, _) = call(
+            &app,
+            Method::DELETE,
+            Some(&alice),
+            &format!("/api/me/feedback/{}", waiting),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        assert!(!exists(&app, waiting).await);
+        let (status, _) = call(
+            &app,
+            Method::DELETE,
+            Some(&alice),
+            &format!("/api/me/feedback/{}", waiting),
+        )
+        .await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+        println!("✅ Pending-only withdrawal test passed!");
+    }
+}
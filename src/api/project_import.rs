@@ -0,0 +1,719 @@
+// 🗂️ Project Import - Dozens of repositories, one CSV! 🗂️
+// POST /admin/projects/import takes a multipart upload whose `file` field is a CSV file
+// with a header line, one project per row. Columns are matched like the feedback
+// import's (case-insensitive, spaces read as underscores):
+//   repository        (required) owner/repo
+//   owner             email or user id of an existing user (new projects default to
+//                     the system user, as projects added on the admin page do)
+//   llm_provider      openai or anthropic
+//   system_message    extra instructions for the LLM on this project
+//   description
+//   is_active, require_approval, check_protection, auto_merge
+//                     automation toggles: true/false, yes/no, on/off or 1/0
+// A blank cell leaves the project's current value alone (or takes the default for a new
+// project). A row updates the project already registered for its repository - the
+// owner's one when several owners registered it - and creates one otherwise. Rows are
+// applied in one transaction: a bad row is skipped with its reason, while a broken file,
+// too many rows or a database error rolls the whole import back. New projects get their
+// repository webhook once the import is committed.
+// Created with love by Aye & Hue! ✨
+
+use anyhow::Context;
+use axum::{
+    body::Bytes,
+    extract::State,
+    http::StatusCode,
+    response::{IntoResponse, Json, Response},
+};
+use axum_extra::extract::{cookie::CookieJar, Multipart};
+use futures_util::{Stream, TryStreamExt};
+use serde::Serialize;
+use serde_json::Value;
+use sqlx::{PgConnection, PgPool};
+use tracing::info;
+use uuid::Uuid;
+
+use crate::{
+    api::{
+        admin::{audit_log, get_or_create_system_user, install_webhook, require_admin_api_auth},
+        feedback_import::{column_name, CsvReader, ImportError},
+        ApiResponse, AppState,
+    },
+    config::LlmProvider,
+    database::project_config::ProjectConfig,
+};
+
+/// 📏 Largest upload the import route accepts
+pub const PROJECT_IMPORT_MAX_BYTES: usize = 1024 * 1024;
+/// 🔢 Most rows one import may hold
+pub const PROJECT_IMPORT_MAX_ROWS: usize = 1_000;
+/// 📋 Columns a project import understands
+pub const PROJECT_IMPORT_COLUMNS: [&str; 9] = [
+    "repository",
+    "owner",
+    "llm_provider",
+    "system_message",
+    "description",
+    "is_active",
+    "require_approval",
+    "check_protection",
+    "auto_merge",
+];
+
+/// 📝 One row as read from the file, before validation
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ProjectImportRow {
+    pub repository: Option<String>,
+    pub owner: Option<String>,
+    pub llm_provider: Option<String>,
+    pub system_message: Option<String>,
+    pub description: Option<String>,
+    pub is_active: Option<String>,
+    pub require_approval: Option<String>,
+    pub check_protection: Option<String>,
+    pub auto_merge: Option<String>,
+}
+
+impl ProjectImportRow {
+    /// ✍️ Set a known column (blank values count as missing)
+    fn set(&mut self, column: &str, value: String) {
+        let slot = match column {
+            "repository" => &mut self.repository,
+            "owner" => &mut self.owner,
+            "llm_provider" => &mut self.llm_provider,
+            "system_message" => &mut self.system_message,
+            "description" => &mut self.description,
+            "is_active" => &mut self.is_active,
+            "require_approval" => &mut self.require_approval,
+            "check_protection" => &mut self.check_protection,
+            "auto_merge" => &mut self.auto_merge,
+            _ => return,
+        };
+        *slot = Some(value.trim().to_string()).filter(|value| !value.is_empty());
+    }
+}
+
+/// ✅ A row that passed validation (None: leave the value as it is)
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ProjectSettings {
+    pub repository: String,
+    pub owner: Option<String>,
+    pub llm_provider: Option<String>,
+    pub system_message: Option<String>,
+    pub description: Option<String>,
+    pub is_active: Option<bool>,
+    pub require_approval: Option<bool>,
+    pub check_protection: Option<bool>,
+    pub auto_merge: Option<bool>,
+}
+
+/// 🔘 Read a toggle cell
+fn parse_toggle(value: &str) -> Option<bool> {
+    match value.to_ascii_lowercase().as_str() {
+        "true" | "yes" | "on" | "1" => Some(true),
+        "false" | "no" | "off" | "0" => Some(false),
+        _ => None,
+    }
+}
+
+/// ✅ Validate one row, collecting every problem into one reason
+pub fn validate_project_row(row: &ProjectImportRow) -> Result<ProjectSettings, String> {
+    let mut errors = Vec::new();
+
+    let repository = row.repository.clone();
+    match repository.as_deref() {
+        None => errors.push("repository is missing".to_string()),
+        Some(repository) => {
+            let valid = repository.len() <= 255
+                && repository.split_once('/').is_some_and(|(owner, name)| {
+                    !owner.is_empty() && !name.is_empty() && !name.contains('/')
+                })
+                && !repository.chars().any(char::is_whitespace);
+            if !valid {
+                errors.push(format!("repository '{}' is not owner/repo", repository));
+            }
+        }
+    }
+
+    let llm_provider = row.llm_provider.as_deref().map(str::to_ascii_lowercase);
+    if let Some(provider) = &llm_provider {
+        if serde_json::from_value::<LlmProvider>(Value::String(provider.clone())).is_err() {
+            errors.push(format!(
+                "llm_provider '{}' is not openai or anthropic",
+                provider
+            ));
+        }
+    }
+
+    let mut toggle = |column: &str, value: &Option<String>| {
+        let value = value.as_deref()?;
+        let parsed = parse_toggle(value);
+        if parsed.is_none() {
+            errors.push(format!("{} '{}' is not true or false", column, value));
+        }
+        parsed
+    };
+    let is_active = toggle("is_active", &row.is_active);
+    let require_approval = toggle("require_approval", &row.require_approval);
+    let check_protection = toggle("check_protection", &row.check_protection);
+    let auto_merge = toggle("auto_merge", &row.auto_merge);
+
+    // 🚫 Postgres text can't hold NUL, and one such row would abort the whole import
+    if [
+        &row.repository,
+        &row.owner,
+        &row.system_message,
+        &row.description,
+    ]
+    .iter()
+    .any(|value| value.as_deref().is_some_and(|value| value.contains('\0')))
+    {
+        errors.push("values can't contain NUL characters".to_string());
+    }
+
+    match repository {
+        Some(repository) if errors.is_empty() => Ok(ProjectSettings {
+            repository,
+            owner: row.owner.clone(),
+            llm_provider,
+            system_message: row.system_message.clone(),
+            description: row.description.clone(),
+            is_active,
+            require_approval,
+            check_protection,
+            auto_merge,
+        }),
+        _ => Err(errors.join("; ")),
+    }
+}
+
+/// 📊 What became of one row
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ProjectRowOutcome {
+    Created,
+    Updated,
+    Skipped,
+}
+
+/// 📊 One row of the report (rows count from 1, the CSV header excluded)
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ProjectRowResult {
+    pub row: usize,
+    pub repository: Option<String>,
+    pub outcome: ProjectRowOutcome,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub project_id: Option<Uuid>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+}
+
+/// 📊 The result of a committed import
+#[derive(Debug, Clone, Serialize)]
+pub struct ProjectImportReport {
+    pub total_rows: usize,
+    pub created: usize,
+    pub updated: usize,
+    pub skipped: usize,
+    /// 🙈 Columns that were not imported
+    pub ignored_columns: Vec<String>,
+    pub rows: Vec<ProjectRowResult>,
+}
+
+/// 📋 Read the CSV header: known columns by position, `repository` among them
+fn csv_header(record: Vec<String>, ignored: &mut Vec<String>) -> Result<Vec<String>, ImportError> {
+    let columns: Vec<String> = record.iter().map(|name| column_name(name)).collect();
+    for (name, column) in record.iter().zip(&columns) {
+        if !PROJECT_IMPORT_COLUMNS.contains(&column.as_str()) {
+            ignored.push(name.trim().trim_start_matches('\u{feff}').to_string());
+        }
+    }
+    if !columns.iter().any(|column| column == "repository") {
+        return Err(ImportError::Malformed(
+            "The CSV header is missing required columns: repository".to_string(),
+        ));
+    }
+    Ok(columns)
+}
+
+/// 📖 One CSV record under the header
+fn csv_row(columns: &[String], record: Vec<String>) -> Result<ProjectImportRow, String> {
+    if record.len() > columns.len() {
+        return Err(format!(
+            "has {} fields but the header has {}",
+            record.len(),
+            columns.len()
+        ));
+    }
+    let mut row = ProjectImportRow::default();
+    for (column, value) in columns.iter().zip(record) {
+        row.set(column, value);
+    }
+    Ok(row)
+}
+
+/// 👤 The user an `owner` cell names (email, any case, or user id)
+async fn find_owner(conn: &mut PgConnection, owner: &str) -> anyhow::Result<Option<Uuid>> {
+    sqlx::query_scalar(
+        "SELECT id FROM users WHERE id::text = $1 OR LOWER(email) = LOWER($1) LIMIT 1",
+    )
+    .bind(owner)
+    .fetch_optional(conn)
+    .await
+    .context("Failed to look up project owner")
+}
+
+/// 🔀 The stored config with the row's pull request toggles applied (None: no change)
+fn merged_config(
+    stored: Option<Value>,
+    settings: &ProjectSettings,
+) -> Result<Option<Value>, String> {
+    if settings.check_protection.is_none() && settings.auto_merge.is_none() {
+        return Ok(None);
+    }
+    let mut config = match stored {
+        Some(stored) => ProjectConfig::from_value(stored)
+            .map_err(|e| format!("the stored project config can't be updated: {:#}", e))?,
+        None => ProjectConfig::default(),
+    };
+    if let Some(check_protection) = settings.check_protection {
+        config.pull_requests.check_protection = check_protection;
+    }
+    if let Some(auto_merge) = settings.auto_merge {
+        config.pull_requests.auto_merge = auto_merge;
+    }
+    Ok(Some(config.to_value()))
+}
+
+/// 💾 Create or update the project for one valid row
+async fn upsert_project(
+    conn: &mut PgConnection,
+    settings: &ProjectSettings,
+    default_owner: Uuid,
+) -> anyhow::Result<Result<(ProjectRowOutcome, Uuid), String>> {
+    let owner = match &settings.owner {
+        None => None,
+        Some(owner) => match find_owner(&mut *conn, owner).await? {
+            Some(id) => Some(id),
+            None => return Ok(Err(format!("owner '{}' is not a known user", owner))),
+        },
+    };
+
+    let existing: Vec<(Uuid, Uuid, Option<Value>)> = sqlx::query_as(
+        "SELECT id, owner_id, config FROM projects WHERE LOWER(repository) = LOWER($1) ORDER BY created_at, id FOR UPDATE",
+    )
+    .bind(&settings.repository)
+    .fetch_all(&mut *conn)
+    .await
+    .context("Failed to look up project")?;
+    let target = match (existing.as_slice(), owner) {
+        ([], _) => None,
+        ([only], None) => Some(only),
+        (projects, Some(owner)) => match projects.iter().find(|(_, id, _)| *id == owner) {
+            Some(project) => Some(project),
+            None if projects.len() == 1 => Some(&projects[0]),
+            None => None,
+        },
+        (_, None) => {
+            return Ok(Err(format!(
+                "{} projects are registered for {}, name the owner to pick one",
+                existing.len(),
+                settings.repository
+            )))
+        }
+    };
+
+    match target {
+        Some((project_id, _, stored)) => {
+            let config = match merged_config(stored.clone(), settings) {
+                Ok(config) => config,
+                Err(reason) => return Ok(Err(reason)),
+            };
+            sqlx::query(
+                r#"
+                UPDATE projects SET
+                    owner_id = COALESCE($2, owner_id),
+                    description = COALESCE($3, description),
+                    default_llm_provider = COALESCE($4, default_llm_provider),
+                    system_message = COALESCE($5, system_message),
+                    config = COALESCE($6, config),
+                    is_active = COALESCE($7, is_active),
+                    require_approval = COALESCE($8, require_approval),
+                    updated_at = NOW()
+                WHERE id = $1
+                "#,
+            )
+            .bind(project_id)
+            .bind(owner)
+            .bind(&settings.description)
+            .bind(&settings.llm_provider)
+            .bind(&settings.system_message)
+            .bind(config)
+            .bind(settings.is_active)
+            .bind(settings.require_approval)
+            .execute(&mut *conn)
+            .await
+            .context("Failed to update imported project")?;
+            Ok(Ok((ProjectRowOutcome::Updated, *project_id)))
+        }
+        None => {
+            let config = merged_config(None, settings).expect("a new project has no stored config");
+            let project_id: Uuid = sqlx::query_scalar(
+                r#"
+                INSERT INTO projects
+                    (owner_id, repository, description, default_llm_provider, system_message,
+                     config, is_active, require_approval)
+                VALUES ($1, $2, $3, $4, $5, $6, COALESCE($7, TRUE), COALESCE($8, FALSE))
+                RETURNING id
+                "#,
+            )
+            .bind(owner.unwrap_or(default_owner))
+            .bind(&settings.repository)
+            .bind(&settings.description)
+            .bind(&settings.llm_provider)
+            .bind(&settings.system_message)
+            .bind(config)
+            .bind(settings.is_active)
+            .bind(settings.require_approval)
+            .fetch_one(&mut *conn)
+            .await
+            .context("Failed to insert imported project")?;
+            Ok(Ok((ProjectRowOutcome::Created, project_id)))
+        }
+    }
+}
+
+/// 🗂️ Apply every row of `chunks` in one transaction, committed only when the whole
+/// file was read without a systemic failure
+pub async fn import_projects<S>(
+    pool: &PgPool,
+    chunks: S,
+    max_rows: usize,
+    default_owner: Uuid,
+) -> Result<ProjectImportReport, ImportError>
+where
+    S: Stream<Item = Result<Bytes, ImportError>>,
+{
+    let mut chunks = std::pin::pin!(chunks);
+    let mut tx = pool
+        .begin()
+        .await
+        .context("Failed to start project import transaction")
+        .map_err(ImportError::Database)?;
+    let mut reader = CsvReader::default();
+    let mut header: Option<Vec<String>> = None;
+    let mut report = ProjectImportReport {
+        total_rows: 0,
+        created: 0,
+        updated: 0,
+        skipped: 0,
+        ignored_columns: Vec::new(),
+        rows: Vec::new(),
+    };
+
+    let mut done = false;
+    while !done {
+        let chunk = chunks.try_next().await?;
+        done = chunk.is_none();
+        let records = match &chunk {
+            Some(bytes) => reader.feed(bytes),
+            None => reader.finish().into_iter().collect(),
+        };
+        for record in records {
+            let Some(columns) = &header else {
+                header = Some(csv_header(record, &mut report.ignored_columns)?);
+                continue;
+            };
+            report.total_rows += 1;
+            if report.total_rows > max_rows {
+                return Err(ImportError::TooManyRows(max_rows));
+            }
+            let row = csv_row(columns, record);
+            let repository = row.as_ref().ok().and_then(|row| row.repository.clone());
+            let outcome = match row.and_then(|row| validate_project_row(&row)) {
+                Err(reason) => Err(reason),
+                Ok(settings) => upsert_project(&mut tx, &settings, default_owner)
+                    .await
+                    .map_err(ImportError::Database)?,
+            };
+            let (outcome, project_id, reason) = match outcome {
+                Ok((outcome, project_id)) => (outcome, Some(project_id), None),
+                Err(reason) => (ProjectRowOutcome::Skipped, None, Some(reason)),
+            };
+            match outcome {
+                ProjectRowOutcome::Created => report.created += 1,
+                ProjectRowOutcome::Updated => report.updated += 1,
+                ProjectRowOutcome::Skipped => report.skipped += 1,
+            }
+            report.rows.push(ProjectRowResult {
+                row: report.total_rows,
+                repository,
+                outcome,
+                project_id,
+                reason,
+            });
+        }
+    }
+    if header.is_none() {
+        return Err(ImportError::Malformed("The CSV file is empty".to_string()));
+    }
+
+    tx.commit()
+        .await
+        .context("Failed to commit project import")
+        .map_err(ImportError::Database)?;
+    Ok(report)
+}
+
+/// 🗂️ POST /admin/projects/import (multipart, the CSV in the `file` field)
+pub async fn import_projects_handler(
+    State(app_state): State<AppState>,
+    jar: CookieJar,
+    mut multipart: Multipart,
+) -> Response {
+    if let Some(denied) = require_admin_api_auth(&jar, &app_state).await {
+        return denied;
+    }
+    let upload_error = |e: axum_extra::extract::multipart::MultipartError| {
+        ImportError::Upload(e.status(), e.body_text())
+    };
+
+    let field = loop {
+        match multipart.next_field().await {
+            Ok(Some(field)) if field.name() == Some("file") => break field,
+            Ok(Some(_)) => continue,
+            Ok(None) => {
+                return ImportError::Malformed("The upload has no `file` field".to_string())
+                    .into_response()
+            }
+            Err(e) => return upload_error(e).into_response(),
+        }
+    };
+    let file_name = field.file_name().map(str::to_string);
+    let Some(default_owner) = get_or_create_system_user(&app_state).await else {
+        return ImportError::Database(anyhow::anyhow!("No system user to own imported projects"))
+            .into_response();
+    };
+
+    let report = match import_projects(
+        &app_state.db_pool,
+        field.map_err(upload_error),
+        PROJECT_IMPORT_MAX_ROWS,
+        default_owner,
+    )
+    .await
+    {
+        Ok(report) => report,
+        Err(e) => return e.into_response(),
+    };
+    info!(
+        "🗂️ Imported {} new and {} updated projects from {}",
+        report.created,
+        report.updated,
+        file_name.as_deref().unwrap_or("an upload")
+    );
+    for row in &report.rows {
+        if let (ProjectRowOutcome::Created, Some(project_id)) = (row.outcome, row.project_id) {
+            install_webhook(&app_state, project_id).await;
+        }
+    }
+    audit_log(
+        &app_state,
+        &jar,
+        "projects_imported",
+        serde_json::json!({
+            "file": file_name,
+            "created": report.created,
+            "updated": report.updated,
+            "skipped": report.skipped,
+        }),
+    )
+    .await;
+    (
+        StatusCode::OK,
+        Json(ApiResponse::success(
+            format!(
+                "Imported {} new and {} updated projects, skipped {}",
+                report.created, report.updated, report.skipped
+            ),
+            report,
+        )),
+    )
+        .into_response()
+}
+
+// 🧪 Tests - A whole fleet of repositories in one go!
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{spawn_test_app, TestApp};
+
+    /// 📮 Upload a CSV as the `file` part of a multipart body
+    async fn upload(app: &TestApp, content: &str) -> reqwest::Response {
+        let boundary = "feedbacker-project-import";
+        let body = format!(
+            "--{b}\r\nContent-Disposition: form-data; name=\"file\"; filename=\"projects.csv\"\r\n\
+             Content-Type: text/csv\r\n\r\n{c}\r\n--{b}--\r\n",
+            b = boundary,
+            c = content
+        );
+        app.client
+            .post(app.url("/admin/projects/import"))
+            .header(
+                "content-type",
+                format!("multipart/form-data; boundary={}", boundary),
+            )
+            .body(body)
+            .send()
+            .await
+            .unwrap()
+    }
+
+    #[test]
+    fn test_project_rows_are_validated_with_every_reason() {
+        let row = |repository: &str, provider: &str, toggle: &str| ProjectImportRow {
+            repository: Some(repository.to_string()).filter(|value| !value.is_empty()),
+            llm_provider: Some(provider.to_string()).filter(|value| !value.is_empty()),
+            auto_merge: Some(toggle.to_string()).filter(|value| !value.is_empty()),
+            ..Default::default()
+        };
+        let valid = validate_project_row(&row("8b-is/smart-tree", "OpenAI", "yes")).unwrap();
+        assert_eq!(valid.llm_provider.as_deref(), Some("openai"));
+        assert_eq!(valid.auto_merge, Some(true));
+        assert_eq!(valid.is_active, None);
+        assert_eq!(
+            validate_project_row(&row("smart-tree", "gemini", "maybe")),
+            Err("repository 'smart-tree' is not owner/repo; llm_provider 'gemini' is not openai or anthropic; auto_merge 'maybe' is not true or false".to_string())
+        );
+        assert_eq!(
+            validate_project_row(&row("", "", "")),
+            Err("repository is missing".to_string())
+        );
+        println!("✅ Project import validation test passed!");
+    }
+
+    #[tokio::test]
+    async fn test_csv_import_creates_and_updates_projects() {
+        let Some(app) = spawn_test_app().await else {
+            return;
+        };
+        let pool = &app.db_pool;
+        let owner: Uuid = sqlx::query_scalar(
+            "INSERT INTO users (email, name, password_hash) VALUES ('ops@example.com', 'Ops', 'x') RETURNING id",
+        )
+        .fetch_one(pool)
+        .await
+        .unwrap();
+        let existing: Uuid = sqlx::query_scalar(
+            "INSERT INTO projects (owner_id, repository, system_message, config) VALUES ($1, '8b-is/mem8', 'Be brief', '{\"comments\": {\"footer\": null}}') RETURNING id",
+        )
+        .bind(owner)
+        .fetch_one(pool)
+        .await
+        .unwrap();
+
+        let csv =
+            "Repository,Owner,LLM Provider,System Message,Require Approval,Auto Merge,Notes\n\
+            8b-is/smart-tree,OPS@example.com,anthropic,\"Prefer small, focused PRs\",yes,,first\n\
+            8b-is/mem8,,openai,,,true,\n\
+            8b-is/feedbacker,,,,,,\n\
+            8b-is/ghost,nobody@example.com,,,,,\n\
+            not-a-repo,,,,,,\n";
+        assert_eq!(upload(&app, csv).await.status(), 401);
+        app.login_admin().await.unwrap();
+        let response = upload(&app, csv).await;
+        assert_eq!(response.status(), 200);
+        let body: Value = response.json().await.unwrap();
+        let report = &body["data"];
+        assert_eq!(
+            (&report["created"], &report["updated"], &report["skipped"]),
+            (&Value::from(2), &Value::from(1), &Value::from(2))
+        );
+        assert_eq!(report["ignored_columns"], serde_json::json!(["Notes"]));
+        let outcomes: Vec<(&str, Option<&str>)> = report["rows"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|row| (row["outcome"].as_str().unwrap(), row["reason"].as_str()))
+            .collect();
+        assert_eq!(
+            outcomes,
+            [
+                ("created", None),
+                ("updated", None),
+                ("created", None),
+                (
+                    "skipped",
+                    Some("owner 'nobody@example.com' is not a known user")
+                ),
+                ("skipped", Some("repository 'not-a-repo' is not owner/repo")),
+            ]
+        );
+
+        // 🆕 Created with the row's settings and owner
+        let (owner_id, provider, message, approval): (Uuid, Option<String>, Option<String>, bool) =
+            sqlx::query_as(
+                "SELECT owner_id, default_llm_provider, system_message, require_approval FROM projects WHERE repository = '8b-is/smart-tree'",
+            )
+            .fetch_one(pool)
+            .await
+            .unwrap();
+        assert_eq!(owner_id, owner);
+        assert_eq!(provider.as_deref(), Some("anthropic"));
+        assert_eq!(message.as_deref(), Some("Prefer small, focused PRs"));
+        assert!(approval);
+
+        // ✏️ Blank cells left the rest of the existing project alone
+        let (provider, message, config): (Option<String>, Option<String>, Value) = sqlx::query_as(
+            "SELECT default_llm_provider, system_message, config FROM projects WHERE id = $1",
+        )
+        .bind(existing)
+        .fetch_one(pool)
+        .await
+        .unwrap();
+        assert_eq!(provider.as_deref(), Some("openai"));
+        assert_eq!(message.as_deref(), Some("Be brief"));
+        let config = ProjectConfig::from_value(config).unwrap();
+        assert!(config.pull_requests.auto_merge);
+        assert_eq!(config.comments.footer, Some(None));
+
+        // 🔁 Importing the same file again only updates
+        let body: Value = upload(&app, csv).await.json().await.unwrap();
+        assert_eq!(body["data"]["created"], 0);
+        assert_eq!(body["data"]["updated"], 3);
+        let logged: i64 = sqlx::query_scalar(
+            "SELECT COUNT(*) FROM admin_audit_log WHERE action = 'projects_imported'",
+        )
+        .fetch_one(pool)
+        .await
+        .unwrap();
+        assert_eq!(logged, 2);
+        println!("✅ Project CSV import test passed!");
+    }
+
+    #[tokio::test]
+    async fn test_broken_project_imports_roll_back() {
+        let Some(app) = spawn_test_app().await else {
+            return;
+        };
+        app.login_admin().await.unwrap();
+        let response = upload(
+            &app,
+            "Owner,Description\nops@example.com,Missing repository\n",
+        )
+        .await;
+        assert_eq!(response.status(), 400);
+
+        let mut csv = String::from("repository\n8b-is/first\n");
+        for i in 0..PROJECT_IMPORT_MAX_ROWS {
+            csv.push_str(&format!("8b-is/repo-{}\n", i));
+        }
+        assert_eq!(upload(&app, &csv).await.status(), 413);
+        let projects: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM projects")
+            .fetch_one(&app.db_pool)
+            .await
+            .unwrap();
+        assert_eq!(projects, 0);
+        println!("✅ Project import rollback test passed!");
+    }
+}
@@ -1,14 +1,29 @@
 // 🏠 Projects API - Repository Management! 🏠
 // This module handles project management endpoints
+//
+// Transfers hand a project to another user (by email or GitHub username). Only the
+// current owner or an admin may do it, and only to someone GitHub says can push to
+// the repository. If the target already registered the same repository the two
+// projects are merged: the older one survives, its config comes from whichever side
+// `merge_config` picks, webhooks move over and the newer project is deleted.
+// The ownership change, both owners' notifications and the audit entry commit
+// together or not at all.
 // Created with love by Aye & Hue! ✨
 
-use crate::api::{ApiResponse, AppState};
+use crate::api::{admin::record_audit, utils, ApiResponse, AppState, ErrorCode};
+use crate::database::models::User;
+use crate::middleware::auth::AuthenticatedUser;
+use anyhow::Context;
 use axum::{
     extract::{Path, State},
     http::StatusCode,
-    response::{IntoResponse, Json},
+    response::{IntoResponse, Json, Response},
+    Extension,
 };
-use serde::Serialize;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{Acquire, PgConnection};
+use tracing::{info, warn};
 use uuid::Uuid;
 
 #[derive(Debug, Serialize)]
@@ -51,3 +66,691 @@ pub async fn get_project(
         )),
     )
 }
+
+/// 🔀 Body of POST /api/projects/:id/transfer
+#[derive(Debug, Deserialize)]
+pub struct TransferRequest {
+    /// 👤 New owner's email address or GitHub username
+    pub to: String,
+    /// 🧩 Whose config the merged project keeps, required when the new owner already
+    /// has a project for the same repository
+    #[serde(default)]
+    pub merge_config: Option<MergeConfig>,
+}
+
+/// 🧩 Which project's config survives a merge
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MergeConfig {
+    /// 📦 The project being transferred
+    Transferred,
+    /// 🏠 The new owner's existing project
+    Existing,
+}
+
+/// ✅ What a transfer did
+#[derive(Debug, Serialize)]
+pub struct TransferOutcome {
+    /// 🏠 The project now owned by the target (the older one after a merge)
+    pub project_id: Uuid,
+    pub owner_id: Uuid,
+    /// 🧩 Whether two projects were merged
+    pub merged: bool,
+    /// 🗑️ The project deleted by the merge
+    pub removed_project_id: Option<Uuid>,
+}
+
+/// 🏠 The columns a transfer needs
+#[derive(Debug, sqlx::FromRow)]
+struct TransferProject {
+    id: Uuid,
+    owner_id: Uuid,
+    repository: String,
+    config: Option<serde_json::Value>,
+    created_at: DateTime<Utc>,
+}
+
+/// 🚧 Why a transfer stopped inside the transaction
+enum TransferBlocked {
+    /// 🤝 The target already has this repository and no merge_config was given
+    Collision(Uuid),
+    /// 🏃 Someone else changed the project meanwhile
+    Conflict,
+}
+
+/// ❌ Error response with a status and details of its own
+fn transfer_error(
+    status: StatusCode,
+    code: ErrorCode,
+    message: &str,
+    details: Option<serde_json::Value>,
+) -> Response {
+    (
+        status,
+        Json(ApiResponse::<()>::error(code, message.to_string(), details)),
+    )
+        .into_response()
+}
+
+/// 🔀 Hand a project to another user, merging with theirs when they already have it
+pub async fn transfer_project(
+    State(app_state): State<AppState>,
+    Path(id): Path<Uuid>,
+    Extension(user): Extension<AuthenticatedUser>,
+    Json(request): Json<TransferRequest>,
+) -> Response {
+    let pool = &app_state.db_pool;
+    let project: Option<TransferProject> = match sqlx::query_as(
+        "SELECT id, owner_id, repository, config, created_at FROM projects WHERE id = $1",
+    )
+    .bind(id)
+    .fetch_optional(pool)
+    .await
+    {
+        Ok(project) => project,
+        Err(e) => return utils::handle_error(e.into()).into_response(),
+    };
+    let Some(project) = project else {
+        return utils::not_found_error("Project").into_response();
+    };
+    if project.owner_id != user.id && !user.is_admin() {
+        return utils::forbidden_error().into_response();
+    }
+
+    let target = match User::find_active_by_handle(pool, &request.to).await {
+        Ok(Some(target)) => target,
+        Ok(None) => return utils::not_found_error("User").into_response(),
+        Err(e) => return utils::handle_error(e).into_response(),
+    };
+    if target.id == project.owner_id {
+        return utils::validation_error(vec!["Target user already owns this project".to_string()])
+            .into_response();
+    }
+    let Some(github_username) = target.github_username.as_deref() else {
+        return transfer_error(
+            StatusCode::UNPROCESSABLE_ENTITY,
+            ErrorCode::GithubUsernameRequired,
+            "Target user has no GitHub username to verify repository access with",
+            None,
+        );
+    };
+
+    // 🔑 Ask GitHub now, not whatever was true when the target signed up
+    let Some((owner, repo)) = project.repository.split_once('/') else {
+        return utils::validation_error(vec!["Project repository is not owner/repo".to_string()])
+            .into_response();
+    };
+    match app_state
+        .github
+        .has_write_access(owner, repo, github_username)
+        .await
+    {
+        Ok(true) => {}
+        Ok(false) => {
+            return transfer_error(
+                StatusCode::FORBIDDEN,
+                ErrorCode::NoWriteAccess,
+                "Target user cannot push to the repository",
+                None,
+            )
+        }
+        Err(e) => {
+            warn!("⚠️ Could not verify {}'s access: {:#}", github_username, e);
+            return transfer_error(
+                StatusCode::BAD_GATEWAY,
+                ErrorCode::GithubUnavailable,
+                "Could not verify repository access with GitHub",
+                None,
+            );
+        }
+    }
+
+    // 🧾 Ownership, notifications and the audit entry commit together
+    let transferred = async {
+        let mut tx = app_state.tx().await?;
+        let outcome = async {
+            let outcome =
+                match apply_transfer(&mut tx, &project, target.id, request.merge_config).await? {
+                    Ok(outcome) => outcome,
+                    Err(blocked) => return Ok(Err(blocked)),
+                };
+            record_transfer(&mut tx, &user, &project, &target, &request, &outcome).await?;
+            Ok(Ok(outcome))
+        }
+        .await;
+        tx.finish(outcome).await
+    }
+    .await;
+    let outcome = match transferred {
+        Ok(Ok(outcome)) => outcome,
+        Ok(Err(TransferBlocked::Collision(existing))) => {
+            return transfer_error(
+                StatusCode::CONFLICT,
+                ErrorCode::RepositoryCollision,
+                "Target user already has a project for this repository, set merge_config to \
+                 \"transferred\" or \"existing\" to merge them",
+                Some(serde_json::json!({ "existing_project_id": existing })),
+            )
+        }
+        Ok(Err(TransferBlocked::Conflict)) => {
+            return transfer_error(
+                StatusCode::CONFLICT,
+                ErrorCode::ConcurrentChange,
+                "The project changed while transferring, try again",
+                None,
+            )
+        }
+        Err(e) => return utils::handle_error(e).into_response(),
+    };
+
+    info!(
+        "🔀 Project {} ({}) transferred to {} by {}",
+        project.id, project.repository, target.email, user.email
+    );
+    (
+        StatusCode::OK,
+        Json(ApiResponse::success(
+            "Project transferred".to_string(),
+            outcome,
+        )),
+    )
+        .into_response()
+}
+
+/// 🔔 Tell both owners about the transfer and audit it
+async fn record_transfer(
+    conn: &mut PgConnection,
+    user: &AuthenticatedUser,
+    project: &TransferProject,
+    target: &User,
+    request: &TransferRequest,
+    outcome: &TransferOutcome,
+) -> anyhow::Result<()> {
+    let summary = if outcome.merged {
+        format!(
+            "{} was transferred to {} and merged with their existing project",
+            project.repository, target.email
+        )
+    } else {
+        format!("{} was transferred to {}", project.repository, target.email)
+    };
+    for recipient in [project.owner_id, target.id] {
+        notify(conn, recipient, &summary, outcome.project_id).await?;
+    }
+    record_audit(
+        conn,
+        &user.email,
+        "project_transferred",
+        serde_json::json!({
+            "project_id": project.id,
+            "repository": project.repository,
+            "from_owner_id": project.owner_id,
+            "to_owner_id": target.id,
+            "merged": outcome.merged,
+            "merge_config": request.merge_config,
+            "surviving_project_id": outcome.project_id,
+            "removed_project_id": outcome.removed_project_id,
+        }),
+    )
+    .await
+}
+
+/// 🔒 Move ownership (in a savepoint of the caller's transaction), merging on a
+/// repository collision
+async fn apply_transfer(
+    conn: &mut PgConnection,
+    project: &TransferProject,
+    target_id: Uuid,
+    merge_config: Option<MergeConfig>,
+) -> anyhow::Result<Result<TransferOutcome, TransferBlocked>> {
+    let mut tx = conn.begin().await.context("Failed to start transfer")?;
+
+    // 🔒 Lock both sides so a concurrent transfer or edit can't slip in between
+    let locked: Option<Uuid> =
+        sqlx::query_scalar("SELECT owner_id FROM projects WHERE id = $1 FOR UPDATE")
+            .bind(project.id)
+            .fetch_optional(&mut *tx)
+            .await
+            .context("Failed to lock project")?;
+    if locked != Some(project.owner_id) {
+        return Ok(Err(TransferBlocked::Conflict));
+    }
+    let existing: Option<TransferProject> = sqlx::query_as(
+        "SELECT id, owner_id, repository, config, created_at FROM projects \
+         WHERE owner_id = $1 AND repository = $2 FOR UPDATE",
+    )
+    .bind(target_id)
+    .bind(&project.repository)
+    .fetch_optional(&mut *tx)
+    .await
+    .context("Failed to look up target's projects")?;
+
+    let outcome = match existing {
+        None => {
+            let moved =
+                sqlx::query("UPDATE projects SET owner_id = $2, updated_at = NOW() WHERE id = $1")
+                    .bind(project.id)
+                    .bind(target_id)
+                    .execute(&mut *tx)
+                    .await;
+            match moved {
+                Ok(_) => {}
+                // 🏃 The target registered the repository after our lookup
+                Err(sqlx::Error::Database(e)) if e.is_unique_violation() => {
+                    return Ok(Err(TransferBlocked::Conflict))
+                }
+                Err(e) => return Err(e).context("Failed to transfer project"),
+            }
+            TransferOutcome {
+                project_id: project.id,
+                owner_id: target_id,
+                merged: false,
+                removed_project_id: None,
+            }
+        }
+        Some(existing) => {
+            let Some(merge_config) = merge_config else {
+                return Ok(Err(TransferBlocked::Collision(existing.id)));
+            };
+            merge_projects(&mut tx, project, &existing, target_id, merge_config).await?
+        }
+    };
+
+    tx.commit().await.context("Failed to commit transfer")?;
+    Ok(Ok(outcome))
+}
+
+/// 🧩 Keep the older project with the chosen config, fold the newer one into it
+async fn merge_projects(
+    conn: &mut PgConnection,
+    transferred: &TransferProject,
+    existing: &TransferProject,
+    target_id: Uuid,
+    merge_config: MergeConfig,
+) -> anyhow::Result<TransferOutcome> {
+    let (survivor, removed) = if transferred.created_at <= existing.created_at {
+        (transferred, existing)
+    } else {
+        (existing, transferred)
+    };
+    let config = match merge_config {
+        MergeConfig::Transferred => &transferred.config,
+        MergeConfig::Existing => &existing.config,
+    };
+
+    sqlx::query("UPDATE webhooks SET project_id = $1 WHERE project_id = $2")
+        .bind(survivor.id)
+        .bind(removed.id)
+        .execute(&mut *conn)
+        .await
+        .context("Failed to move webhooks")?;
+    // 🪝 Both projects share the repository, so a GitHub hook either one registered is theirs
+    sqlx::query(
+        r#"
+        UPDATE projects SET
+            webhook_id = COALESCE(projects.webhook_id, removed.webhook_id),
+            webhook_error = CASE WHEN COALESCE(projects.webhook_id, removed.webhook_id) IS NULL
+                THEN projects.webhook_error END
+        FROM projects removed
+        WHERE projects.id = $1 AND removed.id = $2
+        "#,
+    )
+    .bind(survivor.id)
+    .bind(removed.id)
+    .execute(&mut *conn)
+    .await
+    .context("Failed to move the repository webhook")?;
+    // 🗂️ The survivor serves every repository either project did
+    sqlx::query(
+        r#"
+        INSERT INTO project_repositories (project_id, repository, created_at)
+        SELECT $1, repository, created_at FROM project_repositories WHERE project_id = $2
+        ON CONFLICT DO NOTHING
+        "#,
+    )
+    .bind(survivor.id)
+    .bind(removed.id)
+    .execute(&mut *conn)
+    .await
+    .context("Failed to move project repositories")?;
+    // 🗑️ Delete first so the survivor can take over (owner_id, repository)
+    sqlx::query("DELETE FROM projects WHERE id = $1")
+        .bind(removed.id)
+        .execute(&mut *conn)
+        .await
+        .context("Failed to remove merged project")?;
+    sqlx::query("UPDATE projects SET owner_id = $2, config = $3, updated_at = NOW() WHERE id = $1")
+        .bind(survivor.id)
+        .bind(target_id)
+        .bind(config)
+        .execute(&mut *conn)
+        .await
+        .context("Failed to update merged project")?;
+
+    Ok(TransferOutcome {
+        project_id: survivor.id,
+        owner_id: target_id,
+        merged: true,
+        removed_project_id: Some(removed.id),
+    })
+}
+
+/// 🔔 Tell a user about the transfer
+async fn notify(
+    conn: &mut PgConnection,
+    user_id: Uuid,
+    content: &str,
+    project_id: Uuid,
+) -> anyhow::Result<()> {
+    sqlx::query(
+        "INSERT INTO notifications (user_id, notification_type, title, content, related_id) \
+         VALUES ($1, 'system_update', 'Project transferred', $2, $3)",
+    )
+    .bind(user_id)
+    .bind(content)
+    .bind(project_id)
+    .execute(conn)
+    .await
+    .with_context(|| format!("Failed to notify {} about the transfer", user_id))?;
+    Ok(())
+}
+
+// 🧪 Tests - Handing projects over!
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::models::UserRole;
+    use crate::middleware::auth::jwt_utils;
+    use crate::test_support::{spawn_test_app, TestApp};
+
+    async fn user(app: &TestApp, email: &str, github: Option<&str>, role: UserRole) -> User {
+        sqlx::query_as(
+            "INSERT INTO users (email, name, github_username, password_hash, role) \
+             VALUES ($1, $1, $2, 'x', $3) RETURNING *",
+        )
+        .bind(email)
+        .bind(github)
+        .bind(role)
+        .fetch_one(&app.db_pool)
+        .await
+        .unwrap()
+    }
+
+    async fn project(
+        app: &TestApp,
+        owner: &User,
+        config: serde_json::Value,
+        age_days: i32,
+    ) -> Uuid {
+        sqlx::query_scalar(
+            "INSERT INTO projects (owner_id, repository, config, created_at) \
+             VALUES ($1, '8b-is/smart-tree', $2, NOW() - make_interval(days => $3)) RETURNING id",
+        )
+        .bind(owner.id)
+        .bind(config)
+        .bind(age_days)
+        .fetch_one(&app.db_pool)
+        .await
+        .unwrap()
+    }
+
+    async fn transfer(
+        app: &TestApp,
+        as_user: Option<&User>,
+        id: Uuid,
+        body: serde_json::Value,
+    ) -> (StatusCode, serde_json::Value) {
+        let mut request = app
+            .client
+            .post(app.url(&format!("/api/projects/{}/transfer", id)))
+            .json(&body);
+        if let Some(as_user) = as_user {
+            let token =
+                jwt_utils::create_jwt_token(as_user, &app.app_state.config.auth.jwt_secret, 1)
+                    .unwrap();
+            request = request.bearer_auth(token);
+        }
+        let response = request.send().await.unwrap();
+        let status = StatusCode::from_u16(response.status().as_u16()).unwrap();
+        (status, response.json().await.unwrap_or_default())
+    }
+
+    async fn owner_of(app: &TestApp, id: Uuid) -> Option<Uuid> {
+        sqlx::query_scalar("SELECT owner_id FROM projects WHERE id = $1")
+            .bind(id)
+            .fetch_optional(&app.db_pool)
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_only_owners_and_admins_transfer_to_users_with_write_access() {
+        let Some(app) = spawn_test_app().await else {
+            return;
+        };
+        let owner = user(&app, "owner@example.com", Some("owner"), UserRole::User).await;
+        let target = user(&app, "target@example.com", Some("Target"), UserRole::User).await;
+        let stranger = user(&app, "stranger@example.com", None, UserRole::User).await;
+        let admin = user(&app, "admin@example.com", None, UserRole::Admin).await;
+        let id = project(&app, &owner, serde_json::json!({}), 0).await;
+        let to_target = serde_json::json!({ "to": "target" });
+
+        // 🔐 No token, or a token for someone who doesn't own it
+        let (status, _) = transfer(&app, None, id, to_target.clone()).await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+        let (status, _) = transfer(&app, Some(&stranger), id, to_target.clone()).await;
+        assert_eq!(status, StatusCode::FORBIDDEN);
+
+        // ✍️ GitHub doesn't list the target as a writer yet
+        let (status, body) = transfer(&app, Some(&owner), id, to_target.clone()).await;
+        assert_eq!(status, StatusCode::FORBIDDEN);
+        assert_eq!(body["error"]["code"], "no_write_access");
+        // 👤 Unknown targets, and targets without a GitHub username to check
+        let (status, _) = transfer(
+            &app,
+            Some(&owner),
+            id,
+            serde_json::json!({ "to": "nobody" }),
+        )
+        .await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+        let (status, _) = transfer(
+            &app,
+            Some(&owner),
+            id,
+            serde_json::json!({ "to": "stranger@example.com" }),
+        )
+        .await;
+        assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+        assert_eq!(owner_of(&app, id).await, Some(owner.id));
+
+        // ✅ The owner hands it over, by GitHub username (case-insensitive)
+        app.github
+            .writers
+            .lock()
+            .unwrap()
+            .push(("8b-is/smart-tree".to_string(), "target".to_string()));
+        let (status, body) = transfer(&app, Some(&owner), id, to_target).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["data"]["merged"], false);
+        assert_eq!(owner_of(&app, id).await, Some(target.id));
+
+        // 👑 The previous owner can't take it back, an admin can (by email)
+        app.github
+            .writers
+            .lock()
+            .unwrap()
+            .push(("8b-is/smart-tree".to_string(), "owner".to_string()));
+        let to_owner = serde_json::json!({ "to": "OWNER@example.com" });
+        let (status, _) = transfer(&app, Some(&owner), id, to_owner.clone()).await;
+        assert_eq!(status, StatusCode::FORBIDDEN);
+        let (status, _) = transfer(&app, Some(&admin), id, to_owner).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(owner_of(&app, id).await, Some(owner.id));
+
+        // 🐙 GitHub being down never counts as "has access"
+        *app.github.fail_with.lock().unwrap() = Some("GitHub is down".to_string());
+        let (status, _) = transfer(
+            &app,
+            Some(&owner),
+            id,
+            serde_json::json!({ "to": "target" }),
+        )
+        .await;
+        assert_eq!(status, StatusCode::BAD_GATEWAY);
+        assert_eq!(owner_of(&app, id).await, Some(owner.id));
+        println!("✅ Project transfer permission test passed!");
+    }
+
+    #[tokio::test]
+    async fn test_collision_merges_into_the_older_project_with_the_chosen_config() {
+        let Some(app) = spawn_test_app().await else {
+            return;
+        };
+        let owner = user(&app, "owner@example.com", Some("owner"), UserRole::User).await;
+        let target = user(&app, "target@example.com", Some("target"), UserRole::User).await;
+        app.github
+            .writers
+            .lock()
+            .unwrap()
+            .push(("8b-is/smart-tree".to_string(), "target".to_string()));
+        // 🏠 The target registered the repository first, the transferred one is newer
+        let existing = project(&app, &target, serde_json::json!({ "tone": "existing" }), 10).await;
+        let transferred = project(
+            &app,
+            &owner,
+            serde_json::json!({ "tone": "transferred" }),
+            1,
+        )
+        .await;
+        sqlx::query(
+            "INSERT INTO webhooks (project_id, event_type, payload) VALUES ($1, 'issues', '{}')",
+        )
+        .bind(transferred)
+        .execute(&app.db_pool)
+        .await
+        .unwrap();
+
+        // 🤝 Without a choice the collision is reported, nothing changes
+        let (status, body) = transfer(
+            &app,
+            Some(&owner),
+            transferred,
+            serde_json::json!({ "to": "target@example.com" }),
+        )
+        .await;
+        assert_eq!(status, StatusCode::CONFLICT);
+        assert_eq!(body["error"]["code"], "repository_collision");
+        assert_eq!(
+            body["error"]["details"]["existing_project_id"],
+            existing.to_string()
+        );
+        assert_eq!(owner_of(&app, transferred).await, Some(owner.id));
+
+        // 🧩 Merge keeping the transferred config
+        let (status, body) = transfer(
+            &app,
+            Some(&owner),
+            transferred,
+            serde_json::json!({ "to": "target@example.com", "merge_config": "transferred" }),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["data"]["merged"], true);
+        assert_eq!(body["data"]["project_id"], existing.to_string());
+        assert_eq!(body["data"]["removed_project_id"], transferred.to_string());
+
+        assert_eq!(owner_of(&app, transferred).await, None);
+        let (owner_id, config): (Uuid, serde_json::Value) =
+            sqlx::query_as("SELECT owner_id, config FROM projects WHERE id = $1")
+                .bind(existing)
+                .fetch_one(&app.db_pool)
+                .await
+                .unwrap();
+        assert_eq!(owner_id, target.id);
+        assert_eq!(config["tone"], "transferred");
+        let webhooks: i64 =
+            sqlx::query_scalar("SELECT COUNT(*) FROM webhooks WHERE project_id = $1")
+                .bind(existing)
+                .fetch_one(&app.db_pool)
+                .await
+                .unwrap();
+        assert_eq!(webhooks, 1);
+
+        // 🔔 Both parties hear about it, and it is audit-logged under the caller
+        let notified: Vec<Uuid> = sqlx::query_scalar(
+            "SELECT user_id FROM notifications WHERE related_id = $1 ORDER BY user_id",
+        )
+        .bind(existing)
+        .fetch_all(&app.db_pool)
+        .await
+        .unwrap();
+        let mut expected = vec![owner.id, target.id];
+        expected.sort();
+        assert_eq!(notified, expected);
+        let (actor, details): (String, serde_json::Value) = sqlx::query_as(
+            "SELECT actor, details FROM admin_audit_log WHERE action = 'project_transferred'",
+        )
+        .fetch_one(&app.db_pool)
+        .await
+        .unwrap();
+        assert_eq!(actor, "owner@example.com");
+        assert_eq!(details["merge_config"], "transferred");
+        assert_eq!(details["removed_project_id"], transferred.to_string());
+        println!("✅ Project transfer merge test passed!");
+    }
+
+    #[tokio::test]
+    async fn test_a_failed_audit_entry_undoes_the_whole_transfer() {
+        let Some(app) = spawn_test_app().await else {
+            return;
+        };
+        let pool = &app.db_pool;
+        let owner = user(&app, "owner@example.com", Some("owner"), UserRole::User).await;
+        let target = user(&app, "target@example.com", Some("target"), UserRole::User).await;
+        app.github
+            .writers
+            .lock()
+            .unwrap()
+            .push(("8b-is/smart-tree".to_string(), "target".to_string()));
+        let id = project(&app, &owner, serde_json::json!({}), 0).await;
+
+        // 💥 The last write of the handler fails
+        sqlx::raw_sql(
+            r#"
+            CREATE FUNCTION refuse_audit() RETURNS trigger AS $$
+            BEGIN RAISE EXCEPTION 'audit log is read-only'; END;
+            $$ LANGUAGE plpgsql;
+            CREATE TRIGGER refuse_audit BEFORE INSERT ON admin_audit_log
+                FOR EACH ROW EXECUTE FUNCTION refuse_audit();
+            "#,
+        )
+        .execute(pool)
+        .await
+        .unwrap();
+        let to_target = serde_json::json!({ "to": "target" });
+        let (status, _) = transfer(&app, Some(&owner), id, to_target.clone()).await;
+        assert_eq!(status, StatusCode::INTERNAL_SERVER_ERROR);
+        assert_eq!(owner_of(&app, id).await, Some(owner.id));
+        let notifications: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM notifications")
+            .fetch_one(pool)
+            .await
+            .unwrap();
+        assert_eq!(notifications, 0);
+
+        // ✅ With the audit log writable again it all goes through
+        sqlx::query("DROP TRIGGER refuse_audit ON admin_audit_log")
+            .execute(pool)
+            .await
+            .unwrap();
+        let (status, _) = transfer(&app, Some(&owner), id, to_target).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(owner_of(&app, id).await, Some(target.id));
+        let notifications: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM notifications")
+            .fetch_one(pool)
+            .await
+            .unwrap();
+        assert_eq!(notifications, 2);
+        println!("✅ Project transfer rollback test passed!");
+    }
+}
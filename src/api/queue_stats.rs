@@ -0,0 +1,268 @@
+// ⏳ Queue Statistics - "Pending" with an actual sense of time! ⏳
+// One snapshot of the pending queue and recent processing durations, shared by
+// every status poll and recomputed at most every 30 seconds, so a room full of
+// refreshing submitters costs two queries per half minute instead of two per poll.
+// Created with love by Aye & Hue! ✨
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use sqlx::PgPool;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use uuid::Uuid;
+
+use crate::database::models::{Feedback, FeedbackStatus};
+
+/// ⏰ How long a snapshot is reused before it's recomputed
+pub const QUEUE_STATS_TTL: Duration = Duration::from_secs(30);
+
+/// 📅 Completed items considered for the average duration
+const DURATION_WINDOW_DAYS: i64 = 7;
+
+/// 📊 The derived numbers shown next to a feedback item's status
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct QueueEstimate {
+    /// 🔢 Pending items submitted before this one (pending items only)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub queue_position: Option<u64>,
+    /// ⏰ When processing should begin (pending items only, needs some history)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub estimated_start: Option<DateTime<Utc>>,
+    /// ⏱️ Average submit-to-completion time over the last 7 days for the same category
+    /// (all categories when this one has no recent history)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub average_duration_seconds: Option<f64>,
+}
+
+/// 📸 The pending queue and recent durations at one moment
+#[derive(Debug, Clone, Default)]
+pub struct QueueSnapshot {
+    /// ⏳ (created_at, id) of every pending item, oldest first
+    pending: Vec<(DateTime<Utc>, Uuid)>,
+    /// ⏱️ Average seconds per `metadata.category`
+    category_durations: HashMap<String, f64>,
+    /// ⏱️ Average seconds across everything (uncategorized included)
+    overall_duration: Option<f64>,
+    /// 👷 Items processed in parallel
+    concurrency: u32,
+}
+
+impl QueueSnapshot {
+    /// 🗄️ Read the pending queue and the last week's durations
+    pub async fn load(pool: &PgPool, concurrency: u32) -> Result<Self> {
+        // ⏸️ Held feedback (see database::processing_holds) isn't waiting on the workers
+        let pending: Vec<(DateTime<Utc>, Uuid)> = sqlx::query_as(
+            r#"
+            SELECT created_at, id FROM feedback f
+            WHERE status = 'pending'
+              AND NOT EXISTS (SELECT 1 FROM processing_holds h WHERE h.feedback_id = f.id)
+            ORDER BY created_at, id
+            "#,
+        )
+        .fetch_all(pool)
+        .await
+        .context("Failed to read the pending queue")?;
+
+        let rows: Vec<(Option<String>, bool, Option<f64>)> = sqlx::query_as(
+            r#"
+            SELECT metadata->>'category',
+                   GROUPING(metadata->>'category') = 1,
+                   AVG(EXTRACT(EPOCH FROM completed_at - created_at))::float8
+            FROM feedback
+            WHERE status = 'completed'
+              AND completed_at IS NOT NULL
+              AND completed_at >= NOW() - make_interval(days => $1)
+            GROUP BY ROLLUP(metadata->>'category')
+            "#,
+        )
+        .bind(DURATION_WINDOW_DAYS as i32)
+        .fetch_all(pool)
+        .await
+        .context("Failed to read recent processing durations")?;
+
+        let mut snapshot = Self {
+            pending,
+            concurrency: concurrency.max(1),
+            ..Self::default()
+        };
+        for (category, overall, seconds) in rows {
+            match (overall, category, seconds) {
+                (true, _, seconds) => snapshot.overall_duration = seconds,
+                (false, Some(category), Some(seconds)) => {
+                    snapshot.category_durations.insert(category, seconds);
+                }
+                _ => {}
+            }
+        }
+        Ok(snapshot)
+    }
+
+    /// 🔢 Pending items created before (created_at, id) - ties are broken by id like the admin list
+    pub fn queue_position(&self, created_at: DateTime<Utc>, id: Uuid) -> u64 {
+        self.pending
+            .partition_point(|entry| *entry < (created_at, id)) as u64
+    }
+
+    /// ⏱️ Average duration for a category, falling back to the overall average
+    pub fn average_duration(&self, category: Option<&str>) -> Option<f64> {
+        category
+            .and_then(|category| self.category_durations.get(category).copied())
+            .or(self.overall_duration)
+    }
+
+    /// 📊 Everything the status response shows for one feedback item
+    pub fn estimate(&self, feedback: &Feedback, now: DateTime<Utc>) -> QueueEstimate {
+        let category = feedback
+            .metadata
+            .as_ref()
+            .and_then(|metadata| metadata.get("category"))
+            .and_then(|category| category.as_str());
+        let average = self.average_duration(category);
+
+        if feedback.status != FeedbackStatus::Pending {
+            return QueueEstimate {
+                average_duration_seconds: average,
+                ..QueueEstimate::default()
+            };
+        }
+
+        let position = self.queue_position(feedback.created_at, feedback.id);
+        // 👷 Everyone ahead is split across the workers; each batch takes about `average`
+        let estimated_start = average.map(|seconds| {
+            let batches_ahead = position / self.concurrency as u64;
+            now + chrono::Duration::milliseconds((batches_ahead as f64 * seconds * 1000.0) as i64)
+        });
+        QueueEstimate {
+            queue_position: Some(position),
+            estimated_start,
+            average_duration_seconds: average,
+        }
+    }
+}
+
+/// 🗃️ The shared, periodically refreshed snapshot
+#[derive(Debug, Default)]
+pub struct QueueStatsCache {
+    entry: Mutex<Option<(Instant, Arc<QueueSnapshot>)>>,
+}
+
+impl QueueStatsCache {
+    /// 📸 The cached snapshot, recomputed when older than QUEUE_STATS_TTL
+    pub async fn snapshot(&self, pool: &PgPool, concurrency: u32) -> Result<Arc<QueueSnapshot>> {
+        if let Some((stored_at, snapshot)) = self.entry.lock().unwrap().as_ref() {
+            if stored_at.elapsed() < QUEUE_STATS_TTL {
+                return Ok(snapshot.clone());
+            }
+        }
+        // 🏃 Concurrent refreshes may both query; the last one wins, which is harmless
+        let snapshot = Arc::new(QueueSnapshot::load(pool, concurrency).await?);
+        *self.entry.lock().unwrap() = Some((Instant::now(), snapshot.clone()));
+        Ok(snapshot)
+    }
+}
+
+// 🧪 Tests - Counting the people in front of you!
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::spawn_test_app;
+
+    #[tokio::test]
+    async fn test_queue_position_and_estimates_from_seeded_rows() {
+        let Some(app) = spawn_test_app().await else {
+            return;
+        };
+        let pool = &app.db_pool;
+
+        // ⏳ Five pending items a minute apart, the last two sharing a timestamp
+        for minutes_ago in [50, 40, 30, 20, 20] {
+            sqlx::query(
+                "INSERT INTO feedback (repository, content, created_at, metadata) VALUES ('8b-is/smart-tree', 'Waiting', NOW() - make_interval(mins => $1), '{\"category\": \"bug\"}')",
+            )
+            .bind(minutes_ago)
+            .execute(pool)
+            .await
+            .unwrap();
+        }
+        // ⏱️ Recent history: bugs take 10 minutes, docs 30, and an old outlier is ignored
+        for (category, minutes, days_ago) in [("bug", 10, 1), ("docs", 30, 2), ("bug", 600, 30)] {
+            sqlx::query(
+                "INSERT INTO feedback (repository, content, status, metadata, created_at, completed_at) VALUES ('8b-is/smart-tree', 'Done', 'completed', jsonb_build_object('category', $1::text), NOW() - make_interval(days => $3, mins => $2), NOW() - make_interval(days => $3))",
+            )
+            .bind(category)
+            .bind(minutes)
+            .bind(days_ago)
+            .execute(pool)
+            .await
+            .unwrap();
+        }
+
+        let snapshot = QueueSnapshot::load(pool, 2).await.unwrap();
+        let pending: Vec<Feedback> = sqlx::query_as(
+            "SELECT * FROM feedback WHERE status = 'pending' ORDER BY created_at, id",
+        )
+        .fetch_all(pool)
+        .await
+        .unwrap();
+        let positions: Vec<u64> = pending
+            .iter()
+            .map(|f| snapshot.queue_position(f.created_at, f.id))
+            .collect();
+        assert_eq!(positions, vec![0, 1, 2, 3, 4]);
+
+        assert_eq!(
+            snapshot.average_duration(Some("bug")).map(f64::round),
+            Some(600.0)
+        );
+        assert_eq!(
+            snapshot.average_duration(Some("docs")).map(f64::round),
+            Some(1800.0)
+        );
+        assert_eq!(
+            snapshot
+                .average_duration(Some("never-seen"))
+                .map(f64::round),
+            Some(1200.0)
+        );
+
+        // 👷 Position 4 with two workers: two batches ahead, 10 minutes each
+        let now = Utc::now();
+        let estimate = snapshot.estimate(&pending[4], now);
+        assert_eq!(estimate.queue_position, Some(4));
+        let wait = estimate.estimated_start.unwrap() - now;
+        assert_eq!(wait.num_seconds(), 1200);
+
+        // ✅ Finished items only get the average
+        let done: Feedback =
+            sqlx::query_as("SELECT * FROM feedback WHERE status = 'completed' LIMIT 1")
+                .fetch_one(pool)
+                .await
+                .unwrap();
+        let estimate = snapshot.estimate(&done, now);
+        assert_eq!(estimate.queue_position, None);
+        assert!(estimate.average_duration_seconds.is_some());
+        println!("✅ Queue position math test passed!");
+    }
+
+    #[tokio::test]
+    async fn test_snapshot_is_cached_between_polls() {
+        let Some(app) = spawn_test_app().await else {
+            return;
+        };
+        let cache = QueueStatsCache::default();
+        let first = cache.snapshot(&app.db_pool, 1).await.unwrap();
+        sqlx::query(
+            "INSERT INTO feedback (repository, content) VALUES ('8b-is/smart-tree', 'New')",
+        )
+        .execute(&app.db_pool)
+        .await
+        .unwrap();
+        let second = cache.snapshot(&app.db_pool, 1).await.unwrap();
+        // 🗃️ Same snapshot within the TTL, even though the queue grew
+        assert!(Arc::ptr_eq(&first, &second));
+        assert_eq!(second.pending.len(), 0);
+        println!("✅ Queue snapshot cache test passed!");
+    }
+}
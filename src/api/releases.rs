@@ -0,0 +1,376 @@
+// 📜 Releases - Everything that changed since the version you're on! 📜
+// Every version published through /mcp/version or the admin MCP page is kept in
+// `releases`, so clients several versions behind can fetch the combined notes:
+// GET /mcp/changelog?product=smart-tree&from=4.6.0&to=4.9.1
+// Yanked releases never show up. Pre-releases only do when `to` is one.
+// Created with love by Aye & Hue! ✨
+
+use anyhow::{Context, Result};
+use axum::{
+    extract::{Query, State},
+    response::{IntoResponse, Json, Response},
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::PgPool;
+
+use crate::api::{downloads::DEFAULT_PRODUCT, settings_cache::ProductRelease, AppState};
+use crate::utils::versions::Version;
+
+/// 📦 One published release
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ReleaseEntry {
+    pub version: String,
+    pub notes: Option<String>,
+    pub features: Vec<String>,
+    pub published_at: DateTime<Utc>,
+}
+
+/// 🔍 GET /mcp/changelog query
+#[derive(Debug, Deserialize)]
+pub struct ChangelogQuery {
+    /// 🌳 Defaults to smart-tree
+    pub product: Option<String>,
+    /// 📍 The version the client has (releases after it are listed)
+    pub from: Option<String>,
+    /// 🎯 The version it's going to (defaults to the latest stable release)
+    pub to: Option<String>,
+}
+
+/// 📜 The releases between two versions, newest first
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ChangelogResponse {
+    pub product: String,
+    pub from: Option<String>,
+    pub to: Option<String>,
+    pub releases: Vec<ReleaseEntry>,
+    /// 📝 All of the above as one markdown document, ready to display
+    pub markdown: String,
+}
+
+/// 🗄️ (version, notes, features, published_at)
+type ReleaseRow = (String, Option<String>, serde_json::Value, DateTime<Utc>);
+
+/// 📥 Record a published version (re-publishing updates its notes and features and un-yanks it)
+pub async fn record_release(
+    pool: &PgPool,
+    product: &str,
+    version: &str,
+    notes: Option<&str>,
+    features: Option<&[String]>,
+) -> Result<()> {
+    sqlx::query(
+        r#"
+        INSERT INTO releases (product, version, notes, features)
+        VALUES ($1, $2, $3, COALESCE($4, '[]'::jsonb))
+        ON CONFLICT (product, version) DO UPDATE
+        SET notes = COALESCE(EXCLUDED.notes, releases.notes),
+            features = COALESCE($4, releases.features),
+            yanked = FALSE
+        "#,
+    )
+    .bind(product)
+    .bind(version)
+    .bind(notes)
+    .bind(features.map(|features| serde_json::json!(features)))
+    .execute(pool)
+    .await
+    .context("Failed to record release")?;
+    Ok(())
+}
+
+/// 🔍 Does the product have a release (yanked or not) with this exact version?
+async fn is_known_release(pool: &PgPool, product: &str, version: &str) -> Result<bool> {
+    sqlx::query_scalar("SELECT EXISTS(SELECT 1 FROM releases WHERE product = $1 AND version = $2)")
+        .bind(product)
+        .bind(version)
+        .fetch_one(pool)
+        .await
+        .context("Failed to look up release")
+}
+
+/// 📜 Releases of `product` after `from` and up to `to`, newest first.
+/// A `from` we have no release for means "everything up to `to`".
+pub async fn changelog(
+    pool: &PgPool,
+    product: &str,
+    from: Option<&str>,
+    to: Option<&Version>,
+) -> Result<Vec<ReleaseEntry>> {
+    let rows: Vec<ReleaseRow> = sqlx::query_as(
+        "SELECT version, notes, features, published_at FROM releases WHERE product = $1 AND NOT yanked",
+    )
+    .bind(product)
+    .fetch_all(pool)
+    .await
+    .context("Failed to load releases")?;
+
+    let mut releases: Vec<(Version, ReleaseEntry)> = rows
+        .into_iter()
+        .filter_map(|(version, notes, features, published_at)| {
+            let parsed = Version::parse(&version)?;
+            Some((
+                parsed,
+                ReleaseEntry {
+                    version,
+                    notes,
+                    features: serde_json::from_value(features).unwrap_or_default(),
+                    published_at,
+                },
+            ))
+        })
+        .collect();
+
+    // 🧪 Pre-releases only belong in the list when the target is one
+    let include_prereleases = to.is_some_and(Version::is_prerelease);
+    releases.retain(|(version, _)| include_prereleases || !version.is_prerelease());
+
+    let upper = match to {
+        Some(to) => Some(to.clone()),
+        None => releases.iter().map(|(version, _)| version).max().cloned(),
+    };
+    let lower = match from {
+        Some(from) if is_known_release(pool, product, from).await? => Version::parse(from),
+        _ => None,
+    };
+
+    releases.retain(|(version, _)| {
+        upper.as_ref().is_some_and(|upper| version <= upper)
+            && lower.as_ref().is_none_or(|lower| version > lower)
+    });
+    releases.sort_by(|(a, _), (b, _)| b.cmp(a));
+    Ok(releases.into_iter().map(|(_, entry)| entry).collect())
+}
+
+/// 📝 One markdown document: a section per release, newest first
+pub fn changelog_markdown(releases: &[ReleaseEntry]) -> String {
+    releases
+        .iter()
+        .map(|release| {
+            let mut section = format!("## {}\n", release.version);
+            if let Some(notes) = release.notes.as_deref().filter(|n| !n.trim().is_empty()) {
+                section.push_str(&format!("\n{}\n", notes.trim()));
+            }
+            if !release.features.is_empty() {
+                section.push('\n');
+                for feature in &release.features {
+                    section.push_str(&format!("- {}\n", feature));
+                }
+            }
+            section
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// 🏷️ The newest stable release among `releases` (versions we can't read don't count)
+pub fn latest_stable(releases: &[ProductRelease]) -> Option<&ProductRelease> {
+    releases
+        .iter()
+        .filter_map(|release| Some((Version::parse(&release.version)?, release)))
+        .filter(|(version, _)| !version.is_prerelease())
+        .max_by(|(a, _), (b, _)| a.cmp(b))
+        .map(|(_, release)| release)
+}
+
+/// 📜 GET /mcp/changelog - combined release notes between two versions
+pub async fn mcp_changelog(
+    State(app_state): State<AppState>,
+    Query(query): Query<ChangelogQuery>,
+) -> Response {
+    let product = query
+        .product
+        .filter(|product| !product.is_empty())
+        .unwrap_or_else(|| DEFAULT_PRODUCT.to_string());
+    let to = match query.to.as_deref() {
+        Some(to) => match Version::parse(to) {
+            Some(version) => Some(version),
+            None => {
+                return crate::api::utils::validation_error(vec![format!(
+                    "`to` is not a version: {}",
+                    to
+                )])
+                .into_response()
+            }
+        },
+        None => None,
+    };
+
+    match changelog(
+        &app_state.db_pool,
+        &product,
+        query.from.as_deref(),
+        to.as_ref(),
+    )
+    .await
+    {
+        Ok(releases) => Json(ChangelogResponse {
+            markdown: changelog_markdown(&releases),
+            to: query
+                .to
+                .or_else(|| releases.first().map(|release| release.version.clone())),
+            product,
+            from: query.from,
+            releases,
+        })
+        .into_response(),
+        Err(e) => crate::api::utils::handle_error(e).into_response(),
+    }
+}
+
+// 🧪 Tests - Reading the history books!
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::spawn_test_app;
+    use axum::http::StatusCode;
+
+    #[test]
+    fn test_markdown_has_a_section_per_release() {
+        let release = |version: &str, notes: Option<&str>, features: &[&str]| ReleaseEntry {
+            version: version.to_string(),
+            notes: notes.map(str::to_string),
+            features: features.iter().map(|f| f.to_string()).collect(),
+            published_at: Utc::now(),
+        };
+        let markdown = changelog_markdown(&[
+            release("4.9.1", Some("Faster trees\n"), &["quantum mode"]),
+            release("4.9.0", None, &[]),
+        ]);
+        assert_eq!(
+            markdown,
+            "## 4.9.1\n\nFaster trees\n\n- quantum mode\n\n## 4.9.0\n"
+        );
+        assert_eq!(changelog_markdown(&[]), "");
+        println!("✅ Changelog markdown test passed!");
+    }
+
+    #[test]
+    fn test_latest_stable_goes_by_version_precedence() {
+        let release = |version: &str| ProductRelease {
+            version: version.to_string(),
+            notes: None,
+            features: Vec::new(),
+        };
+        let releases = [
+            release("1.10.0"),
+            release("1.9.0"),
+            release("2.0.0-beta.1"),
+            release("latest"),
+        ];
+        assert_eq!(
+            latest_stable(&releases).map(|release| release.version.as_str()),
+            Some("1.10.0")
+        );
+        assert!(latest_stable(&[release("2.0.0-rc.1")]).is_none());
+        println!("✅ Latest stable release test passed!");
+    }
+
+    #[tokio::test]
+    async fn test_changelog_lists_releases_between_versions() {
+        let Some(app) = spawn_test_app().await else {
+            return;
+        };
+        for (version, notes, features, yanked) in [
+            ("4.6.0", "Old times", "[]", false),
+            ("4.7.0", "Colours", r#"["colour output"]"#, false),
+            ("4.8.0", "Broken build", "[]", true),
+            ("4.9.0", "Speed", "[]", false),
+            ("4.9.1", "Fixes", r#"["quieter logs"]"#, false),
+            ("5.0.0-rc.1", "Preview", "[]", false),
+        ] {
+            sqlx::query(
+                "INSERT INTO releases (product, version, notes, features, yanked) VALUES ('smart-tree', $1, $2, $3::jsonb, $4)",
+            )
+            .bind(version)
+            .bind(notes)
+            .bind(features)
+            .bind(yanked)
+            .execute(&app.db_pool)
+            .await
+            .unwrap();
+        }
+        let fetch = |query: &str| {
+            let request = app
+                .client
+                .get(app.url(&format!("/mcp/changelog?{}", query)));
+            async move {
+                let response = request.send().await.unwrap();
+                assert_eq!(response.status(), StatusCode::OK);
+                response.json::<ChangelogResponse>().await.unwrap()
+            }
+        };
+        let versions = |changelog: &ChangelogResponse| -> Vec<String> {
+            changelog
+                .releases
+                .iter()
+                .map(|release| release.version.clone())
+                .collect()
+        };
+
+        // 📜 Strictly after `from`, up to `to`, newest first, without the yanked 4.8.0
+        let changelog = fetch("product=smart-tree&from=4.6.0&to=4.9.1").await;
+        assert_eq!(versions(&changelog), vec!["4.9.1", "4.9.0", "4.7.0"]);
+        assert!(changelog
+            .markdown
+            .starts_with("## 4.9.1\n\nFixes\n\n- quieter logs\n"));
+        assert!(changelog.markdown.contains("- colour output"));
+        assert!(!changelog.markdown.contains("Broken build"));
+
+        // 🎯 Without `to`: up to the latest stable release (the rc stays out)
+        let changelog = fetch("from=4.9.0").await;
+        assert_eq!(versions(&changelog), vec!["4.9.1"]);
+        assert_eq!(changelog.to.as_deref(), Some("4.9.1"));
+
+        // 🧪 Aiming at a pre-release brings pre-releases in
+        let changelog = fetch("from=4.9.0&to=5.0.0-rc.1").await;
+        assert_eq!(versions(&changelog), vec!["5.0.0-rc.1", "4.9.1"]);
+
+        // 🤷 A `from` we never released means everything up to `to`
+        let changelog = fetch("from=4.6.5&to=4.7.0").await;
+        assert_eq!(versions(&changelog), vec!["4.7.0", "4.6.0"]);
+
+        // 📭 Nothing for another product, and a `to` that isn't a version is a 400
+        assert!(fetch("product=other").await.releases.is_empty());
+        let bad = app
+            .client
+            .get(app.url("/mcp/changelog?to=latest"))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(bad.status(), StatusCode::BAD_REQUEST);
+        println!("✅ Changelog range test passed!");
+    }
+
+    #[tokio::test]
+    async fn test_publishing_a_version_records_the_release() {
+        let Some(app) = spawn_test_app().await else {
+            return;
+        };
+        let features = vec!["quantum mode".to_string()];
+        record_release(
+            &app.db_pool,
+            DEFAULT_PRODUCT,
+            "5.1.0",
+            Some("New tree"),
+            Some(&features),
+        )
+        .await
+        .unwrap();
+        sqlx::query("UPDATE releases SET yanked = TRUE")
+            .execute(&app.db_pool)
+            .await
+            .unwrap();
+        // 🔁 Publishing it again un-yanks it and keeps the notes and features
+        record_release(&app.db_pool, DEFAULT_PRODUCT, "5.1.0", None, None)
+            .await
+            .unwrap();
+        let releases = changelog(&app.db_pool, DEFAULT_PRODUCT, None, None)
+            .await
+            .unwrap();
+        assert_eq!(releases.len(), 1);
+        assert_eq!(releases[0].notes.as_deref(), Some("New tree"));
+        assert_eq!(releases[0].features, features);
+        println!("✅ Release recording test passed!");
+    }
+}
@@ -0,0 +1,537 @@
+// 🔖 Saved Views - Name a feedback filter once, open it forever! 🔖
+// Each admin keeps their own named sets of feedback list filters (see
+// database::saved_views). The JSON API under /admin/api/views lists, creates, updates
+// and deletes them; the feedback page shows them in a "Views" dropdown, saves the
+// filters it is showing under a new name (POST /admin/views) and opens a view through
+// `/admin/feedback?view=<id>`, which redirects to the view's filters.
+// Created with love by Aye & Hue! ✨
+
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::{IntoResponse, Json, Redirect, Response},
+    Form,
+};
+use axum_extra::extract::cookie::CookieJar;
+use serde::Deserialize;
+use tracing::warn;
+use uuid::Uuid;
+
+use crate::{
+    api::{
+        admin::{admin_identity, html_escape, require_admin_api_auth, require_admin_auth},
+        json::ApiJson,
+        utils::{handle_error, not_found_error, unauthorized_error, validation_error},
+        ApiResponse, AppState, ErrorCode,
+    },
+    database::saved_views::{self, SaveRefused, SavedView, ViewFilters, VIEW_NAME_MAX_CHARS},
+};
+
+/// ➕ POST /admin/api/views body
+#[derive(Debug, Deserialize)]
+pub struct CreateViewRequest {
+    pub name: String,
+    #[serde(default)]
+    pub filters: ViewFilters,
+}
+
+/// ✏️ PUT /admin/api/views/:id body (missing fields are left alone)
+#[derive(Debug, Deserialize)]
+pub struct UpdateViewRequest {
+    pub name: Option<String>,
+    pub filters: Option<ViewFilters>,
+}
+
+/// 📮 The feedback page's "Save view" form
+#[derive(Debug, Deserialize)]
+pub struct SaveViewForm {
+    pub name: String,
+    /// 🔗 The feedback page link whose filters are saved
+    pub return_to: String,
+}
+
+/// 📮 The feedback page's view dropdown, when its Delete button is pressed
+#[derive(Debug, Deserialize)]
+pub struct DeleteViewForm {
+    pub view: Uuid,
+}
+
+/// 🪪 Views belong to whoever the audit log says is signed in
+async fn owner(jar: &CookieJar, app_state: &AppState) -> Option<String> {
+    admin_identity(jar, app_state)
+        .await
+        .map(|identity| identity.actor)
+}
+
+/// 🔎 The filters in a feedback page link's query string
+pub fn filters_from_link(link: &str) -> ViewFilters {
+    let mut filters = ViewFilters::default();
+    let Ok(url) = reqwest::Url::parse(&format!("http://localhost{}", link)) else {
+        return filters;
+    };
+    for (name, value) in url.query_pairs() {
+        let slot = match name.as_ref() {
+            "range" => &mut filters.range,
+            "sort" => &mut filters.sort,
+            "dir" => &mut filters.dir,
+            "tag" => &mut filters.tag,
+            "source" => &mut filters.source,
+            "status" => &mut filters.status,
+            "repo" => &mut filters.repo,
+            "q" => &mut filters.q,
+            "from" => &mut filters.from,
+            "to" => &mut filters.to,
+            _ => continue,
+        };
+        *slot = Some(value.into_owned());
+    }
+    filters
+}
+
+/// 🚫 The API answer for a refused save
+fn refused(refusal: SaveRefused) -> Response {
+    match refusal {
+        SaveRefused::InvalidName => validation_error(vec![format!(
+            "name must be 1 to {} characters",
+            VIEW_NAME_MAX_CHARS
+        )])
+        .into_response(),
+        SaveRefused::DuplicateName => (
+            StatusCode::CONFLICT,
+            Json(ApiResponse::<()>::error(
+                ErrorCode::DuplicateName,
+                "You already have a view with that name".to_string(),
+                None,
+            )),
+        )
+            .into_response(),
+    }
+}
+
+/// 📋 GET /admin/api/views - the signed-in admin's views
+pub async fn list_views_handler(State(app_state): State<AppState>, jar: CookieJar) -> Response {
+    if let Some(denied) = require_admin_api_auth(&jar, &app_state).await {
+        return denied;
+    }
+    let Some(owner) = owner(&jar, &app_state).await else {
+        return unauthorized_error().into_response();
+    };
+    match saved_views::list(&app_state.db_pool, &owner).await {
+        Ok(views) => Json(ApiResponse::success(
+            format!("{} saved views", views.len()),
+            views,
+        ))
+        .into_response(),
+        Err(e) => handle_error(e).into_response(),
+    }
+}
+
+/// ➕ POST /admin/api/views - save a named set of filters
+pub async fn create_view_handler(
+    State(app_state): State<AppState>,
+    jar: CookieJar,
+    ApiJson(request): ApiJson<CreateViewRequest>,
+) -> Response {
+    if let Some(denied) = require_admin_api_auth(&jar, &app_state).await {
+        return denied;
+    }
+    let Some(owner) = owner(&jar, &app_state).await else {
+        return unauthorized_error().into_response();
+    };
+    let filters = match request.filters.normalized() {
+        Ok(filters) => filters,
+        Err(reason) => return validation_error(vec![reason]).into_response(),
+    };
+    match saved_views::create(&app_state.db_pool, &owner, &request.name, &filters).await {
+        Ok(Ok(view)) => (
+            StatusCode::CREATED,
+            Json(ApiResponse::success(
+                format!("Saved view '{}'", view.name),
+                view,
+            )),
+        )
+            .into_response(),
+        Ok(Err(refusal)) => refused(refusal),
+        Err(e) => handle_error(e).into_response(),
+    }
+}
+
+/// ✏️ PUT /admin/api/views/:id - rename a view and/or replace its filters
+pub async fn update_view_handler(
+    State(app_state): State<AppState>,
+    jar: CookieJar,
+    Path(view_id): Path<Uuid>,
+    ApiJson(request): ApiJson<UpdateViewRequest>,
+) -> Response {
+    if let Some(denied) = require_admin_api_auth(&jar, &app_state).await {
+        return denied;
+    }
+    let Some(owner) = owner(&jar, &app_state).await else {
+        return unauthorized_error().into_response();
+    };
+    let filters = match request.filters.map(ViewFilters::normalized).transpose() {
+        Ok(filters) => filters,
+        Err(reason) => return validation_error(vec![reason]).into_response(),
+    };
+    match saved_views::update(
+        &app_state.db_pool,
+        &owner,
+        view_id,
+        request.name.as_deref(),
+        filters.as_ref(),
+    )
+    .await
+    {
+        Ok(Ok(Some(view))) => Json(ApiResponse::success(
+            format!("Updated view '{}'", view.name),
+            view,
+        ))
+        .into_response(),
+        Ok(Ok(None)) => not_found_error("Saved view").into_response(),
+        Ok(Err(refusal)) => refused(refusal),
+        Err(e) => handle_error(e).into_response(),
+    }
+}
+
+/// 🗑️ DELETE /admin/api/views/:id
+pub async fn delete_view_handler(
+    State(app_state): State<AppState>,
+    jar: CookieJar,
+    Path(view_id): Path<Uuid>,
+) -> Response {
+    if let Some(denied) = require_admin_api_auth(&jar, &app_state).await {
+        return denied;
+    }
+    let Some(owner) = owner(&jar, &app_state).await else {
+        return unauthorized_error().into_response();
+    };
+    match saved_views::delete(&app_state.db_pool, &owner, view_id).await {
+        Ok(true) => Json(ApiResponse::<()>::success_no_data(
+            "Deleted view".to_string(),
+        ))
+        .into_response(),
+        Ok(false) => not_found_error("Saved view").into_response(),
+        Err(e) => handle_error(e).into_response(),
+    }
+}
+
+/// 📮 POST /admin/views - save the feedback page's current filters under a name, then
+/// show the view
+pub async fn save_view_form(
+    State(app_state): State<AppState>,
+    jar: CookieJar,
+    Form(form): Form<SaveViewForm>,
+) -> Response {
+    if let Some(redirect) = require_admin_auth(&jar, &app_state).await {
+        return redirect;
+    }
+    let Some(owner) = owner(&jar, &app_state).await else {
+        return Redirect::to("/admin/login").into_response();
+    };
+    let filters = filters_from_link(&form.return_to)
+        .normalized()
+        .unwrap_or_default();
+    match saved_views::create(&app_state.db_pool, &owner, &form.name, &filters).await {
+        Ok(Ok(view)) => Redirect::to(&view.filters.link()).into_response(),
+        // 🤷 A blank or taken name just leaves the admin where they were
+        Ok(Err(refusal)) => {
+            warn!("🔖 View '{}' not saved: {:?}", form.name, refusal);
+            Redirect::to(&filters.link()).into_response()
+        }
+        Err(e) => handle_error(e).into_response(),
+    }
+}
+
+/// 📮 POST /admin/views/delete - delete the view picked in the dropdown
+pub async fn delete_view_form(
+    State(app_state): State<AppState>,
+    jar: CookieJar,
+    Form(form): Form<DeleteViewForm>,
+) -> Response {
+    if let Some(redirect) = require_admin_auth(&jar, &app_state).await {
+        return redirect;
+    }
+    let Some(owner) = owner(&jar, &app_state).await else {
+        return Redirect::to("/admin/login").into_response();
+    };
+    if let Err(e) = saved_views::delete(&app_state.db_pool, &owner, form.view).await {
+        return handle_error(e).into_response();
+    }
+    Redirect::to("/admin/feedback").into_response()
+}
+
+/// 🔗 Where `/admin/feedback?view=<id>` leads: the view's filters, or the unfiltered
+/// list when the admin has no such view
+pub async fn view_link(jar: &CookieJar, app_state: &AppState, view_id: Uuid) -> String {
+    let Some(owner) = owner(jar, app_state).await else {
+        return "/admin/feedback".to_string();
+    };
+    match saved_views::find(&app_state.db_pool, &owner, view_id).await {
+        Ok(Some(view)) => view.filters.link(),
+        Ok(None) => "/admin/feedback".to_string(),
+        Err(e) => {
+            warn!("⚠️ Failed to open saved view {}: {:#}", view_id, e);
+            "/admin/feedback".to_string()
+        }
+    }
+}
+
+/// 📋 The signed-in admin's views, for the feedback page dropdown
+pub async fn views_for(jar: &CookieJar, app_state: &AppState) -> Vec<SavedView> {
+    let Some(owner) = owner(jar, app_state).await else {
+        return Vec::new();
+    };
+    saved_views::list(&app_state.db_pool, &owner)
+        .await
+        .unwrap_or_else(|e| {
+            warn!("⚠️ Failed to load saved views: {:#}", e);
+            Vec::new()
+        })
+}
+
+/// 🔖 "Views" dropdown for the feedback page: open or delete a saved view, or save the
+/// filters on screen (`return_to`) as a new one
+pub fn render_view_picker(views: &[SavedView], return_to: &str) -> String {
+    let current = filters_from_link(return_to)
+        .normalized()
+        .unwrap_or_default();
+    let open = if views.is_empty() {
+        r#"<p class="muted">No saved views yet</p>"#.to_string()
+    } else {
+        let options: String = views
+            .iter()
+            .map(|view| {
+                format!(
+                    r#"<option value="{}"{}>{}</option>"#,
+                    view.id,
+                    if view.filters.0 == current {
+                        " selected"
+                    } else {
+                        ""
+                    },
+                    html_escape(&view.name)
+                )
+            })
+            .collect();
+        format!(
+            r#"<form method="GET" action="/admin/feedback">
+                    <select name="view" aria-label="Saved view">{}</select>
+                    <button type="submit" class="btn btn-primary">Open</button>
+                    <button type="submit" class="btn" formmethod="POST" formaction="/admin/views/delete">Delete</button>
+                </form>"#,
+            options
+        )
+    };
+    format!(
+        r#"<details class="saved-views">
+                <summary>Views</summary>
+                <div class="saved-views-menu">
+                {}
+                <form method="POST" action="/admin/views">
+                    <input type="hidden" name="return_to" value="{}">
+                    <input type="text" name="name" maxlength="{}" placeholder="Name these filters" aria-label="View name" required>
+                    <button type="submit" class="btn">Save view</button>
+                </form>
+                </div>
+            </details>"#,
+        open,
+        html_escape(return_to),
+        VIEW_NAME_MAX_CHARS
+    )
+}
+
+// 🧪 Tests - Bookmarking the good stuff!
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::models::Feedback;
+    use crate::test_support::spawn_test_app;
+    use serde_json::{json, Value};
+
+    #[test]
+    fn test_filters_are_read_back_from_feedback_links() {
+        let filters = filters_from_link(
+            "/admin/feedback?range=7d&status=failed&repo=8b-is%2Fsmart-tree&q=dark%20mode&cursor=abc",
+        );
+        assert_eq!(
+            filters,
+            ViewFilters {
+                range: Some("7d".to_string()),
+                status: Some("failed".to_string()),
+                repo: Some("8b-is/smart-tree".to_string()),
+                q: Some("dark mode".to_string()),
+                ..ViewFilters::default()
+            }
+        );
+        assert_eq!(filters_from_link("/admin/feedback"), ViewFilters::default());
+        println!("✅ Saved view link parsing test passed!");
+    }
+
+    #[tokio::test]
+    async fn test_saved_views_crud_and_open_from_the_feedback_page() {
+        let Some(app) = spawn_test_app().await else {
+            return;
+        };
+        assert_eq!(
+            app.client
+                .get(app.url("/admin/api/views"))
+                .send()
+                .await
+                .unwrap()
+                .status(),
+            401
+        );
+        app.login_admin().await.unwrap();
+
+        // ➕ Create, and refuse a second view with the same name
+        let filters = json!({ "status": "failed", "repo": "8b-is/smart-tree", "q": "dark mode" });
+        let created = app
+            .client
+            .post(app.url("/admin/api/views"))
+            .json(&json!({ "name": " Failed dark mode ", "filters": filters }))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(created.status(), 201);
+        let created: Value = created.json().await.unwrap();
+        let id = created["data"]["id"].as_str().unwrap().to_string();
+        assert_eq!(created["data"]["name"], "Failed dark mode");
+        assert_eq!(created["data"]["filters"], filters);
+        let duplicate = app
+            .client
+            .post(app.url("/admin/api/views"))
+            .json(&json!({ "name": "Failed dark mode" }))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(duplicate.status(), 409);
+        let blank = app
+            .client
+            .post(app.url("/admin/api/views"))
+            .json(&json!({ "name": "  " }))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(blank.status(), 400);
+
+        // 👤 Other admins' views stay out of the list
+        sqlx::query(
+            "INSERT INTO saved_views (owner, name) VALUES ('someone@example.com', 'Theirs')",
+        )
+        .execute(&app.db_pool)
+        .await
+        .unwrap();
+        let listed: Value = app
+            .client
+            .get(app.url("/admin/api/views"))
+            .send()
+            .await
+            .unwrap()
+            .json()
+            .await
+            .unwrap();
+        assert_eq!(listed["data"].as_array().unwrap().len(), 1);
+
+        // ✏️ Rename keeps the filters
+        let renamed: Value = app
+            .client
+            .put(app.url(&format!("/admin/api/views/{}", id)))
+            .json(&json!({ "name": "Dark mode failures" }))
+            .send()
+            .await
+            .unwrap()
+            .json()
+            .await
+            .unwrap();
+        assert_eq!(renamed["data"]["name"], "Dark mode failures");
+        assert_eq!(renamed["data"]["filters"]["repo"], "8b-is/smart-tree");
+
+        // 🔗 Opening the view redirects to its filters, which the list applies
+        for (repository, content) in [
+            ("8b-is/smart-tree", "Dark mode crashes the tree view"),
+            ("8b-is/smart-tree", "Add a light theme"),
+            ("8b-is/mem8", "Dark mode is too dark"),
+        ] {
+            let feedback = Feedback::create(
+                &app.db_pool,
+                None,
+                repository.to_string(),
+                content.to_string(),
+                None,
+                0,
+                None,
+                "web",
+            )
+            .await
+            .unwrap();
+            sqlx::query("UPDATE feedback SET status = 'failed' WHERE id = $1")
+                .bind(feedback.id)
+                .execute(&app.db_pool)
+                .await
+                .unwrap();
+        }
+        let redirected_to = |response: reqwest::Response| {
+            assert_eq!(response.status(), 303);
+            response.headers()["location"].to_str().unwrap().to_string()
+        };
+        let app = &app;
+        let page = |path: String| async move {
+            let response = app.client.get(app.url(&path)).send().await.unwrap();
+            assert_eq!(response.status(), 200);
+            response.text().await.unwrap()
+        };
+        let opened = app
+            .client
+            .get(app.url(&format!("/admin/feedback?view={}", id)))
+            .send()
+            .await
+            .unwrap();
+        let link = redirected_to(opened);
+        assert_eq!(
+            link,
+            "/admin/feedback?status=failed&repo=8b-is%2Fsmart-tree&q=dark%20mode"
+        );
+        let html = page(link).await;
+        assert!(html.contains("Dark mode crashes the tree view"));
+        assert!(!html.contains("Add a light theme"));
+        assert!(!html.contains("Dark mode is too dark"));
+        assert!(html.contains("Dark mode failures</option>"));
+
+        // 📮 Saving from the page stores the filters on screen
+        let saved = app
+            .client
+            .post(app.url("/admin/views"))
+            .form(&[
+                ("name", "Mem8"),
+                ("return_to", "/admin/feedback?repo=8b-is%2Fmem8&range=30d"),
+            ])
+            .send()
+            .await
+            .unwrap();
+        let link = redirected_to(saved);
+        assert_eq!(link, "/admin/feedback?range=30d&repo=8b-is%2Fmem8");
+        let html = page(link).await;
+        assert!(html.contains("Dark mode is too dark"));
+        assert!(!html.contains("Dark mode crashes the tree view"));
+
+        // 🗑️ Delete through the API, then a second time is a 404
+        let delete = || async {
+            app.client
+                .delete(app.url(&format!("/admin/api/views/{}", id)))
+                .send()
+                .await
+                .unwrap()
+                .status()
+        };
+        assert_eq!(delete().await, 200);
+        assert_eq!(delete().await, 404);
+        let gone = app
+            .client
+            .get(app.url(&format!("/admin/feedback?view={}", id)))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(redirected_to(gone), "/admin/feedback");
+        println!("✅ Saved views CRUD test passed!");
+    }
+}
@@ -0,0 +1,212 @@
+// ⚡ Settings Cache - Runtime overrides without a query per request! ⚡
+// The `settings` table holds values an admin can change at runtime (the latest
+// Smart Tree release, its notes and its per-platform downloads), plus the LLM provider health the monitor
+// computes and any default provider it switched to, plus when the console admin's second factor
+// was enabled (which every bootstrap session is checked against). The release history of the
+// other MCP products rides along, so /mcp/check can answer for them too. /mcp/check used to read them on every call;
+// now a background task reloads them every few seconds into an `ArcSwap`, and
+// handlers just grab the current snapshot. Refreshes that overlap (the ticker, the
+// startup load, a burst of callers) share one database read; writers that must see
+// their own write use `reload`, which always reads.
+// Created with love by Aye & Hue! ✨
+//
+// Part of the library crate (src/lib.rs), so it only depends on external crates and
+// `utils::coalesce`; benches/mcp_check.rs links against it there.
+
+use anyhow::{Context, Result};
+use arc_swap::ArcSwap;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::PgPool;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::warn;
+
+use crate::utils::coalesce::Coalesce;
+
+/// ⏰ How often the background task reloads the settings table
+pub const SETTINGS_REFRESH_INTERVAL: Duration = Duration::from_secs(5);
+
+/// 🔑 Settings keys mirrored into the cache
+const LATEST_VERSION_KEY: &str = "smart_tree_latest_version";
+const RELEASE_NOTES_KEY: &str = "smart_tree_release_notes";
+/// ✨ Headline features of the latest release (JSON list)
+pub const NEW_FEATURES_KEY: &str = "smart_tree_new_features";
+/// 📦 Per-platform assets of the latest release (JSON), taken from its GitHub release
+pub const RELEASE_DOWNLOADS_KEY: &str = "smart_tree_release_downloads";
+/// 🩺 Per-provider health report (JSON), written by the LLM health monitor
+pub const LLM_HEALTH_KEY: &str = "llm_health";
+/// 🔀 Default LLM provider chosen at runtime, overriding LLM_DEFAULT_PROVIDER
+pub const LLM_DEFAULT_PROVIDER_KEY: &str = "llm_default_provider";
+
+/// 📋 Runtime overrides read from the `settings` table
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RuntimeSettings {
+    /// 🌳 Latest Smart Tree release announced to MCP clients
+    pub smart_tree_latest_version: Option<String>,
+    /// 📝 Notes for that release
+    pub smart_tree_release_notes: Option<String>,
+    /// ✨ New features in that release (stored as a JSON array)
+    pub smart_tree_new_features: Option<Vec<String>>,
+    /// 📦 Direct downloads of that release, one per platform/arch it ships for
+    pub smart_tree_release_downloads: Option<Vec<ReleaseDownload>>,
+    /// 🩺 Latest LLM provider health report
+    pub llm_health: Option<serde_json::Value>,
+    /// 🔀 Default LLM provider override ("openai", "anthropic")
+    pub llm_default_provider: Option<String>,
+    /// 🌲 Published (not yanked) releases of every product but Smart Tree, by product -
+    /// their latest is picked by version precedence where it's needed
+    pub product_releases: HashMap<String, Vec<ProductRelease>>,
+    /// 🔢 When the bootstrap admin's second factor was enabled (None = password only)
+    pub console_second_factor_since: Option<DateTime<Utc>>,
+    /// ✅ Read from the database - the default snapshot before the first load isn't,
+    /// and must not be trusted for security decisions
+    pub loaded: bool,
+}
+
+/// 📦 One release asset a client can fetch directly
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReleaseDownload {
+    pub platform: String,
+    pub arch: String,
+    pub url: String,
+    /// 📏 Size in bytes
+    pub size: u64,
+}
+
+/// 📜 One release from the `releases` table
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProductRelease {
+    pub version: String,
+    pub notes: Option<String>,
+    pub features: Vec<String>,
+}
+
+impl RuntimeSettings {
+    /// 🗄️ Read every cached key in one query
+    pub async fn load(pool: &PgPool) -> Result<Self> {
+        let rows: Vec<(String, String)> =
+            sqlx::query_as("SELECT key, value FROM settings WHERE key = ANY($1)")
+                .bind(
+                    &[
+                        LATEST_VERSION_KEY,
+                        RELEASE_NOTES_KEY,
+                        NEW_FEATURES_KEY,
+                        RELEASE_DOWNLOADS_KEY,
+                        LLM_HEALTH_KEY,
+                        LLM_DEFAULT_PROVIDER_KEY,
+                    ][..],
+                )
+                .fetch_all(pool)
+                .await
+                .context("Failed to read runtime settings")?;
+
+        let console_second_factor_since: Option<Option<DateTime<Utc>>> = sqlx::query_scalar(
+            "SELECT enabled_at FROM admin_second_factors WHERE subject = 'config'",
+        )
+        .fetch_optional(pool)
+        .await
+        .context("Failed to read the console second factor")?;
+
+        // 🌳 Smart Tree's latest is the settings keys above, not its history
+        let releases: Vec<(String, String, Option<String>, serde_json::Value)> = sqlx::query_as(
+            "SELECT product, version, notes, features FROM releases WHERE product <> 'smart-tree' AND NOT yanked",
+        )
+        .fetch_all(pool)
+        .await
+        .context("Failed to read product releases")?;
+        let mut product_releases: HashMap<String, Vec<ProductRelease>> = HashMap::new();
+        for (product, version, notes, features) in releases {
+            product_releases
+                .entry(product)
+                .or_default()
+                .push(ProductRelease {
+                    version,
+                    notes,
+                    features: serde_json::from_value(features).unwrap_or_default(),
+                });
+        }
+
+        let mut settings = Self {
+            product_releases,
+            console_second_factor_since: console_second_factor_since.flatten(),
+            loaded: true,
+            ..Self::default()
+        };
+        for (key, value) in rows {
+            match key.as_str() {
+                LATEST_VERSION_KEY => settings.smart_tree_latest_version = Some(value),
+                RELEASE_NOTES_KEY => settings.smart_tree_release_notes = Some(value),
+                NEW_FEATURES_KEY => {
+                    settings.smart_tree_new_features = serde_json::from_str(&value).ok()
+                }
+                RELEASE_DOWNLOADS_KEY => {
+                    settings.smart_tree_release_downloads = serde_json::from_str(&value).ok()
+                }
+                LLM_HEALTH_KEY => settings.llm_health = serde_json::from_str(&value).ok(),
+                LLM_DEFAULT_PROVIDER_KEY => settings.llm_default_provider = Some(value),
+                _ => {}
+            }
+        }
+        Ok(settings)
+    }
+}
+
+/// 🗃️ The current `RuntimeSettings`, swapped wholesale on every refresh
+#[derive(Debug, Default)]
+pub struct SettingsCache {
+    current: ArcSwap<RuntimeSettings>,
+    /// 🧵 Loads in flight, shared by overlapping refreshes
+    loads: Coalesce<(), Result<Arc<RuntimeSettings>, Arc<anyhow::Error>>>,
+}
+
+impl SettingsCache {
+    /// 📸 The latest snapshot (no locking, no queries)
+    pub fn get(&self) -> Arc<RuntimeSettings> {
+        self.current.load_full()
+    }
+
+    /// 🔄 Reload from the database and publish the result, joining a load already in
+    /// flight (which may have started before a write of yours - see `reload`)
+    pub async fn refresh(&self, pool: &PgPool) -> Result<()> {
+        let settings = self
+            .loads
+            .run((), || async {
+                RuntimeSettings::load(pool)
+                    .await
+                    .map(Arc::new)
+                    .map_err(Arc::new)
+            })
+            .await
+            .map_err(|e| anyhow::anyhow!("{:#}", e))?;
+        self.current.store(settings);
+        Ok(())
+    }
+
+    /// ✍️ Read the settings afresh and publish them, for callers that just wrote one
+    pub async fn reload(&self, pool: &PgPool) -> Result<()> {
+        let settings = RuntimeSettings::load(pool).await?;
+        self.current.store(Arc::new(settings));
+        Ok(())
+    }
+
+    /// 📊 Database loads the coalesced refreshes have run
+    pub fn loads(&self) -> u64 {
+        self.loads.fetches()
+    }
+
+    /// 🔁 Keep refreshing in the background (a failed reload keeps the previous snapshot)
+    pub fn spawn_refresher(self: &Arc<Self>, pool: PgPool, interval: Duration) {
+        let cache = Arc::clone(self);
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            loop {
+                ticker.tick().await;
+                if let Err(e) = cache.refresh(&pool).await {
+                    warn!("⚠️ Failed to refresh runtime settings: {:#}", e);
+                }
+            }
+        });
+    }
+}
@@ -2,10 +2,10 @@
 // This module provides Smart Tree MCP integration endpoints
 // Created with love by Aye & Hue! ✨
 
-use crate::api::{ApiResponse, AppState};
+use crate::api::{assets::SHORT_PUBLIC_CACHE_CONTROL, ApiResponse, AppState};
 use axum::{
     extract::State,
-    http::StatusCode,
+    http::{header, StatusCode},
     response::{IntoResponse, Json},
 };
 use serde::Serialize;
@@ -24,8 +24,10 @@ pub async fn get_latest_version(State(_app_state): State<AppState>) -> impl Into
         release_notes: "Latest Smart Tree MCP release".to_string(),
     };
 
+    // ⏰ Release info changes rarely - let browsers and proxies cache it briefly
     (
         StatusCode::OK,
+        [(header::CACHE_CONTROL, SHORT_PUBLIC_CACHE_CONTROL)],
         Json(ApiResponse::success(
             "Version info retrieved".to_string(),
             version_info,
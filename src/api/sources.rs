@@ -0,0 +1,121 @@
+// 📡 Feedback Sources - Where did this feedback come from? 📡
+// Every submission records its channel (cli, web_form, an integration's name...)
+// in `feedback.source`: the request's `source` field, else `metadata.source`,
+// else a guess from the User-Agent, else "unknown". The admin panel breaks
+// feedback down by source and filters the list on it.
+// Created with love by Aye & Hue! ✨
+
+use anyhow::{Context, Result};
+use axum::http::{header, HeaderMap};
+use serde::Serialize;
+
+use crate::api::AppState;
+
+/// ❓ Source when nothing says where feedback came from
+pub const UNKNOWN_SOURCE: &str = "unknown";
+/// 📮 Source of the public HTML feedback form
+pub const WEB_FORM_SOURCE: &str = "web_form";
+/// 📏 Longest source name
+pub const MAX_SOURCE_LENGTH: usize = 32;
+
+/// 🧼 Lowercase and trim; None unless 1-32 characters of a-z, 0-9, '_', '-' or '.'
+pub fn normalize_source(source: &str) -> Option<String> {
+    let normalized = source.trim().to_ascii_lowercase();
+    let valid = !normalized.is_empty()
+        && normalized.len() <= MAX_SOURCE_LENGTH
+        && normalized
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.'));
+    valid.then_some(normalized)
+}
+
+/// 🕵️ Channel implied by a User-Agent, when it's one we recognise
+pub fn source_from_user_agent(user_agent: &str) -> Option<&'static str> {
+    let product = user_agent
+        .split(['/', ' '])
+        .next()
+        .unwrap_or_default()
+        .to_ascii_lowercase();
+    match product.as_str() {
+        "smart-tree" | "st" | "feedbacker-cli" | "curl" | "httpie" | "wget" => Some("cli"),
+        "mozilla" => Some("web"),
+        _ => None,
+    }
+}
+
+/// 🕵️ The request's User-Agent (non-UTF8 bytes replaced)
+pub fn user_agent(headers: &HeaderMap) -> Option<String> {
+    headers
+        .get(header::USER_AGENT)
+        .map(|value| String::from_utf8_lossy(value.as_bytes()).into_owned())
+}
+
+/// 📊 One source and how much feedback came from it
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, sqlx::FromRow)]
+pub struct SourceCount {
+    pub source: String,
+    pub count: i64,
+}
+
+/// 📊 Feedback per source for items created at or after `since` (None = all time),
+/// including completed feedback that retention has since removed
+pub async fn source_counts(
+    app_state: &AppState,
+    since: Option<chrono::DateTime<chrono::Utc>>,
+) -> Result<Vec<SourceCount>> {
+    sqlx::query_as::<_, SourceCount>(
+        r#"
+        SELECT source, SUM(count)::bigint AS count
+        FROM (
+            SELECT source, COUNT(*) AS count
+            FROM feedback
+            WHERE ($1::timestamptz IS NULL OR created_at >= $1)
+            GROUP BY source
+            UNION ALL
+            SELECT source, SUM(feedback_count)
+            FROM feedback_rollup
+            WHERE ($1::timestamptz IS NULL OR created_date >= ($1 AT TIME ZONE 'UTC')::date)
+            GROUP BY source
+        ) counts
+        GROUP BY source
+        ORDER BY count DESC, source
+        "#,
+    )
+    .bind(since)
+    .fetch_all(&app_state.db_pool)
+    .await
+    .context("Failed to fetch feedback source statistics")
+}
+
+// 🧪 Tests - Following feedback back to its channel!
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_source_normalization() {
+        assert_eq!(normalize_source("  CLI "), Some("cli".to_string()));
+        assert_eq!(
+            normalize_source("acme-bot.v2"),
+            Some("acme-bot.v2".to_string())
+        );
+        assert_eq!(normalize_source(""), None);
+        assert_eq!(normalize_source("web form"), None);
+        assert_eq!(normalize_source("<script>"), None);
+        assert_eq!(normalize_source(&"x".repeat(MAX_SOURCE_LENGTH + 1)), None);
+        println!("✅ Source normalization test passed!");
+    }
+
+    #[test]
+    fn test_source_from_user_agent() {
+        assert_eq!(source_from_user_agent("smart-tree/5.2.0"), Some("cli"));
+        assert_eq!(source_from_user_agent("curl/8.4.0"), Some("cli"));
+        assert_eq!(
+            source_from_user_agent("Mozilla/5.0 (X11; Linux x86_64)"),
+            Some("web")
+        );
+        assert_eq!(source_from_user_agent("python-requests/2.31"), None);
+        assert_eq!(source_from_user_agent(""), None);
+        println!("✅ User-Agent source test passed!");
+    }
+}
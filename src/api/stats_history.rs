@@ -0,0 +1,445 @@
+// 📈 Statistics History - Trends the current tables can't tell you! 📈
+// Once a night the daily_stats_snapshot job copies the dashboard numbers, the
+// job queue depth and the day's unique MCP clients into `daily_stats`, one row
+// per UTC date. The admin dashboard charts it and GET /admin/api/stats/history
+// hands it out as JSON, ending with a point for today from the status stats view
+// (api::status_stats) until tonight's snapshot exists. Days from before the table
+// existed were backfilled from feedback creation dates, so only their feedback
+// counts are known.
+// Created with love by Aye & Hue! ✨
+
+use anyhow::{Context, Result};
+use axum::{
+    extract::{Query, State},
+    http::StatusCode,
+    response::{IntoResponse, Json, Response},
+};
+use axum_extra::extract::cookie::CookieJar;
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::PgPool;
+
+use crate::api::{
+    admin::require_admin_api_auth, labels::label_html, status_stats::StatusStats, ApiResponse,
+    AppState,
+};
+use crate::config::LabelStyle;
+
+/// 📅 Default and longest history the endpoint returns (days)
+pub const DEFAULT_HISTORY_DAYS: i64 = 90;
+const MAX_HISTORY_DAYS: i64 = 730;
+
+/// 📸 One day's snapshot. Backfilled days only know their feedback counts.
+#[derive(Debug, Clone, PartialEq, Serialize, sqlx::FromRow)]
+pub struct DailyStats {
+    pub date: NaiveDate,
+    pub total_users: Option<i64>,
+    pub total_projects: Option<i64>,
+    pub total_feedback: i64,
+    pub pending_feedback: Option<i64>,
+    pub completed_feedback: Option<i64>,
+    pub failed_feedback: Option<i64>,
+    /// 🔄 Background jobs waiting to run
+    pub queue_depth: Option<i64>,
+    /// 🌲 Distinct stored client IPs (or IP hashes) among the day's MCP checks
+    pub unique_mcp_clients: Option<i64>,
+    /// 📝 Feedback submitted that day
+    pub feedback_created: i64,
+    /// 🕰️ Reconstructed by the migration rather than captured on the day
+    pub backfilled: bool,
+}
+
+/// 📸 Capture today's numbers under `date`; running it again for the same date
+/// overwrites that row instead of adding another
+pub async fn snapshot_daily_stats(app_state: &AppState, date: NaiveDate) -> Result<DailyStats> {
+    let stats = crate::api::admin::get_dashboard_stats(app_state, None)
+        .await
+        .context("Failed to read dashboard statistics")?;
+
+    sqlx::query_as(
+        r#"
+        INSERT INTO daily_stats (
+            date, total_users, total_projects, total_feedback, pending_feedback,
+            completed_feedback, failed_feedback, queue_depth, unique_mcp_clients,
+            feedback_created, backfilled, captured_at
+        )
+        VALUES (
+            $1, $2, $3, $4, $5, $6, $7,
+            (SELECT COUNT(*) FROM background_jobs WHERE status = 'pending'),
+            (SELECT COUNT(DISTINCT COALESCE(ip_hash, host(ip_address))) FROM mcp_analytics
+             WHERE (checked_at AT TIME ZONE 'UTC')::date = $1),
+            (SELECT COUNT(*) FROM feedback WHERE (created_at AT TIME ZONE 'UTC')::date = $1),
+            FALSE, NOW()
+        )
+        ON CONFLICT (date) DO UPDATE SET
+            total_users = EXCLUDED.total_users,
+            total_projects = EXCLUDED.total_projects,
+            total_feedback = EXCLUDED.total_feedback,
+            pending_feedback = EXCLUDED.pending_feedback,
+            completed_feedback = EXCLUDED.completed_feedback,
+            failed_feedback = EXCLUDED.failed_feedback,
+            queue_depth = EXCLUDED.queue_depth,
+            unique_mcp_clients = EXCLUDED.unique_mcp_clients,
+            feedback_created = EXCLUDED.feedback_created,
+            backfilled = FALSE,
+            captured_at = NOW()
+        RETURNING date, total_users, total_projects, total_feedback, pending_feedback,
+                  completed_feedback, failed_feedback, queue_depth, unique_mcp_clients,
+                  feedback_created, backfilled
+        "#,
+    )
+    .bind(date)
+    .bind(stats.total_users)
+    .bind(stats.total_projects)
+    .bind(stats.total_feedback)
+    .bind(stats.pending_feedback)
+    .bind(stats.completed_feedback)
+    .bind(stats.failed_feedback)
+    .fetch_one(&app_state.db_pool)
+    .await
+    .with_context(|| format!("Failed to store daily statistics for {}", date))
+}
+
+/// 📅 Snapshots of the last `days` days (today included), oldest first
+pub async fn stats_history(pool: &PgPool, days: i64) -> Result<Vec<DailyStats>> {
+    sqlx::query_as(
+        r#"
+        SELECT date, total_users, total_projects, total_feedback, pending_feedback,
+               completed_feedback, failed_feedback, queue_depth, unique_mcp_clients,
+               feedback_created, backfilled
+        FROM daily_stats
+        WHERE date > (NOW() AT TIME ZONE 'UTC')::date - $1::int
+        ORDER BY date
+        "#,
+    )
+    .bind(days as i32)
+    .fetch_all(pool)
+    .await
+    .context("Failed to read statistics history")
+}
+
+/// 📅 `?days=` for the history endpoint
+#[derive(Debug, Default, Deserialize)]
+pub struct StatsHistoryQuery {
+    pub days: Option<i64>,
+}
+
+/// 📈 History response body
+#[derive(Debug, Serialize)]
+pub struct StatsHistory {
+    pub days: i64,
+    pub history: Vec<DailyStats>,
+    /// ⏰ When today's point was counted (None: the history has no point for today)
+    pub refreshed_at: Option<DateTime<Utc>>,
+}
+
+/// 📅 Snapshots of the last `days` days, plus a point for today from the status stats
+/// view until tonight's snapshot exists. Returns when that point was counted.
+pub async fn stats_history_through_today(
+    pool: &PgPool,
+    days: i64,
+) -> Result<(Vec<DailyStats>, Option<DateTime<Utc>>)> {
+    let mut history = stats_history(pool, days).await?;
+    let stats = StatusStats::load(pool).await?;
+    let today = stats.today();
+    if history.last().map(|day| day.date) >= Some(today.date) {
+        return Ok((history, None));
+    }
+    history.push(today);
+    Ok((history, Some(stats.refreshed_at)))
+}
+
+/// 📈 GET /admin/api/stats/history - daily snapshots for trend charts
+pub async fn admin_stats_history(
+    State(app_state): State<AppState>,
+    jar: CookieJar,
+    Query(query): Query<StatsHistoryQuery>,
+) -> Response {
+    if let Some(denied) = require_admin_api_auth(&jar, &app_state).await {
+        return denied;
+    }
+    let days = query
+        .days
+        .unwrap_or(DEFAULT_HISTORY_DAYS)
+        .clamp(1, MAX_HISTORY_DAYS);
+
+    match stats_history_through_today(&app_state.db_pool, days).await {
+        Ok((history, refreshed_at)) => (
+            StatusCode::OK,
+            Json(ApiResponse::success(
+                format!("{} days of statistics", history.len()),
+                StatsHistory {
+                    days,
+                    history,
+                    refreshed_at,
+                },
+            )),
+        )
+            .into_response(),
+        Err(e) => crate::api::utils::handle_error(e).into_response(),
+    }
+}
+
+/// 📐 Chart size (SVG user units)
+const CHART_WIDTH: f64 = 600.0;
+const CHART_HEIGHT: f64 = 160.0;
+
+/// 📈 Inline SVG chart of total and pending feedback over the history
+pub fn render_trend_chart(history: &[DailyStats], style: LabelStyle) -> String {
+    if history.len() < 2 {
+        return format!(
+            r#"<div class="empty-state">{}</div>"#,
+            label_html(
+                "📈 Trends appear after a couple of nightly snapshots",
+                style
+            )
+        );
+    }
+
+    let max = history
+        .iter()
+        .map(|day| day.total_feedback.max(day.pending_feedback.unwrap_or(0)))
+        .max()
+        .unwrap_or(0)
+        .max(1) as f64;
+    let step = CHART_WIDTH / (history.len() - 1) as f64;
+    let points = |value: fn(&DailyStats) -> Option<i64>| -> String {
+        history
+            .iter()
+            .enumerate()
+            .filter_map(|(index, day)| {
+                value(day).map(|value| {
+                    format!(
+                        "{:.1},{:.1}",
+                        index as f64 * step,
+                        CHART_HEIGHT - value as f64 / max * CHART_HEIGHT
+                    )
+                })
+            })
+            .collect::<Vec<_>>()
+            .join(" ")
+    };
+    let first = history.first().map(|day| day.date).unwrap_or_default();
+    let last = history.last().map(|day| day.date).unwrap_or_default();
+
+    format!(
+        r#"<svg class="trend-chart" viewBox="0 0 {width} {height}" preserveAspectRatio="none" role="img" aria-label="Total and pending feedback from {first} to {last}">
+                <polyline class="trend-total" fill="none" points="{total}"/>
+                <polyline class="trend-pending" fill="none" points="{pending}"/>
+            </svg>
+            <div class="trend-legend muted">
+                <span class="trend-total">Total feedback</span>
+                <span class="trend-pending">Pending</span>
+                <span>{first} – {last}</span>
+            </div>"#,
+        width = CHART_WIDTH,
+        height = CHART_HEIGHT,
+        first = first,
+        last = last,
+        total = points(|day| Some(day.total_feedback)),
+        pending = points(|day| day.pending_feedback),
+    )
+}
+
+/// 🕛 The UTC date a snapshot taken now should be filed under
+pub fn today() -> NaiveDate {
+    Utc::now().date_naive()
+}
+
+// 🧪 Tests - Yesterday's numbers, exactly once!
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::migrations::get_all_migrations;
+
+    fn day(date: &str, total: i64, pending: Option<i64>) -> DailyStats {
+        DailyStats {
+            date: date.parse().unwrap(),
+            total_users: None,
+            total_projects: None,
+            total_feedback: total,
+            pending_feedback: pending,
+            completed_feedback: None,
+            failed_feedback: None,
+            queue_depth: None,
+            unique_mcp_clients: None,
+            feedback_created: 0,
+            backfilled: pending.is_none(),
+        }
+    }
+
+    #[test]
+    fn test_trend_chart_scales_and_skips_unknown_days() {
+        let empty = render_trend_chart(&[day("2026-01-01", 3, None)], LabelStyle::Plain);
+        assert!(empty.contains(r#"<div class="empty-state">Trends appear"#));
+
+        let chart = render_trend_chart(
+            &[
+                day("2026-01-01", 0, None),
+                day("2026-01-02", 5, Some(2)),
+                day("2026-01-03", 10, Some(4)),
+            ],
+            LabelStyle::Emoji,
+        );
+        assert!(chart.contains(r#"points="0.0,160.0 300.0,80.0 600.0,0.0""#));
+        // 🕰️ The backfilled day has no pending count, so that line starts later
+        assert!(chart.contains(r#"points="300.0,128.0 600.0,96.0""#));
+        assert!(chart.contains("from 2026-01-01 to 2026-01-03"));
+        println!("✅ Trend chart test passed!");
+    }
+
+    #[tokio::test]
+    async fn test_snapshot_is_idempotent_per_date() {
+        let Some(app) = crate::test_support::spawn_test_app().await else {
+            return;
+        };
+        let date: NaiveDate = "2026-03-14".parse().unwrap();
+        sqlx::query("INSERT INTO feedback (repository, content) VALUES ('8b-is/a', 'Hi')")
+            .execute(&app.db_pool)
+            .await
+            .unwrap();
+        let first = snapshot_daily_stats(&app.app_state, date).await.unwrap();
+        assert_eq!(first.total_feedback, 1);
+        assert_eq!(first.pending_feedback, Some(1));
+        assert!(!first.backfilled);
+
+        sqlx::query("INSERT INTO feedback (repository, content, status) VALUES ('8b-is/b', 'Hi', 'completed')")
+            .execute(&app.db_pool)
+            .await
+            .unwrap();
+        let second = snapshot_daily_stats(&app.app_state, date).await.unwrap();
+        assert_eq!(second.total_feedback, 2);
+        assert_eq!(second.completed_feedback, Some(1));
+
+        // 🔁 Same date, same single row - with the newest numbers
+        let rows: Vec<(NaiveDate, i64)> =
+            sqlx::query_as("SELECT date, total_feedback FROM daily_stats")
+                .fetch_all(&app.db_pool)
+                .await
+                .unwrap();
+        assert_eq!(rows, vec![(date, 2)]);
+        println!("✅ Idempotent snapshot test passed!");
+    }
+
+    #[tokio::test]
+    async fn test_history_endpoint_requires_admin_and_clamps_days() {
+        let Some(app) = crate::test_support::spawn_test_app().await else {
+            return;
+        };
+        let url = app.url("/admin/api/stats/history?days=100000");
+        let denied = app.client.get(&url).send().await.unwrap();
+        assert_eq!(denied.status(), StatusCode::UNAUTHORIZED);
+
+        snapshot_daily_stats(&app.app_state, today()).await.unwrap();
+        app.login_admin().await.unwrap();
+        let body: serde_json::Value = app
+            .client
+            .get(&url)
+            .send()
+            .await
+            .unwrap()
+            .json()
+            .await
+            .unwrap();
+        assert_eq!(body["data"]["days"], MAX_HISTORY_DAYS);
+        assert_eq!(body["data"]["history"].as_array().unwrap().len(), 1);
+        assert_eq!(body["data"]["history"][0]["date"], today().to_string());
+        assert!(body["data"]["refreshed_at"].is_null());
+        println!("✅ Stats history endpoint test passed!");
+    }
+
+    #[tokio::test]
+    async fn test_history_ends_with_today_from_the_status_view() {
+        let Some(app) = crate::test_support::spawn_test_app().await else {
+            return;
+        };
+        let yesterday = today() - chrono::Duration::days(1);
+        snapshot_daily_stats(&app.app_state, yesterday)
+            .await
+            .unwrap();
+        sqlx::query("INSERT INTO feedback (repository, content) VALUES ('8b-is/a', 'Hi')")
+            .execute(&app.db_pool)
+            .await
+            .unwrap();
+        let refreshed_at = crate::api::status_stats::refresh(&app.db_pool)
+            .await
+            .unwrap()
+            .unwrap();
+
+        let (history, counted_at) = stats_history_through_today(&app.db_pool, 10).await.unwrap();
+        assert_eq!(counted_at, Some(refreshed_at));
+        let dates: Vec<NaiveDate> = history.iter().map(|day| day.date).collect();
+        assert_eq!(dates, vec![yesterday, today()]);
+        assert_eq!(history[1].total_feedback, 1);
+        assert_eq!(history[1].pending_feedback, Some(1));
+        println!("✅ History through today test passed!");
+    }
+
+    #[tokio::test]
+    async fn test_backfill_counts_feedback_per_day_with_running_total() {
+        let Some(app) = crate::test_support::spawn_test_app().await else {
+            return;
+        };
+        let today = today();
+        // 📝 Two items four days ago, none three days ago, one the day before yesterday
+        // (late in the UTC day), three today
+        for (days_ago, hour) in [(4, 1), (4, 23), (2, 23), (0, 0), (0, 0), (0, 0)] {
+            let created_at = (today - chrono::Duration::days(days_ago))
+                .and_hms_opt(hour, 30, 0)
+                .unwrap()
+                .and_utc();
+            sqlx::query(
+                "INSERT INTO feedback (repository, content, created_at) VALUES ('8b-is/a', 'Hi', $1)",
+            )
+            .bind(created_at)
+            .execute(&app.db_pool)
+            .await
+            .unwrap();
+        }
+        // 📸 A real snapshot from yesterday must survive the backfill untouched
+        let yesterday = today - chrono::Duration::days(1);
+        sqlx::query("INSERT INTO daily_stats (date, total_feedback, feedback_created, total_users) VALUES ($1, 99, 7, 5)")
+            .bind(yesterday)
+            .execute(&app.db_pool)
+            .await
+            .unwrap();
+
+        // 🔁 Replay v11 (its table is already there, so only the backfill does anything)
+        let v11 = get_all_migrations()
+            .into_iter()
+            .find(|m| m.id == "v11_daily_stats")
+            .unwrap();
+        sqlx::raw_sql(&v11.up_sql)
+            .execute(&app.db_pool)
+            .await
+            .unwrap();
+
+        let history = stats_history(&app.db_pool, 10).await.unwrap();
+        let summary: Vec<(i64, i64, i64, bool)> = history
+            .iter()
+            .map(|day| {
+                (
+                    (today - day.date).num_days(),
+                    day.feedback_created,
+                    day.total_feedback,
+                    day.backfilled,
+                )
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                (4, 2, 2, true),
+                (3, 0, 2, true),
+                (2, 1, 3, true),
+                (1, 7, 99, false),
+                (0, 3, 6, true),
+            ]
+        );
+        assert!(history
+            .iter()
+            .filter(|day| day.backfilled)
+            .all(|day| day.total_users.is_none()));
+        println!("✅ Daily stats backfill test passed!");
+    }
+}
@@ -0,0 +1,412 @@
+// 📊 Status Stats - Numbers monitoring bots can poll all day! 📊
+// GET /api/status (public) and today's point on the admin trend chart read feedback
+// and job counts from the `status_stats` materialized view instead of counting the
+// live tables. A status_stats_refresh job rebuilds it every five minutes with
+// REFRESH MATERIALIZED VIEW CONCURRENTLY (see jobs::status_stats), so a read that
+// overlaps a refresh sees the previous numbers instead of waiting. Every answer says
+// when its numbers were counted. Before migration v34 has run there is no view, and
+// the same query is counted live.
+// Created with love by Aye & Hue! ✨
+
+use anyhow::{Context, Result};
+use axum::{
+    extract::State,
+    http::StatusCode,
+    response::{IntoResponse, Json, Response},
+};
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::Serialize;
+use sqlx::PgPool;
+use std::collections::BTreeMap;
+
+use crate::api::{stats_history::DailyStats, ApiResponse, AppState};
+
+/// 📅 Days of finished jobs the success ratio covers (today included)
+pub const JOB_WINDOW_DAYS: i64 = 7;
+/// 🔒 Advisory lock held by a refresh, so overlapping ones skip instead of queueing up
+const REFRESH_LOCK_KEY: i64 = 0x5354_4154_5553;
+/// 🚫 Postgres "undefined_table": the view (or its refresh log) isn't there yet
+const UNDEFINED_TABLE: &str = "42P01";
+/// 📊 Feedback per (UTC creation day, status), rolled-up feedback counted as completed,
+/// and finished background jobs per (UTC completion day, outcome). The same counts the
+/// v34 migration froze into the `status_stats` view, run as is while that view doesn't
+/// exist yet.
+const STATUS_STATS_SQL: &str = r#"
+SELECT 'feedback'::text AS metric, day, outcome, SUM(count)::bigint AS count
+FROM (
+    SELECT (created_at AT TIME ZONE 'UTC')::date AS day, status::text AS outcome, COUNT(*) AS count
+    FROM feedback
+    GROUP BY 1, 2
+    UNION ALL
+    SELECT created_date, 'completed', SUM(feedback_count)
+    FROM feedback_rollup
+    GROUP BY 1
+) feedback_days
+GROUP BY day, outcome
+UNION ALL
+SELECT 'jobs'::text, (completed_at AT TIME ZONE 'UTC')::date, status::text, COUNT(*)::bigint
+FROM background_jobs
+WHERE status IN ('completed', 'failed') AND completed_at IS NOT NULL
+GROUP BY 2, 3
+"#;
+
+/// 🧮 One row of the view: a count per metric ("feedback" or "jobs"), UTC day and outcome
+#[derive(Debug, Clone, PartialEq, sqlx::FromRow)]
+struct StatRow {
+    metric: String,
+    day: NaiveDate,
+    outcome: String,
+    count: i64,
+}
+
+/// 📸 The counts from one refresh of the view
+#[derive(Debug, Clone)]
+pub struct StatusStats {
+    /// ⏰ When the numbers were counted
+    pub refreshed_at: DateTime<Utc>,
+    /// 🔴 Counted live because the view doesn't exist yet
+    pub live: bool,
+    rows: Vec<StatRow>,
+}
+
+/// ✅ Finished background jobs over the last JOB_WINDOW_DAYS days
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct JobOutcomes {
+    pub window_days: i64,
+    pub completed: i64,
+    pub failed: i64,
+    /// 📐 completed / (completed + failed), None before any job has finished
+    pub success_ratio: Option<f64>,
+}
+
+/// 📊 Feedback counts across all time (rolled-up feedback counts as completed)
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct FeedbackCounts {
+    pub total: i64,
+    pub by_status: BTreeMap<String, i64>,
+}
+
+/// 🌐 GET /api/status response body
+#[derive(Debug, Clone, Serialize)]
+pub struct ServiceStatus {
+    /// ⏰ When these numbers were counted (at most a refresh interval ago)
+    pub refreshed_at: DateTime<Utc>,
+    pub feedback: FeedbackCounts,
+    pub jobs: JobOutcomes,
+}
+
+impl StatusStats {
+    /// 🗄️ Read the view and when it was refreshed, in one statement so a refresh
+    /// committing in between can't pair old counts with a new timestamp
+    pub async fn load(pool: &PgPool) -> Result<Self> {
+        let read: Result<Vec<ViewRow>, sqlx::Error> = sqlx::query_as(
+            r#"
+            SELECT r.refreshed_at, s.metric, s.day, s.outcome, s.count
+            FROM materialized_view_refreshes r
+            LEFT JOIN status_stats s ON TRUE
+            WHERE r.view_name = 'status_stats'
+            "#,
+        )
+        .fetch_all(pool)
+        .await;
+        match read {
+            Ok(rows) if !rows.is_empty() => Ok(Self::from_view(rows)),
+            Ok(_) => Self::load_live(pool).await,
+            Err(sqlx::Error::Database(e)) if e.code().as_deref() == Some(UNDEFINED_TABLE) => {
+                Self::load_live(pool).await
+            }
+            Err(e) => Err(e).context("Failed to read the status stats view"),
+        }
+    }
+
+    /// 🔴 The view's query, counted right now
+    async fn load_live(pool: &PgPool) -> Result<Self> {
+        let rows = sqlx::query_as(&format!(
+            "SELECT metric, day, outcome, count FROM ({}) counts",
+            STATUS_STATS_SQL
+        ))
+        .fetch_all(pool)
+        .await
+        .context("Failed to count status stats")?;
+        Ok(Self {
+            refreshed_at: Utc::now(),
+            live: true,
+            rows,
+        })
+    }
+
+    fn from_view(rows: Vec<ViewRow>) -> Self {
+        let refreshed_at = rows[0].0;
+        let rows = rows
+            .into_iter()
+            .filter_map(|(_, metric, day, outcome, count)| {
+                Some(StatRow {
+                    metric: metric?,
+                    day: day?,
+                    outcome: outcome?,
+                    count: count?,
+                })
+            })
+            .collect();
+        Self {
+            refreshed_at,
+            live: false,
+            rows,
+        }
+    }
+
+    fn counts<'a>(&'a self, metric: &'a str) -> impl Iterator<Item = &'a StatRow> {
+        self.rows.iter().filter(move |row| row.metric == metric)
+    }
+
+    /// 📊 All-time feedback per status
+    pub fn feedback(&self) -> FeedbackCounts {
+        let mut by_status = BTreeMap::new();
+        for row in self.counts("feedback") {
+            *by_status.entry(row.outcome.clone()).or_insert(0) += row.count;
+        }
+        FeedbackCounts {
+            total: by_status.values().sum(),
+            by_status,
+        }
+    }
+
+    /// ✅ Jobs finished over the JOB_WINDOW_DAYS days up to `today`
+    pub fn jobs(&self, today: NaiveDate) -> JobOutcomes {
+        let since = today - chrono::Duration::days(JOB_WINDOW_DAYS - 1);
+        let (mut completed, mut failed) = (0, 0);
+        for row in self.counts("jobs").filter(|row| row.day >= since) {
+            match row.outcome.as_str() {
+                "completed" => completed += row.count,
+                "failed" => failed += row.count,
+                _ => {}
+            }
+        }
+        JobOutcomes {
+            window_days: JOB_WINDOW_DAYS,
+            completed,
+            failed,
+            success_ratio: (completed + failed > 0)
+                .then(|| completed as f64 / (completed + failed) as f64),
+        }
+    }
+
+    /// 📈 Today's point for the trend chart (only what the view knows is filled in)
+    pub fn today(&self) -> DailyStats {
+        let date = self.refreshed_at.date_naive();
+        let feedback = self.feedback();
+        let status = |name: &str| feedback.by_status.get(name).copied().unwrap_or(0);
+        DailyStats {
+            date,
+            total_users: None,
+            total_projects: None,
+            total_feedback: feedback.total,
+            pending_feedback: Some(status("pending")),
+            completed_feedback: Some(status("completed")),
+            failed_feedback: Some(status("failed")),
+            queue_depth: None,
+            unique_mcp_clients: None,
+            feedback_created: self
+                .counts("feedback")
+                .filter(|row| row.day == date)
+                .map(|row| row.count)
+                .sum(),
+            backfilled: false,
+        }
+    }
+}
+
+/// 🧾 (refreshed_at, metric, day, outcome, count) - NULLs when the view is empty
+type ViewRow = (
+    DateTime<Utc>,
+    Option<String>,
+    Option<NaiveDate>,
+    Option<String>,
+    Option<i64>,
+);
+
+/// 🔄 Rebuild the view without blocking readers and stamp the time; None when another
+/// refresh is already running
+pub async fn refresh(pool: &PgPool) -> Result<Option<DateTime<Utc>>> {
+    let mut tx = pool.begin().await?;
+    let locked: bool = sqlx::query_scalar("SELECT pg_try_advisory_xact_lock($1)")
+        .bind(REFRESH_LOCK_KEY)
+        .fetch_one(&mut *tx)
+        .await
+        .context("Failed to take the status stats refresh lock")?;
+    if !locked {
+        return Ok(None);
+    }
+    sqlx::query("REFRESH MATERIALIZED VIEW CONCURRENTLY status_stats")
+        .execute(&mut *tx)
+        .await
+        .context("Failed to refresh the status stats view")?;
+    let refreshed_at = sqlx::query_scalar(
+        r#"
+        INSERT INTO materialized_view_refreshes (view_name, refreshed_at) VALUES ('status_stats', NOW())
+        ON CONFLICT (view_name) DO UPDATE SET refreshed_at = EXCLUDED.refreshed_at
+        RETURNING refreshed_at
+        "#,
+    )
+    .fetch_one(&mut *tx)
+    .await
+    .context("Failed to record the status stats refresh")?;
+    tx.commit().await?;
+    Ok(Some(refreshed_at))
+}
+
+/// 🌐 GET /api/status - feedback counts and the job success ratio, for status pages
+/// and SLO monitors (public; served from the view, see `refreshed_at`)
+pub async fn service_status(State(app_state): State<AppState>) -> Response {
+    match StatusStats::load(&app_state.db_pool).await {
+        Ok(stats) => (
+            StatusCode::OK,
+            Json(ApiResponse::success(
+                "Service status".to_string(),
+                ServiceStatus {
+                    refreshed_at: stats.refreshed_at,
+                    feedback: stats.feedback(),
+                    jobs: stats.jobs(Utc::now().date_naive()),
+                },
+            )),
+        )
+            .into_response(),
+        Err(e) => crate::api::utils::handle_error(e).into_response(),
+    }
+}
+
+// 🧪 Tests - Fresh enough for the bots!
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::spawn_test_app;
+
+    #[tokio::test]
+    async fn test_refresh_advances_the_freshness_and_the_counts() {
+        let Some(app) = spawn_test_app().await else {
+            return;
+        };
+        let pool = &app.db_pool;
+        sqlx::query(
+            "INSERT INTO feedback (repository, content, status) VALUES \
+             ('8b-is/smart-tree', 'a', 'completed'), ('8b-is/smart-tree', 'b', 'pending')",
+        )
+        .execute(pool)
+        .await
+        .unwrap();
+        sqlx::query(
+            "INSERT INTO background_jobs (job_type, payload, status, completed_at) VALUES \
+             ('tally', '{}', 'completed', NOW()), ('tally', '{}', 'completed', NOW()), \
+             ('tally', '{}', 'failed', NOW()), ('tally', '{}', 'failed', NOW() - INTERVAL '30 days')",
+        )
+        .execute(pool)
+        .await
+        .unwrap();
+
+        // 📸 Until the view is refreshed it still shows the numbers it was created with
+        let before = StatusStats::load(pool).await.unwrap();
+        assert!(!before.live);
+        assert_eq!(before.feedback().total, 0);
+
+        let refreshed_at = refresh(pool).await.unwrap().unwrap();
+        let after = StatusStats::load(pool).await.unwrap();
+        assert!(after.refreshed_at > before.refreshed_at);
+        assert_eq!(after.refreshed_at, refreshed_at);
+        assert_eq!(after.feedback().total, 2);
+        assert_eq!(after.feedback().by_status["pending"], 1);
+        let jobs = after.jobs(Utc::now().date_naive());
+        assert_eq!((jobs.completed, jobs.failed), (2, 1));
+        assert_eq!(jobs.success_ratio.map(|r| (r * 100.0).round()), Some(67.0));
+        let today = after.today();
+        assert_eq!(today.feedback_created, 2);
+        assert_eq!(today.pending_feedback, Some(1));
+
+        // 🔁 The runtime fallback still counts what the migration's view does
+        let sorted = |mut rows: Vec<StatRow>| {
+            rows.sort_by(|a, b| {
+                (&a.metric, a.day, &a.outcome).cmp(&(&b.metric, b.day, &b.outcome))
+            });
+            rows
+        };
+        assert_eq!(
+            sorted(StatusStats::load_live(pool).await.unwrap().rows),
+            sorted(after.rows.clone())
+        );
+
+        // 🌐 The public endpoint serves the same numbers, with their age
+        let body: serde_json::Value = app
+            .client
+            .get(app.url("/api/status"))
+            .send()
+            .await
+            .unwrap()
+            .json()
+            .await
+            .unwrap();
+        assert_eq!(body["data"]["feedback"]["total"], 2);
+        assert_eq!(body["data"]["jobs"]["failed"], 1);
+        assert_eq!(
+            body["data"]["refreshed_at"]
+                .as_str()
+                .unwrap()
+                .parse::<DateTime<Utc>>()
+                .unwrap(),
+            refreshed_at
+        );
+        println!("✅ Status stats refresh test passed!");
+    }
+
+    #[tokio::test]
+    async fn test_overlapping_refreshes_skip_and_reads_never_wait() {
+        let Some(app) = spawn_test_app().await else {
+            return;
+        };
+        let pool = &app.db_pool;
+        let mut holder = pool.begin().await.unwrap();
+        sqlx::query("SELECT pg_advisory_xact_lock($1)")
+            .bind(REFRESH_LOCK_KEY)
+            .execute(&mut *holder)
+            .await
+            .unwrap();
+        assert_eq!(refresh(pool).await.unwrap(), None);
+
+        // 🔄 A refresh in flight holds its lock on the view, reads still go through
+        let mut refreshing = pool.begin().await.unwrap();
+        sqlx::query("REFRESH MATERIALIZED VIEW CONCURRENTLY status_stats")
+            .execute(&mut *refreshing)
+            .await
+            .unwrap();
+        tokio::time::timeout(std::time::Duration::from_secs(5), StatusStats::load(pool))
+            .await
+            .expect("reading during a refresh must not block")
+            .unwrap();
+        refreshing.rollback().await.unwrap();
+        holder.rollback().await.unwrap();
+        assert!(refresh(pool).await.unwrap().is_some());
+        println!("✅ Overlapping refresh test passed!");
+    }
+
+    #[tokio::test]
+    async fn test_counts_are_live_before_the_migration() {
+        let Some(app) = spawn_test_app().await else {
+            return;
+        };
+        let pool = &app.db_pool;
+        sqlx::query("DROP TABLE materialized_view_refreshes")
+            .execute(pool)
+            .await
+            .unwrap();
+        sqlx::query("DROP MATERIALIZED VIEW status_stats")
+            .execute(pool)
+            .await
+            .unwrap();
+        sqlx::query("INSERT INTO feedback (repository, content) VALUES ('8b-is/smart-tree', 'a')")
+            .execute(pool)
+            .await
+            .unwrap();
+        let stats = StatusStats::load(pool).await.unwrap();
+        assert!(stats.live);
+        assert_eq!(stats.feedback().by_status["pending"], 1);
+        println!("✅ Pre-migration status stats test passed!");
+    }
+}
@@ -0,0 +1,284 @@
+// 🏷️ Feedback Tags - Themes you can actually see across submissions! 🏷️
+// Free-form tags arrive on the request (or inside `metadata.tags`), get
+// normalized, and land in the `feedback_tags` join table so they can be
+// counted, filtered on and shown as a tag cloud in the admin panel.
+// Created with love by Aye & Hue! ✨
+
+use anyhow::{Context, Result};
+use axum::{
+    extract::{Query, State},
+    http::StatusCode,
+    response::{IntoResponse, Json, Response},
+};
+use axum_extra::extract::cookie::CookieJar;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::api::{
+    admin::{require_admin_api_auth, DashboardRange},
+    ApiResponse, AppState,
+};
+
+/// 🔢 Most tags one feedback item may carry
+pub const MAX_TAGS: usize = 20;
+/// 📏 Longest tag (characters, after normalization)
+pub const MAX_TAG_LENGTH: usize = 50;
+/// 📊 Default and largest number of tags the stats endpoint returns
+const DEFAULT_TAG_LIMIT: i64 = 50;
+const MAX_TAG_LIMIT: i64 = 200;
+
+/// 🧼 Lowercase, trim and hyphenate whitespace: " Dark  Mode " -> "dark-mode"
+pub fn normalize_tag(tag: &str) -> Option<String> {
+    let normalized = tag
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join("-")
+        .to_lowercase();
+    (!normalized.is_empty() && !normalized.chars().any(char::is_control)).then_some(normalized)
+}
+
+/// 🧼 Normalize a list of tags, dropping blanks and duplicates (first spelling wins)
+pub fn normalize_tags<'a>(tags: impl IntoIterator<Item = &'a str>) -> Vec<String> {
+    let mut normalized: Vec<String> = Vec::new();
+    for tag in tags.into_iter().filter_map(normalize_tag) {
+        if !normalized.contains(&tag) {
+            normalized.push(tag);
+        }
+    }
+    normalized
+}
+
+/// ✅ Problems with an already-normalized tag list
+pub fn validate_tags(tags: &[String]) -> Vec<String> {
+    let mut errors = Vec::new();
+    if tags.len() > MAX_TAGS {
+        errors.push(format!("At most {} tags are allowed", MAX_TAGS));
+    }
+    if let Some(tag) = tags.iter().find(|tag| tag.chars().count() > MAX_TAG_LENGTH) {
+        errors.push(format!(
+            "Tag '{}...' is longer than {} characters",
+            tag.chars().take(20).collect::<String>(),
+            MAX_TAG_LENGTH
+        ));
+    }
+    errors
+}
+
+/// 💾 Attach tags to a feedback item (already-present tags are left alone)
+pub async fn store_tags<'e, E>(executor: E, feedback_id: Uuid, tags: &[String]) -> Result<()>
+where
+    E: sqlx::PgExecutor<'e>,
+{
+    if tags.is_empty() {
+        return Ok(());
+    }
+    sqlx::query(
+        "INSERT INTO feedback_tags (feedback_id, tag) SELECT $1, unnest($2::text[]) ON CONFLICT DO NOTHING",
+    )
+    .bind(feedback_id)
+    .bind(tags)
+    .execute(executor)
+    .await
+    .context("Failed to store feedback tags")?;
+    Ok(())
+}
+
+/// 🏷️ Tags on one feedback item, alphabetically
+pub async fn tags_for<'e, E>(executor: E, feedback_id: Uuid) -> Result<Vec<String>>
+where
+    E: sqlx::PgExecutor<'e>,
+{
+    sqlx::query_scalar("SELECT tag FROM feedback_tags WHERE feedback_id = $1 ORDER BY tag")
+        .bind(feedback_id)
+        .fetch_all(executor)
+        .await
+        .context("Failed to fetch feedback tags")
+}
+
+/// 📊 One tag and how many feedback items carry it
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, sqlx::FromRow)]
+pub struct TagCount {
+    pub tag: String,
+    pub count: i64,
+}
+
+/// 📊 Most used tags on feedback created at or after `since` (None = all time)
+pub async fn top_tags(
+    app_state: &AppState,
+    since: Option<chrono::DateTime<chrono::Utc>>,
+    limit: i64,
+) -> Result<Vec<TagCount>> {
+    sqlx::query_as::<_, TagCount>(
+        r#"
+        SELECT t.tag, COUNT(*) AS count
+        FROM feedback_tags t
+        JOIN feedback f ON f.id = t.feedback_id
+        WHERE ($1::timestamptz IS NULL OR f.created_at >= $1)
+        GROUP BY t.tag
+        ORDER BY count DESC, t.tag
+        LIMIT $2
+        "#,
+    )
+    .bind(since)
+    .bind(limit)
+    .fetch_all(&app_state.db_pool)
+    .await
+    .context("Failed to fetch tag statistics")
+}
+
+/// 🔍 `?range=` and `?limit=` for the tag statistics endpoint
+#[derive(Debug, Default, Deserialize)]
+pub struct TagStatsQuery {
+    pub range: Option<String>,
+    pub limit: Option<i64>,
+}
+
+/// 📊 Tag statistics response body
+#[derive(Debug, Serialize)]
+pub struct TagStats {
+    pub range: &'static str,
+    pub tags: Vec<TagCount>,
+}
+
+/// 🏷️ GET /admin/api/tags - tag frequencies for the selected range
+pub async fn admin_tag_stats(
+    State(app_state): State<AppState>,
+    jar: CookieJar,
+    Query(query): Query<TagStatsQuery>,
+) -> Response {
+    if let Some(denied) = require_admin_api_auth(&jar, &app_state).await {
+        return denied;
+    }
+    let range = DashboardRange::from_param(query.range.as_deref());
+    let limit = query
+        .limit
+        .unwrap_or(DEFAULT_TAG_LIMIT)
+        .clamp(1, MAX_TAG_LIMIT);
+
+    match top_tags(&app_state, range.cutoff(chrono::Utc::now()), limit).await {
+        Ok(tags) => (
+            StatusCode::OK,
+            Json(ApiResponse::success(
+                format!("{} tags", tags.len()),
+                TagStats {
+                    range: range.as_param(),
+                    tags,
+                },
+            )),
+        )
+            .into_response(),
+        Err(e) => crate::api::utils::handle_error(e).into_response(),
+    }
+}
+
+/// 🔗 Percent-encode a value for a query string
+pub fn encode_query_value(value: &str) -> String {
+    value
+        .bytes()
+        .map(|byte| match byte {
+            b'a'..=b'z' | b'A'..=b'Z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                (byte as char).to_string()
+            }
+            _ => format!("%{:02X}", byte),
+        })
+        .collect()
+}
+
+// 🧪 Tests - Counting themes, one tag at a time!
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::spawn_test_app;
+
+    #[test]
+    fn test_tag_normalization() {
+        assert_eq!(
+            normalize_tag("  Dark   Mode "),
+            Some("dark-mode".to_string())
+        );
+        assert_eq!(normalize_tag("C++"), Some("c++".to_string()));
+        assert_eq!(normalize_tag("   "), None);
+        assert_eq!(
+            normalize_tags(["UI", "ui", " ", "Perf", "u i"]),
+            vec!["ui", "perf", "u-i"]
+        );
+
+        let too_many: Vec<String> = (0..=MAX_TAGS).map(|n| format!("tag-{}", n)).collect();
+        assert_eq!(validate_tags(&too_many).len(), 1);
+        assert_eq!(validate_tags(&["x".repeat(MAX_TAG_LENGTH + 1)]).len(), 1);
+        assert!(validate_tags(&["ok".to_string()]).is_empty());
+
+        assert_eq!(
+            encode_query_value("c++ & co/ä"),
+            "c%2B%2B%20%26%20co%2F%C3%A4"
+        );
+        println!("✅ Tag normalization test passed!");
+    }
+
+    #[tokio::test]
+    async fn test_tag_stats_endpoint_counts_by_range() {
+        let Some(app) = spawn_test_app().await else {
+            return;
+        };
+        let tagged = [
+            (0, vec!["ui", "dark-mode"]),
+            (0, vec!["ui"]),
+            (2, vec!["ui", "perf"]),
+            (40, vec!["perf", "legacy"]),
+        ];
+        for (days_ago, tags) in tagged {
+            let id: Uuid = sqlx::query_scalar(
+                "INSERT INTO feedback (repository, content, created_at) VALUES ('8b-is/smart-tree', 'Hi', NOW() - make_interval(days => $1)) RETURNING id",
+            )
+            .bind(days_ago)
+            .fetch_one(&app.db_pool)
+            .await
+            .unwrap();
+            let tags: Vec<String> = tags.into_iter().map(str::to_string).collect();
+            store_tags(&app.db_pool, id, &tags).await.unwrap();
+            // 🔁 Storing again is harmless
+            store_tags(&app.db_pool, id, &tags).await.unwrap();
+        }
+
+        let denied = app
+            .client
+            .get(app.url("/admin/api/tags"))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(denied.status(), 401);
+        app.login_admin().await.unwrap();
+
+        let fetch = |query: &'static str| {
+            let client = app.client.clone();
+            let url = app.url(&format!("/admin/api/tags{}", query));
+            async move {
+                let body: serde_json::Value =
+                    client.get(url).send().await.unwrap().json().await.unwrap();
+                body["data"].clone()
+            }
+        };
+
+        let all = fetch("").await;
+        assert_eq!(all["range"], "all");
+        assert_eq!(
+            all["tags"],
+            serde_json::json!([
+                { "tag": "ui", "count": 3 },
+                { "tag": "perf", "count": 2 },
+                { "tag": "dark-mode", "count": 1 },
+                { "tag": "legacy", "count": 1 },
+            ])
+        );
+
+        let week = fetch("?range=7d&limit=2").await;
+        assert_eq!(
+            week["tags"],
+            serde_json::json!([
+                { "tag": "ui", "count": 3 },
+                { "tag": "dark-mode", "count": 1 },
+            ])
+        );
+        println!("✅ Tag statistics endpoint test passed!");
+    }
+}
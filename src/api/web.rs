@@ -8,6 +8,30 @@ use axum::{
     response::{Html, IntoResponse},
 };
 
+/// 🖼️ Shared layout for public pages: the same stylesheet as the admin panel, no sidebar
+pub(crate) fn render_public_page(title: &str, content: &str) -> String {
+    format!(
+        r#"<!DOCTYPE html>
+<html lang="en">
+<head>
+    <meta charset="UTF-8">
+    <meta name="viewport" content="width=device-width, initial-scale=1.0">
+    <title>{title}</title>
+    {css_link}
+</head>
+<body>
+    <main class="public-main">
+        <header><h1 class="public-brand"><a href="/"><span aria-hidden="true">🚢</span> Feedbacker</a></h1></header>
+{content}    </main>
+</body>
+</html>
+"#,
+        title = crate::api::admin::html_escape(title),
+        css_link = crate::api::assets::admin_css_link(),
+        content = content,
+    )
+}
+
 pub async fn projects_page(State(_app_state): State<AppState>) -> impl IntoResponse {
     Html("<h1>🏠 Projects Dashboard</h1><p>Coming soon...</p>")
 }
@@ -24,8 +48,25 @@ pub async fn register_page(State(_app_state): State<AppState>) -> impl IntoRespo
     Html("<h1>📝 Register</h1><p>Coming soon...</p>")
 }
 
-pub async fn docs_page(State(_app_state): State<AppState>) -> impl IntoResponse {
-    Html("<h1>📚 Documentation</h1><p>Coming soon...</p>")
+pub async fn docs_page(State(app_state): State<AppState>) -> impl IntoResponse {
+    Html(format!(
+        r#"<h1>📚 Documentation</h1>
+<h2>📝 POST /api/feedback</h2>
+<ul>
+    <li><code>repository</code> - target repository in <code>owner/repo</code> format</li>
+    <li><code>content</code> - your feedback, 10 to {max_content} characters</li>
+    <li><code>llm_provider</code> - optional, <code>openai</code> or <code>anthropic</code></li>
+    <li><code>callback_url</code> - optional public <code>https</code> URL; we POST the final status there,
+        signed in <code>X-Feedbacker-Signature</code> with the returned <code>callback_secret</code>
+        (for a while after a rotation, <code>X-Feedbacker-Signature-Previous</code> carries the old secret's signature)</li>
+</ul>
+<p>📏 Content over {max_content} characters is rejected with <code>400 validation_error</code>.
+Request bodies over {max_body} bytes are rejected with <code>413 Payload Too Large</code>.</p>
+<p>🧾 Bodies that aren't valid JSON for this shape get <code>400 invalid_json</code>, with the
+parser's message and the <code>line</code>/<code>column</code> it stopped at in <code>error.details</code>.</p>"#,
+        max_content = app_state.config.feedback.max_content_length,
+        max_body = app_state.config.server.max_body_size,
+    ))
 }
 
 pub async fn about_page(State(_app_state): State<AppState>) -> impl IntoResponse {
@@ -2,13 +2,38 @@
 // This module handles GitHub webhook endpoints
 // Created with love by Aye & Hue! ✨
 
-use crate::api::{ApiResponse, AppState};
+use crate::api::{ApiResponse, AppState, ErrorCode};
+use crate::github::installations::{
+    apply_installation_event, InstallationEvent, INSTALLATION_EVENT,
+    INSTALLATION_REPOSITORIES_EVENT,
+};
+use crate::utils::deliveries::{
+    delivery_key, delivery_timestamp, verify_delivery, DeliveryRejection,
+};
+use crate::utils::signatures::SecretMatch;
 use axum::{
+    body::Bytes,
     extract::State,
-    http::StatusCode,
-    response::{IntoResponse, Json},
+    http::{HeaderMap, StatusCode},
+    response::{IntoResponse, Json, Response},
 };
-use serde::Deserialize;
+use serde::{
+    de::{DeserializeOwned, IgnoredAny},
+    Deserialize, Serialize,
+};
+use tracing::{debug, error, info, warn};
+
+/// 🔏 Header GitHub puts the body's HMAC-SHA256 in
+pub const GITHUB_SIGNATURE_HEADER: &str = "x-hub-signature-256";
+/// 📣 Header naming the delivery's event (ping, issues, installation, ...)
+pub const GITHUB_EVENT_HEADER: &str = "x-github-event";
+/// 🆔 Header with GitHub's id for the delivery (kept on a manual redelivery, logged only -
+/// it isn't signed, so replay protection goes by the body)
+pub const GITHUB_DELIVERY_HEADER: &str = "x-github-delivery";
+/// 🗄️ Shortest time accepted deliveries are kept in the database
+const MIN_DELIVERY_RETENTION_HOURS: i64 = 1;
+/// 🗄️ How long they are kept when timestamps aren't checked
+const UNCHECKED_DELIVERY_RETENTION_DAYS: i64 = 7;
 
 #[derive(Debug, Deserialize)]
 pub struct GitHubWebhookPayload {
@@ -17,10 +42,274 @@ pub struct GitHubWebhookPayload {
     pub pull_request: Option<serde_json::Value>,
 }
 
+/// 🎟️ A verified delivery we've claimed: nobody else processes the same body until
+/// it is settled
+#[derive(Debug)]
+pub(crate) struct DeliveryClaim {
+    /// 🔑 `delivery_key` of the body (None when deliveries aren't verified)
+    key: Option<String>,
+}
+
+impl DeliveryClaim {
+    /// 🏁 Hand back the handler's response - and when processing failed on our side
+    /// (5xx), forget the delivery so GitHub's redelivery isn't refused as a replay
+    pub(crate) async fn settle(self, app_state: &AppState, response: Response) -> Response {
+        if let Some(key) = self.key.filter(|_| response.status().is_server_error()) {
+            app_state.webhook_replays.forget(&key);
+            if let Err(e) =
+                crate::database::webhook_deliveries::forget_delivery(&app_state.db_pool, &key).await
+            {
+                warn!(
+                    "⚠️ Couldn't forget failed webhook delivery, a redelivery will be refused: {:#}",
+                    e
+                );
+            }
+        }
+        response
+    }
+}
+
+/// 🔏 Check a GitHub delivery's signature, timestamp and freshness, and claim it -
+/// Err(response) when it's turned away: 401 unsigned, 400 undated, 409 expired or
+/// replayed (see `utils::deliveries`). Without GITHUB_WEBHOOK_SECRET every delivery is
+/// refused with 401, unless GITHUB_WEBHOOK_ALLOW_UNSIGNED accepts them unverified.
+/// Pass the handler's response through `DeliveryClaim::settle`.
+pub(crate) async fn claim_delivery(
+    app_state: &AppState,
+    headers: &HeaderMap,
+    body: &[u8],
+) -> Result<DeliveryClaim, Response> {
+    let Some(secrets) = app_state.config.github_webhook_secrets() else {
+        if app_state.config.github.allow_unsigned_webhooks {
+            warn!("🚨 Accepting an UNVERIFIED webhook delivery - GITHUB_WEBHOOK_ALLOW_UNSIGNED is on and no GITHUB_WEBHOOK_SECRET is set");
+            return Ok(DeliveryClaim { key: None });
+        }
+        error!("🔏 Refusing webhook delivery: GITHUB_WEBHOOK_SECRET is not set, so it can't be verified");
+        return Err(crate::api::utils::unauthorized_error().into_response());
+    };
+    let header = |name: &str| headers.get(name).and_then(|value| value.to_str().ok());
+    let signature = header(GITHUB_SIGNATURE_HEADER).unwrap_or_default();
+    let max_skew = app_state.config.webhook_max_skew();
+    match verify_delivery(
+        &secrets,
+        body,
+        signature,
+        delivery_timestamp(body),
+        chrono::Utc::now(),
+        max_skew,
+        &app_state.webhook_replays,
+    ) {
+        Ok(SecretMatch::Primary) => {
+            info!("🔏 Webhook signature matched the primary secret");
+        }
+        Ok(SecretMatch::Previous) => {
+            warn!("🔏 Webhook signature matched the previous secret - update the secret on GitHub before the overlap ends");
+        }
+        Err(rejection) => return Err(reject_delivery(rejection)),
+    }
+
+    // 🗄️ The memory cache forgets on restart and isn't shared, the database doesn't
+    let retention = match max_skew {
+        Some(skew) => (skew * 2).max(chrono::Duration::hours(MIN_DELIVERY_RETENTION_HOURS)),
+        None => chrono::Duration::days(UNCHECKED_DELIVERY_RETENTION_DAYS),
+    };
+    let key = delivery_key(body);
+    match crate::database::webhook_deliveries::record_delivery(&app_state.db_pool, &key, retention)
+        .await
+    {
+        Ok(true) => {}
+        Ok(false) => {
+            warn!(
+                "🔁 Replayed webhook delivery {}",
+                header(GITHUB_DELIVERY_HEADER).unwrap_or("(no id)")
+            );
+            return Err(reject_delivery(DeliveryRejection::Replayed));
+        }
+        Err(e) => {
+            warn!(
+                "⚠️ Couldn't record webhook delivery, relying on the in-memory replay check: {:#}",
+                e
+            );
+        }
+    }
+    Ok(DeliveryClaim { key: Some(key) })
+}
+
+/// 🚫 The answer to a delivery that didn't verify
+fn reject_delivery(rejection: DeliveryRejection) -> Response {
+    let (status, message) = match rejection {
+        DeliveryRejection::BadSignature => {
+            warn!("🚫 Rejected webhook delivery with a missing or invalid signature");
+            return crate::api::utils::unauthorized_error().into_response();
+        }
+        DeliveryRejection::MissingTimestamp => (
+            StatusCode::BAD_REQUEST,
+            "Delivery has no timestamp (updated_at or delivered_at in the signed payload)",
+        ),
+        DeliveryRejection::Expired => (
+            StatusCode::CONFLICT,
+            "Delivery timestamp is outside the allowed skew window",
+        ),
+        DeliveryRejection::Replayed => (StatusCode::CONFLICT, "Delivery was already received"),
+    };
+    warn!("🚫 Rejected webhook delivery: {}", rejection.code());
+    (
+        status,
+        Json(ApiResponse::<()>::error(
+            rejection.code(),
+            message.to_string(),
+            None,
+        )),
+    )
+        .into_response()
+}
+
+/// 📦 Parse a verified delivery's JSON body (400 when it isn't what we expect)
+pub(crate) fn parse_delivery<T: DeserializeOwned>(body: &[u8]) -> Result<T, Box<Response>> {
+    crate::api::json::parse_json_body(body)
+}
+
+/// 🏓 What a `ping` delivery tells us about the hook that was just set up
+#[derive(Debug, Deserialize, Serialize)]
+pub struct PingDetails {
+    #[serde(default)]
+    pub hook_id: Option<i64>,
+    #[serde(default)]
+    pub zen: Option<String>,
+}
+
+/// 🧩 Answer the events a freshly configured hook or app sends before any real traffic.
+/// `ping` is acknowledged and installation events are recorded; Some(response) when
+/// the delivery was one of these, None to carry on with the endpoint's own payload.
+pub(crate) async fn handle_setup_event(
+    app_state: &AppState,
+    headers: &HeaderMap,
+    body: &[u8],
+) -> Option<Response> {
+    let event = headers
+        .get(GITHUB_EVENT_HEADER)
+        .and_then(|value| value.to_str().ok())?;
+
+    match event {
+        "ping" => {
+            let details: PingDetails = match parse_delivery(body) {
+                Ok(details) => details,
+                Err(response) => return Some(*response),
+            };
+            info!("🏓 GitHub ping for hook {:?}", details.hook_id);
+            Some(
+                (
+                    StatusCode::OK,
+                    Json(ApiResponse::success("pong".to_string(), details)),
+                )
+                    .into_response(),
+            )
+        }
+        INSTALLATION_EVENT | INSTALLATION_REPOSITORIES_EVENT => {
+            let payload: InstallationEvent = match parse_delivery(body) {
+                Ok(payload) => payload,
+                Err(response) => return Some(*response),
+            };
+            if let Err(e) = apply_installation_event(&app_state.db_pool, event, &payload).await {
+                error!("❌ Failed to record {} event: {:#}", event, e);
+                return Some(crate::api::utils::handle_error(e).into_response());
+            }
+            Some(
+                (
+                    StatusCode::OK,
+                    Json(ApiResponse::<()>::success_no_data(format!(
+                        "Installation {} {} recorded",
+                        payload.installation.id, payload.action
+                    ))),
+                )
+                    .into_response(),
+            )
+        }
+        _ => None,
+    }
+}
+
+/// 🎯 The two things the event filter looks at (everything else in the body is skipped)
+#[derive(Debug, Deserialize)]
+struct DeliveryKind {
+    #[serde(default)]
+    action: Option<String>,
+    #[serde(default)]
+    comment: Option<IgnoredAny>,
+}
+
+/// 🎯 Is `event.action` one of the enabled pairs (`event.*` enables every action)?
+pub fn is_event_enabled(enabled: &[String], event: &str, action: &str) -> bool {
+    enabled.iter().any(|entry| match entry.split_once('.') {
+        Some((name, "*")) => name == event,
+        Some((name, wanted)) => name == event && wanted == action,
+        None => false,
+    })
+}
+
+/// 🙈 Acknowledge deliveries outside GITHUB_WEBHOOK_EVENTS with a 200 and nothing else -
+/// Some(response) when the delivery is dropped. Without an X-GitHub-Event header the
+/// event is `issue_comment` when there is a comment and `issues` otherwise; a body we
+/// can't read (or without an `action`) goes on to the real parser, which reports it.
+pub(crate) fn ignore_unwanted_event(
+    app_state: &AppState,
+    headers: &HeaderMap,
+    body: &[u8],
+) -> Option<Response> {
+    let kind: DeliveryKind = serde_json::from_slice(body).ok()?;
+    let event = match headers
+        .get(GITHUB_EVENT_HEADER)
+        .and_then(|value| value.to_str().ok())
+    {
+        Some(event) => event.trim().to_lowercase(),
+        None if kind.comment.is_some() => "issue_comment".to_string(),
+        None => "issues".to_string(),
+    };
+    let action = kind.action?.to_lowercase();
+    if is_event_enabled(&app_state.config.github.webhook_events, &event, &action) {
+        return None;
+    }
+    debug!("🙈 Ignoring {}.{} delivery", event, action);
+    Some(
+        (
+            StatusCode::OK,
+            Json(ApiResponse::<()>::success_no_data(format!(
+                "Ignored {}.{} (not in GITHUB_WEBHOOK_EVENTS)",
+                event, action
+            ))),
+        )
+            .into_response(),
+    )
+}
+
 pub async fn github_webhook(
-    State(_app_state): State<AppState>,
-    Json(_payload): Json<GitHubWebhookPayload>,
-) -> impl IntoResponse {
+    State(app_state): State<AppState>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> Response {
+    let claim = match claim_delivery(&app_state, &headers, &body).await {
+        Ok(claim) => claim,
+        Err(rejected) => return rejected,
+    };
+    let response = process_github_webhook(&app_state, &headers, &body).await;
+    claim.settle(&app_state, response).await
+}
+
+/// 🔧 Everything after verification, for `github_webhook` (and the pull request
+/// deliveries of the hook we register, which land on the issues endpoint)
+pub(crate) async fn process_github_webhook(
+    app_state: &AppState,
+    headers: &HeaderMap,
+    body: &[u8],
+) -> Response {
+    if let Some(handled) = handle_setup_event(app_state, headers, body).await {
+        return handled;
+    }
+    let _payload: GitHubWebhookPayload = match parse_delivery(body) {
+        Ok(payload) => payload,
+        Err(response) => return *response,
+    };
+
     // TODO: Implement GitHub webhook processing
     (
         StatusCode::OK,
@@ -29,4 +318,500 @@ pub async fn github_webhook(
             "Webhook processed".to_string(),
         )),
     )
+        .into_response()
+}
+
+// 🧪 Tests - Only GitHub gets past the door!
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{spawn_test_app, spawn_test_app_with_config};
+    use crate::utils::signatures::sign;
+
+    #[test]
+    fn test_event_filter_matches_pairs_and_wildcards() {
+        let enabled = vec!["issues.opened".to_string(), "issue_comment.*".to_string()];
+        assert!(is_event_enabled(&enabled, "issues", "opened"));
+        assert!(!is_event_enabled(&enabled, "issues", "edited"));
+        assert!(is_event_enabled(&enabled, "issue_comment", "deleted"));
+        assert!(!is_event_enabled(&enabled, "pull_request", "opened"));
+        println!("✅ Webhook event filter test passed!");
+    }
+
+    #[tokio::test]
+    async fn test_unwanted_issue_events_are_acknowledged_without_work() {
+        let Some(app) = spawn_test_app_with_config(|_| {}).await else {
+            return;
+        };
+        let owner_id: uuid::Uuid = sqlx::query_scalar(
+            "INSERT INTO users (email, name, password_hash) VALUES ('hooks@example.com', 'Hooks', 'x') RETURNING id",
+        )
+        .fetch_one(&app.db_pool)
+        .await
+        .unwrap();
+        sqlx::query("INSERT INTO projects (owner_id, repository) VALUES ($1, '8b-is/smart-tree')")
+            .bind(owner_id)
+            .execute(&app.db_pool)
+            .await
+            .unwrap();
+        let deliver = |event: &str, action: &str| {
+            let request = app
+                .client
+                .post(app.url("/api/webhook/issues"))
+                .header(GITHUB_EVENT_HEADER, event)
+                .json(&serde_json::json!({
+                    "action": action,
+                    "issue": {
+                        "id": 1, "number": 42, "title": "Tree output is empty", "body": "It broke",
+                        "state": "open", "html_url": "https://github.com/8b-is/smart-tree/issues/42",
+                        "user": { "id": 7, "login": "someone" }, "labels": [], "assignees": []
+                    },
+                    "repository": {
+                        "id": 2, "name": "smart-tree", "full_name": "8b-is/smart-tree",
+                        "owner": { "id": 3, "login": "8b-is" }
+                    },
+                    "sender": { "id": 7, "login": "someone" }
+                }));
+            async move {
+                let response = request.send().await.unwrap();
+                let status = response.status();
+                (status, response.json::<serde_json::Value>().await.unwrap())
+            }
+        };
+        let recorded = || async {
+            sqlx::query_scalar::<_, i64>("SELECT COUNT(*) FROM webhooks")
+                .fetch_one(&app.db_pool)
+                .await
+                .unwrap()
+        };
+
+        let (status, body) = deliver("issues", "edited").await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(
+            body["message"],
+            "Ignored issues.edited (not in GITHUB_WEBHOOK_EVENTS)"
+        );
+        let (status, _) = deliver("star", "created").await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(recorded().await, 0);
+        assert!(app.github.calls().is_empty());
+
+        // ✅ The default set still reaches the automation
+        let (status, body) = deliver("issues", "assigned").await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["message"], "Issue automation completed");
+        assert_eq!(recorded().await, 1);
+        println!("✅ Unwanted webhook event test passed!");
+    }
+
+    #[tokio::test]
+    async fn test_pull_request_deliveries_on_the_issues_endpoint() {
+        let Some(app) = spawn_test_app().await else {
+            return;
+        };
+        // 🔀 The hook we register sends pull_request events to the issues endpoint too
+        let response = app
+            .client
+            .post(app.url("/api/webhook/issues"))
+            .header(GITHUB_EVENT_HEADER, "pull_request")
+            .json(&serde_json::json!({
+                "action": "closed",
+                "repository": {"full_name": "8b-is/smart-tree"},
+                "pull_request": {"number": 7, "merged": true}
+            }))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let body: serde_json::Value = response.json().await.unwrap();
+        assert_eq!(body["message"], "Webhook processed");
+        assert!(app.github.calls().is_empty());
+        println!("✅ Pull request delivery test passed!");
+    }
+
+    #[tokio::test]
+    async fn test_deliveries_are_refused_without_a_secret_unless_opted_out() {
+        for allow_unsigned in [false, true] {
+            let Some(app) = spawn_test_app_with_config(|config| {
+                config.github.webhook_secret = None;
+                config.github.allow_unsigned_webhooks = allow_unsigned;
+            })
+            .await
+            else {
+                return;
+            };
+            let body = serde_json::json!({
+                "action": "closed",
+                "repository": {"full_name": "8b-is/smart-tree"},
+                "pull_request": {"number": 1, "updated_at": chrono::Utc::now()}
+            })
+            .to_string();
+            for path in ["/api/webhook/github", "/api/webhook/issues"] {
+                let response = app
+                    .client
+                    .post(app.url(path))
+                    .header("content-type", "application/json")
+                    .header(GITHUB_EVENT_HEADER, "pull_request")
+                    .header(GITHUB_SIGNATURE_HEADER, sign("guessed", body.as_bytes()))
+                    .body(body.clone())
+                    .send()
+                    .await
+                    .unwrap();
+                let expected = if allow_unsigned {
+                    StatusCode::OK
+                } else {
+                    StatusCode::UNAUTHORIZED
+                };
+                assert_eq!(response.status(), expected, "{}", path);
+            }
+        }
+        println!("✅ Webhook fail-closed test passed!");
+    }
+
+    #[tokio::test]
+    async fn test_webhooks_accept_either_secret_during_overlap() {
+        let Some(app) = spawn_test_app_with_config(|config| {
+            config.github.webhook_secret = Some("new-secret".to_string());
+            config.github.webhook_secret_previous = Some("old-secret".to_string());
+            config.github.webhook_secret_rotated_at = Some(chrono::Utc::now());
+        })
+        .await
+        else {
+            return;
+        };
+        // 🔁 A body is only accepted once, so every delivery is a different pull request
+        let body = |number: u64| {
+            serde_json::json!({
+                "action": "closed",
+                "repository": {"full_name": "8b-is/smart-tree"},
+                "pull_request": {"number": number, "updated_at": chrono::Utc::now()}
+            })
+            .to_string()
+        };
+        let deliver = |body: String, secret: Option<&str>| {
+            let mut request = app
+                .client
+                .post(app.url("/api/webhook/github"))
+                .header("content-type", "application/json");
+            if let Some(secret) = secret {
+                request = request.header(GITHUB_SIGNATURE_HEADER, sign(secret, body.as_bytes()));
+            }
+            let request = request.body(body);
+            async move { request.send().await.unwrap().status() }
+        };
+
+        assert_eq!(deliver(body(1), Some("new-secret")).await, StatusCode::OK);
+        assert_eq!(deliver(body(2), Some("old-secret")).await, StatusCode::OK);
+        assert_eq!(
+            deliver(body(3), Some("wrong-secret")).await,
+            StatusCode::UNAUTHORIZED
+        );
+        assert_eq!(deliver(body(4), None).await, StatusCode::UNAUTHORIZED);
+        println!("✅ Webhook secret overlap test passed!");
+    }
+
+    #[tokio::test]
+    async fn test_previous_webhook_secret_rejected_after_overlap() {
+        let Some(app) = spawn_test_app_with_config(|config| {
+            config.github.webhook_secret = Some("new-secret".to_string());
+            config.github.webhook_secret_previous = Some("old-secret".to_string());
+            config.github.webhook_secret_rotated_at =
+                Some(chrono::Utc::now() - chrono::Duration::hours(25));
+            config.auth.secret_overlap_hours = 24;
+        })
+        .await
+        else {
+            return;
+        };
+        let body = serde_json::json!({
+            "action": "closed",
+            "repository": {},
+            "pull_request": {"updated_at": chrono::Utc::now()}
+        })
+        .to_string();
+        let status = |secret: &str| {
+            let request = app
+                .client
+                .post(app.url("/api/webhook/github"))
+                .header(GITHUB_SIGNATURE_HEADER, sign(secret, body.as_bytes()))
+                .body(body.clone());
+            async move { request.send().await.unwrap().status() }
+        };
+
+        assert_eq!(status("old-secret").await, StatusCode::UNAUTHORIZED);
+        assert_eq!(status("new-secret").await, StatusCode::OK);
+        println!("✅ Webhook overlap expiry test passed!");
+    }
+
+    #[tokio::test]
+    async fn test_setup_events_are_acknowledged_and_recorded() {
+        let Some(app) = spawn_test_app_with_config(|config| {
+            config.github.webhook_secret = Some("hook-secret".to_string());
+        })
+        .await
+        else {
+            return;
+        };
+        let deliver = |path: &'static str, event: &'static str, body: serde_json::Value| {
+            let body = body.to_string();
+            let request = app
+                .client
+                .post(app.url(path))
+                .header("content-type", "application/json")
+                .header(GITHUB_EVENT_HEADER, event)
+                .header(
+                    GITHUB_SIGNATURE_HEADER,
+                    sign("hook-secret", body.as_bytes()),
+                )
+                .body(body);
+            async move { request.send().await.unwrap() }
+        };
+
+        // 🏓 A fresh hook's ping is a 200 on both endpoints, not a parse error
+        let now = chrono::Utc::now();
+        let ping = serde_json::json!({
+            "zen": "Keep it logically awesome.",
+            "hook_id": 42,
+            "hook": {"updated_at": now}
+        });
+        for path in ["/api/webhook/github", "/api/webhook/issues"] {
+            // 🔁 The same ping twice would be a replay, so each endpoint gets its own
+            let mut ping = ping.clone();
+            ping["path"] = path.into();
+            let response = deliver(path, "ping", ping).await;
+            assert_eq!(response.status(), StatusCode::OK);
+            let body: serde_json::Value = response.json().await.unwrap();
+            assert_eq!(body["data"]["hook_id"], 42);
+        }
+
+        // 🧩 Installation created with two repositories, then one added and one removed
+        let installation = serde_json::json!({
+            "id": 1001,
+            "account": {"login": "8b-is", "type": "Organization"},
+            "repository_selection": "selected",
+            "updated_at": now
+        });
+        let response = deliver(
+            "/api/webhook/issues",
+            "installation",
+            serde_json::json!({
+                "action": "created",
+                "installation": installation,
+                "repositories": [{"full_name": "8b-is/smart-tree"}, {"full_name": "8b-is/feedbacker"}]
+            }),
+        )
+        .await;
+        assert_eq!(response.status(), StatusCode::OK);
+        let response = deliver(
+            "/api/webhook/github",
+            "installation_repositories",
+            serde_json::json!({
+                "action": "added",
+                "installation": installation,
+                "repositories_added": [{"full_name": "8b-is/mem8"}],
+                "repositories_removed": [{"full_name": "8b-is/feedbacker"}]
+            }),
+        )
+        .await;
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let recorded = crate::github::installations::find(&app.db_pool, 1001)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(recorded.account_login, "8b-is");
+        assert_eq!(recorded.account_type, "Organization");
+        assert_eq!(recorded.status, "active");
+        assert_eq!(
+            crate::github::installations::repositories(&app.db_pool, 1001)
+                .await
+                .unwrap(),
+            vec!["8b-is/mem8".to_string(), "8b-is/smart-tree".to_string()]
+        );
+        let covering =
+            crate::github::installations::installation_for_repository(&app.db_pool, "8b-is/mem8")
+                .await
+                .unwrap();
+        assert_eq!(covering.map(|i| i.installation_id), Some(1001));
+
+        // 🗑️ Uninstalling keeps the row but drops its repositories
+        let response = deliver(
+            "/api/webhook/github",
+            "installation",
+            serde_json::json!({"action": "deleted", "installation": installation}),
+        )
+        .await;
+        assert_eq!(response.status(), StatusCode::OK);
+        let recorded = crate::github::installations::find(&app.db_pool, 1001)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(recorded.status, "deleted");
+        assert!(crate::github::installations::installation_for_repository(
+            &app.db_pool,
+            "8b-is/mem8"
+        )
+        .await
+        .unwrap()
+        .is_none());
+
+        // 🚫 Other events still go through the endpoint's own parsing, signature first
+        let response = deliver(
+            "/api/webhook/issues",
+            "issues",
+            serde_json::json!({"zen": "hi", "hook": {"updated_at": now}}),
+        )
+        .await;
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        let unsigned = app
+            .client
+            .post(app.url("/api/webhook/github"))
+            .header(GITHUB_EVENT_HEADER, "ping")
+            .body(ping.to_string())
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(unsigned.status(), StatusCode::UNAUTHORIZED);
+        println!("✅ Webhook setup events test passed!");
+    }
+
+    #[tokio::test]
+    async fn test_stale_undated_and_replayed_deliveries_are_refused() {
+        let Some(app) = spawn_test_app_with_config(|config| {
+            config.github.webhook_secret = Some("hook-secret".to_string());
+            config.github.webhook_max_skew_seconds = 300;
+        })
+        .await
+        else {
+            return;
+        };
+        let deliver = |delivery_id: &str, body: serde_json::Value| {
+            let body = body.to_string();
+            let request = app
+                .client
+                .post(app.url("/api/webhook/github"))
+                .header(GITHUB_DELIVERY_HEADER, delivery_id)
+                .header(
+                    GITHUB_SIGNATURE_HEADER,
+                    sign("hook-secret", body.as_bytes()),
+                )
+                // ⏱️ Unsigned, so it never stands in for a date
+                .header("x-delivery-timestamp", chrono::Utc::now().timestamp())
+                .body(body);
+            async move {
+                let response = request.send().await.unwrap();
+                let status = response.status();
+                let body: serde_json::Value = response.json().await.unwrap();
+                (status, body["error"]["code"].clone())
+            }
+        };
+        let now = chrono::Utc::now();
+        let closed = |updated_at: chrono::DateTime<chrono::Utc>| {
+            serde_json::json!({
+                "action": "closed",
+                "repository": {},
+                "pull_request": {"number": 7, "updated_at": updated_at}
+            })
+        };
+
+        assert_eq!(deliver("d-1", closed(now)).await.0, StatusCode::OK);
+        // 🔁 The captured delivery again: caught by the in-memory cache
+        assert_eq!(
+            deliver("d-1", closed(now)).await,
+            (StatusCode::CONFLICT, "delivery_replayed".into())
+        );
+        // 🗄️ Same bytes under a made-up delivery id, on an instance that never saw
+        // them: the database knows the body
+        app.app_state
+            .webhook_replays
+            .forget(&delivery_key(closed(now).to_string().as_bytes()));
+        assert_eq!(
+            deliver("d-forged", closed(now)).await,
+            (StatusCode::CONFLICT, "delivery_replayed".into())
+        );
+        // 🕰️ Outside the skew window, either way
+        assert_eq!(
+            deliver("d-2", closed(now - chrono::Duration::minutes(10))).await,
+            (StatusCode::CONFLICT, "delivery_expired".into())
+        );
+        assert_eq!(
+            deliver("d-3", closed(now + chrono::Duration::minutes(10))).await,
+            (StatusCode::CONFLICT, "delivery_expired".into())
+        );
+        // ⏱️ Undated bodies are refused, whatever the headers say
+        let undated = serde_json::json!({"action": "closed", "repository": {}});
+        assert_eq!(
+            deliver("d-4", undated).await,
+            (StatusCode::BAD_REQUEST, "delivery_timestamp_missing".into())
+        );
+        // 🔏 Signatures are still checked first
+        let forged = app
+            .client
+            .post(app.url("/api/webhook/github"))
+            .header(GITHUB_SIGNATURE_HEADER, "sha256=00")
+            .body(closed(now).to_string())
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(forged.status(), StatusCode::UNAUTHORIZED);
+        println!("✅ Webhook replay protection test passed!");
+    }
+
+    #[tokio::test]
+    async fn test_failed_deliveries_can_be_redelivered() {
+        let Some(app) = spawn_test_app_with_config(|config| {
+            config.github.webhook_secret = Some("hook-secret".to_string());
+        })
+        .await
+        else {
+            return;
+        };
+        let owner_id: uuid::Uuid = sqlx::query_scalar(
+            "INSERT INTO users (email, name, password_hash) VALUES ('hooks@example.com', 'Hooks', 'x') RETURNING id",
+        )
+        .fetch_one(&app.db_pool)
+        .await
+        .unwrap();
+        sqlx::query("INSERT INTO projects (owner_id, repository) VALUES ($1, '8b-is/smart-tree')")
+            .bind(owner_id)
+            .execute(&app.db_pool)
+            .await
+            .unwrap();
+        let body = serde_json::json!({
+            "action": "opened",
+            "issue": {
+                "id": 1, "number": 42, "title": "Tree output is empty", "body": "It broke",
+                "state": "open", "html_url": "https://github.com/8b-is/smart-tree/issues/42",
+                "user": { "id": 7, "login": "someone" }, "labels": [], "assignees": [],
+                "updated_at": chrono::Utc::now()
+            },
+            "repository": {
+                "id": 2, "name": "smart-tree", "full_name": "8b-is/smart-tree",
+                "owner": { "id": 3, "login": "8b-is" }
+            },
+            "sender": { "id": 7, "login": "someone" }
+        })
+        .to_string();
+        let deliver = || {
+            let request = app
+                .client
+                .post(app.url("/api/webhook/issues"))
+                .header(GITHUB_EVENT_HEADER, "issues")
+                .header(
+                    GITHUB_SIGNATURE_HEADER,
+                    sign("hook-secret", body.as_bytes()),
+                )
+                .body(body.clone());
+            async move { request.send().await.unwrap().status() }
+        };
+
+        // 💥 GitHub is down: the delivery fails and is forgotten...
+        *app.github.fail_with.lock().unwrap() = Some("GitHub is down".to_string());
+        assert_eq!(deliver().await, StatusCode::INTERNAL_SERVER_ERROR);
+        // 🔁 ...so GitHub's redelivery is processed, and only then remembered
+        *app.github.fail_with.lock().unwrap() = None;
+        assert_eq!(deliver().await, StatusCode::OK);
+        assert_eq!(deliver().await, StatusCode::CONFLICT);
+        println!("✅ Failed delivery redelivery test passed!");
+    }
 }
@@ -1,2 +1,6 @@
 // 🔐 Authentication Module - User Management! 🔐
 // TODO: Implement authentication logic
+
+pub mod password; // 🔑 Argon2 password hashes
+pub mod session; // 🍪 Signed admin session tokens
+pub mod totp; // 🔢 TOTP two-factor for the admin login
@@ -0,0 +1,47 @@
+// 🔑 Password Hashing - Argon2id, so a leaked users table stays boring! 🔑
+// Hashes are stored as PHC strings (`$argon2id$v=19$...`), which carry their own
+// salt and parameters, so old hashes keep verifying if the defaults ever change.
+// Created with love by Aye & Hue! ✨
+
+use anyhow::Result;
+use argon2::password_hash::{rand_core::OsRng, PasswordHash, SaltString};
+use argon2::{Argon2, PasswordHasher, PasswordVerifier};
+
+/// #️⃣ Hash a password with a fresh random salt
+pub fn hash_password(password: &str) -> Result<String> {
+    let salt = SaltString::generate(&mut OsRng);
+    Argon2::default()
+        .hash_password(password.as_bytes(), &salt)
+        .map(|hash| hash.to_string())
+        .map_err(|e| anyhow::anyhow!("Failed to hash password: {}", e))
+}
+
+/// ✅ Does the password match the stored hash? (unparseable hashes never match)
+pub fn verify_password(password: &str, hash: &str) -> bool {
+    PasswordHash::new(hash)
+        .map(|parsed| {
+            Argon2::default()
+                .verify_password(password.as_bytes(), &parsed)
+                .is_ok()
+        })
+        .unwrap_or(false)
+}
+
+// 🧪 Tests - Salt, hash, repeat!
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hashes_verify_and_are_salted() {
+        let hash = hash_password("correct horse").unwrap();
+        assert!(hash.starts_with("$argon2id$"));
+        assert!(verify_password("correct horse", &hash));
+        assert!(!verify_password("correct horse!", &hash));
+        assert_ne!(hash, hash_password("correct horse").unwrap());
+        // 🚫 Placeholder or legacy values are not hashes
+        assert!(!verify_password("", ""));
+        assert!(!verify_password("x", "not-a-hash"));
+        println!("✅ Password hashing test passed!");
+    }
+}
@@ -0,0 +1,174 @@
+// 🍪 Admin Sessions - Signed cookies that know who is logged in! 🍪
+// A session token is `<subject>.<expires>.<hmac>`: the subject is a user id, or
+// `config` for the bootstrap admin from ADMIN_USERNAME / ADMIN_PASSWORD. The HMAC
+// (keyed with JWT_SECRET) also covers that account's current password hash - or the
+// configured password - and when its second factor was switched on, so changing a
+// password or turning 2FA on or off ends every session issued before it.
+// Created with love by Aye & Hue! ✨
+
+use chrono::{DateTime, Utc};
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+use uuid::Uuid;
+
+use super::totp::constant_time_eq;
+
+/// ⏳ How long an admin session lasts
+pub const SESSION_TTL_SECS: i64 = 24 * 60 * 60;
+/// 🏷️ Subject used for the configured bootstrap admin
+const BOOTSTRAP_SUBJECT: &str = "config";
+
+/// 🪪 Whose session it is
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SessionSubject {
+    /// 🥾 The admin from the config (bootstrap fallback)
+    Bootstrap,
+    /// 👤 An admin or service account from `users`
+    User(Uuid),
+}
+
+impl SessionSubject {
+    /// 🔑 Stable key for this subject (`config` or the user id), used in tokens and as
+    /// the `admin_second_factors` primary key
+    pub fn key(&self) -> String {
+        match self {
+            SessionSubject::Bootstrap => BOOTSTRAP_SUBJECT.to_string(),
+            SessionSubject::User(id) => id.to_string(),
+        }
+    }
+
+    /// 🔍 The subject a `key` names
+    pub fn from_key(key: &str) -> Option<Self> {
+        match key {
+            BOOTSTRAP_SUBJECT => Some(SessionSubject::Bootstrap),
+            id => id.parse().ok().map(SessionSubject::User),
+        }
+    }
+}
+
+/// 🔏 What a session is signed over: the subject's password (hash) and when its second
+/// factor was enabled, if it is. Logging in checks the factor once; the session then
+/// only stays valid while that same factor is.
+pub fn credential(password: &str, second_factor_since: Option<DateTime<Utc>>) -> String {
+    match second_factor_since {
+        Some(since) => format!("{}:2fa:{}", password, since.timestamp_micros()),
+        None => password.to_string(),
+    }
+}
+
+/// 📦 A token split into its parts, not yet verified
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnverifiedSession<'a> {
+    pub subject: SessionSubject,
+    expires: i64,
+    signature: &'a str,
+}
+
+impl UnverifiedSession<'_> {
+    /// ✅ Genuine, unexpired, and signed over this `credential` (the subject's current
+    /// password hash, or the configured password for the bootstrap admin)?
+    pub fn verify(&self, credential: &str, key: &str, now: i64) -> bool {
+        self.expires >= now
+            && constant_time_eq(
+                self.signature.as_bytes(),
+                signature(self.subject, self.expires, credential, key).as_bytes(),
+            )
+    }
+}
+
+/// 🎫 Issue a session token for `subject`
+pub fn issue(subject: SessionSubject, credential: &str, key: &str, now: i64) -> String {
+    let expires = now + SESSION_TTL_SECS;
+    format!(
+        "{}.{}.{}",
+        subject.key(),
+        expires,
+        signature(subject, expires, credential, key)
+    )
+}
+
+/// 🔍 Split a token so the caller can look up the subject's credential
+pub fn parse(token: &str) -> Option<UnverifiedSession<'_>> {
+    let mut parts = token.splitn(3, '.');
+    let subject = SessionSubject::from_key(parts.next()?)?;
+    let expires = parts.next()?.parse().ok()?;
+    let signature = parts.next()?;
+    Some(UnverifiedSession {
+        subject,
+        expires,
+        signature,
+    })
+}
+
+fn signature(subject: SessionSubject, expires: i64, credential: &str, key: &str) -> String {
+    let mut mac =
+        Hmac::<Sha256>::new_from_slice(key.as_bytes()).expect("HMAC accepts any key length");
+    mac.update(format!("admin-session:{}:{}:", subject.key(), expires).as_bytes());
+    mac.update(credential.as_bytes());
+    hex::encode(mac.finalize().into_bytes())
+}
+
+// 🧪 Tests - Cookies with a signature!
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_session_tokens_are_bound_to_subject_credential_and_time() {
+        let user = SessionSubject::User(Uuid::new_v4());
+        let token = issue(user, "hash-1", "secret", 1_000);
+        let session = parse(&token).unwrap();
+        assert_eq!(session.subject, user);
+        assert!(session.verify("hash-1", "secret", 1_000 + SESSION_TTL_SECS));
+
+        // 🚫 Expired, password changed, or another key
+        assert!(!session.verify("hash-1", "secret", 1_001 + SESSION_TTL_SECS));
+        assert!(!session.verify("hash-2", "secret", 1_000));
+        assert!(!session.verify("hash-1", "other", 1_000));
+
+        // 🚫 Swapping the subject breaks the signature
+        let other = Uuid::new_v4();
+        let forged = token.replacen(&token[..36], &other.to_string(), 1);
+        assert!(!parse(&forged).unwrap().verify("hash-1", "secret", 1_000));
+
+        let bootstrap = issue(SessionSubject::Bootstrap, "pw", "secret", 1_000);
+        assert!(bootstrap.starts_with("config."));
+        assert!(parse(&bootstrap).unwrap().verify("pw", "secret", 1_000));
+        assert!(parse("garbage").is_none());
+        assert!(parse("config.soon.sig").is_none());
+        println!("✅ Admin session token test passed!");
+    }
+
+    #[test]
+    fn test_enabling_a_second_factor_changes_the_credential() {
+        let since = chrono::Utc::now();
+        let token = issue(
+            SessionSubject::Bootstrap,
+            &credential("pw", None),
+            "k",
+            1_000,
+        );
+        let session = parse(&token).unwrap();
+        assert!(session.verify(&credential("pw", None), "k", 1_000));
+        assert!(!session.verify(&credential("pw", Some(since)), "k", 1_000));
+
+        let token = issue(
+            SessionSubject::Bootstrap,
+            &credential("pw", Some(since)),
+            "k",
+            1_000,
+        );
+        let session = parse(&token).unwrap();
+        assert!(session.verify(&credential("pw", Some(since)), "k", 1_000));
+        // 🔁 Disabling and re-enabling is a new factor, not the one logged in with
+        let later = since + chrono::Duration::seconds(1);
+        assert!(!session.verify(&credential("pw", Some(later)), "k", 1_000));
+        assert!(!session.verify(&credential("pw", None), "k", 1_000));
+        assert_eq!(
+            SessionSubject::from_key("config"),
+            Some(SessionSubject::Bootstrap)
+        );
+        assert_eq!(SessionSubject::from_key("nope"), None);
+        println!("✅ Second factor session binding test passed!");
+    }
+}
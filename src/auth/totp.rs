@@ -0,0 +1,189 @@
+// 🔢 TOTP Two-Factor - Six little digits between attackers and the admin panel! 🔢
+// RFC 6238 codes (SHA-1, 6 digits, 30s steps, ±1 step of clock drift) via totp-rs,
+// and single-use backup codes stored as hashes. The login challenge linking the
+// password step to the code step lives in database::second_factors.
+// Created with love by Aye & Hue! ✨
+
+use anyhow::{Context, Result};
+use rand::Rng;
+use sha2::{Digest, Sha256};
+use totp_rs::{Algorithm, Secret, TOTP};
+
+/// 🏷️ Issuer shown in authenticator apps
+pub const ISSUER: &str = "Feedbacker";
+/// ⏱️ Length of one TOTP time step in seconds
+pub const STEP_SECS: u64 = 30;
+/// 🕰️ Accepted clock drift, in steps either side of now
+pub const SKEW_STEPS: u8 = 1;
+/// 🎟️ Backup codes handed out at enrollment
+pub const BACKUP_CODE_COUNT: usize = 10;
+/// ⏳ How long the password step stays valid while we wait for the code
+pub const PRE_AUTH_TTL_SECS: i64 = 300;
+
+/// 🔑 Fresh random 160-bit secret, base32 encoded
+pub fn generate_secret() -> String {
+    let mut bytes = [0u8; 20];
+    rand::thread_rng().fill(&mut bytes);
+    Secret::Raw(bytes.to_vec()).to_encoded().to_string()
+}
+
+/// 🔧 Build the TOTP generator for a base32 secret
+fn totp(secret: &str, account: &str) -> Result<TOTP> {
+    let bytes = Secret::Encoded(secret.to_string())
+        .to_bytes()
+        .map_err(|e| anyhow::anyhow!("Invalid TOTP secret: {:?}", e))?;
+    TOTP::new(
+        Algorithm::SHA1,
+        6,
+        SKEW_STEPS,
+        STEP_SECS,
+        bytes,
+        Some(ISSUER.to_string()),
+        account.replace(':', "_"),
+    )
+    .context("Invalid TOTP parameters")
+}
+
+/// 🔗 `otpauth://` URI for authenticator apps
+pub fn otpauth_uri(secret: &str, account: &str) -> Result<String> {
+    Ok(totp(secret, account)?.get_url())
+}
+
+/// 🔳 The otpauth URI as an inline SVG QR code
+pub fn qr_svg(uri: &str) -> Result<String> {
+    let code = qrcode::QrCode::new(uri.as_bytes()).context("Failed to build QR code")?;
+    Ok(code
+        .render::<qrcode::render::svg::Color>()
+        .min_dimensions(200, 200)
+        .build())
+}
+
+/// 🔢 The code for a given unix time (used by tests and enrollment previews)
+pub fn code_at(secret: &str, unix_time: u64) -> Result<String> {
+    Ok(totp(secret, "")?.generate(unix_time))
+}
+
+/// ✅ Check a code at `unix_time`, allowing ±1 step of drift.
+/// Returns the matching time step so callers can refuse to accept it twice.
+pub fn verify_code(secret: &str, code: &str, unix_time: u64) -> Option<u64> {
+    let code = code.trim();
+    if code.len() != 6 || !code.chars().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+    let totp = totp(secret, "").ok()?;
+    let current_step = unix_time / STEP_SECS;
+    let skew = SKEW_STEPS as u64;
+    (current_step.saturating_sub(skew)..=current_step + skew).find(|step| {
+        let expected = totp.generate(step * STEP_SECS);
+        constant_time_eq(expected.as_bytes(), code.as_bytes())
+    })
+}
+
+/// 🎟️ Ten random backup codes like `k3f9-x2mq`
+pub fn generate_backup_codes() -> Vec<String> {
+    const ALPHABET: &[u8] = b"abcdefghjkmnpqrstuvwxyz23456789";
+    let mut rng = rand::thread_rng();
+    (0..BACKUP_CODE_COUNT)
+        .map(|_| {
+            let mut code: String = (0..8)
+                .map(|_| ALPHABET[rng.gen_range(0..ALPHABET.len())] as char)
+                .collect();
+            code.insert(4, '-');
+            code
+        })
+        .collect()
+}
+
+/// #️⃣ Hash a backup code for storage (case and dashes don't matter when typing it)
+pub fn hash_backup_code(code: &str) -> String {
+    let normalized: String = code
+        .chars()
+        .filter(|c| c.is_ascii_alphanumeric())
+        .map(|c| c.to_ascii_lowercase())
+        .collect();
+    hex::encode(Sha256::digest(normalized.as_bytes()))
+}
+
+/// 🎟️ Use up a backup code: removes its hash and returns true when it matched
+pub fn consume_backup_code(hashes: &mut Vec<String>, code: &str) -> bool {
+    let hash = hash_backup_code(code);
+    match hashes
+        .iter()
+        .position(|stored| constant_time_eq(stored.as_bytes(), hash.as_bytes()))
+    {
+        Some(index) => {
+            hashes.remove(index);
+            true
+        }
+        None => false,
+    }
+}
+
+/// ⚖️ Compare without leaking where the first difference is
+pub(crate) fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+// 🧪 Tests - Counting to six, very carefully!
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 🔑 RFC 6238 test secret ("12345678901234567890")
+    const RFC_SECRET: &str = "GEZDGNBVGY3TQOJQGEZDGNBVGY3TQOJQ";
+
+    #[test]
+    fn test_rfc6238_vectors() {
+        // 🔢 Appendix B values, last 6 digits
+        assert_eq!(code_at(RFC_SECRET, 59).unwrap(), "287082");
+        assert_eq!(code_at(RFC_SECRET, 1111111109).unwrap(), "081804");
+        assert_eq!(code_at(RFC_SECRET, 2000000000).unwrap(), "279037");
+        println!("✅ RFC 6238 vector test passed!");
+    }
+
+    #[test]
+    fn test_verification_window() {
+        let now = 1_700_000_015; // 15s into a step
+        let code = code_at(RFC_SECRET, now).unwrap();
+        let step = now / STEP_SECS;
+        assert_eq!(verify_code(RFC_SECRET, &code, now), Some(step));
+
+        // 🕰️ One step of drift either way is fine...
+        assert_eq!(verify_code(RFC_SECRET, &code, now + STEP_SECS), Some(step));
+        assert_eq!(verify_code(RFC_SECRET, &code, now - STEP_SECS), Some(step));
+        // 🚫 ...two steps is not
+        assert_eq!(verify_code(RFC_SECRET, &code, now + 2 * STEP_SECS), None);
+        assert_eq!(verify_code(RFC_SECRET, &code, now - 2 * STEP_SECS), None);
+
+        assert_eq!(verify_code(RFC_SECRET, "12345", now), None);
+        assert_eq!(verify_code(RFC_SECRET, "abcdef", now), None);
+        println!("✅ TOTP window test passed!");
+    }
+
+    #[test]
+    fn test_generated_secret_and_uri() {
+        let secret = generate_secret();
+        assert_eq!(secret.len(), 32);
+        let uri = otpauth_uri(&secret, "admin").unwrap();
+        assert!(uri.starts_with("otpauth://totp/Feedbacker:admin?"));
+        assert!(uri.contains(&format!("secret={}", secret)));
+        assert!(qr_svg(&uri).unwrap().contains("<svg"));
+        println!("✅ TOTP enrollment URI test passed!");
+    }
+
+    #[test]
+    fn test_backup_codes_are_single_use() {
+        let codes = generate_backup_codes();
+        assert_eq!(codes.len(), BACKUP_CODE_COUNT);
+        let mut hashes: Vec<String> = codes.iter().map(|code| hash_backup_code(code)).collect();
+        assert!(!hashes.contains(&codes[0]));
+
+        // 🔠 Typing it in caps without the dash still works - once
+        let typed = codes[3].to_uppercase().replace('-', "");
+        assert!(consume_backup_code(&mut hashes, &typed));
+        assert!(!consume_backup_code(&mut hashes, &codes[3]));
+        assert_eq!(hashes.len(), BACKUP_CODE_COUNT - 1);
+        assert!(!consume_backup_code(&mut hashes, "nope-nope"));
+        println!("✅ Backup code test passed!");
+    }
+}
@@ -0,0 +1,78 @@
+// 🏷️ Build Info - Which build is this, exactly? 🏷️
+// The crate version, the git commit and the build time, stamped in at compile time by
+// build.rs, plus the optional features this binary was compiled with. Shown in the
+// startup banner, the admin sidebar and `GET /api/version`. Nothing here is secret:
+// it's what `git log` and Cargo.toml already say.
+// Created with love by Aye & Hue! ✨
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+
+/// 📦 Crate version from Cargo.toml
+pub const VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// 🔖 Full git commit the binary was built from ("unknown" outside a checkout)
+pub const GIT_SHA: &str = env!("FEEDBACKER_GIT_SHA");
+
+const BUILD_EPOCH: &str = env!("FEEDBACKER_BUILD_EPOCH");
+
+/// ✂️ The commit as `git log --oneline` shows it
+pub fn short_sha() -> &'static str {
+    GIT_SHA.get(..7).unwrap_or(GIT_SHA)
+}
+
+/// 🕰️ When the binary was built
+pub fn built_at() -> Option<DateTime<Utc>> {
+    BUILD_EPOCH
+        .parse::<i64>()
+        .ok()
+        .and_then(|secs| DateTime::from_timestamp(secs, 0))
+}
+
+/// 🧩 Optional Cargo features compiled into this binary
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq)]
+pub struct Features {
+    pub dev_mode: bool,
+    /// 🌱 Seed data tooling (--seed, POST /admin/api/dev/seed)
+    pub dev_tools: bool,
+    pub redis_cache: bool,
+}
+
+pub fn features() -> Features {
+    Features {
+        dev_mode: cfg!(feature = "dev-mode"),
+        dev_tools: cfg!(feature = "dev-tools"),
+        redis_cache: cfg!(feature = "redis-cache"),
+    }
+}
+
+/// 📝 One-line summary for logs: "v0.1.0 (abc1234, built 2026-10-15T09:00:00Z)"
+pub fn summary() -> String {
+    match built_at() {
+        Some(at) => format!(
+            "v{} ({}, built {})",
+            VERSION,
+            short_sha(),
+            at.to_rfc3339_opts(chrono::SecondsFormat::Secs, true)
+        ),
+        None => format!("v{} ({})", VERSION, short_sha()),
+    }
+}
+
+// 🧪 Tests - Know thyself!
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_build_metadata_is_stamped_in() {
+        assert!(!GIT_SHA.is_empty());
+        assert!(GIT_SHA.starts_with(short_sha()));
+        assert!(short_sha().len() <= 7);
+        assert!(built_at().is_some());
+        assert!(summary().starts_with(&format!("v{} ({}", VERSION, short_sha())));
+        assert_eq!(features().redis_cache, cfg!(feature = "redis-cache"));
+        assert_eq!(features().dev_tools, cfg!(feature = "dev-tools"));
+        println!("✅ Build info test passed!");
+    }
+}
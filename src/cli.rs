@@ -0,0 +1,419 @@
+// 🧰 Command Line - Everything `feedbacker` can do besides serving! 🧰
+// `feedbacker` (optionally with `--seed`, in dev-tools builds) runs the server as always, waiting for the
+// database to come up unless `--fail-fast` says to give up at the first failure;
+// `feedbacker migrate <run|plan|status|rollback [id]>` manages the schema
+// explicitly, for deployments that keep AUTO_MIGRATE off, and
+// `feedbacker admin add <email> [admin|service]` creates (or resets) an admin console
+// account, reading its password from the first line of stdin, and
+// `feedbacker apikey add <name> <scope,...> [owner/repo,...]` issues an API key
+// (limited to the listed repositories, or to registered projects without a list)
+// and `feedbacker apikey revoke <key_id>` revokes one.
+// Created with love by Aye & Hue! ✨
+
+use anyhow::{Context, Result};
+use sqlx::PgPool;
+
+use crate::database::api_keys::ApiKey;
+use crate::database::models::UserRole;
+use crate::database::{self, migrations};
+
+/// 📖 Shown whenever the arguments don't make sense
+pub const USAGE: &str = "Usage: feedbacker [--seed] [--fail-fast]\n       feedbacker migrate <run|plan|status|rollback [migration_id]>\n       feedbacker admin add <email> [admin|service]\n       feedbacker apikey add <name> <scope,...> [owner/repo,...]\n       feedbacker apikey revoke <key_id>";
+
+/// 🎯 What this invocation should do
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Command {
+    /// 🚀 Run the HTTP server
+    Serve,
+    /// 🏃‍♂️ Manage migrations, then exit
+    Migrate(MigrateCommand),
+    /// 👥 Manage admin console accounts, then exit
+    Admin(AdminCommand),
+    /// 🔑 Manage API keys, then exit
+    ApiKey(ApiKeyCommand),
+}
+
+/// 🏃‍♂️ `feedbacker migrate ...` subcommands
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MigrateCommand {
+    /// ✅ Apply every pending migration
+    Run,
+    /// 📋 Show what `run` would apply, SQL included
+    Plan,
+    /// 📊 List every migration and when it was applied
+    Status,
+    /// 🔙 Roll back the given migration, or the most recently applied one
+    Rollback(Option<String>),
+}
+
+/// 👥 `feedbacker admin ...` subcommands
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AdminCommand {
+    /// ➕ Create the account, or reset its password and role if it exists
+    Add { email: String, role: UserRole },
+}
+
+/// 🔑 `feedbacker apikey ...` subcommands
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ApiKeyCommand {
+    /// ➕ Issue a key with these scopes, optionally limited to these repositories
+    Add {
+        name: String,
+        scopes: Vec<String>,
+        allowed_repos: Option<Vec<String>>,
+    },
+    /// 🚫 Revoke the key with this id (as printed when it was issued)
+    Revoke { id: uuid::Uuid },
+}
+
+/// ✂️ "a, b,c" -> ["a", "b", "c"]
+fn comma_list(value: &str) -> Vec<String> {
+    value
+        .split(',')
+        .map(str::trim)
+        .filter(|item| !item.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+/// 🔍 Parse the arguments after the program name. Flags (`--seed`) are left to the server.
+pub fn parse_args(args: &[String]) -> Result<Command> {
+    let mut positional = args
+        .iter()
+        .map(String::as_str)
+        .filter(|arg| !arg.starts_with("--"));
+    let command = match positional.next() {
+        None => Command::Serve,
+        Some("migrate") => {
+            let subcommand = match positional.next() {
+                Some("run") => MigrateCommand::Run,
+                Some("plan") => MigrateCommand::Plan,
+                Some("status") => MigrateCommand::Status,
+                Some("rollback") => MigrateCommand::Rollback(positional.next().map(str::to_string)),
+                Some(other) => anyhow::bail!("Unknown migrate command: {}\n{}", other, USAGE),
+                None => anyhow::bail!("Missing migrate command\n{}", USAGE),
+            };
+            Command::Migrate(subcommand)
+        }
+        Some("admin") => match positional.next() {
+            Some("add") => {
+                let Some(email) = positional.next() else {
+                    anyhow::bail!("Missing account email\n{}", USAGE);
+                };
+                let role = match positional.next() {
+                    None | Some("admin") => UserRole::Admin,
+                    Some("service") => UserRole::Service,
+                    Some(other) => anyhow::bail!("Unknown admin role: {}\n{}", other, USAGE),
+                };
+                Command::Admin(AdminCommand::Add {
+                    email: email.to_string(),
+                    role,
+                })
+            }
+            Some(other) => anyhow::bail!("Unknown admin command: {}\n{}", other, USAGE),
+            None => anyhow::bail!("Missing admin command\n{}", USAGE),
+        },
+        Some("apikey") => match positional.next() {
+            Some("add") => {
+                let (Some(name), Some(scopes)) = (positional.next(), positional.next()) else {
+                    anyhow::bail!("Missing key name or scopes\n{}", USAGE);
+                };
+                Command::ApiKey(ApiKeyCommand::Add {
+                    name: name.to_string(),
+                    scopes: comma_list(scopes),
+                    allowed_repos: positional.next().map(comma_list),
+                })
+            }
+            Some("revoke") => {
+                let Some(id) = positional.next() else {
+                    anyhow::bail!("Missing key id\n{}", USAGE);
+                };
+                let id = id
+                    .parse()
+                    .with_context(|| format!("{} is not an API key id\n{}", id, USAGE))?;
+                Command::ApiKey(ApiKeyCommand::Revoke { id })
+            }
+            Some(other) => anyhow::bail!("Unknown apikey command: {}\n{}", other, USAGE),
+            None => anyhow::bail!("Missing apikey command\n{}", USAGE),
+        },
+        Some(other) => anyhow::bail!("Unknown command: {}\n{}", other, USAGE),
+    };
+    if let Some(extra) = positional.next() {
+        anyhow::bail!("Unexpected argument: {}\n{}", extra, USAGE);
+    }
+    Ok(command)
+}
+
+/// 🏃‍♂️ Carry out a migrate subcommand, printing a report for the operator
+pub async fn run_migrate(pool: &PgPool, command: &MigrateCommand) -> Result<()> {
+    match command {
+        MigrateCommand::Run => {
+            let pending = migrations::pending_migrations(pool).await?;
+            database::run_migrations(pool).await?;
+            println!("✅ Applied {} migrations", pending.len());
+            for migration in pending {
+                println!("   {} - {}", migration.id, migration.description);
+            }
+        }
+        MigrateCommand::Plan => {
+            let pending = migrations::pending_migrations(pool).await?;
+            if pending.is_empty() {
+                println!("✅ Database schema is up to date, nothing to apply");
+            }
+            for migration in pending {
+                println!("📝 {} - {}", migration.id, migration.description);
+                println!("{}", migration.up_sql.trim_matches('\n'));
+                println!();
+            }
+        }
+        MigrateCommand::Status => {
+            for state in migrations::migration_status(pool).await? {
+                match state.applied_at {
+                    Some(applied_at) if state.has_drifted() => println!(
+                        "⚠️ {:<32} applied {} - checksum differs from this build",
+                        state.id,
+                        applied_at.format("%Y-%m-%d %H:%M:%S UTC")
+                    ),
+                    Some(applied_at) => println!(
+                        "✅ {:<32} applied {}",
+                        state.id,
+                        applied_at.format("%Y-%m-%d %H:%M:%S UTC")
+                    ),
+                    None => println!("⏳ {:<32} pending", state.id),
+                }
+            }
+        }
+        MigrateCommand::Rollback(id) => {
+            let id = match id {
+                Some(id) => id.clone(),
+                None => latest_applied(pool)
+                    .await?
+                    .ok_or_else(|| anyhow::anyhow!("No migrations have been applied"))?,
+            };
+            migrations::rollback_migration(pool, &id).await?;
+            println!("🔙 Rolled back {}", id);
+        }
+    }
+    Ok(())
+}
+
+/// 👥 Carry out an admin subcommand; the password is the first line of stdin
+pub async fn run_admin(pool: &PgPool, command: &AdminCommand) -> Result<()> {
+    match command {
+        AdminCommand::Add { email, role } => {
+            let mut password = String::new();
+            std::io::stdin()
+                .read_line(&mut password)
+                .context("Failed to read the password from stdin")?;
+            let id =
+                upsert_admin(pool, email, role, password.trim_end_matches(['\r', '\n'])).await?;
+            println!(
+                "✅ {} can now sign in to /admin ({:?}, id {})",
+                email, role, id
+            );
+        }
+    }
+    Ok(())
+}
+
+/// 🔑 Carry out an apikey subcommand; the new key is printed once and never again
+pub async fn run_api_key(pool: &PgPool, command: &ApiKeyCommand) -> Result<()> {
+    match command {
+        ApiKeyCommand::Add {
+            name,
+            scopes,
+            allowed_repos,
+        } => {
+            if let Some(repo) = allowed_repos
+                .iter()
+                .flatten()
+                .find(|repo| repo.split('/').count() != 2)
+            {
+                anyhow::bail!("{} is not an owner/repo", repo);
+            }
+            let (key, secret) =
+                ApiKey::create(pool, name, scopes, allowed_repos.as_deref(), None).await?;
+            println!(
+                "✅ API key {} ({}) created - store it now, it isn't shown again:",
+                key.name, key.id
+            );
+            println!("{}", secret);
+        }
+        ApiKeyCommand::Revoke { id } => {
+            if !ApiKey::revoke(pool, *id).await? {
+                anyhow::bail!("No unrevoked API key with id {}", id);
+            }
+            println!("✅ API key {} revoked", id);
+        }
+    }
+    Ok(())
+}
+
+/// ➕ Create or update an admin console account with a freshly hashed password
+async fn upsert_admin(
+    pool: &PgPool,
+    email: &str,
+    role: &UserRole,
+    password: &str,
+) -> Result<uuid::Uuid> {
+    if password.is_empty() {
+        anyhow::bail!("Refusing to set an empty password");
+    }
+    let hash = crate::auth::password::hash_password(password)?;
+    let name = email.split('@').next().unwrap_or(email);
+    sqlx::query_scalar(
+        r#"
+        INSERT INTO users (email, name, password_hash, email_verified, role, is_active)
+        VALUES ($1, $2, $3, true, $4, true)
+        ON CONFLICT (email) DO UPDATE SET
+            password_hash = EXCLUDED.password_hash,
+            role = EXCLUDED.role,
+            is_active = true,
+            updated_at = NOW()
+        RETURNING id
+        "#,
+    )
+    .bind(email)
+    .bind(name)
+    .bind(hash)
+    .bind(role)
+    .fetch_one(pool)
+    .await
+    .context("Failed to save admin account")
+}
+
+/// 🔍 The most recently applied migration (the last one in apply order)
+async fn latest_applied(pool: &PgPool) -> Result<Option<String>> {
+    Ok(migrations::migration_status(pool)
+        .await?
+        .into_iter()
+        .rev()
+        .find(|state| state.applied_at.is_some())
+        .map(|state| state.id))
+}
+
+// 🧪 Tests - Typing commands so operators don't have to guess!
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(line: &str) -> Vec<String> {
+        line.split_whitespace().map(str::to_string).collect()
+    }
+
+    #[test]
+    fn test_argument_parsing() {
+        assert_eq!(parse_args(&args("")).unwrap(), Command::Serve);
+        assert_eq!(parse_args(&args("--seed")).unwrap(), Command::Serve);
+        assert_eq!(
+            parse_args(&args("--fail-fast --seed")).unwrap(),
+            Command::Serve
+        );
+        assert_eq!(
+            parse_args(&args("migrate status")).unwrap(),
+            Command::Migrate(MigrateCommand::Status)
+        );
+        assert_eq!(
+            parse_args(&args("migrate rollback v9_feedback_tags")).unwrap(),
+            Command::Migrate(MigrateCommand::Rollback(Some(
+                "v9_feedback_tags".to_string()
+            )))
+        );
+        assert_eq!(
+            parse_args(&args("migrate rollback")).unwrap(),
+            Command::Migrate(MigrateCommand::Rollback(None))
+        );
+        assert!(parse_args(&args("migrate")).is_err());
+        assert!(parse_args(&args("migrate sideways")).is_err());
+        assert!(parse_args(&args("serve-please")).is_err());
+        assert!(parse_args(&args("migrate run now")).is_err());
+        assert_eq!(
+            parse_args(&args("admin add ops@example.com")).unwrap(),
+            Command::Admin(AdminCommand::Add {
+                email: "ops@example.com".to_string(),
+                role: UserRole::Admin
+            })
+        );
+        assert_eq!(
+            parse_args(&args("admin add bot@example.com service")).unwrap(),
+            Command::Admin(AdminCommand::Add {
+                email: "bot@example.com".to_string(),
+                role: UserRole::Service
+            })
+        );
+        assert!(parse_args(&args("admin add")).is_err());
+        assert!(parse_args(&args("admin add ops@example.com user")).is_err());
+        assert_eq!(
+            parse_args(&args(
+                "apikey add issue-bot issues:write 8b-is/smart-tree,8b-is/mem8"
+            ))
+            .unwrap(),
+            Command::ApiKey(ApiKeyCommand::Add {
+                name: "issue-bot".to_string(),
+                scopes: vec!["issues:write".to_string()],
+                allowed_repos: Some(vec![
+                    "8b-is/smart-tree".to_string(),
+                    "8b-is/mem8".to_string()
+                ]),
+            })
+        );
+        assert!(matches!(
+            parse_args(&args("apikey add issue-bot issues:write")).unwrap(),
+            Command::ApiKey(ApiKeyCommand::Add {
+                allowed_repos: None,
+                ..
+            })
+        ));
+        assert!(parse_args(&args("apikey add issue-bot")).is_err());
+        assert_eq!(
+            parse_args(&args("apikey revoke 6f1c2d3e-4a5b-4c6d-8e7f-8091a2b3c4d5")).unwrap(),
+            Command::ApiKey(ApiKeyCommand::Revoke {
+                id: "6f1c2d3e-4a5b-4c6d-8e7f-8091a2b3c4d5".parse().unwrap()
+            })
+        );
+        assert!(parse_args(&args("apikey revoke")).is_err());
+        assert!(parse_args(&args("apikey revoke fbk_1234")).is_err());
+        println!("✅ CLI argument parsing test passed!");
+    }
+
+    #[tokio::test]
+    async fn test_pending_migrations_block_startup_until_migrated() {
+        let Some(app) = crate::test_support::spawn_test_app().await else {
+            return;
+        };
+        let pool = &app.db_pool;
+        database::ensure_no_pending_migrations(pool).await.unwrap();
+
+        // 🔙 Roll back the newest migration: the schema is now one behind
+        let latest = latest_applied(pool).await.unwrap().unwrap();
+        assert_eq!(
+            Some(latest.as_str()),
+            migrations::get_all_migrations()
+                .last()
+                .map(|m| m.id.as_str())
+        );
+        run_migrate(pool, &MigrateCommand::Rollback(None))
+            .await
+            .unwrap();
+
+        let error = database::ensure_no_pending_migrations(pool)
+            .await
+            .unwrap_err()
+            .to_string();
+        assert!(error.contains("1 pending migrations"));
+        assert!(error.contains(&latest));
+        let status = migrations::migration_status(pool).await.unwrap();
+        assert!(status.iter().any(|state| state.id == latest
+            && state.pending
+            && state.applied_at.is_none()
+            && state.checksum_matches.is_none()));
+
+        // 🔙 Rolling back something that isn't applied is an error, not a no-op
+        assert!(migrations::rollback_migration(pool, &latest).await.is_err());
+
+        run_migrate(pool, &MigrateCommand::Plan).await.unwrap();
+        run_migrate(pool, &MigrateCommand::Run).await.unwrap();
+        database::ensure_no_pending_migrations(pool).await.unwrap();
+        println!("✅ Pending migration startup guard test passed!");
+    }
+}
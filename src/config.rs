@@ -29,6 +29,22 @@ pub struct Config {
     pub logging: LoggingConfig,
     /// 🔧 Feature flags and toggles
     pub features: FeaturesConfig,
+    /// 🧪 Startup self-test configuration
+    pub self_test: SelfTestConfig,
+    /// 📝 Feedback intake limits
+    pub feedback: FeedbackConfig,
+    /// 📊 Analytics privacy settings
+    pub analytics: AnalyticsConfig,
+    /// 📦 Where /mcp/check points clients for downloads
+    pub downloads: DownloadsConfig,
+    /// 📎 Where feedback attachments are stored, and what is accepted
+    pub attachments: AttachmentsConfig,
+    /// 🐛 Filing our own bugs from failed feedback runs
+    pub self_issues: SelfIssuesConfig,
+    /// 🗃️ Archiving and removing old completed feedback
+    pub retention: RetentionConfig,
+    /// 🧩 What anonymous submissions must solve first
+    pub challenge: ChallengeConfig,
 }
 
 // 🌐 Server configuration - Where we listen and how we behave
@@ -40,8 +56,16 @@ pub struct ServerConfig {
     pub timeout_seconds: u64,
     /// 📏 Maximum request body size in bytes
     pub max_body_size: usize,
+    /// 🗜️ Minimum response size in bytes before compression kicks in
+    pub compression_min_bytes: u16,
     /// 🌍 Environment (development, staging, production)
     pub environment: Environment,
+    /// 🔗 Where clients reach us (scheme, host and any path prefix, no trailing slash),
+    /// used for the absolute links in API responses
+    pub public_base_url: String,
+    /// 🛡️ Reverse proxies whose X-Forwarded-For / X-Real-IP headers are believed
+    /// (empty = the connection's address is always the client)
+    pub trusted_proxies: Vec<crate::utils::client_ip::ProxyRange>,
 }
 
 // 🗄️ Database configuration - Our data storage settings
@@ -53,8 +77,10 @@ pub struct DatabaseConfig {
     pub max_connections: u32,
     /// ⏱️ Connection timeout in seconds
     pub connection_timeout_seconds: u64,
-    /// 🔄 Enable automatic migrations
+    /// 🔄 Apply pending migrations at startup (AUTO_MIGRATE, on by default in development only)
     pub auto_migrate: bool,
+    /// ⏳ How long startup keeps retrying while the database isn't reachable yet (0 = one try)
+    pub startup_timeout_seconds: u64,
 }
 
 // 🐙 GitHub configuration - Settings for the legendary aye-is user!
@@ -72,6 +98,46 @@ pub struct GitHubConfig {
     pub default_commit_message: String,
     /// 🌿 Default branch name for new branches
     pub default_branch_prefix: String,
+    /// ✍️ Signature appended to bot comments (None = no footer)
+    pub comment_footer: Option<String>,
+    /// ⏲️ Bot comments on one issue closer together than this are folded into the
+    /// previous comment or dropped, per `comment_cooldown_mode` (0 = no cooldown)
+    pub comment_cooldown_seconds: u64,
+    /// 🧺 What happens to a bot comment inside the cooldown
+    pub comment_cooldown_mode: CommentCooldownMode,
+    /// 📝 Template for generated PR bodies (None = github::pr_body::DEFAULT_PR_BODY_TEMPLATE)
+    pub pr_body_template: Option<String>,
+    /// 🔏 Secret GitHub signs webhook deliveries with (None = every delivery is refused,
+    /// unless `allow_unsigned_webhooks`)
+    pub webhook_secret: Option<String>,
+    /// 🚨 Accept unverified deliveries while no webhook secret is set (local development only)
+    pub allow_unsigned_webhooks: bool,
+    /// 🔏 The secret it replaced, still accepted until the overlap window closes
+    pub webhook_secret_previous: Option<String>,
+    /// ⏰ When the webhook secret was rotated (starts the overlap window)
+    pub webhook_secret_rotated_at: Option<chrono::DateTime<chrono::Utc>>,
+    /// 🕰️ How far a signed delivery's timestamp may be from our clock (0 = not checked)
+    pub webhook_max_skew_seconds: u64,
+    /// 🧠 Recently seen delivery bodies (by hash) remembered in memory for replay checks
+    pub webhook_replay_cache_size: usize,
+    /// 🚰 Comment/label/assign/close calls allowed per minute per installation (also the burst size)
+    pub writes_per_minute: u32,
+    /// ⏳ Longest a write is delayed in place before it is deferred to the job queue
+    pub write_max_wait_seconds: u64,
+    /// 📣 How long writes must stay throttled before admins are notified
+    pub write_saturation_alert_seconds: u64,
+    /// 🧊 How long every GitHub call pauses after a secondary rate limit response
+    pub secondary_limit_cooldown_seconds: u64,
+    /// 🎟️ GitHub requests allowed in flight at once; further calls queue for a slot
+    pub max_concurrent_requests: usize,
+    /// 🎯 `event.action` pairs the issue webhook acts on (`event.*` takes every action);
+    /// other deliveries are acknowledged and dropped before any work
+    pub webhook_events: Vec<String>,
+    /// 🚦 How the commit stage reports progress on the branch's head commit
+    pub status_reporting: StatusReporting,
+    /// 🎫 Issues an API key may create through POST /api/issues per UTC day, unless
+    /// the key has its own quota
+    pub issue_relay_daily_quota: u32,
 }
 
 // 🤖 LLM configuration - Settings for all our AI friends!
@@ -85,8 +151,14 @@ pub struct LlmConfig {
     pub default_provider: LlmProvider,
     /// ⏱️ Request timeout in seconds
     pub timeout_seconds: u64,
+    /// 🌊 Longest gap between streamed chunks before a streaming call gives up
+    pub stream_idle_timeout_seconds: u64,
     /// 🔄 Maximum retry attempts
     pub max_retries: u32,
+    /// 🩺 Success rate (0-1) over the last 24h below which the default provider is unhealthy
+    pub health_success_floor: f64,
+    /// 🔀 Switch the default to a healthier provider instead of only suggesting it
+    pub auto_switch: bool,
 }
 
 // 🧠 OpenAI specific configuration
@@ -128,6 +200,8 @@ pub struct AuthConfig {
     pub admin_username: String,
     /// 🔧 Admin password (from ADMIN_PASSWORD env)
     pub admin_password: String,
+    /// 🔏 Hours a rotated webhook or callback secret keeps working alongside its replacement
+    pub secret_overlap_hours: u64,
 }
 
 // 🚦 Rate limiting configuration
@@ -141,6 +215,10 @@ pub struct RateLimitConfig {
     pub burst_size: u32,
     /// ⏱️ Rate limit window in seconds
     pub window_seconds: u64,
+    /// 🌲 Smart Tree version checks per minute, per client IP
+    pub mcp_checks_per_minute: u32,
+    /// 🧹 How often expired per-IP windows are evicted from memory
+    pub sweep_interval_seconds: u64,
 }
 
 // 📧 Email configuration (optional feature)
@@ -165,12 +243,15 @@ pub struct EmailConfig {
 pub struct LoggingConfig {
     /// 📈 Log level (trace, debug, info, warn, error)
     pub level: String,
-    /// 📄 Log format (json, pretty, compact)
-    pub format: String,
+    /// 📄 Log format: human-readable text, or one JSON object per line for aggregators
+    pub format: LogFormat,
     /// 📁 Log file path (optional)
     pub file_path: Option<String>,
     /// 🔄 Enable request logging
     pub log_requests: bool,
+    /// 🎲 High-volume handlers (/mcp/check, health probes) log 1 in N requests (1 = all).
+    /// Errors are always logged, and debug builds ignore this.
+    pub sample_every: u64,
 }
 
 // 🔧 Feature flags configuration
@@ -188,6 +269,279 @@ pub struct FeaturesConfig {
     pub enable_metrics: bool,
     /// 🧪 Enable development features
     pub enable_dev_features: bool,
+    /// 🔤 Default label style for rendered pages (admins can override it per browser)
+    pub label_style: LabelStyle,
+}
+
+// 🧪 Startup self-test configuration - Prove the wiring works before taking traffic!
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SelfTestConfig {
+    /// 🧪 Run the dry-run self-test at startup (SELF_TEST=1)
+    pub enabled: bool,
+    /// 🛑 Refuse to start when a stage fails (otherwise just warn)
+    pub strict: bool,
+    /// 🏖️ Sandbox repository (owner/repo) used for the GitHub dry-run stage
+    pub sandbox_repository: Option<String>,
+}
+
+// 🐛 Self-issue configuration - Feedbacker reporting its own bugs!
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SelfIssuesConfig {
+    /// 🐛 Open issues for failures that look like Feedbacker bugs (SELF_ISSUES_ENABLED)
+    pub enabled: bool,
+    /// 🎯 Where they go (owner/repo)
+    pub repository: String,
+    /// 🚦 New issues per UTC day at most (repeats only comment, and don't count)
+    pub max_per_day: u32,
+}
+
+// 🗃️ Retention configuration - Keeping the feedback table lean!
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RetentionConfig {
+    /// ⏰ Schedule the nightly retention run (RETENTION_ENABLED)
+    pub enabled: bool,
+    /// 📅 Completed feedback older than this many days leaves the feedback table
+    pub completed_after_days: u32,
+    /// 🗄️ Where removed feedback is copied first
+    pub archive: RetentionArchive,
+    /// 🧪 Only count what would be removed, change nothing
+    pub dry_run: bool,
+    /// 📦 Items removed per transaction
+    pub batch_size: u32,
+}
+
+// 🧩 Anonymous challenge configuration - A little friction for the bots!
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChallengeConfig {
+    /// 🧩 What anonymous submissions must solve (ANON_CHALLENGE, off by default)
+    pub kind: ChallengeKind,
+    /// 🪪 Public site key rendered into the hCaptcha/Turnstile widget
+    pub site_key: Option<String>,
+    /// 🔑 Secret key for the provider's siteverify API
+    pub secret_key: Option<String>,
+    /// 🔗 Siteverify endpoint override (defaults to the provider's own)
+    pub verify_url: Option<String>,
+    /// 💪 Leading zero bits a proof-of-work hash must have
+    pub pow_difficulty: u32,
+    /// ⏳ How long a proof-of-work challenge can be solved and redeemed
+    pub pow_ttl_seconds: u64,
+}
+
+// 🧩 Challenges anonymous submissions can be asked to solve
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum ChallengeKind {
+    /// 🔓 None at all
+    #[default]
+    Off,
+    /// 🧑 An hCaptcha widget
+    HCaptcha,
+    /// ☁️ A Cloudflare Turnstile widget
+    Turnstile,
+    /// 💪 A SHA-256 proof-of-work the browser (or client) solves itself
+    #[serde(rename = "pow")]
+    ProofOfWork,
+}
+
+// 🗄️ Where retention puts feedback before removing it
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum RetentionArchive {
+    /// 🚮 Nowhere - only the rollup counts remain
+    None,
+    /// 🗃️ The `feedback_archive` table
+    Table,
+    /// 🪣 JSON lines in the attachment blob store (ATTACHMENTS_BACKEND)
+    Storage,
+}
+
+// 📝 Feedback intake configuration - Keeping submissions a sensible size!
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FeedbackConfig {
+    /// 📏 Maximum feedback content length in characters
+    pub max_content_length: usize,
+    /// 🧑‍💻 Accept http and internal callback URLs (local development only)
+    pub allow_private_callbacks: bool,
+    /// 🔝 Priority for feedback that arrives without scores or an explicit priority (0-100)
+    pub default_priority: i32,
+    /// 👷 Feedback items processed in parallel (used for queue wait estimates)
+    pub worker_concurrency: u32,
+    /// 🔁 An identical submission from the same user within this many seconds returns
+    /// the first one instead of creating another (0 = off)
+    pub dedup_window_seconds: u64,
+    /// ✋ Days held changes wait for the project owner before they expire (failed)
+    pub approval_expiry_days: u32,
+    /// 🔒 Seconds a processing, generating_changes or creating_pull_request item may go
+    /// untouched (with no live job for it) before it counts as abandoned by a crash
+    pub lease_timeout_seconds: u64,
+    /// 🔁 Times an abandoned item is put back to pending before it is failed instead
+    pub max_reconcile_resets: u32,
+}
+
+// 📊 Analytics configuration - Coarse numbers without hoarding PII!
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AnalyticsConfig {
+    /// 🕶️ How client IPs are stored (geo lookup always sees the full IP first)
+    pub ip_storage: IpStorageMode,
+    /// 🧂 Salt for `hash` mode
+    pub ip_hash_salt: Option<String>,
+    /// 🤝 User-Agent prefixes (lowercase) whose /mcp/check calls are logged; other
+    /// checks still get an answer but leave no analytics (empty = log every check)
+    pub mcp_trusted_user_agents: Vec<String>,
+}
+
+/// 🎯 Issue webhook deliveries the automation handles
+pub const DEFAULT_WEBHOOK_EVENTS: [&str; 5] = [
+    "issues.opened",
+    "issues.closed",
+    "issues.labeled",
+    "issues.assigned",
+    "issue_comment.created",
+];
+
+/// 🌳 Where Smart Tree releases are published
+const DEFAULT_RELEASE_REPOSITORY: &str = "8b-is/smart-tree";
+
+// 📦 Download configuration - GitHub releases unless the operator hosts the artifacts!
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DownloadsConfig {
+    /// 🪣 Base URL of the operator's artifact storage (CDN or bucket)
+    pub artifact_base_url: Option<String>,
+    /// 🔏 HMAC secret the storage uses to check download signatures
+    pub signing_secret: Option<String>,
+    /// ⏳ How long a signed download URL stays valid
+    pub url_ttl_seconds: u64,
+    /// 🎯 `product/platform` pairs served from the artifact storage (`*` matches any)
+    pub signed_targets: Vec<String>,
+    /// 🌳 Products /mcp/check answers for; anything else gets `supported: false`
+    pub products: Vec<String>,
+    /// 💬 Message for checks about other products (`{product}` is replaced)
+    pub unknown_product_message: Option<String>,
+    /// 🐙 "owner/repo" whose GitHub releases back the published Smart Tree versions
+    pub release_repository: String,
+    /// 🔎 Check a version against its GitHub release before /mcp/version publishes it
+    /// (MCP_VERIFY_RELEASES, on by default in production)
+    pub verify_releases: bool,
+}
+
+impl Default for DownloadsConfig {
+    fn default() -> Self {
+        Self {
+            artifact_base_url: None,
+            signing_secret: None,
+            url_ttl_seconds: 900,
+            signed_targets: Vec::new(),
+            products: vec![crate::api::downloads::DEFAULT_PRODUCT.to_string()],
+            unknown_product_message: None,
+            release_repository: DEFAULT_RELEASE_REPOSITORY.to_string(),
+            verify_releases: false,
+        }
+    }
+}
+
+// 📎 Attachment configuration - Logs and screenshots that don't fit in a code string!
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AttachmentsConfig {
+    /// 🗄️ Where the blobs go (metadata always lives in the database)
+    pub backend: AttachmentBackend,
+    /// 📁 Directory for the `local` backend
+    pub local_dir: String,
+    /// 📏 Largest accepted attachment in bytes
+    pub max_bytes: usize,
+    /// 🧾 Accepted content types (`image/*` matches any image)
+    pub allowed_content_types: Vec<String>,
+    /// 🪣 Bucket settings for the `s3` backend
+    pub s3: Option<S3Config>,
+}
+
+// 🪣 S3-compatible bucket (AWS, MinIO, R2, ...) for attachments
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct S3Config {
+    /// 🌐 Endpoint, e.g. https://s3.eu-west-1.amazonaws.com (requests are path-style)
+    pub endpoint: String,
+    pub bucket: String,
+    pub region: String,
+    pub access_key_id: String,
+    pub secret_access_key: String,
+}
+
+// 🗄️ Attachment storage backends
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum AttachmentBackend {
+    /// 📁 A directory on this machine
+    Local,
+    /// 🪣 An S3-compatible bucket
+    S3,
+}
+
+// 📄 Log output formats
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum LogFormat {
+    /// 🌈 Human-readable lines (development)
+    #[default]
+    Pretty,
+    /// 🧾 One JSON object per line, with oversized fields truncated (production)
+    Json,
+}
+
+// 🕶️ IP storage modes for analytics rows
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum IpStorageMode {
+    /// 📍 Store the address as received
+    Full,
+    /// ✂️ Keep only the network: /24 for IPv4, /48 for IPv6
+    Truncate,
+    /// #️⃣ Store a salted hash instead of the address
+    Hash,
+    /// 🚫 Don't store anything
+    None,
+}
+
+// 🔤 How decorative emoji in page labels are rendered
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum LabelStyle {
+    /// 🎨 Keep them, hidden from screen readers
+    #[default]
+    Emoji,
+    /// 📄 Strip them for a plain-text UI
+    Plain,
+}
+
+impl LabelStyle {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            LabelStyle::Emoji => "emoji",
+            LabelStyle::Plain => "plain",
+        }
+    }
+}
+
+// 🚦 How pipeline progress shows up on GitHub
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum StatusReporting {
+    /// 🚦 Commit statuses (any token with repo:status)
+    #[default]
+    CommitStatus,
+    /// ✅ Check runs (GitHub App installation tokens only)
+    CheckRun,
+    /// 🔇 Don't report
+    Off,
+}
+
+// ⏲️ What the issue automation does with a comment inside the per-issue cooldown
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum CommentCooldownMode {
+    /// 🧺 Add it to the previous bot comment as a new section
+    #[default]
+    Consolidate,
+    /// 🔇 Don't post it
+    Suppress,
 }
 
 // 🌍 Environment enumeration
@@ -217,9 +571,11 @@ impl Config {
         }
 
         // 🏗️ Build configuration from environment variables
+        let server = ServerConfig::load()?;
+        let downloads = DownloadsConfig::load(&server.environment)?;
         let config = Self {
-            server: ServerConfig::load()?,
-            database: DatabaseConfig::load()?,
+            database: DatabaseConfig::load(&server.environment)?,
+            server,
             github: GitHubConfig::load()?,
             llm: LlmConfig::load()?,
             auth: AuthConfig::load()?,
@@ -227,6 +583,14 @@ impl Config {
             email: EmailConfig::load_optional(),
             logging: LoggingConfig::load()?,
             features: FeaturesConfig::load()?,
+            self_test: SelfTestConfig::load()?,
+            feedback: FeedbackConfig::load()?,
+            analytics: AnalyticsConfig::load()?,
+            downloads,
+            attachments: AttachmentsConfig::load()?,
+            self_issues: SelfIssuesConfig::load()?,
+            retention: RetentionConfig::load()?,
+            challenge: ChallengeConfig::load()?,
         };
 
         // ✅ Validate the configuration
@@ -250,14 +614,181 @@ impl Config {
             anyhow::bail!("GitHub token cannot be empty");
         }
 
+        // 🔗 Links are built by appending paths, so the base must be a plain http(s) URL
+        let base =
+            reqwest::Url::parse(&self.server.public_base_url).context("Invalid PUBLIC_BASE_URL")?;
+        if !matches!(base.scheme(), "http" | "https")
+            || base.query().is_some()
+            || base.fragment().is_some()
+        {
+            anyhow::bail!("PUBLIC_BASE_URL must be an http(s) URL without a query or fragment");
+        }
+
         if self.auth.jwt_secret.len() < 32 {
             anyhow::bail!("JWT secret must be at least 32 characters long");
         }
 
+        // 🔏 A previous webhook secret needs a primary to replace it and a clock to retire it
+        if self.github.webhook_secret_previous.is_some() {
+            if self.github.webhook_secret.is_none() {
+                anyhow::bail!("GITHUB_WEBHOOK_SECRET_PREVIOUS requires GITHUB_WEBHOOK_SECRET");
+            }
+            if self.github.webhook_secret_rotated_at.is_none() {
+                anyhow::bail!(
+                    "GITHUB_WEBHOOK_SECRET_PREVIOUS requires GITHUB_WEBHOOK_SECRET_ROTATED_AT so the overlap can end"
+                );
+            }
+        }
+
+        // 🚨 Anyone could forge deliveries - fine on a laptop, never in production
+        if self.github.allow_unsigned_webhooks && self.is_production() {
+            anyhow::bail!("GITHUB_WEBHOOK_ALLOW_UNSIGNED cannot be enabled in production");
+        }
+
+        // 📝 A PR body template with a typo would only show up on GitHub
+        if let Some(template) = &self.github.pr_body_template {
+            crate::github::pr_body::validate_template(template)
+                .map_err(|reason| anyhow::anyhow!("Invalid GITHUB_PR_BODY_TEMPLATE: {}", reason))?;
+        }
+
         // 🎯 Validate rate limiting values
         if self.rate_limiting.requests_per_minute == 0 {
             anyhow::bail!("Rate limiting requests per minute must be greater than 0");
         }
+        if let Some(event) = self.github.webhook_events.iter().find(|event| {
+            !event
+                .split_once('.')
+                .is_some_and(|(name, action)| !name.is_empty() && !action.is_empty())
+        }) {
+            anyhow::bail!(
+                "Invalid GITHUB_WEBHOOK_EVENTS entry {} (expected event.action, e.g. issues.opened)",
+                event
+            );
+        }
+        if self.github.writes_per_minute == 0 {
+            anyhow::bail!("GITHUB_WRITES_PER_MINUTE must be greater than 0");
+        }
+        if !(0.0..=1.0).contains(&self.llm.health_success_floor) {
+            anyhow::bail!("LLM_HEALTH_SUCCESS_FLOOR must be between 0 and 1");
+        }
+
+        // 📏 The body-size cap is the hard backstop, so it must leave room for the content limit
+        if self.feedback.max_content_length == 0 {
+            anyhow::bail!("FEEDBACK_MAX_CONTENT_LENGTH must be greater than 0");
+        }
+        if self.server.max_body_size <= self.feedback.max_content_length {
+            anyhow::bail!(
+                "SERVER_MAX_BODY_SIZE ({}) must be larger than FEEDBACK_MAX_CONTENT_LENGTH ({})",
+                self.server.max_body_size,
+                self.feedback.max_content_length
+            );
+        }
+
+        // 🛡️ Internal callback targets are an SSRF hole outside local development
+        if self.feedback.allow_private_callbacks && self.is_production() {
+            anyhow::bail!("FEEDBACK_CALLBACK_ALLOW_PRIVATE cannot be enabled in production");
+        }
+
+        // 🔝 Same scale as impact x frequency (1-10 each)
+        if !(0..=100).contains(&self.feedback.default_priority) {
+            anyhow::bail!("FEEDBACK_DEFAULT_PRIORITY must be between 0 and 100");
+        }
+
+        if self.feedback.worker_concurrency == 0 {
+            anyhow::bail!("FEEDBACK_WORKER_CONCURRENCY must be at least 1");
+        }
+
+        if self.feedback.approval_expiry_days == 0 {
+            anyhow::bail!("FEEDBACK_APPROVAL_EXPIRY_DAYS must be at least 1");
+        }
+
+        if self.feedback.lease_timeout_seconds < 60 {
+            anyhow::bail!("FEEDBACK_LEASE_TIMEOUT_SECONDS must be at least 60");
+        }
+
+        let release_repository = self.downloads.release_repository.split_once('/');
+        if !release_repository.is_some_and(|(owner, repo)| !owner.is_empty() && !repo.is_empty()) {
+            anyhow::bail!("MCP_RELEASE_REPOSITORY must look like owner/repo");
+        }
+
+        if self.logging.sample_every == 0 {
+            anyhow::bail!("LOG_SAMPLE_EVERY must be at least 1 (1 logs every request)");
+        }
+
+        // 🧂 An unsalted hash of the IPv4 space is trivially reversible
+        if self.analytics.ip_storage == IpStorageMode::Hash
+            && self.analytics.ip_hash_salt.as_deref().unwrap_or("").len() < 16
+        {
+            anyhow::bail!("ANALYTICS_IP_HASH_SALT must be at least 16 characters when ANALYTICS_IP_STORAGE=hash");
+        }
+
+        // 📦 Signed downloads need somewhere to point and a key the storage shares
+        if !self.downloads.signed_targets.is_empty() {
+            if self.downloads.artifact_base_url.is_none() {
+                anyhow::bail!("ARTIFACT_SIGNED_TARGETS requires ARTIFACT_BASE_URL");
+            }
+            if self.downloads.signing_secret.as_deref().unwrap_or("").len() < 32 {
+                anyhow::bail!(
+                    "ARTIFACT_SIGNING_SECRET must be at least 32 characters when ARTIFACT_SIGNED_TARGETS is set"
+                );
+            }
+            if self.downloads.url_ttl_seconds == 0 {
+                anyhow::bail!("ARTIFACT_URL_TTL_SECONDS must be greater than 0");
+            }
+        }
+
+        // 🐛 Self-issues need a repository to go to and room for at least one a day
+        if self.self_issues.enabled {
+            if self.self_issues.repository.split('/').count() != 2 {
+                anyhow::bail!("SELF_ISSUES_REPOSITORY must be in 'owner/repo' format");
+            }
+            if self.self_issues.max_per_day == 0 {
+                anyhow::bail!(
+                    "SELF_ISSUES_MAX_PER_DAY must be at least 1 when SELF_ISSUES_ENABLED is set"
+                );
+            }
+        }
+
+        // 🗃️ Retention needs a positive age and batch
+        if self.retention.completed_after_days == 0 {
+            anyhow::bail!("RETENTION_COMPLETED_AFTER_DAYS must be at least 1");
+        }
+        if self.retention.batch_size == 0 {
+            anyhow::bail!("RETENTION_BATCH_SIZE must be greater than 0");
+        }
+
+        // 🧩 Captchas need their keys, proof-of-work a sensible difficulty
+        if matches!(
+            self.challenge.kind,
+            ChallengeKind::HCaptcha | ChallengeKind::Turnstile
+        ) && (self.challenge.site_key.is_none() || self.challenge.secret_key.is_none())
+        {
+            anyhow::bail!(
+                "ANON_CHALLENGE={} requires CAPTCHA_SITE_KEY and CAPTCHA_SECRET_KEY",
+                self.challenge.kind
+            );
+        }
+        if self.challenge.kind == ChallengeKind::ProofOfWork {
+            if !(1..=32).contains(&self.challenge.pow_difficulty) {
+                anyhow::bail!("POW_DIFFICULTY must be between 1 and 32");
+            }
+            if self.challenge.pow_ttl_seconds == 0 {
+                anyhow::bail!("POW_TTL_SECONDS must be greater than 0");
+            }
+        }
+
+        // 📎 Attachments need a size cap, something to accept, and a bucket for s3
+        if self.attachments.max_bytes == 0 {
+            anyhow::bail!("ATTACHMENTS_MAX_BYTES must be greater than 0");
+        }
+        if self.attachments.allowed_content_types.is_empty() {
+            anyhow::bail!("ATTACHMENTS_ALLOWED_TYPES cannot be empty");
+        }
+        if self.attachments.backend == AttachmentBackend::S3 && self.attachments.s3.is_none() {
+            anyhow::bail!(
+                "ATTACHMENTS_BACKEND=s3 requires ATTACHMENTS_S3_ENDPOINT, ATTACHMENTS_S3_BUCKET, ATTACHMENTS_S3_ACCESS_KEY_ID and ATTACHMENTS_S3_SECRET_ACCESS_KEY"
+            );
+        }
 
         // ✅ All validations passed!
         Ok(())
@@ -272,12 +803,48 @@ impl Config {
     pub fn is_production(&self) -> bool {
         self.server.environment == Environment::Production
     }
+
+    /// ⏳ How long a rotated-out secret keeps verifying
+    pub fn secret_overlap(&self) -> chrono::Duration {
+        chrono::Duration::hours(self.auth.secret_overlap_hours as i64)
+    }
+
+    /// 🕰️ Allowed skew of signed webhook deliveries (None = timestamps aren't checked)
+    pub fn webhook_max_skew(&self) -> Option<chrono::Duration> {
+        match self.github.webhook_max_skew_seconds {
+            0 => None,
+            secs => Some(chrono::Duration::seconds(secs as i64)),
+        }
+    }
+
+    /// 🔗 Absolute URL for `path` (which starts with '/') under PUBLIC_BASE_URL
+    pub fn public_url(&self, path: &str) -> String {
+        format!("{}{}", self.server.public_base_url, path)
+    }
+
+    /// 🔏 GitHub webhook secrets to verify against (None = no secret is set)
+    pub fn github_webhook_secrets(&self) -> Option<crate::utils::signatures::SecretPair> {
+        let primary = self.github.webhook_secret.clone()?;
+        Some(crate::utils::signatures::SecretPair {
+            primary,
+            previous: self.github.webhook_secret_previous.clone(),
+            previous_expires_at: self
+                .github
+                .webhook_secret_rotated_at
+                .map(|rotated_at| rotated_at + self.secret_overlap()),
+        })
+    }
 }
 
 impl ServerConfig {
     fn load() -> Result<Self> {
+        let address = env::var("SERVER_ADDRESS").unwrap_or_else(|_| "127.0.0.1:3000".to_string());
         Ok(Self {
-            address: env::var("SERVER_ADDRESS").unwrap_or_else(|_| "127.0.0.1:3000".to_string()),
+            public_base_url: env::var("PUBLIC_BASE_URL")
+                .unwrap_or_else(|_| format!("http://{}", address))
+                .trim_end_matches('/')
+                .to_string(),
+            address,
             timeout_seconds: env::var("SERVER_TIMEOUT_SECONDS")
                 .unwrap_or_else(|_| "30".to_string())
                 .parse()
@@ -286,16 +853,27 @@ impl ServerConfig {
                 .unwrap_or_else(|_| "1048576".to_string()) // 1MB default
                 .parse()
                 .context("Invalid SERVER_MAX_BODY_SIZE")?,
+            compression_min_bytes: env::var("SERVER_COMPRESSION_MIN_BYTES")
+                .unwrap_or_else(|_| "1024".to_string())
+                .parse()
+                .context("Invalid SERVER_COMPRESSION_MIN_BYTES")?,
             environment: env::var("ENVIRONMENT")
                 .unwrap_or_else(|_| "development".to_string())
                 .parse()
                 .unwrap_or(Environment::Development),
+            trusted_proxies: env::var("TRUSTED_PROXIES")
+                .unwrap_or_default()
+                .split(',')
+                .filter(|proxy| !proxy.trim().is_empty())
+                .map(str::parse)
+                .collect::<Result<_>>()
+                .context("Invalid TRUSTED_PROXIES")?,
         })
     }
 }
 
 impl DatabaseConfig {
-    fn load() -> Result<Self> {
+    fn load(environment: &Environment) -> Result<Self> {
         Ok(Self {
             url: env::var("DATABASE_URL")
                 .context("DATABASE_URL environment variable is required")?,
@@ -307,12 +885,24 @@ impl DatabaseConfig {
                 .unwrap_or_else(|_| "30".to_string())
                 .parse()
                 .context("Invalid DATABASE_CONNECTION_TIMEOUT_SECONDS")?,
-            auto_migrate: env::var("DATABASE_AUTO_MIGRATE")
-                .unwrap_or_else(|_| "true".to_string())
+            // 🔄 DATABASE_AUTO_MIGRATE is the older spelling, still honoured
+            auto_migrate: match env::var("AUTO_MIGRATE")
+                .or_else(|_| env::var("DATABASE_AUTO_MIGRATE"))
+            {
+                Ok(value) => parse_flag(&value).context("Invalid AUTO_MIGRATE")?,
+                Err(_) => Self::default_auto_migrate(environment),
+            },
+            startup_timeout_seconds: env::var("DATABASE_STARTUP_TIMEOUT_SECONDS")
+                .unwrap_or_else(|_| "60".to_string())
                 .parse()
-                .context("Invalid DATABASE_AUTO_MIGRATE")?,
+                .context("Invalid DATABASE_STARTUP_TIMEOUT_SECONDS")?,
         })
     }
+
+    /// 🔄 Schema changes apply themselves in development; elsewhere an operator runs `feedbacker migrate`
+    pub fn default_auto_migrate(environment: &Environment) -> bool {
+        *environment == Environment::Development
+    }
 }
 
 impl GitHubConfig {
@@ -330,10 +920,101 @@ impl GitHubConfig {
                 .unwrap_or_else(|_| "🤖 AI-generated improvement based on user feedback\n\n✨ Generated by Feedbacker with love by Aye & Hue".to_string()),
             default_branch_prefix: env::var("GITHUB_DEFAULT_BRANCH_PREFIX")
                 .unwrap_or_else(|_| "feedbacker/".to_string()),
+            comment_footer: match env::var("GITHUB_COMMENT_FOOTER") {
+                Ok(value) => parse_comment_footer(&value),
+                Err(_) => Some(DEFAULT_COMMENT_FOOTER.to_string()),
+            },
+            comment_cooldown_seconds: env::var("GITHUB_COMMENT_COOLDOWN_SECONDS")
+                .unwrap_or_else(|_| "0".to_string())
+                .parse()
+                .context("Invalid GITHUB_COMMENT_COOLDOWN_SECONDS")?,
+            comment_cooldown_mode: env::var("GITHUB_COMMENT_COOLDOWN_MODE")
+                .unwrap_or_else(|_| "consolidate".to_string())
+                .parse()
+                .context("Invalid GITHUB_COMMENT_COOLDOWN_MODE")?,
+            pr_body_template: optional_env("GITHUB_PR_BODY_TEMPLATE")
+                .map(|template| template.replace("\\n", "\n")),
+            webhook_secret: optional_env("GITHUB_WEBHOOK_SECRET"),
+            allow_unsigned_webhooks: parse_flag(
+                &env::var("GITHUB_WEBHOOK_ALLOW_UNSIGNED").unwrap_or_else(|_| "false".to_string()),
+            )
+            .context("Invalid GITHUB_WEBHOOK_ALLOW_UNSIGNED")?,
+            webhook_secret_previous: optional_env("GITHUB_WEBHOOK_SECRET_PREVIOUS"),
+            webhook_secret_rotated_at: optional_env("GITHUB_WEBHOOK_SECRET_ROTATED_AT")
+                .map(|value| {
+                    chrono::DateTime::parse_from_rfc3339(&value)
+                        .map(|rotated_at| rotated_at.with_timezone(&chrono::Utc))
+                        .context("Invalid GITHUB_WEBHOOK_SECRET_ROTATED_AT (expected RFC 3339)")
+                })
+                .transpose()?,
+            webhook_max_skew_seconds: env::var("GITHUB_WEBHOOK_MAX_SKEW_SECONDS")
+                .unwrap_or_else(|_| "600".to_string())
+                .parse()
+                .context("Invalid GITHUB_WEBHOOK_MAX_SKEW_SECONDS")?,
+            webhook_replay_cache_size: env::var("GITHUB_WEBHOOK_REPLAY_CACHE_SIZE")
+                .unwrap_or_else(|_| "10000".to_string())
+                .parse()
+                .context("Invalid GITHUB_WEBHOOK_REPLAY_CACHE_SIZE")?,
+            writes_per_minute: env::var("GITHUB_WRITES_PER_MINUTE")
+                .unwrap_or_else(|_| "30".to_string())
+                .parse()
+                .context("Invalid GITHUB_WRITES_PER_MINUTE")?,
+            write_max_wait_seconds: env::var("GITHUB_WRITE_MAX_WAIT_SECONDS")
+                .unwrap_or_else(|_| "5".to_string())
+                .parse()
+                .context("Invalid GITHUB_WRITE_MAX_WAIT_SECONDS")?,
+            write_saturation_alert_seconds: env::var("GITHUB_WRITE_SATURATION_ALERT_SECONDS")
+                .unwrap_or_else(|_| "300".to_string())
+                .parse()
+                .context("Invalid GITHUB_WRITE_SATURATION_ALERT_SECONDS")?,
+            secondary_limit_cooldown_seconds: env::var("GITHUB_SECONDARY_LIMIT_COOLDOWN_SECONDS")
+                .unwrap_or_else(|_| "60".to_string())
+                .parse()
+                .context("Invalid GITHUB_SECONDARY_LIMIT_COOLDOWN_SECONDS")?,
+            max_concurrent_requests: env::var("GITHUB_MAX_CONCURRENT_REQUESTS")
+                .unwrap_or_else(|_| "8".to_string())
+                .parse()
+                .context("Invalid GITHUB_MAX_CONCURRENT_REQUESTS")?,
+            webhook_events: env::var("GITHUB_WEBHOOK_EVENTS")
+                .ok()
+                .filter(|events| !events.trim().is_empty())
+                .unwrap_or_else(|| DEFAULT_WEBHOOK_EVENTS.join(","))
+                .split(',')
+                .map(|event| event.trim().to_lowercase())
+                .filter(|event| !event.is_empty())
+                .collect(),
+            status_reporting: env::var("GITHUB_STATUS_REPORTING")
+                .unwrap_or_else(|_| "commit_status".to_string())
+                .parse()
+                .context("Invalid GITHUB_STATUS_REPORTING")?,
+            issue_relay_daily_quota: env::var("GITHUB_ISSUE_RELAY_DAILY_QUOTA")
+                .unwrap_or_else(|_| "20".to_string())
+                .parse()
+                .context("Invalid GITHUB_ISSUE_RELAY_DAILY_QUOTA")?,
         })
     }
 }
 
+/// 🔍 An environment variable that counts as unset when empty
+fn optional_env(name: &str) -> Option<String> {
+    env::var(name)
+        .ok()
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty())
+}
+
+/// ✍️ Footer used on bot comments unless GITHUB_COMMENT_FOOTER says otherwise
+pub const DEFAULT_COMMENT_FOOTER: &str = "*- Aye & Hue*";
+
+/// ✍️ Parse a comment footer setting: empty, "none" or "off" disables the footer
+pub fn parse_comment_footer(value: &str) -> Option<String> {
+    let value = value.trim();
+    match value.to_lowercase().as_str() {
+        "" | "none" | "off" => None,
+        _ => Some(value.replace("\\n", "\n")),
+    }
+}
+
 impl LlmConfig {
     fn load() -> Result<Self> {
         Ok(Self {
@@ -347,10 +1028,22 @@ impl LlmConfig {
                 .unwrap_or_else(|_| "60".to_string())
                 .parse()
                 .context("Invalid LLM_TIMEOUT_SECONDS")?,
+            stream_idle_timeout_seconds: env::var("LLM_STREAM_IDLE_TIMEOUT_SECONDS")
+                .unwrap_or_else(|_| "30".to_string())
+                .parse()
+                .context("Invalid LLM_STREAM_IDLE_TIMEOUT_SECONDS")?,
             max_retries: env::var("LLM_MAX_RETRIES")
                 .unwrap_or_else(|_| "3".to_string())
                 .parse()
                 .context("Invalid LLM_MAX_RETRIES")?,
+            health_success_floor: env::var("LLM_HEALTH_SUCCESS_FLOOR")
+                .unwrap_or_else(|_| "0.8".to_string())
+                .parse()
+                .context("Invalid LLM_HEALTH_SUCCESS_FLOOR")?,
+            auto_switch: env::var("LLM_AUTO_SWITCH")
+                .unwrap_or_else(|_| "false".to_string())
+                .parse()
+                .context("Invalid LLM_AUTO_SWITCH")?,
         })
     }
 }
@@ -405,6 +1098,10 @@ impl AuthConfig {
                 .context("Invalid ENABLE_REGISTRATION")?,
             admin_username: env::var("ADMIN_USERNAME").unwrap_or_else(|_| "admin".to_string()),
             admin_password: env::var("ADMIN_PASSWORD").unwrap_or_else(|_| "".to_string()),
+            secret_overlap_hours: env::var("WEBHOOK_SECRET_OVERLAP_HOURS")
+                .unwrap_or_else(|_| "24".to_string())
+                .parse()
+                .context("Invalid WEBHOOK_SECRET_OVERLAP_HOURS")?,
         })
     }
 }
@@ -428,6 +1125,14 @@ impl RateLimitConfig {
                 .unwrap_or_else(|_| "60".to_string())
                 .parse()
                 .context("Invalid RATE_LIMIT_WINDOW_SECONDS")?,
+            mcp_checks_per_minute: env::var("RATE_LIMIT_MCP_CHECKS_PER_MINUTE")
+                .unwrap_or_else(|_| "30".to_string())
+                .parse()
+                .context("Invalid RATE_LIMIT_MCP_CHECKS_PER_MINUTE")?,
+            sweep_interval_seconds: env::var("RATE_LIMIT_SWEEP_INTERVAL_SECONDS")
+                .unwrap_or_else(|_| "60".to_string())
+                .parse()
+                .context("Invalid RATE_LIMIT_SWEEP_INTERVAL_SECONDS")?,
         })
     }
 }
@@ -457,12 +1162,19 @@ impl LoggingConfig {
     fn load() -> Result<Self> {
         Ok(Self {
             level: env::var("LOG_LEVEL").unwrap_or_else(|_| "info".to_string()),
-            format: env::var("LOG_FORMAT").unwrap_or_else(|_| "pretty".to_string()),
+            format: env::var("LOG_FORMAT")
+                .unwrap_or_else(|_| "pretty".to_string())
+                .parse()
+                .context("Invalid LOG_FORMAT")?,
             file_path: env::var("LOG_FILE_PATH").ok(),
             log_requests: env::var("LOG_REQUESTS")
                 .unwrap_or_else(|_| "true".to_string())
                 .parse()
                 .context("Invalid LOG_REQUESTS")?,
+            sample_every: env::var("LOG_SAMPLE_EVERY")
+                .unwrap_or_else(|_| "1".to_string())
+                .parse()
+                .context("Invalid LOG_SAMPLE_EVERY")?,
         })
     }
 }
@@ -494,10 +1206,265 @@ impl FeaturesConfig {
                 .unwrap_or_else(|_| "false".to_string())
                 .parse()
                 .context("Invalid ENABLE_DEV_FEATURES")?,
+            label_style: env::var("ADMIN_LABEL_STYLE")
+                .unwrap_or_else(|_| "emoji".to_string())
+                .parse()
+                .context("Invalid ADMIN_LABEL_STYLE")?,
+        })
+    }
+}
+
+impl SelfTestConfig {
+    fn load() -> Result<Self> {
+        Ok(Self {
+            enabled: parse_flag(&env::var("SELF_TEST").unwrap_or_else(|_| "false".to_string()))
+                .context("Invalid SELF_TEST")?,
+            strict: parse_flag(
+                &env::var("SELF_TEST_STRICT").unwrap_or_else(|_| "false".to_string()),
+            )
+            .context("Invalid SELF_TEST_STRICT")?,
+            sandbox_repository: env::var("SELF_TEST_SANDBOX_REPO")
+                .ok()
+                .filter(|repo| !repo.is_empty()),
+        })
+    }
+}
+
+impl SelfIssuesConfig {
+    fn load() -> Result<Self> {
+        Ok(Self {
+            enabled: parse_flag(
+                &env::var("SELF_ISSUES_ENABLED").unwrap_or_else(|_| "false".to_string()),
+            )
+            .context("Invalid SELF_ISSUES_ENABLED")?,
+            repository: env::var("SELF_ISSUES_REPOSITORY")
+                .unwrap_or_else(|_| "8b-is/Feedbacker".to_string()),
+            max_per_day: env::var("SELF_ISSUES_MAX_PER_DAY")
+                .unwrap_or_else(|_| "3".to_string())
+                .parse()
+                .context("Invalid SELF_ISSUES_MAX_PER_DAY")?,
+        })
+    }
+}
+
+impl RetentionConfig {
+    fn load() -> Result<Self> {
+        Ok(Self {
+            enabled: parse_flag(
+                &env::var("RETENTION_ENABLED").unwrap_or_else(|_| "false".to_string()),
+            )
+            .context("Invalid RETENTION_ENABLED")?,
+            completed_after_days: env::var("RETENTION_COMPLETED_AFTER_DAYS")
+                .unwrap_or_else(|_| "365".to_string())
+                .parse()
+                .context("Invalid RETENTION_COMPLETED_AFTER_DAYS")?,
+            archive: env::var("RETENTION_ARCHIVE")
+                .unwrap_or_else(|_| "table".to_string())
+                .parse()
+                .context("Invalid RETENTION_ARCHIVE")?,
+            dry_run: parse_flag(
+                &env::var("RETENTION_DRY_RUN").unwrap_or_else(|_| "false".to_string()),
+            )
+            .context("Invalid RETENTION_DRY_RUN")?,
+            batch_size: env::var("RETENTION_BATCH_SIZE")
+                .unwrap_or_else(|_| "500".to_string())
+                .parse()
+                .context("Invalid RETENTION_BATCH_SIZE")?,
         })
     }
 }
 
+impl FeedbackConfig {
+    fn load() -> Result<Self> {
+        Ok(Self {
+            max_content_length: env::var("FEEDBACK_MAX_CONTENT_LENGTH")
+                .unwrap_or_else(|_| "10000".to_string())
+                .parse()
+                .context("Invalid FEEDBACK_MAX_CONTENT_LENGTH")?,
+            allow_private_callbacks: parse_flag(
+                &env::var("FEEDBACK_CALLBACK_ALLOW_PRIVATE")
+                    .unwrap_or_else(|_| "false".to_string()),
+            )
+            .context("Invalid FEEDBACK_CALLBACK_ALLOW_PRIVATE")?,
+            default_priority: env::var("FEEDBACK_DEFAULT_PRIORITY")
+                .unwrap_or_else(|_| "25".to_string())
+                .parse()
+                .context("Invalid FEEDBACK_DEFAULT_PRIORITY")?,
+            worker_concurrency: env::var("FEEDBACK_WORKER_CONCURRENCY")
+                .unwrap_or_else(|_| "1".to_string())
+                .parse()
+                .context("Invalid FEEDBACK_WORKER_CONCURRENCY")?,
+            dedup_window_seconds: env::var("FEEDBACK_DEDUP_WINDOW_SECONDS")
+                .unwrap_or_else(|_| "300".to_string())
+                .parse()
+                .context("Invalid FEEDBACK_DEDUP_WINDOW_SECONDS")?,
+            approval_expiry_days: env::var("FEEDBACK_APPROVAL_EXPIRY_DAYS")
+                .unwrap_or_else(|_| "14".to_string())
+                .parse()
+                .context("Invalid FEEDBACK_APPROVAL_EXPIRY_DAYS")?,
+            lease_timeout_seconds: env::var("FEEDBACK_LEASE_TIMEOUT_SECONDS")
+                .unwrap_or_else(|_| "900".to_string())
+                .parse()
+                .context("Invalid FEEDBACK_LEASE_TIMEOUT_SECONDS")?,
+            max_reconcile_resets: env::var("FEEDBACK_MAX_RECONCILE_RESETS")
+                .unwrap_or_else(|_| "3".to_string())
+                .parse()
+                .context("Invalid FEEDBACK_MAX_RECONCILE_RESETS")?,
+        })
+    }
+}
+
+impl AnalyticsConfig {
+    fn load() -> Result<Self> {
+        Ok(Self {
+            ip_storage: env::var("ANALYTICS_IP_STORAGE")
+                .unwrap_or_else(|_| "full".to_string())
+                .parse()
+                .context("Invalid ANALYTICS_IP_STORAGE")?,
+            ip_hash_salt: env::var("ANALYTICS_IP_HASH_SALT")
+                .ok()
+                .filter(|salt| !salt.is_empty()),
+            mcp_trusted_user_agents: env::var("MCP_TRUSTED_USER_AGENTS")
+                .unwrap_or_default()
+                .split(',')
+                .map(|prefix| prefix.trim().to_lowercase())
+                .filter(|prefix| !prefix.is_empty())
+                .collect(),
+        })
+    }
+
+    /// 🤝 Do checks from this User-Agent count towards analytics?
+    pub fn is_trusted_client(&self, user_agent: Option<&str>) -> bool {
+        if self.mcp_trusted_user_agents.is_empty() {
+            return true;
+        }
+        let user_agent = user_agent.unwrap_or_default().trim().to_lowercase();
+        self.mcp_trusted_user_agents
+            .iter()
+            .any(|prefix| user_agent.starts_with(prefix.as_str()))
+    }
+}
+
+impl DownloadsConfig {
+    fn load(environment: &Environment) -> Result<Self> {
+        Ok(Self {
+            artifact_base_url: env::var("ARTIFACT_BASE_URL")
+                .ok()
+                .map(|url| url.trim_end_matches('/').to_string())
+                .filter(|url| !url.is_empty()),
+            signing_secret: env::var("ARTIFACT_SIGNING_SECRET")
+                .ok()
+                .filter(|secret| !secret.is_empty()),
+            url_ttl_seconds: env::var("ARTIFACT_URL_TTL_SECONDS")
+                .unwrap_or_else(|_| "900".to_string())
+                .parse()
+                .context("Invalid ARTIFACT_URL_TTL_SECONDS")?,
+            signed_targets: env::var("ARTIFACT_SIGNED_TARGETS")
+                .unwrap_or_default()
+                .split(',')
+                .map(|target| target.trim().to_lowercase())
+                .filter(|target| !target.is_empty())
+                .collect(),
+            products: env::var("MCP_PRODUCTS")
+                .unwrap_or_else(|_| crate::api::downloads::DEFAULT_PRODUCT.to_string())
+                .split(',')
+                .map(|product| product.trim().to_lowercase())
+                .filter(|product| !product.is_empty())
+                .collect(),
+            unknown_product_message: env::var("MCP_UNKNOWN_PRODUCT_MESSAGE")
+                .ok()
+                .filter(|message| !message.trim().is_empty()),
+            release_repository: env::var("MCP_RELEASE_REPOSITORY")
+                .ok()
+                .map(|repository| repository.trim().to_string())
+                .filter(|repository| !repository.is_empty())
+                .unwrap_or_else(|| DEFAULT_RELEASE_REPOSITORY.to_string()),
+            verify_releases: match env::var("MCP_VERIFY_RELEASES") {
+                Ok(value) if !value.trim().is_empty() => {
+                    parse_flag(&value).context("Invalid MCP_VERIFY_RELEASES")?
+                }
+                _ => *environment == Environment::Production,
+            },
+        })
+    }
+}
+
+impl AttachmentsConfig {
+    fn load() -> Result<Self> {
+        Ok(Self {
+            backend: env::var("ATTACHMENTS_BACKEND")
+                .unwrap_or_else(|_| "local".to_string())
+                .parse()
+                .context("Invalid ATTACHMENTS_BACKEND")?,
+            local_dir: env::var("ATTACHMENTS_DIR")
+                .ok()
+                .filter(|dir| !dir.is_empty())
+                .unwrap_or_else(|| "./data/attachments".to_string()),
+            max_bytes: env::var("ATTACHMENTS_MAX_BYTES")
+                .unwrap_or_else(|_| "5242880".to_string()) // 5MB default
+                .parse()
+                .context("Invalid ATTACHMENTS_MAX_BYTES")?,
+            allowed_content_types: env::var("ATTACHMENTS_ALLOWED_TYPES")
+                .unwrap_or_else(|_| {
+                    "text/plain,application/json,application/gzip,application/zip,image/png,image/jpeg,image/gif,image/webp".to_string()
+                })
+                .split(',')
+                .map(|content_type| content_type.trim().to_lowercase())
+                .filter(|content_type| !content_type.is_empty())
+                .collect(),
+            s3: S3Config::load_optional(),
+        })
+    }
+}
+
+impl S3Config {
+    /// 🪣 All of endpoint, bucket and credentials, or None
+    fn load_optional() -> Option<Self> {
+        let var = |name: &str| env::var(name).ok().filter(|value| !value.is_empty());
+        Some(Self {
+            endpoint: var("ATTACHMENTS_S3_ENDPOINT")?
+                .trim_end_matches('/')
+                .to_string(),
+            bucket: var("ATTACHMENTS_S3_BUCKET")?,
+            region: var("ATTACHMENTS_S3_REGION").unwrap_or_else(|| "us-east-1".to_string()),
+            access_key_id: var("ATTACHMENTS_S3_ACCESS_KEY_ID")?,
+            secret_access_key: var("ATTACHMENTS_S3_SECRET_ACCESS_KEY")?,
+        })
+    }
+}
+
+impl ChallengeConfig {
+    fn load() -> Result<Self> {
+        let optional = |name: &str| env::var(name).ok().filter(|value| !value.trim().is_empty());
+        Ok(Self {
+            kind: env::var("ANON_CHALLENGE")
+                .unwrap_or_else(|_| "off".to_string())
+                .parse()
+                .context("Invalid ANON_CHALLENGE")?,
+            site_key: optional("CAPTCHA_SITE_KEY"),
+            secret_key: optional("CAPTCHA_SECRET_KEY"),
+            verify_url: optional("CAPTCHA_VERIFY_URL"),
+            pow_difficulty: env::var("POW_DIFFICULTY")
+                .unwrap_or_else(|_| "18".to_string())
+                .parse()
+                .context("Invalid POW_DIFFICULTY")?,
+            pow_ttl_seconds: env::var("POW_TTL_SECONDS")
+                .unwrap_or_else(|_| "600".to_string())
+                .parse()
+                .context("Invalid POW_TTL_SECONDS")?,
+        })
+    }
+}
+
+/// 🚩 Parse a boolean flag that may be written as 1/0, true/false, yes/no or on/off
+fn parse_flag(value: &str) -> Result<bool> {
+    match value.trim().to_lowercase().as_str() {
+        "1" | "true" | "yes" | "on" => Ok(true),
+        "0" | "false" | "no" | "off" | "" => Ok(false),
+        other => anyhow::bail!("Invalid boolean flag: {}", other),
+    }
+}
+
 // 🎯 Implement string parsing for enums
 impl std::str::FromStr for Environment {
     type Err = anyhow::Error;
@@ -512,6 +1479,152 @@ impl std::str::FromStr for Environment {
     }
 }
 
+impl std::str::FromStr for LogFormat {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "pretty" | "text" => Ok(LogFormat::Pretty),
+            "json" => Ok(LogFormat::Json),
+            _ => anyhow::bail!("Invalid log format: {} (expected pretty or json)", s),
+        }
+    }
+}
+
+impl std::str::FromStr for AttachmentBackend {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "local" | "filesystem" | "fs" => Ok(AttachmentBackend::Local),
+            "s3" => Ok(AttachmentBackend::S3),
+            _ => anyhow::bail!("Invalid attachment backend: {}", s),
+        }
+    }
+}
+
+impl std::str::FromStr for ChallengeKind {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "off" | "none" | "" => Ok(ChallengeKind::Off),
+            "hcaptcha" => Ok(ChallengeKind::HCaptcha),
+            "turnstile" => Ok(ChallengeKind::Turnstile),
+            "pow" | "proof-of-work" | "proof_of_work" => Ok(ChallengeKind::ProofOfWork),
+            _ => anyhow::bail!(
+                "Invalid anonymous challenge: {} (expected off, hcaptcha, turnstile or pow)",
+                s
+            ),
+        }
+    }
+}
+
+impl std::fmt::Display for ChallengeKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            ChallengeKind::Off => "off",
+            ChallengeKind::HCaptcha => "hcaptcha",
+            ChallengeKind::Turnstile => "turnstile",
+            ChallengeKind::ProofOfWork => "pow",
+        })
+    }
+}
+
+impl std::str::FromStr for RetentionArchive {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "none" | "off" => Ok(RetentionArchive::None),
+            "table" | "database" => Ok(RetentionArchive::Table),
+            "storage" | "blob" | "s3" => Ok(RetentionArchive::Storage),
+            _ => anyhow::bail!(
+                "Invalid retention archive: {} (expected none, table or storage)",
+                s
+            ),
+        }
+    }
+}
+
+impl std::str::FromStr for IpStorageMode {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "full" | "raw" => Ok(IpStorageMode::Full),
+            "truncate" | "truncated" => Ok(IpStorageMode::Truncate),
+            "hash" | "hashed" => Ok(IpStorageMode::Hash),
+            "none" | "off" => Ok(IpStorageMode::None),
+            _ => anyhow::bail!("Invalid IP storage mode: {}", s),
+        }
+    }
+}
+
+impl std::str::FromStr for StatusReporting {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.trim().to_lowercase().as_str() {
+            "commit_status" | "status" => Ok(StatusReporting::CommitStatus),
+            "check_run" | "checks" => Ok(StatusReporting::CheckRun),
+            "off" | "none" | "" => Ok(StatusReporting::Off),
+            _ => anyhow::bail!(
+                "Invalid status reporting: {} (expected commit_status, check_run or off)",
+                s
+            ),
+        }
+    }
+}
+
+impl std::str::FromStr for CommentCooldownMode {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.trim().to_lowercase().as_str() {
+            "consolidate" | "batch" | "" => Ok(CommentCooldownMode::Consolidate),
+            "suppress" | "drop" => Ok(CommentCooldownMode::Suppress),
+            _ => anyhow::bail!(
+                "Invalid comment cooldown mode: {} (expected consolidate or suppress)",
+                s
+            ),
+        }
+    }
+}
+
+impl std::str::FromStr for LabelStyle {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.trim().to_lowercase().as_str() {
+            "emoji" => Ok(LabelStyle::Emoji),
+            "plain" | "text" => Ok(LabelStyle::Plain),
+            _ => anyhow::bail!("Invalid label style: {} (expected emoji or plain)", s),
+        }
+    }
+}
+
+impl LlmProvider {
+    /// 📋 Every provider we can talk to
+    pub const ALL: [LlmProvider; 2] = [LlmProvider::OpenAi, LlmProvider::Anthropic];
+
+    /// 🏷️ Name as stored and shown ("openai", "anthropic")
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            LlmProvider::OpenAi => "openai",
+            LlmProvider::Anthropic => "anthropic",
+        }
+    }
+
+    /// 🏷️ Name for people ("OpenAI", "Anthropic")
+    pub fn label(&self) -> &'static str {
+        match self {
+            LlmProvider::OpenAi => "OpenAI",
+            LlmProvider::Anthropic => "Anthropic",
+        }
+    }
+}
+
 impl std::str::FromStr for LlmProvider {
     type Err = anyhow::Error;
 
@@ -560,6 +1673,107 @@ mod tests {
         println!("✅ LLM provider parsing test passed!");
     }
 
+    #[test]
+    fn test_parse_flag() {
+        assert!(parse_flag("1").unwrap());
+        assert!(parse_flag("TRUE").unwrap());
+        assert!(!parse_flag("0").unwrap());
+        assert!(!parse_flag("off").unwrap());
+        assert!(parse_flag("maybe").is_err());
+        println!("✅ Flag parsing test passed!");
+    }
+
+    #[test]
+    fn test_auto_migrate_defaults_by_environment() {
+        assert!(DatabaseConfig::default_auto_migrate(
+            &Environment::Development
+        ));
+        assert!(!DatabaseConfig::default_auto_migrate(&Environment::Staging));
+        assert!(!DatabaseConfig::default_auto_migrate(
+            &Environment::Production
+        ));
+        println!("✅ Auto-migrate default test passed!");
+    }
+
+    #[test]
+    fn test_parse_comment_footer() {
+        assert_eq!(parse_comment_footer(""), None);
+        assert_eq!(parse_comment_footer("  none "), None);
+        assert_eq!(parse_comment_footer("OFF"), None);
+        assert_eq!(
+            parse_comment_footer("— Acme Bot\\nhttps://acme.dev"),
+            Some("— Acme Bot\nhttps://acme.dev".to_string())
+        );
+        println!("✅ Comment footer parsing test passed!");
+    }
+
+    #[test]
+    fn test_ip_storage_mode_parsing() {
+        assert_eq!(
+            "full".parse::<IpStorageMode>().unwrap(),
+            IpStorageMode::Full
+        );
+        assert_eq!(
+            "Truncated".parse::<IpStorageMode>().unwrap(),
+            IpStorageMode::Truncate
+        );
+        assert_eq!(
+            "hash".parse::<IpStorageMode>().unwrap(),
+            IpStorageMode::Hash
+        );
+        assert_eq!("off".parse::<IpStorageMode>().unwrap(), IpStorageMode::None);
+        assert!("scramble".parse::<IpStorageMode>().is_err());
+        println!("✅ IP storage mode parsing test passed!");
+    }
+
+    #[test]
+    fn test_status_reporting_parsing() {
+        assert_eq!(
+            "commit_status".parse::<StatusReporting>().unwrap(),
+            StatusReporting::CommitStatus
+        );
+        assert_eq!(
+            "Check_Run".parse::<StatusReporting>().unwrap(),
+            StatusReporting::CheckRun
+        );
+        assert_eq!(
+            "off".parse::<StatusReporting>().unwrap(),
+            StatusReporting::Off
+        );
+        assert!("smoke_signals".parse::<StatusReporting>().is_err());
+        println!("✅ Status reporting parsing test passed!");
+    }
+
+    #[test]
+    fn test_comment_cooldown_mode_parsing() {
+        assert_eq!(
+            " Consolidate ".parse::<CommentCooldownMode>().unwrap(),
+            CommentCooldownMode::Consolidate
+        );
+        assert_eq!(
+            "suppress".parse::<CommentCooldownMode>().unwrap(),
+            CommentCooldownMode::Suppress
+        );
+        assert!("shout".parse::<CommentCooldownMode>().is_err());
+        println!("✅ Comment cooldown mode parsing test passed!");
+    }
+
+    #[test]
+    fn test_trusted_mcp_clients_match_by_prefix() {
+        let mut analytics = AnalyticsConfig {
+            ip_storage: IpStorageMode::Full,
+            ip_hash_salt: None,
+            mcp_trusted_user_agents: Vec::new(),
+        };
+        assert!(analytics.is_trusted_client(None));
+        analytics.mcp_trusted_user_agents = vec!["smart-tree/".to_string(), "st-mcp".to_string()];
+        assert!(analytics.is_trusted_client(Some("Smart-Tree/5.2.0 (linux; x86_64)")));
+        assert!(analytics.is_trusted_client(Some("st-mcp/1.0")));
+        assert!(!analytics.is_trusted_client(Some("Mozilla/5.0 smart-tree/5.2.0")));
+        assert!(!analytics.is_trusted_client(None));
+        println!("✅ Trusted MCP client test passed!");
+    }
+
     #[test]
     fn test_config_validation() {
         // Set up minimal required environment variables for testing
@@ -575,6 +1789,40 @@ mod tests {
             config.is_ok(),
             "Config loading should succeed with valid environment"
         );
+
+        let mut config = config.unwrap();
+        config.server.public_base_url = "https://feedback.example.com/fb".to_string();
+        assert!(config.validate().is_ok());
+        assert_eq!(
+            config.public_url("/feedback/1"),
+            "https://feedback.example.com/fb/feedback/1"
+        );
+        for bad in [
+            "feedback.example.com",
+            "ftp://example.com",
+            "https://example.com/?a=1",
+        ] {
+            config.server.public_base_url = bad.to_string();
+            assert!(config.validate().is_err(), "{}", bad);
+        }
+        config.server.public_base_url = "https://feedback.example.com".to_string();
+
+        // 📝 PR body templates may only use placeholders we can fill
+        config.github.pr_body_template = Some("Thanks {submiter}".to_string());
+        assert!(config.validate().is_err());
+        config.github.pr_body_template = Some("Thanks {submitter}".to_string());
+        assert!(config.validate().is_ok());
+        config.github.pr_body_template = None;
+
+        // 🧩 A captcha without its keys, or an impossible proof-of-work, is refused
+        config.challenge.kind = "turnstile".parse().unwrap();
+        assert!(config.validate().is_err());
+        config.challenge.site_key = Some("site".to_string());
+        config.challenge.secret_key = Some("secret".to_string());
+        assert!(config.validate().is_ok());
+        config.challenge.kind = ChallengeKind::ProofOfWork;
+        config.challenge.pow_difficulty = 64;
+        assert!(config.validate().is_err());
         println!("✅ Configuration validation test passed!");
     }
 }
@@ -0,0 +1,240 @@
+// 🔑 API Keys - Machine callers with exactly the reach they were given! 🔑
+// Keys look like `fbk_<64 hex>` and are stored only as their SHA-256, so a leaked
+// database doesn't leak working keys. Each key carries scopes (`issues:write` for the
+// issue relay), optionally an explicit list of repositories it may target (otherwise:
+// registered projects only), and a daily issue quota counted per UTC day.
+// `feedbacker apikey add` issues them and `feedbacker apikey revoke` retires them.
+// Created with love by Aye & Hue! ✨
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use sha2::{Digest, Sha256};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+/// 🎫 Scope needed to create issues through POST /api/issues
+pub const SCOPE_ISSUES_WRITE: &str = "issues:write";
+/// 📋 Every scope a key can be given
+pub const SCOPES: &[&str] = &[SCOPE_ISSUES_WRITE];
+/// 🏷️ What every key starts with (and how it's recognised in logs)
+const KEY_PREFIX: &str = "fbk_";
+
+/// 🔑 A stored API key (never the key itself)
+#[derive(Debug, Clone, PartialEq, sqlx::FromRow)]
+pub struct ApiKey {
+    pub id: Uuid,
+    pub name: String,
+    /// 👀 First characters of the key, enough to tell keys apart
+    pub key_prefix: String,
+    pub scopes: Vec<String>,
+    /// 🎯 Repositories ("owner/repo") it may target; None = registered projects only
+    pub allowed_repos: Option<Vec<String>>,
+    /// 📏 Issues per UTC day; None = GITHUB_ISSUE_RELAY_DAILY_QUOTA
+    pub daily_issue_quota: Option<i32>,
+    pub created_at: DateTime<Utc>,
+    pub last_used_at: Option<DateTime<Utc>>,
+}
+
+impl ApiKey {
+    /// 🎫 Was the key given this scope?
+    pub fn has_scope(&self, scope: &str) -> bool {
+        self.scopes.iter().any(|granted| granted == scope)
+    }
+
+    /// 🎯 Does the key's own allowlist cover `repository` (None when it has no list)?
+    pub fn allows_repo(&self, repository: &str) -> Option<bool> {
+        self.allowed_repos.as_ref().map(|repos| {
+            repos
+                .iter()
+                .any(|allowed| allowed.eq_ignore_ascii_case(repository))
+        })
+    }
+
+    /// ➕ Store a new key; the returned string is the key, shown to the operator once
+    pub async fn create(
+        pool: &PgPool,
+        name: &str,
+        scopes: &[String],
+        allowed_repos: Option<&[String]>,
+        daily_issue_quota: Option<i32>,
+    ) -> Result<(Self, String)> {
+        if let Some(unknown) = scopes
+            .iter()
+            .find(|scope| !SCOPES.contains(&scope.as_str()))
+        {
+            anyhow::bail!(
+                "Unknown scope {} (expected one of {})",
+                unknown,
+                SCOPES.join(", ")
+            );
+        }
+        let key = generate_key();
+        let created = sqlx::query_as(
+            r#"
+            INSERT INTO api_keys (name, key_prefix, key_hash, scopes, allowed_repos, daily_issue_quota)
+            VALUES ($1, $2, $3, $4, $5, $6)
+            RETURNING id, name, key_prefix, scopes, allowed_repos, daily_issue_quota, created_at, last_used_at
+            "#,
+        )
+        .bind(name)
+        .bind(&key[..KEY_PREFIX.len() + 8])
+        .bind(hash_key(&key))
+        .bind(scopes)
+        .bind(allowed_repos)
+        .bind(daily_issue_quota)
+        .fetch_one(pool)
+        .await
+        .context("Failed to store API key")?;
+        Ok((created, key))
+    }
+
+    /// 🔍 The unrevoked key behind `key` (and note that it was used)
+    pub async fn authenticate(pool: &PgPool, key: &str) -> Result<Option<Self>> {
+        if !key.starts_with(KEY_PREFIX) {
+            return Ok(None);
+        }
+        sqlx::query_as(
+            r#"
+            UPDATE api_keys SET last_used_at = NOW()
+            WHERE key_hash = $1 AND revoked_at IS NULL
+            RETURNING id, name, key_prefix, scopes, allowed_repos, daily_issue_quota, created_at, last_used_at
+            "#,
+        )
+        .bind(hash_key(key))
+        .fetch_optional(pool)
+        .await
+        .context("Failed to look up API key")
+    }
+
+    /// 🎟️ Take one of today's issues from the key's quota (false once it's used up)
+    pub async fn reserve_issue(&self, pool: &PgPool, default_quota: u32) -> Result<bool> {
+        let quota = self.daily_issue_quota.unwrap_or(default_quota as i32);
+        let reserved: Option<i32> = sqlx::query_scalar(
+            r#"
+            UPDATE api_keys
+            SET issues_today = CASE WHEN issues_day = (NOW() AT TIME ZONE 'UTC')::date
+                                    THEN issues_today + 1 ELSE 1 END,
+                issues_day = (NOW() AT TIME ZONE 'UTC')::date
+            WHERE id = $1
+              AND $2 > CASE WHEN issues_day = (NOW() AT TIME ZONE 'UTC')::date
+                            THEN issues_today ELSE 0 END
+            RETURNING issues_today
+            "#,
+        )
+        .bind(self.id)
+        .bind(quota)
+        .fetch_optional(pool)
+        .await
+        .context("Failed to count issue against the API key quota")?;
+        Ok(reserved.is_some())
+    }
+
+    /// ↩️ Give a reserved issue back (GitHub didn't create it after all)
+    pub async fn release_issue(&self, pool: &PgPool) -> Result<()> {
+        sqlx::query(
+            r#"
+            UPDATE api_keys SET issues_today = issues_today - 1
+            WHERE id = $1 AND issues_day = (NOW() AT TIME ZONE 'UTC')::date AND issues_today > 0
+            "#,
+        )
+        .bind(self.id)
+        .execute(pool)
+        .await
+        .context("Failed to return issue to the API key quota")?;
+        Ok(())
+    }
+
+    /// 🚫 Revoke the key with this id for good (false when there's no such unrevoked key)
+    pub async fn revoke(pool: &PgPool, id: Uuid) -> Result<bool> {
+        let revoked = sqlx::query(
+            "UPDATE api_keys SET revoked_at = NOW() WHERE id = $1 AND revoked_at IS NULL",
+        )
+        .bind(id)
+        .execute(pool)
+        .await
+        .context("Failed to revoke API key")?;
+        Ok(revoked.rows_affected() == 1)
+    }
+}
+
+/// 🎲 A fresh random key
+fn generate_key() -> String {
+    format!(
+        "{}{}",
+        KEY_PREFIX,
+        super::models::generate_callback_secret()
+    )
+}
+
+/// 🔏 What is stored in place of the key
+fn hash_key(key: &str) -> String {
+    hex::encode(Sha256::digest(key.as_bytes()))
+}
+
+// 🧪 Tests - Keys that open only the doors they should!
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::spawn_test_app;
+
+    #[tokio::test]
+    async fn test_keys_authenticate_by_hash_and_count_their_quota() {
+        let Some(app) = spawn_test_app().await else {
+            return;
+        };
+        let pool = &app.db_pool;
+        let repos = vec!["8b-is/Smart-Tree".to_string()];
+        let (key, secret) = ApiKey::create(
+            pool,
+            "issue bot",
+            &[SCOPE_ISSUES_WRITE.to_string()],
+            Some(&repos),
+            Some(2),
+        )
+        .await
+        .unwrap();
+        assert!(secret.starts_with("fbk_") && secret.starts_with(&key.key_prefix));
+        let stored: String = sqlx::query_scalar("SELECT key_hash FROM api_keys WHERE id = $1")
+            .bind(key.id)
+            .fetch_one(pool)
+            .await
+            .unwrap();
+        assert_ne!(stored, secret);
+
+        let found = ApiKey::authenticate(pool, &secret).await.unwrap().unwrap();
+        assert!(found.has_scope(SCOPE_ISSUES_WRITE));
+        assert!(found.last_used_at.is_some());
+        assert_eq!(found.allows_repo("8b-is/smart-tree"), Some(true));
+        assert_eq!(found.allows_repo("8b-is/other"), Some(false));
+        assert!(ApiKey::authenticate(pool, "fbk_nope")
+            .await
+            .unwrap()
+            .is_none());
+
+        // 🎟️ Two a day, and a returned one can be taken again
+        assert!(found.reserve_issue(pool, 50).await.unwrap());
+        assert!(found.reserve_issue(pool, 50).await.unwrap());
+        assert!(!found.reserve_issue(pool, 50).await.unwrap());
+        found.release_issue(pool).await.unwrap();
+        assert!(found.reserve_issue(pool, 50).await.unwrap());
+        // 🌅 A new day starts from zero
+        sqlx::query("UPDATE api_keys SET issues_day = issues_day - 1 WHERE id = $1")
+            .bind(key.id)
+            .execute(pool)
+            .await
+            .unwrap();
+        assert!(found.reserve_issue(pool, 50).await.unwrap());
+
+        assert!(ApiKey::revoke(pool, key.id).await.unwrap());
+        assert!(ApiKey::authenticate(pool, &secret).await.unwrap().is_none());
+        // 🚫 Revoking twice (or a key that never existed) reports nothing revoked
+        assert!(!ApiKey::revoke(pool, key.id).await.unwrap());
+        assert!(!ApiKey::revoke(pool, Uuid::new_v4()).await.unwrap());
+        assert!(
+            ApiKey::create(pool, "typo", &["issue:write".to_string()], None, None)
+                .await
+                .is_err()
+        );
+        println!("✅ API key test passed!");
+    }
+}
@@ -0,0 +1,172 @@
+// 📒 Automation Log - Which bot comments we left where! 📒
+// Every comment the issue automation posts is recorded with both of its ids (REST for
+// delete/react, GraphQL for minimize), so a later step can find its own earlier
+// comments - the needs-info reminder once the author replies, say - and tidy them up
+// instead of leaving stale bot noise in the thread. Issues opened through the relay
+// (POST /api/issues) are logged too, with the API key that asked for them.
+// Each row keeps the text its step wrote: inside the per-issue comment cooldown a later
+// step adds its text to the previous comment instead of posting a new one, and the rows
+// sharing a comment_id are then that comment's sections, oldest first.
+// Created with love by Aye & Hue! ✨
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use sqlx::{PgExecutor, PgPool};
+use uuid::Uuid;
+
+use crate::github::ops::PostedComment;
+
+/// 🎫 Step of issues created through POST /api/issues
+pub const RELAYED_ISSUE_STEP: &str = "relayed_issue";
+
+/// 🧹 What happened to a logged comment once it was no longer needed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Cleanup {
+    Minimized,
+    Deleted,
+}
+
+impl Cleanup {
+    /// 🏷️ Value stored in `automation_log.cleanup`
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Minimized => "minimized",
+            Self::Deleted => "deleted",
+        }
+    }
+}
+
+/// 💬 A bot comment that is still showing in full
+#[derive(Debug, Clone, PartialEq, sqlx::FromRow)]
+pub struct LoggedComment {
+    pub id: Uuid,
+    pub comment_id: i64,
+    pub comment_node_id: String,
+}
+
+/// 🧩 One step's part of a (possibly shared) bot comment
+#[derive(Debug, Clone, PartialEq, sqlx::FromRow)]
+pub struct CommentSection {
+    pub id: Uuid,
+    pub step: String,
+    /// 📝 The step's text without the footer (None for rows logged before v29)
+    pub body: Option<String>,
+}
+
+/// ✍️ Remember the `body` automation `step` posted (or added) as comment `posted` on
+/// `repository#issue_number`
+pub async fn record_comment(
+    executor: impl PgExecutor<'_>,
+    repository: &str,
+    issue_number: u32,
+    step: &str,
+    posted: &PostedComment,
+    body: &str,
+) -> Result<()> {
+    sqlx::query(
+        "INSERT INTO automation_log (repository, issue_number, step, comment_id, comment_node_id, body) \
+         VALUES ($1, $2, $3, $4, $5, $6)",
+    )
+    .bind(repository)
+    .bind(issue_number as i32)
+    .bind(step)
+    .bind(posted.id as i64)
+    .bind(&posted.node_id)
+    .bind(body)
+    .execute(executor)
+    .await
+    .context("Failed to record bot comment")?;
+    Ok(())
+}
+
+/// 🎫 Attribute an issue created through the relay to the API key that asked for it
+pub async fn record_relayed_issue(
+    pool: &PgPool,
+    repository: &str,
+    issue_number: u64,
+    api_key_id: Uuid,
+) -> Result<()> {
+    sqlx::query(
+        "INSERT INTO automation_log (repository, issue_number, step, api_key_id) \
+         VALUES ($1, $2, $3, $4)",
+    )
+    .bind(repository)
+    .bind(issue_number as i32)
+    .bind(RELAYED_ISSUE_STEP)
+    .bind(api_key_id)
+    .execute(pool)
+    .await
+    .context("Failed to record relayed issue")?;
+    Ok(())
+}
+
+/// 🔍 Comments from `step` on an issue that haven't been cleaned up yet, oldest first
+pub async fn open_comments(
+    pool: &PgPool,
+    repository: &str,
+    issue_number: u32,
+    step: &str,
+) -> Result<Vec<LoggedComment>> {
+    sqlx::query_as(
+        "SELECT id, comment_id, comment_node_id FROM automation_log \
+         WHERE repository = $1 AND issue_number = $2 AND step = $3 AND cleaned_up_at IS NULL \
+         ORDER BY created_at",
+    )
+    .bind(repository)
+    .bind(issue_number as i32)
+    .bind(step)
+    .fetch_all(pool)
+    .await
+    .context("Failed to load bot comments")
+}
+
+/// ⏲️ The bot comment last written to on an issue, if that was after `since` and the
+/// comment is still showing - the one a step inside the cooldown would add to
+pub async fn last_open_comment(
+    executor: impl PgExecutor<'_>,
+    repository: &str,
+    issue_number: u32,
+    since: DateTime<Utc>,
+) -> Result<Option<LoggedComment>> {
+    sqlx::query_as(
+        "SELECT id, comment_id, comment_node_id FROM automation_log \
+         WHERE repository = $1 AND issue_number = $2 AND comment_id IS NOT NULL \
+           AND cleaned_up_at IS NULL AND created_at >= $3 \
+         ORDER BY created_at DESC LIMIT 1",
+    )
+    .bind(repository)
+    .bind(issue_number as i32)
+    .bind(since)
+    .fetch_optional(executor)
+    .await
+    .context("Failed to load the last bot comment")
+}
+
+/// 🧩 The sections of comment `comment_id` that are still showing, oldest first
+pub async fn comment_sections(
+    executor: impl PgExecutor<'_>,
+    repository: &str,
+    comment_id: i64,
+) -> Result<Vec<CommentSection>> {
+    sqlx::query_as(
+        "SELECT id, step, body FROM automation_log \
+         WHERE repository = $1 AND comment_id = $2 AND cleaned_up_at IS NULL \
+         ORDER BY created_at",
+    )
+    .bind(repository)
+    .bind(comment_id)
+    .fetch_all(executor)
+    .await
+    .context("Failed to load bot comment sections")
+}
+
+/// ✅ Note that a logged comment has been minimized or deleted
+pub async fn mark_cleaned_up(pool: &PgPool, id: Uuid, cleanup: Cleanup) -> Result<()> {
+    sqlx::query("UPDATE automation_log SET cleaned_up_at = NOW(), cleanup = $2 WHERE id = $1")
+        .bind(id)
+        .bind(cleanup.as_str())
+        .execute(pool)
+        .await
+        .context("Failed to update bot comment")?;
+    Ok(())
+}
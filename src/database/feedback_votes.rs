@@ -0,0 +1,241 @@
+// 👍 Feedback Votes - How many people want the same thing! 👍
+// Signed-in users vote for feedback they care about (one vote each), and admins mark
+// later reports of the same thing as duplicates of the first (`feedback.duplicate_of`).
+// Together they are the feedback's reception, which pull request bodies show and the
+// nightly refresh keeps current (`jobs::pr_refresh`).
+// Created with love by Aye & Hue! ✨
+
+use anyhow::{Context, Result};
+use serde::Serialize;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+/// 📣 How a feedback item was received since it was sent
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, sqlx::FromRow)]
+pub struct FeedbackReception {
+    /// 👍 Votes cast for it
+    pub votes: i64,
+    /// 👯 People who reported the same thing again (the original submitter doesn't
+    /// count, every anonymous report does)
+    pub also_reported_by: i64,
+}
+
+/// 👍 Vote for a feedback item (voting twice is harmless); returns its votes
+pub async fn vote(pool: &PgPool, feedback_id: Uuid, user_id: Uuid) -> Result<i64> {
+    sqlx::query(
+        "INSERT INTO feedback_votes (feedback_id, user_id) VALUES ($1, $2) \
+         ON CONFLICT DO NOTHING",
+    )
+    .bind(feedback_id)
+    .bind(user_id)
+    .execute(pool)
+    .await
+    .context("Failed to record vote")?;
+    count_votes(pool, feedback_id).await
+}
+
+/// 👎 Take a vote back; returns the votes left
+pub async fn unvote(pool: &PgPool, feedback_id: Uuid, user_id: Uuid) -> Result<i64> {
+    sqlx::query("DELETE FROM feedback_votes WHERE feedback_id = $1 AND user_id = $2")
+        .bind(feedback_id)
+        .bind(user_id)
+        .execute(pool)
+        .await
+        .context("Failed to remove vote")?;
+    count_votes(pool, feedback_id).await
+}
+
+async fn count_votes(pool: &PgPool, feedback_id: Uuid) -> Result<i64> {
+    sqlx::query_scalar("SELECT COUNT(*) FROM feedback_votes WHERE feedback_id = $1")
+        .bind(feedback_id)
+        .fetch_one(pool)
+        .await
+        .context("Failed to count votes")
+}
+
+/// 📣 Votes and duplicate reports of a feedback item
+pub async fn reception(pool: &PgPool, feedback_id: Uuid) -> Result<FeedbackReception> {
+    sqlx::query_as(
+        r#"
+        SELECT
+            (SELECT COUNT(*) FROM feedback_votes WHERE feedback_id = f.id) AS votes,
+            (SELECT COUNT(DISTINCT COALESCE(d.user_id::text, d.id::text))
+             FROM feedback d
+             WHERE d.duplicate_of = f.id
+               AND (d.user_id IS NULL OR d.user_id IS DISTINCT FROM f.user_id)) AS also_reported_by
+        FROM feedback f
+        WHERE f.id = $1
+        "#,
+    )
+    .bind(feedback_id)
+    .fetch_optional(pool)
+    .await
+    .context("Failed to load feedback reception")
+    .map(Option::unwrap_or_default)
+}
+
+/// 👯 The report a feedback item duplicates, if any
+pub async fn duplicate_of(pool: &PgPool, feedback_id: Uuid) -> Result<Option<Uuid>> {
+    sqlx::query_scalar::<_, Option<Uuid>>("SELECT duplicate_of FROM feedback WHERE id = $1")
+        .bind(feedback_id)
+        .fetch_optional(pool)
+        .await
+        .context("Failed to load duplicate_of")
+        .map(Option::flatten)
+}
+
+/// 👯 Why a feedback item couldn't be marked as a duplicate
+#[derive(Debug, PartialEq, Eq)]
+pub enum DuplicateRefused {
+    /// 🔍 The original doesn't exist
+    UnknownOriginal,
+    /// 🔁 Marking it would make a loop (or point it at itself)
+    Cycle,
+}
+
+/// 👯 Mark `feedback_id` as a report of the same thing as `original` (None unmarks
+/// it). A duplicate of a duplicate counts for the first report, and so do the
+/// duplicates of a report that turns out to be one itself.
+pub async fn mark_duplicate(
+    pool: &PgPool,
+    feedback_id: Uuid,
+    original: Option<Uuid>,
+) -> Result<Result<(), DuplicateRefused>> {
+    let original = match original {
+        Some(original) if original == feedback_id => return Ok(Err(DuplicateRefused::Cycle)),
+        Some(original) => {
+            let root: Option<Uuid> =
+                sqlx::query_scalar("SELECT COALESCE(duplicate_of, id) FROM feedback WHERE id = $1")
+                    .bind(original)
+                    .fetch_optional(pool)
+                    .await
+                    .context("Failed to load original feedback")?;
+            match root {
+                None => return Ok(Err(DuplicateRefused::UnknownOriginal)),
+                Some(root) if root == feedback_id => return Ok(Err(DuplicateRefused::Cycle)),
+                Some(root) => Some(root),
+            }
+        }
+        None => None,
+    };
+    sqlx::query(
+        "UPDATE feedback SET duplicate_of = $2 \
+         WHERE id = $1 OR ($2::uuid IS NOT NULL AND duplicate_of = $1)",
+    )
+    .bind(feedback_id)
+    .bind(original)
+    .execute(pool)
+    .await
+    .context("Failed to mark duplicate")?;
+    Ok(Ok(()))
+}
+
+// 🧪 Tests - Counting heads!
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::models::Feedback;
+    use crate::test_support::spawn_test_app;
+
+    #[tokio::test]
+    async fn test_votes_and_duplicates_make_the_reception() {
+        let Some(app) = spawn_test_app().await else {
+            return;
+        };
+        let pool = &app.db_pool;
+        let mut users = Vec::new();
+        for name in ["ayes", "hues", "trisha"] {
+            let id: Uuid = sqlx::query_scalar(
+                "INSERT INTO users (email, name, password_hash) VALUES ($1, $1, 'x') RETURNING id",
+            )
+            .bind(format!("{}@8b.is", name))
+            .fetch_one(pool)
+            .await
+            .unwrap();
+            users.push(id);
+        }
+        let report = |user_id: Option<Uuid>| {
+            Feedback::create(
+                pool,
+                user_id,
+                "8b-is/smart-tree".to_string(),
+                "Please add dark mode".to_string(),
+                None,
+                25,
+                None,
+                "cli",
+            )
+        };
+        let original = report(Some(users[0])).await.unwrap();
+        assert_eq!(
+            reception(pool, original.id).await.unwrap(),
+            FeedbackReception::default()
+        );
+
+        // 👍 One vote per user
+        assert_eq!(vote(pool, original.id, users[1]).await.unwrap(), 1);
+        assert_eq!(vote(pool, original.id, users[1]).await.unwrap(), 1);
+        assert_eq!(vote(pool, original.id, users[2]).await.unwrap(), 2);
+        assert_eq!(unvote(pool, original.id, users[2]).await.unwrap(), 1);
+
+        // 👯 Duplicates by others count, the submitter's own repeat doesn't
+        let by_hue = report(Some(users[1])).await.unwrap();
+        let again_by_hue = report(Some(users[1])).await.unwrap();
+        let anonymous = report(None).await.unwrap();
+        let own_repeat = report(Some(users[0])).await.unwrap();
+        for duplicate in [&by_hue, &anonymous, &own_repeat] {
+            assert_eq!(
+                mark_duplicate(pool, duplicate.id, Some(original.id))
+                    .await
+                    .unwrap(),
+                Ok(())
+            );
+        }
+        // 🔗 Pointing at a duplicate lands on the original
+        mark_duplicate(pool, again_by_hue.id, Some(by_hue.id))
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            reception(pool, original.id).await.unwrap(),
+            FeedbackReception {
+                votes: 1,
+                also_reported_by: 2,
+            }
+        );
+
+        assert_eq!(
+            mark_duplicate(pool, original.id, Some(by_hue.id))
+                .await
+                .unwrap(),
+            Err(DuplicateRefused::Cycle)
+        );
+        assert_eq!(
+            mark_duplicate(pool, original.id, Some(Uuid::new_v4()))
+                .await
+                .unwrap(),
+            Err(DuplicateRefused::UnknownOriginal)
+        );
+        mark_duplicate(pool, anonymous.id, None)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            reception(pool, original.id).await.unwrap().also_reported_by,
+            1
+        );
+
+        // 🪢 A report with duplicates of its own hands them on
+        let first = report(Some(users[2])).await.unwrap();
+        mark_duplicate(pool, original.id, Some(first.id))
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            reception(pool, original.id).await.unwrap().also_reported_by,
+            0
+        );
+        assert_eq!(reception(pool, first.id).await.unwrap().also_reported_by, 2);
+        println!("✅ Feedback reception test passed!");
+    }
+}
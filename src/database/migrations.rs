@@ -308,6 +308,62 @@ CREATE TRIGGER update_feedback_updated_at BEFORE UPDATE ON feedback FOR EACH ROW
             "#.to_string(),
             down_sql: Some("DROP SCHEMA public CASCADE; CREATE SCHEMA public;".to_string()),
         },
+        Migration {
+            id: "v1_1_mcp_tables".to_string(),
+            description: "Bring hand-made settings and mcp_analytics tables up to what the code uses".to_string(),
+            up_sql: r#"
+-- Some deployments created these tables by hand before v2_mcp_analytics shipped. Its
+-- CREATE TABLE IF NOT EXISTS then keeps their columns, and its indexes and trigger fail
+-- on the ones that are missing. This runs first and adds whatever the code relies on.
+-- The indexes stay with v2_mcp_analytics, which creates them unconditionally.
+CREATE TABLE IF NOT EXISTS settings (
+    key VARCHAR(255) PRIMARY KEY,
+    value TEXT NOT NULL,
+    description TEXT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+ALTER TABLE settings ADD COLUMN IF NOT EXISTS key VARCHAR(255);
+ALTER TABLE settings ADD COLUMN IF NOT EXISTS value TEXT;
+ALTER TABLE settings ADD COLUMN IF NOT EXISTS description TEXT;
+ALTER TABLE settings ADD COLUMN IF NOT EXISTS created_at TIMESTAMPTZ NOT NULL DEFAULT NOW();
+ALTER TABLE settings ADD COLUMN IF NOT EXISTS updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW();
+-- upserts use ON CONFLICT (key), which needs key to be unique
+DO $$
+BEGIN
+    IF NOT EXISTS (
+        SELECT 1 FROM pg_index i
+        JOIN pg_attribute a ON a.attrelid = i.indrelid AND a.attnum = i.indkey[0]
+        WHERE i.indrelid = 'settings'::regclass AND i.indisunique
+          AND i.indnatts = 1 AND a.attname = 'key'
+    ) THEN
+        CREATE UNIQUE INDEX idx_settings_key ON settings(key);
+    END IF;
+END
+$$;
+
+CREATE TABLE IF NOT EXISTS mcp_analytics (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    client_version VARCHAR(50) NOT NULL,
+    platform VARCHAR(50) NOT NULL,
+    arch VARCHAR(50) NOT NULL,
+    checked_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+ALTER TABLE mcp_analytics ADD COLUMN IF NOT EXISTS id UUID DEFAULT gen_random_uuid();
+ALTER TABLE mcp_analytics ADD COLUMN IF NOT EXISTS client_version VARCHAR(50);
+ALTER TABLE mcp_analytics ADD COLUMN IF NOT EXISTS platform VARCHAR(50);
+ALTER TABLE mcp_analytics ADD COLUMN IF NOT EXISTS arch VARCHAR(50);
+ALTER TABLE mcp_analytics ADD COLUMN IF NOT EXISTS checked_at TIMESTAMPTZ NOT NULL DEFAULT NOW();
+ALTER TABLE mcp_analytics ADD COLUMN IF NOT EXISTS ip_address INET;
+ALTER TABLE mcp_analytics ADD COLUMN IF NOT EXISTS country VARCHAR(2);
+ALTER TABLE mcp_analytics ADD COLUMN IF NOT EXISTS region VARCHAR(100);
+ALTER TABLE mcp_analytics ADD COLUMN IF NOT EXISTS city VARCHAR(100);
+ALTER TABLE mcp_analytics ADD COLUMN IF NOT EXISTS latitude DOUBLE PRECISION;
+ALTER TABLE mcp_analytics ADD COLUMN IF NOT EXISTS longitude DOUBLE PRECISION;
+            "#.to_string(),
+            // 🗑️ The tables belong to v2_mcp_analytics, whose down SQL drops them
+            down_sql: Some("DROP INDEX IF EXISTS idx_settings_key;".to_string()),
+        },
         Migration {
             id: "v2_mcp_analytics".to_string(),
             description: "Add MCP analytics and settings tables for Smart Tree".to_string(),
@@ -363,10 +419,867 @@ ALTER TABLE mcp_analytics DROP COLUMN IF EXISTS latitude;
 ALTER TABLE mcp_analytics DROP COLUMN IF EXISTS longitude;
             "#.to_string()),
         },
+        Migration {
+            id: "v4_feedback_callbacks".to_string(),
+            description: "Add per-feedback callback URL and signing secret".to_string(),
+            up_sql: r#"
+ALTER TABLE feedback ADD COLUMN IF NOT EXISTS callback_url TEXT;
+ALTER TABLE feedback ADD COLUMN IF NOT EXISTS callback_secret VARCHAR(64);
+
+-- Job claiming looks up due jobs of one type
+CREATE INDEX IF NOT EXISTS idx_background_jobs_due ON background_jobs(job_type, status, scheduled_at);
+            "#.to_string(),
+            down_sql: Some(r#"
+DROP INDEX IF EXISTS idx_background_jobs_due;
+ALTER TABLE feedback DROP COLUMN IF EXISTS callback_secret;
+ALTER TABLE feedback DROP COLUMN IF EXISTS callback_url;
+            "#.to_string()),
+        },
+        Migration {
+            id: "v5_dashboard_range_indexes".to_string(),
+            description: "Indexes for time-ranged dashboard statistics".to_string(),
+            up_sql: r#"
+-- Range-bounded feedback counts and the per-repository breakdown scan only this index
+CREATE INDEX IF NOT EXISTS idx_feedback_created_repo_status ON feedback(created_at, repository, status);
+CREATE INDEX IF NOT EXISTS idx_users_created_at ON users(created_at);
+            "#.to_string(),
+            down_sql: Some(r#"
+DROP INDEX IF EXISTS idx_users_created_at;
+DROP INDEX IF EXISTS idx_feedback_created_repo_status;
+            "#.to_string()),
+        },
+        Migration {
+            id: "v6_admin_audit_log".to_string(),
+            description: "Audit log for security-relevant admin actions".to_string(),
+            up_sql: r#"
+CREATE TABLE IF NOT EXISTS admin_audit_log (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    actor VARCHAR(255) NOT NULL,
+    action VARCHAR(100) NOT NULL,
+    details JSONB,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+CREATE INDEX IF NOT EXISTS idx_admin_audit_log_created_at ON admin_audit_log(created_at);
+            "#.to_string(),
+            down_sql: Some(r#"
+DROP TABLE IF EXISTS admin_audit_log;
+            "#.to_string()),
+        },
+        Migration {
+            id: "v7_mcp_analytics_ip_hash".to_string(),
+            description: "Salted IP hash column for anonymized MCP analytics".to_string(),
+            up_sql: r#"
+-- ANALYTICS_IP_STORAGE decides what new rows keep (full / truncate / hash / none).
+-- Rows written before it was set keep their full address. To bring them in line with
+-- truncate mode, run once:
+--   UPDATE mcp_analytics SET ip_address = host(network(set_masklen(ip_address,
+--       CASE WHEN family(ip_address) = 4 THEN 24 ELSE 48 END)))::inet
+--   WHERE ip_address IS NOT NULL;
+-- or drop them entirely with: UPDATE mcp_analytics SET ip_address = NULL;
+ALTER TABLE mcp_analytics ADD COLUMN IF NOT EXISTS ip_hash VARCHAR(64);
+            "#.to_string(),
+            down_sql: Some(r#"
+ALTER TABLE mcp_analytics DROP COLUMN IF EXISTS ip_hash;
+            "#.to_string()),
+        },
+        Migration {
+            id: "v8_feedback_priority".to_string(),
+            description: "Effective priority on feedback and background jobs".to_string(),
+            up_sql: r#"
+-- Existing rows start at 0, new feedback gets impact x frequency, an explicit value
+-- or FEEDBACK_DEFAULT_PRIORITY. Jobs inherit the priority of their feedback.
+ALTER TABLE feedback ADD COLUMN IF NOT EXISTS priority INTEGER NOT NULL DEFAULT 0;
+CREATE INDEX IF NOT EXISTS idx_feedback_status_priority ON feedback(status, priority DESC, created_at);
+ALTER TABLE background_jobs ADD COLUMN IF NOT EXISTS priority INTEGER NOT NULL DEFAULT 0;
+CREATE INDEX IF NOT EXISTS idx_background_jobs_claim ON background_jobs(status, priority DESC, scheduled_at);
+            "#.to_string(),
+            down_sql: Some(r#"
+DROP INDEX IF EXISTS idx_background_jobs_claim;
+ALTER TABLE background_jobs DROP COLUMN IF EXISTS priority;
+DROP INDEX IF EXISTS idx_feedback_status_priority;
+ALTER TABLE feedback DROP COLUMN IF EXISTS priority;
+            "#.to_string()),
+        },
+        Migration {
+            id: "v9_feedback_tags".to_string(),
+            description: "Normalized feedback tags for statistics and filtering".to_string(),
+            up_sql: r#"
+-- Tags are stored lowercased with whitespace turned into '-', one row per tag.
+-- Tags already sitting in feedback.metadata->'tags' are backfilled the same way.
+CREATE TABLE IF NOT EXISTS feedback_tags (
+    feedback_id UUID NOT NULL REFERENCES feedback(id) ON DELETE CASCADE,
+    tag VARCHAR(50) NOT NULL,
+    PRIMARY KEY (feedback_id, tag)
+);
+CREATE INDEX IF NOT EXISTS idx_feedback_tags_tag ON feedback_tags(tag);
+INSERT INTO feedback_tags (feedback_id, tag)
+SELECT DISTINCT f.id, lower(regexp_replace(btrim(t.tag), '\s+', '-', 'g'))
+FROM feedback f
+CROSS JOIN LATERAL jsonb_array_elements_text(f.metadata->'tags') AS t(tag)
+WHERE jsonb_typeof(f.metadata->'tags') = 'array'
+  AND btrim(t.tag) <> ''
+  AND char_length(lower(regexp_replace(btrim(t.tag), '\s+', '-', 'g'))) <= 50
+ON CONFLICT DO NOTHING;
+            "#.to_string(),
+            down_sql: Some(r#"
+DROP TABLE IF EXISTS feedback_tags;
+            "#.to_string()),
+        },
+        Migration {
+            id: "v10_callback_secret_rotation".to_string(),
+            description: "Previous callback secret kept for an overlap window after rotation".to_string(),
+            up_sql: r#"
+ALTER TABLE feedback ADD COLUMN IF NOT EXISTS callback_secret_previous VARCHAR(64);
+ALTER TABLE feedback ADD COLUMN IF NOT EXISTS callback_secret_previous_expires_at TIMESTAMPTZ;
+            "#.to_string(),
+            down_sql: Some(r#"
+ALTER TABLE feedback DROP COLUMN IF EXISTS callback_secret_previous_expires_at;
+ALTER TABLE feedback DROP COLUMN IF EXISTS callback_secret_previous;
+            "#.to_string()),
+        },
+        Migration {
+            id: "v11_daily_stats".to_string(),
+            description: "Nightly statistics snapshots for trend charts".to_string(),
+            up_sql: r#"
+-- One row per UTC date, written by the nightly daily_stats_snapshot job (reruns
+-- overwrite the same date). Columns that can't be reconstructed from history stay
+-- NULL on backfilled rows.
+CREATE TABLE IF NOT EXISTS daily_stats (
+    date DATE PRIMARY KEY,
+    total_users BIGINT,
+    total_projects BIGINT,
+    total_feedback BIGINT NOT NULL,
+    pending_feedback BIGINT,
+    completed_feedback BIGINT,
+    failed_feedback BIGINT,
+    queue_depth BIGINT,
+    unique_mcp_clients BIGINT,
+    feedback_created BIGINT NOT NULL DEFAULT 0,
+    backfilled BOOLEAN NOT NULL DEFAULT FALSE,
+    captured_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+INSERT INTO daily_stats (date, total_feedback, feedback_created, backfilled)
+SELECT days.date::date,
+       (SUM(COALESCE(per_day.created, 0)) OVER (ORDER BY days.date))::bigint,
+       COALESCE(per_day.created, 0),
+       TRUE
+FROM generate_series(
+         (SELECT MIN((created_at AT TIME ZONE 'UTC')::date) FROM feedback),
+         (NOW() AT TIME ZONE 'UTC')::date,
+         INTERVAL '1 day'
+     ) AS days(date)
+LEFT JOIN (
+    SELECT (created_at AT TIME ZONE 'UTC')::date AS date, COUNT(*) AS created
+    FROM feedback
+    GROUP BY 1
+) per_day ON per_day.date = days.date::date
+ON CONFLICT (date) DO NOTHING;
+
+            "#.to_string(),
+            down_sql: Some(r#"
+DROP TABLE IF EXISTS daily_stats;
+            "#.to_string()),
+        },
+        Migration {
+            id: "v12_mcp_analytics_user_agent".to_string(),
+            description: "User-Agent and integration name on MCP checks".to_string(),
+            up_sql: r#"
+-- Kept for tracing bursts of odd checks back to a client. The User-Agent is
+-- truncated to 256 characters before it's written. Older rows stay NULL.
+ALTER TABLE mcp_analytics ADD COLUMN IF NOT EXISTS user_agent VARCHAR(256);
+ALTER TABLE mcp_analytics ADD COLUMN IF NOT EXISTS integration VARCHAR(64);
+CREATE INDEX IF NOT EXISTS idx_mcp_analytics_user_agent ON mcp_analytics(user_agent, checked_at);
+            "#.to_string(),
+            down_sql: Some(r#"
+DROP INDEX IF EXISTS idx_mcp_analytics_user_agent;
+ALTER TABLE mcp_analytics DROP COLUMN IF EXISTS integration;
+ALTER TABLE mcp_analytics DROP COLUMN IF EXISTS user_agent;
+            "#.to_string()),
+        },
+        Migration {
+            id: "v13_feedback_source".to_string(),
+            description: "Submission channel on feedback".to_string(),
+            up_sql: r#"
+-- Where feedback was submitted from (cli, web_form, an integration name...).
+-- Rows from the HTML form already carry it in metadata, everything else is unknown.
+ALTER TABLE feedback ADD COLUMN IF NOT EXISTS source VARCHAR(32) NOT NULL DEFAULT 'unknown';
+UPDATE feedback SET source = metadata->>'source'
+WHERE source = 'unknown' AND metadata->>'source' ~ '^[a-z0-9_.-]{1,32}$';
+CREATE INDEX IF NOT EXISTS idx_feedback_source ON feedback(source, created_at);
+            "#.to_string(),
+            down_sql: Some(r#"
+DROP INDEX IF EXISTS idx_feedback_source;
+ALTER TABLE feedback DROP COLUMN IF EXISTS source;
+            "#.to_string()),
+        },
+        Migration {
+            id: "v14_event_outbox".to_string(),
+            description: "Transactional outbox for feedback side effects".to_string(),
+            up_sql: r#"
+-- Written in the same transaction as the status change that caused it, then
+-- handed to every consumer by the outbox dispatcher.
+CREATE TABLE IF NOT EXISTS outbox_events (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    event_type VARCHAR(100) NOT NULL,
+    feedback_id UUID REFERENCES feedback(id) ON DELETE CASCADE,
+    payload JSONB NOT NULL,
+    status VARCHAR(20) NOT NULL DEFAULT 'pending' CHECK (status IN ('pending', 'delivered', 'failed')),
+    attempts INTEGER NOT NULL DEFAULT 0,
+    last_error TEXT,
+    next_attempt_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    locked_until TIMESTAMPTZ,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    delivered_at TIMESTAMPTZ
+);
+CREATE INDEX IF NOT EXISTS idx_outbox_events_due ON outbox_events(next_attempt_at) WHERE status = 'pending';
+-- One row per consumer that has handled an event, committed with the consumer's own writes
+CREATE TABLE IF NOT EXISTS outbox_deliveries (
+    event_id UUID NOT NULL REFERENCES outbox_events(id) ON DELETE CASCADE,
+    consumer VARCHAR(50) NOT NULL,
+    delivered_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (event_id, consumer)
+);
+            "#.to_string(),
+            down_sql: Some(r#"
+DROP TABLE IF EXISTS outbox_deliveries;
+DROP TABLE IF EXISTS outbox_events;
+            "#.to_string()),
+        },
+        Migration {
+            id: "v15_github_installations".to_string(),
+            description: "GitHub App installations and the repositories they cover".to_string(),
+            up_sql: r#"
+-- Maintained from installation and installation_repositories webhook events.
+-- Deleted installations keep their row (status 'deleted') but lose their repositories.
+CREATE TABLE IF NOT EXISTS github_installations (
+    installation_id BIGINT PRIMARY KEY,
+    account_login VARCHAR(255) NOT NULL,
+    account_type VARCHAR(50) NOT NULL,
+    repository_selection VARCHAR(20),
+    status VARCHAR(20) NOT NULL DEFAULT 'active' CHECK (status IN ('active', 'suspended', 'deleted')),
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+CREATE TABLE IF NOT EXISTS github_installation_repositories (
+    installation_id BIGINT NOT NULL REFERENCES github_installations(installation_id) ON DELETE CASCADE,
+    repository VARCHAR(255) NOT NULL,
+    added_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (installation_id, repository)
+);
+CREATE INDEX IF NOT EXISTS idx_github_installation_repositories_repository ON github_installation_repositories(repository);
+            "#.to_string(),
+            down_sql: Some(r#"
+DROP TABLE IF EXISTS github_installation_repositories;
+DROP TABLE IF EXISTS github_installations;
+            "#.to_string()),
+        },
+        Migration {
+            id: "v16_releases".to_string(),
+            description: "Release history per product, for changelogs across versions".to_string(),
+            up_sql: r#"
+-- Versions are ordered in the application (semver precedence), not by SQL
+CREATE TABLE IF NOT EXISTS releases (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    product VARCHAR(64) NOT NULL,
+    version VARCHAR(64) NOT NULL,
+    notes TEXT,
+    features JSONB NOT NULL DEFAULT '[]',
+    yanked BOOLEAN NOT NULL DEFAULT FALSE,
+    published_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    UNIQUE(product, version)
+);
+-- The version currently advertised by /mcp/check becomes the first release
+INSERT INTO releases (product, version, notes)
+SELECT 'smart-tree', latest.value, notes.value
+FROM settings latest
+LEFT JOIN settings notes ON notes.key = 'smart_tree_release_notes'
+WHERE latest.key = 'smart_tree_latest_version'
+ON CONFLICT (product, version) DO NOTHING;
+            "#.to_string(),
+            down_sql: Some(r#"
+DROP TABLE IF EXISTS releases;
+            "#.to_string()),
+        },
+        Migration {
+            id: "v17_attachments".to_string(),
+            description: "Metadata for feedback attachments (the bytes live in blob storage)".to_string(),
+            up_sql: r#"
+-- storage_key is where the blob store keeps the bytes: <feedback id>/<attachment id>
+CREATE TABLE IF NOT EXISTS attachments (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    feedback_id UUID NOT NULL REFERENCES feedback(id) ON DELETE CASCADE,
+    filename VARCHAR(255) NOT NULL,
+    content_type VARCHAR(255) NOT NULL,
+    size_bytes BIGINT NOT NULL CHECK (size_bytes >= 0),
+    sha256 CHAR(64) NOT NULL,
+    storage_backend VARCHAR(20) NOT NULL,
+    storage_key VARCHAR(255) NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+CREATE INDEX IF NOT EXISTS idx_attachments_feedback_id ON attachments(feedback_id, created_at);
+            "#.to_string(),
+            down_sql: Some(r#"
+DROP TABLE IF EXISTS attachments;
+            "#.to_string()),
+        },
+        Migration {
+            id: "v18_automation_log".to_string(),
+            description: "Bot comments posted by issue automation, so later steps can tidy them up".to_string(),
+            up_sql: r#"
+-- comment_node_id is the GraphQL id that minimizeComment needs
+CREATE TABLE IF NOT EXISTS automation_log (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    repository VARCHAR(255) NOT NULL,
+    issue_number INTEGER NOT NULL,
+    step VARCHAR(50) NOT NULL,
+    comment_id BIGINT NOT NULL,
+    comment_node_id VARCHAR(255) NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    cleaned_up_at TIMESTAMPTZ,
+    cleanup VARCHAR(20) CHECK (cleanup IN ('minimized', 'deleted'))
+);
+CREATE INDEX IF NOT EXISTS idx_automation_log_issue ON automation_log(repository, issue_number, step);
+            "#.to_string(),
+            down_sql: Some(r#"
+DROP TABLE IF EXISTS automation_log;
+            "#.to_string()),
+        },
+        Migration {
+            id: "v19_feedback_dedup_hash".to_string(),
+            description: "Catch accidental double submits by (user, content hash)".to_string(),
+            up_sql: r#"
+-- Only the newest submission inside the dedup window holds its hash, older ones are set back to NULL
+ALTER TABLE feedback ADD COLUMN IF NOT EXISTS dedup_hash VARCHAR(64);
+CREATE UNIQUE INDEX IF NOT EXISTS idx_feedback_dedup_hash ON feedback(dedup_hash) WHERE dedup_hash IS NOT NULL;
+            "#.to_string(),
+            down_sql: Some(r#"
+DROP INDEX IF EXISTS idx_feedback_dedup_hash;
+ALTER TABLE feedback DROP COLUMN IF EXISTS dedup_hash;
+            "#.to_string()),
+        },
+        Migration {
+            id: "v20_feedback_retention".to_string(),
+            description: "Archive and rollup for completed feedback removed by retention".to_string(),
+            up_sql: r#"
+-- record is the whole feedback row (minus callback secrets) with its tags and attachment metadata
+CREATE TABLE IF NOT EXISTS feedback_archive (
+    id UUID PRIMARY KEY,
+    repository VARCHAR(255) NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL,
+    completed_at TIMESTAMPTZ,
+    archived_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    record JSONB NOT NULL
+);
+CREATE INDEX IF NOT EXISTS idx_feedback_archive_repository ON feedback_archive(repository, created_at);
+-- Removed feedback still counts: completed items per repository, source and creation day (UTC)
+CREATE TABLE IF NOT EXISTS feedback_rollup (
+    repository VARCHAR(255) NOT NULL,
+    source VARCHAR(32) NOT NULL,
+    created_date DATE NOT NULL,
+    feedback_count BIGINT NOT NULL CHECK (feedback_count >= 0),
+    PRIMARY KEY (repository, source, created_date)
+);
+            "#.to_string(),
+            down_sql: Some(r#"
+DROP TABLE IF EXISTS feedback_rollup;
+DROP TABLE IF EXISTS feedback_archive;
+            "#.to_string()),
+        },
+        Migration {
+            id: "v21_project_webhooks".to_string(),
+            description: "GitHub webhook registered for each project".to_string(),
+            up_sql: r#"
+-- webhook_id is the hook we registered on the repository, webhook_error why that failed
+ALTER TABLE projects ADD COLUMN IF NOT EXISTS webhook_id BIGINT;
+ALTER TABLE projects ADD COLUMN IF NOT EXISTS webhook_error TEXT;
+            "#.to_string(),
+            down_sql: Some(r#"
+ALTER TABLE projects DROP COLUMN IF EXISTS webhook_error;
+ALTER TABLE projects DROP COLUMN IF EXISTS webhook_id;
+            "#.to_string()),
+        },
+        Migration {
+            id: "v22_feedback_approval".to_string(),
+            description: "Hold generated changes for the project owner's approval".to_string(),
+            up_sql: r#"
+-- New enum values can be added in a transaction as long as nothing here uses them
+ALTER TYPE feedback_status ADD VALUE IF NOT EXISTS 'awaiting_approval' AFTER 'generating_changes';
+ALTER TYPE notification_type ADD VALUE IF NOT EXISTS 'approval_requested';
+
+ALTER TABLE projects ADD COLUMN IF NOT EXISTS require_approval BOOLEAN NOT NULL DEFAULT FALSE;
+
+-- The proposed changes of a held feedback item, and what became of them
+CREATE TABLE IF NOT EXISTS feedback_approvals (
+    feedback_id UUID PRIMARY KEY REFERENCES feedback(id) ON DELETE CASCADE,
+    changes JSONB NOT NULL,
+    requested_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    expires_at TIMESTAMPTZ NOT NULL,
+    decision TEXT CHECK (decision IN ('approved', 'rejected', 'expired')),
+    decided_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    decided_at TIMESTAMPTZ
+);
+CREATE INDEX IF NOT EXISTS idx_feedback_approvals_pending ON feedback_approvals(expires_at) WHERE decision IS NULL;
+            "#.to_string(),
+            // ⚠️ Enum values can't be dropped, the new statuses stay behind
+            down_sql: Some(r#"
+DROP TABLE IF EXISTS feedback_approvals;
+ALTER TABLE projects DROP COLUMN IF EXISTS require_approval;
+            "#.to_string()),
+        },
+        Migration {
+            id: "v23_feedback_import".to_string(),
+            description: "Imported historical feedback, kept for reporting only".to_string(),
+            up_sql: r#"
+-- Nothing processes imported feedback, it only counts in reports
+ALTER TYPE feedback_status ADD VALUE IF NOT EXISTS 'imported';
+
+-- Finding (or undoing) everything one import brought in
+CREATE INDEX IF NOT EXISTS idx_feedback_import_batch ON feedback ((metadata->'import'->>'batch')) WHERE source = 'import';
+            "#.to_string(),
+            // ⚠️ Enum values can't be dropped, the new status stays behind
+            down_sql: Some(r#"
+DROP INDEX IF EXISTS idx_feedback_import_batch;
+            "#.to_string()),
+        },
+        Migration {
+            id: "v24_llm_usage".to_string(),
+            description: "One row per LLM call, for the provider health scores".to_string(),
+            up_sql: r#"
+-- outcome is success, error or timeout - latency_ms is measured until the answer (or its stream) arrived
+CREATE TABLE IF NOT EXISTS llm_usage (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    provider VARCHAR(50) NOT NULL,
+    outcome VARCHAR(20) NOT NULL CHECK (outcome IN ('success', 'error', 'timeout')),
+    latency_ms BIGINT NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+CREATE INDEX IF NOT EXISTS idx_llm_usage_created_at ON llm_usage(created_at);
+            "#.to_string(),
+            down_sql: Some(r#"
+DROP TABLE IF EXISTS llm_usage;
+            "#.to_string()),
+        },
+        Migration {
+            id: "v25_api_keys".to_string(),
+            description: "Scoped API keys for the issue relay, attributed in the automation log".to_string(),
+            up_sql: r#"
+-- key_hash is the SHA-256 of the key, the key itself is only shown once
+-- allowed_repos NULL means registered projects only
+-- daily_issue_quota NULL means GITHUB_ISSUE_RELAY_DAILY_QUOTA
+-- issues_today counts issues created on issues_day (UTC)
+CREATE TABLE IF NOT EXISTS api_keys (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    name VARCHAR(255) NOT NULL,
+    key_prefix VARCHAR(16) NOT NULL,
+    key_hash CHAR(64) NOT NULL UNIQUE,
+    scopes TEXT[] NOT NULL DEFAULT '{}',
+    allowed_repos TEXT[],
+    daily_issue_quota INTEGER CHECK (daily_issue_quota >= 0),
+    issues_day DATE,
+    issues_today INTEGER NOT NULL DEFAULT 0,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    last_used_at TIMESTAMPTZ,
+    revoked_at TIMESTAMPTZ
+);
+-- Issues created through the relay are logged without a comment
+ALTER TABLE automation_log ALTER COLUMN comment_id DROP NOT NULL;
+ALTER TABLE automation_log ALTER COLUMN comment_node_id DROP NOT NULL;
+ALTER TABLE automation_log ADD COLUMN IF NOT EXISTS api_key_id UUID REFERENCES api_keys(id) ON DELETE SET NULL;
+            "#.to_string(),
+            down_sql: Some(r#"
+DELETE FROM automation_log WHERE comment_id IS NULL;
+ALTER TABLE automation_log DROP COLUMN IF EXISTS api_key_id;
+ALTER TABLE automation_log ALTER COLUMN comment_id SET NOT NULL;
+ALTER TABLE automation_log ALTER COLUMN comment_node_id SET NOT NULL;
+DROP TABLE IF EXISTS api_keys;
+            "#.to_string()),
+        },
+        Migration {
+            id: "v26_job_error_kind".to_string(),
+            description: "Why a background job failed, as a kind that can be counted".to_string(),
+            up_sql: r#"
+-- Set with error_message on every failed attempt, cleared when the job completes
+ALTER TABLE background_jobs ADD COLUMN IF NOT EXISTS error_kind VARCHAR(20)
+    CHECK (error_kind IN ('transient', 'permanent', 'rate_limited', 'validation', 'external'));
+CREATE INDEX IF NOT EXISTS idx_background_jobs_failed_kind ON background_jobs(error_kind, completed_at) WHERE status = 'failed';
+            "#.to_string(),
+            down_sql: Some(r#"
+DROP INDEX IF EXISTS idx_background_jobs_failed_kind;
+ALTER TABLE background_jobs DROP COLUMN IF EXISTS error_kind;
+            "#.to_string()),
+        },
+        Migration {
+            id: "v27_webhook_deliveries".to_string(),
+            description: "Recently accepted signed webhook deliveries, for replay protection".to_string(),
+            up_sql: r#"
+-- delivery_key is X-GitHub-Delivery, or the signature when a delivery has no id
+CREATE TABLE IF NOT EXISTS webhook_deliveries (
+    delivery_key TEXT PRIMARY KEY,
+    received_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+CREATE INDEX IF NOT EXISTS idx_webhook_deliveries_received_at ON webhook_deliveries(received_at);
+            "#.to_string(),
+            down_sql: Some(r#"
+DROP TABLE IF EXISTS webhook_deliveries;
+            "#.to_string()),
+        },
+        Migration {
+            id: "v28_project_repositories".to_string(),
+            description: "Further repositories per project, and the routes from repository to project".to_string(),
+            up_sql: r#"
+CREATE TABLE IF NOT EXISTS project_repositories (
+    project_id UUID NOT NULL REFERENCES projects(id) ON DELETE CASCADE,
+    repository VARCHAR(255) NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (project_id, repository)
+);
+CREATE INDEX IF NOT EXISTS idx_project_repositories_repository ON project_repositories(repository);
+-- every repository a project serves, its own first
+CREATE OR REPLACE VIEW project_repository_routes AS
+    SELECT id AS project_id, repository, TRUE AS is_primary FROM projects
+    UNION ALL
+    SELECT project_id, repository, FALSE AS is_primary FROM project_repositories;
+            "#.to_string(),
+            down_sql: Some(r#"
+DROP VIEW IF EXISTS project_repository_routes;
+DROP TABLE IF EXISTS project_repositories;
+            "#.to_string()),
+        },
+        Migration {
+            id: "v29_automation_log_sections".to_string(),
+            description: "The section each automation step wrote, so bot comments within the cooldown can share one comment".to_string(),
+            up_sql: r#"
+-- body is the step's text without the footer, rows sharing a comment_id are its sections
+ALTER TABLE automation_log ADD COLUMN IF NOT EXISTS body TEXT;
+CREATE INDEX IF NOT EXISTS idx_automation_log_comment_id ON automation_log(comment_id) WHERE comment_id IS NOT NULL;
+            "#.to_string(),
+            down_sql: Some(r#"
+DROP INDEX IF EXISTS idx_automation_log_comment_id;
+ALTER TABLE automation_log DROP COLUMN IF EXISTS body;
+            "#.to_string()),
+        },
+        Migration {
+            id: "v30_saved_views".to_string(),
+            description: "Named feedback list filters, per admin".to_string(),
+            up_sql: r#"
+-- owner is the admin's audit name (account email or the configured admin username)
+CREATE TABLE IF NOT EXISTS saved_views (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    owner VARCHAR(255) NOT NULL,
+    name VARCHAR(100) NOT NULL,
+    filters JSONB NOT NULL DEFAULT '{}',
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    UNIQUE (owner, name)
+);
+            "#.to_string(),
+            down_sql: Some(r#"
+DROP TABLE IF EXISTS saved_views;
+            "#.to_string()),
+        },
+        Migration {
+            id: "v31_feedback_reception".to_string(),
+            description: "Votes and duplicate reports on feedback, and the pull requests whose bodies show them".to_string(),
+            up_sql: r#"
+-- one vote per signed-in user
+CREATE TABLE IF NOT EXISTS feedback_votes (
+    feedback_id UUID NOT NULL REFERENCES feedback(id) ON DELETE CASCADE,
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (feedback_id, user_id)
+);
+ALTER TABLE feedback ADD COLUMN IF NOT EXISTS duplicate_of UUID REFERENCES feedback(id) ON DELETE SET NULL;
+CREATE INDEX IF NOT EXISTS idx_feedback_duplicate_of ON feedback(duplicate_of) WHERE duplicate_of IS NOT NULL;
+-- body_template is the PR body with its live placeholders left in, body_hash the hash of what GitHub has
+CREATE TABLE IF NOT EXISTS feedback_pull_requests (
+    feedback_id UUID PRIMARY KEY REFERENCES feedback(id) ON DELETE CASCADE,
+    repository VARCHAR(255) NOT NULL,
+    number BIGINT NOT NULL,
+    body_template TEXT NOT NULL,
+    body_hash TEXT NOT NULL,
+    closed_at TIMESTAMPTZ,
+    refreshed_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+            "#.to_string(),
+            down_sql: Some(r#"
+DROP TABLE IF EXISTS feedback_pull_requests;
+DROP INDEX IF EXISTS idx_feedback_duplicate_of;
+ALTER TABLE feedback DROP COLUMN IF EXISTS duplicate_of;
+DROP TABLE IF EXISTS feedback_votes;
+            "#.to_string()),
+        },
+        Migration {
+            id: "v32_unavailable_repositories".to_string(),
+            description: "Repositories GitHub stopped letting us at, skipped until their project is reactivated".to_string(),
+            up_sql: r#"
+-- repository is stored lowercased, feedback_id is the item that ran into it
+CREATE TABLE IF NOT EXISTS unavailable_repositories (
+    repository VARCHAR(255) PRIMARY KEY,
+    reason TEXT NOT NULL,
+    feedback_id UUID REFERENCES feedback(id) ON DELETE SET NULL,
+    detected_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+            "#.to_string(),
+            down_sql: Some(r#"
+DROP TABLE IF EXISTS unavailable_repositories;
+            "#.to_string()),
+        },
+        Migration {
+            id: "v33_processing_holds".to_string(),
+            description: "Feedback scored below its project's threshold, left pending until started by hand".to_string(),
+            up_sql: r#"
+CREATE TABLE IF NOT EXISTS processing_holds (
+    feedback_id UUID PRIMARY KEY REFERENCES feedback(id) ON DELETE CASCADE,
+    score INTEGER NOT NULL,
+    min_score INTEGER NOT NULL,
+    held_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+            "#.to_string(),
+            down_sql: Some(r#"
+DROP TABLE IF EXISTS processing_holds;
+            "#.to_string()),
+        },
+        Migration {
+            id: "v34_status_stats_view".to_string(),
+            description: "Materialized feedback and job counts for the status endpoints, refreshed every 5 minutes".to_string(),
+            up_sql: r#"
+-- REFRESH MATERIALIZED VIEW CONCURRENTLY needs a unique index over plain columns
+-- covering every row, here (metric, day, outcome). The view is created with data,
+-- so the first refresh can already be concurrent.
+CREATE MATERIALIZED VIEW IF NOT EXISTS status_stats AS
+SELECT 'feedback'::text AS metric, day, outcome, SUM(count)::bigint AS count
+FROM (
+    SELECT (created_at AT TIME ZONE 'UTC')::date AS day, status::text AS outcome, COUNT(*) AS count
+    FROM feedback
+    GROUP BY 1, 2
+    UNION ALL
+    SELECT created_date, 'completed', SUM(feedback_count)
+    FROM feedback_rollup
+    GROUP BY 1
+) feedback_days
+GROUP BY day, outcome
+UNION ALL
+SELECT 'jobs'::text, (completed_at AT TIME ZONE 'UTC')::date, status::text, COUNT(*)::bigint
+FROM background_jobs
+WHERE status IN ('completed', 'failed') AND completed_at IS NOT NULL
+GROUP BY 2, 3;
+CREATE UNIQUE INDEX IF NOT EXISTS idx_status_stats_key ON status_stats(metric, day, outcome);
+-- When each materialized view was last refreshed (written in the refresh transaction)
+CREATE TABLE IF NOT EXISTS materialized_view_refreshes (
+    view_name VARCHAR(100) PRIMARY KEY,
+    refreshed_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+INSERT INTO materialized_view_refreshes (view_name) VALUES ('status_stats')
+ON CONFLICT (view_name) DO NOTHING;
+            "#.to_string(),
+            down_sql: Some(r#"
+DROP TABLE IF EXISTS materialized_view_refreshes;
+DROP MATERIALIZED VIEW IF EXISTS status_stats;
+            "#.to_string()),
+        },
+        Migration {
+            id: "v35_admin_second_factors".to_string(),
+            description: "Per-account admin two-factor secrets, moving the console admin's out of settings".to_string(),
+            up_sql: r#"
+-- One row per admin session subject: 'config' for the bootstrap admin, otherwise the
+-- account's user id. enabled_at is set exactly while a secret is active.
+CREATE TABLE IF NOT EXISTS admin_second_factors (
+    subject VARCHAR(64) PRIMARY KEY,
+    secret TEXT,
+    pending_secret TEXT,
+    backup_codes TEXT NOT NULL DEFAULT '[]',
+    last_step BIGINT,
+    enabled_at TIMESTAMPTZ,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    CHECK ((secret IS NULL) = (enabled_at IS NULL))
+);
+INSERT INTO admin_second_factors (subject, secret, pending_secret, backup_codes, last_step, enabled_at)
+SELECT 'config',
+    (SELECT value FROM settings WHERE key = 'admin_totp_secret'),
+    (SELECT value FROM settings WHERE key = 'admin_totp_pending_secret'),
+    COALESCE((SELECT value FROM settings WHERE key = 'admin_totp_backup_codes'), '[]'),
+    (SELECT value::BIGINT FROM settings WHERE key = 'admin_totp_last_step'),
+    (SELECT updated_at FROM settings WHERE key = 'admin_totp_secret')
+WHERE EXISTS (
+    SELECT 1 FROM settings WHERE key IN ('admin_totp_secret', 'admin_totp_pending_secret')
+)
+ON CONFLICT (subject) DO NOTHING;
+DELETE FROM settings WHERE key IN (
+    'admin_totp_secret', 'admin_totp_pending_secret', 'admin_totp_backup_codes', 'admin_totp_last_step'
+);
+            "#.to_string(),
+            down_sql: Some(r#"
+INSERT INTO settings (key, value)
+SELECT k.key, k.value
+FROM admin_second_factors f
+CROSS JOIN LATERAL (VALUES
+    ('admin_totp_secret', f.secret),
+    ('admin_totp_pending_secret', f.pending_secret),
+    ('admin_totp_backup_codes', CASE WHEN f.secret IS NOT NULL THEN f.backup_codes END),
+    ('admin_totp_last_step', f.last_step::TEXT)
+) AS k(key, value)
+WHERE f.subject = 'config' AND k.value IS NOT NULL
+ON CONFLICT (key) DO UPDATE SET value = EXCLUDED.value;
+DROP TABLE IF EXISTS admin_second_factors;
+            "#.to_string()),
+        },
+        Migration {
+            id: "v36_admin_login_challenges".to_string(),
+            description: "Single-use login challenges for the admin two-factor step, with failure counts".to_string(),
+            up_sql: r#"
+-- token_hash is the SHA-256 of the token the browser holds between the password and
+-- code steps. consumed_at is set by the first right code or the last allowed wrong one.
+CREATE TABLE IF NOT EXISTS admin_login_challenges (
+    token_hash VARCHAR(64) PRIMARY KEY,
+    subject VARCHAR(64) NOT NULL,
+    failures INTEGER NOT NULL DEFAULT 0,
+    expires_at TIMESTAMPTZ NOT NULL,
+    consumed_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+CREATE INDEX IF NOT EXISTS idx_admin_login_challenges_subject ON admin_login_challenges(subject, created_at);
+            "#.to_string(),
+            down_sql: Some(r#"
+DROP TABLE IF EXISTS admin_login_challenges;
+            "#.to_string()),
+        },
+        Migration {
+            id: "v37_release_features".to_string(),
+            description: "Fill in the features of the release currently advertised by /mcp/check".to_string(),
+            up_sql: r#"
+-- Releases were recorded without their features until now. Only the latest release
+-- still has them, in the smart_tree_new_features setting.
+UPDATE releases
+SET features = features_setting.value::jsonb
+FROM settings latest, settings features_setting
+WHERE latest.key = 'smart_tree_latest_version'
+  AND features_setting.key = 'smart_tree_new_features'
+  AND releases.product = 'smart-tree'
+  AND releases.version = latest.value
+  AND releases.features = '[]'::jsonb
+  AND jsonb_typeof(features_setting.value::jsonb) = 'array';
+            "#.to_string(),
+            down_sql: Some(r#"
+UPDATE releases
+SET features = '[]'::jsonb
+FROM settings latest
+WHERE latest.key = 'smart_tree_latest_version'
+  AND releases.product = 'smart-tree'
+  AND releases.version = latest.value;
+            "#.to_string()),
+        },
+        Migration {
+            id: "v38_backfill_feedback_priority".to_string(),
+            description: "Give feedback from before priorities the default priority instead of 0".to_string(),
+            up_sql: r#"
+-- Feedback created before v8 was left at 0, below every new submission (which gets at
+-- least FEEDBACK_DEFAULT_PRIORITY, 25 out of the box). Their scores were never stored,
+-- so they get the default, and their pending callbacks follow as new ones would.
+UPDATE feedback
+SET priority = 25
+WHERE priority = 0
+  AND created_at < (SELECT applied_at FROM migrations WHERE id = 'v8_feedback_priority');
+UPDATE background_jobs
+SET priority = feedback.priority
+FROM feedback
+WHERE background_jobs.job_type = 'feedback_callback'
+  AND background_jobs.status = 'pending'
+  AND background_jobs.priority = 0
+  AND background_jobs.created_at < (SELECT applied_at FROM migrations WHERE id = 'v8_feedback_priority')
+  AND feedback.id::text = background_jobs.payload->'body'->>'feedback_id';
+            "#.to_string(),
+            down_sql: Some(r#"
+UPDATE background_jobs
+SET priority = 0
+WHERE job_type = 'feedback_callback'
+  AND status = 'pending'
+  AND priority = 25
+  AND created_at < (SELECT applied_at FROM migrations WHERE id = 'v8_feedback_priority');
+UPDATE feedback
+SET priority = 0
+WHERE priority = 25
+  AND created_at < (SELECT applied_at FROM migrations WHERE id = 'v8_feedback_priority');
+            "#.to_string()),
+        },
     ]
 }
 
-/// 🔙 Rollback (for development)
+/// 📊 One known migration and when (if ever) it was applied
+#[derive(Debug, Clone)]
+pub struct MigrationState {
+    pub id: String,
+    pub description: String,
+    pub applied_at: Option<chrono::DateTime<chrono::Utc>>,
+    /// ⏳ Not applied yet
+    pub pending: bool,
+    /// 🔢 Whether the recorded checksum still matches this binary's SQL (None while pending).
+    /// `false` means the migration was edited after it ran - the schema may not be what the code expects.
+    pub checksum_matches: Option<bool>,
+    /// 🔙 Whether it ships down SQL and can be rolled back
+    pub reversible: bool,
+}
+
+impl MigrationState {
+    /// ⚠️ Applied, but with different SQL than this binary ships
+    pub fn has_drifted(&self) -> bool {
+        self.checksum_matches == Some(false)
+    }
+}
+
+/// 📊 Every known migration in apply order, with its applied state.
+/// A database without the tracking table simply has nothing applied yet.
+pub async fn migration_status(pool: &PgPool) -> Result<Vec<MigrationState>> {
+    let tracked: bool = sqlx::query_scalar("SELECT to_regclass('migrations') IS NOT NULL")
+        .fetch_one(pool)
+        .await
+        .context("Failed to check for the migrations table")?;
+    let applied: std::collections::HashMap<String, (chrono::DateTime<chrono::Utc>, String)> =
+        if tracked {
+            sqlx::query_as::<_, (String, chrono::DateTime<chrono::Utc>, String)>(
+                "SELECT id, applied_at, checksum FROM migrations",
+            )
+            .fetch_all(pool)
+            .await
+            .context("Failed to fetch applied migrations")?
+            .into_iter()
+            .map(|(id, applied_at, checksum)| (id, (applied_at, checksum)))
+            .collect()
+        } else {
+            Default::default()
+        };
+
+    Ok(get_all_migrations()
+        .into_iter()
+        .map(|migration| {
+            let record = applied.get(&migration.id);
+            MigrationState {
+                applied_at: record.map(|(applied_at, _)| *applied_at),
+                pending: record.is_none(),
+                checksum_matches: record
+                    .map(|(_, checksum)| *checksum == calculate_checksum(&migration.up_sql)),
+                reversible: migration.down_sql.is_some(),
+                id: migration.id,
+                description: migration.description,
+            }
+        })
+        .collect())
+}
+
+/// 📋 Migrations that have not been applied yet, in apply order
+pub async fn pending_migrations(pool: &PgPool) -> Result<Vec<Migration>> {
+    let status = migration_status(pool).await?;
+    Ok(get_all_migrations()
+        .into_iter()
+        .filter(|migration| {
+            status
+                .iter()
+                .any(|state| state.id == migration.id && state.pending)
+        })
+        .collect())
+}
+
+/// 🔙 Rollback an applied migration using its down SQL
 pub async fn rollback_migration(pool: &PgPool, migration_id: &str) -> Result<()> {
     warn!("⚠️ Rolling back migration: {}", migration_id);
 
@@ -376,6 +1289,14 @@ pub async fn rollback_migration(pool: &PgPool, migration_id: &str) -> Result<()>
         .find(|m| m.id == migration_id)
         .context("Migration not found")?;
 
+    let applied = migration_status(pool)
+        .await?
+        .iter()
+        .any(|state| state.id == migration_id && state.applied_at.is_some());
+    if !applied {
+        anyhow::bail!("Migration {} has not been applied", migration_id);
+    }
+
     if let Some(down_sql) = &migration.down_sql {
         let mut tx = pool.begin().await?;
 
@@ -393,6 +1314,11 @@ pub async fn rollback_migration(pool: &PgPool, migration_id: &str) -> Result<()>
 
         tx.commit().await?;
         info!("✅ Rolled back {}", migration_id);
+    } else {
+        anyhow::bail!(
+            "Migration {} has no down SQL and cannot be rolled back",
+            migration_id
+        );
     }
 
     Ok(())
@@ -1,6 +1,8 @@
 // 🗄️ Database Module - The Data Storage Heart of Feedbacker! 🗄️
 // This module handles all database operations, connections, and migrations
 // Built with SQLx for async performance and safety - Trisha loves type safety! 📊
+// PostgreSQL only: SQLite self-hosting is de-scoped until the handlers, stats and
+// job claiming can run behind a dialect layer.
 // Created with love by Aye & Hue - Making data management as smooth as silk! ✨
 
 use anyhow::{Context, Result};
@@ -9,8 +11,19 @@ use std::time::Duration;
 use tracing::{info, warn};
 
 // 📦 Re-export modules for easy access
+pub mod api_keys;
+pub mod automation_log;
+pub mod feedback_votes;
 pub mod migrations;
 pub mod models;
+pub mod processing_holds;
+pub mod project_config;
+pub mod project_repositories;
+pub mod saved_views;
+pub mod second_factors;
+pub mod startup;
+pub mod tx;
+pub mod webhook_deliveries;
 
 // 🔄 Re-export commonly used types
 pub use models::*;
@@ -65,6 +78,20 @@ pub async fn run_migrations(pool: &PgPool) -> Result<()> {
     Ok(())
 }
 
+/// 🛑 Refuse to start on a schema that is behind (used when AUTO_MIGRATE is off)
+pub async fn ensure_no_pending_migrations(pool: &PgPool) -> Result<()> {
+    let pending = migrations::pending_migrations(pool).await?;
+    if !pending.is_empty() {
+        let ids: Vec<&str> = pending.iter().map(|m| m.id.as_str()).collect();
+        anyhow::bail!(
+            "Database has {} pending migrations ({}) - run `feedbacker migrate run` or set AUTO_MIGRATE=true",
+            pending.len(),
+            ids.join(", ")
+        );
+    }
+    Ok(())
+}
+
 /// 🔍 Check database connection health
 /// Perfect for health checks and monitoring!
 pub async fn check_connection_health(pool: &PgPool) -> Result<bool> {
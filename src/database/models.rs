@@ -7,7 +7,7 @@
 use anyhow::{Context, Result};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
-use sqlx::{FromRow, PgPool};
+use sqlx::{Connection, FromRow, PgConnection, PgPool};
 use uuid::Uuid;
 
 // 📝 Feedback Model - The heart of our system!
@@ -40,11 +40,21 @@ pub struct Feedback {
     pub updated_at: DateTime<Utc>,
     /// ✅ When processing was completed (if applicable)
     pub completed_at: Option<DateTime<Utc>>,
+    /// 📞 Where to POST the final status (optional, validated at submission)
+    pub callback_url: Option<String>,
+    /// 🔏 Secret used to sign callback payloads (never serialized)
+    #[serde(skip_serializing, default)]
+    pub callback_secret: Option<String>,
+    /// 🔝 Effective queue priority (higher is handled first)
+    pub priority: i32,
+    /// 📡 Submission channel (cli, web_form, ... or "unknown")
+    pub source: String,
 }
 
 // 📋 Feedback Status Enum - Track where we are in the process!
-#[derive(Debug, Clone, Serialize, Deserialize, sqlx::Type)]
-#[sqlx(type_name = "feedback_status", rename_all = "lowercase")]
+// Hand-rolled sqlx/serde impls so a status added to the database enum before
+// this binary knows about it decodes as `Unknown` instead of failing the row.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum FeedbackStatus {
     /// 📥 Just received, waiting for processing
     Pending,
@@ -52,6 +62,8 @@ pub enum FeedbackStatus {
     Processing,
     /// 🤖 AI analysis complete, creating GitHub changes
     GeneratingChanges,
+    /// ✋ Changes generated, waiting for the project owner to approve them
+    AwaitingApproval,
     /// 🐙 Creating branch and pull request
     CreatingPullRequest,
     /// ✅ Successfully completed with PR created
@@ -60,6 +72,136 @@ pub enum FeedbackStatus {
     Failed,
     /// ⏸️ Paused (waiting for user input or manual intervention)
     Paused,
+    /// 📦 Historical feedback brought in by an admin import (never processed)
+    Imported,
+    /// ❓ A database value this build doesn't know yet (kept verbatim)
+    Unknown(String),
+}
+
+impl FeedbackStatus {
+    /// 📚 Every status this build knows about, in pipeline order
+    pub const KNOWN: [FeedbackStatus; 9] = [
+        FeedbackStatus::Pending,
+        FeedbackStatus::Processing,
+        FeedbackStatus::GeneratingChanges,
+        FeedbackStatus::AwaitingApproval,
+        FeedbackStatus::CreatingPullRequest,
+        FeedbackStatus::Completed,
+        FeedbackStatus::Failed,
+        FeedbackStatus::Paused,
+        FeedbackStatus::Imported,
+    ];
+
+    /// 🏷️ Value as stored in the `feedback_status` database enum
+    pub fn as_str(&self) -> &str {
+        match self {
+            FeedbackStatus::Pending => "pending",
+            FeedbackStatus::Processing => "processing",
+            FeedbackStatus::GeneratingChanges => "generating_changes",
+            FeedbackStatus::AwaitingApproval => "awaiting_approval",
+            FeedbackStatus::CreatingPullRequest => "creating_pull_request",
+            FeedbackStatus::Completed => "completed",
+            FeedbackStatus::Failed => "failed",
+            FeedbackStatus::Paused => "paused",
+            FeedbackStatus::Imported => "imported",
+            FeedbackStatus::Unknown(value) => value,
+        }
+    }
+
+    /// 🗄️ Parse a database value; anything unrecognised becomes `Unknown`
+    pub fn from_db(value: &str) -> Self {
+        Self::KNOWN
+            .iter()
+            .find(|status| status.as_str() == value)
+            .cloned()
+            .unwrap_or_else(|| FeedbackStatus::Unknown(value.to_string()))
+    }
+
+    /// 🎨 CSS class for the status badge (see admin.css)
+    pub fn css_class(&self) -> &'static str {
+        match self {
+            FeedbackStatus::Pending | FeedbackStatus::Paused | FeedbackStatus::AwaitingApproval => {
+                "status-pending"
+            }
+            FeedbackStatus::Processing
+            | FeedbackStatus::GeneratingChanges
+            | FeedbackStatus::CreatingPullRequest => "status-processing",
+            FeedbackStatus::Completed => "status-completed",
+            FeedbackStatus::Failed => "status-failed",
+            FeedbackStatus::Imported => "status-imported",
+            FeedbackStatus::Unknown(_) => "status-unknown",
+        }
+    }
+
+    /// 🏁 Is this a final state (no more processing will happen)?
+    pub fn is_terminal(&self) -> bool {
+        matches!(self, FeedbackStatus::Completed | FeedbackStatus::Failed)
+    }
+}
+
+impl std::fmt::Display for FeedbackStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// 🚫 Parsing client input is strict - unknown values are rejected
+impl std::str::FromStr for FeedbackStatus {
+    type Err = anyhow::Error;
+
+    fn from_str(value: &str) -> Result<Self> {
+        match Self::from_db(value) {
+            FeedbackStatus::Unknown(value) => {
+                anyhow::bail!("Unknown feedback status: {}", value)
+            }
+            status => Ok(status),
+        }
+    }
+}
+
+impl Serialize for FeedbackStatus {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for FeedbackStatus {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = String::deserialize(deserializer)?;
+        value.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+impl sqlx::Type<sqlx::Postgres> for FeedbackStatus {
+    fn type_info() -> sqlx::postgres::PgTypeInfo {
+        sqlx::postgres::PgTypeInfo::with_name("feedback_status")
+    }
+
+    fn compatible(ty: &sqlx::postgres::PgTypeInfo) -> bool {
+        *ty == Self::type_info() || <&str as sqlx::Type<sqlx::Postgres>>::compatible(ty)
+    }
+}
+
+impl sqlx::postgres::PgHasArrayType for FeedbackStatus {
+    fn array_type_info() -> sqlx::postgres::PgTypeInfo {
+        sqlx::postgres::PgTypeInfo::with_name("_feedback_status")
+    }
+}
+
+impl<'r> sqlx::Decode<'r, sqlx::Postgres> for FeedbackStatus {
+    fn decode(value: sqlx::postgres::PgValueRef<'r>) -> Result<Self, sqlx::error::BoxDynError> {
+        let value = <&str as sqlx::Decode<sqlx::Postgres>>::decode(value)?;
+        Ok(Self::from_db(value))
+    }
+}
+
+impl sqlx::Encode<'_, sqlx::Postgres> for FeedbackStatus {
+    fn encode_by_ref(
+        &self,
+        buf: &mut sqlx::postgres::PgArgumentBuffer,
+    ) -> Result<sqlx::encode::IsNull, sqlx::error::BoxDynError> {
+        <&str as sqlx::Encode<sqlx::Postgres>>::encode_by_ref(&self.as_str(), buf)
+    }
 }
 
 // 👤 User Model - Our amazing users who provide feedback!
@@ -90,8 +232,9 @@ pub struct User {
 }
 
 // 👑 User Role Enum - Different levels of access
-#[derive(Debug, Clone, Serialize, Deserialize, sqlx::Type)]
-#[sqlx(type_name = "user_role", rename_all = "lowercase")]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, sqlx::Type)]
+#[serde(rename_all = "snake_case")]
+#[sqlx(type_name = "user_role", rename_all = "snake_case")]
 pub enum UserRole {
     /// 🎯 Regular user
     User,
@@ -101,6 +244,13 @@ pub enum UserRole {
     Service,
 }
 
+impl UserRole {
+    /// 🔧 May this role sign in to the admin console?
+    pub fn can_administer(&self) -> bool {
+        matches!(self, UserRole::Admin | UserRole::Service)
+    }
+}
+
 // 🏠 Project Model - GitHub repositories we manage
 #[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
 pub struct Project {
@@ -189,7 +339,8 @@ pub struct Notification {
 
 // 🔔 Notification Type Enum
 #[derive(Debug, Clone, Serialize, Deserialize, sqlx::Type)]
-#[sqlx(type_name = "notification_type", rename_all = "lowercase")]
+#[serde(rename_all = "snake_case")]
+#[sqlx(type_name = "notification_type", rename_all = "snake_case")]
 pub enum NotificationType {
     /// ✅ Feedback processing completed
     FeedbackCompleted,
@@ -201,69 +352,214 @@ pub enum NotificationType {
     SystemUpdate,
     /// ⚠️ Warning or important notice
     Warning,
+    /// ✋ Generated changes wait for the project owner's approval
+    ApprovalRequested,
 }
 
 // 🏭 Implementation blocks for our models
 impl Feedback {
     /// ➕ Create a new feedback record
+    /// A callback secret is generated whenever a callback URL is given.
+    #[allow(clippy::too_many_arguments)]
     pub async fn create(
         pool: &PgPool,
         user_id: Option<Uuid>,
         repository: String,
         content: String,
+        callback_url: Option<String>,
+        priority: i32,
+        metadata: Option<serde_json::Value>,
+        source: &str,
     ) -> Result<Self> {
-        let id = Uuid::new_v4();
-        let now = Utc::now();
+        let mut conn = pool
+            .acquire()
+            .await
+            .context("Failed to acquire connection")?;
+        Self::create_in(
+            &mut conn,
+            user_id,
+            repository,
+            content,
+            callback_url,
+            priority,
+            metadata,
+            source,
+        )
+        .await
+    }
 
-        // TODO: Implement proper query when database is set up
-        // For now, return a placeholder feedback object
-        let feedback = Feedback {
-            id,
+    /// ➕ `create` on the caller's connection, so the record commits with its transaction
+    #[allow(clippy::too_many_arguments)]
+    pub async fn create_in(
+        conn: &mut PgConnection,
+        user_id: Option<Uuid>,
+        repository: String,
+        content: String,
+        callback_url: Option<String>,
+        priority: i32,
+        metadata: Option<serde_json::Value>,
+        source: &str,
+    ) -> Result<Self> {
+        Self::insert(
+            conn,
             user_id,
             repository,
             content,
-            status: FeedbackStatus::Pending,
-            branch_name: None,
-            pull_request_url: None,
-            llm_provider: None,
-            metadata: None,
-            error_message: None,
-            created_at: now,
-            updated_at: now,
-            completed_at: None,
+            callback_url,
+            priority,
+            metadata,
+            source,
+            None,
+        )
+        .await?
+        .context("Failed to insert feedback")
+    }
+
+    /// 🔁 Create a feedback record unless one with the same `dedup_hash` was created
+    /// within `window`. Returns the new record and false, or the earlier one and true.
+    /// Only the newest record in the window holds the hash, so the partial unique index
+    /// settles two racing double submits: the loser's insert does nothing and it reads
+    /// the winner back. Inside the caller's transaction this runs in a savepoint.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn create_deduplicated(
+        conn: &mut PgConnection,
+        user_id: Option<Uuid>,
+        repository: String,
+        content: String,
+        callback_url: Option<String>,
+        priority: i32,
+        metadata: Option<serde_json::Value>,
+        source: &str,
+        dedup_hash: &str,
+        window: chrono::Duration,
+    ) -> Result<(Self, bool)> {
+        let mut tx = conn
+            .begin()
+            .await
+            .context("Failed to start feedback transaction")?;
+
+        // ⏰ Submissions older than the window give their hash up
+        sqlx::query(
+            "UPDATE feedback SET dedup_hash = NULL WHERE dedup_hash = $1 AND created_at <= $2",
+        )
+        .bind(dedup_hash)
+        .bind(Utc::now() - window)
+        .execute(&mut *tx)
+        .await
+        .context("Failed to release expired dedup hash")?;
+
+        let created = Self::insert(
+            &mut tx,
+            user_id,
+            repository,
+            content,
+            callback_url,
+            priority,
+            metadata,
+            source,
+            Some(dedup_hash),
+        )
+        .await?;
+        let result = match created {
+            Some(feedback) => (feedback, false),
+            None => {
+                let earlier =
+                    sqlx::query_as::<_, Feedback>("SELECT * FROM feedback WHERE dedup_hash = $1")
+                        .bind(dedup_hash)
+                        .fetch_one(&mut *tx)
+                        .await
+                        .context("Failed to load duplicate feedback")?;
+                (earlier, true)
+            }
         };
+        tx.commit().await.context("Failed to commit feedback")?;
+        Ok(result)
+    }
 
-        Ok(feedback)
+    /// ➕ INSERT shared by `create` and `create_deduplicated` (None when the hash is taken)
+    #[allow(clippy::too_many_arguments)]
+    async fn insert(
+        conn: &mut PgConnection,
+        user_id: Option<Uuid>,
+        repository: String,
+        content: String,
+        callback_url: Option<String>,
+        priority: i32,
+        metadata: Option<serde_json::Value>,
+        source: &str,
+        dedup_hash: Option<&str>,
+    ) -> Result<Option<Self>> {
+        let callback_secret = callback_url.as_ref().map(|_| generate_callback_secret());
+
+        sqlx::query_as::<_, Feedback>(
+            r#"
+            INSERT INTO feedback (user_id, repository, content, callback_url, callback_secret, priority, metadata, source, dedup_hash)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+            ON CONFLICT (dedup_hash) WHERE dedup_hash IS NOT NULL DO NOTHING
+            RETURNING *
+            "#,
+        )
+        .bind(user_id)
+        .bind(repository)
+        .bind(content)
+        .bind(callback_url)
+        .bind(callback_secret)
+        .bind(priority)
+        .bind(metadata)
+        .bind(source)
+        .bind(dedup_hash)
+        .fetch_optional(conn)
+        .await
+        .context("Failed to insert feedback")
     }
 
     /// 🔍 Find feedback by ID
     pub async fn find_by_id(pool: &PgPool, id: Uuid) -> Result<Option<Self>> {
-        // TODO: Implement proper query when database is set up
-        let feedback: Option<Feedback> = None;
-
-        Ok(feedback)
+        sqlx::query_as::<_, Feedback>("SELECT * FROM feedback WHERE id = $1")
+            .bind(id)
+            .fetch_optional(pool)
+            .await
+            .context("Failed to fetch feedback")
     }
 
     /// 🔄 Update feedback status
+    /// Reaching a terminal state writes an outbox event in the same transaction; the
+    /// outbox dispatcher turns it into the callback, notifications and ops alerts.
     pub async fn update_status(
         &mut self,
         pool: &PgPool,
         status: FeedbackStatus,
         error_message: Option<String>,
     ) -> Result<()> {
-        let now = Utc::now();
-        let completed_at = if matches!(status, FeedbackStatus::Completed | FeedbackStatus::Failed) {
-            Some(now)
-        } else {
-            None
-        };
-
-        // TODO: Implement proper query when database is set up
-
-        self.status = status;
-        self.error_message = error_message;
-        self.updated_at = now;
-        self.completed_at = completed_at;
+        let mut tx = pool
+            .begin()
+            .await
+            .context("Failed to start feedback status transaction")?;
+        let updated = sqlx::query_as::<_, Feedback>(
+            r#"
+            UPDATE feedback
+            SET status = $2,
+                error_message = $3,
+                completed_at = CASE WHEN $4 THEN NOW() ELSE NULL END
+            WHERE id = $1
+            RETURNING *
+            "#,
+        )
+        .bind(self.id)
+        .bind(&status)
+        .bind(&error_message)
+        .bind(status.is_terminal())
+        .fetch_one(&mut *tx)
+        .await
+        .context("Failed to update feedback status")?;
+
+        if updated.status.is_terminal() {
+            crate::jobs::outbox::record_status_change(&mut tx, &updated).await?;
+        }
+        tx.commit()
+            .await
+            .context("Failed to commit feedback status change")?;
+        *self = updated;
 
         Ok(())
     }
@@ -281,6 +577,14 @@ impl Feedback {
     }
 }
 
+/// 🔏 Random 32-byte hex secret for signing callbacks
+pub(crate) fn generate_callback_secret() -> String {
+    use rand::RngCore;
+    let mut bytes = [0u8; 32];
+    rand::thread_rng().fill_bytes(&mut bytes);
+    hex::encode(bytes)
+}
+
 // 📊 Feedback Statistics Structure
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FeedbackStats {
@@ -327,6 +631,28 @@ impl User {
 
         Ok(user)
     }
+
+    /// 🔍 Find an active user by id (None when missing or deactivated)
+    pub async fn find_active_by_id(pool: &PgPool, id: Uuid) -> Result<Option<Self>> {
+        sqlx::query_as("SELECT * FROM users WHERE id = $1 AND is_active")
+            .bind(id)
+            .fetch_optional(pool)
+            .await
+            .context("Failed to load user")
+    }
+
+    /// 🔍 Find an active user by email address or GitHub username (case-insensitive)
+    pub async fn find_active_by_handle(pool: &PgPool, handle: &str) -> Result<Option<Self>> {
+        sqlx::query_as(
+            "SELECT * FROM users WHERE is_active \
+             AND (LOWER(email) = LOWER($1) OR LOWER(github_username) = LOWER($1)) \
+             ORDER BY LOWER(email) = LOWER($1) DESC LIMIT 1",
+        )
+        .bind(handle.trim())
+        .fetch_optional(pool)
+        .await
+        .context("Failed to look up user")
+    }
 }
 
 impl Project {
@@ -372,6 +698,74 @@ mod tests {
         println!("✅ Feedback status serialization test passed!");
     }
 
+    #[test]
+    fn test_unknown_feedback_status_is_kept_verbatim() {
+        let status = FeedbackStatus::from_db("awaiting_review");
+        assert_eq!(
+            status,
+            FeedbackStatus::Unknown("awaiting_review".to_string())
+        );
+        assert_eq!(status.to_string(), "awaiting_review");
+        assert_eq!(status.css_class(), "status-unknown");
+        assert_eq!(
+            serde_json::to_string(&status).unwrap(),
+            "\"awaiting_review\""
+        );
+        // 🚫 ...but clients can't send one
+        assert!(serde_json::from_str::<FeedbackStatus>("\"awaiting_review\"").is_err());
+        assert_eq!(
+            serde_json::from_str::<FeedbackStatus>("\"generating_changes\"").unwrap(),
+            FeedbackStatus::GeneratingChanges
+        );
+        println!("✅ Unknown feedback status test passed!");
+    }
+
+    #[test]
+    fn test_feedback_status_matches_migration_enum() {
+        // 🔍 Pull the enum values out of the migration SQL so drift fails here
+        let migrations = crate::database::migrations::get_all_migrations();
+        let marker = "CREATE TYPE feedback_status AS ENUM (";
+        let sql = migrations
+            .iter()
+            .find_map(|m| m.up_sql.find(marker).map(|i| &m.up_sql[i + marker.len()..]))
+            .expect("feedback_status enum missing from migrations");
+        let mut db_values: Vec<String> = sql[..sql.find(')').unwrap()]
+            .split(',')
+            .map(|v| v.trim().trim_matches('\'').to_string())
+            .collect();
+        // ➕ Values added later with ALTER TYPE ... ADD VALUE count too
+        for m in &migrations {
+            for line in m.up_sql.lines() {
+                if let Some(rest) = line
+                    .trim()
+                    .strip_prefix("ALTER TYPE feedback_status ADD VALUE")
+                {
+                    let rest = rest.trim().trim_start_matches("IF NOT EXISTS").trim();
+                    let rest = rest.trim_end_matches(';');
+                    let (value, after) = match rest.split_once(" AFTER ") {
+                        Some((value, after)) => (value, Some(after.trim().trim_matches('\''))),
+                        None => (rest, None),
+                    };
+                    let value = value.trim().trim_matches('\'').to_string();
+                    match after.and_then(|after| db_values.iter().position(|v| v == after)) {
+                        Some(index) => db_values.insert(index + 1, value),
+                        None => db_values.push(value),
+                    }
+                }
+            }
+        }
+
+        let rust_values: Vec<String> = FeedbackStatus::KNOWN
+            .iter()
+            .map(|s| s.as_str().to_string())
+            .collect();
+        assert_eq!(rust_values, db_values);
+        for status in &FeedbackStatus::KNOWN {
+            assert_ne!(status.css_class(), "status-unknown");
+        }
+        println!("✅ Feedback status / migration drift test passed!");
+    }
+
     #[test]
     fn test_user_role_serialization() {
         let role = UserRole::Admin;
@@ -0,0 +1,204 @@
+// ⏸️ Processing Holds - Trivial feedback waits for a human! ⏸️
+// A project can set `processing.min_score` in its config. Feedback whose impact ×
+// frequency falls below it is stored as usual but held in `pending`, so no LLM or
+// GitHub calls are spent on it until someone presses "Release hold" in the admin
+// console. Feedback without both scores is never held - there's nothing to compare.
+// Created with love by Aye & Hue! ✨
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use sqlx::{PgConnection, PgPool};
+use uuid::Uuid;
+
+use crate::database::project_config::ProjectConfig;
+
+/// 📏 Why a feedback item is waiting
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, sqlx::FromRow)]
+pub struct ProcessingHold {
+    /// 💥 Its impact × frequency
+    pub score: i32,
+    /// 📏 The project's threshold when it was submitted
+    pub min_score: i32,
+}
+
+/// ⏸️ A held feedback item, as the admin console lists it
+#[derive(Debug, Clone, Serialize, sqlx::FromRow)]
+pub struct HeldFeedback {
+    pub feedback_id: Uuid,
+    pub repository: String,
+    pub content: String,
+    pub score: i32,
+    pub min_score: i32,
+    pub held_at: DateTime<Utc>,
+}
+
+/// 📏 The hold `score` earns under a project's config (None: process it)
+pub fn threshold_hold(config: &ProjectConfig, score: i32) -> Option<ProcessingHold> {
+    config
+        .processing
+        .min_score
+        .filter(|min_score| score < *min_score)
+        .map(|min_score| ProcessingHold { score, min_score })
+}
+
+/// ⏸️ Hold freshly stored feedback when its score is below its project's threshold.
+/// Returns the hold, or None when the feedback may be processed right away. Run it in
+/// the transaction that stores the feedback, so no worker sees the item unheld.
+pub async fn hold_if_below_threshold(
+    conn: &mut PgConnection,
+    feedback_id: Uuid,
+    repository: &str,
+    score: i32,
+) -> Result<Option<ProcessingHold>> {
+    let Some(config) = ProjectConfig::for_repository(&mut *conn, repository).await? else {
+        return Ok(None);
+    };
+    let Some(hold) = threshold_hold(&config, score) else {
+        return Ok(None);
+    };
+    sqlx::query(
+        "INSERT INTO processing_holds (feedback_id, score, min_score) VALUES ($1, $2, $3) \
+         ON CONFLICT (feedback_id) DO NOTHING",
+    )
+    .bind(feedback_id)
+    .bind(hold.score)
+    .bind(hold.min_score)
+    .execute(conn)
+    .await
+    .context("Failed to hold feedback below the threshold")?;
+    Ok(Some(hold))
+}
+
+/// 🔍 The hold on a feedback item, if any
+pub async fn find(pool: &PgPool, feedback_id: Uuid) -> Result<Option<ProcessingHold>> {
+    sqlx::query_as("SELECT score, min_score FROM processing_holds WHERE feedback_id = $1")
+        .bind(feedback_id)
+        .fetch_optional(pool)
+        .await
+        .context("Failed to load processing hold")
+}
+
+/// ▶️ Let a held item be processed ("Release hold"); returns its hold, or None
+/// when it wasn't held
+pub async fn release(pool: &PgPool, feedback_id: Uuid) -> Result<Option<ProcessingHold>> {
+    sqlx::query_as("DELETE FROM processing_holds WHERE feedback_id = $1 RETURNING score, min_score")
+        .bind(feedback_id)
+        .fetch_optional(pool)
+        .await
+        .context("Failed to release processing hold")
+}
+
+/// 📋 Held feedback still pending, oldest first
+pub async fn list(pool: &PgPool, limit: i64) -> Result<Vec<HeldFeedback>> {
+    sqlx::query_as(
+        r#"
+        SELECT h.feedback_id, f.repository, f.content, h.score, h.min_score, h.held_at
+        FROM processing_holds h
+        JOIN feedback f ON f.id = h.feedback_id
+        WHERE f.status = 'pending'
+        ORDER BY h.held_at, h.feedback_id
+        LIMIT $1
+        "#,
+    )
+    .bind(limit)
+    .fetch_all(pool)
+    .await
+    .context("Failed to list held feedback")
+}
+
+// 🧪 Tests - Small stuff waits its turn!
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::models::Feedback;
+    use crate::test_support::spawn_test_app;
+    use serde_json::json;
+
+    #[test]
+    fn test_only_scores_below_the_threshold_are_held() {
+        let config =
+            ProjectConfig::from_value(json!({ "processing": { "min_score": 12 } })).unwrap();
+        assert_eq!(
+            threshold_hold(&config, 6),
+            Some(ProcessingHold {
+                score: 6,
+                min_score: 12
+            })
+        );
+        assert_eq!(threshold_hold(&config, 12), None);
+        assert_eq!(threshold_hold(&ProjectConfig::default(), 1), None);
+        println!("✅ Threshold hold test passed!");
+    }
+
+    #[tokio::test]
+    async fn test_held_feedback_is_listed_until_released() {
+        let Some(app) = spawn_test_app().await else {
+            return;
+        };
+        let pool = &app.db_pool;
+        let owner_id: Uuid = sqlx::query_scalar(
+            "INSERT INTO users (email, name, password_hash) VALUES ('owner@8b.is', 'owner', 'x') RETURNING id",
+        )
+        .fetch_one(pool)
+        .await
+        .unwrap();
+        sqlx::query("INSERT INTO projects (owner_id, repository, config) VALUES ($1, '8b-is/smart-tree', $2)")
+            .bind(owner_id)
+            .bind(json!({ "processing": { "min_score": 20 } }))
+            .execute(pool)
+            .await
+            .unwrap();
+        let submit = |repository: &str| {
+            Feedback::create(
+                pool,
+                None,
+                repository.to_string(),
+                "Typo in the README".to_string(),
+                None,
+                4,
+                None,
+                "cli",
+            )
+        };
+
+        let mut conn = pool.acquire().await.unwrap();
+        let trivial = submit("8b-is/smart-tree").await.unwrap();
+        let hold = hold_if_below_threshold(&mut conn, trivial.id, "8b-is/smart-tree", 4)
+            .await
+            .unwrap();
+        assert_eq!(
+            hold,
+            Some(ProcessingHold {
+                score: 4,
+                min_score: 20
+            })
+        );
+        let important = submit("8b-is/smart-tree").await.unwrap();
+        assert_eq!(
+            hold_if_below_threshold(&mut conn, important.id, "8b-is/smart-tree", 40)
+                .await
+                .unwrap(),
+            None
+        );
+        // 🤷 No project, no threshold
+        let elsewhere = submit("8b-is/elsewhere").await.unwrap();
+        assert_eq!(
+            hold_if_below_threshold(&mut conn, elsewhere.id, "8b-is/elsewhere", 1)
+                .await
+                .unwrap(),
+            None
+        );
+
+        let held = list(pool, 10).await.unwrap();
+        assert_eq!(held.len(), 1);
+        assert_eq!(held[0].feedback_id, trivial.id);
+        assert_eq!(find(pool, trivial.id).await.unwrap(), hold);
+
+        // ▶️ Released once, then it's an ordinary pending item
+        assert_eq!(release(pool, trivial.id).await.unwrap(), hold);
+        assert_eq!(release(pool, trivial.id).await.unwrap(), None);
+        assert!(list(pool, 10).await.unwrap().is_empty());
+        println!("✅ Held feedback listing test passed!");
+    }
+}
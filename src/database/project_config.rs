@@ -0,0 +1,907 @@
+// 🗂️ Project Config - The versioned shape of `projects.config`! 🗂️
+// Every stored config carries a `config_version`. Reading one runs it through the
+// upgrade chain (v1 → v2 → ... → CURRENT_CONFIG_VERSION) so new code never guesses
+// at an old layout. A config written by a newer build (say, before a rollback) is
+// refused with `ConfigTooNew` rather than misread, and automation for that project
+// is skipped until a build that understands it is back.
+// Created with love by Aye & Hue! ✨
+//
+// Versions:
+//   v1 (no `config_version`): flat `allowed_paths`, `denied_paths`, `comment_footer`
+//   v2: path globs grouped as `paths: { allowed, denied }`
+//   v3: comment settings grouped as `comments: { footer }` (null still disables it)
+//
+// Optional sections added without a version bump (older builds keep them in `other`):
+//   `schedule: { timezone, start, end, days }` - business hours for the welcome comment, in an
+//     IANA zone or a fixed offset (an `end` before `start` runs overnight)
+//   `pull_requests: { check_protection, auto_merge, body_template }` - how generated PRs treat
+//     the base branch, and the template their body is rendered from
+//   `labels: { "<name>": { color, description } }` - how labels the automation creates look
+//   `processing: { min_score }` - feedback scoring impact × frequency below `min_score` is
+//     held in `pending` until someone starts it by hand
+
+use crate::utils::timezones::TimeZone;
+use anyhow::{Context, Result};
+use chrono::{DateTime, Datelike, FixedOffset, NaiveDateTime, NaiveTime, Utc, Weekday};
+use serde::{Deserialize, Deserializer, Serialize};
+use serde_json::{Map, Value};
+use sqlx::{PgExecutor, PgPool};
+use std::collections::BTreeMap;
+use std::fmt;
+
+/// 🔢 The newest config layout this build reads and writes
+pub const CURRENT_CONFIG_VERSION: u32 = 3;
+
+/// 🪜 One upgrade step, from the version before to the version after
+type Upgrade = fn(Map<String, Value>) -> Result<Map<String, Value>>;
+
+/// 🪜 Upgrade steps, `UPGRADES[n]` turning a v(n+1) config into v(n+2)
+const UPGRADES: &[Upgrade] = &[upgrade_v1_to_v2, upgrade_v2_to_v3];
+const _: () = assert!(UPGRADES.len() as u32 + 1 == CURRENT_CONFIG_VERSION);
+
+/// ⚙️ A project's config at the current version
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ProjectConfig {
+    pub config_version: u32,
+    #[serde(default, skip_serializing_if = "PathSettings::is_empty")]
+    pub paths: PathSettings,
+    #[serde(default, skip_serializing_if = "CommentSettings::is_empty")]
+    pub comments: CommentSettings,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub schedule: Option<BusinessHours>,
+    #[serde(default, skip_serializing_if = "PullRequestSettings::is_default")]
+    pub pull_requests: PullRequestSettings,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub labels: BTreeMap<String, LabelOverride>,
+    #[serde(default, skip_serializing_if = "ProcessingSettings::is_default")]
+    pub processing: ProcessingSettings,
+    /// 📦 Keys this build doesn't interpret, kept as they are
+    #[serde(flatten)]
+    pub other: Map<String, Value>,
+}
+
+/// 🛡️ Path policy globs (see github::path_policy)
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PathSettings {
+    /// ✅ Replaces the default allowed globs
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub allowed: Option<Vec<String>>,
+    /// 🚫 Added to the always-denied globs
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub denied: Option<Vec<String>>,
+}
+
+impl PathSettings {
+    fn is_empty(&self) -> bool {
+        self.allowed.is_none() && self.denied.is_none()
+    }
+}
+
+/// 💬 How bot comments look on this project
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct CommentSettings {
+    /// ✍️ None: use GITHUB_COMMENT_FOOTER, Some(None): no footer, Some(Some(_)): this one
+    #[serde(
+        default,
+        deserialize_with = "present",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub footer: Option<Option<String>>,
+}
+
+impl CommentSettings {
+    fn is_empty(&self) -> bool {
+        self.footer.is_none()
+    }
+}
+
+/// 🔀 How generated pull requests treat the base branch (see github::protection)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PullRequestSettings {
+    /// 🔒 Read the base branch's protection first (required reviews and checks go in the PR body)
+    #[serde(default = "enabled")]
+    pub check_protection: bool,
+    /// 🔀 Merge the PR right away when the base branch is known (so `check_protection`
+    /// is on) to require no reviews or checks
+    #[serde(default)]
+    pub auto_merge: bool,
+    /// 📝 Template for the PR body (see github::pr_body; None = GITHUB_PR_BODY_TEMPLATE)
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        deserialize_with = "pr_body_template"
+    )]
+    pub body_template: Option<String>,
+}
+
+impl Default for PullRequestSettings {
+    fn default() -> Self {
+        Self {
+            check_protection: true,
+            auto_merge: false,
+            body_template: None,
+        }
+    }
+}
+
+impl PullRequestSettings {
+    fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// ⏸️ Which feedback gets processed without anyone asking
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProcessingSettings {
+    /// 📏 Lowest impact × frequency (1-100) processed on its own; feedback without
+    /// both scores is never held
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        deserialize_with = "min_score"
+    )]
+    pub min_score: Option<i32>,
+}
+
+impl ProcessingSettings {
+    fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// 📏 A threshold impact × frequency can actually fall below (scores run 1-10)
+fn min_score<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<i32>, D::Error> {
+    let Some(score) = Option::<i32>::deserialize(deserializer)? else {
+        return Ok(None);
+    };
+    if !(1..=100).contains(&score) {
+        return Err(serde::de::Error::custom(format!(
+            "min_score must be between 1 and 100, got {}",
+            score
+        )));
+    }
+    Ok(Some(score))
+}
+
+fn enabled() -> bool {
+    true
+}
+
+/// 🎨 A project's take on one label (see github::labels), either field left to the default
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LabelOverride {
+    /// 🎨 Six hex digits, with or without `#`
+    #[serde(
+        default,
+        deserialize_with = "hex_color",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub color: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+}
+
+/// 🎨 "#D73A4A" → "d73a4a", the form GitHub takes
+fn hex_color<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<String>, D::Error> {
+    let Some(color) = Option::<String>::deserialize(deserializer)? else {
+        return Ok(None);
+    };
+    let hex = color.trim().trim_start_matches('#');
+    if hex.len() != 6 || !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Err(serde::de::Error::custom(format!(
+            "label color must be six hex digits like d73a4a, got {:?}",
+            color
+        )));
+    }
+    Ok(Some(hex.to_lowercase()))
+}
+
+/// 📝 A PR body template, refused when it uses a placeholder we can't fill
+fn pr_body_template<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Option<String>, D::Error> {
+    let Some(template) = Option::<String>::deserialize(deserializer)? else {
+        return Ok(None);
+    };
+    crate::github::pr_body::validate_template(&template)
+        .map_err(|reason| serde::de::Error::custom(format!("body_template: {}", reason)))?;
+    Ok(Some(template))
+}
+
+/// 🕘 When the team is around to answer new issues. Times are local to `timezone`,
+/// and an `end` before `start` runs overnight into the next day - the window belongs
+/// to the day it opens on.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BusinessHours {
+    pub timezone: ScheduleZone,
+    pub start: NaiveTime,
+    pub end: NaiveTime,
+    pub days: Vec<Weekday>,
+}
+
+/// 📝 `schedule` as it is stored
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct StoredBusinessHours {
+    timezone: String,
+    start: String,
+    end: String,
+    #[serde(default = "weekdays")]
+    days: Vec<String>,
+}
+
+/// 📅 Monday to Friday, for schedules that don't list their days
+fn weekdays() -> Vec<String> {
+    ["mon", "tue", "wed", "thu", "fri"]
+        .map(String::from)
+        .to_vec()
+}
+
+/// 🌍 The clock a schedule runs on: an IANA zone ("Europe/Paris", "UTC") that follows
+/// its DST rules, or a fixed offset ("+02:00", "-05:30") that never moves
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ScheduleZone {
+    Named(TimeZone),
+    Offset(FixedOffset),
+}
+
+impl ScheduleZone {
+    /// 🕰️ `at` as wall-clock time in this zone
+    pub fn local(self, at: DateTime<Utc>) -> NaiveDateTime {
+        match self {
+            Self::Named(zone) => zone.to_local(at).0.naive_local(),
+            Self::Offset(offset) => at.with_timezone(&offset).naive_local(),
+        }
+    }
+
+    /// 🕰️ The instant a wall-clock time in this zone names
+    pub fn at_local(self, local: NaiveDateTime) -> DateTime<Utc> {
+        match self {
+            Self::Named(zone) => zone.at_local(local),
+            Self::Offset(offset) => (local - offset).and_utc(),
+        }
+    }
+}
+
+impl fmt::Display for ScheduleZone {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Named(zone) => f.write_str(zone.name()),
+            Self::Offset(offset) => write!(f, "UTC{}", offset),
+        }
+    }
+}
+
+impl BusinessHours {
+    /// 🚪 Is `now` inside business hours?
+    pub fn is_open(&self, now: DateTime<Utc>) -> bool {
+        let local = self.timezone.local(now);
+        let (today, time) = (local.weekday(), local.time());
+        if self.start < self.end {
+            self.days.contains(&today) && (self.start..self.end).contains(&time)
+        } else {
+            // 🌙 Overnight: tonight's window, or the tail of the one that opened yesterday
+            (time >= self.start && self.days.contains(&today))
+                || (time < self.end && self.days.contains(&today.pred()))
+        }
+    }
+
+    /// 🌅 When business hours next open after `now` - today's opening if it's still
+    /// ahead, otherwise the opening on the next listed day
+    pub fn next_opening(&self, now: DateTime<Utc>) -> DateTime<Utc> {
+        self.timezone
+            .local(now)
+            .date()
+            .iter_days()
+            .take(9)
+            .filter(|day| self.days.contains(&day.weekday()))
+            .map(|day| self.timezone.at_local(day.and_time(self.start)))
+            .find(|opening| *opening > now)
+            .expect("every listed day comes round within a week")
+    }
+
+    /// 🏷️ "09:00-17:00 Europe/Paris, Mon-Fri"-style summary for comments
+    pub fn describe(&self) -> String {
+        let days = self
+            .days
+            .iter()
+            .map(|day| day.to_string())
+            .collect::<Vec<_>>()
+            .join(", ");
+        format!(
+            "{}–{} {}, {}",
+            self.start.format("%H:%M"),
+            self.end.format("%H:%M"),
+            self.timezone,
+            days
+        )
+    }
+}
+
+/// 🌍 An IANA zone name ("Europe/Paris"), "UTC"/"Z", or a fixed "+HH:MM" offset
+fn parse_zone(timezone: &str) -> Result<ScheduleZone> {
+    let invalid = || {
+        format!(
+            "`schedule.timezone` must be a zone like Europe/Paris, UTC or an offset like +02:00, got {:?}",
+            timezone
+        )
+    };
+    match timezone.trim() {
+        "UTC" | "utc" | "Z" => Ok(ScheduleZone::Named(TimeZone::utc())),
+        offset if offset.starts_with(['+', '-']) => offset
+            .parse()
+            .map(ScheduleZone::Offset)
+            .with_context(invalid),
+        name => TimeZone::load(name)
+            .map(ScheduleZone::Named)
+            .with_context(invalid),
+    }
+}
+
+/// 🕘 "HH:MM" local time
+fn parse_clock(field: &str, value: &str) -> Result<NaiveTime> {
+    NaiveTime::parse_from_str(value.trim(), "%H:%M")
+        .with_context(|| format!("`schedule.{}` must be HH:MM, got {:?}", field, value))
+}
+
+impl TryFrom<StoredBusinessHours> for BusinessHours {
+    type Error = anyhow::Error;
+
+    fn try_from(stored: StoredBusinessHours) -> Result<Self> {
+        let start = parse_clock("start", &stored.start)?;
+        let end = parse_clock("end", &stored.end)?;
+        if start == end {
+            anyhow::bail!("`schedule.start` and `schedule.end` must differ");
+        }
+        let mut days = Vec::new();
+        for day in &stored.days {
+            let day: Weekday = day
+                .parse()
+                .map_err(|_| anyhow::anyhow!("`schedule.days` has an unknown day {:?}", day))?;
+            if !days.contains(&day) {
+                days.push(day);
+            }
+        }
+        if days.is_empty() {
+            anyhow::bail!("`schedule.days` needs at least one day");
+        }
+        Ok(Self {
+            timezone: parse_zone(&stored.timezone)?,
+            start,
+            end,
+            days,
+        })
+    }
+}
+
+impl From<&BusinessHours> for StoredBusinessHours {
+    fn from(hours: &BusinessHours) -> Self {
+        Self {
+            timezone: match hours.timezone {
+                ScheduleZone::Named(zone) => zone.name().to_string(),
+                ScheduleZone::Offset(offset) => offset.to_string(),
+            },
+            start: hours.start.format("%H:%M").to_string(),
+            end: hours.end.format("%H:%M").to_string(),
+            days: hours
+                .days
+                .iter()
+                .map(|day| day.to_string().to_lowercase())
+                .collect(),
+        }
+    }
+}
+
+impl Serialize for BusinessHours {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        StoredBusinessHours::from(self).serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for BusinessHours {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let stored = StoredBusinessHours::deserialize(deserializer)?;
+        Self::try_from(stored).map_err(|e| serde::de::Error::custom(format!("{:#}", e)))
+    }
+}
+
+/// 🔍 Tell an explicit null apart from a missing key
+fn present<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<Option<String>>, D::Error> {
+    Option::<String>::deserialize(deserializer).map(Some)
+}
+
+/// ⏭️ The stored config was written by a newer build than this one
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ConfigTooNew {
+    pub stored: u32,
+    pub supported: u32,
+}
+
+impl fmt::Display for ConfigTooNew {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "project config version {} is newer than this build understands (up to {})",
+            self.stored, self.supported
+        )
+    }
+}
+
+impl std::error::Error for ConfigTooNew {}
+
+impl ConfigTooNew {
+    /// 🔍 Find a too-new refusal anywhere in an error chain
+    pub fn find(error: &anyhow::Error) -> Option<Self> {
+        error
+            .chain()
+            .find_map(|cause| cause.downcast_ref::<Self>().copied())
+    }
+}
+
+impl Default for ProjectConfig {
+    fn default() -> Self {
+        Self {
+            config_version: CURRENT_CONFIG_VERSION,
+            paths: PathSettings::default(),
+            comments: CommentSettings::default(),
+            schedule: None,
+            pull_requests: PullRequestSettings::default(),
+            labels: BTreeMap::new(),
+            processing: ProcessingSettings::default(),
+            other: Map::new(),
+        }
+    }
+}
+
+/// 🔢 The version a stored config was written at (unversioned configs are v1)
+pub fn stored_version(value: &Value) -> Result<u32> {
+    match value.get("config_version") {
+        None | Some(Value::Null) => Ok(1),
+        Some(version) => version
+            .as_u64()
+            .and_then(|version| u32::try_from(version).ok())
+            .filter(|version| *version >= 1)
+            .context("`config_version` must be a positive integer"),
+    }
+}
+
+impl ProjectConfig {
+    /// 🪜 Read a stored config, upgrading it step by step to the current version
+    pub fn from_value(value: Value) -> Result<Self> {
+        let version = stored_version(&value)?;
+        if version > CURRENT_CONFIG_VERSION {
+            return Err(ConfigTooNew {
+                stored: version,
+                supported: CURRENT_CONFIG_VERSION,
+            }
+            .into());
+        }
+        let Value::Object(mut config) = value else {
+            anyhow::bail!("project config must be a JSON object");
+        };
+        for upgrade in &UPGRADES[version as usize - 1..] {
+            config = upgrade(config)?;
+        }
+        config.insert("config_version".to_string(), CURRENT_CONFIG_VERSION.into());
+        serde_json::from_value(Value::Object(config)).context("Invalid project config")
+    }
+
+    /// 💾 The JSON to store, at the current version
+    pub fn to_value(&self) -> Value {
+        serde_json::to_value(self).expect("project config always serializes")
+    }
+
+    /// 🔍 Config of the active project serving `repository` - registered for it or
+    /// declaring it (None without one, defaults without a config)
+    pub async fn for_repository(
+        executor: impl PgExecutor<'_>,
+        repository: &str,
+    ) -> Result<Option<Self>> {
+        let config: Option<Option<Value>> = sqlx::query_scalar(
+            r#"
+            SELECT p.config FROM projects p
+            JOIN project_repository_routes r ON r.project_id = p.id
+            WHERE r.repository = $1 AND p.is_active
+            ORDER BY r.is_primary DESC, p.created_at, p.id
+            LIMIT 1
+            "#,
+        )
+        .bind(repository)
+        .fetch_optional(executor)
+        .await
+        .context("Failed to load project config")?;
+        config
+            .map(|config| {
+                config
+                    .map(Self::from_value)
+                    .unwrap_or_else(|| Ok(Self::default()))
+            })
+            .transpose()
+            .with_context(|| format!("Unusable project config for {}", repository))
+    }
+}
+
+/// 💾 Rewrite a project's stored config at the current version.
+/// Returns (from, to) versions, or None when the project has no config.
+/// A config from a newer build is refused, never downgraded.
+pub async fn migrate_stored(pool: &PgPool, project_id: uuid::Uuid) -> Result<Option<(u32, u32)>> {
+    let mut tx = pool.begin().await?;
+    let stored: Option<Option<Value>> =
+        sqlx::query_scalar("SELECT config FROM projects WHERE id = $1 FOR UPDATE")
+            .bind(project_id)
+            .fetch_optional(&mut *tx)
+            .await
+            .context("Failed to load project config")?;
+    let Some(stored) = stored.flatten() else {
+        return Ok(None);
+    };
+    let from = stored_version(&stored)?;
+    let config = ProjectConfig::from_value(stored)?;
+    sqlx::query("UPDATE projects SET config = $2, updated_at = NOW() WHERE id = $1")
+        .bind(project_id)
+        .bind(config.to_value())
+        .execute(&mut *tx)
+        .await
+        .context("Failed to store migrated project config")?;
+    tx.commit().await?;
+    Ok(Some((from, config.config_version)))
+}
+
+/// ✏️ Change a project's config (defaults when it has none) and store it at the
+/// current version. Returns the stored config, or None when there's no such project.
+pub async fn update_stored(
+    pool: &PgPool,
+    project_id: uuid::Uuid,
+    change: impl FnOnce(&mut ProjectConfig),
+) -> Result<Option<ProjectConfig>> {
+    let mut tx = pool.begin().await?;
+    let stored: Option<Option<Value>> =
+        sqlx::query_scalar("SELECT config FROM projects WHERE id = $1 FOR UPDATE")
+            .bind(project_id)
+            .fetch_optional(&mut *tx)
+            .await
+            .context("Failed to load project config")?;
+    let Some(stored) = stored else {
+        return Ok(None);
+    };
+    let mut config = stored
+        .map(ProjectConfig::from_value)
+        .transpose()?
+        .unwrap_or_default();
+    change(&mut config);
+    sqlx::query("UPDATE projects SET config = $2, updated_at = NOW() WHERE id = $1")
+        .bind(project_id)
+        .bind(config.to_value())
+        .execute(&mut *tx)
+        .await
+        .context("Failed to store project config")?;
+    tx.commit().await?;
+    Ok(Some(config))
+}
+
+/// 🪜 v1 → v2: `allowed_paths` / `denied_paths` move under `paths`
+fn upgrade_v1_to_v2(mut config: Map<String, Value>) -> Result<Map<String, Value>> {
+    let mut paths = Map::new();
+    for (old, new) in [("allowed_paths", "allowed"), ("denied_paths", "denied")] {
+        if let Some(globs) = config.remove(old) {
+            let is_glob_list = globs
+                .as_array()
+                .is_some_and(|items| items.iter().all(Value::is_string));
+            if !is_glob_list {
+                anyhow::bail!("`{}` must be an array of glob strings", old);
+            }
+            paths.insert(new.to_string(), globs);
+        }
+    }
+    if !paths.is_empty() {
+        config.insert("paths".to_string(), Value::Object(paths));
+    }
+    config.insert("config_version".to_string(), 2.into());
+    Ok(config)
+}
+
+/// 🪜 v2 → v3: `comment_footer` moves to `comments.footer` (an explicit null survives)
+fn upgrade_v2_to_v3(mut config: Map<String, Value>) -> Result<Map<String, Value>> {
+    if let Some(footer) = config.remove("comment_footer") {
+        if !(footer.is_string() || footer.is_null()) {
+            anyhow::bail!("`comment_footer` must be a string or null");
+        }
+        config.insert(
+            "comments".to_string(),
+            serde_json::json!({ "footer": footer }),
+        );
+    }
+    config.insert("config_version".to_string(), 3.into());
+    Ok(config)
+}
+
+// 🧪 Tests - Old configs, new configs, configs from the future!
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn object(value: Value) -> Map<String, Value> {
+        match value {
+            Value::Object(map) => map,
+            _ => panic!("not an object"),
+        }
+    }
+
+    #[test]
+    fn test_upgrade_v1_to_v2_groups_path_globs() {
+        let upgraded = upgrade_v1_to_v2(object(json!({
+            "allowed_paths": ["src/**"],
+            "denied_paths": ["migrations/**"],
+            "comment_footer": "— Acme Bot",
+        })))
+        .unwrap();
+        assert_eq!(
+            Value::Object(upgraded),
+            json!({
+                "config_version": 2,
+                "paths": { "allowed": ["src/**"], "denied": ["migrations/**"] },
+                "comment_footer": "— Acme Bot",
+            })
+        );
+        assert_eq!(
+            Value::Object(upgrade_v1_to_v2(Map::new()).unwrap()),
+            json!({ "config_version": 2 })
+        );
+        let error = upgrade_v1_to_v2(object(json!({ "allowed_paths": "src/**" }))).unwrap_err();
+        assert!(error
+            .to_string()
+            .contains("`allowed_paths` must be an array"));
+        println!("✅ Config v1 → v2 upgrade test passed!");
+    }
+
+    #[test]
+    fn test_upgrade_v2_to_v3_groups_comment_settings() {
+        let upgraded = upgrade_v2_to_v3(object(json!({
+            "config_version": 2,
+            "comment_footer": null,
+            "labels": ["triage"],
+        })))
+        .unwrap();
+        assert_eq!(
+            Value::Object(upgraded),
+            json!({
+                "config_version": 3,
+                "comments": { "footer": null },
+                "labels": ["triage"],
+            })
+        );
+        assert!(upgrade_v2_to_v3(object(json!({ "comment_footer": 7 }))).is_err());
+        println!("✅ Config v2 → v3 upgrade test passed!");
+    }
+
+    #[test]
+    fn test_from_value_runs_the_whole_chain() {
+        let config = ProjectConfig::from_value(json!({
+            "allowed_paths": ["**"],
+            "comment_footer": null,
+            "custom": true,
+        }))
+        .unwrap();
+        assert_eq!(config.config_version, CURRENT_CONFIG_VERSION);
+        assert_eq!(config.paths.allowed, Some(vec!["**".to_string()]));
+        assert_eq!(config.paths.denied, None);
+        assert_eq!(config.comments.footer, Some(None));
+        assert_eq!(config.other["custom"], json!(true));
+
+        // 💾 Rewriting at the current version reads back the same
+        let stored = config.to_value();
+        assert_eq!(stored["config_version"], json!(CURRENT_CONFIG_VERSION));
+        assert_eq!(ProjectConfig::from_value(stored).unwrap(), config);
+        assert_eq!(
+            ProjectConfig::from_value(json!({})).unwrap(),
+            ProjectConfig::default()
+        );
+        println!("✅ Config upgrade chain test passed!");
+    }
+
+    #[test]
+    fn test_business_hours_schedule() {
+        use chrono::TimeZone;
+
+        let config = ProjectConfig::from_value(json!({
+            "config_version": CURRENT_CONFIG_VERSION,
+            "schedule": { "timezone": "+02:00", "start": "09:00", "end": "17:30" },
+        }))
+        .unwrap();
+        let schedule = config.schedule.as_ref().unwrap();
+        assert_eq!(schedule.days.len(), 5);
+        assert_eq!(
+            schedule.describe(),
+            "09:00–17:30 UTC+02:00, Mon, Tue, Wed, Thu, Fri"
+        );
+
+        // 🕘 2026-10-14 is a Wednesday: 07:00 UTC is 09:00 local, 15:30 UTC is 17:30 local
+        let at = |day, hour, minute| {
+            Utc.with_ymd_and_hms(2026, 10, day, hour, minute, 0)
+                .unwrap()
+        };
+        assert!(!schedule.is_open(at(14, 6, 59)));
+        assert!(schedule.is_open(at(14, 7, 0)));
+        assert!(schedule.is_open(at(14, 15, 29)));
+        assert!(!schedule.is_open(at(14, 15, 30)));
+        // 📅 Saturday local time, even though it's still Friday in UTC... and the reverse
+        assert!(!schedule.is_open(at(17, 8, 0)));
+        assert!(!schedule.is_open(at(16, 23, 0)));
+
+        // 💾 Round-trips in the stored shape
+        let stored = config.to_value();
+        assert_eq!(
+            stored["schedule"],
+            json!({
+                "timezone": "+02:00", "start": "09:00", "end": "17:30",
+                "days": ["mon", "tue", "wed", "thu", "fri"],
+            })
+        );
+        assert_eq!(ProjectConfig::from_value(stored).unwrap(), config);
+
+        let weekend = ProjectConfig::from_value(json!({
+            "schedule": { "timezone": "UTC", "start": "10:00", "end": "14:00", "days": ["Saturday", "sun"] },
+        }))
+        .unwrap();
+        assert!(weekend.schedule.unwrap().is_open(at(17, 12, 0)));
+
+        for (schedule, message) in [
+            (
+                json!({ "timezone": "Europe/Atlantis", "start": "09:00", "end": "17:00" }),
+                "schedule.timezone",
+            ),
+            (
+                json!({ "timezone": "+25:00", "start": "09:00", "end": "17:00" }),
+                "schedule.timezone",
+            ),
+            (
+                json!({ "timezone": "UTC", "start": "9am", "end": "17:00" }),
+                "schedule.start",
+            ),
+            (
+                json!({ "timezone": "UTC", "start": "09:00", "end": "09:00" }),
+                "must differ",
+            ),
+            (
+                json!({ "timezone": "UTC", "start": "09:00", "end": "17:00", "days": ["funday"] }),
+                "unknown day",
+            ),
+            (
+                json!({ "timezone": "UTC", "start": "09:00", "end": "17:00", "days": [] }),
+                "at least one day",
+            ),
+        ] {
+            let error = ProjectConfig::from_value(json!({ "schedule": schedule })).unwrap_err();
+            assert!(format!("{:#}", error).contains(message), "{:#}", error);
+        }
+        println!("✅ Business hours schedule test passed!");
+    }
+
+    #[test]
+    fn test_named_zone_and_overnight_schedules() {
+        use chrono::TimeZone;
+
+        let at = |month, day, hour, minute| {
+            Utc.with_ymd_and_hms(2026, month, day, hour, minute, 0)
+                .unwrap()
+        };
+        let paris = ProjectConfig::from_value(json!({
+            "schedule": { "timezone": "Europe/Paris", "start": "09:00", "end": "17:00" },
+        }))
+        .unwrap();
+        let schedule = paris.schedule.as_ref().unwrap();
+        assert_eq!(
+            schedule.describe(),
+            "09:00–17:00 Europe/Paris, Mon, Tue, Wed, Thu, Fri"
+        );
+        // ☀️ 09:00 in Paris is 07:00 UTC in summer (CEST)...
+        assert!(!schedule.is_open(at(10, 14, 6, 59)));
+        assert!(schedule.is_open(at(10, 14, 7, 0)));
+        // ❄️ ...and 08:00 UTC once the clocks go back on October 25th (CET)
+        assert!(!schedule.is_open(at(10, 26, 7, 30)));
+        assert!(schedule.is_open(at(10, 26, 8, 0)));
+        assert_eq!(
+            paris.to_value()["schedule"]["timezone"],
+            json!("Europe/Paris")
+        );
+        assert_eq!(ProjectConfig::from_value(paris.to_value()).unwrap(), paris);
+
+        // 🌅 The next opening: later today, tomorrow, or Monday after a weekend
+        // (2026-10-14 is a Wednesday, 2026-10-16 a Friday)
+        assert_eq!(schedule.next_opening(at(10, 14, 5, 0)), at(10, 14, 7, 0));
+        assert_eq!(schedule.next_opening(at(10, 14, 16, 0)), at(10, 15, 7, 0));
+        assert_eq!(schedule.next_opening(at(10, 16, 16, 0)), at(10, 19, 7, 0));
+        // 🍂 Friday before the clocks change: Monday opens at 08:00 UTC, not 07:00
+        assert_eq!(schedule.next_opening(at(10, 23, 16, 0)), at(10, 26, 8, 0));
+
+        // 🌙 22:00-06:00 on Mon-Fri nights: Friday night runs into Saturday morning,
+        // but Sunday night isn't a shift
+        let night = ProjectConfig::from_value(json!({
+            "schedule": { "timezone": "UTC", "start": "22:00", "end": "06:00" },
+        }))
+        .unwrap();
+        let night = night.schedule.as_ref().unwrap();
+        assert!(night.is_open(at(10, 14, 23, 0)));
+        assert!(night.is_open(at(10, 15, 5, 59)));
+        assert!(!night.is_open(at(10, 15, 6, 0)));
+        assert!(!night.is_open(at(10, 15, 12, 0)));
+        assert!(night.is_open(at(10, 17, 3, 0)));
+        assert!(!night.is_open(at(10, 19, 3, 0)));
+        assert_eq!(night.next_opening(at(10, 15, 12, 0)), at(10, 15, 22, 0));
+        assert_eq!(night.next_opening(at(10, 17, 12, 0)), at(10, 19, 22, 0));
+        println!("✅ Named zone and overnight schedule test passed!");
+    }
+
+    #[test]
+    fn test_label_overrides() {
+        let config = ProjectConfig::from_value(json!({
+            "labels": { "bug": { "color": "#FF0000" }, "needs-info": { "description": "Tell us more" } }
+        }))
+        .unwrap();
+        assert_eq!(config.labels["bug"].color.as_deref(), Some("ff0000"));
+        assert_eq!(config.labels["needs-info"].color, None);
+        assert_eq!(
+            config.to_value()["labels"],
+            json!({ "bug": { "color": "ff0000" }, "needs-info": { "description": "Tell us more" } })
+        );
+        let error = ProjectConfig::from_value(json!({ "labels": { "bug": { "color": "red" } } }))
+            .unwrap_err();
+        assert!(format!("{:#}", error).contains("six hex digits"));
+        println!("✅ Label overrides test passed!");
+    }
+
+    #[test]
+    fn test_pr_body_templates_are_checked() {
+        let config = ProjectConfig::from_value(json!({
+            "pull_requests": { "body_template": "Closes {feedback_url}\n{changes}" }
+        }))
+        .unwrap();
+        assert_eq!(
+            config.pull_requests.body_template.as_deref(),
+            Some("Closes {feedback_url}\n{changes}")
+        );
+        assert!(config.pull_requests.check_protection);
+        let error = ProjectConfig::from_value(json!({
+            "pull_requests": { "body_template": "Thanks {submiter}" }
+        }))
+        .unwrap_err();
+        assert!(format!("{:#}", error).contains("unknown placeholder {submiter}"));
+        println!("✅ PR body template config test passed!");
+    }
+
+    #[test]
+    fn test_processing_threshold() {
+        let config =
+            ProjectConfig::from_value(json!({ "processing": { "min_score": 12 } })).unwrap();
+        assert_eq!(config.processing.min_score, Some(12));
+        assert_eq!(config.to_value()["processing"], json!({ "min_score": 12 }));
+        assert!(ProjectConfig::default()
+            .to_value()
+            .get("processing")
+            .is_none());
+        for score in [0, 101] {
+            let error = ProjectConfig::from_value(json!({ "processing": { "min_score": score } }))
+                .unwrap_err();
+            assert!(format!("{:#}", error).contains("between 1 and 100"));
+        }
+        println!("✅ Processing threshold config test passed!");
+    }
+
+    #[test]
+    fn test_configs_from_a_newer_build_are_refused() {
+        let error =
+            ProjectConfig::from_value(json!({ "config_version": CURRENT_CONFIG_VERSION + 1 }))
+                .unwrap_err();
+        assert_eq!(
+            ConfigTooNew::find(&error),
+            Some(ConfigTooNew {
+                stored: CURRENT_CONFIG_VERSION + 1,
+                supported: CURRENT_CONFIG_VERSION,
+            })
+        );
+        assert!(ProjectConfig::from_value(json!({ "config_version": 0 })).is_err());
+        assert!(ProjectConfig::from_value(json!({ "config_version": "2" })).is_err());
+        assert!(ProjectConfig::from_value(json!(["not", "an", "object"])).is_err());
+        println!("✅ Too-new config guard test passed!");
+    }
+}
@@ -0,0 +1,169 @@
+// 🗂️ Project Repositories - One product, several repositories! 🗂️
+// A project is registered for one repository (`projects.repository`), but a product may
+// span a monorepo and its satellites. Further repositories are declared here, and the
+// `project_repository_routes` view lists every repository a project serves, its own
+// first. Whatever goes from a repository to its project (config, approval, issue
+// events, the relay's registered check) reads that view, so feedback for any of them
+// gets the same project settings. Should two active projects serve one repository,
+// the project registered for it wins, then the oldest.
+// Created with love by Aye & Hue! ✨
+
+use anyhow::{Context, Result};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+/// 🔍 The active project serving `repository`, if any
+pub async fn project_for_repository(pool: &PgPool, repository: &str) -> Result<Option<Uuid>> {
+    sqlx::query_scalar(
+        r#"
+        SELECT p.id FROM projects p
+        JOIN project_repository_routes r ON r.project_id = p.id
+        WHERE r.repository = $1 AND p.is_active
+        ORDER BY r.is_primary DESC, p.created_at, p.id
+        LIMIT 1
+        "#,
+    )
+    .bind(repository)
+    .fetch_optional(pool)
+    .await
+    .context("Failed to resolve the project for a repository")
+}
+
+/// 📋 A project's further repositories, in the order they were added
+pub async fn list(pool: &PgPool, project_id: Uuid) -> Result<Vec<String>> {
+    sqlx::query_scalar(
+        "SELECT repository FROM project_repositories WHERE project_id = $1 ORDER BY created_at, repository",
+    )
+    .bind(project_id)
+    .fetch_all(pool)
+    .await
+    .context("Failed to list project repositories")
+}
+
+/// ➕ Declare another repository for a project; false when it was already declared
+pub async fn add(pool: &PgPool, project_id: Uuid, repository: &str) -> Result<bool> {
+    let repository = repository.trim();
+    let valid = repository.split_once('/').is_some_and(|(owner, name)| {
+        !owner.is_empty()
+            && !name.is_empty()
+            && !name.contains('/')
+            && !repository.contains(char::is_whitespace)
+    });
+    if !valid {
+        anyhow::bail!(
+            "Invalid repository format. Expected 'owner/repo', got '{}'",
+            repository
+        );
+    }
+    let own: Option<String> = sqlx::query_scalar("SELECT repository FROM projects WHERE id = $1")
+        .bind(project_id)
+        .fetch_optional(pool)
+        .await
+        .context("Failed to load project")?;
+    let Some(own) = own else {
+        anyhow::bail!("Project {} not found", project_id);
+    };
+    if own.eq_ignore_ascii_case(repository) {
+        anyhow::bail!("{} is already the project's own repository", repository);
+    }
+    let added = sqlx::query(
+        "INSERT INTO project_repositories (project_id, repository) VALUES ($1, $2) ON CONFLICT DO NOTHING",
+    )
+    .bind(project_id)
+    .bind(repository)
+    .execute(pool)
+    .await
+    .context("Failed to add project repository")?
+    .rows_affected();
+    Ok(added == 1)
+}
+
+/// ➖ Stop routing a repository to a project; false when it wasn't declared
+pub async fn remove(pool: &PgPool, project_id: Uuid, repository: &str) -> Result<bool> {
+    let removed =
+        sqlx::query("DELETE FROM project_repositories WHERE project_id = $1 AND repository = $2")
+            .bind(project_id)
+            .bind(repository)
+            .execute(pool)
+            .await
+            .context("Failed to remove project repository")?
+            .rows_affected();
+    Ok(removed == 1)
+}
+
+// 🧪 Tests - Every road leads to the right project!
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::spawn_test_app;
+
+    #[tokio::test]
+    async fn test_further_repositories_route_to_their_project() {
+        let Some(app) = spawn_test_app().await else {
+            return;
+        };
+        let pool = &app.db_pool;
+        let owner_id: Uuid = sqlx::query_scalar(
+            "INSERT INTO users (email, name, password_hash) VALUES ('mono@example.com', 'Mono', 'x') RETURNING id",
+        )
+        .fetch_one(pool)
+        .await
+        .unwrap();
+        let mut projects = Vec::new();
+        for repository in ["8b-is/smart-tree", "8b-is/mem8"] {
+            let id: Uuid = sqlx::query_scalar(
+                "INSERT INTO projects (owner_id, repository) VALUES ($1, $2) RETURNING id",
+            )
+            .bind(owner_id)
+            .bind(repository)
+            .fetch_one(pool)
+            .await
+            .unwrap();
+            projects.push(id);
+        }
+        let (tree, mem8) = (projects[0], projects[1]);
+
+        assert!(add(pool, tree, "8b-is/smart-tree-vscode").await.unwrap());
+        assert!(!add(pool, tree, "8b-is/smart-tree-vscode").await.unwrap());
+        assert!(add(pool, tree, "8b-is/smart-tree").await.is_err());
+        assert!(add(pool, tree, "smart-tree-docs").await.is_err());
+        assert!(add(pool, Uuid::new_v4(), "8b-is/docs").await.is_err());
+        assert_eq!(list(pool, tree).await.unwrap(), ["8b-is/smart-tree-vscode"]);
+        assert_eq!(
+            project_for_repository(pool, "8b-is/smart-tree-vscode")
+                .await
+                .unwrap(),
+            Some(tree)
+        );
+        assert_eq!(
+            project_for_repository(pool, "8b-is/nowhere").await.unwrap(),
+            None
+        );
+
+        // 🥇 A project registered for the repository itself beats one that declared it
+        assert!(add(pool, tree, "8b-is/mem8").await.unwrap());
+        assert_eq!(
+            project_for_repository(pool, "8b-is/mem8").await.unwrap(),
+            Some(mem8)
+        );
+        sqlx::query("UPDATE projects SET is_active = FALSE WHERE id = $1")
+            .bind(mem8)
+            .execute(pool)
+            .await
+            .unwrap();
+        assert_eq!(
+            project_for_repository(pool, "8b-is/mem8").await.unwrap(),
+            Some(tree)
+        );
+
+        assert!(remove(pool, tree, "8b-is/smart-tree-vscode").await.unwrap());
+        assert!(!remove(pool, tree, "8b-is/smart-tree-vscode").await.unwrap());
+        assert_eq!(
+            project_for_repository(pool, "8b-is/smart-tree-vscode")
+                .await
+                .unwrap(),
+            None
+        );
+        println!("✅ Project repository routing test passed!");
+    }
+}
@@ -0,0 +1,264 @@
+// 🔖 Saved Views - Your favourite feedback filters, one click away! 🔖
+// A saved view is a named set of feedback list filters (range, sort, status, repository,
+// dates, tag, source, search) belonging to one admin - "failed bugs this week in
+// 8b-is/smart-tree", say. The filters are kept as the feedback page's own query
+// parameters, so opening a view is just following `ViewFilters::link`, and the page
+// reads them like any other link. Names are unique per admin.
+// Created with love by Aye & Hue! ✨
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{types::Json, PgPool};
+use uuid::Uuid;
+
+/// 📏 Longest view name
+pub const VIEW_NAME_MAX_CHARS: usize = 100;
+/// 📏 Longest value of any one filter
+const FILTER_MAX_CHARS: usize = 200;
+
+/// 🔎 The feedback page query a view opens, one field per query parameter
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ViewFilters {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub range: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sort: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dir: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tag: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub status: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub repo: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub q: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub from: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub to: Option<String>,
+}
+
+impl ViewFilters {
+    /// 📋 (parameter, value) pairs in query string order
+    fn params(&self) -> [(&'static str, Option<&str>); 10] {
+        [
+            ("range", self.range.as_deref()),
+            ("sort", self.sort.as_deref()),
+            ("dir", self.dir.as_deref()),
+            ("tag", self.tag.as_deref()),
+            ("source", self.source.as_deref()),
+            ("status", self.status.as_deref()),
+            ("repo", self.repo.as_deref()),
+            ("q", self.q.as_deref()),
+            ("from", self.from.as_deref()),
+            ("to", self.to.as_deref()),
+        ]
+    }
+
+    /// 🧹 Trim every value and drop the blank ones; Err names a value that is too long
+    pub fn normalized(self) -> Result<Self, String> {
+        let clean = |value: Option<String>| -> Result<Option<String>, String> {
+            match value.as_deref().map(str::trim) {
+                None | Some("") => Ok(None),
+                Some(value) if value.chars().count() > FILTER_MAX_CHARS => Err(format!(
+                    "filter values must be at most {} characters",
+                    FILTER_MAX_CHARS
+                )),
+                Some(value) => Ok(Some(value.to_string())),
+            }
+        };
+        Ok(Self {
+            range: clean(self.range)?,
+            sort: clean(self.sort)?,
+            dir: clean(self.dir)?,
+            tag: clean(self.tag)?,
+            source: clean(self.source)?,
+            status: clean(self.status)?,
+            repo: clean(self.repo)?,
+            q: clean(self.q)?,
+            from: clean(self.from)?,
+            to: clean(self.to)?,
+        })
+    }
+
+    /// 🔗 The feedback page showing this view
+    pub fn link(&self) -> String {
+        let query: Vec<String> = self
+            .params()
+            .into_iter()
+            .filter_map(|(name, value)| {
+                value.map(|value| {
+                    format!("{}={}", name, crate::api::tags::encode_query_value(value))
+                })
+            })
+            .collect();
+        if query.is_empty() {
+            "/admin/feedback".to_string()
+        } else {
+            format!("/admin/feedback?{}", query.join("&"))
+        }
+    }
+}
+
+/// 🔖 One admin's saved view
+#[derive(Debug, Clone, Serialize, sqlx::FromRow)]
+pub struct SavedView {
+    pub id: Uuid,
+    pub name: String,
+    pub filters: Json<ViewFilters>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// 🚫 Why a view couldn't be saved
+#[derive(Debug)]
+pub enum SaveRefused {
+    /// ✏️ The name is blank or too long
+    InvalidName,
+    /// 👯 The admin already has a view by that name
+    DuplicateName,
+}
+
+/// ✏️ A view name trimmed, if it is usable
+pub fn valid_name(name: &str) -> Option<&str> {
+    let name = name.trim();
+    (!name.is_empty() && name.chars().count() <= VIEW_NAME_MAX_CHARS).then_some(name)
+}
+
+/// 📋 An admin's views, by name
+pub async fn list(pool: &PgPool, owner: &str) -> Result<Vec<SavedView>> {
+    sqlx::query_as(
+        "SELECT id, name, filters, created_at, updated_at FROM saved_views \
+         WHERE owner = $1 ORDER BY LOWER(name), name",
+    )
+    .bind(owner)
+    .fetch_all(pool)
+    .await
+    .context("Failed to list saved views")
+}
+
+/// 🔍 One of an admin's views
+pub async fn find(pool: &PgPool, owner: &str, id: Uuid) -> Result<Option<SavedView>> {
+    sqlx::query_as(
+        "SELECT id, name, filters, created_at, updated_at FROM saved_views \
+         WHERE owner = $1 AND id = $2",
+    )
+    .bind(owner)
+    .bind(id)
+    .fetch_optional(pool)
+    .await
+    .context("Failed to load saved view")
+}
+
+/// ➕ Save a new view
+pub async fn create(
+    pool: &PgPool,
+    owner: &str,
+    name: &str,
+    filters: &ViewFilters,
+) -> Result<Result<SavedView, SaveRefused>> {
+    let Some(name) = valid_name(name) else {
+        return Ok(Err(SaveRefused::InvalidName));
+    };
+    let created = sqlx::query_as(
+        "INSERT INTO saved_views (owner, name, filters) VALUES ($1, $2, $3) \
+         RETURNING id, name, filters, created_at, updated_at",
+    )
+    .bind(owner)
+    .bind(name)
+    .bind(Json(filters))
+    .fetch_one(pool)
+    .await;
+    match created {
+        Ok(view) => Ok(Ok(view)),
+        Err(sqlx::Error::Database(e)) if e.is_unique_violation() => {
+            Ok(Err(SaveRefused::DuplicateName))
+        }
+        Err(e) => Err(e).context("Failed to save view"),
+    }
+}
+
+/// ✏️ Rename a view and/or replace its filters (None when the admin has no such view)
+pub async fn update(
+    pool: &PgPool,
+    owner: &str,
+    id: Uuid,
+    name: Option<&str>,
+    filters: Option<&ViewFilters>,
+) -> Result<Result<Option<SavedView>, SaveRefused>> {
+    let name = match name.map(valid_name) {
+        Some(None) => return Ok(Err(SaveRefused::InvalidName)),
+        Some(Some(name)) => Some(name),
+        None => None,
+    };
+    let updated = sqlx::query_as(
+        "UPDATE saved_views SET name = COALESCE($3, name), filters = COALESCE($4, filters), \
+         updated_at = NOW() WHERE owner = $1 AND id = $2 \
+         RETURNING id, name, filters, created_at, updated_at",
+    )
+    .bind(owner)
+    .bind(id)
+    .bind(name)
+    .bind(filters.map(Json))
+    .fetch_optional(pool)
+    .await;
+    match updated {
+        Ok(view) => Ok(Ok(view)),
+        Err(sqlx::Error::Database(e)) if e.is_unique_violation() => {
+            Ok(Err(SaveRefused::DuplicateName))
+        }
+        Err(e) => Err(e).context("Failed to update saved view"),
+    }
+}
+
+/// 🗑️ Delete one of an admin's views; false when there was no such view
+pub async fn delete(pool: &PgPool, owner: &str, id: Uuid) -> Result<bool> {
+    let deleted = sqlx::query("DELETE FROM saved_views WHERE owner = $1 AND id = $2")
+        .bind(owner)
+        .bind(id)
+        .execute(pool)
+        .await
+        .context("Failed to delete saved view")?;
+    Ok(deleted.rows_affected() > 0)
+}
+
+// 🧪 Tests - Bookmarks for the impatient!
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_filters_normalize_and_link() {
+        let filters = ViewFilters {
+            status: Some(" failed ".to_string()),
+            repo: Some("8b-is/smart-tree".to_string()),
+            q: Some("dark mode".to_string()),
+            tag: Some("   ".to_string()),
+            range: Some("7d".to_string()),
+            ..ViewFilters::default()
+        }
+        .normalized()
+        .unwrap();
+        assert_eq!(filters.tag, None);
+        assert_eq!(
+            filters.link(),
+            "/admin/feedback?range=7d&status=failed&repo=8b-is%2Fsmart-tree&q=dark%20mode"
+        );
+        assert_eq!(ViewFilters::default().link(), "/admin/feedback");
+        assert!(ViewFilters {
+            q: Some("x".repeat(FILTER_MAX_CHARS + 1)),
+            ..ViewFilters::default()
+        }
+        .normalized()
+        .is_err());
+
+        assert_eq!(valid_name("  Failed bugs "), Some("Failed bugs"));
+        assert_eq!(valid_name(" "), None);
+        assert_eq!(valid_name(&"v".repeat(VIEW_NAME_MAX_CHARS + 1)), None);
+        println!("✅ Saved view filters test passed!");
+    }
+}
@@ -0,0 +1,450 @@
+// 🔢 Admin Second Factors - One authenticator per admin, not one for the whole console! 🔢
+// Every admin session subject (the bootstrap admin from the config, or an admin or
+// service account) enrolls its own TOTP secret and backup codes, keyed by
+// `SessionSubject::key`. Login checks the subject's factor once; the session is then
+// signed over `enabled_at` (see auth::session), so switching a factor on or off ends
+// the sessions issued before it without a lookup per request.
+//
+// Between the password and the code step the browser holds a login challenge: a
+// random token stored (hashed) in `admin_login_challenges`. It is spent by the first
+// code that passes, or by too many wrong ones, and wrong codes also count against the
+// subject across challenges - so a stolen or replayed token buys a handful of guesses,
+// not an unlimited supply for five minutes.
+// Created with love by Aye & Hue! ✨
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use rand::Rng;
+use sha2::{Digest, Sha256};
+use sqlx::PgPool;
+use tracing::warn;
+use uuid::Uuid;
+
+use crate::auth::session::SessionSubject;
+use crate::auth::totp;
+use crate::database::models::User;
+
+/// 🎯 Wrong codes one login challenge takes before it is spent
+pub const MAX_CHALLENGE_FAILURES: i32 = 5;
+/// 🔒 Wrong codes a subject may collect (across challenges) within the window below
+/// before the code step refuses it, right codes included
+pub const MAX_SUBJECT_FAILURES: i64 = 10;
+/// ⏳ Window, in minutes, for `MAX_SUBJECT_FAILURES`
+pub const SUBJECT_FAILURE_WINDOW_MINS: i32 = 15;
+
+/// 🔢 Which kind of second factor was accepted
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SecondFactor {
+    Totp,
+    BackupCode,
+}
+
+impl std::fmt::Display for SecondFactor {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SecondFactor::Totp => f.write_str("TOTP"),
+            SecondFactor::BackupCode => f.write_str("backup code"),
+        }
+    }
+}
+
+/// 📋 One subject's two-factor state, as the settings page shows it
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Status {
+    /// ⏰ When the active secret was switched on (None = 2FA off)
+    pub enabled_since: Option<DateTime<Utc>>,
+    /// 🎟️ Backup codes not spent yet
+    pub backup_codes_left: usize,
+}
+
+/// ✅ A code that was accepted, and the factor it belongs to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Verified {
+    pub method: SecondFactor,
+    /// ⏰ When that factor was enabled - what the new session is signed over
+    pub enabled_at: DateTime<Utc>,
+}
+
+/// ✅ A freshly activated factor
+#[derive(Debug, Clone)]
+pub struct Activated {
+    pub enabled_at: DateTime<Utc>,
+    /// 🎟️ The plain backup codes, shown exactly once
+    pub backup_codes: Vec<String>,
+}
+
+/// 👤 An active account together with when its second factor was enabled, read in one
+/// query for the per-request session check
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct AccountWithFactor {
+    #[sqlx(flatten)]
+    pub user: User,
+    pub second_factor_since: Option<DateTime<Utc>>,
+}
+
+/// 🔍 Load an active account and its second factor's `enabled_at`
+pub async fn find_active_account(pool: &PgPool, id: Uuid) -> Result<Option<AccountWithFactor>> {
+    sqlx::query_as(
+        "SELECT u.*, f.enabled_at AS second_factor_since FROM users u \
+         LEFT JOIN admin_second_factors f ON f.subject = u.id::text \
+         WHERE u.id = $1 AND u.is_active",
+    )
+    .bind(id)
+    .fetch_optional(pool)
+    .await
+    .context("Failed to load admin account")
+}
+
+/// ⏰ When `subject`'s second factor was enabled (None = it signs in with a password only)
+pub async fn enabled_since(
+    pool: &PgPool,
+    subject: SessionSubject,
+) -> Result<Option<DateTime<Utc>>> {
+    let since: Option<Option<DateTime<Utc>>> =
+        sqlx::query_scalar("SELECT enabled_at FROM admin_second_factors WHERE subject = $1")
+            .bind(subject.key())
+            .fetch_optional(pool)
+            .await
+            .context("Failed to check the second factor")?;
+    Ok(since.flatten())
+}
+
+/// 📋 `subject`'s two-factor state
+pub async fn status(pool: &PgPool, subject: SessionSubject) -> Result<Status> {
+    let row: Option<(Option<DateTime<Utc>>, String)> = sqlx::query_as(
+        "SELECT enabled_at, backup_codes FROM admin_second_factors WHERE subject = $1",
+    )
+    .bind(subject.key())
+    .fetch_optional(pool)
+    .await
+    .context("Failed to load the second factor")?;
+    Ok(match row {
+        Some((enabled_since @ Some(_), codes)) => Status {
+            enabled_since,
+            backup_codes_left: serde_json::from_str::<Vec<String>>(&codes)
+                .map(|codes| codes.len())
+                .unwrap_or(0),
+        },
+        _ => Status::default(),
+    })
+}
+
+/// 🔢 Start enrollment with a fresh pending secret. None when a factor is already
+/// active - replacing it would skip proving the current one, so it must be disabled first.
+pub async fn start_enrollment(pool: &PgPool, subject: SessionSubject) -> Result<Option<String>> {
+    let secret = totp::generate_secret();
+    let stored: Option<String> = sqlx::query_scalar(
+        "INSERT INTO admin_second_factors (subject, pending_secret) VALUES ($1, $2) \
+         ON CONFLICT (subject) DO UPDATE SET pending_secret = EXCLUDED.pending_secret, updated_at = NOW() \
+         WHERE admin_second_factors.secret IS NULL \
+         RETURNING subject",
+    )
+    .bind(subject.key())
+    .bind(&secret)
+    .fetch_optional(pool)
+    .await
+    .context("Failed to store the pending TOTP secret")?;
+    Ok(stored.map(|_| secret))
+}
+
+/// 🔢 The secret waiting for its first code (None when there is none, or 2FA is on)
+pub async fn pending_secret(pool: &PgPool, subject: SessionSubject) -> Result<Option<String>> {
+    let secret: Option<Option<String>> = sqlx::query_scalar(
+        "SELECT pending_secret FROM admin_second_factors WHERE subject = $1 AND secret IS NULL",
+    )
+    .bind(subject.key())
+    .fetch_optional(pool)
+    .await
+    .context("Failed to load the pending TOTP secret")?;
+    Ok(secret.flatten())
+}
+
+/// ✅ Make the pending `secret` active, `step` being the time step of the code that
+/// proved it (so that code can't be replayed at login). None when `secret` is no longer
+/// the pending one.
+pub async fn activate(
+    pool: &PgPool,
+    subject: SessionSubject,
+    secret: &str,
+    step: u64,
+) -> Result<Option<Activated>> {
+    let backup_codes = totp::generate_backup_codes();
+    let hashes: Vec<String> = backup_codes
+        .iter()
+        .map(|code| totp::hash_backup_code(code))
+        .collect();
+    let enabled_at: Option<DateTime<Utc>> = sqlx::query_scalar(
+        "UPDATE admin_second_factors SET secret = pending_secret, pending_secret = NULL, \
+         backup_codes = $3, last_step = $4, enabled_at = NOW(), updated_at = NOW() \
+         WHERE subject = $1 AND secret IS NULL AND pending_secret = $2 \
+         RETURNING enabled_at",
+    )
+    .bind(subject.key())
+    .bind(secret)
+    .bind(serde_json::to_string(&hashes)?)
+    .bind(step as i64)
+    .fetch_optional(pool)
+    .await
+    .context("Failed to activate the second factor")?;
+    Ok(enabled_at.map(|enabled_at| Activated {
+        enabled_at,
+        backup_codes,
+    }))
+}
+
+/// 🔢 Accept a current TOTP code (each time step only once) or an unused backup code
+/// for `subject`. None when the code is wrong or the subject has no active factor.
+pub async fn check(pool: &PgPool, subject: SessionSubject, code: &str) -> Result<Option<Verified>> {
+    let mut tx = pool.begin().await?;
+    let verified = check_in(&mut tx, subject, code).await?;
+    tx.commit().await?;
+    Ok(verified)
+}
+
+/// 🔢 `check` inside the caller's transaction (a spent code is only spent once it commits)
+async fn check_in(
+    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    subject: SessionSubject,
+    code: &str,
+) -> Result<Option<Verified>> {
+    // 🔒 Lock the row so two logins can't spend the same code concurrently
+    let row: Option<(String, String, Option<i64>, DateTime<Utc>)> = sqlx::query_as(
+        "SELECT secret, backup_codes, last_step, enabled_at FROM admin_second_factors \
+         WHERE subject = $1 AND secret IS NOT NULL FOR UPDATE",
+    )
+    .bind(subject.key())
+    .fetch_optional(&mut **tx)
+    .await
+    .context("Failed to load the second factor")?;
+    let Some((secret, backup_codes, last_step, enabled_at)) = row else {
+        return Ok(None);
+    };
+
+    let now = Utc::now().timestamp() as u64;
+    if let Some(step) = totp::verify_code(&secret, code, now) {
+        if last_step.is_some_and(|last| step as i64 <= last) {
+            warn!("🔁 Rejecting a reused TOTP code");
+            return Ok(None);
+        }
+        sqlx::query(
+            "UPDATE admin_second_factors SET last_step = $2, updated_at = NOW() WHERE subject = $1",
+        )
+        .bind(subject.key())
+        .bind(step as i64)
+        .execute(&mut **tx)
+        .await?;
+        return Ok(Some(Verified {
+            method: SecondFactor::Totp,
+            enabled_at,
+        }));
+    }
+
+    let mut hashes: Vec<String> = serde_json::from_str(&backup_codes).unwrap_or_default();
+    if totp::consume_backup_code(&mut hashes, code) {
+        sqlx::query(
+            "UPDATE admin_second_factors SET backup_codes = $2, updated_at = NOW() WHERE subject = $1",
+        )
+        .bind(subject.key())
+        .bind(serde_json::to_string(&hashes)?)
+        .execute(&mut **tx)
+        .await?;
+        return Ok(Some(Verified {
+            method: SecondFactor::BackupCode,
+            enabled_at,
+        }));
+    }
+
+    Ok(None)
+}
+
+/// 🎫 Start the code step for `subject`, who just passed the password step. The token
+/// goes to the browser; only its hash is stored.
+pub async fn issue_challenge(pool: &PgPool, subject: SessionSubject) -> Result<String> {
+    // 🧹 Long expired challenges only matter for the failure window
+    sqlx::query("DELETE FROM admin_login_challenges WHERE created_at < NOW() - INTERVAL '1 day'")
+        .execute(pool)
+        .await
+        .context("Failed to prune login challenges")?;
+
+    let token = hex::encode(rand::thread_rng().gen::<[u8; 32]>());
+    sqlx::query(
+        "INSERT INTO admin_login_challenges (token_hash, subject, expires_at) \
+         VALUES ($1, $2, NOW() + make_interval(secs => $3))",
+    )
+    .bind(challenge_hash(&token))
+    .bind(subject.key())
+    .bind(totp::PRE_AUTH_TTL_SECS as f64)
+    .execute(pool)
+    .await
+    .context("Failed to store the login challenge")?;
+    Ok(token)
+}
+
+/// 🎲 What answering a login challenge led to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChallengeOutcome {
+    /// ✅ Right code: the challenge is spent and `subject` may have a session
+    Passed {
+        subject: SessionSubject,
+        verified: Verified,
+    },
+    /// 🚫 Wrong code, the challenge can be answered again
+    WrongCode { attempts_left: i32 },
+    /// ⌛ Unknown, expired, already spent, or just spent by its last wrong code
+    Expired,
+    /// 🔒 Too many wrong codes for this subject lately (the challenge is spent too)
+    Locked,
+}
+
+/// 🔢 Answer a login challenge with a TOTP or backup code
+pub async fn answer_challenge(pool: &PgPool, token: &str, code: &str) -> Result<ChallengeOutcome> {
+    let mut tx = pool.begin().await?;
+    let hash = challenge_hash(token);
+    // 🔒 Lock the challenge so parallel answers queue up behind each other
+    let row: Option<(String, i32)> = sqlx::query_as(
+        "SELECT subject, failures FROM admin_login_challenges \
+         WHERE token_hash = $1 AND consumed_at IS NULL AND expires_at > NOW() FOR UPDATE",
+    )
+    .bind(&hash)
+    .fetch_optional(&mut *tx)
+    .await
+    .context("Failed to load the login challenge")?;
+    let Some((subject, failures)) = row.and_then(|(key, failures)| {
+        SessionSubject::from_key(&key).map(|subject| (subject, failures))
+    }) else {
+        return Ok(ChallengeOutcome::Expired);
+    };
+
+    let recent_failures: i64 = sqlx::query_scalar(
+        "SELECT COALESCE(SUM(failures), 0) FROM admin_login_challenges \
+         WHERE subject = $1 AND created_at > NOW() - make_interval(mins => $2)",
+    )
+    .bind(subject.key())
+    .bind(SUBJECT_FAILURE_WINDOW_MINS)
+    .fetch_one(&mut *tx)
+    .await
+    .context("Failed to count failed login codes")?;
+    if recent_failures >= MAX_SUBJECT_FAILURES {
+        warn!(
+            "🔒 Refusing the code step for {}: {} wrong codes in the last {} minutes",
+            subject.key(),
+            recent_failures,
+            SUBJECT_FAILURE_WINDOW_MINS
+        );
+        spend_challenge(&mut tx, &hash).await?;
+        tx.commit().await?;
+        return Ok(ChallengeOutcome::Locked);
+    }
+
+    if let Some(verified) = check_in(&mut tx, subject, code).await? {
+        spend_challenge(&mut tx, &hash).await?;
+        tx.commit().await?;
+        return Ok(ChallengeOutcome::Passed { subject, verified });
+    }
+
+    let failures = failures + 1;
+    sqlx::query(
+        "UPDATE admin_login_challenges SET failures = $2, \
+         consumed_at = CASE WHEN $2 >= $3 THEN NOW() END WHERE token_hash = $1",
+    )
+    .bind(&hash)
+    .bind(failures)
+    .bind(MAX_CHALLENGE_FAILURES)
+    .execute(&mut *tx)
+    .await
+    .context("Failed to record a wrong login code")?;
+    tx.commit().await?;
+    Ok(if failures >= MAX_CHALLENGE_FAILURES {
+        ChallengeOutcome::Expired
+    } else {
+        ChallengeOutcome::WrongCode {
+            attempts_left: MAX_CHALLENGE_FAILURES - failures,
+        }
+    })
+}
+
+/// 🎟️ Mark a challenge as spent
+async fn spend_challenge(tx: &mut sqlx::Transaction<'_, sqlx::Postgres>, hash: &str) -> Result<()> {
+    sqlx::query("UPDATE admin_login_challenges SET consumed_at = NOW() WHERE token_hash = $1")
+        .bind(hash)
+        .execute(&mut **tx)
+        .await
+        .context("Failed to spend the login challenge")?;
+    Ok(())
+}
+
+fn challenge_hash(token: &str) -> String {
+    hex::encode(Sha256::digest(token.as_bytes()))
+}
+
+/// 🗑️ Switch `subject`'s second factor off (and drop any pending enrollment)
+pub async fn disable(pool: &PgPool, subject: SessionSubject) -> Result<()> {
+    sqlx::query("DELETE FROM admin_second_factors WHERE subject = $1")
+        .bind(subject.key())
+        .execute(pool)
+        .await
+        .context("Failed to disable the second factor")?;
+    Ok(())
+}
+
+// 🧪 Tests - One authenticator each!
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::migrations;
+    use crate::test_support::spawn_test_app;
+
+    #[tokio::test]
+    async fn test_console_factor_moves_out_of_settings_and_back() {
+        let Some(app) = spawn_test_app().await else {
+            return;
+        };
+        let pool = &app.db_pool;
+        migrations::rollback_migration(pool, "v35_admin_second_factors")
+            .await
+            .unwrap();
+        let secret = totp::generate_secret();
+        sqlx::query(
+            "INSERT INTO settings (key, value) VALUES \
+             ('admin_totp_secret', $1), ('admin_totp_backup_codes', '[\"h\"]'), ('admin_totp_last_step', '42')",
+        )
+        .bind(&secret)
+        .execute(pool)
+        .await
+        .unwrap();
+
+        // ⬆️ The console admin keeps 2FA, now in its own row
+        crate::database::run_migrations(pool).await.unwrap();
+        let status = status(pool, SessionSubject::Bootstrap).await.unwrap();
+        assert!(status.enabled_since.is_some());
+        assert_eq!(status.backup_codes_left, 1);
+        let last_step: Option<i64> = sqlx::query_scalar(
+            "SELECT last_step FROM admin_second_factors WHERE subject = 'config'",
+        )
+        .fetch_one(pool)
+        .await
+        .unwrap();
+        assert_eq!(last_step, Some(42));
+        let leftover: i64 =
+            sqlx::query_scalar("SELECT COUNT(*) FROM settings WHERE key LIKE 'admin_totp_%'")
+                .fetch_one(pool)
+                .await
+                .unwrap();
+        assert_eq!(leftover, 0);
+        // 👤 Accounts start without one
+        let account = SessionSubject::User(Uuid::new_v4());
+        assert_eq!(enabled_since(pool, account).await.unwrap(), None);
+
+        // ⬇️ Rolling back puts the settings rows back
+        migrations::rollback_migration(pool, "v35_admin_second_factors")
+            .await
+            .unwrap();
+        let restored: Option<String> =
+            sqlx::query_scalar("SELECT value FROM settings WHERE key = 'admin_totp_secret'")
+                .fetch_optional(pool)
+                .await
+                .unwrap();
+        assert_eq!(restored, Some(secret));
+        println!("✅ Second factor migration test passed!");
+    }
+}
@@ -0,0 +1,229 @@
+// ⏳ Startup Connection - Wait for Postgres instead of falling over! ⏳
+// Under docker-compose or Kubernetes Feedbacker often starts before Postgres is ready
+// (the host doesn't resolve yet, the port refuses, the server is still starting up).
+// `connect_with_retry` keeps trying to create the pool with capped exponential backoff
+// (0.5s, 1s, 2s ... 10s) until DATABASE_STARTUP_TIMEOUT_SECONDS have passed, logging
+// every failed attempt. Errors that waiting won't fix - a bad URL, a wrong password -
+// give up at once. `warm_up` then opens the pool's minimum connections so the first
+// requests don't pay for them. `--fail-fast` (a zero budget) makes one attempt, for CI.
+// Created with love by Aye & Hue! ✨
+
+use std::future::Future;
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use sqlx::PgPool;
+use tokio::time::Instant;
+use tracing::{info, warn};
+
+/// ⏱️ First pause between attempts
+pub const INITIAL_BACKOFF: Duration = Duration::from_millis(500);
+/// 🧢 Longest pause between attempts
+pub const MAX_BACKOFF: Duration = Duration::from_secs(10);
+
+/// 🔍 Is this a failure that may go away if we wait (the server isn't up yet)?
+pub fn is_transient(error: &anyhow::Error) -> bool {
+    error
+        .chain()
+        .any(|cause| match cause.downcast_ref::<sqlx::Error>() {
+            Some(sqlx::Error::Io(_) | sqlx::Error::PoolTimedOut | sqlx::Error::Tls(_)) => true,
+            // 🐘 57P03: the database system is starting up (or shutting down)
+            Some(sqlx::Error::Database(e)) => e.code().as_deref() == Some("57P03"),
+            _ => cause.is::<tokio::time::error::Elapsed>(),
+        })
+}
+
+/// 🔁 Run `attempt` until it succeeds, a non-transient error comes back, or `budget` is
+/// spent (a zero budget means exactly one attempt). Each attempt is cut off at the end
+/// of the budget, and pauses double from INITIAL_BACKOFF up to MAX_BACKOFF.
+pub async fn retry_until<T, F, Fut>(what: &str, budget: Duration, mut attempt: F) -> Result<T>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T>>,
+{
+    let deadline = Instant::now() + budget;
+    let mut backoff = INITIAL_BACKOFF;
+    let mut attempts: u32 = 0;
+    loop {
+        attempts += 1;
+        let remaining = deadline.saturating_duration_since(Instant::now());
+        let outcome = if budget.is_zero() {
+            attempt().await
+        } else {
+            tokio::time::timeout(remaining, attempt())
+                .await
+                .unwrap_or_else(|elapsed| Err(elapsed.into()))
+        };
+        let error = match outcome {
+            Ok(value) => {
+                if attempts > 1 {
+                    info!("✅ {} succeeded on attempt {}", what, attempts);
+                }
+                return Ok(value);
+            }
+            Err(error) => error,
+        };
+        if !is_transient(&error) {
+            return Err(error);
+        }
+        let remaining = deadline.saturating_duration_since(Instant::now());
+        if remaining.is_zero() {
+            return Err(error).with_context(|| {
+                format!(
+                    "Gave up on {} after {} attempt(s) over {}s",
+                    what,
+                    attempts,
+                    budget.as_secs()
+                )
+            });
+        }
+        let pause = backoff.min(remaining);
+        warn!(
+            "⏳ {} failed (attempt {}), retrying in {:.1}s: {:#}",
+            what,
+            attempts,
+            pause.as_secs_f64(),
+            error
+        );
+        tokio::time::sleep(pause).await;
+        backoff = (backoff * 2).min(MAX_BACKOFF);
+    }
+}
+
+/// 🔌 Create the pool, waiting up to `budget` for the database to accept connections
+pub async fn connect_with_retry(database_url: &str, budget: Duration) -> Result<PgPool> {
+    retry_until("Connecting to the database", budget, || {
+        super::create_pool(database_url)
+    })
+    .await
+}
+
+/// 🔥 Open the pool's minimum connections up front
+pub async fn warm_up(pool: &PgPool) -> Result<()> {
+    let count = pool.options().get_min_connections();
+    let connections = futures_util::future::try_join_all((0..count).map(|_| pool.acquire()))
+        .await
+        .context("Failed to warm up the database pool")?;
+    info!(
+        "🔥 Database pool warmed up with {} connections",
+        connections.len()
+    );
+    Ok(())
+}
+
+// 🧪 Tests - Patience is a virtue!
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicU32, Ordering};
+    use tokio::net::{TcpListener, TcpStream};
+
+    fn refused() -> anyhow::Error {
+        anyhow::Error::new(sqlx::Error::Io(std::io::Error::from(
+            std::io::ErrorKind::ConnectionRefused,
+        )))
+        .context("Failed to create database connection pool")
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_retries_back_off_and_give_up_at_the_budget() {
+        let attempts = AtomicU32::new(0);
+        let started = Instant::now();
+        let error = retry_until("Connecting", Duration::from_secs(30), || async {
+            attempts.fetch_add(1, Ordering::SeqCst);
+            Err::<(), _>(refused())
+        })
+        .await
+        .unwrap_err();
+        // ⏱️ 0.5 + 1 + 2 + 4 + 8 + 10, then the last 4.5s of the budget
+        assert_eq!(attempts.load(Ordering::SeqCst), 8);
+        assert_eq!(started.elapsed(), Duration::from_secs(30));
+        assert!(
+            format!("{:#}", error).starts_with("Gave up on Connecting after 8 attempt(s) over 30s")
+        );
+
+        // 🏁 A success after a few failures ends the wait
+        let attempts = AtomicU32::new(0);
+        let value = retry_until("Connecting", Duration::from_secs(30), || async {
+            match attempts.fetch_add(1, Ordering::SeqCst) {
+                0 | 1 => Err(refused()),
+                _ => Ok(42),
+            }
+        })
+        .await
+        .unwrap();
+        assert_eq!(value, 42);
+
+        // 🚫 Waiting won't fix a bad password, and a zero budget is one attempt
+        let attempts = AtomicU32::new(0);
+        retry_until("Connecting", Duration::from_secs(30), || async {
+            attempts.fetch_add(1, Ordering::SeqCst);
+            Err::<(), _>(anyhow::anyhow!("password authentication failed"))
+        })
+        .await
+        .unwrap_err();
+        let fail_fast = AtomicU32::new(0);
+        retry_until("Connecting", Duration::ZERO, || async {
+            fail_fast.fetch_add(1, Ordering::SeqCst);
+            Err::<(), _>(refused())
+        })
+        .await
+        .unwrap_err();
+        assert_eq!(
+            (
+                attempts.load(Ordering::SeqCst),
+                fail_fast.load(Ordering::SeqCst)
+            ),
+            (1, 1)
+        );
+        println!("✅ Startup retry backoff test passed!");
+    }
+
+    #[tokio::test]
+    async fn test_startup_waits_for_a_database_that_comes_up_late() {
+        let Ok(database_url) = std::env::var("TEST_DATABASE_URL") else {
+            return;
+        };
+        let mut url = reqwest::Url::parse(&database_url).unwrap();
+        let upstream = format!("{}:{}", url.host_str().unwrap(), url.port().unwrap_or(5432));
+
+        // 🚪 A proxy to the real database that only starts listening after a delay
+        let port = TcpListener::bind("127.0.0.1:0")
+            .await
+            .unwrap()
+            .local_addr()
+            .unwrap()
+            .port();
+        tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_millis(1500)).await;
+            let listener = TcpListener::bind(("127.0.0.1", port)).await.unwrap();
+            loop {
+                let Ok((mut client, _)) = listener.accept().await else {
+                    return;
+                };
+                let upstream = upstream.clone();
+                tokio::spawn(async move {
+                    if let Ok(mut server) = TcpStream::connect(upstream).await {
+                        let _ = tokio::io::copy_bidirectional(&mut client, &mut server).await;
+                    }
+                });
+            }
+        });
+        url.set_host(Some("127.0.0.1")).unwrap();
+        url.set_port(Some(port)).unwrap();
+
+        let started = std::time::Instant::now();
+        let pool = connect_with_retry(url.as_str(), Duration::from_secs(30))
+            .await
+            .unwrap();
+        assert!(started.elapsed() >= Duration::from_millis(1500));
+        warm_up(&pool).await.unwrap();
+        assert!(pool.size() >= pool.options().get_min_connections());
+        let one: i32 = sqlx::query_scalar("SELECT 1")
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+        assert_eq!(one, 1);
+        println!("✅ Delayed database startup test passed!");
+    }
+}
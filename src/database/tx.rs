@@ -0,0 +1,152 @@
+// 🧾 Request Transactions - All of a handler's writes, or none of them! 🧾
+// Handlers that write several rows (a decision plus its audit entry, a transfer plus
+// its notifications) take a `Tx` from `AppState::tx()` and pass `&mut tx` (a
+// `PgConnection`) to their helpers. `finish` commits when the work succeeded and rolls
+// back when it didn't; a `Tx` dropped without either (an early return, an error
+// bubbling up with `?`, a panic unwinding) rolls back too. Helpers that open their own
+// transaction with `Acquire::begin` get a savepoint inside a `Tx`, so they work the
+// same inside or outside one.
+// Created with love by Aye & Hue! ✨
+
+use anyhow::{Context, Result};
+use sqlx::{PgConnection, PgPool, Postgres, Transaction};
+use std::ops::{Deref, DerefMut};
+use tracing::{debug, warn};
+
+/// 🧾 A transaction that commits only when told to
+pub struct Tx {
+    inner: Option<Transaction<'static, Postgres>>,
+}
+
+impl Tx {
+    /// 🚪 Start a transaction on a pooled connection
+    pub async fn begin(pool: &PgPool) -> Result<Self> {
+        let inner = pool.begin().await.context("Failed to start transaction")?;
+        Ok(Self { inner: Some(inner) })
+    }
+
+    /// ✅ Make every write so far permanent
+    pub async fn commit(mut self) -> Result<()> {
+        let inner = self.inner.take().expect("transaction already finished");
+        inner.commit().await.context("Failed to commit transaction")
+    }
+
+    /// ↩️ Throw every write so far away
+    pub async fn rollback(mut self) -> Result<()> {
+        let inner = self.inner.take().expect("transaction already finished");
+        inner
+            .rollback()
+            .await
+            .context("Failed to roll back transaction")
+    }
+
+    /// ⚖️ Commit when `result` is Ok, roll back when it's an error (which is returned as is)
+    pub async fn finish<T>(self, result: Result<T>) -> Result<T> {
+        match result {
+            Ok(value) => {
+                self.commit().await?;
+                Ok(value)
+            }
+            Err(e) => {
+                if let Err(rollback) = self.rollback().await {
+                    warn!("⚠️ {:#}", rollback);
+                }
+                Err(e)
+            }
+        }
+    }
+}
+
+impl Deref for Tx {
+    type Target = PgConnection;
+
+    fn deref(&self) -> &PgConnection {
+        self.inner.as_ref().expect("transaction already finished")
+    }
+}
+
+impl DerefMut for Tx {
+    fn deref_mut(&mut self) -> &mut PgConnection {
+        self.inner.as_mut().expect("transaction already finished")
+    }
+}
+
+impl Drop for Tx {
+    fn drop(&mut self) {
+        // ↩️ sqlx rolls the transaction back as it drops
+        if self.inner.is_some() {
+            if std::thread::panicking() {
+                warn!("↩️ Rolling back a transaction after a panic");
+            } else {
+                debug!("↩️ Rolling back an unfinished transaction");
+            }
+        }
+    }
+}
+
+// 🧪 Tests - All or nothing!
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::spawn_test_app;
+
+    async fn insert(conn: &mut PgConnection, key: &str) -> Result<()> {
+        sqlx::query("INSERT INTO settings (key, value) VALUES ($1, 'x')")
+            .bind(key)
+            .execute(conn)
+            .await?;
+        Ok(())
+    }
+
+    async fn stored(pool: &PgPool, key: &str) -> bool {
+        sqlx::query_scalar("SELECT EXISTS (SELECT 1 FROM settings WHERE key = $1)")
+            .bind(key)
+            .fetch_one(pool)
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_transactions_keep_all_or_nothing() {
+        let Some(app) = spawn_test_app().await else {
+            return;
+        };
+        let pool = &app.db_pool;
+
+        let mut tx = Tx::begin(pool).await.unwrap();
+        let result = insert(&mut tx, "tx.committed").await;
+        tx.finish(result).await.unwrap();
+        assert!(stored(pool, "tx.committed").await);
+
+        // 💥 The second write fails, so the first one goes too
+        let mut tx = Tx::begin(pool).await.unwrap();
+        let result = async {
+            insert(&mut tx, "tx.failed").await?;
+            insert(&mut tx, "tx.committed").await
+        }
+        .await;
+        assert!(tx.finish(result).await.is_err());
+        assert!(!stored(pool, "tx.failed").await);
+
+        // 🚪 Returning early without finishing rolls back
+        {
+            let mut tx = Tx::begin(pool).await.unwrap();
+            insert(&mut tx, "tx.dropped").await.unwrap();
+        }
+        assert!(!stored(pool, "tx.dropped").await);
+
+        // 😵 So does a panic halfway through
+        let panicked = tokio::spawn({
+            let pool = pool.clone();
+            async move {
+                let mut tx = Tx::begin(&pool).await.unwrap();
+                insert(&mut tx, "tx.panicked").await.unwrap();
+                panic!("handler blew up");
+            }
+        })
+        .await;
+        assert!(panicked.is_err());
+        assert!(!stored(pool, "tx.panicked").await);
+        println!("✅ Request transaction test passed!");
+    }
+}
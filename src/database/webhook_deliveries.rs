@@ -0,0 +1,85 @@
+// 🧾 Webhook Deliveries - Every signed delivery we accepted, for a little while! 🧾
+// The in-memory replay cache forgets on restart and isn't shared between instances,
+// so accepted deliveries are also recorded here by `utils::deliveries::delivery_key`
+// (a hash of the signed body - an unsigned id header could be swapped on a replay).
+// A delivery whose processing fails is forgotten again, so GitHub's redelivery gets
+// through. A row only has to outlive the skew window - an older replay fails the
+// timestamp check anyway - so rows past the retention are pruned as new deliveries
+// come in.
+// Created with love by Aye & Hue! ✨
+
+use anyhow::{Context, Result};
+use sqlx::PgPool;
+
+/// ✅ Record a delivery; false when it was already recorded within `retention`
+pub async fn record_delivery(
+    pool: &PgPool,
+    delivery_key: &str,
+    retention: chrono::Duration,
+) -> Result<bool> {
+    let retention_secs = retention.num_seconds() as f64;
+    sqlx::query(
+        "DELETE FROM webhook_deliveries WHERE received_at < NOW() - make_interval(secs => $1)",
+    )
+    .bind(retention_secs)
+    .execute(pool)
+    .await
+    .context("Failed to prune old webhook deliveries")?;
+    let recorded = sqlx::query(
+        "INSERT INTO webhook_deliveries (delivery_key) VALUES ($1) ON CONFLICT (delivery_key) DO NOTHING",
+    )
+    .bind(delivery_key)
+    .execute(pool)
+    .await
+    .context("Failed to record webhook delivery")?
+    .rows_affected();
+    Ok(recorded == 1)
+}
+
+/// 🧹 Forget a delivery whose processing failed, so a redelivery is accepted
+pub async fn forget_delivery(pool: &PgPool, delivery_key: &str) -> Result<()> {
+    sqlx::query("DELETE FROM webhook_deliveries WHERE delivery_key = $1")
+        .bind(delivery_key)
+        .execute(pool)
+        .await
+        .context("Failed to forget webhook delivery")?;
+    Ok(())
+}
+
+// 🧪 Tests - Once per delivery!
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::spawn_test_app;
+
+    #[tokio::test]
+    async fn test_deliveries_are_recorded_once_until_they_age_out() {
+        let Some(app) = spawn_test_app().await else {
+            return;
+        };
+        let pool = &app.db_pool;
+        let retention = chrono::Duration::hours(1);
+        assert!(record_delivery(pool, "72d3162e-cc78-11e3", retention)
+            .await
+            .unwrap());
+        assert!(!record_delivery(pool, "72d3162e-cc78-11e3", retention)
+            .await
+            .unwrap());
+
+        // 🕰️ Past the retention the row is pruned, and the id is accepted again
+        sqlx::query("UPDATE webhook_deliveries SET received_at = NOW() - INTERVAL '2 hours'")
+            .execute(pool)
+            .await
+            .unwrap();
+        assert!(record_delivery(pool, "72d3162e-cc78-11e3", retention)
+            .await
+            .unwrap());
+
+        // 🧹 A forgotten delivery is accepted again right away
+        forget_delivery(pool, "72d3162e-cc78-11e3").await.unwrap();
+        assert!(record_delivery(pool, "72d3162e-cc78-11e3", retention)
+            .await
+            .unwrap());
+        println!("✅ Webhook delivery record test passed!");
+    }
+}
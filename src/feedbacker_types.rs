@@ -0,0 +1,271 @@
+// 🤝 Feedbacker Types - The wire format servers and clients agree on! 🤝
+// The JSON envelope every API endpoint answers with, and the error codes it can carry.
+// The server builds these (constructors live in api/mod.rs), and clients such as
+// examples/feedback_client.rs include this file by path and match on `ErrorCode`, so a
+// renamed or removed code breaks their build instead of their users. It only needs
+// serde, serde_json and chrono - keep it that way.
+// The wire strings are part of the API: never change one, add a new code instead.
+// Created with love by Aye & Hue! ✨
+
+use serde::{Deserialize, Serialize};
+
+/// 🎯 Why a request failed, as stable snake_case strings on the wire
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ErrorCode {
+    // 🧾 Requests
+    /// 📝 The request was understood but its fields are not acceptable
+    ValidationError,
+    /// 🧾 The body is not the JSON the endpoint expects
+    InvalidJson,
+    /// 🧾 The body is not JSON at all
+    UnsupportedMediaType,
+    /// 🔍 No such resource (or not one the caller may see)
+    NotFound,
+    /// 🚫 No or invalid credentials
+    Unauthorized,
+    /// 🛡️ Valid credentials that aren't allowed to do this
+    Forbidden,
+    /// 🚦 Too many requests; details carry `retry_after_seconds`
+    RateLimitExceeded,
+    /// 💥 Something broke on our side
+    InternalError,
+    /// ✋ A destructive admin action was sent without its confirmation
+    ConfirmationRequired,
+    /// 👯 The name is already taken
+    DuplicateName,
+    /// 🗂️ The request body is in an API version this server doesn't accept
+    UnsupportedApiVersion,
+
+    // 📝 Feedback
+    /// 🧩 The CAPTCHA or proof-of-work answer was missing or wrong
+    ChallengeFailed,
+    /// ⚖️ The held changes were already approved or rejected
+    AlreadyDecided,
+    /// ⚖️ The feedback has no changes waiting for approval
+    NotAwaitingApproval,
+    /// 🙋 The feedback was already picked up, so it can't be withdrawn
+    NotPending,
+    /// 📎 The attachment is over the size limit
+    AttachmentTooLarge,
+    /// 📎 The attachment's type isn't accepted
+    UnsupportedAttachmentType,
+
+    // 🏠 Projects and imports
+    /// 🤝 The target already has a project for this repository
+    RepositoryCollision,
+    /// 🏃 Someone else changed the project meanwhile
+    ConcurrentChange,
+    /// 🐙 GitHub couldn't be asked
+    GithubUnavailable,
+    /// 🐙 The target account has no GitHub username to check access with
+    GithubUsernameRequired,
+    /// 🐙 The target account can't push to the repository
+    NoWriteAccess,
+    /// 🚪 The repository is gone or no longer open to us
+    RepositoryUnavailable,
+    /// 📄 The import file can't be read as the format it claims to be
+    MalformedImport,
+    /// 🔢 The import file has too many rows
+    ImportTooLarge,
+    /// 📡 The upload itself failed
+    UploadFailed,
+
+    // 🪝 Webhook deliveries
+    /// 🔏 Missing or wrong signature
+    InvalidSignature,
+    /// ⏰ The delivery says nothing about when it was sent
+    DeliveryTimestampMissing,
+    /// ⏰ The delivery was sent too long ago (or in the future)
+    DeliveryExpired,
+    /// 🔁 The delivery was already received
+    DeliveryReplayed,
+
+    // 🎯 Issue automation
+    /// 🎯 The webhook's automation failed
+    AutomationFailed,
+    /// 🐙 Creating the issue failed
+    IssueCreationFailed,
+    /// 💬 Commenting failed
+    CommentFailed,
+    /// 🏷️ Labelling failed
+    LabelsFailed,
+    /// 🔒 Closing the issue failed
+    CloseFailed,
+    /// 🔑 Creating issues needs an API key
+    ApiKeyRequired,
+    /// 🔑 The API key lacks the scope this needs
+    InsufficientScope,
+    /// 🔑 The API key is limited to other repositories
+    RepositoryNotAllowed,
+    /// 🔢 The API key has used up its issue quota for now
+    IssueQuotaExhausted,
+
+    /// ❓ A code this build doesn't know yet (a newer server)
+    #[serde(other)]
+    Unknown,
+}
+
+impl ErrorCode {
+    /// 🏷️ The code as it appears on the wire
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ErrorCode::ValidationError => "validation_error",
+            ErrorCode::InvalidJson => "invalid_json",
+            ErrorCode::UnsupportedMediaType => "unsupported_media_type",
+            ErrorCode::NotFound => "not_found",
+            ErrorCode::Unauthorized => "unauthorized",
+            ErrorCode::Forbidden => "forbidden",
+            ErrorCode::RateLimitExceeded => "rate_limit_exceeded",
+            ErrorCode::InternalError => "internal_error",
+            ErrorCode::ConfirmationRequired => "confirmation_required",
+            ErrorCode::DuplicateName => "duplicate_name",
+            ErrorCode::UnsupportedApiVersion => "unsupported_api_version",
+            ErrorCode::ChallengeFailed => "challenge_failed",
+            ErrorCode::AlreadyDecided => "already_decided",
+            ErrorCode::NotAwaitingApproval => "not_awaiting_approval",
+            ErrorCode::NotPending => "not_pending",
+            ErrorCode::AttachmentTooLarge => "attachment_too_large",
+            ErrorCode::UnsupportedAttachmentType => "unsupported_attachment_type",
+            ErrorCode::RepositoryCollision => "repository_collision",
+            ErrorCode::ConcurrentChange => "concurrent_change",
+            ErrorCode::GithubUnavailable => "github_unavailable",
+            ErrorCode::GithubUsernameRequired => "github_username_required",
+            ErrorCode::NoWriteAccess => "no_write_access",
+            ErrorCode::RepositoryUnavailable => "repository_unavailable",
+            ErrorCode::MalformedImport => "malformed_import",
+            ErrorCode::ImportTooLarge => "import_too_large",
+            ErrorCode::UploadFailed => "upload_failed",
+            ErrorCode::InvalidSignature => "invalid_signature",
+            ErrorCode::DeliveryTimestampMissing => "delivery_timestamp_missing",
+            ErrorCode::DeliveryExpired => "delivery_expired",
+            ErrorCode::DeliveryReplayed => "delivery_replayed",
+            ErrorCode::AutomationFailed => "automation_failed",
+            ErrorCode::IssueCreationFailed => "issue_creation_failed",
+            ErrorCode::CommentFailed => "comment_failed",
+            ErrorCode::LabelsFailed => "labels_failed",
+            ErrorCode::CloseFailed => "close_failed",
+            ErrorCode::ApiKeyRequired => "api_key_required",
+            ErrorCode::InsufficientScope => "insufficient_scope",
+            ErrorCode::RepositoryNotAllowed => "repository_not_allowed",
+            ErrorCode::IssueQuotaExhausted => "issue_quota_exhausted",
+            ErrorCode::Unknown => "unknown",
+        }
+    }
+}
+
+impl std::fmt::Display for ErrorCode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// 📝 Standard API response structure
+/// Provides consistent response format across all endpoints
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ApiResponse<T> {
+    /// ✅ Whether the operation was successful
+    pub success: bool,
+    /// 📝 Human-readable message
+    pub message: String,
+    /// 📊 Response data (optional)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub data: Option<T>,
+    /// ❌ Error details (only present if success = false)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<ApiError>,
+    /// ⏰ Response timestamp
+    pub timestamp: chrono::DateTime<chrono::Utc>,
+}
+
+/// ❌ API error structure
+/// Provides structured error information for debugging and user feedback
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ApiError {
+    /// 🎯 Error code for programmatic handling
+    pub code: ErrorCode,
+    /// 📝 Human-readable error message
+    pub message: String,
+    /// 🔍 Additional error details (optional)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub details: Option<serde_json::Value>,
+}
+
+// 🧪 Tests - Promises kept on the wire!
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 📸 Every code's wire string. Appending is fine; editing a line breaks clients.
+    const WIRE_SNAPSHOT: &str = "\
+validation_error
+invalid_json
+unsupported_media_type
+not_found
+unauthorized
+forbidden
+rate_limit_exceeded
+internal_error
+confirmation_required
+duplicate_name
+challenge_failed
+already_decided
+not_awaiting_approval
+not_pending
+attachment_too_large
+unsupported_attachment_type
+repository_collision
+concurrent_change
+github_unavailable
+github_username_required
+no_write_access
+malformed_import
+import_too_large
+upload_failed
+invalid_signature
+delivery_timestamp_missing
+delivery_expired
+delivery_replayed
+automation_failed
+issue_creation_failed
+comment_failed
+labels_failed
+close_failed
+api_key_required
+insufficient_scope
+repository_not_allowed
+issue_quota_exhausted
+unsupported_api_version
+repository_unavailable
+unknown";
+
+    #[test]
+    fn test_error_codes_keep_their_wire_strings() {
+        for wire in WIRE_SNAPSHOT.lines() {
+            let code: ErrorCode = serde_json::from_value(serde_json::json!(wire)).unwrap();
+            assert_eq!(code.as_str(), wire);
+            assert_eq!(serde_json::to_value(code).unwrap(), wire);
+        }
+        // 🆕 A newer server's code still parses
+        let newer: ErrorCode =
+            serde_json::from_value(serde_json::json!("quota_exhausted")).unwrap();
+        assert_eq!(newer, ErrorCode::Unknown);
+        println!("✅ Error code wire snapshot test passed!");
+    }
+
+    #[test]
+    fn test_error_envelope_round_trips() {
+        let body = serde_json::json!({
+            "success": false,
+            "message": "Operation failed",
+            "error": { "code": "rate_limit_exceeded", "message": "Slow down", "details": { "retry_after_seconds": 30 } },
+            "timestamp": "2026-10-15T09:00:00Z",
+        });
+        let response: ApiResponse<serde_json::Value> =
+            serde_json::from_value(body.clone()).unwrap();
+        let error = response.error.as_ref().unwrap();
+        assert_eq!(error.code, ErrorCode::RateLimitExceeded);
+        assert_eq!(serde_json::to_value(&response).unwrap(), body);
+        println!("✅ Error envelope round trip test passed!");
+    }
+}
@@ -0,0 +1,616 @@
+// 🔗 GitHub Artifacts - Where a feedback item ended up on GitHub! 🔗
+// One place turns the scattered columns (`branch_name`, `pull_request_url`, the
+// `feedback_pull_requests` row, `metadata.self_issue`) into links for the branch, the
+// pull request and the issue. Our database only records creation, so single-item
+// lookups ask GitHub what became of the PR and the issue since; answers are cached
+// for ARTIFACT_STATE_TTL, and failures (a 404, GitHub down) for ARTIFACT_ERROR_TTL, in
+// an LRU of at most ARTIFACT_STATE_CAPACITY entries. When GitHub can't be asked the
+// stored states are served with `stale: true`. Anonymous views (`ArtifactLookup::Cached`)
+// never wait on GitHub: they get whatever the cache holds, and a miss is looked up in
+// the background for the next visitor.
+// Created with love by Aye & Hue! ✨
+
+use anyhow::{Context, Result};
+use serde::Serialize;
+use sqlx::PgPool;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tracing::warn;
+
+use super::ops::GitHubOps;
+use super::throttle::{Clock, SystemClock};
+use crate::database::models::Feedback;
+
+/// ⏰ How long an answer from GitHub is reused
+pub const ARTIFACT_STATE_TTL: Duration = Duration::from_secs(10 * 60);
+/// ⏰ How long a failed lookup is remembered before GitHub is asked again
+pub const ARTIFACT_ERROR_TTL: Duration = Duration::from_secs(60);
+/// 📏 Most answers kept (the least recently used go first)
+pub const ARTIFACT_STATE_CAPACITY: usize = 10_000;
+
+/// 🌐 Where links point when nothing stored says otherwise
+const GITHUB_WEB_URL: &str = "https://github.com";
+
+/// 🚦 What became of a pull request or issue
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ArtifactState {
+    Open,
+    Closed,
+    /// 🔀 Pull requests only
+    Merged,
+}
+
+/// 🌿 The branch the changes were pushed to
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct BranchLink {
+    pub name: String,
+    pub url: String,
+}
+
+/// 🔢 A pull request or issue
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct NumberedLink {
+    /// 📦 "owner/repo" it lives in
+    pub repository: String,
+    pub number: u64,
+    pub url: String,
+    /// 🚦 None when nothing is known yet
+    pub state: Option<ArtifactState>,
+}
+
+/// 🔗 Everything a feedback item produced on GitHub
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct GithubArtifacts {
+    pub branch: Option<BranchLink>,
+    pub pull_request: Option<NumberedLink>,
+    pub issue: Option<NumberedLink>,
+    /// 🕰️ GitHub couldn't be asked, so the states are as we last recorded them
+    pub stale: bool,
+}
+
+/// 🗄️ The columns artifacts are assembled from (any of them may be missing)
+#[derive(Debug, Clone, Default)]
+pub struct StoredArtifacts<'a> {
+    pub repository: &'a str,
+    pub branch_name: Option<&'a str>,
+    pub pull_request_url: Option<&'a str>,
+    /// 🔢 From `feedback_pull_requests`
+    pub pull_request_number: Option<u64>,
+    /// 🚪 `feedback_pull_requests.closed_at` is set
+    pub pull_request_closed: bool,
+    /// 🐛 ("owner/repo", number) from `metadata.self_issue`
+    pub issue: Option<(&'a str, u64)>,
+}
+
+impl GithubArtifacts {
+    /// 🧩 Links from whatever was stored
+    pub fn assemble(stored: &StoredArtifacts) -> Self {
+        let parsed_pr = stored.pull_request_url.and_then(parse_pull_request_url);
+        // 🌐 A stored PR URL tells us which GitHub this is
+        let web = parsed_pr
+            .as_ref()
+            .map(|(web, ..)| web.as_str())
+            .unwrap_or(GITHUB_WEB_URL);
+
+        let branch = stored.branch_name.map(|name| BranchLink {
+            name: name.to_string(),
+            url: format!("{}/{}/tree/{}", web, stored.repository, name),
+        });
+        let pull_request = match (parsed_pr.as_ref(), stored.pull_request_number) {
+            (Some((_, repository, number)), _) => Some((repository.clone(), *number)),
+            (None, Some(number)) => Some((stored.repository.to_string(), number)),
+            (None, None) => None,
+        }
+        .map(|(repository, number)| NumberedLink {
+            url: stored
+                .pull_request_url
+                .filter(|_| parsed_pr.is_some())
+                .map(str::to_string)
+                .unwrap_or_else(|| format!("{}/{}/pull/{}", web, repository, number)),
+            repository,
+            number,
+            state: Some(if stored.pull_request_closed {
+                ArtifactState::Closed
+            } else {
+                ArtifactState::Open
+            }),
+        });
+        let issue = stored.issue.map(|(repository, number)| NumberedLink {
+            repository: repository.to_string(),
+            number,
+            url: format!("{}/{}/issues/{}", web, repository, number),
+            state: None,
+        });
+        Self {
+            branch,
+            pull_request,
+            issue,
+            stale: false,
+        }
+    }
+
+    /// 🔄 Ask GitHub (or the cache) what the PR and issue are now; anything that can't
+    /// be asked keeps its stored state and marks the whole thing stale
+    pub async fn refresh(&mut self, github: &dyn GitHubOps, cache: &ArtifactStateCache) {
+        for (kind, link) in [
+            (ArtifactKind::PullRequest, self.pull_request.as_mut()),
+            (ArtifactKind::Issue, self.issue.as_mut()),
+        ] {
+            let Some(link) = link else {
+                continue;
+            };
+            match cache
+                .state(github, kind, &link.repository, link.number)
+                .await
+            {
+                Ok(state) => link.state = Some(state),
+                Err(e) => {
+                    warn!("⚠️ Serving stored state for {}: {:#}", link.url, e);
+                    self.stale = true;
+                }
+            }
+        }
+    }
+
+    /// 🗃️ The PR's and issue's states from the cache alone. An answer past its TTL is
+    /// still served (it's newer than what we stored); a missing or outdated one is
+    /// looked up in the background, and a remembered failure marks the result stale.
+    pub fn refresh_from_cache(
+        &mut self,
+        github: &Arc<dyn GitHubOps>,
+        cache: &Arc<ArtifactStateCache>,
+    ) {
+        for (kind, link) in [
+            (ArtifactKind::PullRequest, self.pull_request.as_mut()),
+            (ArtifactKind::Issue, self.issue.as_mut()),
+        ] {
+            let Some(link) = link else {
+                continue;
+            };
+            let key = (kind, link.repository.clone(), link.number);
+            let cached = cache.lookup(&key);
+            if !cached.as_ref().is_some_and(|(fresh, _)| *fresh) {
+                cache.refresh_in_background(github, key);
+            }
+            match cached {
+                Some((_, Ok(state))) => link.state = Some(state),
+                Some((_, Err(_))) => self.stale = true,
+                None => {}
+            }
+        }
+    }
+}
+
+/// 🚦 How far a lookup may go for the PR's and issue's current states
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ArtifactLookup {
+    /// 🌐 Ask GitHub when the cache has nothing fresh
+    Live,
+    /// 🗃️ Cached answers only (anonymous views)
+    Cached,
+}
+
+/// 🔍 (web base, "owner/repo", number) from `https://github.com/owner/repo/pull/42`
+fn parse_pull_request_url(url: &str) -> Option<(String, String, u64)> {
+    let (before, number) = url.trim_end_matches('/').rsplit_once("/pull/")?;
+    let number = number.parse().ok()?;
+    let (before, repo) = before.rsplit_once('/')?;
+    let (web, owner) = before.rsplit_once('/')?;
+    if !web.contains("://") || owner.is_empty() || repo.is_empty() {
+        return None;
+    }
+    Some((web.to_string(), format!("{}/{}", owner, repo), number))
+}
+
+/// 🏷️ Pull requests and issues share numbers, so the cache tells them apart
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ArtifactKind {
+    PullRequest,
+    Issue,
+}
+
+/// 🔑 (kind, "owner/repo", number)
+type ArtifactKey = (ArtifactKind, String, u64);
+
+/// 📨 GitHub's answer, or why there wasn't one
+type Answer = std::result::Result<ArtifactState, String>;
+
+/// 🗃️ Recent answers from GitHub (failures too), in a bounded LRU
+#[derive(Debug)]
+pub struct ArtifactStateCache {
+    capacity: usize,
+    clock: Arc<dyn Clock>,
+    inner: Mutex<LruAnswers>,
+}
+
+#[derive(Debug, Default)]
+struct LruAnswers {
+    tick: u64,
+    /// 🔑 key -> (when it was answered, the answer, tick it was last used at)
+    entries: HashMap<ArtifactKey, (Instant, Answer, u64)>,
+    /// 🕰️ tick -> key, least recently used first
+    order: BTreeMap<u64, ArtifactKey>,
+    /// 🔄 Keys a background lookup is already running for
+    refreshing: HashSet<ArtifactKey>,
+}
+
+impl Default for ArtifactStateCache {
+    fn default() -> Self {
+        Self::with_clock(ARTIFACT_STATE_CAPACITY, Arc::new(SystemClock))
+    }
+}
+
+impl ArtifactStateCache {
+    pub fn with_clock(capacity: usize, clock: Arc<dyn Clock>) -> Self {
+        Self {
+            capacity: capacity.max(1),
+            clock,
+            inner: Mutex::default(),
+        }
+    }
+
+    /// 🚦 The cached state if it's still fresh, else GitHub's (a failure is remembered
+    /// for ARTIFACT_ERROR_TTL, so a missing PR isn't asked about on every view)
+    pub async fn state(
+        &self,
+        github: &dyn GitHubOps,
+        kind: ArtifactKind,
+        repository: &str,
+        number: u64,
+    ) -> Result<ArtifactState> {
+        let key = (kind, repository.to_string(), number);
+        let answer = match self.lookup(&key) {
+            Some((true, answer)) => answer,
+            _ => self.ask(github, key).await,
+        };
+        answer.map_err(|e| anyhow::anyhow!(e))
+    }
+
+    /// 🔍 The remembered answer for `key` and whether it's still fresh
+    fn lookup(&self, key: &ArtifactKey) -> Option<(bool, Answer)> {
+        let now = self.clock.now();
+        let mut lru = self.inner.lock().unwrap();
+        lru.tick += 1;
+        let tick = lru.tick;
+        let (answered, answer, used) = lru.entries.get_mut(key)?;
+        let ttl = match answer {
+            Ok(_) => ARTIFACT_STATE_TTL,
+            Err(_) => ARTIFACT_ERROR_TTL,
+        };
+        let found = (now.duration_since(*answered) < ttl, answer.clone());
+        let last_used = std::mem::replace(used, tick);
+        lru.order.remove(&last_used);
+        lru.order.insert(tick, key.clone());
+        Some(found)
+    }
+
+    /// 🌐 Ask GitHub and remember the answer, whatever it is
+    async fn ask(&self, github: &dyn GitHubOps, key: ArtifactKey) -> Answer {
+        let (kind, repository, number) = &key;
+        let answer = match repository.split_once('/') {
+            None => Err(format!("Invalid repository {}", repository)),
+            Some((owner, repo)) => match kind {
+                ArtifactKind::PullRequest => github.pull_request_state(owner, repo, *number).await,
+                ArtifactKind::Issue => github.issue_state(owner, repo, *number).await,
+            }
+            .map_err(|e| format!("{:#}", e)),
+        };
+        self.remember(key, answer.clone());
+        answer
+    }
+
+    /// 💾 Store an answer, evicting the least recently used beyond capacity
+    fn remember(&self, key: ArtifactKey, answer: Answer) {
+        let now = self.clock.now();
+        let mut lru = self.inner.lock().unwrap();
+        lru.tick += 1;
+        let tick = lru.tick;
+        if let Some((_, _, used)) = lru.entries.insert(key.clone(), (now, answer, tick)) {
+            lru.order.remove(&used);
+        }
+        lru.order.insert(tick, key);
+        while lru.entries.len() > self.capacity {
+            let Some((_, oldest)) = lru.order.pop_first() else {
+                break;
+            };
+            lru.entries.remove(&oldest);
+        }
+    }
+
+    /// 🔄 Look `key` up without anyone waiting for it (one lookup per key at a time)
+    fn refresh_in_background(self: &Arc<Self>, github: &Arc<dyn GitHubOps>, key: ArtifactKey) {
+        if !self.inner.lock().unwrap().refreshing.insert(key.clone()) {
+            return;
+        }
+        let (cache, github) = (self.clone(), github.clone());
+        tokio::spawn(async move {
+            if let Err(e) = cache.ask(github.as_ref(), key.clone()).await {
+                warn!("⚠️ Background lookup of {} #{} failed: {}", key.1, key.2, e);
+            }
+            cache.inner.lock().unwrap().refreshing.remove(&key);
+        });
+    }
+
+    /// 📏 Answers currently remembered
+    pub fn len(&self) -> usize {
+        self.inner.lock().unwrap().entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// 🗄️ Assemble a feedback item's artifacts from the database (no GitHub calls)
+pub async fn stored_artifacts(pool: &PgPool, feedback: &Feedback) -> Result<GithubArtifacts> {
+    let pull_request: Option<(i64, bool)> = sqlx::query_as(
+        "SELECT number, closed_at IS NOT NULL FROM feedback_pull_requests WHERE feedback_id = $1",
+    )
+    .bind(feedback.id)
+    .fetch_optional(pool)
+    .await
+    .context("Failed to load the feedback's pull request")?;
+    let self_issue = feedback
+        .metadata
+        .as_ref()
+        .and_then(|metadata| metadata.get("self_issue"));
+    let issue = self_issue.and_then(|issue| {
+        Some((
+            issue.get("repository")?.as_str()?,
+            issue.get("number")?.as_u64()?,
+        ))
+    });
+    Ok(GithubArtifacts::assemble(&StoredArtifacts {
+        repository: &feedback.repository,
+        branch_name: feedback.branch_name.as_deref(),
+        pull_request_url: feedback.pull_request_url.as_deref(),
+        pull_request_number: pull_request.map(|(number, _)| number as u64),
+        pull_request_closed: pull_request.is_some_and(|(_, closed)| closed),
+        issue,
+    }))
+}
+
+/// 🔄 Stored artifacts with the PR's and issue's current state (single-item lookups)
+pub async fn current_artifacts(
+    pool: &PgPool,
+    github: &Arc<dyn GitHubOps>,
+    cache: &Arc<ArtifactStateCache>,
+    feedback: &Feedback,
+    lookup: ArtifactLookup,
+) -> Result<GithubArtifacts> {
+    let mut artifacts = stored_artifacts(pool, feedback).await?;
+    match lookup {
+        ArtifactLookup::Live => artifacts.refresh(github.as_ref(), cache).await,
+        ArtifactLookup::Cached => artifacts.refresh_from_cache(github, cache),
+    }
+    Ok(artifacts)
+}
+
+// 🧪 Tests - Every link where it belongs!
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{FakeClock, FakeGitHub};
+
+    #[test]
+    fn test_links_are_assembled_from_partial_data() {
+        assert_eq!(
+            GithubArtifacts::assemble(&StoredArtifacts {
+                repository: "8b-is/smart-tree",
+                ..Default::default()
+            }),
+            GithubArtifacts::default()
+        );
+
+        // 🌿 Only a branch so far
+        let branch_only = GithubArtifacts::assemble(&StoredArtifacts {
+            repository: "8b-is/smart-tree",
+            branch_name: Some("feedbacker/dark-mode"),
+            ..Default::default()
+        });
+        assert_eq!(
+            branch_only.branch.unwrap().url,
+            "https://github.com/8b-is/smart-tree/tree/feedbacker/dark-mode"
+        );
+        assert_eq!(branch_only.pull_request, None);
+
+        // 🔀 The PR URL wins for number, repository and host
+        let full = GithubArtifacts::assemble(&StoredArtifacts {
+            repository: "8b-is/smart-tree",
+            branch_name: Some("feedbacker/dark-mode"),
+            pull_request_url: Some("https://ghe.8b.is/8b-is/smart-tree/pull/42"),
+            pull_request_number: Some(41),
+            pull_request_closed: true,
+            issue: Some(("8b-is/Feedbacker", 7)),
+        });
+        assert_eq!(
+            full.branch.unwrap().url,
+            "https://ghe.8b.is/8b-is/smart-tree/tree/feedbacker/dark-mode"
+        );
+        let pr = full.pull_request.unwrap();
+        assert_eq!(
+            (pr.number, pr.url.as_str(), pr.state),
+            (
+                42,
+                "https://ghe.8b.is/8b-is/smart-tree/pull/42",
+                Some(ArtifactState::Closed)
+            )
+        );
+        let issue = full.issue.unwrap();
+        assert_eq!(
+            (issue.url.as_str(), issue.state),
+            ("https://ghe.8b.is/8b-is/Feedbacker/issues/7", None)
+        );
+
+        // 🔢 A number without a (usable) URL still makes a link
+        let number_only = GithubArtifacts::assemble(&StoredArtifacts {
+            repository: "8b-is/smart-tree",
+            pull_request_url: Some("not a url"),
+            pull_request_number: Some(9),
+            ..Default::default()
+        });
+        let pr = number_only.pull_request.unwrap();
+        assert_eq!(
+            (pr.url.as_str(), pr.state),
+            (
+                "https://github.com/8b-is/smart-tree/pull/9",
+                Some(ArtifactState::Open)
+            )
+        );
+        println!("✅ Artifact link assembly test passed!");
+    }
+
+    #[tokio::test]
+    async fn test_refresh_is_cached_and_falls_back_to_stored_states() {
+        let github = FakeGitHub::default();
+        let clock = Arc::new(FakeClock::default());
+        let cache = ArtifactStateCache::with_clock(ARTIFACT_STATE_CAPACITY, clock.clone());
+        let stored = GithubArtifacts::assemble(&StoredArtifacts {
+            repository: "8b-is/smart-tree",
+            pull_request_url: Some("https://github.com/8b-is/smart-tree/pull/42"),
+            issue: Some(("8b-is/Feedbacker", 7)),
+            ..Default::default()
+        });
+
+        // 🕰️ GitHub is down: stored states, flagged stale
+        *github.fail_with.lock().unwrap() = Some("GitHub is down".to_string());
+        let mut artifacts = stored.clone();
+        artifacts.refresh(&github, &cache).await;
+        assert!(artifacts.stale);
+        assert_eq!(
+            artifacts.pull_request.as_ref().unwrap().state,
+            Some(ArtifactState::Open)
+        );
+        assert_eq!(artifacts.issue.as_ref().unwrap().state, None);
+
+        // 🔀 Back up: the PR was merged and the issue closed since we recorded them.
+        // The failure is remembered for ARTIFACT_ERROR_TTL, then GitHub is asked again
+        *github.fail_with.lock().unwrap() = None;
+        github
+            .merged_pull_requests
+            .lock()
+            .unwrap()
+            .push(("8b-is/smart-tree".to_string(), 42));
+        github
+            .closed_issues
+            .lock()
+            .unwrap()
+            .push(("8b-is/Feedbacker".to_string(), 7));
+        let mut artifacts = stored.clone();
+        artifacts.refresh(&github, &cache).await;
+        assert!(artifacts.stale);
+        clock.advance(ARTIFACT_ERROR_TTL);
+        let mut artifacts = stored.clone();
+        artifacts.refresh(&github, &cache).await;
+        assert!(!artifacts.stale);
+        assert_eq!(
+            artifacts.pull_request.as_ref().unwrap().state,
+            Some(ArtifactState::Merged)
+        );
+        assert_eq!(
+            artifacts.issue.as_ref().unwrap().state,
+            Some(ArtifactState::Closed)
+        );
+
+        // 🗃️ Within the TTL the cached answers are served even while GitHub is down
+        *github.fail_with.lock().unwrap() = Some("GitHub is down".to_string());
+        let mut cached = stored.clone();
+        cached.refresh(&github, &cache).await;
+        assert_eq!(cached, artifacts);
+        println!("✅ Artifact refresh test passed!");
+    }
+
+    #[tokio::test]
+    async fn test_cache_is_bounded_and_least_recently_used_goes_first() {
+        let github = FakeGitHub::default();
+        let clock = Arc::new(FakeClock::default());
+        let cache = ArtifactStateCache::with_clock(2, clock);
+        let state = |number| {
+            cache.state(
+                &github,
+                ArtifactKind::PullRequest,
+                "8b-is/smart-tree",
+                number,
+            )
+        };
+        for number in [1, 2] {
+            assert_eq!(state(number).await.unwrap(), ArtifactState::Open);
+        }
+        // 🔀 Both merge on GitHub; #1 is used again, so #2 is the one a third PR evicts
+        for number in [1, 2] {
+            github
+                .merged_pull_requests
+                .lock()
+                .unwrap()
+                .push(("8b-is/smart-tree".to_string(), number));
+        }
+        assert_eq!(state(1).await.unwrap(), ArtifactState::Open);
+        assert_eq!(state(3).await.unwrap(), ArtifactState::Open);
+        assert_eq!(cache.len(), 2);
+        assert_eq!(state(1).await.unwrap(), ArtifactState::Open);
+        assert_eq!(state(2).await.unwrap(), ArtifactState::Merged);
+        println!("✅ Bounded artifact cache test passed!");
+    }
+
+    #[tokio::test]
+    async fn test_cached_lookups_never_wait_on_github() {
+        let fake = Arc::new(FakeGitHub::default());
+        let github: Arc<dyn GitHubOps> = fake.clone();
+        let cache = Arc::new(ArtifactStateCache::default());
+        fake.merged_pull_requests
+            .lock()
+            .unwrap()
+            .push(("8b-is/smart-tree".to_string(), 42));
+        let stored = GithubArtifacts::assemble(&StoredArtifacts {
+            repository: "8b-is/smart-tree",
+            pull_request_number: Some(42),
+            ..Default::default()
+        });
+
+        // 🗃️ Nothing cached: the stored state, with the lookup left to the background
+        let mut artifacts = stored.clone();
+        artifacts.refresh_from_cache(&github, &cache);
+        assert_eq!(
+            artifacts.pull_request.as_ref().unwrap().state,
+            Some(ArtifactState::Open)
+        );
+        assert!(!artifacts.stale);
+        for _ in 0..50 {
+            if !cache.is_empty() {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        let mut artifacts = stored.clone();
+        artifacts.refresh_from_cache(&github, &cache);
+        assert_eq!(
+            artifacts.pull_request.as_ref().unwrap().state,
+            Some(ArtifactState::Merged)
+        );
+
+        // 🚫 A PR GitHub doesn't know is remembered as a failure and shown stale
+        *fake.fail_with.lock().unwrap() = Some("404 Not Found".to_string());
+        let missing = GithubArtifacts::assemble(&StoredArtifacts {
+            repository: "8b-is/smart-tree",
+            pull_request_number: Some(404),
+            ..Default::default()
+        });
+        missing.clone().refresh_from_cache(&github, &cache);
+        for _ in 0..50 {
+            if cache.len() == 2 {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        let mut artifacts = missing.clone();
+        artifacts.refresh_from_cache(&github, &cache);
+        assert!(artifacts.stale);
+        assert_eq!(
+            artifacts.pull_request.as_ref().unwrap().state,
+            Some(ArtifactState::Open)
+        );
+        println!("✅ Cache-only artifact lookup test passed!");
+    }
+}
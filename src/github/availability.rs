@@ -0,0 +1,419 @@
+// 🚪 Repository Availability - Noticing when a repository is gone or shut to us! 🚪
+// A repository can be deleted, made private beyond our token, archived, or have our app
+// uninstalled while feedback for it is mid-pipeline. A 404 or 403 from one call only
+// hints at that (a missing file answers 404 too), so the hint is confirmed by fetching
+// the repository itself: when that fails the same way, or we can no longer push, the
+// repository is unavailable. The feedback then fails with `repository_unavailable`
+// (never retried), the projects serving the repository are deactivated and their
+// owners notified, and issue automation skips the repository until an admin
+// reactivates the project - which checks access again before flipping it back.
+// Created with love by Aye & Hue! ✨
+
+use anyhow::{Context, Result};
+use serde::Serialize;
+use sqlx::{PgConnection, PgPool};
+use std::fmt;
+use tracing::warn;
+use uuid::Uuid;
+
+use super::ops::GitHubOps;
+
+/// ❌ `error_message` of feedback whose repository went away mid-pipeline
+pub const REPOSITORY_UNAVAILABLE: &str = "repository_unavailable";
+
+/// 🚪 GitHub no longer lets us at a repository
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct RepositoryUnavailable {
+    /// 🎯 "owner/repo"
+    pub repository: String,
+    /// 📝 What GitHub said (not found, forbidden, can't push, ...)
+    pub reason: String,
+}
+
+impl RepositoryUnavailable {
+    pub fn new(repository: impl Into<String>, reason: impl Into<String>) -> Self {
+        Self {
+            repository: repository.into(),
+            reason: reason.into(),
+        }
+    }
+
+    /// 🔍 Find a confirmed unavailability anywhere in an error chain
+    pub fn find(error: &anyhow::Error) -> Option<Self> {
+        error
+            .chain()
+            .find_map(|cause| cause.downcast_ref::<Self>().cloned())
+    }
+}
+
+impl fmt::Display for RepositoryUnavailable {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Repository {} is unavailable: {}",
+            self.repository, self.reason
+        )
+    }
+}
+
+impl std::error::Error for RepositoryUnavailable {}
+
+/// 🔍 What a GitHub answer means for our access, when it's one that can mean "gone":
+/// 404 (deleted, renamed away or private to us), 403 that isn't a rate limit
+/// (installation removed, token scope, archived), 451 (blocked)
+fn lost_access_reason(status: u16, message: &str) -> Option<String> {
+    match status {
+        404 => Some("not found (deleted, or private beyond our token)".to_string()),
+        403 if message.to_lowercase().contains("rate limit") => None,
+        403 => Some(format!("forbidden: {}", message)),
+        451 => Some(format!("blocked: {}", message)),
+        _ => None,
+    }
+}
+
+/// 🔍 The lost-access signature in an error chain, if it has one
+pub fn lost_access(error: &anyhow::Error) -> Option<String> {
+    error.chain().find_map(|cause| {
+        if let Some(unavailable) = cause.downcast_ref::<RepositoryUnavailable>() {
+            return Some(unavailable.reason.clone());
+        }
+        match cause.downcast_ref::<octocrab::Error>() {
+            Some(octocrab::Error::GitHub { source, .. }) => {
+                lost_access_reason(source.status_code.as_u16(), &source.message)
+            }
+            _ => None,
+        }
+    })
+}
+
+/// 🔑 Fetch the repository to see whether it's still ours to work on. Errors that say
+/// nothing about access (GitHub down, ...) are returned as errors.
+pub async fn check(
+    github: &dyn GitHubOps,
+    repository: &str,
+) -> Result<Option<RepositoryUnavailable>> {
+    let (owner, repo) = repository
+        .split_once('/')
+        .with_context(|| format!("Repository {} is not in owner/repo format", repository))?;
+    match github.repository_access(owner, repo).await {
+        Ok(access) if access.can_push => Ok(None),
+        Ok(_) => Ok(Some(RepositoryUnavailable::new(
+            repository,
+            "our token can no longer push to it",
+        ))),
+        Err(e) => match lost_access(&e) {
+            Some(reason) => Ok(Some(RepositoryUnavailable::new(repository, reason))),
+            None => Err(e),
+        },
+    }
+}
+
+/// 🕵️ Did a failed GitHub call lose us the repository? Only errors with the
+/// lost-access signature are checked, and only a confirmed loss counts.
+pub async fn confirm(
+    github: &dyn GitHubOps,
+    repository: &str,
+    error: &anyhow::Error,
+) -> Option<RepositoryUnavailable> {
+    if let Some(unavailable) = RepositoryUnavailable::find(error) {
+        return Some(unavailable);
+    }
+    lost_access(error)?;
+    match check(github, repository).await {
+        Ok(unavailable) => unavailable,
+        Err(e) => {
+            warn!("⚠️ Couldn't check access to {}: {:#}", repository, e);
+            None
+        }
+    }
+}
+
+/// 🔒 Record the repository as unavailable, deactivate the active projects serving it
+/// and tell their owners; returns the deactivated projects
+pub async fn mark_unavailable(
+    conn: &mut PgConnection,
+    unavailable: &RepositoryUnavailable,
+    feedback_id: Option<Uuid>,
+) -> Result<Vec<Uuid>> {
+    sqlx::query(
+        r#"
+        INSERT INTO unavailable_repositories (repository, reason, feedback_id)
+        VALUES (LOWER($1), $2, $3)
+        ON CONFLICT (repository) DO UPDATE
+        SET reason = EXCLUDED.reason, feedback_id = EXCLUDED.feedback_id, detected_at = NOW()
+        "#,
+    )
+    .bind(&unavailable.repository)
+    .bind(&unavailable.reason)
+    .bind(feedback_id)
+    .execute(&mut *conn)
+    .await
+    .context("Failed to record the unavailable repository")?;
+
+    let deactivated: Vec<(Uuid, Uuid)> = sqlx::query_as(
+        r#"
+        UPDATE projects p SET is_active = FALSE, updated_at = NOW()
+        FROM project_repository_routes r
+        WHERE r.project_id = p.id AND LOWER(r.repository) = LOWER($1) AND p.is_active
+        RETURNING p.id, p.owner_id
+        "#,
+    )
+    .bind(&unavailable.repository)
+    .fetch_all(&mut *conn)
+    .await
+    .context("Failed to deactivate projects")?;
+    for (project_id, owner_id) in &deactivated {
+        sqlx::query(
+            "INSERT INTO notifications (user_id, notification_type, title, content, related_id) \
+             VALUES ($1, 'warning', 'Project deactivated', $2, $3)",
+        )
+        .bind(owner_id)
+        .bind(format!(
+            "{}. The project is paused until an admin reactivates it.",
+            unavailable
+        ))
+        .bind(project_id)
+        .execute(&mut *conn)
+        .await
+        .with_context(|| format!("Failed to notify {} about the deactivation", owner_id))?;
+    }
+    Ok(deactivated
+        .into_iter()
+        .map(|(project_id, _)| project_id)
+        .collect())
+}
+
+/// 🔍 Why a repository is recorded as unavailable, if it is
+pub async fn unavailable(pool: &PgPool, repository: &str) -> Result<Option<RepositoryUnavailable>> {
+    let reason: Option<String> = sqlx::query_scalar(
+        "SELECT reason FROM unavailable_repositories WHERE repository = LOWER($1)",
+    )
+    .bind(repository)
+    .fetch_optional(pool)
+    .await
+    .context("Failed to look up repository availability")?;
+    Ok(reason.map(|reason| RepositoryUnavailable::new(repository, reason)))
+}
+
+/// 🔓 What reactivating a project came to
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Reactivation {
+    /// ✅ Active again, with the repositories it serves (all checked)
+    Reactivated(Vec<String>),
+    /// 🚪 A repository is still out of reach; nothing changed
+    StillUnavailable(RepositoryUnavailable),
+    /// 🔍 No such project
+    NotFound,
+}
+
+/// 🔓 Check every repository a project serves, then reactivate it and lift their
+/// unavailable marks
+pub async fn reactivate(
+    pool: &PgPool,
+    github: &dyn GitHubOps,
+    project_id: Uuid,
+) -> Result<Reactivation> {
+    let repositories: Vec<String> = sqlx::query_scalar(
+        "SELECT repository FROM project_repository_routes WHERE project_id = $1 ORDER BY is_primary DESC, repository",
+    )
+    .bind(project_id)
+    .fetch_all(pool)
+    .await
+    .context("Failed to load the project's repositories")?;
+    if repositories.is_empty() {
+        return Ok(Reactivation::NotFound);
+    }
+    for repository in &repositories {
+        if let Some(unavailable) = check(github, repository).await? {
+            return Ok(Reactivation::StillUnavailable(unavailable));
+        }
+    }
+
+    let mut tx = pool
+        .begin()
+        .await
+        .context("Failed to start reactivating the project")?;
+    sqlx::query("UPDATE projects SET is_active = TRUE, updated_at = NOW() WHERE id = $1")
+        .bind(project_id)
+        .execute(&mut *tx)
+        .await
+        .context("Failed to reactivate the project")?;
+    sqlx::query(
+        "DELETE FROM unavailable_repositories WHERE repository IN (SELECT LOWER(r) FROM UNNEST($1::text[]) AS r)",
+    )
+    .bind(&repositories)
+    .execute(&mut *tx)
+    .await
+    .context("Failed to lift the unavailable marks")?;
+    tx.commit()
+        .await
+        .context("Failed to reactivate the project")?;
+    Ok(Reactivation::Reactivated(repositories))
+}
+
+// 🧪 Tests - Knowing a 404 from a goodbye!
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lost_access_signatures() {
+        assert!(lost_access_reason(404, "Not Found").is_some());
+        assert_eq!(
+            lost_access_reason(403, "Resource not accessible by integration").as_deref(),
+            Some("forbidden: Resource not accessible by integration")
+        );
+        assert!(lost_access_reason(451, "Repository access blocked").is_some());
+        // 🚦 Rate limits and ordinary failures say nothing about access
+        assert_eq!(
+            lost_access_reason(403, "API rate limit exceeded for installation"),
+            None
+        );
+        assert_eq!(lost_access_reason(422, "Validation Failed"), None);
+        assert_eq!(lost_access_reason(502, "Bad Gateway"), None);
+
+        let confirmed = anyhow::Error::new(RepositoryUnavailable::new("8b-is/gone", "not found"))
+            .context("Failed to commit changes");
+        assert_eq!(lost_access(&confirmed).as_deref(), Some("not found"));
+        assert_eq!(
+            RepositoryUnavailable::find(&confirmed).map(|found| found.repository),
+            Some("8b-is/gone".to_string())
+        );
+        assert_eq!(lost_access(&anyhow::anyhow!("connection reset")), None);
+        println!("✅ Lost access signature test passed!");
+    }
+
+    #[tokio::test]
+    async fn test_unavailable_repositories_are_skipped_until_reactivated() {
+        use crate::database::models::{Feedback, FeedbackStatus};
+        use crate::test_support::spawn_test_app;
+        use axum::{
+            extract::{Path, State},
+            http::StatusCode,
+        };
+
+        let Some(app) = spawn_test_app().await else {
+            return;
+        };
+        let pool = &app.db_pool;
+        let owner_id: Uuid = sqlx::query_scalar(
+            "INSERT INTO users (email, name, password_hash) VALUES ('owner@example.com', 'Owner', 'x') RETURNING id",
+        )
+        .fetch_one(pool)
+        .await
+        .unwrap();
+        let project_id: Uuid = sqlx::query_scalar(
+            "INSERT INTO projects (owner_id, repository) VALUES ($1, '8b-is/smart-tree') RETURNING id",
+        )
+        .bind(owner_id)
+        .fetch_one(pool)
+        .await
+        .unwrap();
+        let mut feedback = Feedback::create(
+            pool,
+            None,
+            "8b-is/smart-tree".to_string(),
+            "Please add dark mode".to_string(),
+            None,
+            25,
+            None,
+            "cli",
+        )
+        .await
+        .unwrap();
+        feedback
+            .update_status(
+                pool,
+                FeedbackStatus::Failed,
+                Some(REPOSITORY_UNAVAILABLE.to_string()),
+            )
+            .await
+            .unwrap();
+        let gone = RepositoryUnavailable::new("8b-is/smart-tree", "not found");
+        let mut conn = pool.acquire().await.unwrap();
+        let deactivated = mark_unavailable(&mut conn, &gone, Some(feedback.id))
+            .await
+            .unwrap();
+        assert_eq!(deactivated, vec![project_id]);
+        app.github
+            .lost_repositories
+            .lock()
+            .unwrap()
+            .push("8b-is/smart-tree".to_string());
+
+        let opened = || {
+            app.client
+                .post(app.url("/api/webhook/issues"))
+                .json(&serde_json::json!({
+                    "action": "opened",
+                    "issue": {
+                        "id": 1,
+                        "number": 42,
+                        "title": "Tree output is empty",
+                        "body": "It prints nothing",
+                        "state": "open",
+                        "html_url": "https://github.com/8b-is/smart-tree/issues/42",
+                        "user": { "id": 7, "login": "someone" },
+                        "labels": [],
+                        "assignees": [],
+                        "author_association": "NONE"
+                    },
+                    "repository": {
+                        "id": 2,
+                        "name": "smart-tree",
+                        "full_name": "8b-is/Smart-Tree",
+                        "owner": { "id": 3, "login": "8b-is" }
+                    },
+                    "sender": { "id": 7, "login": "someone" }
+                }))
+                .send()
+        };
+
+        // ⏭️ No automation while the repository is out of reach
+        let response = opened().await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let body: serde_json::Value = response.json().await.unwrap();
+        assert_eq!(
+            body["data"]["action_taken"],
+            "skipped_repository_unavailable"
+        );
+        assert!(app.github.calls().is_empty());
+        // 🔁 ... and no retrying the feedback that ran into it
+        let retried =
+            crate::api::feedback::retry_feedback(State(app.app_state.clone()), Path(feedback.id))
+                .await;
+        assert_eq!(retried.status(), StatusCode::CONFLICT);
+
+        // 🔓 Reactivating checks access first
+        app.login_admin().await.unwrap();
+        let reactivate = || {
+            app.client
+                .post(app.url(&format!("/admin/api/projects/{}/reactivate", project_id)))
+                .send()
+        };
+        let response = reactivate().await.unwrap();
+        assert_eq!(response.status(), StatusCode::CONFLICT);
+        let body: serde_json::Value = response.json().await.unwrap();
+        assert_eq!(body["error"]["code"], "repository_unavailable");
+        assert_eq!(body["error"]["details"]["repository"], "8b-is/smart-tree");
+
+        app.github.lost_repositories.lock().unwrap().clear();
+        let response = reactivate().await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let active: bool = sqlx::query_scalar("SELECT is_active FROM projects WHERE id = $1")
+            .bind(project_id)
+            .fetch_one(pool)
+            .await
+            .unwrap();
+        assert!(active);
+        assert_eq!(unavailable(pool, "8b-is/smart-tree").await.unwrap(), None);
+        let response = opened().await.unwrap();
+        let body: serde_json::Value = response.json().await.unwrap();
+        assert_ne!(
+            body["data"]["action_taken"],
+            "skipped_repository_unavailable"
+        );
+        assert!(!app.github.calls().is_empty());
+        println!("✅ Repository reactivation test passed!");
+    }
+}
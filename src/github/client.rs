@@ -5,22 +5,292 @@
 use anyhow::{Context, Result};
 use octocrab::models::{issues::Issue, Repository};
 use octocrab::Octocrab;
-use tracing::{info, warn};
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+use sqlx::PgPool;
+use std::sync::Arc;
+use tokio::sync::SemaphorePermit;
+use tracing::{debug, info, warn};
+
+use super::artifacts::ArtifactState;
+use super::concurrency::RequestLimiter;
+use super::cooldown::{is_secondary_rate_limit, CooldownGate};
+use super::installations;
+use super::labels::{LabelCache, LabelSpec};
+use super::ops::{MinimizeReason, PostedComment, Reaction};
+use super::protection::{BaseProtection, BranchProtection};
+use super::releases::GitHubRelease;
+use super::statuses::{CommitStatus, STATUS_CONTEXT};
+use super::throttle::WriteThrottle;
+use super::{
+    pr_body::{applied_changes, render_pr_body},
+    AppliedChange, ChangeType, CodeImprovement, CommittedChanges, FeedbackProcessingRequest,
+    PullRequestResult,
+};
+
+/// 🔍 Issues `find_recent_issue` looks through (one page, newest first)
+const RECENT_ISSUES_SCANNED: u32 = 50;
 
 /// 🐙 GitHub API client wrapper
 pub struct GitHubClient {
     octocrab: Octocrab,
+    /// 🚰 Write budget per installation, shared by comment/label/assign/close
+    write_throttle: Arc<WriteThrottle>,
+    /// 🧩 Where to look up which installation covers a repository (without it every
+    /// write draws from the token's own bucket)
+    installations: Option<PgPool>,
+    /// 🧊 Pause shared by every call (and every worker) after a secondary rate limit
+    cooldown: CooldownGate,
+    /// 🎟️ Bound on requests in flight, shared with every other user of the token
+    requests: Arc<RequestLimiter>,
+    /// 🏷️ Labels each repository is known to have
+    labels: LabelCache,
 }
 
 impl GitHubClient {
-    /// 🔧 Create a new GitHub client with authentication
-    pub fn new(token: &str) -> Result<Self> {
+    /// 🔧 Create a new GitHub client with authentication, talking to `api_base_url`
+    /// (GITHUB_API_BASE_URL, https://api.github.com unless it's GitHub Enterprise)
+    pub fn new(
+        token: &str,
+        api_base_url: &str,
+        write_throttle: Arc<WriteThrottle>,
+        cooldown: CooldownGate,
+        requests: Arc<RequestLimiter>,
+    ) -> Result<Self> {
         let octocrab = Octocrab::builder()
             .personal_token(token.to_string())
+            .base_uri(api_base_url)
+            .context("Invalid GITHUB_API_BASE_URL")?
             .build()
             .context("Failed to create GitHub client")?;
 
-        Ok(Self { octocrab })
+        Ok(Self {
+            octocrab,
+            write_throttle,
+            installations: None,
+            cooldown,
+            requests,
+            labels: LabelCache::default(),
+        })
+    }
+
+    /// 🧩 Throttle writes per installation, looked up in `github_installations`
+    pub fn with_installations(mut self, pool: PgPool) -> Self {
+        self.installations = Some(pool);
+        self
+    }
+
+    /// 🎟️ Wait for the cooldown gate to open, then for a request slot (held until the
+    /// returned permit is dropped - never across a call to another method, which
+    /// takes its own)
+    async fn begin_request(&self) -> Result<SemaphorePermit<'_>> {
+        self.cooldown.pass().await?;
+        Ok(self.requests.acquire().await)
+    }
+
+    /// 🧩 The installation covering owner/repo, if we know of one. A failed lookup
+    /// falls back to the token's own bucket rather than failing the write.
+    async fn installation_of(&self, owner: &str, repo: &str) -> Option<i64> {
+        let pool = self.installations.as_ref()?;
+        let repository = format!("{}/{}", owner, repo);
+        match installations::installation_for_repository(pool, &repository).await {
+            Ok(installation) => installation.map(|installation| installation.installation_id),
+            Err(e) => {
+                warn!(
+                    "⚠️ Throttling {} without its installation: {:#}",
+                    repository, e
+                );
+                None
+            }
+        }
+    }
+
+    /// 🚰 Wait for a write token from owner/repo's installation, or fail with
+    /// `WriteThrottled` if that would take too long
+    async fn throttle_write(&self, owner: &str, repo: &str) -> Result<()> {
+        let installation = self.installation_of(owner, repo).await;
+        let wait = self
+            .write_throttle
+            .acquire(installation)
+            .inspect_err(|throttled| {
+                warn!("🚰 {} ({}/{})", throttled, owner, repo);
+            })?;
+        if !wait.is_zero() {
+            info!("🚰 Delaying GitHub write by {:?}", wait);
+            tokio::time::sleep(wait).await;
+        }
+        Ok(())
+    }
+
+    /// 🕸️ Run a GraphQL query or mutation and return its `data`.
+    /// GraphQL reports most failures as `errors` in a 200 response; those become Err too.
+    pub async fn graphql<T: DeserializeOwned>(&self, query: &str, variables: Value) -> Result<T> {
+        let _slot = self.begin_request().await?;
+        let response: Value = self
+            .octocrab
+            .post(
+                "/graphql",
+                Some(&serde_json::json!({ "query": query, "variables": variables })),
+            )
+            .await
+            .map_err(|e| self.cooldown.observe(e))
+            .context("GitHub GraphQL request failed")?;
+
+        if let Some(errors) = response.get("errors").and_then(Value::as_array) {
+            if !errors.is_empty() {
+                let messages = errors
+                    .iter()
+                    .map(|error| {
+                        error
+                            .get("message")
+                            .and_then(Value::as_str)
+                            .unwrap_or("unknown error")
+                    })
+                    .collect::<Vec<_>>()
+                    .join("; ");
+                anyhow::bail!("GitHub GraphQL error: {}", messages);
+            }
+        }
+        let data = response
+            .get("data")
+            .cloned()
+            .context("GitHub GraphQL response has no data")?;
+        serde_json::from_value(data).context("Unexpected GitHub GraphQL response")
+    }
+
+    /// 🙈 Minimize (collapse) a comment by its GraphQL node id
+    pub async fn minimize_comment(
+        &self,
+        owner: &str,
+        repo: &str,
+        node_id: &str,
+        reason: MinimizeReason,
+    ) -> Result<()> {
+        debug!(
+            "🙈 Minimizing comment {} in {}/{} as {}",
+            node_id,
+            owner,
+            repo,
+            reason.classifier()
+        );
+        self.cooldown.pass().await?;
+        self.throttle_write(owner, repo).await?;
+
+        let data: Value = self
+            .graphql(
+                "mutation($subjectId: ID!, $classifier: ReportedContentClassifiers!) { \
+                 minimizeComment(input: { subjectId: $subjectId, classifier: $classifier }) { \
+                 minimizedComment { isMinimized } } }",
+                serde_json::json!({ "subjectId": node_id, "classifier": reason.classifier() }),
+            )
+            .await
+            .with_context(|| format!("Failed to minimize comment {}", node_id))?;
+
+        let minimized = data
+            .pointer("/minimizeComment/minimizedComment/isMinimized")
+            .and_then(Value::as_bool)
+            .unwrap_or(false);
+        if !minimized {
+            anyhow::bail!("GitHub did not minimize comment {}", node_id);
+        }
+        debug!("✅ Comment {} minimized", node_id);
+        Ok(())
+    }
+
+    /// ✏️ Replace the body of an issue comment
+    pub async fn update_comment(
+        &self,
+        owner: &str,
+        repo: &str,
+        comment_id: u64,
+        comment: &str,
+    ) -> Result<()> {
+        debug!("✏️ Updating comment {} in {}/{}", comment_id, owner, repo);
+        self.cooldown.pass().await?;
+        self.throttle_write(owner, repo).await?;
+        let _slot = self.requests.acquire().await;
+
+        let _: Value = self
+            .octocrab
+            .patch(
+                format!("/repos/{}/{}/issues/comments/{}", owner, repo, comment_id),
+                Some(&serde_json::json!({ "body": comment })),
+            )
+            .await
+            .map_err(|e| self.cooldown.observe(e))
+            .with_context(|| {
+                format!(
+                    "Failed to update comment {} in {}/{}",
+                    comment_id, owner, repo
+                )
+            })?;
+
+        debug!("✅ Comment {} updated", comment_id);
+        Ok(())
+    }
+
+    /// 🗑️ Delete an issue comment
+    pub async fn delete_comment(&self, owner: &str, repo: &str, comment_id: u64) -> Result<()> {
+        debug!("🗑️ Deleting comment {} in {}/{}", comment_id, owner, repo);
+        self.cooldown.pass().await?;
+        self.throttle_write(owner, repo).await?;
+        let _slot = self.requests.acquire().await;
+
+        self.octocrab
+            .issues(owner, repo)
+            .delete_comment(comment_id.into())
+            .await
+            .map_err(|e| self.cooldown.observe(e))
+            .with_context(|| {
+                format!(
+                    "Failed to delete comment {} in {}/{}",
+                    comment_id, owner, repo
+                )
+            })?;
+
+        debug!("✅ Comment {} deleted", comment_id);
+        Ok(())
+    }
+
+    /// 👍 React to an issue comment
+    pub async fn add_reaction(
+        &self,
+        owner: &str,
+        repo: &str,
+        comment_id: u64,
+        reaction: Reaction,
+    ) -> Result<()> {
+        debug!(
+            "👍 Reacting {} to comment {} in {}/{}",
+            reaction.content(),
+            comment_id,
+            owner,
+            repo
+        );
+        self.cooldown.pass().await?;
+        self.throttle_write(owner, repo).await?;
+        let _slot = self.requests.acquire().await;
+
+        let _: Value = self
+            .octocrab
+            .post(
+                format!(
+                    "/repos/{}/{}/issues/comments/{}/reactions",
+                    owner, repo, comment_id
+                ),
+                Some(&serde_json::json!({ "content": reaction.content() })),
+            )
+            .await
+            .map_err(|e| self.cooldown.observe(e))
+            .with_context(|| {
+                format!(
+                    "Failed to react to comment {} in {}/{}",
+                    comment_id, owner, repo
+                )
+            })?;
+
+        debug!("✅ Reaction added to comment {}", comment_id);
+        Ok(())
     }
 
     /// 📝 Add a comment to an issue
@@ -30,16 +300,21 @@ impl GitHubClient {
         repo: &str,
         issue_number: u32,
         comment: &str,
-    ) -> Result<()> {
-        info!(
+    ) -> Result<PostedComment> {
+        debug!(
             "💬 Adding comment to issue #{} in {}/{}",
             issue_number, owner, repo
         );
+        self.cooldown.pass().await?;
+        self.throttle_write(owner, repo).await?;
+        let _slot = self.requests.acquire().await;
 
-        self.octocrab
+        let posted = self
+            .octocrab
             .issues(owner, repo)
             .create_comment(issue_number.into(), comment)
             .await
+            .map_err(|e| self.cooldown.observe(e))
             .with_context(|| {
                 format!(
                     "Failed to add comment to issue #{} in {}/{}",
@@ -47,8 +322,11 @@ impl GitHubClient {
                 )
             })?;
 
-        info!("✅ Comment added successfully to issue #{}", issue_number);
-        Ok(())
+        debug!("✅ Comment added successfully to issue #{}", issue_number);
+        Ok(PostedComment {
+            id: posted.id.into_inner(),
+            node_id: posted.node_id,
+        })
     }
 
     /// 🏷️ Add labels to an issue
@@ -59,15 +337,19 @@ impl GitHubClient {
         issue_number: u32,
         labels: &[String],
     ) -> Result<()> {
-        info!(
+        debug!(
             "🏷️ Adding labels {:?} to issue #{} in {}/{}",
             labels, issue_number, owner, repo
         );
+        self.cooldown.pass().await?;
+        self.throttle_write(owner, repo).await?;
+        let _slot = self.requests.acquire().await;
 
         self.octocrab
             .issues(owner, repo)
             .add_labels(issue_number.into(), labels)
             .await
+            .map_err(|e| self.cooldown.observe(e))
             .with_context(|| {
                 format!(
                     "Failed to add labels to issue #{} in {}/{}",
@@ -75,10 +357,129 @@ impl GitHubClient {
                 )
             })?;
 
-        info!("✅ Labels added successfully to issue #{}", issue_number);
+        debug!("✅ Labels added successfully to issue #{}", issue_number);
         Ok(())
     }
 
+    /// 📋 The labels an issue carries
+    pub async fn issue_labels(
+        &self,
+        owner: &str,
+        repo: &str,
+        issue_number: u32,
+    ) -> Result<Vec<String>> {
+        debug!(
+            "📋 Reading labels of issue #{} in {}/{}",
+            issue_number, owner, repo
+        );
+        let _slot = self.begin_request().await?;
+        let labels = self
+            .octocrab
+            .issues(owner, repo)
+            .list_labels_for_issue(issue_number.into())
+            .per_page(100)
+            .send()
+            .await
+            .map_err(|e| self.cooldown.observe(e))
+            .with_context(|| {
+                format!(
+                    "Failed to read labels of issue #{} in {}/{}",
+                    issue_number, owner, repo
+                )
+            })?;
+        Ok(labels.items.into_iter().map(|label| label.name).collect())
+    }
+
+    /// 🏷️ Make sure every label in `labels` exists in the repository, creating the
+    /// missing ones with their color and description. The repository's labels are
+    /// listed at most once per LABEL_CACHE_TTL. Returns the names created.
+    pub async fn ensure_labels(
+        &self,
+        owner: &str,
+        repo: &str,
+        labels: &[LabelSpec],
+    ) -> Result<Vec<String>> {
+        let repository = format!("{}/{}", owner, repo);
+        let mut known = match self.labels.known(&repository) {
+            Some(known) => known,
+            None => {
+                let names = self.list_label_names(owner, repo).await?;
+                self.labels.remember(&repository, names);
+                self.labels.known(&repository).unwrap_or_default()
+            }
+        };
+
+        let mut created = Vec::new();
+        for spec in labels {
+            if !known.insert(spec.name.to_lowercase()) {
+                continue;
+            }
+            if self.create_label(owner, repo, spec).await? {
+                created.push(spec.name.clone());
+            }
+            self.labels.add(&repository, &spec.name);
+        }
+        if !created.is_empty() {
+            info!("🏷️ Created labels {:?} in {}", created, repository);
+        }
+        Ok(created)
+    }
+
+    /// 📋 Every label name in a repository, page by page
+    async fn list_label_names(&self, owner: &str, repo: &str) -> Result<Vec<String>> {
+        #[derive(serde::Deserialize)]
+        struct Label {
+            name: String,
+        }
+
+        let mut names = Vec::new();
+        for page in 1u32.. {
+            let _slot = self.begin_request().await?;
+            let labels: Vec<Label> = self
+                .octocrab
+                .get(
+                    format!("/repos/{}/{}/labels", owner, repo),
+                    Some(&[("per_page", "100".to_string()), ("page", page.to_string())]),
+                )
+                .await
+                .map_err(|e| self.cooldown.observe(e))
+                .with_context(|| format!("Failed to list labels of {}/{}", owner, repo))?;
+            let last_page = labels.len() < 100;
+            names.extend(labels.into_iter().map(|label| label.name));
+            if last_page {
+                break;
+            }
+        }
+        debug!("📋 {}/{} has {} labels", owner, repo, names.len());
+        Ok(names)
+    }
+
+    /// ➕ Create a label. Returns false when it turned out to exist already.
+    async fn create_label(&self, owner: &str, repo: &str, spec: &LabelSpec) -> Result<bool> {
+        debug!("➕ Creating label {:?} in {}/{}", spec.name, owner, repo);
+        self.cooldown.pass().await?;
+        self.throttle_write(owner, repo).await?;
+        let _slot = self.requests.acquire().await;
+
+        let created: Result<Value, _> = self
+            .octocrab
+            .post(format!("/repos/{}/{}/labels", owner, repo), Some(spec))
+            .await;
+        match created {
+            Ok(_) => Ok(true),
+            // 🤝 Someone else created it since we listed
+            Err(octocrab::Error::GitHub { source, .. }) if source.status_code.as_u16() == 422 => {
+                Ok(false)
+            }
+            Err(e) => Err(self.cooldown.observe(e)).with_context(|| {
+                format!(
+                    "Failed to create label {:?} in {}/{}",
+                    spec.name, owner, repo
+                )
+            }),
+        }
+    }
+
     /// 👤 Assign an issue to a user
     pub async fn assign_issue(
         &self,
@@ -87,23 +488,41 @@ impl GitHubClient {
         issue_number: u32,
         assignee: &str,
     ) -> Result<()> {
-        info!(
+        debug!(
             "👤 Assigning issue #{} to {} in {}/{}",
             issue_number, assignee, owner, repo
         );
+        self.cooldown.pass().await?;
+        self.throttle_write(owner, repo).await?;
+        let _slot = self.requests.acquire().await;
 
-        self.octocrab
+        let issue = self
+            .octocrab
             .issues(owner, repo)
             .add_assignees(issue_number.into(), &[assignee])
             .await
+            .map_err(|e| self.cooldown.observe(e))
             .with_context(|| {
                 format!(
                     "Failed to assign issue #{} to {} in {}/{}",
                     issue_number, assignee, owner, repo
                 )
             })?;
+        // 🤫 GitHub answers 201 but quietly drops users who can't be assigned
+        if !issue
+            .assignees
+            .iter()
+            .any(|user| user.login.eq_ignore_ascii_case(assignee))
+        {
+            anyhow::bail!(
+                "{} can't be assigned to issues in {}/{}",
+                assignee,
+                owner,
+                repo
+            );
+        }
 
-        info!(
+        debug!(
             "✅ Issue #{} assigned successfully to {}",
             issue_number, assignee
         );
@@ -112,7 +531,10 @@ impl GitHubClient {
 
     /// ✅ Close an issue
     pub async fn close_issue(&self, owner: &str, repo: &str, issue_number: u32) -> Result<()> {
-        info!("✅ Closing issue #{} in {}/{}", issue_number, owner, repo);
+        debug!("✅ Closing issue #{} in {}/{}", issue_number, owner, repo);
+        self.cooldown.pass().await?;
+        self.throttle_write(owner, repo).await?;
+        let _slot = self.requests.acquire().await;
 
         self.octocrab
             .issues(owner, repo)
@@ -120,6 +542,7 @@ impl GitHubClient {
             .state(octocrab::models::IssueState::Closed)
             .send()
             .await
+            .map_err(|e| self.cooldown.observe(e))
             .with_context(|| {
                 format!(
                     "Failed to close issue #{} in {}/{}",
@@ -127,22 +550,24 @@ impl GitHubClient {
                 )
             })?;
 
-        info!("✅ Issue #{} closed successfully", issue_number);
+        debug!("✅ Issue #{} closed successfully", issue_number);
         Ok(())
     }
 
     /// 🔍 Get issue details
     pub async fn get_issue(&self, owner: &str, repo: &str, issue_number: u32) -> Result<Issue> {
-        info!(
+        debug!(
             "🔍 Fetching issue #{} from {}/{}",
             issue_number, owner, repo
         );
 
+        let _slot = self.begin_request().await?;
         let issue = self
             .octocrab
             .issues(owner, repo)
             .get(issue_number.into())
             .await
+            .map_err(|e| self.cooldown.observe(e))
             .with_context(|| {
                 format!(
                     "Failed to fetch issue #{} from {}/{}",
@@ -150,7 +575,7 @@ impl GitHubClient {
                 )
             })?;
 
-        info!("✅ Issue #{} fetched successfully", issue_number);
+        debug!("✅ Issue #{} fetched successfully", issue_number);
         Ok(issue)
     }
 
@@ -162,7 +587,7 @@ impl GitHubClient {
         state: Option<&str>,
         _labels: Option<&str>,
     ) -> Result<Vec<Issue>> {
-        info!("📋 Listing issues from {}/{}", owner, repo);
+        debug!("📋 Listing issues from {}/{}", owner, repo);
 
         let state_param = match state {
             Some("open") => octocrab::params::State::Open,
@@ -170,6 +595,7 @@ impl GitHubClient {
             _ => octocrab::params::State::All,
         };
 
+        let _slot = self.begin_request().await?;
         let page = self
             .octocrab
             .issues(owner, repo)
@@ -177,9 +603,10 @@ impl GitHubClient {
             .state(state_param)
             .send()
             .await
+            .map_err(|e| self.cooldown.observe(e))
             .with_context(|| format!("Failed to list issues from {}/{}", owner, repo))?;
 
-        info!("✅ Found {} issues in {}/{}", page.items.len(), owner, repo);
+        debug!("✅ Found {} issues in {}/{}", page.items.len(), owner, repo);
         Ok(page.items)
     }
 
@@ -193,11 +620,12 @@ impl GitHubClient {
         head: &str,
         base: &str,
     ) -> Result<octocrab::models::pulls::PullRequest> {
-        info!(
+        debug!(
             "🔗 Creating pull request from {} to {} in {}/{}",
             head, base, owner, repo
         );
 
+        let _slot = self.begin_request().await?;
         let pr = self
             .octocrab
             .pulls(owner, repo)
@@ -205,6 +633,7 @@ impl GitHubClient {
             .body(body)
             .send()
             .await
+            .map_err(|e| self.cooldown.observe(e))
             .with_context(|| {
                 format!(
                     "Failed to create pull request from {} to {} in {}/{}",
@@ -212,22 +641,24 @@ impl GitHubClient {
                 )
             })?;
 
-        info!("✅ Pull request #{} created successfully", pr.number);
+        debug!("✅ Pull request #{} created successfully", pr.number);
         Ok(pr)
     }
 
     /// 🏠 Get repository information
     pub async fn get_repository(&self, owner: &str, repo: &str) -> Result<Repository> {
-        info!("🏠 Fetching repository {}/{}", owner, repo);
+        debug!("🏠 Fetching repository {}/{}", owner, repo);
 
+        let _slot = self.begin_request().await?;
         let repository = self
             .octocrab
             .repos(owner, repo)
             .get()
             .await
+            .map_err(|e| self.cooldown.observe(e))
             .with_context(|| format!("Failed to fetch repository {}/{}", owner, repo))?;
 
-        info!("✅ Repository {}/{} fetched successfully", owner, repo);
+        debug!("✅ Repository {}/{} fetched successfully", owner, repo);
         Ok(repository)
     }
 
@@ -239,12 +670,13 @@ impl GitHubClient {
         branch_name: &str,
         from_sha: &str,
     ) -> Result<()> {
-        info!(
+        debug!(
             "🌿 Creating branch {} from {} in {}/{}",
             branch_name, from_sha, owner, repo
         );
 
         // Use the API endpoint directly
+        let _slot = self.begin_request().await?;
         let _: serde_json::Value = self
             .octocrab
             .post(
@@ -255,6 +687,7 @@ impl GitHubClient {
                 })),
             )
             .await
+            .map_err(|e| self.cooldown.observe(e))
             .with_context(|| {
                 format!(
                     "Failed to create branch {} in {}/{}",
@@ -262,11 +695,11 @@ impl GitHubClient {
                 )
             })?;
 
-        info!("✅ Branch {} created successfully", branch_name);
+        debug!("✅ Branch {} created successfully", branch_name);
         Ok(())
     }
 
-    /// 📝 Update file content in repository
+    /// 📝 Update file content in repository, returning the new commit's sha
     #[allow(clippy::too_many_arguments)]
     pub async fn update_file(
         &self,
@@ -277,9 +710,9 @@ impl GitHubClient {
         message: &str,
         branch: &str,
         sha: Option<&str>,
-    ) -> Result<()> {
+    ) -> Result<String> {
         use base64::Engine;
-        info!(
+        debug!(
             "📝 Updating file {} in branch {} of {}/{}",
             path, branch, owner, repo
         );
@@ -299,82 +732,1458 @@ impl GitHubClient {
             body["branch"] = serde_json::json!(branch);
         }
 
-        let _: serde_json::Value = self
+        let _slot = self.begin_request().await?;
+        let updated: serde_json::Value = self
             .octocrab
             .put(
                 format!("/repos/{}/{}/contents/{}", owner, repo, path),
                 Some(&body),
             )
             .await
+            .map_err(|e| self.cooldown.observe(e))
             .with_context(|| format!("Failed to update file {} in {}/{}", path, owner, repo))?;
 
-        info!("✅ File {} updated successfully", path);
-        Ok(())
+        debug!("✅ File {} updated successfully", path);
+        Ok(updated
+            .pointer("/commit/sha")
+            .and_then(Value::as_str)
+            .unwrap_or_default()
+            .to_string())
     }
 
-    /// 🔍 Check if user is a collaborator
-    pub async fn is_collaborator(&self, owner: &str, repo: &str, username: &str) -> Result<bool> {
-        info!(
-            "🔍 Checking if {} is a collaborator on {}/{}",
-            username, owner, repo
+    /// 📄 Blob sha and decoded content of a file on `branch` (None when it doesn't exist)
+    async fn file_at(
+        &self,
+        owner: &str,
+        repo: &str,
+        path: &str,
+        branch: &str,
+    ) -> Result<Option<(String, String)>> {
+        use base64::Engine;
+        let _slot = self.begin_request().await?;
+        let response = self
+            .octocrab
+            ._get(format!(
+                "/repos/{}/{}/contents/{}?ref={}",
+                owner, repo, path, branch
+            ))
+            .await
+            .map_err(|e| self.cooldown.observe(e))
+            .with_context(|| format!("Failed to read {} in {}/{}", path, owner, repo))?;
+        if response.status() == 404 {
+            return Ok(None);
+        }
+        let file: Value = self
+            .octocrab
+            .body_to_string(
+                octocrab::map_github_error(response)
+                    .await
+                    .map_err(|e| self.cooldown.observe(e))
+                    .with_context(|| format!("Failed to read {} in {}/{}", path, owner, repo))?,
+            )
+            .await
+            .map_err(anyhow::Error::from)
+            .and_then(|body| serde_json::from_str(&body).map_err(anyhow::Error::from))
+            .with_context(|| format!("Unreadable contents response for {}", path))?;
+        let sha = file
+            .get("sha")
+            .and_then(Value::as_str)
+            .with_context(|| format!("{} in {}/{} is not a file", path, owner, repo))?;
+        // 📦 GitHub wraps the base64 at 60 columns
+        let encoded: String = file
+            .get("content")
+            .and_then(Value::as_str)
+            .unwrap_or_default()
+            .split_whitespace()
+            .collect();
+        let content = base64::engine::general_purpose::STANDARD
+            .decode(encoded)
+            .ok()
+            .and_then(|bytes| String::from_utf8(bytes).ok())
+            .with_context(|| format!("{} in {}/{} is not UTF-8 text", path, owner, repo))?;
+        Ok(Some((sha.to_string(), content)))
+    }
+
+    /// 🗑️ Delete a file on `branch`, returning the new commit's sha
+    async fn delete_file(
+        &self,
+        owner: &str,
+        repo: &str,
+        path: &str,
+        message: &str,
+        branch: &str,
+        sha: &str,
+    ) -> Result<String> {
+        debug!(
+            "🗑️ Deleting {} in branch {} of {}/{}",
+            path, branch, owner, repo
         );
+        let _slot = self.begin_request().await?;
+        let response = self
+            .octocrab
+            ._delete(
+                format!("/repos/{}/{}/contents/{}", owner, repo, path),
+                Some(&serde_json::json!({
+                    "message": message,
+                    "sha": sha,
+                    "branch": branch,
+                })),
+            )
+            .await
+            .map_err(|e| self.cooldown.observe(e))
+            .with_context(|| format!("Failed to delete {} in {}/{}", path, owner, repo))?;
+        let response = octocrab::map_github_error(response)
+            .await
+            .map_err(|e| self.cooldown.observe(e))
+            .with_context(|| format!("Failed to delete {} in {}/{}", path, owner, repo))?;
+        let deleted: Value = serde_json::from_str(&self.octocrab.body_to_string(response).await?)
+            .unwrap_or_default();
+        Ok(deleted
+            .pointer("/commit/sha")
+            .and_then(Value::as_str)
+            .unwrap_or_default()
+            .to_string())
+    }
 
-        // Use the API endpoint directly to check collaborator status
-        let result: Result<serde_json::Value, _> = self
+    /// 🌿 The commit stage: branch off the default branch and write the feedback's
+    /// changes to it, one commit per file
+    pub async fn commit_feedback_changes(
+        &self,
+        request: &FeedbackProcessingRequest,
+    ) -> Result<CommittedChanges> {
+        let (owner, repo) = split_repository(&request.repository)?;
+        let branch = request.branch_name.as_str();
+        let base = self
+            .get_repository(owner, repo)
+            .await?
+            .default_branch
+            .unwrap_or_else(|| "main".to_string());
+
+        let slot = self.begin_request().await?;
+        let head: Value = self
             .octocrab
             .get(
-                format!("/repos/{}/{}/collaborators/{}", owner, repo, username),
+                format!("/repos/{}/{}/git/ref/heads/{}", owner, repo, base),
                 None::<&()>,
             )
-            .await;
+            .await
+            .map_err(|e| self.cooldown.observe(e))
+            .with_context(|| format!("Failed to read {} of {}/{}", base, owner, repo))?;
+        // 🎟️ Each call below takes a slot of its own
+        drop(slot);
+        let base_sha = head
+            .pointer("/object/sha")
+            .and_then(Value::as_str)
+            .with_context(|| format!("Branch {} of {}/{} has no commit", base, owner, repo))?;
+        self.create_branch(owner, repo, branch, base_sha).await?;
 
-        match result {
-            Ok(_) => {
-                info!("✅ {} is a collaborator on {}/{}", username, owner, repo);
-                Ok(true)
-            }
-            Err(_) => {
-                info!(
-                    "❌ {} is not a collaborator on {}/{}",
-                    username, owner, repo
-                );
-                Ok(false)
-            }
+        let mut applied = Vec::new();
+        for improvement in &request.improvements {
+            let path = improvement.file_path.as_str();
+            let message = format!("{}\n\n{}", request.commit_message, improvement.description);
+            let existing = self.file_at(owner, repo, path, branch).await?;
+            let written = match (&improvement.change_type, &existing) {
+                (ChangeType::Delete, Some(_)) => None,
+                (ChangeType::Delete, None) => {
+                    warn!("🗑️ {} is already absent, nothing to delete", path);
+                    continue;
+                }
+                (ChangeType::Append, Some((_, content))) => {
+                    Some(format!("{}{}", content, improvement.new_content))
+                }
+                _ => Some(improvement.new_content.clone()),
+            };
+            let sha = existing.map(|(sha, _)| sha);
+            let commit_sha = match (&written, sha) {
+                (None, Some(sha)) => {
+                    self.delete_file(owner, repo, path, &message, branch, &sha)
+                        .await?
+                }
+                (Some(content), sha) => {
+                    self.update_file(owner, repo, path, content, &message, branch, sha.as_deref())
+                        .await?
+                }
+                (None, None) => unreachable!("absent deletions are skipped above"),
+            };
+            applied.push(AppliedChange {
+                improvement: improvement.clone(),
+                commit_sha,
+                written,
+            });
         }
+        Ok(CommittedChanges {
+            base_branch: base,
+            branch_name: branch.to_string(),
+            applied,
+        })
     }
 
-    /// 🎫 Create a new issue in a repository
-    pub async fn create_issue(
+    /// 🚀 Open the pull request for a branch `commit_feedback_changes` wrote; what
+    /// `protection` requires before merging goes in the body
+    pub async fn open_feedback_pull_request(
+        &self,
+        request: &FeedbackProcessingRequest,
+        committed: &CommittedChanges,
+        protection: &BaseProtection,
+    ) -> Result<PullRequestResult> {
+        let (owner, repo) = split_repository(&request.repository)?;
+        let title = request
+            .commit_message
+            .lines()
+            .next()
+            .unwrap_or("Feedbacker improvements")
+            .to_string();
+        let applied = applied_changes(committed);
+        let body = render_pr_body(
+            request,
+            &applied,
+            &protection.pr_section(&committed.base_branch),
+        );
+        let pr = self
+            .create_pull_request(
+                owner,
+                repo,
+                &title,
+                &body,
+                &committed.branch_name,
+                &committed.base_branch,
+            )
+            .await?;
+        info!(
+            "🚀 Pull request #{} opened for feedback {}",
+            pr.number, request.feedback_id
+        );
+        Ok(PullRequestResult {
+            url: pr.html_url.map(|url| url.to_string()).unwrap_or_default(),
+            number: pr.number,
+            title,
+            branch_name: committed.branch_name.clone(),
+            base_branch: committed.base_branch.clone(),
+            success: true,
+            error_message: None,
+        })
+    }
+
+    /// 🔒 Protection rules of `branch` (None when it isn't protected). Reading them
+    /// needs admin rights on the repository; without them this fails.
+    pub async fn get_branch_protection(
         &self,
         owner: &str,
         repo: &str,
-        title: &str,
-        body: &str,
-        labels: Option<&[String]>,
-        assignees: Option<&[String]>,
-    ) -> Result<Issue> {
-        info!("🎫 Creating issue '{}' in {}/{}", title, owner, repo);
-
-        let issues_handler = self.octocrab.issues(owner, repo);
-        let mut issue_builder = issues_handler.create(title).body(body);
-
-        if let Some(labels) = labels {
-            issue_builder = issue_builder.labels(labels.to_vec());
-        }
-
-        if let Some(assignees) = assignees {
-            issue_builder = issue_builder.assignees(assignees.to_vec());
+        branch: &str,
+    ) -> Result<Option<BranchProtection>> {
+        debug!("🔒 Reading protection of {} in {}/{}", branch, owner, repo);
+        let _slot = self.begin_request().await?;
+        let response = self
+            .octocrab
+            ._get(format!(
+                "/repos/{}/{}/branches/{}/protection",
+                owner, repo, branch
+            ))
+            .await
+            .map_err(|e| self.cooldown.observe(e))
+            .with_context(|| {
+                format!(
+                    "Failed to read protection of {} in {}/{}",
+                    branch, owner, repo
+                )
+            })?;
+        if response.status() == 404 {
+            return Ok(None);
         }
+        let response = octocrab::map_github_error(response)
+            .await
+            .map_err(|e| self.cooldown.observe(e))
+            .with_context(|| {
+                format!(
+                    "Failed to read protection of {} in {}/{}",
+                    branch, owner, repo
+                )
+            })?;
+        let protection: Value = serde_json::from_str(
+            &self.octocrab.body_to_string(response).await?,
+        )
+        .with_context(|| format!("Unreadable protection of {} in {}/{}", branch, owner, repo))?;
+        Ok(Some(BranchProtection::from_api(&protection)))
+    }
 
-        let issue = issue_builder
-            .send()
+    /// 🔀 Squash-merge a pull request
+    pub async fn merge_pull_request(&self, owner: &str, repo: &str, number: u64) -> Result<()> {
+        debug!("🔀 Merging pull request #{} in {}/{}", number, owner, repo);
+        let _slot = self.begin_request().await?;
+        let _: Value = self
+            .octocrab
+            .put(
+                format!("/repos/{}/{}/pulls/{}/merge", owner, repo, number),
+                Some(&serde_json::json!({ "merge_method": "squash" })),
+            )
             .await
-            .with_context(|| format!("Failed to create issue '{}' in {}/{}", title, owner, repo))?;
+            .map_err(|e| self.cooldown.observe(e))
+            .with_context(|| {
+                format!(
+                    "Failed to merge pull request #{} in {}/{}",
+                    number, owner, repo
+                )
+            })?;
+        Ok(())
+    }
 
-        info!(
-            "✅ Issue #{} created successfully: {}",
-            issue.number, issue.html_url
+    /// ✏️ Replace the description of a pull request
+    pub async fn update_pull_request_body(
+        &self,
+        owner: &str,
+        repo: &str,
+        number: u64,
+        body: &str,
+    ) -> Result<()> {
+        debug!(
+            "✏️ Updating the body of pull request #{} in {}/{}",
+            number, owner, repo
         );
-        Ok(issue)
+        let _slot = self.begin_request().await?;
+        let _: Value = self
+            .octocrab
+            .patch(
+                format!("/repos/{}/{}/pulls/{}", owner, repo, number),
+                Some(&serde_json::json!({ "body": body })),
+            )
+            .await
+            .map_err(|e| self.cooldown.observe(e))
+            .with_context(|| {
+                format!(
+                    "Failed to update pull request #{} in {}/{}",
+                    number, owner, repo
+                )
+            })?;
+        Ok(())
+    }
+
+    /// 🚪 Whether a pull request is still open (neither closed nor merged)
+    pub async fn pull_request_state(
+        &self,
+        owner: &str,
+        repo: &str,
+        number: u64,
+    ) -> Result<ArtifactState> {
+        debug!("🚪 Reading pull request #{} in {}/{}", number, owner, repo);
+        let _slot = self.begin_request().await?;
+        let pr = self
+            .octocrab
+            .pulls(owner, repo)
+            .get(number)
+            .await
+            .map_err(|e| self.cooldown.observe(e))
+            .with_context(|| {
+                format!(
+                    "Failed to read pull request #{} in {}/{}",
+                    number, owner, repo
+                )
+            })?;
+        Ok(if pr.merged_at.is_some() {
+            ArtifactState::Merged
+        } else if pr.state == Some(octocrab::models::IssueState::Open) {
+            ArtifactState::Open
+        } else {
+            ArtifactState::Closed
+        })
+    }
+
+    /// 🚪 Whether an issue is open or closed
+    pub async fn issue_state(&self, owner: &str, repo: &str, number: u64) -> Result<ArtifactState> {
+        debug!("🚪 Reading issue #{} in {}/{}", number, owner, repo);
+        let _slot = self.begin_request().await?;
+        let issue = self
+            .octocrab
+            .issues(owner, repo)
+            .get(number)
+            .await
+            .map_err(|e| self.cooldown.observe(e))
+            .with_context(|| format!("Failed to read issue #{} in {}/{}", number, owner, repo))?;
+        Ok(if issue.state == octocrab::models::IssueState::Open {
+            ArtifactState::Open
+        } else {
+            ArtifactState::Closed
+        })
+    }
+
+    /// 🔍 The newest RECENT_ISSUES_SCANNED issues (pull requests aside), open or closed,
+    /// searched for one whose body contains `marker`. The listing is read directly
+    /// rather than through the search API, whose index lags behind new issues.
+    pub async fn find_recent_issue(
+        &self,
+        owner: &str,
+        repo: &str,
+        marker: &str,
+    ) -> Result<Option<u64>> {
+        #[derive(serde::Deserialize)]
+        struct ListedIssue {
+            number: u64,
+            #[serde(default)]
+            body: Option<String>,
+            #[serde(default)]
+            pull_request: Option<Value>,
+        }
+
+        let _slot = self.begin_request().await?;
+        let issues: Vec<ListedIssue> = self
+            .octocrab
+            .get(
+                format!("/repos/{}/{}/issues", owner, repo),
+                Some(&[
+                    ("state", "all".to_string()),
+                    ("sort", "created".to_string()),
+                    ("direction", "desc".to_string()),
+                    ("per_page", RECENT_ISSUES_SCANNED.to_string()),
+                ]),
+            )
+            .await
+            .map_err(|e| self.cooldown.observe(e))
+            .with_context(|| format!("Failed to list recent issues in {}/{}", owner, repo))?;
+        Ok(issues
+            .into_iter()
+            .filter(|issue| issue.pull_request.is_none())
+            .find(|issue| {
+                issue
+                    .body
+                    .as_deref()
+                    .is_some_and(|body| body.contains(marker))
+            })
+            .map(|issue| issue.number))
+    }
+
+    /// 📄 Content of a file on `branch` (None when it doesn't exist)
+    pub async fn file_content(
+        &self,
+        owner: &str,
+        repo: &str,
+        path: &str,
+        branch: &str,
+    ) -> Result<Option<String>> {
+        Ok(self
+            .file_at(owner, repo, path, branch)
+            .await?
+            .map(|(_, content)| content))
+    }
+
+    /// 🪓 Delete a branch (a branch that is already gone is fine)
+    pub async fn delete_branch(&self, owner: &str, repo: &str, branch: &str) -> Result<()> {
+        debug!("🪓 Deleting branch {} of {}/{}", branch, owner, repo);
+        let _slot = self.begin_request().await?;
+        let response = self
+            .octocrab
+            ._delete(
+                format!("/repos/{}/{}/git/refs/heads/{}", owner, repo, branch),
+                None::<&()>,
+            )
+            .await
+            .map_err(|e| self.cooldown.observe(e))
+            .with_context(|| format!("Failed to delete branch {} of {}/{}", branch, owner, repo))?;
+        if response.status() == 404 || response.status() == 422 {
+            return Ok(());
+        }
+        octocrab::map_github_error(response)
+            .await
+            .map_err(|e| self.cooldown.observe(e))
+            .with_context(|| format!("Failed to delete branch {} of {}/{}", branch, owner, repo))?;
+        Ok(())
+    }
+
+    /// 🏷️ The release behind a tag (None when there is no such release)
+    pub async fn get_release_by_tag(
+        &self,
+        owner: &str,
+        repo: &str,
+        tag: &str,
+    ) -> Result<Option<GitHubRelease>> {
+        debug!("🏷️ Reading release {} of {}/{}", tag, owner, repo);
+        let _slot = self.begin_request().await?;
+        let response = self
+            .octocrab
+            ._get(format!("/repos/{}/{}/releases/tags/{}", owner, repo, tag))
+            .await
+            .map_err(|e| self.cooldown.observe(e))
+            .with_context(|| format!("Failed to read release {} of {}/{}", tag, owner, repo))?;
+        if response.status() == 404 {
+            return Ok(None);
+        }
+        let response = octocrab::map_github_error(response)
+            .await
+            .map_err(|e| self.cooldown.observe(e))
+            .with_context(|| format!("Failed to read release {} of {}/{}", tag, owner, repo))?;
+        let release = serde_json::from_str(&self.octocrab.body_to_string(response).await?)
+            .with_context(|| format!("Unreadable release {} of {}/{}", tag, owner, repo))?;
+        Ok(Some(release))
+    }
+
+    /// 🚦 Set our commit status on `sha` (replaces the previous one with our context)
+    pub async fn create_commit_status(
+        &self,
+        owner: &str,
+        repo: &str,
+        sha: &str,
+        status: &CommitStatus,
+    ) -> Result<()> {
+        debug!(
+            "🚦 Status {} on {} in {}/{}",
+            status.state.as_str(),
+            sha,
+            owner,
+            repo
+        );
+        let _slot = self.begin_request().await?;
+        let _: Value = self
+            .octocrab
+            .post(
+                format!("/repos/{}/{}/statuses/{}", owner, repo, sha),
+                Some(&serde_json::json!({
+                    "state": status.state.as_str(),
+                    "description": status.description,
+                    "target_url": status.target_url,
+                    "context": STATUS_CONTEXT,
+                })),
+            )
+            .await
+            .map_err(|e| self.cooldown.observe(e))
+            .with_context(|| {
+                format!("Failed to set the status of {} in {}/{}", sha, owner, repo)
+            })?;
+        Ok(())
+    }
+
+    /// ✅ Report on `sha` as a check run (only GitHub App installation tokens may).
+    /// Returns the run's id, so later states can update it in place.
+    pub async fn create_check_run(
+        &self,
+        owner: &str,
+        repo: &str,
+        sha: &str,
+        status: &CommitStatus,
+    ) -> Result<u64> {
+        debug!(
+            "✅ Check run {} on {} in {}/{}",
+            status.state.as_str(),
+            sha,
+            owner,
+            repo
+        );
+        let _slot = self.begin_request().await?;
+        let mut body = check_run_body(status);
+        body["head_sha"] = Value::from(sha);
+        let run: Value = self
+            .octocrab
+            .post(format!("/repos/{}/{}/check-runs", owner, repo), Some(&body))
+            .await
+            .map_err(|e| self.cooldown.observe(e))
+            .with_context(|| {
+                format!(
+                    "Failed to create a check run on {} in {}/{}",
+                    sha, owner, repo
+                )
+            })?;
+        run.get("id").and_then(Value::as_u64).with_context(|| {
+            format!(
+                "GitHub returned a check run without an id for {} in {}/{}",
+                sha, owner, repo
+            )
+        })
+    }
+
+    /// ✅ Move a check run `create_check_run` opened to a new state
+    pub async fn update_check_run(
+        &self,
+        owner: &str,
+        repo: &str,
+        check_run_id: u64,
+        status: &CommitStatus,
+    ) -> Result<()> {
+        debug!(
+            "✅ Check run {} is now {} in {}/{}",
+            check_run_id,
+            status.state.as_str(),
+            owner,
+            repo
+        );
+        let _slot = self.begin_request().await?;
+        let _: Value = self
+            .octocrab
+            .patch(
+                format!("/repos/{}/{}/check-runs/{}", owner, repo, check_run_id),
+                Some(&check_run_body(status)),
+            )
+            .await
+            .map_err(|e| self.cooldown.observe(e))
+            .with_context(|| {
+                format!(
+                    "Failed to update check run {} in {}/{}",
+                    check_run_id, owner, repo
+                )
+            })?;
+        Ok(())
+    }
+
+    /// 🔍 Check if user is a collaborator
+    pub async fn is_collaborator(&self, owner: &str, repo: &str, username: &str) -> Result<bool> {
+        debug!(
+            "🔍 Checking if {} is a collaborator on {}/{}",
+            username, owner, repo
+        );
+
+        let _slot = self.begin_request().await?;
+        // Use the API endpoint directly to check collaborator status
+        let result: Result<serde_json::Value, _> = self
+            .octocrab
+            .get(
+                format!("/repos/{}/{}/collaborators/{}", owner, repo, username),
+                None::<&()>,
+            )
+            .await;
+
+        match result {
+            Ok(_) => {
+                debug!("✅ {} is a collaborator on {}/{}", username, owner, repo);
+                Ok(true)
+            }
+            // 🧊 "Not now" is not "no"
+            Err(e) if is_secondary_rate_limit(&e) => Err(self.cooldown.observe(e)),
+            Err(_) => {
+                debug!(
+                    "❌ {} is not a collaborator on {}/{}",
+                    username, owner, repo
+                );
+                Ok(false)
+            }
+        }
+    }
+
+    /// 🔑 A user's permission on a repository ("admin", "write", "read" or "none"; GitHub
+    /// reports maintain as write and triage as read). None when GitHub doesn't know the user.
+    pub async fn collaborator_permission(
+        &self,
+        owner: &str,
+        repo: &str,
+        username: &str,
+    ) -> Result<Option<String>> {
+        debug!(
+            "🔑 Checking {}'s permission on {}/{}",
+            username, owner, repo
+        );
+
+        let _slot = self.begin_request().await?;
+        let result: Result<Value, _> = self
+            .octocrab
+            .get(
+                format!(
+                    "/repos/{}/{}/collaborators/{}/permission",
+                    owner, repo, username
+                ),
+                None::<&()>,
+            )
+            .await;
+
+        match result {
+            Ok(body) => Ok(body
+                .get("permission")
+                .and_then(Value::as_str)
+                .map(str::to_string)),
+            Err(octocrab::Error::GitHub { source, .. }) if source.status_code.as_u16() == 404 => {
+                Ok(None)
+            }
+            Err(e) => Err(self.cooldown.observe(e)).with_context(|| {
+                format!(
+                    "Failed to check {}'s permission on {}/{}",
+                    username, owner, repo
+                )
+            }),
+        }
+    }
+
+    /// 🎫 Create a new issue in a repository
+    pub async fn open_issue(
+        &self,
+        owner: &str,
+        repo: &str,
+        title: &str,
+        body: &str,
+    ) -> Result<Issue> {
+        debug!("🎫 Creating issue '{}' in {}/{}", title, owner, repo);
+
+        let _slot = self.begin_request().await?;
+        let issue = self
+            .octocrab
+            .issues(owner, repo)
+            .create(title)
+            .body(body)
+            .send()
+            .await
+            .map_err(|e| self.cooldown.observe(e))
+            .with_context(|| format!("Failed to create issue '{}' in {}/{}", title, owner, repo))?;
+
+        debug!(
+            "✅ Issue #{} created successfully: {}",
+            issue.number, issue.html_url
+        );
+        Ok(issue)
+    }
+
+    /// 🪝 Register a repository webhook sending `events` to `url`, signed with `secret`
+    /// when there is one. When GitHub says that hook already exists, the existing one is
+    /// updated instead. Returns the hook id.
+    pub async fn create_repo_webhook(
+        &self,
+        owner: &str,
+        repo: &str,
+        url: &str,
+        secret: Option<&str>,
+        events: &[&str],
+    ) -> Result<i64> {
+        debug!("🪝 Registering webhook {} on {}/{}", url, owner, repo);
+        self.cooldown.pass().await?;
+        self.throttle_write(owner, repo).await?;
+        let slot = self.requests.acquire().await;
+
+        let mut config = serde_json::json!({
+            "url": url,
+            "content_type": "json",
+            "insecure_ssl": "0",
+        });
+        if let Some(secret) = secret {
+            config["secret"] = Value::from(secret);
+        }
+        let created: Result<Value, _> = self
+            .octocrab
+            .post(
+                format!("/repos/{}/{}/hooks", owner, repo),
+                Some(&serde_json::json!({
+                    "name": "web",
+                    "active": true,
+                    "events": events,
+                    "config": config,
+                })),
+            )
+            .await;
+
+        let hook = match created {
+            Ok(hook) => hook,
+            Err(e) if is_hook_already_exists(&e) => {
+                info!(
+                    "🪝 Webhook {} already exists on {}/{}, updating it",
+                    url, owner, repo
+                );
+                // 🎟️ Looking it up takes a slot of its own
+                drop(slot);
+                let hook_id = self
+                    .find_repo_webhook(owner, repo, url)
+                    .await?
+                    .with_context(|| {
+                        format!(
+                            "GitHub reports a webhook for {} on {}/{} but doesn't list it",
+                            url, owner, repo
+                        )
+                    })?;
+                let _slot = self.begin_request().await?;
+                self.octocrab
+                    .patch(
+                        format!("/repos/{}/{}/hooks/{}", owner, repo, hook_id),
+                        Some(&serde_json::json!({
+                            "active": true,
+                            "events": events,
+                            "config": config,
+                        })),
+                    )
+                    .await
+                    .map_err(|e| self.cooldown.observe(e))
+                    .with_context(|| {
+                        format!("Failed to update webhook {} on {}/{}", hook_id, owner, repo)
+                    })?
+            }
+            Err(e) => {
+                return Err(self.cooldown.observe(e))
+                    .with_context(|| format!("Failed to create webhook on {}/{}", owner, repo))
+            }
+        };
+
+        let hook_id = hook
+            .get("id")
+            .and_then(Value::as_i64)
+            .context("GitHub returned a webhook without an id")?;
+        debug!("✅ Webhook {} registered on {}/{}", hook_id, owner, repo);
+        Ok(hook_id)
+    }
+
+    /// 🔍 Id of the repository webhook delivering to `url`, if there is one
+    async fn find_repo_webhook(&self, owner: &str, repo: &str, url: &str) -> Result<Option<i64>> {
+        let _slot = self.begin_request().await?;
+        let hooks: Vec<Value> = self
+            .octocrab
+            .get(
+                format!("/repos/{}/{}/hooks", owner, repo),
+                Some(&serde_json::json!({ "per_page": 100 })),
+            )
+            .await
+            .map_err(|e| self.cooldown.observe(e))
+            .with_context(|| format!("Failed to list webhooks on {}/{}", owner, repo))?;
+        Ok(hooks
+            .iter()
+            .find(|hook| hook.pointer("/config/url").and_then(Value::as_str) == Some(url))
+            .and_then(|hook| hook.get("id"))
+            .and_then(Value::as_i64))
+    }
+
+    /// 🗑️ Remove a repository webhook (one that is already gone counts as removed)
+    pub async fn delete_repo_webhook(&self, owner: &str, repo: &str, hook_id: i64) -> Result<()> {
+        debug!("🗑️ Removing webhook {} from {}/{}", hook_id, owner, repo);
+        self.cooldown.pass().await?;
+        self.throttle_write(owner, repo).await?;
+        let _slot = self.requests.acquire().await;
+
+        let response = self
+            .octocrab
+            ._delete(
+                format!("/repos/{}/{}/hooks/{}", owner, repo, hook_id),
+                None::<&()>,
+            )
+            .await
+            .map_err(|e| self.cooldown.observe(e))
+            .with_context(|| {
+                format!(
+                    "Failed to remove webhook {} from {}/{}",
+                    hook_id, owner, repo
+                )
+            })?;
+        if response.status() == 404 {
+            debug!("🗑️ Webhook {} was already gone", hook_id);
+            return Ok(());
+        }
+        octocrab::map_github_error(response)
+            .await
+            .map(drop)
+            .map_err(|e| self.cooldown.observe(e))
+            .with_context(|| {
+                format!(
+                    "Failed to remove webhook {} from {}/{}",
+                    hook_id, owner, repo
+                )
+            })
+    }
+}
+
+/// 🔍 Is this GitHub's 422 "Hook already exists on this repository"?
+fn is_hook_already_exists(error: &octocrab::Error) -> bool {
+    match error {
+        octocrab::Error::GitHub { source, .. } => {
+            source.status_code.as_u16() == 422
+                && source.errors.iter().flatten().any(|error| {
+                    error
+                        .get("message")
+                        .and_then(Value::as_str)
+                        .is_some_and(|message| message.contains("already exists"))
+                })
+        }
+        _ => false,
+    }
+}
+
+/// ✂️ "owner/repo" into its two halves
+fn split_repository(repository: &str) -> Result<(&str, &str)> {
+    repository
+        .split_once('/')
+        .with_context(|| format!("Repository {} is not in owner/repo format", repository))
+}
+
+/// ✅ The fields of a check run that follow our progress (without its `head_sha`)
+fn check_run_body(status: &CommitStatus) -> Value {
+    let (run_status, conclusion) = status.state.check_run();
+    let mut body = serde_json::json!({
+        "name": STATUS_CONTEXT,
+        "status": run_status,
+        "details_url": status.target_url,
+        "output": {
+            "title": status.description,
+            "summary": status.description,
+        },
+    });
+    if let Some(conclusion) = conclusion {
+        body["conclusion"] = Value::from(conclusion);
+    }
+    body
+}
+
+// 🧪 Tests - GraphQL and comment tidying against a mock GitHub!
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::github::throttle::WriteThrottled;
+    use std::time::Duration;
+    use wiremock::matchers::{body_partial_json, header, method, path, query_param};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn client(server: &MockServer) -> GitHubClient {
+        GitHubClient::new(
+            "test_token",
+            &server.uri(),
+            Arc::new(WriteThrottle::new(600, Duration::from_secs(5))),
+            CooldownGate::new(Duration::from_secs(60), Duration::ZERO),
+            Arc::new(RequestLimiter::new(4)),
+        )
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_graphql_returns_data_and_surfaces_errors() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/graphql"))
+            .and(header("authorization", "Bearer test_token"))
+            .and(body_partial_json(
+                serde_json::json!({ "variables": { "login": "8b-is" } }),
+            ))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "data": { "organization": { "name": "8b" } }
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/graphql"))
+            .and(body_partial_json(
+                serde_json::json!({ "variables": { "login": "nobody" } }),
+            ))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "data": { "organization": null },
+                "errors": [{ "message": "Could not resolve to an Organization" }]
+            })))
+            .mount(&server)
+            .await;
+
+        let client = client(&server);
+        let query = "query($login: String!) { organization(login: $login) { name } }";
+        let data: Value = client
+            .graphql(query, serde_json::json!({ "login": "8b-is" }))
+            .await
+            .unwrap();
+        assert_eq!(data["organization"]["name"], "8b");
+
+        let error = client
+            .graphql::<Value>(query, serde_json::json!({ "login": "nobody" }))
+            .await
+            .unwrap_err();
+        assert!(error
+            .to_string()
+            .contains("Could not resolve to an Organization"));
+        println!("✅ GraphQL helper test passed!");
+    }
+
+    #[tokio::test]
+    async fn test_recent_issues_are_found_by_marker() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/repos/8b-is/Feedbacker/issues"))
+            .and(query_param("state", "all"))
+            .and(query_param("sort", "created"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([
+                { "number": 12, "body": "A PR mentioning <!-- marker-1 -->", "pull_request": {} },
+                { "number": 11, "body": null },
+                { "number": 10, "body": "Filed automatically.\n\n<!-- marker-1 -->" },
+            ])))
+            .expect(2)
+            .mount(&server)
+            .await;
+
+        let client = client(&server);
+        assert_eq!(
+            client
+                .find_recent_issue("8b-is", "Feedbacker", "<!-- marker-1 -->")
+                .await
+                .unwrap(),
+            Some(10)
+        );
+        assert_eq!(
+            client
+                .find_recent_issue("8b-is", "Feedbacker", "<!-- marker-2 -->")
+                .await
+                .unwrap(),
+            None
+        );
+        println!("✅ Recent issue lookup test passed!");
+    }
+
+    #[tokio::test]
+    async fn test_minimize_delete_and_react() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/graphql"))
+            .and(body_partial_json(serde_json::json!({
+                "variables": { "subjectId": "IC_kwDOA", "classifier": "OUTDATED" }
+            })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "data": { "minimizeComment": { "minimizedComment": { "isMinimized": true } } }
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("DELETE"))
+            .and(path("/repos/8b-is/smart-tree/issues/comments/42"))
+            .respond_with(ResponseTemplate::new(204))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("PATCH"))
+            .and(path("/repos/8b-is/smart-tree/issues/comments/42"))
+            .and(body_partial_json(
+                serde_json::json!({ "body": "Welcome!\n\n---\n\nThanks!" }),
+            ))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({ "id": 42 })))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/repos/8b-is/smart-tree/issues/comments/42/reactions"))
+            .and(body_partial_json(serde_json::json!({ "content": "+1" })))
+            .respond_with(ResponseTemplate::new(201).set_body_json(serde_json::json!({
+                "id": 1, "content": "+1"
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let client = client(&server);
+        client
+            .minimize_comment("8b-is", "smart-tree", "IC_kwDOA", MinimizeReason::Outdated)
+            .await
+            .unwrap();
+        client
+            .update_comment("8b-is", "smart-tree", 42, "Welcome!\n\n---\n\nThanks!")
+            .await
+            .unwrap();
+        client
+            .delete_comment("8b-is", "smart-tree", 42)
+            .await
+            .unwrap();
+        client
+            .add_reaction("8b-is", "smart-tree", 42, Reaction::ThumbsUp)
+            .await
+            .unwrap();
+        println!("✅ Comment tidying calls test passed!");
+    }
+
+    #[tokio::test]
+    async fn test_ensure_labels_creates_only_the_missing_ones() {
+        use crate::github::labels::LABEL_CACHE_TTL;
+        use crate::test_support::FakeClock;
+
+        let server = MockServer::start().await;
+        // 📋 A full first page (with "BUG" in it) and a second page with the rest
+        let first_page: Vec<Value> = std::iter::once(serde_json::json!({ "name": "BUG" }))
+            .chain((1..100).map(|n| serde_json::json!({ "name": format!("area-{}", n) })))
+            .collect();
+        Mock::given(method("GET"))
+            .and(path("/repos/8b-is/smart-tree/labels"))
+            .and(query_param("page", "1"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(first_page))
+            .expect(2)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/repos/8b-is/smart-tree/labels"))
+            .and(query_param("page", "2"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(serde_json::json!([{ "name": "Needs-Info" }])),
+            )
+            .expect(2)
+            .mount(&server)
+            .await;
+        let created = |name: &str, color: &str| {
+            Mock::given(method("POST"))
+                .and(path("/repos/8b-is/smart-tree/labels"))
+                .and(body_partial_json(
+                    serde_json::json!({ "name": name, "color": color }),
+                ))
+                .respond_with(
+                    ResponseTemplate::new(201).set_body_json(serde_json::json!({ "id": 1 })),
+                )
+                .expect(1)
+        };
+        created("possible-duplicate", "cfd3d7").mount(&server).await;
+        created("question", "d876e3").mount(&server).await;
+        // 🤝 Created by someone else after we listed
+        Mock::given(method("POST"))
+            .and(path("/repos/8b-is/smart-tree/labels"))
+            .and(body_partial_json(
+                serde_json::json!({ "name": "enhancement" }),
+            ))
+            .respond_with(ResponseTemplate::new(422).set_body_json(serde_json::json!({
+                "message": "Validation Failed",
+                "errors": [{ "resource": "Label", "code": "already_exists", "field": "name" }],
+                "documentation_url": "https://docs.github.com/rest/issues/labels#create-a-label"
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let clock = Arc::new(FakeClock::default());
+        let mut client = client(&server);
+        client.labels = LabelCache::with_clock(LABEL_CACHE_TTL, clock.clone());
+        let specs = |names: &[&str]| {
+            LabelSpec::for_names(
+                &names
+                    .iter()
+                    .map(|name| name.to_string())
+                    .collect::<Vec<_>>(),
+                &Default::default(),
+            )
+        };
+
+        let made = client
+            .ensure_labels(
+                "8b-is",
+                "smart-tree",
+                &specs(&["bug", "needs-info", "possible-duplicate"]),
+            )
+            .await
+            .unwrap();
+        assert_eq!(made, vec!["possible-duplicate"]);
+
+        // 🧠 Within the hour nothing is listed again, and what we created is remembered
+        let made = client
+            .ensure_labels(
+                "8b-is",
+                "smart-tree",
+                &specs(&["possible-duplicate", "question", "enhancement", "Question"]),
+            )
+            .await
+            .unwrap();
+        assert_eq!(made, vec!["question"]);
+
+        // ⏰ After it the repository is listed afresh (and has everything)
+        clock.advance(LABEL_CACHE_TTL);
+        let made = client
+            .ensure_labels("8b-is", "smart-tree", &specs(&["bug"]))
+            .await
+            .unwrap();
+        assert!(made.is_empty());
+        println!("✅ Ensure labels test passed!");
+    }
+
+    #[tokio::test]
+    async fn test_secondary_rate_limit_pauses_every_call() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/repos/8b-is/smart-tree/issues/7/comments"))
+            .respond_with(ResponseTemplate::new(403).set_body_json(serde_json::json!({
+                "message": "You have exceeded a secondary rate limit. Please wait a few minutes before you try again.",
+                "documentation_url": "https://docs.github.com/rest/overview/rate-limits-for-the-rest-api"
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+        // 🚫 Nothing else may reach GitHub while the gate is closed, reads included
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(500))
+            .expect(0)
+            .mount(&server)
+            .await;
+
+        let client = client(&server);
+        let tripped = client
+            .add_comment_to_issue("8b-is", "smart-tree", 7, "Thanks!")
+            .await
+            .unwrap_err();
+        assert_eq!(
+            WriteThrottled::find(&tripped).map(|throttled| throttled.retry_after),
+            Some(Duration::from_secs(60))
+        );
+
+        let refused = client
+            .get_issue("8b-is", "smart-tree", 7)
+            .await
+            .unwrap_err();
+        assert!(WriteThrottled::find(&refused).is_some());
+        assert!(client.cooldown.remaining().is_some());
+        println!("✅ Secondary rate limit cooldown test passed!");
+    }
+
+    #[tokio::test]
+    async fn test_writes_are_throttled_per_installation() {
+        let Some(app) = crate::test_support::spawn_test_app().await else {
+            return;
+        };
+        for (installation_id, login, repository) in
+            [(1_i64, "8b-is", "8b-is/smart-tree"), (2, "hue", "hue/mem8")]
+        {
+            sqlx::query(
+                "INSERT INTO github_installations (installation_id, account_login, account_type, status) VALUES ($1, $2, 'Organization', 'active')",
+            )
+            .bind(installation_id)
+            .bind(login)
+            .execute(&app.db_pool)
+            .await
+            .unwrap();
+            sqlx::query(
+                "INSERT INTO github_installation_repositories (installation_id, repository) VALUES ($1, $2)",
+            )
+            .bind(installation_id)
+            .bind(repository)
+            .execute(&app.db_pool)
+            .await
+            .unwrap();
+        }
+        let server = MockServer::start().await;
+        Mock::given(method("DELETE"))
+            .respond_with(ResponseTemplate::new(204))
+            .expect(3)
+            .mount(&server)
+            .await;
+
+        // 🚰 One write a minute each, and no waiting
+        let mut client = client(&server).with_installations(app.db_pool.clone());
+        client.write_throttle = Arc::new(WriteThrottle::new(1, Duration::ZERO));
+        for (owner, repo) in [("8b-is", "smart-tree"), ("hue", "mem8"), ("8b-is", "other")] {
+            client.delete_comment(owner, repo, 42).await.unwrap();
+        }
+        // 🪣 smart-tree's installation is spent; mem8's and the token's are separate
+        let refused = client
+            .delete_comment("8b-is", "smart-tree", 43)
+            .await
+            .unwrap_err();
+        assert!(WriteThrottled::find(&refused).is_some());
+        println!("✅ Per-installation write throttle client test passed!");
+    }
+
+    #[tokio::test]
+    async fn test_requests_past_the_limit_queue_for_a_slot() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/repos/8b-is/smart-tree/collaborators/hue"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(serde_json::json!({}))
+                    .set_delay(Duration::from_millis(100)),
+            )
+            .expect(2)
+            .mount(&server)
+            .await;
+
+        let mut client = client(&server);
+        client.requests = Arc::new(RequestLimiter::new(1));
+        let (first, second) = tokio::join!(
+            client.is_collaborator("8b-is", "smart-tree", "hue"),
+            client.is_collaborator("8b-is", "smart-tree", "hue"),
+        );
+        assert!(first.unwrap() && second.unwrap());
+        // 🎟️ One slot, so the second call waited for the first to finish
+        assert_eq!(client.requests.queued_total(), 1);
+        assert_eq!(client.requests.in_use(), 0);
+        println!("✅ GitHub request limit test passed!");
+    }
+    #[tokio::test]
+    async fn test_repo_webhooks_create_update_existing_and_delete() {
+        let server = MockServer::start().await;
+        let url = "https://feedbacker.example/api/webhook/issues";
+        Mock::given(method("POST"))
+            .and(path("/repos/8b-is/smart-tree/hooks"))
+            .and(body_partial_json(serde_json::json!({
+                "name": "web",
+                "events": ["issues"],
+                "config": { "url": url, "content_type": "json", "secret": "s3cret" }
+            })))
+            .respond_with(ResponseTemplate::new(201).set_body_json(serde_json::json!({
+                "id": 11, "config": { "url": url }
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+        // 🔁 The second repository already has our hook: it's found and updated
+        Mock::given(method("POST"))
+            .and(path("/repos/8b-is/feedbacker/hooks"))
+            .respond_with(ResponseTemplate::new(422).set_body_json(serde_json::json!({
+                "message": "Validation Failed",
+                "errors": [{ "resource": "Hook", "code": "custom", "message": "Hook already exists on this repository" }]
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/repos/8b-is/feedbacker/hooks"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([
+                { "id": 21, "config": { "url": "https://ci.example/hook" } },
+                { "id": 22, "config": { "url": url } }
+            ])))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("PATCH"))
+            .and(path("/repos/8b-is/feedbacker/hooks/22"))
+            .and(body_partial_json(serde_json::json!({
+                "active": true,
+                "config": { "url": url }
+            })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "id": 22, "config": { "url": url }
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("DELETE"))
+            .and(path("/repos/8b-is/smart-tree/hooks/11"))
+            .respond_with(ResponseTemplate::new(204))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("DELETE"))
+            .and(path("/repos/8b-is/smart-tree/hooks/12"))
+            .respond_with(ResponseTemplate::new(404).set_body_json(serde_json::json!({
+                "message": "Not Found"
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("DELETE"))
+            .and(path("/repos/8b-is/smart-tree/hooks/13"))
+            .respond_with(ResponseTemplate::new(403).set_body_json(serde_json::json!({
+                "message": "Must have admin rights to Repository."
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let client = client(&server);
+        let created = client
+            .create_repo_webhook("8b-is", "smart-tree", url, Some("s3cret"), &["issues"])
+            .await
+            .unwrap();
+        assert_eq!(created, 11);
+        let updated = client
+            .create_repo_webhook("8b-is", "feedbacker", url, None, &["issues"])
+            .await
+            .unwrap();
+        assert_eq!(updated, 22);
+
+        client
+            .delete_repo_webhook("8b-is", "smart-tree", 11)
+            .await
+            .unwrap();
+        // 🤷 Already gone is fine, no permission is not
+        client
+            .delete_repo_webhook("8b-is", "smart-tree", 12)
+            .await
+            .unwrap();
+        let error = client
+            .delete_repo_webhook("8b-is", "smart-tree", 13)
+            .await
+            .unwrap_err();
+        assert!(format!("{:#}", error).contains("admin rights"));
+        println!("✅ Repository webhook calls test passed!");
+    }
+
+    #[tokio::test]
+    async fn test_branch_protection_reads_rules_or_none() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/repos/8b-is/smart-tree/branches/main/protection"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "required_status_checks": { "strict": false, "contexts": ["ci/test"] },
+                "required_pull_request_reviews": { "required_approving_review_count": 1 }
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/repos/8b-is/smart-tree/branches/dev/protection"))
+            .respond_with(ResponseTemplate::new(404).set_body_json(serde_json::json!({
+                "message": "Branch not protected"
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/repos/8b-is/feedbacker/branches/main/protection"))
+            .respond_with(ResponseTemplate::new(403).set_body_json(serde_json::json!({
+                "message": "Resource not accessible by integration"
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("PUT"))
+            .and(path("/repos/8b-is/smart-tree/pulls/7/merge"))
+            .and(body_partial_json(
+                serde_json::json!({ "merge_method": "squash" }),
+            ))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "merged": true
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+        let client = client(&server);
+
+        let protection = client
+            .get_branch_protection("8b-is", "smart-tree", "main")
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(protection.required_approving_reviews, 1);
+        assert_eq!(protection.required_status_checks, vec!["ci/test"]);
+        assert_eq!(
+            client
+                .get_branch_protection("8b-is", "smart-tree", "dev")
+                .await
+                .unwrap(),
+            None
+        );
+        // 🔐 Without admin rights the rules can't be read - that's an error, not "unprotected"
+        assert!(client
+            .get_branch_protection("8b-is", "feedbacker", "main")
+            .await
+            .is_err());
+        client
+            .merge_pull_request("8b-is", "smart-tree", 7)
+            .await
+            .unwrap();
+        println!("✅ Branch protection client test passed!");
+    }
+
+    #[tokio::test]
+    async fn test_check_runs_are_created_once_and_then_updated() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/repos/8b-is/smart-tree/check-runs"))
+            .and(body_partial_json(serde_json::json!({
+                "name": "Feedbacker",
+                "head_sha": "abc123",
+                "status": "in_progress"
+            })))
+            .respond_with(
+                ResponseTemplate::new(201).set_body_json(serde_json::json!({ "id": 4242 })),
+            )
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("PATCH"))
+            .and(path("/repos/8b-is/smart-tree/check-runs/4242"))
+            .and(body_partial_json(serde_json::json!({
+                "status": "completed",
+                "conclusion": "success",
+                "details_url": "https://github.com/8b-is/smart-tree/pull/7"
+            })))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_json(serde_json::json!({ "id": 4242 })),
+            )
+            .expect(1)
+            .mount(&server)
+            .await;
+        let client = client(&server);
+
+        let id = client
+            .create_check_run("8b-is", "smart-tree", "abc123", &CommitStatus::generating())
+            .await
+            .unwrap();
+        assert_eq!(id, 4242);
+        client
+            .update_check_run(
+                "8b-is",
+                "smart-tree",
+                id,
+                &CommitStatus::proposed("https://github.com/8b-is/smart-tree/pull/7"),
+            )
+            .await
+            .unwrap();
+        println!("✅ Check run client test passed!");
     }
 }
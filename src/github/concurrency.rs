@@ -0,0 +1,99 @@
+// 🎟️ GitHub Request Limiter - Only so many calls on the wire at once! 🎟️
+// Every GitHubClient call takes a slot from one shared semaphore before its request
+// goes out and gives it back once the response is read, so a burst of workers queues
+// up here instead of hitting GitHub all at once and tripping its secondary rate
+// limits. GITHUB_MAX_CONCURRENT_REQUESTS sets the number of slots; /metrics reports
+// how many are taken.
+// Created with love by Aye & Hue! ✨
+
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use tokio::sync::{Semaphore, SemaphorePermit};
+
+use crate::config::GitHubConfig;
+
+/// 🎟️ Bounded number of GitHub requests in flight (shared by every worker)
+#[derive(Debug)]
+pub struct RequestLimiter {
+    slots: Semaphore,
+    capacity: usize,
+    /// ⏳ Requests that found every slot taken and had to queue
+    queued: AtomicU64,
+}
+
+impl RequestLimiter {
+    /// ⚙️ `capacity` requests at once (at least one)
+    pub fn new(capacity: usize) -> Self {
+        let capacity = capacity.max(1);
+        Self {
+            slots: Semaphore::new(capacity),
+            capacity,
+            queued: AtomicU64::new(0),
+        }
+    }
+
+    /// ⚙️ Limiter sized by GITHUB_MAX_CONCURRENT_REQUESTS
+    pub fn from_config(config: &GitHubConfig) -> Self {
+        Self::new(config.max_concurrent_requests)
+    }
+
+    /// 🎟️ Wait for a free slot; the request holds it until the permit is dropped
+    pub async fn acquire(&self) -> SemaphorePermit<'_> {
+        if let Ok(permit) = self.slots.try_acquire() {
+            return permit;
+        }
+        self.queued.fetch_add(1, Ordering::Relaxed);
+        self.slots
+            .acquire()
+            .await
+            .expect("the request semaphore is never closed")
+    }
+
+    /// 📏 Slots in total
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// 📡 Requests in flight right now
+    pub fn in_use(&self) -> usize {
+        self.capacity - self.slots.available_permits()
+    }
+
+    /// ⏳ Requests that had to wait for a slot so far
+    pub fn queued_total(&self) -> u64 {
+        self.queued.load(Ordering::Relaxed)
+    }
+}
+
+// 🧪 Tests - Wait your turn!
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    #[tokio::test]
+    async fn test_requests_beyond_capacity_wait_for_a_slot() {
+        let limiter = Arc::new(RequestLimiter::new(2));
+        let first = limiter.acquire().await;
+        let _second = limiter.acquire().await;
+        assert_eq!(limiter.in_use(), 2);
+
+        let waiting = {
+            let limiter = Arc::clone(&limiter);
+            tokio::spawn(async move {
+                let _third = limiter.acquire().await;
+                limiter.in_use()
+            })
+        };
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        assert!(!waiting.is_finished());
+        assert_eq!(limiter.queued_total(), 1);
+
+        drop(first);
+        assert_eq!(waiting.await.unwrap(), 2);
+        assert_eq!(limiter.in_use(), 1);
+        assert_eq!(RequestLimiter::new(0).capacity(), 1);
+        println!("✅ GitHub request limiter test passed!");
+    }
+}
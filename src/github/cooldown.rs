@@ -0,0 +1,213 @@
+// 🧊 GitHub Cooldown Gate - Everybody stops when GitHub says "slow down"! 🧊
+// A secondary rate limit (a 403/429 saying so) applies to the whole token, not just
+// the call that hit it, so per-call retries only make it worse. The first response
+// like that closes this gate for GITHUB_SECONDARY_LIMIT_COOLDOWN_SECONDS; every
+// GitHubClient call checks it first and either sleeps until it reopens (if that's
+// within GITHUB_WRITE_MAX_WAIT_SECONDS) or fails with `SecondaryRateLimited`, which
+// issue automation defers through the job queue just like a throttled write.
+// Created with love by Aye & Hue! ✨
+
+use std::fmt;
+use std::sync::{Arc, RwLock};
+use std::time::{Duration, Instant};
+use tracing::{info, warn};
+
+use super::throttle::{Clock, SystemClock};
+use crate::config::GitHubConfig;
+
+/// 🚫 GitHub asked us to back off and the gate is still closed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SecondaryRateLimited {
+    /// ⏳ When the gate opens again
+    pub retry_after: Duration,
+}
+
+impl fmt::Display for SecondaryRateLimited {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "GitHub secondary rate limit, all requests paused for {}s",
+            self.retry_after.as_secs_f64().ceil()
+        )
+    }
+}
+
+impl std::error::Error for SecondaryRateLimited {}
+
+/// 🔍 Is this GitHub's "you have exceeded a secondary rate limit" answer?
+pub fn is_secondary_rate_limit(error: &octocrab::Error) -> bool {
+    match error {
+        octocrab::Error::GitHub { source, .. } => {
+            matches!(source.status_code.as_u16(), 403 | 429)
+                && source
+                    .message
+                    .to_lowercase()
+                    .contains("secondary rate limit")
+        }
+        _ => false,
+    }
+}
+
+/// 🧊 Shared "no GitHub requests until ..." gate (cloning shares the same gate)
+#[derive(Debug, Clone)]
+pub struct CooldownGate {
+    /// ⏰ Closed until this instant (None = open)
+    until: Arc<RwLock<Option<Instant>>>,
+    /// 🧊 How long a secondary limit closes the gate for
+    cooldown: Duration,
+    /// ⏳ Longest a call sleeps in place before failing instead
+    max_wait: Duration,
+    clock: Arc<dyn Clock>,
+}
+
+impl CooldownGate {
+    /// ➕ An open gate on the system clock
+    pub fn new(cooldown: Duration, max_wait: Duration) -> Self {
+        Self::with_clock(cooldown, max_wait, Arc::new(SystemClock))
+    }
+
+    /// ⚙️ From GITHUB_SECONDARY_LIMIT_COOLDOWN_SECONDS and GITHUB_WRITE_MAX_WAIT_SECONDS
+    pub fn from_config(config: &GitHubConfig) -> Self {
+        Self::new(
+            Duration::from_secs(config.secondary_limit_cooldown_seconds),
+            Duration::from_secs(config.write_max_wait_seconds),
+        )
+    }
+
+    /// 🧪 An open gate on an explicit clock
+    pub fn with_clock(cooldown: Duration, max_wait: Duration, clock: Arc<dyn Clock>) -> Self {
+        Self {
+            until: Arc::new(RwLock::new(None)),
+            cooldown,
+            max_wait,
+            clock,
+        }
+    }
+
+    /// ⏳ How long until the gate opens (None when it is open)
+    pub fn remaining(&self) -> Option<Duration> {
+        let until = *self.until.read().unwrap_or_else(|e| e.into_inner());
+        until
+            .map(|until| until.saturating_duration_since(self.clock.now()))
+            .filter(|remaining| !remaining.is_zero())
+    }
+
+    /// 🧊 Close the gate for the cooldown (never shortening a longer one already running)
+    pub fn trip(&self) -> SecondaryRateLimited {
+        let reopens = self.clock.now() + self.cooldown;
+        let mut until = self.until.write().unwrap_or_else(|e| e.into_inner());
+        if until.is_none_or(|current| current < reopens) {
+            *until = Some(reopens);
+        }
+        SecondaryRateLimited {
+            retry_after: self.cooldown,
+        }
+    }
+
+    /// 🚦 Ok(wait) to sleep before the request (zero when open); Err when the gate stays
+    /// closed for longer than a call may wait
+    pub fn check(&self) -> Result<Duration, SecondaryRateLimited> {
+        match self.remaining() {
+            None => Ok(Duration::ZERO),
+            Some(remaining) if remaining <= self.max_wait => Ok(remaining),
+            Some(remaining) => Err(SecondaryRateLimited {
+                retry_after: remaining,
+            }),
+        }
+    }
+
+    /// 🚦 Wait for the gate to open. Loops, since another worker may trip it again
+    /// while we sleep.
+    pub async fn pass(&self) -> Result<(), SecondaryRateLimited> {
+        loop {
+            let wait = self
+                .check()
+                .inspect_err(|limited| warn!("🧊 {}", limited))?;
+            if wait.is_zero() {
+                return Ok(());
+            }
+            info!(
+                "🧊 Holding GitHub request for {:?} (secondary rate limit)",
+                wait
+            );
+            tokio::time::sleep(wait).await;
+        }
+    }
+
+    /// 🔍 Turn an octocrab error into ours, closing the gate if it was a secondary limit
+    pub fn observe(&self, error: octocrab::Error) -> anyhow::Error {
+        if is_secondary_rate_limit(&error) {
+            let limited = self.trip();
+            warn!("🧊 {} ({})", limited, error);
+            return anyhow::Error::new(limited);
+        }
+        anyhow::Error::new(error)
+    }
+}
+
+// 🧪 Tests - Cooling off together!
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::FakeClock;
+
+    fn gate(cooldown_secs: u64, max_wait_secs: u64) -> (CooldownGate, Arc<FakeClock>) {
+        let clock = Arc::new(FakeClock::default());
+        let gate = CooldownGate::with_clock(
+            Duration::from_secs(cooldown_secs),
+            Duration::from_secs(max_wait_secs),
+            clock.clone(),
+        );
+        (gate, clock)
+    }
+
+    #[test]
+    fn test_gate_closes_for_the_cooldown_and_is_shared() {
+        let (gate, clock) = gate(60, 5);
+        let worker = gate.clone();
+        assert_eq!(worker.check(), Ok(Duration::ZERO));
+
+        assert_eq!(gate.trip().retry_after, Duration::from_secs(60));
+        // 🚫 Every clone sees it, and a minute is too long to sleep through
+        assert_eq!(
+            worker.check(),
+            Err(SecondaryRateLimited {
+                retry_after: Duration::from_secs(60)
+            })
+        );
+
+        // ⏳ Close to reopening, short waits are slept through instead
+        clock.advance(Duration::from_secs(57));
+        assert_eq!(worker.check(), Ok(Duration::from_secs(3)));
+
+        // 🧊 Tripping again pushes it out, but an earlier trip never shortens it
+        worker.trip();
+        assert_eq!(gate.remaining(), Some(Duration::from_secs(60)));
+        let short = CooldownGate {
+            cooldown: Duration::from_secs(1),
+            ..gate.clone()
+        };
+        short.trip();
+        assert_eq!(gate.remaining(), Some(Duration::from_secs(60)));
+
+        clock.advance(Duration::from_secs(60));
+        assert_eq!(gate.remaining(), None);
+        assert_eq!(worker.check(), Ok(Duration::ZERO));
+        println!("✅ Cooldown gate test passed!");
+    }
+
+    #[tokio::test]
+    async fn test_pass_sleeps_through_short_cooldowns() {
+        let gate = CooldownGate::new(Duration::from_millis(30), Duration::from_secs(1));
+        gate.pass().await.unwrap();
+        gate.trip();
+        let started = Instant::now();
+        gate.pass().await.unwrap();
+        assert!(started.elapsed() >= Duration::from_millis(25));
+
+        let strict = CooldownGate::new(Duration::from_secs(60), Duration::ZERO);
+        strict.trip();
+        assert!(strict.pass().await.is_err());
+        println!("✅ Cooldown gate wait test passed!");
+    }
+}
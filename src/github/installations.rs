@@ -0,0 +1,209 @@
+// 🧩 GitHub App Installations - Who let us in, and where! 🧩
+// Kept up to date from `installation` and `installation_repositories` webhook
+// events, so we know which installation covers a repository and whether it's
+// still active (suspended and deleted installations keep their row for history).
+// Created with love by Aye & Hue! ✨
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use sqlx::PgPool;
+use tracing::info;
+
+/// 📣 Webhook event for an installation being created, suspended, deleted, ...
+pub const INSTALLATION_EVENT: &str = "installation";
+/// 📣 Webhook event for repositories being added to or removed from an installation
+pub const INSTALLATION_REPOSITORIES_EVENT: &str = "installation_repositories";
+
+/// 📦 The parts of an installation delivery we keep
+#[derive(Debug, Clone, Deserialize)]
+pub struct InstallationEvent {
+    pub action: String,
+    pub installation: InstallationData,
+    /// 📚 Repositories granted at creation (installation "created" only)
+    #[serde(default)]
+    pub repositories: Vec<InstallationRepository>,
+    /// ➕ Repositories newly granted (installation_repositories "added")
+    #[serde(default)]
+    pub repositories_added: Vec<InstallationRepository>,
+    /// ➖ Repositories no longer granted (installation_repositories "removed")
+    #[serde(default)]
+    pub repositories_removed: Vec<InstallationRepository>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct InstallationData {
+    pub id: i64,
+    pub account: InstallationAccount,
+    /// 🎯 "all" or "selected"
+    #[serde(default)]
+    pub repository_selection: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct InstallationAccount {
+    pub login: String,
+    #[serde(rename = "type", default = "default_account_type")]
+    pub account_type: String,
+}
+
+fn default_account_type() -> String {
+    "User".to_string()
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct InstallationRepository {
+    pub full_name: String,
+}
+
+/// 🗄️ An installation as we last heard of it
+#[derive(Debug, Clone, Serialize, sqlx::FromRow)]
+pub struct Installation {
+    pub installation_id: i64,
+    pub account_login: String,
+    pub account_type: String,
+    pub repository_selection: Option<String>,
+    /// 🚦 "active", "suspended" or "deleted"
+    pub status: String,
+}
+
+/// 🚦 Status an installation action leaves it in (None when the action doesn't change it)
+fn status_for_action(action: &str) -> Option<&'static str> {
+    match action {
+        "created" | "unsuspend" | "new_permissions_accepted" => Some("active"),
+        "suspend" => Some("suspended"),
+        "deleted" => Some("deleted"),
+        _ => None,
+    }
+}
+
+/// 📝 Apply an `installation` or `installation_repositories` delivery to our records
+pub async fn apply_installation_event(
+    pool: &PgPool,
+    event_name: &str,
+    event: &InstallationEvent,
+) -> Result<()> {
+    let installation = &event.installation;
+    let status = if event_name == INSTALLATION_EVENT {
+        status_for_action(&event.action)
+    } else {
+        None
+    };
+
+    let mut tx = pool.begin().await?;
+    // 🧩 Upsert the installation itself - repository events can arrive for one we missed
+    sqlx::query(
+        r#"
+        INSERT INTO github_installations (installation_id, account_login, account_type, repository_selection, status)
+        VALUES ($1, $2, $3, $4, COALESCE($5, 'active'))
+        ON CONFLICT (installation_id) DO UPDATE
+        SET account_login = EXCLUDED.account_login,
+            account_type = EXCLUDED.account_type,
+            repository_selection = COALESCE(EXCLUDED.repository_selection, github_installations.repository_selection),
+            status = COALESCE($5, github_installations.status),
+            updated_at = NOW()
+        "#,
+    )
+    .bind(installation.id)
+    .bind(&installation.account.login)
+    .bind(&installation.account.account_type)
+    .bind(&installation.repository_selection)
+    .bind(status)
+    .execute(&mut *tx)
+    .await
+    .context("Failed to record GitHub installation")?;
+
+    if status == Some("deleted") {
+        sqlx::query("DELETE FROM github_installation_repositories WHERE installation_id = $1")
+            .bind(installation.id)
+            .execute(&mut *tx)
+            .await
+            .context("Failed to clear installation repositories")?;
+    }
+
+    for repository in event.repositories.iter().chain(&event.repositories_added) {
+        sqlx::query(
+            "INSERT INTO github_installation_repositories (installation_id, repository) VALUES ($1, $2) ON CONFLICT DO NOTHING",
+        )
+        .bind(installation.id)
+        .bind(&repository.full_name)
+        .execute(&mut *tx)
+        .await
+        .context("Failed to add installation repository")?;
+    }
+    for repository in &event.repositories_removed {
+        sqlx::query(
+            "DELETE FROM github_installation_repositories WHERE installation_id = $1 AND repository = $2",
+        )
+        .bind(installation.id)
+        .bind(&repository.full_name)
+        .execute(&mut *tx)
+        .await
+        .context("Failed to remove installation repository")?;
+    }
+    tx.commit().await?;
+
+    info!(
+        "🧩 GitHub installation {} ({}) {} {}",
+        installation.id, installation.account.login, event_name, event.action
+    );
+    Ok(())
+}
+
+/// 🔍 One installation by id
+pub async fn find(pool: &PgPool, installation_id: i64) -> Result<Option<Installation>> {
+    sqlx::query_as(
+        "SELECT installation_id, account_login, account_type, repository_selection, status FROM github_installations WHERE installation_id = $1",
+    )
+    .bind(installation_id)
+    .fetch_optional(pool)
+    .await
+    .context("Failed to load GitHub installation")
+}
+
+/// 🎯 The active installation covering a repository, if any
+pub async fn installation_for_repository(
+    pool: &PgPool,
+    repository: &str,
+) -> Result<Option<Installation>> {
+    sqlx::query_as(
+        r#"
+        SELECT i.installation_id, i.account_login, i.account_type, i.repository_selection, i.status
+        FROM github_installations i
+        JOIN github_installation_repositories r ON r.installation_id = i.installation_id
+        WHERE r.repository = $1 AND i.status = 'active'
+        ORDER BY i.updated_at DESC
+        LIMIT 1
+        "#,
+    )
+    .bind(repository)
+    .fetch_optional(pool)
+    .await
+    .context("Failed to look up installation for repository")
+}
+
+/// 📚 Repositories an installation currently covers
+pub async fn repositories(pool: &PgPool, installation_id: i64) -> Result<Vec<String>> {
+    sqlx::query_scalar(
+        "SELECT repository FROM github_installation_repositories WHERE installation_id = $1 ORDER BY repository",
+    )
+    .bind(installation_id)
+    .fetch_all(pool)
+    .await
+    .context("Failed to list installation repositories")
+}
+
+// 🧪 Tests - Keeping the guest list straight!
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_installation_actions_map_to_statuses() {
+        assert_eq!(status_for_action("created"), Some("active"));
+        assert_eq!(status_for_action("unsuspend"), Some("active"));
+        assert_eq!(status_for_action("suspend"), Some("suspended"));
+        assert_eq!(status_for_action("deleted"), Some("deleted"));
+        assert_eq!(status_for_action("added"), None);
+        println!("✅ Installation status mapping test passed!");
+    }
+}
@@ -0,0 +1,156 @@
+// 📋 Issue Forms - Reading the sections GitHub issue forms leave behind! 📋
+// Issue forms render every field as a `### Heading` followed by the answer,
+// with "_No response_" for fields left empty. We turn that back into fields.
+// Created with love by Aye & Hue! ✨
+
+use serde::Serialize;
+
+/// 🫥 What GitHub writes for an optional field nobody filled in
+const NO_RESPONSE: &str = "_No response_";
+
+/// 🏷️ Well-known fields, recognised by their heading
+pub const STEPS_TO_REPRODUCE: &str = "steps_to_reproduce";
+pub const EXPECTED_BEHAVIOR: &str = "expected_behavior";
+pub const ACTUAL_BEHAVIOR: &str = "actual_behavior";
+pub const VERSION: &str = "version";
+
+/// 🐛 Sections a bug report is expected to fill in
+const BUG_REPORT_SECTIONS: &[&str] = &[STEPS_TO_REPRODUCE, EXPECTED_BEHAVIOR, ACTUAL_BEHAVIOR];
+
+/// 📄 One `### Heading` section of an issue form
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct IssueFormSection {
+    /// 📝 Heading as written in the form
+    pub heading: String,
+    /// 🔑 Normalised key (well-known fields get a fixed key)
+    pub key: String,
+    /// 💬 Answer, or None when the field was left empty
+    pub value: Option<String>,
+}
+
+/// 📋 All sections extracted from an issue body
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct IssueForm {
+    pub sections: Vec<IssueFormSection>,
+}
+
+impl IssueForm {
+    /// 🔍 Answer for a key, if the section exists and was filled in
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.sections
+            .iter()
+            .find(|section| section.key == key)
+            .and_then(|section| section.value.as_deref())
+    }
+
+    /// 🐛 Does this look like a bug report form?
+    pub fn is_bug_report(&self) -> bool {
+        self.sections
+            .iter()
+            .any(|section| BUG_REPORT_SECTIONS.contains(&section.key.as_str()))
+    }
+
+    /// 🫥 Headings of bug report sections that are present but empty
+    pub fn missing_required(&self) -> Vec<&str> {
+        self.sections
+            .iter()
+            .filter(|section| {
+                BUG_REPORT_SECTIONS.contains(&section.key.as_str()) && section.value.is_none()
+            })
+            .map(|section| section.heading.as_str())
+            .collect()
+    }
+}
+
+/// 📋 Parse an issue body into form sections.
+/// Returns None when the body has no `### ` headings (i.e. it wasn't written by a form).
+pub fn parse_issue_form(body: &str) -> Option<IssueForm> {
+    let mut form = IssueForm::default();
+    let mut current: Option<(String, Vec<&str>)> = None;
+
+    for line in body.lines() {
+        if let Some(heading) = line.strip_prefix("### ") {
+            if let Some((heading, lines)) = current.take() {
+                form.sections.push(build_section(heading, &lines));
+            }
+            current = Some((heading.trim().to_string(), Vec::new()));
+        } else if let Some((_, lines)) = current.as_mut() {
+            lines.push(line);
+        }
+    }
+    if let Some((heading, lines)) = current {
+        form.sections.push(build_section(heading, &lines));
+    }
+
+    if form.sections.is_empty() {
+        None
+    } else {
+        Some(form)
+    }
+}
+
+fn build_section(heading: String, lines: &[&str]) -> IssueFormSection {
+    let text = lines.join("\n").trim().to_string();
+    let value = if text.is_empty() || text == NO_RESPONSE {
+        None
+    } else {
+        Some(text)
+    };
+    IssueFormSection {
+        key: normalize_heading(&heading),
+        heading,
+        value,
+    }
+}
+
+/// 🔑 Map a heading onto a well-known key, or slugify it
+fn normalize_heading(heading: &str) -> String {
+    let lower = heading.to_lowercase();
+    if lower.contains("reproduce") || lower.starts_with("steps") {
+        STEPS_TO_REPRODUCE.to_string()
+    } else if lower.contains("expected") {
+        EXPECTED_BEHAVIOR.to_string()
+    } else if lower.contains("actual") || lower.contains("what happened") {
+        ACTUAL_BEHAVIOR.to_string()
+    } else if lower.contains("version") {
+        VERSION.to_string()
+    } else {
+        lower
+            .split(|c: char| !c.is_alphanumeric())
+            .filter(|word| !word.is_empty())
+            .collect::<Vec<_>>()
+            .join("_")
+    }
+}
+
+// 🧪 Tests - Filling in forms so you don't have to!
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BUG_FORM: &str = "### Steps to reproduce\n\n1. Run `st --mode ai`\n2. Watch it crash\n\n### Expected behavior\n\nA tree\n\n### Actual behavior\n\n_No response_\n\n### Smart Tree version\n\n5.2.0\n\n### Anything else?\n\n_No response_";
+
+    #[test]
+    fn test_parse_bug_report_form() {
+        let form = parse_issue_form(BUG_FORM).unwrap();
+        assert_eq!(form.sections.len(), 5);
+        assert_eq!(
+            form.get(STEPS_TO_REPRODUCE),
+            Some("1. Run `st --mode ai`\n2. Watch it crash")
+        );
+        assert_eq!(form.get(EXPECTED_BEHAVIOR), Some("A tree"));
+        assert_eq!(form.get(ACTUAL_BEHAVIOR), None);
+        assert_eq!(form.get(VERSION), Some("5.2.0"));
+        assert_eq!(form.sections[4].key, "anything_else");
+        assert!(form.is_bug_report());
+        assert_eq!(form.missing_required(), vec!["Actual behavior"]);
+        println!("✅ Issue form parsing test passed!");
+    }
+
+    #[test]
+    fn test_free_form_body_is_not_a_form() {
+        assert!(parse_issue_form("It crashes when I run it. Please fix!").is_none());
+        assert!(parse_issue_form("").is_none());
+        println!("✅ Free-form issue body test passed!");
+    }
+}
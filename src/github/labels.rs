@@ -0,0 +1,247 @@
+// 🏷️ Issue Labels - Our labels, in our colors! 🏷️
+// Adding a label that doesn't exist makes GitHub create it in plain grey with no
+// description (or, on some setups, refuse). So before the issue automation applies
+// labels, `GitHubClient::ensure_labels` creates the missing ones from `STANDARD_LABELS`
+// (or the project's `labels` overrides). What a repository already has is listed once
+// and remembered for LABEL_CACHE_TTL, so applying labels doesn't cost a listing each
+// time. Labels an issue already carries aren't sent again (`missing_labels`), which
+// saves a call and the `labeled` webhook echo. Names compare case-insensitively, like
+// GitHub's.
+// Created with love by Aye & Hue! ✨
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use serde::Serialize;
+
+use super::throttle::{Clock, SystemClock};
+use crate::database::project_config::LabelOverride;
+
+/// ⏰ How long a repository's label listing is trusted
+pub const LABEL_CACHE_TTL: Duration = Duration::from_secs(60 * 60);
+
+/// 🎨 Labels the issue automation applies: (name, color, description)
+pub const STANDARD_LABELS: &[(&str, &str, &str)] = &[
+    ("bug", "d73a4a", "Something isn't working"),
+    ("enhancement", "a2eeef", "New feature or request"),
+    (
+        "documentation",
+        "0075ca",
+        "Improvements or additions to documentation",
+    ),
+    ("question", "d876e3", "Further information is requested"),
+    (
+        "performance",
+        "fbca04",
+        "Something is slower than it should be",
+    ),
+    (
+        "needs-info",
+        "e4e669",
+        "Waiting on the author for more details",
+    ),
+    (
+        "possible-duplicate",
+        "cfd3d7",
+        "This may already be covered by another issue",
+    ),
+];
+
+/// 🩶 Color for labels outside the table (GitHub's own default)
+const FALLBACK_COLOR: &str = "ededed";
+
+/// 🏷️ A label as we want it to exist in a repository
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct LabelSpec {
+    pub name: String,
+    /// 🎨 Six hex digits, no `#`
+    pub color: String,
+    pub description: String,
+}
+
+impl LabelSpec {
+    /// 🎨 How `name` should look: the project's override, else the standard table, else
+    /// plain grey without a description
+    pub fn for_name(name: &str, overrides: &BTreeMap<String, LabelOverride>) -> Self {
+        let standard = STANDARD_LABELS
+            .iter()
+            .find(|(standard, _, _)| standard.eq_ignore_ascii_case(name));
+        let mut spec = Self {
+            name: name.to_string(),
+            color: standard
+                .map_or(FALLBACK_COLOR, |(_, color, _)| color)
+                .to_string(),
+            description: standard
+                .map_or("", |(_, _, description)| description)
+                .to_string(),
+        };
+        let custom = overrides
+            .iter()
+            .find(|(label, _)| label.eq_ignore_ascii_case(name));
+        if let Some((_, custom)) = custom {
+            if let Some(color) = &custom.color {
+                spec.color = color.clone();
+            }
+            if let Some(description) = &custom.description {
+                spec.description = description.clone();
+            }
+        }
+        spec
+    }
+
+    /// 🎨 Specs for every label in `names`
+    pub fn for_names(names: &[String], overrides: &BTreeMap<String, LabelOverride>) -> Vec<Self> {
+        names
+            .iter()
+            .map(|name| Self::for_name(name, overrides))
+            .collect()
+    }
+}
+
+/// 🔍 The labels of `wanted` that aren't in `current` yet, each once, in order
+pub fn missing_labels(wanted: &[String], current: &[String]) -> Vec<String> {
+    let mut have: HashSet<String> = current.iter().map(|name| name.to_lowercase()).collect();
+    wanted
+        .iter()
+        .filter(|name| have.insert(name.to_lowercase()))
+        .cloned()
+        .collect()
+}
+
+/// 📋 A repository's labels (lowercased) and when they were listed
+#[derive(Debug)]
+struct KnownLabels {
+    listed_at: Instant,
+    names: HashSet<String>,
+}
+
+/// 🧠 Labels known to exist, per repository, for LABEL_CACHE_TTL after listing them
+#[derive(Debug)]
+pub struct LabelCache {
+    ttl: Duration,
+    clock: Arc<dyn Clock>,
+    repos: Mutex<HashMap<String, KnownLabels>>,
+}
+
+impl Default for LabelCache {
+    fn default() -> Self {
+        Self::with_clock(LABEL_CACHE_TTL, Arc::new(SystemClock))
+    }
+}
+
+impl LabelCache {
+    pub fn with_clock(ttl: Duration, clock: Arc<dyn Clock>) -> Self {
+        Self {
+            ttl,
+            clock,
+            repos: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// 🔍 Lowercased labels of `repository`, or None when it needs listing (again)
+    pub fn known(&self, repository: &str) -> Option<HashSet<String>> {
+        let repos = self.repos.lock().unwrap();
+        repos
+            .get(&repository.to_lowercase())
+            .filter(|known| self.clock.now().duration_since(known.listed_at) < self.ttl)
+            .map(|known| known.names.clone())
+    }
+
+    /// 📋 Remember a fresh listing of `repository`
+    pub fn remember(&self, repository: &str, names: impl IntoIterator<Item = String>) {
+        self.repos.lock().unwrap().insert(
+            repository.to_lowercase(),
+            KnownLabels {
+                listed_at: self.clock.now(),
+                names: names.into_iter().map(|name| name.to_lowercase()).collect(),
+            },
+        );
+    }
+
+    /// ➕ Note a label that now exists (without extending the listing's lifetime)
+    pub fn add(&self, repository: &str, name: &str) {
+        if let Some(known) = self
+            .repos
+            .lock()
+            .unwrap()
+            .get_mut(&repository.to_lowercase())
+        {
+            known.names.insert(name.to_lowercase());
+        }
+    }
+}
+
+// 🧪 Tests - Painting by numbers!
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::FakeClock;
+
+    #[test]
+    fn test_specs_come_from_overrides_then_the_table() {
+        let overrides = BTreeMap::from([(
+            "Bug".to_string(),
+            LabelOverride {
+                color: Some("ff0000".to_string()),
+                description: None,
+            },
+        )]);
+        let specs = LabelSpec::for_names(
+            &[
+                "BUG".to_string(),
+                "needs-info".to_string(),
+                "wontfix".to_string(),
+            ],
+            &overrides,
+        );
+        assert_eq!(
+            specs[0],
+            LabelSpec {
+                name: "BUG".to_string(),
+                color: "ff0000".to_string(),
+                description: "Something isn't working".to_string(),
+            }
+        );
+        assert_eq!(specs[1].color, "e4e669");
+        assert_eq!(specs[2].color, FALLBACK_COLOR);
+        assert_eq!(specs[2].description, "");
+        println!("✅ Label spec test passed!");
+    }
+
+    #[test]
+    fn test_only_missing_labels_are_sent() {
+        let names = |names: &[&str]| {
+            names
+                .iter()
+                .map(|name| name.to_string())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            missing_labels(&names(&["bug", "needs-info", "Bug"]), &names(&["BUG"])),
+            names(&["needs-info"])
+        );
+        assert!(missing_labels(&names(&["bug"]), &names(&["bug", "question"])).is_empty());
+        assert_eq!(
+            missing_labels(&names(&["bug", "bug"]), &[]),
+            names(&["bug"])
+        );
+        println!("✅ Missing labels test passed!");
+    }
+
+    #[test]
+    fn test_cache_expires_after_the_ttl() {
+        let clock = Arc::new(FakeClock::default());
+        let cache = LabelCache::with_clock(Duration::from_secs(60), clock.clone());
+        assert_eq!(cache.known("8b-is/smart-tree"), None);
+
+        cache.remember("8b-is/Smart-Tree", ["Bug".to_string()]);
+        cache.add("8b-is/smart-tree", "needs-info");
+        let known = cache.known("8B-IS/smart-tree").unwrap();
+        assert!(known.contains("bug") && known.contains("needs-info"));
+
+        clock.advance(Duration::from_secs(60));
+        assert_eq!(cache.known("8b-is/smart-tree"), None);
+        println!("✅ Label cache test passed!");
+    }
+}
@@ -10,15 +10,34 @@ use octocrab::{
     Octocrab,
 };
 use serde::{Deserialize, Serialize};
+use sqlx::PgPool;
 use std::path::Path;
 use tracing::{debug, error, info, warn};
 use uuid::Uuid;
 
 use crate::config::GitHubConfig;
+use crate::database::models::Feedback;
 
+pub mod artifacts; // 🔗 Branch, PR and issue links per feedback, with their current state
+pub mod availability; // 🚪 Repositories that were deleted or shut to us mid-pipeline
 pub mod client; // 🤖 GitHub API client wrapper
+pub mod concurrency; // 🎟️ Bound on GitHub requests in flight at once
+pub mod cooldown; // 🧊 Token-wide pause after a secondary rate limit
+pub mod installations; // 🧩 GitHub App installations and their repositories
+pub mod issue_forms; // 📋 Structured sections from issue form bodies
+pub mod labels; // 🏷️ Our standard labels, created in repositories that lack them
 pub mod operations; // 🔧 High-level GitHub operations
+pub mod ops; // 🧩 GitHubOps trait (real client + test fakes)
+pub mod patch; // 🧩 Unified diffs of generated changes, kept on feedback metadata
+pub mod path_policy; // 🛡️ Per-project allow/deny globs for generated file changes
+pub mod pr_body; // 📝 Pull request bodies rendered from a template with the feedback's context
+pub mod protection; // 🔒 Base branch protection and what it means for our PRs
+pub mod releases; // 🏷️ Published releases behind the versions we announce
+pub mod repo_hooks; // 🪝 Webhooks we register on project repositories
 pub mod ssh; // 🔐 SSH key management for git operations
+pub mod statuses; // 🚦 Commit statuses / check runs reporting pipeline progress
+pub mod throttle; // 🚰 Token-bucket throttle for issue writes
+pub mod verify; // 🔏 Read committed branches back before opening the PR
 pub mod webhooks; // 🪝 Webhook payload handling
 
 /// 🤖 GitHub client for API operations
@@ -46,6 +65,8 @@ pub struct FeedbackProcessingRequest {
     pub commit_message: String,
     /// 🌿 Branch name for the PR
     pub branch_name: String,
+    /// 🧭 Where the feedback came from, for the PR body
+    pub pull_request: pr_body::PullRequestContext,
 }
 
 /// 🔧 Code improvement generated by AI
@@ -98,6 +119,27 @@ pub struct PullRequestResult {
     pub error_message: Option<String>,
 }
 
+/// 🌿 A feedback branch with its changes committed, before the pull request is opened
+#[derive(Debug, Clone)]
+pub struct CommittedChanges {
+    /// 🎯 Branch the pull request will target
+    pub base_branch: String,
+    /// 🌿 Branch the changes were committed to
+    pub branch_name: String,
+    /// ✍️ Every change that was written, in order
+    pub applied: Vec<AppliedChange>,
+}
+
+/// ✍️ One change as it was committed
+#[derive(Debug, Clone)]
+pub struct AppliedChange {
+    pub improvement: CodeImprovement,
+    /// 🔖 Sha of the commit that wrote it
+    pub commit_sha: String,
+    /// 📄 The full file content we wrote (None for a deletion)
+    pub written: Option<String>,
+}
+
 /// 📊 Repository information
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RepositoryInfo {
@@ -200,30 +242,8 @@ impl GitHubClient {
         Ok("mock_sha".to_string())
     }
 
-    /// 📝 Apply code improvements to a repository
-    pub async fn apply_improvements(
-        &self,
-        request: &FeedbackProcessingRequest,
-    ) -> Result<PullRequestResult> {
-        info!(
-            "📝 Applying improvements for feedback: {}",
-            request.feedback_id
-        );
-        // TODO: Implement proper improvement application when GitHub API is ready
-        Ok(PullRequestResult {
-            url: "https://github.com/mock/repo/pull/1".to_string(),
-            number: 1,
-            title: "Mock PR".to_string(),
-            branch_name: request.branch_name.clone(),
-            base_branch: "main".to_string(),
-            success: true,
-            error_message: None,
-        })
-    }
-
-    // TODO: Implement apply_single_improvement when GitHub API is ready
-
-    // TODO: Implement create_pull_request when GitHub API is ready
+    // 📝 Generated changes go through `jobs::approval::submit_changes` (path policy,
+    // approval gate), and the commit stage applies them with `commit_changes`
 }
 
 /// 🔧 Parse repository string (owner/repo) into components
@@ -238,36 +258,6 @@ fn parse_repository(repository: &str) -> Result<(String, String)> {
     Ok((parts[0].to_string(), parts[1].to_string()))
 }
 
-/// 📝 Generate pull request description from feedback and improvements
-fn generate_pr_description(
-    feedback_content: &str,
-    applied_improvements: &[(CodeImprovement, String)],
-) -> String {
-    let mut description = String::new();
-
-    description.push_str("## 🤖 AI-Generated Improvements\n\n");
-    description.push_str("This pull request contains improvements generated by Feedbacker based on user feedback.\n\n");
-
-    description.push_str("### 📝 Original Feedback\n");
-    description.push_str(&format!("> {}\n\n", feedback_content));
-
-    description.push_str("### 🔧 Applied Changes\n");
-    for (improvement, _) in applied_improvements {
-        description.push_str(&format!(
-            "- **{}**: {} ({})\n",
-            improvement.file_path,
-            improvement.description,
-            format!("{:?}", improvement.change_type).to_lowercase()
-        ));
-    }
-
-    description.push_str("\n---\n");
-    description.push_str("🚢 Generated with love by [Feedbacker](https://github.com/aye-is/feedbacker) - Aye & Hue\n");
-    description.push_str("🤖 Powered by AI for intelligent code improvements\n");
-
-    description
-}
-
 // 🧪 Tests - Because GitHub integration needs thorough testing!
 #[cfg(test)]
 mod tests {
@@ -299,7 +289,16 @@ mod tests {
             "abc123".to_string(),
         )];
 
-        let description = generate_pr_description(feedback, &improvements);
+        let request = FeedbackProcessingRequest {
+            feedback_id: Uuid::nil(),
+            repository: "owner/repo".to_string(),
+            feedback_content: feedback.to_string(),
+            improvements: Vec::new(),
+            commit_message: "Add error handling".to_string(),
+            branch_name: "feedbacker/errors".to_string(),
+            pull_request: pr_body::PullRequestContext::default(),
+        };
+        let description = pr_body::render_pr_body(&request, &improvements, "");
         assert!(description.contains("AI-Generated Improvements"));
         assert!(description.contains("Please add error handling"));
         assert!(description.contains("src/main.rs"));
@@ -0,0 +1,638 @@
+// 🧩 GitHub Operations - The GitHub calls handlers are allowed to make! 🧩
+// Handlers talk to `Arc<dyn GitHubOps>` on AppState instead of building clients,
+// so tests can swap in a fake that records every call.
+// Created with love by Aye & Hue! ✨
+
+use anyhow::Result;
+use async_trait::async_trait;
+use serde::Serialize;
+
+use super::artifacts::ArtifactState;
+use super::client::GitHubClient;
+use super::labels::{missing_labels, LabelSpec};
+use super::protection::{BaseProtection, BranchProtection};
+use super::releases::GitHubRelease;
+use super::statuses::CommitStatus;
+use super::{CommittedChanges, FeedbackProcessingRequest, PullRequestResult};
+
+/// 🎫 The parts of a freshly created issue we hand back to API callers
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct CreatedIssue {
+    pub number: u64,
+    pub html_url: String,
+    pub title: String,
+    pub state: String,
+}
+
+/// 🎫 A new issue, and which of its labels and assignees made it on
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct CreateIssueResult {
+    pub issue: CreatedIssue,
+    /// 🏷️ Labels applied (empty when applying them failed, see `label_error`)
+    pub applied_labels: Vec<String>,
+    /// 🏷️ Why the labels couldn't be applied
+    pub label_error: Option<String>,
+    /// 👤 Assignees GitHub wouldn't take, with why
+    pub failed_assignees: Vec<FailedAssignee>,
+}
+
+impl CreateIssueResult {
+    /// ✅ Everything asked for was applied
+    pub fn is_complete(&self) -> bool {
+        self.label_error.is_none() && self.failed_assignees.is_empty()
+    }
+}
+
+/// 👤 An assignee that couldn't be assigned
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct FailedAssignee {
+    pub login: String,
+    pub error: String,
+}
+
+/// 💬 Ids of a comment we just posted: `id` for REST calls, `node_id` for GraphQL
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct PostedComment {
+    pub id: u64,
+    pub node_id: String,
+}
+
+/// 🙈 Why a comment gets minimized (GitHub's `ReportedContentClassifiers`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MinimizeReason {
+    Outdated,
+    Resolved,
+    Duplicate,
+    OffTopic,
+    Spam,
+    Abuse,
+}
+
+impl MinimizeReason {
+    /// 🏷️ The GraphQL enum value
+    pub fn classifier(self) -> &'static str {
+        match self {
+            Self::Outdated => "OUTDATED",
+            Self::Resolved => "RESOLVED",
+            Self::Duplicate => "DUPLICATE",
+            Self::OffTopic => "OFF_TOPIC",
+            Self::Spam => "SPAM",
+            Self::Abuse => "ABUSE",
+        }
+    }
+}
+
+/// 👍 Reactions GitHub accepts on a comment
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Reaction {
+    ThumbsUp,
+    ThumbsDown,
+    Laugh,
+    Confused,
+    Heart,
+    Hooray,
+    Rocket,
+    Eyes,
+}
+
+impl Reaction {
+    /// 🏷️ The REST `content` value
+    pub fn content(self) -> &'static str {
+        match self {
+            Self::ThumbsUp => "+1",
+            Self::ThumbsDown => "-1",
+            Self::Laugh => "laugh",
+            Self::Confused => "confused",
+            Self::Heart => "heart",
+            Self::Hooray => "hooray",
+            Self::Rocket => "rocket",
+            Self::Eyes => "eyes",
+        }
+    }
+}
+
+/// 🔑 What the token is allowed to do in a repository
+#[derive(Debug, Clone, PartialEq)]
+pub struct RepositoryAccess {
+    pub default_branch: String,
+    pub can_push: bool,
+    /// 🔧 Admin rights (needed to manage the repository's webhooks)
+    pub can_admin: bool,
+}
+
+/// 🐙 GitHub operations used by the handlers and the self-test
+#[async_trait]
+pub trait GitHubOps: Send + Sync {
+    /// 📝 Add a comment to an issue
+    async fn add_comment_to_issue(
+        &self,
+        owner: &str,
+        repo: &str,
+        issue_number: u32,
+        comment: &str,
+    ) -> Result<PostedComment>;
+
+    /// ✏️ Replace the body of an issue comment
+    async fn update_comment(
+        &self,
+        owner: &str,
+        repo: &str,
+        comment_id: u64,
+        comment: &str,
+    ) -> Result<()>;
+
+    /// 🙈 Hide a comment behind "This comment was marked as ..."
+    async fn minimize_comment(
+        &self,
+        owner: &str,
+        repo: &str,
+        node_id: &str,
+        reason: MinimizeReason,
+    ) -> Result<()>;
+
+    /// 🗑️ Delete an issue comment
+    async fn delete_comment(&self, owner: &str, repo: &str, comment_id: u64) -> Result<()>;
+
+    /// 👍 React to an issue comment
+    async fn add_reaction(
+        &self,
+        owner: &str,
+        repo: &str,
+        comment_id: u64,
+        reaction: Reaction,
+    ) -> Result<()>;
+
+    /// 🏷️ Add labels to an issue
+    async fn add_labels_to_issue(
+        &self,
+        owner: &str,
+        repo: &str,
+        issue_number: u32,
+        labels: &[String],
+    ) -> Result<()>;
+
+    /// 📋 The labels an issue carries
+    async fn issue_labels(&self, owner: &str, repo: &str, issue_number: u32)
+        -> Result<Vec<String>>;
+
+    /// 🏷️ Add the labels the issue doesn't carry yet, and nothing when it has them all.
+    /// `current` is what the issue is known to carry (e.g. from the webhook payload);
+    /// without it the issue's labels are read first. Returns the labels added.
+    async fn add_missing_labels(
+        &self,
+        owner: &str,
+        repo: &str,
+        issue_number: u32,
+        labels: &[String],
+        current: Option<&[String]>,
+    ) -> Result<Vec<String>> {
+        let missing = match current {
+            Some(current) => missing_labels(labels, current),
+            None => missing_labels(labels, &self.issue_labels(owner, repo, issue_number).await?),
+        };
+        if !missing.is_empty() {
+            self.add_labels_to_issue(owner, repo, issue_number, &missing)
+                .await?;
+        }
+        Ok(missing)
+    }
+
+    /// 🎨 Create any of `labels` the repository doesn't have yet (returns those created)
+    async fn ensure_labels(
+        &self,
+        owner: &str,
+        repo: &str,
+        labels: &[LabelSpec],
+    ) -> Result<Vec<String>>;
+
+    /// 👤 Assign an issue to a user
+    async fn assign_issue(
+        &self,
+        owner: &str,
+        repo: &str,
+        issue_number: u32,
+        assignee: &str,
+    ) -> Result<()>;
+
+    /// ✅ Close an issue
+    async fn close_issue(&self, owner: &str, repo: &str, issue_number: u32) -> Result<()>;
+
+    /// 🎫 Open an issue with just its title and body
+    async fn open_issue(
+        &self,
+        owner: &str,
+        repo: &str,
+        title: &str,
+        body: &str,
+    ) -> Result<CreatedIssue>;
+
+    /// 🎫 Open an issue, then apply its labels and each assignee in follow-up calls, so
+    /// an assignee GitHub won't take (not a collaborator, ...) costs only itself. Fails
+    /// only when the issue itself couldn't be opened.
+    async fn create_issue(
+        &self,
+        owner: &str,
+        repo: &str,
+        title: &str,
+        body: &str,
+        labels: Option<&[String]>,
+        assignees: Option<&[String]>,
+    ) -> Result<CreateIssueResult> {
+        let issue = self.open_issue(owner, repo, title, body).await?;
+        let number = issue.number as u32;
+        let mut result = CreateIssueResult {
+            issue,
+            applied_labels: Vec::new(),
+            label_error: None,
+            failed_assignees: Vec::new(),
+        };
+        if let Some(labels) = labels.filter(|labels| !labels.is_empty()) {
+            match self.add_labels_to_issue(owner, repo, number, labels).await {
+                Ok(()) => result.applied_labels = labels.to_vec(),
+                Err(e) => result.label_error = Some(format!("{:#}", e)),
+            }
+        }
+        for login in assignees.unwrap_or_default() {
+            if let Err(e) = self.assign_issue(owner, repo, number, login).await {
+                result.failed_assignees.push(FailedAssignee {
+                    login: login.clone(),
+                    error: format!("{:#}", e),
+                });
+            }
+        }
+        Ok(result)
+    }
+
+    /// 🔑 Look up the default branch and our push permission
+    async fn repository_access(&self, owner: &str, repo: &str) -> Result<RepositoryAccess>;
+
+    /// ✍️ Can `username` push to the repository? (admin, maintain and write all can)
+    async fn has_write_access(&self, owner: &str, repo: &str, username: &str) -> Result<bool>;
+
+    /// 🪝 Register (or update the existing) repository webhook, returning its id
+    async fn create_repo_webhook(
+        &self,
+        owner: &str,
+        repo: &str,
+        url: &str,
+        secret: Option<&str>,
+        events: &[&str],
+    ) -> Result<i64>;
+
+    /// 🗑️ Remove a repository webhook
+    async fn delete_repo_webhook(&self, owner: &str, repo: &str, hook_id: i64) -> Result<()>;
+
+    /// 🌿 Commit a feedback item's changes to a new branch off the default branch
+    async fn commit_changes(&self, request: &FeedbackProcessingRequest)
+        -> Result<CommittedChanges>;
+
+    /// 📄 A file's content on a branch (None when it doesn't exist)
+    async fn file_content(
+        &self,
+        owner: &str,
+        repo: &str,
+        path: &str,
+        branch: &str,
+    ) -> Result<Option<String>>;
+
+    /// 🪓 Delete a branch
+    async fn delete_branch(&self, owner: &str, repo: &str, branch: &str) -> Result<()>;
+
+    /// 🔒 Protection rules of a branch (None when it isn't protected)
+    async fn branch_protection(
+        &self,
+        owner: &str,
+        repo: &str,
+        branch: &str,
+    ) -> Result<Option<BranchProtection>>;
+
+    /// 🚀 Open the pull request for committed changes
+    async fn open_pull_request(
+        &self,
+        request: &FeedbackProcessingRequest,
+        committed: &CommittedChanges,
+        protection: &BaseProtection,
+    ) -> Result<PullRequestResult>;
+
+    /// 🔀 Merge a pull request
+    async fn merge_pull_request(&self, owner: &str, repo: &str, number: u64) -> Result<()>;
+
+    /// 🚪 Whether a pull request is open, closed or merged
+    async fn pull_request_state(
+        &self,
+        owner: &str,
+        repo: &str,
+        number: u64,
+    ) -> Result<ArtifactState>;
+
+    /// 🚪 Whether an issue is open or closed
+    async fn issue_state(&self, owner: &str, repo: &str, number: u64) -> Result<ArtifactState>;
+
+    /// 🔍 The number of a recently opened issue (open or closed) whose body contains
+    /// `marker`, if there is one
+    async fn find_recent_issue(&self, owner: &str, repo: &str, marker: &str)
+        -> Result<Option<u64>>;
+
+    /// ✏️ Replace the description of a pull request
+    async fn update_pull_request_body(
+        &self,
+        owner: &str,
+        repo: &str,
+        number: u64,
+        body: &str,
+    ) -> Result<()>;
+
+    /// 🏷️ The release behind a tag (None when there is no such release)
+    async fn release_by_tag(
+        &self,
+        owner: &str,
+        repo: &str,
+        tag: &str,
+    ) -> Result<Option<GitHubRelease>>;
+
+    /// 🚦 Set our commit status on a commit
+    async fn create_commit_status(
+        &self,
+        owner: &str,
+        repo: &str,
+        sha: &str,
+        status: &CommitStatus,
+    ) -> Result<()>;
+
+    /// ✅ Report on a commit as a check run; returns the run's id
+    async fn create_check_run(
+        &self,
+        owner: &str,
+        repo: &str,
+        sha: &str,
+        status: &CommitStatus,
+    ) -> Result<u64>;
+
+    /// ✅ Move one of our check runs to a new state
+    async fn update_check_run(
+        &self,
+        owner: &str,
+        repo: &str,
+        check_run_id: u64,
+        status: &CommitStatus,
+    ) -> Result<()>;
+}
+
+#[async_trait]
+impl GitHubOps for GitHubClient {
+    async fn add_comment_to_issue(
+        &self,
+        owner: &str,
+        repo: &str,
+        issue_number: u32,
+        comment: &str,
+    ) -> Result<PostedComment> {
+        GitHubClient::add_comment_to_issue(self, owner, repo, issue_number, comment).await
+    }
+
+    async fn update_comment(
+        &self,
+        owner: &str,
+        repo: &str,
+        comment_id: u64,
+        comment: &str,
+    ) -> Result<()> {
+        GitHubClient::update_comment(self, owner, repo, comment_id, comment).await
+    }
+
+    async fn minimize_comment(
+        &self,
+        owner: &str,
+        repo: &str,
+        node_id: &str,
+        reason: MinimizeReason,
+    ) -> Result<()> {
+        GitHubClient::minimize_comment(self, owner, repo, node_id, reason).await
+    }
+
+    async fn delete_comment(&self, owner: &str, repo: &str, comment_id: u64) -> Result<()> {
+        GitHubClient::delete_comment(self, owner, repo, comment_id).await
+    }
+
+    async fn add_reaction(
+        &self,
+        owner: &str,
+        repo: &str,
+        comment_id: u64,
+        reaction: Reaction,
+    ) -> Result<()> {
+        GitHubClient::add_reaction(self, owner, repo, comment_id, reaction).await
+    }
+
+    async fn add_labels_to_issue(
+        &self,
+        owner: &str,
+        repo: &str,
+        issue_number: u32,
+        labels: &[String],
+    ) -> Result<()> {
+        GitHubClient::add_labels_to_issue(self, owner, repo, issue_number, labels).await
+    }
+
+    async fn issue_labels(
+        &self,
+        owner: &str,
+        repo: &str,
+        issue_number: u32,
+    ) -> Result<Vec<String>> {
+        GitHubClient::issue_labels(self, owner, repo, issue_number).await
+    }
+
+    async fn ensure_labels(
+        &self,
+        owner: &str,
+        repo: &str,
+        labels: &[LabelSpec],
+    ) -> Result<Vec<String>> {
+        GitHubClient::ensure_labels(self, owner, repo, labels).await
+    }
+
+    async fn assign_issue(
+        &self,
+        owner: &str,
+        repo: &str,
+        issue_number: u32,
+        assignee: &str,
+    ) -> Result<()> {
+        GitHubClient::assign_issue(self, owner, repo, issue_number, assignee).await
+    }
+
+    async fn close_issue(&self, owner: &str, repo: &str, issue_number: u32) -> Result<()> {
+        GitHubClient::close_issue(self, owner, repo, issue_number).await
+    }
+
+    async fn open_issue(
+        &self,
+        owner: &str,
+        repo: &str,
+        title: &str,
+        body: &str,
+    ) -> Result<CreatedIssue> {
+        let issue = GitHubClient::open_issue(self, owner, repo, title, body).await?;
+        Ok(CreatedIssue {
+            number: issue.number,
+            html_url: issue.html_url.to_string(),
+            title: issue.title,
+            state: format!("{:?}", issue.state),
+        })
+    }
+
+    async fn repository_access(&self, owner: &str, repo: &str) -> Result<RepositoryAccess> {
+        let repository = self.get_repository(owner, repo).await?;
+        Ok(RepositoryAccess {
+            can_push: repository
+                .permissions
+                .as_ref()
+                .map(|permissions| permissions.push)
+                .unwrap_or(false),
+            can_admin: repository
+                .permissions
+                .as_ref()
+                .map(|permissions| permissions.admin)
+                .unwrap_or(false),
+            default_branch: repository
+                .default_branch
+                .unwrap_or_else(|| "main".to_string()),
+        })
+    }
+
+    async fn has_write_access(&self, owner: &str, repo: &str, username: &str) -> Result<bool> {
+        let permission = self.collaborator_permission(owner, repo, username).await?;
+        Ok(matches!(permission.as_deref(), Some("admin" | "write")))
+    }
+
+    async fn create_repo_webhook(
+        &self,
+        owner: &str,
+        repo: &str,
+        url: &str,
+        secret: Option<&str>,
+        events: &[&str],
+    ) -> Result<i64> {
+        GitHubClient::create_repo_webhook(self, owner, repo, url, secret, events).await
+    }
+
+    async fn delete_repo_webhook(&self, owner: &str, repo: &str, hook_id: i64) -> Result<()> {
+        GitHubClient::delete_repo_webhook(self, owner, repo, hook_id).await
+    }
+
+    async fn commit_changes(
+        &self,
+        request: &FeedbackProcessingRequest,
+    ) -> Result<CommittedChanges> {
+        self.commit_feedback_changes(request).await
+    }
+
+    async fn file_content(
+        &self,
+        owner: &str,
+        repo: &str,
+        path: &str,
+        branch: &str,
+    ) -> Result<Option<String>> {
+        GitHubClient::file_content(self, owner, repo, path, branch).await
+    }
+
+    async fn delete_branch(&self, owner: &str, repo: &str, branch: &str) -> Result<()> {
+        GitHubClient::delete_branch(self, owner, repo, branch).await
+    }
+
+    async fn branch_protection(
+        &self,
+        owner: &str,
+        repo: &str,
+        branch: &str,
+    ) -> Result<Option<BranchProtection>> {
+        self.get_branch_protection(owner, repo, branch).await
+    }
+
+    async fn open_pull_request(
+        &self,
+        request: &FeedbackProcessingRequest,
+        committed: &CommittedChanges,
+        protection: &BaseProtection,
+    ) -> Result<PullRequestResult> {
+        self.open_feedback_pull_request(request, committed, protection)
+            .await
+    }
+
+    async fn merge_pull_request(&self, owner: &str, repo: &str, number: u64) -> Result<()> {
+        GitHubClient::merge_pull_request(self, owner, repo, number).await
+    }
+
+    async fn pull_request_state(
+        &self,
+        owner: &str,
+        repo: &str,
+        number: u64,
+    ) -> Result<ArtifactState> {
+        GitHubClient::pull_request_state(self, owner, repo, number).await
+    }
+
+    async fn issue_state(&self, owner: &str, repo: &str, number: u64) -> Result<ArtifactState> {
+        GitHubClient::issue_state(self, owner, repo, number).await
+    }
+
+    async fn find_recent_issue(
+        &self,
+        owner: &str,
+        repo: &str,
+        marker: &str,
+    ) -> Result<Option<u64>> {
+        GitHubClient::find_recent_issue(self, owner, repo, marker).await
+    }
+
+    async fn update_pull_request_body(
+        &self,
+        owner: &str,
+        repo: &str,
+        number: u64,
+        body: &str,
+    ) -> Result<()> {
+        GitHubClient::update_pull_request_body(self, owner, repo, number, body).await
+    }
+
+    async fn release_by_tag(
+        &self,
+        owner: &str,
+        repo: &str,
+        tag: &str,
+    ) -> Result<Option<GitHubRelease>> {
+        self.get_release_by_tag(owner, repo, tag).await
+    }
+
+    async fn create_commit_status(
+        &self,
+        owner: &str,
+        repo: &str,
+        sha: &str,
+        status: &CommitStatus,
+    ) -> Result<()> {
+        GitHubClient::create_commit_status(self, owner, repo, sha, status).await
+    }
+
+    async fn create_check_run(
+        &self,
+        owner: &str,
+        repo: &str,
+        sha: &str,
+        status: &CommitStatus,
+    ) -> Result<u64> {
+        GitHubClient::create_check_run(self, owner, repo, sha, status).await
+    }
+
+    async fn update_check_run(
+        &self,
+        owner: &str,
+        repo: &str,
+        check_run_id: u64,
+        status: &CommitStatus,
+    ) -> Result<()> {
+        GitHubClient::update_check_run(self, owner, repo, check_run_id, status).await
+    }
+}
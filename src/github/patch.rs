@@ -0,0 +1,243 @@
+// 🧩 Generated Patches - What the automation proposes, readable before it lands! 🧩
+// Once generated changes have passed the path policy, each file change is turned
+// into a unified diff (three lines of context, `@@` hunk headers) and stored under
+// `metadata.generated_patch`, so the admin detail page can show reviewers exactly
+// what would be committed - held for approval or not.
+// Created with love by Aye & Hue! ✨
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sqlx::PgPool;
+
+use super::{ChangeType, CodeImprovement};
+use crate::database::models::Feedback;
+
+/// 🏷️ Metadata key the per-file patches are stored under
+pub const PATCH_METADATA_KEY: &str = "generated_patch";
+/// 📐 Unchanged lines kept around each change
+const CONTEXT_LINES: usize = 3;
+/// 📏 Largest old × new line count we diff properly (beyond it: all removed, all added)
+const MAX_DIFF_CELLS: usize = 4_000_000;
+
+/// 📄 One file's proposed change as a unified diff
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FilePatch {
+    pub file_path: String,
+    pub change_type: ChangeType,
+    pub description: String,
+    pub additions: usize,
+    pub deletions: usize,
+    /// 📜 `---`/`+++` header lines followed by the hunks
+    pub diff: String,
+}
+
+impl FilePatch {
+    /// 🔧 Diff a generated change against the content it replaces
+    pub fn from_change(change: &CodeImprovement) -> Self {
+        let original = change.original_content.as_deref().unwrap_or_default();
+        let appended;
+        let (old, new) = match change.change_type {
+            ChangeType::Create => ("", change.new_content.as_str()),
+            ChangeType::Modify => (original, change.new_content.as_str()),
+            ChangeType::Append => {
+                appended = format!("{}{}", original, change.new_content);
+                (original, appended.as_str())
+            }
+            ChangeType::Delete => (original, ""),
+        };
+        let lines = line_diff(old, new);
+        let (from, to) = match change.change_type {
+            ChangeType::Create => ("/dev/null".to_string(), format!("b/{}", change.file_path)),
+            ChangeType::Delete => (format!("a/{}", change.file_path), "/dev/null".to_string()),
+            _ => (
+                format!("a/{}", change.file_path),
+                format!("b/{}", change.file_path),
+            ),
+        };
+        Self {
+            file_path: change.file_path.clone(),
+            change_type: change.change_type.clone(),
+            description: change.description.clone(),
+            additions: lines.iter().filter(|(marker, _)| *marker == '+').count(),
+            deletions: lines.iter().filter(|(marker, _)| *marker == '-').count(),
+            diff: format!("--- {}\n+++ {}\n{}", from, to, hunks(&lines)),
+        }
+    }
+}
+
+/// 📝 Store the patch of validated changes on the feedback (replacing an earlier one)
+pub async fn record_patch(
+    pool: &PgPool,
+    feedback: &mut Feedback,
+    improvements: &[CodeImprovement],
+) -> Result<()> {
+    let patches: Vec<FilePatch> = improvements.iter().map(FilePatch::from_change).collect();
+    feedback.metadata = sqlx::query_scalar(
+        r#"
+        UPDATE feedback
+        SET metadata = COALESCE(metadata, '{}'::jsonb) || jsonb_build_object($2::text, $3::jsonb),
+            updated_at = NOW()
+        WHERE id = $1
+        RETURNING metadata
+        "#,
+    )
+    .bind(feedback.id)
+    .bind(PATCH_METADATA_KEY)
+    .bind(serde_json::to_value(&patches)?)
+    .fetch_one(pool)
+    .await
+    .context("Failed to record generated patch")?;
+    Ok(())
+}
+
+/// 🔍 The patches stored on a feedback item's metadata, if any
+pub fn stored_patch(metadata: Option<&Value>) -> Option<Vec<FilePatch>> {
+    serde_json::from_value(metadata?.get(PATCH_METADATA_KEY)?.clone()).ok()
+}
+
+/// ✂️ Group diff lines into `@@` hunks with CONTEXT_LINES of context
+fn hunks(lines: &[(char, &str)]) -> String {
+    let mut ranges: Vec<(usize, usize)> = Vec::new();
+    for (index, _) in lines.iter().enumerate().filter(|(_, (m, _))| *m != ' ') {
+        let start = index.saturating_sub(CONTEXT_LINES);
+        let end = (index + CONTEXT_LINES + 1).min(lines.len());
+        match ranges.last_mut() {
+            Some((_, last_end)) if start <= *last_end => *last_end = end,
+            _ => ranges.push((start, end)),
+        }
+    }
+
+    let mut out = String::new();
+    for (start, end) in ranges {
+        let before = &lines[..start];
+        let hunk = &lines[start..end];
+        let position = |skip: char, lines: &[(char, &str)]| {
+            lines.iter().filter(|(marker, _)| *marker != skip).count()
+        };
+        let (old_before, new_before) = (position('+', before), position('-', before));
+        let (old_len, new_len) = (position('+', hunk), position('-', hunk));
+        // 🔢 An empty side points at the line before it, as diff(1) does
+        out.push_str(&format!(
+            "@@ -{},{} +{},{} @@\n",
+            old_before + usize::from(old_len > 0),
+            old_len,
+            new_before + usize::from(new_len > 0),
+            new_len
+        ));
+        for (marker, line) in hunk {
+            out.push(*marker);
+            out.push_str(line);
+            out.push('\n');
+        }
+    }
+    out
+}
+
+/// ➕➖ Line diff of `old` against `new` via longest common subsequence: each line
+/// marked ' ' (kept), '-' (removed) or '+' (added)
+pub fn line_diff<'a>(old: &'a str, new: &'a str) -> Vec<(char, &'a str)> {
+    let old: Vec<&str> = old.lines().collect();
+    let new: Vec<&str> = new.lines().collect();
+    if old.len() * new.len() > MAX_DIFF_CELLS {
+        return old
+            .iter()
+            .map(|line| ('-', *line))
+            .chain(new.iter().map(|line| ('+', *line)))
+            .collect();
+    }
+    // 🧮 common[i][j] = LCS length of old[i..] and new[j..]
+    let mut common = vec![vec![0usize; new.len() + 1]; old.len() + 1];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            common[i][j] = if old[i] == new[j] {
+                common[i + 1][j + 1] + 1
+            } else {
+                common[i + 1][j].max(common[i][j + 1])
+            };
+        }
+    }
+    let (mut i, mut j) = (0, 0);
+    let mut lines = Vec::with_capacity(old.len() + new.len());
+    while i < old.len() && j < new.len() {
+        if old[i] == new[j] {
+            lines.push((' ', old[i]));
+            i += 1;
+            j += 1;
+        } else if common[i + 1][j] >= common[i][j + 1] {
+            lines.push(('-', old[i]));
+            i += 1;
+        } else {
+            lines.push(('+', new[j]));
+            j += 1;
+        }
+    }
+    lines.extend(old[i..].iter().map(|line| ('-', *line)));
+    lines.extend(new[j..].iter().map(|line| ('+', *line)));
+    lines
+}
+
+// 🧪 Tests - Patches a reviewer can actually read!
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn change(change_type: ChangeType, original: Option<&str>, new: &str) -> CodeImprovement {
+        CodeImprovement {
+            file_path: "src/lib.rs".to_string(),
+            description: "Tidy up".to_string(),
+            change_type,
+            original_content: original.map(str::to_string),
+            new_content: new.to_string(),
+            line_number: None,
+        }
+    }
+
+    #[test]
+    fn test_line_diff_keeps_common_lines() {
+        assert_eq!(
+            line_diff("a\nb\nc\n", "a\nc\nd\n"),
+            vec![(' ', "a"), ('-', "b"), (' ', "c"), ('+', "d")]
+        );
+        assert_eq!(line_diff("", "new\n"), vec![('+', "new")]);
+        assert_eq!(line_diff("gone\n", ""), vec![('-', "gone")]);
+        println!("✅ Line diff test passed!");
+    }
+
+    #[test]
+    fn test_patches_are_unified_diffs_with_hunks() {
+        let old: String = (1..=20).map(|n| format!("line {}\n", n)).collect();
+        let new = old
+            .replace("line 2\n", "line two\n")
+            .replace("line 18\n", "");
+        let patch = FilePatch::from_change(&change(ChangeType::Modify, Some(&old), &new));
+        assert_eq!((patch.additions, patch.deletions), (1, 2));
+        assert_eq!(
+            patch.diff,
+            "--- a/src/lib.rs\n+++ b/src/lib.rs\n\
+             @@ -1,5 +1,5 @@\n line 1\n-line 2\n+line two\n line 3\n line 4\n line 5\n\
+             @@ -15,6 +15,5 @@\n line 15\n line 16\n line 17\n-line 18\n line 19\n line 20\n"
+        );
+
+        let created = FilePatch::from_change(&change(ChangeType::Create, None, "fn main() {}\n"));
+        assert_eq!(
+            created.diff,
+            "--- /dev/null\n+++ b/src/lib.rs\n@@ -0,0 +1,1 @@\n+fn main() {}\n"
+        );
+        let appended = FilePatch::from_change(&change(ChangeType::Append, Some("a\n"), "b\n"));
+        assert_eq!(
+            appended.diff,
+            "--- a/src/lib.rs\n+++ b/src/lib.rs\n@@ -1,1 +1,2 @@\n a\n+b\n"
+        );
+        let deleted = FilePatch::from_change(&change(ChangeType::Delete, Some("a\n"), ""));
+        assert!(deleted
+            .diff
+            .starts_with("--- a/src/lib.rs\n+++ /dev/null\n@@ -1,1 +0,0 @@"));
+        assert!(
+            FilePatch::from_change(&change(ChangeType::Modify, Some("same\n"), "same\n"))
+                .diff
+                .ends_with("+++ b/src/lib.rs\n")
+        );
+        println!("✅ Unified patch test passed!");
+    }
+}
@@ -0,0 +1,405 @@
+// 🛡️ Path Policy - Keeping generated changes away from CI configs and secrets! 🛡️
+// Every file change the LLM proposes is checked against a per-project glob
+// allow/deny list before anything is committed. Disallowed changes are dropped
+// and listed in the feedback's `metadata.dropped_paths`; when nothing is left
+// the feedback fails with the reason.
+// Created with love by Aye & Hue! ✨
+//
+// Project config keys (`paths`, both optional, arrays of globs):
+//   "allowed": replaces DEFAULT_ALLOWED_PATHS
+//   "denied":  added to ALWAYS_DENIED_PATHS (which can't be lifted)
+// Globs match the whole repository-relative path: `*` and `?` stay within one
+// path segment, `**` spans any number of segments.
+
+use anyhow::{Context, Result};
+use serde::Serialize;
+use serde_json::Value;
+use sqlx::PgPool;
+use tracing::warn;
+
+use super::CodeImprovement;
+use crate::database::models::{Feedback, FeedbackStatus};
+use crate::database::project_config::{PathSettings, ProjectConfig};
+
+/// ✅ What generated changes may touch when the project doesn't say otherwise
+pub const DEFAULT_ALLOWED_PATHS: &[&str] = &[
+    "src/**",
+    "lib/**",
+    "tests/**",
+    "docs/**",
+    "examples/**",
+    "*.md",
+];
+
+/// 🚫 Never touched, whatever the project allows
+pub const ALWAYS_DENIED_PATHS: &[&str] = &[
+    ".github/**",
+    ".gitlab-ci.yml",
+    ".circleci/**",
+    ".travis.yml",
+    "azure-pipelines.yml",
+    "Jenkinsfile",
+    "**/.env",
+    "**/.env.*",
+    "**/*.pem",
+    "**/*.key",
+    "**/id_rsa*",
+    "**/secrets/**",
+];
+
+/// 🗂️ Globs deciding which paths generated changes may modify
+#[derive(Debug, Clone, PartialEq)]
+pub struct PathPolicy {
+    allowed: Vec<String>,
+    denied: Vec<String>,
+}
+
+impl Default for PathPolicy {
+    fn default() -> Self {
+        Self {
+            allowed: DEFAULT_ALLOWED_PATHS
+                .iter()
+                .map(|p| p.to_string())
+                .collect(),
+            denied: ALWAYS_DENIED_PATHS.iter().map(|p| p.to_string()).collect(),
+        }
+    }
+}
+
+/// 🗑️ A change that was dropped, and why
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct DroppedPath {
+    pub file_path: String,
+    pub reason: String,
+}
+
+/// 📋 Changes split into the ones we keep and the ones we drop
+#[derive(Debug, Clone)]
+pub struct PathFilterOutcome {
+    pub allowed: Vec<CodeImprovement>,
+    pub dropped: Vec<DroppedPath>,
+}
+
+impl PathPolicy {
+    /// ⚙️ Build the policy from a project's path settings
+    pub fn from_settings(settings: &PathSettings) -> Self {
+        let mut policy = Self::default();
+        if let Some(allowed) = &settings.allowed {
+            policy.allowed = allowed.clone();
+        }
+        if let Some(denied) = &settings.denied {
+            policy.denied.extend(denied.iter().cloned());
+        }
+        policy
+    }
+
+    /// ⚙️ Build the policy from a project's stored `config` JSON (any config version)
+    pub fn from_project_config(config: Option<&Value>) -> Result<Self> {
+        match config {
+            Some(config) => Ok(Self::from_settings(
+                &ProjectConfig::from_value(config.clone())?.paths,
+            )),
+            None => Ok(Self::default()),
+        }
+    }
+
+    /// 🔍 Load the policy of the active project for `repository` (defaults without one).
+    /// A config newer than this build fails rather than falling back to the defaults.
+    pub async fn for_repository(pool: &PgPool, repository: &str) -> Result<Self> {
+        let config = ProjectConfig::for_repository(pool, repository)
+            .await
+            .with_context(|| format!("Invalid path policy for {}", repository))?;
+        Ok(config
+            .map(|config| Self::from_settings(&config.paths))
+            .unwrap_or_default())
+    }
+
+    /// ⚖️ Why `path` may not be modified, or None when it may
+    pub fn rejection(&self, path: &str) -> Option<String> {
+        let segments: Vec<&str> = path.split('/').collect();
+        if path.contains('\\')
+            || segments
+                .iter()
+                .any(|segment| segment.is_empty() || *segment == "." || *segment == "..")
+        {
+            return Some("not a plain repository-relative path".to_string());
+        }
+        if let Some(pattern) = self
+            .denied
+            .iter()
+            .find(|pattern| glob_matches(pattern, &segments))
+        {
+            return Some(format!("matches denied pattern `{}`", pattern));
+        }
+        if !self
+            .allowed
+            .iter()
+            .any(|pattern| glob_matches(pattern, &segments))
+        {
+            return Some("not covered by any allowed pattern".to_string());
+        }
+        None
+    }
+
+    /// ✂️ Split changes into allowed and dropped
+    pub fn filter(&self, improvements: Vec<CodeImprovement>) -> PathFilterOutcome {
+        let mut outcome = PathFilterOutcome {
+            allowed: Vec::new(),
+            dropped: Vec::new(),
+        };
+        for improvement in improvements {
+            match self.rejection(&improvement.file_path) {
+                Some(reason) => outcome.dropped.push(DroppedPath {
+                    file_path: improvement.file_path,
+                    reason,
+                }),
+                None => outcome.allowed.push(improvement),
+            }
+        }
+        outcome
+    }
+}
+
+/// 🛡️ Apply the repository's path policy to generated changes before committing.
+/// Dropped paths land in `metadata.dropped_paths`; if every change was dropped the
+/// feedback is marked failed and an error explains why.
+pub async fn enforce_path_policy(
+    pool: &PgPool,
+    feedback: &mut Feedback,
+    improvements: Vec<CodeImprovement>,
+) -> Result<Vec<CodeImprovement>> {
+    let policy = PathPolicy::for_repository(pool, &feedback.repository).await?;
+    let total = improvements.len();
+    let outcome = policy.filter(improvements);
+    if outcome.dropped.is_empty() {
+        return Ok(outcome.allowed);
+    }
+
+    warn!(
+        "🛡️ Dropped {} of {} generated changes for feedback {}",
+        outcome.dropped.len(),
+        total,
+        feedback.id
+    );
+    feedback.metadata = sqlx::query_scalar(
+        r#"
+        UPDATE feedback
+        SET metadata = COALESCE(metadata, '{}'::jsonb) || jsonb_build_object('dropped_paths', $2::jsonb),
+            updated_at = NOW()
+        WHERE id = $1
+        RETURNING metadata
+        "#,
+    )
+    .bind(feedback.id)
+    .bind(serde_json::to_value(&outcome.dropped)?)
+    .fetch_one(pool)
+    .await
+    .context("Failed to record dropped paths")?;
+
+    if outcome.allowed.is_empty() {
+        let paths: Vec<&str> = outcome
+            .dropped
+            .iter()
+            .map(|dropped| dropped.file_path.as_str())
+            .collect();
+        let reason = format!(
+            "All {} generated changes touch paths this project doesn't allow: {}",
+            total,
+            paths.join(", ")
+        );
+        feedback
+            .update_status(pool, FeedbackStatus::Failed, Some(reason.clone()))
+            .await?;
+        anyhow::bail!(reason);
+    }
+    Ok(outcome.allowed)
+}
+
+/// 🌟 Does a glob match the (already split) path?
+fn glob_matches(pattern: &str, path: &[&str]) -> bool {
+    let pattern: Vec<&str> = pattern.split('/').collect();
+    segments_match(&pattern, path)
+}
+
+fn segments_match(pattern: &[&str], path: &[&str]) -> bool {
+    match pattern.split_first() {
+        None => path.is_empty(),
+        Some((&"**", rest)) => {
+            segments_match(rest, path) || (!path.is_empty() && segments_match(pattern, &path[1..]))
+        }
+        Some((first, rest)) => {
+            !path.is_empty() && segment_matches(first, path[0]) && segments_match(rest, &path[1..])
+        }
+    }
+}
+
+/// ✳️ `*` and `?` wildcards within a single path segment
+fn segment_matches(pattern: &str, segment: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let segment: Vec<char> = segment.chars().collect();
+    let (mut p, mut s) = (0, 0);
+    let mut backtrack: Option<(usize, usize)> = None;
+    while s < segment.len() {
+        match pattern.get(p) {
+            Some('*') => {
+                backtrack = Some((p, s));
+                p += 1;
+            }
+            Some(&c) if c == '?' || c == segment[s] => {
+                p += 1;
+                s += 1;
+            }
+            _ => match backtrack {
+                Some((star, matched)) => {
+                    p = star + 1;
+                    s = matched + 1;
+                    backtrack = Some((star, matched + 1));
+                }
+                None => return false,
+            },
+        }
+    }
+    pattern[p..].iter().all(|&c| c == '*')
+}
+
+// 🧪 Tests - Making sure the pipeline stays in its lane!
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::github::ChangeType;
+
+    fn change(file_path: &str) -> CodeImprovement {
+        CodeImprovement {
+            file_path: file_path.to_string(),
+            description: "Generated change".to_string(),
+            change_type: ChangeType::Modify,
+            original_content: None,
+            new_content: "// new".to_string(),
+            line_number: None,
+        }
+    }
+
+    #[test]
+    fn test_glob_matching() {
+        let matches =
+            |pattern: &str, path: &str| glob_matches(pattern, &path.split('/').collect::<Vec<_>>());
+        assert!(matches("src/**", "src/main.rs"));
+        assert!(matches("src/**", "src/api/deep/mod.rs"));
+        assert!(matches("*.md", "README.md"));
+        assert!(!matches("*.md", "docs/guide.md"));
+        assert!(matches("**/*.pem", "certs/prod/server.pem"));
+        assert!(matches("**/.env.*", ".env.production"));
+        assert!(matches("**/id_rsa*", "keys/id_rsa.pub"));
+        assert!(matches("src/?.rs", "src/a.rs"));
+        assert!(!matches("src/?.rs", "src/ab.rs"));
+        assert!(!matches("src/*.rs", "src/api/mod.rs"));
+        println!("✅ Glob matching test passed!");
+    }
+
+    #[test]
+    fn test_default_policy_is_conservative() {
+        let policy = PathPolicy::default();
+        assert_eq!(policy.rejection("src/lib.rs"), None);
+        assert_eq!(policy.rejection("README.md"), None);
+        assert_eq!(
+            policy.rejection(".github/workflows/ci.yml").as_deref(),
+            Some("matches denied pattern `.github/**`")
+        );
+        assert_eq!(
+            policy.rejection("Cargo.toml").as_deref(),
+            Some("not covered by any allowed pattern")
+        );
+        assert!(policy.rejection("src/config/.env").is_some());
+        assert!(policy.rejection("src/../.github/ci.yml").is_some());
+        assert!(policy.rejection("/etc/passwd").is_some());
+        println!("✅ Default path policy test passed!");
+    }
+
+    #[test]
+    fn test_project_config_overrides() {
+        let config = serde_json::json!({
+            "allowed_paths": ["**"],
+            "denied_paths": ["migrations/**"],
+        });
+        let policy = PathPolicy::from_project_config(Some(&config)).unwrap();
+        assert_eq!(policy.rejection("Cargo.toml"), None);
+        assert!(policy.rejection("migrations/001.sql").is_some());
+        // 🔒 The built-in denials survive an allow-everything list
+        assert!(policy.rejection(".github/CODEOWNERS").is_some());
+
+        let outcome = policy.filter(vec![change("src/main.rs"), change(".travis.yml")]);
+        assert_eq!(outcome.allowed.len(), 1);
+        assert_eq!(outcome.dropped[0].file_path, ".travis.yml");
+
+        let broken = serde_json::json!({ "allowed_paths": "src/**" });
+        let error = PathPolicy::from_project_config(Some(&broken)).unwrap_err();
+        assert!(error
+            .to_string()
+            .contains("`allowed_paths` must be an array"));
+        println!("✅ Project path policy test passed!");
+    }
+
+    #[tokio::test]
+    async fn test_enforce_records_dropped_paths_and_fails_when_nothing_is_left() {
+        let Some(app) = crate::test_support::spawn_test_app().await else {
+            return;
+        };
+        let pool = &app.db_pool;
+        let mut partly = Feedback::create(
+            pool,
+            None,
+            "acme/widgets".to_string(),
+            "Fix the thing".to_string(),
+            None,
+            0,
+            None,
+            "unknown",
+        )
+        .await
+        .unwrap();
+
+        let kept = enforce_path_policy(
+            pool,
+            &mut partly,
+            vec![change("src/main.rs"), change(".github/workflows/ci.yml")],
+        )
+        .await
+        .unwrap();
+        assert_eq!(kept.len(), 1);
+        assert!(partly.metadata.as_ref().unwrap()["dropped_paths"].is_array());
+        let metadata = Feedback::find_by_id(pool, partly.id)
+            .await
+            .unwrap()
+            .unwrap()
+            .metadata
+            .unwrap();
+        assert_eq!(
+            metadata["dropped_paths"][0]["file_path"],
+            ".github/workflows/ci.yml"
+        );
+
+        let mut blocked = Feedback::create(
+            pool,
+            None,
+            "acme/widgets".to_string(),
+            "Tweak CI".to_string(),
+            None,
+            0,
+            None,
+            "unknown",
+        )
+        .await
+        .unwrap();
+        let error = enforce_path_policy(pool, &mut blocked, vec![change(".github/ci.yml")])
+            .await
+            .unwrap_err();
+        assert!(error.to_string().contains("doesn't allow: .github/ci.yml"));
+        assert_eq!(blocked.status, FeedbackStatus::Failed);
+        assert!(blocked
+            .error_message
+            .as_deref()
+            .unwrap()
+            .contains(".github/ci.yml"));
+        println!("✅ Path policy enforcement test passed!");
+    }
+}
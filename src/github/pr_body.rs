@@ -0,0 +1,427 @@
+// 📝 Pull Request Bodies - Every generated PR says where it came from! 📝
+// The body of a pipeline PR is rendered from a template with `{placeholder}`s, filled in
+// when the PR is opened: which feedback it answers (id and status page link), who sent
+// it (unless anonymous), its category and examples, the applied changes and any notes
+// about the base branch. GITHUB_PR_BODY_TEMPLATE replaces DEFAULT_PR_BODY_TEMPLATE for
+// every project, and a project's `pull_requests.body_template` replaces both. Templates
+// are checked when they're configured, so a typo fails loudly instead of shipping
+// `{submiter}` to GitHub. `{{` and `}}` stand for literal braces.
+// The live placeholders (votes and duplicate reports) change after the PR is opened:
+// the body is kept with them left in (`freeze_pr_body`), and the nightly refresh fills
+// them in again (`finish_pr_body`) and edits the PR when the result differs.
+// Created with love by Aye & Hue! ✨
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+
+use super::{CodeImprovement, CommittedChanges, FeedbackProcessingRequest};
+use crate::database::feedback_votes::FeedbackReception;
+
+/// 📝 The body used unless a template is configured
+pub const DEFAULT_PR_BODY_TEMPLATE: &str = "## 🤖 AI-Generated Improvements
+
+This pull request contains improvements generated by Feedbacker based on [feedback {feedback_id}]({feedback_url}).
+
+### 📝 Original Feedback
+{feedback}
+
+**Submitted by:** {submitter} · **Category:** {category} · **Votes:** {votes}{also_reported_by}
+
+{examples}### 🔧 Applied Changes
+{changes}
+
+{notes}---
+🚢 Generated with love by [Feedbacker](https://github.com/aye-is/feedbacker) - Aye & Hue
+🤖 Powered by AI for intelligent code improvements
+";
+
+/// 🏷️ Everything a template may use
+pub const PLACEHOLDERS: &[&str] = &[
+    "feedback_id",
+    "feedback_url",
+    "repository",
+    "feedback",
+    "submitter",
+    "category",
+    "examples",
+    "changes",
+    "notes",
+    "votes",
+    "also_reported_by",
+];
+
+/// 🔄 Placeholders filled in again whenever the PR body is refreshed
+pub const LIVE_PLACEHOLDERS: &[&str] = &["votes", "also_reported_by"];
+
+/// 🧪 One example attached to the feedback (`metadata.examples`)
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FeedbackExample {
+    pub description: String,
+    #[serde(default)]
+    pub code: Option<String>,
+    #[serde(default)]
+    pub expected_output: Option<String>,
+}
+
+impl FeedbackExample {
+    /// 🧪 The examples in feedback metadata; a plain string is an example with only a
+    /// description, and anything unreadable is skipped
+    pub fn from_metadata(metadata: Option<&Value>) -> Vec<Self> {
+        let Some(examples) = metadata
+            .and_then(|metadata| metadata.get("examples"))
+            .and_then(Value::as_array)
+        else {
+            return Vec::new();
+        };
+        examples
+            .iter()
+            .filter_map(|example| match example {
+                Value::String(description) => Some(Self {
+                    description: description.clone(),
+                    ..Self::default()
+                }),
+                other => serde_json::from_value(other.clone()).ok(),
+            })
+            .collect()
+    }
+}
+
+/// 🧭 Where the feedback behind a PR came from, for its body
+#[derive(Debug, Clone, Default)]
+pub struct PullRequestContext {
+    /// 🔗 The feedback's public status page
+    pub feedback_url: String,
+    /// 👤 Who sent it (None = anonymous)
+    pub submitter: Option<String>,
+    pub category: Option<String>,
+    pub examples: Vec<FeedbackExample>,
+    /// 📣 Votes and duplicate reports when the PR is opened
+    pub reception: FeedbackReception,
+    /// 📝 The template to render (None = DEFAULT_PR_BODY_TEMPLATE)
+    pub body_template: Option<String>,
+}
+
+/// 🔍 Check a template: Err names an unknown placeholder or an unmatched brace
+pub fn validate_template(template: &str) -> Result<(), String> {
+    let mut rest = template;
+    while let Some(at) = rest.find(['{', '}']) {
+        let tail = &rest[at..];
+        if tail.starts_with("{{") || tail.starts_with("}}") {
+            rest = &tail[2..];
+            continue;
+        }
+        if tail.starts_with('}') {
+            return Err("unmatched '}' (write '}}' for a literal brace)".to_string());
+        }
+        let Some(end) = tail.find('}') else {
+            return Err("unclosed '{' (write '{{' for a literal brace)".to_string());
+        };
+        let name = &tail[1..end];
+        if !PLACEHOLDERS.contains(&name) {
+            return Err(format!(
+                "unknown placeholder {{{}}} (known: {})",
+                name,
+                PLACEHOLDERS.join(", ")
+            ));
+        }
+        rest = &tail[end + 1..];
+    }
+    Ok(())
+}
+
+/// 🖨️ Fill in `template`; placeholders it doesn't know are left as they are
+pub fn render_template(template: &str, value: impl Fn(&str) -> Option<String>) -> String {
+    fill(template, value, false)
+}
+
+/// 🖨️ `render_template`, optionally keeping `{{` and `}}` for a second rendering
+fn fill(template: &str, value: impl Fn(&str) -> Option<String>, keep_escapes: bool) -> String {
+    let mut out = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(at) = rest.find(['{', '}']) {
+        out.push_str(&rest[..at]);
+        let tail = &rest[at..];
+        if tail.starts_with("{{") || tail.starts_with("}}") {
+            out.push_str(&tail[..if keep_escapes { 2 } else { 1 }]);
+            rest = &tail[2..];
+            continue;
+        }
+        let filled = tail
+            .strip_prefix('{')
+            .and_then(|inner| inner.find('}').map(|end| (&inner[..end], end + 2)))
+            .and_then(|(name, used)| value(name).map(|value| (value, used)));
+        match filled {
+            Some((value, used)) => {
+                out.push_str(&value);
+                rest = &tail[used..];
+            }
+            None => {
+                out.push_str(&tail[..1]);
+                rest = &tail[1..];
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+/// 🧪 The examples as a section of their own (empty when there are none)
+fn examples_section(examples: &[FeedbackExample]) -> String {
+    if examples.is_empty() {
+        return String::new();
+    }
+    let mut section = "### 🧪 Examples\n".to_string();
+    for (number, example) in examples.iter().enumerate() {
+        section.push_str(&format!("{}. {}\n", number + 1, example.description));
+        if let Some(code) = &example.code {
+            section.push_str(&format!("```\n{}\n```\n", code.trim_end()));
+        }
+        if let Some(expected) = &example.expected_output {
+            section.push_str(&format!("Expected:\n```\n{}\n```\n", expected.trim_end()));
+        }
+    }
+    section.push('\n');
+    section
+}
+
+/// ✍️ The committed changes as (change, commit sha) pairs
+pub fn applied_changes(committed: &CommittedChanges) -> Vec<(CodeImprovement, String)> {
+    committed
+        .applied
+        .iter()
+        .map(|change| (change.improvement.clone(), change.commit_sha.clone()))
+        .collect()
+}
+
+/// 📝 The PR body for `request`: its template filled in with the feedback, the applied
+/// changes (each with the sha of the commit that applied it) and `notes` (e.g. the base
+/// branch's protection)
+pub fn render_pr_body(
+    request: &FeedbackProcessingRequest,
+    applied: &[(CodeImprovement, String)],
+    notes: &str,
+) -> String {
+    finish_pr_body(
+        &freeze_pr_body(request, applied, notes),
+        &request.pull_request.reception,
+    )
+}
+
+/// 🧊 `render_pr_body` with the live placeholders left in: a template of its own, for
+/// `finish_pr_body` now and on every refresh
+pub fn freeze_pr_body(
+    request: &FeedbackProcessingRequest,
+    applied: &[(CodeImprovement, String)],
+    notes: &str,
+) -> String {
+    let context = &request.pull_request;
+    let template = context
+        .body_template
+        .as_deref()
+        .unwrap_or(DEFAULT_PR_BODY_TEMPLATE);
+    let value = |name: &str| {
+        Some(match name {
+            "feedback_id" => request.feedback_id.to_string(),
+            "feedback_url" => context.feedback_url.clone(),
+            "repository" => request.repository.clone(),
+            "feedback" => request
+                .feedback_content
+                .lines()
+                .map(|line| format!("> {}", line).trim_end().to_string())
+                .collect::<Vec<_>>()
+                .join("\n"),
+            "submitter" => context
+                .submitter
+                .clone()
+                .unwrap_or_else(|| "anonymous".to_string()),
+            "category" => context
+                .category
+                .clone()
+                .unwrap_or_else(|| "uncategorized".to_string()),
+            "examples" => examples_section(&context.examples),
+            "changes" => applied
+                .iter()
+                .map(|(improvement, sha)| {
+                    format!(
+                        "- **{}**: {} ({}, {})",
+                        improvement.file_path,
+                        improvement.description,
+                        format!("{:?}", improvement.change_type).to_lowercase(),
+                        sha.get(..7).unwrap_or(sha)
+                    )
+                })
+                .collect::<Vec<_>>()
+                .join("\n"),
+            "notes" if notes.trim().is_empty() => String::new(),
+            "notes" => format!("{}\n\n", notes.trim_end()),
+            _ => return None,
+        })
+    };
+    // 🧱 Braces in the values must survive the second rendering
+    fill(
+        template,
+        |name| value(name).map(|value| value.replace('{', "{{").replace('}', "}}")),
+        true,
+    )
+}
+
+/// 🔄 A frozen body with the live placeholders filled in
+pub fn finish_pr_body(frozen: &str, reception: &FeedbackReception) -> String {
+    render_template(frozen, |name| {
+        Some(match name {
+            "votes" => reception.votes.to_string(),
+            "also_reported_by" => match reception.also_reported_by {
+                0 => String::new(),
+                1 => "\n\n👯 Also reported by 1 user".to_string(),
+                others => format!("\n\n👯 Also reported by {} users", others),
+            },
+            _ => return None,
+        })
+    })
+}
+
+/// #️⃣ Fingerprint of a rendered body, to tell whether GitHub's copy is current
+pub fn body_hash(body: &str) -> String {
+    hex::encode(Sha256::digest(body.as_bytes()))
+}
+
+// 🧪 Tests - Paperwork, but the good kind!
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::github::ChangeType;
+    use uuid::Uuid;
+
+    fn request(context: PullRequestContext) -> FeedbackProcessingRequest {
+        FeedbackProcessingRequest {
+            feedback_id: Uuid::nil(),
+            repository: "8b-is/smart-tree".to_string(),
+            feedback_content: "Colours please\nThe output is grey".to_string(),
+            improvements: Vec::new(),
+            commit_message: "Add colours".to_string(),
+            branch_name: "feedbacker/colours".to_string(),
+            pull_request: context,
+        }
+    }
+
+    fn applied() -> Vec<(CodeImprovement, String)> {
+        vec![(
+            CodeImprovement {
+                file_path: "src/main.rs".to_string(),
+                description: "Colour the output".to_string(),
+                change_type: ChangeType::Modify,
+                original_content: None,
+                new_content: String::new(),
+                line_number: None,
+            },
+            "abc1234def".to_string(),
+        )]
+    }
+
+    #[test]
+    fn test_default_body_carries_the_feedback_context() {
+        let body = render_pr_body(
+            &request(PullRequestContext {
+                feedback_url: "https://f.8b.is/feedback/1".to_string(),
+                submitter: Some("@hue".to_string()),
+                category: Some("feature".to_string()),
+                examples: FeedbackExample::from_metadata(Some(&serde_json::json!({
+                    "examples": [
+                        "Run st --mode ls",
+                        { "description": "Classic mode", "code": "st --mode classic", "expected_output": "coloured tree" },
+                        42
+                    ]
+                }))),
+                reception: FeedbackReception {
+                    votes: 3,
+                    also_reported_by: 2,
+                },
+                body_template: None,
+            }),
+            &applied(),
+            "### 🔒 Branch protection\nNeeds a review\n",
+        );
+        assert!(body.contains(&format!(
+            "[feedback {}](https://f.8b.is/feedback/1)",
+            Uuid::nil()
+        )));
+        assert!(body.contains("> Colours please\n> The output is grey\n"));
+        assert!(body.contains(
+            "**Submitted by:** @hue · **Category:** feature · **Votes:** 3\n\n👯 Also reported by 2 users\n\n"
+        ));
+        assert!(body.contains("1. Run st --mode ls\n2. Classic mode\n```\nst --mode classic\n```\nExpected:\n```\ncoloured tree\n```\n"));
+        assert!(body.contains("- **src/main.rs**: Colour the output (modify, abc1234)\n\n### 🔒 Branch protection\nNeeds a review\n\n---\n"));
+
+        // 🙈 Anonymous, uncategorized and without examples
+        let body = render_pr_body(&request(PullRequestContext::default()), &applied(), "");
+        assert!(body.contains(
+            "**Submitted by:** anonymous · **Category:** uncategorized · **Votes:** 0\n\n"
+        ));
+        assert!(!body.contains("Examples"));
+        assert!(body.contains("(modify, abc1234)\n\n---\n"));
+        println!("✅ Default PR body test passed!");
+    }
+
+    #[test]
+    fn test_custom_templates_are_checked_and_rendered() {
+        let template = "Fixes {feedback_url} for {{team}}\n{changes}";
+        assert_eq!(validate_template(template), Ok(()));
+        assert_eq!(validate_template(DEFAULT_PR_BODY_TEMPLATE), Ok(()));
+        assert!(validate_template("From {submiter}")
+            .unwrap_err()
+            .starts_with("unknown placeholder {submiter}"));
+        assert!(validate_template("Oops {feedback").is_err());
+        assert!(validate_template("Oops }").is_err());
+
+        let body = render_pr_body(
+            &request(PullRequestContext {
+                feedback_url: "https://f.8b.is/feedback/1".to_string(),
+                body_template: Some(template.to_string()),
+                ..PullRequestContext::default()
+            }),
+            &applied(),
+            "",
+        );
+        assert_eq!(
+            body,
+            "Fixes https://f.8b.is/feedback/1 for {team}\n- **src/main.rs**: Colour the output (modify, abc1234)"
+        );
+        println!("✅ Custom PR body template test passed!");
+    }
+
+    #[test]
+    fn test_frozen_bodies_only_change_with_the_reception() {
+        let mut request = request(PullRequestContext {
+            body_template: Some("{{{feedback}}} has {votes} votes{also_reported_by}".to_string()),
+            ..PullRequestContext::default()
+        });
+        request.feedback_content = "Print {braces} and }} too".to_string();
+        let frozen = freeze_pr_body(&request, &applied(), "");
+        assert_eq!(
+            frozen,
+            "{{> Print {{braces}} and }}}} too}} has {votes} votes{also_reported_by}"
+        );
+
+        let quiet = finish_pr_body(&frozen, &FeedbackReception::default());
+        assert_eq!(quiet, "{> Print {braces} and }} too} has 0 votes");
+        assert_eq!(quiet, render_pr_body(&request, &applied(), ""));
+        let busy = finish_pr_body(
+            &frozen,
+            &FeedbackReception {
+                votes: 5,
+                also_reported_by: 1,
+            },
+        );
+        assert_eq!(
+            busy,
+            "{> Print {braces} and }} too} has 5 votes\n\n👯 Also reported by 1 user"
+        );
+        assert_eq!(
+            body_hash(&quiet),
+            body_hash(&finish_pr_body(&frozen, &FeedbackReception::default()))
+        );
+        assert_ne!(body_hash(&quiet), body_hash(&busy));
+        println!("✅ Frozen PR body test passed!");
+    }
+}
@@ -0,0 +1,225 @@
+// 🔒 Branch Protection - Knowing the base branch's rules before opening a PR! 🔒
+// The commit stage reads the protection of the branch a generated PR targets. Required
+// reviews and status checks are listed in the PR body so nobody wonders why it sits
+// there, and a project with `pull_requests.auto_merge` only gets its PR merged when
+// nothing gates the merge. Protection we can't read (the endpoint needs admin rights)
+// counts as unknown: the PR is opened as usual, but never merged by us.
+// Created with love by Aye & Hue! ✨
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tracing::warn;
+
+use super::ops::GitHubOps;
+use crate::database::project_config::PullRequestSettings;
+
+/// 🔒 The rules on a protected branch that matter for merging a PR
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BranchProtection {
+    /// 👀 Approving reviews needed (0 when reviews aren't required)
+    pub required_approving_reviews: u32,
+    /// 👥 Whether a code owner has to be among the reviewers
+    pub require_code_owner_reviews: bool,
+    /// ✅ Status check contexts that must pass
+    pub required_status_checks: Vec<String>,
+    /// 🔄 Whether the branch must be up to date with the base before merging
+    pub strict_status_checks: bool,
+}
+
+impl BranchProtection {
+    /// 📖 From the GitHub branch protection API response
+    pub fn from_api(protection: &Value) -> Self {
+        let reviews = protection.get("required_pull_request_reviews");
+        let checks = protection.get("required_status_checks");
+        let mut contexts: Vec<String> = checks
+            .and_then(|checks| checks.get("contexts"))
+            .and_then(Value::as_array)
+            .into_iter()
+            .flatten()
+            .filter_map(Value::as_str)
+            .map(str::to_string)
+            .collect();
+        // 🆕 Newer rules list checks with their app; the names can repeat `contexts`
+        for check in checks
+            .and_then(|checks| checks.get("checks"))
+            .and_then(Value::as_array)
+            .into_iter()
+            .flatten()
+        {
+            if let Some(context) = check.get("context").and_then(Value::as_str) {
+                if !contexts.iter().any(|known| known == context) {
+                    contexts.push(context.to_string());
+                }
+            }
+        }
+        Self {
+            required_approving_reviews: reviews
+                .map(|reviews| {
+                    reviews
+                        .get("required_approving_review_count")
+                        .and_then(Value::as_u64)
+                        .unwrap_or(1) as u32
+                })
+                .unwrap_or_default(),
+            require_code_owner_reviews: reviews
+                .and_then(|reviews| reviews.get("require_code_owner_reviews"))
+                .and_then(Value::as_bool)
+                .unwrap_or_default(),
+            required_status_checks: contexts,
+            strict_status_checks: checks
+                .and_then(|checks| checks.get("strict"))
+                .and_then(Value::as_bool)
+                .unwrap_or_default(),
+        }
+    }
+
+    /// 🚧 Does anything have to happen before the PR can be merged?
+    pub fn gates_merge(&self) -> bool {
+        self.required_approving_reviews > 0
+            || self.require_code_owner_reviews
+            || !self.required_status_checks.is_empty()
+    }
+}
+
+/// 🔍 What we know about the base branch when opening the PR
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BaseProtection {
+    /// 🔓 No protection rules
+    Unprotected,
+    /// 🔒 Protected, with these rules
+    Protected(BranchProtection),
+    /// ❓ Not checked, or the check failed
+    Unknown,
+}
+
+impl BaseProtection {
+    /// 🔀 May we merge the PR ourselves right after opening it?
+    pub fn allows_auto_merge(&self) -> bool {
+        match self {
+            BaseProtection::Unprotected => true,
+            BaseProtection::Protected(protection) => !protection.gates_merge(),
+            BaseProtection::Unknown => false,
+        }
+    }
+
+    /// 📝 The PR body section explaining what the merge waits for (empty when nothing does)
+    pub fn pr_section(&self, base_branch: &str) -> String {
+        let BaseProtection::Protected(protection) = self else {
+            return String::new();
+        };
+        if !protection.gates_merge() {
+            return String::new();
+        }
+        let mut section = format!(
+            "### 🔒 Branch protection\n`{}` is protected, so this pull request needs the following before it can be merged:\n",
+            base_branch
+        );
+        if protection.required_approving_reviews > 0 || protection.require_code_owner_reviews {
+            section.push_str(&format!(
+                "- {} approving review{}{}\n",
+                protection.required_approving_reviews.max(1),
+                if protection.required_approving_reviews > 1 {
+                    "s"
+                } else {
+                    ""
+                },
+                if protection.require_code_owner_reviews {
+                    ", including a code owner"
+                } else {
+                    ""
+                }
+            ));
+        }
+        if !protection.required_status_checks.is_empty() {
+            let checks: Vec<String> = protection
+                .required_status_checks
+                .iter()
+                .map(|check| format!("`{}`", check))
+                .collect();
+            section.push_str(&format!("- Passing checks: {}\n", checks.join(", ")));
+        }
+        if protection.strict_status_checks {
+            section.push_str(&format!("- Being up to date with `{}`\n", base_branch));
+        }
+        section.push('\n');
+        section
+    }
+}
+
+/// 🔍 Read the base branch's protection when the project wants it checked. A read
+/// that fails (most often for lack of admin rights) is logged and counts as unknown.
+pub async fn check_base(
+    github: &dyn GitHubOps,
+    repository: &str,
+    branch: &str,
+    settings: &PullRequestSettings,
+) -> BaseProtection {
+    let Some((owner, repo)) = repository.split_once('/') else {
+        return BaseProtection::Unknown;
+    };
+    if !settings.check_protection {
+        return BaseProtection::Unknown;
+    }
+    match github.branch_protection(owner, repo, branch).await {
+        Ok(Some(protection)) => BaseProtection::Protected(protection),
+        Ok(None) => BaseProtection::Unprotected,
+        Err(e) => {
+            warn!(
+                "⚠️ Couldn't read the protection of {} in {}: {:#}",
+                branch, repository, e
+            );
+            BaseProtection::Unknown
+        }
+    }
+}
+
+// 🧪 Tests - Reading the rules of the road!
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_protection_is_read_and_explained() {
+        let protection = BranchProtection::from_api(&json!({
+            "required_status_checks": {
+                "strict": true,
+                "contexts": ["ci/test"],
+                "checks": [{"context": "ci/test", "app_id": 1}, {"context": "lint", "app_id": null}]
+            },
+            "required_pull_request_reviews": {
+                "required_approving_review_count": 2,
+                "require_code_owner_reviews": true
+            },
+            "enforce_admins": {"enabled": true}
+        }));
+        assert_eq!(
+            protection,
+            BranchProtection {
+                required_approving_reviews: 2,
+                require_code_owner_reviews: true,
+                required_status_checks: vec!["ci/test".to_string(), "lint".to_string()],
+                strict_status_checks: true,
+            }
+        );
+        let base = BaseProtection::Protected(protection);
+        assert!(!base.allows_auto_merge());
+        assert_eq!(
+            base.pr_section("main"),
+            "### 🔒 Branch protection\n`main` is protected, so this pull request needs the following before it can be merged:\n\
+             - 2 approving reviews, including a code owner\n\
+             - Passing checks: `ci/test`, `lint`\n\
+             - Being up to date with `main`\n\n"
+        );
+
+        // 🔓 Protection that doesn't gate merging (say, no force pushes) changes nothing
+        let loose = BaseProtection::Protected(BranchProtection::from_api(&json!({
+            "allow_force_pushes": {"enabled": false}
+        })));
+        assert!(loose.allows_auto_merge());
+        assert_eq!(loose.pr_section("main"), "");
+        assert!(BaseProtection::Unprotected.allows_auto_merge());
+        assert!(!BaseProtection::Unknown.allows_auto_merge());
+        println!("✅ Branch protection test passed!");
+    }
+}
@@ -0,0 +1,66 @@
+// 🏷️ Releases - Checking a version really shipped before we announce it! 🏷️
+// POST /mcp/version reads the release behind a tag before publishing the version,
+// so clients are never sent to a download that 404s. Only the fields we use are kept.
+// Created with love by Aye & Hue! ✨
+
+use serde::{Deserialize, Serialize};
+
+/// 🏷️ A GitHub release (from GET /repos/{owner}/{repo}/releases/tags/{tag})
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GitHubRelease {
+    pub tag_name: String,
+    /// 📝 Drafts aren't public yet - nobody can download them
+    #[serde(default)]
+    pub draft: bool,
+    #[serde(default)]
+    pub prerelease: bool,
+    pub html_url: String,
+    #[serde(default)]
+    pub assets: Vec<ReleaseAsset>,
+}
+
+/// 📦 One file attached to a release
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReleaseAsset {
+    pub name: String,
+    pub browser_download_url: String,
+    /// 📏 Size in bytes
+    pub size: u64,
+}
+
+/// 🏷️ The tag a version is released under ("5.2.0" -> "v5.2.0")
+pub fn release_tag(version: &str) -> String {
+    if version.starts_with('v') {
+        version.to_string()
+    } else {
+        format!("v{}", version)
+    }
+}
+
+// 🧪 Tests - Reading what GitHub says about a release!
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_release_parses_from_the_api_shape() {
+        let release: GitHubRelease = serde_json::from_value(serde_json::json!({
+            "tag_name": "v5.2.0",
+            "draft": false,
+            "prerelease": false,
+            "html_url": "https://github.com/8b-is/smart-tree/releases/tag/v5.2.0",
+            "author": { "login": "aye-is" },
+            "assets": [{
+                "name": "st-v5.2.0-x86_64-unknown-linux-gnu.tar.gz",
+                "browser_download_url": "https://github.com/8b-is/smart-tree/releases/download/v5.2.0/st.tar.gz",
+                "size": 4242,
+                "download_count": 7
+            }]
+        }))
+        .unwrap();
+        assert_eq!(release.assets[0].size, 4242);
+        assert_eq!(release_tag("5.2.0"), "v5.2.0");
+        assert_eq!(release_tag("v5.2.0"), "v5.2.0");
+        println!("✅ Release parsing test passed!");
+    }
+}
@@ -0,0 +1,257 @@
+// 🪝 Repository Webhooks - Connecting a project wires up its webhook too! 🪝
+// When a project is registered we create the issues/PR webhook on its repository
+// ourselves (GITHUB_WEBHOOK_SECRET signs it), as long as our GitHub App installation
+// covers the repository or our token has admin rights on it. The hook id goes on
+// the project row; a failure leaves the project in place with `webhook_error` set,
+// which the admin projects page shows next to a retry button. Removing a project
+// removes its hook again, unless another project for the same repository still uses it.
+// Created with love by Aye & Hue! ✨
+
+use anyhow::{Context, Result};
+use sqlx::PgPool;
+use tracing::{info, warn};
+use uuid::Uuid;
+
+use super::installations;
+use super::ops::GitHubOps;
+use crate::config::Config;
+
+/// 🎯 Where the registered hook delivers (the issue automation endpoint)
+pub const REPO_WEBHOOK_PATH: &str = "/api/webhook/issues";
+/// 📣 Events the registered hook subscribes to
+pub const REPO_WEBHOOK_EVENTS: &[&str] = &["issues", "issue_comment", "pull_request"];
+
+/// 🚦 What registering a project's webhook came to
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HookInstall {
+    /// ✅ Registered (or an existing hook was updated), with its id
+    Installed(i64),
+    /// ❌ Not installed, and why (also stored as the project's `webhook_error`)
+    Failed(String),
+    /// 💤 ENABLE_GITHUB_WEBHOOKS is off, nothing was attempted
+    Disabled,
+}
+
+/// 🪝 Register the webhook for a project and record the outcome on its row.
+/// Err only for database trouble; GitHub failures come back as `HookInstall::Failed`.
+pub async fn install_project_webhook(
+    github: &dyn GitHubOps,
+    pool: &PgPool,
+    config: &Config,
+    project_id: Uuid,
+) -> Result<HookInstall> {
+    if !config.features.enable_github_webhooks {
+        return Ok(HookInstall::Disabled);
+    }
+    let repository: String = sqlx::query_scalar("SELECT repository FROM projects WHERE id = $1")
+        .bind(project_id)
+        .fetch_one(pool)
+        .await
+        .context("Failed to load project for webhook registration")?;
+
+    let outcome = match register(github, pool, config, &repository).await {
+        Ok(hook_id) => {
+            info!("🪝 Webhook {} installed on {}", hook_id, repository);
+            HookInstall::Installed(hook_id)
+        }
+        Err(e) => {
+            warn!("❌ Webhook not installed on {}: {:#}", repository, e);
+            HookInstall::Failed(format!("{:#}", e))
+        }
+    };
+    let (hook_id, error) = match &outcome {
+        HookInstall::Installed(hook_id) => (Some(*hook_id), None),
+        HookInstall::Failed(error) => (None, Some(error.as_str())),
+        HookInstall::Disabled => unreachable!("returned above"),
+    };
+    sqlx::query(
+        "UPDATE projects SET webhook_id = COALESCE($2, webhook_id), webhook_error = $3, updated_at = NOW() WHERE id = $1",
+    )
+    .bind(project_id)
+    .bind(hook_id)
+    .bind(error)
+    .execute(pool)
+    .await
+    .context("Failed to record webhook registration")?;
+    Ok(outcome)
+}
+
+/// 🔑 Check we may manage the repository's hooks, then create (or update) ours
+async fn register(
+    github: &dyn GitHubOps,
+    pool: &PgPool,
+    config: &Config,
+    repository: &str,
+) -> Result<i64> {
+    let (owner, repo) = repository
+        .split_once('/')
+        .with_context(|| format!("Repository {} is not in owner/repo format", repository))?;
+    let covered = installations::installation_for_repository(pool, repository)
+        .await?
+        .is_some();
+    if !covered && !github.repository_access(owner, repo).await?.can_admin {
+        anyhow::bail!(
+            "Feedbacker has no admin access to {} - install the GitHub App on it, or add the webhook by hand",
+            repository
+        );
+    }
+    github
+        .create_repo_webhook(
+            owner,
+            repo,
+            &config.public_url(REPO_WEBHOOK_PATH),
+            config.github.webhook_secret.as_deref(),
+            REPO_WEBHOOK_EVENTS,
+        )
+        .await
+}
+
+/// 🗑️ Remove a project, and its webhook unless another project for the repository
+/// still relies on it. A hook that can't be removed is logged, not a reason to keep
+/// the project. Returns false when there was no such project.
+pub async fn remove_project(
+    github: &dyn GitHubOps,
+    pool: &PgPool,
+    project_id: Uuid,
+) -> Result<bool> {
+    let Some((repository, hook_id)): Option<(String, Option<i64>)> =
+        sqlx::query_as("SELECT repository, webhook_id FROM projects WHERE id = $1")
+            .bind(project_id)
+            .fetch_optional(pool)
+            .await
+            .context("Failed to load project")?
+    else {
+        return Ok(false);
+    };
+
+    if let Some(hook_id) = hook_id {
+        let shared: bool = sqlx::query_scalar(
+            "SELECT EXISTS (SELECT 1 FROM projects WHERE repository = $1 AND id <> $2 AND webhook_id IS NOT NULL)",
+        )
+        .bind(&repository)
+        .bind(project_id)
+        .fetch_one(pool)
+        .await
+        .context("Failed to check for other projects on the repository")?;
+        match repository.split_once('/') {
+            _ if shared => info!(
+                "🪝 Keeping webhook {} on {}, still in use",
+                hook_id, repository
+            ),
+            Some((owner, repo)) => {
+                if let Err(e) = github.delete_repo_webhook(owner, repo, hook_id).await {
+                    warn!(
+                        "⚠️ Failed to remove webhook {} from {}: {:#}",
+                        hook_id, repository, e
+                    );
+                }
+            }
+            None => {}
+        }
+    }
+
+    sqlx::query("DELETE FROM projects WHERE id = $1")
+        .bind(project_id)
+        .execute(pool)
+        .await
+        .context("Failed to remove project")?;
+    info!("🗑️ Project {} removed", repository);
+    Ok(true)
+}
+
+// 🧪 Tests - Hooks in, hooks out!
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{spawn_test_app, GitHubCall};
+
+    async fn add_project(pool: &PgPool, email: &str, repository: &str) -> Uuid {
+        let owner_id: Uuid = sqlx::query_scalar(
+            "INSERT INTO users (email, name, password_hash) VALUES ($1, 'Owner', 'x') RETURNING id",
+        )
+        .bind(email)
+        .fetch_one(pool)
+        .await
+        .unwrap();
+        sqlx::query_scalar(
+            "INSERT INTO projects (owner_id, repository) VALUES ($1, $2) RETURNING id",
+        )
+        .bind(owner_id)
+        .bind(repository)
+        .fetch_one(pool)
+        .await
+        .unwrap()
+    }
+
+    async fn webhook_columns(pool: &PgPool, project_id: Uuid) -> (Option<i64>, Option<String>) {
+        sqlx::query_as("SELECT webhook_id, webhook_error FROM projects WHERE id = $1")
+            .bind(project_id)
+            .fetch_one(pool)
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_project_webhooks_are_installed_flagged_and_removed() {
+        let Some(app) = spawn_test_app().await else {
+            return;
+        };
+        let github = &*app.app_state.github;
+        let (pool, config) = (&app.db_pool, &app.app_state.config);
+        let first = add_project(pool, "a@example.com", "8b-is/smart-tree").await;
+        let second = add_project(pool, "b@example.com", "8b-is/smart-tree").await;
+
+        // 🛑 GitHub trouble leaves the project in place, flagged
+        *app.github.fail_with.lock().unwrap() = Some("Not Found".to_string());
+        let HookInstall::Failed(error) = install_project_webhook(github, pool, config, first)
+            .await
+            .unwrap()
+        else {
+            panic!("expected the registration to fail");
+        };
+        assert!(error.contains("Not Found"));
+        assert_eq!(webhook_columns(pool, first).await, (None, Some(error)));
+
+        // 🔁 Retrying once GitHub cooperates installs it and clears the flag
+        *app.github.fail_with.lock().unwrap() = None;
+        let HookInstall::Installed(hook_id) = install_project_webhook(github, pool, config, first)
+            .await
+            .unwrap()
+        else {
+            panic!("expected the webhook to be installed");
+        };
+        assert_eq!(webhook_columns(pool, first).await, (Some(hook_id), None));
+        assert_eq!(
+            app.github.calls(),
+            vec![GitHubCall::CreateHook {
+                repo: "8b-is/smart-tree".to_string(),
+                url: config.public_url("/api/webhook/issues"),
+                signed: false,
+                events: vec![
+                    "issues".to_string(),
+                    "issue_comment".to_string(),
+                    "pull_request".to_string(),
+                ],
+            }]
+        );
+        install_project_webhook(github, pool, config, second)
+            .await
+            .unwrap();
+
+        // 🗑️ The hook stays while another project on the repository uses it...
+        assert!(remove_project(github, pool, first).await.unwrap());
+        assert_eq!(app.github.calls().len(), 2);
+        // ...and goes with the last one
+        let (last_hook, _) = webhook_columns(pool, second).await;
+        assert!(remove_project(github, pool, second).await.unwrap());
+        assert_eq!(
+            app.github.calls().last(),
+            Some(&GitHubCall::DeleteHook {
+                repo: "8b-is/smart-tree".to_string(),
+                hook_id: last_hook.unwrap(),
+            })
+        );
+        assert!(!remove_project(github, pool, second).await.unwrap());
+        println!("✅ Project webhook lifecycle test passed!");
+    }
+}
@@ -0,0 +1,107 @@
+// 🚦 Statuses - The pipeline's progress where contributors already look! 🚦
+// The commit stage reports on the head commit of the feedback branch, so the PR shows
+// "Feedbacker: generating changes" while we work and "changes proposed" once it's open.
+// GITHUB_STATUS_REPORTING picks a commit status (any token with repo:status), a check
+// run (GitHub App tokens only) or nothing. A check run is opened once per branch head
+// and updated in place after that. Reporting is best effort: a status that
+// can't be posted never holds up the pull request.
+// Created with love by Aye & Hue! ✨
+
+use serde::{Deserialize, Serialize};
+
+/// 🏷️ Context of our commit statuses and name of our check runs
+pub const STATUS_CONTEXT: &str = "Feedbacker";
+/// 📏 GitHub rejects status descriptions longer than this
+const MAX_DESCRIPTION_CHARS: usize = 140;
+
+/// 🚦 Where the pipeline stands on a commit
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CommitState {
+    Pending,
+    Success,
+    Failure,
+}
+
+impl CommitState {
+    /// 🏷️ `state` of a commit status
+    pub fn as_str(self) -> &'static str {
+        match self {
+            CommitState::Pending => "pending",
+            CommitState::Success => "success",
+            CommitState::Failure => "failure",
+        }
+    }
+
+    /// ✅ `status` and `conclusion` of the matching check run
+    pub fn check_run(self) -> (&'static str, Option<&'static str>) {
+        match self {
+            CommitState::Pending => ("in_progress", None),
+            CommitState::Success => ("completed", Some("success")),
+            CommitState::Failure => ("completed", Some("failure")),
+        }
+    }
+}
+
+/// 📣 One progress report for a commit
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CommitStatus {
+    pub state: CommitState,
+    pub description: String,
+    /// 🔗 Where "Details" leads (the pull request, once there is one)
+    pub target_url: Option<String>,
+}
+
+impl CommitStatus {
+    fn new(state: CommitState, description: &str, target_url: Option<&str>) -> Self {
+        Self {
+            state,
+            description: description.chars().take(MAX_DESCRIPTION_CHARS).collect(),
+            target_url: target_url.map(str::to_string),
+        }
+    }
+
+    /// ⏳ The branch is committed and we're getting the changes ready for review
+    pub fn generating() -> Self {
+        Self::new(CommitState::Pending, "Feedbacker: generating changes", None)
+    }
+
+    /// ✅ The pull request with the changes is open
+    pub fn proposed(pull_request_url: &str) -> Self {
+        Self::new(
+            CommitState::Success,
+            "Feedbacker: changes proposed",
+            Some(pull_request_url),
+        )
+    }
+
+    /// ❌ We gave up on these changes
+    pub fn failed(reason: &str) -> Self {
+        Self::new(
+            CommitState::Failure,
+            &format!("Feedbacker: {}", reason),
+            None,
+        )
+    }
+}
+
+// 🧪 Tests - Red, yellow, green!
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_statuses_fit_githubs_limits() {
+        let failed = CommitStatus::failed(&"x".repeat(500));
+        assert_eq!(failed.state.as_str(), "failure");
+        assert_eq!(failed.description.chars().count(), MAX_DESCRIPTION_CHARS);
+        assert!(failed.description.starts_with("Feedbacker: xxx"));
+        let proposed = CommitStatus::proposed("https://github.com/8b-is/smart-tree/pull/7");
+        assert_eq!(proposed.state.check_run(), ("completed", Some("success")));
+        assert_eq!(
+            CommitStatus::generating().state.check_run(),
+            ("in_progress", None)
+        );
+        println!("✅ Commit status test passed!");
+    }
+}
@@ -0,0 +1,450 @@
+// 🚰 GitHub Write Throttle - Keeping us under GitHub's secondary rate limits! 🚰
+// One token bucket per GitHub App installation (secondary limits are counted per
+// installation), shared by every comment/label/assign/close call on its
+// repositories; writes no installation covers draw from the token's own bucket. A write that would wait a little is delayed
+// in place; one that would wait longer than the configured maximum is refused
+// with `WriteThrottled` so callers can defer it through the job queue.
+// Created with love by Aye & Hue! ✨
+
+use anyhow::{Context, Result};
+use sqlx::PgPool;
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tracing::error;
+
+use super::cooldown::SecondaryRateLimited;
+use crate::config::GitHubConfig;
+
+/// 🔥 Writes delayed or refused at least this often keep a saturation episode going
+const SATURATION_GRACE: Duration = Duration::from_secs(60);
+/// ⏱️ How often the monitor looks for sustained saturation
+const SATURATION_CHECK_INTERVAL: Duration = Duration::from_secs(60);
+
+/// ⏰ Where the throttle gets the time from (a fake in tests)
+pub trait Clock: Send + Sync + fmt::Debug {
+    fn now(&self) -> Instant;
+}
+
+/// ⏰ The real monotonic clock
+#[derive(Debug, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> Instant {
+        Instant::now()
+    }
+}
+
+/// 🚫 A write would have waited longer than the throttle allows
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WriteThrottled {
+    /// ⏳ When a token will be free for this write
+    pub retry_after: Duration,
+}
+
+impl fmt::Display for WriteThrottled {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "GitHub write throttled, retry in {}s",
+            self.retry_after.as_secs_f64().ceil()
+        )
+    }
+}
+
+impl std::error::Error for WriteThrottled {}
+
+impl WriteThrottled {
+    /// 🔍 Find a throttle refusal anywhere in an error chain. A closed cooldown gate
+    /// counts too: the caller should defer either way.
+    pub fn find(error: &anyhow::Error) -> Option<Self> {
+        error.chain().find_map(|cause| {
+            cause.downcast_ref::<Self>().copied().or_else(|| {
+                cause
+                    .downcast_ref::<SecondaryRateLimited>()
+                    .map(|limited| Self {
+                        retry_after: limited.retry_after,
+                    })
+            })
+        })
+    }
+}
+
+/// 🪣 One installation's bucket
+#[derive(Debug)]
+struct Bucket {
+    /// 🪙 Tokens left; negative while writes are queued for future tokens
+    tokens: f64,
+    last_refill: Instant,
+    /// 🔥 Since when writes have kept finding the bucket empty
+    saturated_since: Option<Instant>,
+    /// 🔥 Last time a write was delayed or refused
+    last_pressure: Instant,
+    /// 📣 Already reported the current saturation episode?
+    alerted: bool,
+}
+
+impl Bucket {
+    /// ➕ A full bucket
+    fn full(writes_per_minute: u32, now: Instant) -> Self {
+        Self {
+            tokens: writes_per_minute as f64,
+            last_refill: now,
+            saturated_since: None,
+            last_pressure: now,
+            alerted: false,
+        }
+    }
+
+    /// 😌 End the saturation episode once a minute passes without pressure
+    fn expire_saturation(&mut self, now: Instant) {
+        if now.saturating_duration_since(self.last_pressure) > SATURATION_GRACE {
+            self.saturated_since = None;
+            self.alerted = false;
+        }
+    }
+}
+
+/// 🚰 Token buckets for GitHub writes, keyed by installation id
+/// (None for writes no installation covers)
+#[derive(Debug)]
+pub struct WriteThrottle {
+    /// 🪣 Capacity of each bucket, and tokens refilled per minute
+    writes_per_minute: u32,
+    /// ⏳ Longest a write may be delayed before it is refused instead
+    max_wait: Duration,
+    clock: Arc<dyn Clock>,
+    buckets: Mutex<HashMap<Option<i64>, Bucket>>,
+    /// 📈 Writes that had to wait for a token
+    delayed_total: AtomicU64,
+    /// 📈 Total time writes spent waiting, in microseconds
+    wait_micros_total: AtomicU64,
+    /// 📈 Writes refused because the wait would exceed `max_wait`
+    deferred_total: AtomicU64,
+}
+
+impl WriteThrottle {
+    /// ➕ Full buckets on the system clock
+    pub fn new(writes_per_minute: u32, max_wait: Duration) -> Self {
+        Self::with_clock(writes_per_minute, max_wait, Arc::new(SystemClock))
+    }
+
+    /// ⚙️ From GITHUB_WRITES_PER_MINUTE and GITHUB_WRITE_MAX_WAIT_SECONDS
+    pub fn from_config(config: &GitHubConfig) -> Self {
+        Self::new(
+            config.writes_per_minute,
+            Duration::from_secs(config.write_max_wait_seconds),
+        )
+    }
+
+    /// 🧪 Full buckets on an explicit clock
+    pub fn with_clock(writes_per_minute: u32, max_wait: Duration, clock: Arc<dyn Clock>) -> Self {
+        Self {
+            writes_per_minute: writes_per_minute.max(1),
+            max_wait,
+            clock,
+            buckets: Mutex::default(),
+            delayed_total: AtomicU64::new(0),
+            wait_micros_total: AtomicU64::new(0),
+            deferred_total: AtomicU64::new(0),
+        }
+    }
+
+    /// 💧 Top the bucket up for the time since the last refill
+    fn refill(&self, bucket: &mut Bucket, now: Instant) {
+        let elapsed = now.saturating_duration_since(bucket.last_refill);
+        let refilled = elapsed.as_secs_f64() * self.writes_per_minute as f64 / 60.0;
+        bucket.tokens = (bucket.tokens + refilled).min(self.writes_per_minute as f64);
+        bucket.last_refill = now;
+    }
+
+    /// 🎟️ Reserve a token for one write to a repository of `installation`. Ok(wait)
+    /// is how long to sleep before sending it (zero when a token is free); Err when
+    /// that would exceed `max_wait`, in which case nothing is reserved.
+    pub fn acquire(&self, installation: Option<i64>) -> Result<Duration, WriteThrottled> {
+        let now = self.clock.now();
+        let mut buckets = self.buckets.lock().unwrap_or_else(|e| e.into_inner());
+        let bucket = buckets
+            .entry(installation)
+            .or_insert_with(|| Bucket::full(self.writes_per_minute, now));
+        self.refill(bucket, now);
+
+        if bucket.tokens >= 1.0 {
+            bucket.tokens -= 1.0;
+            return Ok(Duration::ZERO);
+        }
+
+        // 🔥 Starts (or continues) a saturation episode
+        if bucket.saturated_since.is_none()
+            || now.saturating_duration_since(bucket.last_pressure) > SATURATION_GRACE
+        {
+            bucket.saturated_since = Some(now);
+            bucket.alerted = false;
+        }
+        bucket.last_pressure = now;
+
+        let wait =
+            Duration::from_secs_f64((1.0 - bucket.tokens) * 60.0 / self.writes_per_minute as f64);
+        if wait > self.max_wait {
+            self.deferred_total.fetch_add(1, Ordering::Relaxed);
+            return Err(WriteThrottled { retry_after: wait });
+        }
+
+        bucket.tokens -= 1.0;
+        self.delayed_total.fetch_add(1, Ordering::Relaxed);
+        self.wait_micros_total
+            .fetch_add(wait.as_micros() as u64, Ordering::Relaxed);
+        Ok(wait)
+    }
+
+    /// 🔥 How long writes to the most saturated installation have kept being delayed
+    /// or refused (None once a minute has passed without either, everywhere)
+    pub fn saturated_for(&self) -> Option<Duration> {
+        let now = self.clock.now();
+        let mut buckets = self.buckets.lock().unwrap_or_else(|e| e.into_inner());
+        buckets
+            .values_mut()
+            .filter_map(|bucket| {
+                bucket.expire_saturation(now);
+                bucket.saturated_since
+            })
+            .map(|since| now.saturating_duration_since(since))
+            .max()
+    }
+
+    /// 📣 An installation saturated for at least `threshold` and not reported yet?
+    /// Returns which and for how long, once per saturation episode.
+    pub fn take_saturation_alert(&self, threshold: Duration) -> Option<(Option<i64>, Duration)> {
+        let now = self.clock.now();
+        let mut buckets = self.buckets.lock().unwrap_or_else(|e| e.into_inner());
+        let (installation, bucket, saturated_for) = buckets
+            .iter_mut()
+            .filter_map(|(installation, bucket)| {
+                bucket.expire_saturation(now);
+                let saturated_for = now.saturating_duration_since(bucket.saturated_since?);
+                (saturated_for >= threshold && !bucket.alerted).then_some((
+                    *installation,
+                    bucket,
+                    saturated_for,
+                ))
+            })
+            .max_by_key(|(_, _, saturated_for)| *saturated_for)?;
+        bucket.alerted = true;
+        Some((installation, saturated_for))
+    }
+
+    /// 📈 Writes that had to wait for a token
+    pub fn delayed_total(&self) -> u64 {
+        self.delayed_total.load(Ordering::Relaxed)
+    }
+
+    /// 📈 Total time writes spent waiting for a token
+    pub fn wait_total(&self) -> Duration {
+        Duration::from_micros(self.wait_micros_total.load(Ordering::Relaxed))
+    }
+
+    /// 📈 Writes refused because they would have waited too long
+    pub fn deferred_total(&self) -> u64 {
+        self.deferred_total.load(Ordering::Relaxed)
+    }
+}
+
+/// 📣 Leave every active admin a warning notification; returns how many were written
+pub async fn notify_saturation(pool: &PgPool, saturated_for: Duration) -> Result<u64> {
+    let minutes = saturated_for.as_secs() / 60;
+    let result = sqlx::query(
+        r#"
+        INSERT INTO notifications (user_id, notification_type, title, content)
+        SELECT id, 'warning', $1, $2 FROM users WHERE role = 'admin' AND is_active = true
+        "#,
+    )
+    .bind("GitHub writes are being throttled")
+    .bind(format!(
+        "Comments, labels and assignments have been held back by the GitHub write throttle for {} minutes. \
+         Deferred issue automation is waiting in the job queue; raise GITHUB_WRITES_PER_MINUTE \
+         only if GitHub's secondary rate limits allow it.",
+        minutes
+    ))
+    .execute(pool)
+    .await
+    .context("Failed to write throttle saturation notifications")?;
+    Ok(result.rows_affected())
+}
+
+/// 🚀 Check for sustained saturation every minute and warn the admins once per episode
+pub fn spawn_saturation_monitor(
+    throttle: Arc<WriteThrottle>,
+    pool: PgPool,
+    threshold: Duration,
+) -> tokio::task::JoinHandle<()> {
+    tokio::spawn(async move {
+        loop {
+            tokio::time::sleep(SATURATION_CHECK_INTERVAL).await;
+            let Some((installation, saturated_for)) = throttle.take_saturation_alert(threshold)
+            else {
+                continue;
+            };
+            error!(
+                "🔥 GitHub writes ({}) have been throttled for {:?} ({} deferred so far)",
+                installation
+                    .map(|id| format!("installation {}", id))
+                    .unwrap_or_else(|| "no installation".to_string()),
+                saturated_for,
+                throttle.deferred_total()
+            );
+            if let Err(e) = notify_saturation(&pool, saturated_for).await {
+                error!("❌ {:#}", e);
+            }
+        }
+    })
+}
+
+// 🧪 Tests - Drip, drip, drip!
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::FakeClock;
+
+    fn throttle(writes_per_minute: u32, max_wait_secs: u64) -> (WriteThrottle, Arc<FakeClock>) {
+        let clock = Arc::new(FakeClock::default());
+        let throttle = WriteThrottle::with_clock(
+            writes_per_minute,
+            Duration::from_secs(max_wait_secs),
+            clock.clone(),
+        );
+        (throttle, clock)
+    }
+
+    #[test]
+    fn test_bucket_drains_and_refills() {
+        let (throttle, clock) = throttle(6, 60);
+
+        // 🪣 A full minute's worth goes straight through
+        for _ in 0..6 {
+            assert_eq!(throttle.acquire(None), Ok(Duration::ZERO));
+        }
+        // ⏳ Then each write queues behind the previous one, 10s apart
+        assert_eq!(throttle.acquire(None), Ok(Duration::from_secs(10)));
+        assert_eq!(throttle.acquire(None), Ok(Duration::from_secs(20)));
+        assert_eq!(throttle.delayed_total(), 2);
+        assert_eq!(throttle.wait_total(), Duration::from_secs(30));
+
+        // 💧 After 30s the queued writes are paid off and one token is back
+        clock.advance(Duration::from_secs(30));
+        assert_eq!(throttle.acquire(None), Ok(Duration::ZERO));
+        assert_eq!(throttle.acquire(None), Ok(Duration::from_secs(10)));
+
+        // 🧱 Never more than a minute's worth, however long we idle
+        clock.advance(Duration::from_secs(3600));
+        for _ in 0..6 {
+            assert_eq!(throttle.acquire(None), Ok(Duration::ZERO));
+        }
+        assert!(throttle.acquire(None).unwrap() > Duration::ZERO);
+        println!("✅ Write throttle refill test passed!");
+    }
+
+    #[test]
+    fn test_long_waits_are_refused_without_reserving() {
+        let (throttle, clock) = throttle(2, 20);
+        assert_eq!(throttle.acquire(None), Ok(Duration::ZERO));
+        assert_eq!(throttle.acquire(None), Ok(Duration::ZERO));
+
+        // 🚫 The next token is 30s away - more than the 20s we may wait
+        let refused = throttle.acquire(None).unwrap_err();
+        assert_eq!(refused.retry_after, Duration::from_secs(30));
+        assert_eq!(throttle.deferred_total(), 1);
+
+        // 🪙 Refusals don't eat tokens: 15s later a 15s wait is fine
+        clock.advance(Duration::from_secs(15));
+        assert_eq!(throttle.acquire(None), Ok(Duration::from_secs(15)));
+
+        let error =
+            anyhow::Error::new(throttle.acquire(None).unwrap_err()).context("Labels failed");
+        assert!(WriteThrottled::find(&error).is_some());
+        assert!(WriteThrottled::find(&anyhow::anyhow!("boom")).is_none());
+        println!("✅ Write throttle refusal test passed!");
+    }
+
+    #[test]
+    fn test_installations_have_their_own_buckets() {
+        let (throttle, _clock) = throttle(2, 0);
+        let threshold = Duration::ZERO;
+
+        // 🪣 Draining one installation leaves the others (and the token's own) alone
+        for _ in 0..2 {
+            assert_eq!(throttle.acquire(Some(1)), Ok(Duration::ZERO));
+        }
+        assert!(throttle.acquire(Some(1)).is_err());
+        for installation in [Some(2), None] {
+            for _ in 0..2 {
+                assert_eq!(throttle.acquire(installation), Ok(Duration::ZERO));
+            }
+        }
+
+        // 📣 Only the saturated installation is reported
+        assert_eq!(
+            throttle.take_saturation_alert(threshold),
+            Some((Some(1), Duration::ZERO))
+        );
+        assert!(throttle.take_saturation_alert(threshold).is_none());
+        println!("✅ Per-installation write throttle test passed!");
+    }
+
+    #[test]
+    fn test_saturation_is_reported_once_per_episode() {
+        let (throttle, clock) = throttle(1, 0);
+        let threshold = Duration::from_secs(120);
+        assert_eq!(throttle.acquire(None), Ok(Duration::ZERO));
+        assert!(throttle.saturated_for().is_none());
+
+        // 🔥 Ask for twice what the bucket gives, for three minutes
+        for _ in 0..6 {
+            let _ = throttle.acquire(None);
+            clock.advance(Duration::from_secs(30));
+        }
+        assert!(throttle.saturated_for().unwrap() >= Duration::from_secs(150));
+        assert!(throttle.take_saturation_alert(threshold).is_some());
+        assert!(throttle.take_saturation_alert(threshold).is_none());
+
+        // 😌 A quiet minute ends the episode
+        clock.advance(Duration::from_secs(61));
+        assert!(throttle.saturated_for().is_none());
+        assert!(throttle.take_saturation_alert(Duration::ZERO).is_none());
+        println!("✅ Write throttle saturation test passed!");
+    }
+
+    #[tokio::test]
+    async fn test_saturation_notifies_active_admins() {
+        let Some(app) = crate::test_support::spawn_test_app().await else {
+            return;
+        };
+        sqlx::query(
+            r#"
+            INSERT INTO users (email, name, password_hash, role, is_active) VALUES
+                ('ops@example.com', 'Ops', 'x', 'admin', true),
+                ('gone@example.com', 'Gone', 'x', 'admin', false),
+                ('user@example.com', 'User', 'x', 'user', true)
+            "#,
+        )
+        .execute(&app.db_pool)
+        .await
+        .unwrap();
+
+        let written = notify_saturation(&app.db_pool, Duration::from_secs(600))
+            .await
+            .unwrap();
+        assert_eq!(written, 1);
+        let (email, content): (String, String) = sqlx::query_as(
+            "SELECT u.email, n.content FROM notifications n JOIN users u ON u.id = n.user_id WHERE n.notification_type = 'warning'",
+        )
+        .fetch_one(&app.db_pool)
+        .await
+        .unwrap();
+        assert_eq!(email, "ops@example.com");
+        assert!(content.contains("for 10 minutes"));
+        println!("✅ Saturation notification test passed!");
+    }
+}
@@ -0,0 +1,88 @@
+// 🔏 Commit Verification - The PR holds what we generated, or there is no PR! 🔏
+// After the commit stage writes a feedback branch, every changed path is read back
+// from the branch and the hash of its content compared with the hash of what we
+// wrote (a deletion must leave the path absent). A second process pushing to the
+// same branch in between shows up as a mismatch. GitHub may add or drop the final
+// newline of a file, so trailing newlines don't count.
+// Created with love by Aye & Hue! ✨
+
+use anyhow::Result;
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+
+use super::ops::GitHubOps;
+use super::CommittedChanges;
+
+/// ❌ `error_message` of feedback whose branch didn't hold what we committed
+pub const POST_COMMIT_MISMATCH: &str = "post_commit_mismatch";
+
+/// 🔍 One changed path: the hash we wrote and the hash found on the branch
+/// (None for a file that should be, or is, absent)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct FileCheck {
+    pub path: String,
+    pub expected: Option<String>,
+    pub found: Option<String>,
+}
+
+impl FileCheck {
+    /// ✅ Did the branch end up with what we wrote?
+    pub fn matches(&self) -> bool {
+        self.expected == self.found
+    }
+}
+
+/// #️⃣ SHA-256 of file content, ignoring trailing newlines
+pub fn content_hash(content: &str) -> String {
+    hex::encode(Sha256::digest(
+        content.trim_end_matches(['\n', '\r']).as_bytes(),
+    ))
+}
+
+/// 🔏 Read every committed path back from the branch and compare it with what we
+/// wrote. A path written more than once is checked against its last write.
+pub async fn verify_committed(
+    github: &dyn GitHubOps,
+    repository: &str,
+    committed: &CommittedChanges,
+) -> Result<Vec<FileCheck>> {
+    let (owner, repo) = repository
+        .split_once('/')
+        .ok_or_else(|| anyhow::anyhow!("Repository {} is not in owner/repo format", repository))?;
+    let mut expected: Vec<(&str, Option<String>)> = Vec::new();
+    for change in &committed.applied {
+        let path = change.improvement.file_path.as_str();
+        expected.retain(|(earlier, _)| *earlier != path);
+        expected.push((path, change.written.as_deref().map(content_hash)));
+    }
+
+    let mut checks = Vec::with_capacity(expected.len());
+    for (path, expected) in expected {
+        let found = github
+            .file_content(owner, repo, path, &committed.branch_name)
+            .await?
+            .as_deref()
+            .map(content_hash);
+        checks.push(FileCheck {
+            path: path.to_string(),
+            expected,
+            found,
+        });
+    }
+    Ok(checks)
+}
+
+// 🧪 Tests - Hashes that shrug at newlines but nothing else!
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_content_hash_tolerates_trailing_newlines_only() {
+        assert_eq!(content_hash("fn main() {}"), content_hash("fn main() {}\n"));
+        assert_eq!(content_hash("a\r\n"), content_hash("a\n\n"));
+        assert_ne!(content_hash("a\n"), content_hash("a \n"));
+        assert_ne!(content_hash("\na"), content_hash("a"));
+        println!("✅ Content hash test passed!");
+    }
+}
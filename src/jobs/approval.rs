@@ -0,0 +1,1754 @@
+// ✋ Approval Hold - A human signs off before the bot opens a pull request! ✋
+// Generated changes enter through `submit_changes`, which applies the path policy and
+// records the patch. Projects with `require_approval` set stop the pipeline there:
+// the validated changes are stored in `feedback_approvals`, the feedback waits in
+// `awaiting_approval`, and the project owner is notified. Approving (owner or admin)
+// queues the commit stage, which branches, commits and opens the PR; rejecting fails
+// the feedback with `rejected_by_owner`. Held changes nobody decides on within
+// FEEDBACK_APPROVAL_EXPIRY_DAYS fail with `approval_expired` (swept hourly). Changes
+// that need no sign-off are stored as approved and go to the commit stage directly.
+// The first decision wins: deciding again reports it instead of redoing anything.
+// The commit stage applies the path policy again, so a path the project denied
+// after approval is never committed.
+// Before the PR is opened the committed branch is read back (`github::verify`); if
+// it doesn't hold what we wrote, the branch is deleted and the feedback fails with
+// `post_commit_mismatch` instead. Progress shows up on the branch's head commit as
+// a commit status or check run (GITHUB_STATUS_REPORTING). A stage that finds the
+// repository deleted or shut to us fails the feedback with `repository_unavailable`
+// straight away and deactivates the project (`github::availability`).
+// Created with love by Aye & Hue! ✨
+
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sqlx::{Acquire, PgConnection, PgPool, Postgres};
+use std::time::Duration;
+use tracing::{error, info, warn};
+use uuid::Uuid;
+
+use crate::{
+    api::{events::AppEvent, AppState},
+    config::StatusReporting,
+    database::{
+        feedback_votes,
+        models::{Feedback, FeedbackStatus, User},
+        project_config::{ProjectConfig, PullRequestSettings},
+    },
+    github::{
+        artifacts,
+        availability::{self, RepositoryUnavailable, REPOSITORY_UNAVAILABLE},
+        patch, path_policy,
+        pr_body::{self, FeedbackExample, PullRequestContext},
+        protection,
+        statuses::CommitStatus,
+        verify::{self, FileCheck, POST_COMMIT_MISMATCH},
+        CodeImprovement, CommittedChanges, FeedbackProcessingRequest,
+    },
+};
+
+use super::outbox::{self, OutboxConsumer, OutboxEvent};
+use super::pr_refresh;
+use super::{JobContext, JobError, JobErrorKind, JobHandler};
+
+/// 📣 A feedback item's changes are waiting for approval
+pub const APPROVAL_REQUESTED_EVENT: &str = "feedback.awaiting_approval";
+/// 🏷️ Job type for the commit stage of approved changes
+pub const COMMIT_APPROVED_JOB: &str = "feedback_commit_approved";
+/// ❌ `error_message` of feedback whose changes were rejected
+pub const REJECTED_BY_OWNER: &str = "rejected_by_owner";
+/// ⌛ `error_message` of feedback whose changes nobody decided on in time
+pub const APPROVAL_EXPIRED: &str = "approval_expired";
+/// 🔏 A committed branch was read back and compared with what we wrote
+pub const COMMIT_VERIFIED_EVENT: &str = "feedback.commit_verified";
+/// ✅ Metadata key of the check run reporting on the feedback's branch
+pub const CHECK_RUN_KEY: &str = "check_run";
+/// ⏱️ How often overdue approvals are expired
+const SWEEP_INTERVAL: Duration = Duration::from_secs(3600);
+
+/// 🚦 What happens to validated changes
+#[derive(Debug, Clone)]
+pub enum ApprovalGate {
+    /// ➡️ No sign-off needed, carry on with the commit stage
+    Proceed(Vec<CodeImprovement>),
+    /// ✋ Stored and waiting for the project owner
+    Held,
+}
+
+/// ⚖️ What a held feedback item's changes came to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Decision {
+    Approved,
+    Rejected,
+    Expired,
+}
+
+impl Decision {
+    /// 🏷️ Value stored in `feedback_approvals.decision`
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Decision::Approved => "approved",
+            Decision::Rejected => "rejected",
+            Decision::Expired => "expired",
+        }
+    }
+
+    fn from_db(value: &str) -> Option<Self> {
+        [Decision::Approved, Decision::Rejected, Decision::Expired]
+            .into_iter()
+            .find(|decision| decision.as_str() == value)
+    }
+}
+
+/// 📋 The outcome of `decide`
+#[derive(Debug, Clone)]
+pub enum DecisionOutcome {
+    /// ✅ Recorded, with the feedback as it is now
+    Decided(Box<Feedback>),
+    /// 🔁 Somebody got there first (approving twice lands here, not in a second PR)
+    AlreadyDecided(Decision),
+    /// 🤷 The feedback was never held for approval
+    NotHeld,
+}
+
+/// 🗄️ A held feedback item's stored changes
+#[derive(Debug, Clone, Serialize, sqlx::FromRow)]
+pub struct PendingApproval {
+    pub feedback_id: Uuid,
+    pub changes: sqlx::types::Json<Vec<CodeImprovement>>,
+    pub requested_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+    pub decision: Option<String>,
+    pub decided_by: Option<Uuid>,
+    pub decided_at: Option<DateTime<Utc>>,
+}
+
+impl PendingApproval {
+    /// 🔍 The approval record for a feedback item, if it was ever held
+    pub async fn find(pool: &PgPool, feedback_id: Uuid) -> Result<Option<Self>> {
+        sqlx::query_as("SELECT * FROM feedback_approvals WHERE feedback_id = $1")
+            .bind(feedback_id)
+            .fetch_optional(pool)
+            .await
+            .context("Failed to load feedback approval")
+    }
+}
+
+/// ✋ Does any active project serving the repository want to sign off on changes first?
+pub async fn requires_approval(pool: &PgPool, repository: &str) -> Result<bool> {
+    sqlx::query_scalar(
+        r#"
+        SELECT EXISTS (
+            SELECT 1 FROM projects p
+            JOIN project_repository_routes r ON r.project_id = p.id
+            WHERE r.repository = $1 AND p.require_approval AND p.is_active
+        )
+        "#,
+    )
+    .bind(repository)
+    .fetch_one(pool)
+    .await
+    .context("Failed to check whether the project requires approval")
+}
+
+/// 🚦 Run after `path_policy::enforce_path_policy`: record the patch for review, then
+/// hold the changes when the project requires approval, otherwise hand them straight back
+pub async fn gate(
+    pool: &PgPool,
+    feedback: &mut Feedback,
+    improvements: Vec<CodeImprovement>,
+    expiry_days: u32,
+) -> Result<ApprovalGate> {
+    patch::record_patch(pool, feedback, &improvements).await?;
+    if !requires_approval(pool, &feedback.repository).await? {
+        return Ok(ApprovalGate::Proceed(improvements));
+    }
+    hold_for_approval(pool, feedback, &improvements, expiry_days).await?;
+    Ok(ApprovalGate::Held)
+}
+
+/// 📥 Where generated changes enter the pipeline: the path policy, then `gate`. Changes
+/// that need no sign-off are stored as approved (by nobody) and the commit stage is
+/// queued with them; held ones wait for `decide`. Err when the path policy refused them all.
+pub async fn submit_changes(
+    app_state: &AppState,
+    feedback: &mut Feedback,
+    improvements: Vec<CodeImprovement>,
+) -> Result<ApprovalGate> {
+    let pool = &app_state.db_pool;
+    let improvements = path_policy::enforce_path_policy(pool, feedback, improvements).await?;
+    let expiry_days = app_state.config.feedback.approval_expiry_days;
+    let gated = gate(pool, feedback, improvements, expiry_days).await?;
+    if let ApprovalGate::Proceed(improvements) = &gated {
+        queue_commit(pool, feedback, improvements).await?;
+    }
+    Ok(gated)
+}
+
+/// ➡️ Store changes nobody has to sign off on and queue the commit stage for them
+async fn queue_commit(
+    pool: &PgPool,
+    feedback: &mut Feedback,
+    improvements: &[CodeImprovement],
+) -> Result<()> {
+    let mut tx = pool
+        .begin()
+        .await
+        .context("Failed to start queueing the commit")?;
+    sqlx::query(
+        r#"
+        INSERT INTO feedback_approvals (feedback_id, changes, expires_at, decision, decided_at)
+        VALUES ($1, $2, NOW(), $3, NOW())
+        ON CONFLICT (feedback_id) DO UPDATE SET
+            changes = EXCLUDED.changes,
+            requested_at = NOW(),
+            expires_at = EXCLUDED.expires_at,
+            decision = EXCLUDED.decision,
+            decided_by = NULL,
+            decided_at = EXCLUDED.decided_at
+        "#,
+    )
+    .bind(feedback.id)
+    .bind(serde_json::to_value(improvements)?)
+    .bind(Decision::Approved.as_str())
+    .execute(&mut *tx)
+    .await
+    .context("Failed to store changes for the commit stage")?;
+    let updated = set_status(
+        &mut tx,
+        feedback.id,
+        FeedbackStatus::CreatingPullRequest,
+        None,
+    )
+    .await?;
+    super::enqueue(
+        &mut *tx,
+        COMMIT_APPROVED_JOB,
+        serde_json::json!(CommitApprovedJob {
+            feedback_id: feedback.id
+        }),
+    )
+    .await?;
+    tx.commit().await.context("Failed to queue the commit")?;
+    info!(
+        "➡️ Feedback {} needs no sign-off, {} changes queued for commit",
+        feedback.id,
+        improvements.len()
+    );
+    *feedback = updated;
+    Ok(())
+}
+
+/// 🅿️ Store the changes and park the feedback in `awaiting_approval`. The notification
+/// goes through the outbox, so it commits (or not) with the status change.
+pub async fn hold_for_approval(
+    pool: &PgPool,
+    feedback: &mut Feedback,
+    improvements: &[CodeImprovement],
+    expiry_days: u32,
+) -> Result<()> {
+    let mut tx = pool
+        .begin()
+        .await
+        .context("Failed to start approval hold")?;
+    let expires_at: DateTime<Utc> = sqlx::query_scalar(
+        r#"
+        INSERT INTO feedback_approvals (feedback_id, changes, expires_at)
+        VALUES ($1, $2, NOW() + make_interval(days => $3))
+        ON CONFLICT (feedback_id) DO UPDATE SET
+            changes = EXCLUDED.changes,
+            requested_at = NOW(),
+            expires_at = EXCLUDED.expires_at,
+            decision = NULL,
+            decided_by = NULL,
+            decided_at = NULL
+        RETURNING expires_at
+        "#,
+    )
+    .bind(feedback.id)
+    .bind(serde_json::to_value(improvements)?)
+    .bind(expiry_days as i32)
+    .fetch_one(&mut *tx)
+    .await
+    .context("Failed to store changes for approval")?;
+
+    let updated = set_status(&mut tx, feedback.id, FeedbackStatus::AwaitingApproval, None).await?;
+    outbox::record(
+        &mut tx,
+        APPROVAL_REQUESTED_EVENT,
+        Some(feedback.id),
+        serde_json::json!({
+            "feedback_id": feedback.id,
+            "repository": feedback.repository,
+            "changes": improvements.len(),
+            "expires_at": expires_at,
+        }),
+    )
+    .await?;
+    tx.commit()
+        .await
+        .context("Failed to commit approval hold")?;
+    info!(
+        "✋ Feedback {} held for approval until {}",
+        feedback.id, expires_at
+    );
+    *feedback = updated;
+    Ok(())
+}
+
+/// ⚖️ Record the decision on held changes. Approving queues the commit stage in the
+/// same transaction; rejecting or expiring fails the feedback with the reason. Takes a
+/// pool or a connection - inside a handler's `Tx` it commits with the rest of its writes.
+pub async fn decide<'a>(
+    db: impl Acquire<'a, Database = Postgres>,
+    feedback_id: Uuid,
+    decision: Decision,
+    decided_by: Option<Uuid>,
+) -> Result<DecisionOutcome> {
+    let mut tx = db
+        .begin()
+        .await
+        .context("Failed to start approval decision")?;
+    // 🔒 Concurrent decisions queue up here, and the later ones see the first
+    let current: Option<Option<String>> = sqlx::query_scalar(
+        "SELECT decision FROM feedback_approvals WHERE feedback_id = $1 FOR UPDATE",
+    )
+    .bind(feedback_id)
+    .fetch_optional(&mut *tx)
+    .await
+    .context("Failed to lock feedback approval")?;
+    match current {
+        None => return Ok(DecisionOutcome::NotHeld),
+        Some(Some(earlier)) => {
+            let earlier = Decision::from_db(&earlier)
+                .with_context(|| format!("Unknown approval decision {}", earlier))?;
+            return Ok(DecisionOutcome::AlreadyDecided(earlier));
+        }
+        Some(None) => {}
+    }
+
+    sqlx::query(
+        "UPDATE feedback_approvals SET decision = $2, decided_by = $3, decided_at = NOW() WHERE feedback_id = $1",
+    )
+    .bind(feedback_id)
+    .bind(decision.as_str())
+    .bind(decided_by)
+    .execute(&mut *tx)
+    .await
+    .context("Failed to record approval decision")?;
+
+    let feedback = match decision {
+        Decision::Approved => {
+            let feedback = set_status(
+                &mut tx,
+                feedback_id,
+                FeedbackStatus::CreatingPullRequest,
+                None,
+            )
+            .await?;
+            super::enqueue(
+                &mut *tx,
+                COMMIT_APPROVED_JOB,
+                serde_json::json!(CommitApprovedJob { feedback_id }),
+            )
+            .await?;
+            feedback
+        }
+        Decision::Rejected | Decision::Expired => {
+            let reason = if decision == Decision::Rejected {
+                REJECTED_BY_OWNER
+            } else {
+                APPROVAL_EXPIRED
+            };
+            let feedback =
+                set_status(&mut tx, feedback_id, FeedbackStatus::Failed, Some(reason)).await?;
+            outbox::record_status_change(&mut tx, &feedback).await?;
+            feedback
+        }
+    };
+    tx.commit()
+        .await
+        .context("Failed to commit approval decision")?;
+    info!(
+        "⚖️ Changes for feedback {} {}",
+        feedback_id,
+        decision.as_str()
+    );
+    Ok(DecisionOutcome::Decided(Box::new(feedback)))
+}
+
+/// 🔄 Move a feedback item to `status` inside the caller's transaction
+async fn set_status(
+    conn: &mut PgConnection,
+    feedback_id: Uuid,
+    status: FeedbackStatus,
+    error_message: Option<&str>,
+) -> Result<Feedback> {
+    sqlx::query_as::<_, Feedback>(
+        r#"
+        UPDATE feedback
+        SET status = $2,
+            error_message = $3,
+            completed_at = CASE WHEN $4 THEN NOW() ELSE NULL END
+        WHERE id = $1
+        RETURNING *
+        "#,
+    )
+    .bind(feedback_id)
+    .bind(&status)
+    .bind(error_message)
+    .bind(status.is_terminal())
+    .fetch_one(conn)
+    .await
+    .context("Failed to update feedback status")
+}
+
+/// ⌛ Fail every held feedback item whose approval window has closed; returns how many
+pub async fn expire_overdue(app_state: &AppState) -> Result<usize> {
+    let overdue: Vec<Uuid> = sqlx::query_scalar(
+        "SELECT feedback_id FROM feedback_approvals WHERE decision IS NULL AND expires_at <= NOW() ORDER BY expires_at",
+    )
+    .fetch_all(&app_state.db_pool)
+    .await
+    .context("Failed to find overdue approvals")?;
+
+    let mut expired = 0;
+    for feedback_id in overdue {
+        if let DecisionOutcome::Decided(feedback) =
+            decide(&app_state.db_pool, feedback_id, Decision::Expired, None).await?
+        {
+            app_state.events.publish(AppEvent::FeedbackStatusChanged {
+                id: feedback.id,
+                status: feedback.status,
+            });
+            expired += 1;
+        }
+    }
+    if expired > 0 {
+        warn!(
+            "⌛ {} held feedback items expired without a decision",
+            expired
+        );
+    }
+    Ok(expired)
+}
+
+/// 🚀 Start the hourly expiry sweep
+pub fn spawn_expiry_sweeper(app_state: AppState) -> tokio::task::JoinHandle<()> {
+    info!("⌛ Starting approval expiry sweeper");
+    tokio::spawn(async move {
+        loop {
+            if let Err(e) = expire_overdue(&app_state).await {
+                error!("❌ Approval expiry sweep failed: {:#}", e);
+            }
+            tokio::time::sleep(SWEEP_INTERVAL).await;
+        }
+    })
+}
+
+/// 🧭 What the PR body says about the feedback: its status page, the submitter's
+/// GitHub handle or name (none for anonymous feedback or a deactivated account),
+/// category, examples, votes and duplicate reports, and the project's template, else
+/// the global one
+async fn pull_request_context(
+    app_state: &AppState,
+    feedback: &Feedback,
+    settings: &PullRequestSettings,
+) -> PullRequestContext {
+    let submitter = match feedback.user_id {
+        Some(user_id) => match User::find_active_by_id(&app_state.db_pool, user_id).await {
+            Ok(user) => user.map(|user| match user.github_username {
+                Some(handle) => format!("@{}", handle),
+                None => user.name,
+            }),
+            Err(e) => {
+                warn!("⚠️ Couldn't look up who sent {}: {:#}", feedback.id, e);
+                None
+            }
+        },
+        None => None,
+    };
+    let reception = feedback_votes::reception(&app_state.db_pool, feedback.id)
+        .await
+        .inspect_err(|e| warn!("⚠️ Couldn't count votes for {}: {:#}", feedback.id, e))
+        .unwrap_or_default();
+    let metadata = feedback.metadata.as_ref();
+    PullRequestContext {
+        feedback_url: app_state
+            .config
+            .public_url(&format!("/feedback/{}", feedback.id)),
+        submitter,
+        category: metadata
+            .and_then(|metadata| metadata.get("category"))
+            .and_then(Value::as_str)
+            .map(str::to_string),
+        examples: FeedbackExample::from_metadata(metadata),
+        reception,
+        body_template: settings
+            .body_template
+            .clone()
+            .or_else(|| app_state.config.github.pr_body_template.clone()),
+    }
+}
+
+/// 📋 Job payload for the commit stage
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CommitApprovedJob {
+    pub feedback_id: Uuid,
+}
+
+/// 🐙 The commit stage for approved changes: branch, commit, read the branch back,
+/// check the base branch's protection, open the PR (and merge it, when the project
+/// asks for that and nothing gates the merge)
+pub struct CommitApprovedHandler;
+
+#[async_trait]
+impl JobHandler for CommitApprovedHandler {
+    const TYPE: &'static str = COMMIT_APPROVED_JOB;
+
+    async fn run(&self, payload: Value, ctx: &JobContext<'_>) -> Result<()> {
+        let job: CommitApprovedJob =
+            serde_json::from_value(payload).context("Invalid commit job payload")?;
+        let app_state = ctx.app_state;
+        let pool = &app_state.db_pool;
+        let Some(mut feedback) = Feedback::find_by_id(pool, job.feedback_id).await? else {
+            warn!("🤷 Feedback {} is gone, nothing to commit", job.feedback_id);
+            return Ok(());
+        };
+        if feedback.status != FeedbackStatus::CreatingPullRequest {
+            info!(
+                "⏭️ Feedback {} is {}, not committing again",
+                feedback.id, feedback.status
+            );
+            return Ok(());
+        }
+        let approval = PendingApproval::find(pool, feedback.id)
+            .await?
+            .ok_or_else(|| {
+                JobError::permanent(format!("No stored changes for feedback {}", feedback.id))
+            })?;
+        // 🛡️ Checked again at commit time: the policy may have tightened since approval
+        let improvements =
+            match path_policy::enforce_path_policy(pool, &mut feedback, approval.changes.0).await {
+                Ok(improvements) => improvements,
+                Err(e) if feedback.status == FeedbackStatus::Failed => {
+                    app_state.events.publish(AppEvent::FeedbackStatusChanged {
+                        id: feedback.id,
+                        status: feedback.status.clone(),
+                    });
+                    return Err(JobError::permanent(format!("{:#}", e)).into());
+                }
+                Err(e) => return Err(e),
+            };
+
+        let settings = match ProjectConfig::for_repository(pool, &feedback.repository).await {
+            Ok(config) => config
+                .map(|config| config.pull_requests)
+                .unwrap_or_default(),
+            Err(e) => {
+                warn!("⚠️ Using default pull request settings: {:#}", e);
+                PullRequestSettings::default()
+            }
+        };
+        let request = FeedbackProcessingRequest {
+            feedback_id: feedback.id,
+            repository: feedback.repository.clone(),
+            feedback_content: feedback.content.clone(),
+            improvements,
+            commit_message: app_state.config.github.default_commit_message.clone(),
+            branch_name: feedback.branch_name.clone().unwrap_or_else(|| {
+                format!(
+                    "{}feedback-{}",
+                    app_state.config.github.default_branch_prefix, feedback.id
+                )
+            }),
+            pull_request: pull_request_context(app_state, &feedback, &settings).await,
+        };
+        let github = app_state.github.as_ref();
+        let committed = match github.commit_changes(&request).await {
+            Ok(committed) => committed,
+            Err(e) => return retry_or_give_up(ctx, &mut feedback, e).await,
+        };
+        report_progress(
+            app_state,
+            feedback.id,
+            &request.repository,
+            &committed,
+            CommitStatus::generating(),
+        )
+        .await;
+        let checks = match verify::verify_committed(github, &request.repository, &committed).await {
+            Ok(checks) => checks,
+            Err(e) => return retry_or_give_up(ctx, &mut feedback, e).await,
+        };
+        let verified = checks.iter().all(FileCheck::matches);
+        let mut tx = pool
+            .begin()
+            .await
+            .context("Failed to start recording the verification")?;
+        outbox::record(
+            &mut tx,
+            COMMIT_VERIFIED_EVENT,
+            Some(feedback.id),
+            serde_json::json!({
+                "branch": committed.branch_name,
+                "verified": verified,
+                "files": checks,
+            }),
+        )
+        .await?;
+        if !verified {
+            // 🚨 Somebody else wrote to the branch: no PR with content we can't vouch for
+            let mismatched: Vec<&str> = checks
+                .iter()
+                .filter(|check| !check.matches())
+                .map(|check| check.path.as_str())
+                .collect();
+            error!(
+                "🚨 Branch {} for feedback {} doesn't hold what was committed: {}",
+                committed.branch_name,
+                feedback.id,
+                mismatched.join(", ")
+            );
+            // 📣 The failed event alerts ops through the outbox
+            feedback = set_status(
+                &mut tx,
+                feedback.id,
+                FeedbackStatus::Failed,
+                Some(POST_COMMIT_MISMATCH),
+            )
+            .await?;
+            outbox::record_status_change(&mut tx, &feedback).await?;
+        }
+        tx.commit()
+            .await
+            .context("Failed to record the verification")?;
+        if !verified {
+            // 🗑️ Only once the failure is recorded, so no transaction waits on GitHub
+            if let Some((owner, repo)) = request.repository.split_once('/') {
+                if let Err(e) = github
+                    .delete_branch(owner, repo, &committed.branch_name)
+                    .await
+                {
+                    warn!(
+                        "⚠️ Failed to delete branch {}: {:#}",
+                        committed.branch_name, e
+                    );
+                }
+            }
+            app_state.events.publish(AppEvent::FeedbackStatusChanged {
+                id: feedback.id,
+                status: feedback.status.clone(),
+            });
+            return Ok(());
+        }
+
+        let protection = protection::check_base(
+            github,
+            &request.repository,
+            &committed.base_branch,
+            &settings,
+        )
+        .await;
+        let pr = match github
+            .open_pull_request(&request, &committed, &protection)
+            .await
+        {
+            Ok(pr) => pr,
+            Err(e) => {
+                if is_last_attempt(ctx, &e) {
+                    report_progress(
+                        app_state,
+                        feedback.id,
+                        &request.repository,
+                        &committed,
+                        CommitStatus::failed("could not open the pull request"),
+                    )
+                    .await;
+                }
+                return retry_or_give_up(ctx, &mut feedback, e).await;
+            }
+        };
+        report_progress(
+            app_state,
+            feedback.id,
+            &request.repository,
+            &committed,
+            CommitStatus::proposed(&pr.url),
+        )
+        .await;
+        if settings.auto_merge {
+            // 🚧 Reviews or checks would make a merge attempt fail, so we leave it to them
+            match (
+                protection.allows_auto_merge(),
+                request.repository.split_once('/'),
+            ) {
+                (true, Some((owner, repo))) => {
+                    if let Err(e) = github.merge_pull_request(owner, repo, pr.number).await {
+                        warn!("⚠️ Failed to merge {}: {:#}", pr.url, e);
+                    }
+                }
+                _ => info!(
+                    "🔒 Not merging {}: the base branch gates merges or couldn't be read",
+                    pr.url
+                ),
+            }
+        }
+
+        sqlx::query("UPDATE feedback SET branch_name = $2, pull_request_url = $3 WHERE id = $1")
+            .bind(feedback.id)
+            .bind(&pr.branch_name)
+            .bind(&pr.url)
+            .execute(pool)
+            .await
+            .context("Failed to record the pull request")?;
+        // 🔄 Kept so the nightly refresh can show new votes and duplicate reports
+        let frozen = pr_body::freeze_pr_body(
+            &request,
+            &pr_body::applied_changes(&committed),
+            &protection.pr_section(&committed.base_branch),
+        );
+        let body = pr_body::finish_pr_body(&frozen, &request.pull_request.reception);
+        if let Err(e) = pr_refresh::record_opened(
+            pool,
+            feedback.id,
+            &request.repository,
+            pr.number,
+            &frozen,
+            &body,
+        )
+        .await
+        {
+            warn!("⚠️ Failed to keep the body of {}: {:#}", pr.url, e);
+        }
+        feedback
+            .update_status(pool, FeedbackStatus::Completed, None)
+            .await?;
+        match artifacts::stored_artifacts(pool, &feedback).await {
+            Ok(github) => app_state.events.publish(AppEvent::FeedbackCompleted {
+                id: feedback.id,
+                github: Box::new(github),
+            }),
+            Err(e) => {
+                warn!("⚠️ Failed to link {}'s artifacts: {:#}", feedback.id, e);
+                app_state.events.publish(AppEvent::FeedbackStatusChanged {
+                    id: feedback.id,
+                    status: feedback.status.clone(),
+                });
+            }
+        }
+        info!("🚀 Approved changes for {} are in {}", feedback.id, pr.url);
+        Ok(())
+    }
+}
+
+/// 🚦 Show where the commit stage is on the branch's head commit (a failure to report
+/// is logged and otherwise ignored)
+async fn report_progress(
+    app_state: &AppState,
+    feedback_id: Uuid,
+    repository: &str,
+    committed: &CommittedChanges,
+    status: CommitStatus,
+) {
+    let (Some(head), Some((owner, repo))) = (committed.applied.last(), repository.split_once('/'))
+    else {
+        return;
+    };
+    let github = app_state.github.as_ref();
+    let sha = &head.commit_sha;
+    let reported = match app_state.config.github.status_reporting {
+        StatusReporting::Off => return,
+        StatusReporting::CommitStatus => {
+            github.create_commit_status(owner, repo, sha, &status).await
+        }
+        StatusReporting::CheckRun => {
+            report_check_run(app_state, feedback_id, owner, repo, sha, &status).await
+        }
+    };
+    if let Err(e) = reported {
+        warn!(
+            "⚠️ Failed to report {} on {} in {}: {:#}",
+            status.state.as_str(),
+            sha,
+            repository,
+            e
+        );
+    }
+}
+
+/// ✅ The check run reporting on a feedback branch's head, kept on the feedback's
+/// metadata (under CHECK_RUN_KEY) so every later state updates it, retries included
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct OpenCheckRun {
+    id: u64,
+    sha: String,
+}
+
+/// ✅ Move the check run already open on `sha` to `status`, or open one and keep its id
+async fn report_check_run(
+    app_state: &AppState,
+    feedback_id: Uuid,
+    owner: &str,
+    repo: &str,
+    sha: &str,
+    status: &CommitStatus,
+) -> Result<()> {
+    let pool = &app_state.db_pool;
+    let github = app_state.github.as_ref();
+    let stored: Option<Value> =
+        sqlx::query_scalar("SELECT metadata->$2 FROM feedback WHERE id = $1")
+            .bind(feedback_id)
+            .bind(CHECK_RUN_KEY)
+            .fetch_optional(pool)
+            .await
+            .context("Failed to load the feedback's check run")?
+            .flatten();
+    let open = stored
+        .and_then(|run| serde_json::from_value::<OpenCheckRun>(run).ok())
+        .filter(|run| run.sha == sha);
+    if let Some(run) = open {
+        return github.update_check_run(owner, repo, run.id, status).await;
+    }
+    let id = github.create_check_run(owner, repo, sha, status).await?;
+    sqlx::query(
+        "UPDATE feedback SET metadata = COALESCE(metadata, '{}'::jsonb) || jsonb_build_object($2::text, $3::jsonb) WHERE id = $1",
+    )
+    .bind(feedback_id)
+    .bind(CHECK_RUN_KEY)
+    .bind(serde_json::to_value(OpenCheckRun {
+        id,
+        sha: sha.to_string(),
+    })?)
+    .execute(pool)
+    .await
+    .context("Failed to keep the feedback's check run")?;
+    Ok(())
+}
+
+/// 🔁 The queue retries a failed commit stage; only the last attempt gives the feedback up
+/// (or the first, when the repository is gone or the error can't be retried)
+async fn retry_or_give_up(
+    ctx: &JobContext<'_>,
+    feedback: &mut Feedback,
+    e: anyhow::Error,
+) -> Result<()> {
+    let github = ctx.app_state.github.as_ref();
+    if let Some(unavailable) = availability::confirm(github, &feedback.repository, &e).await {
+        return give_up_on_repository(ctx.app_state, feedback, unavailable).await;
+    }
+    if !is_last_attempt(ctx, &e) {
+        return Err(e);
+    }
+    // 🧾 The feedback fails together with its job, never one without the other
+    let message = format!("{:#}", e);
+    let mut tx = ctx.app_state.tx().await?;
+    let result = async {
+        let failed =
+            set_status(&mut tx, feedback.id, FeedbackStatus::Failed, Some(&message)).await?;
+        outbox::record_status_change(&mut tx, &failed).await?;
+        super::park(&mut *tx, ctx.job.id, &message, JobErrorKind::classify(&e)).await?;
+        Ok(failed)
+    }
+    .await;
+    *feedback = tx.finish(result).await?;
+    ctx.app_state
+        .events
+        .publish(AppEvent::FeedbackStatusChanged {
+            id: feedback.id,
+            status: feedback.status.clone(),
+        });
+    Err(e)
+}
+
+/// 🪦 Will the queue park the job after this error (out of retries, or not worth one)?
+fn is_last_attempt(ctx: &JobContext<'_>, e: &anyhow::Error) -> bool {
+    !JobErrorKind::classify(e).is_retryable() || ctx.job.retries >= ctx.job.max_retries
+}
+
+/// 🚪 The repository went away: fail the feedback for good and deactivate its project.
+/// The job is parked without retries.
+async fn give_up_on_repository(
+    app_state: &AppState,
+    feedback: &mut Feedback,
+    unavailable: RepositoryUnavailable,
+) -> Result<()> {
+    let mut tx = app_state.tx().await?;
+    let result = async {
+        let failed = set_status(
+            &mut tx,
+            feedback.id,
+            FeedbackStatus::Failed,
+            Some(REPOSITORY_UNAVAILABLE),
+        )
+        .await?;
+        outbox::record_status_change(&mut tx, &failed).await?;
+        let deactivated =
+            availability::mark_unavailable(&mut tx, &unavailable, Some(feedback.id)).await?;
+        Ok((failed, deactivated))
+    }
+    .await;
+    let (failed, deactivated) = tx.finish(result).await?;
+    *feedback = failed;
+    error!(
+        "🚪 {} - feedback {} failed, {} project(s) deactivated",
+        unavailable,
+        feedback.id,
+        deactivated.len()
+    );
+    app_state.events.publish(AppEvent::FeedbackStatusChanged {
+        id: feedback.id,
+        status: feedback.status.clone(),
+    });
+    Err(JobError::permanent(unavailable.to_string()).into())
+}
+
+/// 🔔 Tells the project owner that changes are waiting for their decision
+pub struct ApprovalRequestConsumer;
+
+#[async_trait]
+impl OutboxConsumer for ApprovalRequestConsumer {
+    fn name(&self) -> &'static str {
+        "approval_request"
+    }
+
+    fn wants(&self, event_type: &str) -> bool {
+        event_type == APPROVAL_REQUESTED_EVENT
+    }
+
+    async fn consume(&self, event: &OutboxEvent, conn: &mut PgConnection) -> Result<()> {
+        let feedback_id = event
+            .feedback_id
+            .context("Approval event without feedback")?;
+        let repository = event.payload["repository"].as_str().unwrap_or_default();
+        let expires_at = event.payload["expires_at"].as_str().unwrap_or("soon");
+        sqlx::query(
+            r#"
+            INSERT INTO notifications (user_id, notification_type, title, content, related_id)
+            SELECT DISTINCT p.owner_id, 'approval_requested'::notification_type, $2, $3, $4
+            FROM projects p
+            JOIN project_repository_routes r ON r.project_id = p.id
+            WHERE r.repository = $1 AND p.require_approval
+            "#,
+        )
+        .bind(repository)
+        .bind(format!("Changes for {} await your approval", repository))
+        .bind(format!(
+            "Feedback {} has {} proposed change(s). Approve or reject them before {}, or they expire.",
+            feedback_id,
+            event.payload["changes"].as_u64().unwrap_or_default(),
+            expires_at
+        ))
+        .bind(feedback_id)
+        .execute(conn)
+        .await
+        .context("Failed to write approval notification")?;
+        Ok(())
+    }
+}
+
+// 🧪 Tests - Nothing ships until somebody says so!
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::github::{protection::BranchProtection, ChangeType};
+    use crate::jobs::outbox::OutboxDispatcher;
+    use crate::test_support::{spawn_test_app, spawn_test_app_with_config, GitHubCall};
+
+    /// 👤 A project owner with `require_approval` on for 8b-is/smart-tree
+    async fn approval_project(pool: &PgPool) -> Uuid {
+        let owner_id: Uuid = sqlx::query_scalar(
+            "INSERT INTO users (email, name, password_hash) VALUES ('owner@example.com', 'Owner', 'x') RETURNING id",
+        )
+        .fetch_one(pool)
+        .await
+        .unwrap();
+        sqlx::query(
+            "INSERT INTO projects (owner_id, repository, require_approval) VALUES ($1, '8b-is/smart-tree', TRUE)",
+        )
+        .bind(owner_id)
+        .execute(pool)
+        .await
+        .unwrap();
+        owner_id
+    }
+
+    async fn held_feedback(app_state: &AppState) -> Feedback {
+        let pool = &app_state.db_pool;
+        let mut feedback = Feedback::create(
+            pool,
+            None,
+            "8b-is/smart-tree".to_string(),
+            "The README should mention the --mode flag".to_string(),
+            None,
+            25,
+            None,
+            "cli",
+        )
+        .await
+        .unwrap();
+        let changes = vec![CodeImprovement {
+            file_path: "README.md".to_string(),
+            description: "Document --mode".to_string(),
+            change_type: ChangeType::Append,
+            original_content: None,
+            new_content: "\nUse `--mode` to pick an output format.\n".to_string(),
+            line_number: None,
+        }];
+        let gated = gate(pool, &mut feedback, changes, 14).await.unwrap();
+        assert!(matches!(gated, ApprovalGate::Held));
+        feedback
+    }
+
+    async fn verification(pool: &PgPool, id: Uuid) -> Value {
+        sqlx::query_scalar(
+            "SELECT payload FROM outbox_events WHERE event_type = $1 AND feedback_id = $2",
+        )
+        .bind(COMMIT_VERIFIED_EVENT)
+        .bind(id)
+        .fetch_one(pool)
+        .await
+        .unwrap()
+    }
+
+    async fn status_of(pool: &PgPool, id: Uuid) -> (FeedbackStatus, Option<String>) {
+        sqlx::query_as("SELECT status, error_message FROM feedback WHERE id = $1")
+            .bind(id)
+            .fetch_one(pool)
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_approve_opens_the_pull_request_once() {
+        let Some(app) = spawn_test_app().await else {
+            return;
+        };
+        let pool = &app.db_pool;
+        let owner_id = approval_project(pool).await;
+        let feedback = held_feedback(&app.app_state).await;
+        assert_eq!(feedback.status, FeedbackStatus::AwaitingApproval);
+
+        // 🔔 The owner hears about it through the outbox
+        OutboxDispatcher::builtin()
+            .dispatch_due(pool)
+            .await
+            .unwrap();
+        let notified: Vec<(Uuid, String)> = sqlx::query_as(
+            "SELECT user_id, notification_type::text FROM notifications WHERE related_id = $1",
+        )
+        .bind(feedback.id)
+        .fetch_all(pool)
+        .await
+        .unwrap();
+        assert_eq!(notified, vec![(owner_id, "approval_requested".to_string())]);
+
+        let DecisionOutcome::Decided(approved) =
+            decide(pool, feedback.id, Decision::Approved, Some(owner_id))
+                .await
+                .unwrap()
+        else {
+            panic!("expected the approval to be recorded");
+        };
+        assert_eq!(approved.status, FeedbackStatus::CreatingPullRequest);
+        // 🔁 Approving again changes nothing and queues nothing
+        assert!(matches!(
+            decide(pool, feedback.id, Decision::Approved, Some(owner_id))
+                .await
+                .unwrap(),
+            DecisionOutcome::AlreadyDecided(Decision::Approved)
+        ));
+        let queued: i64 =
+            sqlx::query_scalar("SELECT COUNT(*) FROM background_jobs WHERE job_type = $1")
+                .bind(COMMIT_APPROVED_JOB)
+                .fetch_one(pool)
+                .await
+                .unwrap();
+        assert_eq!(queued, 1);
+        // 🧭 The PR body says who asked for what
+        sqlx::query(
+            r#"UPDATE feedback SET user_id = $2, metadata = '{"category": "docs", "examples": ["st --help"]}' WHERE id = $1"#,
+        )
+        .bind(feedback.id)
+        .bind(owner_id)
+        .execute(pool)
+        .await
+        .unwrap();
+
+        // ✂️ GitHub dropping the final newline still verifies
+        *app.github.race_commit_with.lock().unwrap() = Some((
+            "README.md".to_string(),
+            "\nUse `--mode` to pick an output format.".to_string(),
+        ));
+        crate::jobs::run_due_jobs(&app.app_state).await.unwrap();
+        let done = Feedback::find_by_id(pool, feedback.id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(done.status, FeedbackStatus::Completed);
+        let branch = format!("feedbacker/feedback-{}", feedback.id);
+        assert_eq!(
+            app.github.calls(),
+            vec![
+                GitHubCall::CommitChanges {
+                    repo: "8b-is/smart-tree".to_string(),
+                    branch: branch.clone(),
+                    files: vec!["README.md".to_string()],
+                },
+                GitHubCall::OpenPullRequest {
+                    repo: "8b-is/smart-tree".to_string(),
+                    branch,
+                    notes: String::new(),
+                }
+            ]
+        );
+        assert_eq!(
+            done.pull_request_url.as_deref(),
+            Some("https://github.com/8b-is/smart-tree/pull/2")
+        );
+        let bodies = app.github.pr_bodies.lock().unwrap().clone();
+        assert!(bodies[0].contains(&format!(
+            "[feedback {id}]({}/feedback/{id})",
+            app.app_state.config.server.public_base_url,
+            id = feedback.id
+        )));
+        assert!(bodies[0].contains("**Submitted by:** Owner · **Category:** docs"));
+        assert!(bodies[0].contains("1. st --help"));
+        // 🔄 Kept for the nightly refresh, matching what GitHub got
+        let (template, hash): (String, String) = sqlx::query_as(
+            "SELECT body_template, body_hash FROM feedback_pull_requests WHERE feedback_id = $1",
+        )
+        .bind(feedback.id)
+        .fetch_one(pool)
+        .await
+        .unwrap();
+        assert!(template.contains("**Votes:** {votes}{also_reported_by}"));
+        assert_eq!(hash, pr_body::body_hash(&bodies[0]));
+        assert_eq!(verification(pool, feedback.id).await["verified"], true);
+
+        // 🚦 The branch head shows the progress, ending with a link to the PR
+        let head = format!("{:040x}", 1);
+        assert_eq!(
+            *app.github.statuses.lock().unwrap(),
+            vec![
+                (
+                    "8b-is/smart-tree".to_string(),
+                    head.clone(),
+                    "status",
+                    CommitStatus::generating()
+                ),
+                (
+                    "8b-is/smart-tree".to_string(),
+                    head,
+                    "status",
+                    CommitStatus::proposed("https://github.com/8b-is/smart-tree/pull/2")
+                ),
+            ]
+        );
+        println!("✅ Approval state machine test passed!");
+    }
+
+    #[tokio::test]
+    async fn test_submitted_changes_reach_the_pull_request_with_or_without_sign_off() {
+        let Some(app) = spawn_test_app().await else {
+            return;
+        };
+        let pool = &app.db_pool;
+        let owner_id = approval_project(pool).await;
+        sqlx::query("INSERT INTO projects (owner_id, repository) VALUES ($1, '8b-is/feedbacker')")
+            .bind(owner_id)
+            .execute(pool)
+            .await
+            .unwrap();
+        let submit = |repository: &'static str| {
+            let app_state = app.app_state.clone();
+            async move {
+                let mut feedback = Feedback::create(
+                    &app_state.db_pool,
+                    None,
+                    repository.to_string(),
+                    "The README should mention the --mode flag".to_string(),
+                    None,
+                    25,
+                    None,
+                    "cli",
+                )
+                .await
+                .unwrap();
+                let changes = vec![CodeImprovement {
+                    file_path: "README.md".to_string(),
+                    description: "Document --mode".to_string(),
+                    change_type: ChangeType::Append,
+                    original_content: None,
+                    new_content: "\nUse `--mode` to pick an output format.\n".to_string(),
+                    line_number: None,
+                }];
+                let gated = submit_changes(&app_state, &mut feedback, changes)
+                    .await
+                    .unwrap();
+                (feedback, gated)
+            }
+        };
+        let queued = || async {
+            sqlx::query_scalar::<_, i64>("SELECT COUNT(*) FROM background_jobs WHERE job_type = $1")
+                .bind(COMMIT_APPROVED_JOB)
+                .fetch_one(pool)
+                .await
+                .unwrap()
+        };
+
+        // ✋ The project that wants sign-off holds the changes, with nothing queued
+        let (held, gated) = submit("8b-is/smart-tree").await;
+        assert!(matches!(gated, ApprovalGate::Held));
+        assert_eq!(held.status, FeedbackStatus::AwaitingApproval);
+        assert!(patch::stored_patch(held.metadata.as_ref()).is_some());
+        assert_eq!(queued().await, 0);
+        crate::jobs::run_due_jobs(&app.app_state).await.unwrap();
+        assert!(app.github.calls().is_empty());
+
+        // ➡️ The other goes straight to the commit stage
+        let (direct, gated) = submit("8b-is/feedbacker").await;
+        assert!(matches!(gated, ApprovalGate::Proceed(ref changes) if changes.len() == 1));
+        assert_eq!(direct.status, FeedbackStatus::CreatingPullRequest);
+        assert_eq!(queued().await, 1);
+        crate::jobs::run_due_jobs(&app.app_state).await.unwrap();
+        assert_eq!(
+            status_of(pool, direct.id).await.0,
+            FeedbackStatus::Completed
+        );
+        assert_eq!(
+            status_of(pool, held.id).await.0,
+            FeedbackStatus::AwaitingApproval
+        );
+        // 🔁 ...and its stored changes can't be approved a second time
+        assert!(matches!(
+            decide(pool, direct.id, Decision::Approved, Some(owner_id))
+                .await
+                .unwrap(),
+            DecisionOutcome::AlreadyDecided(Decision::Approved)
+        ));
+
+        // ✅ Approving the held one opens its PR too
+        decide(pool, held.id, Decision::Approved, Some(owner_id))
+            .await
+            .unwrap();
+        crate::jobs::run_due_jobs(&app.app_state).await.unwrap();
+        assert_eq!(status_of(pool, held.id).await.0, FeedbackStatus::Completed);
+        let opened: Vec<String> = app
+            .github
+            .calls()
+            .into_iter()
+            .filter_map(|call| match call {
+                GitHubCall::OpenPullRequest { repo, .. } => Some(repo),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(opened, vec!["8b-is/feedbacker", "8b-is/smart-tree"]);
+        println!("✅ Submitted changes end-to-end test passed!");
+    }
+
+    #[tokio::test]
+    async fn test_one_check_run_follows_the_commit_stage() {
+        let Some(app) = spawn_test_app_with_config(|config| {
+            config.github.status_reporting = StatusReporting::CheckRun
+        })
+        .await
+        else {
+            return;
+        };
+        let pool = &app.db_pool;
+        let owner_id = approval_project(pool).await;
+        let feedback = held_feedback(&app.app_state).await;
+        decide(pool, feedback.id, Decision::Approved, Some(owner_id))
+            .await
+            .unwrap();
+        crate::jobs::run_due_jobs(&app.app_state).await.unwrap();
+        assert_eq!(
+            status_of(pool, feedback.id).await.0,
+            FeedbackStatus::Completed
+        );
+
+        // ✅ Opened once on the branch head, then moved along instead of piling up runs
+        let head = format!("{:040x}", 1);
+        let repo = "8b-is/smart-tree".to_string();
+        assert_eq!(
+            *app.github.statuses.lock().unwrap(),
+            vec![
+                (
+                    repo.clone(),
+                    head.clone(),
+                    "check_run",
+                    CommitStatus::generating()
+                ),
+                (
+                    repo,
+                    head.clone(),
+                    "check_run_update",
+                    CommitStatus::proposed("https://github.com/8b-is/smart-tree/pull/2")
+                ),
+            ]
+        );
+        let kept: Value = sqlx::query_scalar("SELECT metadata->$2 FROM feedback WHERE id = $1")
+            .bind(feedback.id)
+            .bind(CHECK_RUN_KEY)
+            .fetch_one(pool)
+            .await
+            .unwrap();
+        assert_eq!(kept, serde_json::json!({ "id": 1, "sha": head }));
+        println!("✅ Check run progress test passed!");
+    }
+
+    #[tokio::test]
+    async fn test_errors_not_worth_retrying_fail_the_feedback_with_its_job() {
+        let Some(app) = spawn_test_app().await else {
+            return;
+        };
+        let pool = &app.db_pool;
+        let owner_id = approval_project(pool).await;
+        let mut feedback = held_feedback(&app.app_state).await;
+        decide(pool, feedback.id, Decision::Approved, Some(owner_id))
+            .await
+            .unwrap();
+        let job = crate::jobs::claim_next(pool).await.unwrap().unwrap();
+        assert!(job.retries < job.max_retries);
+        let ctx = JobContext {
+            app_state: &app.app_state,
+            job: &job,
+        };
+
+        // 🔁 A transient error with retries left leaves everything to the next attempt
+        assert!(
+            retry_or_give_up(&ctx, &mut feedback, anyhow::anyhow!("GitHub timed out"))
+                .await
+                .is_err()
+        );
+        assert_eq!(
+            status_of(pool, feedback.id).await.0,
+            FeedbackStatus::CreatingPullRequest
+        );
+
+        // 🪦 One that can't be retried gives up on the first attempt, job and feedback together
+        let error = JobError::validation("GitHub rejected the change").into();
+        assert!(retry_or_give_up(&ctx, &mut feedback, error).await.is_err());
+        assert_eq!(feedback.status, FeedbackStatus::Failed);
+        assert_eq!(
+            status_of(pool, feedback.id).await,
+            (
+                FeedbackStatus::Failed,
+                Some("GitHub rejected the change".to_string())
+            )
+        );
+        let parked: (String, Option<String>) =
+            sqlx::query_as("SELECT status, error_kind FROM background_jobs WHERE id = $1")
+                .bind(job.id)
+                .fetch_one(pool)
+                .await
+                .unwrap();
+        assert_eq!(
+            parked,
+            ("failed".to_string(), Some("validation".to_string()))
+        );
+        let announced: i64 = sqlx::query_scalar(
+            "SELECT COUNT(*) FROM outbox_events WHERE feedback_id = $1 AND event_type = 'feedback.failed'",
+        )
+        .bind(feedback.id)
+        .fetch_one(pool)
+        .await
+        .unwrap();
+        assert_eq!(announced, 1);
+        println!("✅ Give-up classification test passed!");
+    }
+
+    #[tokio::test]
+    async fn test_a_path_denied_after_approval_stops_the_commit() {
+        let Some(app) = spawn_test_app().await else {
+            return;
+        };
+        let pool = &app.db_pool;
+        let owner_id = approval_project(pool).await;
+        let feedback = held_feedback(&app.app_state).await;
+        decide(pool, feedback.id, Decision::Approved, Some(owner_id))
+            .await
+            .unwrap();
+        // 🛡️ The owner locks the README down before the commit stage runs
+        sqlx::query(r#"UPDATE projects SET config = '{"paths": {"denied": ["*.md"]}}'"#)
+            .execute(pool)
+            .await
+            .unwrap();
+
+        crate::jobs::run_due_jobs(&app.app_state).await.unwrap();
+        assert!(app.github.calls().is_empty());
+        let (status, error) = status_of(pool, feedback.id).await;
+        assert_eq!(status, FeedbackStatus::Failed);
+        assert!(error.unwrap().contains("README.md"));
+        let dropped: Value =
+            sqlx::query_scalar("SELECT metadata->'dropped_paths' FROM feedback WHERE id = $1")
+                .bind(feedback.id)
+                .fetch_one(pool)
+                .await
+                .unwrap();
+        assert_eq!(dropped[0]["file_path"], "README.md");
+        // 🪦 Retrying can't help, so the job isn't retried
+        let job: (String, i32) =
+            sqlx::query_as("SELECT status, retries FROM background_jobs WHERE job_type = $1")
+                .bind(COMMIT_APPROVED_JOB)
+                .fetch_one(pool)
+                .await
+                .unwrap();
+        assert_eq!(job, ("failed".to_string(), 0));
+        println!("✅ Commit-time path policy test passed!");
+    }
+
+    #[tokio::test]
+    async fn test_a_raced_branch_is_deleted_instead_of_opened() {
+        let Some(app) = spawn_test_app().await else {
+            return;
+        };
+        let pool = &app.db_pool;
+        let owner_id = approval_project(pool).await;
+        sqlx::query("UPDATE users SET role = 'admin' WHERE id = $1")
+            .bind(owner_id)
+            .execute(pool)
+            .await
+            .unwrap();
+        let feedback = held_feedback(&app.app_state).await;
+        decide(pool, feedback.id, Decision::Approved, Some(owner_id))
+            .await
+            .unwrap();
+
+        // 🏁 Another process pushes to the branch right after our commit
+        *app.github.race_commit_with.lock().unwrap() = Some((
+            "README.md".to_string(),
+            "Something else entirely\n".to_string(),
+        ));
+        crate::jobs::run_due_jobs(&app.app_state).await.unwrap();
+        assert_eq!(
+            status_of(pool, feedback.id).await,
+            (
+                FeedbackStatus::Failed,
+                Some(POST_COMMIT_MISMATCH.to_string())
+            )
+        );
+        let branch = format!("feedbacker/feedback-{}", feedback.id);
+        assert_eq!(
+            app.github.calls()[1..],
+            [GitHubCall::DeleteBranch {
+                repo: "8b-is/smart-tree".to_string(),
+                branch,
+            }]
+        );
+        assert!(app.github.branch_files.lock().unwrap().is_empty());
+        // 🚦 Never reported as proposed
+        let statuses = app.github.statuses.lock().unwrap().clone();
+        assert_eq!(statuses.len(), 1);
+        assert_eq!(statuses[0].3, CommitStatus::generating());
+
+        let recorded = verification(pool, feedback.id).await;
+        assert_eq!(recorded["verified"], false);
+        assert_eq!(recorded["files"][0]["path"], "README.md");
+        assert_ne!(
+            recorded["files"][0]["expected"],
+            recorded["files"][0]["found"]
+        );
+
+        // 🚨 Ops hear about it through the failed event
+        OutboxDispatcher::builtin()
+            .dispatch_due(pool)
+            .await
+            .unwrap();
+        let alerts: i64 = sqlx::query_scalar(
+            "SELECT COUNT(*) FROM notifications WHERE related_id = $1 AND notification_type = 'feedback_failed' AND user_id = $2",
+        )
+        .bind(feedback.id)
+        .bind(owner_id)
+        .fetch_one(pool)
+        .await
+        .unwrap();
+        assert_eq!(alerts, 1);
+        println!("✅ Post-commit mismatch test passed!");
+    }
+
+    #[tokio::test]
+    async fn test_protected_base_branches_are_noted_and_not_merged() {
+        let Some(app) = spawn_test_app().await else {
+            return;
+        };
+        let pool = &app.db_pool;
+        let owner_id = approval_project(pool).await;
+        sqlx::query(
+            r#"UPDATE projects SET config = '{"config_version": 3, "pull_requests": {"auto_merge": true, "body_template": "Answers {feedback_url}\n\n{notes}"}}'"#,
+        )
+        .execute(pool)
+        .await
+        .unwrap();
+        let approve_and_commit = || async {
+            let feedback = held_feedback(&app.app_state).await;
+            decide(pool, feedback.id, Decision::Approved, Some(owner_id))
+                .await
+                .unwrap();
+            crate::jobs::run_due_jobs(&app.app_state).await.unwrap();
+            assert_eq!(
+                status_of(pool, feedback.id).await,
+                (FeedbackStatus::Completed, None)
+            );
+            app.github.calls()
+        };
+
+        // 🔒 Required reviews and checks go in the body, and nothing is merged
+        app.github.protections.lock().unwrap().insert(
+            "8b-is/smart-tree:main".to_string(),
+            BranchProtection {
+                required_approving_reviews: 1,
+                required_status_checks: vec!["ci/test".to_string()],
+                ..Default::default()
+            },
+        );
+        let calls = approve_and_commit().await;
+        let Some(GitHubCall::OpenPullRequest { notes, .. }) = calls.last() else {
+            panic!("expected the PR to be the last call, got {:?}", calls);
+        };
+        assert!(notes.contains("- 1 approving review\n"));
+        assert!(notes.contains("- Passing checks: `ci/test`\n"));
+        // 📝 The project's own template shapes the body
+        let body = app
+            .github
+            .pr_bodies
+            .lock()
+            .unwrap()
+            .last()
+            .cloned()
+            .unwrap();
+        assert!(body.starts_with("Answers http"));
+        assert!(body.contains("- Passing checks: `ci/test`\n"));
+
+        // 🔓 Once the branch is unprotected, the PR is merged right away
+        app.github.protections.lock().unwrap().clear();
+        let calls = approve_and_commit().await;
+        assert!(matches!(
+            &calls[calls.len() - 2..],
+            [
+                GitHubCall::OpenPullRequest { notes, .. },
+                GitHubCall::MergePullRequest { number: 4, .. }
+            ] if notes.is_empty()
+        ));
+        println!("✅ Branch protection awareness test passed!");
+    }
+
+    #[tokio::test]
+    async fn test_reject_and_expiry_fail_the_feedback() {
+        let Some(app) = spawn_test_app().await else {
+            return;
+        };
+        let pool = &app.db_pool;
+        let owner_id = approval_project(pool).await;
+
+        let rejected = held_feedback(&app.app_state).await;
+        assert!(matches!(
+            decide(pool, rejected.id, Decision::Rejected, Some(owner_id))
+                .await
+                .unwrap(),
+            DecisionOutcome::Decided(_)
+        ));
+        assert_eq!(
+            status_of(pool, rejected.id).await,
+            (FeedbackStatus::Failed, Some(REJECTED_BY_OWNER.to_string()))
+        );
+        // 🚫 A late approval can't undo the rejection
+        assert!(matches!(
+            decide(pool, rejected.id, Decision::Approved, Some(owner_id))
+                .await
+                .unwrap(),
+            DecisionOutcome::AlreadyDecided(Decision::Rejected)
+        ));
+
+        // ⌛ Only approvals past their window expire
+        let overdue = held_feedback(&app.app_state).await;
+        let fresh = held_feedback(&app.app_state).await;
+        sqlx::query(
+            "UPDATE feedback_approvals SET expires_at = NOW() - INTERVAL '1 minute' WHERE feedback_id = $1",
+        )
+        .bind(overdue.id)
+        .execute(pool)
+        .await
+        .unwrap();
+        assert_eq!(expire_overdue(&app.app_state).await.unwrap(), 1);
+        assert_eq!(
+            status_of(pool, overdue.id).await,
+            (FeedbackStatus::Failed, Some(APPROVAL_EXPIRED.to_string()))
+        );
+        assert_eq!(
+            status_of(pool, fresh.id).await,
+            (FeedbackStatus::AwaitingApproval, None)
+        );
+        assert_eq!(expire_overdue(&app.app_state).await.unwrap(), 0);
+
+        // 🤷 Feedback that was never held can't be decided on
+        let mut plain = Feedback::find_by_id(pool, fresh.id).await.unwrap().unwrap();
+        plain.id = Uuid::new_v4();
+        assert!(matches!(
+            decide(pool, plain.id, Decision::Approved, None)
+                .await
+                .unwrap(),
+            DecisionOutcome::NotHeld
+        ));
+        assert!(app.github.calls().is_empty());
+        println!("✅ Approval rejection and expiry test passed!");
+    }
+
+    #[tokio::test]
+    async fn test_a_repository_lost_mid_commit_fails_for_good_and_deactivates_the_project() {
+        for lost_at in ["commit_changes", "file_content", "open_pull_request"] {
+            let Some(app) = spawn_test_app().await else {
+                return;
+            };
+            let pool = &app.db_pool;
+            let owner_id = approval_project(pool).await;
+            let feedback = held_feedback(&app.app_state).await;
+            decide(pool, feedback.id, Decision::Approved, Some(owner_id))
+                .await
+                .unwrap();
+
+            // 🚪 The repository is deleted while the commit stage runs
+            *app.github.lose_access_at.lock().unwrap() =
+                Some(("8b-is/smart-tree".to_string(), lost_at));
+            crate::jobs::run_due_jobs(&app.app_state).await.unwrap();
+            assert_eq!(
+                status_of(pool, feedback.id).await,
+                (
+                    FeedbackStatus::Failed,
+                    Some(REPOSITORY_UNAVAILABLE.to_string())
+                ),
+                "lost at {}",
+                lost_at
+            );
+            // 🪦 Parked on the first attempt, no retries
+            let (job_status, kind, retries): (String, Option<String>, i32) = sqlx::query_as(
+                "SELECT status, error_kind, retries FROM background_jobs WHERE job_type = $1",
+            )
+            .bind(COMMIT_APPROVED_JOB)
+            .fetch_one(pool)
+            .await
+            .unwrap();
+            assert_eq!(
+                (job_status.as_str(), kind.as_deref(), retries),
+                ("failed", Some("permanent"), 0),
+                "lost at {}",
+                lost_at
+            );
+            assert!(!app
+                .github
+                .calls()
+                .iter()
+                .any(|call| matches!(call, GitHubCall::OpenPullRequest { .. })));
+
+            // 🔒 The project is paused and its owner told why
+            let active: bool = sqlx::query_scalar(
+                "SELECT is_active FROM projects WHERE repository = '8b-is/smart-tree'",
+            )
+            .fetch_one(pool)
+            .await
+            .unwrap();
+            assert!(!active);
+            let (notified, content): (Uuid, String) = sqlx::query_as(
+                "SELECT user_id, content FROM notifications WHERE title = 'Project deactivated'",
+            )
+            .fetch_one(pool)
+            .await
+            .unwrap();
+            assert_eq!(notified, owner_id);
+            assert!(content.contains("8b-is/smart-tree is unavailable"));
+            assert!(availability::unavailable(pool, "8B-is/Smart-Tree")
+                .await
+                .unwrap()
+                .is_some());
+        }
+        println!("✅ Lost repository test passed!");
+    }
+
+    #[tokio::test]
+    async fn test_a_real_404_from_github_parks_the_job_and_marks_the_repository_unavailable() {
+        use crate::github::{client::GitHubClient, cooldown::CooldownGate};
+        use std::sync::Arc;
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let Some(app) = spawn_test_app().await else {
+            return;
+        };
+        let pool = &app.db_pool;
+        let owner_id = approval_project(pool).await;
+        let feedback = held_feedback(&app.app_state).await;
+        decide(pool, feedback.id, Decision::Approved, Some(owner_id))
+            .await
+            .unwrap();
+
+        // 🌐 A real client against a GitHub where the repository was deleted: every
+        // call answers 404 the way GitHub does, the access check included
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/repos/8b-is/smart-tree"))
+            .respond_with(ResponseTemplate::new(404).set_body_json(serde_json::json!({
+                "message": "Not Found",
+                "documentation_url": "https://docs.github.com/rest/repos/repos#get-a-repository"
+            })))
+            .expect(1..)
+            .mount(&server)
+            .await;
+        Mock::given(wiremock::matchers::any())
+            .respond_with(ResponseTemplate::new(404).set_body_json(serde_json::json!({
+                "message": "Not Found",
+                "documentation_url": "https://docs.github.com/rest"
+            })))
+            .mount(&server)
+            .await;
+        let mut app_state = app.app_state.clone();
+        app_state.github = Arc::new(
+            GitHubClient::new(
+                "test_token",
+                &server.uri(),
+                app_state.github_throttle.clone(),
+                CooldownGate::from_config(&app_state.config.github),
+                app_state.github_requests.clone(),
+            )
+            .unwrap(),
+        );
+
+        crate::jobs::run_due_jobs(&app_state).await.unwrap();
+        assert_eq!(
+            status_of(pool, feedback.id).await,
+            (
+                FeedbackStatus::Failed,
+                Some(REPOSITORY_UNAVAILABLE.to_string())
+            )
+        );
+        let (job_status, kind, retries): (String, Option<String>, i32) = sqlx::query_as(
+            "SELECT status, error_kind, retries FROM background_jobs WHERE job_type = $1",
+        )
+        .bind(COMMIT_APPROVED_JOB)
+        .fetch_one(pool)
+        .await
+        .unwrap();
+        assert_eq!(
+            (job_status.as_str(), kind.as_deref(), retries),
+            ("failed", Some("permanent"), 0)
+        );
+        let unavailable = availability::unavailable(pool, "8b-is/smart-tree")
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(
+            unavailable.reason.starts_with("not found"),
+            "{:?}",
+            unavailable
+        );
+        let active: bool = sqlx::query_scalar(
+            "SELECT is_active FROM projects WHERE repository = '8b-is/smart-tree'",
+        )
+        .fetch_one(pool)
+        .await
+        .unwrap();
+        assert!(!active);
+        println!("✅ Real 404 lost repository test passed!");
+    }
+}
@@ -0,0 +1,377 @@
+// 📞 Feedback Callbacks - We'll call you when it's done! 📞
+// When a feedback item reaches a terminal state we POST a signed status payload
+// to the submitter's callback URL, retried through the background job queue.
+// The job is queued by the outbox dispatcher, never straight from the status change.
+// Created with love by Aye & Hue! ✨
+//
+// Receivers verify `X-Feedbacker-Signature: sha256=<hex>`, an HMAC-SHA256 of the
+// raw request body keyed with the `callback_secret` returned at submission.
+// After an admin rotates that secret, `X-Feedbacker-Signature-Previous` carries the
+// old secret's signature until the overlap ends, so receivers can switch at leisure.
+// Every attempt is dated by `delivered_at` in the (signed) body. Receivers should
+// refuse deliveries dated too far from their clock, and bodies they have already seen -
+// `verify_callback` does all three checks with the same code that guards our own
+// webhook endpoints.
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{PgExecutor, PgPool};
+use std::time::Duration;
+use tracing::info;
+use uuid::Uuid;
+
+use crate::{
+    api::AppState,
+    database::models::{Feedback, FeedbackStatus},
+    utils::{
+        deliveries::{delivery_timestamp, verify_delivery, DeliveryRejection, ReplayCache},
+        net::resolve_outbound_url,
+        signatures::{sign, SecretMatch, SecretPair},
+    },
+};
+
+use super::{JobContext, JobError, JobHandler};
+
+/// 🏷️ Job type for callback deliveries
+pub const FEEDBACK_CALLBACK_JOB: &str = "feedback_callback";
+/// 🔏 Header carrying the payload signature
+pub const SIGNATURE_HEADER: &str = "x-feedbacker-signature";
+/// 🔏 Same signature made with the rotated-out secret, sent only during its overlap window
+pub const PREVIOUS_SIGNATURE_HEADER: &str = "x-feedbacker-signature-previous";
+/// 📣 Header naming the event
+pub const EVENT_HEADER: &str = "x-feedbacker-event";
+/// 🆔 Header with the delivery (job) id, stable across retries
+pub const DELIVERY_HEADER: &str = "x-feedbacker-delivery";
+/// ⏱️ How long we wait for the receiver
+const DELIVERY_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// 📦 What the submitter's endpoint receives
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct FeedbackCallbackPayload {
+    /// 📣 "feedback.completed" or "feedback.failed"
+    pub event: String,
+    pub feedback_id: Uuid,
+    pub repository: String,
+    pub status: FeedbackStatus,
+    pub pull_request_url: Option<String>,
+    pub error_message: Option<String>,
+    pub completed_at: Option<DateTime<Utc>>,
+    /// ⏱️ When this attempt was sent (set on every delivery, so retries are fresh)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub delivered_at: Option<DateTime<Utc>>,
+}
+
+/// 📋 Job payload - the secret stays in the feedback row, not in the queue
+#[derive(Debug, Serialize, Deserialize)]
+struct CallbackJob {
+    url: String,
+    body: FeedbackCallbackPayload,
+}
+
+impl FeedbackCallbackPayload {
+    /// 📸 Snapshot of a feedback item that just reached a terminal state
+    pub fn for_feedback(feedback: &Feedback) -> Self {
+        Self {
+            event: if feedback.status == FeedbackStatus::Completed {
+                super::outbox::FEEDBACK_COMPLETED_EVENT
+            } else {
+                super::outbox::FEEDBACK_FAILED_EVENT
+            }
+            .to_string(),
+            feedback_id: feedback.id,
+            repository: feedback.repository.clone(),
+            status: feedback.status.clone(),
+            pull_request_url: feedback.pull_request_url.clone(),
+            error_message: feedback.error_message.clone(),
+            completed_at: feedback.completed_at,
+            delivered_at: None,
+        }
+    }
+}
+
+/// 📥 Queue delivery of `body` to a callback URL (inside the caller's transaction, if any)
+pub async fn enqueue_callback(
+    executor: impl PgExecutor<'_>,
+    url: String,
+    body: FeedbackCallbackPayload,
+    priority: i32,
+) -> Result<Uuid> {
+    super::enqueue_with_priority(
+        executor,
+        FEEDBACK_CALLBACK_JOB,
+        serde_json::to_value(CallbackJob { url, body })?,
+        priority,
+    )
+    .await
+}
+
+/// 🗄️ (callback_secret, callback_secret_previous, callback_secret_previous_expires_at)
+type CallbackSecretsRow = (Option<String>, Option<String>, Option<DateTime<Utc>>);
+
+/// 🔑 A feedback item's callback secret, plus the rotated-out one while it overlaps
+pub async fn callback_secrets(pool: &PgPool, feedback_id: Uuid) -> Result<Option<SecretPair>> {
+    let row: Option<CallbackSecretsRow> = sqlx::query_as(
+        "SELECT callback_secret, callback_secret_previous, callback_secret_previous_expires_at FROM feedback WHERE id = $1",
+    )
+    .bind(feedback_id)
+    .fetch_optional(pool)
+    .await
+    .context("Failed to read callback secrets")?;
+
+    Ok(row.and_then(|(primary, previous, previous_expires_at)| {
+        Some(SecretPair {
+            primary: primary?,
+            previous,
+            previous_expires_at,
+        })
+    }))
+}
+
+/// 🔄 Replace a feedback item's callback secret, keeping the old one for `overlap`.
+/// Returns the new secret and when the old one stops being sent, or None without a callback.
+pub async fn rotate_callback_secret(
+    pool: &PgPool,
+    feedback_id: Uuid,
+    overlap: chrono::Duration,
+) -> Result<Option<(String, DateTime<Utc>)>> {
+    let secret = crate::database::models::generate_callback_secret();
+    let expires_at = Utc::now() + overlap;
+    let rotated = sqlx::query(
+        r#"
+        UPDATE feedback
+        SET callback_secret_previous = callback_secret,
+            callback_secret_previous_expires_at = $3,
+            callback_secret = $2,
+            updated_at = NOW()
+        WHERE id = $1 AND callback_secret IS NOT NULL
+        "#,
+    )
+    .bind(feedback_id)
+    .bind(&secret)
+    .bind(expires_at)
+    .execute(pool)
+    .await
+    .context("Failed to rotate callback secret")?
+    .rows_affected();
+
+    Ok((rotated > 0).then_some((secret, expires_at)))
+}
+
+/// 📞 Delivers queued feedback callbacks
+pub struct FeedbackCallbackHandler;
+
+#[async_trait::async_trait]
+impl JobHandler for FeedbackCallbackHandler {
+    const TYPE: &'static str = FEEDBACK_CALLBACK_JOB;
+
+    async fn run(&self, payload: serde_json::Value, ctx: &JobContext<'_>) -> Result<()> {
+        deliver(ctx.app_state, ctx.job.id, payload).await
+    }
+}
+
+/// 🚀 Deliver one queued callback (errors make the queue retry it)
+pub async fn deliver(
+    app_state: &AppState,
+    delivery_id: Uuid,
+    payload: serde_json::Value,
+) -> Result<()> {
+    let callback: CallbackJob =
+        serde_json::from_value(payload).context("Invalid callback job payload")?;
+
+    let secrets = callback_secrets(&app_state.db_pool, callback.body.feedback_id)
+        .await?
+        .ok_or_else(|| JobError::permanent("Feedback or its callback secret no longer exists"))?;
+
+    // 🛡️ Re-check right before calling out, in case DNS changed since submission, and
+    // connect only to the addresses that passed
+    let target = resolve_outbound_url(
+        &callback.url,
+        app_state.config.feedback.allow_private_callbacks,
+    )
+    .await?;
+
+    let delivered_at = Utc::now();
+    let body = serde_json::to_vec(&FeedbackCallbackPayload {
+        delivered_at: Some(delivered_at),
+        ..callback.body.clone()
+    })?;
+    let client = target.client(DELIVERY_TIMEOUT)?;
+
+    let mut request = client
+        .post(target.url.clone())
+        .header(reqwest::header::CONTENT_TYPE, "application/json")
+        .header(SIGNATURE_HEADER, sign(&secrets.primary, &body));
+    if let Some(previous) = secrets.active_previous(Utc::now()) {
+        request = request.header(PREVIOUS_SIGNATURE_HEADER, sign(previous, &body));
+    }
+    request
+        .header(EVENT_HEADER, &callback.body.event)
+        .header(DELIVERY_HEADER, delivery_id.to_string())
+        .body(body)
+        .send()
+        .await
+        .context("Callback request failed")?
+        .error_for_status()
+        .context("Callback endpoint returned an error status")?;
+
+    info!(
+        "📞 Delivered {} callback for feedback {}",
+        callback.body.event, callback.body.feedback_id
+    );
+    Ok(())
+}
+
+/// 🛡️ Receiver side: check a callback delivery's signature (either header, so a receiver
+/// still holding the rotated-out secret keeps working), that it was sent within
+/// `max_skew` of `now`, and that `seen` hasn't had it before
+pub fn verify_callback(
+    secret: &str,
+    headers: &axum::http::HeaderMap,
+    body: &[u8],
+    now: DateTime<Utc>,
+    max_skew: chrono::Duration,
+    seen: &ReplayCache,
+) -> Result<SecretMatch, DeliveryRejection> {
+    let secrets = SecretPair {
+        primary: secret.to_string(),
+        previous: None,
+        previous_expires_at: None,
+    };
+    let header = |name: &str| headers.get(name).and_then(|value| value.to_str().ok());
+    let signature = [SIGNATURE_HEADER, PREVIOUS_SIGNATURE_HEADER]
+        .into_iter()
+        .filter_map(header)
+        .find(|signature| secrets.verify(body, signature, now).is_some())
+        .unwrap_or_default();
+    verify_delivery(
+        &secrets,
+        body,
+        signature,
+        delivery_timestamp(body),
+        now,
+        Some(max_skew),
+        seen,
+    )
+}
+
+// 🧪 Tests - Ringing a pretend phone!
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::spawn_test_app;
+    use std::sync::Arc;
+    use wiremock::matchers::{header, header_exists, method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    #[test]
+    fn test_sign_known_vector() {
+        // 🔢 Expected value computed independently with Python's hmac module
+        assert_eq!(
+            sign("secret", b"hello"),
+            "sha256=88aab3ede8d3adf94d26ab90d3bafd4a2083070c3bcce9c014ee04a443847c0b"
+        );
+        println!("✅ Callback signature test passed!");
+    }
+
+    #[tokio::test]
+    async fn test_terminal_status_delivers_signed_callback_with_retries() {
+        let Some(app) = spawn_test_app().await else {
+            return;
+        };
+        let mut config = (*app.app_state.config).clone();
+        config.feedback.allow_private_callbacks = true;
+        let app_state = AppState {
+            config: Arc::new(config),
+            ..app.app_state.clone()
+        };
+
+        let receiver = MockServer::start().await;
+        let mut feedback = Feedback::create(
+            &app.db_pool,
+            None,
+            "8b-is/smart-tree".to_string(),
+            "Please add a dark mode to the tree output".to_string(),
+            Some(format!("{}/hooks/feedback", receiver.uri())),
+            0,
+            None,
+            "unknown",
+        )
+        .await
+        .unwrap();
+        let secret = feedback.callback_secret.clone().unwrap();
+
+        // 💥 First attempt fails: the job goes back to pending with a retry counted
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(500))
+            .up_to_n_times(1)
+            .mount(&receiver)
+            .await;
+        feedback
+            .update_status(&app.db_pool, FeedbackStatus::Completed, None)
+            .await
+            .unwrap();
+        crate::jobs::outbox::dispatch_due(&app_state).await.unwrap();
+        assert_eq!(crate::jobs::run_due_jobs(&app_state).await.unwrap(), 1);
+        let retries: i32 = sqlx::query_scalar(
+            "SELECT retries FROM background_jobs WHERE job_type = $1 AND status = 'pending'",
+        )
+        .bind(FEEDBACK_CALLBACK_JOB)
+        .fetch_one(&app.db_pool)
+        .await
+        .unwrap();
+        assert_eq!(retries, 1);
+
+        // ✅ Make it due again; the second attempt lands with a valid signature
+        sqlx::query("UPDATE background_jobs SET scheduled_at = NOW()")
+            .execute(&app.db_pool)
+            .await
+            .unwrap();
+        Mock::given(method("POST"))
+            .and(path("/hooks/feedback"))
+            .and(header(EVENT_HEADER, "feedback.completed"))
+            .and(header_exists(SIGNATURE_HEADER))
+            .respond_with(ResponseTemplate::new(204))
+            .mount(&receiver)
+            .await;
+        assert_eq!(crate::jobs::run_due_jobs(&app_state).await.unwrap(), 1);
+
+        let requests = receiver.received_requests().await.unwrap();
+        let delivered = requests.last().unwrap();
+        assert_eq!(
+            delivered.headers[SIGNATURE_HEADER].to_str().unwrap(),
+            sign(&secret, &delivered.body)
+        );
+        let payload: FeedbackCallbackPayload = serde_json::from_slice(&delivered.body).unwrap();
+        assert_eq!(payload.feedback_id, feedback.id);
+        assert_eq!(payload.status, FeedbackStatus::Completed);
+
+        // 🛡️ What a receiver does: fresh and signed is fine once, never twice or late
+        let seen = ReplayCache::new(8);
+        let skew = chrono::Duration::minutes(5);
+        let verify = |now: DateTime<Utc>| {
+            verify_callback(
+                &secret,
+                &delivered.headers,
+                &delivered.body,
+                now,
+                skew,
+                &seen,
+            )
+        };
+        assert_eq!(
+            verify(Utc::now() + chrono::Duration::hours(1)),
+            Err(DeliveryRejection::Expired)
+        );
+        assert_eq!(verify(Utc::now()), Ok(SecretMatch::Primary));
+        assert_eq!(verify(Utc::now()), Err(DeliveryRejection::Replayed));
+        assert!(payload.delivered_at.is_some());
+
+        let status: String = sqlx::query_scalar("SELECT status FROM background_jobs")
+            .fetch_one(&app.db_pool)
+            .await
+            .unwrap();
+        assert_eq!(status, "completed");
+        println!("✅ Feedback callback delivery test passed!");
+    }
+}
@@ -0,0 +1,119 @@
+// 📈 Daily Stats Snapshots - Writing today down before it's forgotten! 📈
+// A small scheduler enqueues one daily_stats_snapshot job shortly after every
+// UTC midnight for the day that just ended; the handler upserts that date's
+// row, so a retried job or a second instance never produces a duplicate.
+// Created with love by Aye & Hue! ✨
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+use tracing::{error, info};
+
+use crate::api::{stats_history, AppState};
+
+use super::{JobContext, JobHandler};
+
+/// 🏷️ Job type for the nightly statistics snapshot
+pub const DAILY_STATS_JOB: &str = "daily_stats_snapshot";
+/// 🕛 How long after UTC midnight the snapshot is taken
+const SNAPSHOT_DELAY_AFTER_MIDNIGHT: Duration = Duration::from_secs(5 * 60);
+
+/// 📋 Job payload; without a date the snapshot is filed under today
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct DailyStatsJob {
+    pub date: Option<NaiveDate>,
+}
+
+/// 📸 Writes one day's statistics snapshot
+pub struct DailyStatsHandler;
+
+#[async_trait::async_trait]
+impl JobHandler for DailyStatsHandler {
+    const TYPE: &'static str = DAILY_STATS_JOB;
+
+    async fn run(&self, payload: serde_json::Value, ctx: &JobContext<'_>) -> Result<()> {
+        let job: DailyStatsJob =
+            serde_json::from_value(payload).context("Invalid daily stats job payload")?;
+        let date = job.date.unwrap_or_else(stats_history::today);
+        let snapshot = stats_history::snapshot_daily_stats(ctx.app_state, date).await?;
+        info!(
+            "📈 Stored statistics snapshot for {} ({} feedback, {} jobs queued)",
+            date,
+            snapshot.total_feedback,
+            snapshot.queue_depth.unwrap_or_default()
+        );
+        Ok(())
+    }
+}
+
+/// ⏰ Time until the next snapshot is due (a few minutes past the next UTC midnight)
+fn until_next_snapshot(now: DateTime<Utc>) -> Duration {
+    let next_midnight = (now.date_naive() + chrono::Duration::days(1))
+        .and_hms_opt(0, 0, 0)
+        .unwrap_or_default()
+        .and_utc();
+    (next_midnight - now).to_std().unwrap_or_default() + SNAPSHOT_DELAY_AFTER_MIDNIGHT
+}
+
+/// 🚀 Enqueue a snapshot of the day that just ended, every night
+pub fn spawn_scheduler(app_state: AppState) -> tokio::task::JoinHandle<()> {
+    tokio::spawn(async move {
+        loop {
+            tokio::time::sleep(until_next_snapshot(Utc::now())).await;
+            let finished_day = stats_history::today() - chrono::Duration::days(1);
+            let payload = serde_json::json!(DailyStatsJob {
+                date: Some(finished_day),
+            });
+            if let Err(e) = super::enqueue(&app_state.db_pool, DAILY_STATS_JOB, payload).await {
+                error!("❌ Failed to schedule the statistics snapshot: {:#}", e);
+            }
+        }
+    })
+}
+
+// 🧪 Tests - Midnight comes every day!
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_next_snapshot_is_just_after_utc_midnight() {
+        let evening: DateTime<Utc> = "2026-05-01T22:00:00Z".parse().unwrap();
+        assert_eq!(
+            until_next_snapshot(evening),
+            Duration::from_secs(2 * 3600 + 5 * 60)
+        );
+        let just_after: DateTime<Utc> = "2026-05-02T00:06:00Z".parse().unwrap();
+        assert_eq!(
+            until_next_snapshot(just_after),
+            Duration::from_secs(24 * 3600 - 60)
+        );
+        println!("✅ Snapshot schedule test passed!");
+    }
+
+    #[tokio::test]
+    async fn test_snapshot_job_runs_through_the_queue() {
+        let Some(app) = crate::test_support::spawn_test_app().await else {
+            return;
+        };
+        let date: NaiveDate = "2026-02-01".parse().unwrap();
+        for _ in 0..2 {
+            super::super::enqueue(
+                &app.db_pool,
+                DAILY_STATS_JOB,
+                serde_json::json!({ "date": date }),
+            )
+            .await
+            .unwrap();
+        }
+        assert_eq!(super::super::run_due_jobs(&app.app_state).await.unwrap(), 2);
+
+        let rows: Vec<NaiveDate> = sqlx::query_scalar("SELECT date FROM daily_stats")
+            .fetch_all(&app.db_pool)
+            .await
+            .unwrap();
+        assert_eq!(rows, vec![date]);
+        println!("✅ Snapshot job test passed!");
+    }
+}
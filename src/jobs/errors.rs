@@ -0,0 +1,200 @@
+// 🏷️ Job Errors - Why a job failed, in a word you can GROUP BY! 🏷️
+// Every failed attempt stores an `error_kind` next to the free-text `error_message`.
+// Handlers that know better return a `JobError` with an explicit kind; anything else
+// is classified from the error chain (GitHub rate limits, HTTP status codes, bad
+// payloads, database trouble). The kind also decides what the queue does next:
+// transient, rate_limited and external failures retry with backoff, while permanent
+// and validation failures are parked straight away - retrying can't fix them.
+// Created with love by Aye & Hue! ✨
+
+use std::fmt;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+use crate::github::cooldown::SecondaryRateLimited;
+
+/// 🏷️ The failure causes an operator can tell apart
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum JobErrorKind {
+    /// 🌩️ Network blips, timeouts, database hiccups - likely fine next time
+    Transient,
+    /// 🪦 Will never succeed (the thing it works on is gone, no handler, ...)
+    Permanent,
+    /// 🚦 Someone asked us to slow down
+    RateLimited,
+    /// 📋 The job itself is malformed (payload doesn't parse, invalid input)
+    Validation,
+    /// 🌍 Another service answered with an error
+    External,
+}
+
+impl JobErrorKind {
+    /// 📚 Every kind, in the order the dashboard lists them
+    pub const ALL: [JobErrorKind; 5] = [
+        JobErrorKind::Transient,
+        JobErrorKind::RateLimited,
+        JobErrorKind::External,
+        JobErrorKind::Validation,
+        JobErrorKind::Permanent,
+    ];
+
+    /// 🏷️ Value stored in `background_jobs.error_kind`
+    pub fn as_str(self) -> &'static str {
+        match self {
+            JobErrorKind::Transient => "transient",
+            JobErrorKind::Permanent => "permanent",
+            JobErrorKind::RateLimited => "rate_limited",
+            JobErrorKind::Validation => "validation",
+            JobErrorKind::External => "external",
+        }
+    }
+
+    /// 🔁 Is another attempt worth it?
+    pub fn is_retryable(self) -> bool {
+        !matches!(self, JobErrorKind::Permanent | JobErrorKind::Validation)
+    }
+
+    /// 🔍 Kind of a handler's error: an explicit `JobError` wins, then what the chain
+    /// looks like; anything unrecognised counts as transient (and is retried)
+    pub fn classify(error: &anyhow::Error) -> Self {
+        for cause in error.chain() {
+            if let Some(job_error) = cause.downcast_ref::<JobError>() {
+                return job_error.kind;
+            }
+        }
+        for cause in error.chain() {
+            if cause.is::<SecondaryRateLimited>() {
+                return JobErrorKind::RateLimited;
+            }
+            if let Some(octocrab::Error::GitHub { source, .. }) =
+                cause.downcast_ref::<octocrab::Error>()
+            {
+                let rate_limited = source.message.to_lowercase().contains("rate limit");
+                return from_status(source.status_code.as_u16(), rate_limited);
+            }
+            if let Some(http) = cause.downcast_ref::<reqwest::Error>() {
+                return match http.status() {
+                    Some(status) => from_status(status.as_u16(), false),
+                    None if http.is_decode() => JobErrorKind::External,
+                    None => JobErrorKind::Transient,
+                };
+            }
+            if cause.is::<serde_json::Error>() {
+                return JobErrorKind::Validation;
+            }
+            if cause.is::<sqlx::Error>() {
+                return JobErrorKind::Transient;
+            }
+        }
+        JobErrorKind::Transient
+    }
+}
+
+/// 🌐 Kind of an HTTP error answer
+fn from_status(status: u16, says_rate_limit: bool) -> JobErrorKind {
+    match status {
+        429 => JobErrorKind::RateLimited,
+        403 if says_rate_limit => JobErrorKind::RateLimited,
+        408 | 502..=504 => JobErrorKind::Transient,
+        _ => JobErrorKind::External,
+    }
+}
+
+impl fmt::Display for JobErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for JobErrorKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        JobErrorKind::ALL
+            .into_iter()
+            .find(|kind| kind.as_str() == s)
+            .ok_or_else(|| format!("Unknown job error kind: {}", s))
+    }
+}
+
+/// ❌ A handler error that states its own kind
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JobError {
+    pub kind: JobErrorKind,
+    pub message: String,
+}
+
+impl JobError {
+    pub fn new(kind: JobErrorKind, message: impl Into<String>) -> Self {
+        Self {
+            kind,
+            message: message.into(),
+        }
+    }
+
+    /// 🪦 Retrying can't help
+    pub fn permanent(message: impl Into<String>) -> Self {
+        Self::new(JobErrorKind::Permanent, message)
+    }
+
+    /// 📋 The job's input is wrong
+    pub fn validation(message: impl Into<String>) -> Self {
+        Self::new(JobErrorKind::Validation, message)
+    }
+}
+
+impl fmt::Display for JobError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl std::error::Error for JobError {}
+
+// 🧪 Tests - Sorting failures into the right drawer!
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::Context;
+    use std::time::Duration;
+
+    #[test]
+    fn test_errors_are_classified_from_their_chain() {
+        let explicit = anyhow::Error::new(JobError::permanent("Feedback is gone"))
+            .context("Delivering callback");
+        assert_eq!(JobErrorKind::classify(&explicit), JobErrorKind::Permanent);
+
+        let payload = serde_json::from_str::<serde_json::Value>("{")
+            .context("Invalid job payload")
+            .unwrap_err();
+        assert_eq!(JobErrorKind::classify(&payload), JobErrorKind::Validation);
+
+        let throttled = anyhow::Error::new(SecondaryRateLimited {
+            retry_after: Duration::from_secs(60),
+        });
+        assert_eq!(
+            JobErrorKind::classify(&throttled),
+            JobErrorKind::RateLimited
+        );
+        assert_eq!(
+            JobErrorKind::classify(&anyhow::Error::new(sqlx::Error::PoolTimedOut)),
+            JobErrorKind::Transient
+        );
+        assert_eq!(
+            JobErrorKind::classify(&anyhow::anyhow!("receiver is down")),
+            JobErrorKind::Transient
+        );
+        assert_eq!(from_status(503, false), JobErrorKind::Transient);
+        assert_eq!(from_status(403, true), JobErrorKind::RateLimited);
+        assert_eq!(from_status(422, false), JobErrorKind::External);
+
+        for kind in JobErrorKind::ALL {
+            assert_eq!(kind.as_str().parse::<JobErrorKind>(), Ok(kind));
+        }
+        assert!(!JobErrorKind::Validation.is_retryable());
+        assert!(JobErrorKind::RateLimited.is_retryable());
+        println!("✅ Job error classification test passed!");
+    }
+}
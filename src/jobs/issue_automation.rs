@@ -0,0 +1,98 @@
+// ⏳ Deferred Issue Automation - Finishing the job once GitHub lets us! ⏳
+// When the write throttle would hold a webhook response up for too long, the
+// issue event is queued here instead, along with the writes that already went
+// through. The handler replays the rest and defers itself again while the
+// throttle stays saturated.
+// Created with love by Aye & Hue! ✨
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sqlx::PgPool;
+use std::time::Duration;
+use tracing::{info, warn};
+use uuid::Uuid;
+
+use crate::{
+    api::issue_hooks::{process_issue_event, AutomationStep, IssueWebhookPayload},
+    github::throttle::WriteThrottled,
+};
+
+use super::{JobContext, JobHandler};
+
+/// 🏷️ Job type for throttled issue automation
+pub const ISSUE_AUTOMATION_JOB: &str = "issue_automation";
+/// 🧱 Throttle deferrals before a run counts as an ordinary failure
+const MAX_DEFERRALS: u32 = 24;
+
+/// 📋 Job payload: the raw webhook event and how far the automation got
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IssueAutomationJob {
+    pub event: Value,
+    #[serde(default)]
+    pub done: Vec<AutomationStep>,
+    #[serde(default)]
+    pub deferrals: u32,
+}
+
+/// 📥 Queue the automation to run once the throttle has a token again
+pub async fn defer_issue_automation(
+    pool: &PgPool,
+    job: &IssueAutomationJob,
+    retry_after: Duration,
+) -> Result<Uuid> {
+    super::enqueue_after(
+        pool,
+        ISSUE_AUTOMATION_JOB,
+        serde_json::to_value(job)?,
+        retry_after,
+    )
+    .await
+}
+
+/// 🤖 Replays deferred issue automation
+pub struct IssueAutomationHandler;
+
+#[async_trait::async_trait]
+impl JobHandler for IssueAutomationHandler {
+    const TYPE: &'static str = ISSUE_AUTOMATION_JOB;
+
+    async fn run(&self, payload: Value, ctx: &JobContext<'_>) -> Result<()> {
+        let job: IssueAutomationJob =
+            serde_json::from_value(payload).context("Invalid issue automation job payload")?;
+        let event: IssueWebhookPayload = serde_json::from_value(job.event.clone())
+            .context("Invalid issue event in automation job")?;
+
+        let mut done = job.done.clone();
+        let error = match process_issue_event(ctx.app_state, &event, &mut done).await {
+            Ok(_) => {
+                info!(
+                    "✅ Deferred automation finished for issue #{}",
+                    event.issue.number
+                );
+                return Ok(());
+            }
+            Err(e) => e,
+        };
+
+        // ⏳ Still throttled: go back in the queue with the progress made this time
+        match WriteThrottled::find(&error) {
+            Some(throttled) if job.deferrals < MAX_DEFERRALS => {
+                let next = IssueAutomationJob {
+                    event: job.event,
+                    done,
+                    deferrals: job.deferrals + 1,
+                };
+                let job_id =
+                    defer_issue_automation(&ctx.app_state.db_pool, &next, throttled.retry_after)
+                        .await?;
+                warn!(
+                    "⏳ Issue #{} still throttled, deferred again as job {}",
+                    event.issue.number, job_id
+                );
+                Ok(())
+            }
+            _ => Err(error),
+        }
+    }
+}
@@ -0,0 +1,236 @@
+// 🩺 LLM Health Monitor - Noticing when the default provider has a bad day! 🩺
+// Every few minutes the provider health report is recomputed from `llm_usage` and
+// stored in the settings table (so every instance's settings cache, the admin
+// settings page and /admin/api/llm/health see it). When the default provider falls
+// below LLM_HEALTH_SUCCESS_FLOOR and another one looks healthy, the admins get a
+// warning suggesting a switch - once per episode - or, with LLM_AUTO_SWITCH, the
+// default is switched through the settings override and the switch is audit-logged.
+// Created with love by Aye & Hue! ✨
+
+use anyhow::{Context, Result};
+use serde_json::json;
+use std::time::Duration;
+use tracing::{error, warn};
+
+use crate::api::settings_cache::{LLM_DEFAULT_PROVIDER_KEY, LLM_HEALTH_KEY};
+use crate::api::AppState;
+use crate::config::LlmProvider;
+use crate::llm::health::{advise, load_events, HealthReport, SwitchAdvice};
+
+/// ⏰ How often the report is recomputed
+pub const LLM_HEALTH_INTERVAL: Duration = Duration::from_secs(300);
+/// 🧹 Usage rows older than this are removed by the monitor
+const USAGE_RETENTION_DAYS: i32 = 7;
+/// 📜 Who the audit log says switched the provider
+const MONITOR_ACTOR: &str = "llm-health-monitor";
+
+/// 🩺 Recompute and store the report, then act on it. `alerted` remembers the
+/// provider the admins were last warned about, so an episode warns only once.
+pub async fn run_once(
+    app_state: &AppState,
+    alerted: &mut Option<LlmProvider>,
+) -> Result<HealthReport> {
+    let pool = &app_state.db_pool;
+    sqlx::query("DELETE FROM llm_usage WHERE created_at < NOW() - make_interval(days => $1)")
+        .bind(USAGE_RETENTION_DAYS)
+        .execute(pool)
+        .await
+        .context("Failed to prune old LLM usage")?;
+    let report = HealthReport::compute(&load_events(pool).await?, chrono::Utc::now());
+    store_setting(app_state, LLM_HEALTH_KEY, &serde_json::to_string(&report)?).await?;
+
+    let llm = &app_state.llm;
+    let configured: Vec<LlmProvider> = LlmProvider::ALL
+        .into_iter()
+        .filter(|provider| llm.is_configured(provider))
+        .collect();
+    let config = &app_state.config.llm;
+    let Some(advice) = advise(
+        &report,
+        &llm.default_provider(),
+        &configured,
+        config.health_success_floor,
+        config.auto_switch,
+    ) else {
+        *alerted = None;
+        return Ok(report);
+    };
+
+    if advice.switch {
+        store_setting(app_state, LLM_DEFAULT_PROVIDER_KEY, advice.to.as_str()).await?;
+        crate::api::admin::audit_log_as(
+            app_state,
+            MONITOR_ACTOR,
+            "llm_default_provider_switched",
+            json!({
+                "from": advice.from.as_str(),
+                "to": advice.to.as_str(),
+                "from_success_rate": advice.from_success_rate,
+                "to_success_rate": advice.to_success_rate,
+                "success_floor": config.health_success_floor,
+            }),
+        )
+        .await;
+        warn!(
+            "🔀 Switched the default LLM provider from {} to {}",
+            advice.from.label(),
+            advice.to.label()
+        );
+        notify_admins(app_state, &advice, config.health_success_floor).await?;
+        *alerted = None;
+    } else if alerted.as_ref() != Some(&advice.from) {
+        warn!(
+            "🩺 {} is below the LLM health floor, suggesting {}",
+            advice.from.label(),
+            advice.to.label()
+        );
+        notify_admins(app_state, &advice, config.health_success_floor).await?;
+        *alerted = Some(advice.from.clone());
+    }
+    Ok(report)
+}
+
+/// 💾 Write a settings row and reload the cache so this instance sees it right away
+async fn store_setting(app_state: &AppState, key: &str, value: &str) -> Result<()> {
+    sqlx::query(
+        "INSERT INTO settings (key, value, updated_at) VALUES ($1, $2, NOW()) \
+         ON CONFLICT (key) DO UPDATE SET value = $2, updated_at = NOW()",
+    )
+    .bind(key)
+    .bind(value)
+    .execute(&app_state.db_pool)
+    .await
+    .with_context(|| format!("Failed to store the {} setting", key))?;
+    app_state.settings.reload(&app_state.db_pool).await
+}
+
+/// 📣 Leave every active admin a warning about the switch (made or suggested)
+async fn notify_admins(app_state: &AppState, advice: &SwitchAdvice, floor: f64) -> Result<u64> {
+    let alternative = match advice.to_success_rate {
+        Some(rate) => format!("{} succeeded on {:.0}%", advice.to.label(), rate * 100.0),
+        None => format!("{} had no calls to judge by", advice.to.label()),
+    };
+    let (title, action) = if advice.switch {
+        (
+            format!("Default LLM provider switched to {}", advice.to.label()),
+            "LLM_AUTO_SWITCH is on, so new requests now use it.".to_string(),
+        )
+    } else {
+        (
+            format!("{} is struggling", advice.from.label()),
+            format!(
+                "Consider setting LLM_DEFAULT_PROVIDER={} (or LLM_AUTO_SWITCH=true).",
+                advice.to.as_str()
+            ),
+        )
+    };
+    let result = sqlx::query(
+        r#"
+        INSERT INTO notifications (user_id, notification_type, title, content)
+        SELECT id, 'warning', $1, $2 FROM users WHERE role = 'admin' AND is_active = true
+        "#,
+    )
+    .bind(title)
+    .bind(format!(
+        "{} succeeded on {:.0}% of its calls in the last day, below the {:.0}% floor; {}. {}",
+        advice.from.label(),
+        advice.from_success_rate * 100.0,
+        floor * 100.0,
+        alternative,
+        action
+    ))
+    .execute(&app_state.db_pool)
+    .await
+    .context("Failed to write LLM health notifications")?;
+    Ok(result.rows_affected())
+}
+
+/// 🚀 Recompute the health report every LLM_HEALTH_INTERVAL
+pub fn spawn_monitor(app_state: AppState) -> tokio::task::JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut alerted = None;
+        let mut ticker = tokio::time::interval(LLM_HEALTH_INTERVAL);
+        loop {
+            ticker.tick().await;
+            if let Err(e) = run_once(&app_state, &mut alerted).await {
+                error!("❌ LLM health check failed: {:#}", e);
+            }
+        }
+    })
+}
+
+// 🧪 Tests - A provider having a bad day!
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::llm::usage::{record, CallOutcome};
+    use crate::test_support::spawn_test_app;
+
+    async fn calls(app_state: &AppState, provider: &LlmProvider, outcome: CallOutcome, n: usize) {
+        for _ in 0..n {
+            record(&app_state.db_pool, provider, outcome, 1200)
+                .await
+                .unwrap();
+        }
+    }
+
+    async fn warnings(app_state: &AppState) -> i64 {
+        sqlx::query_scalar("SELECT COUNT(*) FROM notifications WHERE notification_type = 'warning'")
+            .fetch_one(&app_state.db_pool)
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_monitor_warns_once_then_switches_when_allowed() {
+        let Some(app) = spawn_test_app().await else {
+            return;
+        };
+        sqlx::query(
+            "INSERT INTO users (email, name, password_hash, role) VALUES ('ops@example.com', 'Ops', 'x', 'admin')",
+        )
+        .execute(&app.db_pool)
+        .await
+        .unwrap();
+        let app_state = &app.app_state;
+        calls(app_state, &LlmProvider::OpenAi, CallOutcome::Success, 5).await;
+        calls(app_state, &LlmProvider::OpenAi, CallOutcome::Timeout, 10).await;
+        calls(app_state, &LlmProvider::Anthropic, CallOutcome::Success, 3).await;
+
+        // 📣 Suggested once per episode, and the report lands in the settings cache
+        let mut alerted = None;
+        let report = run_once(app_state, &mut alerted).await.unwrap();
+        assert_eq!(report.get(&LlmProvider::OpenAi).unwrap().calls, 15);
+        run_once(app_state, &mut alerted).await.unwrap();
+        assert_eq!(warnings(app_state).await, 1);
+        assert_eq!(alerted, Some(LlmProvider::OpenAi));
+        let cached = HealthReport::from_settings(app_state.settings.get().llm_health.as_ref());
+        assert_eq!(cached.unwrap().providers.len(), 2);
+        assert_eq!(app_state.llm.default_provider(), LlmProvider::OpenAi);
+
+        // 🔀 With auto-switch the override is written, audit-logged and picked up
+        let mut config = (*app_state.config).clone();
+        config.llm.auto_switch = true;
+        let switching = AppState {
+            config: std::sync::Arc::new(config),
+            ..app_state.clone()
+        };
+        run_once(&switching, &mut alerted).await.unwrap();
+        assert_eq!(switching.llm.default_provider(), LlmProvider::Anthropic);
+        assert_eq!(warnings(app_state).await, 2);
+        let audited: i64 = sqlx::query_scalar(
+            "SELECT COUNT(*) FROM admin_audit_log WHERE action = 'llm_default_provider_switched' \
+             AND actor = $1 AND details->>'to' = 'anthropic'",
+        )
+        .bind(MONITOR_ACTOR)
+        .fetch_one(&app.db_pool)
+        .await
+        .unwrap();
+        assert_eq!(audited, 1);
+
+        // 🩺 Anthropic is healthy: nothing more to do
+        run_once(&switching, &mut alerted).await.unwrap();
+        assert_eq!(warnings(app_state).await, 2);
+        println!("✅ LLM health monitor test passed!");
+    }
+}
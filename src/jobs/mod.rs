@@ -1,2 +1,471 @@
 // 🔄 Background Jobs Module - Async Task Processing! 🔄
-// TODO: Implement background job processing with tokio-cron-scheduler
+// A tiny Postgres-backed queue on top of the `background_jobs` table:
+// enqueue, claim with SKIP LOCKED, then complete or fail with backoff.
+// Failures carry an `error_kind` (see `errors`) that decides retry vs park.
+// Job types and their handlers live in `registry`.
+// Created with love by Aye & Hue! ✨
+
+use anyhow::{Context, Result};
+use serde_json::Value;
+use sqlx::{PgExecutor, PgPool, Row};
+use std::time::Duration;
+use tracing::{error, info};
+use uuid::Uuid;
+
+use crate::api::AppState;
+
+pub mod approval; // ✋ Holding generated changes for the project owner's sign-off
+pub mod callbacks; // 📞 Per-feedback callback delivery
+pub mod daily_stats; // 📈 Nightly statistics snapshots
+pub mod errors; // 🏷️ Failure kinds: what went wrong and whether to retry
+pub mod issue_automation; // ⏳ Issue automation deferred by the GitHub write throttle
+pub mod llm_health; // 🩺 LLM provider health report, warnings and automatic switches
+pub mod outbox; // 📬 Transactional outbox for status change side effects
+pub mod pr_refresh; // 🔄 Nightly refresh of PR bodies with new votes and duplicate reports
+pub mod reconcile; // 🩹 Requeuing feedback a crashed process left mid-pipeline
+pub mod registry; // 🗂️ Job types and the dispatcher
+pub mod retention; // 🗃️ Archiving and removing old completed feedback
+pub mod self_issues; // 🐛 Issues in our own repo for failures that look like our bugs
+pub mod status_stats; // 📊 Five-minute refresh of the status stats materialized view
+pub mod webhook_secrets; // 🔏 Pushing a rotated GitHub webhook secret to our repository hooks
+
+pub use errors::{JobError, JobErrorKind};
+pub use registry::{JobContext, JobHandler, JobRegistry};
+
+/// ⏱️ How often the worker looks for due jobs
+const POLL_INTERVAL: Duration = Duration::from_secs(5);
+/// ⏳ First retry delay, doubled on every further attempt
+const BASE_RETRY_DELAY_SECS: i64 = 30;
+/// 🧱 Longest delay between retries
+const MAX_RETRY_DELAY_SECS: i64 = 3600;
+/// 🚦 Shortest delay after being rate limited (backing off for 30s rarely helps)
+const MIN_RATE_LIMITED_DELAY_SECS: i64 = 300;
+
+/// 📦 A claimed job, ready to run
+#[derive(Debug, Clone)]
+pub struct Job {
+    pub id: Uuid,
+    pub job_type: String,
+    pub payload: Value,
+    pub retries: i32,
+    pub max_retries: i32,
+}
+
+/// ➕ Queue a job to run as soon as a worker is free
+pub async fn enqueue(
+    executor: impl PgExecutor<'_>,
+    job_type: &str,
+    payload: Value,
+) -> Result<Uuid> {
+    enqueue_with_priority(executor, job_type, payload, 0).await
+}
+
+/// ➕ Queue a job ahead of lower-priority work that is already due
+/// (pass a transaction to queue it only if the rest of the transaction commits)
+pub async fn enqueue_with_priority(
+    executor: impl PgExecutor<'_>,
+    job_type: &str,
+    payload: Value,
+    priority: i32,
+) -> Result<Uuid> {
+    let id: Uuid = sqlx::query_scalar(
+        "INSERT INTO background_jobs (job_type, payload, priority) VALUES ($1, $2, $3) RETURNING id",
+    )
+    .bind(job_type)
+    .bind(payload)
+    .bind(priority)
+    .fetch_one(executor)
+    .await
+    .with_context(|| format!("Failed to enqueue {} job", job_type))?;
+
+    info!("📥 Queued {} job {}", job_type, id);
+    Ok(id)
+}
+
+/// ⏰ Queue a job that must not run before `delay` has passed
+pub async fn enqueue_after(
+    pool: &PgPool,
+    job_type: &str,
+    payload: Value,
+    delay: Duration,
+) -> Result<Uuid> {
+    let id: Uuid = sqlx::query_scalar(
+        "INSERT INTO background_jobs (job_type, payload, scheduled_at) VALUES ($1, $2, NOW() + make_interval(secs => $3)) RETURNING id",
+    )
+    .bind(job_type)
+    .bind(payload)
+    .bind(delay.as_secs_f64())
+    .fetch_one(pool)
+    .await
+    .with_context(|| format!("Failed to enqueue {} job", job_type))?;
+
+    info!("📥 Queued {} job {} to run in {:?}", job_type, id, delay);
+    Ok(id)
+}
+
+/// ➕ Queue a scheduled job unless one of the same type is still pending; returns
+/// whether one was queued. Every instance runs the schedulers, so the check and the
+/// insert happen under an advisory lock on the job type - instances waking together
+/// queue one job between them, and a stopped worker doesn't come back to a pile
+pub async fn enqueue_unless_pending(pool: &PgPool, job_type: &str, payload: Value) -> Result<bool> {
+    let mut tx = pool.begin().await?;
+    sqlx::query("SELECT pg_advisory_xact_lock(hashtext('scheduler#' || $1))")
+        .bind(job_type)
+        .execute(&mut *tx)
+        .await
+        .with_context(|| format!("Failed to take the {} scheduler lock", job_type))?;
+    let waiting: bool = sqlx::query_scalar(
+        "SELECT EXISTS (SELECT 1 FROM background_jobs WHERE job_type = $1 AND status = 'pending')",
+    )
+    .bind(job_type)
+    .fetch_one(&mut *tx)
+    .await?;
+    if waiting {
+        return Ok(false);
+    }
+    enqueue(&mut *tx, job_type, payload).await?;
+    tx.commit().await?;
+    Ok(true)
+}
+
+/// 🎣 Claim the highest-priority due job, oldest first within a priority
+/// (other workers skip it while we hold it)
+pub async fn claim_next(pool: &PgPool) -> Result<Option<Job>> {
+    let row = sqlx::query(
+        r#"
+        UPDATE background_jobs
+        SET status = 'running', started_at = NOW()
+        WHERE id = (
+            SELECT id FROM background_jobs
+            WHERE status = 'pending' AND scheduled_at <= NOW()
+            ORDER BY priority DESC, scheduled_at, id
+            LIMIT 1
+            FOR UPDATE SKIP LOCKED
+        )
+        RETURNING id, job_type, payload, retries, max_retries
+        "#,
+    )
+    .fetch_optional(pool)
+    .await
+    .context("Failed to claim background job")?;
+
+    Ok(row.map(|row| Job {
+        id: row.get("id"),
+        job_type: row.get("job_type"),
+        payload: row.get("payload"),
+        retries: row.get("retries"),
+        max_retries: row.get("max_retries"),
+    }))
+}
+
+/// ✅ Mark a job as done
+pub async fn complete(pool: &PgPool, job_id: Uuid) -> Result<()> {
+    sqlx::query(
+        "UPDATE background_jobs SET status = 'completed', completed_at = NOW(), error_message = NULL, error_kind = NULL WHERE id = $1",
+    )
+    .bind(job_id)
+    .execute(pool)
+    .await
+    .context("Failed to complete background job")?;
+    Ok(())
+}
+
+/// ❌ Record a failed attempt: reschedule with backoff, or give up after max_retries
+/// (or right away when the kind isn't worth retrying). Returns true when the job will
+/// be retried.
+pub async fn fail(
+    pool: &PgPool,
+    job: &Job,
+    error_message: &str,
+    kind: JobErrorKind,
+) -> Result<bool> {
+    let will_retry = kind.is_retryable() && job.retries < job.max_retries;
+    if !will_retry {
+        return park(pool, job.id, error_message, kind)
+            .await
+            .map(|()| false);
+    }
+    let mut delay = retry_delay_secs(job.retries);
+    if kind == JobErrorKind::RateLimited {
+        delay = delay.max(MIN_RATE_LIMITED_DELAY_SECS);
+    }
+    sqlx::query(
+        r#"
+        UPDATE background_jobs
+        SET status = 'pending', retries = retries + 1, error_message = $2, error_kind = $3,
+            scheduled_at = NOW() + make_interval(secs => $4)
+        WHERE id = $1
+        "#,
+    )
+    .bind(job.id)
+    .bind(error_message)
+    .bind(kind.as_str())
+    .bind(delay as f64)
+    .execute(pool)
+    .await
+    .context("Failed to record background job failure")?;
+
+    Ok(true)
+}
+
+/// 🅿️ Fail a job immediately without retries (e.g. no handler for its type)
+pub async fn park(
+    executor: impl PgExecutor<'_>,
+    job_id: Uuid,
+    error_message: &str,
+    kind: JobErrorKind,
+) -> Result<()> {
+    sqlx::query(
+        "UPDATE background_jobs SET status = 'failed', error_message = $2, error_kind = $3, completed_at = NOW() WHERE id = $1",
+    )
+    .bind(job_id)
+    .bind(error_message)
+    .bind(kind.as_str())
+    .execute(executor)
+    .await
+    .context("Failed to park background job")?;
+    Ok(())
+}
+
+/// ⏳ Exponential backoff: 30s, 60s, 120s, ... capped at an hour
+fn retry_delay_secs(retries: i32) -> i64 {
+    let exponent = retries.clamp(0, 16) as u32;
+    (BASE_RETRY_DELAY_SECS * 2i64.pow(exponent)).min(MAX_RETRY_DELAY_SECS)
+}
+
+/// 📊 Failed jobs of one kind
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+pub struct FailureBreakdown {
+    pub kind: JobErrorKind,
+    pub count: i64,
+    /// 🏷️ Job types that failed this way, most failures first
+    pub job_types: Vec<String>,
+    /// 📝 The newest error message of this kind
+    pub latest_message: Option<String>,
+}
+
+/// 🧮 Failed jobs of one kind and type
+#[derive(sqlx::FromRow)]
+struct FailureRow {
+    kind: String,
+    job_type: String,
+    count: i64,
+    latest_at: Option<chrono::DateTime<chrono::Utc>>,
+    latest_message: Option<String>,
+}
+
+/// 📊 Jobs that failed for good since `since` (None = ever), grouped by kind
+/// (kinds with none left out)
+pub async fn failure_breakdown(
+    pool: &PgPool,
+    since: Option<chrono::DateTime<chrono::Utc>>,
+) -> Result<Vec<FailureBreakdown>> {
+    // 🕰️ Jobs that failed before kinds were recorded count as transient (the old default)
+    let rows: Vec<FailureRow> = sqlx::query_as(
+        r#"
+        SELECT COALESCE(error_kind, 'transient') AS kind, job_type, COUNT(*) AS count,
+               MAX(completed_at) AS latest_at,
+               (ARRAY_AGG(error_message ORDER BY completed_at DESC))[1] AS latest_message
+        FROM background_jobs
+        WHERE status = 'failed' AND ($1::timestamptz IS NULL OR completed_at >= $1)
+        GROUP BY 1, 2
+        ORDER BY 3 DESC, 2
+        "#,
+    )
+    .bind(since)
+    .fetch_all(pool)
+    .await
+    .context("Failed to count failed background jobs by kind")?;
+
+    let mut breakdown: Vec<(FailureBreakdown, Option<chrono::DateTime<chrono::Utc>>)> = Vec::new();
+    for row in rows {
+        let FailureRow {
+            kind,
+            job_type,
+            count,
+            latest_at,
+            latest_message: message,
+        } = row;
+        let kind = kind.parse().unwrap_or(JobErrorKind::Transient);
+        match breakdown.iter_mut().find(|(entry, _)| entry.kind == kind) {
+            Some((entry, newest)) => {
+                entry.count += count;
+                entry.job_types.push(job_type);
+                if latest_at > *newest {
+                    *newest = latest_at;
+                    entry.latest_message = message;
+                }
+            }
+            None => breakdown.push((
+                FailureBreakdown {
+                    kind,
+                    count,
+                    job_types: vec![job_type],
+                    latest_message: message,
+                },
+                latest_at,
+            )),
+        }
+    }
+    let mut breakdown: Vec<FailureBreakdown> =
+        breakdown.into_iter().map(|(entry, _)| entry).collect();
+    breakdown.sort_by_key(|entry| {
+        JobErrorKind::ALL
+            .iter()
+            .position(|kind| *kind == entry.kind)
+    });
+    Ok(breakdown)
+}
+
+/// 🏃 Run every due job once with the built-in handlers; returns how many were attempted
+pub async fn run_due_jobs(app_state: &AppState) -> Result<usize> {
+    JobRegistry::builtin().run_due_jobs(app_state).await
+}
+
+/// 🚀 Start the background worker loop
+pub fn spawn_worker(app_state: AppState) -> tokio::task::JoinHandle<()> {
+    let registry = JobRegistry::builtin();
+    info!(
+        "🔄 Starting background job worker for {:?}",
+        registry.job_types()
+    );
+    tokio::spawn(async move {
+        loop {
+            if let Err(e) = registry.run_due_jobs(&app_state).await {
+                error!("❌ Background job run failed: {:#}", e);
+            }
+            tokio::time::sleep(POLL_INTERVAL).await;
+        }
+    })
+}
+
+// 🧪 Tests - Making sure the queue keeps its promises!
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_retry_backoff() {
+        assert_eq!(retry_delay_secs(0), 30);
+        assert_eq!(retry_delay_secs(1), 60);
+        assert_eq!(retry_delay_secs(3), 240);
+        assert_eq!(retry_delay_secs(20), MAX_RETRY_DELAY_SECS);
+        println!("✅ Retry backoff test passed!");
+    }
+
+    #[tokio::test]
+    async fn test_higher_priority_jobs_are_claimed_first() {
+        let Some(app) = crate::test_support::spawn_test_app().await else {
+            return;
+        };
+        let pool = &app.db_pool;
+        let trivial = enqueue(pool, "echo", serde_json::json!({})).await.unwrap();
+        let urgent = enqueue_with_priority(pool, "echo", serde_json::json!({}), 90)
+            .await
+            .unwrap();
+        let older_medium = enqueue_with_priority(pool, "echo", serde_json::json!({}), 40)
+            .await
+            .unwrap();
+        let newer_medium = enqueue_with_priority(pool, "echo", serde_json::json!({}), 40)
+            .await
+            .unwrap();
+
+        let mut claimed = Vec::new();
+        while let Some(job) = claim_next(pool).await.unwrap() {
+            claimed.push(job.id);
+        }
+        assert_eq!(claimed, vec![urgent, older_medium, newer_medium, trivial]);
+        println!("✅ Priority claim order test passed!");
+    }
+
+    #[tokio::test]
+    async fn test_error_kind_decides_retry_or_park_and_is_counted() {
+        let Some(app) = crate::test_support::spawn_test_app().await else {
+            return;
+        };
+        let pool = &app.db_pool;
+        for job_type in ["echo", "echo", "echo", "tally"] {
+            enqueue(pool, job_type, serde_json::json!({}))
+                .await
+                .unwrap();
+        }
+        let mut claimed = Vec::new();
+        while let Some(job) = claim_next(pool).await.unwrap() {
+            claimed.push(job);
+        }
+        let row = |id: Uuid| async move {
+            sqlx::query_as::<_, (String, Option<String>, i32, f64)>(
+                "SELECT status, error_kind, retries, EXTRACT(EPOCH FROM scheduled_at - NOW())::float8 FROM background_jobs WHERE id = $1",
+            )
+            .bind(id)
+            .fetch_one(pool)
+            .await
+            .unwrap()
+        };
+
+        // 🔁 Transient and rate limited failures come back, the latter not before 5 minutes
+        assert!(
+            fail(pool, &claimed[0], "timed out", JobErrorKind::Transient)
+                .await
+                .unwrap()
+        );
+        let (status, kind, retries, due_in) = row(claimed[0].id).await;
+        assert_eq!(
+            (status.as_str(), kind.as_deref(), retries),
+            ("pending", Some("transient"), 1)
+        );
+        assert!(due_in < 60.0);
+        assert!(
+            fail(pool, &claimed[1], "slow down", JobErrorKind::RateLimited)
+                .await
+                .unwrap()
+        );
+        assert!(row(claimed[1].id).await.3 > 290.0);
+
+        // 🅿️ A bad payload is parked on the first attempt
+        assert!(
+            !fail(pool, &claimed[2], "missing field", JobErrorKind::Validation)
+                .await
+                .unwrap()
+        );
+        let (status, kind, retries, _) = row(claimed[2].id).await;
+        assert_eq!(
+            (status.as_str(), kind.as_deref(), retries),
+            ("failed", Some("validation"), 0)
+        );
+        park(pool, claimed[3].id, "no handler", JobErrorKind::Permanent)
+            .await
+            .unwrap();
+        // 🕰️ A failure from before kinds existed
+        sqlx::query("INSERT INTO background_jobs (job_type, payload, status, error_message, completed_at) VALUES ('tally', '{}', 'failed', 'old', NOW())")
+            .execute(pool)
+            .await
+            .unwrap();
+
+        let breakdown = failure_breakdown(pool, None).await.unwrap();
+        let kinds: Vec<(JobErrorKind, i64)> = breakdown
+            .iter()
+            .map(|entry| (entry.kind, entry.count))
+            .collect();
+        assert_eq!(
+            kinds,
+            vec![
+                (JobErrorKind::Transient, 1),
+                (JobErrorKind::Validation, 1),
+                (JobErrorKind::Permanent, 1)
+            ]
+        );
+        assert_eq!(breakdown[1].job_types, vec!["echo".to_string()]);
+        assert_eq!(breakdown[2].latest_message.as_deref(), Some("no handler"));
+        let tomorrow = chrono::Utc::now() + chrono::Duration::days(1);
+        assert!(failure_breakdown(pool, Some(tomorrow))
+            .await
+            .unwrap()
+            .is_empty());
+
+        // ✅ Completing clears the kind of an earlier attempt
+        complete(pool, claimed[0].id).await.unwrap();
+        assert_eq!(row(claimed[0].id).await.1, None);
+        println!("✅ Job error kind test passed!");
+    }
+}
@@ -0,0 +1,601 @@
+// 📬 Event Outbox - Side effects that survive a crash! 📬
+// A terminal status change writes an `outbox_events` row in the same transaction
+// as the feedback update, so the side effects can't be lost between the two.
+// The dispatcher leases due events and hands each one to every consumer that wants
+// it. A consumer's writes commit together with its `outbox_deliveries` row (the
+// idempotency key is event + consumer), so a retried or re-leased event never runs
+// a consumer twice. Failed events are retried with the job queue's backoff.
+// Created with love by Aye & Hue! ✨
+
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use serde_json::Value;
+use sqlx::{PgConnection, PgPool, Row};
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::{error, info, warn};
+use uuid::Uuid;
+
+use crate::{api::AppState, database::models::Feedback};
+
+use super::callbacks::{self, FeedbackCallbackPayload};
+
+/// 📣 A feedback item finished successfully
+pub const FEEDBACK_COMPLETED_EVENT: &str = "feedback.completed";
+/// 📣 A feedback item failed for good
+pub const FEEDBACK_FAILED_EVENT: &str = "feedback.failed";
+/// ⏱️ How often the dispatcher looks for due events
+const DISPATCH_INTERVAL: Duration = Duration::from_secs(2);
+/// 🔒 How long a claimed event stays invisible to other dispatchers
+/// (a dispatcher that dies mid-delivery releases it when this runs out)
+const LEASE_SECS: f64 = 60.0;
+/// 📦 Events claimed per round
+const CLAIM_BATCH: i64 = 50;
+/// 💀 Attempts before an event is marked failed
+pub const MAX_OUTBOX_ATTEMPTS: i32 = 10;
+
+/// 📨 A claimed outbox event
+#[derive(Debug, Clone)]
+pub struct OutboxEvent {
+    pub id: Uuid,
+    pub event_type: String,
+    pub feedback_id: Option<Uuid>,
+    pub payload: Value,
+    /// 🔢 Including the current one
+    pub attempts: i32,
+}
+
+/// ✍️ Add an event to the outbox inside the caller's transaction
+pub async fn record(
+    conn: &mut PgConnection,
+    event_type: &str,
+    feedback_id: Option<Uuid>,
+    payload: Value,
+) -> Result<Uuid> {
+    sqlx::query_scalar(
+        "INSERT INTO outbox_events (event_type, feedback_id, payload) VALUES ($1, $2, $3) RETURNING id",
+    )
+    .bind(event_type)
+    .bind(feedback_id)
+    .bind(payload)
+    .fetch_one(conn)
+    .await
+    .with_context(|| format!("Failed to record {} outbox event", event_type))
+}
+
+/// ✍️ Record the event for a feedback item that just reached a terminal state
+pub async fn record_status_change(conn: &mut PgConnection, feedback: &Feedback) -> Result<Uuid> {
+    let payload = FeedbackCallbackPayload::for_feedback(feedback);
+    record(
+        conn,
+        &payload.event,
+        Some(feedback.id),
+        serde_json::to_value(&payload)?,
+    )
+    .await
+}
+
+/// 🎣 Lease up to `CLAIM_BATCH` due events, oldest first
+pub async fn claim_due(pool: &PgPool) -> Result<Vec<OutboxEvent>> {
+    let rows = sqlx::query(
+        r#"
+        UPDATE outbox_events
+        SET attempts = attempts + 1, locked_until = NOW() + make_interval(secs => $1)
+        WHERE id IN (
+            SELECT id FROM outbox_events
+            WHERE status = 'pending' AND next_attempt_at <= NOW()
+              AND (locked_until IS NULL OR locked_until <= NOW())
+            ORDER BY created_at, id
+            LIMIT $2
+            FOR UPDATE SKIP LOCKED
+        )
+        RETURNING id, event_type, feedback_id, payload, attempts, created_at
+        "#,
+    )
+    .bind(LEASE_SECS)
+    .bind(CLAIM_BATCH)
+    .fetch_all(pool)
+    .await
+    .context("Failed to claim outbox events")?;
+
+    let mut events: Vec<(chrono::DateTime<chrono::Utc>, OutboxEvent)> = rows
+        .into_iter()
+        .map(|row| {
+            (
+                row.get("created_at"),
+                OutboxEvent {
+                    id: row.get("id"),
+                    event_type: row.get("event_type"),
+                    feedback_id: row.get("feedback_id"),
+                    payload: row.get("payload"),
+                    attempts: row.get("attempts"),
+                },
+            )
+        })
+        .collect();
+    events.sort_by_key(|(created_at, event)| (*created_at, event.id));
+    Ok(events.into_iter().map(|(_, event)| event).collect())
+}
+
+/// 📥 Something that reacts to outbox events
+#[async_trait]
+pub trait OutboxConsumer: Send + Sync + 'static {
+    /// 🏷️ Value stored in `outbox_deliveries.consumer` (never rename a live consumer)
+    fn name(&self) -> &'static str;
+
+    /// 🔍 Does this consumer care about the event type?
+    fn wants(&self, event_type: &str) -> bool;
+
+    /// 🏃 Handle the event; everything written through `conn` commits with the delivery record
+    async fn consume(&self, event: &OutboxEvent, conn: &mut PgConnection) -> Result<()>;
+}
+
+/// 🚚 Hands outbox events to their consumers
+#[derive(Clone, Default)]
+pub struct OutboxDispatcher {
+    consumers: Vec<Arc<dyn OutboxConsumer>>,
+}
+
+impl OutboxDispatcher {
+    /// 📚 Every consumer this build delivers to
+    pub fn builtin() -> Self {
+        Self::default()
+            .register(CallbackConsumer)
+            .register(SubmitterNotificationConsumer)
+            .register(OpsAlertConsumer)
+            .register(super::approval::ApprovalRequestConsumer)
+    }
+
+    /// 📚 The built-in consumers plus the ones this deployment switched on
+    pub fn for_app(app_state: &AppState) -> Self {
+        let dispatcher = Self::builtin();
+        if !app_state.config.self_issues.enabled {
+            return dispatcher;
+        }
+        dispatcher.register(super::self_issues::SelfIssueConsumer::new(
+            app_state.github.clone(),
+            app_state.config.self_issues.clone(),
+        ))
+    }
+
+    /// ➕ Add a consumer (panics on duplicate names - that's a wiring bug)
+    pub fn register<C: OutboxConsumer>(mut self, consumer: C) -> Self {
+        assert!(
+            self.consumers.iter().all(|c| c.name() != consumer.name()),
+            "outbox consumer {} registered twice",
+            consumer.name()
+        );
+        self.consumers.push(Arc::new(consumer));
+        self
+    }
+
+    /// 🏃 Claim and deliver every due event once; returns how many were attempted
+    pub async fn dispatch_due(&self, pool: &PgPool) -> Result<usize> {
+        let events = claim_due(pool).await?;
+        for event in &events {
+            self.deliver(pool, event).await?;
+        }
+        Ok(events.len())
+    }
+
+    /// 📬 Run every interested consumer that hasn't handled the event yet, then settle it
+    pub async fn deliver(&self, pool: &PgPool, event: &OutboxEvent) -> Result<()> {
+        let mut errors = Vec::new();
+        for consumer in self
+            .consumers
+            .iter()
+            .filter(|consumer| consumer.wants(&event.event_type))
+        {
+            if let Err(e) = deliver_to(pool, consumer.as_ref(), event).await {
+                errors.push(format!("{}: {:#}", consumer.name(), e));
+            }
+        }
+
+        if errors.is_empty() {
+            sqlx::query(
+                "UPDATE outbox_events SET status = 'delivered', delivered_at = NOW(), locked_until = NULL, last_error = NULL WHERE id = $1",
+            )
+            .bind(event.id)
+            .execute(pool)
+            .await
+            .context("Failed to mark outbox event delivered")?;
+            return Ok(());
+        }
+
+        let message = errors.join("; ");
+        if event.attempts >= MAX_OUTBOX_ATTEMPTS {
+            error!(
+                "💀 Outbox event {} ({}) failed for good: {}",
+                event.id, event.event_type, message
+            );
+            sqlx::query(
+                "UPDATE outbox_events SET status = 'failed', locked_until = NULL, last_error = $2 WHERE id = $1",
+            )
+            .bind(event.id)
+            .bind(&message)
+            .execute(pool)
+            .await
+        } else {
+            warn!(
+                "🔁 Outbox event {} ({}) failed, will retry: {}",
+                event.id, event.event_type, message
+            );
+            sqlx::query(
+                r#"
+                UPDATE outbox_events
+                SET locked_until = NULL, last_error = $2,
+                    next_attempt_at = NOW() + make_interval(secs => $3)
+                WHERE id = $1
+                "#,
+            )
+            .bind(event.id)
+            .bind(&message)
+            .bind(super::retry_delay_secs(event.attempts - 1) as f64)
+            .execute(pool)
+            .await
+        }
+        .context("Failed to record outbox delivery failure")?;
+        Ok(())
+    }
+}
+
+/// 🔑 One consumer, one transaction: the delivery row and the consumer's writes land together
+async fn deliver_to(
+    pool: &PgPool,
+    consumer: &dyn OutboxConsumer,
+    event: &OutboxEvent,
+) -> Result<()> {
+    let mut tx = pool
+        .begin()
+        .await
+        .context("Failed to start outbox delivery")?;
+    let first_time = sqlx::query(
+        "INSERT INTO outbox_deliveries (event_id, consumer) VALUES ($1, $2) ON CONFLICT DO NOTHING",
+    )
+    .bind(event.id)
+    .bind(consumer.name())
+    .execute(&mut *tx)
+    .await
+    .context("Failed to record outbox delivery")?
+    .rows_affected()
+        == 1;
+    if !first_time {
+        return Ok(());
+    }
+
+    consumer.consume(event, &mut tx).await?;
+    tx.commit()
+        .await
+        .context("Failed to commit outbox delivery")?;
+    info!(
+        "📬 {} handled {} {}",
+        consumer.name(),
+        event.event_type,
+        event.id
+    );
+    Ok(())
+}
+
+/// 🔍 Is this one of the terminal feedback events?
+fn is_terminal_event(event_type: &str) -> bool {
+    matches!(event_type, FEEDBACK_COMPLETED_EVENT | FEEDBACK_FAILED_EVENT)
+}
+
+/// 📞 Queues the signed callback to the submitter's URL (the job queue does the HTTP)
+pub struct CallbackConsumer;
+
+#[async_trait]
+impl OutboxConsumer for CallbackConsumer {
+    fn name(&self) -> &'static str {
+        "callback"
+    }
+
+    fn wants(&self, event_type: &str) -> bool {
+        is_terminal_event(event_type)
+    }
+
+    async fn consume(&self, event: &OutboxEvent, conn: &mut PgConnection) -> Result<()> {
+        let body: FeedbackCallbackPayload = serde_json::from_value(event.payload.clone())
+            .context("Invalid feedback event payload")?;
+        let target: Option<(Option<String>, i32)> =
+            sqlx::query_as("SELECT callback_url, priority FROM feedback WHERE id = $1")
+                .bind(body.feedback_id)
+                .fetch_optional(&mut *conn)
+                .await
+                .context("Failed to read callback target")?;
+        if let Some((Some(url), priority)) = target {
+            callbacks::enqueue_callback(&mut *conn, url, body, priority).await?;
+        }
+        Ok(())
+    }
+}
+
+/// 🔔 Tells the submitter (when they have an account) how their feedback ended
+pub struct SubmitterNotificationConsumer;
+
+#[async_trait]
+impl OutboxConsumer for SubmitterNotificationConsumer {
+    fn name(&self) -> &'static str {
+        "submitter_notification"
+    }
+
+    fn wants(&self, event_type: &str) -> bool {
+        is_terminal_event(event_type)
+    }
+
+    async fn consume(&self, event: &OutboxEvent, conn: &mut PgConnection) -> Result<()> {
+        let body: FeedbackCallbackPayload = serde_json::from_value(event.payload.clone())
+            .context("Invalid feedback event payload")?;
+        let (notification_type, title, content) = if event.event_type == FEEDBACK_FAILED_EVENT {
+            (
+                "feedback_failed",
+                format!("Feedback for {} failed", body.repository),
+                body.error_message
+                    .clone()
+                    .unwrap_or_else(|| "Processing failed".to_string()),
+            )
+        } else if let Some(url) = &body.pull_request_url {
+            (
+                "pull_request_created",
+                format!("Pull request opened for {}", body.repository),
+                url.clone(),
+            )
+        } else {
+            (
+                "feedback_completed",
+                format!("Feedback for {} completed", body.repository),
+                "Your feedback has been processed.".to_string(),
+            )
+        };
+
+        sqlx::query(
+            r#"
+            INSERT INTO notifications (user_id, notification_type, title, content, related_id)
+            SELECT user_id, $2::notification_type, $3, $4, id
+            FROM feedback WHERE id = $1 AND user_id IS NOT NULL
+            "#,
+        )
+        .bind(body.feedback_id)
+        .bind(notification_type)
+        .bind(title)
+        .bind(content)
+        .execute(conn)
+        .await
+        .context("Failed to write submitter notification")?;
+        Ok(())
+    }
+}
+
+/// 🚨 Leaves every active admin a notification when feedback fails
+pub struct OpsAlertConsumer;
+
+#[async_trait]
+impl OutboxConsumer for OpsAlertConsumer {
+    fn name(&self) -> &'static str {
+        "ops_alert"
+    }
+
+    fn wants(&self, event_type: &str) -> bool {
+        event_type == FEEDBACK_FAILED_EVENT
+    }
+
+    async fn consume(&self, event: &OutboxEvent, conn: &mut PgConnection) -> Result<()> {
+        let body: FeedbackCallbackPayload = serde_json::from_value(event.payload.clone())
+            .context("Invalid feedback event payload")?;
+        sqlx::query(
+            r#"
+            INSERT INTO notifications (user_id, notification_type, title, content, related_id)
+            SELECT id, 'feedback_failed', $1, $2, $3 FROM users WHERE role = 'admin' AND is_active = true
+            "#,
+        )
+        .bind(format!("Feedback failed for {}", body.repository))
+        .bind(format!(
+            "Feedback {} failed: {}",
+            body.feedback_id,
+            body.error_message.as_deref().unwrap_or("no error message")
+        ))
+        .bind(body.feedback_id)
+        .execute(conn)
+        .await
+        .context("Failed to write ops alert notifications")?;
+        Ok(())
+    }
+}
+
+/// 🏃 Deliver every due event once with the app's consumers; returns how many were attempted
+pub async fn dispatch_due(app_state: &AppState) -> Result<usize> {
+    OutboxDispatcher::for_app(app_state)
+        .dispatch_due(&app_state.db_pool)
+        .await
+}
+
+/// 🚀 Start the outbox dispatcher loop
+pub fn spawn_dispatcher(app_state: AppState) -> tokio::task::JoinHandle<()> {
+    let dispatcher = OutboxDispatcher::for_app(&app_state);
+    info!("📬 Starting outbox dispatcher");
+    tokio::spawn(async move {
+        loop {
+            if let Err(e) = dispatcher.dispatch_due(&app_state.db_pool).await {
+                error!("❌ Outbox dispatch failed: {:#}", e);
+            }
+            tokio::time::sleep(DISPATCH_INTERVAL).await;
+        }
+    })
+}
+
+// 🧪 Tests - Pulling the plug mid-delivery!
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::models::FeedbackStatus;
+    use crate::test_support::spawn_test_app;
+
+    /// 💥 Fails until told otherwise, counting real runs
+    struct FlakyConsumer {
+        healthy: Arc<std::sync::atomic::AtomicBool>,
+    }
+
+    #[async_trait]
+    impl OutboxConsumer for FlakyConsumer {
+        fn name(&self) -> &'static str {
+            "flaky"
+        }
+
+        fn wants(&self, event_type: &str) -> bool {
+            is_terminal_event(event_type)
+        }
+
+        async fn consume(&self, _event: &OutboxEvent, _conn: &mut PgConnection) -> Result<()> {
+            if self.healthy.load(std::sync::atomic::Ordering::SeqCst) {
+                Ok(())
+            } else {
+                anyhow::bail!("receiver is down")
+            }
+        }
+    }
+
+    async fn count(pool: &PgPool, sql: &str) -> i64 {
+        sqlx::query_scalar(sql).fetch_one(pool).await.unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_outbox_delivery_resumes_after_a_crash_without_duplicates() {
+        let Some(app) = spawn_test_app().await else {
+            return;
+        };
+        let pool = &app.db_pool;
+        let user_id: Uuid = sqlx::query_scalar(
+            "INSERT INTO users (email, name, password_hash, role, is_active) VALUES ('ops@example.com', 'Ops', 'x', 'admin', true) RETURNING id",
+        )
+        .fetch_one(pool)
+        .await
+        .unwrap();
+        let mut feedback = Feedback::create(
+            pool,
+            Some(user_id),
+            "8b-is/smart-tree".to_string(),
+            "The tree output loses colours when piped".to_string(),
+            Some("https://hooks.example.com/feedback".to_string()),
+            0,
+            None,
+            "unknown",
+        )
+        .await
+        .unwrap();
+
+        // ✍️ The status change and its event commit together, nothing is delivered yet
+        feedback
+            .update_status(
+                pool,
+                FeedbackStatus::Failed,
+                Some("LLM gave up".to_string()),
+            )
+            .await
+            .unwrap();
+        assert_eq!(
+            count(
+                pool,
+                "SELECT COUNT(*) FROM outbox_events WHERE status = 'pending'"
+            )
+            .await,
+            1
+        );
+        assert_eq!(count(pool, "SELECT COUNT(*) FROM background_jobs").await, 0);
+        assert_eq!(count(pool, "SELECT COUNT(*) FROM notifications").await, 0);
+
+        // 💥 A dispatcher claims the event and dies before delivering anything
+        assert_eq!(claim_due(pool).await.unwrap().len(), 1);
+        assert_eq!(dispatch_due(&app.app_state).await.unwrap(), 0);
+
+        // ⏰ Once its lease runs out the next dispatcher picks the event up
+        sqlx::query("UPDATE outbox_events SET locked_until = NOW() - INTERVAL '1 second'")
+            .execute(pool)
+            .await
+            .unwrap();
+        assert_eq!(dispatch_due(&app.app_state).await.unwrap(), 1);
+        assert_eq!(count(pool, "SELECT COUNT(*) FROM background_jobs").await, 1);
+        // 🔔 One for the submitter, one ops alert (the submitter is also the only admin)
+        assert_eq!(count(pool, "SELECT COUNT(*) FROM notifications").await, 2);
+
+        // 💥 Dying after the consumers committed but before the event was settled:
+        // the redelivery finds every consumer's idempotency key and does nothing
+        sqlx::query("UPDATE outbox_events SET status = 'pending', delivered_at = NULL")
+            .execute(pool)
+            .await
+            .unwrap();
+        assert_eq!(dispatch_due(&app.app_state).await.unwrap(), 1);
+        assert_eq!(count(pool, "SELECT COUNT(*) FROM background_jobs").await, 1);
+        assert_eq!(count(pool, "SELECT COUNT(*) FROM notifications").await, 2);
+        assert_eq!(
+            count(
+                pool,
+                "SELECT COUNT(*) FROM outbox_events WHERE status = 'delivered'"
+            )
+            .await,
+            1
+        );
+        println!("✅ Outbox crash recovery test passed!");
+    }
+
+    #[tokio::test]
+    async fn test_failed_consumer_is_retried_alone_with_backoff() {
+        let Some(app) = spawn_test_app().await else {
+            return;
+        };
+        let pool = &app.db_pool;
+        let healthy = Arc::new(std::sync::atomic::AtomicBool::new(false));
+        let dispatcher = OutboxDispatcher::builtin().register(FlakyConsumer {
+            healthy: healthy.clone(),
+        });
+        let mut feedback = Feedback::create(
+            pool,
+            None,
+            "8b-is/smart-tree".to_string(),
+            "Add a --json flag to the stats command".to_string(),
+            Some("https://hooks.example.com/feedback".to_string()),
+            0,
+            None,
+            "unknown",
+        )
+        .await
+        .unwrap();
+        feedback
+            .update_status(pool, FeedbackStatus::Completed, None)
+            .await
+            .unwrap();
+
+        // 🔁 The flaky consumer fails; the callback still goes out exactly once
+        assert_eq!(dispatcher.dispatch_due(pool).await.unwrap(), 1);
+        let (status, last_error, delay): (String, String, f64) = sqlx::query_as(
+            "SELECT status, last_error, EXTRACT(EPOCH FROM next_attempt_at - NOW())::float8 FROM outbox_events",
+        )
+        .fetch_one(pool)
+        .await
+        .unwrap();
+        assert_eq!(status, "pending");
+        assert!(last_error.contains("flaky: receiver is down"));
+        assert!(delay > 20.0 && delay <= 30.0);
+        assert_eq!(dispatcher.dispatch_due(pool).await.unwrap(), 0);
+
+        healthy.store(true, std::sync::atomic::Ordering::SeqCst);
+        sqlx::query("UPDATE outbox_events SET next_attempt_at = NOW()")
+            .execute(pool)
+            .await
+            .unwrap();
+        assert_eq!(dispatcher.dispatch_due(pool).await.unwrap(), 1);
+        assert_eq!(count(pool, "SELECT COUNT(*) FROM background_jobs").await, 1);
+        assert_eq!(
+            count(pool, "SELECT COUNT(*) FROM outbox_deliveries").await,
+            3
+        );
+        assert_eq!(
+            count(
+                pool,
+                "SELECT COUNT(*) FROM outbox_events WHERE status = 'delivered'"
+            )
+            .await,
+            1
+        );
+        println!("✅ Outbox retry test passed!");
+    }
+}
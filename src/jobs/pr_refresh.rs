@@ -0,0 +1,383 @@
+// 🔄 Pull Request Refresh - PR bodies that keep up with the votes! 🔄
+// When a pipeline PR is opened its body is stored with the live placeholders left in
+// (`pr_body::freeze_pr_body`), next to the hash of what GitHub got. Votes and duplicate
+// reports keep arriving afterwards, so a nightly pull_request_refresh job fills the
+// stored bodies of completed feedback in again and edits the PRs whose body came out
+// different. An unchanged body costs no GitHub call at all, so the PR timeline only
+// shows real changes. A PR found closed or merged is marked and never looked at again.
+// Every instance runs the scheduler, but a run is only queued when none is pending (see
+// `jobs::enqueue_unless_pending`), so one refresh runs per night however many there are.
+// Created with love by Aye & Hue! ✨
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::PgPool;
+use std::time::Duration;
+use tracing::{error, info, warn};
+use uuid::Uuid;
+
+use crate::api::AppState;
+use crate::database::feedback_votes;
+use crate::github::artifacts::ArtifactState;
+use crate::github::pr_body::{body_hash, finish_pr_body};
+
+use super::{JobContext, JobHandler};
+
+/// 🏷️ Job type for the nightly refresh
+pub const PR_REFRESH_JOB: &str = "pull_request_refresh";
+/// 🌙 UTC hour the nightly run is queued (after retention)
+const RUN_AT_HOUR_UTC: u32 = 4;
+
+/// 📋 Job payload (nothing to configure yet)
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct PrRefreshJob {}
+
+/// 📊 What one refresh did
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct RefreshSummary {
+    /// ✏️ PRs whose body was edited
+    pub updated: Vec<Uuid>,
+    /// 💤 PRs whose body would come out the same
+    pub unchanged: usize,
+    /// 🚪 PRs found closed or merged (and skipped from now on)
+    pub closed: Vec<Uuid>,
+    /// ❌ PRs that couldn't be refreshed this time
+    pub failed: usize,
+}
+
+/// 📝 A stored pull request body, awaiting refresh
+#[derive(Debug, sqlx::FromRow)]
+struct StoredPullRequest {
+    feedback_id: Uuid,
+    repository: String,
+    number: i64,
+    body_template: String,
+    body_hash: String,
+}
+
+/// 🚀 Remember the PR opened for `feedback_id`: its frozen body and what GitHub got
+pub async fn record_opened(
+    pool: &PgPool,
+    feedback_id: Uuid,
+    repository: &str,
+    number: u64,
+    frozen: &str,
+    body: &str,
+) -> Result<()> {
+    sqlx::query(
+        r#"
+        INSERT INTO feedback_pull_requests (feedback_id, repository, number, body_template, body_hash)
+        VALUES ($1, $2, $3, $4, $5)
+        ON CONFLICT (feedback_id) DO UPDATE
+        SET repository = $2, number = $3, body_template = $4, body_hash = $5,
+            closed_at = NULL, refreshed_at = NOW()
+        "#,
+    )
+    .bind(feedback_id)
+    .bind(repository)
+    .bind(number as i64)
+    .bind(frozen)
+    .bind(body_hash(body))
+    .execute(pool)
+    .await
+    .context("Failed to record the pull request body")?;
+    Ok(())
+}
+
+/// 🔍 PRs worth refreshing: those of completed feedback not yet seen closed
+async fn open_pull_requests(pool: &PgPool) -> Result<Vec<StoredPullRequest>> {
+    sqlx::query_as(
+        r#"
+        SELECT p.feedback_id, p.repository, p.number, p.body_template, p.body_hash
+        FROM feedback_pull_requests p
+        JOIN feedback f ON f.id = p.feedback_id
+        WHERE f.status = 'completed' AND p.closed_at IS NULL
+        ORDER BY p.refreshed_at, p.feedback_id
+        "#,
+    )
+    .fetch_all(pool)
+    .await
+    .context("Failed to list pull requests to refresh")
+}
+
+/// ✏️ What happened to one PR
+enum Refreshed {
+    Updated,
+    Unchanged,
+    Closed,
+}
+
+/// 🔄 Refresh one PR's body if its reception changed it
+async fn refresh_one(app_state: &AppState, pr: &StoredPullRequest) -> Result<Refreshed> {
+    let pool = &app_state.db_pool;
+    let reception = feedback_votes::reception(pool, pr.feedback_id).await?;
+    let body = finish_pr_body(&pr.body_template, &reception);
+    let hash = body_hash(&body);
+    if hash == pr.body_hash {
+        return Ok(Refreshed::Unchanged);
+    }
+    let (owner, repo) = pr
+        .repository
+        .split_once('/')
+        .with_context(|| format!("Invalid repository {}", pr.repository))?;
+    let number = pr.number as u64;
+    let github = app_state.github.as_ref();
+    if github.pull_request_state(owner, repo, number).await? != ArtifactState::Open {
+        sqlx::query("UPDATE feedback_pull_requests SET closed_at = NOW() WHERE feedback_id = $1")
+            .bind(pr.feedback_id)
+            .execute(pool)
+            .await
+            .context("Failed to mark the pull request closed")?;
+        return Ok(Refreshed::Closed);
+    }
+    github
+        .update_pull_request_body(owner, repo, number, &body)
+        .await?;
+    sqlx::query(
+        "UPDATE feedback_pull_requests SET body_hash = $2, refreshed_at = NOW() \
+         WHERE feedback_id = $1",
+    )
+    .bind(pr.feedback_id)
+    .bind(&hash)
+    .execute(pool)
+    .await
+    .context("Failed to record the refreshed body")?;
+    Ok(Refreshed::Updated)
+}
+
+/// 🔄 Refresh every open pipeline PR; one PR failing doesn't stop the others
+pub async fn refresh_pull_requests(app_state: &AppState) -> Result<RefreshSummary> {
+    let mut summary = RefreshSummary::default();
+    for pr in open_pull_requests(&app_state.db_pool).await? {
+        match refresh_one(app_state, &pr).await {
+            Ok(Refreshed::Updated) => summary.updated.push(pr.feedback_id),
+            Ok(Refreshed::Unchanged) => summary.unchanged += 1,
+            Ok(Refreshed::Closed) => summary.closed.push(pr.feedback_id),
+            Err(e) => {
+                warn!(
+                    "⚠️ Failed to refresh {}#{} for feedback {}: {:#}",
+                    pr.repository, pr.number, pr.feedback_id, e
+                );
+                summary.failed += 1;
+            }
+        }
+    }
+    Ok(summary)
+}
+
+/// 🔄 Runs the nightly refresh
+pub struct PrRefreshHandler;
+
+#[async_trait::async_trait]
+impl JobHandler for PrRefreshHandler {
+    const TYPE: &'static str = PR_REFRESH_JOB;
+
+    async fn run(&self, payload: serde_json::Value, ctx: &JobContext<'_>) -> Result<()> {
+        let _: PrRefreshJob =
+            serde_json::from_value(payload).context("Invalid pull request refresh payload")?;
+        let summary = refresh_pull_requests(ctx.app_state).await?;
+        info!(
+            "🔄 Refreshed pull request bodies: {} updated, {} unchanged, {} closed, {} failed",
+            summary.updated.len(),
+            summary.unchanged,
+            summary.closed.len(),
+            summary.failed
+        );
+        Ok(())
+    }
+}
+
+/// ⏰ Time until the next run is due (RUN_AT_HOUR_UTC today, else tomorrow)
+fn until_next_run(now: DateTime<Utc>) -> Duration {
+    let today = now
+        .date_naive()
+        .and_hms_opt(RUN_AT_HOUR_UTC, 0, 0)
+        .unwrap_or_default()
+        .and_utc();
+    let next = if today > now {
+        today
+    } else {
+        today + chrono::Duration::days(1)
+    };
+    (next - now).to_std().unwrap_or_default()
+}
+
+/// ➕ Queue a refresh unless one is already waiting; returns whether one was queued
+async fn enqueue_refresh(pool: &PgPool) -> Result<bool> {
+    super::enqueue_unless_pending(
+        pool,
+        PR_REFRESH_JOB,
+        serde_json::json!(PrRefreshJob::default()),
+    )
+    .await
+}
+
+/// 🚀 Enqueue a refresh every night
+pub fn spawn_scheduler(app_state: AppState) -> tokio::task::JoinHandle<()> {
+    tokio::spawn(async move {
+        loop {
+            tokio::time::sleep(until_next_run(Utc::now())).await;
+            if let Err(e) = enqueue_refresh(&app_state.db_pool).await {
+                error!("❌ Failed to schedule the pull request refresh: {:#}", e);
+            }
+        }
+    })
+}
+
+// 🧪 Tests - Fresh paint, but only where it's needed!
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::models::{Feedback, FeedbackStatus};
+    use crate::test_support::{spawn_test_app, GitHubCall};
+
+    /// 📝 Feedback with a stored PR body, in `status`
+    async fn with_pull_request(pool: &PgPool, status: FeedbackStatus, number: u64) -> Uuid {
+        let mut feedback = Feedback::create(
+            pool,
+            None,
+            "8b-is/smart-tree".to_string(),
+            "Please add dark mode".to_string(),
+            None,
+            25,
+            None,
+            "cli",
+        )
+        .await
+        .unwrap();
+        feedback.update_status(pool, status, None).await.unwrap();
+        let frozen = "Votes: {votes}{also_reported_by}";
+        let body = finish_pr_body(frozen, &Default::default());
+        record_opened(pool, feedback.id, "8b-is/smart-tree", number, frozen, &body)
+            .await
+            .unwrap();
+        feedback.id
+    }
+
+    async fn add_vote(pool: &PgPool, feedback_id: Uuid, email: &str) {
+        let user_id: Uuid = sqlx::query_scalar(
+            "INSERT INTO users (email, name, password_hash) VALUES ($1, $1, 'x') RETURNING id",
+        )
+        .bind(email)
+        .fetch_one(pool)
+        .await
+        .unwrap();
+        feedback_votes::vote(pool, feedback_id, user_id)
+            .await
+            .unwrap();
+    }
+
+    fn updates(calls: &[GitHubCall]) -> Vec<(u64, String)> {
+        calls
+            .iter()
+            .filter_map(|call| match call {
+                GitHubCall::UpdatePullRequest { number, body, .. } => Some((*number, body.clone())),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_runs_are_queued_at_four_utc() {
+        let night: DateTime<Utc> = "2026-05-01T02:00:00Z".parse().unwrap();
+        assert_eq!(until_next_run(night), Duration::from_secs(2 * 3600));
+        let morning: DateTime<Utc> = "2026-05-01T04:00:00Z".parse().unwrap();
+        assert_eq!(until_next_run(morning), Duration::from_secs(24 * 3600));
+        println!("✅ Refresh schedule test passed!");
+    }
+
+    #[tokio::test]
+    async fn test_every_instance_waking_at_four_queues_one_refresh() {
+        let Some(app) = spawn_test_app().await else {
+            return;
+        };
+        let ticks = (0..10).map(|_| enqueue_refresh(&app.db_pool));
+        let queued = futures_util::future::join_all(ticks)
+            .await
+            .into_iter()
+            .map(Result::unwrap)
+            .filter(|queued| *queued)
+            .count();
+        assert_eq!(queued, 1);
+        // 🏃 Once it has run, the next night queues again
+        assert_eq!(super::super::run_due_jobs(&app.app_state).await.unwrap(), 1);
+        assert!(enqueue_refresh(&app.db_pool).await.unwrap());
+        println!("✅ One nightly refresh test passed!");
+    }
+
+    #[tokio::test]
+    async fn test_unchanged_bodies_are_left_alone() {
+        let Some(app) = spawn_test_app().await else {
+            return;
+        };
+        let pool = &app.db_pool;
+        let feedback_id = with_pull_request(pool, FeedbackStatus::Completed, 7).await;
+
+        // 💤 Nothing new: no GitHub call at all
+        let summary = refresh_pull_requests(&app.app_state).await.unwrap();
+        assert_eq!(summary.unchanged, 1);
+        assert!(app.github.calls().is_empty());
+
+        // 👍 A vote changes the body once
+        add_vote(pool, feedback_id, "fan@8b.is").await;
+        let summary = refresh_pull_requests(&app.app_state).await.unwrap();
+        assert_eq!(summary.updated, vec![feedback_id]);
+        assert_eq!(
+            updates(&app.github.calls()),
+            vec![(7, "Votes: 1".to_string())]
+        );
+        let summary = refresh_pull_requests(&app.app_state).await.unwrap();
+        assert_eq!(summary.unchanged, 1);
+        assert_eq!(updates(&app.github.calls()).len(), 1);
+
+        // ❌ A failed edit is retried next time
+        add_vote(pool, feedback_id, "another-fan@8b.is").await;
+        *app.github.fail_with.lock().unwrap() = Some("GitHub is down".to_string());
+        let summary = refresh_pull_requests(&app.app_state).await.unwrap();
+        assert_eq!(summary.failed, 1);
+        *app.github.fail_with.lock().unwrap() = None;
+        let summary = refresh_pull_requests(&app.app_state).await.unwrap();
+        assert_eq!(summary.updated, vec![feedback_id]);
+        assert_eq!(updates(&app.github.calls())[1], (7, "Votes: 2".to_string()));
+        println!("✅ Unchanged PR body test passed!");
+    }
+
+    #[tokio::test]
+    async fn test_only_open_pull_requests_of_completed_feedback_are_refreshed() {
+        let Some(app) = spawn_test_app().await else {
+            return;
+        };
+        let pool = &app.db_pool;
+        let open = with_pull_request(pool, FeedbackStatus::Completed, 1).await;
+        let merged = with_pull_request(pool, FeedbackStatus::Completed, 2).await;
+        let failed = with_pull_request(pool, FeedbackStatus::Failed, 3).await;
+        for (feedback_id, email) in [(open, "a@8b.is"), (merged, "b@8b.is"), (failed, "c@8b.is")] {
+            add_vote(pool, feedback_id, email).await;
+        }
+        app.github
+            .merged_pull_requests
+            .lock()
+            .unwrap()
+            .push(("8b-is/smart-tree".to_string(), 2));
+
+        let summary = refresh_pull_requests(&app.app_state).await.unwrap();
+        assert_eq!(summary.updated, vec![open]);
+        assert_eq!(summary.closed, vec![merged]);
+        assert_eq!(
+            updates(&app.github.calls()),
+            vec![(1, "Votes: 1".to_string())]
+        );
+
+        // 🚪 The merged PR isn't even looked at again
+        let summary = refresh_pull_requests(&app.app_state).await.unwrap();
+        assert_eq!(
+            summary,
+            RefreshSummary {
+                unchanged: 1,
+                ..RefreshSummary::default()
+            }
+        );
+        println!("✅ Open PR filter test passed!");
+    }
+}
@@ -0,0 +1,232 @@
+// 🩹 Stuck Feedback Reconciler - Picking up what a crash dropped on the floor! 🩹
+// A process that dies while feedback is `processing`, `generating_changes` or
+// `creating_pull_request` leaves the row there forever. At startup and then every
+// few minutes, items that haven't been touched for FEEDBACK_LEASE_TIMEOUT_SECONDS and
+// have no live background job (pending, or running and started within the lease)
+// go back to `pending`. Each reset is counted in `metadata.reconcile_resets`; an
+// item that keeps getting stuck fails with `stuck_after_restarts` once it has been
+// reset FEEDBACK_MAX_RECONCILE_RESETS times, so a poison item can't loop forever.
+// Created with love by Aye & Hue! ✨
+
+use anyhow::{Context, Result};
+use std::time::Duration;
+use tracing::{error, info, warn};
+use uuid::Uuid;
+
+use crate::api::{events::AppEvent, AppState};
+use crate::database::models::{Feedback, FeedbackStatus};
+
+use super::outbox;
+
+/// ⏰ How often the reconciler looks for abandoned items
+const RECONCILE_INTERVAL: Duration = Duration::from_secs(300);
+/// 🔢 Metadata key counting how often an item was put back to pending
+pub const RESETS_KEY: &str = "reconcile_resets";
+/// ❌ Error message for items that were reset too often
+pub const STUCK_AFTER_RESTARTS: &str = "stuck_after_restarts";
+
+/// 🔍 Abandoned in-flight feedback: untouched for the lease ($1 seconds) and no live job.
+/// Each query below adds its own check of the reset count against `$2`.
+const ABANDONED: &str = r#"
+    status IN ('processing', 'generating_changes', 'creating_pull_request')
+    AND updated_at < NOW() - make_interval(secs => $1)
+    AND NOT EXISTS (
+        SELECT 1 FROM background_jobs j
+        WHERE j.payload->>'feedback_id' = feedback.id::text
+          AND (j.status = 'pending'
+               OR (j.status = 'running' AND j.started_at > NOW() - make_interval(secs => $1)))
+    )
+"#;
+
+/// 📊 What one reconcile pass did
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct ReconcileSummary {
+    /// 🔁 Items put back to pending
+    pub reset: Vec<Uuid>,
+    /// 💀 Items failed after too many resets
+    pub failed: Vec<Uuid>,
+}
+
+/// 🩹 Requeue (or give up on) every abandoned item
+pub async fn reconcile_stuck(app_state: &AppState) -> Result<ReconcileSummary> {
+    let config = &app_state.config.feedback;
+    let lease_secs = config.lease_timeout_seconds as f64;
+    let max_resets = config.max_reconcile_resets as i32;
+    let resets = format!("COALESCE((metadata->>'{}')::int, 0)", RESETS_KEY);
+
+    let mut tx = app_state
+        .db_pool
+        .begin()
+        .await
+        .context("Failed to start reconciling stuck feedback")?;
+
+    // 💀 Out of resets: fail it (with the usual terminal status side effects)
+    let failed: Vec<Feedback> = sqlx::query_as(&format!(
+        r#"
+        UPDATE feedback
+        SET status = 'failed', error_message = $3, completed_at = NOW()
+        WHERE {ABANDONED} AND {resets} >= $2
+        RETURNING *
+        "#
+    ))
+    .bind(lease_secs)
+    .bind(max_resets)
+    .bind(STUCK_AFTER_RESTARTS)
+    .fetch_all(&mut *tx)
+    .await
+    .context("Failed to fail repeatedly stuck feedback")?;
+    for feedback in &failed {
+        outbox::record_status_change(&mut tx, feedback).await?;
+    }
+
+    // 🔁 Everything else goes back to the queue, one reset more
+    let reset: Vec<Uuid> = sqlx::query_scalar(&format!(
+        r#"
+        UPDATE feedback
+        SET status = 'pending', error_message = NULL,
+            metadata = jsonb_set(COALESCE(metadata, '{{}}'::jsonb), '{{{RESETS_KEY}}}', to_jsonb({resets} + 1))
+        WHERE {ABANDONED} AND {resets} < $2
+        RETURNING id
+        "#
+    ))
+    .bind(lease_secs)
+    .bind(max_resets)
+    .fetch_all(&mut *tx)
+    .await
+    .context("Failed to requeue stuck feedback")?;
+
+    tx.commit()
+        .await
+        .context("Failed to commit reconciled feedback")?;
+
+    for id in &reset {
+        app_state.events.publish(AppEvent::FeedbackStatusChanged {
+            id: *id,
+            status: FeedbackStatus::Pending,
+        });
+    }
+    for feedback in &failed {
+        app_state.events.publish(AppEvent::FeedbackStatusChanged {
+            id: feedback.id,
+            status: feedback.status.clone(),
+        });
+    }
+    if !reset.is_empty() {
+        info!("🩹 Requeued {} stuck feedback items", reset.len());
+    }
+    if !failed.is_empty() {
+        warn!(
+            "💀 Failed {} feedback items that kept getting stuck",
+            failed.len()
+        );
+    }
+    Ok(ReconcileSummary {
+        reset,
+        failed: failed.into_iter().map(|feedback| feedback.id).collect(),
+    })
+}
+
+/// 🚀 Reconcile right away (catching the last crash), then every RECONCILE_INTERVAL
+pub fn spawn_reconciler(app_state: AppState) -> tokio::task::JoinHandle<()> {
+    info!("🩹 Starting stuck feedback reconciler");
+    tokio::spawn(async move {
+        loop {
+            if let Err(e) = reconcile_stuck(&app_state).await {
+                error!("❌ Reconciling stuck feedback failed: {:#}", e);
+            }
+            tokio::time::sleep(RECONCILE_INTERVAL).await;
+        }
+    })
+}
+
+// 🧪 Tests - Crash, restart, carry on!
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::spawn_test_app;
+    use serde_json::json;
+
+    async fn insert(
+        app_state: &AppState,
+        status: &str,
+        minutes_ago: i32,
+        metadata: Option<serde_json::Value>,
+    ) -> Uuid {
+        sqlx::query_scalar(
+            r#"
+            INSERT INTO feedback (repository, content, status, metadata, updated_at)
+            VALUES ('8b-is/crash', 'Half done', $1::feedback_status, $2,
+                    NOW() - make_interval(mins => $3))
+            RETURNING id
+            "#,
+        )
+        .bind(status)
+        .bind(metadata)
+        .bind(minutes_ago)
+        .fetch_one(&app_state.db_pool)
+        .await
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_abandoned_feedback_is_requeued_then_given_up_on() {
+        let Some(app) = spawn_test_app().await else {
+            return;
+        };
+        let app_state = &app.app_state;
+        let pool = &app.db_pool;
+        let abandoned = insert(app_state, "generating_changes", 60, None).await;
+        let fresh = insert(app_state, "processing", 1, None).await;
+        let with_job = insert(app_state, "creating_pull_request", 60, None).await;
+        crate::jobs::enqueue(
+            pool,
+            "feedback_commit_approved",
+            json!({ "feedback_id": with_job }),
+        )
+        .await
+        .unwrap();
+        let poison = insert(app_state, "processing", 60, Some(json!({ RESETS_KEY: 3 }))).await;
+        let done = insert(app_state, "completed", 60, None).await;
+
+        let summary = reconcile_stuck(app_state).await.unwrap();
+        assert_eq!(summary.reset, vec![abandoned]);
+        assert_eq!(summary.failed, vec![poison]);
+
+        let requeued = Feedback::find_by_id(pool, abandoned)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(requeued.status, FeedbackStatus::Pending);
+        assert_eq!(requeued.metadata.unwrap()[RESETS_KEY], 1);
+        let given_up = Feedback::find_by_id(pool, poison).await.unwrap().unwrap();
+        assert_eq!(given_up.status, FeedbackStatus::Failed);
+        assert_eq!(
+            given_up.error_message.as_deref(),
+            Some(STUCK_AFTER_RESTARTS)
+        );
+        for (id, status) in [
+            (fresh, FeedbackStatus::Processing),
+            (with_job, FeedbackStatus::CreatingPullRequest),
+            (done, FeedbackStatus::Completed),
+        ] {
+            let untouched = Feedback::find_by_id(pool, id).await.unwrap().unwrap();
+            assert_eq!(untouched.status, status);
+        }
+
+        // 📬 The give-up goes through the outbox like any other failure
+        let events: i64 =
+            sqlx::query_scalar("SELECT COUNT(*) FROM outbox_events WHERE feedback_id = $1")
+                .bind(poison)
+                .fetch_one(pool)
+                .await
+                .unwrap();
+        assert_eq!(events, 1);
+
+        // 🔁 Nothing left to do on the next pass
+        assert_eq!(
+            reconcile_stuck(app_state).await.unwrap(),
+            ReconcileSummary::default()
+        );
+        println!("✅ Stuck feedback reconcile test passed!");
+    }
+}
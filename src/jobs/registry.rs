@@ -0,0 +1,248 @@
+// 🗂️ Job Registry - Every job type registers here, exactly once! 🗂️
+// Handlers implement `JobHandler`; the worker claims due jobs and dispatches
+// them by `job_type`. Jobs nobody can handle are parked as failed.
+// Created with love by Aye & Hue! ✨
+
+use anyhow::Result;
+use async_trait::async_trait;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::sync::Arc;
+use tracing::{error, warn};
+
+use crate::api::AppState;
+
+use super::{
+    approval::CommitApprovedHandler, callbacks::FeedbackCallbackHandler,
+    daily_stats::DailyStatsHandler, issue_automation::IssueAutomationHandler,
+    pr_refresh::PrRefreshHandler, retention::RetentionHandler, self_issues::SelfIssueOpenHandler,
+    status_stats::StatusStatsRefreshHandler, webhook_secrets::WebhookSecretSyncHandler, Job,
+    JobErrorKind,
+};
+
+/// 🧰 What a handler gets besides its payload
+pub struct JobContext<'a> {
+    /// 🎯 Shared application state (database, GitHub, LLM, config)
+    pub app_state: &'a AppState,
+    /// 📦 The claimed job (id, attempt count, ...)
+    pub job: &'a Job,
+}
+
+/// 🔧 One kind of background work
+#[async_trait]
+pub trait JobHandler: Send + Sync + 'static {
+    /// 🏷️ Value stored in `background_jobs.job_type`
+    const TYPE: &'static str;
+
+    /// 🏃 Do the work; an error makes the queue retry with backoff, unless its kind
+    /// (a `JobError`, or what `JobErrorKind::classify` makes of it) says retrying is pointless
+    async fn run(&self, payload: Value, ctx: &JobContext<'_>) -> Result<()>;
+}
+
+/// 🎭 Object-safe wrapper so handlers with different `TYPE`s share one map
+#[async_trait]
+trait RegisteredHandler: Send + Sync {
+    async fn run(&self, payload: Value, ctx: &JobContext<'_>) -> Result<()>;
+}
+
+#[async_trait]
+impl<H: JobHandler> RegisteredHandler for H {
+    async fn run(&self, payload: Value, ctx: &JobContext<'_>) -> Result<()> {
+        JobHandler::run(self, payload, ctx).await
+    }
+}
+
+/// 🗂️ Job type -> handler
+#[derive(Clone, Default)]
+pub struct JobRegistry {
+    handlers: HashMap<&'static str, Arc<dyn RegisteredHandler>>,
+}
+
+impl JobRegistry {
+    /// 📚 Every job type this build knows how to run
+    pub fn builtin() -> Self {
+        Self::default()
+            .register(FeedbackCallbackHandler)
+            .register(DailyStatsHandler)
+            .register(IssueAutomationHandler)
+            .register(RetentionHandler)
+            .register(CommitApprovedHandler)
+            .register(PrRefreshHandler)
+            .register(StatusStatsRefreshHandler)
+            .register(SelfIssueOpenHandler)
+            .register(WebhookSecretSyncHandler)
+    }
+
+    /// ➕ Add a handler (panics on duplicate types - that's a wiring bug)
+    pub fn register<H: JobHandler>(mut self, handler: H) -> Self {
+        let previous = self.handlers.insert(H::TYPE, Arc::new(handler));
+        assert!(previous.is_none(), "job type {} registered twice", H::TYPE);
+        self
+    }
+
+    /// 🔍 Is there a handler for this job type?
+    pub fn handles(&self, job_type: &str) -> bool {
+        self.handlers.contains_key(job_type)
+    }
+
+    /// 🏷️ Registered job types, sorted
+    pub fn job_types(&self) -> Vec<&'static str> {
+        let mut types: Vec<_> = self.handlers.keys().copied().collect();
+        types.sort_unstable();
+        types
+    }
+
+    /// 🏃 Run every due job once; returns how many were attempted
+    pub async fn run_due_jobs(&self, app_state: &AppState) -> Result<usize> {
+        let pool = &app_state.db_pool;
+        let mut attempted = 0;
+
+        while let Some(job) = super::claim_next(pool).await? {
+            attempted += 1;
+
+            // 🅿️ Nothing can ever run this job here - park it instead of retrying forever
+            let Some(handler) = self.handlers.get(job.job_type.as_str()) else {
+                let message = format!("No handler registered for job type {}", job.job_type);
+                error!("🅿️ Parking job {}: {}", job.id, message);
+                super::park(pool, job.id, &message, JobErrorKind::Permanent).await?;
+                continue;
+            };
+
+            let ctx = JobContext {
+                app_state,
+                job: &job,
+            };
+            match handler.run(job.payload.clone(), &ctx).await {
+                Ok(()) => super::complete(pool, job.id).await?,
+                Err(e) => {
+                    let message = format!("{:#}", e);
+                    let kind = JobErrorKind::classify(&e);
+                    if super::fail(pool, &job, &message, kind).await? {
+                        warn!(
+                            "🔁 Job {} failed ({}), will retry: {}",
+                            job.id, kind, message
+                        );
+                    } else {
+                        error!("💀 Job {} failed for good ({}): {}", job.id, kind, message);
+                    }
+                }
+            }
+        }
+
+        Ok(attempted)
+    }
+}
+
+impl std::fmt::Debug for JobRegistry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("JobRegistry")
+            .field("job_types", &self.job_types())
+            .finish()
+    }
+}
+
+// 🧪 Tests - Everyone gets a handler!
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::spawn_test_app;
+    use std::sync::Mutex;
+
+    /// 📝 Records the payloads it was given
+    #[derive(Default)]
+    struct EchoHandler {
+        seen: Arc<Mutex<Vec<Value>>>,
+    }
+
+    #[async_trait]
+    impl JobHandler for EchoHandler {
+        const TYPE: &'static str = "echo";
+
+        async fn run(&self, payload: Value, _ctx: &JobContext<'_>) -> Result<()> {
+            self.seen.lock().unwrap().push(payload);
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_builtin_registry_knows_its_types() {
+        let registry = JobRegistry::builtin();
+        assert!(registry.handles(super::super::callbacks::FEEDBACK_CALLBACK_JOB));
+        assert!(registry.handles(super::super::daily_stats::DAILY_STATS_JOB));
+        assert!(registry.handles(super::super::issue_automation::ISSUE_AUTOMATION_JOB));
+        assert!(registry.handles(super::super::retention::RETENTION_JOB));
+        assert!(registry.handles(super::super::approval::COMMIT_APPROVED_JOB));
+        assert!(registry.handles(super::super::pr_refresh::PR_REFRESH_JOB));
+        assert!(registry.handles(super::super::status_stats::STATUS_STATS_REFRESH_JOB));
+        assert!(registry.handles(super::super::self_issues::SELF_ISSUE_OPEN_JOB));
+        assert!(!registry.handles("geoip_refresh"));
+
+        let registry = registry.register(EchoHandler::default());
+        assert_eq!(
+            registry.job_types(),
+            vec![
+                "daily_stats_snapshot",
+                "echo",
+                "feedback_callback",
+                "feedback_commit_approved",
+                "feedback_retention",
+                "issue_automation",
+                "pull_request_refresh",
+                "self_issue_open",
+                "status_stats_refresh",
+                "webhook_secret_sync"
+            ]
+        );
+        println!("✅ Job registry test passed!");
+    }
+
+    #[test]
+    #[should_panic(expected = "registered twice")]
+    fn test_duplicate_registration_panics() {
+        JobRegistry::default()
+            .register(EchoHandler::default())
+            .register(EchoHandler::default());
+    }
+
+    #[tokio::test]
+    async fn test_dispatch_and_parking_of_unknown_types() {
+        let Some(app) = spawn_test_app().await else {
+            return;
+        };
+        let echo = EchoHandler::default();
+        let seen = echo.seen.clone();
+        let registry = JobRegistry::default().register(echo);
+
+        let echo_id = super::super::enqueue(&app.db_pool, "echo", serde_json::json!({"n": 1}))
+            .await
+            .unwrap();
+        let orphan_id = super::super::enqueue(&app.db_pool, "geoip_refresh", serde_json::json!({}))
+            .await
+            .unwrap();
+
+        assert_eq!(registry.run_due_jobs(&app.app_state).await.unwrap(), 2);
+        assert_eq!(*seen.lock().unwrap(), vec![serde_json::json!({"n": 1})]);
+
+        let status = |id: uuid::Uuid| {
+            let pool = app.db_pool.clone();
+            async move {
+                sqlx::query_as::<_, (String, Option<String>, i32)>(
+                    "SELECT status, error_message, retries FROM background_jobs WHERE id = $1",
+                )
+                .bind(id)
+                .fetch_one(&pool)
+                .await
+                .unwrap()
+            }
+        };
+        assert_eq!(status(echo_id).await.0, "completed");
+        let (parked, message, retries) = status(orphan_id).await;
+        assert_eq!(parked, "failed");
+        assert_eq!(retries, 0);
+        assert!(message.unwrap().contains("geoip_refresh"));
+
+        // 🔁 Nothing left to do
+        assert_eq!(registry.run_due_jobs(&app.app_state).await.unwrap(), 0);
+        println!("✅ Job dispatch test passed!");
+    }
+}
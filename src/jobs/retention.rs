@@ -0,0 +1,471 @@
+// 🗃️ Feedback Retention - Old completed feedback moves out of the hot table! 🗃️
+// A nightly feedback_retention job (scheduled when RETENTION_ENABLED is set) takes
+// completed feedback finished more than RETENTION_COMPLETED_AFTER_DAYS ago, copies it
+// to the archive (RETENTION_ARCHIVE: the feedback_archive table, JSON lines in the
+// attachment blob store, or nowhere), adds it to the per-day feedback_rollup and deletes
+// it, one batch per transaction. Dashboard and repository totals add the rollup back,
+// so counts don't drop when rows leave. A dry run only counts what would go.
+// Created with love by Aye & Hue! ✨
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::PgPool;
+use std::time::Duration;
+use tracing::{error, info, warn};
+use uuid::Uuid;
+
+use crate::api::AppState;
+use crate::config::RetentionArchive;
+
+use super::{JobContext, JobHandler};
+
+/// 🏷️ Job type for the retention run
+pub const RETENTION_JOB: &str = "feedback_retention";
+/// 🌙 UTC hour the nightly run is queued (after the statistics snapshot)
+const RUN_AT_HOUR_UTC: u32 = 3;
+
+/// 📦 What goes into the archive for each item: the row without its callback secrets
+/// and dedup hash, plus its tags and attachment metadata (the bytes stay in the blob
+/// store, the storage keys point at them)
+const ARCHIVE_RECORD_SQL: &str = r#"
+    (to_jsonb(f) - 'callback_secret' - 'callback_secret_previous'
+        - 'callback_secret_previous_expires_at' - 'dedup_hash')
+    || jsonb_build_object(
+        'tags', COALESCE((
+            SELECT jsonb_agg(t.tag ORDER BY t.tag) FROM feedback_tags t WHERE t.feedback_id = f.id
+        ), '[]'::jsonb),
+        'attachments', COALESCE((
+            SELECT jsonb_agg(jsonb_build_object(
+                'id', a.id, 'filename', a.filename, 'content_type', a.content_type,
+                'size_bytes', a.size_bytes, 'sha256', a.sha256,
+                'storage_backend', a.storage_backend, 'storage_key', a.storage_key
+            ) ORDER BY a.created_at, a.id)
+            FROM attachments a WHERE a.feedback_id = f.id
+        ), '[]'::jsonb)
+    )
+"#;
+
+/// 📋 Job payload; without `dry_run` RETENTION_DRY_RUN decides
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct RetentionJob {
+    pub dry_run: Option<bool>,
+}
+
+/// 📊 What a retention run did (or, dry, would do)
+#[derive(Debug, Default, Clone, PartialEq, Serialize)]
+pub struct RetentionReport {
+    pub dry_run: bool,
+    /// 🔍 Completed feedback past the cutoff when the run started
+    pub eligible: i64,
+    /// 🗑️ Items removed from the feedback table
+    pub removed: u64,
+    /// 🪣 Blob keys written (RETENTION_ARCHIVE=storage), one per batch
+    pub archive_keys: Vec<String>,
+}
+
+/// 🗃️ Runs retention with the configured age and archive
+pub struct RetentionHandler;
+
+#[async_trait::async_trait]
+impl JobHandler for RetentionHandler {
+    const TYPE: &'static str = RETENTION_JOB;
+
+    async fn run(&self, payload: serde_json::Value, ctx: &JobContext<'_>) -> Result<()> {
+        let job: RetentionJob =
+            serde_json::from_value(payload).context("Invalid retention job payload")?;
+        let config = &ctx.app_state.config.retention;
+        let dry_run = job.dry_run.unwrap_or(config.dry_run);
+        let report = apply_retention(ctx.app_state, dry_run, Utc::now()).await?;
+        if report.dry_run {
+            info!(
+                "🧪 Retention dry run: {} completed feedback older than {} days would be removed (archive: {:?})",
+                report.eligible, config.completed_after_days, config.archive
+            );
+        } else {
+            info!(
+                "🗃️ Retention removed {} completed feedback older than {} days (archive: {:?})",
+                report.removed, config.completed_after_days, config.archive
+            );
+        }
+        Ok(())
+    }
+}
+
+/// 🗃️ Archive and remove completed feedback older than the configured age, batch by batch
+pub async fn apply_retention(
+    app_state: &AppState,
+    dry_run: bool,
+    now: DateTime<Utc>,
+) -> Result<RetentionReport> {
+    let config = &app_state.config.retention;
+    let cutoff = now - chrono::Duration::days(config.completed_after_days.into());
+    let eligible: i64 = sqlx::query_scalar(
+        "SELECT COUNT(*) FROM feedback WHERE status = 'completed' AND COALESCE(completed_at, updated_at) < $1",
+    )
+    .bind(cutoff)
+    .fetch_one(&app_state.db_pool)
+    .await
+    .context("Failed to count expired feedback")?;
+
+    let mut report = RetentionReport {
+        dry_run,
+        eligible,
+        ..Default::default()
+    };
+    if dry_run {
+        return Ok(report);
+    }
+    loop {
+        let (removed, archive_key) = remove_batch(app_state, cutoff, now).await?;
+        report.removed += removed;
+        report.archive_keys.extend(archive_key);
+        if removed < u64::from(config.batch_size) {
+            return Ok(report);
+        }
+    }
+}
+
+/// 📦 One transaction: lock a batch, archive it, roll it up, delete it
+async fn remove_batch(
+    app_state: &AppState,
+    cutoff: DateTime<Utc>,
+    now: DateTime<Utc>,
+) -> Result<(u64, Option<String>)> {
+    let config = &app_state.config.retention;
+    let mut tx = app_state
+        .db_pool
+        .begin()
+        .await
+        .context("Failed to start retention transaction")?;
+
+    // 🔒 Rows another run (or a status change) holds are left for next time
+    let ids: Vec<Uuid> = sqlx::query_scalar(
+        r#"
+        SELECT id FROM feedback
+        WHERE status = 'completed' AND COALESCE(completed_at, updated_at) < $1
+        ORDER BY COALESCE(completed_at, updated_at), id
+        LIMIT $2
+        FOR UPDATE SKIP LOCKED
+        "#,
+    )
+    .bind(cutoff)
+    .bind(i64::from(config.batch_size))
+    .fetch_all(&mut *tx)
+    .await
+    .context("Failed to select expired feedback")?;
+    if ids.is_empty() {
+        return Ok((0, None));
+    }
+
+    let mut archive_key = None;
+    let mut orphaned_blobs: Vec<String> = Vec::new();
+    match config.archive {
+        RetentionArchive::Table => {
+            sqlx::query(&format!(
+                r#"
+                INSERT INTO feedback_archive (id, repository, created_at, completed_at, record)
+                SELECT f.id, f.repository, f.created_at, f.completed_at, {}
+                FROM feedback f WHERE f.id = ANY($1)
+                ON CONFLICT (id) DO NOTHING
+                "#,
+                ARCHIVE_RECORD_SQL
+            ))
+            .bind(&ids)
+            .execute(&mut *tx)
+            .await
+            .context("Failed to archive expired feedback")?;
+        }
+        RetentionArchive::Storage => {
+            let records: Vec<serde_json::Value> = sqlx::query_scalar(&format!(
+                "SELECT {} FROM feedback f WHERE f.id = ANY($1) ORDER BY f.created_at, f.id",
+                ARCHIVE_RECORD_SQL
+            ))
+            .bind(&ids)
+            .fetch_all(&mut *tx)
+            .await
+            .context("Failed to read expired feedback for the archive")?;
+            let mut lines = String::new();
+            for record in &records {
+                lines.push_str(&record.to_string());
+                lines.push('\n');
+            }
+            // 🪣 Written before the delete commits: a failed commit leaves a spare copy
+            // that the retry archives again, never a removal without one
+            let key = format!("{}/{}", now.date_naive(), Uuid::new_v4());
+            app_state
+                .blobs
+                .put(&key, "application/x-ndjson", lines.into_bytes())
+                .await
+                .context("Failed to write the retention archive")?;
+            archive_key = Some(key);
+        }
+        RetentionArchive::None => {
+            // 🚮 Nothing refers to the attachment bytes afterwards, so they go too
+            orphaned_blobs = sqlx::query_scalar(
+                "SELECT storage_key FROM attachments WHERE feedback_id = ANY($1)",
+            )
+            .bind(&ids)
+            .fetch_all(&mut *tx)
+            .await
+            .context("Failed to list attachments of expired feedback")?;
+        }
+    }
+
+    sqlx::query(
+        r#"
+        INSERT INTO feedback_rollup (repository, source, created_date, feedback_count)
+        SELECT repository, source, (created_at AT TIME ZONE 'UTC')::date, COUNT(*)
+        FROM feedback WHERE id = ANY($1)
+        GROUP BY 1, 2, 3
+        ON CONFLICT (repository, source, created_date)
+        DO UPDATE SET feedback_count = feedback_rollup.feedback_count + EXCLUDED.feedback_count
+        "#,
+    )
+    .bind(&ids)
+    .execute(&mut *tx)
+    .await
+    .context("Failed to roll up expired feedback")?;
+
+    let removed = sqlx::query("DELETE FROM feedback WHERE id = ANY($1)")
+        .bind(&ids)
+        .execute(&mut *tx)
+        .await
+        .context("Failed to remove expired feedback")?
+        .rows_affected();
+    tx.commit()
+        .await
+        .context("Failed to commit retention batch")?;
+
+    for key in orphaned_blobs {
+        if let Err(e) = app_state.blobs.delete(&key).await {
+            warn!("⚠️ Failed to delete attachment blob {}: {:#}", key, e);
+        }
+    }
+    Ok((removed, archive_key))
+}
+
+/// 🔢 Completed feedback removed by retention that was created at or after `since`
+/// (None = all time). The rollup is per UTC day, so `since` counts from its whole day.
+pub async fn rolled_up_count(pool: &PgPool, since: Option<DateTime<Utc>>) -> Result<i64> {
+    sqlx::query_scalar(
+        r#"
+        SELECT COALESCE(SUM(feedback_count), 0)::bigint FROM feedback_rollup
+        WHERE ($1::timestamptz IS NULL OR created_date >= ($1 AT TIME ZONE 'UTC')::date)
+        "#,
+    )
+    .bind(since)
+    .fetch_one(pool)
+    .await
+    .context("Failed to read the feedback rollup")
+}
+
+/// ⏰ Time until the next nightly run (RUN_AT_HOUR_UTC, today if still ahead)
+fn until_next_run(now: DateTime<Utc>) -> Duration {
+    let today = now
+        .date_naive()
+        .and_hms_opt(RUN_AT_HOUR_UTC, 0, 0)
+        .unwrap_or_default()
+        .and_utc();
+    let next = if today > now {
+        today
+    } else {
+        today + chrono::Duration::days(1)
+    };
+    (next - now).to_std().unwrap_or_default()
+}
+
+/// 🚀 Enqueue a retention run every night
+pub fn spawn_scheduler(app_state: AppState) -> tokio::task::JoinHandle<()> {
+    tokio::spawn(async move {
+        loop {
+            tokio::time::sleep(until_next_run(Utc::now())).await;
+            let payload = serde_json::json!(RetentionJob::default());
+            if let Err(e) = super::enqueue(&app_state.db_pool, RETENTION_JOB, payload).await {
+                error!("❌ Failed to schedule the retention run: {:#}", e);
+            }
+        }
+    })
+}
+
+// 🧪 Tests - Out with the old, counts stay put!
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::models::Feedback;
+
+    /// 📝 A completed feedback item finished `days_ago` days ago
+    async fn completed_feedback(pool: &PgPool, repository: &str, days_ago: i64) -> Uuid {
+        let feedback = Feedback::create(
+            pool,
+            None,
+            repository.to_string(),
+            "Please add dark mode".to_string(),
+            None,
+            25,
+            None,
+            "cli",
+        )
+        .await
+        .unwrap();
+        sqlx::query(
+            "UPDATE feedback SET status = 'completed', completed_at = NOW() - make_interval(days => $2) WHERE id = $1",
+        )
+        .bind(feedback.id)
+        .bind(days_ago as i32)
+        .execute(pool)
+        .await
+        .unwrap();
+        feedback.id
+    }
+
+    async fn count(pool: &PgPool, sql: &str) -> i64 {
+        sqlx::query_scalar(sql).fetch_one(pool).await.unwrap()
+    }
+
+    #[test]
+    fn test_next_run_is_the_coming_3am_utc() {
+        let night: DateTime<Utc> = "2026-05-01T01:30:00Z".parse().unwrap();
+        assert_eq!(until_next_run(night), Duration::from_secs(90 * 60));
+        let morning: DateTime<Utc> = "2026-05-01T03:00:00Z".parse().unwrap();
+        assert_eq!(until_next_run(morning), Duration::from_secs(24 * 3600));
+        println!("✅ Retention schedule test passed!");
+    }
+
+    #[tokio::test]
+    async fn test_retention_archives_removes_and_keeps_counts() {
+        let Some(app) = crate::test_support::spawn_test_app_with_config(|config| {
+            config.retention.completed_after_days = 30;
+            config.retention.batch_size = 2;
+        })
+        .await
+        else {
+            return;
+        };
+        let pool = &app.db_pool;
+        let tagged = completed_feedback(pool, "8b-is/smart-tree", 90).await;
+        sqlx::query("INSERT INTO feedback_tags (feedback_id, tag) VALUES ($1, 'ux')")
+            .bind(tagged)
+            .execute(pool)
+            .await
+            .unwrap();
+        completed_feedback(pool, "8b-is/smart-tree", 60).await;
+        completed_feedback(pool, "8b-is/feedbacker", 45).await;
+        let recent = completed_feedback(pool, "8b-is/smart-tree", 5).await;
+        // ⏳ Old but not completed: never touched
+        Feedback::create(
+            pool,
+            None,
+            "8b-is/smart-tree".to_string(),
+            "Still waiting".to_string(),
+            None,
+            25,
+            None,
+            "cli",
+        )
+        .await
+        .unwrap();
+        sqlx::query("UPDATE feedback SET created_at = NOW() - INTERVAL '400 days'")
+            .execute(pool)
+            .await
+            .unwrap();
+        let before = crate::api::admin::get_dashboard_stats(&app.app_state, None)
+            .await
+            .unwrap();
+
+        // 🧪 Dry run counts and changes nothing
+        let report = apply_retention(&app.app_state, true, Utc::now())
+            .await
+            .unwrap();
+        assert_eq!(report.eligible, 3);
+        assert_eq!(report.removed, 0);
+        assert_eq!(count(pool, "SELECT COUNT(*) FROM feedback").await, 5);
+
+        // 🗃️ The real thing runs through the queue, two per batch
+        super::super::enqueue(pool, RETENTION_JOB, serde_json::json!({}))
+            .await
+            .unwrap();
+        assert_eq!(super::super::run_due_jobs(&app.app_state).await.unwrap(), 1);
+        let remaining: Vec<Uuid> =
+            sqlx::query_scalar("SELECT id FROM feedback WHERE status = 'completed'")
+                .fetch_all(pool)
+                .await
+                .unwrap();
+        assert_eq!(remaining, vec![recent]);
+        assert_eq!(count(pool, "SELECT COUNT(*) FROM feedback").await, 2);
+        assert_eq!(
+            count(pool, "SELECT COUNT(*) FROM feedback_archive").await,
+            3
+        );
+
+        let record: serde_json::Value =
+            sqlx::query_scalar("SELECT record FROM feedback_archive WHERE id = $1")
+                .bind(tagged)
+                .fetch_one(pool)
+                .await
+                .unwrap();
+        assert_eq!(record["content"], "Please add dark mode");
+        assert_eq!(record["tags"], serde_json::json!(["ux"]));
+        assert!(record.get("callback_secret").is_none());
+
+        // 📊 Totals and completed counts are unchanged, per repository too
+        let after = crate::api::admin::get_dashboard_stats(&app.app_state, None)
+            .await
+            .unwrap();
+        assert_eq!(after.total_feedback, before.total_feedback);
+        assert_eq!(after.completed_feedback, before.completed_feedback);
+        assert_eq!(
+            count(
+                pool,
+                "SELECT SUM(feedback_count)::bigint FROM feedback_rollup WHERE repository = '8b-is/smart-tree'"
+            )
+            .await,
+            2
+        );
+        println!("✅ Retention archive test passed!");
+    }
+
+    #[tokio::test]
+    async fn test_retention_can_archive_to_blob_storage() {
+        let Some(app) = crate::test_support::spawn_test_app_with_config(|config| {
+            config.retention.completed_after_days = 30;
+            config.retention.archive = RetentionArchive::Storage;
+        })
+        .await
+        else {
+            return;
+        };
+        let first = completed_feedback(&app.db_pool, "8b-is/smart-tree", 90).await;
+        let second = completed_feedback(&app.db_pool, "8b-is/smart-tree", 40).await;
+
+        let report = apply_retention(&app.app_state, false, Utc::now())
+            .await
+            .unwrap();
+        assert_eq!(report.removed, 2);
+        assert_eq!(report.archive_keys.len(), 1);
+        assert!(crate::storage::is_valid_key(&report.archive_keys[0]));
+
+        let archived = app
+            .app_state
+            .blobs
+            .get(&report.archive_keys[0])
+            .await
+            .unwrap()
+            .unwrap();
+        let ids: Vec<String> = String::from_utf8(archived)
+            .unwrap()
+            .lines()
+            .map(|line| {
+                let record: serde_json::Value = serde_json::from_str(line).unwrap();
+                record["id"].as_str().unwrap().to_string()
+            })
+            .collect();
+        assert_eq!(ids.len(), 2);
+        assert!(ids.contains(&first.to_string()) && ids.contains(&second.to_string()));
+        assert_eq!(
+            count(&app.db_pool, "SELECT COUNT(*) FROM feedback_archive").await,
+            0
+        );
+        println!("✅ Retention blob archive test passed!");
+    }
+}
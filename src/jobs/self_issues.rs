@@ -0,0 +1,922 @@
+// 🐛 Self-Issues - Feedbacker files its own bug reports! 🐛
+// An outbox consumer for failed feedback. Most failures are about the target
+// repository (the LLM gave up, every path was blocked, ...) and stay the submitter's
+// business. The ones that look like our bugs - `internal_error` failures, panics, and a
+// diff validation failure once it has happened in more than one repository - open an
+// issue in SELF_ISSUES_REPOSITORY with the error detail and a redacted feedback excerpt.
+//
+// Errors are fingerprinted (digits and ids normalized away) and each fingerprint keeps
+// its record in the `settings` table under `self_issue:<fingerprint>`, so a repeat gets
+// a 👍 comment on the existing issue instead of a new one. At most
+// SELF_ISSUES_MAX_PER_DAY issues are opened per UTC day (counted in `self_issue_daily`);
+// a throttled fingerprint files on its next occurrence once the day has rolled over.
+// The issue number is written back into the feedback's metadata under `self_issue`.
+//
+// Opening the issue happens outside the locked transaction: the consumer reserves it
+// (a marker on the fingerprint record, a slot in the daily count) and queues a
+// self_issue_open job. The job looks for an issue carrying the marker before creating
+// one, so a retry after GitHub answered - or after recording the number failed - adopts
+// the issue it already opened instead of filing a second one.
+// Created with love by Aye & Hue! ✨
+
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use sqlx::{PgConnection, PgPool};
+use std::sync::Arc;
+use tracing::{info, warn};
+use uuid::Uuid;
+
+use super::callbacks::FeedbackCallbackPayload;
+use super::outbox::{OutboxConsumer, OutboxEvent, FEEDBACK_FAILED_EVENT};
+use super::{JobContext, JobHandler};
+use crate::config::SelfIssuesConfig;
+use crate::github::labels::LabelSpec;
+use crate::github::ops::GitHubOps;
+use crate::utils::privacy::redact_excerpt;
+
+/// 🏷️ Label on every self-issue
+pub const SELF_ISSUE_LABEL: &str = "self-issue";
+/// 🏷️ Job type that opens a reserved self-issue
+pub const SELF_ISSUE_OPEN_JOB: &str = "self_issue_open";
+/// ⏳ How long a reserved issue may take to open before the next occurrence queues it
+/// again (under the same marker, so an issue that did get opened is still adopted)
+const OPENING_TIMEOUT_MINUTES: i64 = 60;
+/// 🔑 Settings key holding today's self-issue count
+const DAILY_COUNT_KEY: &str = "self_issue_daily";
+/// ✂️ Feedback characters quoted in the issue
+const EXCERPT_CHARS: usize = 300;
+/// ✂️ Error lines (and characters per line) quoted in the issue
+const ERROR_LINES: usize = 40;
+const ERROR_LINE_CHARS: usize = 300;
+
+/// 🔍 The kinds of failure that point at Feedbacker rather than the target repository
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FailureKind {
+    /// 💥 A non-retryable `internal_error`
+    InternalError,
+    /// 💥 Something panicked
+    Panic,
+    /// 🧩 A generated diff failed validation (only ours once seen in two repositories)
+    DiffValidation,
+}
+
+impl FailureKind {
+    /// 🏷️ Short name used in titles and labels
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::InternalError => "internal_error",
+            Self::Panic => "panic",
+            Self::DiffValidation => "diff_validation",
+        }
+    }
+
+    /// 🔁 Distinct repositories a failure must show up in before it counts as ours
+    fn repositories_needed(self) -> usize {
+        match self {
+            Self::DiffValidation => 2,
+            Self::InternalError | Self::Panic => 1,
+        }
+    }
+}
+
+/// 🔍 Is this error message one of ours?
+pub fn classify(error_message: &str) -> Option<FailureKind> {
+    let lower = error_message.trim_start().to_lowercase();
+    if lower.contains("panicked at") {
+        Some(FailureKind::Panic)
+    } else if lower.starts_with("internal_error") {
+        Some(FailureKind::InternalError)
+    } else if lower.starts_with("diff_validation")
+        || lower.contains("diff validation failed")
+        || lower.contains("invalid diff")
+    {
+        Some(FailureKind::DiffValidation)
+    } else {
+        None
+    }
+}
+
+/// #️⃣ Stable id of an error: the kind plus its first line (from "panicked at" on, for
+/// panics) with numbers and hex ids normalized, so line numbers and feedback ids don't
+/// split one bug into many
+pub fn fingerprint(kind: FailureKind, error_message: &str) -> String {
+    let line = error_message.lines().next().unwrap_or_default();
+    let line = match line.to_lowercase().find("panicked at") {
+        Some(start) if kind == FailureKind::Panic => &line[start..],
+        _ => line,
+    };
+    let normalized = line
+        .split_whitespace()
+        .map(normalize_word)
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    let mut hasher = Sha256::new();
+    hasher.update(kind.as_str().as_bytes());
+    hasher.update([0]);
+    hasher.update(normalized.as_bytes());
+    hex::encode(hasher.finalize())[..12].to_string()
+}
+
+/// 🔢 Hex ids become "X", then every run of digits "N"
+fn normalize_word(word: &str) -> String {
+    let bare = word.trim_matches(|c: char| !c.is_ascii_alphanumeric());
+    if bare.len() >= 8
+        && bare.chars().all(|c| c.is_ascii_hexdigit() || c == '-')
+        && bare.chars().any(|c| c.is_ascii_digit())
+    {
+        return word.replace(bare, "X");
+    }
+    let mut normalized = String::with_capacity(word.len());
+    for c in word.chars() {
+        if c.is_ascii_digit() {
+            if !normalized.ends_with('N') {
+                normalized.push('N');
+            }
+        } else {
+            normalized.push(c);
+        }
+    }
+    normalized
+}
+
+/// 🏷️ The label that ties an issue to its fingerprint
+pub fn fingerprint_label(fingerprint: &str) -> String {
+    format!("fingerprint:{}", fingerprint)
+}
+
+/// 🎨 The labels a self-issue carries, as they're created if the repository lacks them
+fn self_issue_labels(fingerprint: &str) -> Vec<LabelSpec> {
+    vec![
+        LabelSpec {
+            name: SELF_ISSUE_LABEL.to_string(),
+            color: "b60205".to_string(),
+            description: "Filed by Feedbacker for a failure that looks like its own bug"
+                .to_string(),
+        },
+        LabelSpec {
+            name: fingerprint_label(fingerprint),
+            color: "ededed".to_string(),
+            description: "Ties the issue to one failure fingerprint".to_string(),
+        },
+    ]
+}
+
+/// 📋 What we remember about one fingerprint
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct FingerprintRecord {
+    #[serde(default)]
+    issue_number: Option<u64>,
+    /// ⏳ An issue reserved and queued, but not opened (or not recorded) yet
+    #[serde(default)]
+    opening: Option<OpeningIssue>,
+    #[serde(default)]
+    repositories: Vec<String>,
+    #[serde(default)]
+    occurrences: u64,
+}
+
+/// 🎫 A reserved self-issue: the marker its body carries and when it was queued
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+struct OpeningIssue {
+    marker: Uuid,
+    queued_at: DateTime<Utc>,
+}
+
+/// 🔖 Hidden line in the issue body that ties it to its reservation
+fn marker_line(marker: Uuid) -> String {
+    format!("<!-- feedbacker-self-issue: {} -->", marker)
+}
+
+/// 🚦 Self-issues opened on one UTC day
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct DailyCount {
+    date: NaiveDate,
+    count: u32,
+}
+
+/// 📥 The failure being reported
+#[derive(Debug, Clone)]
+pub struct FailedFeedback {
+    pub feedback_id: Uuid,
+    pub repository: String,
+    pub error_message: String,
+    pub content: String,
+}
+
+/// ✅ What reporting a failure did
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SelfIssueOutcome {
+    /// 🤷 Not a Feedbacker bug
+    NotOurs,
+    /// ⏳ Recorded, but it hasn't shown up in enough repositories yet
+    Watching,
+    /// 🚦 Would have opened an issue, but today's allowance is used up
+    Throttled,
+    /// 🎫 Reserved an issue and queued the job that opens it
+    Queued,
+    /// ⏳ An issue for it is already being opened
+    Opening,
+    /// 👍 Commented on this existing issue
+    Commented(u64),
+}
+
+/// 🐛 Report a failed feedback item: queue a new issue, 👍 on an existing one, or
+/// nothing. Settings rows are locked through `conn` (the outbox delivery transaction),
+/// so concurrent failures with the same fingerprint queue up behind each other instead
+/// of reserving two issues; the job is queued through `conn` too.
+pub async fn report_failure(
+    conn: &mut PgConnection,
+    github: &dyn GitHubOps,
+    config: &SelfIssuesConfig,
+    failure: &FailedFeedback,
+    now: DateTime<Utc>,
+) -> Result<SelfIssueOutcome> {
+    let Some(kind) = classify(&failure.error_message) else {
+        return Ok(SelfIssueOutcome::NotOurs);
+    };
+    let (owner, repo) = config
+        .repository
+        .split_once('/')
+        .context("SELF_ISSUES_REPOSITORY must be owner/repo")?;
+    let fingerprint = fingerprint(kind, &failure.error_message);
+    let key = format!("self_issue:{}", fingerprint);
+
+    let mut record: FingerprintRecord = lock_setting(conn, &key, "Self-issue fingerprint").await?;
+    record.occurrences += 1;
+    if !record.repositories.contains(&failure.repository) {
+        record.repositories.push(failure.repository.clone());
+        record.repositories.sort();
+    }
+
+    let outcome = if let Some(number) = record.issue_number {
+        github
+            .add_comment_to_issue(
+                owner,
+                repo,
+                number as u32,
+                &format!(
+                    "👍 Seen again on feedback `{}` ({}), {} occurrences in {} repositories so far.",
+                    failure.feedback_id,
+                    failure.repository,
+                    record.occurrences,
+                    record.repositories.len()
+                ),
+            )
+            .await
+            .context("Failed to comment on self-issue")?;
+        SelfIssueOutcome::Commented(number)
+    } else if record
+        .opening
+        .is_some_and(|opening| now - opening.queued_at < Duration::minutes(OPENING_TIMEOUT_MINUTES))
+    {
+        SelfIssueOutcome::Opening
+    } else if record.repositories.len() < kind.repositories_needed() {
+        SelfIssueOutcome::Watching
+    } else {
+        let mut daily: DailyCount =
+            lock_setting(conn, DAILY_COUNT_KEY, "Self-issues opened today").await?;
+        let today = now.date_naive();
+        if daily.date != today {
+            daily = DailyCount {
+                date: today,
+                count: 0,
+            };
+        }
+        if daily.count >= config.max_per_day {
+            warn!(
+                "🚦 Self-issue for {} skipped, {} already opened today",
+                fingerprint, daily.count
+            );
+            SelfIssueOutcome::Throttled
+        } else {
+            // 🔖 A timed-out reservation keeps its marker, in case its issue did get opened
+            let marker = record
+                .opening
+                .map_or_else(Uuid::new_v4, |opening| opening.marker);
+            let job = SelfIssueJob {
+                fingerprint: fingerprint.clone(),
+                marker,
+                feedback_id: failure.feedback_id,
+                title: issue_title(kind, &failure.error_message),
+                body: issue_body(kind, &fingerprint, failure, &record, marker),
+            };
+            super::enqueue(&mut *conn, SELF_ISSUE_OPEN_JOB, serde_json::json!(job)).await?;
+            record.opening = Some(OpeningIssue {
+                marker,
+                queued_at: now,
+            });
+            daily.count += 1;
+            store_setting(conn, DAILY_COUNT_KEY, &daily).await?;
+            info!("🐛 Queued a self-issue for {}", fingerprint);
+            SelfIssueOutcome::Queued
+        }
+    };
+    store_setting(conn, &key, &record).await?;
+
+    if let SelfIssueOutcome::Commented(number) = outcome {
+        record_on_feedback(conn, failure.feedback_id, config, number, &fingerprint).await?;
+    }
+    Ok(outcome)
+}
+
+/// 🏷️ Point a feedback item's metadata at its self-issue
+async fn record_on_feedback(
+    conn: &mut PgConnection,
+    feedback_id: Uuid,
+    config: &SelfIssuesConfig,
+    number: u64,
+    fingerprint: &str,
+) -> Result<()> {
+    sqlx::query(
+        "UPDATE feedback SET metadata = COALESCE(metadata, '{}'::jsonb) || jsonb_build_object('self_issue', $2::jsonb) WHERE id = $1",
+    )
+    .bind(feedback_id)
+    .bind(serde_json::json!({
+        "repository": config.repository,
+        "number": number,
+        "fingerprint": fingerprint,
+    }))
+    .execute(&mut *conn)
+    .await
+    .context("Failed to record self-issue on feedback")?;
+    Ok(())
+}
+
+/// 📋 self_issue_open payload: everything needed to file the reserved issue
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SelfIssueJob {
+    pub fingerprint: String,
+    pub marker: Uuid,
+    /// 📥 The failure that reserved it (its metadata gets the issue number)
+    pub feedback_id: Uuid,
+    pub title: String,
+    pub body: String,
+}
+
+/// 🎫 Open a reserved self-issue - or adopt the one an earlier attempt opened - and
+/// record its number. GitHub is called with no row locked; only recording the number
+/// takes the fingerprint's lock, briefly.
+pub async fn open_self_issue(
+    pool: &PgPool,
+    github: &dyn GitHubOps,
+    config: &SelfIssuesConfig,
+    job: &SelfIssueJob,
+) -> Result<u64> {
+    let (owner, repo) = config
+        .repository
+        .split_once('/')
+        .context("SELF_ISSUES_REPOSITORY must be owner/repo")?;
+    let marker = marker_line(job.marker);
+    let number = match github.find_recent_issue(owner, repo, &marker).await? {
+        Some(number) => {
+            info!(
+                "🔖 Self-issue #{} for {} was already opened",
+                number, job.fingerprint
+            );
+            number
+        }
+        None => {
+            let specs = self_issue_labels(&job.fingerprint);
+            if let Err(e) = github.ensure_labels(owner, repo, &specs).await {
+                warn!(
+                    "⚠️ Couldn't create self-issue labels in {}: {:#}",
+                    config.repository, e
+                );
+            }
+            let labels: Vec<String> = specs.into_iter().map(|spec| spec.name).collect();
+            let created = github
+                .create_issue(owner, repo, &job.title, &job.body, Some(&labels), None)
+                .await
+                .context("Failed to open self-issue")?;
+            if let Some(e) = &created.label_error {
+                warn!(
+                    "⚠️ Self-issue #{} left unlabelled: {}",
+                    created.issue.number, e
+                );
+            }
+            info!(
+                "🐛 Opened self-issue #{} for {}",
+                created.issue.number, job.fingerprint
+            );
+            created.issue.number
+        }
+    };
+
+    let mut tx = pool.begin().await?;
+    let key = format!("self_issue:{}", job.fingerprint);
+    let mut record: FingerprintRecord =
+        lock_setting(&mut tx, &key, "Self-issue fingerprint").await?;
+    record.issue_number = Some(number);
+    record.opening = None;
+    store_setting(&mut tx, &key, &record).await?;
+    record_on_feedback(&mut tx, job.feedback_id, config, number, &job.fingerprint).await?;
+    tx.commit().await?;
+    Ok(number)
+}
+
+/// 🎫 Runs `open_self_issue` for queued reservations
+pub struct SelfIssueOpenHandler;
+
+#[async_trait]
+impl JobHandler for SelfIssueOpenHandler {
+    const TYPE: &'static str = SELF_ISSUE_OPEN_JOB;
+
+    async fn run(&self, payload: serde_json::Value, ctx: &JobContext<'_>) -> Result<()> {
+        let job: SelfIssueJob =
+            serde_json::from_value(payload).context("Invalid self-issue job payload")?;
+        let app_state = ctx.app_state;
+        open_self_issue(
+            &app_state.db_pool,
+            app_state.github.as_ref(),
+            &app_state.config.self_issues,
+            &job,
+        )
+        .await?;
+        Ok(())
+    }
+}
+
+/// 🔒 Read a JSON settings value with the row locked (created empty the first time)
+async fn lock_setting<T>(conn: &mut PgConnection, key: &str, description: &str) -> Result<T>
+where
+    T: serde::de::DeserializeOwned + Default,
+{
+    sqlx::query(
+        "INSERT INTO settings (key, value, description) VALUES ($1, '{}', $2) ON CONFLICT (key) DO NOTHING",
+    )
+    .bind(key)
+    .bind(description)
+    .execute(&mut *conn)
+    .await
+    .with_context(|| format!("Failed to create setting {}", key))?;
+    let value: String = sqlx::query_scalar("SELECT value FROM settings WHERE key = $1 FOR UPDATE")
+        .bind(key)
+        .fetch_one(&mut *conn)
+        .await
+        .with_context(|| format!("Failed to lock setting {}", key))?;
+    // 🤷 An unreadable value starts over rather than blocking every report
+    Ok(serde_json::from_str(&value).unwrap_or_default())
+}
+
+/// ✍️ Write a JSON settings value back
+async fn store_setting<T: Serialize>(conn: &mut PgConnection, key: &str, value: &T) -> Result<()> {
+    sqlx::query("UPDATE settings SET value = $2 WHERE key = $1")
+        .bind(key)
+        .bind(serde_json::to_string(value)?)
+        .execute(&mut *conn)
+        .await
+        .with_context(|| format!("Failed to store setting {}", key))?;
+    Ok(())
+}
+
+/// 🎫 "🐛 panic: panicked at src/github/client.rs:…" (redacted, one line)
+fn issue_title(kind: FailureKind, error_message: &str) -> String {
+    let first_line = error_message.lines().next().unwrap_or_default();
+    format!("🐛 {}: {}", kind.as_str(), redact_excerpt(first_line, 80))
+}
+
+/// 📝 Error detail, where it came from and a redacted excerpt of the feedback
+fn issue_body(
+    kind: FailureKind,
+    fingerprint: &str,
+    failure: &FailedFeedback,
+    record: &FingerprintRecord,
+    marker: Uuid,
+) -> String {
+    let error = failure
+        .error_message
+        .lines()
+        .take(ERROR_LINES)
+        .map(|line| redact_excerpt(line, ERROR_LINE_CHARS))
+        .collect::<Vec<_>>()
+        .join("\n");
+    format!(
+        "Feedback `{id}` for {repository} failed in a way that looks like a Feedbacker bug.\n\n\
+         - **Kind:** {kind}\n\
+         - **Fingerprint:** `{fingerprint}`\n\
+         - **Seen:** {occurrences} times in {repositories} repositories\n\n\
+         ### Error\n\n```text\n{error}\n```\n\n\
+         ### Feedback excerpt (redacted)\n\n> {excerpt}\n\n\
+         _Filed automatically. Later occurrences of the same fingerprint are added as comments._\n\n\
+         {marker}",
+        id = failure.feedback_id,
+        repository = failure.repository,
+        kind = kind.as_str(),
+        fingerprint = fingerprint,
+        occurrences = record.occurrences,
+        repositories = record.repositories.len(),
+        error = error,
+        excerpt = redact_excerpt(&failure.content, EXCERPT_CHARS),
+        marker = marker_line(marker),
+    )
+}
+
+/// 📬 Reports failed feedback through `report_failure` (registered when SELF_ISSUES_ENABLED)
+pub struct SelfIssueConsumer {
+    github: Arc<dyn GitHubOps>,
+    config: SelfIssuesConfig,
+}
+
+impl SelfIssueConsumer {
+    /// ➕ Consumer filing into `config.repository`
+    pub fn new(github: Arc<dyn GitHubOps>, config: SelfIssuesConfig) -> Self {
+        Self { github, config }
+    }
+}
+
+#[async_trait]
+impl OutboxConsumer for SelfIssueConsumer {
+    fn name(&self) -> &'static str {
+        "self_issue"
+    }
+
+    fn wants(&self, event_type: &str) -> bool {
+        event_type == FEEDBACK_FAILED_EVENT
+    }
+
+    async fn consume(&self, event: &OutboxEvent, conn: &mut PgConnection) -> Result<()> {
+        let body: FeedbackCallbackPayload = serde_json::from_value(event.payload.clone())
+            .context("Invalid feedback event payload")?;
+        let Some(error_message) = body.error_message else {
+            return Ok(());
+        };
+        let content: Option<String> =
+            sqlx::query_scalar("SELECT content FROM feedback WHERE id = $1")
+                .bind(body.feedback_id)
+                .fetch_optional(&mut *conn)
+                .await
+                .context("Failed to read failed feedback")?;
+        let failure = FailedFeedback {
+            feedback_id: body.feedback_id,
+            repository: body.repository,
+            error_message,
+            content: content.unwrap_or_default(),
+        };
+        report_failure(
+            conn,
+            self.github.as_ref(),
+            &self.config,
+            &failure,
+            Utc::now(),
+        )
+        .await?;
+        Ok(())
+    }
+}
+
+// 🧪 Tests - Filing our own bugs, once!
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{spawn_test_app, GitHubCall, TestApp};
+    use chrono::{Duration, TimeZone};
+
+    fn config(max_per_day: u32) -> SelfIssuesConfig {
+        SelfIssuesConfig {
+            enabled: true,
+            repository: "8b-is/Feedbacker".to_string(),
+            max_per_day,
+        }
+    }
+
+    async fn failure(app: &TestApp, repository: &str, error_message: &str) -> FailedFeedback {
+        let feedback = crate::database::models::Feedback::create(
+            &app.db_pool,
+            None,
+            repository.to_string(),
+            "Crashes for me (jane@example.com) every time".to_string(),
+            None,
+            0,
+            None,
+            "cli",
+        )
+        .await
+        .unwrap();
+        FailedFeedback {
+            feedback_id: feedback.id,
+            repository: repository.to_string(),
+            error_message: error_message.to_string(),
+            content: feedback.content,
+        }
+    }
+
+    async fn report(
+        app: &TestApp,
+        config: &SelfIssuesConfig,
+        failure: &FailedFeedback,
+        now: DateTime<Utc>,
+    ) -> SelfIssueOutcome {
+        let mut conn = app.db_pool.acquire().await.unwrap();
+        report_failure(&mut conn, app.github.as_ref(), config, failure, now)
+            .await
+            .unwrap()
+    }
+
+    /// 🎫 Report, then run the queued self_issue_open job; the issue number it recorded
+    async fn report_and_open(
+        app: &TestApp,
+        config: &SelfIssuesConfig,
+        failure: &FailedFeedback,
+        now: DateTime<Utc>,
+    ) -> u64 {
+        assert_eq!(
+            report(app, config, failure, now).await,
+            SelfIssueOutcome::Queued
+        );
+        assert_eq!(crate::jobs::run_due_jobs(&app.app_state).await.unwrap(), 1);
+        let metadata: serde_json::Value =
+            sqlx::query_scalar("SELECT metadata FROM feedback WHERE id = $1")
+                .bind(failure.feedback_id)
+                .fetch_one(&app.db_pool)
+                .await
+                .unwrap();
+        metadata["self_issue"]["number"].as_u64().unwrap()
+    }
+
+    /// 🎫 Each opened issue's title, with the labels applied right after it
+    fn created_issues(app: &TestApp) -> Vec<(String, Vec<String>)> {
+        let calls = app.github.calls();
+        calls
+            .iter()
+            .enumerate()
+            .filter_map(|(i, call)| match call {
+                GitHubCall::CreateIssue { title, .. } => {
+                    let labels = match calls.get(i + 1) {
+                        Some(GitHubCall::Labels { labels, .. }) => labels.clone(),
+                        _ => Vec::new(),
+                    };
+                    Some((title.clone(), labels))
+                }
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_classification_and_fingerprints() {
+        assert_eq!(
+            classify("internal_error: worker lost feedback 7f3a9c21"),
+            Some(FailureKind::InternalError)
+        );
+        assert_eq!(
+            classify("thread 'tokio-runtime-worker' panicked at src/llm/mod.rs:412:9:\nindex out of bounds"),
+            Some(FailureKind::Panic)
+        );
+        assert_eq!(
+            classify("Diff validation failed: hunk 3 does not apply"),
+            Some(FailureKind::DiffValidation)
+        );
+        // 🙅 The target repository's problems stay the submitter's
+        assert_eq!(classify("LLM gave up"), None);
+        assert_eq!(
+            classify("All 2 generated changes touch paths this project doesn't allow: .github/x"),
+            None
+        );
+
+        // 🔢 Line numbers, ids and thread names don't split a bug
+        let panic = |at: &str| {
+            fingerprint(
+                FailureKind::Panic,
+                &format!("thread '{}' panicked at src/llm/mod.rs:{}:\nboom", at, at),
+            )
+        };
+        assert_eq!(panic("412:9"), panic("415:13"));
+        assert_eq!(
+            fingerprint(
+                FailureKind::InternalError,
+                "internal_error: worker lost feedback 7f3a9c21-0b1d"
+            ),
+            fingerprint(
+                FailureKind::InternalError,
+                "internal_error: worker lost feedback 0e12ab34-99ff"
+            )
+        );
+        assert_ne!(
+            fingerprint(
+                FailureKind::InternalError,
+                "internal_error: worker lost feedback"
+            ),
+            fingerprint(
+                FailureKind::InternalError,
+                "internal_error: queue table missing"
+            )
+        );
+        assert_eq!(
+            fingerprint_label(&panic("1:1")).len(),
+            "fingerprint:".len() + 12
+        );
+        println!("✅ Failure classification test passed!");
+    }
+
+    #[tokio::test]
+    async fn test_repeats_comment_on_the_existing_issue() {
+        let Some(app) = spawn_test_app().await else {
+            return;
+        };
+        let config = config(3);
+        let now = Utc.with_ymd_and_hms(2026, 10, 15, 9, 0, 0).unwrap();
+
+        let first = failure(
+            &app,
+            "8b-is/smart-tree",
+            "internal_error: worker lost 7f3a9c21",
+        )
+        .await;
+        let number = report_and_open(&app, &config, &first, now).await;
+        let issues = created_issues(&app);
+        assert_eq!(issues.len(), 1);
+        assert!(issues[0].0.starts_with("🐛 internal_error:"));
+        assert!(issues[0].1.contains(&SELF_ISSUE_LABEL.to_string()));
+        // 🎨 ... after making sure its labels exist in the repository
+        let ensured = app.github.ensured_labels.lock().unwrap().clone();
+        assert_eq!(ensured.len(), 1);
+        assert_eq!(
+            ensured[0]
+                .1
+                .iter()
+                .map(|spec| spec.name.clone())
+                .collect::<Vec<_>>(),
+            issues[0].1
+        );
+        let body = app
+            .github
+            .calls()
+            .into_iter()
+            .find_map(|call| match call {
+                GitHubCall::CreateIssue { body, .. } => Some(body),
+                _ => None,
+            })
+            .unwrap();
+        // 🕶️ The excerpt is redacted
+        assert!(body.contains("Crashes for me [email] every time"));
+        assert!(!body.contains("jane@example.com"));
+
+        // 👍 The same bug elsewhere, with another id: a comment, no new issue
+        let again = failure(&app, "8b-is/other", "internal_error: worker lost 0e12ab34").await;
+        assert_eq!(
+            report(&app, &config, &again, now + Duration::hours(1)).await,
+            SelfIssueOutcome::Commented(number)
+        );
+        assert_eq!(created_issues(&app).len(), 1);
+        assert!(app.github.calls().iter().any(|call| matches!(
+            call,
+            GitHubCall::Comment { repo, issue_number, body }
+                if repo == "8b-is/Feedbacker" && *issue_number as u64 == number
+                    && body.contains("2 occurrences in 2 repositories")
+        )));
+
+        // 🏷️ Both feedback items point at the issue
+        for id in [first.feedback_id, again.feedback_id] {
+            let metadata: serde_json::Value =
+                sqlx::query_scalar("SELECT metadata FROM feedback WHERE id = $1")
+                    .bind(id)
+                    .fetch_one(&app.db_pool)
+                    .await
+                    .unwrap();
+            assert_eq!(metadata["self_issue"]["number"], number);
+            assert_eq!(metadata["self_issue"]["repository"], "8b-is/Feedbacker");
+        }
+
+        // 🧩 Diff validation only counts once a second repository hits it
+        let diff = "Diff validation failed: hunk 3 does not apply";
+        for repository in ["8b-is/smart-tree", "8b-is/smart-tree"] {
+            let failure = failure(&app, repository, diff).await;
+            assert_eq!(
+                report(&app, &config, &failure, now).await,
+                SelfIssueOutcome::Watching
+            );
+        }
+        let elsewhere = failure(&app, "8b-is/other", diff).await;
+        report_and_open(&app, &config, &elsewhere, now).await;
+
+        // 🤷 Ordinary failures are left alone
+        let ordinary = failure(&app, "8b-is/other", "LLM gave up").await;
+        assert_eq!(
+            report(&app, &config, &ordinary, now).await,
+            SelfIssueOutcome::NotOurs
+        );
+        assert_eq!(created_issues(&app).len(), 2);
+        println!("✅ Self-issue dedupe test passed!");
+    }
+
+    #[tokio::test]
+    async fn test_daily_throttle_resets_at_midnight_utc() {
+        let Some(app) = spawn_test_app().await else {
+            return;
+        };
+        let config = config(1);
+        let evening = Utc.with_ymd_and_hms(2026, 10, 15, 23, 50, 0).unwrap();
+
+        let first = failure(
+            &app,
+            "8b-is/smart-tree",
+            "internal_error: queue table missing",
+        )
+        .await;
+        report_and_open(&app, &config, &first, evening).await;
+        // 🚦 A second bug the same day is only recorded
+        let second = failure(
+            &app,
+            "8b-is/smart-tree",
+            "thread 'main' panicked at src/a.rs:1:1",
+        )
+        .await;
+        assert_eq!(
+            report(&app, &config, &second, evening + Duration::minutes(5)).await,
+            SelfIssueOutcome::Throttled
+        );
+        assert_eq!(created_issues(&app).len(), 1);
+        let metadata: Option<serde_json::Value> =
+            sqlx::query_scalar("SELECT metadata FROM feedback WHERE id = $1")
+                .bind(second.feedback_id)
+                .fetch_one(&app.db_pool)
+                .await
+                .unwrap();
+        assert_eq!(metadata, None);
+
+        // 📅 After midnight its next occurrence files, counting the throttled one
+        let next_day = failure(
+            &app,
+            "8b-is/smart-tree",
+            "thread 'main' panicked at src/a.rs:2:7",
+        )
+        .await;
+        report_and_open(&app, &config, &next_day, evening + Duration::minutes(15)).await;
+        let issues = app
+            .github
+            .calls()
+            .into_iter()
+            .filter_map(|call| match call {
+                GitHubCall::CreateIssue { body, .. } => Some(body),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(issues.len(), 2);
+        assert!(issues[1].contains("**Seen:** 2 times in 1 repositories"));
+        // 🚦 ... and uses up the new day's allowance
+        let third = failure(&app, "8b-is/smart-tree", "internal_error: cache poisoned").await;
+        assert_eq!(
+            report(&app, &config, &third, evening + Duration::hours(2)).await,
+            SelfIssueOutcome::Throttled
+        );
+        println!("✅ Self-issue daily throttle test passed!");
+    }
+
+    #[tokio::test]
+    async fn test_an_issue_is_opened_once_however_often_the_job_runs() {
+        let Some(app) = spawn_test_app().await else {
+            return;
+        };
+        let config = config(5);
+        let now = Utc.with_ymd_and_hms(2026, 10, 15, 9, 0, 0).unwrap();
+        let first = failure(&app, "8b-is/smart-tree", "internal_error: lease lost").await;
+        assert_eq!(
+            report(&app, &config, &first, now).await,
+            SelfIssueOutcome::Queued
+        );
+
+        // ⏳ Occurrences while the issue is being opened don't reserve another
+        let repeat = failure(&app, "8b-is/smart-tree", "internal_error: lease lost").await;
+        assert_eq!(
+            report(&app, &config, &repeat, now + Duration::minutes(10)).await,
+            SelfIssueOutcome::Opening
+        );
+        // ⏰ ...until the reservation times out: queued again, under the same marker
+        assert_eq!(
+            report(&app, &config, &repeat, now + Duration::minutes(61)).await,
+            SelfIssueOutcome::Queued
+        );
+        let jobs: Vec<serde_json::Value> = sqlx::query_scalar(
+            "SELECT payload FROM background_jobs WHERE job_type = $1 ORDER BY created_at",
+        )
+        .bind(SELF_ISSUE_OPEN_JOB)
+        .fetch_all(&app.db_pool)
+        .await
+        .unwrap();
+        assert_eq!(jobs.len(), 2);
+        assert_eq!(jobs[0]["marker"], jobs[1]["marker"]);
+
+        // 🔖 The first job opens the issue, the second finds it by its marker
+        assert_eq!(crate::jobs::run_due_jobs(&app.app_state).await.unwrap(), 2);
+        assert_eq!(created_issues(&app).len(), 1);
+        let job: SelfIssueJob = serde_json::from_value(jobs[0].clone()).unwrap();
+        assert!(job.body.ends_with(&marker_line(job.marker)));
+
+        // 🔁 A retry after GitHub answered but before the number was recorded adopts it too
+        let number = open_self_issue(&app.db_pool, app.github.as_ref(), &config, &job)
+            .await
+            .unwrap();
+        assert_eq!(created_issues(&app).len(), 1);
+        let later = failure(&app, "8b-is/other", "internal_error: lease lost").await;
+        assert_eq!(
+            report(&app, &config, &later, now + Duration::hours(2)).await,
+            SelfIssueOutcome::Commented(number)
+        );
+        println!("✅ Self-issue reconciliation test passed!");
+    }
+}
@@ -0,0 +1,110 @@
+// 📊 Status Stats Refresh - Keeping the public numbers five minutes fresh! 📊
+// Every instance runs a scheduler that queues a status_stats_refresh job every five
+// minutes, but only one job waits at a time (see `jobs::enqueue_unless_pending`), and the handler rebuilds the `status_stats` materialized view
+// concurrently (see api::status_stats). A refresh that finds another one running
+// skips - the numbers are about to be fresh anyway.
+// Created with love by Aye & Hue! ✨
+
+use anyhow::Result;
+use std::time::Duration;
+use tracing::{debug, error};
+
+use crate::api::{status_stats, AppState};
+
+use super::{JobContext, JobHandler};
+
+/// 🏷️ Job type for the view refresh
+pub const STATUS_STATS_REFRESH_JOB: &str = "status_stats_refresh";
+/// ⏰ How often the view is refreshed
+pub const REFRESH_INTERVAL: Duration = Duration::from_secs(5 * 60);
+
+/// 🔄 Rebuilds the status stats view
+pub struct StatusStatsRefreshHandler;
+
+#[async_trait::async_trait]
+impl JobHandler for StatusStatsRefreshHandler {
+    const TYPE: &'static str = STATUS_STATS_REFRESH_JOB;
+
+    async fn run(&self, _payload: serde_json::Value, ctx: &JobContext<'_>) -> Result<()> {
+        match status_stats::refresh(&ctx.app_state.db_pool).await? {
+            Some(refreshed_at) => debug!("📊 Refreshed status stats at {}", refreshed_at),
+            None => debug!("📊 Status stats refresh already running, skipped"),
+        }
+        Ok(())
+    }
+}
+
+/// ➕ Queue a refresh unless one is already waiting; returns whether one was queued
+async fn enqueue_refresh(app_state: &AppState) -> Result<bool> {
+    super::enqueue_unless_pending(
+        &app_state.db_pool,
+        STATUS_STATS_REFRESH_JOB,
+        serde_json::json!({}),
+    )
+    .await
+}
+
+/// 🚀 Enqueue a refresh every REFRESH_INTERVAL
+pub fn spawn_scheduler(app_state: AppState) -> tokio::task::JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(REFRESH_INTERVAL);
+        loop {
+            interval.tick().await;
+            if let Err(e) = enqueue_refresh(&app_state).await {
+                error!("❌ Failed to schedule the status stats refresh: {:#}", e);
+            }
+        }
+    })
+}
+
+// 🧪 Tests - One refresh in the queue at a time!
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_refresh_job_is_queued_once_and_runs() {
+        let Some(app) = crate::test_support::spawn_test_app().await else {
+            return;
+        };
+        assert!(enqueue_refresh(&app.app_state).await.unwrap());
+        assert!(!enqueue_refresh(&app.app_state).await.unwrap());
+
+        let before: chrono::DateTime<chrono::Utc> = sqlx::query_scalar(
+            "SELECT refreshed_at FROM materialized_view_refreshes WHERE view_name = 'status_stats'",
+        )
+        .fetch_one(&app.db_pool)
+        .await
+        .unwrap();
+        assert_eq!(super::super::run_due_jobs(&app.app_state).await.unwrap(), 1);
+        let stats = status_stats::StatusStats::load(&app.db_pool).await.unwrap();
+        assert!(stats.refreshed_at > before);
+        assert!(enqueue_refresh(&app.app_state).await.unwrap());
+        println!("✅ Status stats refresh job test passed!");
+    }
+
+    #[tokio::test]
+    async fn test_schedulers_on_every_instance_queue_one_refresh() {
+        let Some(app) = crate::test_support::spawn_test_app().await else {
+            return;
+        };
+        // 🏁 Twenty instances' schedulers tick at the same moment
+        let ticks = (0..20).map(|_| enqueue_refresh(&app.app_state));
+        let queued = futures_util::future::join_all(ticks)
+            .await
+            .into_iter()
+            .map(Result::unwrap)
+            .filter(|queued| *queued)
+            .count();
+        assert_eq!(queued, 1);
+        let pending: i64 = sqlx::query_scalar(
+            "SELECT COUNT(*) FROM background_jobs WHERE job_type = $1 AND status = 'pending'",
+        )
+        .bind(STATUS_STATS_REFRESH_JOB)
+        .fetch_one(&app.db_pool)
+        .await
+        .unwrap();
+        assert_eq!(pending, 1);
+        println!("✅ Concurrent scheduler test passed!");
+    }
+}
@@ -0,0 +1,215 @@
+// 🔏 Webhook Secret Sync - Rotating the secret we sign with, on every hook we made! 🔏
+// The repository webhooks registered for projects carry GITHUB_WEBHOOK_SECRET inside
+// GitHub, and GitHub signs with whatever it was given. After a rotation those hooks
+// keep signing with the old secret, which stops verifying once the overlap ends - so
+// while GITHUB_WEBHOOK_SECRET_PREVIOUS is set, startup queues one webhook_secret_sync
+// job per rotation (keyed by GITHUB_WEBHOOK_SECRET_ROTATED_AT) that updates each of
+// our hooks to the primary secret. Hooks added by hand are not ours to touch: the logs
+// say which secret each delivery matched, so stragglers show up there.
+// Created with love by Aye & Hue! ✨
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::PgPool;
+use tracing::{error, info, warn};
+use uuid::Uuid;
+
+use crate::api::AppState;
+use crate::github::repo_hooks::{install_project_webhook, HookInstall};
+
+use super::{JobContext, JobHandler};
+
+/// 🏷️ Job type for pushing a rotated secret to our repository webhooks
+pub const WEBHOOK_SECRET_SYNC_JOB: &str = "webhook_secret_sync";
+
+/// 📋 Job payload: the rotation it belongs to
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WebhookSecretSyncJob {
+    pub rotated_at: DateTime<Utc>,
+}
+
+/// ➕ Queue the sync for a rotation unless it was already queued (or done) - every
+/// instance calls this at startup, under the same lock as the schedulers
+pub async fn enqueue_sync(pool: &PgPool, rotated_at: DateTime<Utc>) -> Result<bool> {
+    let payload = serde_json::json!(WebhookSecretSyncJob { rotated_at });
+    let mut tx = pool.begin().await?;
+    sqlx::query("SELECT pg_advisory_xact_lock(hashtext('scheduler#' || $1))")
+        .bind(WEBHOOK_SECRET_SYNC_JOB)
+        .execute(&mut *tx)
+        .await
+        .context("Failed to take the webhook secret sync lock")?;
+    let queued: bool = sqlx::query_scalar(
+        "SELECT EXISTS (SELECT 1 FROM background_jobs WHERE job_type = $1 AND payload = $2 AND status <> 'failed')",
+    )
+    .bind(WEBHOOK_SECRET_SYNC_JOB)
+    .bind(&payload)
+    .fetch_one(&mut *tx)
+    .await?;
+    if queued {
+        return Ok(false);
+    }
+    super::enqueue(&mut *tx, WEBHOOK_SECRET_SYNC_JOB, payload).await?;
+    tx.commit().await?;
+    Ok(true)
+}
+
+/// 🔏 Update every webhook we registered to the current secret (one project per
+/// repository - they share the hook); returns the repositories that failed
+pub async fn sync_webhook_secrets(app_state: &AppState) -> Result<Vec<String>> {
+    let projects: Vec<(Uuid, String)> = sqlx::query_as(
+        "SELECT DISTINCT ON (repository) id, repository FROM projects WHERE webhook_id IS NOT NULL ORDER BY repository, created_at",
+    )
+    .fetch_all(&app_state.db_pool)
+    .await
+    .context("Failed to list projects with webhooks")?;
+
+    let mut failed = Vec::new();
+    for (project_id, repository) in projects {
+        match install_project_webhook(
+            &*app_state.github,
+            &app_state.db_pool,
+            &app_state.config,
+            project_id,
+        )
+        .await?
+        {
+            HookInstall::Installed(_) | HookInstall::Disabled => {}
+            HookInstall::Failed(reason) => {
+                warn!(
+                    "⚠️ Webhook on {} still has the old secret: {}",
+                    repository, reason
+                );
+                failed.push(repository);
+            }
+        }
+    }
+    Ok(failed)
+}
+
+/// 🔏 Runs the sync; hooks that couldn't be updated make the job retry
+pub struct WebhookSecretSyncHandler;
+
+#[async_trait::async_trait]
+impl JobHandler for WebhookSecretSyncHandler {
+    const TYPE: &'static str = WEBHOOK_SECRET_SYNC_JOB;
+
+    async fn run(&self, payload: serde_json::Value, ctx: &JobContext<'_>) -> Result<()> {
+        let job: WebhookSecretSyncJob =
+            serde_json::from_value(payload).context("Invalid webhook secret sync payload")?;
+        let failed = sync_webhook_secrets(ctx.app_state).await?;
+        if !failed.is_empty() {
+            anyhow::bail!(
+                "Webhooks on {} still carry the secret rotated out at {}",
+                failed.join(", "),
+                job.rotated_at
+            );
+        }
+        info!(
+            "🔏 Repository webhooks now sign with the secret rotated in at {}",
+            job.rotated_at
+        );
+        Ok(())
+    }
+}
+
+/// 🚀 Queue the sync at startup while a rotation is in progress
+pub fn spawn_on_rotation(app_state: AppState) -> Option<tokio::task::JoinHandle<()>> {
+    let github = &app_state.config.github;
+    if github.webhook_secret_previous.is_none() || !app_state.config.features.enable_github_webhooks
+    {
+        return None;
+    }
+    let rotated_at = github.webhook_secret_rotated_at?;
+    Some(tokio::spawn(async move {
+        match enqueue_sync(&app_state.db_pool, rotated_at).await {
+            Ok(true) => info!(
+                "🔏 Queued the webhook secret sync for the rotation at {}",
+                rotated_at
+            ),
+            Ok(false) => {}
+            Err(e) => error!("❌ Failed to queue the webhook secret sync: {:#}", e),
+        }
+    }))
+}
+
+// 🧪 Tests - Old secrets don't linger on our hooks!
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{spawn_test_app_with_config, GitHubCall};
+
+    #[tokio::test]
+    async fn test_a_rotation_updates_each_registered_hook_once() {
+        let rotated_at = Utc::now();
+        let Some(app) = spawn_test_app_with_config(|config| {
+            config.github.webhook_secret = Some("new-secret".to_string());
+            config.github.webhook_secret_previous = Some("old-secret".to_string());
+            config.github.webhook_secret_rotated_at = Some(rotated_at);
+        })
+        .await
+        else {
+            return;
+        };
+        for (owner, repository, webhook_id) in [
+            ("a@example.com", "8b-is/smart-tree", Some(1_i64)),
+            ("b@example.com", "8b-is/smart-tree", Some(1)),
+            ("a@example.com", "8b-is/mem8", Some(2)),
+            ("a@example.com", "8b-is/by-hand", None),
+        ] {
+            let owner_id: Uuid = sqlx::query_scalar(
+                "INSERT INTO users (email, name, password_hash) VALUES ($1, 'Owner', 'x') \
+                 ON CONFLICT (email) DO UPDATE SET name = EXCLUDED.name RETURNING id",
+            )
+            .bind(owner)
+            .fetch_one(&app.db_pool)
+            .await
+            .unwrap();
+            sqlx::query(
+                "INSERT INTO projects (owner_id, repository, webhook_id) VALUES ($1, $2, $3)",
+            )
+            .bind(owner_id)
+            .bind(repository)
+            .bind(webhook_id)
+            .execute(&app.db_pool)
+            .await
+            .unwrap();
+        }
+
+        // 🔁 Every instance asks at startup; the rotation gets one job
+        assert!(enqueue_sync(&app.db_pool, rotated_at).await.unwrap());
+        assert!(!enqueue_sync(&app.db_pool, rotated_at).await.unwrap());
+        crate::jobs::registry::JobRegistry::builtin()
+            .run_due_jobs(&app.app_state)
+            .await
+            .unwrap();
+        assert!(!enqueue_sync(&app.db_pool, rotated_at).await.unwrap());
+
+        // 🔏 Each of our hooks once, signed; the hand-made one is left alone
+        let mut updated: Vec<_> = app
+            .github
+            .calls()
+            .into_iter()
+            .map(|call| match call {
+                GitHubCall::CreateHook { repo, signed, .. } => (repo, signed),
+                other => panic!("unexpected call {:?}", other),
+            })
+            .collect();
+        updated.sort();
+        assert_eq!(
+            updated,
+            vec![
+                ("8b-is/mem8".to_string(), true),
+                ("8b-is/smart-tree".to_string(), true)
+            ]
+        );
+
+        // 🔄 The next rotation is a new job
+        assert!(
+            enqueue_sync(&app.db_pool, rotated_at + chrono::Duration::days(1))
+                .await
+                .unwrap()
+        );
+        println!("✅ Webhook secret sync test passed!");
+    }
+}
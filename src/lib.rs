@@ -0,0 +1,14 @@
+// 📚 Feedbacker Library - The pieces that stand on their own! 📚
+// The service is the `feedbacker` binary (src/main.rs). Modules that only depend on
+// external crates live here instead, so benches and tooling can link against them;
+// the binary re-exports each one where it has always been (`api::settings_cache`,
+// `utils::coalesce`).
+// Created with love by Aye & Hue! ✨
+
+pub mod api {
+    pub mod settings_cache; // ⚡ Runtime settings overrides, refreshed in the background
+}
+
+pub mod utils {
+    pub mod coalesce; // 🧵 Single-flight guard so concurrent cache misses share one fetch
+}
@@ -0,0 +1,335 @@
+// 🩺 LLM Provider Health - Picking the default provider with data, not hunches! 🩺
+// From the last 24 hours of `llm_usage` rows, each provider gets a success rate, a
+// timeout rate, its p95 latency and a score: the exponential moving average of its
+// call outcomes (1 = success, 0 = anything else), oldest first, so recent trouble
+// weighs more than old trouble. `advise` decides whether the default provider is
+// unhealthy enough, and another one healthy enough, to suggest (or make) a switch.
+// Created with love by Aye & Hue! ✨
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::PgPool;
+
+use super::usage::CallOutcome;
+use crate::config::LlmProvider;
+
+/// 📅 Calls considered for the health report
+pub const HEALTH_WINDOW_HOURS: i64 = 24;
+/// 📉 Weight of the newest call in the score
+pub const EMA_ALPHA: f64 = 0.1;
+/// 🔢 Calls the default provider needs in the window before it can be called unhealthy
+pub const MIN_CALLS_FOR_ADVICE: u64 = 10;
+
+/// 🧾 One recorded LLM call
+#[derive(Debug, Clone, PartialEq)]
+pub struct UsageEvent {
+    pub provider: String,
+    pub outcome: CallOutcome,
+    pub latency_ms: i64,
+    pub at: DateTime<Utc>,
+}
+
+/// 🩺 How one provider did over the window
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ProviderHealth {
+    pub provider: String,
+    pub calls: u64,
+    /// ✅ Share of calls that succeeded (0-1)
+    pub success_rate: f64,
+    /// ⏱️ Share of calls that timed out (0-1)
+    pub timeout_rate: f64,
+    /// 🐢 95th percentile latency (nearest rank)
+    pub p95_latency_ms: i64,
+    /// 📉 Exponential moving average of the outcomes (0-1)
+    pub score: f64,
+}
+
+/// 📋 Every provider with calls in the window (providers without calls are absent)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HealthReport {
+    pub computed_at: DateTime<Utc>,
+    pub providers: Vec<ProviderHealth>,
+}
+
+impl HealthReport {
+    /// 🧮 Compute the report from the calls made in the HEALTH_WINDOW_HOURS before `now`
+    pub fn compute(events: &[UsageEvent], now: DateTime<Utc>) -> Self {
+        let since = now - Duration::hours(HEALTH_WINDOW_HOURS);
+        let mut recent: Vec<&UsageEvent> = events
+            .iter()
+            .filter(|event| event.at >= since && event.at <= now)
+            .collect();
+        recent.sort_by_key(|event| event.at);
+
+        let mut names: Vec<&str> = recent.iter().map(|event| event.provider.as_str()).collect();
+        names.sort_unstable();
+        names.dedup();
+        let providers = names
+            .into_iter()
+            .map(|name| {
+                let calls: Vec<&UsageEvent> = recent
+                    .iter()
+                    .copied()
+                    .filter(|event| event.provider == name)
+                    .collect();
+                ProviderHealth::from_calls(name, &calls)
+            })
+            .collect();
+        Self {
+            computed_at: now,
+            providers,
+        }
+    }
+
+    /// 🔍 One provider's health (None without calls in the window)
+    pub fn get(&self, provider: &LlmProvider) -> Option<&ProviderHealth> {
+        self.providers
+            .iter()
+            .find(|health| health.provider == provider.as_str())
+    }
+
+    /// 🗃️ The report the monitor last stored, from the settings cache
+    pub fn from_settings(value: Option<&serde_json::Value>) -> Option<Self> {
+        serde_json::from_value(value?.clone()).ok()
+    }
+}
+
+impl ProviderHealth {
+    /// 🧮 Rates, p95 and score of one provider's calls, oldest first (at least one)
+    fn from_calls(provider: &str, calls: &[&UsageEvent]) -> Self {
+        let total = calls.len() as f64;
+        let successes = calls
+            .iter()
+            .filter(|event| event.outcome == CallOutcome::Success)
+            .count();
+        let timeouts = calls
+            .iter()
+            .filter(|event| event.outcome == CallOutcome::Timeout)
+            .count();
+
+        let mut latencies: Vec<i64> = calls.iter().map(|event| event.latency_ms).collect();
+        latencies.sort_unstable();
+        let rank = ((latencies.len() as f64) * 0.95).ceil() as usize;
+        let p95_latency_ms = latencies[rank.clamp(1, latencies.len()) - 1];
+
+        let mut outcomes = calls
+            .iter()
+            .map(|event| f64::from(u8::from(event.outcome == CallOutcome::Success)));
+        let first = outcomes.next().unwrap_or_default();
+        let score = outcomes.fold(first, |score, outcome| {
+            EMA_ALPHA * outcome + (1.0 - EMA_ALPHA) * score
+        });
+
+        Self {
+            provider: provider.to_string(),
+            calls: calls.len() as u64,
+            success_rate: successes as f64 / total,
+            timeout_rate: timeouts as f64 / total,
+            p95_latency_ms,
+            score,
+        }
+    }
+}
+
+/// 🔀 The default provider is struggling and another one looks better
+#[derive(Debug, Clone, PartialEq)]
+pub struct SwitchAdvice {
+    pub from: LlmProvider,
+    pub to: LlmProvider,
+    /// ✅ The default provider's success rate in the window
+    pub from_success_rate: f64,
+    /// ✅ The suggested provider's success rate (None when it had no calls)
+    pub to_success_rate: Option<f64>,
+    /// 🤖 Make the switch (LLM_AUTO_SWITCH) instead of only suggesting it
+    pub switch: bool,
+}
+
+/// 🤔 Should the default provider change? Only when it made at least
+/// MIN_CALLS_FOR_ADVICE calls and succeeded on fewer than `floor` of them, and another
+/// configured provider is at or above the floor (or untried). Among those, the
+/// highest score wins, and providers with data beat untried ones.
+pub fn advise(
+    report: &HealthReport,
+    current: &LlmProvider,
+    configured: &[LlmProvider],
+    floor: f64,
+    auto_switch: bool,
+) -> Option<SwitchAdvice> {
+    let current_health = report.get(current)?;
+    if current_health.calls < MIN_CALLS_FOR_ADVICE || current_health.success_rate >= floor {
+        return None;
+    }
+    let (to, to_health) = configured
+        .iter()
+        .filter(|provider| *provider != current)
+        .map(|provider| (provider, report.get(provider)))
+        .filter(|(_, health)| health.is_none_or(|health| health.success_rate >= floor))
+        .max_by(|(_, a), (_, b)| {
+            let rank = |health: &Option<&ProviderHealth>| health.map_or(-1.0, |h| h.score);
+            rank(a).total_cmp(&rank(b))
+        })?;
+    Some(SwitchAdvice {
+        from: current.clone(),
+        to: to.clone(),
+        from_success_rate: current_health.success_rate,
+        to_success_rate: to_health.map(|health| health.success_rate),
+        switch: auto_switch,
+    })
+}
+
+/// 🗄️ Calls recorded in the HEALTH_WINDOW_HOURS before now
+pub async fn load_events(pool: &PgPool) -> Result<Vec<UsageEvent>> {
+    let rows: Vec<(String, String, i64, DateTime<Utc>)> = sqlx::query_as(
+        "SELECT provider, outcome, latency_ms, created_at FROM llm_usage \
+         WHERE created_at >= NOW() - make_interval(hours => $1)",
+    )
+    .bind(HEALTH_WINDOW_HOURS as i32)
+    .fetch_all(pool)
+    .await
+    .context("Failed to read LLM usage")?;
+    Ok(rows
+        .into_iter()
+        .filter_map(|(provider, outcome, latency_ms, at)| {
+            Some(UsageEvent {
+                provider,
+                outcome: CallOutcome::parse(&outcome)?,
+                latency_ms,
+                at,
+            })
+        })
+        .collect())
+}
+
+// 🧪 Tests - Synthetic calls, real arithmetic!
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn events(
+        provider: &LlmProvider,
+        outcomes: &[CallOutcome],
+        now: DateTime<Utc>,
+    ) -> Vec<UsageEvent> {
+        outcomes
+            .iter()
+            .enumerate()
+            .map(|(n, outcome)| UsageEvent {
+                provider: provider.as_str().to_string(),
+                outcome: *outcome,
+                latency_ms: 100 * (n as i64 + 1),
+                // 🕰️ In order, the last one a minute ago
+                at: now - Duration::minutes((outcomes.len() - n) as i64),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_health_is_computed_from_the_last_day() {
+        let now: DateTime<Utc> = "2026-06-01T12:00:00Z".parse().unwrap();
+        use CallOutcome::*;
+        let mut all = events(
+            &LlmProvider::OpenAi,
+            &[
+                Success, Success, Error, Timeout, Success, Success, Success, Success, Success,
+                Success, Success, Success, Success, Success, Success, Success, Success, Success,
+                Success, Timeout,
+            ],
+            now,
+        );
+        // 📅 Older than the window: doesn't count
+        all.push(UsageEvent {
+            provider: "openai".to_string(),
+            outcome: Error,
+            latency_ms: 1,
+            at: now - Duration::hours(HEALTH_WINDOW_HOURS + 1),
+        });
+        all.extend(events(&LlmProvider::Anthropic, &[Error, Success], now));
+
+        let report = HealthReport::compute(&all, now);
+        assert_eq!(report.providers.len(), 2);
+        let openai = report.get(&LlmProvider::OpenAi).unwrap();
+        assert_eq!(openai.calls, 20);
+        assert!((openai.success_rate - 0.85).abs() < 1e-9);
+        assert!((openai.timeout_rate - 0.10).abs() < 1e-9);
+        // 🐢 Latencies 100..=2000 ms: the 19th of 20 is the p95
+        assert_eq!(openai.p95_latency_ms, 1900);
+        // 📉 The last call timed out, so the score sits just below its pre-timeout value
+        assert!(openai.score < 0.9 && openai.score > 0.7);
+
+        // 🧮 EMA by hand for error then success: 0.0, then 0.1 * 1 + 0.9 * 0
+        let anthropic = report.get(&LlmProvider::Anthropic).unwrap();
+        assert!((anthropic.score - EMA_ALPHA).abs() < 1e-9);
+        assert_eq!(anthropic.p95_latency_ms, 200);
+        println!("✅ Provider health computation test passed!");
+    }
+
+    #[test]
+    fn test_switch_advice_needs_an_unhealthy_default_and_a_better_alternative() {
+        let now: DateTime<Utc> = "2026-06-01T12:00:00Z".parse().unwrap();
+        use CallOutcome::*;
+        let both = [LlmProvider::OpenAi, LlmProvider::Anthropic];
+        let flaky: Vec<CallOutcome> = (0..20)
+            .map(|n| if n % 2 == 0 { Success } else { Error })
+            .collect();
+
+        // 🔢 Too few calls to judge
+        let report = HealthReport::compute(&events(&LlmProvider::OpenAi, &flaky[..6], now), now);
+        assert_eq!(
+            advise(&report, &LlmProvider::OpenAi, &both, 0.8, false),
+            None
+        );
+
+        // 🤷 Unhealthy, and the untried alternative is suggested
+        let mut all = events(&LlmProvider::OpenAi, &flaky, now);
+        let report = HealthReport::compute(&all, now);
+        let advice = advise(&report, &LlmProvider::OpenAi, &both, 0.8, false).unwrap();
+        assert_eq!(advice.to, LlmProvider::Anthropic);
+        assert!((advice.from_success_rate - 0.5).abs() < 1e-9);
+        assert_eq!(advice.to_success_rate, None);
+        assert!(!advice.switch);
+        // 🔧 ...unless it isn't configured
+        assert_eq!(
+            advise(
+                &report,
+                &LlmProvider::OpenAi,
+                &[LlmProvider::OpenAi],
+                0.8,
+                false
+            ),
+            None
+        );
+
+        // 📉 An alternative below the floor is no better
+        all.extend(events(
+            &LlmProvider::Anthropic,
+            &[Success, Error, Error],
+            now,
+        ));
+        let report = HealthReport::compute(&all, now);
+        assert_eq!(
+            advise(&report, &LlmProvider::OpenAi, &both, 0.8, true),
+            None
+        );
+        // 🎚️ With a lower floor OpenAI is fine as it is
+        assert_eq!(
+            advise(&report, &LlmProvider::OpenAi, &both, 0.5, true),
+            None
+        );
+
+        // ✅ A healthy alternative is switched to when auto-switch is on
+        let mut all = events(&LlmProvider::OpenAi, &flaky, now);
+        all.extend(events(&LlmProvider::Anthropic, &[Success; 12], now));
+        let report = HealthReport::compute(&all, now);
+        let advice = advise(&report, &LlmProvider::OpenAi, &both, 0.8, true).unwrap();
+        assert_eq!(advice.to, LlmProvider::Anthropic);
+        assert_eq!(advice.to_success_rate, Some(1.0));
+        assert!(advice.switch);
+        // 🩺 And nothing is advised once Anthropic is the default
+        assert_eq!(
+            advise(&report, &LlmProvider::Anthropic, &both, 0.8, true),
+            None
+        );
+        println!("✅ Switch advice test passed!");
+    }
+}
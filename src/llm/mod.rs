@@ -1,2 +1,587 @@
 // 🤖 LLM Integration Module - AI Magic! 🤖
-// TODO: Implement OpenAI, Anthropic, and other LLM integrations
+// A small, dependency-light client for the chat APIs of our AI friends.
+// Created with love by Aye & Hue - Making AI calls boring (in the best way)! ✨
+
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+use tracing::{debug, info};
+
+use crate::config::{LlmConfig, LlmProvider};
+
+pub mod health; // 🩺 Per-provider success rates, latency and health scores
+pub mod streaming; // 🌊 Streamed completions (server-sent events)
+pub mod usage; // 🧾 Recording every call for the health report
+
+pub use streaming::{LlmChunk, LlmStream};
+
+/// 🧠 Default OpenAI API base URL
+pub const OPENAI_API_BASE: &str = "https://api.openai.com";
+/// 🎭 Default Anthropic API base URL
+pub const ANTHROPIC_API_BASE: &str = "https://api.anthropic.com";
+/// 🎭 Anthropic API version header value
+const ANTHROPIC_VERSION: &str = "2023-06-01";
+
+/// 💬 A completed LLM call
+#[derive(Debug, Clone, Serialize)]
+pub struct LlmCompletion {
+    /// 🤖 Provider that answered
+    pub provider: LlmProvider,
+    /// 🧠 Model that answered
+    pub model: String,
+    /// 📝 Text of the answer
+    pub text: String,
+}
+
+/// 🤖 LLM client - one entry point for every configured provider
+#[derive(Debug, Clone)]
+pub struct LlmClient {
+    config: LlmConfig,
+    http: reqwest::Client,
+    /// 🌊 Same, without the total timeout (streams are bounded by idle gaps instead)
+    stream_http: reqwest::Client,
+    stream_idle_timeout: Duration,
+    openai_base: String,
+    anthropic_base: String,
+}
+
+impl LlmClient {
+    /// ➕ Create a client using the public provider endpoints
+    pub fn new(config: LlmConfig) -> Result<Self> {
+        let http = reqwest::Client::builder()
+            .timeout(Duration::from_secs(config.timeout_seconds))
+            .build()
+            .context("Failed to create LLM HTTP client")?;
+        let stream_http = reqwest::Client::builder()
+            .connect_timeout(Duration::from_secs(config.timeout_seconds))
+            .build()
+            .context("Failed to create LLM streaming HTTP client")?;
+
+        Ok(Self {
+            stream_idle_timeout: Duration::from_secs(config.stream_idle_timeout_seconds),
+            config,
+            http,
+            stream_http,
+            openai_base: OPENAI_API_BASE.to_string(),
+            anthropic_base: ANTHROPIC_API_BASE.to_string(),
+        })
+    }
+
+    /// 🔧 Point the client at different API hosts (proxies, mocks in tests)
+    pub fn with_base_urls(mut self, openai_base: &str, anthropic_base: &str) -> Self {
+        self.openai_base = openai_base.trim_end_matches('/').to_string();
+        self.anthropic_base = anthropic_base.trim_end_matches('/').to_string();
+        self
+    }
+
+    /// ⏱️ Override LLM_STREAM_IDLE_TIMEOUT_SECONDS (tests want milliseconds)
+    pub fn with_stream_idle_timeout(mut self, idle_timeout: Duration) -> Self {
+        self.stream_idle_timeout = idle_timeout;
+        self
+    }
+
+    /// 🎯 The provider used when a request doesn't ask for one
+    pub fn default_provider(&self) -> &LlmProvider {
+        &self.config.default_provider
+    }
+
+    /// 🔍 Is the given provider configured with credentials?
+    pub fn is_configured(&self, provider: &LlmProvider) -> bool {
+        match provider {
+            LlmProvider::OpenAi => self.config.openai.is_some(),
+            LlmProvider::Anthropic => self.config.anthropic.is_some(),
+        }
+    }
+
+    /// 💬 Send a prompt (with an optional system message) and return the answer text
+    pub async fn complete(
+        &self,
+        provider: &LlmProvider,
+        system: Option<&str>,
+        prompt: &str,
+    ) -> Result<LlmCompletion> {
+        info!(
+            "🤖 Sending prompt to {:?} ({} chars)",
+            provider,
+            prompt.len()
+        );
+        match provider {
+            LlmProvider::OpenAi => self.complete_openai(system, prompt).await,
+            LlmProvider::Anthropic => self.complete_anthropic(system, prompt).await,
+        }
+    }
+
+    /// 🌊 Same as `complete`, but the answer arrives as a stream of chunks.
+    /// There is no total time limit; the stream fails after a quiet idle gap instead.
+    pub async fn complete_streaming(
+        &self,
+        provider: &LlmProvider,
+        system: Option<&str>,
+        prompt: &str,
+    ) -> Result<LlmStream> {
+        info!(
+            "🌊 Streaming prompt to {:?} ({} chars)",
+            provider,
+            prompt.len()
+        );
+        let request = match provider {
+            LlmProvider::OpenAi => self.openai_request(&self.stream_http, system, prompt, true)?,
+            LlmProvider::Anthropic => {
+                self.anthropic_request(&self.stream_http, system, prompt, true)?
+            }
+        };
+        let response = tokio::time::timeout(self.stream_idle_timeout, request.send())
+            .await
+            .map_err(|elapsed| {
+                anyhow::Error::new(elapsed).context(format!(
+                    "{:?} did not start streaming within {:?}",
+                    provider, self.stream_idle_timeout
+                ))
+            })?
+            .with_context(|| format!("Failed to reach {:?}", provider))?
+            .error_for_status()
+            .with_context(|| format!("{:?} returned an error status", provider))?;
+
+        Ok(streaming::chunk_stream(
+            provider.clone(),
+            response,
+            self.stream_idle_timeout,
+        ))
+    }
+
+    /// 🧠 OpenAI chat completions request (streamed as server-sent events when `stream`)
+    fn openai_request(
+        &self,
+        http: &reqwest::Client,
+        system: Option<&str>,
+        prompt: &str,
+        stream: bool,
+    ) -> Result<reqwest::RequestBuilder> {
+        let openai = self
+            .config
+            .openai
+            .as_ref()
+            .context("OpenAI is not configured (set OPENAI_API_KEY)")?;
+
+        let mut messages = Vec::new();
+        if let Some(system) = system {
+            messages.push(serde_json::json!({ "role": "system", "content": system }));
+        }
+        messages.push(serde_json::json!({ "role": "user", "content": prompt }));
+
+        let mut body = serde_json::json!({
+            "model": openai.default_model,
+            "messages": messages,
+            "temperature": openai.temperature,
+            "max_tokens": openai.max_tokens,
+        });
+        if stream {
+            body["stream"] = serde_json::Value::Bool(true);
+        }
+
+        Ok(http
+            .post(format!("{}/v1/chat/completions", self.openai_base))
+            .bearer_auth(&openai.api_key)
+            .json(&body))
+    }
+
+    /// 🧠 OpenAI chat completions call
+    async fn complete_openai(&self, system: Option<&str>, prompt: &str) -> Result<LlmCompletion> {
+        let response: OpenAiResponse = self
+            .openai_request(&self.http, system, prompt, false)?
+            .send()
+            .await
+            .context("Failed to reach OpenAI")?
+            .error_for_status()
+            .context("OpenAI returned an error status")?
+            .json()
+            .await
+            .context("Failed to parse OpenAI response")?;
+
+        let text = response
+            .choices
+            .into_iter()
+            .next()
+            .map(|choice| choice.message.content)
+            .context("OpenAI response contained no choices")?;
+
+        debug!("🧠 OpenAI answered with {} chars", text.len());
+        Ok(LlmCompletion {
+            provider: LlmProvider::OpenAi,
+            model: self.model(&LlmProvider::OpenAi),
+            text,
+        })
+    }
+
+    /// 🎭 Anthropic messages request (streamed as server-sent events when `stream`)
+    fn anthropic_request(
+        &self,
+        http: &reqwest::Client,
+        system: Option<&str>,
+        prompt: &str,
+        stream: bool,
+    ) -> Result<reqwest::RequestBuilder> {
+        let anthropic = self
+            .config
+            .anthropic
+            .as_ref()
+            .context("Anthropic is not configured (set ANTHROPIC_API_KEY)")?;
+
+        let mut body = serde_json::json!({
+            "model": anthropic.default_model,
+            "max_tokens": anthropic.max_tokens,
+            "messages": [{ "role": "user", "content": prompt }],
+        });
+        if let Some(system) = system {
+            body["system"] = serde_json::Value::String(system.to_string());
+        }
+        if stream {
+            body["stream"] = serde_json::Value::Bool(true);
+        }
+
+        Ok(http
+            .post(format!("{}/v1/messages", self.anthropic_base))
+            .header("x-api-key", &anthropic.api_key)
+            .header("anthropic-version", ANTHROPIC_VERSION)
+            .json(&body))
+    }
+
+    /// 🧠 Configured model name for a provider (empty when it isn't configured)
+    fn model(&self, provider: &LlmProvider) -> String {
+        match provider {
+            LlmProvider::OpenAi => self.config.openai.as_ref().map(|c| c.default_model.clone()),
+            LlmProvider::Anthropic => self
+                .config
+                .anthropic
+                .as_ref()
+                .map(|c| c.default_model.clone()),
+        }
+        .unwrap_or_default()
+    }
+
+    /// 🎭 Anthropic messages call
+    async fn complete_anthropic(
+        &self,
+        system: Option<&str>,
+        prompt: &str,
+    ) -> Result<LlmCompletion> {
+        let response: AnthropicResponse = self
+            .anthropic_request(&self.http, system, prompt, false)?
+            .send()
+            .await
+            .context("Failed to reach Anthropic")?
+            .error_for_status()
+            .context("Anthropic returned an error status")?
+            .json()
+            .await
+            .context("Failed to parse Anthropic response")?;
+
+        let text: String = response
+            .content
+            .into_iter()
+            .filter_map(|block| block.text)
+            .collect();
+
+        debug!("🎭 Anthropic answered with {} chars", text.len());
+        Ok(LlmCompletion {
+            provider: LlmProvider::Anthropic,
+            model: self.model(&LlmProvider::Anthropic),
+            text,
+        })
+    }
+}
+
+/// 🧩 LLM operations used by handlers and the self-test (swappable for fakes in tests)
+#[async_trait]
+pub trait LlmOps: Send + Sync {
+    /// 🎯 The provider used when a request doesn't ask for one
+    fn default_provider(&self) -> LlmProvider;
+
+    /// 🔍 Is the given provider configured with credentials?
+    fn is_configured(&self, provider: &LlmProvider) -> bool;
+
+    /// 💬 Send a prompt (with an optional system message) and return the answer
+    async fn complete(
+        &self,
+        provider: &LlmProvider,
+        system: Option<&str>,
+        prompt: &str,
+    ) -> Result<LlmCompletion>;
+
+    /// 🌊 Same prompt, answered as a stream of chunks for callers that show progress.
+    /// Implementations without real streaming hand back the whole answer as one chunk.
+    async fn complete_streaming(
+        &self,
+        provider: &LlmProvider,
+        system: Option<&str>,
+        prompt: &str,
+    ) -> Result<LlmStream> {
+        let completion = self.complete(provider, system, prompt).await?;
+        Ok(Box::pin(futures_util::stream::once(async move {
+            Ok(LlmChunk {
+                text: completion.text,
+            })
+        })))
+    }
+}
+
+#[async_trait]
+impl LlmOps for LlmClient {
+    fn default_provider(&self) -> LlmProvider {
+        LlmClient::default_provider(self).clone()
+    }
+
+    fn is_configured(&self, provider: &LlmProvider) -> bool {
+        LlmClient::is_configured(self, provider)
+    }
+
+    async fn complete(
+        &self,
+        provider: &LlmProvider,
+        system: Option<&str>,
+        prompt: &str,
+    ) -> Result<LlmCompletion> {
+        LlmClient::complete(self, provider, system, prompt).await
+    }
+
+    async fn complete_streaming(
+        &self,
+        provider: &LlmProvider,
+        system: Option<&str>,
+        prompt: &str,
+    ) -> Result<LlmStream> {
+        LlmClient::complete_streaming(self, provider, system, prompt).await
+    }
+}
+
+/// 🧠 The parts of an OpenAI chat completion we care about
+#[derive(Debug, Deserialize)]
+struct OpenAiResponse {
+    choices: Vec<OpenAiChoice>,
+}
+
+#[derive(Debug, Deserialize)]
+struct OpenAiChoice {
+    message: OpenAiMessage,
+}
+
+#[derive(Debug, Deserialize)]
+struct OpenAiMessage {
+    content: String,
+}
+
+/// 🎭 The parts of an Anthropic message we care about
+#[derive(Debug, Deserialize)]
+struct AnthropicResponse {
+    content: Vec<AnthropicContentBlock>,
+}
+
+#[derive(Debug, Deserialize)]
+struct AnthropicContentBlock {
+    text: Option<String>,
+}
+
+// 🧪 Tests - Talking to pretend AIs so we don't pay for real ones!
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{AnthropicConfig, OpenAiConfig};
+    use wiremock::matchers::{header, method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn test_config() -> LlmConfig {
+        LlmConfig {
+            openai: Some(OpenAiConfig {
+                api_key: "sk-test".to_string(),
+                default_model: "gpt-test".to_string(),
+                temperature: 0.0,
+                max_tokens: 16,
+            }),
+            anthropic: Some(AnthropicConfig {
+                api_key: "ak-test".to_string(),
+                default_model: "claude-test".to_string(),
+                max_tokens: 16,
+            }),
+            default_provider: LlmProvider::OpenAi,
+            timeout_seconds: 5,
+            stream_idle_timeout_seconds: 5,
+            max_retries: 0,
+            health_success_floor: 0.8,
+            auto_switch: false,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_openai_and_anthropic_completions() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/v1/chat/completions"))
+            .and(header("authorization", "Bearer sk-test"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "choices": [{ "message": { "role": "assistant", "content": "OK" } }]
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/v1/messages"))
+            .and(header("x-api-key", "ak-test"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "content": [{ "type": "text", "text": "OK" }]
+            })))
+            .mount(&server)
+            .await;
+
+        let client = LlmClient::new(test_config())
+            .unwrap()
+            .with_base_urls(&server.uri(), &server.uri());
+
+        let openai = client
+            .complete(&LlmProvider::OpenAi, Some("Be brief"), "Say OK")
+            .await
+            .unwrap();
+        assert_eq!(openai.text, "OK");
+        assert_eq!(openai.model, "gpt-test");
+
+        let anthropic = client
+            .complete(&LlmProvider::Anthropic, None, "Say OK")
+            .await
+            .unwrap();
+        assert_eq!(anthropic.text, "OK");
+        println!("✅ LLM completion test passed!");
+    }
+
+    #[tokio::test]
+    async fn test_streamed_completions_arrive_in_chunks() {
+        let server = MockServer::start().await;
+        let sse = |body: &str| {
+            ResponseTemplate::new(200)
+                .insert_header("content-type", "text/event-stream")
+                .set_body_string(body.to_string())
+        };
+        Mock::given(method("POST"))
+            .and(path("/v1/chat/completions"))
+            .and(wiremock::matchers::body_partial_json(
+                serde_json::json!({ "stream": true }),
+            ))
+            .respond_with(sse(concat!(
+                "data: {\"choices\":[{\"delta\":{\"role\":\"assistant\"}}]}\n\n",
+                "data: {\"choices\":[{\"delta\":{\"content\":\"O\"}}]}\n\n",
+                "data: {\"choices\":[{\"delta\":{\"content\":\"K\"}}]}\n\n",
+                "data: [DONE]\n\n",
+            )))
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/v1/messages"))
+            .respond_with(sse(concat!(
+                "event: message_start\ndata: {\"type\":\"message_start\"}\n\n",
+                "event: content_block_delta\ndata: {\"type\":\"content_block_delta\",\"delta\":{\"type\":\"text_delta\",\"text\":\"Ahoy\"}}\n\n",
+                "event: content_block_delta\ndata: {\"type\":\"content_block_delta\",\"delta\":{\"type\":\"text_delta\",\"text\":\"!\"}}\n\n",
+                "event: message_stop\ndata: {\"type\":\"message_stop\"}\n\n",
+            )))
+            .mount(&server)
+            .await;
+
+        let client = LlmClient::new(test_config())
+            .unwrap()
+            .with_base_urls(&server.uri(), &server.uri());
+
+        let mut seen = Vec::new();
+        let stream = client
+            .complete_streaming(&LlmProvider::OpenAi, None, "Say OK")
+            .await
+            .unwrap();
+        let text = streaming::collect_text(stream, |chunk| seen.push(chunk.text.clone()))
+            .await
+            .unwrap();
+        assert_eq!(text, "OK");
+        assert_eq!(seen, vec!["O", "K"]);
+
+        let stream = client
+            .complete_streaming(&LlmProvider::Anthropic, Some("Be a pirate"), "Greet")
+            .await
+            .unwrap();
+        assert_eq!(
+            streaming::collect_text(stream, |_| {}).await.unwrap(),
+            "Ahoy!"
+        );
+        println!("✅ Streamed completion test passed!");
+    }
+
+    #[tokio::test]
+    async fn test_stream_fails_on_idle_gap_or_early_end() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/v1/chat/completions"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_string("data: [DONE]\n\n")
+                    .set_delay(Duration::from_secs(2)),
+            )
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/v1/messages"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(
+                "data: {\"type\":\"content_block_delta\",\"delta\":{\"text\":\"Ah\"}}\n\n",
+            ))
+            .mount(&server)
+            .await;
+
+        let client = LlmClient::new(test_config())
+            .unwrap()
+            .with_base_urls(&server.uri(), &server.uri())
+            .with_stream_idle_timeout(Duration::from_millis(200));
+
+        // ⏱️ Nothing within the idle window
+        let error = match client
+            .complete_streaming(&LlmProvider::OpenAi, None, "hi")
+            .await
+        {
+            Ok(_) => panic!("expected the idle timeout"),
+            Err(e) => e,
+        };
+        assert!(error.to_string().contains("did not start streaming"));
+
+        // ✂️ Cut off before message_stop: the partial text arrives, then an error
+        let mut stream = client
+            .complete_streaming(&LlmProvider::Anthropic, None, "hi")
+            .await
+            .unwrap();
+        use futures_util::StreamExt;
+        assert_eq!(stream.next().await.unwrap().unwrap().text, "Ah");
+        let error = stream.next().await.unwrap().unwrap_err();
+        assert!(format!("{:#}", error).contains("ended before it finished"));
+        println!("✅ Stream idle/early-end test passed!");
+    }
+
+    #[tokio::test]
+    async fn test_default_streaming_is_one_chunk() {
+        let fake = crate::test_support::FakeLlm::default();
+        fake.push_response("All at once");
+        let stream = fake
+            .complete_streaming(&LlmProvider::OpenAi, None, "hi")
+            .await
+            .unwrap();
+        let mut chunks = 0;
+        let text = streaming::collect_text(stream, |_| chunks += 1)
+            .await
+            .unwrap();
+        assert_eq!((text.as_str(), chunks), ("All at once", 1));
+        println!("✅ Default streaming test passed!");
+    }
+
+    #[tokio::test]
+    async fn test_unconfigured_provider_errors() {
+        let mut config = test_config();
+        config.anthropic = None;
+        let client = LlmClient::new(config).unwrap();
+        assert!(!client.is_configured(&LlmProvider::Anthropic));
+        assert!(client
+            .complete(&LlmProvider::Anthropic, None, "hi")
+            .await
+            .is_err());
+        println!("✅ Unconfigured LLM provider test passed!");
+    }
+}
@@ -0,0 +1,252 @@
+// 🌊 LLM Streaming - Watching the answer arrive word by word! 🌊
+// Both providers stream completions as server-sent events. This turns the raw
+// response body into a stream of text chunks, failing when the provider goes
+// quiet for longer than the idle timeout instead of capping the total time.
+// Created with love by Aye & Hue! ✨
+
+use anyhow::{Context, Result};
+use futures_util::stream::{BoxStream, StreamExt};
+use std::collections::VecDeque;
+use std::time::Duration;
+
+use crate::config::LlmProvider;
+
+/// 🧩 One piece of a streamed answer
+#[derive(Debug, Clone, PartialEq)]
+pub struct LlmChunk {
+    /// 📝 Text to append to what came before
+    pub text: String,
+}
+
+/// 🌊 Chunks in arrival order; ends once the provider says it's done
+pub type LlmStream = BoxStream<'static, Result<LlmChunk>>;
+
+/// 📨 One server-sent event
+#[derive(Debug, Default, PartialEq)]
+struct SseEvent {
+    event: Option<String>,
+    data: String,
+}
+
+/// ✂️ Splits a byte stream into server-sent events (events may straddle reads)
+#[derive(Debug, Default)]
+struct SseParser {
+    buffer: Vec<u8>,
+}
+
+impl SseParser {
+    /// 📥 Feed bytes in, get every event they completed
+    fn push(&mut self, bytes: &[u8]) -> Vec<SseEvent> {
+        self.buffer
+            .extend(bytes.iter().filter(|&&byte| byte != b'\r'));
+        let mut events = Vec::new();
+        while let Some(end) = self.buffer.windows(2).position(|pair| pair == b"\n\n") {
+            let block: Vec<u8> = self.buffer.drain(..end + 2).collect();
+            if let Some(event) = parse_event(&String::from_utf8_lossy(&block)) {
+                events.push(event);
+            }
+        }
+        events
+    }
+}
+
+/// 🔍 Parse one event block ("event:" and "data:" lines; comments ignored)
+fn parse_event(block: &str) -> Option<SseEvent> {
+    let mut event = SseEvent::default();
+    let mut data_lines = Vec::new();
+    for line in block.lines() {
+        let (field, value) = line.split_once(':').unwrap_or((line, ""));
+        let value = value.strip_prefix(' ').unwrap_or(value);
+        match field {
+            "event" => event.event = Some(value.to_string()),
+            "data" => data_lines.push(value),
+            _ => {}
+        }
+    }
+    if data_lines.is_empty() && event.event.is_none() {
+        return None;
+    }
+    event.data = data_lines.join("\n");
+    Some(event)
+}
+
+/// 🔀 What one event means for the answer
+#[derive(Debug, PartialEq)]
+enum Delta {
+    Text(String),
+    Done,
+    Skip,
+}
+
+/// 🧠 OpenAI: `choices[0].delta.content` until `data: [DONE]`
+fn openai_delta(event: &SseEvent) -> Result<Delta> {
+    if event.data == "[DONE]" {
+        return Ok(Delta::Done);
+    }
+    let value: serde_json::Value =
+        serde_json::from_str(&event.data).context("Invalid OpenAI stream event")?;
+    if let Some(error) = value.get("error") {
+        anyhow::bail!("OpenAI stream error: {}", error);
+    }
+    Ok(value["choices"][0]["delta"]["content"]
+        .as_str()
+        .filter(|text| !text.is_empty())
+        .map(|text| Delta::Text(text.to_string()))
+        .unwrap_or(Delta::Skip))
+}
+
+/// 🎭 Anthropic: `content_block_delta` text until `message_stop`
+fn anthropic_delta(event: &SseEvent) -> Result<Delta> {
+    if event.data.is_empty() {
+        return Ok(Delta::Skip);
+    }
+    let value: serde_json::Value =
+        serde_json::from_str(&event.data).context("Invalid Anthropic stream event")?;
+    Ok(match value["type"].as_str() {
+        Some("content_block_delta") => value["delta"]["text"]
+            .as_str()
+            .map(|text| Delta::Text(text.to_string()))
+            .unwrap_or(Delta::Skip),
+        Some("message_stop") => Delta::Done,
+        Some("error") => anyhow::bail!("Anthropic stream error: {}", value["error"]),
+        _ => Delta::Skip,
+    })
+}
+
+/// 🔁 Where the chunk stream is between polls
+struct StreamState {
+    provider: LlmProvider,
+    response: reqwest::Response,
+    parser: SseParser,
+    pending: VecDeque<LlmChunk>,
+    done: bool,
+}
+
+/// 🌊 Turn a streaming response into text chunks, giving up after `idle_timeout` without data
+pub(super) fn chunk_stream(
+    provider: LlmProvider,
+    response: reqwest::Response,
+    idle_timeout: Duration,
+) -> LlmStream {
+    let state = StreamState {
+        provider,
+        response,
+        parser: SseParser::default(),
+        pending: VecDeque::new(),
+        done: false,
+    };
+    futures_util::stream::try_unfold(state, move |mut state| async move {
+        loop {
+            if let Some(chunk) = state.pending.pop_front() {
+                return Ok(Some((chunk, state)));
+            }
+            if state.done {
+                return Ok(None);
+            }
+
+            let bytes = tokio::time::timeout(idle_timeout, state.response.chunk())
+                .await
+                .map_err(|_| {
+                    anyhow::anyhow!(
+                        "{:?} sent nothing for {:?}, giving up on the stream",
+                        state.provider,
+                        idle_timeout
+                    )
+                })?
+                .with_context(|| format!("Failed to read {:?} stream", state.provider))?
+                .with_context(|| format!("{:?} stream ended before it finished", state.provider))?;
+
+            for event in state.parser.push(&bytes) {
+                let delta = match state.provider {
+                    LlmProvider::OpenAi => openai_delta(&event)?,
+                    LlmProvider::Anthropic => anthropic_delta(&event)?,
+                };
+                match delta {
+                    Delta::Text(text) => state.pending.push_back(LlmChunk { text }),
+                    Delta::Done => state.done = true,
+                    Delta::Skip => {}
+                }
+            }
+        }
+    })
+    .boxed()
+}
+
+/// 📝 Drain a stream into the full answer, calling `on_chunk` as each piece arrives
+pub async fn collect_text(
+    mut stream: LlmStream,
+    mut on_chunk: impl FnMut(&LlmChunk),
+) -> Result<String> {
+    let mut text = String::new();
+    while let Some(chunk) = stream.next().await {
+        let chunk = chunk?;
+        on_chunk(&chunk);
+        text.push_str(&chunk.text);
+    }
+    Ok(text)
+}
+
+// 🧪 Tests - Parsing the drip feed!
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sse_events_survive_split_reads() {
+        let mut parser = SseParser::default();
+        assert!(parser.push(b"event: content_block_delta\r\nda").is_empty());
+        let events = parser.push(b"ta: {\"a\":1}\r\n\r\n: keep-alive\n\ndata: [DONE]\n\n");
+        assert_eq!(
+            events,
+            vec![
+                SseEvent {
+                    event: Some("content_block_delta".to_string()),
+                    data: "{\"a\":1}".to_string(),
+                },
+                SseEvent {
+                    event: None,
+                    data: "[DONE]".to_string(),
+                },
+            ]
+        );
+        println!("✅ SSE parser test passed!");
+    }
+
+    #[test]
+    fn test_provider_deltas() {
+        let event = |data: &str| SseEvent {
+            event: None,
+            data: data.to_string(),
+        };
+        assert_eq!(
+            openai_delta(&event(r#"{"choices":[{"delta":{"content":"Hi"}}]}"#)).unwrap(),
+            Delta::Text("Hi".to_string())
+        );
+        assert_eq!(
+            openai_delta(&event(r#"{"choices":[{"delta":{"role":"assistant"}}]}"#)).unwrap(),
+            Delta::Skip
+        );
+        assert_eq!(openai_delta(&event("[DONE]")).unwrap(), Delta::Done);
+
+        assert_eq!(
+            anthropic_delta(&event(
+                r#"{"type":"content_block_delta","delta":{"type":"text_delta","text":"Ahoy"}}"#
+            ))
+            .unwrap(),
+            Delta::Text("Ahoy".to_string())
+        );
+        assert_eq!(
+            anthropic_delta(&event(r#"{"type":"ping"}"#)).unwrap(),
+            Delta::Skip
+        );
+        assert_eq!(
+            anthropic_delta(&event(r#"{"type":"message_stop"}"#)).unwrap(),
+            Delta::Done
+        );
+        assert!(anthropic_delta(&event(
+            r#"{"type":"error","error":{"type":"overloaded_error"}}"#
+        ))
+        .is_err());
+        println!("✅ Provider stream delta test passed!");
+    }
+}
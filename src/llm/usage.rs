@@ -0,0 +1,181 @@
+// 🧾 LLM Usage - Every call counted, so provider health isn't guesswork! 🧾
+// `RecordedLlm` wraps the LLM client: each completion (or the opening of a stream)
+// leaves one `llm_usage` row with the provider, the outcome and how long it took.
+// It also answers `default_provider()` with the runtime override from the settings
+// cache, which is how a switch by the health monitor takes effect without a restart.
+// Created with love by Aye & Hue! ✨
+
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use sqlx::PgPool;
+use std::sync::Arc;
+use std::time::Instant;
+use tracing::warn;
+
+use super::{LlmCompletion, LlmOps, LlmStream};
+use crate::api::settings_cache::SettingsCache;
+use crate::config::LlmProvider;
+
+/// 🎯 How an LLM call ended
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CallOutcome {
+    Success,
+    Error,
+    Timeout,
+}
+
+impl CallOutcome {
+    /// 🏷️ Value of `llm_usage.outcome`
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CallOutcome::Success => "success",
+            CallOutcome::Error => "error",
+            CallOutcome::Timeout => "timeout",
+        }
+    }
+
+    /// 🔍 Parse `llm_usage.outcome`
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "success" => Some(CallOutcome::Success),
+            "error" => Some(CallOutcome::Error),
+            "timeout" => Some(CallOutcome::Timeout),
+            _ => None,
+        }
+    }
+
+    /// ⏱️ Was it a failure, and was that failure running out of time?
+    pub fn of<T>(result: &Result<T>) -> Self {
+        match result {
+            Ok(_) => CallOutcome::Success,
+            Err(e) if is_timeout(e) => CallOutcome::Timeout,
+            Err(_) => CallOutcome::Error,
+        }
+    }
+}
+
+/// ⏱️ Did the call give up waiting (HTTP client timeout or our own deadline)?
+fn is_timeout(error: &anyhow::Error) -> bool {
+    error.chain().any(|cause| {
+        cause
+            .downcast_ref::<reqwest::Error>()
+            .is_some_and(reqwest::Error::is_timeout)
+            || cause.is::<tokio::time::error::Elapsed>()
+    })
+}
+
+/// 📝 Store one call
+pub async fn record(
+    pool: &PgPool,
+    provider: &LlmProvider,
+    outcome: CallOutcome,
+    latency_ms: i64,
+) -> Result<()> {
+    sqlx::query("INSERT INTO llm_usage (provider, outcome, latency_ms) VALUES ($1, $2, $3)")
+        .bind(provider.as_str())
+        .bind(outcome.as_str())
+        .bind(latency_ms)
+        .execute(pool)
+        .await
+        .context("Failed to record LLM usage")?;
+    Ok(())
+}
+
+/// 🧾 An `LlmOps` that records every call it passes on
+pub struct RecordedLlm {
+    inner: Arc<dyn LlmOps>,
+    pool: PgPool,
+    settings: Arc<SettingsCache>,
+}
+
+impl RecordedLlm {
+    /// ➕ Wrap `inner`, recording into `pool` and reading overrides from `settings`
+    pub fn new(inner: Arc<dyn LlmOps>, pool: PgPool, settings: Arc<SettingsCache>) -> Self {
+        Self {
+            inner,
+            pool,
+            settings,
+        }
+    }
+
+    /// 📝 Record a finished call (a failed write is logged, never the caller's problem)
+    async fn observe(&self, provider: &LlmProvider, started: Instant, outcome: CallOutcome) {
+        let latency_ms = started.elapsed().as_millis() as i64;
+        if let Err(e) = record(&self.pool, provider, outcome, latency_ms).await {
+            warn!("⚠️ {:#}", e);
+        }
+    }
+}
+
+#[async_trait]
+impl LlmOps for RecordedLlm {
+    fn default_provider(&self) -> LlmProvider {
+        // 🔀 A runtime switch wins, as long as it names a provider we have credentials for
+        self.settings
+            .get()
+            .llm_default_provider
+            .as_deref()
+            .and_then(|name| name.parse::<LlmProvider>().ok())
+            .filter(|provider| self.inner.is_configured(provider))
+            .unwrap_or_else(|| self.inner.default_provider())
+    }
+
+    fn is_configured(&self, provider: &LlmProvider) -> bool {
+        self.inner.is_configured(provider)
+    }
+
+    async fn complete(
+        &self,
+        provider: &LlmProvider,
+        system: Option<&str>,
+        prompt: &str,
+    ) -> Result<LlmCompletion> {
+        let started = Instant::now();
+        let result = self.inner.complete(provider, system, prompt).await;
+        self.observe(provider, started, CallOutcome::of(&result))
+            .await;
+        result
+    }
+
+    async fn complete_streaming(
+        &self,
+        provider: &LlmProvider,
+        system: Option<&str>,
+        prompt: &str,
+    ) -> Result<LlmStream> {
+        let started = Instant::now();
+        let result = self
+            .inner
+            .complete_streaming(provider, system, prompt)
+            .await;
+        self.observe(provider, started, CallOutcome::of(&result))
+            .await;
+        result
+    }
+}
+
+// 🧪 Tests - Counting calls, good and bad!
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_timeouts_are_told_apart_from_other_errors() {
+        let elapsed = tokio::time::timeout(std::time::Duration::ZERO, std::future::pending::<()>())
+            .await
+            .unwrap_err();
+        let timed_out: Result<()> =
+            Err(anyhow::Error::new(elapsed).context("openai did not start streaming"));
+        assert_eq!(CallOutcome::of(&timed_out), CallOutcome::Timeout);
+        let failed: Result<()> = Err(anyhow::anyhow!("500 Internal Server Error"));
+        assert_eq!(CallOutcome::of(&failed), CallOutcome::Error);
+        assert_eq!(
+            CallOutcome::of(&Ok::<_, anyhow::Error>(())),
+            CallOutcome::Success
+        );
+        assert_eq!(CallOutcome::parse("timeout"), Some(CallOutcome::Timeout));
+        println!("✅ Call outcome test passed!");
+    }
+}
@@ -8,72 +8,205 @@
 
 use anyhow::{Context, Result};
 use axum::{
+    extract::{DefaultBodyLimit, MatchedPath},
     http::StatusCode,
     middleware as axum_middleware,
     response::{Html, IntoResponse},
-    routing::{get, post},
+    routing::{get, post, put},
     Router,
 };
 use std::net::SocketAddr;
 use tokio::signal;
 use tower::ServiceBuilder;
-use tower_http::{compression::CompressionLayer, cors::CorsLayer, trace::TraceLayer};
+use tower_http::{
+    compression::{
+        predicate::{Predicate, SizeAbove},
+        CompressionLayer,
+    },
+    cors::CorsLayer,
+    request_id::{MakeRequestUuid, PropagateRequestIdLayer, SetRequestIdLayer},
+    trace::TraceLayer,
+};
 use tracing::{error, info, warn};
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
 
 // 🎯 Import all our amazing modules that we're about to create!
 mod api; // 📡 API routes for feedback submission and management
 mod auth; // 🔐 Authentication and authorization magic
+mod build_info; // 🏷️ Version, git commit and build time stamped in at compile time
+mod cli; // 🧰 Command-line subcommands (migrate)
 mod config; // ⚙️  Configuration management (because settings matter!)
 mod database; // 🗄️  Database operations and connections
+mod feedbacker_types; // 🤝 API response envelope and error codes shared with clients
 mod github; // 🐙 GitHub integration for the legendary aye-is user
 mod jobs; // 🔄 Background job processing for async operations
 mod llm; // 🤖 LLM integration (OpenAI, Anthropic, and friends!)
+mod metrics; // 📈 In-process counters
 mod middleware; // 🛡️  Custom middleware for rate limiting and security
 mod models; // 📊 Data models and structures
+mod self_test; // 🧪 Startup dry-run self-test
+mod storage; // 🗄️  Blob storage for feedback attachments (local or S3)
+#[cfg(test)]
+mod test_support; // 🧪 Ephemeral app + temp database for integration tests
 mod utils; // 🔧 Utility functions and helpers
 
-use config::Config;
-use middleware::{auth::auth_middleware, rate_limiting::rate_limit_middleware};
+use config::{Config, LogFormat, LoggingConfig};
+use middleware::{
+    auth::auth_middleware, panic::catch_panic_middleware, rate_limiting::rate_limit_middleware,
+};
+
+/// 💥 Test-only route that always panics (exercises the panic layer)
+#[cfg(test)]
+pub(crate) const PANIC_TEST_ROUTE: &str = "/admin/api/test/panic";
 
 // 🎊 The main function - Where the magic begins! 🎊
 #[tokio::main]
 async fn main() -> Result<()> {
-    // 🌈 Initialize our beautiful logging system
-    // Because knowing what's happening is half the battle!
-    init_logging()?;
-
-    // 🎨 Display our fabulous startup banner
-    display_startup_banner();
+    // 🧰 `feedbacker migrate ...` manages the schema and exits; no arguments means serve
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let command = cli::parse_args(&args)?;
 
     // ⚙️ Load configuration from environment and files
     let config = Config::load()
         .context("Failed to load configuration - check your environment variables!")?;
 
+    // 🌈 Initialize our beautiful logging system (LOG_FORMAT picks text or JSON lines)
+    // Because knowing what's happening is half the battle!
+    init_logging(&config.logging)?;
+
+    // 🎨 Display our fabulous startup banner (not in the middle of a JSON log stream)
+    if config.logging.format == LogFormat::Pretty {
+        display_startup_banner();
+    }
+
+    info!("🏷️ Feedbacker {}", build_info::summary());
     info!("🚀 Configuration loaded successfully!");
     info!("🎯 Server will listen on: {}", config.server.address);
     info!(
         "🗄️ Database URL: {}",
         mask_database_url(&config.database.url)
     );
+    // 🔏 Webhook deliveries are verified, refused or - by explicit opt-out - trusted blindly
+    if config.github.webhook_secret.is_none() {
+        if config.github.allow_unsigned_webhooks {
+            warn!("🚨 GITHUB_WEBHOOK_ALLOW_UNSIGNED is on: webhook deliveries are accepted WITHOUT verification - anyone can forge them");
+        } else {
+            warn!("🔏 GITHUB_WEBHOOK_SECRET is not set: every webhook delivery will be refused");
+        }
+    }
 
-    // 🔗 Initialize database connection pool
-    let db_pool = database::create_pool(&config.database.url)
+    // 🔗 Initialize database connection pool, waiting for a database that isn't up yet
+    // (`--fail-fast` gives up after the first attempt, for CI)
+    let startup_budget = if args.iter().any(|arg| arg == "--fail-fast") {
+        std::time::Duration::ZERO
+    } else {
+        std::time::Duration::from_secs(config.database.startup_timeout_seconds)
+    };
+    let db_pool = database::startup::connect_with_retry(&config.database.url, startup_budget)
         .await
         .context("Failed to create database connection pool")?;
+    database::startup::warm_up(&db_pool).await?;
 
-    // 🏃‍♂️ Run database migrations (keeping things up to date!)
-    database::run_migrations(&db_pool)
-        .await
-        .context("Failed to run database migrations")?;
+    if let cli::Command::Migrate(migrate) = &command {
+        return cli::run_migrate(&db_pool, migrate).await;
+    }
+
+    // 🏃‍♂️ Run database migrations (keeping things up to date!), or insist someone already did
+    if config.database.auto_migrate {
+        database::run_migrations(&db_pool)
+            .await
+            .context("Failed to run database migrations")?;
+    } else {
+        database::ensure_no_pending_migrations(&db_pool)
+            .await
+            .context("AUTO_MIGRATE is off and the database schema is behind")?;
+    }
 
     info!("✅ Database connection established and migrations complete!");
 
+    if let cli::Command::Admin(admin) = &command {
+        return cli::run_admin(&db_pool, admin).await;
+    }
+    if let cli::Command::ApiKey(api_key) = &command {
+        return cli::run_api_key(&db_pool, api_key).await;
+    }
+
+    // 🌱 `--seed` fills a development database with sample data before starting
+    if args.iter().any(|arg| arg == "--seed") {
+        #[cfg(feature = "dev-tools")]
+        {
+            let summary = api::dev::seed_database(&db_pool, &config)
+                .await
+                .context("Failed to seed development data")?;
+            info!("🌱 Seeded development data: {:?}", summary);
+        }
+        #[cfg(not(feature = "dev-tools"))]
+        anyhow::bail!(
+            "--seed needs a build with the dev-tools feature: cargo run --features dev-tools -- --seed"
+        );
+    }
+
     // 🌍 Initialize GeoIP database (auto-download if credentials are set)
     api::mcp::init_geoip_database().await;
 
     // 🎯 Create our amazing application state
-    let app_state = api::AppState::new(config.clone(), db_pool);
+    let app_state = api::AppState::new(config.clone(), db_pool)
+        .context("Failed to create application state")?;
+
+    // 🧪 Optional dry-run self-test (SELF_TEST=1) before we accept any traffic
+    if config.self_test.enabled {
+        let report = self_test::run_self_test(&app_state).await;
+        if !report.passed() {
+            let failed = report.failed_stages().join(", ");
+            if config.self_test.strict {
+                anyhow::bail!("Startup self-test failed: {}", failed);
+            }
+            warn!("⚠️ Startup self-test failed ({}), starting anyway", failed);
+        } else {
+            info!("✅ Startup self-test passed!");
+        }
+    }
+
+    // 🧹 Evict expired per-IP rate limit windows so memory stays bounded
+    app_state
+        .rate_limiter
+        .spawn_sweeper(std::time::Duration::from_secs(
+            config.rate_limiting.sweep_interval_seconds,
+        ));
+
+    // ⚡ Runtime settings: load once before serving, then keep them fresh in the background
+    if let Err(e) = app_state.settings.refresh(&app_state.db_pool).await {
+        warn!("⚠️ Failed to load runtime settings: {:#}", e);
+    }
+    app_state.settings.spawn_refresher(
+        app_state.db_pool.clone(),
+        api::settings_cache::SETTINGS_REFRESH_INTERVAL,
+    );
+
+    // 🔥 Warn the admins when GitHub writes stay throttled
+    github::throttle::spawn_saturation_monitor(
+        app_state.github_throttle.clone(),
+        app_state.db_pool.clone(),
+        std::time::Duration::from_secs(config.github.write_saturation_alert_seconds),
+    );
+
+    // 🩺 Keep the LLM provider health report fresh (and warn about a struggling default)
+    jobs::llm_health::spawn_monitor(app_state.clone());
+
+    // 🔄 Background worker (feedback callbacks and friends) and the outbox feeding it
+    if config.features.enable_background_jobs {
+        jobs::spawn_worker(app_state.clone());
+        jobs::outbox::spawn_dispatcher(app_state.clone());
+        jobs::daily_stats::spawn_scheduler(app_state.clone());
+        jobs::approval::spawn_expiry_sweeper(app_state.clone());
+        jobs::reconcile::spawn_reconciler(app_state.clone());
+        jobs::pr_refresh::spawn_scheduler(app_state.clone());
+        jobs::status_stats::spawn_scheduler(app_state.clone());
+        jobs::webhook_secrets::spawn_on_rotation(app_state.clone());
+        if config.retention.enabled {
+            jobs::retention::spawn_scheduler(app_state.clone());
+        }
+    }
 
     // 🏗️ Build our beautiful Axum router
     let app = create_router(app_state, &config).context("Failed to create router")?;
@@ -111,19 +244,43 @@ async fn main() -> Result<()> {
 }
 
 // 🌈 Initialize our beautiful logging system
-// This makes debugging a joy instead of a chore!
-fn init_logging() -> Result<()> {
-    tracing_subscriber::registry()
-        .with(
-            tracing_subscriber::EnvFilter::try_from_default_env()
-                .unwrap_or_else(|_| "feedbacker=debug,tower_http=debug".into()),
-        )
-        .with(tracing_subscriber::fmt::layer())
-        .init();
+// This makes debugging a joy instead of a chore! RUST_LOG wins over LOG_LEVEL.
+fn init_logging(config: &LoggingConfig) -> Result<()> {
+    let filter = tracing_subscriber::EnvFilter::try_from_default_env()
+        .unwrap_or_else(|_| format!("feedbacker={0},tower_http={0}", config.level).into());
+    let registry = tracing_subscriber::registry().with(filter);
+    match config.format {
+        LogFormat::Pretty => registry.with(tracing_subscriber::fmt::layer()).init(),
+        LogFormat::Json => registry
+            .with(utils::json_logs::JsonLogLayer::new(std::io::stdout))
+            .init(),
+    }
 
     Ok(())
 }
 
+// 🏷️ Span around every request: the request id and the matched route are first-class
+// fields, so every line logged while handling it can be found by either
+fn request_span(request: &axum::http::Request<axum::body::Body>) -> tracing::Span {
+    let route = request
+        .extensions()
+        .get::<MatchedPath>()
+        .map(MatchedPath::as_str)
+        .unwrap_or("unmatched");
+    let request_id = request
+        .headers()
+        .get("x-request-id")
+        .and_then(|value| value.to_str().ok())
+        .unwrap_or_default();
+    tracing::info_span!(
+        "request",
+        method = %request.method(),
+        uri = %request.uri(),
+        route,
+        request_id
+    )
+}
+
 // 🎨 Display our fabulous startup banner
 // Because every great service needs a great entrance!
 fn display_startup_banner() {
@@ -131,6 +288,7 @@ fn display_startup_banner() {
     println!("🚢 ⚓ FEEDBACKER - AI-Powered Repository Management ⚓ 🚢");
     println!("{}", "=".repeat(80));
     println!("🤖 Built with Rust, Axum, and lots of ❤️  by Aye & Hue");
+    println!("🏷️  Build: {}", build_info::summary());
     println!("📧 Contact: aye@8b.is | hue@8b.is");
     println!("🌟 Making GitHub PRs as smooth as Elvis's dance moves!");
     println!("💝 Special thanks to Trisha from Accounting for keeping us organized!");
@@ -151,8 +309,47 @@ fn create_router(app_state: api::AppState, config: &Config) -> Result<Router> {
     let api_router = Router::new()
         // 📝 Feedback submission endpoint - the heart of our service!
         .route("/api/feedback", post(api::feedback::submit_feedback))
+        .route("/api/feedback/:id", get(api::feedback::get_feedback))
+        // 🧩 CAPTCHA site key or proof-of-work challenge for anonymous submissions
+        .route(
+            "/api/feedback/challenge",
+            get(api::challenge::get_challenge),
+        )
+        // ✋ The project owner's decision on held changes
+        .route(
+            "/api/feedback/:id/approve",
+            post(api::feedback::approve_feedback),
+        )
+        .route(
+            "/api/feedback/:id/reject",
+            post(api::feedback::reject_feedback),
+        )
+        // 👍 Votes from signed-in users, shown in the feedback's pull request
+        .route(
+            "/api/feedback/:id/vote",
+            post(api::feedback::vote_feedback).delete(api::feedback::unvote_feedback),
+        )
+        // 📎 Logs and screenshots attached to a feedback item
+        .route(
+            "/api/feedback/:id/attachments",
+            post(api::attachments::upload_attachment),
+        )
+        .route(
+            "/api/feedback/:id/attachments/:attachment_id",
+            get(api::attachments::download_attachment),
+        )
+        // 🙋 The signed-in user's own feedback
+        .route("/api/me/feedback", get(api::my_feedback::list_my_feedback))
+        .route(
+            "/api/me/feedback/:id",
+            get(api::my_feedback::get_my_feedback).delete(api::my_feedback::delete_my_feedback),
+        )
         // 📊 Status and health check endpoints
         .route("/api/health", get(api::health::health_check))
+        // 🏷️ Which build is running (commit, build time, schema level)
+        .route("/api/version", get(api::health::version))
+        // 🌐 Feedback and job numbers for status pages and SLO monitors
+        .route("/api/status", get(api::status_stats::service_status))
         .route(
             "/api/status/:project_id",
             get(api::status::get_project_status),
@@ -160,6 +357,10 @@ fn create_router(app_state: api::AppState, config: &Config) -> Result<Router> {
         // 🔍 Project management endpoints
         .route("/api/projects", get(api::projects::list_projects))
         .route("/api/projects/:id", get(api::projects::get_project))
+        .route(
+            "/api/projects/:id/transfer",
+            post(api::projects::transfer_project),
+        )
         // 🐙 GitHub webhook endpoint for status updates
         .route("/api/webhook/github", post(api::webhooks::github_webhook))
         // 🎯 GitHub issue automation webhooks
@@ -189,6 +390,7 @@ fn create_router(app_state: api::AppState, config: &Config) -> Result<Router> {
         )
         // 🤖 MCP (Model Context Protocol) endpoints for Smart Tree
         .route("/mcp/check", get(api::mcp::mcp_check))
+        .route("/mcp/changelog", get(api::releases::mcp_changelog))
         .route("/mcp/stats", get(api::mcp::mcp_stats))
         .route("/mcp/version", post(api::mcp::mcp_set_version))
         // 🔐 Authentication endpoints
@@ -196,6 +398,15 @@ fn create_router(app_state: api::AppState, config: &Config) -> Result<Router> {
         .route("/api/auth/logout", post(api::auth::logout))
         .route("/api/auth/register", post(api::auth::register));
 
+    // 📈 Prometheus scrape endpoints
+    let api_router = if config.features.enable_metrics {
+        api_router
+            .route("/metrics", get(api::health::metrics))
+            .route("/mcp/metrics", get(api::mcp_metrics::mcp_metrics))
+    } else {
+        api_router
+    };
+
     // 🎨 Create the web UI router for our beautiful interface
     let web_router = Router::new()
         // 🏠 Home page - welcome to Feedbacker!
@@ -206,36 +417,189 @@ fn create_router(app_state: api::AppState, config: &Config) -> Result<Router> {
         // 🔐 Authentication pages
         .route("/login", get(api::web::login_page))
         .route("/register", get(api::web::register_page))
+        // 📮 Public feedback form (for people without the CLI)
+        .route(
+            "/feedback",
+            get(api::feedback_form::feedback_form_page)
+                .post(api::feedback_form::feedback_form_submit),
+        )
+        .route(
+            "/feedback/:id",
+            get(api::feedback_form::feedback_status_page),
+        )
         // 📚 Documentation and help
         .route("/docs", get(api::web::docs_page))
         .route("/about", get(api::web::about_page));
 
     // 🔧 Create the admin router for system management
     let admin_router = Router::new()
-        // 🔐 Admin login page
+        // 🔐 Admin login page (plus the TOTP step when two-factor is on)
         .route("/admin/login", get(api::admin::admin_login))
         .route("/admin/login", post(api::admin::admin_login_post))
+        .route("/admin/login/totp", post(api::admin::admin_login_totp_post))
         // 🚪 Admin logout
         .route("/admin/logout", get(api::admin::admin_logout))
         // 📊 Admin dashboard - system overview
         .route("/admin", get(api::admin::admin_dashboard))
         // 📝 Feedback management
         .route("/admin/feedback", get(api::admin::admin_feedback))
+        .route(
+            "/admin/feedback/columns",
+            post(api::admin::admin_feedback_columns),
+        )
+        .route(
+            "/admin/feedback/:id",
+            get(api::admin::admin_feedback_detail),
+        )
+        .route(
+            "/admin/feedback/:id/approve",
+            post(api::admin::admin_feedback_approve),
+        )
+        .route(
+            "/admin/feedback/:id/reject",
+            post(api::admin::admin_feedback_reject),
+        )
+        .route(
+            "/admin/feedback/:id/release",
+            post(api::admin::admin_feedback_release),
+        )
+        .route(
+            "/admin/feedback/:id/duplicate",
+            post(api::admin::admin_feedback_duplicate),
+        )
+        .route(
+            "/admin/feedback/:id/attachments/:attachment_id",
+            get(api::attachments::admin_download_attachment),
+        )
+        .route("/admin/api/feedback", get(api::admin::admin_feedback_api))
+        // 🔖 Saved views of the feedback list
+        .route(
+            "/admin/api/views",
+            get(api::saved_views::list_views_handler).post(api::saved_views::create_view_handler),
+        )
+        .route(
+            "/admin/api/views/:id",
+            put(api::saved_views::update_view_handler)
+                .delete(api::saved_views::delete_view_handler),
+        )
+        .route("/admin/views", post(api::saved_views::save_view_form))
+        .route(
+            "/admin/views/delete",
+            post(api::saved_views::delete_view_form),
+        )
+        .route(
+            "/admin/api/llm/health",
+            get(api::admin::admin_llm_health_api),
+        )
+        .route(
+            "/admin/api/feedback/import",
+            post(api::feedback_import::import_feedback_handler).layer(DefaultBodyLimit::max(
+                api::feedback_import::IMPORT_MAX_BYTES,
+            )),
+        )
+        .route("/admin/api/events", get(api::events::admin_events))
+        .route("/admin/api/tags", get(api::tags::admin_tag_stats))
+        .route(
+            "/admin/api/stats/history",
+            get(api::stats_history::admin_stats_history),
+        )
+        .route(
+            "/admin/api/feedback/:id/callback-secret/rotate",
+            post(api::callback_secrets::rotate_callback_secret_handler),
+        )
         // 🏠 Projects management
         .route("/admin/projects", get(api::admin::admin_projects))
         .route("/admin/projects/add", post(api::admin::admin_projects_add))
+        .route(
+            "/admin/projects/import",
+            post(api::project_import::import_projects_handler).layer(DefaultBodyLimit::max(
+                api::project_import::PROJECT_IMPORT_MAX_BYTES,
+            )),
+        )
+        .route(
+            "/admin/projects/:id/config/migrate",
+            post(api::admin::admin_project_config_migrate),
+        )
+        .route(
+            "/admin/projects/:id/webhook",
+            post(api::admin::admin_project_webhook_retry),
+        )
+        .route(
+            "/admin/projects/:id/threshold",
+            post(api::admin::admin_project_threshold),
+        )
+        .route(
+            "/admin/projects/:id/approval",
+            post(api::admin::admin_project_approval_toggle),
+        )
+        .route(
+            "/admin/projects/:id/repositories",
+            post(api::admin::admin_project_repository_add),
+        )
+        .route(
+            "/admin/projects/:id/repositories/remove",
+            post(api::admin::admin_project_repository_remove),
+        )
+        .route(
+            "/admin/projects/:id/delete",
+            post(api::admin::admin_project_delete),
+        )
+        .route(
+            "/admin/api/projects/:id/reactivate",
+            post(api::admin::admin_project_reactivate_api),
+        )
         // 👥 Users management
         .route("/admin/users", get(api::admin::admin_users))
         // 🔄 Background jobs monitoring
         .route("/admin/jobs", get(api::admin::admin_jobs))
+        // 🗄️ Migration status and checksum drift
+        .route("/admin/migrations", get(api::admin::admin_migrations))
         // 🤖 MCP Analytics
         .route("/admin/mcp", get(api::admin::admin_mcp))
         .route(
             "/admin/mcp/set-version",
             post(api::admin::admin_mcp_set_version),
         )
-        // ⚙️ System settings
-        .route("/admin/settings", get(api::admin::admin_settings));
+        .route(
+            "/admin/api/mcp/analytics/export.csv",
+            get(api::mcp_export::export_analytics_csv),
+        )
+        .route(
+            "/admin/api/mcp/analytics/purge",
+            post(api::mcp_export::purge_analytics_handler),
+        )
+        // ⚙️ System settings (and two-factor enrollment)
+        .route("/admin/settings", get(api::admin::admin_settings))
+        .route(
+            "/admin/settings/labels",
+            post(api::admin::admin_settings_labels),
+        )
+        .route(
+            "/admin/settings/timezone",
+            post(api::admin::admin_settings_timezone),
+        )
+        .route(
+            "/admin/settings/totp/enroll",
+            post(api::admin::admin_totp_enroll),
+        )
+        .route(
+            "/admin/settings/totp/activate",
+            post(api::admin::admin_totp_activate),
+        )
+        .route(
+            "/admin/settings/totp/disable",
+            post(api::admin::admin_totp_disable),
+        )
+        // 🎨 Embedded, content-hashed static assets (stylesheet)
+        .route("/admin/assets/:file", get(api::assets::admin_asset));
+
+    // 🌱 Development seed data (dev-tools builds only, and it refuses to run in production)
+    #[cfg(feature = "dev-tools")]
+    let admin_router = admin_router.route("/admin/api/dev/seed", post(api::dev::dev_seed));
+
+    // 💥 Deliberately panicking route for the panic-layer test
+    #[cfg(test)]
+    let admin_router = admin_router.route(PANIC_TEST_ROUTE, get(panicking_test_handler));
 
     // 🛡️ Apply middleware layers (like adding layers to a delicious cake!)
     let app = Router::new()
@@ -244,10 +608,21 @@ fn create_router(app_state: api::AppState, config: &Config) -> Result<Router> {
         .merge(admin_router)
         .layer(
             ServiceBuilder::new()
-                // 📊 Tracing layer for request logging
-                .layer(TraceLayer::new_for_http())
-                // 🗜️ Compression for faster responses
-                .layer(CompressionLayer::new())
+                // 🆔 Every request gets an x-request-id (kept if the client sent one)...
+                .layer(SetRequestIdLayer::x_request_id(MakeRequestUuid))
+                // 📊 Tracing layer for request logging (request id + route on every line)
+                .layer(TraceLayer::new_for_http().make_span_with(request_span))
+                // 🆔 ...and every response carries it back
+                .layer(PropagateRequestIdLayer::x_request_id())
+                // 💥 Panics below here become a JSON 500 instead of a dropped connection
+                .layer(axum_middleware::from_fn_with_state(
+                    app_state.clone(),
+                    catch_panic_middleware,
+                ))
+                // 📏 Hard cap on request bodies (feedback content has its own, smaller limit)
+                .layer(DefaultBodyLimit::max(config.server.max_body_size))
+                // 🗜️ Compression for faster responses (HTML/JSON/CSS over a size threshold)
+                .layer(compression_layer(config.server.compression_min_bytes))
                 // 🌍 CORS support for web clients
                 .layer(CorsLayer::permissive()) // TODO: Make this more restrictive in production
                 // 🚦 Rate limiting to prevent abuse
@@ -268,6 +643,35 @@ fn create_router(app_state: api::AppState, config: &Config) -> Result<Router> {
     Ok(app)
 }
 
+// 💥 Always panics - only routed in tests
+#[cfg(test)]
+async fn panicking_test_handler() -> &'static str {
+    panic!("deliberate panic from the test-only route")
+}
+
+// 🗜️ Build the compression layer - gzip/brotli for text payloads worth squeezing
+fn compression_layer(min_bytes: u16) -> CompressionLayer<impl Predicate> {
+    CompressionLayer::new().compress_when(SizeAbove::new(min_bytes).and(is_compressible_response))
+}
+
+// 🎯 Only HTML, JSON and CSS are worth compressing (images and streams are left alone)
+fn is_compressible_response(
+    _status: axum::http::StatusCode,
+    _version: axum::http::Version,
+    headers: &axum::http::HeaderMap,
+    _extensions: &axum::http::Extensions,
+) -> bool {
+    headers
+        .get(axum::http::header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .map(|content_type| {
+            content_type.starts_with("text/html")
+                || content_type.starts_with("application/json")
+                || content_type.starts_with("text/css")
+        })
+        .unwrap_or(false)
+}
+
 // 🏠 Home page handler - Our beautiful welcome page!
 async fn web_home() -> impl IntoResponse {
     Html(
@@ -431,10 +835,63 @@ mod tests {
         println!("✅ Database URL masking works perfectly!");
     }
 
+    #[tokio::test]
+    async fn test_compression_layer_headers() {
+        use axum::{body::Body, http::Request};
+        use tower::ServiceExt;
+
+        let app = Router::new()
+            .route(
+                "/big",
+                get(|| async { Html("<p>Feedbacker!</p>".repeat(200)) }),
+            )
+            .route("/small", get(|| async { Html("<p>hi</p>") }))
+            .layer(compression_layer(1024));
+
+        let request = |uri: &str| {
+            Request::builder()
+                .uri(uri)
+                .header("accept-encoding", "gzip")
+                .body(Body::empty())
+                .unwrap()
+        };
+
+        let big = app.clone().oneshot(request("/big")).await.unwrap();
+        assert_eq!(big.headers().get("content-encoding").unwrap(), "gzip");
+        assert_eq!(big.headers().get("vary").unwrap(), "accept-encoding");
+
+        let small = app.oneshot(request("/small")).await.unwrap();
+        assert!(small.headers().get("content-encoding").is_none());
+        println!("✅ Compression layer header test passed!");
+    }
+
+    #[cfg(not(feature = "dev-tools"))]
+    #[tokio::test]
+    async fn test_seed_route_needs_the_dev_tools_feature() {
+        let Some(app) = crate::test_support::spawn_test_app().await else {
+            return;
+        };
+        app.login_admin().await.unwrap();
+        let response = app
+            .client
+            .post(app.url("/admin/api/dev/seed"))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), reqwest::StatusCode::NOT_FOUND);
+        println!("✅ Seed route gating test passed!");
+    }
+
     #[tokio::test]
     async fn test_logging_initialization() {
         // This test ensures our logging setup doesn't panic
-        let result = init_logging();
+        let result = init_logging(&LoggingConfig {
+            level: "info".to_string(),
+            format: LogFormat::Json,
+            file_path: None,
+            log_requests: true,
+            sample_every: 1,
+        });
         assert!(result.is_ok());
         println!("✅ Logging initialization test passed!");
     }
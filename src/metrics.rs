@@ -0,0 +1,243 @@
+// 📈 Metrics - Little counters for the things we want to know about! 📈
+// In-process counters kept on the AppState. Labels are plain strings
+// (e.g. the matched route); GET /metrics renders them for Prometheus.
+// Created with love by Aye & Hue! ✨
+
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+
+use crate::github::concurrency::RequestLimiter;
+use crate::github::throttle::WriteThrottle;
+use crate::middleware::rate_limiting::IpRateLimiter;
+
+/// 📊 Counters shared by every handler and middleware
+#[derive(Debug, Default)]
+pub struct Metrics {
+    /// 💥 Handler panics caught by the panic layer, by route
+    panics: Mutex<BTreeMap<String, u64>>,
+    /// 🙈 MCP checks that asked not to be tracked (only ever counted, never logged)
+    dnt_checks: AtomicU64,
+    /// 🤷 MCP checks from a User-Agent outside MCP_TRUSTED_USER_AGENTS (answered, not logged)
+    untrusted_checks: AtomicU64,
+    /// 🐢 Times an event subscriber fell behind and was resynchronized
+    event_lags: AtomicU64,
+    /// 🕳️ Events those subscribers never saw
+    events_skipped: AtomicU64,
+}
+
+impl Metrics {
+    /// 💥 Count one caught panic on `route`
+    pub fn record_panic(&self, route: &str) {
+        let mut panics = self.panics.lock().unwrap_or_else(|e| e.into_inner());
+        *panics.entry(route.to_string()).or_default() += 1;
+    }
+
+    /// 🔢 Panics caught so far on `route`
+    pub fn panic_count(&self, route: &str) -> u64 {
+        let panics = self.panics.lock().unwrap_or_else(|e| e.into_inner());
+        panics.get(route).copied().unwrap_or_default()
+    }
+
+    /// 📋 Every route that has panicked, with its count
+    pub fn panics(&self) -> BTreeMap<String, u64> {
+        self.panics
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone()
+    }
+
+    /// 🙈 Count one Do-Not-Track MCP check
+    pub fn record_dnt_check(&self) {
+        self.dnt_checks.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// 🔢 Do-Not-Track MCP checks so far
+    pub fn dnt_checks(&self) -> u64 {
+        self.dnt_checks.load(Ordering::Relaxed)
+    }
+
+    /// 🤷 Count one MCP check from an untrusted client
+    pub fn record_untrusted_check(&self) {
+        self.untrusted_checks.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// 🔢 MCP checks from untrusted clients so far
+    pub fn untrusted_checks(&self) -> u64 {
+        self.untrusted_checks.load(Ordering::Relaxed)
+    }
+
+    /// 🐢 Count one event subscriber lag that skipped `skipped` events
+    pub fn record_event_lag(&self, skipped: u64) {
+        self.event_lags.fetch_add(1, Ordering::Relaxed);
+        self.events_skipped.fetch_add(skipped, Ordering::Relaxed);
+    }
+
+    /// 🔢 (lags, events skipped) so far
+    pub fn event_lags(&self) -> (u64, u64) {
+        (
+            self.event_lags.load(Ordering::Relaxed),
+            self.events_skipped.load(Ordering::Relaxed),
+        )
+    }
+
+    /// 📜 Prometheus text exposition of the counters, plus the rate limiter's and
+    /// GitHub write throttle's and request limiter's gauges
+    pub fn render_prometheus(
+        &self,
+        rate_limiter: &IpRateLimiter,
+        github_throttle: &WriteThrottle,
+        github_requests: &RequestLimiter,
+    ) -> String {
+        let mut out = String::new();
+        let _ = writeln!(
+            out,
+            "# HELP feedbacker_rate_limit_tracked_keys Per-IP rate limit windows held in memory"
+        );
+        let _ = writeln!(out, "# TYPE feedbacker_rate_limit_tracked_keys gauge");
+        let _ = writeln!(
+            out,
+            "feedbacker_rate_limit_tracked_keys {}",
+            rate_limiter.tracked_keys()
+        );
+        let _ = writeln!(
+            out,
+            "# HELP feedbacker_rate_limit_evicted_total Expired rate limit windows evicted by the sweeper"
+        );
+        let _ = writeln!(out, "# TYPE feedbacker_rate_limit_evicted_total counter");
+        let _ = writeln!(
+            out,
+            "feedbacker_rate_limit_evicted_total {}",
+            rate_limiter.evicted_total()
+        );
+        let _ = writeln!(
+            out,
+            "# HELP feedbacker_github_write_throttle_wait_seconds_total Time GitHub writes spent waiting for the write throttle"
+        );
+        let _ = writeln!(
+            out,
+            "# TYPE feedbacker_github_write_throttle_wait_seconds_total counter"
+        );
+        let _ = writeln!(
+            out,
+            "feedbacker_github_write_throttle_wait_seconds_total {}",
+            github_throttle.wait_total().as_secs_f64()
+        );
+        let _ = writeln!(
+            out,
+            "# HELP feedbacker_github_writes_delayed_total GitHub writes that waited for the write throttle"
+        );
+        let _ = writeln!(out, "# TYPE feedbacker_github_writes_delayed_total counter");
+        let _ = writeln!(
+            out,
+            "feedbacker_github_writes_delayed_total {}",
+            github_throttle.delayed_total()
+        );
+        let _ = writeln!(
+            out,
+            "# HELP feedbacker_github_writes_deferred_total GitHub writes deferred to the job queue by the write throttle"
+        );
+        let _ = writeln!(
+            out,
+            "# TYPE feedbacker_github_writes_deferred_total counter"
+        );
+        let _ = writeln!(
+            out,
+            "feedbacker_github_writes_deferred_total {}",
+            github_throttle.deferred_total()
+        );
+        let _ = writeln!(
+            out,
+            "# HELP feedbacker_github_write_saturated_seconds How long GitHub writes have been continuously throttled"
+        );
+        let _ = writeln!(
+            out,
+            "# TYPE feedbacker_github_write_saturated_seconds gauge"
+        );
+        let _ = writeln!(
+            out,
+            "feedbacker_github_write_saturated_seconds {}",
+            github_throttle
+                .saturated_for()
+                .unwrap_or_default()
+                .as_secs_f64()
+        );
+        let _ = writeln!(
+            out,
+            "# HELP feedbacker_github_requests_in_flight GitHub requests holding a slot right now"
+        );
+        let _ = writeln!(out, "# TYPE feedbacker_github_requests_in_flight gauge");
+        let _ = writeln!(
+            out,
+            "feedbacker_github_requests_in_flight {}",
+            github_requests.in_use()
+        );
+        let _ = writeln!(
+            out,
+            "# HELP feedbacker_github_requests_limit GitHub requests allowed in flight at once (GITHUB_MAX_CONCURRENT_REQUESTS)"
+        );
+        let _ = writeln!(out, "# TYPE feedbacker_github_requests_limit gauge");
+        let _ = writeln!(
+            out,
+            "feedbacker_github_requests_limit {}",
+            github_requests.capacity()
+        );
+        let _ = writeln!(
+            out,
+            "# HELP feedbacker_github_requests_queued_total GitHub requests that waited for a free slot"
+        );
+        let _ = writeln!(
+            out,
+            "# TYPE feedbacker_github_requests_queued_total counter"
+        );
+        let _ = writeln!(
+            out,
+            "feedbacker_github_requests_queued_total {}",
+            github_requests.queued_total()
+        );
+        let _ = writeln!(
+            out,
+            "# HELP feedbacker_mcp_dnt_checks_total MCP version checks that opted out of analytics (Do-Not-Track)"
+        );
+        let _ = writeln!(out, "# TYPE feedbacker_mcp_dnt_checks_total counter");
+        let _ = writeln!(out, "feedbacker_mcp_dnt_checks_total {}", self.dnt_checks());
+        let _ = writeln!(
+            out,
+            "# HELP feedbacker_mcp_untrusted_checks_total MCP version checks answered without analytics (User-Agent not in MCP_TRUSTED_USER_AGENTS)"
+        );
+        let _ = writeln!(out, "# TYPE feedbacker_mcp_untrusted_checks_total counter");
+        let _ = writeln!(
+            out,
+            "feedbacker_mcp_untrusted_checks_total {}",
+            self.untrusted_checks()
+        );
+        let (lags, skipped) = self.event_lags();
+        let _ = writeln!(
+            out,
+            "# HELP feedbacker_event_bus_lagged_total Event subscribers that fell behind and were resynchronized"
+        );
+        let _ = writeln!(out, "# TYPE feedbacker_event_bus_lagged_total counter");
+        let _ = writeln!(out, "feedbacker_event_bus_lagged_total {}", lags);
+        let _ = writeln!(
+            out,
+            "# HELP feedbacker_event_bus_skipped_total Events dropped for lagging subscribers"
+        );
+        let _ = writeln!(out, "# TYPE feedbacker_event_bus_skipped_total counter");
+        let _ = writeln!(out, "feedbacker_event_bus_skipped_total {}", skipped);
+        let _ = writeln!(
+            out,
+            "# HELP feedbacker_handler_panics_total Handler panics caught by the panic layer"
+        );
+        let _ = writeln!(out, "# TYPE feedbacker_handler_panics_total counter");
+        for (route, count) in self.panics() {
+            let _ = writeln!(
+                out,
+                "feedbacker_handler_panics_total{{route=\"{}\"}} {}",
+                route.replace('\\', "\\\\").replace('"', "\\\""),
+                count
+            );
+        }
+        out
+    }
+}
@@ -17,7 +17,7 @@ use tracing::{debug, error, warn};
 use uuid::Uuid;
 
 use crate::{
-    api::{ApiResponse, AppState},
+    api::{ApiResponse, AppState, ErrorCode},
     database::models::{User, UserRole},
 };
 
@@ -169,18 +169,26 @@ pub async fn auth_middleware(
 /// 🔍 Check if a path is public (doesn't require authentication)
 fn is_public_path(path: &str) -> bool {
     let public_paths = [
-        "/",                      // Home page
-        "/api/health",            // Health checks
-        "/api/readiness",         // Readiness probe
-        "/api/liveness",          // Liveness probe
-        "/api/auth/login",        // Login endpoint
-        "/api/auth/register",     // Registration endpoint
-        "/api/webhook/github",    // GitHub webhooks (authenticated differently)
-        "/api/smart-tree/latest", // Smart Tree version check
-        "/about",                 // About page
-        "/docs",                  // Documentation
-        "/login",                 // Login page
-        "/register",              // Registration page
+        "/",                       // Home page
+        "/api/health",             // Health checks
+        "/api/readiness",          // Readiness probe
+        "/api/liveness",           // Liveness probe
+        "/api/version",            // Build metadata (nothing secret)
+        "/api/status",             // Aggregate feedback and job counts (status pages)
+        "/metrics",                // Prometheus scrape (only routed with ENABLE_METRICS)
+        "/mcp/metrics",            // MCP analytics scrape (same)
+        "/api/auth/login",         // Login endpoint
+        "/api/auth/register",      // Registration endpoint
+        "/api/webhook/github",     // GitHub webhooks (authenticated differently)
+        "/api/webhook/issues",     // GitHub issue webhooks (same as above)
+        "/api/issues",             // Issue relay (scoped API key, checked by the handler)
+        "/api/smart-tree/latest",  // Smart Tree version check
+        "/about",                  // About page
+        "/docs",                   // Documentation
+        "/feedback",               // Public feedback form
+        "/api/feedback/challenge", // What anonymous submissions must solve
+        "/login",                  // Login page
+        "/register",               // Registration page
     ];
 
     // 🎯 Check exact matches
@@ -190,11 +198,13 @@ fn is_public_path(path: &str) -> bool {
 
     // 🎯 Check prefixes for public endpoints
     let public_prefixes = [
-        "/static/",   // Static assets
-        "/assets/",   // Assets
-        "/favicon",   // Favicon
-        "/admin",     // Admin pages (auth handled by admin module via cookies)
-        "/mcp/check", // MCP version check (called by Smart Tree clients)
+        "/static/",       // Static assets
+        "/assets/",       // Assets
+        "/favicon",       // Favicon
+        "/admin",         // Admin pages (auth handled by admin module via cookies)
+        "/mcp/check",     // MCP version check (called by Smart Tree clients)
+        "/mcp/changelog", // Release notes between two versions (same clients)
+        "/feedback/",     // Public status page of one submission
     ];
 
     public_prefixes
@@ -247,8 +257,7 @@ async fn verify_user_active(
     let user_id = Uuid::parse_str(&claims.sub)
         .map_err(|e| anyhow::anyhow!("Invalid user ID in token: {}", e))?;
 
-    // TODO: Implement proper user verification when database is ready
-    let user: Option<User> = None;
+    let user = User::find_active_by_id(&app_state.db_pool, user_id).await?;
 
     match user {
         Some(user) => {
@@ -278,6 +287,11 @@ fn get_required_permission(path: &str) -> Option<Permission> {
         return Some(Permission::ManageUsers);
     }
 
+    // 🔀 Owners transfer their own projects; the handler checks owner-or-admin itself
+    if path.starts_with("/api/projects/") && path.ends_with("/transfer") {
+        return None;
+    }
+
     if path.starts_with("/api/projects/") && !path.contains("/feedback") {
         return Some(Permission::ManageProjects);
     }
@@ -286,6 +300,18 @@ fn get_required_permission(path: &str) -> Option<Permission> {
         return Some(Permission::ViewAllFeedback);
     }
 
+    // ✋ Owners decide on held changes; the handler checks owner-or-admin itself
+    if path.starts_with("/api/feedback/")
+        && (path.ends_with("/approve") || path.ends_with("/reject"))
+    {
+        return None;
+    }
+
+    // 📎 Adding attachments is part of submitting feedback
+    if path.starts_with("/api/feedback/") && path.ends_with("/attachments") {
+        return Some(Permission::SubmitFeedback);
+    }
+
     // 📝 Most feedback endpoints just require basic authentication
     if path.starts_with("/api/feedback/") {
         return Some(Permission::ReadFeedback);
@@ -298,15 +324,14 @@ fn get_required_permission(path: &str) -> Option<Permission> {
 /// 🚫 Create unauthorized error response
 fn unauthorized_response(message: &str) -> Response {
     let error_response =
-        ApiResponse::<()>::error("unauthorized".to_string(), message.to_string(), None);
+        ApiResponse::<()>::error(ErrorCode::Unauthorized, message.to_string(), None);
 
     (StatusCode::UNAUTHORIZED, Json(error_response)).into_response()
 }
 
 /// 🛡️ Create forbidden error response
 fn forbidden_response(message: &str) -> Response {
-    let error_response =
-        ApiResponse::<()>::error("forbidden".to_string(), message.to_string(), None);
+    let error_response = ApiResponse::<()>::error(ErrorCode::Forbidden, message.to_string(), None);
 
     (StatusCode::FORBIDDEN, Json(error_response)).into_response()
 }
@@ -379,10 +404,17 @@ mod tests {
     fn test_is_public_path() {
         assert!(is_public_path("/"));
         assert!(is_public_path("/api/health"));
+        assert!(is_public_path("/api/version"));
+        assert!(is_public_path("/api/status"));
         assert!(is_public_path("/api/auth/login"));
         assert!(is_public_path("/static/css/style.css"));
         assert!(is_public_path("/favicon.ico"));
 
+        assert!(is_public_path("/feedback"));
+        assert!(is_public_path("/api/feedback/challenge"));
+        assert!(is_public_path(
+            "/feedback/5f0c5a51-6f0e-4d3b-9a57-1f1b2c3d4e5f"
+        ));
         assert!(!is_public_path("/api/feedback"));
         assert!(!is_public_path("/api/projects"));
         assert!(!is_public_path("/dashboard"));
@@ -467,6 +499,7 @@ mod tests {
             get_required_permission("/api/projects/create"),
             Some(Permission::ManageProjects)
         );
+        assert_eq!(get_required_permission("/api/projects/123/transfer"), None);
         assert_eq!(
             get_required_permission("/api/feedback/all"),
             Some(Permission::ViewAllFeedback)
@@ -475,6 +508,16 @@ mod tests {
             get_required_permission("/api/feedback/123"),
             Some(Permission::ReadFeedback)
         );
+        assert_eq!(
+            get_required_permission("/api/feedback/123/attachments"),
+            Some(Permission::SubmitFeedback)
+        );
+        assert_eq!(
+            get_required_permission("/api/feedback/123/attachments/456"),
+            Some(Permission::ReadFeedback)
+        );
+        assert_eq!(get_required_permission("/api/feedback/123/approve"), None);
+        assert_eq!(get_required_permission("/api/feedback/123/reject"), None);
 
         println!("✅ Required permission mapping test passed!");
     }
@@ -7,6 +7,7 @@
 pub mod auth; // 🔐 Authentication middleware
 pub mod cors; // 🌍 CORS handling middleware
 pub mod logging; // 📊 Request logging middleware
+pub mod panic; // 💥 Panic recovery (clean 500s instead of dropped connections)
 pub mod rate_limiting; // 🚦 Rate limiting middleware
 pub mod security; // 🛡️ Security headers middleware
 
@@ -14,5 +15,6 @@ pub mod security; // 🛡️ Security headers middleware
 pub use auth::auth_middleware;
 pub use cors::cors_middleware;
 pub use logging::logging_middleware;
+pub use panic::catch_panic_middleware;
 pub use rate_limiting::rate_limit_middleware;
 pub use security::security_headers_middleware;
@@ -0,0 +1,144 @@
+// 💥 Panic Recovery - One bad handler shouldn't drop the connection! 💥
+// Catches panics from everything inside this layer, answers with the usual
+// JSON 500 envelope (plus the request id), counts the panic per route and
+// logs the payload so there is a structured record of what blew up.
+// Created with love by Aye & Hue! ✨
+
+use axum::{
+    extract::{MatchedPath, Request, State},
+    http::{HeaderValue, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Json, Response},
+};
+use futures_util::FutureExt;
+use std::any::Any;
+use std::panic::AssertUnwindSafe;
+use tracing::error;
+
+use crate::api::{ApiResponse, AppState, ErrorCode};
+
+/// 🆔 Header carrying the request id (set by the request-id layer, echoed back)
+pub const REQUEST_ID_HEADER: &str = "x-request-id";
+
+/// 💥 Turn a panic anywhere below this layer into a clean 500
+pub async fn catch_panic_middleware(
+    State(app_state): State<AppState>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let method = request.method().clone();
+    let route = request
+        .extensions()
+        .get::<MatchedPath>()
+        .map(|path| path.as_str().to_string())
+        .unwrap_or_else(|| request.uri().path().to_string());
+    let request_id = request
+        .headers()
+        .get(REQUEST_ID_HEADER)
+        .and_then(|value| value.to_str().ok())
+        .map(str::to_string)
+        .unwrap_or_else(|| uuid::Uuid::new_v4().to_string());
+
+    // 🛡️ Handlers share nothing we could leave half-updated that outlives the request
+    let payload = match AssertUnwindSafe(next.run(request)).catch_unwind().await {
+        Ok(response) => return response,
+        Err(payload) => payload,
+    };
+
+    app_state.metrics.record_panic(&route);
+    error!(
+        request_id = %request_id,
+        route = %route,
+        method = %method,
+        "💥 Handler panicked: {}",
+        panic_message(payload.as_ref())
+    );
+
+    let mut response = (
+        StatusCode::INTERNAL_SERVER_ERROR,
+        Json(ApiResponse::<()>::error(
+            ErrorCode::InternalError,
+            "An internal error occurred".to_string(),
+            Some(serde_json::json!({ "request_id": request_id })),
+        )),
+    )
+        .into_response();
+    if let Ok(value) = HeaderValue::from_str(&request_id) {
+        response.headers_mut().insert(REQUEST_ID_HEADER, value);
+    }
+    response
+}
+
+/// 📝 The panic message, when the payload is a string (it almost always is)
+fn panic_message(payload: &(dyn Any + Send)) -> &str {
+    payload
+        .downcast_ref::<&str>()
+        .copied()
+        .or_else(|| payload.downcast_ref::<String>().map(String::as_str))
+        .unwrap_or("<non-string panic payload>")
+}
+
+// 🧪 Tests - Breaking things on purpose, safely!
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::spawn_test_app;
+
+    #[test]
+    fn test_panic_message_extraction() {
+        let payload: Box<dyn Any + Send> = Box::new("static message");
+        assert_eq!(panic_message(payload.as_ref()), "static message");
+        let payload: Box<dyn Any + Send> = Box::new(format!("row {} was NULL", 7));
+        assert_eq!(panic_message(payload.as_ref()), "row 7 was NULL");
+        let payload: Box<dyn Any + Send> = Box::new(42_u8);
+        assert_eq!(
+            panic_message(payload.as_ref()),
+            "<non-string panic payload>"
+        );
+        println!("✅ Panic message extraction test passed!");
+    }
+
+    #[tokio::test]
+    async fn test_handler_panic_becomes_json_500_and_is_counted() {
+        let Some(app) = spawn_test_app().await else {
+            return;
+        };
+        let metrics = app.app_state.metrics.clone();
+        assert_eq!(metrics.panic_count(crate::PANIC_TEST_ROUTE), 0);
+
+        let response = app
+            .client
+            .get(app.url(crate::PANIC_TEST_ROUTE))
+            .header(REQUEST_ID_HEADER, "req-panic-123")
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), 500);
+        assert_eq!(response.headers()[REQUEST_ID_HEADER], "req-panic-123");
+        let body: serde_json::Value = response.json().await.unwrap();
+        assert_eq!(body["success"], false);
+        assert_eq!(body["error"]["code"], "internal_error");
+        assert_eq!(body["error"]["details"]["request_id"], "req-panic-123");
+        assert_eq!(metrics.panic_count(crate::PANIC_TEST_ROUTE), 1);
+
+        // 🆔 Without an incoming id, one is generated and still reported
+        let response = app
+            .client
+            .get(app.url(crate::PANIC_TEST_ROUTE))
+            .send()
+            .await
+            .unwrap();
+        let request_id = response.headers()[REQUEST_ID_HEADER]
+            .to_str()
+            .unwrap()
+            .to_string();
+        let body: serde_json::Value = response.json().await.unwrap();
+        assert_eq!(body["error"]["details"]["request_id"], request_id);
+        assert_eq!(metrics.panic_count(crate::PANIC_TEST_ROUTE), 2);
+
+        // 🚢 The server is still happily serving other routes
+        let health = app.client.get(app.url("/api/health")).send().await.unwrap();
+        assert_ne!(health.status(), 500);
+        println!("✅ Panic recovery test passed!");
+    }
+}
@@ -4,29 +4,33 @@
 // Created with love by Aye & Hue - Making fair usage beautiful! ✨
 // Trisha from Accounting appreciates when resources are used fairly! 📊
 
+use crate::utils::client_ip::ProxyRange;
 use axum::{
-    extract::{Request, State},
+    extract::{ConnectInfo, Request, State},
     http::{HeaderMap, StatusCode},
     middleware::Next,
     response::{IntoResponse, Json, Response},
 };
+use dashmap::DashMap;
 use governor::{
     clock::{DefaultClock, QuantaClock},
     state::{InMemoryState, NotKeyed},
     Quota, RateLimiter,
 };
-use nonzero_ext::*;
 use std::{
     collections::HashMap,
-    net::IpAddr,
-    str::FromStr,
-    sync::{Arc, Mutex},
-    time::Duration,
+    net::{IpAddr, SocketAddr},
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, Mutex,
+    },
+    time::{Duration, Instant},
 };
 use tracing::{debug, info, warn};
 
 use crate::{
-    api::{ApiResponse, AppState},
+    api::{ApiResponse, AppState, ErrorCode},
+    config::RateLimitConfig,
     database::models::RateLimit,
 };
 
@@ -124,6 +128,164 @@ impl RateLimitManager {
     // TODO: Implement database rate limiting when database is ready
 }
 
+/// 🎯 What a per-IP allowance is for; each scope has its own quota
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RateLimitScope {
+    /// 📝 Feedback ingestion (POST /api/feedback and the public form)
+    Feedback,
+    /// 🌲 Smart Tree version checks (GET /mcp/check)
+    McpCheck,
+}
+
+/// 📏 `limit` requests per `window`
+#[derive(Debug, Clone, Copy)]
+pub struct WindowQuota {
+    pub limit: u32,
+    pub window: Duration,
+}
+
+impl WindowQuota {
+    pub fn per_hour(limit: u32) -> Self {
+        Self {
+            limit,
+            window: Duration::from_secs(3600),
+        }
+    }
+
+    pub fn per_minute(limit: u32) -> Self {
+        Self {
+            limit,
+            window: Duration::from_secs(60),
+        }
+    }
+}
+
+/// 🪟 One client's current window
+#[derive(Debug, Clone, Copy)]
+struct Window {
+    started: Instant,
+    count: u32,
+}
+
+/// 🌐 Fixed-window, per-client-IP limiter that lives as long as the app.
+/// Windows sit in a `DashMap`, so concurrent requests only contend on a shard, and a
+/// sweeper task evicts expired windows so a flood of distinct IPs can't grow it forever.
+pub struct IpRateLimiter {
+    windows: DashMap<(RateLimitScope, IpAddr), Window>,
+    feedback: WindowQuota,
+    mcp_check: WindowQuota,
+    /// 🧹 Windows evicted by sweeps so far
+    evicted: AtomicU64,
+}
+
+impl IpRateLimiter {
+    /// ➕ Create a limiter with a quota per scope
+    pub fn new(feedback: WindowQuota, mcp_check: WindowQuota) -> Self {
+        Self {
+            windows: DashMap::new(),
+            feedback,
+            mcp_check,
+            evicted: AtomicU64::new(0),
+        }
+    }
+
+    /// ⚙️ Quotas from RATE_LIMIT_FEEDBACK_PER_HOUR and RATE_LIMIT_MCP_CHECKS_PER_MINUTE
+    pub fn from_config(config: &RateLimitConfig) -> Self {
+        Self::new(
+            WindowQuota::per_hour(config.feedback_per_hour),
+            WindowQuota::per_minute(config.mcp_checks_per_minute),
+        )
+    }
+
+    fn quota(&self, scope: RateLimitScope) -> WindowQuota {
+        match scope {
+            RateLimitScope::Feedback => self.feedback,
+            RateLimitScope::McpCheck => self.mcp_check,
+        }
+    }
+
+    /// 🔍 Take one request from this IP's allowance, or say how long until the next one
+    pub fn check(&self, scope: RateLimitScope, ip: IpAddr) -> Result<(), Duration> {
+        self.check_at(scope, ip, Instant::now())
+    }
+
+    fn check_at(&self, scope: RateLimitScope, ip: IpAddr, now: Instant) -> Result<(), Duration> {
+        let quota = self.quota(scope);
+        let mut window = self.windows.entry((scope, ip)).or_insert(Window {
+            started: now,
+            count: 0,
+        });
+        if now.saturating_duration_since(window.started) >= quota.window {
+            *window = Window {
+                started: now,
+                count: 0,
+            };
+        }
+        if window.count < quota.limit.max(1) {
+            window.count += 1;
+            Ok(())
+        } else {
+            Err((window.started + quota.window).saturating_duration_since(now))
+        }
+    }
+
+    /// 🧹 Drop every window that has run out, returning how many were evicted
+    pub fn sweep(&self) -> usize {
+        self.sweep_at(Instant::now())
+    }
+
+    fn sweep_at(&self, now: Instant) -> usize {
+        let before = self.windows.len();
+        self.windows.retain(|(scope, _), window| {
+            now.saturating_duration_since(window.started) < self.quota(*scope).window
+        });
+        let evicted = before.saturating_sub(self.windows.len());
+        self.evicted.fetch_add(evicted as u64, Ordering::Relaxed);
+        evicted
+    }
+
+    /// 🔢 Client windows currently held in memory (exposed on /metrics)
+    pub fn tracked_keys(&self) -> usize {
+        self.windows.len()
+    }
+
+    /// 🔢 Windows evicted by sweeps since startup
+    pub fn evicted_total(&self) -> u64 {
+        self.evicted.load(Ordering::Relaxed)
+    }
+
+    /// 🧹 Sweep every `interval` in the background; the task ends once the limiter is dropped
+    pub fn spawn_sweeper(self: &Arc<Self>, interval: Duration) -> tokio::task::JoinHandle<()> {
+        let limiter = Arc::downgrade(self);
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval.max(Duration::from_secs(1)));
+            ticker.tick().await;
+            loop {
+                ticker.tick().await;
+                let Some(limiter) = limiter.upgrade() else {
+                    break;
+                };
+                let evicted = limiter.sweep();
+                if evicted > 0 {
+                    debug!(
+                        "🧹 Evicted {} expired rate limit windows ({} still tracked)",
+                        evicted,
+                        limiter.tracked_keys()
+                    );
+                }
+            }
+        })
+    }
+}
+
+impl std::fmt::Debug for IpRateLimiter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("IpRateLimiter")
+            .field("tracked_keys", &self.tracked_keys())
+            .finish()
+    }
+}
+
 /// 🚦 Rate limit types for different endpoints
 #[derive(Debug, Clone)]
 pub enum RateLimitType {
@@ -158,7 +320,7 @@ pub async fn rate_limit_middleware(
     next: Next,
 ) -> Result<Response, Response> {
     let path = request.uri().path();
-    let client_ip = extract_client_ip(&headers, &request);
+    let client_ip = extract_client_ip(&headers, &request, &app_state.config.server.trusted_proxies);
 
     // 🎯 Determine the type of rate limiting based on the path
     let limit_type = determine_limit_type(path);
@@ -190,7 +352,7 @@ pub async fn rate_limit_middleware(
             );
 
             let error_response = ApiResponse::<()>::error(
-                "rate_limit_exceeded".to_string(),
+                ErrorCode::RateLimitExceeded,
                 format!(
                     "Rate limit exceeded for {}. Try again in {} seconds.",
                     limit_type,
@@ -236,43 +398,20 @@ pub async fn rate_limit_middleware(
 }
 
 /// 🌐 Extract client IP address from request
-/// Handles various proxy headers for accurate IP detection
-fn extract_client_ip(headers: &HeaderMap, _request: &Request) -> IpAddr {
-    // 🔍 Check common proxy headers
-    if let Some(forwarded_for) = headers.get("X-Forwarded-For") {
-        if let Ok(header_str) = forwarded_for.to_str() {
-            if let Some(ip_str) = header_str.split(',').next() {
-                if let Ok(ip) = IpAddr::from_str(ip_str.trim()) {
-                    return ip;
-                }
-            }
-        }
-    }
-
-    if let Some(real_ip) = headers.get("X-Real-IP") {
-        if let Ok(header_str) = real_ip.to_str() {
-            if let Ok(ip) = IpAddr::from_str(header_str.trim()) {
-                return ip;
-            }
-        }
-    }
-
-    if let Some(cf_connecting_ip) = headers.get("CF-Connecting-IP") {
-        if let Ok(header_str) = cf_connecting_ip.to_str() {
-            if let Ok(ip) = IpAddr::from_str(header_str.trim()) {
-                return ip;
-            }
-        }
-    }
-
-    // 🎯 Fall back to connection peer (may not be accurate behind proxies)
-    // For now, return a default IP - in a real implementation, you'd extract from the connection
-    IpAddr::from_str("127.0.0.1").unwrap()
+/// Proxy headers only count when the connection comes from a TRUSTED_PROXIES address
+fn extract_client_ip(headers: &HeaderMap, request: &Request, trusted: &[ProxyRange]) -> IpAddr {
+    let peer = request
+        .extensions()
+        .get::<ConnectInfo<SocketAddr>>()
+        .map(|connect_info| connect_info.0.ip());
+    crate::utils::client_ip::client_ip(headers, peer, trusted)
+        // 🎯 No connection info (e.g. a router called without a listener)
+        .unwrap_or(IpAddr::from([127, 0, 0, 1]))
 }
 
 /// 🎯 Determine rate limit type based on request path
 fn determine_limit_type(path: &str) -> RateLimitType {
-    if path.starts_with("/api/feedback") && !path.ends_with("/stats") {
+    if (path.starts_with("/api/feedback") && !path.ends_with("/stats")) || path == "/feedback" {
         RateLimitType::Feedback
     } else if path.starts_with("/api/webhook") {
         RateLimitType::Webhook
@@ -309,9 +448,95 @@ mod tests {
             determine_limit_type("/api/health"),
             RateLimitType::Api
         ));
+        assert!(matches!(
+            determine_limit_type("/feedback"),
+            RateLimitType::Feedback
+        ));
         println!("✅ Rate limit type determination test passed!");
     }
 
+    fn limiter(limit: u32) -> IpRateLimiter {
+        IpRateLimiter::new(WindowQuota::per_hour(limit), WindowQuota::per_hour(limit))
+    }
+
+    #[test]
+    fn test_ip_rate_limiter_is_per_ip_and_scope() {
+        let limiter = limiter(2);
+        let first = IpAddr::V4(Ipv4Addr::new(203, 0, 113, 1));
+        let second = IpAddr::V4(Ipv4Addr::new(203, 0, 113, 2));
+
+        assert!(limiter.check(RateLimitScope::Feedback, first).is_ok());
+        assert!(limiter.check(RateLimitScope::Feedback, first).is_ok());
+        let retry_after = limiter.check(RateLimitScope::Feedback, first).unwrap_err();
+        assert!(retry_after > Duration::ZERO && retry_after <= Duration::from_secs(3600));
+        // 🌐 Someone else's allowance, and this IP's other scope, are untouched
+        assert!(limiter.check(RateLimitScope::Feedback, second).is_ok());
+        assert!(limiter.check(RateLimitScope::McpCheck, first).is_ok());
+        assert_eq!(limiter.tracked_keys(), 3);
+        println!("✅ Per-IP rate limiter test passed!");
+    }
+
+    #[test]
+    fn test_ip_rate_limiter_window_resets_and_sweeps() {
+        let limiter = limiter(1);
+        let start = Instant::now();
+        let ip = IpAddr::V4(Ipv4Addr::new(198, 51, 100, 7));
+        assert!(limiter
+            .check_at(RateLimitScope::Feedback, ip, start)
+            .is_ok());
+        assert!(limiter
+            .check_at(
+                RateLimitScope::Feedback,
+                ip,
+                start + Duration::from_secs(10)
+            )
+            .is_err());
+        // ⏰ A new window gives a fresh allowance
+        let later = start + Duration::from_secs(3600);
+        assert!(limiter
+            .check_at(RateLimitScope::Feedback, ip, later)
+            .is_ok());
+
+        // 🌊 A flood of distinct IPs is evicted once their windows run out
+        for n in 0..1000u32 {
+            let flood_ip = IpAddr::V4(Ipv4Addr::from(0x0A00_0000 + n));
+            assert!(limiter
+                .check_at(RateLimitScope::McpCheck, flood_ip, start)
+                .is_ok());
+        }
+        assert_eq!(limiter.tracked_keys(), 1001);
+        assert_eq!(limiter.sweep_at(start + Duration::from_secs(60)), 0);
+        assert_eq!(limiter.sweep_at(later), 1000);
+        assert_eq!(limiter.tracked_keys(), 1);
+        assert_eq!(limiter.evicted_total(), 1000);
+        println!("✅ Rate limit window sweep test passed!");
+    }
+
+    #[test]
+    fn test_ip_rate_limiter_is_safe_under_concurrency() {
+        let limiter = Arc::new(limiter(100));
+        let ip = IpAddr::V4(Ipv4Addr::new(192, 0, 2, 1));
+        let allowed = Arc::new(AtomicU64::new(0));
+        let threads: Vec<_> = (0..8)
+            .map(|_| {
+                let (limiter, allowed) = (limiter.clone(), allowed.clone());
+                std::thread::spawn(move || {
+                    for _ in 0..50 {
+                        if limiter.check(RateLimitScope::Feedback, ip).is_ok() {
+                            allowed.fetch_add(1, Ordering::Relaxed);
+                        }
+                    }
+                })
+            })
+            .collect();
+        for thread in threads {
+            thread.join().unwrap();
+        }
+        // 🔒 400 attempts, exactly the quota got through
+        assert_eq!(allowed.load(Ordering::Relaxed), 100);
+        println!("✅ Concurrent rate limiter test passed!");
+    }
+
     #[test]
     fn test_extract_client_ip() {
         let mut headers = HeaderMap::new();
@@ -319,20 +544,25 @@ mod tests {
             "X-Forwarded-For",
             "192.168.1.100, 10.0.0.1".parse().unwrap(),
         );
-
-        // Create a mock request (in real implementation, you'd need to create a proper request)
-        // For this test, we'll focus on the header parsing logic
-
-        // Test that we can parse the first IP from X-Forwarded-For
-        if let Some(forwarded_for) = headers.get("X-Forwarded-For") {
-            if let Ok(header_str) = forwarded_for.to_str() {
-                if let Some(ip_str) = header_str.split(',').next() {
-                    if let Ok(ip) = IpAddr::from_str(ip_str.trim()) {
-                        assert_eq!(ip, IpAddr::V4(Ipv4Addr::new(192, 168, 1, 100)));
-                    }
-                }
-            }
-        }
+        let request_from = |peer: [u8; 4]| {
+            let mut request = Request::new(axum::body::Body::empty());
+            request
+                .extensions_mut()
+                .insert(ConnectInfo(SocketAddr::from((peer, 4000))));
+            request
+        };
+        let trusted: Vec<ProxyRange> = vec!["10.0.0.0/8".parse().unwrap()];
+
+        // 🛡️ Behind our proxy, the last hop it didn't append is the client
+        assert_eq!(
+            extract_client_ip(&headers, &request_from([10, 0, 0, 2]), &trusted),
+            IpAddr::V4(Ipv4Addr::new(192, 168, 1, 100))
+        );
+        // 🙅 Straight from the internet, the header is whatever the client made up
+        assert_eq!(
+            extract_client_ip(&headers, &request_from([203, 0, 113, 9]), &trusted),
+            IpAddr::V4(Ipv4Addr::new(203, 0, 113, 9))
+        );
 
         println!("✅ Client IP extraction test passed!");
     }
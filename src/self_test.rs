@@ -0,0 +1,254 @@
+// 🧪 Startup Self-Test - A dress rehearsal before the doors open! 🧪
+// Runs a synthetic feedback item through every pipeline stage in dry-run mode,
+// so broken credentials and connectivity show up at boot instead of at 3am.
+// Created with love by Aye & Hue! ✨
+
+use std::time::Instant;
+use tracing::{error, info, warn};
+
+use crate::api::{feedback::SubmitFeedbackRequest, AppState, ValidateRequest};
+
+/// 📝 Prompt used for the LLM stage - cheap, deterministic, and easy to check
+const SELF_TEST_PROMPT: &str = "This is a connectivity check. Reply with the single word OK.";
+
+/// 🚦 Outcome of a single self-test stage
+#[derive(Debug, Clone, PartialEq)]
+pub enum StageStatus {
+    /// ✅ Stage worked
+    Passed,
+    /// ❌ Stage failed
+    Failed,
+    /// ⏭️ Stage not configured, nothing to check
+    Skipped,
+}
+
+/// 📋 Result of one pipeline stage
+#[derive(Debug, Clone)]
+pub struct StageResult {
+    /// 🏷️ Stage name
+    pub name: &'static str,
+    /// 🚦 Outcome
+    pub status: StageStatus,
+    /// 📝 Human readable detail
+    pub detail: String,
+    /// ⏱️ How long the stage took
+    pub duration_ms: u128,
+}
+
+/// 📊 Full self-test report
+#[derive(Debug, Clone, Default)]
+pub struct SelfTestReport {
+    pub stages: Vec<StageResult>,
+}
+
+impl SelfTestReport {
+    /// ✅ True when no stage failed (skipped stages don't count against us)
+    pub fn passed(&self) -> bool {
+        self.stages
+            .iter()
+            .all(|stage| stage.status != StageStatus::Failed)
+    }
+
+    /// ❌ Names of the failed stages
+    pub fn failed_stages(&self) -> Vec<&'static str> {
+        self.stages
+            .iter()
+            .filter(|stage| stage.status == StageStatus::Failed)
+            .map(|stage| stage.name)
+            .collect()
+    }
+
+    /// 📢 Log one line per stage
+    pub fn log(&self) {
+        for stage in &self.stages {
+            match stage.status {
+                StageStatus::Passed => info!(
+                    "🧪 ✅ {} passed in {}ms: {}",
+                    stage.name, stage.duration_ms, stage.detail
+                ),
+                StageStatus::Skipped => {
+                    warn!("🧪 ⏭️ {} skipped: {}", stage.name, stage.detail)
+                }
+                StageStatus::Failed => error!(
+                    "🧪 ❌ {} failed after {}ms: {}",
+                    stage.name, stage.duration_ms, stage.detail
+                ),
+            }
+        }
+    }
+
+    fn record(&mut self, name: &'static str, started: Instant, outcome: (StageStatus, String)) {
+        self.stages.push(StageResult {
+            name,
+            status: outcome.0,
+            detail: outcome.1,
+            duration_ms: started.elapsed().as_millis(),
+        });
+    }
+}
+
+/// 🧪 Run every stage of the pipeline in dry-run mode
+pub async fn run_self_test(app_state: &AppState) -> SelfTestReport {
+    info!("🧪 Running startup self-test (dry-run)...");
+    let mut report = SelfTestReport::default();
+
+    let started = Instant::now();
+    report.record("feedback_validation", started, check_synthetic_feedback());
+
+    let started = Instant::now();
+    report.record("database", started, check_database(app_state).await);
+
+    let started = Instant::now();
+    report.record("llm", started, check_llm(app_state).await);
+
+    let started = Instant::now();
+    report.record("github", started, check_github(app_state).await);
+
+    report.log();
+    report
+}
+
+/// 📝 The synthetic feedback item must pass our own intake validation
+fn check_synthetic_feedback() -> (StageStatus, String) {
+    let synthetic = SubmitFeedbackRequest {
+        repository: "feedbacker/self-test".to_string(),
+        content: "Synthetic self-test feedback: please ignore, nothing to change here.".to_string(),
+        llm_provider: None,
+        metadata: Some(serde_json::json!({ "source": "self_test" })),
+        user_info: None,
+        callback_url: None,
+        impact_score: Some(1),
+        frequency_score: Some(1),
+        priority: None,
+        tags: None,
+        source: Some("self_test".to_string()),
+        challenge: None,
+    };
+
+    match synthetic.validate() {
+        Ok(()) => (
+            StageStatus::Passed,
+            "synthetic feedback accepted".to_string(),
+        ),
+        Err(errors) => (StageStatus::Failed, errors.join("; ")),
+    }
+}
+
+/// 🗄️ The database must answer a trivial query
+async fn check_database(app_state: &AppState) -> (StageStatus, String) {
+    match sqlx::query_scalar::<_, i32>("SELECT 1")
+        .fetch_one(&app_state.db_pool)
+        .await
+    {
+        Ok(_) => (StageStatus::Passed, "SELECT 1 succeeded".to_string()),
+        Err(e) => (StageStatus::Failed, e.to_string()),
+    }
+}
+
+/// 🤖 The default LLM provider must answer a trivial prompt
+async fn check_llm(app_state: &AppState) -> (StageStatus, String) {
+    let client = app_state.llm.as_ref();
+    let provider = client.default_provider();
+
+    if !client.is_configured(&provider) {
+        return (
+            StageStatus::Failed,
+            format!("default provider {:?} has no credentials", provider),
+        );
+    }
+
+    match client.complete(&provider, None, SELF_TEST_PROMPT).await {
+        Ok(completion) if !completion.text.trim().is_empty() => (
+            StageStatus::Passed,
+            format!("{:?}/{} answered", provider, completion.model),
+        ),
+        Ok(_) => (
+            StageStatus::Failed,
+            format!("{:?} returned an empty answer", provider),
+        ),
+        Err(e) => (StageStatus::Failed, format!("{:#}", e)),
+    }
+}
+
+/// 🐙 GitHub must let us see (and push to) the sandbox repository.
+/// This is a dry run: we only log the branch we *would* create.
+async fn check_github(app_state: &AppState) -> (StageStatus, String) {
+    let Some(sandbox) = app_state.config.self_test.sandbox_repository.as_deref() else {
+        return (
+            StageStatus::Skipped,
+            "SELF_TEST_SANDBOX_REPO not set".to_string(),
+        );
+    };
+
+    let Some((owner, repo)) = sandbox.split_once('/') else {
+        return (
+            StageStatus::Failed,
+            format!(
+                "sandbox repository '{}' is not in owner/repo format",
+                sandbox
+            ),
+        );
+    };
+
+    match app_state.github.repository_access(owner, repo).await {
+        Ok(access) => {
+            if !access.can_push {
+                return (
+                    StageStatus::Failed,
+                    format!("token cannot push to {}", sandbox),
+                );
+            }
+
+            let base = access.default_branch;
+            let branch = format!(
+                "{}self-test-{}",
+                app_state.config.github.default_branch_prefix,
+                chrono::Utc::now().timestamp()
+            );
+            info!(
+                "🧪 Dry run: would create branch {} from {} in {}",
+                branch, base, sandbox
+            );
+            (
+                StageStatus::Passed,
+                format!("would create {} from {} in {}", branch, base, sandbox),
+            )
+        }
+        Err(e) => (StageStatus::Failed, format!("{:#}", e)),
+    }
+}
+
+// 🧪 Tests - Testing the tester!
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stage(name: &'static str, status: StageStatus) -> StageResult {
+        StageResult {
+            name,
+            status,
+            detail: String::new(),
+            duration_ms: 0,
+        }
+    }
+
+    #[test]
+    fn test_report_pass_fail() {
+        let mut report = SelfTestReport::default();
+        report.stages.push(stage("database", StageStatus::Passed));
+        report.stages.push(stage("github", StageStatus::Skipped));
+        assert!(report.passed());
+
+        report.stages.push(stage("llm", StageStatus::Failed));
+        assert!(!report.passed());
+        assert_eq!(report.failed_stages(), vec!["llm"]);
+        println!("✅ Self-test report test passed!");
+    }
+
+    #[test]
+    fn test_synthetic_feedback_is_valid() {
+        let (status, detail) = check_synthetic_feedback();
+        assert_eq!(status, StageStatus::Passed, "{}", detail);
+        println!("✅ Synthetic feedback validation test passed!");
+    }
+}
@@ -0,0 +1,108 @@
+// 📁 Local Store - Attachments as plain files! 📁
+// Each key becomes a file under ATTACHMENTS_DIR (`<feedback id>/<attachment id>`).
+// Fine for a single instance; several instances need a shared volume or the s3 backend.
+// Created with love by Aye & Hue! ✨
+
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use std::path::PathBuf;
+
+use super::{is_valid_key, BlobStore};
+
+/// 📁 Blobs stored under one directory
+#[derive(Debug, Clone)]
+pub struct LocalStore {
+    root: PathBuf,
+}
+
+impl LocalStore {
+    /// ➕ Store under `root` (created on the first write)
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    /// 📍 Where a key lives on disk
+    fn path(&self, key: &str) -> Result<PathBuf> {
+        if !is_valid_key(key) {
+            anyhow::bail!("Invalid blob key: {:?}", key);
+        }
+        Ok(self.root.join(key))
+    }
+}
+
+#[async_trait]
+impl BlobStore for LocalStore {
+    async fn put(&self, key: &str, _content_type: &str, bytes: Vec<u8>) -> Result<()> {
+        let path = self.path(key)?;
+        if let Some(parent) = path.parent() {
+            tokio::fs::create_dir_all(parent)
+                .await
+                .with_context(|| format!("Failed to create {}", parent.display()))?;
+        }
+        // ✍️ Write next to it and rename, so a reader never sees half a file
+        let partial = path.with_extension("partial");
+        tokio::fs::write(&partial, bytes)
+            .await
+            .with_context(|| format!("Failed to write {}", partial.display()))?;
+        tokio::fs::rename(&partial, &path)
+            .await
+            .with_context(|| format!("Failed to move {} into place", path.display()))
+    }
+
+    async fn get(&self, key: &str) -> Result<Option<Vec<u8>>> {
+        let path = self.path(key)?;
+        match tokio::fs::read(&path).await {
+            Ok(bytes) => Ok(Some(bytes)),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e).with_context(|| format!("Failed to read {}", path.display())),
+        }
+    }
+
+    async fn delete(&self, key: &str) -> Result<()> {
+        let path = self.path(key)?;
+        match tokio::fs::remove_file(&path).await {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => {
+                Err(e).with_context(|| format!("Failed to delete {}", path.display()))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    fn backend(&self) -> &'static str {
+        "local"
+    }
+}
+
+// 🧪 Tests - Round trips through the filesystem!
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_local_store_round_trip() {
+        let root = std::env::temp_dir().join(format!("feedbacker_blobs_{}", uuid::Uuid::new_v4()));
+        let store = LocalStore::new(&root);
+        let key = format!("{}/{}", uuid::Uuid::new_v4(), uuid::Uuid::new_v4());
+
+        assert_eq!(store.get(&key).await.unwrap(), None);
+        store
+            .put(&key, "text/plain", b"panic at line 42".to_vec())
+            .await
+            .unwrap();
+        assert_eq!(
+            store.get(&key).await.unwrap().as_deref(),
+            Some(&b"panic at line 42"[..])
+        );
+        store.delete(&key).await.unwrap();
+        store.delete(&key).await.unwrap();
+        assert_eq!(store.get(&key).await.unwrap(), None);
+
+        // 🚫 Nothing outside the root, whatever the key says
+        assert!(store
+            .put("../escape", "text/plain", Vec::new())
+            .await
+            .is_err());
+        std::fs::remove_dir_all(&root).unwrap();
+        println!("✅ Local blob store test passed!");
+    }
+}
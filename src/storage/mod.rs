@@ -0,0 +1,78 @@
+// 🗄️ Storage Module - Somewhere for the bytes that don't belong in a table! 🗄️
+// Feedback attachments (logs, screenshots) are written through `Arc<dyn BlobStore>`
+// on AppState, so the backend is a deployment choice (ATTACHMENTS_BACKEND): a local
+// directory or an S3-compatible bucket. Metadata stays in the `attachments` table.
+// Created with love by Aye & Hue! ✨
+
+use anyhow::Result;
+use async_trait::async_trait;
+use std::sync::Arc;
+
+use crate::config::{AttachmentBackend, AttachmentsConfig};
+
+pub mod local; // 📁 Blobs as files under a directory
+pub mod s3; // 🪣 Blobs in an S3-compatible bucket (aws-sigv4 signed reqwest calls)
+
+/// 🗄️ Where attachment blobs live. Keys are ours (`<feedback id>/<attachment id>`, or
+/// `<date>/<batch id>` for retention archives), never user input, and contain only
+/// `[0-9a-f-/]`.
+#[async_trait]
+pub trait BlobStore: Send + Sync {
+    /// 📥 Store `bytes` under `key`, replacing anything already there
+    async fn put(&self, key: &str, content_type: &str, bytes: Vec<u8>) -> Result<()>;
+
+    /// 📤 The bytes stored under `key` (None when there is nothing)
+    async fn get(&self, key: &str) -> Result<Option<Vec<u8>>>;
+
+    /// 🗑️ Remove `key` (removing something that isn't there is fine)
+    async fn delete(&self, key: &str) -> Result<()>;
+
+    /// 🏷️ Backend name for logs and the admin UI
+    fn backend(&self) -> &'static str;
+}
+
+/// 🧼 Only the key shapes we generate get near a path or a URL
+pub fn is_valid_key(key: &str) -> bool {
+    !key.is_empty()
+        && key.split('/').all(|segment| {
+            !segment.is_empty() && segment.chars().all(|c| c.is_ascii_hexdigit() || c == '-')
+        })
+}
+
+/// 🏗️ The store ATTACHMENTS_BACKEND asks for
+pub fn from_config(config: &AttachmentsConfig) -> Result<Arc<dyn BlobStore>> {
+    Ok(match config.backend {
+        AttachmentBackend::Local => Arc::new(local::LocalStore::new(&config.local_dir)),
+        AttachmentBackend::S3 => {
+            let s3 = config
+                .s3
+                .as_ref()
+                .ok_or_else(|| anyhow::anyhow!("ATTACHMENTS_BACKEND=s3 without bucket settings"))?;
+            Arc::new(s3::S3Store::new(s3.clone())?)
+        }
+    })
+}
+
+// 🧪 Tests - Keys we'd let near a filesystem!
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_only_generated_keys_are_valid() {
+        let id = uuid::Uuid::new_v4();
+        assert!(is_valid_key(&format!("{}/{}", id, uuid::Uuid::new_v4())));
+        for bad in [
+            "",
+            "/",
+            "a//b",
+            "../etc/passwd",
+            "a/./b",
+            "abc/x y",
+            "abc\\def",
+        ] {
+            assert!(!is_valid_key(bad), "{:?}", bad);
+        }
+        println!("✅ Blob key validation test passed!");
+    }
+}
@@ -0,0 +1,220 @@
+// 🧭 Client IP - Who is really on the other end? 🧭
+// X-Forwarded-For, X-Real-IP and CF-Connecting-IP are just headers anyone can send,
+// so they only count when the connection comes from a proxy we trust (TRUSTED_PROXIES).
+// X-Forwarded-For is read right to left: every hop a trusted proxy appended is
+// skipped, and the first address no trusted proxy vouches for is the client.
+// Created with love by Aye & Hue! ✨
+
+use axum::http::HeaderMap;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::net::IpAddr;
+use std::str::FromStr;
+
+/// 🛡️ A proxy address or network (`10.0.0.7`, `10.0.0.0/8`, `fd00::/8`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct ProxyRange {
+    network: IpAddr,
+    prefix: u8,
+}
+
+impl ProxyRange {
+    /// 🔍 Is `ip` inside this range? (IPv4-mapped IPv6 counts as IPv4)
+    pub fn contains(&self, ip: &IpAddr) -> bool {
+        match (self.network, canonical(*ip)) {
+            (IpAddr::V4(network), IpAddr::V4(ip)) => {
+                masked(u32::from(network).into(), self.prefix, 32)
+                    == masked(u32::from(ip).into(), self.prefix, 32)
+            }
+            (IpAddr::V6(network), IpAddr::V6(ip)) => {
+                masked(u128::from(network), self.prefix, 128)
+                    == masked(u128::from(ip), self.prefix, 128)
+            }
+            _ => false,
+        }
+    }
+}
+
+impl FromStr for ProxyRange {
+    type Err = anyhow::Error;
+
+    fn from_str(value: &str) -> anyhow::Result<Self> {
+        let value = value.trim();
+        let (address, prefix) = match value.split_once('/') {
+            Some((address, prefix)) => (address, Some(prefix)),
+            None => (value, None),
+        };
+        let network = canonical(
+            address
+                .parse::<IpAddr>()
+                .map_err(|_| anyhow::anyhow!("{} is not an IP address or CIDR range", value))?,
+        );
+        let bits = if network.is_ipv4() { 32 } else { 128 };
+        let prefix = match prefix {
+            Some(prefix) => prefix
+                .parse::<u8>()
+                .ok()
+                .filter(|prefix| *prefix <= bits)
+                .ok_or_else(|| anyhow::anyhow!("{} has an invalid prefix length", value))?,
+            None => bits,
+        };
+        Ok(Self { network, prefix })
+    }
+}
+
+impl TryFrom<String> for ProxyRange {
+    type Error = anyhow::Error;
+
+    fn try_from(value: String) -> anyhow::Result<Self> {
+        value.parse()
+    }
+}
+
+impl From<ProxyRange> for String {
+    fn from(range: ProxyRange) -> Self {
+        range.to_string()
+    }
+}
+
+impl fmt::Display for ProxyRange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}/{}", self.network, self.prefix)
+    }
+}
+
+/// 🔢 The top `prefix` bits of a `bits`-wide address
+fn masked(address: u128, prefix: u8, bits: u8) -> u128 {
+    match prefix {
+        0 => 0,
+        prefix => address >> (bits - prefix),
+    }
+}
+
+/// 🔄 IPv4-mapped IPv6 (`::ffff:10.0.0.1`) as the IPv4 address it is
+fn canonical(ip: IpAddr) -> IpAddr {
+    match ip {
+        IpAddr::V6(v6) => v6.to_ipv4_mapped().map(IpAddr::V4).unwrap_or(ip),
+        ip => ip,
+    }
+}
+
+/// 🧭 The client behind `peer` (the TCP connection's address). Forwarding headers
+/// are ignored unless `peer` is one of the `trusted` proxies.
+pub fn client_ip(
+    headers: &HeaderMap,
+    peer: Option<IpAddr>,
+    trusted: &[ProxyRange],
+) -> Option<IpAddr> {
+    let is_trusted = |ip: &IpAddr| trusted.iter().any(|range| range.contains(ip));
+    let peer = peer?;
+    if !is_trusted(&peer) {
+        return Some(peer);
+    }
+    let header = |name: &str| headers.get(name).and_then(|value| value.to_str().ok());
+
+    // 🔗 Each proxy appends who it heard from, so walk back until nobody trusted did
+    if let Some(forwarded) = header("x-forwarded-for") {
+        let mut client = peer;
+        for hop in forwarded.rsplit(',') {
+            let Ok(hop) = hop.trim().parse::<IpAddr>() else {
+                break;
+            };
+            client = hop;
+            if !is_trusted(&hop) {
+                break;
+            }
+        }
+        return Some(client);
+    }
+
+    ["x-real-ip", "cf-connecting-ip"]
+        .into_iter()
+        .find_map(|name| header(name)?.trim().parse::<IpAddr>().ok())
+        .or(Some(peer))
+}
+
+// 🧪 Tests - Nobody gets to pick their own IP!
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn headers(pairs: &[(&'static str, &str)]) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        for (name, value) in pairs {
+            headers.insert(*name, value.parse().unwrap());
+        }
+        headers
+    }
+
+    fn ip(value: &str) -> Option<IpAddr> {
+        Some(value.parse().unwrap())
+    }
+
+    #[test]
+    fn test_proxy_ranges_parse_and_match() {
+        let network: ProxyRange = "10.0.0.0/8".parse().unwrap();
+        assert!(network.contains(&"10.200.1.1".parse().unwrap()));
+        assert!(network.contains(&"::ffff:10.0.0.1".parse().unwrap()));
+        assert!(!network.contains(&"11.0.0.1".parse().unwrap()));
+
+        let single: ProxyRange = "fd00::7".parse().unwrap();
+        assert_eq!(single.to_string(), "fd00::7/128");
+        assert!(single.contains(&"fd00::7".parse().unwrap()));
+        assert!(!single.contains(&"fd00::8".parse().unwrap()));
+
+        let everything: ProxyRange = "0.0.0.0/0".parse().unwrap();
+        assert!(everything.contains(&"203.0.113.9".parse().unwrap()));
+
+        for bad in ["10.0.0.0/33", "fd00::/129", "proxy.internal", "10.0.0.0/x"] {
+            assert!(bad.parse::<ProxyRange>().is_err(), "{} accepted", bad);
+        }
+        println!("✅ Proxy range test passed!");
+    }
+
+    #[test]
+    fn test_forwarding_headers_only_count_from_trusted_proxies() {
+        let trusted = vec!["10.0.0.0/8".parse().unwrap()];
+        let spoofed = headers(&[
+            ("x-forwarded-for", "203.0.113.9"),
+            ("x-real-ip", "203.0.113.9"),
+        ]);
+
+        // 🙅 A client talking to us directly can't pick its address
+        assert_eq!(
+            client_ip(&spoofed, ip("198.51.100.4"), &trusted),
+            ip("198.51.100.4")
+        );
+        assert_eq!(client_ip(&spoofed, ip("10.0.0.1"), &[]), ip("10.0.0.1"));
+        assert_eq!(client_ip(&spoofed, None, &trusted), None);
+
+        // 🛡️ Through a trusted proxy it can
+        assert_eq!(
+            client_ip(&spoofed, ip("10.0.0.1"), &trusted),
+            ip("203.0.113.9")
+        );
+        let real_ip = headers(&[("x-real-ip", "203.0.113.9")]);
+        assert_eq!(
+            client_ip(&real_ip, ip("10.0.0.1"), &trusted),
+            ip("203.0.113.9")
+        );
+        assert_eq!(
+            client_ip(&HeaderMap::new(), ip("10.0.0.1"), &trusted),
+            ip("10.0.0.1")
+        );
+
+        // 🔗 Whatever the client prepended itself is ignored: the first hop a trusted
+        // proxy didn't vouch for is the client
+        let chain = headers(&[("x-forwarded-for", "1.2.3.4, 198.51.100.4, 10.0.0.2")]);
+        assert_eq!(
+            client_ip(&chain, ip("10.0.0.1"), &trusted),
+            ip("198.51.100.4")
+        );
+        let garbage = headers(&[("x-forwarded-for", "1.2.3.4, nonsense, 10.0.0.2")]);
+        assert_eq!(
+            client_ip(&garbage, ip("10.0.0.1"), &trusted),
+            ip("10.0.0.2")
+        );
+        println!("✅ Trusted proxy header test passed!");
+    }
+}
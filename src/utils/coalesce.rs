@@ -0,0 +1,157 @@
+// 🧵 Coalesce - One fetch in flight per key, however many callers ask! 🧵
+// When a burst of callers all miss the same cache at once (a CI fleet checking for
+// updates at the top of the hour, a few admins opening the dashboard), each of them
+// would run the same query for the same answer. `Coalesce::run` makes the first
+// caller for a key do the fetch while everyone arriving before it finishes awaits that
+// result. Callers arriving afterwards start a new flight, so a value is never older
+// than the fetch the caller waited on - caching it for longer is up to the caller.
+// If the caller doing the fetch is cancelled, one of the waiters takes over.
+// Created with love by Aye & Hue! ✨
+
+use std::collections::HashMap;
+use std::future::Future;
+use std::hash::Hash;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+
+use tokio::sync::OnceCell;
+
+/// 🧵 Single-flight guard: concurrent `run`s for the same key share one fetch
+#[derive(Debug)]
+pub struct Coalesce<K, V> {
+    flights: Mutex<HashMap<K, Arc<OnceCell<V>>>>,
+    /// 📊 Fetches actually run (as opposed to calls)
+    fetches: AtomicU64,
+}
+
+impl<K, V> Default for Coalesce<K, V> {
+    fn default() -> Self {
+        Self {
+            flights: Mutex::default(),
+            fetches: AtomicU64::new(0),
+        }
+    }
+}
+
+impl<K: Eq + Hash + Clone, V: Clone> Coalesce<K, V> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 🎯 The value for `key`: joins the fetch already in flight, or runs `fetch`.
+    /// Errors are values too - use a cloneable error (e.g. `Arc<anyhow::Error>`) so
+    /// every waiter sees the failure instead of retrying it.
+    pub async fn run<F, Fut>(&self, key: K, fetch: F) -> V
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = V>,
+    {
+        let flight = Arc::clone(self.flights.lock().unwrap().entry(key.clone()).or_default());
+        let value = flight
+            .get_or_init(|| {
+                self.fetches.fetch_add(1, Ordering::Relaxed);
+                fetch()
+            })
+            .await
+            .clone();
+
+        // 🛬 Landed: the next caller starts a fresh flight
+        let mut flights = self.flights.lock().unwrap();
+        if flights
+            .get(&key)
+            .is_some_and(|current| Arc::ptr_eq(current, &flight))
+        {
+            flights.remove(&key);
+        }
+        value
+    }
+
+    /// 📊 How many fetches have run since this guard was created
+    pub fn fetches(&self) -> u64 {
+        self.fetches.load(Ordering::Relaxed)
+    }
+
+    /// ✈️ Keys with a fetch in flight right now
+    pub fn in_flight(&self) -> usize {
+        self.flights.lock().unwrap().len()
+    }
+}
+
+// 🧪 Tests - Many askers, one answer!
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::AtomicUsize;
+    use std::time::Duration;
+
+    #[tokio::test]
+    async fn test_concurrent_callers_share_one_fetch_per_key() {
+        let coalesce = Arc::new(Coalesce::<&'static str, u32>::new());
+        let started = Arc::new(AtomicUsize::new(0));
+        let mut callers = Vec::new();
+        for i in 0..50 {
+            let coalesce = Arc::clone(&coalesce);
+            let started = Arc::clone(&started);
+            let key = if i % 2 == 0 { "version" } else { "notes" };
+            callers.push(tokio::spawn(async move {
+                coalesce
+                    .run(key, || async move {
+                        started.fetch_add(1, Ordering::SeqCst);
+                        tokio::time::sleep(Duration::from_millis(50)).await;
+                        key.len() as u32
+                    })
+                    .await
+            }));
+        }
+        for (i, caller) in callers.into_iter().enumerate() {
+            let expected = if i % 2 == 0 { 7 } else { 5 };
+            assert_eq!(caller.await.unwrap(), expected);
+        }
+        assert_eq!(started.load(Ordering::SeqCst), 2);
+        assert_eq!(coalesce.fetches(), 2);
+        assert_eq!(coalesce.in_flight(), 0);
+
+        // 🛬 Once a flight has landed, the next call fetches again
+        assert_eq!(coalesce.run("version", || async { 8 }).await, 8);
+        assert_eq!(coalesce.fetches(), 3);
+        println!("✅ Coalesced fetch test passed!");
+    }
+
+    #[tokio::test]
+    async fn test_a_cancelled_fetch_is_taken_over_by_a_waiter() {
+        let coalesce = Arc::new(Coalesce::<(), Result<u32, Arc<String>>>::new());
+        let leader = {
+            let coalesce = Arc::clone(&coalesce);
+            tokio::spawn(async move {
+                coalesce
+                    .run((), || async {
+                        tokio::time::sleep(Duration::from_secs(60)).await;
+                        Ok(1)
+                    })
+                    .await
+            })
+        };
+        while coalesce.fetches() == 0 {
+            tokio::task::yield_now().await;
+        }
+        let waiter = {
+            let coalesce = Arc::clone(&coalesce);
+            tokio::spawn(async move {
+                coalesce
+                    .run((), || async {
+                        Err(Arc::new("settings table is gone".into()))
+                    })
+                    .await
+            })
+        };
+        tokio::task::yield_now().await;
+        leader.abort();
+        assert_eq!(
+            waiter.await.unwrap(),
+            Err(Arc::new("settings table is gone".to_string()))
+        );
+        assert_eq!(coalesce.fetches(), 2);
+        assert_eq!(coalesce.in_flight(), 0);
+        println!("✅ Coalesce takeover test passed!");
+    }
+}
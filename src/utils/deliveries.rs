@@ -0,0 +1,271 @@
+// 🛡️ Deliveries - A signed webhook is only good once, and only while it's fresh! 🛡️
+// A valid HMAC proves who sent a delivery, not when: a captured delivery verifies
+// forever. So a verified delivery must also carry a timestamp within the allowed skew
+// of our clock, and its body must not have been seen recently. The timestamp only
+// counts when it is signed, so it comes from the body (our own `delivered_at`, or the
+// newest `updated_at` of the objects in a GitHub payload) - an undated body is refused.
+// Deliveries are known by a hash of that signed body (`delivery_key`), never by a
+// header the sender could change; recently seen ones live in a bounded LRU, checked
+// before any database work. The same core verifies GitHub's deliveries to us and is
+// what receivers of our callbacks use (`jobs::callbacks::verify_callback`).
+// Created with love by Aye & Hue! ✨
+
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Mutex;
+
+use chrono::{DateTime, Duration, Utc};
+use sha2::{Digest, Sha256};
+
+use super::signatures::{SecretMatch, SecretPair};
+use crate::api::ErrorCode;
+
+/// 🚫 Why a delivery was turned away
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DeliveryRejection {
+    /// 🔏 Signature missing or made with a secret we don't (or no longer) accept
+    BadSignature,
+    /// ⏱️ Nothing in the delivery says when it was sent
+    MissingTimestamp,
+    /// 🕰️ Sent further from now than the allowed skew
+    Expired,
+    /// 🔁 We already accepted this exact delivery
+    Replayed,
+}
+
+impl DeliveryRejection {
+    /// 🏷️ Error code reported to the sender
+    pub fn code(self) -> ErrorCode {
+        match self {
+            DeliveryRejection::BadSignature => ErrorCode::InvalidSignature,
+            DeliveryRejection::MissingTimestamp => ErrorCode::DeliveryTimestampMissing,
+            DeliveryRejection::Expired => ErrorCode::DeliveryExpired,
+            DeliveryRejection::Replayed => ErrorCode::DeliveryReplayed,
+        }
+    }
+}
+
+/// ⏱️ Is `sent_at` within `max_skew` of `now` (either direction, boundary included)?
+pub fn check_timestamp(
+    sent_at: Option<DateTime<Utc>>,
+    now: DateTime<Utc>,
+    max_skew: Duration,
+) -> Result<(), DeliveryRejection> {
+    let sent_at = sent_at.ok_or(DeliveryRejection::MissingTimestamp)?;
+    if (now - sent_at).abs() > max_skew {
+        return Err(DeliveryRejection::Expired);
+    }
+    Ok(())
+}
+
+/// 🔍 When a delivery was sent: the signed body's `delivered_at`, else the newest
+/// `updated_at` among its top-level objects (how GitHub dates an event)
+pub fn delivery_timestamp(body: &[u8]) -> Option<DateTime<Utc>> {
+    let parse = |value: &serde_json::Value| {
+        value
+            .as_str()
+            .and_then(|text| DateTime::parse_from_rfc3339(text).ok())
+            .map(|at| at.with_timezone(&Utc))
+    };
+    let payload = serde_json::from_slice::<serde_json::Value>(body).ok()?;
+    let object = payload.as_object()?;
+    object.get("delivered_at").and_then(parse).or_else(|| {
+        object
+            .values()
+            .filter_map(|value| value.get("updated_at").and_then(parse))
+            .max()
+    })
+}
+
+/// 🔑 What a delivery is remembered by: a hash of its signed body. The same bytes are
+/// the same delivery whichever secret signed them or whatever id header came along.
+pub fn delivery_key(body: &[u8]) -> String {
+    format!("sha256:{}", hex::encode(Sha256::digest(body)))
+}
+
+/// 🧠 Bounded LRU of recently accepted delivery keys (least recently seen goes first)
+#[derive(Debug)]
+pub struct ReplayCache {
+    capacity: usize,
+    inner: Mutex<LruSet>,
+}
+
+#[derive(Debug, Default)]
+struct LruSet {
+    tick: u64,
+    /// 🔑 key -> tick it was last seen at
+    entries: HashMap<String, u64>,
+    /// 🕰️ tick -> key, oldest first
+    order: BTreeMap<u64, String>,
+}
+
+impl ReplayCache {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity: capacity.max(1),
+            inner: Mutex::default(),
+        }
+    }
+
+    /// ✅ Remember `key`; false when it was already there (a replay, which also counts
+    /// as a fresh sighting so a replayed delivery stays remembered)
+    pub fn first_sighting(&self, key: &str) -> bool {
+        let mut lru = self.inner.lock().unwrap();
+        lru.tick += 1;
+        let tick = lru.tick;
+        if let Some(seen_at) = lru.entries.insert(key.to_string(), tick) {
+            lru.order.remove(&seen_at);
+            lru.order.insert(tick, key.to_string());
+            return false;
+        }
+        lru.order.insert(tick, key.to_string());
+        while lru.entries.len() > self.capacity {
+            let Some((_, oldest)) = lru.order.pop_first() else {
+                break;
+            };
+            lru.entries.remove(&oldest);
+        }
+        true
+    }
+
+    /// 🧹 Forget `key` again (a delivery whose processing failed may be redelivered)
+    pub fn forget(&self, key: &str) {
+        let mut lru = self.inner.lock().unwrap();
+        if let Some(seen_at) = lru.entries.remove(key) {
+            lru.order.remove(&seen_at);
+        }
+    }
+
+    /// 📏 Deliveries currently remembered
+    pub fn len(&self) -> usize {
+        self.inner.lock().unwrap().entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// 🛡️ Verify a delivery: signature, then timestamp (unless `max_skew` is None), then
+/// replay. Only deliveries that pass the first two are remembered.
+pub fn verify_delivery(
+    secrets: &SecretPair,
+    body: &[u8],
+    signature: &str,
+    sent_at: Option<DateTime<Utc>>,
+    now: DateTime<Utc>,
+    max_skew: Option<Duration>,
+    seen: &ReplayCache,
+) -> Result<SecretMatch, DeliveryRejection> {
+    let matched = secrets
+        .verify(body, signature, now)
+        .ok_or(DeliveryRejection::BadSignature)?;
+    if let Some(max_skew) = max_skew {
+        check_timestamp(sent_at, now, max_skew)?;
+    }
+    if !seen.first_sighting(&delivery_key(body)) {
+        return Err(DeliveryRejection::Replayed);
+    }
+    Ok(matched)
+}
+
+// 🧪 Tests - Fresh, once, and signed!
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::signatures::sign;
+
+    #[test]
+    fn test_skew_window_boundaries() {
+        let now = Utc::now();
+        let skew = Duration::seconds(300);
+        assert_eq!(check_timestamp(Some(now), now, skew), Ok(()));
+        assert_eq!(check_timestamp(Some(now - skew), now, skew), Ok(()));
+        assert_eq!(check_timestamp(Some(now + skew), now, skew), Ok(()));
+        assert_eq!(
+            check_timestamp(Some(now - skew - Duration::seconds(1)), now, skew),
+            Err(DeliveryRejection::Expired)
+        );
+        assert_eq!(
+            check_timestamp(Some(now + skew + Duration::seconds(1)), now, skew),
+            Err(DeliveryRejection::Expired)
+        );
+        assert_eq!(
+            check_timestamp(None, now, skew),
+            Err(DeliveryRejection::MissingTimestamp)
+        );
+        println!("✅ Delivery skew window test passed!");
+    }
+
+    #[test]
+    fn test_replay_cache_evicts_least_recently_seen() {
+        let cache = ReplayCache::new(2);
+        assert!(cache.first_sighting("a"));
+        assert!(cache.first_sighting("b"));
+        // 🔁 Seeing "a" again refreshes it, so "b" is now the oldest
+        assert!(!cache.first_sighting("a"));
+        assert!(cache.first_sighting("c"));
+        assert_eq!(cache.len(), 2);
+        assert!(!cache.first_sighting("a"));
+        assert!(!cache.first_sighting("c"));
+        assert!(cache.first_sighting("b"));
+        assert_eq!(cache.len(), 2);
+        cache.forget("b");
+        assert!(cache.first_sighting("b"));
+        println!("✅ Replay cache eviction test passed!");
+    }
+
+    #[test]
+    fn test_timestamps_come_only_from_the_signed_body() {
+        let body = br#"{"action":"opened","issue":{"updated_at":"2026-10-15T10:00:00Z"},"comment":{"updated_at":"2026-10-15T10:05:00Z"}}"#;
+        assert_eq!(
+            delivery_timestamp(body),
+            Some("2026-10-15T10:05:00Z".parse().unwrap())
+        );
+        let ours = br#"{"delivered_at":"2026-10-15T09:00:00Z","issue":{"updated_at":"2026-10-15T10:00:00Z"}}"#;
+        assert_eq!(
+            delivery_timestamp(ours),
+            Some("2026-10-15T09:00:00Z".parse().unwrap())
+        );
+        assert_eq!(delivery_timestamp(b"{}"), None);
+        assert_eq!(delivery_timestamp(b"not json"), None);
+        println!("✅ Delivery timestamp test passed!");
+    }
+
+    #[test]
+    fn test_verified_deliveries_are_accepted_once() {
+        let now = Utc::now();
+        let secrets = SecretPair {
+            primary: "hook-secret".to_string(),
+            previous: None,
+            previous_expires_at: None,
+        };
+        let seen = ReplayCache::new(16);
+        let body = br#"{"action":"opened"}"#;
+        let signature = sign("hook-secret", body);
+        let skew = Some(Duration::seconds(60));
+        let verify = |signature: &str, sent_at| {
+            verify_delivery(&secrets, body, signature, sent_at, now, skew, &seen)
+        };
+
+        assert_eq!(
+            verify("sha256=00", Some(now)),
+            Err(DeliveryRejection::BadSignature)
+        );
+        assert_eq!(
+            verify(&signature, Some(now - Duration::hours(1))),
+            Err(DeliveryRejection::Expired)
+        );
+        // 🧠 Rejections aren't remembered, so the fresh delivery still gets in once
+        assert!(seen.is_empty());
+        assert_eq!(verify(&signature, Some(now)), Ok(SecretMatch::Primary));
+        assert_eq!(
+            verify(&signature, Some(now)),
+            Err(DeliveryRejection::Replayed)
+        );
+        assert_eq!(
+            DeliveryRejection::Replayed.code(),
+            ErrorCode::DeliveryReplayed
+        );
+        println!("✅ Delivery verification test passed!");
+    }
+}
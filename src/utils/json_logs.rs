@@ -0,0 +1,260 @@
+// 🧾 JSON Logs - One object per line, the way log aggregators like them! 🧾
+// LOG_FORMAT=json swaps the human-readable fmt output for this layer. Every event
+// becomes a single line: timestamp, level, target, message and the event's fields,
+// flattened to the top level together with the fields of the spans it happened in
+// (so `request_id` and `route` from the request span are queryable directly).
+// Any field longer than MAX_FIELD_BYTES is cut short with a "…[truncated N bytes]"
+// marker, so one payload dump can't produce a multi-megabyte line.
+// Created with love by Aye & Hue! ✨
+
+use serde_json::{Map, Value};
+use std::fmt;
+use std::io::Write;
+use tracing::field::{Field, Visit};
+use tracing::span::{Attributes, Id, Record};
+use tracing::{Event, Subscriber};
+use tracing_subscriber::fmt::MakeWriter;
+use tracing_subscriber::layer::{Context, Layer};
+use tracing_subscriber::registry::LookupSpan;
+
+/// 📏 Longest field value written as-is
+pub const MAX_FIELD_BYTES: usize = 2048;
+/// ✂️ Start of the marker appended to truncated values
+pub const TRUNCATION_MARKER: &str = "…[truncated";
+
+/// ✂️ `value` cut to at most `max` bytes (on a char boundary), marked if anything was cut
+pub fn truncate_field(value: String, max: usize) -> String {
+    if value.len() <= max {
+        return value;
+    }
+    let mut end = max;
+    while !value.is_char_boundary(end) {
+        end -= 1;
+    }
+    format!(
+        "{}{} {} bytes]",
+        &value[..end],
+        TRUNCATION_MARKER,
+        value.len() - end
+    )
+}
+
+/// 📝 Collects fields into a JSON map, truncating as it goes
+struct JsonVisitor<'a> {
+    fields: &'a mut Map<String, Value>,
+    max_field_bytes: usize,
+}
+
+impl JsonVisitor<'_> {
+    fn insert_string(&mut self, field: &Field, value: String) {
+        self.fields.insert(
+            field.name().to_string(),
+            Value::String(truncate_field(value, self.max_field_bytes)),
+        );
+    }
+}
+
+impl Visit for JsonVisitor<'_> {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.insert_string(field, value.to_string());
+    }
+
+    fn record_i64(&mut self, field: &Field, value: i64) {
+        self.fields.insert(field.name().to_string(), value.into());
+    }
+
+    fn record_u64(&mut self, field: &Field, value: u64) {
+        self.fields.insert(field.name().to_string(), value.into());
+    }
+
+    fn record_bool(&mut self, field: &Field, value: bool) {
+        self.fields.insert(field.name().to_string(), value.into());
+    }
+
+    fn record_f64(&mut self, field: &Field, value: f64) {
+        self.fields.insert(field.name().to_string(), value.into());
+    }
+
+    fn record_error(&mut self, field: &Field, value: &(dyn std::error::Error + 'static)) {
+        self.insert_string(field, value.to_string());
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        self.insert_string(field, format!("{:?}", value));
+    }
+}
+
+/// 🗂️ A span's recorded fields, kept in its extensions
+struct SpanFields(Map<String, Value>);
+
+/// 🧾 Writes every event as one line of JSON
+pub struct JsonLogLayer<W> {
+    make_writer: W,
+    max_field_bytes: usize,
+}
+
+impl<W> JsonLogLayer<W>
+where
+    W: for<'w> MakeWriter<'w> + 'static,
+{
+    /// ➕ Write lines to `make_writer` (std::io::stdout in production)
+    pub fn new(make_writer: W) -> Self {
+        Self {
+            make_writer,
+            max_field_bytes: MAX_FIELD_BYTES,
+        }
+    }
+}
+
+impl<S, W> Layer<S> for JsonLogLayer<W>
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+    W: for<'w> MakeWriter<'w> + 'static,
+{
+    fn on_new_span(&self, attrs: &Attributes<'_>, id: &Id, ctx: Context<'_, S>) {
+        let Some(span) = ctx.span(id) else {
+            return;
+        };
+        let mut fields = Map::new();
+        attrs.record(&mut JsonVisitor {
+            fields: &mut fields,
+            max_field_bytes: self.max_field_bytes,
+        });
+        span.extensions_mut().insert(SpanFields(fields));
+    }
+
+    fn on_record(&self, id: &Id, values: &Record<'_>, ctx: Context<'_, S>) {
+        let Some(span) = ctx.span(id) else {
+            return;
+        };
+        let mut extensions = span.extensions_mut();
+        if let Some(SpanFields(fields)) = extensions.get_mut::<SpanFields>() {
+            values.record(&mut JsonVisitor {
+                fields,
+                max_field_bytes: self.max_field_bytes,
+            });
+        }
+    }
+
+    fn on_event(&self, event: &Event<'_>, ctx: Context<'_, S>) {
+        let metadata = event.metadata();
+        let mut fields = Map::new();
+        fields.insert(
+            "timestamp".to_string(),
+            chrono::Utc::now()
+                .to_rfc3339_opts(chrono::SecondsFormat::Millis, true)
+                .into(),
+        );
+        fields.insert("level".to_string(), metadata.level().as_str().into());
+        fields.insert("target".to_string(), metadata.target().into());
+
+        // 🗂️ Span fields first (outermost to innermost), so the event's own fields win
+        if let Some(scope) = ctx.event_scope(event) {
+            for span in scope.from_root() {
+                if let Some(SpanFields(span_fields)) = span.extensions().get::<SpanFields>() {
+                    fields.extend(span_fields.clone());
+                }
+            }
+        }
+        event.record(&mut JsonVisitor {
+            fields: &mut fields,
+            max_field_bytes: self.max_field_bytes,
+        });
+
+        let mut line = Value::Object(fields).to_string();
+        line.push('\n');
+        // 🤐 Nowhere to report a failed log write
+        let _ = self.make_writer.make_writer().write_all(line.as_bytes());
+    }
+}
+
+// 🧪 Tests - Reading our own logs back!
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::{Arc, Mutex};
+    use tracing_subscriber::layer::SubscriberExt;
+
+    /// 📼 Collects everything written to it
+    #[derive(Clone, Default)]
+    struct CapturedOutput(Arc<Mutex<Vec<u8>>>);
+
+    impl Write for CapturedOutput {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.lock().unwrap().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    impl<'w> MakeWriter<'w> for CapturedOutput {
+        type Writer = CapturedOutput;
+
+        fn make_writer(&'w self) -> Self::Writer {
+            self.clone()
+        }
+    }
+
+    #[test]
+    fn test_truncation_keeps_char_boundaries() {
+        assert_eq!(truncate_field("short".to_string(), 10), "short");
+        assert_eq!(
+            truncate_field("abcdef".to_string(), 4),
+            "abcd…[truncated 2 bytes]"
+        );
+        // 🌳 '🌳' is 4 bytes: cutting at 2 backs off to 0
+        assert_eq!(
+            truncate_field("🌳🌳".to_string(), 2),
+            "…[truncated 8 bytes]"
+        );
+        println!("✅ Field truncation test passed!");
+    }
+
+    #[test]
+    fn test_events_are_json_lines_with_span_fields_and_truncation() {
+        let output = CapturedOutput::default();
+        let subscriber = tracing_subscriber::registry().with(JsonLogLayer::new(output.clone()));
+
+        tracing::subscriber::with_default(subscriber, || {
+            let span = tracing::info_span!(
+                "request",
+                request_id = "req-42",
+                route = "/mcp/check",
+                status = tracing::field::Empty
+            );
+            let _entered = span.enter();
+            span.record("status", 200);
+            tracing::info!(attempt = 3, "📊 Check served");
+            tracing::warn!(payload = %"x".repeat(5000), "Big one");
+        });
+
+        let text = String::from_utf8(output.0.lock().unwrap().clone()).unwrap();
+        let lines: Vec<Value> = text
+            .lines()
+            .map(|line| serde_json::from_str(line).expect("every line is one JSON object"))
+            .collect();
+        assert_eq!(lines.len(), 2);
+
+        let first = &lines[0];
+        assert_eq!(first["level"], "INFO");
+        assert_eq!(first["message"], "📊 Check served");
+        assert_eq!(first["attempt"], 3);
+        assert_eq!(first["request_id"], "req-42");
+        assert_eq!(first["route"], "/mcp/check");
+        assert_eq!(first["status"], 200);
+        assert!(first["timestamp"].as_str().unwrap().ends_with('Z'));
+
+        let payload = lines[1]["payload"].as_str().unwrap();
+        assert!(payload.starts_with(&"x".repeat(MAX_FIELD_BYTES)));
+        assert!(payload.ends_with(&format!(
+            "{} {} bytes]",
+            TRUNCATION_MARKER,
+            5000 - MAX_FIELD_BYTES
+        )));
+        assert_eq!(lines[1]["request_id"], "req-42");
+        println!("✅ JSON log line test passed!");
+    }
+}
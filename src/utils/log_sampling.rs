@@ -0,0 +1,130 @@
+// 🎲 Log Sampling - Hearing every request without drowning in them! 🎲
+// Handlers that run on every client poll (/mcp/check, health probes) ask a
+// sampler before logging their routine line: the first request and then 1 in N
+// get through, each one saying how many were skipped since. A change of state
+// (say, healthy → unhealthy) always gets through. Errors never go through a
+// sampler at all. Debug builds log everything.
+// Created with love by Aye & Hue! ✨
+
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use crate::config::LoggingConfig;
+
+/// 🤷 No state seen yet
+const NO_STATE: u64 = u64::MAX;
+
+/// 🎲 Lets 1 in N routine log lines through
+#[derive(Debug)]
+pub struct LogSampler {
+    every: u64,
+    seen: AtomicU64,
+    skipped: AtomicU64,
+    last_state: AtomicU64,
+}
+
+impl LogSampler {
+    /// ➕ Log 1 in `every` (0 and 1 both log everything)
+    pub fn new(every: u64) -> Self {
+        Self {
+            every: every.max(1),
+            seen: AtomicU64::new(0),
+            skipped: AtomicU64::new(0),
+            last_state: AtomicU64::new(NO_STATE),
+        }
+    }
+
+    /// ⚙️ LOG_SAMPLE_EVERY in release builds, everything in debug builds
+    pub fn from_config(config: &LoggingConfig) -> Self {
+        Self::new(if cfg!(debug_assertions) {
+            1
+        } else {
+            config.sample_every
+        })
+    }
+
+    /// 🎲 Some(lines skipped since the last one) when this line should be logged
+    pub fn sample(&self) -> Option<u64> {
+        if self
+            .seen
+            .fetch_add(1, Ordering::Relaxed)
+            .is_multiple_of(self.every)
+        {
+            Some(self.skipped.swap(0, Ordering::Relaxed))
+        } else {
+            self.skipped.fetch_add(1, Ordering::Relaxed);
+            None
+        }
+    }
+
+    /// 🔄 Like `sample`, but a `state` different from the last one is always logged
+    pub fn sample_state(&self, state: u64) -> Option<u64> {
+        let previous = self.last_state.swap(state, Ordering::Relaxed);
+        if previous != NO_STATE && previous != state {
+            return Some(self.skipped.swap(0, Ordering::Relaxed));
+        }
+        self.sample()
+    }
+
+    /// 📝 Suffix for a sampled line, e.g. " (1 in 100, 99 skipped)" - empty without sampling
+    pub fn note(&self, skipped: u64) -> String {
+        if self.every > 1 {
+            format!(" (1 in {}, {} skipped)", self.every, skipped)
+        } else {
+            String::new()
+        }
+    }
+}
+
+/// 🎲 The samplers shared by the high-volume handlers
+#[derive(Debug)]
+pub struct LogSamplers {
+    /// 📊 /mcp/check update polls
+    pub mcp_check: LogSampler,
+    /// 💚 /health, /ready and /live probes
+    pub health: LogSampler,
+}
+
+impl LogSamplers {
+    /// ⚙️ One sampler per handler family, all at the configured rate
+    pub fn from_config(config: &LoggingConfig) -> Self {
+        Self {
+            mcp_check: LogSampler::from_config(config),
+            health: LogSampler::from_config(config),
+        }
+    }
+}
+
+// 🧪 Tests - Counting what we didn't say!
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sampler_logs_first_and_every_nth() {
+        let sampler = LogSampler::new(3);
+        let logged: Vec<Option<u64>> = (0..7).map(|_| sampler.sample()).collect();
+        assert_eq!(
+            logged,
+            vec![Some(0), None, None, Some(2), None, None, Some(2)]
+        );
+        assert_eq!(sampler.note(2), " (1 in 3, 2 skipped)");
+
+        let everything = LogSampler::new(0);
+        assert!((0..5).all(|_| everything.sample() == Some(0)));
+        assert_eq!(everything.note(0), "");
+        println!("✅ Log sampler rate test passed!");
+    }
+
+    #[test]
+    fn test_state_changes_are_always_logged() {
+        let sampler = LogSampler::new(100);
+        assert_eq!(sampler.sample_state(0), Some(0));
+        assert_eq!(sampler.sample_state(0), None);
+        assert_eq!(sampler.sample_state(0), None);
+        // 🔄 Flipped: logged straight away, with the quiet ones counted
+        assert_eq!(sampler.sample_state(1), Some(2));
+        assert_eq!(sampler.sample_state(1), None);
+        assert_eq!(sampler.sample_state(0), Some(1));
+        println!("✅ Log sampler state change test passed!");
+    }
+}
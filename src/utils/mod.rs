@@ -1,2 +1,14 @@
 // 🔧 Utils Module - Helpful Utilities! 🔧
-// TODO: Implement utility functions
+// Small, dependency-free helpers shared across modules.
+
+pub mod client_ip; // 🧭 Client address behind trusted proxies (X-Forwarded-For and friends)
+pub use feedbacker::utils::coalesce; // 🧵 Single-flight guard (lives in the library crate)
+pub mod deliveries; // 🛡️ Replay protection for signed webhook deliveries (skew window + LRU)
+pub mod json_logs; // 🧾 One-JSON-object-per-line log layer with field truncation
+pub mod log_sampling; // 🎲 1-in-N logging for high-volume handlers
+pub mod net; // 🌐 Outbound URL safety checks (SSRF guard)
+pub mod privacy; // 🕶️ IP anonymization for stored analytics
+pub mod signatures; // 🔏 HMAC signing and verification with secret rotation
+pub mod syntax; // 🖍️ Per-line syntax highlighting for patch previews
+pub mod timezones; // 🕰️ IANA time zones from the compiled-in tz database (DST aware)
+pub mod versions; // 🔢 Semver parsing and precedence (pre-releases included)
@@ -0,0 +1,137 @@
+// 🔏 Signatures - HMAC-SHA256 signing with painless secret rotation! 🔏
+// A secret can be rotated without a delivery gap: the previous secret keeps
+// verifying until its overlap window closes, while signing always uses the primary.
+
+use chrono::{DateTime, Utc};
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+
+/// 🔏 `sha256=<hex>` HMAC of the body
+pub fn sign(secret: &str, body: &[u8]) -> String {
+    let mut mac =
+        Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC accepts any key length");
+    mac.update(body);
+    format!("sha256={}", hex::encode(mac.finalize().into_bytes()))
+}
+
+/// ✅ Constant-time check of a `sha256=<hex>` signature
+fn signature_matches(secret: &str, body: &[u8], signature: &str) -> bool {
+    let Some(digest) = signature
+        .strip_prefix("sha256=")
+        .and_then(|hex_digest| hex::decode(hex_digest).ok())
+    else {
+        return false;
+    };
+    let mut mac =
+        Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC accepts any key length");
+    mac.update(body);
+    mac.verify_slice(&digest).is_ok()
+}
+
+/// 🔑 Which secret a signature was made with
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SecretMatch {
+    Primary,
+    Previous,
+}
+
+/// 🔑🔑 The current secret plus the one it replaced, while that one is still honoured
+#[derive(Debug, Clone)]
+pub struct SecretPair {
+    pub primary: String,
+    pub previous: Option<String>,
+    /// ⏰ End of the overlap window - the previous secret stops verifying here
+    pub previous_expires_at: Option<DateTime<Utc>>,
+}
+
+impl SecretPair {
+    /// ⏳ The previous secret, if its overlap window is still open at `now`
+    pub fn active_previous(&self, now: DateTime<Utc>) -> Option<&str> {
+        match (&self.previous, self.previous_expires_at) {
+            (Some(previous), Some(expires_at)) if now < expires_at => Some(previous),
+            _ => None,
+        }
+    }
+
+    /// 🔍 Which secret (if any) produced `signature` over `body`
+    pub fn verify(&self, body: &[u8], signature: &str, now: DateTime<Utc>) -> Option<SecretMatch> {
+        if signature_matches(&self.primary, body, signature) {
+            return Some(SecretMatch::Primary);
+        }
+        self.active_previous(now)
+            .filter(|previous| signature_matches(previous, body, signature))
+            .map(|_| SecretMatch::Previous)
+    }
+}
+
+// 🧪 Tests - Two keys, one lock, and a deadline!
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    fn rotated_pair(now: DateTime<Utc>) -> SecretPair {
+        SecretPair {
+            primary: "new-secret".to_string(),
+            previous: Some("old-secret".to_string()),
+            previous_expires_at: Some(now + Duration::hours(24)),
+        }
+    }
+
+    #[test]
+    fn test_both_secrets_verify_during_overlap() {
+        let now = Utc::now();
+        let pair = rotated_pair(now);
+        let body = br#"{"action":"opened"}"#;
+
+        assert_eq!(
+            pair.verify(body, &sign("new-secret", body), now),
+            Some(SecretMatch::Primary)
+        );
+        assert_eq!(
+            pair.verify(body, &sign("old-secret", body), now),
+            Some(SecretMatch::Previous)
+        );
+        assert_eq!(pair.verify(body, &sign("other", body), now), None);
+        assert_eq!(
+            pair.verify(b"tampered", &sign("new-secret", body), now),
+            None
+        );
+        assert_eq!(pair.verify(body, "sha256=not-hex", now), None);
+        assert_eq!(
+            pair.verify(
+                body,
+                sign("new-secret", body).trim_start_matches("sha256="),
+                now
+            ),
+            None
+        );
+        println!("✅ Overlapping secret verification test passed!");
+    }
+
+    #[test]
+    fn test_previous_secret_expires_after_overlap() {
+        let now = Utc::now();
+        let pair = rotated_pair(now);
+        let body = b"payload";
+        let later = now + Duration::hours(25);
+
+        assert_eq!(pair.verify(body, &sign("old-secret", body), later), None);
+        assert_eq!(
+            pair.verify(body, &sign("new-secret", body), later),
+            Some(SecretMatch::Primary)
+        );
+        assert_eq!(pair.active_previous(later), None);
+
+        // 🚫 A previous secret without an expiry is never honoured
+        let open_ended = SecretPair {
+            previous_expires_at: None,
+            ..rotated_pair(now)
+        };
+        assert_eq!(
+            open_ended.verify(body, &sign("old-secret", body), now),
+            None
+        );
+        println!("✅ Overlap expiry test passed!");
+    }
+}
@@ -0,0 +1,246 @@
+// 🖍️ Syntax Highlighting - A splash of colour for generated patches! 🖍️
+// Just enough of a tokenizer to tell keywords, strings, comments and numbers apart
+// in the languages our repositories are written in, picked by file extension.
+// It works one line at a time (diff lines come one at a time), so a block comment
+// or string that spans lines is only coloured on the line it starts. Everything it
+// returns is HTML-escaped; files it doesn't know come back escaped and uncoloured.
+// Created with love by Aye & Hue! ✨
+
+/// 🗣️ Languages with highlighting rules
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Language {
+    Rust,
+    Python,
+    JavaScript,
+    Go,
+    Shell,
+    Toml,
+    Yaml,
+    Json,
+}
+
+/// 📏 How a language spells comments, strings and keywords
+struct Rules {
+    keywords: &'static str,
+    line_comments: &'static [&'static str],
+    block_comments: bool,
+    /// 🔤 `'...'` is a string (false: only a char literal like `'a'` or `'\n'`)
+    single_quote_strings: bool,
+    backtick_strings: bool,
+}
+
+/// 🔑 Keywords, space-separated
+const RUST_KEYWORDS: &str =
+    "as async await break const continue crate dyn else enum extern false fn for if \
+    impl in let loop match mod move mut pub ref return self Self static struct super \
+    trait true type unsafe use where while";
+const PYTHON_KEYWORDS: &str =
+    "and as assert async await break class continue def del elif else except False \
+    finally for from global if import in is lambda None nonlocal not or pass raise \
+    return True try while with yield";
+const JAVASCRIPT_KEYWORDS: &str =
+    "async await break case catch class const continue default delete do else export \
+    extends false finally for from function if import in instanceof interface let \
+    new null return switch this throw true try type typeof undefined var void while \
+    yield";
+const GO_KEYWORDS: &str =
+    "break case chan const continue default defer else fallthrough false for func go \
+    goto if import interface map nil package range return select struct switch true \
+    type var";
+const SHELL_KEYWORDS: &str =
+    "case do done elif else esac export fi for function if in local return then while";
+const DATA_KEYWORDS: &str = "false null true";
+
+impl Language {
+    /// 🔍 The language of a file, by its extension
+    pub fn from_path(path: &str) -> Option<Self> {
+        let file = path.rsplit('/').next().unwrap_or(path);
+        let (_, extension) = file.rsplit_once('.')?;
+        match extension.to_ascii_lowercase().as_str() {
+            "rs" => Some(Self::Rust),
+            "py" => Some(Self::Python),
+            "js" | "mjs" | "cjs" | "jsx" | "ts" | "tsx" => Some(Self::JavaScript),
+            "go" => Some(Self::Go),
+            "sh" | "bash" | "zsh" => Some(Self::Shell),
+            "toml" => Some(Self::Toml),
+            "yml" | "yaml" => Some(Self::Yaml),
+            "json" => Some(Self::Json),
+            _ => None,
+        }
+    }
+
+    fn rules(self) -> Rules {
+        let (keywords, line_comments, block_comments, single_quote_strings, backtick_strings) =
+            match self {
+                Self::Rust => (RUST_KEYWORDS, &["//"][..], true, false, false),
+                Self::Python => (PYTHON_KEYWORDS, &["#"][..], false, true, false),
+                Self::JavaScript => (JAVASCRIPT_KEYWORDS, &["//"][..], true, true, true),
+                Self::Go => (GO_KEYWORDS, &["//"][..], true, false, true),
+                Self::Shell => (SHELL_KEYWORDS, &["#"][..], false, true, false),
+                Self::Toml | Self::Yaml => (DATA_KEYWORDS, &["#"][..], false, true, false),
+                Self::Json => (DATA_KEYWORDS, &[][..], false, false, false),
+            };
+        Rules {
+            keywords,
+            line_comments,
+            block_comments,
+            single_quote_strings,
+            backtick_strings,
+        }
+    }
+}
+
+/// 🧼 Escape text for HTML
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// 🖍️ Append `text` wrapped in a token span
+fn push_token(out: &mut String, class: &str, text: &str) {
+    out.push_str(&format!(
+        r#"<span class="{}">{}</span>"#,
+        class,
+        escape(text)
+    ));
+}
+
+/// 📏 Length of the string literal at the start of `text` (to the end of the line if
+/// it isn't closed there)
+fn string_len(text: &str, quote: char) -> usize {
+    let mut chars = text.char_indices().skip(1);
+    while let Some((index, c)) = chars.next() {
+        if c == '\\' {
+            chars.next();
+        } else if c == quote {
+            return index + c.len_utf8();
+        }
+    }
+    text.len()
+}
+
+/// 🔤 Does `text` start with a char literal (`'a'`, `'\n'`) rather than a lifetime?
+fn is_char_literal(text: &str) -> bool {
+    let mut chars = text.chars().skip(1);
+    matches!(
+        (chars.next(), chars.next()),
+        (Some('\\'), _) | (Some(_), Some('\''))
+    )
+}
+
+/// 🖍️ One line of code as HTML, keywords, strings, comments and numbers in
+/// `tok-kw`/`tok-str`/`tok-com`/`tok-num` spans (just escaped without a language)
+pub fn highlight_line(code: &str, language: Option<Language>) -> String {
+    let Some(rules) = language.map(Language::rules) else {
+        return escape(code);
+    };
+    let mut out = String::with_capacity(code.len() * 2);
+    let mut rest = code;
+    while let Some(c) = rest.chars().next() {
+        let token_len = if rules
+            .line_comments
+            .iter()
+            .any(|prefix| rest.starts_with(prefix))
+        {
+            push_token(&mut out, "tok-com", rest);
+            break;
+        } else if rules.block_comments && rest.starts_with("/*") {
+            let len = rest[2..].find("*/").map_or(rest.len(), |end| end + 4);
+            push_token(&mut out, "tok-com", &rest[..len]);
+            len
+        } else if c == '"'
+            || (c == '`' && rules.backtick_strings)
+            || (c == '\'' && (rules.single_quote_strings || is_char_literal(rest)))
+        {
+            let len = string_len(rest, c);
+            push_token(&mut out, "tok-str", &rest[..len]);
+            len
+        } else if c.is_ascii_digit() || c.is_alphabetic() || c == '_' {
+            let len = rest
+                .find(|c: char| !(c.is_alphanumeric() || c == '_' || c == '.'))
+                .unwrap_or(rest.len());
+            // 🔢 A number runs through its dots (1.5), a word stops at the first one
+            let len = if c.is_ascii_digit() {
+                len
+            } else {
+                rest[..len].find('.').unwrap_or(len)
+            };
+            let word = &rest[..len];
+            if c.is_ascii_digit() {
+                push_token(&mut out, "tok-num", word);
+            } else if rules
+                .keywords
+                .split_whitespace()
+                .any(|keyword| keyword == word)
+            {
+                push_token(&mut out, "tok-kw", word);
+            } else {
+                out.push_str(&escape(word));
+            }
+            len
+        } else {
+            out.push_str(&escape(&rest[..c.len_utf8()]));
+            c.len_utf8()
+        };
+        rest = &rest[token_len..];
+    }
+    out
+}
+
+// 🧪 Tests - Every colour in its place!
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_languages_come_from_the_extension() {
+        assert_eq!(Language::from_path("src/main.rs"), Some(Language::Rust));
+        assert_eq!(
+            Language::from_path("web/App.TSX"),
+            Some(Language::JavaScript)
+        );
+        assert_eq!(Language::from_path(".github/ci.yml"), Some(Language::Yaml));
+        assert_eq!(Language::from_path("v1.2/README"), None);
+        assert_eq!(Language::from_path("notes.md"), None);
+        println!("✅ Language detection test passed!");
+    }
+
+    #[test]
+    fn test_rust_tokens_are_wrapped() {
+        let rust = Some(Language::Rust);
+        assert_eq!(
+            highlight_line(r#"let s = "a<b"; // why"#, rust),
+            r#"<span class="tok-kw">let</span> s = <span class="tok-str">&quot;a&lt;b&quot;</span>; <span class="tok-com">// why</span>"#
+        );
+        // 🔤 Char literals are strings, lifetimes and identifiers with keywords inside aren't
+        assert_eq!(
+            highlight_line("fn f<'a>(c: char) -> bool { c == 'x' }", rust),
+            r#"<span class="tok-kw">fn</span> f&lt;'a&gt;(c: char) -&gt; bool { c == <span class="tok-str">'x'</span> }"#
+        );
+        assert_eq!(
+            highlight_line("letter = 1.5 /* ok */", rust),
+            r#"letter = <span class="tok-num">1.5</span> <span class="tok-com">/* ok */</span>"#
+        );
+        println!("✅ Rust highlighting test passed!");
+    }
+
+    #[test]
+    fn test_other_languages_and_plain_text() {
+        assert_eq!(
+            highlight_line("def f(): return 'x' # done", Some(Language::Python)),
+            r#"<span class="tok-kw">def</span> f(): <span class="tok-kw">return</span> <span class="tok-str">'x'</span> <span class="tok-com"># done</span>"#
+        );
+        // 🧵 An unclosed string runs to the end of the line
+        assert_eq!(
+            highlight_line(r#"name = "unterminated \" still"#, Some(Language::Toml)),
+            r#"name = <span class="tok-str">&quot;unterminated \&quot; still</span>"#
+        );
+        assert_eq!(
+            highlight_line("<b>if</b> #", None),
+            "&lt;b&gt;if&lt;/b&gt; #"
+        );
+        println!("✅ Other language highlighting test passed!");
+    }
+}
@@ -0,0 +1,194 @@
+// 🕰️ Time Zones - IANA zones with daylight saving time, on every host! 🕰️
+// The tz database is compiled in (chrono-tz), so "Europe/Berlin" behaves the same in a
+// slim container without /usr/share/zoneinfo, and loading a zone is a table lookup -
+// nothing is read or parsed while a page renders.
+// Created with love by Aye & Hue! ✨
+
+use anyhow::Result;
+use chrono::{DateTime, FixedOffset, NaiveDateTime, Offset, TimeZone as _, Utc};
+use chrono_tz::Tz;
+
+/// 🌍 The zone everyone gets until they pick one
+pub const UTC_NAME: &str = "UTC";
+
+/// 📋 Zone names for a picker: UTC, then every "Area/Location" zone, sorted
+pub fn available_zones() -> Vec<String> {
+    let mut zones: Vec<String> = chrono_tz::TZ_VARIANTS
+        .iter()
+        .map(|zone| zone.name())
+        .filter(|name| name.contains('/'))
+        .map(str::to_string)
+        .collect();
+    zones.sort();
+    zones.insert(0, UTC_NAME.to_string());
+    zones
+}
+
+/// 🕰️ A time zone from the compiled-in IANA database
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TimeZone(Tz);
+
+impl TimeZone {
+    /// 🌍 Plain UTC
+    pub fn utc() -> Self {
+        Self(Tz::UTC)
+    }
+
+    /// 📖 Look an IANA zone up by name ("Europe/Berlin")
+    pub fn load(name: &str) -> Result<Self> {
+        name.parse::<Tz>()
+            .map(Self)
+            .map_err(|_| anyhow::anyhow!("Unknown time zone {:?}", name))
+    }
+
+    /// 🏷️ The zone's name
+    pub fn name(&self) -> &'static str {
+        self.0.name()
+    }
+
+    /// 🕰️ The instant as wall-clock time here, with the abbreviation in effect ("CEST")
+    pub fn to_local(self, at: DateTime<Utc>) -> (DateTime<FixedOffset>, String) {
+        let local = at.with_timezone(&self.0);
+        (local.fixed_offset(), local.format("%Z").to_string())
+    }
+
+    /// 🕰️ The instant a wall-clock time here names. Clocks falling back make it
+    /// happen twice - the first one wins. Clocks springing forward skip it - it is
+    /// read with the offset from before the jump, landing just after it
+    pub fn at_local(self, local: NaiveDateTime) -> DateTime<Utc> {
+        match self.0.from_local_datetime(&local).earliest() {
+            Some(at) => at.with_timezone(&Utc),
+            None => {
+                let before = self
+                    .0
+                    .offset_from_utc_datetime(&(local - chrono::Duration::days(1)))
+                    .fix();
+                (local - before).and_utc()
+            }
+        }
+    }
+}
+
+// 🧪 Tests - Clocks going forward and back!
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone as _;
+
+    /// 🕰️ Local wall-clock time and abbreviation
+    fn local(zone: &str, at: DateTime<Utc>) -> (String, String) {
+        let (local, abbreviation) = TimeZone::load(zone).unwrap().to_local(at);
+        (local.format("%Y-%m-%d %H:%M").to_string(), abbreviation)
+    }
+
+    fn utc(y: i32, m: u32, d: u32, h: u32, min: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(y, m, d, h, min, 0).unwrap()
+    }
+
+    fn pair(local: &str, abbreviation: &str) -> (String, String) {
+        (local.to_string(), abbreviation.to_string())
+    }
+
+    #[test]
+    fn test_dst_boundaries_follow_the_zone() {
+        // 🌸 Berlin springs forward at 01:00 UTC on the last Sunday of March 2024
+        assert_eq!(
+            local("Europe/Berlin", utc(2024, 3, 31, 0, 59)),
+            pair("2024-03-31 01:59", "CET")
+        );
+        assert_eq!(
+            local("Europe/Berlin", utc(2024, 3, 31, 1, 0)),
+            pair("2024-03-31 03:00", "CEST")
+        );
+        // 🍂 ... and falls back at 01:00 UTC on October 27th: 02:30 happens twice
+        assert_eq!(
+            local("Europe/Berlin", utc(2024, 10, 27, 0, 30)),
+            pair("2024-10-27 02:30", "CEST")
+        );
+        assert_eq!(
+            local("Europe/Berlin", utc(2024, 10, 27, 1, 30)),
+            pair("2024-10-27 02:30", "CET")
+        );
+        // 🗽 New York's rules differ in both date and offset
+        assert_eq!(
+            local("America/New_York", utc(2024, 3, 10, 6, 59)),
+            pair("2024-03-10 01:59", "EST")
+        );
+        assert_eq!(
+            local("America/New_York", utc(2024, 3, 10, 7, 0)),
+            pair("2024-03-10 03:00", "EDT")
+        );
+        // 🌏 Southern hemisphere, and a half-hour zone without DST
+        assert_eq!(
+            local("Australia/Sydney", utc(2024, 1, 15, 0, 0)),
+            pair("2024-01-15 11:00", "AEDT")
+        );
+        assert_eq!(
+            local("Asia/Kolkata", utc(2024, 7, 1, 0, 0)),
+            pair("2024-07-01 05:30", "IST")
+        );
+        // 🔮 Far past the last scheduled change, the rules still hold
+        assert_eq!(
+            local("Europe/Berlin", utc(2050, 3, 27, 1, 0)),
+            pair("2050-03-27 03:00", "CEST")
+        );
+        println!("✅ DST boundary test passed!");
+    }
+
+    #[test]
+    fn test_wall_clock_times_resolve_across_dst() {
+        let berlin = TimeZone::load("Europe/Berlin").unwrap();
+        let wall = |y, m, d, h, min| {
+            chrono::NaiveDate::from_ymd_opt(y, m, d)
+                .unwrap()
+                .and_hms_opt(h, min, 0)
+                .unwrap()
+        };
+        // 🕘 Winter and summer offsets
+        assert_eq!(
+            berlin.at_local(wall(2024, 1, 15, 9, 0)),
+            utc(2024, 1, 15, 8, 0)
+        );
+        assert_eq!(
+            berlin.at_local(wall(2024, 7, 15, 9, 0)),
+            utc(2024, 7, 15, 7, 0)
+        );
+        // 🌸 02:30 never happens on March 31st - it lands at 03:30 CEST
+        assert_eq!(
+            berlin.at_local(wall(2024, 3, 31, 2, 30)),
+            utc(2024, 3, 31, 1, 30)
+        );
+        // 🍂 02:30 happens twice on October 27th - the first (CEST) one wins
+        assert_eq!(
+            berlin.at_local(wall(2024, 10, 27, 2, 30)),
+            utc(2024, 10, 27, 0, 30)
+        );
+        println!("✅ Wall-clock resolution test passed!");
+    }
+
+    #[test]
+    fn test_invalid_zone_names_are_rejected() {
+        for name in [
+            "",
+            "Mars/Olympus_Mons",
+            "../../etc/passwd",
+            "/etc/passwd",
+            "Europe/",
+            "Europe/../../etc/passwd",
+            "Europe/Berlin; DROP TABLE settings",
+        ] {
+            assert!(TimeZone::load(name).is_err(), "{:?} should not load", name);
+        }
+        assert_eq!(TimeZone::load(UTC_NAME).unwrap(), TimeZone::utc());
+        assert_eq!(
+            TimeZone::load("America/Argentina/Buenos_Aires")
+                .unwrap()
+                .name(),
+            "America/Argentina/Buenos_Aires"
+        );
+        let zones = available_zones();
+        assert_eq!(zones[0], UTC_NAME);
+        assert!(zones.iter().any(|zone| zone == "Europe/Berlin"));
+        println!("✅ Invalid time zone test passed!");
+    }
+}
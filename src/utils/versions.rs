@@ -0,0 +1,175 @@
+// 🔢 Versions - Putting releases in the right order! 🔢
+// Semantic version precedence for release tags: a leading `v` is ignored,
+// missing minor/patch parts count as 0, build metadata (`+...`) never affects
+// order, and pre-releases (`-rc.1`) sort before their release, compared
+// identifier by identifier (numeric ones numerically, and below text ones).
+// Created with love by Aye & Hue! ✨
+
+use std::cmp::Ordering;
+
+/// 🏷️ One dot-separated pre-release identifier
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum PreRelease {
+    Numeric(u64),
+    Text(String),
+}
+
+impl Ord for PreRelease {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self, other) {
+            (Self::Numeric(a), Self::Numeric(b)) => a.cmp(b),
+            (Self::Numeric(_), Self::Text(_)) => Ordering::Less,
+            (Self::Text(_), Self::Numeric(_)) => Ordering::Greater,
+            (Self::Text(a), Self::Text(b)) => a.cmp(b),
+        }
+    }
+}
+
+impl PartialOrd for PreRelease {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// 🔢 A parsed version, ordered by semver precedence
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Version {
+    pub major: u64,
+    pub minor: u64,
+    pub patch: u64,
+    pre: Vec<PreRelease>,
+}
+
+impl Version {
+    /// 🔍 Parse "4.9.1", "v5", "5.0.0-rc.2+build.7", ... (None for anything else)
+    pub fn parse(version: &str) -> Option<Self> {
+        let version = version.trim();
+        let version = version.strip_prefix('v').unwrap_or(version);
+        let version = version.split_once('+').map_or(version, |(core, _)| core);
+        let (core, pre) = match version.split_once('-') {
+            Some((core, pre)) => (core, Some(pre)),
+            None => (version, None),
+        };
+
+        let mut parts = core.split('.');
+        let mut number = |required: bool| -> Option<u64> {
+            match parts.next() {
+                Some(part) if !part.is_empty() && part.bytes().all(|b| b.is_ascii_digit()) => {
+                    part.parse().ok()
+                }
+                Some(_) => None,
+                None if required => None,
+                None => Some(0),
+            }
+        };
+        let (major, minor, patch) = (number(true)?, number(false)?, number(false)?);
+        if parts.next().is_some() {
+            return None;
+        }
+
+        let pre = match pre {
+            None => Vec::new(),
+            Some(pre) => pre
+                .split('.')
+                .map(|identifier| {
+                    if identifier.is_empty()
+                        || !identifier
+                            .bytes()
+                            .all(|b| b.is_ascii_alphanumeric() || b == b'-')
+                    {
+                        None
+                    } else if identifier.bytes().all(|b| b.is_ascii_digit()) {
+                        identifier.parse().ok().map(PreRelease::Numeric)
+                    } else {
+                        Some(PreRelease::Text(identifier.to_string()))
+                    }
+                })
+                .collect::<Option<_>>()?,
+        };
+
+        Some(Self {
+            major,
+            minor,
+            patch,
+            pre,
+        })
+    }
+
+    /// 🧪 A pre-release such as 5.0.0-rc.1?
+    pub fn is_prerelease(&self) -> bool {
+        !self.pre.is_empty()
+    }
+}
+
+impl Ord for Version {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (self.major, self.minor, self.patch)
+            .cmp(&(other.major, other.minor, other.patch))
+            .then_with(|| match (self.pre.is_empty(), other.pre.is_empty()) {
+                (true, true) => Ordering::Equal,
+                // 🧪 A pre-release comes before the release itself
+                (true, false) => Ordering::Greater,
+                (false, true) => Ordering::Less,
+                (false, false) => self.pre.cmp(&other.pre),
+            })
+    }
+}
+
+impl PartialOrd for Version {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+// 🧪 Tests - Lining the versions up!
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn v(version: &str) -> Version {
+        Version::parse(version).unwrap()
+    }
+
+    #[test]
+    fn test_versions_order_by_semver_precedence() {
+        // 🔢 The ordering example from the semver spec, plus partial versions
+        let ordered = [
+            "1.0.0-alpha",
+            "1.0.0-alpha.1",
+            "1.0.0-alpha.beta",
+            "1.0.0-beta",
+            "1.0.0-beta.2",
+            "1.0.0-beta.11",
+            "1.0.0-rc.1",
+            "1.0.0",
+            "1.0.1",
+            "1.2",
+            "v1.10.0",
+            "2",
+        ];
+        for pair in ordered.windows(2) {
+            assert!(v(pair[0]) < v(pair[1]), "{} < {}", pair[0], pair[1]);
+        }
+        assert_eq!(v("v4.9"), v("4.9.0"));
+        assert_eq!(v("4.9.0+build.7"), v("4.9.0"));
+        println!("✅ Version precedence test passed!");
+    }
+
+    #[test]
+    fn test_prereleases_and_garbage() {
+        assert!(v("5.0.0-rc.1").is_prerelease());
+        assert!(!v("5.0.0").is_prerelease());
+        for garbage in [
+            "",
+            "unknown",
+            "1.x",
+            "1.2.3.4",
+            "1..2",
+            "1.0.0-",
+            "1.0.0-rc..1",
+        ] {
+            assert_eq!(Version::parse(garbage), None, "{:?}", garbage);
+        }
+        println!("✅ Version parsing test passed!");
+    }
+}